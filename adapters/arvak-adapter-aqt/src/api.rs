@@ -64,6 +64,11 @@ impl AqtClient {
         })
     }
 
+    /// The API base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Build the `Authorization` header value.
     ///
     /// AQT uses `Authorization: Bearer <token>` (standard Bearer scheme).
@@ -389,7 +394,7 @@ pub struct WorkspaceInfo {
 }
 
 /// Resource info returned by `GET /resources/{id}` or nested in workspaces.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceInfo {
     /// Resource identifier (e.g., `"offline_simulator_no_noise"`).
     pub id: String,
@@ -2,7 +2,6 @@
 
 use std::f64::consts::PI;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use rustc_hash::FxHashMap;
@@ -11,7 +10,7 @@ use tracing::{debug, info, instrument};
 
 use arvak_hal::{
     Backend, BackendAvailability, BackendConfig, BackendFactory, Capabilities, Counts,
-    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ValidationResult,
+    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ResponseCache, ValidationResult,
 };
 use arvak_ir::{
     Circuit,
@@ -40,9 +39,6 @@ const AQT_MAX_OPS: usize = 2000;
 /// Maximum number of cached job entries before evicting terminal-state entries.
 const MAX_CACHED_JOBS: usize = 10_000;
 
-/// TTL for cached resource info before re-fetching from the API.
-const RESOURCE_INFO_TTL: Duration = Duration::from_secs(5 * 60);
-
 /// Cached job entry.
 struct CachedJob {
     job: Job,
@@ -85,8 +81,8 @@ pub struct AqtBackend {
     capabilities: Capabilities,
     /// Cached job metadata and results.
     jobs: Arc<Mutex<FxHashMap<String, CachedJob>>>,
-    /// Cached resource info with TTL.
-    resource_info: Arc<Mutex<Option<(ResourceInfo, Instant)>>>,
+    /// Cache for resource info responses, shared across backends.
+    response_cache: Arc<ResponseCache>,
 }
 
 impl AqtBackend {
@@ -113,21 +109,7 @@ impl AqtBackend {
             std::env::var("AQT_PORTAL_URL").unwrap_or_else(|_| crate::api::BASE_URL.to_string());
 
         let client = AqtClient::with_base_url(base_url, token)?;
-
-        let workspace = workspace.into();
-        let resource = resource.into();
-        let name = format!("{workspace}/{resource}");
-        let capabilities = build_capabilities(&resource, AQT_MAX_QUBITS);
-
-        Ok(Self {
-            client,
-            workspace,
-            resource,
-            name,
-            capabilities,
-            jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            resource_info: Arc::new(Mutex::new(None)),
-        })
+        Ok(Self::from_client(client, workspace.into(), resource.into()))
     }
 
     /// Create a backend with an explicit token (useful for testing).
@@ -136,21 +118,23 @@ impl AqtBackend {
         resource: impl Into<String>,
         token: impl Into<String>,
     ) -> AqtResult<Self> {
-        let workspace = workspace.into();
-        let resource = resource.into();
-        let name = format!("{workspace}/{resource}");
         let client = AqtClient::new(token)?;
+        Ok(Self::from_client(client, workspace.into(), resource.into()))
+    }
+
+    fn from_client(client: AqtClient, workspace: String, resource: String) -> Self {
+        let name = format!("{workspace}/{resource}");
         let capabilities = build_capabilities(&resource, AQT_MAX_QUBITS);
 
-        Ok(Self {
+        Self {
             client,
             workspace,
             resource,
             name,
             capabilities,
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            resource_info: Arc::new(Mutex::new(None)),
-        })
+            response_cache: ResponseCache::global(),
+        }
     }
 
     /// Return the workspace name.
@@ -163,25 +147,19 @@ impl AqtBackend {
         &self.resource
     }
 
+    /// Use `cache` for resource info instead of [`ResponseCache::global`].
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = cache;
+        self
+    }
+
     /// Fetch resource info from the API, using the cache if not stale.
     async fn fetch_resource_info(&self) -> AqtResult<ResourceInfo> {
-        {
-            let cache = self.resource_info.lock().await;
-            if let Some((ref info, fetched_at)) = *cache {
-                if fetched_at.elapsed() < RESOURCE_INFO_TTL {
-                    return Ok(info.clone());
-                }
-            }
-        }
-
-        let info = self.client.get_resource(&self.resource).await?;
-
-        {
-            let mut cache = self.resource_info.lock().await;
-            *cache = Some((info.clone(), Instant::now()));
-        }
-
-        Ok(info)
+        let key = format!("aqt/{}/{}", self.client.base_url(), self.resource);
+        self.response_cache
+            .get_or_fetch(&key, || self.client.get_resource(&self.resource))
+            .await
     }
 
     /// Serialize a circuit to the AQT JSON gate format.
@@ -287,7 +265,9 @@ impl AqtBackend {
                 _ => Err(AqtError::UnsupportedGate(gate.name().to_string())),
             },
 
-            GateKind::Custom(cg) => Err(AqtError::UnsupportedGate(cg.name.clone())),
+            GateKind::Custom(_) | GateKind::Modified(_) => {
+                Err(AqtError::UnsupportedGate(gate.name().to_string()))
+            }
         }
     }
 
@@ -347,6 +327,7 @@ impl Backend for AqtBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: None,
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     let msg = info.status.unwrap_or_else(|| "offline".into());
@@ -363,6 +344,7 @@ impl Backend for AqtBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: Some("offline simulator (status check unavailable)".into()),
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     Ok(BackendAvailability::unavailable(e.to_string()))
@@ -641,7 +623,18 @@ impl BackendFactory for AqtBackend {
             .unwrap_or(DEFAULT_RESOURCE)
             .to_string();
 
-        Self::with_resource(workspace, resource).map_err(|e| HalError::Backend(e.to_string()))
+        // An explicit token (as set by a typed configuration section) takes
+        // precedence over `AQT_TOKEN` / `AQT_PORTAL_URL`.
+        let Some(token) = config.token else {
+            return Self::with_resource(workspace, resource)
+                .map_err(|e| HalError::Backend(e.to_string()));
+        };
+        let base_url = config
+            .endpoint
+            .unwrap_or_else(|| crate::api::BASE_URL.to_string());
+        let client = AqtClient::with_base_url(base_url, token)
+            .map_err(|e| HalError::Backend(e.to_string()))?;
+        Ok(Self::from_client(client, workspace, resource))
     }
 }
 
@@ -11,7 +11,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::time::Duration;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{BraketError, BraketResult};
 
@@ -223,7 +223,7 @@ impl BraketClient {
 }
 
 /// Device type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeviceType {
     /// Quantum processing unit (real hardware).
     Qpu,
@@ -232,7 +232,7 @@ pub enum DeviceType {
 }
 
 /// Device status.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeviceStatus {
     /// Device is online and accepting tasks.
     Online,
@@ -243,7 +243,7 @@ pub enum DeviceStatus {
 }
 
 /// Device information from Braket.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceInfo {
     /// Device ARN.
     pub device_arn: String,
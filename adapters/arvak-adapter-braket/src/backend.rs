@@ -1,15 +1,14 @@
 //! AWS Braket backend implementation.
 
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use rustc_hash::FxHashMap;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::Mutex;
 
 use arvak_hal::{
     Backend, BackendAvailability, Capabilities, Counts, ExecutionResult, HalError, HalResult,
-    JobId, JobStatus, ValidationResult,
+    JobId, JobPhase, JobStatus, ResponseCache, ValidationResult,
 };
 use arvak_ir::Circuit;
 use arvak_qasm3::emit;
@@ -21,9 +20,6 @@ use crate::error::{BraketError, BraketResult};
 /// Maximum number of cached jobs before eviction of terminal entries.
 const MAX_CACHED_JOBS: usize = 10_000;
 
-/// How long to cache device info before refreshing from the API.
-const DEVICE_INFO_TTL: Duration = Duration::from_secs(5 * 60);
-
 /// A cached job entry.
 struct CachedJob {
     /// Job status.
@@ -37,6 +33,11 @@ struct CachedJob {
     shots: u32,
 }
 
+/// Response cache key for a device's info.
+fn device_info_key(device_arn: &str) -> String {
+    format!("braket/{device_arn}")
+}
+
 /// AWS Braket backend adapter.
 ///
 /// Provides access to quantum hardware and simulators available through
@@ -51,8 +52,8 @@ pub struct BraketBackend {
     capabilities: Capabilities,
     /// Job cache: task ARN -> cached job.
     jobs: Arc<Mutex<FxHashMap<String, CachedJob>>>,
-    /// Cached device info with fetch timestamp for TTL-based refresh.
-    device_info: Arc<RwLock<Option<(DeviceInfo, Instant)>>>,
+    /// Cache for device info responses, shared across backends.
+    response_cache: Arc<ResponseCache>,
 }
 
 impl BraketBackend {
@@ -77,11 +78,17 @@ impl BraketBackend {
         let client = BraketClient::new(&region, &s3_bucket, &s3_prefix).await?;
 
         // Use known preset if available, otherwise build from API
+        let response_cache = ResponseCache::global();
         let capabilities = match capabilities_for_device(&device_arn) {
             Some(caps) => caps,
             None => {
-                // Fetch device info for unknown devices
-                let info = client.get_device(&device_arn).await?;
+                // Fetch device info for unknown devices, falling back to a
+                // cached snapshot if Braket cannot be reached.
+                let info: DeviceInfo = response_cache
+                    .get_or_snapshot(&device_info_key(&device_arn), || {
+                        client.get_device(&device_arn)
+                    })
+                    .await?;
                 build_capabilities_from_info(&info)
             }
         };
@@ -91,7 +98,7 @@ impl BraketBackend {
             device_arn,
             capabilities,
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            device_info: Arc::new(RwLock::new(None)),
+            response_cache,
         })
     }
 
@@ -100,28 +107,25 @@ impl BraketBackend {
         &self.device_arn
     }
 
+    /// Use `cache` for device info instead of [`ResponseCache::global`].
+    ///
+    /// [`BraketBackend::connect`] always reads the global cache while
+    /// connecting; the replacement applies to later lookups.
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = cache;
+        self
+    }
+
     /// Get device info with TTL caching.
+    ///
+    /// Never falls back to a stale snapshot: callers check device status.
     async fn get_device_info(&self) -> BraketResult<DeviceInfo> {
-        // Check cache first
-        {
-            let cached = self.device_info.read().await;
-            if let Some((ref info, fetched_at)) = *cached {
-                if fetched_at.elapsed() < DEVICE_INFO_TTL {
-                    return Ok(info.clone());
-                }
-            }
-        }
-
-        // Fetch from API
-        let info = self.client.get_device(&self.device_arn).await?;
-
-        // Cache with timestamp
-        {
-            let mut cached = self.device_info.write().await;
-            *cached = Some((info.clone(), Instant::now()));
-        }
-
-        Ok(info)
+        self.response_cache
+            .get_or_fetch(&device_info_key(&self.device_arn), || {
+                self.client.get_device(&self.device_arn)
+            })
+            .await
     }
 
     /// Convert circuit to OpenQASM 3.0 string.
@@ -185,6 +189,17 @@ impl BraketBackend {
 
         counts
     }
+
+    /// Fetch the raw state of a task.
+    async fn fetch_task_status(&self, job_id: &JobId) -> HalResult<TaskStatus> {
+        self.client
+            .get_task_status(&job_id.0)
+            .await
+            .map_err(|e| match e {
+                BraketError::TaskNotFound(id) => HalError::JobNotFound(id),
+                other => HalError::Backend(other.to_string()),
+            })
+    }
 }
 
 /// Build capabilities from device info for unknown devices.
@@ -240,6 +255,7 @@ impl Backend for BraketBackend {
                             "{} ({})",
                             info.device_name, info.provider_name
                         )),
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     Ok(BackendAvailability::unavailable(format!(
@@ -352,22 +368,7 @@ impl Backend for BraketBackend {
     }
 
     async fn status(&self, job_id: &JobId) -> HalResult<JobStatus> {
-        let task_status = self
-            .client
-            .get_task_status(&job_id.0)
-            .await
-            .map_err(|e| match e {
-                BraketError::TaskNotFound(id) => HalError::JobNotFound(id),
-                other => HalError::Backend(other.to_string()),
-            })?;
-
-        let job_status = match task_status {
-            TaskStatus::Created | TaskStatus::Queued => JobStatus::Queued,
-            TaskStatus::Running => JobStatus::Running,
-            TaskStatus::Completed => JobStatus::Completed,
-            TaskStatus::Failed(msg) => JobStatus::Failed(msg),
-            TaskStatus::Cancelling | TaskStatus::Cancelled => JobStatus::Cancelled,
-        };
+        let job_status = job_status(self.fetch_task_status(job_id).await?);
 
         // Update cache
         {
@@ -380,6 +381,16 @@ impl Backend for BraketBackend {
         Ok(job_status)
     }
 
+    async fn phase(&self, job_id: &JobId) -> HalResult<Option<JobPhase>> {
+        // A created task is still being validated by Braket before it is
+        // queued on the device.
+        let phase = match self.fetch_task_status(job_id).await? {
+            TaskStatus::Created => Some(JobPhase::Validating),
+            other => JobPhase::from_status(&job_status(other)),
+        };
+        Ok(phase)
+    }
+
     async fn result(&self, job_id: &JobId) -> HalResult<ExecutionResult> {
         // Check cache first
         {
@@ -460,6 +471,17 @@ impl Backend for BraketBackend {
     }
 }
 
+/// Map a Braket task state onto the contract status.
+fn job_status(task_status: TaskStatus) -> JobStatus {
+    match task_status {
+        TaskStatus::Created | TaskStatus::Queued => JobStatus::Queued,
+        TaskStatus::Running => JobStatus::Running,
+        TaskStatus::Completed => JobStatus::Completed,
+        TaskStatus::Failed(msg) => JobStatus::Failed(msg),
+        TaskStatus::Cancelling | TaskStatus::Cancelled => JobStatus::Cancelled,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -542,7 +564,7 @@ mod tests {
             device_arn: "arn:aws:braket:::device/quantum-simulator/amazon/sv1".to_string(),
             capabilities: Capabilities::braket_simulator("SV1", 34),
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            device_info: Arc::new(RwLock::new(None)),
+            response_cache: Arc::new(ResponseCache::in_memory()),
         };
         let circuit = Circuit::with_size("test", 2, 2);
 
@@ -159,6 +159,7 @@ impl CudaqBackend {
             is_simulator,
             features: vec!["gpu-accelerated".into(), "qasm3".into()],
             noise_profile: None,
+            decoherence: None,
         };
 
         Ok(Self {
@@ -227,6 +228,7 @@ impl Backend for CudaqBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: None,
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     Ok(BackendAvailability::unavailable("target offline"))
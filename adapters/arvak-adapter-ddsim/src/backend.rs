@@ -203,6 +203,7 @@ impl Backend for DdsimBackend {
                     queue_depth: None,
                     estimated_wait: None,
                     status_message: Some(format!("{e}")),
+                    maintenance_windows: Vec::new(),
                 })
             }
         }
@@ -196,6 +196,11 @@ impl IbmClient {
         self
     }
 
+    /// The API endpoint URL.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
     /// Whether this client uses the new Cloud API.
     pub fn is_cloud_api(&self) -> bool {
         self.cloud_api
@@ -588,7 +593,7 @@ struct BackendStatusResponse {
 }
 
 /// Backend information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendInfo {
     /// Backend name.
     pub name: String,
@@ -617,7 +622,7 @@ pub struct BackendInfo {
 }
 
 /// Backend status.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendStatus {
     /// Whether the backend is operational.
     pub operational: bool,
@@ -630,7 +635,7 @@ pub struct BackendStatus {
 }
 
 /// Processor type information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessorType {
     /// Family (e.g., "Falcon", "Eagle", "Heron").
     pub family: String,
@@ -2,18 +2,18 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::Mutex;
 
 use arvak_hal::{
-    Backend, BackendAvailability, BackendConfig, Capabilities, Counts, ExecutionResult, GateSet,
-    HalError, HalResult, JobId, JobStatus, Topology, TopologyKind, ValidationResult,
+    Backend, BackendAvailability, BackendConfig, BackendFactory, Capabilities, Counts,
+    ExecutionResult, GateSet, HalError, HalResult, JobId, JobPhase, JobStatus, ResponseCache,
+    Topology, TopologyKind, ValidationResult,
 };
 use arvak_ir::Circuit;
 use arvak_qasm3::emit;
 use async_trait::async_trait;
 
-use crate::api::{BackendInfo, IbmClient, LEGACY_ENDPOINT};
+use crate::api::{BackendInfo, IbmClient, JobStatusResponse, LEGACY_ENDPOINT};
 use crate::error::{IbmError, IbmResult};
 
 /// Default IBM Quantum backend (Heron processor, zero-queue).
@@ -62,6 +62,7 @@ fn capabilities_stub(target: &str, num_qubits: u32) -> Capabilities {
         is_simulator: false,
         features: vec!["dynamic_circuits".into()],
         noise_profile: None,
+        decoherence: None,
     }
 }
 
@@ -85,11 +86,14 @@ fn capabilities_from_real_info(target: &str, num_qubits: u32, topology: Topology
         is_simulator: false,
         features: vec!["dynamic_circuits".into()],
         noise_profile: None,
+        decoherence: None,
     }
 }
 
-/// How long to cache backend info before refreshing from the API.
-const BACKEND_INFO_TTL: Duration = Duration::from_secs(5 * 60);
+/// Response cache key for a backend's info on the client's endpoint.
+fn backend_info_key(client: &IbmClient, target: &str) -> String {
+    format!("ibm/{}/{target}", client.endpoint())
+}
 
 /// IBM Quantum backend adapter.
 pub struct IbmBackend {
@@ -99,8 +103,8 @@ pub struct IbmBackend {
     target: String,
     /// Cached capabilities (HAL Contract v2: sync introspection).
     capabilities: Capabilities,
-    /// Cached backend info with fetch timestamp for TTL-based refresh.
-    backend_info: Arc<RwLock<Option<(BackendInfo, Instant)>>>,
+    /// Cache for backend info responses, shared across backends.
+    response_cache: Arc<ResponseCache>,
     /// Whether to tell IBM to skip its own transpilation.
     skip_transpilation: bool,
     /// Submitted shot counts keyed by job ID.
@@ -123,7 +127,7 @@ impl IbmBackend {
             client: Arc::new(client),
             capabilities: capabilities_stub(&target, 127),
             target,
-            backend_info: Arc::new(RwLock::new(None)),
+            response_cache: ResponseCache::global(),
             skip_transpilation: false,
             shots_cache: Arc::new(Mutex::new(HashMap::new())),
         })
@@ -142,7 +146,7 @@ impl IbmBackend {
             client: Arc::new(client),
             capabilities: capabilities_stub(&target, 127),
             target,
-            backend_info: Arc::new(RwLock::new(None)),
+            response_cache: ResponseCache::global(),
             skip_transpilation: false,
             shots_cache: Arc::new(Mutex::new(HashMap::new())),
         })
@@ -163,8 +167,14 @@ impl IbmBackend {
             tracing::info!("connecting to IBM Cloud API (IAM key exchange)");
             let client = IbmClient::connect(&api_key, &service_crn).await?;
 
-            // Eagerly fetch backend info for real topology
-            let info = client.get_backend(&target).await?;
+            // Eagerly fetch backend info for real topology. A cached
+            // snapshot is used if IBM cannot be reached.
+            let response_cache = ResponseCache::global();
+            let info: BackendInfo = response_cache
+                .get_or_snapshot(&backend_info_key(&client, &target), || {
+                    client.get_backend(&target)
+                })
+                .await?;
             let num_qubits = u32::try_from(info.num_qubits).unwrap_or(133);
             let topology = Topology {
                 kind: TopologyKind::HeavyHex,
@@ -176,14 +186,11 @@ impl IbmBackend {
             };
             let capabilities = capabilities_from_real_info(&target, num_qubits, topology);
 
-            // Pre-cache the backend info
-            let backend_info = Arc::new(RwLock::new(Some((info, Instant::now()))));
-
             return Ok(Self {
                 client: Arc::new(client),
                 capabilities,
                 target,
-                backend_info,
+                response_cache,
                 skip_transpilation: false,
                 shots_cache: Arc::new(Mutex::new(HashMap::new())),
             });
@@ -198,7 +205,7 @@ impl IbmBackend {
                 client: Arc::new(client),
                 capabilities: capabilities_stub(&target, 127),
                 target,
-                backend_info: Arc::new(RwLock::new(None)),
+                response_cache: ResponseCache::global(),
                 skip_transpilation: false,
                 shots_cache: Arc::new(Mutex::new(HashMap::new())),
             });
@@ -230,7 +237,7 @@ impl IbmBackend {
             client: Arc::new(client),
             capabilities: capabilities_stub(target, 127),
             target: target.to_string(),
-            backend_info: Arc::new(RwLock::new(None)),
+            response_cache: ResponseCache::global(),
             skip_transpilation: false,
             shots_cache: Arc::new(Mutex::new(HashMap::new())),
         })
@@ -249,28 +256,25 @@ impl IbmBackend {
         &self.target
     }
 
+    /// Use `cache` for backend info instead of [`ResponseCache::global`].
+    ///
+    /// [`IbmBackend::connect`] always reads the global cache while
+    /// connecting; the replacement applies to later lookups.
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = cache;
+        self
+    }
+
     /// Get backend information, fetching from API if not cached or stale.
+    ///
+    /// Never falls back to a stale snapshot: callers check operational status.
     async fn get_backend_info(&self) -> IbmResult<BackendInfo> {
-        // Check cache first; refresh if older than TTL.
-        {
-            let cached = self.backend_info.read().await;
-            if let Some((ref info, fetched_at)) = *cached {
-                if fetched_at.elapsed() < BACKEND_INFO_TTL {
-                    return Ok(info.clone());
-                }
-            }
-        }
-
-        // Fetch from API
-        let info = self.client.get_backend(&self.target).await?;
-
-        // Cache it with current timestamp
-        {
-            let mut cached = self.backend_info.write().await;
-            *cached = Some((info.clone(), Instant::now()));
-        }
-
-        Ok(info)
+        self.response_cache
+            .get_or_fetch(&backend_info_key(&self.client, &self.target), || {
+                self.client.get_backend(&self.target)
+            })
+            .await
     }
 
     /// Convert circuit to `OpenQASM` 3.0 string.
@@ -368,6 +372,17 @@ impl IbmBackend {
 
         counts
     }
+
+    /// Fetch the raw state of a job.
+    async fn fetch_job_status(&self, job_id: &JobId) -> HalResult<JobStatusResponse> {
+        self.client
+            .get_job_status(&job_id.0)
+            .await
+            .map_err(|e| match e {
+                IbmError::JobNotFound(id) => HalError::JobNotFound(id),
+                other => HalError::Backend(other.to_string()),
+            })
+    }
 }
 
 /// Infer the classical register bit width from the V2 hex samples.
@@ -394,6 +409,26 @@ fn infer_bit_width(samples: &[String]) -> usize {
     }
 }
 
+/// Map an IBM job state onto the contract status.
+///
+/// `VALIDATING` happens before the job reaches the device, so it is
+/// reported as queued; [`Backend::phase`] tells it apart.
+fn job_status(status: &JobStatusResponse) -> JobStatus {
+    match status.status.to_uppercase().as_str() {
+        "QUEUED" | "VALIDATING" => JobStatus::Queued,
+        "RUNNING" => JobStatus::Running,
+        "COMPLETED" => JobStatus::Completed,
+        "FAILED" | "ERROR" => {
+            let msg = status
+                .error_message()
+                .unwrap_or_else(|| "Unknown error".to_string());
+            JobStatus::Failed(msg)
+        }
+        "CANCELLED" => JobStatus::Cancelled,
+        _ => JobStatus::Running, // Treat unknown as running
+    }
+}
+
 /// Convert hex string to binary string, padded to `num_qubits` width.
 ///
 /// If `num_qubits` is 0 the width falls back to 4 bits per hex digit.
@@ -436,6 +471,7 @@ impl Backend for IbmBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: info.status.status_msg,
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     Ok(BackendAvailability::unavailable(
@@ -578,30 +614,17 @@ impl Backend for IbmBackend {
     }
 
     async fn status(&self, job_id: &JobId) -> HalResult<JobStatus> {
-        let status = self
-            .client
-            .get_job_status(&job_id.0)
-            .await
-            .map_err(|e| match e {
-                IbmError::JobNotFound(id) => HalError::JobNotFound(id),
-                other => HalError::Backend(other.to_string()),
-            })?;
+        let status = self.fetch_job_status(job_id).await?;
+        Ok(job_status(&status))
+    }
 
-        let job_status = match status.status.to_uppercase().as_str() {
-            "QUEUED" => JobStatus::Queued,
-            "VALIDATING" | "RUNNING" => JobStatus::Running,
-            "COMPLETED" => JobStatus::Completed,
-            "FAILED" | "ERROR" => {
-                let msg = status
-                    .error_message()
-                    .unwrap_or_else(|| "Unknown error".to_string());
-                JobStatus::Failed(msg)
-            }
-            "CANCELLED" => JobStatus::Cancelled,
-            _ => JobStatus::Running, // Treat unknown as running
+    async fn phase(&self, job_id: &JobId) -> HalResult<Option<JobPhase>> {
+        let status = self.fetch_job_status(job_id).await?;
+        let phase = match status.status.to_uppercase().as_str() {
+            "VALIDATING" => Some(JobPhase::Validating),
+            _ => JobPhase::from_status(&job_status(&status)),
         };
-
-        Ok(job_status)
+        Ok(phase)
     }
 
     async fn result(&self, job_id: &JobId) -> HalResult<ExecutionResult> {
@@ -662,6 +685,12 @@ impl Backend for IbmBackend {
     }
 }
 
+impl BackendFactory for IbmBackend {
+    fn from_config(config: BackendConfig) -> HalResult<Self> {
+        Ok(Self::with_config(config)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -696,6 +725,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_typed_config() {
+        let config = arvak_hal::BackendsConfig::from_toml_str(
+            "[backends.torino]\nadapter = \"ibm\"\ntoken = \"t\"\nbackend = \"ibm_brisbane\"\n",
+        )
+        .unwrap()
+        .resolve("torino")
+        .unwrap();
+        let backend = <IbmBackend as BackendFactory>::from_config(config).unwrap();
+        assert_eq!(backend.target(), "ibm_brisbane");
+    }
+
     #[test]
     fn test_results_to_counts_v1() {
         use crate::api::{JobResultResponse, SamplerResult};
@@ -847,4 +888,18 @@ mod tests {
         assert!(data.contains_key("c"));
         assert_eq!(data["c"].samples.len(), 4);
     }
+
+    #[test]
+    fn test_job_status_validating_is_queued() {
+        let status = |state: &str| {
+            let response: JobStatusResponse =
+                serde_json::from_value(serde_json::json!({"id": "job", "status": state})).unwrap();
+            job_status(&response)
+        };
+
+        assert_eq!(status("Validating"), JobStatus::Queued);
+        assert_eq!(status("QUEUED"), JobStatus::Queued);
+        assert_eq!(status("RUNNING"), JobStatus::Running);
+        assert_eq!(status("CANCELLED"), JobStatus::Cancelled);
+    }
 }
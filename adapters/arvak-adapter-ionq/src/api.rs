@@ -63,6 +63,11 @@ impl IonQClient {
         })
     }
 
+    /// The API base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Build the `Authorization` header value.
     ///
     /// IonQ uses `Authorization: apiKey <token>` (not Bearer).
@@ -397,7 +402,7 @@ pub struct JobError {
 }
 
 /// Backend info from `GET /backends` or `GET /backends/{name}`.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendInfo {
     /// Backend name (e.g., "simulator", "qpu.aria-1").
     pub backend: String,
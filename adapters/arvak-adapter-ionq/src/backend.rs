@@ -1,7 +1,6 @@
 //! IonQ backend implementation.
 
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use rustc_hash::FxHashMap;
@@ -10,7 +9,7 @@ use tracing::{debug, info, instrument};
 
 use arvak_hal::{
     Backend, BackendAvailability, BackendConfig, BackendFactory, Capabilities, Counts,
-    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ValidationResult,
+    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ResponseCache, ValidationResult,
 };
 use arvak_ir::{
     Circuit,
@@ -49,9 +48,6 @@ fn qubits_for_backend(backend_name: &str) -> u32 {
 /// Maximum number of cached job entries before evicting terminal-state entries.
 const MAX_CACHED_JOBS: usize = 10_000;
 
-/// TTL for cached backend info before re-fetching from the API.
-const BACKEND_INFO_TTL: Duration = Duration::from_secs(5 * 60);
-
 /// Cached job entry.
 struct CachedJob {
     job: Job,
@@ -95,8 +91,8 @@ pub struct IonQBackend {
     capabilities: Capabilities,
     /// Cached job metadata and results.
     jobs: Arc<Mutex<FxHashMap<String, CachedJob>>>,
-    /// Cached backend info with TTL.
-    backend_info: Arc<Mutex<Option<(BackendInfo, Instant)>>>,
+    /// Cache for backend info responses, shared across backends.
+    response_cache: Arc<ResponseCache>,
 }
 
 impl IonQBackend {
@@ -117,20 +113,7 @@ impl IonQBackend {
             std::env::var("IONQ_API_URL").unwrap_or_else(|_| crate::api::BASE_URL.to_string());
 
         let client = IonQClient::with_base_url(base_url, api_key)?;
-        let backend_name = backend_name.into();
-        let name = format!("ionq_{}", backend_name.replace('.', "_"));
-
-        let max_qubits = qubits_for_backend(&backend_name);
-        let capabilities = build_capabilities(&backend_name, max_qubits);
-
-        Ok(Self {
-            client,
-            backend_name,
-            name,
-            capabilities,
-            jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            backend_info: Arc::new(Mutex::new(None)),
-        })
+        Ok(Self::from_client(client, backend_name.into()))
     }
 
     /// Create a backend with an explicit API key (useful for testing).
@@ -139,20 +122,22 @@ impl IonQBackend {
         api_key: impl Into<String>,
     ) -> IonQResult<Self> {
         let client = IonQClient::new(api_key)?;
-        let backend_name = backend_name.into();
-        let name = format!("ionq_{}", backend_name.replace('.', "_"));
+        Ok(Self::from_client(client, backend_name.into()))
+    }
 
+    fn from_client(client: IonQClient, backend_name: String) -> Self {
+        let name = format!("ionq_{}", backend_name.replace('.', "_"));
         let max_qubits = qubits_for_backend(&backend_name);
         let capabilities = build_capabilities(&backend_name, max_qubits);
 
-        Ok(Self {
+        Self {
             client,
             backend_name,
             name,
             capabilities,
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            backend_info: Arc::new(Mutex::new(None)),
-        })
+            response_cache: ResponseCache::global(),
+        }
     }
 
     /// Return the IonQ backend name (e.g., "simulator", "qpu.aria-1").
@@ -160,25 +145,19 @@ impl IonQBackend {
         &self.backend_name
     }
 
+    /// Use `cache` for backend info instead of [`ResponseCache::global`].
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = cache;
+        self
+    }
+
     /// Fetch backend info from the API, using the cache if not stale.
     async fn fetch_backend_info(&self) -> IonQResult<BackendInfo> {
-        {
-            let cache = self.backend_info.lock().await;
-            if let Some((ref info, fetched_at)) = *cache {
-                if fetched_at.elapsed() < BACKEND_INFO_TTL {
-                    return Ok(info.clone());
-                }
-            }
-        }
-
-        let info = self.client.get_backend(&self.backend_name).await?;
-
-        {
-            let mut cache = self.backend_info.lock().await;
-            *cache = Some((info.clone(), Instant::now()));
-        }
-
-        Ok(info)
+        let key = format!("ionq/{}/{}", self.client.base_url(), self.backend_name);
+        self.response_cache
+            .get_or_fetch(&key, || self.client.get_backend(&self.backend_name))
+            .await
     }
 
     /// Serialize a circuit to IonQ's QIS JSON gate format.
@@ -218,7 +197,9 @@ impl IonQBackend {
 
         match &gate.kind {
             GateKind::Standard(sg) => Self::standard_gate_to_ionq(sg, qubits, q0),
-            GateKind::Custom(cg) => Err(IonQError::UnsupportedGate(cg.name.clone())),
+            GateKind::Custom(_) | GateKind::Modified(_) => {
+                Err(IonQError::UnsupportedGate(gate.name().to_string()))
+            }
         }
     }
 
@@ -479,6 +460,7 @@ impl Backend for IonQBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: Some(format!("status: {}", info.status)),
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     Ok(BackendAvailability::unavailable(format!(
@@ -773,7 +755,17 @@ impl BackendFactory for IonQBackend {
             .unwrap_or(SIMULATOR)
             .to_string();
 
-        Self::with_backend(backend_name).map_err(|e| HalError::Backend(e.to_string()))
+        // An explicit API key (as set by a typed configuration section)
+        // takes precedence over `IONQ_API_KEY` / `IONQ_API_URL`.
+        let Some(api_key) = config.token else {
+            return Self::with_backend(backend_name).map_err(|e| HalError::Backend(e.to_string()));
+        };
+        let base_url = config
+            .endpoint
+            .unwrap_or_else(|| crate::api::BASE_URL.to_string());
+        let client = IonQClient::with_base_url(base_url, api_key)
+            .map_err(|e| HalError::Backend(e.to_string()))?;
+        Ok(Self::from_client(client, backend_name))
     }
 }
 
@@ -928,6 +920,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_typed_config() {
+        let config = arvak_hal::BackendsConfig::from_toml_str(
+            "[backends.aria]\nadapter = \"ionq\"\nbackend = \"qpu.aria-1\"\napi_key = \"key\"\n",
+        )
+        .unwrap();
+        let backend = IonQBackend::from_config(config.resolve("aria").unwrap()).unwrap();
+        assert_eq!(backend.backend_name(), "qpu.aria-1");
+        assert_eq!(backend.name(), "ionq_qpu_aria-1");
+    }
+
     #[test]
     fn test_build_capabilities_simulator() {
         let caps = build_capabilities("simulator", 29);
@@ -329,6 +329,16 @@ impl IqmBackend {
                         "Noise channels are not submittable to live IQM hardware.".into(),
                     ));
                 }
+                InstructionKind::IfElse { .. } => {
+                    return Err(HalError::InvalidCircuit(
+                        "if/else blocks are not supported by IQM Resonance circuit jobs.".into(),
+                    ));
+                }
+                InstructionKind::WhileLoop { .. } => {
+                    return Err(HalError::InvalidCircuit(
+                        "while loops are not supported by IQM Resonance circuit jobs.".into(),
+                    ));
+                }
             }
         }
 
@@ -422,6 +432,7 @@ impl Backend for IqmBackend {
                 queue_depth: None,
                 estimated_wait: None,
                 status_message: None,
+                maintenance_windows: Vec::new(),
             }),
             Ok(false) => Ok(BackendAvailability::unavailable(
                 "Resonance reports QC unhealthy",
@@ -573,6 +584,15 @@ impl Backend for IqmBackend {
         Ok(status)
     }
 
+    #[instrument(skip(self))]
+    async fn queue_position(&self, job_id: &JobId) -> HalResult<Option<u32>> {
+        let response = self.client.get_job(&job_id.0).await.map_err(|e| match e {
+            IqmError::JobNotFound(_) => HalError::JobNotFound(job_id.0.clone()),
+            _ => HalError::Backend(e.to_string()),
+        })?;
+        Ok(response.queue_position)
+    }
+
     #[instrument(skip(self))]
     async fn result(&self, job_id: &JobId) -> HalResult<ExecutionResult> {
         {
@@ -182,6 +182,7 @@ impl QdmiBackend {
             is_simulator: false,
             features: vec!["qdmi".into(), "mqss".into()],
             noise_profile: None,
+            decoherence: None,
         };
         Self {
             config: BackendConfig::new("qdmi"),
@@ -301,6 +302,7 @@ impl QdmiBackend {
             is_simulator: false,
             features: vec!["qdmi".into(), "mqss".into()],
             noise_profile: None,
+            decoherence: None,
         })
     }
 }
@@ -324,6 +326,7 @@ impl QdmiBackend {
             is_simulator: false,
             features: vec!["qdmi".into(), "mqss".into(), "system".into()],
             noise_profile: None,
+            decoherence: None,
         };
         Self {
             config: BackendConfig::new("qdmi"),
@@ -550,6 +553,7 @@ impl QdmiBackend {
                 is_simulator: false,
                 features: vec!["qdmi".into(), "mqss".into(), "system".into()],
                 noise_profile: None,
+                decoherence: None,
             })
         }
     }
@@ -808,6 +812,7 @@ impl Backend for QdmiBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: None,
+                        maintenance_windows: Vec::new(),
                     });
                 }
                 return Ok(BackendAvailability::unavailable("device not idle"));
@@ -821,6 +826,7 @@ impl Backend for QdmiBackend {
                     queue_depth: None,
                     estimated_wait: None,
                     status_message: None,
+                    maintenance_windows: Vec::new(),
                 });
             }
         }
@@ -856,6 +862,7 @@ impl Backend for QdmiBackend {
                                 queue_depth: None,
                                 estimated_wait: None,
                                 status_message: None,
+                                maintenance_windows: Vec::new(),
                             });
                         }
                         return Ok(BackendAvailability::unavailable("device not idle"));
@@ -871,6 +878,7 @@ impl Backend for QdmiBackend {
                     queue_depth: None,
                     estimated_wait: None,
                     status_message: None,
+                    maintenance_windows: Vec::new(),
                 });
             }
         }
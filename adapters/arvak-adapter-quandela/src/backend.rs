@@ -86,6 +86,7 @@ fn build_capabilities(platform: &str) -> Capabilities {
         is_simulator: is_sim,
         features: vec!["photonic".into()],
         noise_profile: None,
+        decoherence: None,
     }
 }
 
@@ -137,6 +138,11 @@ fn circuit_to_json(circuit: &Circuit) -> QuandelaResult<String> {
                         .iter()
                         .filter_map(arvak_ir::ParameterExpression::as_f64)
                         .collect(),
+                    GateKind::Modified(mg) => mg
+                        .parameters()
+                        .into_iter()
+                        .filter_map(arvak_ir::ParameterExpression::as_f64)
+                        .collect(),
                 };
                 gates.push(GateJson {
                     name,
@@ -268,7 +274,10 @@ impl QuandelaBackend {
     }
 
     fn from_config_impl(config: BackendConfig) -> QuandelaResult<Self> {
-        let platform = if config.name.contains(':') {
+        let platform = if let Some(platform) = config.extra.get("platform").and_then(|v| v.as_str())
+        {
+            platform.to_string()
+        } else if config.name.contains(':') {
             config.name.clone()
         } else {
             "sim:ascella".to_string()
@@ -78,6 +78,11 @@ impl QuantinuumClient {
         })
     }
 
+    /// The API base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Authenticate with email + password; stores the JWT and refresh token.
     #[instrument(skip(self))]
     pub async fn login(&self) -> QuantinuumResult<()> {
@@ -447,7 +452,7 @@ impl JobStatusResponse {
 }
 
 /// Machine information returned by `GET /machine/?config=true`.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MachineInfo {
     /// Machine name.
     pub name: String,
@@ -3,13 +3,12 @@
 use async_trait::async_trait;
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{debug, info, instrument};
 
 use arvak_hal::{
     Backend, BackendAvailability, BackendConfig, BackendFactory, Capabilities, Counts,
-    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ValidationResult,
+    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ResponseCache, ValidationResult,
 };
 use arvak_ir::Circuit;
 
@@ -25,9 +24,6 @@ const DEFAULT_NUM_QUBITS: u32 = 32;
 /// Maximum number of cached jobs before evicting completed entries.
 const MAX_CACHED_JOBS: usize = 10_000;
 
-/// How long to cache machine info before refreshing from the API.
-const MACHINE_INFO_TTL: Duration = Duration::from_secs(5 * 60);
-
 /// Cached job entry.
 struct CachedJob {
     job: Job,
@@ -63,8 +59,8 @@ pub struct QuantinuumBackend {
     capabilities: Capabilities,
     /// Cached job metadata and results.
     jobs: Arc<Mutex<FxHashMap<String, CachedJob>>>,
-    /// Cached machine info with TTL.
-    machine_info: Arc<Mutex<Option<(MachineInfo, Instant)>>>,
+    /// Cache for machine info responses, shared across backends.
+    response_cache: Arc<ResponseCache>,
 }
 
 impl QuantinuumBackend {
@@ -92,7 +88,7 @@ impl QuantinuumBackend {
             target,
             capabilities,
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            machine_info: Arc::new(Mutex::new(None)),
+            response_cache: ResponseCache::global(),
         })
     }
 
@@ -111,7 +107,7 @@ impl QuantinuumBackend {
             target,
             capabilities,
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
-            machine_info: Arc::new(Mutex::new(None)),
+            response_cache: ResponseCache::global(),
         })
     }
 
@@ -120,25 +116,19 @@ impl QuantinuumBackend {
         &self.target
     }
 
-    /// Fetch machine info from API, using the cache if not stale.
-    async fn fetch_machine_info(&self) -> QuantinuumResult<MachineInfo> {
-        {
-            let cache = self.machine_info.lock().await;
-            if let Some((ref info, fetched_at)) = *cache {
-                if fetched_at.elapsed() < MACHINE_INFO_TTL {
-                    return Ok(info.clone());
-                }
-            }
-        }
-
-        let info = self.client.get_machine(&self.target).await?;
-
-        {
-            let mut cache = self.machine_info.lock().await;
-            *cache = Some((info.clone(), Instant::now()));
-        }
+    /// Use `cache` for machine info instead of [`ResponseCache::global`].
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = cache;
+        self
+    }
 
-        Ok(info)
+    /// Fetch machine info from the API, using the cache if not stale.
+    async fn fetch_machine_info(&self) -> QuantinuumResult<MachineInfo> {
+        let key = format!("quantinuum/{}/{}", self.client.base_url(), self.target);
+        self.response_cache
+            .get_or_fetch(&key, || self.client.get_machine(&self.target))
+            .await
     }
 
     /// Convert a circuit to QASM 2.0 string for submission.
@@ -235,6 +225,7 @@ impl Backend for QuantinuumBackend {
                         queue_depth: None,
                         estimated_wait: None,
                         status_message: None,
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     let msg = info.status.as_deref().unwrap_or("offline").to_string();
@@ -386,6 +377,15 @@ impl Backend for QuantinuumBackend {
         Ok(status)
     }
 
+    #[instrument(skip(self))]
+    async fn queue_position(&self, job_id: &JobId) -> HalResult<Option<u32>> {
+        let response = self.client.get_job(&job_id.0).await.map_err(|e| match e {
+            QuantinuumError::JobNotFound(_) => HalError::JobNotFound(job_id.0.clone()),
+            _ => HalError::Backend(e.to_string()),
+        })?;
+        Ok(response.queue_position)
+    }
+
     #[instrument(skip(self))]
     async fn result(&self, job_id: &JobId) -> HalResult<ExecutionResult> {
         // Return from cache if available.
@@ -464,7 +464,14 @@ impl BackendFactory for QuantinuumBackend {
             .unwrap_or(DEFAULT_MACHINE)
             .to_string();
 
-        Self::with_target(target).map_err(|e| HalError::Backend(e.to_string()))
+        // Explicit credentials (as set by a typed configuration section, with
+        // the password as token) take precedence over the environment.
+        let email = config.extra.get("email").and_then(|v| v.as_str());
+        match (email, config.token.as_deref()) {
+            (Some(email), Some(password)) => Self::with_credentials(target, email, password),
+            _ => Self::with_target(target),
+        }
+        .map_err(|e| HalError::Backend(e.to_string()))
     }
 }
 
@@ -539,6 +546,17 @@ mod tests {
         assert!(matches!(vr, ValidationResult::Valid), "got {vr:?}");
     }
 
+    #[test]
+    fn test_from_typed_config() {
+        let config = arvak_hal::BackendsConfig::from_toml_str(
+            "[backends.h2]\nadapter = \"quantinuum\"\ntarget = \"H2-1E\"\n\
+             email = \"user@example.com\"\npassword = \"pw\"\n",
+        )
+        .unwrap();
+        let backend = QuantinuumBackend::from_config(config.resolve("h2").unwrap()).unwrap();
+        assert_eq!(backend.target(), "H2-1E");
+    }
+
     #[test]
     fn test_build_capabilities_emulator() {
         let caps = build_capabilities("H2-1LE", 32);
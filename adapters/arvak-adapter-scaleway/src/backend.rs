@@ -321,6 +321,7 @@ impl Backend for ScalewayBackend {
                         queue_depth: session.waiting_job_count,
                         estimated_wait: None,
                         status_message: session.progress_message,
+                        maintenance_windows: Vec::new(),
                     })
                 } else if session.is_starting() {
                     Ok(BackendAvailability {
@@ -331,6 +332,7 @@ impl Backend for ScalewayBackend {
                             "Session starting ({})",
                             session.progress_message.unwrap_or_default()
                         )),
+                        maintenance_windows: Vec::new(),
                     })
                 } else {
                     Ok(BackendAvailability::unavailable(format!(
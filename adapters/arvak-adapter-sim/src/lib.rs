@@ -8,7 +8,14 @@
 //!
 //! - **Exact Simulation**: Full statevector representation (no sampling noise)
 //! - **All Standard Gates**: Supports all gates from `arvak-ir`
+//! - **Custom Unitaries**: Custom gates with a matrix or a definition
+//! - **Initial States**: Start from a basis state or explicit statevector
+//!   via `SimulatorBackend::submit_with_initial_state`
+//! - **Parameter Sweeps**: `SimulatorBackend::run_sweep` runs one circuit
+//!   for many parameter sets, rebinding only parameterized gates and
+//!   running the sets in parallel
 //! - **Measurement Sampling**: Probabilistic measurement with configurable shots
+//! - **Expectation Values**: Exact Pauli observables via `Backend::submit_observable`
 //! - **No External Dependencies**: Pure Rust implementation
 //!
 //! # Performance
@@ -53,4 +60,4 @@
 mod simulator;
 mod statevector;
 
-pub use simulator::SimulatorBackend;
+pub use simulator::{InitialState, SimulatorBackend};
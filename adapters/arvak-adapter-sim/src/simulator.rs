@@ -1,23 +1,62 @@
 //! Simulator backend implementation.
 
 use async_trait::async_trait;
+use num_complex::Complex64;
 use rustc_hash::FxHashMap;
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
+use arvak_hal::negotiation::bind_parameters;
 use arvak_hal::{
     Backend, BackendAvailability, BackendConfig, BackendFactory, Capabilities, Counts,
-    ExecutionResult, HalError, HalResult, Job, JobId, JobStatus, ValidationResult,
+    EXPECTATION_VALUES_FEATURE, ExecutionResult, HalError, HalResult, Job, JobId, JobStatus,
+    Observable, ValidationResult,
 };
-use arvak_ir::Circuit;
+use arvak_ir::{Circuit, Instruction, NoiseModel};
 
 use crate::statevector::Statevector;
 
 /// Maximum number of cached jobs before evicting completed entries.
 const MAX_CACHED_JOBS: usize = 10_000;
 
+/// The state a simulation starts from.
+///
+/// Lets partial circuits and continuation runs start from a prepared state
+/// instead of prepending preparation gates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum InitialState {
+    /// All qubits in `|0⟩`.
+    #[default]
+    Zero,
+    /// A computational basis state. Bit `q` of the index is qubit `q`, so
+    /// `Basis(0b01)` is the state measured as `"01"`.
+    Basis(u64),
+    /// Explicit amplitudes, `2^n` of them with unit norm, indexed like
+    /// [`InitialState::Basis`].
+    Statevector(Vec<Complex64>),
+}
+
+impl InitialState {
+    /// The statevector on `num_qubits` qubits, or why there is none.
+    fn prepare(&self, num_qubits: usize) -> Result<Statevector, String> {
+        match self {
+            Self::Zero => Ok(Statevector::new(num_qubits)),
+            Self::Basis(index) => {
+                let index = usize::try_from(*index).map_err(|e| e.to_string())?;
+                Statevector::basis(num_qubits, index)
+            }
+            Self::Statevector(amplitudes) => {
+                Statevector::from_amplitudes(num_qubits, amplitudes.clone())
+            }
+        }
+    }
+}
+
 /// Job data for the simulator.
 struct SimJob {
     job: Job,
@@ -49,7 +88,7 @@ impl SimulatorBackend {
         let max_qubits = 20;
         Self {
             config: BackendConfig::new("simulator"),
-            capabilities: Capabilities::simulator(max_qubits),
+            capabilities: simulator_capabilities(max_qubits),
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
             max_qubits,
             seed: None,
@@ -60,7 +99,7 @@ impl SimulatorBackend {
     pub fn with_max_qubits(max_qubits: u32) -> Self {
         Self {
             config: BackendConfig::new("simulator"),
-            capabilities: Capabilities::simulator(max_qubits),
+            capabilities: simulator_capabilities(max_qubits),
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
             max_qubits,
             seed: None,
@@ -90,17 +129,223 @@ impl SimulatorBackend {
     /// without going through the async [`Backend`] trait.
     #[instrument(skip(self, circuit))]
     pub fn run_simulation(&self, circuit: &Circuit, shots: u32) -> Result<ExecutionResult, String> {
-        run_simulation_seeded(circuit, shots, self.seed)
+        run_simulation_seeded(circuit, shots, self.seed, &InitialState::Zero)
+    }
+
+    /// Run simulation synchronously, starting from `initial` instead of
+    /// `|0...0⟩`.
+    ///
+    /// Returns an error if `initial` does not fit the circuit's qubits, or
+    /// for the reasons of [`SimulatorBackend::run_simulation`].
+    pub fn run_simulation_from(
+        &self,
+        circuit: &Circuit,
+        shots: u32,
+        initial: &InitialState,
+    ) -> Result<ExecutionResult, String> {
+        run_simulation_seeded(circuit, shots, self.seed, initial)
+    }
+
+    /// Run `circuit` once per parameter set, returning one result per set
+    /// in order.
+    ///
+    /// Faster than binding and running each set separately: the circuit is
+    /// walked once, only parameter-dependent gates are rebuilt per set, and
+    /// sets run in parallel. Each set is sampled with `shots` shots.
+    pub fn run_sweep(
+        &self,
+        circuit: &Circuit,
+        parameter_sets: &[HashMap<String, f64>],
+        shots: u32,
+    ) -> Result<Vec<ExecutionResult>, String> {
+        run_sweep_seeded(circuit, parameter_sets, shots, self.seed)
     }
+
+    /// Submit a circuit that starts from `initial` instead of `|0...0⟩`.
+    ///
+    /// Behaves like [`Backend::submit`] without parameter bindings; an
+    /// initial state that does not fit the circuit is rejected with
+    /// [`HalError::InvalidCircuit`].
+    pub async fn submit_with_initial_state(
+        &self,
+        circuit: &Circuit,
+        shots: u32,
+        initial: InitialState,
+    ) -> HalResult<JobId> {
+        // HAL Contract v2 §3.3 rule 4: validate before dispatching.
+        match self.validate(circuit, shots).await? {
+            ValidationResult::Invalid { reasons } => {
+                return Err(HalError::InvalidCircuit(reasons.join("; ")));
+            }
+            ValidationResult::Valid | ValidationResult::RequiresTranspilation { .. } => {}
+        }
+        if let Err(e) = initial.prepare(circuit.num_qubits()) {
+            return Err(HalError::InvalidCircuit(format!("initial state: {e}")));
+        }
+
+        // Generate job ID
+        let job_id = JobId::new(Uuid::new_v4().to_string());
+
+        // Create job
+        let job = Job::new(job_id.clone(), shots).with_backend("simulator");
+
+        let sim_job = SimJob {
+            job,
+            circuit: circuit.clone(),
+            result: None,
+        };
+
+        // Store job, evicting completed entries if the cache is full.
+        {
+            let mut jobs = self
+                .jobs
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if jobs.len() >= MAX_CACHED_JOBS {
+                jobs.retain(|_, j| !j.job.status.is_terminal());
+            }
+            jobs.insert(job_id.0.clone(), sim_job);
+        }
+
+        debug!("Submitted job: {}", job_id);
+
+        // Run simulation on a blocking thread to avoid starving the async runtime.
+        let circuit_clone = circuit.clone();
+        let seed = self.seed;
+        let result = tokio::task::spawn_blocking(move || {
+            run_simulation_seeded(&circuit_clone, shots, seed, &initial)
+        })
+        .await
+        .map_err(|e| HalError::Backend(format!("simulation task panicked: {e}")))?
+        .map_err(|e| HalError::Backend(format!("simulation failed: {e}")))?;
+
+        // Update job with result
+        {
+            let mut jobs = self
+                .jobs
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if let Some(sim_job) = jobs.get_mut(&job_id.0) {
+                sim_job.result = Some(result);
+                sim_job.job = sim_job.job.clone().with_status(JobStatus::Completed);
+            }
+        }
+
+        Ok(job_id)
+    }
+}
+
+/// Simulator capabilities; the statevector yields expectation values exactly.
+fn simulator_capabilities(max_qubits: u32) -> Capabilities {
+    let mut capabilities = Capabilities::simulator(max_qubits);
+    capabilities
+        .features
+        .push(EXPECTATION_VALUES_FEATURE.into());
+    capabilities
+}
+
+/// Exact expectation values of `observables` in the state prepared by
+/// `circuit`.
+///
+/// Measurements and readout errors are ignored. Circuits with `Reset` or
+/// state noise channels evolve stochastically, so the values are averaged
+/// over `trajectories` independent runs.
+fn expectation_values_seeded(
+    circuit: &Circuit,
+    observables: &[Observable],
+    trajectories: u32,
+    seed: Option<u64>,
+) -> Result<Vec<f64>, String> {
+    use rand::SeedableRng;
+
+    let mut rng = match seed {
+        Some(s) => rand::rngs::StdRng::seed_from_u64(s),
+        None => rand::rngs::StdRng::from_entropy(),
+    };
+    let instructions: Vec<_> = circuit
+        .dag()
+        .topological_ops()
+        .map(|(_, inst)| inst.clone())
+        .collect();
+    let runs = if instructions.iter().any(is_stochastic) {
+        trajectories.max(1)
+    } else {
+        1
+    };
+
+    let mut values = vec![0.0; observables.len()];
+    for _ in 0..runs {
+        let mut sv = Statevector::new(circuit.num_qubits());
+        for inst in &instructions {
+            sv.apply(inst, &mut rng)?;
+        }
+        for (value, observable) in values.iter_mut().zip(observables) {
+            *value += observable
+                .terms
+                .iter()
+                .map(|term| term.coeff * sv.pauli_expectation(&term.paulis))
+                .sum::<f64>();
+        }
+    }
+    for value in &mut values {
+        *value /= f64::from(runs);
+    }
+    Ok(values)
+}
+
+/// Whether `inst` makes the evolution stochastic, so that each shot needs
+/// its own trajectory.
+fn is_stochastic(inst: &arvak_ir::Instruction) -> bool {
+    match &inst.kind {
+        arvak_ir::InstructionKind::Reset => true,
+        arvak_ir::InstructionKind::NoiseChannel { model, .. } => {
+            !matches!(model, NoiseModel::ReadoutError { .. })
+        }
+        _ => false,
+    }
+}
+
+/// Per-qubit probability that a measurement outcome is flipped, combining
+/// every `ReadoutError` channel on that qubit.
+fn readout_flip_probabilities(
+    instructions: &[arvak_ir::Instruction],
+    num_qubits: usize,
+) -> Vec<f64> {
+    let mut flips = vec![0.0; num_qubits];
+    for inst in instructions {
+        if let arvak_ir::InstructionKind::NoiseChannel {
+            model: NoiseModel::ReadoutError { p },
+            ..
+        } = &inst.kind
+        {
+            for qubit in &inst.qubits {
+                let q = &mut flips[qubit.0 as usize];
+                // Two independent flips cancel.
+                *q = *q + p - 2.0 * *q * p;
+            }
+        }
+    }
+    flips
+}
+
+/// Flip each bit of `outcome` with its qubit's readout error probability.
+fn apply_readout_error<R: rand::Rng>(outcome: usize, flips: &[f64], rng: &mut R) -> usize {
+    flips
+        .iter()
+        .enumerate()
+        .filter(|&(_, &p)| p > 0.0 && rng.r#gen::<f64>() < p)
+        .fold(outcome, |acc, (q, _)| acc ^ (1 << q))
 }
 
 /// Free-standing simulation engine (does not need backend state).
 ///
-/// `seed` makes runs reproducible; `None` seeds from OS entropy.
+/// `seed` makes runs reproducible; `None` seeds from OS entropy. Every shot
+/// starts from `initial`.
 pub fn run_simulation_seeded(
     circuit: &Circuit,
     shots: u32,
     seed: Option<u64>,
+    initial: &InitialState,
 ) -> Result<ExecutionResult, String> {
     use rand::SeedableRng;
 
@@ -126,21 +371,35 @@ pub fn run_simulation_seeded(
 
     debug!("Circuit has {} instructions", instructions.len());
 
-    let has_reset = instructions
-        .iter()
-        .any(|inst| matches!(inst.kind, arvak_ir::InstructionKind::Reset));
+    let readout = readout_flip_probabilities(&instructions, num_qubits);
+    let initial = initial.prepare(num_qubits)?;
+    let counts = sample(&instructions, initial, &readout, shots, &mut rng)?;
+
+    let elapsed = start.elapsed();
+    debug!("Simulation completed in {:?}", elapsed);
 
+    Ok(ExecutionResult::new(counts, shots).with_execution_time(elapsed.as_millis() as u64))
+}
+
+/// Evolve `initial` through `instructions` and sample `shots` outcomes.
+fn sample<I: Borrow<Instruction>>(
+    instructions: &[I],
+    initial: Statevector,
+    readout: &[f64],
+    shots: u32,
+    rng: &mut rand::rngs::StdRng,
+) -> Result<Counts, String> {
     let mut counts = Counts::new();
 
-    if has_reset {
-        // Mid-circuit reset collapses stochastically: each shot is an
-        // independent trajectory.
+    if instructions.iter().any(|inst| is_stochastic(inst.borrow())) {
+        // Mid-circuit reset and noise channels act stochastically: each shot
+        // is an independent trajectory.
         for shot in 0..shots {
-            let mut sv = Statevector::new(num_qubits);
-            for inst in &instructions {
-                sv.apply(inst, &mut rng)?;
+            let mut sv = initial.clone();
+            for inst in instructions {
+                sv.apply(inst.borrow(), rng)?;
             }
-            let outcome = sv.sample(&mut rng);
+            let outcome = apply_readout_error(sv.sample(rng), readout, rng);
             counts.insert(sv.outcome_to_bitstring(outcome), 1);
 
             if shot > 0 && shot % 1000 == 0 {
@@ -149,19 +408,141 @@ pub fn run_simulation_seeded(
         }
     } else {
         // Deterministic evolution: simulate once, sample the distribution.
-        let mut sv = Statevector::new(num_qubits);
-        for inst in &instructions {
-            sv.apply(inst, &mut rng)?;
+        let mut sv = initial;
+        for inst in instructions {
+            sv.apply(inst.borrow(), rng)?;
+        }
+        for (outcome, count) in sv.sample_counts(shots, rng) {
+            if readout.iter().all(|&p| p == 0.0) {
+                counts.insert(sv.outcome_to_bitstring(outcome), count.into());
+                continue;
+            }
+            for _ in 0..count {
+                let noisy = apply_readout_error(outcome, readout, rng);
+                counts.insert(sv.outcome_to_bitstring(noisy), 1);
+            }
         }
-        for (outcome, count) in sv.sample_counts(shots, &mut rng) {
-            counts.insert(sv.outcome_to_bitstring(outcome), count.into());
+    }
+
+    Ok(counts)
+}
+
+/// Run `circuit` once per parameter set.
+///
+/// The instruction sequence is collected once and shared by all instances;
+/// only instructions with symbolic parameters are bound per instance.
+/// Instances run in parallel on scoped threads. With a `seed`, instance `i`
+/// samples with seed `seed + i`, so results do not depend on how instances
+/// are spread over threads. Fails if any parameter set leaves a parameter
+/// unbound, or for the reasons of [`run_simulation_seeded`].
+pub fn run_sweep_seeded(
+    circuit: &Circuit,
+    parameter_sets: &[HashMap<String, f64>],
+    shots: u32,
+    seed: Option<u64>,
+) -> Result<Vec<ExecutionResult>, String> {
+    use rand::SeedableRng;
+
+    let names = circuit.parameters();
+    for (index, values) in parameter_sets.iter().enumerate() {
+        let unbound: Vec<&str> = names
+            .iter()
+            .filter(|name| !values.contains_key(*name))
+            .map(String::as_str)
+            .collect();
+        if !unbound.is_empty() {
+            return Err(format!(
+                "parameter set {index}: unbound parameters: {}",
+                unbound.join(", ")
+            ));
         }
     }
 
-    let elapsed = start.elapsed();
-    debug!("Simulation completed in {:?}", elapsed);
+    let num_qubits = circuit.num_qubits();
+    let instructions: Vec<Instruction> = circuit
+        .dag()
+        .topological_ops()
+        .map(|(_, inst)| inst.clone())
+        .collect();
+    let symbolic: Vec<bool> = instructions.iter().map(has_symbolic_parameters).collect();
+    let readout = readout_flip_probabilities(&instructions, num_qubits);
+    let initial = Statevector::new(num_qubits);
+
+    let run_instance = |index: usize| -> Result<ExecutionResult, String> {
+        let start = Instant::now();
+        let values = &parameter_sets[index];
+        let bound: Vec<Cow<'_, Instruction>> = instructions
+            .iter()
+            .zip(&symbolic)
+            .map(|(inst, &symbolic)| {
+                if symbolic {
+                    let mut inst = inst.clone();
+                    inst.bind_parameters(values);
+                    Cow::Owned(inst)
+                } else {
+                    Cow::Borrowed(inst)
+                }
+            })
+            .collect();
+        let mut rng = match seed {
+            Some(s) => rand::rngs::StdRng::seed_from_u64(s.wrapping_add(index as u64)),
+            None => rand::rngs::StdRng::from_entropy(),
+        };
+        let counts = sample(&bound, initial.clone(), &readout, shots, &mut rng)?;
+        Ok(ExecutionResult::new(counts, shots)
+            .with_execution_time(start.elapsed().as_millis() as u64))
+    };
 
-    Ok(ExecutionResult::new(counts, shots).with_execution_time(elapsed.as_millis() as u64))
+    let workers = std::thread::available_parallelism()
+        .map_or(1, std::num::NonZero::get)
+        .min(parameter_sets.len());
+    debug!(
+        "Starting sweep: {} instances of {} instructions on {} threads",
+        parameter_sets.len(),
+        instructions.len(),
+        workers
+    );
+    if workers <= 1 {
+        return (0..parameter_sets.len()).map(run_instance).collect();
+    }
+
+    // Workers take the next instance from a shared counter, so uneven
+    // instances (e.g. stochastic ones) balance out.
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, Result<ExecutionResult, String>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= parameter_sets.len() {
+                            return done;
+                        }
+                        done.push((index, run_instance(index)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("sweep worker panicked"))
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Whether `inst`, or an instruction nested in it, has a symbolic gate
+/// parameter.
+fn has_symbolic_parameters(inst: &Instruction) -> bool {
+    let mut symbolic = false;
+    inst.walk(&mut |inst| {
+        if let Some(gate) = inst.as_gate() {
+            symbolic |= gate.parameters().iter().any(|p| p.is_symbolic());
+        }
+    });
+    symbolic
 }
 
 impl Default for SimulatorBackend {
@@ -216,69 +597,9 @@ impl Backend for SimulatorBackend {
         shots: u32,
         parameters: Option<&std::collections::HashMap<String, f64>>,
     ) -> HalResult<JobId> {
-        // DEBT-25: reject non-empty parameter bindings (not yet supported).
-        if parameters.is_some_and(|p| !p.is_empty()) {
-            return Err(HalError::Unsupported(
-                "Simulator does not support runtime parameter binding".into(),
-            ));
-        }
-
-        // HAL Contract v2 §3.3 rule 4: validate before dispatching.
-        match self.validate(circuit, shots).await? {
-            ValidationResult::Invalid { reasons } => {
-                return Err(HalError::InvalidCircuit(reasons.join("; ")));
-            }
-            ValidationResult::Valid | ValidationResult::RequiresTranspilation { .. } => {}
-        }
-
-        // Generate job ID
-        let job_id = JobId::new(Uuid::new_v4().to_string());
-
-        // Create job
-        let job = Job::new(job_id.clone(), shots).with_backend("simulator");
-
-        let sim_job = SimJob {
-            job,
-            circuit: circuit.clone(),
-            result: None,
-        };
-
-        // Store job, evicting completed entries if the cache is full.
-        {
-            let mut jobs = self
-                .jobs
-                .lock()
-                .unwrap_or_else(std::sync::PoisonError::into_inner);
-            if jobs.len() >= MAX_CACHED_JOBS {
-                jobs.retain(|_, j| !j.job.status.is_terminal());
-            }
-            jobs.insert(job_id.0.clone(), sim_job);
-        }
-
-        debug!("Submitted job: {}", job_id);
-
-        // Run simulation on a blocking thread to avoid starving the async runtime.
-        let circuit_clone = circuit.clone();
-        let seed = self.seed;
-        let result =
-            tokio::task::spawn_blocking(move || run_simulation_seeded(&circuit_clone, shots, seed))
-                .await
-                .map_err(|e| HalError::Backend(format!("simulation task panicked: {e}")))?
-                .map_err(|e| HalError::Backend(format!("simulation failed: {e}")))?;
-
-        // Update job with result
-        {
-            let mut jobs = self
-                .jobs
-                .lock()
-                .unwrap_or_else(std::sync::PoisonError::into_inner);
-            if let Some(sim_job) = jobs.get_mut(&job_id.0) {
-                sim_job.result = Some(result);
-                sim_job.job = sim_job.job.clone().with_status(JobStatus::Completed);
-            }
-        }
-
-        Ok(job_id)
+        let circuit = bind_parameters(circuit, parameters)?;
+        self.submit_with_initial_state(&circuit, shots, InitialState::Zero)
+            .await
     }
 
     async fn status(&self, job_id: &JobId) -> HalResult<JobStatus> {
@@ -324,6 +645,34 @@ impl Backend for SimulatorBackend {
             Err(HalError::JobNotFound(job_id.0.clone()))
         }
     }
+
+    #[instrument(skip(self, circuit, observables))]
+    async fn submit_observable(
+        &self,
+        circuit: &Circuit,
+        observables: &[Observable],
+        shots: u32,
+    ) -> HalResult<Vec<f64>> {
+        match self.validate(circuit, shots).await? {
+            ValidationResult::Invalid { reasons } => {
+                return Err(HalError::InvalidCircuit(reasons.join("; ")));
+            }
+            ValidationResult::Valid | ValidationResult::RequiresTranspilation { .. } => {}
+        }
+        for observable in observables {
+            observable.validate(circuit.num_qubits())?;
+        }
+
+        let circuit = circuit.clone();
+        let observables = observables.to_vec();
+        let seed = self.seed;
+        tokio::task::spawn_blocking(move || {
+            expectation_values_seeded(&circuit, &observables, shots, seed)
+        })
+        .await
+        .map_err(|e| HalError::Backend(format!("simulation task panicked: {e}")))?
+        .map_err(|e| HalError::Backend(format!("simulation failed: {e}")))
+    }
 }
 
 impl BackendFactory for SimulatorBackend {
@@ -346,7 +695,7 @@ impl BackendFactory for SimulatorBackend {
             .and_then(serde_json::value::Value::as_u64);
 
         Ok(Self {
-            capabilities: Capabilities::simulator(max_qubits),
+            capabilities: simulator_capabilities(max_qubits),
             config,
             jobs: Arc::new(Mutex::new(FxHashMap::default())),
             max_qubits,
@@ -402,6 +751,33 @@ mod tests {
         assert_eq!(counts_a.get("11"), counts_b.get("11"));
     }
 
+    #[test]
+    fn test_noise_channels_affect_counts() {
+        use arvak_ir::QubitId;
+
+        // Depolarizing p = 0.3 after X flips back with probability p/2.
+        let mut circuit = Circuit::with_size("noisy", 1, 1);
+        circuit.x(QubitId(0)).unwrap();
+        circuit
+            .channel_noise(NoiseModel::Depolarizing { p: 0.3 }, QubitId(0))
+            .unwrap();
+        circuit.measure_all().unwrap();
+        let result = run_simulation_seeded(&circuit, 4000, Some(1), &InitialState::Zero).unwrap();
+        let zeros = result.counts.get("0");
+        assert!((500..700).contains(&zeros), "{zeros}");
+
+        // Readout error flips outcomes without touching the state.
+        let mut circuit = Circuit::with_size("readout", 2, 2);
+        circuit
+            .channel_noise(NoiseModel::ReadoutError { p: 0.1 }, QubitId(1))
+            .unwrap();
+        circuit.measure_all().unwrap();
+        let result = run_simulation_seeded(&circuit, 4000, Some(2), &InitialState::Zero).unwrap();
+        let flipped = result.counts.get("10");
+        assert!((320..480).contains(&flipped), "{flipped}");
+        assert_eq!(result.counts.get("10") + result.counts.get("00"), 4000);
+    }
+
     #[tokio::test]
     async fn test_simulator_bell_state() {
         let backend = SimulatorBackend::new();
@@ -435,6 +811,84 @@ mod tests {
         assert!(counts.get("000") + counts.get("111") == 1000);
     }
 
+    #[tokio::test]
+    async fn test_submit_binds_input_parameters() {
+        let backend = SimulatorBackend::new();
+        let mut circuit = Circuit::with_size("ansatz", 1, 1);
+        circuit
+            .rx(
+                arvak_ir::ParameterExpression::symbol("theta"),
+                arvak_ir::QubitId(0),
+            )
+            .unwrap();
+        circuit.measure_all().unwrap();
+
+        let values = std::collections::HashMap::from([("theta".to_string(), std::f64::consts::PI)]);
+        let job_id = backend.submit(&circuit, 100, Some(&values)).await.unwrap();
+        let result = backend.result(&job_id).await.unwrap();
+        assert_eq!(result.counts.get("1"), 100);
+
+        let unbound = backend.submit(&circuit, 100, None).await;
+        assert!(unbound.is_err());
+    }
+
+    #[test]
+    fn test_run_sweep() {
+        let backend = SimulatorBackend::new().with_seed(7);
+        let mut circuit = Circuit::with_size("ansatz", 2, 2);
+        circuit
+            .rx(
+                arvak_ir::ParameterExpression::symbol("theta"),
+                arvak_ir::QubitId(0),
+            )
+            .unwrap()
+            .x(arvak_ir::QubitId(1))
+            .unwrap();
+        circuit.measure_all().unwrap();
+
+        let sets: Vec<HashMap<String, f64>> = (0..16)
+            .map(|i| {
+                HashMap::from([("theta".to_string(), f64::from(i % 2) * std::f64::consts::PI)])
+            })
+            .collect();
+        let results = backend.run_sweep(&circuit, &sets, 100).unwrap();
+
+        assert_eq!(results.len(), 16);
+        for (i, result) in results.iter().enumerate() {
+            let expected = if i % 2 == 0 { "10" } else { "11" };
+            assert_eq!(result.counts.get(expected), 100, "instance {i}");
+        }
+
+        // Same results as binding and running each set on its own.
+        let theta = HashMap::from([("theta".to_string(), 1.0)]);
+        let sweep = run_sweep_seeded(&circuit, std::slice::from_ref(&theta), 500, Some(3)).unwrap();
+        let bound = circuit.bind_parameters(&theta).unwrap();
+        let single = run_simulation_seeded(&bound, 500, Some(3), &InitialState::Zero).unwrap();
+        assert_eq!(
+            sweep[0].counts.probabilities(),
+            single.counts.probabilities()
+        );
+    }
+
+    #[test]
+    fn test_run_sweep_unbound_parameter() {
+        let mut circuit = Circuit::with_size("ansatz", 1, 1);
+        circuit
+            .rz(
+                arvak_ir::ParameterExpression::symbol("theta"),
+                arvak_ir::QubitId(0),
+            )
+            .unwrap();
+
+        let sets = [
+            HashMap::from([("theta".to_string(), 0.5)]),
+            HashMap::from([("phi".to_string(), 0.5)]),
+        ];
+        let err = run_sweep_seeded(&circuit, &sets, 10, None).unwrap_err();
+        assert!(err.contains("parameter set 1"), "{err}");
+        assert!(err.contains("theta"), "{err}");
+    }
+
     #[tokio::test]
     async fn test_simulator_too_many_qubits() {
         let backend = SimulatorBackend::with_max_qubits(5);
@@ -456,6 +910,73 @@ mod tests {
         assert!(matches!(result, ValidationResult::Invalid { .. }));
     }
 
+    #[tokio::test]
+    async fn test_submit_observable_exact() {
+        use arvak_hal::{Pauli, estimate};
+
+        let backend = SimulatorBackend::new();
+        assert!(backend.supports_observables());
+
+        // |Φ+⟩ = (|00⟩ + |11⟩)/√2: ⟨ZZ⟩ = ⟨XX⟩ = 1, ⟨YY⟩ = -1, ⟨Z0⟩ = 0.
+        let mut circuit = Circuit::with_size("bell", 2, 0);
+        circuit.h(arvak_ir::QubitId(0)).unwrap();
+        circuit
+            .cx(arvak_ir::QubitId(0), arvak_ir::QubitId(1))
+            .unwrap();
+        let observables = [
+            Observable::default().with_term(1.0, [(0, Pauli::Z), (1, Pauli::Z)]),
+            Observable::default()
+                .with_term(0.5, [(0, Pauli::X), (1, Pauli::X)])
+                .with_term(0.25, [(0, Pauli::Y), (1, Pauli::Y)]),
+            Observable::default()
+                .with_term(1.0, [(0, Pauli::Z)])
+                .with_term(3.0, []),
+        ];
+
+        let values = estimate(&backend, &circuit, &observables, 100)
+            .await
+            .unwrap();
+        assert!((values[0] - 1.0).abs() < 1e-10);
+        assert!((values[1] - 0.25).abs() < 1e-10);
+        assert!((values[2] - 3.0).abs() < 1e-10);
+    }
+
+    #[tokio::test]
+    async fn test_submit_with_initial_state() {
+        let backend = SimulatorBackend::new();
+
+        // A basis state passes straight through to the measurement.
+        let mut circuit = Circuit::with_size("continue", 2, 2);
+        circuit.measure_all().unwrap();
+        let job_id = backend
+            .submit_with_initial_state(&circuit, 50, InitialState::Basis(0b10))
+            .await
+            .unwrap();
+        assert_eq!(backend.result(&job_id).await.unwrap().counts.get("10"), 50);
+
+        // Continue from |+⟩|0⟩: H undoes the preparation.
+        let h = Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0);
+        let zero = Complex64::new(0.0, 0.0);
+        let plus = InitialState::Statevector(vec![h, h, zero, zero]);
+        let mut circuit = Circuit::with_size("continue", 2, 2);
+        circuit.h(arvak_ir::QubitId(0)).unwrap();
+        circuit.measure_all().unwrap();
+        let result = backend.run_simulation_from(&circuit, 50, &plus).unwrap();
+        assert_eq!(result.counts.get("00"), 50);
+
+        // States that do not fit the circuit are rejected up front.
+        let err = backend
+            .submit_with_initial_state(&circuit, 50, InitialState::Basis(4))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HalError::InvalidCircuit(_)));
+        let err = backend
+            .submit_with_initial_state(&circuit, 50, InitialState::Statevector(vec![h, h]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HalError::InvalidCircuit(_)));
+    }
+
     #[tokio::test]
     async fn test_result_rejects_non_completed_job() {
         let backend = SimulatorBackend::new();
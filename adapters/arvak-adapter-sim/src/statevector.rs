@@ -3,9 +3,11 @@
 use num_complex::Complex64;
 use std::f64::consts::PI;
 
-use arvak_ir::{GateKind, Instruction, InstructionKind, StandardGate};
+use arvak_hal::Pauli;
+use arvak_ir::{GateKind, Instruction, InstructionKind, NoiseModel, QubitId, StandardGate};
 
 /// A statevector representing a quantum state.
+#[derive(Clone)]
 pub struct Statevector {
     /// The state amplitudes (2^n complex numbers).
     amplitudes: Vec<Complex64>,
@@ -30,6 +32,40 @@ impl Statevector {
         }
     }
 
+    /// Create the computational basis state `|index⟩`, where bit `q` of
+    /// `index` is qubit `q`.
+    pub fn basis(num_qubits: usize, index: usize) -> Result<Self, String> {
+        let mut sv = Self::new(num_qubits);
+        if index >= sv.amplitudes.len() {
+            return Err(format!(
+                "basis state {index} does not exist on {num_qubits} qubits"
+            ));
+        }
+        sv.amplitudes.swap(0, index);
+        Ok(sv)
+    }
+
+    /// Create a statevector from explicit amplitudes, indexed like
+    /// [`Statevector::basis`].
+    ///
+    /// Fails unless there are `2^num_qubits` amplitudes of unit norm.
+    pub fn from_amplitudes(num_qubits: usize, amplitudes: Vec<Complex64>) -> Result<Self, String> {
+        let mut sv = Self::new(num_qubits);
+        if amplitudes.len() != sv.amplitudes.len() {
+            return Err(format!(
+                "{num_qubits} qubits need {} amplitudes, got {}",
+                sv.amplitudes.len(),
+                amplitudes.len()
+            ));
+        }
+        let norm: f64 = amplitudes.iter().map(Complex64::norm_sqr).sum();
+        if (norm - 1.0).abs() > 1e-8 {
+            return Err(format!("statevector is not normalized (norm² = {norm})"));
+        }
+        sv.amplitudes = amplitudes;
+        Ok(sv)
+    }
+
     /// Get the number of qubits.
     #[allow(dead_code)]
     pub fn num_qubits(&self) -> usize {
@@ -40,8 +76,9 @@ impl Statevector {
     ///
     /// `rng` is used for the stochastic collapse of `Reset` instructions.
     ///
-    /// Returns an error if a parametric gate has unresolved symbolic parameters
-    /// or if a custom gate is encountered.
+    /// Returns an error if a parametric gate has unresolved symbolic parameters,
+    /// if a custom gate has neither a matrix nor a definition, or for
+    /// control-flow blocks.
     pub fn apply<R: rand::Rng>(
         &mut self,
         instruction: &Instruction,
@@ -57,13 +94,29 @@ impl Statevector {
                 let r: f64 = rng.r#gen();
                 self.reset(qubit, r);
             }
+            InstructionKind::NoiseChannel { model, .. } => {
+                for qubit in &instruction.qubits {
+                    self.apply_noise(model, qubit.0 as usize, rng);
+                }
+            }
             InstructionKind::Measure
             | InstructionKind::Barrier
             | InstructionKind::Delay { .. }
-            | InstructionKind::Shuttle { .. }
-            | InstructionKind::NoiseChannel { .. } => {
+            | InstructionKind::Shuttle { .. } => {
                 // These don't modify the statevector in simulation
             }
+            InstructionKind::IfElse { .. } => {
+                return Err(
+                    "if/else blocks cannot be simulated: mid-circuit measurement is not supported"
+                        .to_string(),
+                );
+            }
+            InstructionKind::WhileLoop { .. } => {
+                return Err(
+                    "while loops cannot be simulated: mid-circuit measurement is not supported"
+                        .to_string(),
+                );
+            }
         }
         Ok(())
     }
@@ -75,10 +128,23 @@ impl Statevector {
                 self.apply_standard_gate(std_gate, qubits)?;
             }
             GateKind::Custom(custom_gate) => {
-                return Err(format!(
-                    "Custom gate '{}' cannot be simulated: matrix multiplication not yet implemented",
-                    custom_gate.name
-                ));
+                let matrix = custom_gate.matrix().map_err(|e| {
+                    format!(
+                        "Custom gate '{}' cannot be simulated: {e}",
+                        custom_gate.name
+                    )
+                })?;
+                self.apply_unitary(&matrix, qubits)?;
+            }
+            GateKind::Modified(modified) => {
+                let ids: Vec<QubitId> = qubits.iter().map(|&q| QubitId(q as u32)).collect();
+                for step in modified.expand(&ids).map_err(|e| e.to_string())? {
+                    if let InstructionKind::Gate(g) = &step.kind {
+                        let step_qubits: Vec<usize> =
+                            step.qubits.iter().map(|q| q.0 as usize).collect();
+                        self.apply_gate(&g.kind, &step_qubits)?;
+                    }
+                }
             }
         }
         Ok(())
@@ -97,8 +163,6 @@ impl Statevector {
             StandardGate::Sdg => self.apply_phase(qubits[0], -PI / 2.0),
             StandardGate::T => self.apply_phase(qubits[0], PI / 4.0),
             StandardGate::Tdg => self.apply_phase(qubits[0], -PI / 4.0),
-            StandardGate::SX => self.apply_sx(qubits[0]),
-            StandardGate::SXdg => self.apply_sxdg(qubits[0]),
             StandardGate::Rx(theta) => {
                 let t = theta
                     .as_f64()
@@ -169,8 +233,72 @@ impl Statevector {
             StandardGate::CCX => self.apply_ccx(qubits[0], qubits[1], qubits[2]),
             StandardGate::CSwap => self.apply_cswap(qubits[0], qubits[1], qubits[2]),
 
+            // Multi-controlled gates: controls first, target last
+            StandardGate::MCX(_) | StandardGate::MCZ(_) | StandardGate::MCP(_, _) => {
+                let (&target, controls) = qubits
+                    .split_last()
+                    .ok_or_else(|| format!("{} gate has no qubits", gate.name()))?;
+                match gate {
+                    StandardGate::MCX(_) => self.apply_mcx(controls, target),
+                    StandardGate::MCZ(_) => self.apply_mcp(controls, target, std::f64::consts::PI),
+                    StandardGate::MCP(_, theta) => {
+                        let t = theta.as_f64().ok_or_else(|| {
+                            "MCP gate has unresolved symbolic parameter".to_string()
+                        })?;
+                        self.apply_mcp(controls, target, t);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            // Gates without a dedicated kernel use their dense matrix.
             _ => {
-                return Err(format!("Unhandled gate type in simulation: {:?}", gate));
+                let matrix = gate
+                    .matrix()
+                    .map_err(|e| format!("{} gate cannot be simulated: {e}", gate.name()))?;
+                self.apply_unitary(&matrix, qubits)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a dense unitary to `qubits`.
+    ///
+    /// The matrix is row-major with the first of `qubits` as the most
+    /// significant bit of its index, the convention of `arvak_ir` gate
+    /// matrices.
+    fn apply_unitary(&mut self, matrix: &[Complex64], qubits: &[usize]) -> Result<(), String> {
+        let k = qubits.len();
+        let dim = 1 << k;
+        if matrix.len() != dim * dim {
+            return Err(format!(
+                "{k}-qubit gate needs a {dim}x{dim} matrix, got {} entries",
+                matrix.len()
+            ));
+        }
+
+        // Offset of each local basis state in the full index.
+        let offsets: Vec<usize> = (0..dim)
+            .map(|local| {
+                qubits
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| (local >> (k - 1 - i)) & 1 == 1)
+                    .fold(0, |offset, (_, q)| offset | (1 << q))
+            })
+            .collect();
+        let mask = offsets[dim - 1];
+
+        let mut local = vec![Complex64::new(0.0, 0.0); dim];
+        for base in 0..self.amplitudes.len() {
+            if base & mask != 0 {
+                continue;
+            }
+            for (amp, offset) in local.iter_mut().zip(&offsets) {
+                *amp = self.amplitudes[base | offset];
+            }
+            for (row, offset) in matrix.chunks_exact(dim).zip(&offsets) {
+                self.amplitudes[base | offset] = row.iter().zip(&local).map(|(m, a)| m * a).sum();
             }
         }
         Ok(())
@@ -306,14 +434,6 @@ impl Statevector {
         self.apply_rz(qubit, phi);
     }
 
-    fn apply_sx(&mut self, qubit: usize) {
-        self.apply_rx(qubit, PI / 2.0);
-    }
-
-    fn apply_sxdg(&mut self, qubit: usize) {
-        self.apply_rx(qubit, -PI / 2.0);
-    }
-
     // =========================================================================
     // Two-qubit gate implementations
     // =========================================================================
@@ -456,6 +576,31 @@ impl Statevector {
         }
     }
 
+    // =========================================================================
+    // Multi-controlled gate implementations
+    // =========================================================================
+
+    fn apply_mcx(&mut self, controls: &[usize], target: usize) {
+        let ctrl_mask = controls.iter().fold(0, |mask, c| mask | (1 << c));
+        let tgt_mask = 1 << target;
+        for i in 0..(1 << self.num_qubits) {
+            if (i & ctrl_mask == ctrl_mask) && (i & tgt_mask == 0) {
+                let j = i | tgt_mask;
+                self.amplitudes.swap(i, j);
+            }
+        }
+    }
+
+    fn apply_mcp(&mut self, controls: &[usize], target: usize, theta: f64) {
+        let mask = controls.iter().fold(1 << target, |mask, c| mask | (1 << c));
+        let phase = Complex64::from_polar(1.0, theta);
+        for i in 0..(1 << self.num_qubits) {
+            if i & mask == mask {
+                self.amplitudes[i] *= phase;
+            }
+        }
+    }
+
     /// Reset a qubit to |0⟩ via stochastic projective measurement.
     ///
     /// `r` is a uniform random sample in [0, 1) selecting the measurement
@@ -467,14 +612,7 @@ impl Statevector {
     /// qubit in |−⟩ annihilated the entire statevector.
     fn reset(&mut self, qubit: usize, r: f64) {
         let mask = 1 << qubit;
-
-        // Probability of measuring |1⟩ on this qubit.
-        let mut p1 = 0.0;
-        for i in 0..(1 << self.num_qubits) {
-            if i & mask != 0 {
-                p1 += self.amplitudes[i].norm_sqr();
-            }
-        }
+        let p1 = self.prob_one(qubit);
 
         let outcome_one = r < p1;
         let p_branch = if outcome_one { p1 } else { 1.0 - p1 };
@@ -501,6 +639,67 @@ impl Statevector {
         }
     }
 
+    /// Probability of measuring |1⟩ on `qubit`.
+    fn prob_one(&self, qubit: usize) -> f64 {
+        let mask = 1 << qubit;
+        self.amplitudes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i & mask != 0)
+            .map(|(_, amp)| amp.norm_sqr())
+            .sum()
+    }
+
+    /// Apply one stochastic trajectory of a noise channel to `qubit`.
+    ///
+    /// Pauli channels (depolarizing, bit flip, phase flip, phase damping)
+    /// apply a random Pauli; amplitude damping either jumps to |0⟩ or
+    /// applies the no-jump Kraus operator, each with its Born probability.
+    /// Averaged over trajectories this reproduces the channel. Readout
+    /// errors act on measurement outcomes, not the state, and custom models
+    /// have no simulation semantics; both are no-ops here.
+    fn apply_noise<R: rand::Rng>(&mut self, model: &NoiseModel, qubit: usize, rng: &mut R) {
+        let r: f64 = rng.r#gen();
+        match model {
+            // With probability p the state is replaced by the maximally
+            // mixed state, i.e. a uniformly random Pauli (including I).
+            NoiseModel::Depolarizing { p } if r < *p => match rng.gen_range(0..4) {
+                1 => self.apply_x(qubit),
+                2 => self.apply_y(qubit),
+                3 => self.apply_z(qubit),
+                _ => {}
+            },
+            NoiseModel::BitFlip { p } if r < *p => self.apply_x(qubit),
+            NoiseModel::PhaseFlip { p } if r < *p => self.apply_z(qubit),
+            // Phase damping is a phase flip with p = (1 - √(1-γ)) / 2.
+            NoiseModel::PhaseDamping { gamma } if r < (1.0 - (1.0 - gamma).sqrt()) / 2.0 => {
+                self.apply_z(qubit);
+            }
+            NoiseModel::AmplitudeDamping { gamma } => {
+                let mask = 1 << qubit;
+                let p_jump = gamma * self.prob_one(qubit);
+                if r < p_jump {
+                    // K1 = √γ |0⟩⟨1|
+                    let scale = 1.0 / p_jump.sqrt();
+                    for i in 0..self.amplitudes.len() {
+                        if i & mask != 0 {
+                            self.amplitudes[i & !mask] = self.amplitudes[i] * gamma.sqrt() * scale;
+                            self.amplitudes[i] = Complex64::new(0.0, 0.0);
+                        }
+                    }
+                } else {
+                    // K0 = |0⟩⟨0| + √(1-γ) |1⟩⟨1|
+                    let scale = 1.0 / (1.0 - p_jump).sqrt();
+                    let damp = (1.0 - gamma).sqrt();
+                    for (i, amp) in self.amplitudes.iter_mut().enumerate() {
+                        *amp *= if i & mask != 0 { damp * scale } else { scale };
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Sample a measurement outcome.
     pub fn sample<R: rand::Rng>(&self, rng: &mut R) -> usize {
         let r: f64 = rng.r#gen();
@@ -543,6 +742,39 @@ impl Statevector {
         counts
     }
 
+    /// Expectation value ⟨ψ|P|ψ⟩ of a Pauli string.
+    ///
+    /// P maps basis state |i⟩ to phase(i)·|i ⊕ flip⟩, where `flip` marks the
+    /// X and Y qubits, so ⟨ψ|P|ψ⟩ = Σᵢ conj(ψ[i ⊕ flip])·phase(i)·ψ[i].
+    pub fn pauli_expectation(&self, paulis: &[(u32, Pauli)]) -> f64 {
+        let mut flip = 0usize;
+        for &(qubit, pauli) in paulis {
+            if matches!(pauli, Pauli::X | Pauli::Y) {
+                flip |= 1 << qubit;
+            }
+        }
+
+        let mut sum = Complex64::new(0.0, 0.0);
+        for (i, amp) in self.amplitudes.iter().enumerate() {
+            let mut phase = Complex64::new(1.0, 0.0);
+            for &(qubit, pauli) in paulis {
+                let one = (i >> qubit) & 1 == 1;
+                match pauli {
+                    Pauli::X => {}
+                    // Y|0⟩ = i|1⟩, Y|1⟩ = -i|0⟩
+                    Pauli::Y => phase *= if one { -Complex64::i() } else { Complex64::i() },
+                    Pauli::Z => {
+                        if one {
+                            phase = -phase;
+                        }
+                    }
+                }
+            }
+            sum += self.amplitudes[i ^ flip].conj() * phase * amp;
+        }
+        sum.re
+    }
+
     /// Convert measurement outcome to bitstring.
     ///
     /// HAL Contract bit order: the rightmost character is qubit 0 (OpenQASM 3
@@ -603,6 +835,170 @@ mod tests {
         assert!(approx_eq(sv.amplitudes[1], Complex64::new(1.0, 0.0)));
     }
 
+    #[test]
+    fn test_multi_controlled_gates() {
+        // |1110⟩ (q0..q2 set): MCX on q3 flips the target, MCZ then adds a
+        // sign only once the target is set.
+        let mut sv = Statevector::new(4);
+        sv.apply_x(0);
+        sv.apply_x(1);
+        sv.apply_mcx(&[0, 1], 2);
+        assert!(approx_eq(sv.amplitudes[0b0111], Complex64::new(1.0, 0.0)));
+
+        sv.apply_mcp(&[0, 1, 2], 3, std::f64::consts::PI);
+        assert!(approx_eq(sv.amplitudes[0b0111], Complex64::new(1.0, 0.0)));
+        sv.apply_mcx(&[0, 1, 2], 3);
+        sv.apply_mcp(&[0, 1, 2], 3, std::f64::consts::PI);
+        assert!(approx_eq(sv.amplitudes[0b1111], Complex64::new(-1.0, 0.0)));
+
+        // A missing control leaves the state untouched.
+        sv.apply_mcx(&[0, 1, 2], 3);
+        sv.apply_x(1);
+        sv.apply_mcx(&[0, 1, 2], 3);
+        assert!(approx_eq(sv.amplitudes[0b0101], Complex64::new(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_modified_gate() {
+        // ctrl @ h acts only once the control is set.
+        let gate = arvak_ir::Gate::standard(StandardGate::H).controlled(1).kind;
+        let mut sv = Statevector::new(2);
+        sv.apply_gate(&gate, &[0, 1]).unwrap();
+        assert!(approx_eq(sv.amplitudes[0], Complex64::new(1.0, 0.0)));
+
+        sv.apply_x(0);
+        sv.apply_gate(&gate, &[0, 1]).unwrap();
+        let sqrt2_inv = 1.0 / 2.0_f64.sqrt();
+        assert!(approx_eq(
+            sv.amplitudes[0b01],
+            Complex64::new(sqrt2_inv, 0.0)
+        ));
+        assert!(approx_eq(
+            sv.amplitudes[0b11],
+            Complex64::new(sqrt2_inv, 0.0)
+        ));
+    }
+
+    #[test]
+    fn test_custom_unitary_gate() {
+        use arvak_ir::CustomGate;
+
+        let zero = Complex64::new(0.0, 0.0);
+        let one = Complex64::new(1.0, 0.0);
+        // CX as a custom 2-qubit matrix, first qubit the control.
+        let cx = CustomGate::new("my_cx", 2).with_matrix(vec![
+            one, zero, zero, zero, //
+            zero, one, zero, zero, //
+            zero, zero, zero, one, //
+            zero, zero, one, zero,
+        ]);
+        let gate = GateKind::Custom(cx);
+
+        let mut sv = Statevector::basis(3, 0b100).unwrap();
+        sv.apply_gate(&gate, &[2, 0]).unwrap();
+        assert!(approx_eq(sv.amplitudes[0b101], one));
+        sv.apply_gate(&gate, &[0, 2]).unwrap();
+        assert!(approx_eq(sv.amplitudes[0b001], one));
+
+        // Gates without a dedicated kernel fall back to their matrix.
+        sv.apply_gate(&GateKind::Standard(StandardGate::ECR), &[0, 1])
+            .unwrap();
+        let expected = StandardGate::ECR.matrix().unwrap();
+        // Input |q0=1, q1=0⟩ is local index 0b10; column 2 of the matrix.
+        assert!(approx_eq(sv.amplitudes[0b001], expected[4 * 2 + 2]));
+        assert!(approx_eq(sv.amplitudes[0b011], expected[4 * 3 + 2]));
+
+        let opaque = GateKind::Custom(CustomGate::new("opaque", 1));
+        assert!(sv.apply_gate(&opaque, &[0]).is_err());
+    }
+
+    #[test]
+    fn test_kernels_match_gate_matrices() {
+        // Every dedicated kernel must agree with the gate's matrix in arvak-ir.
+        let t = || arvak_ir::ParameterExpression::constant(0.7);
+        let gates = [
+            StandardGate::I,
+            StandardGate::X,
+            StandardGate::Y,
+            StandardGate::Z,
+            StandardGate::H,
+            StandardGate::S,
+            StandardGate::Sdg,
+            StandardGate::T,
+            StandardGate::Tdg,
+            StandardGate::SX,
+            StandardGate::SXdg,
+            StandardGate::Rx(t()),
+            StandardGate::Ry(t()),
+            StandardGate::Rz(t()),
+            StandardGate::P(t()),
+            StandardGate::U(t(), t(), t()),
+            StandardGate::PRX(t(), t()),
+            StandardGate::CX,
+            StandardGate::CY,
+            StandardGate::CZ,
+            StandardGate::CH,
+            StandardGate::CRx(t()),
+            StandardGate::CRy(t()),
+            StandardGate::CRz(t()),
+            StandardGate::CP(t()),
+            StandardGate::Swap,
+            StandardGate::ISwap,
+            StandardGate::RXX(t()),
+            StandardGate::RYY(t()),
+            StandardGate::RZZ(t()),
+            StandardGate::ECR,
+            StandardGate::CCX,
+            StandardGate::CSwap,
+            StandardGate::MCX(3),
+            StandardGate::MCZ(3),
+            StandardGate::MCP(3, t()),
+        ];
+        for gate in gates {
+            let k = gate.num_qubits() as usize;
+            let m = 1 << k;
+            let matrix = gate.matrix().unwrap();
+            // Rotated qubit order, so local bit k-1-j is global qubit (j+1) % k.
+            let qubits: Vec<usize> = (0..k).map(|j| (j + 1) % k).collect();
+            let global = |local: usize| {
+                (0..k)
+                    .filter(|j| local & (1 << (k - 1 - j)) != 0)
+                    .fold(0, |acc, j| acc | (1 << qubits[j]))
+            };
+            for col in 0..m {
+                let mut sv = Statevector::basis(k, global(col)).unwrap();
+                sv.apply_gate(&GateKind::Standard(gate.clone()), &qubits)
+                    .unwrap();
+                for row in 0..m {
+                    assert!(
+                        approx_eq(sv.amplitudes[global(row)], matrix[row * m + col]),
+                        "{} entry ({row}, {col})",
+                        gate.name()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_states() {
+        let sv = Statevector::basis(2, 0b10).unwrap();
+        assert!(approx_eq(sv.amplitudes[0b10], Complex64::new(1.0, 0.0)));
+        assert_eq!(
+            sv.outcome_to_bitstring(sv.sample(&mut rand::thread_rng())),
+            "10"
+        );
+        assert!(Statevector::basis(2, 4).is_err());
+
+        let h = Complex64::new(1.0 / 2.0_f64.sqrt(), 0.0);
+        let zero = Complex64::new(0.0, 0.0);
+        let mut sv = Statevector::from_amplitudes(1, vec![h, h]).unwrap();
+        sv.apply_h(0);
+        assert!(approx_eq(sv.amplitudes[0], Complex64::new(1.0, 0.0)));
+        assert!(Statevector::from_amplitudes(1, vec![h, zero]).is_err());
+        assert!(Statevector::from_amplitudes(2, vec![h, h]).is_err());
+    }
+
     #[test]
     fn test_sample_deterministic() {
         // |1⟩ state should always sample to 1
@@ -615,6 +1011,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_noise_channels() {
+        use arvak_ir::NoiseRole;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let channel = |model| Instruction::noise_channel(model, NoiseRole::Deficit, QubitId(0));
+
+        // A certain bit flip always flips; a certain phase flip never does.
+        let mut sv = Statevector::new(1);
+        sv.apply(&channel(NoiseModel::BitFlip { p: 1.0 }), &mut rng)
+            .unwrap();
+        sv.apply(&channel(NoiseModel::PhaseFlip { p: 1.0 }), &mut rng)
+            .unwrap();
+        assert!((sv.amplitudes[1].norm() - 1.0).abs() < 1e-12);
+
+        // Full amplitude damping relaxes |1⟩ to |0⟩.
+        sv.apply(
+            &channel(NoiseModel::AmplitudeDamping { gamma: 1.0 }),
+            &mut rng,
+        )
+        .unwrap();
+        assert!((sv.amplitudes[0].norm() - 1.0).abs() < 1e-12);
+
+        // Full depolarization flips |0⟩ in half of the trajectories.
+        let flipped = (0..4000)
+            .filter(|_| {
+                let mut sv = Statevector::new(1);
+                sv.apply(&channel(NoiseModel::Depolarizing { p: 1.0 }), &mut rng)
+                    .unwrap();
+                sv.prob_one(0) > 0.5
+            })
+            .count();
+        assert!((1800..2200).contains(&flipped), "{flipped}");
+
+        // Partial amplitude damping keeps the state normalized.
+        let mut sv = Statevector::new(1);
+        sv.apply_h(0);
+        sv.apply(
+            &channel(NoiseModel::AmplitudeDamping { gamma: 0.3 }),
+            &mut rng,
+        )
+        .unwrap();
+        let norm: f64 = sv.amplitudes.iter().map(Complex64::norm_sqr).sum();
+        assert!((norm - 1.0).abs() < 1e-12);
+    }
+
     #[test]
     fn test_reset_minus_state() {
         // Reset of |−⟩ = (|0⟩−|1⟩)/√2 must yield a normalized |0⟩ state
@@ -163,8 +163,11 @@ pub fn find_reversible_ops(circuit: &Circuit) -> Vec<usize> {
             }
             InstructionKind::Measure
             | InstructionKind::Reset
-            | InstructionKind::NoiseChannel { .. } => {
-                // Non-reversible operations / non-unitary annotations
+            | InstructionKind::NoiseChannel { .. }
+            | InstructionKind::IfElse { .. }
+            | InstructionKind::WhileLoop { .. } => {
+                // Non-reversible operations / non-unitary annotations /
+                // measurement-dependent branches
             }
             InstructionKind::Shuttle { .. } => {
                 // Shuttling is reversible (swap zones)
@@ -2,7 +2,6 @@
 
 use arvak_ir::gate::{GateKind, StandardGate};
 use arvak_ir::instruction::{Instruction, InstructionKind};
-use arvak_ir::parameter::ParameterExpression;
 
 use crate::error::{UncomputeError, UncomputeResult};
 
@@ -30,80 +29,15 @@ pub enum InverseStrategy {
 /// - T† = Tdg
 /// - Rx(θ)† = Rx(-θ)
 pub fn inverse_gate(gate: &StandardGate) -> UncomputeResult<StandardGate> {
-    match gate {
-        // Self-inverse gates (Hermitian)
-        StandardGate::I => Ok(StandardGate::I),
-        StandardGate::X => Ok(StandardGate::X),
-        StandardGate::Y => Ok(StandardGate::Y),
-        StandardGate::Z => Ok(StandardGate::Z),
-        StandardGate::H => Ok(StandardGate::H),
-        StandardGate::CX => Ok(StandardGate::CX),
-        StandardGate::CY => Ok(StandardGate::CY),
-        StandardGate::CZ => Ok(StandardGate::CZ),
-        StandardGate::Swap => Ok(StandardGate::Swap),
-        StandardGate::CCX => Ok(StandardGate::CCX),
-        StandardGate::CSwap => Ok(StandardGate::CSwap),
-
-        // S and T gates
-        StandardGate::S => Ok(StandardGate::Sdg),
-        StandardGate::Sdg => Ok(StandardGate::S),
-        StandardGate::T => Ok(StandardGate::Tdg),
-        StandardGate::Tdg => Ok(StandardGate::T),
-
-        // SX gates
-        StandardGate::SX => Ok(StandardGate::SXdg),
-        StandardGate::SXdg => Ok(StandardGate::SX),
-
-        // Rotation gates: negate the angle
-        StandardGate::Rx(theta) => Ok(StandardGate::Rx(negate_param(theta))),
-        StandardGate::Ry(theta) => Ok(StandardGate::Ry(negate_param(theta))),
-        StandardGate::Rz(theta) => Ok(StandardGate::Rz(negate_param(theta))),
-        StandardGate::P(lambda) => Ok(StandardGate::P(negate_param(lambda))),
-
-        // U gate: U(θ, φ, λ)† = U(-θ, -λ, -φ)
-        StandardGate::U(theta, phi, lambda) => Ok(StandardGate::U(
-            negate_param(theta),
-            negate_param(lambda),
-            negate_param(phi),
-        )),
-
-        // Controlled rotations: negate the angle
-        StandardGate::CRx(theta) => Ok(StandardGate::CRx(negate_param(theta))),
-        StandardGate::CRy(theta) => Ok(StandardGate::CRy(negate_param(theta))),
-        StandardGate::CRz(theta) => Ok(StandardGate::CRz(negate_param(theta))),
-        StandardGate::CP(lambda) => Ok(StandardGate::CP(negate_param(lambda))),
-
-        // Two-qubit rotations: negate the angle
-        StandardGate::RXX(theta) => Ok(StandardGate::RXX(negate_param(theta))),
-        StandardGate::RYY(theta) => Ok(StandardGate::RYY(negate_param(theta))),
-        StandardGate::RZZ(theta) => Ok(StandardGate::RZZ(negate_param(theta))),
-
-        // iSWAP is not self-inverse: iSWAP† ≠ iSWAP.
-        // iSWAP† has conjugated off-diagonal elements (−i instead of i).
-        // A full inverse requires decomposition into basis gates.
-        // TODO: implement ISwap† decomposition (e.g., two CX + Rz sequence).
-        StandardGate::ISwap => Err(UncomputeError::InversionNotImplemented("iswap".into())),
-
-        // CH (controlled-Hadamard) is self-inverse
-        StandardGate::CH => Ok(StandardGate::CH),
-
-        // PRX gate: PRX(θ, φ)† = PRX(-θ, φ)
-        StandardGate::PRX(theta, phi) => Ok(StandardGate::PRX(negate_param(theta), phi.clone())),
-
-        // ECR is self-inverse: ECR† = ECR (Hermitian unitary).
-        StandardGate::ECR => Ok(StandardGate::ECR),
-    }
-}
-
-/// Negate a parameter expression.
-fn negate_param(param: &ParameterExpression) -> ParameterExpression {
-    -param.clone()
+    // The table lives in arvak-ir; only iSWAP lacks a standard-gate inverse.
+    gate.inverse()
+        .ok_or_else(|| UncomputeError::InversionNotImplemented(gate.name().into()))
 }
 
 /// Compute the inverse of an instruction.
 ///
 /// For gate instructions, this inverts the gate.
-/// For non-unitary operations (measure, reset), this returns an error.
+/// For non-unitary operations (measure, reset, if/else), this returns an error.
 pub fn inverse_instruction(instruction: &Instruction) -> UncomputeResult<Instruction> {
     match &instruction.kind {
         InstructionKind::Gate(gate) => {
@@ -113,6 +47,7 @@ pub fn inverse_instruction(instruction: &Instruction) -> UncomputeResult<Instruc
                     // Custom gates cannot be automatically inverted without their definition
                     return Err(UncomputeError::NonInvertibleGate(custom.name.clone()));
                 }
+                GateKind::Modified(modified) => GateKind::Modified(modified.inverse()),
             };
 
             Ok(Instruction {
@@ -120,9 +55,11 @@ pub fn inverse_instruction(instruction: &Instruction) -> UncomputeResult<Instruc
                     kind: inverse_gate_kind,
                     label: gate.label.clone(),
                     condition: gate.condition.clone(),
+                    duration: gate.duration,
                 }),
                 qubits: instruction.qubits.clone(),
                 clbits: instruction.clbits.clone(),
+                metadata: instruction.metadata.clone(),
             })
         }
 
@@ -149,12 +86,21 @@ pub fn inverse_instruction(instruction: &Instruction) -> UncomputeResult<Instruc
                 },
                 qubits: instruction.qubits.clone(),
                 clbits: instruction.clbits.clone(),
+                metadata: instruction.metadata.clone(),
             })
         }
 
         InstructionKind::NoiseChannel { .. } => {
             Err(UncomputeError::NonUnitaryOperation("noise_channel".into()))
         }
+
+        InstructionKind::IfElse { .. } => {
+            Err(UncomputeError::NonUnitaryOperation("if_else".into()))
+        }
+
+        InstructionKind::WhileLoop { .. } => {
+            Err(UncomputeError::NonUnitaryOperation("while_loop".into()))
+        }
     }
 }
 
@@ -173,6 +119,8 @@ pub fn is_self_inverse(gate: &StandardGate) -> bool {
             | StandardGate::Swap
             | StandardGate::CCX
             | StandardGate::CSwap
+            | StandardGate::MCX(_)
+            | StandardGate::MCZ(_)
             | StandardGate::CH
             | StandardGate::ECR
     )
@@ -181,6 +129,7 @@ pub fn is_self_inverse(gate: &StandardGate) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arvak_ir::parameter::ParameterExpression;
     use std::f64::consts::PI;
 
     #[test]
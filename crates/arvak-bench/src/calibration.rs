@@ -0,0 +1,362 @@
+//! Noise model calibration from hardware benchmark results.
+//!
+//! Fits a [`NoiseProfile`] to randomized-benchmarking or mirror-circuit
+//! survival curves measured on a real backend. Running the fitted profile
+//! through [`NoiseInjectionPass`](arvak_compile::NoiseInjectionPass) and
+//! the simulator reproduces the observed decay, so local noisy simulation
+//! tracks the device.
+//!
+//! The model matches what noise injection produces: every occurrence of a
+//! gate depolarizes each qubit it acts on with probability `p`, and each
+//! measured qubit flips its outcome with probability `r`. Under that model
+//! the probability that a qubit returns to |0⟩ after `m` sequence steps is
+//!
+//! ```text
+//! P(m) = 1/2 + (1/2 - r) · f^m,    f = (1 - p)^g
+//! ```
+//!
+//! where `g` is the number of occurrences of the gate on the qubit per step.
+//! Fitting `A · f^m + 1/2` gives `p = 1 - f^(1/g)` and `r = 1/2 - A`.
+//! State-preparation errors are indistinguishable from readout errors here
+//! and are attributed to readout.
+//!
+//! Calibrations drift; [`blend_profiles`] folds a new fit into a running
+//! profile with an exponential moving average.
+
+use std::collections::BTreeMap;
+
+use arvak_ir::noise::NoiseProfile;
+use serde::{Deserialize, Serialize};
+
+use crate::rb::fit_rb_decay;
+
+/// Errors from noise model calibration.
+#[derive(Debug, thiserror::Error)]
+pub enum CalibrationError {
+    /// No decay curves were given.
+    #[error("No decay curves to fit")]
+    NoData,
+
+    /// A curve has too few points for an exponential fit.
+    #[error("Decay curve for '{gate}' on qubit {qubit} has {points} points, need at least 3")]
+    TooFewPoints {
+        /// Gate the curve characterises.
+        gate: String,
+        /// Qubit the curve was measured on.
+        qubit: u32,
+        /// Number of points supplied.
+        points: usize,
+    },
+
+    /// A curve's parameters are out of range.
+    #[error("Invalid decay curve for '{gate}' on qubit {qubit}: {reason}")]
+    InvalidCurve {
+        /// Gate the curve characterises.
+        gate: String,
+        /// Qubit the curve was measured on.
+        qubit: u32,
+        /// What is wrong with it.
+        reason: String,
+    },
+}
+
+/// Measured survival probabilities of one qubit over sequence length.
+///
+/// For a two-qubit gate, supply one curve per qubit; the fitted error rates
+/// are averaged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecayCurve {
+    /// Gate whose error rate the curve characterises (e.g. `"cz"`).
+    pub gate: String,
+    /// Physical qubit the survival probability was measured on.
+    pub qubit: u32,
+    /// Occurrences of `gate` on `qubit` per sequence step, e.g. the average
+    /// number of native gates per Clifford. Errors of other gates in the
+    /// sequence are attributed to `gate`.
+    pub gates_per_step: f64,
+    /// `(sequence length, probability of measuring 0)` pairs.
+    pub points: Vec<(u32, f64)>,
+}
+
+impl DecayCurve {
+    /// Create a curve with one gate occurrence per sequence step.
+    pub fn new(gate: impl Into<String>, qubit: u32, points: Vec<(u32, f64)>) -> Self {
+        Self {
+            gate: gate.into(),
+            qubit,
+            gates_per_step: 1.0,
+            points,
+        }
+    }
+
+    /// Set the number of gate occurrences per sequence step.
+    #[must_use]
+    pub fn with_gates_per_step(mut self, gates_per_step: f64) -> Self {
+        self.gates_per_step = gates_per_step;
+        self
+    }
+
+    fn invalid(&self, reason: impl Into<String>) -> CalibrationError {
+        CalibrationError::InvalidCurve {
+            gate: self.gate.clone(),
+            qubit: self.qubit,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Fitted parameters of a single [`DecayCurve`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurveFit {
+    /// Gate the curve characterises.
+    pub gate: String,
+    /// Qubit the curve was measured on.
+    pub qubit: u32,
+    /// Decay per sequence step `f`.
+    pub decay: f64,
+    /// Depolarizing probability per gate occurrence.
+    pub gate_error: f64,
+    /// Readout (and preparation) flip probability.
+    pub readout_error: f64,
+}
+
+/// Fit one decay curve.
+pub fn fit_curve(curve: &DecayCurve) -> Result<CurveFit, CalibrationError> {
+    if curve.points.len() < 3 {
+        return Err(CalibrationError::TooFewPoints {
+            gate: curve.gate.clone(),
+            qubit: curve.qubit,
+            points: curve.points.len(),
+        });
+    }
+    if !curve.gates_per_step.is_finite() || curve.gates_per_step <= 0.0 {
+        return Err(curve.invalid("gates_per_step must be positive and finite"));
+    }
+    if curve.points.iter().any(|&(_, p)| !(0.0..=1.0).contains(&p)) {
+        return Err(curve.invalid("survival probabilities must lie in [0, 1]"));
+    }
+    if curve.points.iter().filter(|&&(_, p)| p > 0.501).count() < 2 {
+        return Err(curve.invalid("curve has decayed to 1/2, nothing to fit"));
+    }
+
+    let (amplitude, decay, _) = fit_rb_decay(&curve.points);
+    let gate_error = (1.0 - decay.powf(1.0 / curve.gates_per_step)).clamp(0.0, 1.0);
+    let readout_error = (0.5 - amplitude).clamp(0.0, 0.5);
+
+    Ok(CurveFit {
+        gate: curve.gate.clone(),
+        qubit: curve.qubit,
+        decay,
+        gate_error,
+        readout_error,
+    })
+}
+
+/// Fit a noise profile to a set of decay curves.
+///
+/// Gate error rates are averaged over every curve for the same gate, and
+/// readout errors over every curve on the same qubit. Qubits without a
+/// curve get a readout error of zero.
+pub fn fit_noise_profile(curves: &[DecayCurve]) -> Result<NoiseProfile, CalibrationError> {
+    if curves.is_empty() {
+        return Err(CalibrationError::NoData);
+    }
+    let fits = curves
+        .iter()
+        .map(fit_curve)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut gate_errors: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+    let mut readout: BTreeMap<u32, (f64, usize)> = BTreeMap::new();
+    for fit in &fits {
+        let gate = gate_errors.entry(fit.gate.clone()).or_default();
+        gate.0 += fit.gate_error;
+        gate.1 += 1;
+        let qubit = readout.entry(fit.qubit).or_default();
+        qubit.0 += fit.readout_error;
+        qubit.1 += 1;
+    }
+
+    let num_qubits = readout.keys().max().map_or(0, |&q| q as usize + 1);
+    let mut readout_errors = vec![0.0; num_qubits];
+    for (qubit, (sum, n)) in readout {
+        readout_errors[qubit as usize] = sum / n as f64;
+    }
+
+    let mut profile = NoiseProfile::new();
+    profile.gate_errors = gate_errors
+        .into_iter()
+        .map(|(gate, (sum, n))| (gate, sum / n as f64))
+        .collect();
+    profile.readout_errors = Some(readout_errors);
+    Ok(profile)
+}
+
+/// Fold a fresh calibration into a running profile.
+///
+/// Each gate and readout error becomes `(1 - weight) · previous + weight ·
+/// update`. Entries present in only one profile are taken as they are.
+/// Coherence times, coupler errors and the fingerprint come from `update`
+/// when it has them.
+pub fn blend_profiles(previous: &NoiseProfile, update: &NoiseProfile, weight: f64) -> NoiseProfile {
+    let weight = weight.clamp(0.0, 1.0);
+    let mix = |old: f64, new: f64| (1.0 - weight) * old + weight * new;
+
+    let mut gate_errors = previous.gate_errors.clone();
+    for (gate, &rate) in &update.gate_errors {
+        gate_errors
+            .entry(gate.clone())
+            .and_modify(|old| *old = mix(*old, rate))
+            .or_insert(rate);
+    }
+
+    let readout_errors = match (&previous.readout_errors, &update.readout_errors) {
+        (Some(old), Some(new)) => Some(
+            (0..old.len().max(new.len()))
+                .map(|q| match (old.get(q), new.get(q)) {
+                    (Some(&o), Some(&n)) => mix(o, n),
+                    (Some(&r), None) | (None, Some(&r)) => r,
+                    (None, None) => unreachable!(),
+                })
+                .collect(),
+        ),
+        (old, new) => new.clone().or_else(|| old.clone()),
+    };
+
+    NoiseProfile {
+        gate_errors,
+        t1: update.t1.clone().or_else(|| previous.t1.clone()),
+        t2: update.t2.clone().or_else(|| previous.t2.clone()),
+        readout_errors,
+        coupler_errors: update
+            .coupler_errors
+            .clone()
+            .or_else(|| previous.coupler_errors.clone()),
+        fingerprint: update
+            .fingerprint
+            .clone()
+            .or_else(|| previous.fingerprint.clone()),
+    }
+}
+
+/// Build a single-qubit mirror circuit: `2 · steps` X gates on qubit 0
+/// followed by a measurement. Ideally it always returns |0⟩.
+pub fn generate_mirror_circuit(steps: u32) -> arvak_ir::Circuit {
+    use arvak_ir::{Circuit, ClbitId, QubitId};
+
+    let mut circuit = Circuit::with_size(format!("mirror_{steps}"), 1, 1);
+    for _ in 0..2 * steps {
+        let _ = circuit.x(QubitId(0));
+    }
+    let _ = circuit.measure(QubitId(0), ClbitId(0));
+    circuit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_adapter_sim::SimulatorBackend;
+    use arvak_compile::{NoiseInjectionPass, Pass, PropertySet};
+    use arvak_ir::Circuit;
+
+    fn ideal_curve(p: f64, r: f64, gates_per_step: f64) -> Vec<(u32, f64)> {
+        [1, 2, 4, 8, 16, 32]
+            .into_iter()
+            .map(|m| {
+                let f = (1.0 - p).powf(gates_per_step);
+                (m, 0.5 + (0.5 - r) * f.powi(m as i32))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_curve_recovers_parameters() {
+        let curve =
+            DecayCurve::new("prx", 0, ideal_curve(0.004, 0.02, 1.875)).with_gates_per_step(1.875);
+        let fit = fit_curve(&curve).unwrap();
+        assert!((fit.gate_error - 0.004).abs() < 1e-9, "{fit:?}");
+        assert!((fit.readout_error - 0.02).abs() < 1e-9, "{fit:?}");
+    }
+
+    #[test]
+    fn test_fit_curve_rejects_bad_input() {
+        let short = DecayCurve::new("x", 0, vec![(1, 0.9), (2, 0.8)]);
+        assert!(matches!(
+            fit_curve(&short),
+            Err(CalibrationError::TooFewPoints { points: 2, .. })
+        ));
+        let flat = DecayCurve::new("x", 0, vec![(1, 0.5), (2, 0.5), (4, 0.5)]);
+        assert!(matches!(
+            fit_curve(&flat),
+            Err(CalibrationError::InvalidCurve { .. })
+        ));
+        assert!(matches!(
+            fit_noise_profile(&[]),
+            Err(CalibrationError::NoData)
+        ));
+    }
+
+    #[test]
+    fn test_fit_noise_profile_averages() {
+        let curves = [
+            DecayCurve::new("cz", 0, ideal_curve(0.01, 0.02, 1.0)),
+            DecayCurve::new("cz", 2, ideal_curve(0.03, 0.04, 1.0)),
+        ];
+        let profile = fit_noise_profile(&curves).unwrap();
+        assert!((profile.gate_error("cz").unwrap() - 0.02).abs() < 1e-9);
+        let readout = profile.readout_errors.unwrap();
+        assert_eq!(readout.len(), 3);
+        assert!((readout[0] - 0.02).abs() < 1e-9);
+        assert!(readout[1].abs() < 1e-12);
+        assert!((readout[2] - 0.04).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_blend_profiles() {
+        let mut previous = NoiseProfile::new();
+        previous.gate_errors.insert("cz".into(), 0.01);
+        previous.gate_errors.insert("prx".into(), 0.001);
+        previous.readout_errors = Some(vec![0.02]);
+        let mut update = NoiseProfile::new();
+        update.gate_errors.insert("cz".into(), 0.03);
+        update.readout_errors = Some(vec![0.04, 0.05]);
+
+        let blended = blend_profiles(&previous, &update, 0.25);
+        assert!((blended.gate_error("cz").unwrap() - 0.015).abs() < 1e-12);
+        assert_eq!(blended.gate_error("prx"), Some(0.001));
+        let readout = blended.readout_errors.unwrap();
+        assert!((readout[0] - 0.025).abs() < 1e-12);
+        assert!((readout[1] - 0.05).abs() < 1e-12);
+    }
+
+    /// Simulate mirror circuits under a known profile and recover it.
+    #[test]
+    fn test_calibration_closes_loop_with_simulator() {
+        let mut truth = NoiseProfile::new();
+        truth.gate_errors.insert("x".into(), 0.02);
+        truth.readout_errors = Some(vec![0.05]);
+
+        let shots = 20_000;
+        let backend = SimulatorBackend::new().with_seed(11);
+        let points = [1, 2, 4, 8, 16]
+            .into_iter()
+            .map(|steps| {
+                let mut dag = generate_mirror_circuit(steps).into_dag();
+                let mut props = PropertySet::new();
+                props.insert(truth.clone());
+                NoiseInjectionPass.run(&mut dag, &mut props).unwrap();
+                let result = backend
+                    .run_simulation(&Circuit::from_dag(dag), shots)
+                    .unwrap();
+                (steps, result.counts.get("0") as f64 / f64::from(shots))
+            })
+            .collect();
+
+        let curve = DecayCurve::new("x", 0, points).with_gates_per_step(2.0);
+        let fitted = fit_noise_profile(&[curve]).unwrap();
+        let gate_error = fitted.gate_error("x").unwrap();
+        let readout_error = fitted.readout_errors.unwrap()[0];
+        assert!((gate_error - 0.02).abs() < 0.003, "{gate_error}");
+        assert!((readout_error - 0.05).abs() < 0.015, "{readout_error}");
+    }
+}
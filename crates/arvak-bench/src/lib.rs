@@ -4,10 +4,16 @@
 //! - **Quantum Volume (QV)**: Measures the effective size of a quantum computer
 //! - **CLOPS**: Circuit Layer Operations Per Second (throughput benchmark)
 //! - **Randomized Benchmarking (RB)**: Measures gate fidelity via random Clifford sequences
+//! - **Routing**: Compares swap-network routing with SABRE on dense QAOA layers
+//!
+//! The [`calibration`] module fits a noise profile to benchmark results from
+//! a real backend, so noisy simulation can track the device.
 
+pub mod calibration;
 pub mod clops;
 pub mod qv;
 pub mod rb;
+pub mod routing;
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -19,6 +25,10 @@ pub struct BenchmarkResult {
     pub name: String,
     /// Primary metric value.
     pub value: f64,
+    /// Standard error of the primary metric, if it was estimated from
+    /// samples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub std_error: Option<f64>,
     /// Unit of the primary metric.
     pub unit: String,
     /// Total wall-clock time.
@@ -33,6 +43,7 @@ impl BenchmarkResult {
         Self {
             name: name.into(),
             value,
+            std_error: None,
             unit: unit.into(),
             duration: Duration::ZERO,
             metrics: serde_json::Map::new(),
@@ -45,6 +56,12 @@ impl BenchmarkResult {
         self
     }
 
+    /// Set the standard error of the primary metric.
+    pub fn with_std_error(mut self, std_error: f64) -> Self {
+        self.std_error = Some(std_error);
+        self
+    }
+
     /// Add a metric.
     pub fn with_metric(
         mut self,
@@ -117,6 +117,17 @@ pub fn heavy_output_probability(
     heavy_count as f64 / total_shots as f64
 }
 
+/// Standard error of a heavy output probability measured with `shots`
+/// shots, `sqrt(h (1 - h) / shots)`.
+///
+/// A width passes with confidence when `h - 2σ > 2/3`.
+pub fn heavy_output_std_error(probability: f64, shots: u64) -> f64 {
+    if shots == 0 {
+        return 0.0;
+    }
+    (probability * (1.0 - probability) / shots as f64).sqrt()
+}
+
 /// Create a QV benchmark result for a given achieved volume.
 pub fn qv_result(achieved_width: u32, total_trials: u32) -> BenchmarkResult {
     let qv = 1u64.checked_shl(achieved_width).unwrap_or(u64::MAX);
@@ -155,6 +166,7 @@ mod tests {
         let hop = heavy_output_probability(&counts, 2);
         // 00 and 11 each have 40% > 25% (median), so heavy count = 800/1000 = 0.8
         assert!(hop > 0.7);
+        assert!((heavy_output_std_error(hop, 1000) - (0.8f64 * 0.2 / 1000.0).sqrt()).abs() < 1e-12);
     }
 
     #[test]
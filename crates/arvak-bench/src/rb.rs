@@ -7,9 +7,8 @@
 //! Fidelity = (1 + p) / 2, where p is the exponential decay parameter.
 
 use rand::{Rng, SeedableRng};
-use std::f64::consts::FRAC_1_SQRT_2;
 
-use arvak_ir::{Circuit, ClbitId, QubitId};
+use arvak_ir::{Circuit, ClbitId, QubitId, StandardGate};
 
 use crate::BenchmarkResult;
 
@@ -51,26 +50,28 @@ impl Mat2 {
         }
     }
 
-    /// H gate matrix
-    fn h() -> Self {
-        let v = FRAC_1_SQRT_2;
+    /// Matrix of a fixed single-qubit gate, from `arvak_ir`.
+    fn of(gate: &StandardGate) -> Self {
+        let m = gate.matrix().expect("fixed single-qubit gate has a matrix");
+        let entry = |i: usize| (m[i].re, m[i].im);
         Self {
-            m: [[(v, 0.0), (v, 0.0)], [(v, 0.0), (-v, 0.0)]],
+            m: [[entry(0), entry(1)], [entry(2), entry(3)]],
         }
     }
 
+    /// H gate matrix
+    fn h() -> Self {
+        Self::of(&StandardGate::H)
+    }
+
     /// S gate matrix
     fn s() -> Self {
-        Self {
-            m: [[(1.0, 0.0), (0.0, 0.0)], [(0.0, 0.0), (0.0, 1.0)]],
-        }
+        Self::of(&StandardGate::S)
     }
 
     /// X gate matrix
     fn x() -> Self {
-        Self {
-            m: [[(0.0, 0.0), (1.0, 0.0)], [(1.0, 0.0), (0.0, 0.0)]],
-        }
+        Self::of(&StandardGate::X)
     }
 
     /// Multiply two complex numbers: (a+bi)(c+di) = (ac-bd) + (ad+bc)i
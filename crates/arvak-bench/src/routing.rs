@@ -0,0 +1,138 @@
+//! Routing benchmark: swap networks versus SABRE.
+//!
+//! Compiles QAOA circuits on complete graphs, where every qubit pair
+//! interacts in each cost layer, once with swap networks enabled and once
+//! with SABRE alone, and reports the two-qubit gate count and depth of
+//! both. The primary metric is SABRE's two-qubit gate count divided by the
+//! swap network's; above 1 the swap network wins.
+
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+
+use arvak_compile::{BasisGates, CompileResult, CouplingMap, PassManagerBuilder};
+use arvak_ir::{Circuit, ClbitId, QubitId};
+use rand::{Rng, SeedableRng};
+
+use crate::BenchmarkResult;
+
+/// Generate a QAOA circuit on the complete graph of `num_qubits` vertices.
+///
+/// Each of the `layers` layers applies `rzz` with a random angle to every
+/// qubit pair, then an `rx` mixer; all qubits are measured at the end.
+pub fn generate_qaoa_circuit(num_qubits: u32, layers: u32, seed: u64) -> Circuit {
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
+    let mut circuit = Circuit::with_size(
+        format!("qaoa_k{num_qubits}_p{layers}"),
+        num_qubits,
+        num_qubits,
+    );
+
+    for q in 0..num_qubits {
+        let _ = circuit.h(QubitId(q));
+    }
+    for _ in 0..layers {
+        let gamma = rng.gen_range(0.0..PI);
+        for a in 0..num_qubits {
+            for b in a + 1..num_qubits {
+                let _ = circuit.rzz(gamma, QubitId(a), QubitId(b));
+            }
+        }
+        let beta = rng.gen_range(0.0..PI);
+        for q in 0..num_qubits {
+            let _ = circuit.rx(beta, QubitId(q));
+        }
+    }
+    for q in 0..num_qubits {
+        let _ = circuit.measure(QubitId(q), ClbitId(q));
+    }
+
+    circuit
+}
+
+/// Size and compile time of one compiled circuit.
+struct Compiled {
+    two_qubit_gates: usize,
+    depth: usize,
+    duration: Duration,
+}
+
+fn compile(
+    circuit: &Circuit,
+    coupling_map: &CouplingMap,
+    basis_gates: &BasisGates,
+    swap_networks: bool,
+) -> CompileResult<Compiled> {
+    let start = Instant::now();
+    let (pm, mut props) = PassManagerBuilder::new()
+        .with_optimization_level(2)
+        .with_target(coupling_map.clone(), basis_gates.clone())
+        .with_swap_networks(swap_networks)
+        .build();
+    let mut dag = circuit.clone().into_dag();
+    pm.run(&mut dag, &mut props)?;
+    let duration = start.elapsed();
+
+    let two_qubit_gates = dag
+        .topological_ops()
+        .filter(|(_, inst)| inst.is_gate() && inst.qubits.len() == 2)
+        .count();
+    Ok(Compiled {
+        two_qubit_gates,
+        depth: dag.depth(),
+        duration,
+    })
+}
+
+/// Compile `circuit` with swap networks and with SABRE alone and compare
+/// the results.
+#[allow(clippy::cast_precision_loss)]
+pub fn compare_swap_network_routing(
+    circuit: &Circuit,
+    coupling_map: &CouplingMap,
+    basis_gates: &BasisGates,
+) -> CompileResult<BenchmarkResult> {
+    let network = compile(circuit, coupling_map, basis_gates, true)?;
+    let sabre = compile(circuit, coupling_map, basis_gates, false)?;
+
+    let ratio = sabre.two_qubit_gates as f64 / network.two_qubit_gates.max(1) as f64;
+    Ok(
+        BenchmarkResult::new("swap_network_routing", ratio, "2q_gate_ratio")
+            .with_duration(network.duration + sabre.duration)
+            .with_metric("circuit", circuit.name())
+            .with_metric("num_qubits", circuit.num_qubits())
+            .with_metric("swap_network_2q_gates", network.two_qubit_gates)
+            .with_metric("swap_network_depth", network.depth)
+            .with_metric("swap_network_ms", network.duration.as_secs_f64() * 1e3)
+            .with_metric("sabre_2q_gates", sabre.two_qubit_gates)
+            .with_metric("sabre_depth", sabre.depth)
+            .with_metric("sabre_ms", sabre.duration.as_secs_f64() * 1e3),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_qaoa_circuit() {
+        let circuit = generate_qaoa_circuit(5, 2, 7);
+        assert_eq!(circuit.num_qubits(), 5);
+        let rzz = circuit
+            .dag()
+            .topological_ops()
+            .filter(|(_, inst)| inst.name() == "rzz")
+            .count();
+        assert_eq!(rzz, 20);
+    }
+
+    #[test]
+    fn test_compare_swap_network_routing() {
+        let circuit = generate_qaoa_circuit(6, 1, 3);
+        let result =
+            compare_swap_network_routing(&circuit, &CouplingMap::grid(3, 3), &BasisGates::iqm())
+                .unwrap();
+        assert_eq!(result.unit, "2q_gate_ratio");
+        assert!(result.value > 0.0);
+        assert!(result.metrics.contains_key("sabre_2q_gates"));
+    }
+}
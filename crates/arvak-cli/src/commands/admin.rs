@@ -0,0 +1,105 @@
+//! Admin command implementations.
+//!
+//! `arvak admin gc` applies a retention policy to the local job store:
+//! finished jobs older than a cutoff, or beyond a size cap, are deleted or
+//! archived. Use `--dry-run` to preview what would be removed.
+
+use anyhow::Result;
+use console::style;
+
+use arvak_hal::PayloadCipher;
+use arvak_sched::RetentionPolicy;
+use arvak_sched::retention::{RemovalReason, collect_garbage};
+
+use super::common::open_job_store;
+
+/// Execute the `admin gc` command.
+pub async fn execute_gc(
+    older_than_days: Option<u64>,
+    max_jobs: Option<usize>,
+    archive: Option<&str>,
+    dry_run: bool,
+    format: &str,
+) -> Result<()> {
+    let mut policy = RetentionPolicy::new();
+    if let Some(days) = older_than_days {
+        policy = policy.with_max_age_days(days);
+    }
+    if let Some(max) = max_jobs {
+        policy = policy.with_max_jobs(max);
+    }
+    if let Some(dir) = archive {
+        policy = policy.with_archive_dir(dir);
+        if let Some(cipher) = PayloadCipher::from_env()? {
+            policy = policy.with_archive_cipher(cipher);
+        }
+    }
+
+    if policy.is_unbounded() {
+        anyhow::bail!("Nothing to do: specify --older-than and/or --max-jobs");
+    }
+
+    let store = open_job_store()?;
+    let report = collect_garbage(&store, &policy, dry_run)
+        .await
+        .map_err(|e| anyhow::anyhow!("Garbage collection failed: {e}"))?;
+
+    if format == "json" {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    if report.removed.is_empty() {
+        println!(
+            "{} Nothing to collect ({} job(s) in store).",
+            style("✓").green().bold(),
+            report.total_jobs
+        );
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    println!(
+        "{} {} {} of {} job(s):\n",
+        style("→").cyan().bold(),
+        verb,
+        report.removed.len(),
+        report.total_jobs
+    );
+    println!(
+        "  {:<36}  {:<20}  {:<20}  {}",
+        style("JOB ID").bold(),
+        style("NAME").bold(),
+        style("FINISHED").bold(),
+        style("REASON").bold()
+    );
+    println!("  {}", "-".repeat(90));
+
+    for job in &report.removed {
+        let reason = match job.reason {
+            RemovalReason::Expired => "expired",
+            RemovalReason::OverCapacity => "over capacity",
+        };
+        println!(
+            "  {:<36}  {:<20}  {:<20}  {}",
+            style(&job.id).cyan(),
+            job.name,
+            job.finished_at.format("%Y-%m-%d %H:%M:%S"),
+            reason
+        );
+    }
+
+    if dry_run {
+        println!("\n  Dry run: nothing was deleted.");
+    } else if report.archived > 0 {
+        println!(
+            "\n  Archived {} job(s) to {}",
+            report.archived,
+            archive.unwrap_or_default()
+        );
+    }
+
+    Ok(())
+}
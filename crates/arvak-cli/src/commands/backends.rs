@@ -1,10 +1,20 @@
 //! Backends command implementation.
+//!
+//! `arvak backends attest` enrolls a backend's PUF fingerprint in
+//! `~/.arvak/attestation/<backend>.json` and verifies the device against
+//! it. `run` and `submit` verify enrolled backends before every job.
 
-use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
 use console::style;
 
 use arvak_adapter_sim::SimulatorBackend;
-use arvak_hal::Backend;
+use arvak_hal::attestation::PufChallenge;
+use arvak_hal::{Attestation, Backend, PufEnrollment, ResponseCache};
+
+use super::common::{create_backend, default_state_dir};
 
 #[cfg(feature = "iqm")]
 use arvak_adapter_iqm::IqmBackend;
@@ -13,7 +23,14 @@ use arvak_adapter_iqm::IqmBackend;
 use arvak_adapter_ibm::IbmBackend;
 
 /// Execute the backends command.
-pub async fn execute() -> Result<()> {
+///
+/// With `refresh`, the shared response cache is cleared first so device
+/// information is fetched again instead of served from the last snapshot.
+pub async fn execute(refresh: bool) -> Result<()> {
+    if refresh {
+        ResponseCache::global().clear()?;
+    }
+
     println!("{} Available backends:\n", style("Arvak").cyan().bold());
 
     // Simulator
@@ -235,5 +252,166 @@ pub async fn execute() -> Result<()> {
         println!();
     }
 
+    // Backends from the typed configuration document
+    if let Some((path, config)) = super::common::load_backends_config()? {
+        println!("  Configured in {}:", path.display());
+        for (name, section) in &config.backends {
+            match section.to_backend_config(name) {
+                Ok(_) => println!(
+                    "  {} {} ({})",
+                    style("●").green(),
+                    style(name).bold(),
+                    section.adapter()
+                ),
+                Err(e) => {
+                    println!(
+                        "  {} {} ({})",
+                        style("○").red(),
+                        style(name).dim(),
+                        section.adapter()
+                    );
+                    println!("    {e}");
+                }
+            }
+        }
+        for (alias, target) in &config.aliases {
+            println!("  {} {alias} → {target}", style("alias").dim());
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn enrollment_path(backend: &str) -> Result<PathBuf> {
+    let dir = default_state_dir()?.join("attestation");
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    Ok(dir.join(format!("{backend}.json")))
+}
+
+/// Load the enrollment of `backend`, if it was enrolled.
+fn load_enrollment(backend: &str) -> Result<Option<PufEnrollment>> {
+    let path = enrollment_path(backend)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let enrollment = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid enrollment in {}", path.display()))?;
+    Ok(Some(enrollment))
+}
+
+/// Verify `backend` against its enrollment before a job is submitted.
+///
+/// Returns `None` for backends that were never enrolled, and fails if the
+/// device does not match.
+pub async fn attest_enrolled(backend: &dyn Backend) -> Result<Option<Attestation>> {
+    let Some(enrollment) = load_enrollment(backend.name())? else {
+        return Ok(None);
+    };
+    println!("  Attesting {}...", style(backend.name()).yellow());
+    let attestation = enrollment
+        .attest(backend)
+        .await
+        .map_err(|e| anyhow::anyhow!("Attestation of '{}' failed: {e}", backend.name()))?;
+    if !attestation.is_verified() {
+        anyhow::bail!(
+            "Hardware attestation failed: {attestation}. Re-enroll with \
+             'arvak backends attest {} --enroll' if the device was recalibrated.",
+            backend.name()
+        );
+    }
+    println!("  {} Attestation: {attestation}", style("✓").green().bold());
+    Ok(Some(attestation))
+}
+
+/// Execute `arvak backends attest`.
+///
+/// With `enroll`, measure the backend's fingerprint and store it as the
+/// enrollment. Otherwise verify the backend against the stored one.
+pub async fn execute_attest(
+    name: &str,
+    enroll: bool,
+    qubits: Option<u32>,
+    shots: Option<u32>,
+    tolerance: Option<f64>,
+    format: &str,
+) -> Result<()> {
+    let backend = create_backend(name, false).await?;
+    let path = enrollment_path(backend.name())?;
+
+    if enroll {
+        let mut challenge = PufChallenge::for_qubits(backend.capabilities().num_qubits);
+        if let Some(qubits) = qubits {
+            challenge.qubits = qubits;
+        }
+        if let Some(shots) = shots {
+            challenge.shots = shots;
+        }
+        eprintln!(
+            "  Measuring fingerprint of {} ({} qubits, 2 x {} shots)...",
+            backend.name(),
+            challenge.qubits,
+            challenge.shots
+        );
+        let mut enrollment = PufEnrollment::enroll(backend.as_ref(), challenge)
+            .await
+            .map_err(|e| anyhow::anyhow!("Enrollment failed: {e}"))?;
+        if let Some(tolerance) = tolerance {
+            enrollment = enrollment.with_tolerance(tolerance);
+        }
+        fs::write(&path, serde_json::to_string_pretty(&enrollment)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        match format {
+            "json" => println!("{}", serde_json::to_string_pretty(&enrollment)?),
+            _ => {
+                println!(
+                    "{} Enrolled {} ({} qubits)",
+                    style("✓").green().bold(),
+                    style(&enrollment.backend).cyan(),
+                    enrollment.fingerprint.num_qubits()
+                );
+                println!("  Saved to: {}", path.display());
+            }
+        }
+        return Ok(());
+    }
+
+    let Some(mut enrollment) = load_enrollment(backend.name())? else {
+        anyhow::bail!(
+            "Backend '{}' is not enrolled. Enroll it on trusted hardware with \
+             'arvak backends attest {name} --enroll'.",
+            backend.name()
+        );
+    };
+    if let Some(tolerance) = tolerance {
+        enrollment = enrollment.with_tolerance(tolerance);
+    }
+    let attestation = enrollment
+        .attest(backend.as_ref())
+        .await
+        .map_err(|e| anyhow::anyhow!("Attestation failed: {e}"))?;
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&attestation)?),
+        _ => {
+            let mark = if attestation.is_verified() {
+                style("✓").green().bold()
+            } else {
+                style("✗").red().bold()
+            };
+            println!("{mark} {attestation}");
+            println!(
+                "  Enrolled: {}",
+                attestation.enrolled_at.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+        }
+    }
+    if !attestation.is_verified() {
+        anyhow::bail!("Backend '{}' does not match its enrollment", backend.name());
+    }
     Ok(())
 }
@@ -0,0 +1,334 @@
+//! Campaign command implementations.
+//!
+//! `arvak campaign run` executes every point of a campaign spec (circuits ×
+//! backends × shots × mitigation) and appends each result, with its
+//! provenance, to a JSON Lines dataset. Points already in the dataset are
+//! skipped, so rerunning the same command resumes an interrupted campaign
+//! and retries failed points. `arvak campaign status` shows which points
+//! are done.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use console::style;
+
+use arvak_eval::input::InputAnalysis;
+use arvak_hal::{Backend, ExecutionResult};
+use arvak_ir::{Circuit, QubitId};
+use arvak_sched::campaign::Provenance;
+use arvak_sched::{
+    CampaignDataset, CampaignPoint, CampaignRecord, CampaignSpec, CircuitSpec, Executor,
+    HpcScheduler, Mitigation, ReadoutCalibration, ScheduledJob, Scheduler, StateStore,
+};
+
+use super::common::{create_backend, open_job_store};
+
+/// Execute the `campaign run` command.
+pub async fn execute_run(spec_path: &str, output: Option<&str>, restart: bool) -> Result<()> {
+    let spec = load_spec(spec_path)?;
+    let output = dataset_path(spec_path, output);
+    if restart && output.exists() {
+        fs::remove_file(&output)
+            .with_context(|| format!("Failed to remove {}", output.display()))?;
+    }
+    let mut dataset = CampaignDataset::open(&output, &spec.name)
+        .map_err(|e| anyhow::anyhow!("Failed to open dataset: {e}"))?;
+
+    let points = spec.points();
+    let pending: Vec<&CampaignPoint> = points.iter().filter(|p| !dataset.is_done(p)).collect();
+    println!(
+        "{} Campaign {}: {} point(s), {} done, {} pending ({} execution)",
+        style("→").cyan().bold(),
+        style(&spec.name).green(),
+        points.len(),
+        points.len() - pending.len(),
+        pending.len(),
+        style(&spec.executor).magenta()
+    );
+    println!("  Dataset: {}", output.display());
+
+    let base_dir = Path::new(spec_path)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut runner = CampaignRunner::new(spec.executor.clone())?;
+    let mut failed = 0;
+    for (i, point) in pending.iter().enumerate() {
+        let progress = format!("[{}/{}]", i + 1, pending.len());
+        match runner.run_point(&spec, &base_dir, point).await {
+            Ok(record) => {
+                dataset
+                    .append(record)
+                    .map_err(|e| anyhow::anyhow!("Failed to write dataset: {e}"))?;
+                println!(
+                    "  {} {} {}",
+                    style("✓").green(),
+                    style(progress).dim(),
+                    point.key()
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!(
+                    "  {} {} {}: {}",
+                    style("✗").red(),
+                    style(progress).dim(),
+                    point.key(),
+                    e
+                );
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} point(s) failed; rerun to retry them");
+    }
+    println!(
+        "{} Campaign complete: {} record(s) in {}",
+        style("✓").green().bold(),
+        dataset.records().len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Execute the `campaign status` command.
+pub fn execute_status(spec_path: &str, output: Option<&str>, format: &str) -> Result<()> {
+    let spec = load_spec(spec_path)?;
+    let output = dataset_path(spec_path, output);
+    let dataset = CampaignDataset::open(&output, &spec.name)
+        .map_err(|e| anyhow::anyhow!("Failed to open dataset: {e}"))?;
+    let points = spec.points();
+
+    if format == "json" {
+        let rows: Vec<_> = points
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "circuit": p.circuit,
+                    "backend": p.backend,
+                    "shots": p.shots,
+                    "mitigation": p.mitigation,
+                    "done": dataset.is_done(p),
+                })
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&rows)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    let done = points.iter().filter(|p| dataset.is_done(p)).count();
+    println!(
+        "{} Campaign {}: {}/{} point(s) done ({})\n",
+        style("→").cyan().bold(),
+        style(&spec.name).green(),
+        done,
+        points.len(),
+        output.display()
+    );
+    println!(
+        "  {:<24}  {:<14}  {:>8}  {:<10}  {}",
+        style("CIRCUIT").bold(),
+        style("BACKEND").bold(),
+        style("SHOTS").bold(),
+        style("MITIGATION").bold(),
+        style("STATUS").bold()
+    );
+    println!("  {}", "-".repeat(72));
+    for point in &points {
+        let status = if dataset.is_done(point) {
+            style("done").green()
+        } else {
+            style("pending").yellow()
+        };
+        println!(
+            "  {:<24}  {:<14}  {:>8}  {:<10}  {}",
+            point.circuit,
+            point.backend,
+            point.shots,
+            point.mitigation.to_string(),
+            status
+        );
+    }
+    Ok(())
+}
+
+fn load_spec(path: &str) -> Result<CampaignSpec> {
+    CampaignSpec::from_file(path)
+        .map_err(|e| anyhow::anyhow!("Failed to load campaign '{path}': {e}"))
+}
+
+/// The dataset path: `--output`, or the spec path with a `.jsonl` extension.
+fn dataset_path(spec_path: &str, output: Option<&str>) -> PathBuf {
+    output.map_or_else(
+        || Path::new(spec_path).with_extension("jsonl"),
+        PathBuf::from,
+    )
+}
+
+/// Executes campaign points, reusing circuits, backends, schedulers and
+/// readout calibrations across points.
+struct CampaignRunner {
+    executor: Executor,
+    store: Option<Arc<dyn StateStore>>,
+    circuits: HashMap<String, (Circuit, String)>,
+    backends: HashMap<String, Arc<dyn Backend>>,
+    schedulers: HashMap<String, HpcScheduler>,
+    calibrations: HashMap<(String, u32, usize), ReadoutCalibration>,
+}
+
+impl CampaignRunner {
+    fn new(executor: Executor) -> Result<Self> {
+        let store: Option<Arc<dyn StateStore>> = match executor {
+            Executor::Direct => None,
+            _ => Some(Arc::new(open_job_store()?)),
+        };
+        Ok(Self {
+            executor,
+            store,
+            circuits: HashMap::new(),
+            backends: HashMap::new(),
+            schedulers: HashMap::new(),
+            calibrations: HashMap::new(),
+        })
+    }
+
+    async fn run_point(
+        &mut self,
+        spec: &CampaignSpec,
+        base_dir: &Path,
+        point: &CampaignPoint,
+    ) -> Result<CampaignRecord> {
+        if !self.circuits.contains_key(&point.circuit) {
+            let path = base_dir.join(&point.circuit);
+            let source = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read circuit: {}", path.display()))?;
+            let analysis = InputAnalysis::analyze(&source)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", path.display()))?;
+            self.circuits.insert(
+                point.circuit.clone(),
+                (analysis.circuit, analysis.content_hash),
+            );
+        }
+        let (circuit, circuit_hash) = self.circuits[&point.circuit].clone();
+
+        let started_at = Utc::now();
+        let (result, job_id) = self.execute(&point.backend, &circuit, point.shots).await?;
+
+        let (mitigated, readout_calibration) = match point.mitigation {
+            Mitigation::None => (None, None),
+            Mitigation::Readout => {
+                let num_bits = result.counts.iter().next().map_or(0, |(b, _)| b.len());
+                let calibration = self
+                    .calibration(&point.backend, point.shots, num_bits)
+                    .await?;
+                let mitigated = calibration
+                    .mitigate(&result.counts)
+                    .map_err(|e| anyhow::anyhow!("Readout mitigation failed: {e}"))?;
+                (Some(mitigated), Some(calibration))
+            }
+        };
+
+        Ok(CampaignRecord {
+            campaign: spec.name.clone(),
+            circuit: point.circuit.clone(),
+            backend: point.backend.clone(),
+            shots: point.shots,
+            mitigation: point.mitigation,
+            counts: result.counts.into_iter().collect(),
+            mitigated,
+            readout_calibration,
+            provenance: Provenance {
+                arvak_version: env!("CARGO_PKG_VERSION").to_string(),
+                circuit_hash,
+                executor: self.executor.clone(),
+                job_id,
+                started_at,
+                finished_at: Utc::now(),
+                execution_time_ms: result.execution_time_ms,
+            },
+        })
+    }
+
+    /// Run the all-zeros and all-ones calibration circuits once per
+    /// backend, shot count and register width.
+    async fn calibration(
+        &mut self,
+        backend: &str,
+        shots: u32,
+        num_bits: usize,
+    ) -> Result<ReadoutCalibration> {
+        let key = (backend.to_string(), shots, num_bits);
+        if let Some(calibration) = self.calibrations.get(&key) {
+            return Ok(calibration.clone());
+        }
+
+        let width = u32::try_from(num_bits)?;
+        let mut zeros = Circuit::with_size("readout_cal_0", width, width);
+        zeros.measure_all()?;
+        let mut ones = Circuit::with_size("readout_cal_1", width, width);
+        for q in 0..width {
+            ones.x(QubitId(q))?;
+        }
+        ones.measure_all()?;
+
+        let (zeros, _) = self.execute(backend, &zeros, shots).await?;
+        let (ones, _) = self.execute(backend, &ones, shots).await?;
+        let calibration = ReadoutCalibration::from_counts(&zeros.counts, &ones.counts, num_bits)
+            .map_err(|e| anyhow::anyhow!("Readout calibration failed: {e}"))?;
+        self.calibrations.insert(key, calibration.clone());
+        Ok(calibration)
+    }
+
+    /// Execute a circuit on a backend, directly or through the batch
+    /// scheduler. Returns the result and the scheduler job ID.
+    async fn execute(
+        &mut self,
+        backend: &str,
+        circuit: &Circuit,
+        shots: u32,
+    ) -> Result<(ExecutionResult, Option<String>)> {
+        if !self.backends.contains_key(backend) {
+            let backend_impl = create_backend(backend, false).await?;
+            self.backends
+                .insert(backend.to_string(), Arc::from(backend_impl));
+        }
+        let backend_impl = Arc::clone(&self.backends[backend]);
+
+        let Some(config) = self.executor.scheduler_config() else {
+            let result = arvak_hal::run_split(backend_impl.as_ref(), circuit, shots, None).await?;
+            return Ok((result, None));
+        };
+
+        if !self.schedulers.contains_key(backend) {
+            let store = self
+                .store
+                .clone()
+                .context("Batch execution requires a job store")?;
+            let hpc = HpcScheduler::new(config, vec![backend_impl], store)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create scheduler: {e}"))?;
+            self.schedulers.insert(backend.to_string(), hpc);
+        }
+        let hpc = &self.schedulers[backend];
+
+        let circuit_spec = CircuitSpec::from_circuit(circuit)
+            .map_err(|e| anyhow::anyhow!("Failed to create circuit spec: {e}"))?;
+        let job = ScheduledJob::new(circuit.name(), circuit_spec).with_shots(shots);
+        let job_id = hpc
+            .submit(job)
+            .await
+            .map_err(|e| anyhow::anyhow!("Submit failed: {e}"))?;
+        let result = hpc
+            .wait(&job_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Job {job_id} failed: {e}"))?;
+        Ok((result, Some(job_id.to_string())))
+    }
+}
@@ -3,16 +3,45 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use arvak_compile::{BasisGates, CouplingMap};
+use arvak_compile::{AdaptiveOptimization, BasisGates, CouplingMap, OptimizationLevel};
+use arvak_hal::{Attestation, Backend, BackendRegistry, BackendsConfig, PayloadCipher, Provenance};
 use arvak_ir::Circuit;
-use arvak_qasm3::parse;
+use arvak_qasm3::{emit, parse};
 use arvak_sched::{HpcScheduler, SchedulerConfig, SqliteStore};
 
-/// Load a circuit from a QASM3 or JSON file.
-pub fn load_circuit(path: &str) -> Result<Circuit> {
+use arvak_adapter_sim::SimulatorBackend;
+
+#[cfg(feature = "iqm")]
+use arvak_adapter_iqm::IqmBackend;
+
+#[cfg(feature = "ibm")]
+use arvak_adapter_ibm::IbmBackend;
+
+#[cfg(feature = "braket")]
+use arvak_adapter_braket::BraketBackend;
+
+#[cfg(feature = "scaleway")]
+use arvak_adapter_scaleway::ScalewayBackend;
+
+#[cfg(feature = "quantinuum")]
+use arvak_adapter_quantinuum::QuantinuumBackend;
+
+#[cfg(feature = "ddsim")]
+use arvak_adapter_ddsim::DdsimBackend;
+
+/// Load a circuit from a QASM3, JSON or `.arvakc` file, together with its
+/// provenance.
+///
+/// A compiled artifact (`.arvakc`) carries its provenance, so the chain
+/// continues from the compiled circuit; any other file starts a new chain
+/// at its source text.
+pub fn load_circuit_with_provenance(path: &str) -> Result<(Circuit, Provenance)> {
     let path_obj = Path::new(path);
 
     if !path_obj.exists() {
@@ -25,14 +54,96 @@ pub fn load_circuit(path: &str) -> Result<Circuit> {
     let ext = path_obj.extension().and_then(|e| e.to_str()).unwrap_or("");
 
     match ext.to_lowercase().as_str() {
-        "qasm" | "qasm3" => parse(&source).map_err(|e| anyhow::anyhow!("Parse error: {e}")),
+        ARTIFACT_EXTENSION => {
+            let artifact = CompiledArtifact::from_json(&source)
+                .with_context(|| format!("Invalid compiled artifact: {path}"))?;
+            let circuit = parse(&artifact.qasm).map_err(|e| anyhow::anyhow!("Parse error: {e}"))?;
+            Ok((circuit, artifact.provenance))
+        }
         "json" => {
             anyhow::bail!("JSON format not yet supported")
         }
-        _ => parse(&source).map_err(|e| anyhow::anyhow!("Parse error: {e}")),
+        _ => {
+            let circuit = parse(&source).map_err(|e| anyhow::anyhow!("Parse error: {e}"))?;
+            Ok((circuit, Provenance::source(&source)))
+        }
+    }
+}
+
+/// File extension of compiled artifacts written by `arvak compile`.
+pub const ARTIFACT_EXTENSION: &str = "arvakc";
+
+/// A compiled circuit with its provenance, stored as JSON in a `.arvakc`
+/// file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledArtifact {
+    /// Always `"arvakc"`.
+    pub format: String,
+    /// Format version.
+    pub version: u32,
+    /// Provenance of the compiled circuit.
+    pub provenance: Provenance,
+    /// Compilation target.
+    pub target: String,
+    /// Optimization level the circuit was compiled at.
+    pub optimization_level: u8,
+    /// When the artifact was written.
+    pub created_at: DateTime<Utc>,
+    /// The compiled circuit as `OpenQASM` 3.0.
+    pub qasm: String,
+}
+
+impl CompiledArtifact {
+    /// Current format version.
+    pub const VERSION: u32 = 1;
+
+    /// Wrap a compiled circuit.
+    pub fn new(
+        circuit: &Circuit,
+        provenance: Provenance,
+        target: &str,
+        optimization_level: u8,
+    ) -> Result<Self> {
+        Ok(Self {
+            format: ARTIFACT_EXTENSION.to_string(),
+            version: Self::VERSION,
+            provenance,
+            target: target.to_string(),
+            optimization_level,
+            created_at: Utc::now(),
+            qasm: emit(circuit).map_err(|e| anyhow::anyhow!("Emit error: {e}"))?,
+        })
+    }
+
+    /// Parse an artifact, rejecting other formats and newer versions.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let artifact: Self = serde_json::from_str(json)?;
+        if artifact.format != ARTIFACT_EXTENSION {
+            anyhow::bail!("unknown format '{}'", artifact.format);
+        }
+        if artifact.version > Self::VERSION {
+            anyhow::bail!(
+                "version {} is newer than the supported version {}",
+                artifact.version,
+                Self::VERSION
+            );
+        }
+        Ok(artifact)
     }
 }
 
+/// Parse a `NAME=VALUE` binding for an `input` parameter.
+pub fn parse_param(arg: &str) -> std::result::Result<(String, f64), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{arg}'"))?;
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid value for parameter '{name}': {e}"))?;
+    Ok((name.trim().to_string(), value))
+}
+
 /// Get basis gates for a named target (coupling map comes from HAL capabilities).
 pub fn get_basis_gates(target: &str) -> Result<BasisGates> {
     match target.to_lowercase().as_str() {
@@ -60,6 +171,212 @@ pub fn get_basis_gates(target: &str) -> Result<BasisGates> {
     }
 }
 
+/// Load the typed backend configuration document, if there is one.
+///
+/// Read from `$ARVAK_BACKENDS_CONFIG`, or `~/.arvak/backends.toml` when that
+/// exists. The gRPC server reads the same document.
+pub fn load_backends_config() -> Result<Option<(PathBuf, BackendsConfig)>> {
+    let path = match std::env::var_os("ARVAK_BACKENDS_CONFIG") {
+        Some(path) => PathBuf::from(path),
+        None => match dirs::home_dir() {
+            Some(home) if home.join(".arvak/backends.toml").exists() => {
+                home.join(".arvak/backends.toml")
+            }
+            _ => return Ok(None),
+        },
+    };
+    let config = BackendsConfig::from_file(&path)?;
+    Ok(Some((path, config)))
+}
+
+/// Create backend `name` from its section in the backend configuration.
+fn create_configured_backend(config: &BackendsConfig, name: &str) -> Result<Box<dyn Backend>> {
+    let mut registry = BackendRegistry::new();
+    registry.register::<SimulatorBackend>("simulator");
+    #[cfg(feature = "ddsim")]
+    registry.register::<DdsimBackend>("ddsim");
+    #[cfg(feature = "iqm")]
+    registry.register::<IqmBackend>("iqm");
+    #[cfg(feature = "ibm")]
+    registry.register::<IbmBackend>("ibm");
+    #[cfg(feature = "scaleway")]
+    registry.register::<ScalewayBackend>("scaleway");
+    #[cfg(feature = "quantinuum")]
+    registry.register::<QuantinuumBackend>("quantinuum");
+    #[cfg(feature = "aqt")]
+    registry.register::<arvak_adapter_aqt::AqtBackend>("aqt");
+
+    Ok(registry.create_configured(config, name)?)
+}
+
+/// Connect to a backend by name (as accepted by `arvak run --backend`).
+///
+/// Names configured in the backend configuration document (see
+/// [`load_backends_config`]) take precedence over the built-in names.
+/// `skip_transpilation` tells backends with server-side transpilation
+/// (IBM) that the circuit is already compiled for the device.
+/// Connection progress is reported on stderr, so stdout stays clean for
+/// machine-readable output.
+#[cfg_attr(not(feature = "ibm"), allow(unused_variables))]
+pub async fn create_backend(backend: &str, skip_transpilation: bool) -> Result<Box<dyn Backend>> {
+    if let Some((path, config)) = load_backends_config()? {
+        if config.get(backend).is_some() {
+            eprintln!(
+                "  Using backend '{backend}' configured in {}",
+                path.display()
+            );
+            return create_configured_backend(&config, backend);
+        }
+    }
+
+    let backend_impl: Box<dyn Backend> = match backend.to_lowercase().as_str() {
+        "simulator" | "sim" => Box::new(SimulatorBackend::new()),
+        #[cfg(feature = "ddsim")]
+        "ddsim" | "mqt-ddsim" | "mqt_ddsim" => {
+            eprintln!("  Checking MQT DDSIM availability...");
+            Box::new(DdsimBackend::new())
+        }
+        #[cfg(not(feature = "ddsim"))]
+        "ddsim" | "mqt-ddsim" | "mqt_ddsim" => {
+            anyhow::bail!("DDSIM backend not available. Rebuild with --features ddsim");
+        }
+        #[cfg(feature = "iqm")]
+        "iqm" | "garnet" => {
+            eprintln!("  Connecting to IQM Resonance...");
+            match IqmBackend::new() {
+                Ok(b) => Box::new(b),
+                Err(e) => {
+                    anyhow::bail!(
+                        "Failed to connect to IQM: {}. Set IQM_TOKEN environment variable.",
+                        e
+                    );
+                }
+            }
+        }
+        #[cfg(not(feature = "iqm"))]
+        "iqm" | "garnet" => {
+            anyhow::bail!("IQM backend not available. Rebuild with --features iqm");
+        }
+        #[cfg(feature = "ibm")]
+        "ibm" | "ibmq" | "ibm_torino" | "ibm_fez" | "ibm_marrakesh" | "ibm_brisbane"
+        | "ibm_kyoto" | "ibm_osaka" => {
+            eprintln!("  Connecting to IBM Quantum...");
+            match IbmBackend::connect(backend).await {
+                Ok(mut b) => {
+                    if skip_transpilation {
+                        b.set_skip_transpilation(true);
+                    }
+                    Box::new(b)
+                }
+                Err(e) => {
+                    anyhow::bail!(
+                        "Failed to connect to IBM Quantum: {}. Set IBM_API_KEY + IBM_SERVICE_CRN (or IBM_QUANTUM_TOKEN).",
+                        e
+                    );
+                }
+            }
+        }
+        #[cfg(not(feature = "ibm"))]
+        "ibm" | "ibmq" | "ibm_torino" | "ibm_fez" | "ibm_marrakesh" | "ibm_brisbane"
+        | "ibm_kyoto" | "ibm_osaka" => {
+            anyhow::bail!("IBM backend not available. Rebuild with --features ibm");
+        }
+        #[cfg(feature = "braket")]
+        "braket" | "braket-sv1" | "sv1" | "braket-tn1" | "tn1" | "braket-dm1" | "dm1"
+        | "rigetti" | "ankaa" | "ionq" | "aria" => {
+            eprintln!("  Connecting to AWS Braket...");
+            let device_arn = arvak_adapter_braket::device::arn_for_name(backend)
+                .ok_or_else(|| anyhow::anyhow!("Unknown Braket device: {backend}"))?;
+            match BraketBackend::connect(device_arn).await {
+                Ok(b) => Box::new(b),
+                Err(e) => {
+                    anyhow::bail!(
+                        "Failed to connect to AWS Braket: {}. Set ARVAK_BRAKET_S3_BUCKET and configure AWS credentials.",
+                        e
+                    );
+                }
+            }
+        }
+        #[cfg(not(feature = "braket"))]
+        "braket" | "braket-sv1" | "sv1" | "braket-tn1" | "tn1" | "braket-dm1" | "dm1"
+        | "rigetti" | "ankaa" | "ionq" | "aria" => {
+            anyhow::bail!("Braket backend not available. Rebuild with --features braket");
+        }
+        #[cfg(feature = "scaleway")]
+        "scaleway" | "scaleway-garnet" | "scaleway-emerald" => {
+            eprintln!("  Connecting to Scaleway QaaS...");
+            match ScalewayBackend::new() {
+                Ok(b) => {
+                    eprintln!("  Session: {}, Platform: {}", b.session_id(), b.platform());
+                    Box::new(b)
+                }
+                Err(e) => {
+                    anyhow::bail!(
+                        "Failed to connect to Scaleway: {}. Set SCALEWAY_SECRET_KEY, SCALEWAY_PROJECT_ID, and SCALEWAY_SESSION_ID.",
+                        e
+                    );
+                }
+            }
+        }
+        #[cfg(not(feature = "scaleway"))]
+        "scaleway" | "scaleway-garnet" | "scaleway-emerald" => {
+            anyhow::bail!("Scaleway backend not available. Rebuild with --features scaleway");
+        }
+        #[cfg(feature = "quantinuum")]
+        "quantinuum" | "quantinuum-h2" | "h2-1le" | "h2-1e" | "h1-1e" | "h2-1" | "h1-1" => {
+            eprintln!("  Connecting to Quantinuum...");
+            let machine = match backend.to_lowercase().as_str() {
+                "quantinuum" | "quantinuum-h2" | "h2-1le" => "H2-1LE",
+                "h2-1e" => "H2-1E",
+                "h1-1e" => "H1-1E",
+                "h2-1" => "H2-1",
+                "h1-1" => "H1-1",
+                _ => "H2-1LE",
+            };
+            match QuantinuumBackend::with_target(machine) {
+                Ok(b) => Box::new(b),
+                Err(e) => {
+                    anyhow::bail!(
+                        "Failed to connect to Quantinuum: {}. \
+                         Set QUANTINUUM_EMAIL and QUANTINUUM_PASSWORD environment variables.",
+                        e
+                    );
+                }
+            }
+        }
+        #[cfg(not(feature = "quantinuum"))]
+        "quantinuum" | "quantinuum-h2" | "h2-1le" | "h2-1e" | "h1-1e" | "h2-1" | "h1-1" => {
+            anyhow::bail!("Quantinuum backend not available. Rebuild with --features quantinuum");
+        }
+        other => {
+            anyhow::bail!(
+                "Unknown backend: '{other}'. Available: simulator, ddsim, iqm, ibm, braket, scaleway, quantinuum"
+            );
+        }
+    };
+    Ok(backend_impl)
+}
+
+/// Resolve `--compile-budget` in seconds to a duration.
+///
+/// Adaptive optimization always has a budget, defaulting to
+/// [`AdaptiveOptimization::DEFAULT_BUDGET`]; fixed levels only when given.
+pub fn compile_budget_duration(
+    level: OptimizationLevel,
+    seconds: Option<f64>,
+) -> Result<Option<Duration>> {
+    let Some(seconds) = seconds else {
+        return Ok(
+            (level == OptimizationLevel::Auto).then_some(AdaptiveOptimization::DEFAULT_BUDGET)
+        );
+    };
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|d| !d.is_zero())
+        .map(Some)
+        .with_context(|| format!("Invalid compile budget: {seconds}s (expected a positive number)"))
+}
+
 /// Get target coupling map and basis gates for a named target.
 ///
 /// Offline fallback — uses hardcoded topologies. Prefer extracting
@@ -122,15 +439,27 @@ pub fn default_state_dir() -> Result<PathBuf> {
     Ok(state_dir)
 }
 
-/// Create an `HpcScheduler` with mock SLURM adapter backed by local `SQLite` store.
+/// Open the local `SQLite` job store (`~/.arvak/jobs.db`).
 ///
-/// Used by `status`, `result`, and `wait` commands to query local job state
-/// without requiring a real SLURM/PBS installation.
-pub fn create_scheduler() -> Result<HpcScheduler> {
+/// If `ARVAK_STORAGE_KEY` is set, job and result payloads are encrypted at
+/// rest with that key.
+pub fn open_job_store() -> Result<SqliteStore> {
     let state_dir = default_state_dir()?;
     let db_path = state_dir.join("jobs.db");
     let store = SqliteStore::new(&db_path)
         .map_err(|e| anyhow::anyhow!("Failed to open job store at {}: {}", db_path.display(), e))?;
+    match PayloadCipher::from_env()? {
+        Some(cipher) => Ok(store.with_cipher(cipher)),
+        None => Ok(store),
+    }
+}
+
+/// Create an `HpcScheduler` with mock SLURM adapter backed by local `SQLite` store.
+///
+/// Used by `status`, `result`, and `wait` commands to query local job state
+/// without requiring a real SLURM/PBS installation.
+pub fn create_scheduler() -> Result<HpcScheduler> {
+    let store = open_job_store()?;
     let config = SchedulerConfig::default();
     Ok(HpcScheduler::with_mock_slurm(
         config,
@@ -173,4 +502,11 @@ pub fn print_results(result: &arvak_hal::ExecutionResult) {
     if let Some(time_ms) = result.execution_time_ms {
         println!("\n  Execution time: {} ms", style(time_ms).yellow());
     }
+
+    if let Some(provenance) = Provenance::from_result(result) {
+        println!("  Artifact: {}", style(&provenance.artifact_id).cyan());
+    }
+    if let Some(attestation) = Attestation::from_result(result) {
+        println!("  Attestation: {attestation}");
+    }
 }
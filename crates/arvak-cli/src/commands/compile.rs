@@ -1,22 +1,37 @@
 //! Compile command implementation.
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use console::style;
 use std::fs;
 use std::path::Path;
 
-use arvak_compile::PassManagerBuilder;
+use arvak_compile::{
+    AdaptiveOptimization, AdaptiveSelection, CancellationToken, CheckOutcome, CompileError,
+    CompileTimings, OptimizationLevel, PassManagerBuilder, Verification, VerificationReport,
+    passes::QubitReuseResult,
+};
+use arvak_hal::provenance::Stage;
 use arvak_ir::Circuit;
-use arvak_qasm3::emit;
+use arvak_qasm3::{EmitOptions, LayoutPragmas, emit_with_options};
 
-use super::common::{get_target_properties, load_circuit};
+use super::common::{
+    ARTIFACT_EXTENSION, CompiledArtifact, compile_budget_duration, get_target_properties,
+    load_circuit_with_provenance,
+};
+use super::trace;
 
 /// Execute the compile command.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     input: &str,
     output: Option<&str>,
     target: &str,
-    optimization_level: u8,
+    optimization_level: OptimizationLevel,
+    compile_budget: Option<f64>,
+    seed: Option<u64>,
+    deterministic: bool,
+    qubit_reuse: bool,
+    verify: bool,
 ) -> Result<()> {
     println!(
         "{} Compiling {} for target {}",
@@ -26,7 +41,7 @@ pub async fn execute(
     );
 
     // Load circuit
-    let circuit = load_circuit(input)?;
+    let (circuit, source) = load_circuit_with_provenance(input)?;
     println!(
         "  Loaded: {} qubits, depth {}",
         circuit.num_qubits(),
@@ -37,21 +52,122 @@ pub async fn execute(
     let (coupling_map, basis_gates) = get_target_properties(target)?;
 
     // Build pass manager
-    let (pm, mut props) = PassManagerBuilder::new()
-        .with_optimization_level(optimization_level)
-        .with_target(coupling_map, basis_gates)
+    let mut dag = circuit.into_dag();
+    let time_budget = compile_budget_duration(optimization_level, compile_budget)?;
+    let mut builder = PassManagerBuilder::new().with_target(coupling_map, basis_gates);
+    builder = match optimization_level {
+        OptimizationLevel::Fixed(level) => builder.with_optimization_level(level),
+        OptimizationLevel::Auto => {
+            let adaptive = AdaptiveOptimization::new(
+                time_budget.unwrap_or(AdaptiveOptimization::DEFAULT_BUDGET),
+            );
+            builder.with_adaptive_optimization(&dag, &adaptive)
+        }
+    };
+    if let Some(budget) = time_budget {
+        builder = builder.with_time_budget(budget);
+    }
+    if let Some(seed) = seed {
+        builder = builder.with_seed(seed);
+    }
+    if verify {
+        builder = builder.with_verification(Verification::new());
+    }
+    let token = CancellationToken::new();
+    let (pm, mut props) = builder
+        .with_deterministic(deterministic)
+        .with_qubit_reuse(qubit_reuse)
+        .with_cancellation(token.clone())
         .build();
 
-    println!(
-        "  Running {} compilation passes (level {})",
-        pm.len(),
-        optimization_level
-    );
-
-    // Compile
-    let mut dag = circuit.into_dag();
-    pm.run(&mut dag, &mut props)?;
+    let level = match optimization_level {
+        OptimizationLevel::Fixed(level) => level,
+        OptimizationLevel::Auto => props.get::<AdaptiveSelection>().map_or(1, |s| s.level),
+    };
+    match props.get::<AdaptiveSelection>() {
+        Some(selection) => println!(
+            "  Running {} compilation passes (level {}, auto: {})",
+            pm.len(),
+            selection.level,
+            selection.reason
+        ),
+        None => println!(
+            "  Running {} compilation passes (level {})",
+            pm.len(),
+            optimization_level
+        ),
+    }
+
+    // Compile on a blocking thread so Ctrl-C can cancel it between passes
+    let on_ctrl_c = tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            token.cancel();
+        }
+    });
+    let (result, dag, props) = tokio::task::spawn_blocking(move || {
+        let result = pm.run(&mut dag, &mut props);
+        (result, dag, props)
+    })
+    .await
+    .context("Compilation task failed")?;
+    on_ctrl_c.abort();
+    match result {
+        Err(CompileError::Cancelled) => bail!("Compilation cancelled"),
+        result => result?,
+    }
+    if let Some(timings) = props
+        .get::<CompileTimings>()
+        .filter(|t| t.budget_exhausted())
+    {
+        println!(
+            "  {} Compile-time budget spent after {:.2}s; optimization stopped early",
+            style("!").yellow().bold(),
+            timings.total().as_secs_f64()
+        );
+    }
+
+    if let Some(report) = props.get::<VerificationReport>() {
+        println!(
+            "  Verified {} of {} transformation passes",
+            report.verified(),
+            report.checks.len()
+        );
+        for check in report.skipped() {
+            if let CheckOutcome::Skipped(reason) = &check.outcome {
+                println!("    {} not checked: {reason}", check.pass);
+            }
+        }
+    }
+
+    if let Some(reuse) = props.get::<QubitReuseResult>() {
+        println!(
+            "  Qubit reuse: {} -> {} qubits ({} resets)",
+            reuse.qubits_before, reuse.qubits_after, reuse.resets_inserted
+        );
+    }
+
+    if let Some(seed) = props.seed {
+        println!(
+            "  Seed: {seed}{}",
+            if deterministic {
+                " (deterministic)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    // Record the virtual → physical mapping so the QASM output stays
+    // readable without the artifact file.
+    let layout = props.initial_layout.as_ref().map(|initial| {
+        let pragmas = LayoutPragmas::new(initial.iter());
+        match &props.layout {
+            Some(routed) => pragmas.with_final_layout(routed.iter()),
+            None => pragmas,
+        }
+    });
 
+    let provenance = source.compiled(&dag);
     let compiled = Circuit::from_dag(dag);
 
     println!("{} Compilation complete", style("✓").green().bold());
@@ -74,26 +190,52 @@ pub async fn execute(
         }
     };
 
-    save_circuit(&compiled, output_path)?;
+    let artifact = CompiledArtifact::new(&compiled, provenance, target, level)?;
+    save_circuit(&compiled, &artifact, layout, output_path)?;
     println!("  Output: {}", style(output_path).green());
+    println!(
+        "  Artifact: {}",
+        style(&artifact.provenance.artifact_id).cyan()
+    );
+
+    if source.stage == Stage::Source {
+        trace::record(&source, "compile", input);
+    }
+    trace::record(
+        &artifact.provenance,
+        "compile",
+        format!("{input} -> {output_path} (target {target}, level {level})"),
+    );
 
     Ok(())
 }
 
-/// Save a circuit to a file.
-fn save_circuit(circuit: &Circuit, path: &str) -> Result<()> {
+/// Save a circuit to a file. A `.arvakc` file stores the whole artifact,
+/// any other file the circuit alone, with its layout as QASM pragmas.
+fn save_circuit(
+    circuit: &Circuit,
+    artifact: &CompiledArtifact,
+    layout: Option<LayoutPragmas>,
+    path: &str,
+) -> Result<()> {
     let path_obj = Path::new(path);
     let ext = path_obj
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("qasm");
 
+    let mut options = EmitOptions::new();
+    if let Some(layout) = layout {
+        options = options.with_layout(layout);
+    }
     let content = match ext.to_lowercase().as_str() {
-        "qasm" | "qasm3" => emit(circuit).map_err(|e| anyhow::anyhow!("Emit error: {e}"))?,
+        ARTIFACT_EXTENSION => serde_json::to_string_pretty(artifact)?,
         "json" => {
             anyhow::bail!("JSON format not yet supported")
         }
-        _ => emit(circuit).map_err(|e| anyhow::anyhow!("Emit error: {e}"))?,
+        _ => {
+            emit_with_options(circuit, &options).map_err(|e| anyhow::anyhow!("Emit error: {e}"))?
+        }
     };
 
     fs::write(path, content).with_context(|| format!("Failed to write file: {path}"))?;
@@ -0,0 +1,19 @@
+//! Completions command implementation.
+
+use std::io::{self, Write};
+
+use anyhow::Result;
+use clap::Command;
+use clap_complete::Shell;
+
+/// Execute the completions command: write the completion script for
+/// `shell` to stdout.
+pub fn execute(shell: Shell, cli: &mut Command) -> Result<()> {
+    let name = cli.get_name().to_string();
+    // Generate into a buffer: `generate` panics on write errors such as a
+    // closed pipe.
+    let mut script = Vec::new();
+    clap_complete::generate(shell, cli, name, &mut script);
+    io::stdout().write_all(&script)?;
+    Ok(())
+}
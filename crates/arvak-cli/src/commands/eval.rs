@@ -1,11 +1,19 @@
 //! Evaluator command implementation.
 //!
-//! `arvak eval --input <circuit.qasm3> --profile default [--orchestration] [--emit <backend>] [--benchmark <suite>]`
+//! `arvak eval --input <circuit.qasm3> --profile default [--orchestration] [--emit <backend>] [--benchmark <suite>] [--noise-profile <profile.json>]`
 
+use anyhow::Context;
 use console::style;
 use std::path::Path;
 
+use arvak_compile::{OptimizationLevel, PassOutcome};
 use arvak_eval::{EvalConfig, Evaluator};
+use arvak_hal::Provenance;
+use arvak_hal::provenance::Stage;
+use arvak_ir::noise::NoiseProfile;
+
+use super::common::compile_budget_duration;
+use super::trace;
 
 /// Execute the eval command.
 #[allow(clippy::too_many_arguments)]
@@ -13,7 +21,10 @@ pub async fn execute(
     input: &str,
     profile: &str,
     target: &str,
-    optimization_level: u8,
+    optimization_level: OptimizationLevel,
+    compile_budget: Option<f64>,
+    seed: Option<u64>,
+    deterministic: bool,
     output: Option<&str>,
     target_qubits: u32,
     orchestration: bool,
@@ -21,11 +32,28 @@ pub async fn execute(
     emit: Option<&str>,
     benchmark: Option<&str>,
     benchmark_qubits: Option<usize>,
+    noise_profile: Option<&str>,
 ) -> anyhow::Result<()> {
     // Build config from CLI args
+    let time_budget = compile_budget_duration(optimization_level, compile_budget)?;
+    let (optimization_level, compile_budget) = match optimization_level {
+        OptimizationLevel::Fixed(level) => (level, None),
+        OptimizationLevel::Auto => (1, time_budget),
+    };
+    let noise_profile = noise_profile
+        .map(|path| -> anyhow::Result<NoiseProfile> {
+            let json = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read noise profile: {path}"))?;
+            serde_json::from_str(&json).with_context(|| format!("Invalid noise profile: {path}"))
+        })
+        .transpose()?;
     let config = EvalConfig {
         profile: profile.into(),
         optimization_level,
+        compile_budget,
+        time_budget,
+        seed,
+        deterministic,
         target: target.into(),
         target_qubits,
         orchestration,
@@ -33,6 +61,7 @@ pub async fn execute(
         emit_target: emit.map(std::string::ToString::to_string),
         benchmark: benchmark.map(std::string::ToString::to_string),
         benchmark_qubits,
+        noise_profile,
         ..Default::default()
     };
 
@@ -65,6 +94,27 @@ pub async fn execute(
         "  Input:       {} qubits, {} ops, depth {}",
         report.input.num_qubits, report.input.total_ops, report.input.depth
     );
+    eprintln!(
+        "  Structure:   {} workload, {:.0}% Clifford{}",
+        report.input.structure.workload,
+        report.input.structure.clifford_fraction * 100.0,
+        report
+            .input
+            .structure
+            .repeated_layer
+            .as_ref()
+            .map(|l| format!(
+                ", {}x repeated {}-gate layer",
+                l.repetitions, l.gates_per_layer
+            ))
+            .unwrap_or_default(),
+    );
+    if let Some(reason) = &report.compilation.adaptive_reason {
+        eprintln!(
+            "  Level:       {} (auto: {reason})",
+            report.compilation.optimization_level.unwrap_or_default()
+        );
+    }
     eprintln!(
         "  Compiled:    depth {} -> {}, ops {} -> {}",
         report.compilation.initial.depth,
@@ -72,6 +122,58 @@ pub async fn execute(
         report.compilation.initial.total_ops,
         report.compilation.final_snapshot.total_ops,
     );
+    if let Some(budget_ms) = report.compilation.time_budget_ms {
+        let timings = &report.compilation.pass_timings;
+        let spent_ms: f64 = timings.iter().map(|p| p.elapsed_ms).sum();
+        let stopped_early = timings.iter().any(|p| {
+            matches!(
+                p.outcome,
+                PassOutcome::SkippedOverBudget | PassOutcome::CutShort
+            )
+        });
+        eprintln!(
+            "  Budget:      {spent_ms:.0} of {budget_ms:.0} ms{}",
+            if stopped_early {
+                " (optimization stopped early)"
+            } else {
+                ""
+            }
+        );
+    }
+    if let Some(seed) = report.compilation.seed {
+        eprintln!(
+            "  Seed:        {seed}{}",
+            if deterministic {
+                " (deterministic)"
+            } else {
+                ""
+            }
+        );
+    }
+    // Error budget (if a noise profile was given)
+    if let Some(ref budget) = report.error_budget {
+        eprintln!();
+        eprintln!("{}", style("Error Budget").bold().underlined());
+        eprintln!(
+            "  Fidelity:    {:.4} estimated (total error {:.4})",
+            budget.estimated_fidelity, budget.total_error,
+        );
+        for entry in budget.top(3) {
+            let qubits: Vec<String> = entry.qubits.iter().map(|q| format!("q{q}")).collect();
+            eprintln!(
+                "  {:<12} {} x {:.2e} = {:.4} ({:.0}%)",
+                format!("{} {}", entry.operation, qubits.join(",")),
+                entry.count,
+                entry.error_rate,
+                entry.contribution,
+                entry.fraction * 100.0,
+            );
+        }
+        if !budget.unknown_gates.is_empty() {
+            eprintln!("  Unknown:     {}", budget.unknown_gates.join(", "));
+        }
+    }
+
     // Orchestration summary (if enabled)
     if let Some(ref orch) = report.orchestration {
         eprintln!();
@@ -84,7 +186,7 @@ pub async fn execute(
             orch.summary.total_edges,
         );
         eprintln!(
-            "  Critical:    path length {}, cost {:.1}",
+            "  Critical:    path length {}, cost {:.2} µs",
             orch.critical_path.node_indices.len(),
             orch.critical_path.total_cost,
         );
@@ -108,6 +210,10 @@ pub async fn execute(
             sched.fitness_score,
             sched.walltime.batch_capacity,
         );
+        eprintln!(
+            "  Energy:      {:.4} node-h, {:.4} kWh, {:.4} kg CO2e (estimate)",
+            sched.energy.node_hours, sched.energy.energy_kwh, sched.energy.co2_kg,
+        );
         eprintln!("  Assessment:  {}", sched.assessment);
     }
 
@@ -168,5 +274,25 @@ pub async fn execute(
 
     eprintln!("  Profile:     {}", report.profile);
 
+    if let Some(provenance) = &report.provenance {
+        eprintln!("  Artifact:    {}", provenance.artifact_id);
+        if let Some(source) = provenance.parent() {
+            trace::record(
+                &Provenance {
+                    artifact_id: source.clone(),
+                    stage: Stage::Source,
+                    ancestors: Vec::new(),
+                },
+                "eval",
+                input,
+            );
+        }
+        trace::record(
+            provenance,
+            "eval",
+            format!("{input} (profile {profile}, target {target})"),
+        );
+    }
+
     Ok(())
 }
@@ -1,13 +1,23 @@
 //! CLI command implementations.
 
+pub mod admin;
 pub mod auth;
 pub mod backends;
+pub mod campaign;
 pub mod common;
 pub mod compile;
+pub mod completions;
 pub mod eval;
+pub mod qdmi;
 pub mod result;
 pub mod run;
+pub mod scheduler;
+pub mod schema;
+pub mod smoke;
 pub mod status;
 pub mod submit;
+pub mod top;
+pub mod trace;
+pub mod usage;
 pub mod version;
 pub mod wait;
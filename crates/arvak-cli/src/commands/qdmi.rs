@@ -0,0 +1,453 @@
+//! QDMI device catalog.
+//!
+//! `arvak qdmi list` loads every configured QDMI device library and prints
+//! one line per device; `arvak qdmi inspect` prints the sites (with T1/T2),
+//! operations and coupling map of one device. Both print tables or JSON.
+//!
+//! Device libraries come from `--device PREFIX=PATH` arguments and from a
+//! driver configuration file, `$ARVAK_QDMI_CONFIG` or `~/.arvak/qdmi.conf`,
+//! which lists one device per line as `PREFIX PATH`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+
+use arvak_qdmi::{DeviceCapabilities, DeviceSession, QdmiDevice, SiteId, ffi};
+
+/// A QDMI device library and the prefix of its symbols.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceLibrary {
+    pub prefix: String,
+    pub path: PathBuf,
+}
+
+/// Parse a `--device PREFIX=PATH` argument.
+pub fn parse_device(arg: &str) -> std::result::Result<DeviceLibrary, String> {
+    let (prefix, path) = arg
+        .split_once('=')
+        .filter(|(prefix, path)| !prefix.trim().is_empty() && !path.trim().is_empty())
+        .ok_or_else(|| format!("expected PREFIX=PATH, got '{arg}'"))?;
+    Ok(DeviceLibrary {
+        prefix: prefix.trim().to_string(),
+        path: PathBuf::from(path.trim()),
+    })
+}
+
+/// Parse a driver configuration: one `PREFIX PATH` per line, with blank
+/// lines and `#` comments ignored.
+fn parse_config(text: &str, origin: &Path) -> Result<Vec<DeviceLibrary>> {
+    let mut libraries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((prefix, path)) = line.split_once(char::is_whitespace) else {
+            anyhow::bail!(
+                "{}:{}: expected 'PREFIX PATH', got '{line}'",
+                origin.display(),
+                number + 1
+            );
+        };
+        libraries.push(DeviceLibrary {
+            prefix: prefix.to_string(),
+            path: PathBuf::from(path.trim()),
+        });
+    }
+    Ok(libraries)
+}
+
+/// The device libraries to catalog: those given on the command line, then
+/// those of the driver configuration (`config`, or else
+/// `$ARVAK_QDMI_CONFIG`, or else `~/.arvak/qdmi.conf` when it exists).
+fn device_libraries(devices: &[DeviceLibrary], config: Option<&str>) -> Result<Vec<DeviceLibrary>> {
+    let config = match config {
+        Some(path) => Some(PathBuf::from(path)),
+        None => match std::env::var_os("ARVAK_QDMI_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::home_dir()
+                .map(|home| home.join(".arvak/qdmi.conf"))
+                .filter(|path| path.exists()),
+        },
+    };
+
+    let mut libraries = devices.to_vec();
+    if let Some(path) = config {
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read QDMI configuration {}", path.display()))?;
+        libraries.extend(parse_config(&text, &path)?);
+    }
+    if libraries.is_empty() {
+        anyhow::bail!(
+            "No QDMI devices configured: pass --device PREFIX=PATH or list devices in \
+             ~/.arvak/qdmi.conf"
+        );
+    }
+    Ok(libraries)
+}
+
+/// Everything the catalog reports about one device.
+#[derive(Debug, Serialize)]
+struct DeviceInfo {
+    prefix: String,
+    library: String,
+    name: String,
+    version: Option<String>,
+    status: Option<String>,
+    num_qubits: usize,
+    supports_jobs: bool,
+    formats: Vec<String>,
+    sites: Vec<SiteInfo>,
+    operations: Vec<OperationInfo>,
+    /// Directed edges between site indices.
+    coupling_map: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Serialize)]
+struct SiteInfo {
+    index: usize,
+    name: Option<String>,
+    t1_us: Option<f64>,
+    t2_us: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct OperationInfo {
+    name: Option<String>,
+    num_qubits: Option<usize>,
+    num_parameters: Option<usize>,
+    duration_ns: Option<f64>,
+    fidelity: Option<f64>,
+}
+
+/// One line of `qdmi list`: a device, or the reason its library failed.
+#[derive(Debug, Serialize)]
+struct DeviceSummary {
+    prefix: String,
+    library: String,
+    name: Option<String>,
+    status: Option<String>,
+    num_qubits: Option<usize>,
+    num_operations: Option<usize>,
+    num_edges: Option<usize>,
+    error: Option<String>,
+}
+
+impl DeviceInfo {
+    fn new(library: &DeviceLibrary, device: &QdmiDevice, caps: &DeviceCapabilities) -> Self {
+        // Sites are identified by handles that are only valid within the
+        // session; report them by index, falling back to their position.
+        let index_of: HashMap<SiteId, usize> = caps
+            .sites
+            .iter()
+            .enumerate()
+            .map(|(position, site)| {
+                let index = caps
+                    .site_properties
+                    .get(site)
+                    .and_then(|p| p.index)
+                    .unwrap_or(position);
+                (*site, index)
+            })
+            .collect();
+
+        let mut sites: Vec<SiteInfo> = caps
+            .sites
+            .iter()
+            .map(|site| {
+                let props = caps.site_properties.get(site).cloned().unwrap_or_default();
+                SiteInfo {
+                    index: index_of[site],
+                    name: props.name,
+                    t1_us: props.t1.map(|t| t.as_secs_f64() * 1e6),
+                    t2_us: props.t2.map(|t| t.as_secs_f64() * 1e6),
+                }
+            })
+            .collect();
+        sites.sort_by_key(|s| s.index);
+
+        let mut operations: Vec<OperationInfo> = caps
+            .operations
+            .iter()
+            .map(|op| {
+                let props = caps
+                    .operation_properties
+                    .get(op)
+                    .cloned()
+                    .unwrap_or_default();
+                OperationInfo {
+                    name: props.name,
+                    num_qubits: props.num_qubits,
+                    num_parameters: props.num_parameters,
+                    duration_ns: props.duration.map(|d| d.as_secs_f64() * 1e9),
+                    fidelity: props.fidelity,
+                }
+            })
+            .collect();
+        operations.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let coupling_map = caps
+            .coupling_map
+            .edges()
+            .iter()
+            .filter_map(|(a, b)| Some((*index_of.get(a)?, *index_of.get(b)?)))
+            .collect();
+
+        Self {
+            prefix: library.prefix.clone(),
+            library: library.path.display().to_string(),
+            name: caps.name.clone(),
+            version: caps.version.clone(),
+            status: caps.status.map(status_name),
+            num_qubits: caps.num_qubits,
+            supports_jobs: device.supports_jobs(),
+            formats: caps
+                .supported_formats
+                .iter()
+                .map(|f| f.as_device_string().to_string())
+                .collect(),
+            sites,
+            operations,
+            coupling_map,
+        }
+    }
+
+    fn summary(&self) -> DeviceSummary {
+        DeviceSummary {
+            prefix: self.prefix.clone(),
+            library: self.library.clone(),
+            name: Some(self.name.clone()),
+            status: self.status.clone(),
+            num_qubits: Some(self.num_qubits),
+            num_operations: Some(self.operations.len()),
+            num_edges: Some(self.coupling_map.len()),
+            error: None,
+        }
+    }
+}
+
+/// Load `library`, open a session and query the device.
+fn query_device(library: &DeviceLibrary) -> Result<DeviceInfo> {
+    let device = QdmiDevice::load(&library.path, &library.prefix).with_context(|| {
+        format!(
+            "Failed to load QDMI device '{}' from {}",
+            library.prefix,
+            library.path.display()
+        )
+    })?;
+    let session = DeviceSession::open(&device)
+        .with_context(|| format!("Failed to open a session on '{}'", library.prefix))?;
+    let caps = DeviceCapabilities::query(&session)
+        .with_context(|| format!("Failed to query device '{}'", library.prefix))?;
+    Ok(DeviceInfo::new(library, &device, &caps))
+}
+
+/// Name of a QDMI device status code.
+fn status_name(code: i32) -> String {
+    match code {
+        ffi::QDMI_DEVICE_STATUS_OFFLINE => "offline".into(),
+        ffi::QDMI_DEVICE_STATUS_IDLE => "idle".into(),
+        ffi::QDMI_DEVICE_STATUS_BUSY => "busy".into(),
+        ffi::QDMI_DEVICE_STATUS_ERROR => "error".into(),
+        ffi::QDMI_DEVICE_STATUS_MAINTENANCE => "maintenance".into(),
+        ffi::QDMI_DEVICE_STATUS_CALIBRATION => "calibration".into(),
+        other => format!("unknown ({other})"),
+    }
+}
+
+/// Format an optional value, or `-` when it is missing.
+fn or_dash<T>(value: Option<T>, format: impl Fn(T) -> String) -> String {
+    value.map_or_else(|| "-".to_string(), format)
+}
+
+/// Execute the `qdmi list` command.
+pub fn execute_list(devices: &[DeviceLibrary], config: Option<&str>, format: &str) -> Result<()> {
+    let libraries = device_libraries(devices, config)?;
+    let summaries: Vec<DeviceSummary> = libraries
+        .iter()
+        .map(|library| match query_device(library) {
+            Ok(info) => info.summary(),
+            Err(e) => DeviceSummary {
+                prefix: library.prefix.clone(),
+                library: library.path.display().to_string(),
+                name: None,
+                status: None,
+                num_qubits: None,
+                num_operations: None,
+                num_edges: None,
+                error: Some(format!("{e:#}")),
+            },
+        })
+        .collect();
+
+    if format == "json" {
+        let json = serde_json::to_string_pretty(&summaries)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    println!(
+        "{} QDMI devices ({}):\n",
+        style("→").cyan().bold(),
+        summaries.len()
+    );
+    println!(
+        "  {:<10}  {:<24}  {:<12}  {:>6}  {:>4}  {:>5}  {}",
+        style("PREFIX").bold(),
+        style("NAME").bold(),
+        style("STATUS").bold(),
+        style("QUBITS").bold(),
+        style("OPS").bold(),
+        style("EDGES").bold(),
+        style("LIBRARY").bold()
+    );
+    println!("  {}", "-".repeat(90));
+    for summary in &summaries {
+        if let Some(error) = &summary.error {
+            println!(
+                "  {:<10}  {}",
+                style(&summary.prefix).cyan(),
+                style(error).red()
+            );
+            continue;
+        }
+        println!(
+            "  {:<10}  {:<24}  {:<12}  {:>6}  {:>4}  {:>5}  {}",
+            style(&summary.prefix).cyan(),
+            summary.name.as_deref().unwrap_or("-"),
+            summary.status.as_deref().unwrap_or("-"),
+            or_dash(summary.num_qubits, |n| n.to_string()),
+            or_dash(summary.num_operations, |n| n.to_string()),
+            or_dash(summary.num_edges, |n| n.to_string()),
+            summary.library
+        );
+    }
+    Ok(())
+}
+
+/// Execute the `qdmi inspect` command for the device with prefix or name
+/// `device`.
+pub fn execute_inspect(
+    device: &str,
+    devices: &[DeviceLibrary],
+    config: Option<&str>,
+    format: &str,
+) -> Result<()> {
+    let libraries = device_libraries(devices, config)?;
+
+    // Match the prefix first, which needs no library to be loaded; fall
+    // back to the device names.
+    let info = match libraries
+        .iter()
+        .find(|l| l.prefix.eq_ignore_ascii_case(device))
+    {
+        Some(library) => query_device(library)?,
+        None => libraries
+            .iter()
+            .filter_map(|library| query_device(library).ok())
+            .find(|info| info.name == device)
+            .ok_or_else(|| {
+                let prefixes: Vec<&str> = libraries.iter().map(|l| l.prefix.as_str()).collect();
+                anyhow::anyhow!(
+                    "No QDMI device '{device}' (configured: {})",
+                    prefixes.join(", ")
+                )
+            })?,
+    };
+
+    if format == "json" {
+        let json = serde_json::to_string_pretty(&info)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    print_device(&info);
+    Ok(())
+}
+
+fn print_device(info: &DeviceInfo) {
+    println!(
+        "{} {} ({}){}",
+        style("→").cyan().bold(),
+        style(&info.name).green(),
+        info.prefix,
+        info.version
+            .as_deref()
+            .map(|v| format!(", version {v}"))
+            .unwrap_or_default()
+    );
+    println!("  Library: {}", info.library);
+    println!("  Status: {}", info.status.as_deref().unwrap_or("-"));
+    println!("  Qubits: {}", info.num_qubits);
+    println!(
+        "  Jobs: {}",
+        if info.supports_jobs {
+            "supported"
+        } else {
+            "not supported"
+        }
+    );
+    if !info.formats.is_empty() {
+        println!("  Formats: {}", info.formats.join(", "));
+    }
+
+    println!("\n  {} ({}):", style("Sites").bold(), info.sites.len());
+    println!(
+        "  {:>5}  {:<16}  {:>10}  {:>10}",
+        style("INDEX").bold(),
+        style("NAME").bold(),
+        style("T1 (µs)").bold(),
+        style("T2 (µs)").bold()
+    );
+    println!("  {}", "-".repeat(46));
+    for site in &info.sites {
+        println!(
+            "  {:>5}  {:<16}  {:>10}  {:>10}",
+            site.index,
+            site.name.as_deref().unwrap_or("-"),
+            or_dash(site.t1_us, |t| format!("{t:.1}")),
+            or_dash(site.t2_us, |t| format!("{t:.1}"))
+        );
+    }
+
+    println!(
+        "\n  {} ({}):",
+        style("Operations").bold(),
+        info.operations.len()
+    );
+    println!(
+        "  {:<12}  {:>6}  {:>6}  {:>13}  {:>8}",
+        style("NAME").bold(),
+        style("QUBITS").bold(),
+        style("PARAMS").bold(),
+        style("DURATION (ns)").bold(),
+        style("FIDELITY").bold()
+    );
+    println!("  {}", "-".repeat(53));
+    for op in &info.operations {
+        println!(
+            "  {:<12}  {:>6}  {:>6}  {:>13}  {:>8}",
+            op.name.as_deref().unwrap_or("-"),
+            or_dash(op.num_qubits, |n| n.to_string()),
+            or_dash(op.num_parameters, |n| n.to_string()),
+            or_dash(op.duration_ns, |d| format!("{d:.1}")),
+            or_dash(op.fidelity, |f| format!("{f:.5}"))
+        );
+    }
+
+    println!(
+        "\n  {} ({} edges):",
+        style("Coupling map").bold(),
+        info.coupling_map.len()
+    );
+    for chunk in info.coupling_map.chunks(8) {
+        let edges: Vec<String> = chunk.iter().map(|(a, b)| format!("{a}→{b}")).collect();
+        println!("    {}", edges.join("  "));
+    }
+}
@@ -1,33 +1,17 @@
 //! Run command implementation.
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
 
-use arvak_adapter_sim::SimulatorBackend;
-use arvak_compile::PassManagerBuilder;
-use arvak_hal::Backend;
-use arvak_ir::Circuit;
-
-#[cfg(feature = "iqm")]
-use arvak_adapter_iqm::IqmBackend;
-
-#[cfg(feature = "ibm")]
-use arvak_adapter_ibm::IbmBackend;
-
-#[cfg(feature = "braket")]
-use arvak_adapter_braket::BraketBackend;
-
-#[cfg(feature = "scaleway")]
-use arvak_adapter_scaleway::ScalewayBackend;
-
-#[cfg(feature = "quantinuum")]
-use arvak_adapter_quantinuum::QuantinuumBackend;
+use arvak_hal::provenance::Stage;
+use arvak_hal::{Heartbeat, HeartbeatSender, Target, ValidationIssue};
+use tokio::sync::broadcast::error::RecvError;
 
-#[cfg(feature = "ddsim")]
-use arvak_adapter_ddsim::DdsimBackend;
-
-use super::common::{get_basis_gates, load_circuit, print_results};
+use super::common::{create_backend, get_basis_gates, load_circuit_with_provenance, print_results};
+use super::{backends, trace};
 
 /// Execute the run command.
 pub async fn execute(
@@ -36,6 +20,7 @@ pub async fn execute(
     backend: &str,
     do_compile: bool,
     target: Option<&str>,
+    params: &[(String, f64)],
 ) -> Result<()> {
     println!(
         "{} Running {} on {} ({} shots)",
@@ -46,157 +31,43 @@ pub async fn execute(
     );
 
     // Load circuit
-    let mut circuit = load_circuit(input)?;
+    let (mut circuit, mut provenance) = load_circuit_with_provenance(input)?;
+    if provenance.stage == Stage::Source {
+        trace::record(&provenance, "run", input);
+    }
     println!(
         "  Loaded: {} qubits, depth {}",
         circuit.num_qubits(),
         circuit.depth()
     );
 
+    // Bind input parameters before compiling, so the compiler sees numbers
+    if !params.is_empty() || !circuit.parameters().is_empty() {
+        let values: HashMap<String, f64> = params.iter().cloned().collect();
+        circuit = circuit.bind_parameters(&values)?;
+        println!("  Bound {} parameter(s)", values.len());
+    }
+
     // Create backend FIRST so we can extract real topology for compilation
-    let backend_impl: Box<dyn Backend> = match backend.to_lowercase().as_str() {
-        "simulator" | "sim" => Box::new(SimulatorBackend::new()),
-        #[cfg(feature = "ddsim")]
-        "ddsim" | "mqt-ddsim" | "mqt_ddsim" => {
-            println!("  Checking MQT DDSIM availability...");
-            Box::new(DdsimBackend::new())
-        }
-        #[cfg(not(feature = "ddsim"))]
-        "ddsim" | "mqt-ddsim" | "mqt_ddsim" => {
-            anyhow::bail!("DDSIM backend not available. Rebuild with --features ddsim");
-        }
-        #[cfg(feature = "iqm")]
-        "iqm" | "garnet" => {
-            println!("  Connecting to IQM Resonance...");
-            match IqmBackend::new() {
-                Ok(b) => Box::new(b),
-                Err(e) => {
-                    anyhow::bail!(
-                        "Failed to connect to IQM: {}. Set IQM_TOKEN environment variable.",
-                        e
-                    );
-                }
-            }
-        }
-        #[cfg(not(feature = "iqm"))]
-        "iqm" | "garnet" => {
-            anyhow::bail!("IQM backend not available. Rebuild with --features iqm");
-        }
-        #[cfg(feature = "ibm")]
-        "ibm" | "ibmq" | "ibm_torino" | "ibm_fez" | "ibm_marrakesh" | "ibm_brisbane"
-        | "ibm_kyoto" | "ibm_osaka" => {
-            println!("  Connecting to IBM Quantum...");
-            match IbmBackend::connect(backend).await {
-                Ok(mut b) => {
-                    if do_compile {
-                        b.set_skip_transpilation(true);
-                    }
-                    Box::new(b)
-                }
-                Err(e) => {
-                    anyhow::bail!(
-                        "Failed to connect to IBM Quantum: {}. Set IBM_API_KEY + IBM_SERVICE_CRN (or IBM_QUANTUM_TOKEN).",
-                        e
-                    );
-                }
-            }
-        }
-        #[cfg(not(feature = "ibm"))]
-        "ibm" | "ibmq" | "ibm_torino" | "ibm_fez" | "ibm_marrakesh" | "ibm_brisbane"
-        | "ibm_kyoto" | "ibm_osaka" => {
-            anyhow::bail!("IBM backend not available. Rebuild with --features ibm");
-        }
-        #[cfg(feature = "braket")]
-        "braket" | "braket-sv1" | "sv1" | "braket-tn1" | "tn1" | "braket-dm1" | "dm1"
-        | "rigetti" | "ankaa" | "ionq" | "aria" => {
-            println!("  Connecting to AWS Braket...");
-            let device_arn = arvak_adapter_braket::device::arn_for_name(backend)
-                .ok_or_else(|| anyhow::anyhow!("Unknown Braket device: {backend}"))?;
-            match BraketBackend::connect(device_arn).await {
-                Ok(b) => Box::new(b),
-                Err(e) => {
-                    anyhow::bail!(
-                        "Failed to connect to AWS Braket: {}. Set ARVAK_BRAKET_S3_BUCKET and configure AWS credentials.",
-                        e
-                    );
-                }
-            }
-        }
-        #[cfg(not(feature = "braket"))]
-        "braket" | "braket-sv1" | "sv1" | "braket-tn1" | "tn1" | "braket-dm1" | "dm1"
-        | "rigetti" | "ankaa" | "ionq" | "aria" => {
-            anyhow::bail!("Braket backend not available. Rebuild with --features braket");
-        }
-        #[cfg(feature = "scaleway")]
-        "scaleway" | "scaleway-garnet" | "scaleway-emerald" => {
-            println!("  Connecting to Scaleway QaaS...");
-            match ScalewayBackend::new() {
-                Ok(b) => {
-                    println!("  Session: {}, Platform: {}", b.session_id(), b.platform());
-                    Box::new(b)
-                }
-                Err(e) => {
-                    anyhow::bail!(
-                        "Failed to connect to Scaleway: {}. Set SCALEWAY_SECRET_KEY, SCALEWAY_PROJECT_ID, and SCALEWAY_SESSION_ID.",
-                        e
-                    );
-                }
-            }
-        }
-        #[cfg(not(feature = "scaleway"))]
-        "scaleway" | "scaleway-garnet" | "scaleway-emerald" => {
-            anyhow::bail!("Scaleway backend not available. Rebuild with --features scaleway");
-        }
-        #[cfg(feature = "quantinuum")]
-        "quantinuum" | "quantinuum-h2" | "h2-1le" | "h2-1e" | "h1-1e" | "h2-1" | "h1-1" => {
-            println!("  Connecting to Quantinuum...");
-            let machine = match backend.to_lowercase().as_str() {
-                "quantinuum" | "quantinuum-h2" | "h2-1le" => "H2-1LE",
-                "h2-1e" => "H2-1E",
-                "h1-1e" => "H1-1E",
-                "h2-1" => "H2-1",
-                "h1-1" => "H1-1",
-                _ => "H2-1LE",
-            };
-            match QuantinuumBackend::with_target(machine) {
-                Ok(b) => Box::new(b),
-                Err(e) => {
-                    anyhow::bail!(
-                        "Failed to connect to Quantinuum: {}. \
-                         Set QUANTINUUM_EMAIL and QUANTINUUM_PASSWORD environment variables.",
-                        e
-                    );
-                }
-            }
-        }
-        #[cfg(not(feature = "quantinuum"))]
-        "quantinuum" | "quantinuum-h2" | "h2-1le" | "h2-1e" | "h1-1e" | "h2-1" | "h1-1" => {
-            anyhow::bail!("Quantinuum backend not available. Rebuild with --features quantinuum");
-        }
-        other => {
-            anyhow::bail!(
-                "Unknown backend: '{other}'. Available: simulator, ddsim, iqm, ibm, braket, scaleway, quantinuum"
-            );
-        }
-    };
+    let backend_impl = create_backend(backend, do_compile).await?;
 
-    // Compile if requested — use real topology from HAL capabilities
+    // Compile if requested — derive the target from the HAL capabilities;
+    // an explicit --target only overrides the basis gates
     if do_compile {
         let compile_target = target.unwrap_or(backend);
         println!("  Compiling for target: {}", style(compile_target).yellow());
 
-        let caps = backend_impl.capabilities();
-        let coupling_map =
-            arvak_compile::CouplingMap::from_edge_list(caps.num_qubits, &caps.topology.edges);
-        let basis_gates = get_basis_gates(compile_target)?;
-        let (pm, mut props) = PassManagerBuilder::new()
-            .with_optimization_level(1)
-            .with_target(coupling_map, basis_gates)
-            .build();
-
-        let mut dag = circuit.into_dag();
-        pm.run(&mut dag, &mut props)?;
-        circuit = Circuit::from_dag(dag);
+        let mut hal_target = Target::from_backend(backend_impl.as_ref());
+        if let Some(target) = target {
+            hal_target.basis_gates = get_basis_gates(target)?;
+        }
+        circuit = arvak_compile::transpile(&circuit, &hal_target.into(), 1)?;
+        provenance = provenance.compiled(circuit.dag());
+        trace::record(
+            &provenance,
+            "run",
+            format!("{input} compiled for {compile_target}"),
+        );
 
         println!(
             "  Compiled: depth {}, {} ops",
@@ -211,6 +82,22 @@ pub async fn execute(
         anyhow::bail!("Backend '{backend}' is not available");
     }
 
+    // Coherence budget, when the backend reports live T1/T2 data
+    let report = backend_impl.validation_report(&circuit, shots);
+    for warning in &report.warnings {
+        println!("  {} {warning}", style("!").yellow().bold());
+    }
+    if let Some(issue) = report
+        .issues
+        .iter()
+        .find(|issue| matches!(issue, ValidationIssue::CoherenceBudget { .. }))
+    {
+        anyhow::bail!("Circuit exceeds the coherence budget of '{backend}': {issue}");
+    }
+
+    // Hardware attestation, when the backend was enrolled
+    let attestation = backends::attest_enrolled(backend_impl.as_ref()).await?;
+
     // Submit job
     let spinner = ProgressBar::new_spinner();
     spinner.set_style(
@@ -221,15 +108,74 @@ pub async fn execute(
     spinner.set_message("Submitting job...");
     spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
-    let job_id = backend_impl.submit(&circuit, shots, None).await?;
-    spinner.set_message(format!("Running job {job_id}..."));
-
-    // Wait for result
-    let result = backend_impl.wait(&job_id).await?;
+    // Shot counts above the backend's max_shots are split into several
+    // jobs and the counts aggregated.
+    let max_shots = backend_impl.capabilities().max_shots;
+    let message = if max_shots > 0 && shots > max_shots {
+        format!("Running {shots} shots in batches of {max_shots}...")
+    } else {
+        "Running job...".to_string()
+    };
+    spinner.set_message(message.clone());
+
+    // Show where a long hardware job is instead of spinning silently.
+    let heartbeats = HeartbeatSender::new();
+    let mut heartbeat_rx = heartbeats.subscribe();
+    let progress = spinner.clone();
+    let heartbeat_task = tokio::spawn(async move {
+        loop {
+            match heartbeat_rx.recv().await {
+                Ok(heartbeat) => progress.set_message(heartbeat_message(&message, &heartbeat)),
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let result = arvak_hal::run_split_with_heartbeats(
+        backend_impl.as_ref(),
+        &circuit,
+        shots,
+        None,
+        &heartbeats,
+    )
+    .await;
+    drop(heartbeats);
+    let _ = heartbeat_task.await;
     spinner.finish_and_clear();
+    let mut result = result?;
+
+    let bindings: HashMap<String, f64> = params.iter().cloned().collect();
+    let provenance = provenance.submission(
+        backend_impl.name(),
+        shots,
+        (!bindings.is_empty()).then_some(&bindings),
+    );
+    provenance.attach(&mut result);
+    if let Some(attestation) = &attestation {
+        attestation.attach(&mut result);
+    }
+    trace::record(
+        &provenance,
+        "run",
+        format!("{input} on {}, {shots} shots", backend_impl.name()),
+    );
 
     // Print results
     print_results(&result);
 
     Ok(())
 }
+
+/// Spinner message for a heartbeat, e.g. `Running job... queued (position 3), 12m05s`.
+fn heartbeat_message(message: &str, heartbeat: &Heartbeat) -> String {
+    let secs = heartbeat.elapsed_secs;
+    let elapsed = if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    };
+    format!("{message} {}, {elapsed}", heartbeat.describe())
+}
@@ -0,0 +1,106 @@
+//! Scheduler command implementations.
+//!
+//! `arvak scheduler simulate` replays a job trace against a site's
+//! constraints and predicts queue wait times and QPU utilization, so a site
+//! can size a reservation before committing hardware time.
+
+use anyhow::Result;
+use console::style;
+
+use arvak_sched::simulation::RejectionReason;
+use arvak_sched::{SiteConstraints, Workload, simulate};
+
+/// Execute the `scheduler simulate` command.
+pub fn execute_simulate(
+    workload: &str,
+    site: &str,
+    site_config: Option<&str>,
+    qpus: Option<u32>,
+    format: &str,
+) -> Result<()> {
+    let mut constraints = match site_config {
+        Some(path) => SiteConstraints::from_file(path)
+            .map_err(|e| anyhow::anyhow!("Failed to load site config '{path}': {e}"))?,
+        None => SiteConstraints::by_name(site)
+            .ok_or_else(|| anyhow::anyhow!("Unknown site: '{site}' (expected lrz or lumi)"))?,
+    };
+    if let Some(n) = qpus {
+        constraints = constraints.with_num_qpus(n);
+    }
+
+    let trace = Workload::from_file(workload)
+        .map_err(|e| anyhow::anyhow!("Failed to load workload '{workload}': {e}"))?;
+    let report =
+        simulate(&trace, &constraints).map_err(|e| anyhow::anyhow!("Simulation failed: {e}"))?;
+
+    if format == "json" {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    println!(
+        "{} Simulated {} job(s) on {} ({} QPU(s), {} qubits, {}s walltime)\n",
+        style("→").cyan().bold(),
+        report.jobs.len(),
+        style(&report.site.name).cyan(),
+        report.site.num_qpus,
+        report.site.max_qubits,
+        report.site.max_walltime_seconds
+    );
+
+    println!(
+        "  {:<24}  {:>10}  {:>10}  {:>10}  {}",
+        style("JOB").bold(),
+        style("SUBMIT").bold(),
+        style("START").bold(),
+        style("WAIT").bold(),
+        style("QPU").bold()
+    );
+    println!("  {}", "-".repeat(68));
+    for job in &report.jobs {
+        match (job.start_at, job.wait_seconds, job.qpu) {
+            (Some(start), Some(wait), Some(qpu)) => println!(
+                "  {:<24}  {:>10.1}  {:>10.1}  {:>10.1}  {}",
+                job.name, job.submit_at, start, wait, qpu
+            ),
+            _ => {
+                let reason = match job.rejected {
+                    Some(RejectionReason::TooManyQubits) => "rejected: too many qubits",
+                    Some(RejectionReason::ExceedsWalltime) => "rejected: exceeds walltime",
+                    None => "not scheduled",
+                };
+                println!(
+                    "  {:<24}  {:>10.1}  {}",
+                    job.name,
+                    job.submit_at,
+                    style(reason).red()
+                );
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "  Completed:     {} ({} rejected)",
+        report.completed, report.rejected
+    );
+    println!("  Makespan:      {:.1}s", report.makespan_seconds);
+    println!(
+        "  Queue wait:    mean {:.1}s, p50 {:.1}s, p95 {:.1}s, max {:.1}s",
+        report.mean_wait_seconds,
+        report.p50_wait_seconds,
+        report.p95_wait_seconds,
+        report.max_wait_seconds
+    );
+    println!("  Peak queue:    {} job(s)", report.peak_queue_length);
+    println!("  Utilization:   {:.1}%", report.utilization * 100.0);
+    if report.qpu_utilization.len() > 1 {
+        for (qpu, util) in report.qpu_utilization.iter().enumerate() {
+            println!("    QPU {qpu}:       {:.1}%", util * 100.0);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Machine-readable description of the command-line interface.
+//!
+//! `arvak --schema` prints every command with its arguments as JSON, so
+//! wrappers (editor extensions, dashboards, HPC job templates) can follow
+//! the CLI surface without parsing `--help` output. `schema_version` is
+//! bumped when the layout of the document changes.
+
+use clap::{Arg, Command};
+use serde::Serialize;
+
+/// Version of the schema layout.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The schema document.
+#[derive(Debug, Serialize)]
+pub struct CliSchema {
+    /// Version of the schema layout.
+    pub schema_version: u32,
+    /// Version of the CLI.
+    pub version: String,
+    /// The root command.
+    #[serde(flatten)]
+    pub command: CommandSchema,
+}
+
+/// One command or subcommand.
+#[derive(Debug, Serialize)]
+pub struct CommandSchema {
+    /// Name as typed on the command line.
+    pub name: String,
+    /// One-line description.
+    pub about: Option<String>,
+    /// Hidden from `--help`, e.g. internal commands.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Arguments, in declaration order.
+    pub args: Vec<ArgSchema>,
+    /// Subcommands, in declaration order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subcommands: Vec<CommandSchema>,
+}
+
+/// One argument: a positional, an option or a flag.
+#[derive(Debug, Serialize)]
+pub struct ArgSchema {
+    /// Argument id.
+    pub id: String,
+    /// Long flag without dashes.
+    pub long: Option<String>,
+    /// Short flag.
+    pub short: Option<char>,
+    /// Help text.
+    pub help: Option<String>,
+    /// Given by position rather than by flag.
+    pub positional: bool,
+    /// Must be given.
+    pub required: bool,
+    /// Takes a value; `false` for flags.
+    pub takes_value: bool,
+    /// May be given more than once.
+    pub multiple: bool,
+    /// Value placeholder, e.g. `PREFIX=PATH`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_name: Option<String>,
+    /// Default values.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub default: Vec<String>,
+    /// Accepted values, when restricted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub possible_values: Vec<String>,
+    /// Environment variable the value is read from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    /// Accepted by every subcommand.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub global: bool,
+}
+
+impl CliSchema {
+    /// Describe `cli` and all its subcommands.
+    pub fn new(cli: &Command) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            version: cli
+                .get_version()
+                .unwrap_or(env!("CARGO_PKG_VERSION"))
+                .to_string(),
+            command: CommandSchema::new(cli),
+        }
+    }
+}
+
+impl CommandSchema {
+    fn new(command: &Command) -> Self {
+        Self {
+            name: command.get_name().to_string(),
+            about: command.get_about().map(ToString::to_string),
+            hidden: command.is_hide_set(),
+            args: command
+                .get_arguments()
+                .filter(|arg| !arg.is_hide_set())
+                .map(ArgSchema::new)
+                .collect(),
+            subcommands: command.get_subcommands().map(Self::new).collect(),
+        }
+    }
+}
+
+impl ArgSchema {
+    fn new(arg: &Arg) -> Self {
+        let takes_value = arg.get_action().takes_values();
+        Self {
+            id: arg.get_id().to_string(),
+            long: arg.get_long().map(ToString::to_string),
+            short: arg.get_short(),
+            help: arg.get_help().map(ToString::to_string),
+            positional: arg.is_positional(),
+            required: arg.is_required_set(),
+            takes_value,
+            multiple: matches!(
+                arg.get_action(),
+                clap::ArgAction::Append | clap::ArgAction::Count
+            ) || arg.get_num_args().is_some_and(|n| n.max_values() > 1),
+            value_name: arg.get_value_names().filter(|_| takes_value).map(|names| {
+                names
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }),
+            default: arg
+                .get_default_values()
+                .iter()
+                .map(|v| v.to_string_lossy().into_owned())
+                .collect(),
+            possible_values: if takes_value {
+                arg.get_possible_values()
+                    .iter()
+                    .filter(|v| !v.is_hide_set())
+                    .map(|v| v.get_name().to_string())
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            env: arg.get_env().map(|env| env.to_string_lossy().into_owned()),
+            global: arg.is_global_set(),
+        }
+    }
+}
+
+/// Execute `arvak --schema`: print the schema of `cli` as JSON.
+pub fn execute(cli: &Command) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(&CliSchema::new(cli))?);
+    Ok(())
+}
@@ -0,0 +1,388 @@
+//! End-to-end smoke suite (`arvak smoke`, hidden).
+//!
+//! Checks run in tiers, each including the ones before it:
+//!
+//! - `simulator`: Bell, compiled GHZ and parameter-binding jobs on the local
+//!   simulator, and a QASM 3 round trip.
+//! - `mock`: the QDMI adapter in mock mode, and the QDMI device libraries
+//!   given with `--qdmi-device`.
+//! - `live`: one small Bell job per live backend whose credentials are set
+//!   in the environment. Backends without credentials, or built without
+//!   their feature, are skipped.
+//!
+//! `--format json` prints a machine-readable report for CI. The command
+//! fails if any check fails; skipped checks do not count as failures.
+
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Instant;
+
+use anyhow::{Context, Result, ensure};
+use console::style;
+use serde::Serialize;
+
+use arvak_adapter_qdmi::QdmiBackend;
+use arvak_adapter_sim::SimulatorBackend;
+use arvak_hal::{Backend, Capabilities, ExecutionResult, ValidationReport, transpile};
+use arvak_ir::{Circuit, ParameterExpression, QubitId};
+use arvak_qdmi::{DeviceCapabilities, DeviceSession, QdmiDevice};
+
+use super::common::create_backend;
+use super::qdmi::DeviceLibrary;
+
+/// Shots per simulator and mock job.
+const LOCAL_SHOTS: u32 = 1000;
+
+/// Live backends: name as accepted by `arvak run --backend`, whether the
+/// adapter is compiled in, and the alternative sets of environment
+/// variables that hold its credentials.
+const LIVE_BACKENDS: &[(&str, bool, &[&[&str]])] = &[
+    ("iqm", cfg!(feature = "iqm"), &[&["IQM_TOKEN"]]),
+    (
+        "ibm",
+        cfg!(feature = "ibm"),
+        &[&["IBM_API_KEY", "IBM_SERVICE_CRN"], &["IBM_QUANTUM_TOKEN"]],
+    ),
+    (
+        "scaleway",
+        cfg!(feature = "scaleway"),
+        &[&[
+            "SCALEWAY_SECRET_KEY",
+            "SCALEWAY_PROJECT_ID",
+            "SCALEWAY_SESSION_ID",
+        ]],
+    ),
+    (
+        "quantinuum",
+        cfg!(feature = "quantinuum"),
+        &[&["QUANTINUUM_EMAIL", "QUANTINUUM_PASSWORD"]],
+    ),
+    (
+        "braket-sv1",
+        cfg!(feature = "braket"),
+        &[&["ARVAK_BRAKET_S3_BUCKET"]],
+    ),
+];
+
+/// How far the suite goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    Simulator,
+    Mock,
+    Live,
+}
+
+impl FromStr for Tier {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "simulator" | "sim" => Ok(Self::Simulator),
+            "mock" => Ok(Self::Mock),
+            "live" => Ok(Self::Live),
+            other => Err(format!(
+                "unknown tier '{other}' (expected simulator, mock or live)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Simulator => f.write_str("simulator"),
+            Self::Mock => f.write_str("mock"),
+            Self::Live => f.write_str("live"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Fail,
+    Skip,
+}
+
+/// Outcome of one check.
+#[derive(Debug, Serialize)]
+struct Check {
+    tier: Tier,
+    name: String,
+    status: Status,
+    duration_ms: u64,
+    detail: String,
+}
+
+/// The report printed with `--format json`.
+#[derive(Debug, Serialize)]
+struct SmokeReport {
+    tier: Tier,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    checks: Vec<Check>,
+}
+
+/// Run `check`, recording how long it took and whether it passed.
+async fn run_check(
+    tier: Tier,
+    name: impl Into<String>,
+    check: impl Future<Output = Result<String>>,
+) -> Check {
+    let start = Instant::now();
+    let (status, detail) = match check.await {
+        Ok(detail) => (Status::Pass, detail),
+        Err(e) => (Status::Fail, format!("{e:#}")),
+    };
+    Check {
+        tier,
+        name: name.into(),
+        status,
+        duration_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+        detail,
+    }
+}
+
+fn skipped(tier: Tier, name: impl Into<String>, reason: impl Into<String>) -> Check {
+    Check {
+        tier,
+        name: name.into(),
+        status: Status::Skip,
+        duration_ms: 0,
+        detail: reason.into(),
+    }
+}
+
+/// Execute the smoke suite up to `tier`.
+pub async fn execute(
+    tier: Tier,
+    qdmi_devices: &[DeviceLibrary],
+    live_shots: u32,
+    format: &str,
+) -> Result<()> {
+    let mut checks = vec![
+        run_check(Tier::Simulator, "bell", simulator_bell()).await,
+        run_check(Tier::Simulator, "ghz3-compiled", simulator_compiled_ghz()).await,
+        run_check(Tier::Simulator, "parameter-binding", simulator_parameters()).await,
+        run_check(Tier::Simulator, "qasm3-roundtrip", qasm3_roundtrip()).await,
+    ];
+
+    if tier >= Tier::Mock {
+        checks.push(run_check(Tier::Mock, "qdmi-adapter", qdmi_adapter_bell()).await);
+        if qdmi_devices.is_empty() {
+            checks.push(skipped(Tier::Mock, "qdmi-device", "no --qdmi-device given"));
+        }
+        for library in qdmi_devices {
+            let name = format!("qdmi-device/{}", library.prefix);
+            checks.push(run_check(Tier::Mock, name, qdmi_device(library)).await);
+        }
+    }
+
+    if tier >= Tier::Live {
+        for &(backend, compiled, credentials) in LIVE_BACKENDS {
+            let name = format!("live/{backend}");
+            if !compiled {
+                checks.push(skipped(
+                    Tier::Live,
+                    name,
+                    format!("built without the {backend} backend"),
+                ));
+                continue;
+            }
+            let configured = credentials
+                .iter()
+                .any(|vars| vars.iter().all(|var| std::env::var_os(var).is_some()));
+            if !configured {
+                let needed: Vec<String> = credentials.iter().map(|vars| vars.join(" + ")).collect();
+                checks.push(skipped(
+                    Tier::Live,
+                    name,
+                    format!("credentials not set ({})", needed.join(" or ")),
+                ));
+                continue;
+            }
+            checks.push(run_check(Tier::Live, name, live_bell(backend, live_shots)).await);
+        }
+    }
+
+    let count = |status: Status| checks.iter().filter(|c| c.status == status).count();
+    let report = SmokeReport {
+        tier,
+        passed: count(Status::Pass),
+        failed: count(Status::Fail),
+        skipped: count(Status::Skip),
+        checks,
+    };
+
+    if format == "json" {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+    } else {
+        print_report(&report);
+    }
+
+    ensure!(
+        report.failed == 0,
+        "{} smoke check(s) failed",
+        report.failed
+    );
+    Ok(())
+}
+
+fn print_report(report: &SmokeReport) {
+    println!(
+        "{} Smoke suite (tier: {})",
+        style("→").cyan().bold(),
+        report.tier
+    );
+    let mut current = None;
+    for check in &report.checks {
+        if current != Some(check.tier) {
+            println!("\n  {}", style(check.tier).bold());
+            current = Some(check.tier);
+        }
+        let mark = match check.status {
+            Status::Pass => style("✓").green(),
+            Status::Fail => style("✗").red(),
+            Status::Skip => style("⊘").yellow(),
+        };
+        println!(
+            "  {} {:<24} {} ({} ms)",
+            mark, check.name, check.detail, check.duration_ms
+        );
+    }
+    println!(
+        "\n  Summary: {} passed, {} failed, {} skipped",
+        style(report.passed).green(),
+        style(report.failed).red(),
+        style(report.skipped).yellow()
+    );
+}
+
+/// Submit `circuit` and wait for its result.
+async fn run_job(backend: &dyn Backend, circuit: &Circuit, shots: u32) -> Result<ExecutionResult> {
+    let job_id = backend.submit(circuit, shots, None).await?;
+    Ok(backend.wait(&job_id).await?)
+}
+
+/// Check that `result` has `shots` shots and that at least `min_fraction`
+/// of them are correlated (every bit equal, as for a Bell or GHZ state).
+fn check_correlated(result: &ExecutionResult, shots: u32, min_fraction: f64) -> Result<String> {
+    let total = result.counts.total_shots();
+    ensure!(
+        total == u64::from(shots),
+        "expected {shots} shots, got {total}"
+    );
+    let correlated: u64 = result
+        .counts
+        .iter()
+        .filter(|(bits, _)| bits.chars().all(|b| b == '0') || bits.chars().all(|b| b == '1'))
+        .map(|(_, count)| *count)
+        .sum();
+    let fraction = correlated as f64 / total as f64;
+    ensure!(
+        fraction >= min_fraction,
+        "only {:.1}% of outcomes correlated (expected at least {:.1}%)",
+        fraction * 100.0,
+        min_fraction * 100.0
+    );
+    Ok(format!(
+        "{:.1}% correlated over {total} shots",
+        fraction * 100.0
+    ))
+}
+
+async fn simulator_bell() -> Result<String> {
+    let result = run_job(&SimulatorBackend::new(), &Circuit::bell()?, LOCAL_SHOTS).await?;
+    check_correlated(&result, LOCAL_SHOTS, 1.0)
+}
+
+/// Compile GHZ-3 for a 5-qubit IQM device, check the compiled circuit
+/// fits the device, then simulate it.
+async fn simulator_compiled_ghz() -> Result<String> {
+    let caps = Capabilities::iqm("smoke", 5);
+    let compiled = transpile(&Circuit::ghz(3)?, &caps, 2)?;
+
+    let report = ValidationReport::check_circuit(&compiled, &caps);
+    ensure!(report.is_valid(), "compiled circuit does not fit: {report}");
+
+    let result = run_job(&SimulatorBackend::new(), &compiled, LOCAL_SHOTS).await?;
+    ensure!(
+        result.counts.len() <= 2,
+        "expected at most 2 outcomes, got {}",
+        result.counts.len()
+    );
+    Ok(format!(
+        "{} ops, depth {}, {} outcomes",
+        compiled.dag().num_ops(),
+        compiled.depth(),
+        result.counts.len()
+    ))
+}
+
+async fn simulator_parameters() -> Result<String> {
+    let mut circuit = Circuit::with_size("rx", 1, 1);
+    circuit.rx(ParameterExpression::symbol("theta"), QubitId(0))?;
+    circuit.measure_all()?;
+
+    let values = [("theta".to_string(), std::f64::consts::PI)].into();
+    let backend = SimulatorBackend::new();
+    let job_id = backend.submit(&circuit, LOCAL_SHOTS, Some(&values)).await?;
+    let result = backend.wait(&job_id).await?;
+    ensure!(
+        result.counts.get("1") == u64::from(LOCAL_SHOTS),
+        "rx(π) should always measure 1, got {:?}",
+        result.counts
+    );
+    Ok("rx(theta = π) measured 1 on every shot".into())
+}
+
+async fn qasm3_roundtrip() -> Result<String> {
+    let circuit = Circuit::ghz(3)?;
+    let source = arvak_qasm3::emit(&circuit)?;
+    let parsed = arvak_qasm3::parse(&source)?;
+    ensure!(
+        parsed.num_qubits() == circuit.num_qubits()
+            && parsed.dag().num_ops() == circuit.dag().num_ops(),
+        "round trip changed the circuit: {} qubits / {} ops, expected {} / {}",
+        parsed.num_qubits(),
+        parsed.dag().num_ops(),
+        circuit.num_qubits(),
+        circuit.dag().num_ops()
+    );
+    Ok(format!("{} bytes of OpenQASM 3", source.len()))
+}
+
+async fn qdmi_adapter_bell() -> Result<String> {
+    let result = run_job(&QdmiBackend::new(), &Circuit::bell()?, LOCAL_SHOTS).await?;
+    check_correlated(&result, LOCAL_SHOTS, 1.0)
+}
+
+async fn qdmi_device(library: &DeviceLibrary) -> Result<String> {
+    let device = QdmiDevice::load(&library.path, &library.prefix)
+        .with_context(|| format!("failed to load {}", library.path.display()))?;
+    let session = DeviceSession::open(&device)?;
+    let caps = DeviceCapabilities::query(&session)?;
+    ensure!(caps.num_qubits > 0, "device reports no qubits");
+    Ok(format!(
+        "{}: {} qubits, {} operations",
+        caps.name,
+        caps.num_qubits,
+        caps.operations.len()
+    ))
+}
+
+/// Compile a Bell circuit for a live backend and run it. Hardware noise is
+/// allowed for, but most shots must still be correlated.
+async fn live_bell(backend: &str, shots: u32) -> Result<String> {
+    let backend = create_backend(backend, true).await?;
+    let circuit = transpile(&Circuit::bell()?, backend.capabilities(), 1)?;
+
+    let result = arvak_hal::run_split(backend.as_ref(), &circuit, shots, None).await?;
+    check_correlated(&result, shots, 0.5)
+}
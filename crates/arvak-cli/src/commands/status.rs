@@ -1,27 +1,37 @@
 //! Status command implementation.
 //!
-//! Query job status from the local scheduler state store.
+//! Query job status from the local scheduler state store, and the
+//! forensics bundles the scheduler files for failed jobs.
 
 use anyhow::Result;
 use console::style;
 
-use arvak_sched::{JobFilter, ScheduledJobId, Scheduler};
+use arvak_hal::FailureBundle;
+use arvak_sched::{HpcScheduler, JobFilter, ScheduledJobId, Scheduler};
 
 use super::common::create_scheduler;
 
 /// Execute the status command.
-pub async fn execute(job_id: Option<&str>, all: bool) -> Result<()> {
+pub async fn execute(job_id: Option<&str>, all: bool, failed: bool, details: bool) -> Result<()> {
     let scheduler = create_scheduler()?;
 
-    if all {
-        // List all jobs
+    if all || failed {
+        // List all jobs, or only the failed ones
+        let filter = if failed {
+            JobFilter {
+                status: Some(vec!["Failed".to_string()]),
+                ..JobFilter::default()
+            }
+        } else {
+            JobFilter::default()
+        };
         let jobs = scheduler
-            .list_jobs(JobFilter::default())
+            .list_jobs(filter)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to list jobs: {e}"))?;
 
         if jobs.is_empty() {
-            println!("No jobs found.");
+            println!("No {}jobs found.", if failed { "failed " } else { "" });
             return Ok(());
         }
 
@@ -57,12 +67,20 @@ pub async fn execute(job_id: Option<&str>, all: bool) -> Result<()> {
             );
         }
 
+        if details {
+            for job in jobs.iter().filter(|job| job.status.name() == "Failed") {
+                println!();
+                print_failure_details(&scheduler, &job.id.to_string())?;
+            }
+        }
+
         return Ok(());
     }
 
     // Single job status
-    let job_id_str = job_id
-        .ok_or_else(|| anyhow::anyhow!("Please provide a job ID or use --all to list all jobs"))?;
+    let job_id_str = job_id.ok_or_else(|| {
+        anyhow::anyhow!("Please provide a job ID, or use --all or --failed to list jobs")
+    })?;
 
     let parsed_id = ScheduledJobId::parse(job_id_str)
         .map_err(|e| anyhow::anyhow!("Invalid job ID '{job_id_str}': {e}"))?;
@@ -99,5 +117,74 @@ pub async fn execute(job_id: Option<&str>, all: bool) -> Result<()> {
         println!("  Terminal: {}", style("yes").dim());
     }
 
+    if details && status_name == "Failed" {
+        println!();
+        print_failure_details(&scheduler, job_id_str)?;
+    }
+
+    Ok(())
+}
+
+/// Print the forensics bundle recorded for a failed job.
+fn print_failure_details(scheduler: &HpcScheduler, job_id: &str) -> Result<()> {
+    let bundle = scheduler
+        .dead_letter()
+        .get(job_id)
+        .map_err(|e| anyhow::anyhow!("Failed to read failure bundle: {e}"))?;
+
+    let Some(bundle) = bundle else {
+        println!(
+            "{} No failure bundle recorded for job {}",
+            style("!").yellow().bold(),
+            style(job_id).dim()
+        );
+        return Ok(());
+    };
+
+    print_bundle(&bundle);
     Ok(())
 }
+
+fn print_bundle(bundle: &FailureBundle) {
+    println!(
+        "{} Failure details for job {}",
+        style("✗").red().bold(),
+        style(&bundle.job_id).dim()
+    );
+    println!(
+        "  Failed at:       {}",
+        bundle.failed_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    println!("  Reason:          {}", bundle.reason);
+    if let Some(backend) = &bundle.backend {
+        println!("  Backend:         {}", style(backend).cyan());
+    }
+    if let Some(id) = &bundle.provider_job_id {
+        println!("  Provider job ID: {}", style(id).yellow());
+    }
+    if let Some(target) = &bundle.target {
+        println!(
+            "  Target:          {} ({} qubits, native gates: {})",
+            target.name,
+            target.num_qubits,
+            target.gate_set.native.join(", ")
+        );
+    }
+    if let Some(error) = &bundle.provider_error {
+        println!("  Provider error:  {error}");
+    }
+
+    for (i, qasm) in bundle.circuits.iter().enumerate() {
+        println!("\n  {} {}:", style("Circuit").bold(), i);
+        for line in qasm.lines() {
+            println!("    {}", style(line).dim());
+        }
+    }
+
+    if !bundle.trace.is_empty() {
+        println!("\n  {}:", style("Trace").bold());
+        for event in &bundle.trace {
+            println!("    {event}");
+        }
+    }
+}
@@ -9,12 +9,14 @@ use console::style;
 
 use arvak_adapter_sim::SimulatorBackend;
 use arvak_hal::Backend;
+use arvak_hal::attestation::ATTESTATION_METADATA_KEY;
 use arvak_sched::{
-    CircuitSpec, HpcScheduler, PbsConfig, Priority, ScheduledJob, Scheduler, SchedulerConfig,
-    SlurmConfig, SqliteStore,
+    CircuitSpec, HpcScheduler, MaintenanceCalendar, PbsConfig, Priority, ScheduledJob, Scheduler,
+    SchedulerConfig, SlurmConfig,
 };
 
-use super::common::{default_state_dir, load_circuit, print_results};
+use super::common::{load_circuit_with_provenance, open_job_store, print_results};
+use super::{backends, trace};
 
 /// Execute the submit command.
 #[allow(clippy::too_many_arguments)]
@@ -27,6 +29,8 @@ pub async fn execute(
     account: Option<&str>,
     time: Option<&str>,
     priority: Option<&str>,
+    maintenance: Option<&str>,
+    params: &[(String, f64)],
     wait: bool,
 ) -> Result<()> {
     println!(
@@ -38,7 +42,7 @@ pub async fn execute(
     );
 
     // Load and convert circuit
-    let circuit = load_circuit(input)?;
+    let (circuit, provenance) = load_circuit_with_provenance(input)?;
     println!(
         "  Loaded: {} qubits, depth {}",
         circuit.num_qubits(),
@@ -49,10 +53,7 @@ pub async fn execute(
         .map_err(|e| anyhow::anyhow!("Failed to create circuit spec: {e}"))?;
 
     // Build scheduler config
-    let state_dir = default_state_dir()?;
-    let db_path = state_dir.join("jobs.db");
-    let store =
-        SqliteStore::new(&db_path).map_err(|e| anyhow::anyhow!("Failed to open job store: {e}"))?;
+    let store = open_job_store()?;
 
     let sched_config = match scheduler.to_lowercase().as_str() {
         "slurm" => {
@@ -122,6 +123,15 @@ pub async fn execute(
         }
     };
 
+    let sched_config = match maintenance {
+        Some(path) => {
+            sched_config.with_maintenance(MaintenanceCalendar::from_file(path).map_err(|e| {
+                anyhow::anyhow!("Failed to load maintenance calendar '{path}': {e}")
+            })?)
+        }
+        None => sched_config,
+    };
+
     // Create backend
     let backend_impl: Arc<dyn Backend> = match backend.to_lowercase().as_str() {
         "simulator" | "sim" => Arc::new(SimulatorBackend::new()),
@@ -175,6 +185,9 @@ pub async fn execute(
         }
     };
 
+    // Hardware attestation, when the backend was enrolled
+    let attestation = backends::attest_enrolled(backend_impl.as_ref()).await?;
+
     // Create HPC scheduler
     let hpc = HpcScheduler::new(sched_config, vec![backend_impl], Arc::new(store))
         .await
@@ -193,9 +206,28 @@ pub async fn execute(
         |s| s.to_string_lossy().to_string(),
     );
 
-    let job = ScheduledJob::new(&name, circuit_spec)
+    let mut job = ScheduledJob::new(&name, circuit_spec)
         .with_shots(shots)
-        .with_priority(job_priority);
+        .with_priority(job_priority)
+        .with_parameters(params.iter().cloned())
+        .with_provenance(provenance.clone());
+
+    if let Some(attestation) = attestation {
+        job = job.with_metadata(
+            ATTESTATION_METADATA_KEY,
+            serde_json::to_string(&attestation)?,
+        );
+    }
+
+    // Warn if planned maintenance will likely interrupt the job
+    for (backend, window) in hpc.maintenance_conflicts(&job).await {
+        println!(
+            "  {} Maintenance on {} overlaps this job's walltime: {}",
+            style("!").yellow().bold(),
+            style(backend).yellow(),
+            window
+        );
+    }
 
     // Submit
     let job_id = hpc
@@ -208,6 +240,12 @@ pub async fn execute(
         style("✓").green().bold(),
         style(&job_id).cyan()
     );
+    println!("  Artifact: {}", style(&provenance.artifact_id).cyan());
+    trace::record(
+        &provenance,
+        "submit",
+        format!("{input} as job {job_id} via {scheduler}"),
+    );
 
     // Optionally wait for completion
     if wait {
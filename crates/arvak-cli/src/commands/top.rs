@@ -0,0 +1,591 @@
+//! Top command implementation.
+//!
+//! `arvak top` is a terminal monitor for sessions without a browser: it
+//! shows backend availability and the job queue, either from the local
+//! job store or from a running dashboard (`--server`), and refreshes
+//! them periodically. Jobs can be inspected and cancelled in place.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Clear, Gauge, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use serde::Deserialize;
+
+use arvak_hal::Backend;
+use arvak_sched::{HpcScheduler, JobFilter, ScheduledJob, ScheduledJobId, Scheduler};
+
+use super::common::{create_backend, create_scheduler};
+
+/// Maximum number of jobs fetched per refresh.
+const JOB_LIMIT: usize = 200;
+
+/// Timeout of requests to a remote dashboard.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long key presses are waited for between redraws.
+const TICK: Duration = Duration::from_millis(200);
+
+/// One backend row.
+#[derive(Debug, Clone, Deserialize)]
+struct BackendRow {
+    name: String,
+    available: bool,
+    #[serde(default)]
+    num_qubits: u32,
+    #[serde(skip)]
+    queue_depth: Option<u32>,
+}
+
+/// One job row, from the local store or the dashboard's job list.
+#[derive(Debug, Clone, Deserialize)]
+struct JobRow {
+    id: String,
+    name: String,
+    status: String,
+    status_details: Option<String>,
+    backend: Option<String>,
+    shots: u32,
+    num_circuits: usize,
+    priority: u32,
+    created_at: String,
+    submitted_at: Option<String>,
+    completed_at: Option<String>,
+}
+
+impl JobRow {
+    fn from_job(job: &ScheduledJob) -> Self {
+        let format = |t: &DateTime<Utc>| t.to_rfc3339();
+        Self {
+            id: job.id.to_string(),
+            name: job.name.clone(),
+            status: job.status.name().to_string(),
+            status_details: Some(job.status.to_string()),
+            backend: job.matched_backend.clone(),
+            shots: job.shots,
+            num_circuits: job.circuits.len(),
+            priority: job.priority.value(),
+            created_at: format(&job.created_at),
+            submitted_at: job.submitted_at.as_ref().map(format),
+            completed_at: job.completed_at.as_ref().map(format),
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self.status.as_str(), "Completed" | "Failed" | "Cancelled")
+    }
+}
+
+/// Fraction of the job lifecycle a status represents, for the progress
+/// column. Failed and cancelled jobs report no progress.
+fn progress(status: &str) -> f64 {
+    match status {
+        "SlurmQueued" => 0.2,
+        "SlurmRunning" => 0.4,
+        "QuantumSubmitted" => 0.6,
+        "QuantumRunning" => 0.8,
+        "Completed" => 1.0,
+        _ => 0.0,
+    }
+}
+
+fn status_color(status: &str) -> Color {
+    match status {
+        "Completed" => Color::Green,
+        "Failed" | "Cancelled" => Color::Red,
+        "Pending" | "WaitingOnDependencies" => Color::Yellow,
+        _ => Color::Cyan,
+    }
+}
+
+/// Timestamps are shown in local-store format without fractional seconds.
+fn short_time(timestamp: &str) -> String {
+    DateTime::parse_from_rfc3339(timestamp).map_or_else(
+        |_| timestamp.to_string(),
+        |t| t.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )
+}
+
+/// Where jobs and backends are read from.
+enum Source {
+    /// The local job store and backends created by name.
+    Local {
+        scheduler: Box<HpcScheduler>,
+        backends: Vec<Box<dyn Backend>>,
+    },
+    /// A dashboard's REST API.
+    Remote {
+        client: reqwest::Client,
+        base_url: String,
+    },
+}
+
+impl Source {
+    fn describe(&self) -> String {
+        match self {
+            Source::Local { .. } => "local job store".to_string(),
+            Source::Remote { base_url, .. } => base_url.clone(),
+        }
+    }
+
+    async fn backends(&self) -> Result<Vec<BackendRow>> {
+        match self {
+            Source::Local { backends, .. } => {
+                let mut rows = Vec::with_capacity(backends.len());
+                for backend in backends {
+                    let availability = backend.availability().await.ok();
+                    rows.push(BackendRow {
+                        name: backend.name().to_string(),
+                        available: availability.as_ref().is_some_and(|a| a.is_available),
+                        num_qubits: backend.capabilities().num_qubits,
+                        queue_depth: availability.and_then(|a| a.queue_depth),
+                    });
+                }
+                Ok(rows)
+            }
+            Source::Remote { client, base_url } => {
+                let url = format!("{base_url}/api/backends");
+                Ok(client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .with_context(|| format!("Failed to query {url}"))?
+                    .json()
+                    .await?)
+            }
+        }
+    }
+
+    async fn jobs(&self) -> Result<Vec<JobRow>> {
+        let mut jobs = match self {
+            Source::Local { scheduler, .. } => scheduler
+                .list_jobs(JobFilter {
+                    limit: Some(JOB_LIMIT),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to list jobs: {e}"))?
+                .iter()
+                .map(JobRow::from_job)
+                .collect(),
+            Source::Remote { client, base_url } => {
+                let url = format!("{base_url}/api/jobs?limit={JOB_LIMIT}");
+                client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .with_context(|| format!("Failed to query {url}"))?
+                    .json::<Vec<JobRow>>()
+                    .await?
+            }
+        };
+        jobs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(jobs)
+    }
+
+    /// Cancel a job. A dashboard cancels and removes it.
+    async fn cancel(&self, id: &str) -> Result<()> {
+        match self {
+            Source::Local { scheduler, .. } => {
+                let id = ScheduledJobId::parse(id)
+                    .map_err(|e| anyhow::anyhow!("Invalid job ID '{id}': {e}"))?;
+                scheduler
+                    .cancel(&id)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to cancel job: {e}"))
+            }
+            Source::Remote { client, base_url } => {
+                let url = format!("{base_url}/api/jobs/{id}");
+                client
+                    .delete(&url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .with_context(|| format!("Failed to cancel job at {url}"))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// What the keyboard currently controls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Browse,
+    Inspect,
+    ConfirmCancel(String),
+}
+
+struct App {
+    source: Source,
+    backends: Vec<BackendRow>,
+    jobs: Vec<JobRow>,
+    table: TableState,
+    mode: Mode,
+    show_all: bool,
+    message: Option<String>,
+    refreshed_at: Option<DateTime<Utc>>,
+}
+
+impl App {
+    fn new(source: Source) -> Self {
+        Self {
+            source,
+            backends: Vec::new(),
+            jobs: Vec::new(),
+            table: TableState::default(),
+            mode: Mode::Browse,
+            show_all: false,
+            message: None,
+            refreshed_at: None,
+        }
+    }
+
+    /// Jobs shown in the table: queued and running ones, or all with
+    /// `show_all`.
+    fn visible_jobs(&self) -> Vec<&JobRow> {
+        self.jobs
+            .iter()
+            .filter(|job| self.show_all || !job.is_terminal())
+            .collect()
+    }
+
+    fn selected_job(&self) -> Option<&JobRow> {
+        let index = self.table.selected()?;
+        self.visible_jobs().get(index).copied()
+    }
+
+    async fn refresh(&mut self) {
+        let selected = self.selected_job().map(|job| job.id.clone());
+
+        let (backends, jobs) = tokio::join!(self.source.backends(), self.source.jobs());
+        self.message = None;
+        match backends {
+            Ok(backends) => self.backends = backends,
+            Err(e) => self.message = Some(format!("{e:#}")),
+        }
+        match jobs {
+            Ok(jobs) => self.jobs = jobs,
+            Err(e) => self.message = Some(format!("{e:#}")),
+        }
+        self.refreshed_at = Some(Utc::now());
+
+        // Keep the selection on the same job as the list changes.
+        let visible = self.visible_jobs();
+        let index = selected
+            .and_then(|id| visible.iter().position(|job| job.id == id))
+            .or_else(|| (!visible.is_empty()).then_some(0));
+        self.table.select(index);
+    }
+
+    fn select_next(&mut self, forward: bool) {
+        let len = self.visible_jobs().len();
+        if len == 0 {
+            self.table.select(None);
+            return;
+        }
+        let index = match (self.table.selected(), forward) {
+            (Some(i), true) => (i + 1).min(len - 1),
+            (Some(i), false) => i.saturating_sub(1),
+            (None, _) => 0,
+        };
+        self.table.select(Some(index));
+    }
+
+    /// Handle a key press. Returns `false` when the monitor should exit.
+    async fn handle_key(&mut self, key: KeyCode) -> bool {
+        match (&self.mode, key) {
+            (Mode::ConfirmCancel(id), KeyCode::Char('y')) => {
+                let id = id.clone();
+                self.mode = Mode::Browse;
+                match self.source.cancel(&id).await {
+                    Ok(()) => {
+                        self.refresh().await;
+                        self.message = Some(format!("Cancelled job {id}"));
+                    }
+                    Err(e) => self.message = Some(format!("{e:#}")),
+                }
+            }
+            (Mode::ConfirmCancel(_), _) | (Mode::Inspect, KeyCode::Esc) => {
+                self.mode = Mode::Browse;
+            }
+            (_, KeyCode::Char('q')) => return false,
+            (Mode::Browse, KeyCode::Esc) => return false,
+            (_, KeyCode::Down | KeyCode::Char('j')) => self.select_next(true),
+            (_, KeyCode::Up | KeyCode::Char('k')) => self.select_next(false),
+            (Mode::Browse, KeyCode::Enter | KeyCode::Char('i')) => {
+                if self.selected_job().is_some() {
+                    self.mode = Mode::Inspect;
+                }
+            }
+            (Mode::Inspect, KeyCode::Enter | KeyCode::Char('i')) => self.mode = Mode::Browse,
+            (_, KeyCode::Char('c')) => match self.selected_job() {
+                Some(job) if job.is_terminal() => {
+                    self.message = Some(format!("Job {} already finished", job.id));
+                }
+                Some(job) => self.mode = Mode::ConfirmCancel(job.id.clone()),
+                None => {}
+            },
+            (_, KeyCode::Char('a')) => {
+                self.show_all = !self.show_all;
+                self.refresh().await;
+            }
+            (_, KeyCode::Char('r')) => self.refresh().await,
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let backend_height = u16::try_from(self.backends.len()).unwrap_or(u16::MAX);
+        let [header, backends, jobs, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(backend_height.saturating_add(3).min(12)),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let refreshed = self
+            .refreshed_at
+            .map(|t| t.format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        frame.render_widget(
+            Line::from(vec![
+                Span::styled("arvak top", Style::new().cyan().bold()),
+                Span::raw(format!(" — {}  ", self.source.describe())),
+                Span::styled(format!("refreshed {refreshed}"), Style::new().dim()),
+            ]),
+            header,
+        );
+
+        self.draw_backends(frame, backends);
+        self.draw_jobs(frame, jobs);
+
+        let footer_line = match (&self.mode, &self.message) {
+            (Mode::ConfirmCancel(id), _) => Line::from(vec![
+                Span::styled(format!("Cancel job {id}? "), Style::new().red().bold()),
+                Span::raw("y to confirm, any other key to abort"),
+            ]),
+            (_, Some(message)) => Line::styled(message.clone(), Style::new().yellow()),
+            _ => Line::styled(
+                "q quit  ↑/↓ select  i/⏎ inspect  c cancel  a all jobs  r refresh",
+                Style::new().dim(),
+            ),
+        };
+        frame.render_widget(footer_line, footer);
+
+        if self.mode == Mode::Inspect {
+            if let Some(job) = self.selected_job().cloned() {
+                draw_inspect(frame, &job);
+            }
+        }
+    }
+
+    fn draw_backends(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.backends.iter().map(|backend| {
+            let (marker, color) = if backend.available {
+                ("● available", Color::Green)
+            } else {
+                ("○ unavailable", Color::Red)
+            };
+            Row::new(vec![
+                Cell::from(backend.name.clone()).bold(),
+                Cell::from(marker).fg(color),
+                Cell::from(backend.num_qubits.to_string()),
+                Cell::from(
+                    backend
+                        .queue_depth
+                        .map_or_else(|| "-".to_string(), |d| d.to_string()),
+                ),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(16),
+                Constraint::Length(14),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(Row::new(["BACKEND", "STATUS", "QUBITS", "QUEUE"]).bold())
+        .block(Block::bordered().title(" Backends "));
+        frame.render_widget(table, area);
+    }
+
+    fn draw_jobs(&mut self, frame: &mut Frame, area: Rect) {
+        let visible = self.visible_jobs();
+        let title = if self.show_all {
+            format!(" Jobs ({}) ", visible.len())
+        } else {
+            format!(" Queued and running jobs ({}) ", visible.len())
+        };
+        let rows: Vec<Row> = visible
+            .iter()
+            .map(|job| {
+                Row::new(vec![
+                    Cell::from(job.id.chars().take(8).collect::<String>()).dim(),
+                    Cell::from(job.name.clone()),
+                    Cell::from(job.status.clone()).fg(status_color(&job.status)),
+                    Cell::from(progress_bar(progress(&job.status), 10)),
+                    Cell::from(job.backend.clone().unwrap_or_else(|| "-".to_string())),
+                    Cell::from(job.shots.to_string()),
+                    Cell::from(short_time(&job.created_at)),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Min(12),
+                Constraint::Length(22),
+                Constraint::Length(15),
+                Constraint::Length(14),
+                Constraint::Length(8),
+                Constraint::Length(19),
+            ],
+        )
+        .header(
+            Row::new([
+                "ID", "NAME", "STATUS", "PROGRESS", "BACKEND", "SHOTS", "CREATED",
+            ])
+            .bold(),
+        )
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(title));
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+}
+
+/// A text progress bar with percentage, e.g. `████░░░░░░ 40%`.
+fn progress_bar(fraction: f64, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64).round() as usize;
+    format!(
+        "{}{} {:>3.0}%",
+        "█".repeat(filled),
+        "░".repeat(width - filled),
+        fraction * 100.0
+    )
+}
+
+fn draw_inspect(frame: &mut Frame, job: &JobRow) {
+    let [area] = Layout::horizontal([Constraint::Percentage(70)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Constraint::Length(15)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(Clear, area);
+
+    let block = Block::bordered().title(format!(" Job {} ", job.name));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [details, gauge] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{name:<11}"), Style::new().bold()),
+            Span::raw(value),
+        ])
+    };
+    let optional = |value: &Option<String>| value.as_deref().map_or("-".to_string(), short_time);
+    let lines = vec![
+        field("ID", job.id.clone()),
+        field("Status", job.status.clone()),
+        field(
+            "Details",
+            job.status_details
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        field(
+            "Backend",
+            job.backend.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        field("Shots", job.shots.to_string()),
+        field("Circuits", job.num_circuits.to_string()),
+        field("Priority", job.priority.to_string()),
+        field("Created", short_time(&job.created_at)),
+        field("Submitted", optional(&job.submitted_at)),
+        field("Completed", optional(&job.completed_at)),
+        Line::styled("Esc to close, c to cancel", Style::new().dim()),
+    ];
+    frame.render_widget(Paragraph::new(lines), details);
+
+    frame.render_widget(
+        Gauge::default()
+            .ratio(progress(&job.status))
+            .gauge_style(Style::new().fg(status_color(&job.status))),
+        gauge,
+    );
+}
+
+async fn run(terminal: &mut DefaultTerminal, app: &mut App, interval: Duration) -> Result<()> {
+    app.refresh().await;
+    let mut last_refresh = Instant::now();
+
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !app.handle_key(key.code).await {
+                    return Ok(());
+                }
+            }
+        }
+
+        if last_refresh.elapsed() >= interval {
+            app.refresh().await;
+            last_refresh = Instant::now();
+        }
+    }
+}
+
+/// Execute the top command.
+///
+/// With `server`, jobs and backends are read from the dashboard at that
+/// URL; otherwise from the local job store, with the availability of
+/// `backends` polled directly.
+pub async fn execute(server: Option<&str>, backends: &[String], interval: u64) -> Result<()> {
+    let source = match server {
+        Some(url) => Source::Remote {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()?,
+            base_url: url.trim_end_matches('/').to_string(),
+        },
+        None => {
+            let mut created = Vec::with_capacity(backends.len());
+            for name in backends {
+                created.push(create_backend(name, false).await?);
+            }
+            Source::Local {
+                scheduler: Box::new(create_scheduler()?),
+                backends: created,
+            }
+        }
+    };
+
+    let mut app = App::new(source);
+    let mut terminal = ratatui::try_init().context("Failed to initialize the terminal")?;
+    let result = run(
+        &mut terminal,
+        &mut app,
+        Duration::from_secs(interval.max(1)),
+    )
+    .await;
+    ratatui::restore();
+    result
+}
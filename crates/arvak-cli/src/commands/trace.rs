@@ -0,0 +1,299 @@
+//! Trace command implementation and the local provenance ledger.
+//!
+//! `compile`, `run`, `submit` and `eval` append every artifact they
+//! produce (source, compiled circuit, submission) to
+//! `~/.arvak/provenance.jsonl`, one JSON record per line.
+//! `arvak trace <artifact_id>` reconstructs an artifact's lineage from that
+//! ledger and the local job store: the chain of artifacts it was derived
+//! from, everything derived from it, and the jobs and results linked to it.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use arvak_hal::provenance::Stage;
+use arvak_hal::{ArtifactId, Provenance};
+use arvak_sched::{JobFilter, Scheduler};
+
+use super::common::{create_scheduler, default_state_dir};
+
+/// One ledger record: an artifact id and the command that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Provenance of the artifact.
+    pub provenance: Provenance,
+    /// When it was recorded.
+    pub recorded_at: DateTime<Utc>,
+    /// CLI command that produced it (`compile`, `run`, ...).
+    pub command: String,
+    /// What the command did, e.g. input and output paths.
+    pub detail: String,
+}
+
+fn ledger_path() -> Result<PathBuf> {
+    Ok(default_state_dir()?.join("provenance.jsonl"))
+}
+
+/// Append an artifact to the ledger.
+///
+/// Failing to record provenance never fails the command itself; it is
+/// reported as a warning.
+pub fn record(provenance: &Provenance, command: &str, detail: impl Into<String>) {
+    let entry = LedgerEntry {
+        provenance: provenance.clone(),
+        recorded_at: Utc::now(),
+        command: command.to_string(),
+        detail: detail.into(),
+    };
+    if let Err(e) = ledger_path().and_then(|path| append(&path, &entry)) {
+        eprintln!(
+            "  {} Could not record provenance: {e:#}",
+            style("!").yellow().bold()
+        );
+    }
+}
+
+fn append(path: &Path, entry: &LedgerEntry) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Read all ledger entries, skipping lines that do not parse.
+fn read_ledger(path: &Path) -> Result<Vec<LedgerEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// A job whose submission is part of the traced lineage.
+#[derive(Debug, Serialize)]
+struct TracedJob {
+    id: String,
+    name: String,
+    status: String,
+    backend: Option<String>,
+    /// Artifact id of the job's submission.
+    artifact_id: ArtifactId,
+    /// Whether a result is stored for the job.
+    has_result: bool,
+}
+
+/// One artifact of the lineage and the ledger records that mention it.
+#[derive(Debug, Serialize)]
+struct TracedArtifact {
+    artifact_id: ArtifactId,
+    stage: Option<Stage>,
+    records: Vec<TracedRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct TracedRecord {
+    recorded_at: DateTime<Utc>,
+    command: String,
+    detail: String,
+}
+
+/// The full lineage of an artifact.
+#[derive(Debug, Serialize)]
+struct Trace {
+    artifact_id: ArtifactId,
+    /// The artifact's ancestors, source first, then the artifact itself.
+    lineage: Vec<TracedArtifact>,
+    /// Artifacts derived from it.
+    descendants: Vec<TracedArtifact>,
+    /// Jobs that submitted it or one of its descendants.
+    jobs: Vec<TracedJob>,
+}
+
+/// Execute the trace command.
+pub async fn execute(artifact_id: &str, format: &str) -> Result<()> {
+    let entries = read_ledger(&ledger_path()?)?;
+    let scheduler = create_scheduler()?;
+    let jobs = scheduler
+        .list_jobs(JobFilter::default())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list jobs: {e}"))?;
+
+    // Every chain known locally, from the ledger and from stored jobs.
+    let mut chains: Vec<Provenance> = entries.iter().map(|e| e.provenance.clone()).collect();
+    for job in &jobs {
+        chains.extend(job.provenance.clone());
+        chains
+            .extend(job.submission_provenance(job.matched_backend.as_deref().unwrap_or("unknown")));
+    }
+
+    let id = resolve(artifact_id, &chains)?;
+
+    // Ancestors: the chain leading up to the id, from any record holding it.
+    let ancestors: Vec<ArtifactId> = chains
+        .iter()
+        .find_map(|p| {
+            p.chain()
+                .position(|a| a == &id)
+                .map(|i| p.chain().take(i + 1).cloned().collect())
+        })
+        .unwrap_or_default();
+
+    // Descendants: every artifact whose chain passes through the id.
+    let mut descendants: Vec<ArtifactId> = Vec::new();
+    for chain in &chains {
+        if let Some(i) = chain.chain().position(|a| a == &id) {
+            for descendant in chain.chain().skip(i + 1) {
+                if !descendants.contains(descendant) {
+                    descendants.push(descendant.clone());
+                }
+            }
+        }
+    }
+
+    let stages: HashMap<&ArtifactId, Stage> =
+        chains.iter().map(|p| (&p.artifact_id, p.stage)).collect();
+    let traced = |artifact_id: &ArtifactId| TracedArtifact {
+        artifact_id: artifact_id.clone(),
+        stage: stages.get(artifact_id).copied(),
+        records: entries
+            .iter()
+            .filter(|e| &e.provenance.artifact_id == artifact_id)
+            .map(|e| TracedRecord {
+                recorded_at: e.recorded_at,
+                command: e.command.clone(),
+                detail: e.detail.clone(),
+            })
+            .collect(),
+    };
+
+    let mut traced_jobs = Vec::new();
+    for job in &jobs {
+        let backend = job.matched_backend.as_deref().unwrap_or("unknown");
+        let Some(submission) = job.submission_provenance(backend) else {
+            continue;
+        };
+        if !submission.contains(&id) {
+            continue;
+        }
+        let has_result = scheduler.result(&job.id).await.is_ok();
+        traced_jobs.push(TracedJob {
+            id: job.id.to_string(),
+            name: job.name.clone(),
+            status: job.status.name().to_string(),
+            backend: job.matched_backend.clone(),
+            artifact_id: submission.artifact_id,
+            has_result,
+        });
+    }
+
+    let trace = Trace {
+        lineage: ancestors.iter().map(traced).collect(),
+        descendants: descendants.iter().map(traced).collect(),
+        jobs: traced_jobs,
+        artifact_id: id,
+    };
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&trace)?),
+        _ => print_trace(&trace),
+    }
+    Ok(())
+}
+
+/// Resolve a full id or unique prefix against the known chains.
+fn resolve(query: &str, chains: &[Provenance]) -> Result<ArtifactId> {
+    let mut matches: Vec<&ArtifactId> = chains
+        .iter()
+        .flat_map(Provenance::chain)
+        .filter(|id| id.matches_prefix(query))
+        .collect();
+    matches.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    matches.dedup();
+    match matches.as_slice() {
+        [] => anyhow::bail!(
+            "No artifact '{query}' in the provenance ledger or job store. Artifact ids are \
+             recorded by compile, run, submit and eval."
+        ),
+        [id] => Ok((*id).clone()),
+        _ => anyhow::bail!(
+            "Artifact prefix '{query}' is ambiguous ({} matches); give more digits",
+            matches.len()
+        ),
+    }
+}
+
+fn print_trace(trace: &Trace) {
+    println!(
+        "{} Artifact {}",
+        style("→").cyan().bold(),
+        style(&trace.artifact_id).green()
+    );
+
+    println!();
+    println!("{}", style("Lineage").bold().underlined());
+    for artifact in &trace.lineage {
+        print_artifact(artifact, artifact.artifact_id == trace.artifact_id);
+    }
+
+    if !trace.descendants.is_empty() {
+        println!();
+        println!("{}", style("Derived").bold().underlined());
+        for artifact in &trace.descendants {
+            print_artifact(artifact, false);
+        }
+    }
+
+    println!();
+    println!("{}", style("Jobs").bold().underlined());
+    if trace.jobs.is_empty() {
+        println!("  (none in the local job store)");
+    }
+    for job in &trace.jobs {
+        println!(
+            "  {}  {:<16} {:<12} {:<12} {}",
+            style(&job.id).cyan(),
+            job.name,
+            job.status,
+            job.backend.as_deref().unwrap_or("-"),
+            if job.has_result {
+                "result stored"
+            } else {
+                "no result"
+            }
+        );
+    }
+}
+
+fn print_artifact(artifact: &TracedArtifact, highlight: bool) {
+    let id = artifact.artifact_id.short();
+    println!(
+        "  {:<11} {}",
+        artifact.stage.map_or("?", Stage::as_str),
+        if highlight {
+            style(id).green().bold()
+        } else {
+            style(id).dim()
+        }
+    );
+    for record in &artifact.records {
+        println!(
+            "              {} {} {}",
+            record.recorded_at.format("%Y-%m-%d %H:%M:%S"),
+            style(&record.command).yellow(),
+            record.detail
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Usage command implementation.
+//!
+//! Aggregates shots, circuits, wall time and an estimated cost per backend
+//! from the local job store, so a usage report for a reporting period does
+//! not have to be assembled from each provider's dashboard.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
+use console::style;
+use serde::Serialize;
+
+use arvak_sched::{JobFilter, ScheduledJob, Scheduler};
+
+use super::common::create_scheduler;
+
+/// Backend label for jobs that were never matched to a backend.
+const UNASSIGNED: &str = "unassigned";
+
+/// Output format for the usage report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UsageFormat {
+    /// Aligned table for the terminal.
+    Table,
+    /// Pretty-printed JSON keyed by backend.
+    Json,
+}
+
+/// Aggregated usage for a single backend.
+#[derive(Debug, Default, Serialize)]
+struct BackendUsage {
+    jobs: usize,
+    completed: usize,
+    failed: usize,
+    circuits: usize,
+    shots: u64,
+    wall_time_secs: i64,
+    /// `None` when no list price is known for the backend.
+    estimated_cost_usd: Option<f64>,
+}
+
+/// Approximate list prices as `(per task, per shot)` in USD.
+///
+/// Only providers that bill per task/shot are listed; time- or credit-based
+/// plans (IBM, Quantinuum) have no estimate. Simulators are free.
+fn list_price(backend: &str) -> Option<(f64, f64)> {
+    match backend.to_lowercase().as_str() {
+        "simulator" | "sim" | "ddsim" | "mqt-ddsim" | "mqt_ddsim" => Some((0.0, 0.0)),
+        "ionq" | "aria" => Some((0.30, 0.03)),
+        "rigetti" | "ankaa" => Some((0.30, 0.000_9)),
+        "iqm" | "garnet" | "scaleway" | "scaleway-garnet" => Some((0.30, 0.001_45)),
+        _ => None,
+    }
+}
+
+/// Parse a `--since`/`--until` bound: a date (`2026-01-01`, midnight UTC) or
+/// an RFC 3339 timestamp.
+fn parse_bound(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| {
+            anyhow::anyhow!("Invalid date '{value}': expected YYYY-MM-DD or an RFC 3339 timestamp")
+        })
+}
+
+/// Lower and upper creation-time bounds, either of which may be open.
+type DateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Parse the `--since`/`--until` pair, rejecting ranges that cannot
+/// contain any job.
+fn parse_range(since: Option<&str>, until: Option<&str>) -> Result<DateRange> {
+    let created_after = since.map(parse_bound).transpose()?;
+    let created_before = until.map(parse_bound).transpose()?;
+    if let (Some(after), Some(before)) = (created_after, created_before) {
+        if after >= before {
+            anyhow::bail!("Empty range: --since must be earlier than --until");
+        }
+    }
+    Ok((created_after, created_before))
+}
+
+/// Aggregate jobs into per-backend usage, keyed by backend name.
+fn aggregate(jobs: &[ScheduledJob]) -> BTreeMap<String, BackendUsage> {
+    let mut usage: BTreeMap<String, BackendUsage> = BTreeMap::new();
+
+    for job in jobs {
+        let backend = job
+            .matched_backend
+            .as_deref()
+            .or_else(|| {
+                job.requirements
+                    .preferred_backends
+                    .first()
+                    .map(String::as_str)
+            })
+            .unwrap_or(UNASSIGNED);
+        let entry = usage.entry(backend.to_string()).or_default();
+
+        let circuits = job.circuits.len();
+        entry.jobs += 1;
+        entry.circuits += circuits;
+        entry.shots += u64::from(job.shots) * circuits as u64;
+
+        match job.status.name() {
+            "Completed" => entry.completed += 1,
+            "Failed" => entry.failed += 1,
+            _ => {}
+        }
+
+        if let Some(completed_at) = job.completed_at {
+            let started_at = job.submitted_at.unwrap_or(job.created_at);
+            entry.wall_time_secs += (completed_at - started_at).num_seconds().max(0);
+        }
+    }
+
+    for (backend, entry) in &mut usage {
+        entry.estimated_cost_usd = list_price(backend).map(|(per_task, per_shot)| {
+            per_task * entry.circuits as f64 + per_shot * entry.shots as f64
+        });
+    }
+
+    usage
+}
+
+/// Format a duration in seconds as `HH:MM:SS`.
+fn format_wall_time(secs: i64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// Execute the usage command.
+pub async fn execute(since: Option<&str>, until: Option<&str>, format: UsageFormat) -> Result<()> {
+    let (created_after, created_before) = parse_range(since, until)?;
+
+    let scheduler = create_scheduler()?;
+    let jobs = scheduler
+        .list_jobs(JobFilter {
+            created_after,
+            created_before,
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list jobs: {e}"))?;
+
+    let usage = aggregate(&jobs);
+
+    if format == UsageFormat::Json {
+        let json = serde_json::to_string_pretty(&usage)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    let period = match (since, until) {
+        (Some(s), Some(u)) => format!("{s} to {u}"),
+        (Some(s), None) => format!("since {s}"),
+        (None, Some(u)) => format!("until {u}"),
+        (None, None) => "all time".to_string(),
+    };
+
+    if usage.is_empty() {
+        println!("No jobs found ({period}).");
+        return Ok(());
+    }
+
+    println!(
+        "{} Usage {} ({} job(s)):\n",
+        style("→").cyan().bold(),
+        period,
+        jobs.len()
+    );
+
+    println!(
+        "  {:<20}  {:>6}  {:>9}  {:>6}  {:>8}  {:>12}  {:>10}  {:>12}",
+        style("BACKEND").bold(),
+        style("JOBS").bold(),
+        style("COMPLETED").bold(),
+        style("FAILED").bold(),
+        style("CIRCUITS").bold(),
+        style("SHOTS").bold(),
+        style("WALL TIME").bold(),
+        style("EST. COST").bold()
+    );
+    println!("  {}", "-".repeat(100));
+
+    let mut total_cost = 0.0;
+    for (backend, entry) in &usage {
+        let cost = entry.estimated_cost_usd.map_or_else(
+            || "n/a".to_string(),
+            |c| {
+                total_cost += c;
+                format!("${c:.2}")
+            },
+        );
+        println!(
+            "  {:<20}  {:>6}  {:>9}  {:>6}  {:>8}  {:>12}  {:>10}  {:>12}",
+            style(backend).cyan(),
+            entry.jobs,
+            entry.completed,
+            entry.failed,
+            entry.circuits,
+            entry.shots,
+            format_wall_time(entry.wall_time_secs),
+            cost,
+        );
+    }
+
+    println!(
+        "\n  Estimated total: {} (list prices; excludes backends marked n/a)",
+        style(format!("${total_cost:.2}")).yellow()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use arvak_hal::JobId;
+    use arvak_sched::{CircuitSpec, ScheduledJobStatus};
+
+    use super::*;
+
+    fn job(backend: Option<&str>, circuits: usize, shots: u32) -> ScheduledJob {
+        let specs = vec![CircuitSpec::from_qasm("OPENQASM 3.0;"); circuits];
+        let mut job = ScheduledJob::batch("job", specs).with_shots(shots);
+        job.matched_backend = backend.map(str::to_string);
+        job
+    }
+
+    #[test]
+    fn test_aggregate_buckets_by_backend() {
+        let mut done = job(Some("iqm"), 2, 100);
+        done.status = ScheduledJobStatus::Completed {
+            slurm_job_id: "1".into(),
+            quantum_job_id: JobId::new("q1"),
+        };
+        done.submitted_at = Some(done.created_at);
+        done.completed_at = Some(done.created_at + chrono::Duration::seconds(90));
+
+        let mut failed = job(Some("iqm"), 1, 50);
+        failed.status = ScheduledJobStatus::Failed {
+            reason: "calibration".into(),
+            slurm_job_id: None,
+            quantum_job_id: None,
+        };
+
+        let mut preferred = job(None, 1, 10);
+        preferred.requirements.preferred_backends = vec!["ibm".into()];
+
+        let usage = aggregate(&[done, failed, preferred, job(None, 1, 10)]);
+        assert_eq!(
+            usage.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["ibm", "iqm", UNASSIGNED]
+        );
+
+        let iqm = &usage["iqm"];
+        assert_eq!(iqm.jobs, 2);
+        assert_eq!(iqm.completed, 1);
+        assert_eq!(iqm.failed, 1);
+        assert_eq!(iqm.circuits, 3);
+        assert_eq!(iqm.shots, 250);
+        assert_eq!(iqm.wall_time_secs, 90);
+        let cost = iqm.estimated_cost_usd.unwrap();
+        assert!((cost - (0.30 * 3.0 + 0.001_45 * 250.0)).abs() < 1e-9);
+
+        assert!(usage["ibm"].estimated_cost_usd.is_none());
+        assert_eq!(usage[UNASSIGNED].jobs, 1);
+    }
+
+    #[test]
+    fn test_aggregate_empty() {
+        assert!(aggregate(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_bound() {
+        let date = parse_bound("2026-01-01").unwrap();
+        assert_eq!(date.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        let ts = parse_bound("2026-01-01T12:30:00+02:00").unwrap();
+        assert_eq!(ts.to_rfc3339(), "2026-01-01T10:30:00+00:00");
+
+        assert!(parse_bound("2026-13-01").is_err());
+        assert!(parse_bound("01/02/2026").is_err());
+        assert!(parse_bound("").is_err());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let (after, before) = parse_range(Some("2026-01-01"), Some("2026-02-01")).unwrap();
+        assert!(after.is_some() && before.is_some());
+        assert_eq!(parse_range(None, None).unwrap(), (None, None));
+
+        assert!(parse_range(Some("2026-02-01"), Some("2026-01-01")).is_err());
+        assert!(parse_range(Some("2026-01-01"), Some("2026-01-01")).is_err());
+        assert!(parse_range(Some("yesterday"), None).is_err());
+    }
+}
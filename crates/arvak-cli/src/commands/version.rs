@@ -1,9 +1,10 @@
 //! Version command implementation.
 
+use arvak_hal::BuildInfo;
 use console::style;
 
 /// Execute the version command.
-pub fn execute() {
+pub fn execute(features: bool) {
     let version = env!("CARGO_PKG_VERSION");
 
     println!(
@@ -23,4 +24,55 @@ pub fn execute() {
         style("https://github.com/hiq-lab/arvak").underlined()
     );
     println!("License:    {}", style("Apache-2.0").dim());
+
+    if features {
+        println!();
+        print_build(&build_info());
+    }
+}
+
+/// Build info of this binary: the CLI, the adapters it links and the scheduler.
+fn build_info() -> BuildInfo {
+    let adapters = [
+        ("aqt", cfg!(feature = "aqt")),
+        ("braket", cfg!(feature = "braket")),
+        ("ddsim", cfg!(feature = "ddsim")),
+        ("ibm", cfg!(feature = "ibm")),
+        ("iqm", cfg!(feature = "iqm")),
+        ("quantinuum", cfg!(feature = "quantinuum")),
+        ("scaleway", cfg!(feature = "scaleway")),
+    ];
+    let mut info = BuildInfo::current().with_component(
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        adapters,
+    );
+    info = info.with_adapter("simulator").with_adapter("qdmi");
+    for (adapter, _) in adapters.into_iter().filter(|(_, enabled)| *enabled) {
+        info = info.with_adapter(adapter);
+    }
+    arvak_sched::describe_build(info)
+}
+
+fn print_build(info: &BuildInfo) {
+    println!(
+        "Git commit: {}",
+        info.git_commit.as_deref().unwrap_or("unknown")
+    );
+    println!();
+    println!("Features:");
+    for component in &info.components {
+        let features = if component.features.is_empty() {
+            style("(none)".to_string()).dim()
+        } else {
+            style(component.features.join(", "))
+        };
+        println!(
+            "  {:<14} {:<8} {features}",
+            component.name, component.version
+        );
+    }
+    println!();
+    println!("Adapters:         {}", info.adapters.join(", "));
+    println!("Storage backends: {}", info.storage_backends.join(", "));
 }
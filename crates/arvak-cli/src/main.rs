@@ -48,25 +48,35 @@
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-use clap::{Parser, Subcommand};
+use arvak_compile::OptimizationLevel;
+use clap::{CommandFactory, Parser, Subcommand};
 use console::style;
 use tracing_subscriber::EnvFilter;
 
 mod commands;
 
-use commands::{auth, backends, compile, eval, result, run, status, submit, version, wait};
+use commands::common::parse_param;
+use commands::{
+    admin, auth, backends, campaign, compile, completions, eval, qdmi, result, run, scheduler,
+    schema, smoke, status, submit, top, trace, usage, version, wait,
+};
 
 /// Arvak - Rust-native quantum compilation and orchestration for HPC
 #[derive(Parser)]
 #[command(name = "arvak")]
 #[command(author, version, about, long_about = None)]
+#[command(arg_required_else_help = true)]
 struct Cli {
     /// Increase verbosity (-v, -vv, -vvv)
     #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     verbose: u8,
 
+    /// Print a JSON description of all commands and flags
+    #[arg(long)]
+    schema: bool,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -77,7 +87,7 @@ enum Commands {
         #[arg(short, long)]
         input: String,
 
-        /// Output file
+        /// Output file (.qasm, or .arvakc for a compiled artifact with provenance)
         #[arg(short, long)]
         output: Option<String>,
 
@@ -85,9 +95,33 @@ enum Commands {
         #[arg(short, long, default_value = "iqm")]
         target: String,
 
-        /// Optimization level (0-3)
+        /// Optimization level (0-3, or auto to choose per circuit)
         #[arg(long, default_value = "1")]
-        optimization_level: u8,
+        optimization_level: OptimizationLevel,
+
+        /// Compile-time budget in seconds; optimization stops early once it is
+        /// spent [default with --optimization-level auto: 2]
+        #[arg(long)]
+        compile_budget: Option<f64>,
+
+        /// Seed for compiler passes that make random choices
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Produce byte-identical output on every run: seed all random
+        /// choices (default seed 0) and disable the compile-time budget
+        #[arg(long, conflicts_with = "compile_budget")]
+        deterministic: bool,
+
+        /// Reuse measured qubits through mid-circuit reset, so wide
+        /// sequential circuits fit smaller devices
+        #[arg(long)]
+        qubit_reuse: bool,
+
+        /// Check after every pass that the circuit is unchanged in
+        /// meaning, and report the first pass that miscompiles it
+        #[arg(long)]
+        verify: bool,
     },
 
     /// Run a circuit on a backend
@@ -111,6 +145,10 @@ enum Commands {
         /// Target for compilation
         #[arg(long)]
         target: Option<String>,
+
+        /// Value for an `input` parameter of the circuit (repeatable)
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, f64)>,
     },
 
     /// Submit a circuit to an HPC batch scheduler
@@ -147,6 +185,14 @@ enum Commands {
         #[arg(long)]
         priority: Option<String>,
 
+        /// Site maintenance calendar (YAML) listing planned backend downtime
+        #[arg(long, env = "ARVAK_MAINTENANCE_FILE")]
+        maintenance: Option<String>,
+
+        /// Value for an `input` parameter of the circuit (repeatable)
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, f64)>,
+
         /// Wait for job to complete
         #[arg(short, long)]
         wait: bool,
@@ -160,6 +206,14 @@ enum Commands {
         /// List all jobs
         #[arg(short, long)]
         all: bool,
+
+        /// List failed jobs
+        #[arg(long, conflicts_with = "all")]
+        failed: bool,
+
+        /// Show the failure forensics bundle of failed jobs
+        #[arg(long)]
+        details: bool,
     },
 
     /// Retrieve results for a completed job
@@ -188,6 +242,31 @@ enum Commands {
         timeout: u64,
     },
 
+    /// Monitor backends and the job queue in an interactive terminal UI
+    Top {
+        /// Dashboard URL to read jobs and backends from instead of the local job store
+        #[arg(short, long, env = "ARVAK_DASHBOARD_URL")]
+        server: Option<String>,
+
+        /// Backend to show availability of, without --server (repeatable)
+        #[arg(short, long = "backend", default_value = "simulator")]
+        backends: Vec<String>,
+
+        /// Refresh interval in seconds
+        #[arg(short, long, default_value = "2")]
+        interval: u64,
+    },
+
+    /// Trace an artifact's lineage: source, compiled circuit, jobs and results
+    Trace {
+        /// Artifact ID, or a unique prefix of one
+        artifact_id: String,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
     /// Evaluate a circuit: compilation observability, QDMI contract check, metrics
     Eval {
         /// Input file (QASM3)
@@ -202,9 +281,23 @@ enum Commands {
         #[arg(short, long, default_value = "iqm")]
         target: String,
 
-        /// Optimization level (0-3)
+        /// Optimization level (0-3, or auto to choose per circuit)
         #[arg(long, default_value = "1")]
-        optimization_level: u8,
+        optimization_level: OptimizationLevel,
+
+        /// Compile-time budget in seconds; optimization stops early once it is
+        /// spent [default with --optimization-level auto: 2]
+        #[arg(long)]
+        compile_budget: Option<f64>,
+
+        /// Seed for compiler passes that make random choices
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Produce byte-identical output on every run: seed all random
+        /// choices (default seed 0) and disable the compile-time budget
+        #[arg(long, conflicts_with = "compile_budget")]
+        deterministic: bool,
 
         /// Number of qubits on target device
         #[arg(long, default_value = "20")]
@@ -233,13 +326,94 @@ enum Commands {
         /// Number of qubits for benchmark circuit (defaults to input circuit size)
         #[arg(long)]
         benchmark_qubits: Option<usize>,
+
+        /// Device noise profile (JSON) for a ranked error budget of the
+        /// compiled circuit
+        #[arg(long)]
+        noise_profile: Option<String>,
+    },
+
+    /// Report shots, circuits, wall time and estimated cost per backend
+    Usage {
+        /// Only include jobs created on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only include jobs created before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: usage::UsageFormat,
+    },
+
+    /// Administrative maintenance of the local job store
+    Admin {
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+
+    /// Capacity planning for HPC scheduler sites
+    Scheduler {
+        #[command(subcommand)]
+        action: SchedulerAction,
+    },
+
+    /// Run a grid of experiments and collect the results into one dataset
+    Campaign {
+        #[command(subcommand)]
+        action: CampaignAction,
+    },
+
+    /// Browse the devices of QDMI device libraries
+    Qdmi {
+        #[command(subcommand)]
+        action: QdmiAction,
     },
 
     /// List available backends
-    Backends,
+    Backends {
+        /// Discard cached device information and fetch it from the providers
+        #[arg(long)]
+        refresh: bool,
+
+        #[command(subcommand)]
+        action: Option<BackendsAction>,
+    },
 
     /// Show version information
-    Version,
+    Version {
+        /// Also list enabled cargo features, adapters, storage backends and the git commit
+        #[arg(long)]
+        features: bool,
+    },
+
+    /// Generate a shell completion script
+    Completions {
+        /// Shell to generate the script for
+        shell: clap_complete::Shell,
+    },
+
+    /// Run the end-to-end smoke suite
+    #[command(hide = true)]
+    Smoke {
+        /// Highest tier to run (simulator, mock, live); tiers are cumulative
+        #[arg(short, long, default_value = "mock")]
+        tier: smoke::Tier,
+
+        /// QDMI device library to check in the mock tier, as PREFIX=PATH (repeatable)
+        #[arg(long = "qdmi-device", value_name = "PREFIX=PATH", value_parser = qdmi::parse_device)]
+        qdmi_devices: Vec<qdmi::DeviceLibrary>,
+
+        /// Shots per live-backend job
+        #[arg(long, default_value = "100")]
+        live_shots: u32,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -270,10 +444,172 @@ enum AuthAction {
     },
 }
 
+#[derive(Subcommand)]
+enum AdminAction {
+    /// Delete or archive finished jobs by age or store size
+    Gc {
+        /// Remove finished jobs older than this many days
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+
+        /// Keep at most this many jobs, evicting the oldest finished ones
+        #[arg(long)]
+        max_jobs: Option<usize>,
+
+        /// Archive removed jobs as JSON files in this directory
+        #[arg(long, value_name = "DIR")]
+        archive: Option<String>,
+
+        /// Show what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchedulerAction {
+    /// Replay a job trace to predict queue times and QPU utilization
+    Simulate {
+        /// Workload trace (JSON list of jobs)
+        #[arg(short, long)]
+        workload: String,
+
+        /// Site whose constraints to simulate (lrz, lumi)
+        #[arg(long, default_value = "lumi")]
+        site: String,
+
+        /// Site constraints file (JSON), overrides --site
+        #[arg(long, value_name = "FILE")]
+        site_config: Option<String>,
+
+        /// Number of QPUs available to the site
+        #[arg(long)]
+        qpus: Option<u32>,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CampaignAction {
+    /// Run all pending points of a campaign, resuming from its dataset
+    Run {
+        /// Campaign spec (YAML)
+        spec: String,
+
+        /// Dataset file (JSON Lines) [default: the spec path with a .jsonl extension]
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Discard existing results and run every point again
+        #[arg(long)]
+        restart: bool,
+    },
+
+    /// Show which points of a campaign are done
+    Status {
+        /// Campaign spec (YAML)
+        spec: String,
+
+        /// Dataset file (JSON Lines) [default: the spec path with a .jsonl extension]
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum QdmiAction {
+    /// List the devices of all configured QDMI libraries
+    List {
+        /// QDMI device library, as PREFIX=PATH (repeatable)
+        #[arg(short, long = "device", value_name = "PREFIX=PATH", value_parser = qdmi::parse_device)]
+        devices: Vec<qdmi::DeviceLibrary>,
+
+        /// Driver configuration listing 'PREFIX PATH' per line
+        /// [default: $ARVAK_QDMI_CONFIG or ~/.arvak/qdmi.conf]
+        #[arg(short, long)]
+        config: Option<String>,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Show the sites, operations and coupling map of one device
+    Inspect {
+        /// Device prefix or name
+        device: String,
+
+        /// QDMI device library, as PREFIX=PATH (repeatable)
+        #[arg(short, long = "device", value_name = "PREFIX=PATH", value_parser = qdmi::parse_device)]
+        devices: Vec<qdmi::DeviceLibrary>,
+
+        /// Driver configuration listing 'PREFIX PATH' per line
+        /// [default: $ARVAK_QDMI_CONFIG or ~/.arvak/qdmi.conf]
+        #[arg(short, long)]
+        config: Option<String>,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackendsAction {
+    /// Verify a backend against its enrolled hardware fingerprint
+    Attest {
+        /// Backend name
+        name: String,
+
+        /// Measure the fingerprint and store it as the enrollment
+        #[arg(long)]
+        enroll: bool,
+
+        /// Qubits in the fingerprint when enrolling [default: up to 16]
+        #[arg(long)]
+        qubits: Option<u32>,
+
+        /// Shots per challenge circuit when enrolling [default: 2000]
+        #[arg(short, long)]
+        shots: Option<u32>,
+
+        /// Largest RMS fingerprint distance accepted [default: 0.02]
+        #[arg(long)]
+        tolerance: Option<f64>,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
+    if cli.schema {
+        return schema::execute(&Cli::command());
+    }
+    let Some(command) = cli.command else {
+        Cli::command()
+            .error(
+                clap::error::ErrorKind::MissingSubcommand,
+                "a subcommand is required",
+            )
+            .exit();
+    };
+
     // Setup logging
     let filter = match cli.verbose {
         0 => "warn",
@@ -288,13 +624,31 @@ async fn main() -> anyhow::Result<()> {
         .init();
 
     // Execute command
-    let result = match cli.command {
+    let result = match command {
         Commands::Compile {
             input,
             output,
             target,
             optimization_level,
-        } => compile::execute(&input, output.as_deref(), &target, optimization_level).await,
+            compile_budget,
+            seed,
+            deterministic,
+            qubit_reuse,
+            verify,
+        } => {
+            compile::execute(
+                &input,
+                output.as_deref(),
+                &target,
+                optimization_level,
+                compile_budget,
+                seed,
+                deterministic,
+                qubit_reuse,
+                verify,
+            )
+            .await
+        }
 
         Commands::Run {
             input,
@@ -302,7 +656,18 @@ async fn main() -> anyhow::Result<()> {
             backend,
             compile: do_compile,
             target,
-        } => run::execute(&input, shots, &backend, do_compile, target.as_deref()).await,
+            params,
+        } => {
+            run::execute(
+                &input,
+                shots,
+                &backend,
+                do_compile,
+                target.as_deref(),
+                &params,
+            )
+            .await
+        }
 
         Commands::Submit {
             input,
@@ -313,6 +678,8 @@ async fn main() -> anyhow::Result<()> {
             account,
             time,
             priority,
+            maintenance,
+            params,
             wait: do_wait,
         } => {
             submit::execute(
@@ -324,12 +691,19 @@ async fn main() -> anyhow::Result<()> {
                 account.as_deref(),
                 time.as_deref(),
                 priority.as_deref(),
+                maintenance.as_deref(),
+                &params,
                 do_wait,
             )
             .await
         }
 
-        Commands::Status { job_id, all } => status::execute(job_id.as_deref(), all).await,
+        Commands::Status {
+            job_id,
+            all,
+            failed,
+            details,
+        } => status::execute(job_id.as_deref(), all, failed, details).await,
 
         Commands::Result { job_id, format } => result::execute(&job_id, &format).await,
 
@@ -343,11 +717,25 @@ async fn main() -> anyhow::Result<()> {
 
         Commands::Wait { job_id, timeout } => wait::execute(&job_id, timeout).await,
 
+        Commands::Top {
+            server,
+            backends,
+            interval,
+        } => top::execute(server.as_deref(), &backends, interval).await,
+
+        Commands::Trace {
+            artifact_id,
+            format,
+        } => trace::execute(&artifact_id, &format).await,
+
         Commands::Eval {
             input,
             profile,
             target,
             optimization_level,
+            compile_budget,
+            seed,
+            deterministic,
             target_qubits,
             export,
             orchestration,
@@ -355,12 +743,16 @@ async fn main() -> anyhow::Result<()> {
             emit,
             benchmark,
             benchmark_qubits,
+            noise_profile,
         } => {
             eval::execute(
                 &input,
                 &profile,
                 &target,
                 optimization_level,
+                compile_budget,
+                seed,
+                deterministic,
                 export.as_deref(),
                 target_qubits,
                 orchestration,
@@ -368,16 +760,93 @@ async fn main() -> anyhow::Result<()> {
                 emit.as_deref(),
                 benchmark.as_deref(),
                 benchmark_qubits,
+                noise_profile.as_deref(),
             )
             .await
         }
 
-        Commands::Backends => backends::execute().await,
+        Commands::Usage {
+            since,
+            until,
+            format,
+        } => usage::execute(since.as_deref(), until.as_deref(), format).await,
+
+        Commands::Admin { action } => match action {
+            AdminAction::Gc {
+                older_than,
+                max_jobs,
+                archive,
+                dry_run,
+                format,
+            } => {
+                admin::execute_gc(older_than, max_jobs, archive.as_deref(), dry_run, &format).await
+            }
+        },
+
+        Commands::Scheduler { action } => match action {
+            SchedulerAction::Simulate {
+                workload,
+                site,
+                site_config,
+                qpus,
+                format,
+            } => {
+                scheduler::execute_simulate(&workload, &site, site_config.as_deref(), qpus, &format)
+            }
+        },
+
+        Commands::Campaign { action } => match action {
+            CampaignAction::Run {
+                spec,
+                output,
+                restart,
+            } => campaign::execute_run(&spec, output.as_deref(), restart).await,
+            CampaignAction::Status {
+                spec,
+                output,
+                format,
+            } => campaign::execute_status(&spec, output.as_deref(), &format),
+        },
+
+        Commands::Qdmi { action } => match action {
+            QdmiAction::List {
+                devices,
+                config,
+                format,
+            } => qdmi::execute_list(&devices, config.as_deref(), &format),
+            QdmiAction::Inspect {
+                device,
+                devices,
+                config,
+                format,
+            } => qdmi::execute_inspect(&device, &devices, config.as_deref(), &format),
+        },
 
-        Commands::Version => {
-            version::execute();
+        Commands::Backends { refresh, action } => match action {
+            None => backends::execute(refresh).await,
+            Some(BackendsAction::Attest {
+                name,
+                enroll,
+                qubits,
+                shots,
+                tolerance,
+                format,
+            }) => backends::execute_attest(&name, enroll, qubits, shots, tolerance, &format).await,
+        },
+
+        Commands::Version { features } => {
+            version::execute(features);
             Ok(())
         }
+
+        Commands::Completions { shell } => completions::execute(shell, &mut Cli::command()),
+
+        Commands::Smoke {
+            tier,
+            qdmi_devices,
+            live_shots,
+            format,
+        } => smoke::execute(tier, &qdmi_devices, live_shots, &format).await,
     };
 
     // Handle errors
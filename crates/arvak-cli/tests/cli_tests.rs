@@ -154,17 +154,27 @@ mod circuit_loading {
 // ============================================================================
 
 mod clap_parsing {
-    use clap::{Parser, Subcommand};
+    use clap::{Parser, Subcommand, ValueEnum};
 
     // Mirror the CLI struct for testing (since main.rs is a binary)
     #[derive(Parser)]
     #[command(name = "arvak")]
+    #[command(arg_required_else_help = true)]
     struct TestCli {
         #[arg(short, long, action = clap::ArgAction::Count, global = true)]
         verbose: u8,
 
+        #[arg(long)]
+        schema: bool,
+
         #[command(subcommand)]
-        command: TestCommands,
+        command: Option<TestCommands>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    enum TestUsageFormat {
+        Table,
+        Json,
     }
 
     #[derive(Subcommand)]
@@ -208,6 +218,8 @@ mod clap_parsing {
             time: Option<String>,
             #[arg(long)]
             priority: Option<String>,
+            #[arg(long)]
+            maintenance: Option<String>,
             #[arg(short, long)]
             wait: bool,
         },
@@ -230,8 +242,155 @@ mod clap_parsing {
             #[arg(short, long, default_value = "86400")]
             timeout: u64,
         },
-        Backends,
-        Version,
+        Top {
+            #[arg(short, long)]
+            server: Option<String>,
+            #[arg(short, long = "backend", default_value = "simulator")]
+            backends: Vec<String>,
+            #[arg(short, long, default_value = "2")]
+            interval: u64,
+        },
+        Trace {
+            artifact_id: String,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+        Usage {
+            #[arg(long)]
+            since: Option<String>,
+            #[arg(long)]
+            until: Option<String>,
+            #[arg(short, long, value_enum, default_value = "table")]
+            format: TestUsageFormat,
+        },
+        Admin {
+            #[command(subcommand)]
+            action: TestAdminAction,
+        },
+        Scheduler {
+            #[command(subcommand)]
+            action: TestSchedulerAction,
+        },
+        Campaign {
+            #[command(subcommand)]
+            action: TestCampaignAction,
+        },
+        Qdmi {
+            #[command(subcommand)]
+            action: TestQdmiAction,
+        },
+        Backends {
+            #[arg(long)]
+            refresh: bool,
+            #[command(subcommand)]
+            action: Option<TestBackendsAction>,
+        },
+        Version {
+            #[arg(long)]
+            features: bool,
+        },
+        Completions {
+            shell: clap_complete::Shell,
+        },
+        #[command(hide = true)]
+        Smoke {
+            #[arg(short, long, default_value = "mock")]
+            tier: String,
+            #[arg(long = "qdmi-device")]
+            qdmi_devices: Vec<String>,
+            #[arg(long, default_value = "100")]
+            live_shots: u32,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+    }
+
+    #[derive(Subcommand)]
+    enum TestCampaignAction {
+        Run {
+            spec: String,
+            #[arg(short, long)]
+            output: Option<String>,
+            #[arg(long)]
+            restart: bool,
+        },
+        Status {
+            spec: String,
+            #[arg(short, long)]
+            output: Option<String>,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+    }
+
+    #[derive(Subcommand)]
+    enum TestQdmiAction {
+        List {
+            #[arg(short, long = "device")]
+            devices: Vec<String>,
+            #[arg(short, long)]
+            config: Option<String>,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+        Inspect {
+            device: String,
+            #[arg(short, long = "device")]
+            devices: Vec<String>,
+            #[arg(short, long)]
+            config: Option<String>,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+    }
+
+    #[derive(Subcommand)]
+    enum TestBackendsAction {
+        Attest {
+            name: String,
+            #[arg(long)]
+            enroll: bool,
+            #[arg(long)]
+            qubits: Option<u32>,
+            #[arg(short, long)]
+            shots: Option<u32>,
+            #[arg(long)]
+            tolerance: Option<f64>,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+    }
+
+    #[derive(Subcommand)]
+    enum TestAdminAction {
+        Gc {
+            #[arg(long)]
+            older_than: Option<u64>,
+            #[arg(long)]
+            max_jobs: Option<usize>,
+            #[arg(long)]
+            archive: Option<String>,
+            #[arg(long)]
+            dry_run: bool,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
+    }
+
+    #[derive(Subcommand)]
+    enum TestSchedulerAction {
+        Simulate {
+            #[arg(short, long)]
+            workload: String,
+            #[arg(long, default_value = "lumi")]
+            site: String,
+            #[arg(long)]
+            site_config: Option<String>,
+            #[arg(long)]
+            qpus: Option<u32>,
+            #[arg(short, long, default_value = "table")]
+            format: String,
+        },
     }
 
     #[derive(Subcommand)]
@@ -257,7 +416,7 @@ mod clap_parsing {
     #[test]
     fn test_parse_compile_minimal() {
         let cli = TestCli::try_parse_from(["arvak", "compile", "-i", "circuit.qasm"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Compile {
                 input,
                 output,
@@ -288,7 +447,7 @@ mod clap_parsing {
             "3",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Compile {
                 input,
                 output,
@@ -315,7 +474,7 @@ mod clap_parsing {
     #[test]
     fn test_parse_run_minimal() {
         let cli = TestCli::try_parse_from(["arvak", "run", "-i", "bell.qasm"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Run {
                 input,
                 shots,
@@ -349,7 +508,7 @@ mod clap_parsing {
             "iqm",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Run {
                 compile,
                 target,
@@ -371,7 +530,7 @@ mod clap_parsing {
     #[test]
     fn test_parse_submit_minimal() {
         let cli = TestCli::try_parse_from(["arvak", "submit", "-i", "circuit.qasm"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Submit {
                 scheduler, wait, ..
             } => {
@@ -399,16 +558,19 @@ mod clap_parsing {
             "01:00:00",
             "--priority",
             "high",
+            "--maintenance",
+            "site-maintenance.yaml",
             "-w",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Submit {
                 scheduler,
                 partition,
                 account,
                 time,
                 priority,
+                maintenance,
                 wait,
                 ..
             } => {
@@ -417,6 +579,7 @@ mod clap_parsing {
                 assert_eq!(account.unwrap(), "project123");
                 assert_eq!(time.unwrap(), "01:00:00");
                 assert_eq!(priority.unwrap(), "high");
+                assert_eq!(maintenance.unwrap(), "site-maintenance.yaml");
                 assert!(wait);
             }
             _ => panic!("Expected Submit command"),
@@ -430,7 +593,7 @@ mod clap_parsing {
         let cli =
             TestCli::try_parse_from(["arvak", "status", "550e8400-e29b-41d4-a716-446655440000"])
                 .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Status { job_id, all } => {
                 assert_eq!(job_id.unwrap(), "550e8400-e29b-41d4-a716-446655440000");
                 assert!(!all);
@@ -442,7 +605,7 @@ mod clap_parsing {
     #[test]
     fn test_parse_status_all() {
         let cli = TestCli::try_parse_from(["arvak", "status", "--all"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Status { job_id, all } => {
                 assert!(job_id.is_none());
                 assert!(all);
@@ -458,7 +621,7 @@ mod clap_parsing {
         let cli =
             TestCli::try_parse_from(["arvak", "result", "550e8400-e29b-41d4-a716-446655440000"])
                 .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Result { format, .. } => {
                 assert_eq!(format, "table");
             }
@@ -476,7 +639,7 @@ mod clap_parsing {
             "json",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Result { format, .. } => {
                 assert_eq!(format, "json");
             }
@@ -504,7 +667,7 @@ mod clap_parsing {
             "myproject",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Auth {
                 action: TestAuthAction::Login { provider, project },
             } => {
@@ -524,7 +687,7 @@ mod clap_parsing {
     #[test]
     fn test_parse_auth_status() {
         let cli = TestCli::try_parse_from(["arvak", "auth", "status"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Auth {
                 action: TestAuthAction::Status { provider },
             } => {
@@ -537,7 +700,7 @@ mod clap_parsing {
     #[test]
     fn test_parse_auth_logout() {
         let cli = TestCli::try_parse_from(["arvak", "auth", "logout", "-p", "lrz"]).unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Auth {
                 action: TestAuthAction::Logout { provider },
             } => {
@@ -554,7 +717,7 @@ mod clap_parsing {
         let cli =
             TestCli::try_parse_from(["arvak", "wait", "550e8400-e29b-41d4-a716-446655440000"])
                 .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Wait { timeout, .. } => {
                 assert_eq!(timeout, 86400);
             }
@@ -572,7 +735,7 @@ mod clap_parsing {
             "3600",
         ])
         .unwrap();
-        match cli.command {
+        match cli.command.unwrap() {
             TestCommands::Wait { timeout, .. } => {
                 assert_eq!(timeout, 3600);
             }
@@ -580,18 +743,539 @@ mod clap_parsing {
         }
     }
 
+    // --- Top command ---
+
+    #[test]
+    fn test_parse_top_defaults() {
+        let cli = TestCli::try_parse_from(["arvak", "top"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Top {
+                server,
+                backends,
+                interval,
+            } => {
+                assert!(server.is_none());
+                assert_eq!(backends, vec!["simulator"]);
+                assert_eq!(interval, 2);
+            }
+            _ => panic!("Expected Top command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_top_remote() {
+        let cli =
+            TestCli::try_parse_from(["arvak", "top", "--server", "http://login01:3000", "-i", "5"])
+                .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Top {
+                server, interval, ..
+            } => {
+                assert_eq!(server.as_deref(), Some("http://login01:3000"));
+                assert_eq!(interval, 5);
+            }
+            _ => panic!("Expected Top command"),
+        }
+
+        let cli =
+            TestCli::try_parse_from(["arvak", "top", "-b", "simulator", "-b", "iqm"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Top { backends, .. } => assert_eq!(backends, vec!["simulator", "iqm"]),
+            _ => panic!("Expected Top command"),
+        }
+    }
+
+    // --- Trace command ---
+
+    #[test]
+    fn test_parse_trace() {
+        let cli = TestCli::try_parse_from(["arvak", "trace", "3f2a9c"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Trace {
+                artifact_id,
+                format,
+            } => {
+                assert_eq!(artifact_id, "3f2a9c");
+                assert_eq!(format, "table");
+            }
+            _ => panic!("Expected Trace command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_trace_requires_id() {
+        assert!(TestCli::try_parse_from(["arvak", "trace"]).is_err());
+        let cli = TestCli::try_parse_from(["arvak", "trace", "3f2a9c", "-f", "json"]).unwrap();
+        assert!(
+            matches!(cli.command.unwrap(), TestCommands::Trace { format, .. } if format == "json")
+        );
+    }
+
+    // --- Usage command ---
+
+    #[test]
+    fn test_parse_usage_defaults() {
+        let cli = TestCli::try_parse_from(["arvak", "usage"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Usage {
+                since,
+                until,
+                format,
+            } => {
+                assert!(since.is_none());
+                assert!(until.is_none());
+                assert_eq!(format, TestUsageFormat::Table);
+            }
+            _ => panic!("Expected Usage command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_usage_since() {
+        let cli =
+            TestCli::try_parse_from(["arvak", "usage", "--since", "2026-01-01", "-f", "json"])
+                .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Usage { since, format, .. } => {
+                assert_eq!(since.unwrap(), "2026-01-01");
+                assert_eq!(format, TestUsageFormat::Json);
+            }
+            _ => panic!("Expected Usage command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_usage_rejects_unknown_format() {
+        assert!(TestCli::try_parse_from(["arvak", "usage", "-f", "csv"]).is_err());
+    }
+
+    // --- Admin command ---
+
+    #[test]
+    fn test_parse_admin_gc_dry_run() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "admin",
+            "gc",
+            "--older-than",
+            "30",
+            "--max-jobs",
+            "1000",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Admin {
+                action:
+                    TestAdminAction::Gc {
+                        older_than,
+                        max_jobs,
+                        archive,
+                        dry_run,
+                        format,
+                    },
+            } => {
+                assert_eq!(older_than, Some(30));
+                assert_eq!(max_jobs, Some(1000));
+                assert!(archive.is_none());
+                assert!(dry_run);
+                assert_eq!(format, "table");
+            }
+            _ => panic!("Expected Admin Gc command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_admin_gc_archive() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "admin",
+            "gc",
+            "--older-than",
+            "90",
+            "--archive",
+            "/tmp/arvak-archive",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Admin {
+                action:
+                    TestAdminAction::Gc {
+                        archive, dry_run, ..
+                    },
+            } => {
+                assert_eq!(archive.unwrap(), "/tmp/arvak-archive");
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected Admin Gc command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_admin_requires_subcommand() {
+        assert!(TestCli::try_parse_from(["arvak", "admin"]).is_err());
+    }
+
+    // --- Scheduler command ---
+
+    #[test]
+    fn test_parse_scheduler_simulate_defaults() {
+        let cli =
+            TestCli::try_parse_from(["arvak", "scheduler", "simulate", "--workload", "jobs.json"])
+                .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Scheduler {
+                action:
+                    TestSchedulerAction::Simulate {
+                        workload,
+                        site,
+                        site_config,
+                        qpus,
+                        format,
+                    },
+            } => {
+                assert_eq!(workload, "jobs.json");
+                assert_eq!(site, "lumi");
+                assert!(site_config.is_none());
+                assert!(qpus.is_none());
+                assert_eq!(format, "table");
+            }
+            _ => panic!("Expected Scheduler Simulate command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_scheduler_simulate_site() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "scheduler",
+            "simulate",
+            "-w",
+            "jobs.json",
+            "--site",
+            "lrz",
+            "--qpus",
+            "2",
+            "-f",
+            "json",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Scheduler {
+                action:
+                    TestSchedulerAction::Simulate {
+                        site, qpus, format, ..
+                    },
+            } => {
+                assert_eq!(site, "lrz");
+                assert_eq!(qpus, Some(2));
+                assert_eq!(format, "json");
+            }
+            _ => panic!("Expected Scheduler Simulate command"),
+        }
+    }
+
+    // --- Campaign command ---
+
+    #[test]
+    fn test_parse_campaign_run() {
+        let cli = TestCli::try_parse_from(["arvak", "campaign", "run", "study.yaml"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Campaign {
+                action:
+                    TestCampaignAction::Run {
+                        spec,
+                        output,
+                        restart,
+                    },
+            } => {
+                assert_eq!(spec, "study.yaml");
+                assert!(output.is_none());
+                assert!(!restart);
+            }
+            _ => panic!("Expected Campaign Run command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_campaign_status_output() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "campaign",
+            "status",
+            "study.yaml",
+            "-o",
+            "results.jsonl",
+            "-f",
+            "json",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Campaign {
+                action:
+                    TestCampaignAction::Status {
+                        spec,
+                        output,
+                        format,
+                    },
+            } => {
+                assert_eq!(spec, "study.yaml");
+                assert_eq!(output.unwrap(), "results.jsonl");
+                assert_eq!(format, "json");
+            }
+            _ => panic!("Expected Campaign Status command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_campaign_requires_spec() {
+        assert!(TestCli::try_parse_from(["arvak", "campaign", "run"]).is_err());
+    }
+
+    // --- QDMI command ---
+
+    #[test]
+    fn test_parse_qdmi_list_devices() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "qdmi",
+            "list",
+            "-d",
+            "MOCK=/opt/qdmi/libmock.so",
+            "--device",
+            "LRZ=/opt/qdmi/liblrz.so",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Qdmi {
+                action:
+                    TestQdmiAction::List {
+                        devices,
+                        config,
+                        format,
+                    },
+            } => {
+                assert_eq!(devices.len(), 2);
+                assert!(config.is_none());
+                assert_eq!(format, "table");
+            }
+            _ => panic!("Expected Qdmi List command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_qdmi_inspect() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "qdmi",
+            "inspect",
+            "MOCK",
+            "-c",
+            "qdmi.conf",
+            "-f",
+            "json",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Qdmi {
+                action:
+                    TestQdmiAction::Inspect {
+                        device,
+                        devices,
+                        config,
+                        format,
+                    },
+            } => {
+                assert_eq!(device, "MOCK");
+                assert!(devices.is_empty());
+                assert_eq!(config.unwrap(), "qdmi.conf");
+                assert_eq!(format, "json");
+            }
+            _ => panic!("Expected Qdmi Inspect command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_qdmi_inspect_requires_device() {
+        assert!(TestCli::try_parse_from(["arvak", "qdmi", "inspect"]).is_err());
+    }
+
+    // --- Smoke command ---
+
+    #[test]
+    fn test_parse_smoke_defaults() {
+        let cli = TestCli::try_parse_from(["arvak", "smoke"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Smoke {
+                tier,
+                qdmi_devices,
+                live_shots,
+                format,
+            } => {
+                assert_eq!(tier, "mock");
+                assert!(qdmi_devices.is_empty());
+                assert_eq!(live_shots, 100);
+                assert_eq!(format, "table");
+            }
+            _ => panic!("Expected Smoke command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_smoke_live() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "smoke",
+            "--tier",
+            "live",
+            "--qdmi-device",
+            "MOCK=/opt/qdmi/libmock.so",
+            "--live-shots",
+            "50",
+            "-f",
+            "json",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Smoke {
+                tier,
+                qdmi_devices,
+                live_shots,
+                format,
+            } => {
+                assert_eq!(tier, "live");
+                assert_eq!(qdmi_devices.len(), 1);
+                assert_eq!(live_shots, 50);
+                assert_eq!(format, "json");
+            }
+            _ => panic!("Expected Smoke command"),
+        }
+    }
+
     // --- Backends & Version ---
 
     #[test]
     fn test_parse_backends() {
         let cli = TestCli::try_parse_from(["arvak", "backends"]).unwrap();
-        assert!(matches!(cli.command, TestCommands::Backends));
+        assert!(matches!(
+            cli.command.unwrap(),
+            TestCommands::Backends {
+                refresh: false,
+                action: None
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_backends_attest() {
+        let cli = TestCli::try_parse_from(["arvak", "backends", "attest", "garnet"]).unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Backends {
+                action:
+                    Some(TestBackendsAction::Attest {
+                        name,
+                        enroll,
+                        qubits,
+                        tolerance,
+                        format,
+                        ..
+                    }),
+                ..
+            } => {
+                assert_eq!(name, "garnet");
+                assert!(!enroll);
+                assert!(qubits.is_none());
+                assert!(tolerance.is_none());
+                assert_eq!(format, "table");
+            }
+            _ => panic!("Expected Backends Attest command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_backends_attest_enroll() {
+        let cli = TestCli::try_parse_from([
+            "arvak",
+            "backends",
+            "attest",
+            "sim",
+            "--enroll",
+            "--qubits",
+            "8",
+            "-s",
+            "500",
+            "--tolerance",
+            "0.05",
+        ])
+        .unwrap();
+        match cli.command.unwrap() {
+            TestCommands::Backends {
+                action:
+                    Some(TestBackendsAction::Attest {
+                        enroll,
+                        qubits,
+                        shots,
+                        tolerance,
+                        ..
+                    }),
+                ..
+            } => {
+                assert!(enroll);
+                assert_eq!(qubits, Some(8));
+                assert_eq!(shots, Some(500));
+                assert_eq!(tolerance, Some(0.05));
+            }
+            _ => panic!("Expected Backends Attest command"),
+        }
     }
 
     #[test]
     fn test_parse_version() {
         let cli = TestCli::try_parse_from(["arvak", "version"]).unwrap();
-        assert!(matches!(cli.command, TestCommands::Version));
+        assert!(matches!(
+            cli.command.unwrap(),
+            TestCommands::Version { features: false }
+        ));
+    }
+
+    #[test]
+    fn test_parse_version_features() {
+        let cli = TestCli::try_parse_from(["arvak", "version", "--features"]).unwrap();
+        assert!(matches!(
+            cli.command.unwrap(),
+            TestCommands::Version { features: true }
+        ));
+    }
+
+    // --- Completions & schema ---
+
+    #[test]
+    fn test_parse_completions() {
+        let cli = TestCli::try_parse_from(["arvak", "completions", "zsh"]).unwrap();
+        assert!(matches!(
+            cli.command.unwrap(),
+            TestCommands::Completions {
+                shell: clap_complete::Shell::Zsh
+            }
+        ));
+        assert!(TestCli::try_parse_from(["arvak", "completions", "tcsh"]).is_err());
+        assert!(TestCli::try_parse_from(["arvak", "completions"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_schema() {
+        let cli = TestCli::try_parse_from(["arvak", "--schema"]).unwrap();
+        assert!(cli.schema);
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn test_parse_no_arguments_shows_help() {
+        let err = TestCli::try_parse_from(["arvak"]).err().unwrap();
+        assert_eq!(
+            err.kind(),
+            clap::error::ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
     }
 
     // --- Verbose flag ---
@@ -0,0 +1,280 @@
+//! Adaptive optimization level selection.
+//!
+//! With `optimization_level: auto` the compiler picks a level per circuit
+//! instead of making users guess between 1 and 3. It looks at the circuit's
+//! size and structure and at the target, estimates how long each level
+//! would take to compile, and takes the most thorough level that is useful
+//! for this circuit and fits the compile-time budget:
+//!
+//! - Circuits without two-qubit gates, or whose interactions already fit the
+//!   coupling map under the trivial layout, gain nothing from dense layout,
+//!   so level 1 is enough.
+//! - Circuits that need routing get level 2 (dense layout).
+//! - Variational circuits get level 3: they are compiled once and re-run
+//!   for every optimizer step, so extra compile time pays off.
+//!
+//! The time estimates come from a cost model of the current passes: level
+//! 0 is linear in the gate count, while SABRE routing and single-qubit
+//! resynthesis grow roughly quadratically.
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use arvak_compile::{AdaptiveOptimization, BasisGates, CouplingMap, PassManagerBuilder};
+//! use arvak_ir::Circuit;
+//!
+//! let dag = Circuit::ghz(5).unwrap().into_dag();
+//! let builder = PassManagerBuilder::new()
+//!     .with_target(CouplingMap::star(5), BasisGates::iqm())
+//!     .with_adaptive_optimization(&dag, &AdaptiveOptimization::new(Duration::from_secs(2)));
+//! let (_pm, props) = builder.build();
+//!
+//! let selection = props.get::<arvak_compile::AdaptiveSelection>().unwrap();
+//! assert_eq!(selection.level, 2);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use arvak_ir::CircuitDag;
+use arvak_ir::instruction::InstructionKind;
+
+use crate::property::PropertySet;
+
+/// Seconds per gate for the passes every level runs.
+const LINEAR_COST_PER_OP: f64 = 15e-6;
+/// Seconds per squared gate for SABRE routing (levels >= 1).
+const ROUTING_COST_PER_OP2: f64 = 2e-7;
+/// Seconds per squared gate for single-qubit resynthesis (levels >= 1).
+const RESYNTHESIS_COST_PER_OP2: f64 = 3e-6;
+/// Seconds per (circuit qubit × device qubit) for dense layout (levels >= 2).
+const LAYOUT_COST_PER_QUBIT2: f64 = 1e-6;
+
+/// An optimization level: fixed, or chosen per circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// A fixed level (0-3).
+    Fixed(u8),
+    /// Chosen per circuit by [`AdaptiveOptimization`].
+    Auto,
+}
+
+impl Default for OptimizationLevel {
+    fn default() -> Self {
+        OptimizationLevel::Fixed(1)
+    }
+}
+
+impl FromStr for OptimizationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(OptimizationLevel::Auto),
+            other => match other.parse::<u8>() {
+                Ok(level) if level <= 3 => Ok(OptimizationLevel::Fixed(level)),
+                _ => Err(format!(
+                    "invalid optimization level '{s}' (expected 0-3 or auto)"
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for OptimizationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptimizationLevel::Fixed(level) => write!(f, "{level}"),
+            OptimizationLevel::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Settings for adaptive level selection.
+#[derive(Debug, Clone)]
+pub struct AdaptiveOptimization {
+    /// Compile-time budget.
+    budget: Duration,
+    /// Treat the circuit as variational regardless of its parameters.
+    variational: Option<bool>,
+}
+
+impl AdaptiveOptimization {
+    /// Default compile-time budget.
+    pub const DEFAULT_BUDGET: Duration = Duration::from_secs(2);
+
+    /// Create settings with a compile-time budget.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            variational: None,
+        }
+    }
+
+    /// Override whether the circuit is variational.
+    ///
+    /// By default a circuit with free parameters is variational. Callers
+    /// with a better workload classification (e.g. a QAOA circuit with
+    /// bound angles) can say so here.
+    #[must_use]
+    pub fn with_variational(mut self, variational: bool) -> Self {
+        self.variational = Some(variational);
+        self
+    }
+
+    /// Choose a level for `dag` on the target in `properties`.
+    pub fn select(&self, dag: &CircuitDag, properties: &PropertySet) -> AdaptiveSelection {
+        let mut num_ops = 0usize;
+        let mut two_qubit_gates = 0usize;
+        let mut symbolic = false;
+        let mut fits_coupling = true;
+        for (_, inst) in dag.topological_ops() {
+            num_ops += 1;
+            if let InstructionKind::Gate(gate) = &inst.kind {
+                symbolic |= gate.parameters().iter().any(|p| p.is_symbolic());
+            }
+            if inst.is_gate() && inst.qubits.len() >= 2 {
+                two_qubit_gates += 1;
+                if let Some(coupling_map) = &properties.coupling_map {
+                    fits_coupling &= inst.qubits.len() == 2
+                        && coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0);
+                }
+            }
+        }
+        let variational = self.variational.unwrap_or(symbolic);
+        let needs_routing = properties.coupling_map.is_some() && !fits_coupling;
+
+        let (wanted, reason) = if two_qubit_gates == 0 {
+            (1, "no two-qubit gates")
+        } else if !needs_routing {
+            (1, "interactions fit the target without routing")
+        } else if variational {
+            (
+                3,
+                "variational circuit, compile cost amortized over iterations",
+            )
+        } else {
+            (2, "needs routing")
+        };
+
+        let device_qubits = properties
+            .coupling_map
+            .as_ref()
+            .map_or(0, |c| c.num_qubits() as usize);
+        let estimate = |level: u8| -> Duration {
+            let ops = num_ops as f64;
+            let mut seconds = LINEAR_COST_PER_OP * ops;
+            if level >= 1 {
+                seconds += RESYNTHESIS_COST_PER_OP2 * ops * ops;
+                if properties.coupling_map.is_some() {
+                    seconds += ROUTING_COST_PER_OP2 * ops * ops;
+                }
+            }
+            if level >= 2 && properties.coupling_map.is_some() {
+                seconds += LAYOUT_COST_PER_QUBIT2 * dag.num_qubits() as f64 * device_qubits as f64;
+            }
+            Duration::from_secs_f64(seconds)
+        };
+
+        let level = (1..=wanted)
+            .rev()
+            .find(|&level| estimate(level) <= self.budget)
+            .unwrap_or(0);
+        let reason = if level < wanted {
+            format!("{reason}; level {wanted} exceeds the compile-time budget")
+        } else {
+            reason.to_string()
+        };
+
+        AdaptiveSelection {
+            level,
+            reason,
+            estimated_compile_time: estimate(level),
+            budget: self.budget,
+        }
+    }
+}
+
+impl Default for AdaptiveOptimization {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_BUDGET)
+    }
+}
+
+/// The level chosen by [`AdaptiveOptimization`], stored in the
+/// [`PropertySet`] by
+/// [`PassManagerBuilder::with_adaptive_optimization`](crate::PassManagerBuilder::with_adaptive_optimization).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptiveSelection {
+    /// Chosen optimization level.
+    pub level: u8,
+    /// Why this level was chosen.
+    pub reason: String,
+    /// Estimated compile time at this level.
+    pub estimated_compile_time: Duration,
+    /// Compile-time budget the selection respected.
+    pub budget: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{BasisGates, CouplingMap};
+    use arvak_ir::{Circuit, ParameterExpression, QubitId};
+
+    fn target(coupling_map: CouplingMap) -> PropertySet {
+        PropertySet::new().with_target(coupling_map, BasisGates::iqm())
+    }
+
+    #[test]
+    fn test_parse_optimization_level() {
+        assert_eq!("auto".parse(), Ok(OptimizationLevel::Auto));
+        assert_eq!("2".parse(), Ok(OptimizationLevel::Fixed(2)));
+        assert!("4".parse::<OptimizationLevel>().is_err());
+        assert_eq!(OptimizationLevel::Auto.to_string(), "auto");
+    }
+
+    #[test]
+    fn test_select_by_structure() {
+        let auto = AdaptiveOptimization::default();
+        let linear = target(CouplingMap::linear(5));
+
+        // Nearest-neighbour interactions need no routing.
+        let ghz = Circuit::ghz(5).unwrap().into_dag();
+        assert_eq!(auto.select(&ghz, &linear).level, 1);
+
+        let mut far = Circuit::with_size("far", 5, 0);
+        far.cx(QubitId(0), QubitId(4)).unwrap();
+        let far_dag = far.clone().into_dag();
+        assert_eq!(auto.select(&far_dag, &linear).level, 2);
+
+        far.rz(ParameterExpression::symbol("theta"), QubitId(0))
+            .unwrap();
+        assert_eq!(auto.select(&far.into_dag(), &linear).level, 3);
+        assert_eq!(
+            auto.clone()
+                .with_variational(true)
+                .select(&far_dag, &linear)
+                .level,
+            3
+        );
+    }
+
+    #[test]
+    fn test_select_respects_budget() {
+        let mut circuit = Circuit::with_size("wide", 5, 0);
+        for _ in 0..200 {
+            circuit.cx(QubitId(0), QubitId(4)).unwrap();
+        }
+        let dag = circuit.into_dag();
+        let props = target(CouplingMap::linear(5));
+
+        let generous = AdaptiveOptimization::new(Duration::from_secs(60)).select(&dag, &props);
+        assert_eq!(generous.level, 2);
+        assert!(generous.estimated_compile_time <= generous.budget);
+
+        let tight = AdaptiveOptimization::new(Duration::from_millis(10)).select(&dag, &props);
+        assert_eq!(tight.level, 0);
+        assert!(tight.reason.contains("budget"));
+    }
+}
@@ -0,0 +1,233 @@
+//! Shared pool of scratch (ancilla) qubits.
+//!
+//! Several passes need temporary qubits: uncomputation flows borrow clean
+//! qubits to hold intermediate values, and bridge decompositions route a
+//! gate through idle qubits between its operands. An [`AncillaPool`],
+//! stored as a custom property in the [`PropertySet`], hands out physical
+//! qubits that the layout leaves idle, and records which pass holds each
+//! one so two passes never use the same qubit at the same time.
+//!
+//! ```
+//! use arvak_compile::{BasisGates, CouplingMap, Layout, PropertySet};
+//! use arvak_ir::QubitId;
+//!
+//! let mut layout = Layout::new();
+//! layout.add(QubitId(0), 0);
+//! layout.add(QubitId(1), 2);
+//! let coupling_map = CouplingMap::linear(4);
+//! let mut props = PropertySet::new()
+//!     .with_target(coupling_map.clone(), BasisGates::iqm())
+//!     .with_layout(layout);
+//!
+//! let pool = props.ancilla_pool().unwrap();
+//! assert_eq!(pool.available(), vec![1, 3]);
+//!
+//! let ancilla = pool.request_near("MyPass", 2, &coupling_map).unwrap();
+//! assert_eq!(ancilla, 1);
+//! pool.release(ancilla).unwrap();
+//! ```
+//!
+//! [`PropertySet`]: crate::PropertySet
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::{CompileError, CompileResult};
+use crate::property::{CouplingMap, Layout, TargetPatch};
+
+/// Physical qubits available as ancillas, and who holds them.
+///
+/// Requests are served in physical qubit order, so allocation is
+/// reproducible. Passes release what they request; a pass that keeps an
+/// ancilla past its own run (e.g. a routing bridge that stays in the
+/// circuit) leaves it allocated so later passes skip it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AncillaPool {
+    /// Idle qubits not handed out.
+    free: BTreeSet<u32>,
+    /// Handed-out qubits with the name of the pass holding them.
+    allocated: BTreeMap<u32, String>,
+}
+
+impl AncillaPool {
+    /// Create a pool of the given physical qubits.
+    pub fn new(qubits: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            free: qubits.into_iter().collect(),
+            allocated: BTreeMap::new(),
+        }
+    }
+
+    /// Create a pool of the physical qubits of `coupling_map` that have no
+    /// logical qubit in `layout`.
+    ///
+    /// With a `patch`, only idle qubits inside the patch are used.
+    pub fn from_layout(
+        layout: &Layout,
+        coupling_map: &CouplingMap,
+        patch: Option<&TargetPatch>,
+    ) -> Self {
+        Self::new(
+            (0..coupling_map.num_qubits())
+                .filter(|&p| layout.get_logical(p).is_none())
+                .filter(|&p| patch.is_none_or(|patch| patch.contains(p))),
+        )
+    }
+
+    /// Take the lowest-numbered free ancilla for `owner`.
+    pub fn request(&mut self, owner: &str) -> Option<u32> {
+        let qubit = self.free.pop_first()?;
+        self.allocated.insert(qubit, owner.to_string());
+        Some(qubit)
+    }
+
+    /// Take `n` free ancillas for `owner`, or none if fewer are free.
+    pub fn request_many(&mut self, owner: &str, n: usize) -> Option<Vec<u32>> {
+        if self.free.len() < n {
+            return None;
+        }
+        (0..n).map(|_| self.request(owner)).collect()
+    }
+
+    /// Take the free ancilla closest to physical qubit `qubit`.
+    ///
+    /// Distance is counted in couplings of `coupling_map`; ancillas it
+    /// cannot reach are never chosen. Ties go to the lower-numbered qubit.
+    pub fn request_near(
+        &mut self,
+        owner: &str,
+        qubit: u32,
+        coupling_map: &CouplingMap,
+    ) -> Option<u32> {
+        let nearest = self
+            .free
+            .iter()
+            .filter_map(|&a| coupling_map.distance(qubit, a).map(|d| (d, a)))
+            .min()?
+            .1;
+        self.free.remove(&nearest);
+        self.allocated.insert(nearest, owner.to_string());
+        Some(nearest)
+    }
+
+    /// Return an ancilla to the pool.
+    ///
+    /// The caller must have restored it to `|0⟩`.
+    pub fn release(&mut self, qubit: u32) -> CompileResult<()> {
+        if self.allocated.remove(&qubit).is_none() {
+            return Err(CompileError::AncillaNotAllocated(qubit));
+        }
+        self.free.insert(qubit);
+        Ok(())
+    }
+
+    /// Return every ancilla held by `owner`. Returns how many there were.
+    pub fn release_all(&mut self, owner: &str) -> usize {
+        let held: Vec<u32> = self
+            .allocated
+            .iter()
+            .filter(|(_, o)| o.as_str() == owner)
+            .map(|(&q, _)| q)
+            .collect();
+        for &qubit in &held {
+            self.allocated.remove(&qubit);
+            self.free.insert(qubit);
+        }
+        held.len()
+    }
+
+    /// Check whether `qubit` is free to request.
+    pub fn is_free(&self, qubit: u32) -> bool {
+        self.free.contains(&qubit)
+    }
+
+    /// Check whether `qubit` is held by some pass.
+    pub fn is_allocated(&self, qubit: u32) -> bool {
+        self.allocated.contains_key(&qubit)
+    }
+
+    /// The pass holding `qubit`, if it is allocated.
+    pub fn owner(&self, qubit: u32) -> Option<&str> {
+        self.allocated.get(&qubit).map(String::as_str)
+    }
+
+    /// Free ancillas, in physical qubit order.
+    pub fn available(&self) -> Vec<u32> {
+        self.free.iter().copied().collect()
+    }
+
+    /// Allocated ancillas with their owners, in physical qubit order.
+    pub fn allocated(&self) -> impl Iterator<Item = (u32, &str)> + '_ {
+        self.allocated.iter().map(|(&q, o)| (q, o.as_str()))
+    }
+
+    /// Drop free ancillas that now hold a logical qubit in `layout`.
+    ///
+    /// Routing moves logical qubits onto idle positions; call this after
+    /// it so the pool only offers qubits that are still idle.
+    pub fn retain_idle(&mut self, layout: &Layout) {
+        self.free.retain(|&p| layout.get_logical(p).is_none());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::QubitId;
+
+    fn layout(mapping: &[(u32, u32)]) -> Layout {
+        let mut layout = Layout::new();
+        for &(logical, physical) in mapping {
+            layout.add(QubitId(logical), physical);
+        }
+        layout
+    }
+
+    #[test]
+    fn test_pool_from_layout_and_patch() {
+        let layout = layout(&[(0, 0), (1, 1)]);
+        let coupling_map = CouplingMap::linear(5);
+
+        let pool = AncillaPool::from_layout(&layout, &coupling_map, None);
+        assert_eq!(pool.available(), vec![2, 3, 4]);
+
+        let patch = TargetPatch::new(&[0, 1, 2, 3]);
+        let pool = AncillaPool::from_layout(&layout, &coupling_map, Some(&patch));
+        assert_eq!(pool.available(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_request_and_release() {
+        let mut pool = AncillaPool::new([3, 1, 2]);
+        assert_eq!(pool.request("A"), Some(1));
+        assert_eq!(pool.request_many("B", 2), Some(vec![2, 3]));
+        assert_eq!(pool.request("A"), None);
+        assert_eq!(pool.request_many("A", 1), None);
+        assert_eq!(pool.owner(2), Some("B"));
+
+        assert_eq!(pool.release_all("B"), 2);
+        assert!(pool.is_free(2) && pool.is_free(3));
+        pool.release(1).unwrap();
+        assert!(matches!(
+            pool.release(1),
+            Err(CompileError::AncillaNotAllocated(1))
+        ));
+        assert_eq!(pool.allocated().count(), 0);
+    }
+
+    #[test]
+    fn test_request_near() {
+        let coupling_map = CouplingMap::linear(6);
+        let mut pool = AncillaPool::new([0, 4, 5]);
+        assert_eq!(pool.request_near("A", 2, &coupling_map), Some(0));
+        assert_eq!(pool.request_near("A", 2, &coupling_map), Some(4));
+        assert!(pool.is_allocated(4));
+        assert_eq!(pool.available(), vec![5]);
+    }
+
+    #[test]
+    fn test_retain_idle() {
+        let mut pool = AncillaPool::new([1, 2]);
+        pool.retain_idle(&layout(&[(0, 2)]));
+        assert_eq!(pool.available(), vec![1]);
+    }
+}
@@ -0,0 +1,128 @@
+//! Parallel compilation of many circuits.
+//!
+//! Parameter sweeps submit hundreds or thousands of circuits that differ
+//! only in their bound angles. [`compile_batch`] compiles them on the rayon
+//! thread pool, each with its own [`PassManager`](crate::PassManager) and
+//! [`PropertySet`](crate::PropertySet), and returns the results in input
+//! order. One failing circuit does not stop the others.
+//!
+//! ```rust
+//! use arvak_compile::{BasisGates, CompileTarget, CouplingMap, compile_batch};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let circuits: Vec<Circuit> = (0..8)
+//!     .map(|i| {
+//!         let mut circuit = Circuit::with_size("sweep", 2, 0);
+//!         circuit.rx(f64::from(i) * 0.1, QubitId(0)).unwrap();
+//!         circuit.cx(QubitId(0), QubitId(1)).unwrap();
+//!         circuit
+//!     })
+//!     .collect();
+//!
+//! let target = CompileTarget::new(CouplingMap::linear(3), BasisGates::iqm());
+//! let compiled = compile_batch(circuits, &target, 2);
+//! assert_eq!(compiled.len(), 8);
+//! assert!(compiled.iter().all(Result::is_ok));
+//! ```
+
+use rayon::prelude::*;
+use tracing::debug;
+
+use arvak_ir::Circuit;
+
+use crate::error::CompileResult;
+use crate::manager::PassManagerBuilder;
+use crate::transpile::{CompileTarget, compile};
+
+/// Compile `circuits` for `target` at optimization `level`, in parallel.
+///
+/// The results are in the order of `circuits`.
+pub fn compile_batch(
+    circuits: Vec<Circuit>,
+    target: &CompileTarget,
+    level: u8,
+) -> Vec<CompileResult<Circuit>> {
+    compile_batch_with(circuits, || target.builder(level))
+}
+
+/// Compile `circuits` in parallel, each with a pass manager from
+/// `builder`.
+///
+/// For settings [`CompileTarget`] does not cover, such as a time budget or
+/// a seed. The results are in the order of `circuits`.
+pub fn compile_batch_with<F>(circuits: Vec<Circuit>, builder: F) -> Vec<CompileResult<Circuit>>
+where
+    F: Fn() -> PassManagerBuilder + Sync,
+{
+    debug!(
+        "Compiling {} circuits on {} threads",
+        circuits.len(),
+        rayon::current_num_threads()
+    );
+    circuits
+        .into_par_iter()
+        .map(|circuit| compile(builder(), circuit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{BasisGates, CouplingMap};
+    use crate::transpile::transpile;
+    use arvak_ir::QubitId;
+
+    fn sweep(n: usize) -> Vec<Circuit> {
+        (0..n)
+            .map(|i| {
+                let mut circuit = Circuit::with_size(format!("c{i}"), 3, 0);
+                circuit.h(QubitId(0)).unwrap();
+                circuit.ry(i as f64 * 0.01, QubitId(1)).unwrap();
+                circuit.cx(QubitId(0), QubitId(2)).unwrap();
+                circuit
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_matches_serial() {
+        let target = CompileTarget::new(CouplingMap::linear(3), BasisGates::iqm());
+        let circuits = sweep(64);
+
+        let batch = compile_batch(circuits.clone(), &target, 2);
+        assert_eq!(batch.len(), circuits.len());
+
+        for (circuit, compiled) in circuits.iter().zip(batch) {
+            let serial = transpile(circuit, &target, 2).unwrap();
+            let compiled = compiled.unwrap();
+            assert_eq!(compiled.name(), serial.name());
+            assert_eq!(compiled.dag().to_bytes(), serial.dag().to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_batch_reports_errors_per_circuit() {
+        // The middle circuit needs more qubits than the target has.
+        let mut circuits = sweep(3);
+        let mut wide = Circuit::with_size("wide", 5, 0);
+        wide.cx(QubitId(0), QubitId(4)).unwrap();
+        circuits[1] = wide;
+
+        let target = CompileTarget::new(CouplingMap::linear(3), BasisGates::iqm());
+        let results = compile_batch(circuits, &target, 1);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn test_batch_with_builder() {
+        let results = compile_batch_with(sweep(4), || {
+            PassManagerBuilder::new()
+                .with_optimization_level(0)
+                .with_seed(7)
+        });
+        assert!(results.iter().all(Result::is_ok));
+        assert!(compile_batch_with(Vec::new(), PassManagerBuilder::new).is_empty());
+    }
+}
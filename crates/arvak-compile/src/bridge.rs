@@ -0,0 +1,224 @@
+//! CX bridges as an alternative to SWAP insertion.
+//!
+//! A CX between physical qubits two hops apart, `control - middle - target`,
+//! can run in place through the middle qubit:
+//!
+//! ```text
+//! cx control, middle;
+//! cx middle, target;
+//! cx control, middle;
+//! cx middle, target;
+//! ```
+//!
+//! whatever state the middle qubit holds, and leaves it unchanged. That is
+//! four CXs, as many as a SWAP and the CX, but the layout is not permuted.
+//! Which is cheaper depends on what follows: a SWAP that also brings
+//! upcoming gates together pays for itself, one that pulls them apart does
+//! not. With a [`BridgeMode`] stored as a custom property in the
+//! [`PropertySet`], [`BasicRouting`] and [`SabreRouting`] weigh the two for
+//! every second-neighbour CX and record each choice in [`BridgeDecisions`].
+//!
+//! ```
+//! use arvak_compile::passes::{BasicRouting, TrivialLayout};
+//! use arvak_compile::{BasisGates, BridgeDecisions, BridgeMode, CouplingMap, Pass, PropertySet};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let mut circuit = Circuit::with_size("cx", 3, 0);
+//! circuit.cx(QubitId(0), QubitId(2)).unwrap();
+//! let mut dag = circuit.into_dag();
+//! let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+//! props.insert(BridgeMode::Always);
+//!
+//! TrivialLayout.run(&mut dag, &mut props).unwrap();
+//! BasicRouting.run(&mut dag, &mut props).unwrap();
+//!
+//! assert_eq!(dag.num_ops(), 4);
+//! assert_eq!(props.get::<BridgeDecisions>().unwrap().bridged(), 1);
+//! ```
+//!
+//! [`PropertySet`]: crate::PropertySet
+//! [`BasicRouting`]: crate::passes::BasicRouting
+//! [`SabreRouting`]: crate::passes::SabreRouting
+
+use arvak_ir::{GateKind, Instruction, InstructionKind, QubitId, StandardGate};
+
+use crate::error_weights::ErrorCosts;
+use crate::property::CouplingMap;
+
+/// Costs closer than this count as a tie, which the bridge wins.
+const COST_TIE_TOLERANCE: f64 = 1e-9;
+
+/// When routing executes a second-neighbour CX through a bridge instead of
+/// inserting a SWAP.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BridgeMode {
+    /// Always insert SWAPs.
+    #[default]
+    Never,
+    /// Bridge when it costs no more than the SWAP and the CX, counting
+    /// calibrated errors and the SWAP's effect on upcoming gates.
+    WhenCheaper,
+    /// Bridge every second-neighbour CX.
+    Always,
+}
+
+/// One routing choice between a bridge and a SWAP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeDecision {
+    /// Physical qubit of the CX's control.
+    pub control: u32,
+    /// Physical qubit between control and target.
+    pub middle: u32,
+    /// Physical qubit of the CX's target.
+    pub target: u32,
+    /// Whether the CX was bridged; otherwise SWAPs brought it together.
+    pub bridged: bool,
+}
+
+/// The choices made by the last routing pass, stored in the property set
+/// when a [`BridgeMode`] is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BridgeDecisions {
+    /// Every second-neighbour CX weighed, in routing order.
+    pub decisions: Vec<BridgeDecision>,
+}
+
+impl BridgeDecisions {
+    /// Number of CXs executed through a bridge.
+    pub fn bridged(&self) -> usize {
+        self.decisions.iter().filter(|d| d.bridged).count()
+    }
+
+    /// Number of CXs routed with SWAPs instead.
+    pub fn swapped(&self) -> usize {
+        self.decisions.len() - self.bridged()
+    }
+}
+
+/// The qubit between `control` and `target` if `inst` is an unconditioned
+/// CX on second neighbours.
+pub(crate) fn bridge_middle(
+    inst: &Instruction,
+    control: u32,
+    target: u32,
+    coupling_map: &CouplingMap,
+) -> Option<u32> {
+    let InstructionKind::Gate(gate) = &inst.kind else {
+        return None;
+    };
+    if gate.condition.is_some() || !matches!(gate.kind, GateKind::Standard(StandardGate::CX)) {
+        return None;
+    }
+    match coupling_map.shortest_path(control, target)?.as_slice() {
+        &[_, middle, _] => Some(middle),
+        _ => None,
+    }
+}
+
+/// Whether to bridge the CX over `control - middle - target`.
+///
+/// `lookahead(a, b)` is the change in the cost of upcoming gates if the
+/// SWAP `(a, b)` were applied; negative when the SWAP helps them.
+pub(crate) fn prefer_bridge(
+    mode: BridgeMode,
+    costs: Option<&ErrorCosts>,
+    control: u32,
+    middle: u32,
+    target: u32,
+    lookahead: impl Fn(u32, u32) -> f64,
+) -> bool {
+    match mode {
+        BridgeMode::Never => false,
+        BridgeMode::Always => true,
+        BridgeMode::WhenCheaper => {
+            // In SWAP units: a SWAP costs one, a CX a third of one.
+            let swap = |a: u32, b: u32| costs.map_or(1.0, |costs| costs.swap(a, b));
+            let (first, second) = (swap(control, middle), swap(middle, target));
+            let bridge = 2.0 * (first + second) / 3.0;
+            let via_first = first + second / 3.0 + lookahead(control, middle);
+            let via_second = second + first / 3.0 + lookahead(middle, target);
+            bridge <= via_first.min(via_second) + COST_TIE_TOLERANCE
+        }
+    }
+}
+
+/// The four CXs that execute `inst` over `control - middle - target`.
+///
+/// The last CX is `inst` itself on `(middle, target)`, keeping its label;
+/// the others are marked with `origin`.
+pub(crate) fn bridge_cx(
+    inst: &Instruction,
+    control: u32,
+    middle: u32,
+    target: u32,
+    origin: &'static str,
+) -> Vec<Instruction> {
+    let cx = |a: u32, b: u32| {
+        Instruction::two_qubit_gate(StandardGate::CX, QubitId(a), QubitId(b)).with_origin(origin)
+    };
+    let mut last = inst.clone();
+    last.qubits = vec![QubitId(middle), QubitId(target)];
+    vec![
+        cx(control, middle),
+        cx(middle, target),
+        cx(control, middle),
+        last,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::Circuit;
+
+    #[test]
+    fn test_bridge_cx_is_remote_cx() {
+        let cx = Instruction::two_qubit_gate(StandardGate::CX, QubitId(0), QubitId(2));
+        let mut bridged = Circuit::with_size("bridge", 3, 0);
+        for inst in bridge_cx(&cx, 0, 1, 2, "test") {
+            bridged.dag_mut().apply(inst).unwrap();
+        }
+        let mut direct = Circuit::with_size("direct", 3, 0);
+        direct.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let (a, b) = (bridged.to_matrix().unwrap(), direct.to_matrix().unwrap());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_prefer_bridge_weighs_lookahead() {
+        let none = |_: u32, _: u32| 0.0;
+        let helps_first = |a: u32, _: u32| if a == 0 { -1.0 } else { 0.0 };
+        assert!(!prefer_bridge(BridgeMode::Never, None, 0, 1, 2, none));
+        assert!(prefer_bridge(
+            BridgeMode::Always,
+            None,
+            0,
+            1,
+            2,
+            helps_first
+        ));
+        // Equal gate cost: the bridge wins unless a SWAP helps later gates.
+        assert!(prefer_bridge(BridgeMode::WhenCheaper, None, 0, 1, 2, none));
+        assert!(!prefer_bridge(
+            BridgeMode::WhenCheaper,
+            None,
+            0,
+            1,
+            2,
+            helps_first
+        ));
+    }
+
+    #[test]
+    fn test_bridge_middle_requires_second_neighbours() {
+        let coupling_map = CouplingMap::linear(4);
+        let cx = Instruction::two_qubit_gate(StandardGate::CX, QubitId(0), QubitId(2));
+        assert_eq!(bridge_middle(&cx, 0, 2, &coupling_map), Some(1));
+        assert_eq!(bridge_middle(&cx, 0, 3, &coupling_map), None);
+        let cz = Instruction::two_qubit_gate(StandardGate::CZ, QubitId(0), QubitId(2));
+        assert_eq!(bridge_middle(&cz, 0, 2, &coupling_map), None);
+    }
+}
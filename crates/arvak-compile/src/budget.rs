@@ -0,0 +1,146 @@
+//! Wall-clock compile-time budgets.
+//!
+//! A [`PassManager`](crate::PassManager) with a time budget
+//! ([`with_time_budget`](crate::PassManager::with_time_budget)) stores a
+//! [`CompileDeadline`] in the [`PropertySet`](crate::PropertySet) while it
+//! runs. Optimization passes check it between rewrites and stop once it has
+//! passed, keeping the improvements made so far; passes that only optimize
+//! are skipped entirely when the budget is already spent. Required passes
+//! (layout, routing, translation) always run, so the output stays valid for
+//! the target even when the budget is exceeded.
+//!
+//! After a run, the pass manager stores [`CompileTimings`] with the time
+//! each pass took and how it ended.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// The point in time at which optimization must stop.
+#[derive(Debug, Clone, Copy)]
+pub struct CompileDeadline {
+    start: Instant,
+    budget: Duration,
+}
+
+impl CompileDeadline {
+    /// Start a deadline `budget` from now.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            budget,
+        }
+    }
+
+    /// Check whether the budget is spent.
+    pub fn expired(&self) -> bool {
+        self.start.elapsed() >= self.budget
+    }
+
+    /// Time left in the budget.
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.start.elapsed())
+    }
+}
+
+/// How a pass ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PassOutcome {
+    /// The pass ran to completion.
+    Completed,
+    /// The pass decided not to run (`should_run` returned `false`).
+    Skipped,
+    /// The optimization pass was not started because the budget was spent.
+    SkippedOverBudget,
+    /// The optimization pass ran into the budget and stopped early.
+    CutShort,
+}
+
+/// Time spent in one pass.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassTiming {
+    /// Name of the pass.
+    pub name: String,
+    /// Wall-clock time spent in the pass.
+    pub elapsed: Duration,
+    /// How the pass ended.
+    pub outcome: PassOutcome,
+}
+
+/// Per-pass timings of a pass manager run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompileTimings {
+    /// The time budget of the run, if any.
+    pub budget: Option<Duration>,
+    /// One entry per pass, in pipeline order.
+    pub passes: Vec<PassTiming>,
+}
+
+impl CompileTimings {
+    /// Total time spent in passes.
+    pub fn total(&self) -> Duration {
+        self.passes.iter().map(|p| p.elapsed).sum()
+    }
+
+    /// Fraction of the budget `elapsed` consumed, if there is a budget.
+    pub fn budget_fraction(&self, elapsed: Duration) -> Option<f64> {
+        self.budget
+            .filter(|b| !b.is_zero())
+            .map(|b| elapsed.as_secs_f64() / b.as_secs_f64())
+    }
+
+    /// Check whether any optimization was skipped or cut short.
+    pub fn budget_exhausted(&self) -> bool {
+        self.passes.iter().any(|p| {
+            matches!(
+                p.outcome,
+                PassOutcome::SkippedOverBudget | PassOutcome::CutShort
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::PropertySet;
+
+    #[test]
+    fn test_deadline() {
+        let mut props = PropertySet::new();
+        assert!(!props.budget_exhausted());
+
+        props.insert(CompileDeadline::new(Duration::from_secs(3600)));
+        assert!(!props.budget_exhausted());
+
+        props.insert(CompileDeadline::new(Duration::ZERO));
+        assert!(props.budget_exhausted());
+        assert_eq!(
+            props.get::<CompileDeadline>().unwrap().remaining(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_compile_timings() {
+        let timings = CompileTimings {
+            budget: Some(Duration::from_secs(2)),
+            passes: vec![
+                PassTiming {
+                    name: "SabreRouting".into(),
+                    elapsed: Duration::from_secs(1),
+                    outcome: PassOutcome::Completed,
+                },
+                PassTiming {
+                    name: "Optimize1qGates".into(),
+                    elapsed: Duration::from_millis(500),
+                    outcome: PassOutcome::CutShort,
+                },
+            ],
+        };
+        assert_eq!(timings.total(), Duration::from_millis(1500));
+        assert_eq!(timings.budget_fraction(Duration::from_secs(1)), Some(0.5));
+        assert!(timings.budget_exhausted());
+    }
+}
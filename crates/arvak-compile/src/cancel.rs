@@ -0,0 +1,69 @@
+//! Cooperative cancellation of a compilation.
+//!
+//! Heavy optimization of a pathological circuit can run for minutes. A
+//! [`PassManager`](crate::PassManager) given a [`CancellationToken`]
+//! ([`with_cancellation`](crate::PassManager::with_cancellation)) stores it
+//! in the [`PropertySet`](crate::PropertySet) while it runs and checks it
+//! before every pass; long loops such as the routing search check it as
+//! well through [`PropertySet::check_cancelled`](crate::PropertySet::check_cancelled).
+//! Once the token is cancelled, from any thread, the run stops with
+//! [`CompileError::Cancelled`](crate::CompileError::Cancelled) and the DAG
+//! is left partially compiled.
+//!
+//! ```rust
+//! use arvak_compile::{CancellationToken, CompileError, PassManagerBuilder};
+//! use arvak_compile::{BasisGates, CouplingMap};
+//! use arvak_ir::Circuit;
+//!
+//! let token = CancellationToken::new();
+//! let (pm, mut props) = PassManagerBuilder::new()
+//!     .with_target(CouplingMap::linear(3), BasisGates::iqm())
+//!     .with_cancellation(token.clone())
+//!     .build();
+//!
+//! token.cancel();
+//! let mut dag = Circuit::bell().unwrap().into_dag();
+//! assert!(matches!(pm.run(&mut dag, &mut props), Err(CompileError::Cancelled)));
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag that stops a compilation once set.
+///
+/// Clones share the flag, so one clone can be handed to a signal handler or
+/// request while another is checked by the compiler.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every compilation holding this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether [`cancel`](Self::cancel) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_flag() {
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        assert!(!token.is_cancelled());
+
+        std::thread::spawn(move || handle.cancel()).join().unwrap();
+        assert!(token.is_cancelled());
+    }
+}
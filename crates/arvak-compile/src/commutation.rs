@@ -0,0 +1,310 @@
+//! Gate commutation relations.
+//!
+//! [`commutes`] decides whether two gates can be swapped without changing
+//! the circuit. Cheap rules cover the common cases: gates on disjoint qubits,
+//! gates diagonal in the Z basis (`Rz`, `P`, `CZ`, `RZZ`, ...) and gates
+//! that are functions of X (`Rx`, `SX`, `RXX`, ...). Other pairs are
+//! decided by comparing `AB` and `BA` as dense matrices over the qubits they
+//! share; results for parameter-free standard gates are cached by gate names
+//! and relative qubit placement, so each pair is computed once.
+//!
+//! Commutation is exact, including global phase. Pairs that cannot be
+//! decided (symbolic parameters, conditioned gates, more than
+//! [`MAX_MATRIX_QUBITS`] qubits) are reported as not commuting.
+
+use std::sync::{Mutex, OnceLock};
+
+use arvak_ir::gate::{Gate, GateKind, StandardGate};
+use arvak_ir::instruction::{Instruction, InstructionKind};
+use arvak_ir::qubit::{ClbitId, QubitId};
+use arvak_ir::{Circuit, IrResult};
+use num_complex::Complex64;
+use rustc_hash::FxHashMap;
+
+/// Largest number of distinct qubits compared by matrix multiplication.
+pub const MAX_MATRIX_QUBITS: usize = 4;
+
+/// Tolerance for comparing matrix entries.
+const TOLERANCE: f64 = 1e-9;
+
+/// Cache key: gate names with qubits relabelled in order of appearance.
+type CacheKey = (String, Vec<u32>, String, Vec<u32>);
+
+/// Commutation checker with a cache of matrix-derived results.
+#[derive(Debug, Default)]
+pub struct CommutationChecker {
+    cache: Mutex<FxHashMap<CacheKey, bool>>,
+}
+
+impl CommutationChecker {
+    /// Create a checker with an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `gate_a` on `qargs_a` commutes with `gate_b` on `qargs_b`.
+    pub fn commutes(
+        &self,
+        gate_a: &Gate,
+        qargs_a: &[QubitId],
+        gate_b: &Gate,
+        qargs_b: &[QubitId],
+    ) -> bool {
+        if !qargs_a.iter().any(|q| qargs_b.contains(q)) {
+            return true;
+        }
+        if gate_a.condition.is_some() || gate_b.condition.is_some() {
+            return false;
+        }
+        if let (GateKind::Standard(a), GateKind::Standard(b)) = (&gate_a.kind, &gate_b.kind) {
+            if (is_z_diagonal(a) && is_z_diagonal(b)) || (is_x_function(a) && is_x_function(b)) {
+                return true;
+            }
+        }
+
+        let (placement_a, placement_b, num_qubits) = relabel(qargs_a, qargs_b);
+        if num_qubits > MAX_MATRIX_QUBITS {
+            return false;
+        }
+        let key = cache_key(gate_a, &placement_a, gate_b, &placement_b);
+        if let Some(key) = &key {
+            if let Some(&cached) = self.lock().get(key) {
+                return cached;
+            }
+        }
+        let result = matrices_commute(gate_a, &placement_a, gate_b, &placement_b, num_qubits)
+            .unwrap_or(false);
+        if let Some(key) = key {
+            self.lock().insert(key, result);
+        }
+        result
+    }
+
+    /// Whether two instructions can be reordered.
+    ///
+    /// Unconditioned gates use [`Self::commutes`]. Any other pair commutes
+    /// only if it shares no qubits and neither writes a classical bit the
+    /// other reads or writes.
+    pub fn instructions_commute(&self, a: &Instruction, b: &Instruction) -> bool {
+        if let (InstructionKind::Gate(gate_a), InstructionKind::Gate(gate_b)) = (&a.kind, &b.kind) {
+            if gate_a.condition.is_none() && gate_b.condition.is_none() {
+                return self.commutes(gate_a, &a.qubits, gate_b, &b.qubits);
+            }
+        }
+        !a.qubits.iter().any(|q| b.qubits.contains(q))
+            && !writes_conflict(a, b)
+            && !writes_conflict(b, a)
+    }
+
+    /// Number of cached gate pairs.
+    pub fn cache_len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FxHashMap<CacheKey, bool>> {
+        // The cache only holds computed booleans; a poisoned lock is still
+        // consistent.
+        self.cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Whether two gates commute, using a process-wide cached checker.
+///
+/// ```rust
+/// use arvak_compile::commutation::commutes;
+/// use arvak_ir::{Gate, QubitId, StandardGate};
+///
+/// let cx = Gate::standard(StandardGate::CX);
+/// let z = Gate::standard(StandardGate::Z);
+/// let x = Gate::standard(StandardGate::X);
+/// let (control, target) = (QubitId(0), QubitId(1));
+///
+/// assert!(commutes(&cx, &[control, target], &z, &[control]));
+/// assert!(commutes(&cx, &[control, target], &x, &[target]));
+/// assert!(!commutes(&cx, &[control, target], &x, &[control]));
+/// ```
+pub fn commutes(gate_a: &Gate, qargs_a: &[QubitId], gate_b: &Gate, qargs_b: &[QubitId]) -> bool {
+    global().commutes(gate_a, qargs_a, gate_b, qargs_b)
+}
+
+/// Whether two instructions can be reordered, using a process-wide cached
+/// checker. See [`CommutationChecker::instructions_commute`].
+pub fn instructions_commute(a: &Instruction, b: &Instruction) -> bool {
+    global().instructions_commute(a, b)
+}
+
+fn global() -> &'static CommutationChecker {
+    static CHECKER: OnceLock<CommutationChecker> = OnceLock::new();
+    CHECKER.get_or_init(CommutationChecker::new)
+}
+
+/// Gates diagonal in the computational basis.
+pub(crate) fn is_z_diagonal(gate: &StandardGate) -> bool {
+    matches!(
+        gate,
+        StandardGate::I
+            | StandardGate::Z
+            | StandardGate::S
+            | StandardGate::Sdg
+            | StandardGate::T
+            | StandardGate::Tdg
+            | StandardGate::Rz(_)
+            | StandardGate::P(_)
+            | StandardGate::CZ
+            | StandardGate::CRz(_)
+            | StandardGate::CP(_)
+            | StandardGate::RZZ(_)
+            | StandardGate::MCZ(_)
+            | StandardGate::MCP(..)
+    )
+}
+
+/// Gates that are functions of X on each qubit, so they commute pairwise.
+fn is_x_function(gate: &StandardGate) -> bool {
+    matches!(
+        gate,
+        StandardGate::I
+            | StandardGate::X
+            | StandardGate::SX
+            | StandardGate::SXdg
+            | StandardGate::Rx(_)
+            | StandardGate::RXX(_)
+    )
+}
+
+/// Relabel the qubits of both operands to `0..n` in order of appearance.
+fn relabel(qargs_a: &[QubitId], qargs_b: &[QubitId]) -> (Vec<u32>, Vec<u32>, usize) {
+    let mut seen: Vec<QubitId> = Vec::new();
+    let mut index = |q: &QubitId| {
+        let i = seen.iter().position(|s| s == q).unwrap_or_else(|| {
+            seen.push(*q);
+            seen.len() - 1
+        });
+        u32::try_from(i).unwrap_or(u32::MAX)
+    };
+    let a = qargs_a.iter().map(&mut index).collect();
+    let b = qargs_b.iter().map(&mut index).collect();
+    (a, b, seen.len())
+}
+
+/// Cache key for parameter-free standard gates; other gates are not cached.
+fn cache_key(a: &Gate, placement_a: &[u32], b: &Gate, placement_b: &[u32]) -> Option<CacheKey> {
+    let cacheable = |gate: &Gate| match &gate.kind {
+        GateKind::Standard(g) => g.parameters().is_empty(),
+        GateKind::Custom(_) | GateKind::Modified(_) => false,
+    };
+    (cacheable(a) && cacheable(b)).then(|| {
+        (
+            a.name().to_string(),
+            placement_a.to_vec(),
+            b.name().to_string(),
+            placement_b.to_vec(),
+        )
+    })
+}
+
+/// Compare `AB` with `BA` as dense unitaries.
+fn matrices_commute(
+    a: &Gate,
+    placement_a: &[u32],
+    b: &Gate,
+    placement_b: &[u32],
+    num_qubits: usize,
+) -> IrResult<bool> {
+    let product = |first: (&Gate, &[u32]), second: (&Gate, &[u32])| -> IrResult<Vec<Complex64>> {
+        let num_qubits = u32::try_from(num_qubits).unwrap_or(u32::MAX);
+        let mut circuit = Circuit::with_size("commutation", num_qubits, 0);
+        for (gate, placement) in [first, second] {
+            circuit.gate(gate.clone(), placement.iter().map(|&q| QubitId(q)))?;
+        }
+        circuit.to_matrix()
+    };
+    let ab = product((a, placement_a), (b, placement_b))?;
+    let ba = product((b, placement_b), (a, placement_a))?;
+    Ok(ab.iter().zip(&ba).all(|(x, y)| (x - y).norm() < TOLERANCE))
+}
+
+/// Whether `a` writes a classical bit that `b` reads or writes.
+fn writes_conflict(a: &Instruction, b: &Instruction) -> bool {
+    let writes: &[ClbitId] = &a.clbits;
+    if writes.is_empty() {
+        return false;
+    }
+    // A register condition without resolved bits may read any bit.
+    if b.is_conditional() && b.condition_clbits().is_empty() {
+        return true;
+    }
+    writes
+        .iter()
+        .any(|c| b.clbits.contains(c) || b.condition_clbits().contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::ParameterExpression;
+
+    fn gate(g: StandardGate) -> Gate {
+        Gate::standard(g)
+    }
+
+    #[test]
+    fn test_commutation_rules() {
+        let checker = CommutationChecker::new();
+        let (q0, q1, q2) = (QubitId(0), QubitId(1), QubitId(2));
+        let theta = ParameterExpression::symbol("theta");
+
+        // Disjoint qubits and diagonal gates, symbolic parameters included.
+        assert!(checker.commutes(&gate(StandardGate::H), &[q0], &gate(StandardGate::X), &[q1]));
+        assert!(checker.commutes(
+            &gate(StandardGate::Rz(theta.clone())),
+            &[q1],
+            &gate(StandardGate::CZ),
+            &[q0, q1]
+        ));
+        assert!(checker.commutes(
+            &gate(StandardGate::Rx(theta.clone())),
+            &[q0],
+            &gate(StandardGate::SX),
+            &[q0]
+        ));
+        // Undecidable: symbolic rotation against a non-diagonal gate.
+        assert!(!checker.commutes(
+            &gate(StandardGate::Ry(theta)),
+            &[q0],
+            &gate(StandardGate::CX),
+            &[q0, q1]
+        ));
+
+        // Matrix-derived and cached by relative placement.
+        let cx = gate(StandardGate::CX);
+        assert!(checker.commutes(&cx, &[q0, q1], &cx, &[q0, q2]));
+        assert!(checker.commutes(&cx, &[q0, q1], &cx, &[q2, q1]));
+        assert!(!checker.commutes(&cx, &[q0, q1], &cx, &[q1, q2]));
+        assert!(!checker.commutes(&gate(StandardGate::H), &[q0], &gate(StandardGate::S), &[q0]));
+        let cached = checker.cache_len();
+        assert!(checker.commutes(&cx, &[q1, q2], &cx, &[q1, q0]));
+        assert_eq!(checker.cache_len(), cached);
+    }
+
+    #[test]
+    fn test_instructions_commute() {
+        let checker = CommutationChecker::new();
+        let (q0, q1) = (QubitId(0), QubitId(1));
+        let measure = Instruction::measure(q0, ClbitId(0));
+        let mut conditioned = Instruction::single_qubit_gate(StandardGate::X, q1);
+        if let Some(gate) = conditioned.gate_mut() {
+            gate.condition = Some(arvak_ir::ClassicalCondition::on_clbit(ClbitId(0), 1));
+        }
+
+        assert!(checker.instructions_commute(
+            &Instruction::single_qubit_gate(StandardGate::Z, q0),
+            &Instruction::two_qubit_gate(StandardGate::CX, q0, q1)
+        ));
+        assert!(!checker.instructions_commute(&measure, &conditioned));
+        assert!(checker.instructions_commute(&measure, &Instruction::reset(q1)));
+        assert!(!checker.instructions_commute(&measure, &Instruction::barrier([q0, q1])));
+    }
+}
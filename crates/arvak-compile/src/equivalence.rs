@@ -0,0 +1,552 @@
+//! Verification mode: an equivalence check after every pass.
+//!
+//! A [`PassManager`](crate::PassManager) with verification enabled
+//! ([`with_verification`](crate::PassManager::with_verification)) compares
+//! the circuit after each transformation pass with the circuit before it.
+//! Circuits of Clifford gates are compared exactly by their stabilizer
+//! tableaux, at any size. Other circuits are simulated as statevectors on
+//! random product input states, as long as they touch at most
+//! [`Verification::with_max_qubits`] qubits; larger ones are skipped. The
+//! first pass whose output differs fails the run with
+//! [`CompileError::Miscompiled`], naming the pass.
+//!
+//! Passes inside flow controllers such as [`FixedPoint`](crate::FixedPoint)
+//! are checked one by one. Routing passes are compared through the layout
+//! before and after them, and circuits with resets or control flow, which
+//! are not unitary, are skipped. After a run, the pass manager stores a
+//! [`VerificationReport`] listing what was checked and how.
+//!
+//! ```rust
+//! use arvak_compile::{BasisGates, CouplingMap, PassManagerBuilder};
+//! use arvak_compile::{Verification, VerificationReport};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let mut circuit = Circuit::with_size("demo", 3, 0);
+//! circuit.h(QubitId(0)).unwrap();
+//! circuit.t(QubitId(0)).unwrap();
+//! circuit.cx(QubitId(0), QubitId(2)).unwrap();
+//!
+//! let (pm, mut props) = PassManagerBuilder::new()
+//!     .with_optimization_level(2)
+//!     .with_target(CouplingMap::linear(3), BasisGates::iqm())
+//!     .with_verification(Verification::new())
+//!     .build();
+//! let mut dag = circuit.into_dag();
+//! pm.run(&mut dag, &mut props).unwrap();
+//!
+//! let report = props.get::<VerificationReport>().unwrap();
+//! assert!(report.checks.iter().any(|c| c.pass == "SabreRouting"));
+//! ```
+
+use num_complex::Complex64;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+use arvak_ir::{CircuitDag, CliffordTableau, GateKind, Instruction, InstructionKind, QubitId};
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::passes::agnostic::verify_compilation::simulate_ops;
+use crate::property::{Layout, PropertySet};
+use crate::rng::SeededRng;
+
+/// Settings of verification mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Verification {
+    max_qubits: usize,
+    num_trials: usize,
+    tolerance: f64,
+}
+
+impl Verification {
+    /// Largest number of active qubits simulated by default.
+    pub const DEFAULT_MAX_QUBITS: usize = 16;
+
+    /// Random input states per statevector check by default.
+    pub const DEFAULT_NUM_TRIALS: usize = 2;
+
+    /// Largest accepted infidelity between the two output states by default.
+    ///
+    /// Floating-point noise from resynthesis stays orders of magnitude
+    /// below this, and so does approximate Clifford+T synthesis at its
+    /// default precision.
+    pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+    /// Verification with the default settings.
+    pub fn new() -> Self {
+        Self {
+            max_qubits: Self::DEFAULT_MAX_QUBITS,
+            num_trials: Self::DEFAULT_NUM_TRIALS,
+            tolerance: Self::DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Simulate circuits of at most `max_qubits` active qubits.
+    #[must_use]
+    pub fn with_max_qubits(mut self, max_qubits: usize) -> Self {
+        self.max_qubits = max_qubits;
+        self
+    }
+
+    /// Compare outputs on `num_trials` random input states (at least one).
+    #[must_use]
+    pub fn with_num_trials(mut self, num_trials: usize) -> Self {
+        self.num_trials = num_trials.max(1);
+        self
+    }
+
+    /// Accept output states whose infidelity is at most `tolerance`.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl Default for Verification {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a pass was shown to preserve the circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EquivalenceMethod {
+    /// Equal stabilizer tableaux; exact for Clifford circuits.
+    Stabilizer,
+    /// Equal output statevectors on random product input states.
+    Statevector,
+}
+
+/// Result of checking one pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckOutcome {
+    /// The output matched the input.
+    Verified(EquivalenceMethod),
+    /// The pass could not be checked, for the given reason.
+    Skipped(String),
+}
+
+/// Check of one transformation pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PassCheck {
+    /// Name of the pass.
+    pub pass: String,
+    /// What the check found.
+    pub outcome: CheckOutcome,
+}
+
+/// Checks made by a pass manager run in verification mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// One entry per transformation pass that ran, in pipeline order.
+    pub checks: Vec<PassCheck>,
+}
+
+impl VerificationReport {
+    /// Number of passes shown to preserve the circuit.
+    pub fn verified(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|c| matches!(c.outcome, CheckOutcome::Verified(_)))
+            .count()
+    }
+
+    /// Passes that could not be checked.
+    pub fn skipped(&self) -> impl Iterator<Item = &PassCheck> {
+        self.checks
+            .iter()
+            .filter(|c| matches!(c.outcome, CheckOutcome::Skipped(_)))
+    }
+}
+
+/// Run `pass`, checking its output against its input if the running pass
+/// manager is in verification mode.
+///
+/// A pass that checks its own sub-passes, like the flow controllers, is
+/// not checked again as a whole.
+pub(crate) fn run_verified(
+    pass: &dyn Pass,
+    dag: &mut CircuitDag,
+    properties: &mut PropertySet,
+) -> CompileResult<()> {
+    let verification = match properties.get::<Verification>() {
+        Some(verification) if pass.kind() == PassKind::Transformation => *verification,
+        _ => return pass.run(dag, properties),
+    };
+    let before = dag.clone();
+    let layout_before = properties.layout.clone();
+    let checks_before = report_len(properties);
+
+    pass.run(dag, properties)?;
+    if report_len(properties) > checks_before {
+        return Ok(());
+    }
+
+    let seed = properties.seed.unwrap_or(0) ^ checks_before as u64;
+    let outcome = verification
+        .check(pass, &before, layout_before.as_ref(), dag, properties, seed)
+        .map_err(|reason| CompileError::Miscompiled {
+            pass: pass.name().to_string(),
+            reason,
+        })?;
+    if let Some(report) = properties.get_mut::<VerificationReport>() {
+        report.checks.push(PassCheck {
+            pass: pass.name().to_string(),
+            outcome,
+        });
+    }
+    Ok(())
+}
+
+fn report_len(properties: &PropertySet) -> usize {
+    properties
+        .get::<VerificationReport>()
+        .map_or(0, |report| report.checks.len())
+}
+
+impl Verification {
+    /// Compare the circuit before and after `pass`; `Err` describes a
+    /// mismatch.
+    fn check(
+        &self,
+        pass: &dyn Pass,
+        before: &CircuitDag,
+        layout_before: Option<&Layout>,
+        after: &CircuitDag,
+        properties: &PropertySet,
+        seed: u64,
+    ) -> Result<CheckOutcome, String> {
+        if let Some(reason) = not_unitary(before).or_else(|| not_unitary(after)) {
+            return Ok(CheckOutcome::Skipped(reason.to_string()));
+        }
+
+        // Logical qubits of the input and, for each, the wire of the output
+        // it starts and ends on.
+        let mut logical = active_qubits(before);
+        let frame = if pass.is_routing() {
+            let (Some(initial), Some(last)) = (layout_before, properties.layout.as_ref()) else {
+                return Ok(CheckOutcome::Skipped("routing without a layout".into()));
+            };
+            let mut frame = Vec::with_capacity(logical.len());
+            for &q in &logical {
+                match (initial.get_physical(q), last.get_physical(q)) {
+                    (Some(start), Some(end)) => frame.push((QubitId(start), QubitId(end))),
+                    _ => {
+                        return Ok(CheckOutcome::Skipped(format!(
+                            "layout does not place qubit {}",
+                            q.0
+                        )));
+                    }
+                }
+            }
+            Some(frame)
+        } else {
+            let mut wires: FxHashSet<QubitId> = logical.iter().copied().collect();
+            wires.extend(active_qubits(after));
+            logical = sorted(wires);
+            None
+        };
+
+        if frame.is_none() {
+            let index = index_of(&logical);
+            if let (Some(a), Some(b)) = (
+                tableau(before, &index, logical.len()),
+                tableau(after, &index, logical.len()),
+            ) {
+                return if a == b {
+                    Ok(CheckOutcome::Verified(EquivalenceMethod::Stabilizer))
+                } else {
+                    Err("stabilizer tableaux differ".into())
+                };
+            }
+        }
+
+        let frame = frame.unwrap_or_else(|| logical.iter().map(|&q| (q, q)).collect());
+        let mut wires: FxHashSet<QubitId> = active_qubits(after).into_iter().collect();
+        wires.extend(frame.iter().flat_map(|&(start, end)| [start, end]));
+        let wires = sorted(wires);
+
+        let width = logical.len().max(wires.len());
+        if width > self.max_qubits {
+            return Ok(CheckOutcome::Skipped(format!(
+                "{width} active qubits exceed the limit of {}",
+                self.max_qubits
+            )));
+        }
+        self.compare_statevectors(before, &logical, after, &wires, &frame, seed)
+    }
+
+    /// Simulate both circuits on random product states of the logical
+    /// qubits; wires of `after` outside the frame start in |0⟩ and must
+    /// end there.
+    fn compare_statevectors(
+        &self,
+        before: &CircuitDag,
+        logical: &[QubitId],
+        after: &CircuitDag,
+        wires: &[QubitId],
+        frame: &[(QubitId, QubitId)],
+        seed: u64,
+    ) -> Result<CheckOutcome, String> {
+        let skipped = |e: CompileError| CheckOutcome::Skipped(e.to_string());
+        let before_ops = remap(before, &index_of(logical));
+        let wire_index = index_of(wires);
+        let after_ops = remap(after, &wire_index);
+        let start_bits: Vec<usize> = frame.iter().map(|(s, _)| 1 << wire_index[s]).collect();
+        let end_bits: Vec<usize> = frame.iter().map(|(_, e)| 1 << wire_index[e]).collect();
+
+        let mut rng = SeededRng::new(seed);
+        for trial in 0..self.num_trials {
+            let input = random_product_state(logical.len(), &mut rng);
+
+            let mut expected = input.clone();
+            if let Err(e) = simulate_ops(&before_ops, logical.len(), &mut expected) {
+                return Ok(skipped(e));
+            }
+
+            let mut state = vec![Complex64::new(0.0, 0.0); 1 << wires.len()];
+            for (i, amplitude) in input.iter().enumerate() {
+                state[spread(i, &start_bits)] = *amplitude;
+            }
+            if let Err(e) = simulate_ops(&after_ops, wires.len(), &mut state) {
+                return Ok(skipped(e));
+            }
+            let actual: Vec<Complex64> = (0..expected.len())
+                .map(|i| state[spread(i, &end_bits)])
+                .collect();
+
+            let overlap: Complex64 = expected
+                .iter()
+                .zip(&actual)
+                .map(|(a, b)| a.conj() * b)
+                .sum();
+            let infidelity = 1.0 - overlap.norm_sqr();
+            if infidelity > self.tolerance {
+                return Err(format!(
+                    "output state differs on random input {trial} (infidelity {infidelity:.3e})"
+                ));
+            }
+        }
+        Ok(CheckOutcome::Verified(EquivalenceMethod::Statevector))
+    }
+}
+
+/// Why `dag` cannot be compared as a unitary, if it cannot.
+fn not_unitary(dag: &CircuitDag) -> Option<&'static str> {
+    dag.topological_ops()
+        .find_map(|(_, inst)| match &inst.kind {
+            InstructionKind::Reset => Some("circuit has resets"),
+            InstructionKind::IfElse { .. } | InstructionKind::WhileLoop { .. } => {
+                Some("circuit has control flow")
+            }
+            InstructionKind::Gate(gate) if gate.condition.is_some() => {
+                Some("circuit has classically conditioned gates")
+            }
+            _ => None,
+        })
+}
+
+/// Qubits `dag` applies operations to, sorted.
+fn active_qubits(dag: &CircuitDag) -> Vec<QubitId> {
+    sorted(
+        dag.topological_ops()
+            .flat_map(|(_, inst)| inst.qubits.iter().copied())
+            .collect(),
+    )
+}
+
+fn sorted(qubits: FxHashSet<QubitId>) -> Vec<QubitId> {
+    let mut qubits: Vec<QubitId> = qubits.into_iter().collect();
+    qubits.sort_unstable_by_key(|q| q.0);
+    qubits
+}
+
+fn index_of(qubits: &[QubitId]) -> FxHashMap<QubitId, usize> {
+    qubits.iter().enumerate().map(|(i, &q)| (q, i)).collect()
+}
+
+/// The operations of `dag`, on wires renumbered by `index`.
+fn remap(dag: &CircuitDag, index: &FxHashMap<QubitId, usize>) -> Vec<Instruction> {
+    dag.topological_ops()
+        .map(|(_, inst)| {
+            let mut inst = inst.clone();
+            inst.qubits = inst
+                .qubits
+                .iter()
+                .map(|q| QubitId(u32::try_from(index[q]).expect("qubit index overflow")))
+                .collect();
+            inst
+        })
+        .collect()
+}
+
+/// The tableau of `dag`, or `None` if it is not a Clifford circuit.
+///
+/// Measurements are skipped, as in the statevector comparison.
+fn tableau(
+    dag: &CircuitDag,
+    index: &FxHashMap<QubitId, usize>,
+    num_qubits: usize,
+) -> Option<CliffordTableau> {
+    let mut tableau = CliffordTableau::identity(num_qubits);
+    for (_, inst) in dag.topological_ops() {
+        match &inst.kind {
+            InstructionKind::Gate(gate) => {
+                let GateKind::Standard(standard) = &gate.kind else {
+                    return None;
+                };
+                let qubits: Vec<usize> = inst.qubits.iter().map(|q| index[q]).collect();
+                tableau.apply_gate(standard, &qubits).ok()?;
+            }
+            InstructionKind::Barrier
+            | InstructionKind::Delay { .. }
+            | InstructionKind::Measure
+            | InstructionKind::Shuttle { .. }
+            | InstructionKind::NoiseChannel { .. } => {}
+            _ => return None,
+        }
+    }
+    Some(tableau)
+}
+
+/// Basis index with bit `j` of `logical` moved to `bits[j]`.
+fn spread(logical: usize, bits: &[usize]) -> usize {
+    bits.iter()
+        .enumerate()
+        .filter(|(j, _)| logical & (1 << j) != 0)
+        .fold(0, |acc, (_, bit)| acc | bit)
+}
+
+/// A product of random single-qubit states on `num_qubits` qubits.
+fn random_product_state(num_qubits: usize, rng: &mut SeededRng) -> Vec<Complex64> {
+    let mut state = vec![Complex64::new(1.0, 0.0)];
+    for _ in 0..num_qubits {
+        let theta = std::f64::consts::PI * unit(rng);
+        let phi = std::f64::consts::TAU * unit(rng);
+        let zero = Complex64::new((theta / 2.0).cos(), 0.0);
+        let one = Complex64::from_polar((theta / 2.0).sin(), phi);
+        // The new qubit is the most significant bit.
+        state = state
+            .iter()
+            .map(|a| a * zero)
+            .chain(state.iter().map(|a| a * one))
+            .collect();
+    }
+    state
+}
+
+/// A uniform sample from `[0, 1)`.
+#[allow(clippy::cast_precision_loss)]
+fn unit(rng: &mut SeededRng) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::PassManager;
+    use arvak_ir::{Circuit, StandardGate};
+
+    /// Replaces every T gate with S: wrong, but only by a phase.
+    struct TToS;
+
+    impl Pass for TToS {
+        fn name(&self) -> &'static str {
+            "TToS"
+        }
+
+        fn kind(&self) -> PassKind {
+            PassKind::Transformation
+        }
+
+        fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
+            let mut rewritten = CircuitDag::new();
+            for q in dag.qubits().collect::<Vec<_>>() {
+                rewritten.add_qubit(q);
+            }
+            for (_, inst) in dag.topological_ops() {
+                let mut inst = inst.clone();
+                if inst.name() == "t" {
+                    inst = Instruction::single_qubit_gate(StandardGate::S, inst.qubits[0]);
+                }
+                rewritten.apply(inst).map_err(CompileError::Ir)?;
+            }
+            *dag = rewritten;
+            Ok(())
+        }
+    }
+
+    fn run_verified_pm(pm: PassManager, circuit: Circuit) -> CompileResult<PropertySet> {
+        let mut props = PropertySet::new();
+        let mut dag = circuit.into_dag();
+        pm.with_verification(Verification::new())
+            .run(&mut dag, &mut props)?;
+        Ok(props)
+    }
+
+    #[test]
+    fn test_phase_bug_is_reported() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.t(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+        let mut pm = PassManager::new();
+        pm.add_pass(crate::passes::Unroll3q);
+        pm.add_pass(TToS);
+        let err = run_verified_pm(pm, circuit).unwrap_err();
+        assert!(
+            matches!(&err, CompileError::Miscompiled { pass, .. } if pass == "TToS"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_clifford_circuits_use_tableaux() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.s(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let mut pm = PassManager::new();
+        pm.add_pass(crate::passes::CancelCX::new());
+        let props = run_verified_pm(pm, circuit).unwrap();
+        let report = props.get::<VerificationReport>().unwrap();
+        assert_eq!(
+            report.checks,
+            vec![PassCheck {
+                pass: "CancelCX".into(),
+                outcome: CheckOutcome::Verified(EquivalenceMethod::Stabilizer),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_large_non_clifford_circuits_are_skipped() {
+        let mut circuit = Circuit::with_size("test", 20, 0);
+        for q in 0..20 {
+            circuit.t(QubitId(q)).unwrap();
+        }
+
+        let mut pm = PassManager::new();
+        pm.add_pass(crate::passes::Optimize1qGates::new());
+        let props = run_verified_pm(pm, circuit).unwrap();
+        let report = props.get::<VerificationReport>().unwrap();
+        assert_eq!(report.verified(), 0);
+        assert_eq!(report.skipped().count(), 1);
+    }
+
+    #[test]
+    fn test_random_product_state_is_normalized() {
+        let mut rng = SeededRng::new(3);
+        let state = random_product_state(4, &mut rng);
+        let norm: f64 = state.iter().map(Complex64::norm_sqr).sum();
+        assert_eq!(state.len(), 16);
+        assert!((norm - 1.0).abs() < 1e-12);
+    }
+}
@@ -42,6 +42,10 @@ pub enum CompileError {
     #[error("Circuit requires {required} qubits but target only has {available}")]
     CircuitTooLarge { required: usize, available: u32 },
 
+    /// Released a qubit that is not an allocated ancilla.
+    #[error("Physical qubit {0} is not an allocated ancilla")]
+    AncillaNotAllocated(u32),
+
     /// Measurement barrier verification failed.
     #[error("Measurement verification failed: gate '{gate_name}' on qubit {qubit}: {detail}")]
     MeasurementViolation {
@@ -49,6 +53,14 @@ pub enum CompileError {
         qubit: u32,
         detail: String,
     },
+
+    /// Verification mode found a pass that changed the circuit's semantics.
+    #[error("Pass '{pass}' miscompiled the circuit: {reason}")]
+    Miscompiled { pass: String, reason: String },
+
+    /// The compilation was cancelled through its `CancellationToken`.
+    #[error("Compilation cancelled")]
+    Cancelled,
 }
 
 /// Result type for compilation operations.
@@ -0,0 +1,359 @@
+//! Calibration error rates for routing.
+//!
+//! Distance-only routing treats every coupler alike, but on hardware one
+//! coupler's two-qubit error can be several times its neighbour's, and
+//! readout quality varies from qubit to qubit. With [`ErrorWeights`] stored
+//! as a custom property in the [`PropertySet`], [`BasicRouting`] and
+//! [`SabreRouting`] prefer SWAPs over good couplers and avoid moving
+//! measured qubits onto poor readout.
+//!
+//! A gate with error `e` costs `-ln(1 - e)`, so costs add up like
+//! log-infidelities, and a SWAP costs three gates. Costs are scaled so the
+//! average SWAP on the coupling map costs one, and the routing distance of
+//! a path is its hop count plus the scaled cost of its SWAPs. On a device
+//! whose couplers are all alike this orders paths by distance, as before.
+//!
+//! ```
+//! use arvak_compile::passes::{BasicRouting, TrivialLayout};
+//! use arvak_compile::{BasisGates, CouplingMap, ErrorWeights, Pass, PropertySet};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! // A ring 0-1-2-3-0 whose couplers at qubit 1 are poor.
+//! let ring = CouplingMap::from_edge_list(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+//! let weights = ErrorWeights::new()
+//!     .with_coupler_error(0, 1, 0.05)
+//!     .with_coupler_error(1, 2, 0.05)
+//!     .with_coupler_error(2, 3, 0.005)
+//!     .with_coupler_error(3, 0, 0.005);
+//!
+//! let mut circuit = Circuit::with_size("cx", 4, 0);
+//! circuit.cx(QubitId(0), QubitId(2)).unwrap();
+//! let mut dag = circuit.into_dag();
+//! let mut props = PropertySet::new().with_target(ring, BasisGates::iqm());
+//! props.insert(weights);
+//!
+//! TrivialLayout.run(&mut dag, &mut props).unwrap();
+//! BasicRouting.run(&mut dag, &mut props).unwrap();
+//!
+//! // The CX is brought together over qubit 3, not qubit 1.
+//! assert!(dag.topological_ops().all(|(_, inst)| !inst.qubits.contains(&QubitId(1))));
+//! ```
+//!
+//! [`PropertySet`]: crate::PropertySet
+//! [`BasicRouting`]: crate::passes::BasicRouting
+//! [`SabreRouting`]: crate::passes::SabreRouting
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use arvak_ir::noise::NoiseProfile;
+use rustc_hash::FxHashMap;
+
+use crate::property::CouplingMap;
+
+/// Distance of unreachable qubit pairs (`u32::MAX`, as distance-only
+/// routing counts them).
+const UNREACHABLE: f64 = 4_294_967_295.0;
+
+/// Two-qubit and readout error rates that routing weighs SWAPs by.
+///
+/// Couplers without an error rate cost as much as the average known one;
+/// qubits without a readout error count as perfect.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorWeights {
+    /// Two-qubit gate error per coupler, keyed lower qubit first.
+    coupler_errors: BTreeMap<(u32, u32), f64>,
+    /// Readout error per physical qubit.
+    readout_errors: Vec<f64>,
+}
+
+impl ErrorWeights {
+    /// Create weights without any error rates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the coupler and readout errors of a calibration noise profile.
+    pub fn from_noise_profile(profile: &NoiseProfile) -> Self {
+        let mut weights = Self::new();
+        for &(a, b, error) in profile.coupler_errors.iter().flatten() {
+            weights = weights.with_coupler_error(a, b, error);
+        }
+        if let Some(readout) = &profile.readout_errors {
+            weights = weights.with_readout_errors(readout.clone());
+        }
+        weights
+    }
+
+    /// Set the two-qubit gate error of the coupler between `a` and `b`.
+    #[must_use]
+    pub fn with_coupler_error(mut self, a: u32, b: u32, error: f64) -> Self {
+        self.coupler_errors.insert((a.min(b), a.max(b)), error);
+        self
+    }
+
+    /// Set the readout error of every physical qubit, indexed by qubit.
+    #[must_use]
+    pub fn with_readout_errors(mut self, errors: Vec<f64>) -> Self {
+        self.readout_errors = errors;
+        self
+    }
+
+    /// The two-qubit gate error of the coupler between `a` and `b`, if known.
+    pub fn coupler_error(&self, a: u32, b: u32) -> Option<f64> {
+        self.coupler_errors.get(&(a.min(b), a.max(b))).copied()
+    }
+
+    /// The readout error of physical qubit `qubit`, if known.
+    pub fn readout_error(&self, qubit: u32) -> Option<f64> {
+        self.readout_errors.get(qubit as usize).copied()
+    }
+
+    /// Check whether there are no error rates to weigh by.
+    pub fn is_empty(&self) -> bool {
+        self.coupler_errors.is_empty() && self.readout_errors.is_empty()
+    }
+
+    /// Scaled routing costs on `coupling_map`.
+    ///
+    /// Returns `None` when no known error rate applies to the map, in which
+    /// case routing falls back to distance.
+    pub(crate) fn costs(&self, coupling_map: &CouplingMap) -> Option<ErrorCosts> {
+        let log_infidelity = |error: f64| -(1.0 - error.clamp(0.0, 0.999_999)).ln();
+
+        let known_swaps: Vec<((u32, u32), f64)> = coupling_map
+            .edges()
+            .iter()
+            .filter_map(|&(a, b)| {
+                let cost = 3.0 * log_infidelity(self.coupler_error(a, b)?);
+                Some(((a.min(b), a.max(b)), cost))
+            })
+            .collect();
+        let readout: Vec<f64> = (0..coupling_map.num_qubits())
+            .map(|q| self.readout_error(q).map_or(0.0, log_infidelity))
+            .collect();
+
+        // Scale by the average SWAP, or by the average readout when no
+        // coupler is calibrated.
+        let unit = if known_swaps.is_empty() {
+            let known = readout.iter().filter(|&&r| r > 0.0).count();
+            readout.iter().sum::<f64>() / known.max(1) as f64
+        } else {
+            known_swaps.iter().map(|(_, cost)| cost).sum::<f64>() / known_swaps.len() as f64
+        };
+        if !unit.is_finite() || unit <= 0.0 {
+            return None;
+        }
+
+        let mut costs = ErrorCosts {
+            swap: known_swaps
+                .into_iter()
+                .map(|(edge, cost)| (edge, cost / unit))
+                .collect(),
+            readout: readout.into_iter().map(|r| r / unit).collect(),
+            distance: Vec::new(),
+        };
+        costs.distance = (0..coupling_map.num_qubits())
+            .map(|q| {
+                let (dist, _) = costs.shortest_from(coupling_map, q, None);
+                dist.into_iter()
+                    .map(|d| if d.is_finite() { d } else { UNREACHABLE })
+                    .collect()
+            })
+            .collect();
+        Some(costs)
+    }
+}
+
+/// [`ErrorWeights`] scaled for one coupling map, as the routing passes use
+/// them.
+pub(crate) struct ErrorCosts {
+    /// Scaled SWAP cost per calibrated coupler, keyed lower qubit first.
+    swap: FxHashMap<(u32, u32), f64>,
+    /// Scaled readout cost per physical qubit.
+    readout: Vec<f64>,
+    /// Routing distance between every pair of physical qubits.
+    distance: Vec<Vec<f64>>,
+}
+
+impl ErrorCosts {
+    /// Scaled cost of a SWAP between `a` and `b`; one for uncalibrated
+    /// couplers.
+    pub(crate) fn swap(&self, a: u32, b: u32) -> f64 {
+        self.swap.get(&(a.min(b), a.max(b))).copied().unwrap_or(1.0)
+    }
+
+    /// Scaled cost of measuring physical qubit `qubit`.
+    pub(crate) fn readout(&self, qubit: u32) -> f64 {
+        self.readout.get(qubit as usize).copied().unwrap_or(0.0)
+    }
+
+    /// Routing distance between `a` and `b`: hops plus scaled SWAP costs
+    /// along the cheapest path.
+    pub(crate) fn distance(&self, a: u32, b: u32) -> f64 {
+        self.distance
+            .get(a as usize)
+            .and_then(|row| row.get(b as usize))
+            .copied()
+            .unwrap_or(UNREACHABLE)
+    }
+
+    /// The cheapest path that brings the qubit at `from` next to `to`.
+    ///
+    /// Like [`CouplingMap::shortest_path`] the path runs `from → … → to`,
+    /// with SWAPs along all edges but the last, which carries the gate. It
+    /// never passes through `to`. With `measured`, the readout cost of the
+    /// qubit the moved qubit lands on counts too.
+    pub(crate) fn swap_path(
+        &self,
+        coupling_map: &CouplingMap,
+        from: u32,
+        to: u32,
+        measured: bool,
+    ) -> Option<Vec<u32>> {
+        let (dist, pred) = self.shortest_from(coupling_map, from, Some(to));
+        let (_, last) = coupling_map
+            .neighbors(to)
+            .filter(|&n| dist.get(n as usize).is_some_and(|d| d.is_finite()))
+            .map(|n| {
+                let landing = if measured { self.readout(n) } else { 0.0 };
+                (dist[n as usize] + self.swap(n, to) / 3.0 + landing, n)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))?;
+
+        let mut path = vec![to, last];
+        let mut node = last;
+        while let Some(prev) = pred[node as usize] {
+            path.push(prev);
+            node = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Dijkstra from `from`, never entering `avoid`. Returns the routing
+    /// distance to every qubit and its predecessor on the cheapest path.
+    fn shortest_from(
+        &self,
+        coupling_map: &CouplingMap,
+        from: u32,
+        avoid: Option<u32>,
+    ) -> (Vec<f64>, Vec<Option<u32>>) {
+        let n = coupling_map.num_qubits() as usize;
+        let mut dist = vec![f64::INFINITY; n];
+        let mut pred = vec![None; n];
+        let mut heap = BinaryHeap::new();
+        if (from as usize) < n {
+            dist[from as usize] = 0.0;
+            heap.push(Frontier {
+                cost: 0.0,
+                qubit: from,
+            });
+        }
+
+        while let Some(Frontier { cost, qubit }) = heap.pop() {
+            if cost > dist[qubit as usize] {
+                continue;
+            }
+            for next in coupling_map.neighbors(qubit) {
+                if Some(next) == avoid || next as usize >= n {
+                    continue;
+                }
+                let through = cost + 1.0 + self.swap(qubit, next);
+                if through < dist[next as usize] {
+                    dist[next as usize] = through;
+                    pred[next as usize] = Some(qubit);
+                    heap.push(Frontier {
+                        cost: through,
+                        qubit: next,
+                    });
+                }
+            }
+        }
+        (dist, pred)
+    }
+}
+
+/// A qubit on the Dijkstra frontier; the heap pops the cheapest first,
+/// then the lowest qubit.
+#[derive(PartialEq)]
+struct Frontier {
+    cost: f64,
+    qubit: u32,
+}
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.qubit.cmp(&self.qubit))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring() -> CouplingMap {
+        CouplingMap::from_edge_list(4, &[(0, 1), (1, 2), (2, 3), (3, 0)])
+    }
+
+    #[test]
+    fn test_from_noise_profile() {
+        let mut profile = NoiseProfile::new();
+        profile.coupler_errors = Some(vec![(1, 0, 0.01)]);
+        profile.readout_errors = Some(vec![0.02, 0.03]);
+
+        let weights = ErrorWeights::from_noise_profile(&profile);
+        assert_eq!(weights.coupler_error(0, 1), Some(0.01));
+        assert_eq!(weights.readout_error(1), Some(0.03));
+        assert_eq!(weights.readout_error(2), None);
+        assert!(ErrorWeights::from_noise_profile(&NoiseProfile::new()).is_empty());
+    }
+
+    #[test]
+    fn test_uniform_costs_follow_distance() {
+        let map = CouplingMap::linear(4);
+        let weights = (0..3).fold(ErrorWeights::new(), |w, q| {
+            w.with_coupler_error(q, q + 1, 0.01)
+        });
+        let costs = weights.costs(&map).unwrap();
+
+        assert!((costs.swap(1, 2) - 1.0).abs() < 1e-12);
+        assert!((costs.distance(0, 3) - 6.0).abs() < 1e-12);
+        assert_eq!(costs.swap_path(&map, 0, 3, false), map.shortest_path(0, 3));
+    }
+
+    #[test]
+    fn test_swap_path_avoids_poor_couplers() {
+        let weights = ErrorWeights::new()
+            .with_coupler_error(0, 1, 0.05)
+            .with_coupler_error(1, 2, 0.05)
+            .with_coupler_error(2, 3, 0.005)
+            .with_coupler_error(3, 0, 0.005);
+        let costs = weights.costs(&ring()).unwrap();
+
+        assert_eq!(costs.swap_path(&ring(), 0, 2, false), Some(vec![0, 3, 2]));
+        assert!(costs.distance(0, 2) < costs.distance(1, 3));
+    }
+
+    #[test]
+    fn test_swap_path_weighs_readout_of_measured_qubits() {
+        let weights = ErrorWeights::new().with_readout_errors(vec![0.01, 0.2, 0.01, 0.01]);
+        let costs = weights.costs(&ring()).unwrap();
+
+        // Either way is one SWAP; only a measured qubit avoids qubit 1.
+        assert_eq!(costs.swap_path(&ring(), 0, 2, false), Some(vec![0, 1, 2]));
+        assert_eq!(costs.swap_path(&ring(), 0, 2, true), Some(vec![0, 3, 2]));
+        assert!(ErrorWeights::new().costs(&ring()).is_none());
+    }
+}
@@ -0,0 +1,369 @@
+//! Flow control for pass groups.
+//!
+//! A [`PassManager`](crate::PassManager) runs its passes once, in order.
+//! The controllers here wrap a group of passes into a single [`Pass`] with
+//! different flow:
+//!
+//! - [`FixedPoint`] repeats the group until an iteration leaves the DAG
+//!   unchanged, for optimizations that expose new opportunities for each
+//!   other (cancellation after resynthesis, and back).
+//! - [`Conditional`] runs the group only when a predicate on the DAG and
+//!   the properties holds, e.g. "the layout is perfect".
+//!
+//! Controllers nest, and inside a group each pass keeps its own
+//! [`should_run`](Pass::should_run) check and time-budget handling.
+//!
+//! ```rust
+//! use arvak_compile::flow::{Conditional, FixedPoint};
+//! use arvak_compile::passes::{CommutativeCancellation, Optimize1qGates};
+//! use arvak_compile::{PassManager, PropertySet};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let mut pm = PassManager::new();
+//! pm.add_pass(
+//!     FixedPoint::new()
+//!         .with_pass(CommutativeCancellation)
+//!         .with_pass(Optimize1qGates::new()),
+//! );
+//! pm.add_pass(
+//!     Conditional::new(|dag, _| dag.num_ops() > 2).with_pass(Optimize1qGates::new()),
+//! );
+//!
+//! let mut circuit = Circuit::with_size("test", 2, 0);
+//! circuit.h(QubitId(0)).unwrap();
+//! circuit.cx(QubitId(0), QubitId(1)).unwrap();
+//! circuit.cx(QubitId(0), QubitId(1)).unwrap();
+//! circuit.h(QubitId(0)).unwrap();
+//!
+//! let mut dag = circuit.into_dag();
+//! pm.run(&mut dag, &mut PropertySet::new()).unwrap();
+//! assert_eq!(dag.num_ops(), 0);
+//! ```
+
+use tracing::debug;
+
+use arvak_ir::CircuitDag;
+
+use crate::equivalence::run_verified;
+use crate::error::CompileResult;
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+
+/// An ordered group of passes run as one.
+#[derive(Default)]
+struct PassGroup {
+    passes: Vec<Box<dyn Pass>>,
+    /// `Controller(Pass1, Pass2, ...)`.
+    name: String,
+}
+
+impl PassGroup {
+    fn push(&mut self, controller: &str, pass: Box<dyn Pass>) {
+        self.passes.push(pass);
+        let names: Vec<&str> = self.passes.iter().map(|p| p.name()).collect();
+        self.name = format!("{controller}({})", names.join(", "));
+    }
+
+    /// Transformation if any pass transforms the DAG.
+    fn kind(&self) -> PassKind {
+        if self
+            .passes
+            .iter()
+            .any(|p| p.kind() == PassKind::Transformation)
+        {
+            PassKind::Transformation
+        } else {
+            PassKind::Analysis
+        }
+    }
+
+    /// An optimization if every pass is one, so the group as a whole may
+    /// be skipped once the time budget is spent.
+    fn is_optimization(&self) -> bool {
+        !self.passes.is_empty() && self.passes.iter().all(|p| p.is_optimization())
+    }
+
+    /// Run each pass once, with the same checks as the pass manager.
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        for pass in &self.passes {
+            properties.check_cancelled()?;
+            if pass.is_optimization() && properties.budget_exhausted() {
+                debug!("Skipping pass {}: time budget spent", pass.name());
+            } else if pass.should_run(dag, properties) {
+                debug!("Running pass: {}", pass.name());
+                run_verified(pass.as_ref(), dag, properties)?;
+            } else {
+                debug!("Skipping pass: {}", pass.name());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Repeat a group of passes until the DAG stops changing.
+///
+/// The group runs at least once. The loop ends when an iteration leaves
+/// the operation count and depth unchanged, after
+/// [`max_iterations`](Self::with_max_iterations), or when the time budget
+/// is spent. Comparing the gates themselves would rarely settle:
+/// resynthesizing a run of rotations that cannot be shortened still moves
+/// its angles in the last bits.
+pub struct FixedPoint {
+    group: PassGroup,
+    max_iterations: usize,
+}
+
+impl FixedPoint {
+    /// Default iteration limit.
+    pub const DEFAULT_MAX_ITERATIONS: usize = 10;
+
+    /// Create an empty controller.
+    pub fn new() -> Self {
+        Self {
+            group: PassGroup::default(),
+            max_iterations: Self::DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Create a controller over `passes`, in order.
+    pub fn from_passes(passes: Vec<Box<dyn Pass>>) -> Self {
+        passes.into_iter().fold(Self::new(), |controller, pass| {
+            controller.with_boxed_pass(pass)
+        })
+    }
+
+    /// Append a pass to the group.
+    #[must_use]
+    pub fn with_pass(self, pass: impl Pass + 'static) -> Self {
+        self.with_boxed_pass(Box::new(pass))
+    }
+
+    /// Append an already boxed pass to the group.
+    #[must_use]
+    pub fn with_boxed_pass(mut self, pass: Box<dyn Pass>) -> Self {
+        self.group.push("FixedPoint", pass);
+        self
+    }
+
+    /// Stop after `max_iterations` iterations even if the DAG still
+    /// changes, e.g. when two passes keep undoing each other.
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Get the number of passes in the group.
+    pub fn len(&self) -> usize {
+        self.group.passes.len()
+    }
+
+    /// Check if the group has no passes.
+    pub fn is_empty(&self) -> bool {
+        self.group.passes.is_empty()
+    }
+}
+
+impl Default for FixedPoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for FixedPoint {
+    fn name(&self) -> &str {
+        &self.group.name
+    }
+
+    fn kind(&self) -> PassKind {
+        self.group.kind()
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let mut before = (dag.num_ops(), dag.depth());
+        for iteration in 1..=self.max_iterations {
+            self.group.run(dag, properties)?;
+            let after = (dag.num_ops(), dag.depth());
+            if after == before {
+                debug!("{} converged after {iteration} iteration(s)", self.name());
+                return Ok(());
+            }
+            if properties.budget_exhausted() {
+                debug!(
+                    "{} stopped after {iteration} iteration(s): time budget spent",
+                    self.name()
+                );
+                return Ok(());
+            }
+            before = after;
+        }
+        debug!(
+            "{} did not converge within {} iterations",
+            self.name(),
+            self.max_iterations
+        );
+        Ok(())
+    }
+
+    fn is_optimization(&self) -> bool {
+        self.group.is_optimization()
+    }
+}
+
+/// Predicate deciding whether a [`Conditional`] group runs.
+type Predicate = dyn Fn(&CircuitDag, &PropertySet) -> bool + Send + Sync;
+
+/// Run a group of passes only if a predicate holds.
+///
+/// The predicate is evaluated when the pass manager reaches the group, so
+/// it sees the properties set by earlier analysis passes.
+pub struct Conditional {
+    group: PassGroup,
+    predicate: Box<Predicate>,
+}
+
+impl Conditional {
+    /// Create an empty controller that runs when `predicate` holds.
+    pub fn new(
+        predicate: impl Fn(&CircuitDag, &PropertySet) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            group: PassGroup::default(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Append a pass to the group.
+    #[must_use]
+    pub fn with_pass(self, pass: impl Pass + 'static) -> Self {
+        self.with_boxed_pass(Box::new(pass))
+    }
+
+    /// Append an already boxed pass to the group.
+    #[must_use]
+    pub fn with_boxed_pass(mut self, pass: Box<dyn Pass>) -> Self {
+        self.group.push("Conditional", pass);
+        self
+    }
+
+    /// Get the number of passes in the group.
+    pub fn len(&self) -> usize {
+        self.group.passes.len()
+    }
+
+    /// Check if the group has no passes.
+    pub fn is_empty(&self) -> bool {
+        self.group.passes.is_empty()
+    }
+}
+
+impl Pass for Conditional {
+    fn name(&self) -> &str {
+        &self.group.name
+    }
+
+    fn kind(&self) -> PassKind {
+        self.group.kind()
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        self.group.run(dag, properties)
+    }
+
+    fn should_run(&self, dag: &CircuitDag, properties: &PropertySet) -> bool {
+        (self.predicate)(dag, properties)
+    }
+
+    fn is_optimization(&self) -> bool {
+        self.group.is_optimization()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::PassManager;
+    use arvak_ir::{Circuit, QubitId};
+
+    /// Removes the first gate of the DAG per run, counting its runs.
+    struct RemoveOne {
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl Pass for RemoveOne {
+        fn name(&self) -> &'static str {
+            "RemoveOne"
+        }
+
+        fn kind(&self) -> PassKind {
+            PassKind::Transformation
+        }
+
+        fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            let first = dag.topological_ops().map(|(node, _)| node).next();
+            if let Some(node) = first {
+                dag.remove_op(node)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn dag_with_gates(n: usize) -> CircuitDag {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        for _ in 0..n {
+            circuit.x(QubitId(0)).unwrap();
+        }
+        circuit.into_dag()
+    }
+
+    #[test]
+    fn test_fixed_point_runs_until_unchanged() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut pm = PassManager::new();
+        pm.add_pass(FixedPoint::new().with_pass(RemoveOne { runs: runs.clone() }));
+
+        let mut dag = dag_with_gates(3);
+        pm.run(&mut dag, &mut PropertySet::new()).unwrap();
+        assert_eq!(dag.num_ops(), 0);
+        // Three removals, then one run that changes nothing.
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_fixed_point_max_iterations() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let controller = FixedPoint::new()
+            .with_pass(RemoveOne { runs: runs.clone() })
+            .with_max_iterations(2);
+        assert_eq!(controller.name(), "FixedPoint(RemoveOne)");
+
+        let mut dag = dag_with_gates(5);
+        controller.run(&mut dag, &mut PropertySet::new()).unwrap();
+        assert_eq!(dag.num_ops(), 3);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_conditional() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut pm = PassManager::new();
+        pm.add_pass(
+            Conditional::new(|_, props| props.seed == Some(7))
+                .with_pass(RemoveOne { runs: runs.clone() }),
+        );
+
+        let mut dag = dag_with_gates(2);
+        pm.run(&mut dag, &mut PropertySet::new()).unwrap();
+        assert_eq!(dag.num_ops(), 2);
+
+        let mut props = PropertySet::new();
+        props.seed = Some(7);
+        pm.run(&mut dag, &mut props).unwrap();
+        assert_eq!(dag.num_ops(), 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,287 @@
+//! The norm equation `t†t = ξ` over `ℤ[ω]`.
+//!
+//! Given a candidate top-left entry `u`, the bottom-left entry `t` of the
+//! unitary must satisfy `|t|² = 1 - |u|²`. After clearing denominators this
+//! is `t†t = ξ` for a doubly nonnegative `ξ ∈ ℤ[√2]`. The equation is solved
+//! prime by prime from the factorization of the integer norm `ξ·ξ•`; a
+//! candidate whose norm has a factor that cannot be written as `t†t`, or
+//! that is too large to factor, is rejected.
+
+use super::ring::{ZOmega, ZRoot2};
+
+/// Largest integer norm that is factored; larger candidates are skipped.
+const MAX_NORM: u64 = 1 << 62;
+
+/// A `t ∈ ℤ[ω]` with `t†t = xi`, if one exists.
+pub(crate) fn solve_norm_equation(xi: ZRoot2) -> Option<ZOmega> {
+    if xi == ZRoot2::ZERO {
+        return Some(ZOmega::ZERO);
+    }
+    if !xi.is_doubly_nonnegative() {
+        return None;
+    }
+
+    // Factors of √2: (1 + ω)†(1 + ω) = 2 + √2 = √2·λ.
+    let mut rest = xi;
+    let mut t = ZOmega::ONE;
+    while let Some(q) = rest.div_sqrt2() {
+        rest = q;
+        t = t * ZOmega([1, 1, 0, 0]);
+    }
+
+    // Dividing by √2 flips the sign of the √2-conjugate, so the norm may be
+    // negative.
+    let norm = u64::try_from(rest.norm().unsigned_abs())
+        .ok()
+        .filter(|&n| n < MAX_NORM)?;
+    for (p, exp) in factorize(norm) {
+        match p % 8 {
+            // p splits in ℤ[√2] as η·η•.
+            1 | 7 => {
+                let r = sqrt_mod(2, p)?;
+                let eta = ZRoot2::gcd(ZRoot2::new(i128::from(p), 0), ZRoot2::new(i128::from(r), 1));
+                for prime in [eta, eta.conj2()] {
+                    let mut count = 0;
+                    while let Some(q) = rest.div_exact(prime) {
+                        rest = q;
+                        count += 1;
+                    }
+                    if p % 8 == 1 {
+                        // η splits further in ℤ[ω]: η ~ τ†τ.
+                        let s = sqrt_mod(p - 1, p)?;
+                        let tau =
+                            ZOmega::gcd(ZOmega::from(prime), ZOmega([i128::from(s), 0, 1, 0]));
+                        t = t * tau.pow(count);
+                    } else if count % 2 == 0 {
+                        // η stays prime in ℤ[ω], so it must occur squared.
+                        t = t * ZOmega::from(prime).pow(count / 2);
+                    } else {
+                        return None;
+                    }
+                }
+            }
+            // p stays prime in ℤ[√2] (with norm p²) and splits in ℤ[ω]:
+            // p ~ τ†τ with τ = gcd(p, s + i) or gcd(p, s + i√2).
+            3 | 5 => {
+                if exp % 2 != 0 {
+                    return None;
+                }
+                let (square, root) = if p % 8 == 5 {
+                    (p - 1, ZOmega::I)
+                } else {
+                    (p - 2, ZOmega::I_SQRT2)
+                };
+                let s = sqrt_mod(square, p)?;
+                let tau = ZOmega::gcd(
+                    ZOmega::from_int(i128::from(p)),
+                    ZOmega::from_int(i128::from(s)) + root,
+                );
+                t = t * tau.pow(exp / 2);
+            }
+            _ => unreachable!("the norm of ξ/√2^k is odd"),
+        }
+    }
+
+    // xi = unit · t†t, and the unit is doubly positive, hence λ^(2j).
+    let unit = xi.div_exact(t.norm_sq())?;
+    if unit.norm() != 1 || !unit.is_doubly_nonnegative() {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let j = (unit.to_f64().ln() / (2.0 * (1.0 + std::f64::consts::SQRT_2).ln())).round() as i32;
+    if ZRoot2::lambda_pow(2 * j) != unit {
+        return None;
+    }
+    let t = t * ZOmega::from(ZRoot2::lambda_pow(j));
+    (t.norm_sq() == xi).then_some(t)
+}
+
+/// The prime factorization of `n` as `(prime, exponent)` pairs.
+fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut primes = Vec::new();
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+            continue;
+        }
+        let d = find_factor(m);
+        stack.push(d);
+        stack.push(m / d);
+    }
+    primes.sort_unstable();
+
+    let mut out: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match out.last_mut() {
+            Some((q, exp)) if *q == p => *exp += 1,
+            _ => out.push((p, 1)),
+        }
+    }
+    out
+}
+
+/// A nontrivial factor of the composite `n`.
+fn find_factor(n: u64) -> u64 {
+    for p in [2, 3, 5, 7, 11, 13] {
+        if n % p == 0 {
+            return p;
+        }
+    }
+    // Pollard's rho with Floyd cycle detection; retry with a new constant
+    // on the rare failure.
+    for c in 1.. {
+        let f = |x: u64| (mul_mod(x, x, n) + c) % n;
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.abs_diff(y), n);
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!("Pollard's rho finds a factor of every composite")
+}
+
+/// Deterministic Miller–Rabin for 64-bit integers.
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    let (mut d, mut s) = (n - 1, 0);
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+    'witness: for a in WITNESSES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A square root of `a` modulo the odd prime `p` (Tonelli–Shanks).
+fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a == 0 {
+        return Some(0);
+    }
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let (mut q, mut s) = (p - 1, 0);
+    while q % 2 == 0 {
+        q /= 2;
+        s += 1;
+    }
+    let z = (2..p)
+        .find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)
+        .expect("half of the residues are non-squares");
+    let (mut m, mut c, mut t, mut r) = (
+        s,
+        pow_mod(z, q, p),
+        pow_mod(a, q, p),
+        pow_mod(a, q.div_ceil(2), p),
+    );
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+    Some(r)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut acc = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    acc
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        let big = 1_000_000_007 * 998_244_353;
+        assert_eq!(factorize(big), vec![(998_244_353, 1), (1_000_000_007, 1)]);
+        assert!(is_prime(2_305_843_009_213_693_951));
+        for p in [13, 17, 41, 1_000_000_007] {
+            for a in 1..20 {
+                if let Some(r) = sqrt_mod(a, p) {
+                    assert_eq!(mul_mod(r, r, p), a % p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_norm_equation() {
+        // Every t†t is solvable; the solution need not be t itself.
+        for t in [
+            ZOmega([3, -1, 4, 2]),
+            ZOmega([1, 1, 0, 0]),
+            ZOmega([7, 0, 0, 0]),
+            ZOmega([12, 5, -9, 31]),
+            ZOmega([0, 0, 0, 0]),
+        ] {
+            let xi = t.norm_sq();
+            let s = solve_norm_equation(xi).unwrap_or_else(|| panic!("no solution for {xi:?}"));
+            assert_eq!(s.norm_sq(), xi);
+        }
+        // 3 + √2 is a prime above 7, which does not split in ℤ[ω].
+        assert_eq!(solve_norm_equation(ZRoot2::new(3, 1)), None);
+        assert_eq!(solve_norm_equation(ZRoot2::new(-1, 0)), None);
+        // 1 + √2 has a negative √2-conjugate.
+        assert_eq!(solve_norm_equation(ZRoot2::new(1, 1)), None);
+    }
+}
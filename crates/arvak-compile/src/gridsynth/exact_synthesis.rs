@@ -0,0 +1,226 @@
+//! Exact synthesis of single-qubit Clifford+T unitaries.
+//!
+//! A unitary with entries in `ℤ[ω][1/√2]` is a Clifford+T circuit
+//! (Kliuchnikov, Maslov and Mosca, 2013). The circuit is found by peeling
+//! off `H·T^j` factors: while the top-left entry `u` has a denominator, some
+//! `j ∈ 0..4` lowers the smallest denominator exponent (sde) of `|u|²`.
+//! What remains is diagonal or anti-diagonal with powers of `ω` as entries.
+
+use arvak_ir::StandardGate;
+
+use super::ring::ZOmega;
+
+/// A 2×2 unitary `m / √2^k` (row-major) with entries in `ℤ[ω][1/√2]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ExactUnitary {
+    m: [ZOmega; 4],
+    k: u32,
+}
+
+/// Search depth for the last few steps, where no single `H·T^j` need
+/// lower the sde.
+const MAX_SEARCH_DEPTH: u32 = 8;
+
+impl ExactUnitary {
+    pub fn new(m: [ZOmega; 4], k: u32) -> Self {
+        Self { m, k }.reduced()
+    }
+
+    /// The special unitary `[[u, -t†], [t, u†]] / √2^k`.
+    pub fn from_column(u: ZOmega, t: ZOmega, k: u32) -> Self {
+        Self::new([u, -t.conj(), t, u.conj()], k)
+    }
+
+    #[cfg(test)]
+    pub fn mul(&self, other: &Self) -> Self {
+        let [a, b, c, d] = self.m;
+        let [e, f, g, h] = other.m;
+        Self::new(
+            [a * e + b * g, a * f + b * h, c * e + d * g, c * f + d * h],
+            self.k + other.k,
+        )
+    }
+
+    #[cfg(test)]
+    pub fn scaled(&self, phase: ZOmega) -> Self {
+        Self::new(self.m.map(|x| x * phase), self.k)
+    }
+
+    /// Divide out common factors of `√2`.
+    fn reduced(mut self) -> Self {
+        while self.k > 0 {
+            let divided = self.m.map(ZOmega::div_sqrt2);
+            let [Some(a), Some(b), Some(c), Some(d)] = divided else {
+                break;
+            };
+            self.m = [a, b, c, d];
+            self.k -= 1;
+        }
+        self
+    }
+
+    /// `H · T^j · self`.
+    fn apply_ht(&self, j: i64) -> Self {
+        let [a, b, c, d] = self.m;
+        let w = ZOmega::omega_pow(j);
+        Self::new([a + w * c, b + w * d, a - w * c, b - w * d], self.k + 1)
+    }
+
+    /// The sde of `|u|²`: the least `e ≥ 0` with `√2^e·|u|² ∈ ℤ[√2]`.
+    fn sde(&self) -> u32 {
+        let mut n = self.m[0].norm_sq();
+        if n.a == 0 && n.b == 0 {
+            return 0;
+        }
+        // |u|² = n / √2^(2k); cancel the factors of √2 in n.
+        let mut e = 2 * self.k;
+        while e > 0 {
+            let Some(q) = n.div_sqrt2() else { break };
+            n = q;
+            e -= 1;
+        }
+        e
+    }
+}
+
+/// A Clifford+T circuit for `unitary`: gates in circuit order and an
+/// integer `a` with `unitary = ω^a · (gates)`.
+///
+/// Returns `None` only if `unitary` is not unitary.
+pub(crate) fn synthesize(unitary: &ExactUnitary) -> Option<(Vec<StandardGate>, i64)> {
+    let mut steps = Vec::new();
+    let mut current = *unitary;
+    while current.sde() > 0 {
+        let (j, next) = (0..4)
+            .map(|j| (j, current.apply_ht(j)))
+            .min_by_key(|(_, next)| next.sde())
+            .expect("four candidates");
+        if next.sde() < current.sde() {
+            steps.push(j);
+            current = next;
+            continue;
+        }
+        let tail = (1..=MAX_SEARCH_DEPTH).find_map(|depth| search(&current, depth))?;
+        for j in tail {
+            current = current.apply_ht(j);
+            steps.push(j);
+        }
+        break;
+    }
+
+    // unitary = T^{-j₁}·H · T^{-j₂}·H ⋯ T^{-jₙ}·H · current, where current
+    // is ω^a·T^m (diagonal) or ω^a·X·T^m (anti-diagonal).
+    if current.k != 0 {
+        return None;
+    }
+    let [a, b, c, d] = current.m;
+    let (phase, flip, power) = if c == ZOmega::ZERO {
+        let phase = omega_exponent(a)?;
+        (phase, false, omega_exponent(d)? - phase)
+    } else {
+        let phase = omega_exponent(c)?;
+        (phase, true, omega_exponent(b)? - phase)
+    };
+
+    let mut gates = phase_gates(power);
+    if flip {
+        gates.push(StandardGate::X);
+    }
+    for &j in steps.iter().rev() {
+        gates.push(StandardGate::H);
+        gates.extend(phase_gates(-j));
+    }
+    Some((gates, phase))
+}
+
+/// `H·T^j` steps that take `unitary` to sde 0 in exactly `depth` steps.
+fn search(unitary: &ExactUnitary, depth: u32) -> Option<Vec<i64>> {
+    if unitary.sde() == 0 {
+        return (depth == 0).then(Vec::new);
+    }
+    if depth == 0 {
+        return None;
+    }
+    (0..4).find_map(|j| {
+        let mut rest = search(&unitary.apply_ht(j), depth - 1)?;
+        rest.insert(0, j);
+        Some(rest)
+    })
+}
+
+/// The `a` with `x = ω^a`, if `x` is a power of `ω`.
+fn omega_exponent(x: ZOmega) -> Option<i64> {
+    (0..8).find(|&a| ZOmega::omega_pow(a) == x)
+}
+
+/// Gates for the diagonal `T^n = diag(1, ω^n)`.
+pub(crate) fn phase_gates(n: i64) -> Vec<StandardGate> {
+    match n.rem_euclid(8) {
+        0 => vec![],
+        1 => vec![StandardGate::T],
+        2 => vec![StandardGate::S],
+        3 => vec![StandardGate::S, StandardGate::T],
+        4 => vec![StandardGate::Z],
+        5 => vec![StandardGate::Z, StandardGate::T],
+        6 => vec![StandardGate::Sdg],
+        _ => vec![StandardGate::Tdg],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact_gate(gate: &StandardGate) -> ExactUnitary {
+        let (one, zero) = (ZOmega::ONE, ZOmega::ZERO);
+        let diagonal = |n| ExactUnitary::new([one, zero, zero, ZOmega::omega_pow(n)], 0);
+        match gate {
+            StandardGate::I => diagonal(0),
+            StandardGate::H => ExactUnitary::new([one, one, one, -one], 1),
+            StandardGate::X => ExactUnitary::new([zero, one, one, zero], 0),
+            StandardGate::T => diagonal(1),
+            StandardGate::S => diagonal(2),
+            StandardGate::Z => diagonal(4),
+            StandardGate::Sdg => diagonal(6),
+            StandardGate::Tdg => diagonal(7),
+            other => panic!("{other:?} is not a Clifford+T gate"),
+        }
+    }
+
+    fn exact_product(gates: &[StandardGate]) -> ExactUnitary {
+        gates
+            .iter()
+            .fold(exact_gate(&StandardGate::I), |acc, gate| {
+                exact_gate(gate).mul(&acc)
+            })
+    }
+
+    #[test]
+    fn test_synthesis_reproduces_random_words() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for len in [0, 1, 2, 5, 20, 60] {
+            for _ in 0..5 {
+                let word: Vec<StandardGate> = (0..len)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        match state % 4 {
+                            0 => StandardGate::H,
+                            1 => StandardGate::T,
+                            2 => StandardGate::S,
+                            _ => StandardGate::X,
+                        }
+                    })
+                    .collect();
+                let target = exact_product(&word);
+                let (gates, phase) = synthesize(&target).unwrap();
+                assert_eq!(
+                    exact_product(&gates).scaled(ZOmega::omega_pow(phase)),
+                    target,
+                    "{word:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Grid problems: lattice points of `ℤ[√2]` and `ℤ[ω]` in convex regions.
+//!
+//! A candidate for the top-left entry `u = u'/√2^k` of the approximating
+//! unitary must lie in the ε-region (a thin cap of the unit disk near
+//! `e^{-iθ/2}`), while its √2-conjugate `u•` must lie in the unit disk.
+//! Writing `u' = x + iy + sω` with `x, y ∈ ℤ[√2]` and `s ∈ {0, 1}` splits
+//! this into one-dimensional problems for `x` and then `y`, which are
+//! solved exactly after rescaling by powers of `λ = 1 + √2`.
+
+use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+use super::ring::{ZOmega, ZRoot2};
+
+/// `1 + √2`.
+const LAMBDA: f64 = 1.0 + SQRT_2;
+
+/// All `x ∈ ℤ[√2]` with `x ∈ [x0, x1]` and `x• ∈ [y0, y1]`.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn solve_1d(x0: f64, x1: f64, y0: f64, y1: f64) -> Vec<ZRoot2> {
+    if !(x0 <= x1 && y0 <= y1) {
+        return vec![];
+    }
+
+    // Multiplying by λⁿ scales [x0, x1] by λⁿ and [y0, y1] by (-λ)⁻ⁿ.
+    // Choose n so both intervals have about the same width; then the
+    // search below visits O(1 + #solutions) points.
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let n = if dx > 0.0 && dy > 0.0 {
+        ((dy / dx).ln() / (2.0 * LAMBDA.ln())).round() as i32
+    } else {
+        0
+    };
+    let scale = LAMBDA.powi(n);
+    let (x0, x1) = (x0 * scale, x1 * scale);
+    let (y0, y1) = if n % 2 == 0 {
+        (y0 / scale, y1 / scale)
+    } else {
+        (-y1 / scale, -y0 / scale)
+    };
+
+    // x = a + b√2 and x• = a - b√2, so b = (x - x•) / 2√2.
+    let b_min = ((x0 - y1) / (2.0 * SQRT_2)).ceil() as i128;
+    let b_max = ((x1 - y0) / (2.0 * SQRT_2)).floor() as i128;
+    let unscale = ZRoot2::lambda_pow(-n);
+    let mut out = Vec::new();
+    for b in b_min..=b_max {
+        #[allow(clippy::cast_precision_loss)]
+        let b_sqrt2 = b as f64 * SQRT_2;
+        let a_min = (x0 - b_sqrt2).max(y0 + b_sqrt2).ceil() as i128;
+        let a_max = (x1 - b_sqrt2).min(y1 + b_sqrt2).floor() as i128;
+        for a in a_min..=a_max {
+            out.push(ZRoot2::new(a, b) * unscale);
+        }
+    }
+    out
+}
+
+/// All `u' ∈ ℤ[ω]` with `u'/√2^k` in the ε-region around `z` and
+/// `(u'/√2^k)•` in the unit disk, where the ε-region is
+/// `{u : |u| ≤ 1, Re(u z̄) ≥ 1 - ε²/2}`.
+///
+/// `z` is given as `(Re z, Im z)` with `|z| = 1`.
+pub(crate) fn candidates(z: (f64, f64), epsilon: f64, k: u32) -> Vec<ZOmega> {
+    let r = SQRT_2.powi(i32::try_from(k).unwrap_or(i32::MAX));
+    let d = r * (1.0 - epsilon * epsilon / 2.0);
+    let (zx, zy) = z;
+
+    let mut out = Vec::new();
+    for s in [0, 1] {
+        // u' = x + iy + sω, and ω = (1 + i)/√2.
+        let shift = f64::from(s) * FRAC_1_SQRT_2;
+        let (cap_x0, cap_x1) = cap_x_range(z, r, d);
+        for x in solve_1d(cap_x0 - shift, cap_x1 - shift, shift - r, shift + r) {
+            let px = x.to_f64() + shift;
+            let Some((cap_y0, cap_y1)) = cap_y_range(zx, zy, r, d, px) else {
+                continue;
+            };
+            // (u')• = x• + iy• - sω must lie in the disk of radius r.
+            let qx = x.conj2().to_f64() - shift;
+            let h = (r * r - qx * qx).max(0.0).sqrt();
+            for y in solve_1d(cap_y0 - shift, cap_y1 - shift, shift - h, shift + h) {
+                let mut u = ZOmega::from(x) + ZOmega::I * ZOmega::from(y);
+                if s == 1 {
+                    u = u + ZOmega::OMEGA;
+                }
+                out.push(u);
+            }
+        }
+    }
+    out
+}
+
+/// The x-extent of the cap `{p : |p| ≤ r, p·z ≥ d}`.
+fn cap_x_range((zx, zy): (f64, f64), r: f64, d: f64) -> (f64, f64) {
+    // The cap's extreme points are the chord endpoints, plus (±r, 0) when
+    // they lie inside the cap.
+    let h = (r * r - d * d).max(0.0).sqrt();
+    let (e0, e1) = (d * zx - h * zy, d * zx + h * zy);
+    let (mut lo, mut hi) = (e0.min(e1), e0.max(e1));
+    if r * zx >= d {
+        hi = r;
+    }
+    if -r * zx >= d {
+        lo = -r;
+    }
+    (lo, hi)
+}
+
+/// The y-extent of the cap `{p : |p| ≤ r, p·z ≥ d}` at `p.x = px`.
+fn cap_y_range(zx: f64, zy: f64, r: f64, d: f64, px: f64) -> Option<(f64, f64)> {
+    if px.abs() > r {
+        return None;
+    }
+    let h = (r * r - px * px).sqrt();
+    let (mut lo, mut hi) = (-h, h);
+    // px·zx + py·zy ≥ d
+    let rest = d - px * zx;
+    if zy.abs() < f64::EPSILON {
+        if rest > 0.0 {
+            return None;
+        }
+    } else if zy > 0.0 {
+        lo = lo.max(rest / zy);
+    } else {
+        hi = hi.min(rest / zy);
+    }
+    (lo <= hi).then_some((lo, hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_1d_matches_brute_force() {
+        let intervals = [
+            (0.5, 7.25, -3.0, 4.0),
+            (-20.05, -19.9, -100.0, 100.0),
+            (30.0, 30.01, -200.0, 200.0),
+            (1.0, 1.0, 1.0, 1.0),
+        ];
+        for (x0, x1, y0, y1) in intervals {
+            let mut found = solve_1d(x0, x1, y0, y1);
+            found.sort_by_key(|x| (x.a, x.b));
+
+            let mut expected = Vec::new();
+            for a in -400..=400 {
+                for b in -400..=400 {
+                    let x = ZRoot2::new(a, b);
+                    let (v, w) = (x.to_f64(), x.conj2().to_f64());
+                    if (x0..=x1).contains(&v) && (y0..=y1).contains(&w) {
+                        expected.push(x);
+                    }
+                }
+            }
+            assert_eq!(found, expected, "interval {x0}..{x1} / {y0}..{y1}");
+        }
+    }
+
+    #[test]
+    fn test_candidates_lie_in_both_regions() {
+        let theta: f64 = 0.7;
+        let z = ((-theta / 2.0).cos(), (-theta / 2.0).sin());
+        let epsilon = 1e-2;
+        let mut total = 0;
+        for k in 0..16 {
+            let scale = SQRT_2.powi(k);
+            for u in candidates(z, epsilon, k.unsigned_abs()) {
+                let c = u.to_complex() / scale;
+                let c2 = u.conj2().to_complex() / scale;
+                assert!(c.norm() <= 1.0 + 1e-12);
+                assert!(c.re * z.0 + c.im * z.1 >= 1.0 - epsilon * epsilon / 2.0 - 1e-12);
+                assert!(c2.norm() <= 1.0 + 1e-12);
+                total += 1;
+            }
+        }
+        assert!(total > 0);
+    }
+}
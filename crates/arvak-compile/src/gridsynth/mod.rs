@@ -0,0 +1,211 @@
+//! Clifford+T approximation of Z rotations (Ross–Selinger).
+//!
+//! Fault-tolerant targets only offer a discrete gate set, usually Clifford
+//! gates plus `T`. An arbitrary rotation `Rz(θ)` is then approximated to
+//! within an operator-norm error `ε` by a Clifford+T circuit with about
+//! `3·log₂(1/ε)` T gates, using the number-theoretic method of Ross and
+//! Selinger ("Optimal ancilla-free Clifford+T approximation of
+//! z-rotations", 2016):
+//!
+//! 1. Enumerate candidates `u ∈ ℤ[ω][1/√2]` for the top-left entry of the
+//!    approximating unitary, by increasing denominator exponent `k`, so that
+//!    `u` lies in the ε-region around `e^{-iθ/2}` and its √2-conjugate lies
+//!    in the unit disk (two-dimensional grid problems).
+//! 2. Solve `|t|² = 1 - |u|²` for the bottom-left entry (a norm equation
+//!    in `ℤ[ω]`, solved by factoring an integer).
+//! 3. Decompose the resulting exact unitary into `H`, `T` and Clifford
+//!    phase gates.
+//!
+//! ```rust
+//! use arvak_compile::gridsynth::approximate_rz;
+//!
+//! let approx = approximate_rz(0.3, 1e-4).unwrap();
+//! assert!(approx.error <= 1e-4);
+//! assert!(approx.t_count() < 60);
+//! ```
+
+mod diophantine;
+mod exact_synthesis;
+mod grid;
+mod ring;
+
+use std::f64::consts::{FRAC_PI_4, SQRT_2};
+
+use arvak_ir::StandardGate;
+
+use crate::error::{CompileError, CompileResult};
+use diophantine::solve_norm_equation;
+pub(crate) use exact_synthesis::phase_gates;
+use exact_synthesis::{ExactUnitary, synthesize};
+use ring::ZRoot2;
+
+/// Smallest supported approximation error.
+///
+/// The grid search works in `f64`; below this the ε-region is too thin to
+/// resolve reliably.
+pub const MIN_EPSILON: f64 = 1e-7;
+
+/// Largest denominator exponent searched. Typical exponents are around
+/// `1.5·log₂(1/ε) + 3`, far below this bound.
+const MAX_DENOMINATOR_EXPONENT: u32 = 100;
+
+/// Angles within this distance of a multiple of π/4 are synthesized exactly.
+const EXACT_ANGLE_TOLERANCE: f64 = 1e-9;
+
+/// A Clifford+T circuit approximating `Rz(θ)`.
+#[derive(Debug, Clone)]
+pub struct RzApproximation {
+    /// Gates in circuit order, from `H`, `X`, `Z`, `S`, `Sdg`, `T` and `Tdg`.
+    pub gates: Vec<StandardGate>,
+    /// Phase `φ` such that `e^{iφ}` times the unitary of `gates`
+    /// approximates `Rz(θ)`.
+    pub global_phase: f64,
+    /// Operator-norm distance between `Rz(θ)` and the phased circuit.
+    pub error: f64,
+}
+
+impl RzApproximation {
+    /// Number of `T` and `Tdg` gates.
+    pub fn t_count(&self) -> usize {
+        self.gates
+            .iter()
+            .filter(|g| matches!(g, StandardGate::T | StandardGate::Tdg))
+            .count()
+    }
+}
+
+/// Approximate `Rz(theta)` by a Clifford+T circuit with operator-norm error
+/// at most `epsilon`, including the global phase.
+///
+/// Angles that are multiples of π/4 give an exact circuit. `epsilon` must
+/// lie in `[MIN_EPSILON, 1)`.
+pub fn approximate_rz(theta: f64, epsilon: f64) -> CompileResult<RzApproximation> {
+    if !(MIN_EPSILON..1.0).contains(&epsilon) {
+        return Err(CompileError::InvalidConfiguration(format!(
+            "Clifford+T approximation error {epsilon} is outside [{MIN_EPSILON}, 1)"
+        )));
+    }
+    if !theta.is_finite() {
+        return Err(CompileError::InvalidConfiguration(format!(
+            "cannot approximate Rz({theta})"
+        )));
+    }
+
+    // Rz(nπ/4) = e^{-inπ/8}·T^n.
+    if let Some(n) = t_power_of(theta) {
+        #[allow(clippy::cast_precision_loss)]
+        let global_phase = -(n as f64) * FRAC_PI_4 / 2.0;
+        return Ok(RzApproximation {
+            gates: phase_gates(n),
+            global_phase,
+            error: 0.0,
+        });
+    }
+
+    // The top-left entry of Rz(θ).
+    let z = ((-theta / 2.0).cos(), (-theta / 2.0).sin());
+    for k in 0..=MAX_DENOMINATOR_EXPONENT {
+        let scale = SQRT_2.powi(i32::try_from(k).expect("k <= 100"));
+        for u in grid::candidates(z, epsilon, k) {
+            // u = √2·u'' was already tried with exponent k - 1.
+            if k > 0 && u.div_sqrt2().is_some() {
+                continue;
+            }
+            let Some(t) = solve_norm_equation(ZRoot2::new(1 << k, 0) - u.norm_sq()) else {
+                continue;
+            };
+            let (gates, omega_power) = synthesize(&ExactUnitary::from_column(u, t, k))
+                .ok_or_else(|| synthesis_failed(theta, "exact synthesis failed"))?;
+
+            // ‖U - Rz(θ)‖ = √(2 - 2·Re(u·z̄)) for U = [[u, -t†], [t, u†]].
+            let c = u.to_complex() / scale;
+            let overlap = c.re * z.0 + c.im * z.1;
+            #[allow(clippy::cast_precision_loss)]
+            return Ok(RzApproximation {
+                gates,
+                global_phase: omega_power as f64 * FRAC_PI_4,
+                error: (2.0 - 2.0 * overlap).max(0.0).sqrt(),
+            });
+        }
+    }
+    Err(synthesis_failed(theta, "no candidate found"))
+}
+
+/// The `n` with `theta = nπ/4`, if `theta` is a multiple of π/4, so that
+/// `Rz(theta)` is `T^n` up to global phase.
+pub(crate) fn t_power_of(theta: f64) -> Option<i64> {
+    let eighths = theta / FRAC_PI_4;
+    #[allow(clippy::cast_possible_truncation)]
+    ((eighths - eighths.round()).abs() < EXACT_ANGLE_TOLERANCE).then(|| eighths.round() as i64)
+}
+
+fn synthesis_failed(theta: f64, reason: &str) -> CompileError {
+    CompileError::PassFailed {
+        name: "CliffordTSynthesis".into(),
+        reason: format!("Rz({theta}): {reason}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unitary::Unitary2x2;
+    use num_complex::Complex64;
+
+    /// Operator-norm distance between the phased circuit and `Rz(theta)`.
+    fn distance(approx: &RzApproximation, theta: f64) -> f64 {
+        let product = approx.gates.iter().fold(Unitary2x2::identity(), |acc, g| {
+            Unitary2x2::from_gate(g).unwrap() * acc
+        });
+        let phase = Complex64::from_polar(1.0, approx.global_phase);
+        // Both matrices are in SU(2), where the operator norm of the
+        // difference is its Frobenius norm over √2.
+        let frobenius_sq: f64 = Unitary2x2::rz(theta)
+            .data
+            .iter()
+            .zip(product.data)
+            .map(|(a, b)| (a - phase * b).norm_sqr())
+            .sum();
+        (frobenius_sq / 2.0).sqrt()
+    }
+
+    #[test]
+    fn test_approximations_are_within_epsilon() {
+        for &epsilon in &[1e-1, 1e-2, 1e-4, 1e-6] {
+            for &theta in &[0.1, 0.3, 1.0, -2.5, 3.0, 10.0] {
+                let approx = approximate_rz(theta, epsilon).unwrap();
+                assert!(approx.error <= epsilon, "θ={theta}, ε={epsilon}");
+                assert!(
+                    distance(&approx, theta) <= epsilon + 1e-9,
+                    "θ={theta}, ε={epsilon}: {}",
+                    distance(&approx, theta)
+                );
+                // Ross–Selinger: about 3·log₂(1/ε) T gates.
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let bound = (4.0 * (1.0 / epsilon).log2()) as usize + 10;
+                assert!(approx.t_count() <= bound, "θ={theta}, ε={epsilon}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiples_of_pi_over_four_are_exact() {
+        for n in -9..9 {
+            let theta = f64::from(n) * FRAC_PI_4;
+            let approx = approximate_rz(theta, 1e-3).unwrap();
+            assert_eq!(approx.error, 0.0);
+            assert_eq!(approx.t_count(), usize::from(n % 2 != 0));
+            assert!(distance(&approx, theta) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_invalid_epsilon() {
+        for epsilon in [0.0, 1e-9, 1.0, f64::NAN] {
+            assert!(matches!(
+                approximate_rz(0.3, epsilon),
+                Err(CompileError::InvalidConfiguration(_))
+            ));
+        }
+    }
+}
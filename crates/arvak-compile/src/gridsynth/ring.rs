@@ -0,0 +1,338 @@
+//! The rings `ℤ[√2]` and `ℤ[ω]`, `ω = e^{iπ/4}`.
+//!
+//! Clifford+T unitaries are exactly the unitaries with entries in
+//! `ℤ[ω][1/√2]`. Coefficients are `i128`: the integers that occur while
+//! approximating to [`MIN_EPSILON`](super::MIN_EPSILON) stay far below
+//! `2^64`, and products of two of them must not overflow.
+
+use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_complex::Complex64;
+
+/// `a + b√2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ZRoot2 {
+    pub a: i128,
+    pub b: i128,
+}
+
+impl ZRoot2 {
+    pub const ZERO: Self = Self::new(0, 0);
+    pub const ONE: Self = Self::new(1, 0);
+    /// `λ = 1 + √2`, the fundamental unit.
+    pub const LAMBDA: Self = Self::new(1, 1);
+    /// `λ⁻¹ = √2 - 1`.
+    pub const LAMBDA_INV: Self = Self::new(-1, 1);
+
+    pub const fn new(a: i128, b: i128) -> Self {
+        Self { a, b }
+    }
+
+    /// The √2-conjugate `a - b√2`.
+    pub fn conj2(self) -> Self {
+        Self::new(self.a, -self.b)
+    }
+
+    /// `a² - 2b²`, the product with the √2-conjugate.
+    pub fn norm(self) -> i128 {
+        self.a * self.a - 2 * self.b * self.b
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_f64(self) -> f64 {
+        self.a as f64 + self.b as f64 * SQRT_2
+    }
+
+    /// The exact sign of `a + b√2`.
+    pub fn signum(self) -> i32 {
+        let (a, b) = (self.a, self.b);
+        match (a.signum(), b.signum()) {
+            (0, 0) => 0,
+            (x, y) if x >= 0 && y >= 0 => 1,
+            (x, y) if x <= 0 && y <= 0 => -1,
+            // Opposite signs: the term with the larger square wins.
+            (x, _) => {
+                if a * a > 2 * b * b {
+                    i32::from(x > 0) * 2 - 1
+                } else {
+                    i32::from(x < 0) * 2 - 1
+                }
+            }
+        }
+    }
+
+    /// Whether both `x` and its √2-conjugate are `≥ 0`.
+    pub fn is_doubly_nonnegative(self) -> bool {
+        self.signum() >= 0 && self.conj2().signum() >= 0
+    }
+
+    /// `self / other`, if `other` divides `self`.
+    pub fn div_exact(self, other: Self) -> Option<Self> {
+        let n = other.norm();
+        if n == 0 {
+            return None;
+        }
+        let p = self * other.conj2();
+        (p.a % n == 0 && p.b % n == 0).then(|| Self::new(p.a / n, p.b / n))
+    }
+
+    /// Euclidean division: the quotient rounded to the nearest element.
+    fn div_round(self, other: Self) -> Self {
+        let n = other.norm();
+        let p = self * other.conj2();
+        Self::new(round_div(p.a, n), round_div(p.b, n))
+    }
+
+    /// A greatest common divisor (`ℤ[√2]` is norm-Euclidean).
+    pub fn gcd(mut x: Self, mut y: Self) -> Self {
+        while y != Self::ZERO {
+            let r = x - y * x.div_round(y);
+            x = y;
+            y = r;
+        }
+        x
+    }
+
+    /// `x / √2`, if `√2` divides `x`.
+    pub fn div_sqrt2(self) -> Option<Self> {
+        (self.a % 2 == 0).then(|| Self::new(self.b, self.a / 2))
+    }
+
+    pub fn pow(self, exp: u32) -> Self {
+        (0..exp).fold(Self::ONE, |acc, _| acc * self)
+    }
+
+    /// `λ^n` for any integer `n`.
+    pub fn lambda_pow(n: i32) -> Self {
+        if n >= 0 {
+            Self::LAMBDA.pow(n.unsigned_abs())
+        } else {
+            Self::LAMBDA_INV.pow(n.unsigned_abs())
+        }
+    }
+}
+
+impl Add for ZRoot2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.a + rhs.a, self.b + rhs.b)
+    }
+}
+
+impl Sub for ZRoot2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.a - rhs.a, self.b - rhs.b)
+    }
+}
+
+impl Mul for ZRoot2 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.a * rhs.a + 2 * self.b * rhs.b,
+            self.a * rhs.b + self.b * rhs.a,
+        )
+    }
+}
+
+/// `c₀ + c₁ω + c₂ω² + c₃ω³`, with `ω⁴ = -1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ZOmega(pub [i128; 4]);
+
+impl ZOmega {
+    pub const ZERO: Self = Self([0, 0, 0, 0]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+    pub const OMEGA: Self = Self([0, 1, 0, 0]);
+    pub const I: Self = Self([0, 0, 1, 0]);
+    pub const SQRT2: Self = Self([0, 1, 0, -1]);
+    /// `i√2 = ω + ω³`.
+    pub const I_SQRT2: Self = Self([0, 1, 0, 1]);
+
+    pub fn from_int(n: i128) -> Self {
+        Self([n, 0, 0, 0])
+    }
+
+    /// `ω^k` for any integer `k`.
+    pub fn omega_pow(k: i64) -> Self {
+        // ω^k for k in 0..8: ±ω^(k mod 4).
+        let k = k.rem_euclid(8);
+        let mut c = [0; 4];
+        c[usize::try_from(k % 4).expect("k % 4 < 4")] = if k < 4 { 1 } else { -1 };
+        Self(c)
+    }
+
+    /// Complex conjugate: `ω ↦ ω⁻¹ = -ω³`.
+    pub fn conj(self) -> Self {
+        let [c0, c1, c2, c3] = self.0;
+        Self([c0, -c3, -c2, -c1])
+    }
+
+    /// √2-conjugate `•`: `ω ↦ -ω`, which maps `√2 ↦ -√2` and fixes `i`.
+    #[cfg(test)]
+    pub fn conj2(self) -> Self {
+        let [c0, c1, c2, c3] = self.0;
+        Self([c0, -c1, c2, -c3])
+    }
+
+    /// `|x|² = x†x ∈ ℤ[√2]`.
+    pub fn norm_sq(self) -> ZRoot2 {
+        let p = self.conj() * self;
+        // A real element of ℤ[ω] is c₀ + c₁(ω - ω³) = c₀ + c₁√2.
+        debug_assert!(p.0[2] == 0 && p.0[3] == -p.0[1]);
+        ZRoot2::new(p.0[0], p.0[1])
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_complex(self) -> Complex64 {
+        let [c0, c1, c2, c3] = self.0.map(|c| c as f64);
+        Complex64::new(
+            c0 + (c1 - c3) * FRAC_1_SQRT_2,
+            c2 + (c1 + c3) * FRAC_1_SQRT_2,
+        )
+    }
+
+    /// The quotient `self / other` before rounding, scaled by the integer
+    /// norm of `other`: `self · other† · (other†other)•`.
+    fn scaled_quotient(self, other: Self) -> (Self, i128) {
+        let n = other.norm_sq();
+        let p = self * other.conj() * Self::from(n.conj2());
+        (p, n.norm())
+    }
+
+    /// `self / other`, if `other` divides `self`.
+    #[cfg(test)]
+    pub fn div_exact(self, other: Self) -> Option<Self> {
+        let (p, n) = self.scaled_quotient(other);
+        if n == 0 || p.0.iter().any(|c| c % n != 0) {
+            return None;
+        }
+        Some(Self(p.0.map(|c| c / n)))
+    }
+
+    /// `x / √2`, if `√2` divides `x`.
+    pub fn div_sqrt2(self) -> Option<Self> {
+        let p = self * Self::SQRT2;
+        p.0.iter()
+            .all(|c| c % 2 == 0)
+            .then(|| Self(p.0.map(|c| c / 2)))
+    }
+
+    /// A greatest common divisor (`ℤ[ω]` is norm-Euclidean).
+    pub fn gcd(mut x: Self, mut y: Self) -> Self {
+        while y != Self::ZERO {
+            let (p, n) = x.scaled_quotient(y);
+            let q = Self(p.0.map(|c| round_div(c, n)));
+            let r = x - y * q;
+            x = y;
+            y = r;
+        }
+        x
+    }
+
+    pub fn pow(self, exp: u32) -> Self {
+        (0..exp).fold(Self::ONE, |acc, _| acc * self)
+    }
+}
+
+impl From<ZRoot2> for ZOmega {
+    fn from(x: ZRoot2) -> Self {
+        // √2 = ω - ω³.
+        Self([x.a, x.b, 0, -x.b])
+    }
+}
+
+impl Add for ZOmega {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl Sub for ZOmega {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl Neg for ZOmega {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl Mul for ZOmega {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let mut c = [0; 4];
+        for (i, &x) in self.0.iter().enumerate() {
+            for (j, &y) in rhs.0.iter().enumerate() {
+                // ω⁴ = -1
+                if i + j < 4 {
+                    c[i + j] += x * y;
+                } else {
+                    c[i + j - 4] -= x * y;
+                }
+            }
+        }
+        Self(c)
+    }
+}
+
+/// `p / n` rounded to the nearest integer.
+fn round_div(p: i128, n: i128) -> i128 {
+    let (p, n) = if n < 0 { (-p, -n) } else { (p, n) };
+    (2 * p + n).div_euclid(2 * n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_omega_arithmetic() {
+        assert_eq!(ZOmega::OMEGA.pow(2), ZOmega::I);
+        assert_eq!(ZOmega::OMEGA.pow(8), ZOmega::ONE);
+        assert_eq!(ZOmega::SQRT2 * ZOmega::SQRT2, ZOmega::from_int(2));
+        assert_eq!(ZOmega::I_SQRT2 * ZOmega::I_SQRT2, ZOmega::from_int(-2));
+        assert_eq!(ZOmega::SQRT2.conj2(), -ZOmega::SQRT2);
+        assert_eq!(ZOmega::I.conj2(), ZOmega::I);
+        for k in -9..9 {
+            assert_eq!(
+                ZOmega::omega_pow(k),
+                ZOmega::OMEGA.pow(k.rem_euclid(8) as u32)
+            );
+        }
+
+        let x = ZOmega([3, -1, 4, 2]);
+        let z = x.to_complex();
+        assert!((x.norm_sq().to_f64() - z.norm_sqr()).abs() < 1e-9);
+        assert!((x.conj().to_complex() - z.conj()).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_division_and_gcd() {
+        let x = ZOmega([3, -1, 4, 2]);
+        let y = ZOmega([1, 1, 0, 5]);
+        assert_eq!((x * y).div_exact(y), Some(x));
+        assert_eq!((x * ZOmega::SQRT2).div_sqrt2(), Some(x));
+
+        // A common divisor that every other common divisor divides.
+        let g = ZOmega::gcd(x * y, x * ZOmega([2, 0, 1, 0]));
+        assert!(g.div_exact(x).is_some());
+        assert!((x * y).div_exact(g).is_some());
+
+        let a = ZRoot2::new(7, 3);
+        let b = ZRoot2::new(-2, 5);
+        assert_eq!((a * b).div_exact(b), Some(a));
+        let g = ZRoot2::gcd(a * b, a * ZRoot2::new(3, 1));
+        assert!(g.div_exact(a).is_some());
+        assert!((a * b).div_exact(g).is_some());
+        assert_eq!(ZRoot2::new(3, -2).signum(), 1);
+        assert_eq!(ZRoot2::new(2, -2).signum(), -1);
+        assert_eq!(ZRoot2::lambda_pow(3) * ZRoot2::lambda_pow(-3), ZRoot2::ONE);
+    }
+}
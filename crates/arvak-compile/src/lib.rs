@@ -23,9 +23,10 @@
 //! │ PassManager │ ◄── PropertySet (coupling map, basis gates, layout)
 //! └─────────────┘
 //!       │
-//!       ├── TrivialLayout / DenseLayout
+//!       ├── VF2Layout, then TrivialLayout / DenseLayout
 //!       ├── BasicRouting / SabreRouting
-//!       ├── BasisTranslation
+//!       ├── BasisTranslation (CliffordTSynthesis on Clifford+T targets),
+//!       │   then GateDirection on directed couplings
 //!       └── Optimize1qGates / CancelCX / CommutativeCancellation
 //!       │
 //!       ▼
@@ -55,6 +56,9 @@
 //! println!("Compiled depth: {}", compiled.depth());
 //! ```
 //!
+//! For a one-off compilation, [`transpile`] wires up the builder, the
+//! property set and the DAG round trip for a [`CompileTarget`].
+//!
 //! # Optimization Levels
 //!
 //! | Level | Passes Included |
@@ -62,24 +66,59 @@
 //! | 0 | Layout + Routing only |
 //! | 1 | + Basis translation |
 //! | 2 | + CX cancellation, 1q optimization |
-//! | 3 | + Commutative cancellation, aggressive optimization |
+//! | 3 | + Commutative cancellation, two-qubit block resynthesis |
+//!
+//! With [`OptimizationLevel::Auto`] the level is chosen per circuit from its
+//! structure, the target and a compile-time budget; see [`adaptive`].
+//! [`PassManager::with_time_budget`] bounds the wall-clock time of a run:
+//! optimization passes stop at the deadline with the best circuit found so
+//! far; see [`budget`].
+//!
+//! # Batch Compilation
+//!
+//! [`compile_batch`] compiles many circuits for one target in parallel,
+//! e.g. the points of a parameter sweep, and returns the results in order;
+//! see [`batch`].
+//!
+//! [`MultiProgramming`] packs several small circuits onto disjoint patches
+//! of one large device and merges them into a single job; see
+//! [`multiprogram`].
 //!
 //! # Built-in Passes
 //!
 //! ## Layout Passes
 //! - [`passes::TrivialLayout`]: Simple 1:1 mapping of virtual to physical qubits
+//! - [`passes::VF2Layout`]: SWAP-free placement by subgraph isomorphism, when one exists
 //! - `passes::DenseLayout` (planned): Pack qubits into well-connected region
 //!
 //! ## Routing Passes
 //! - [`passes::BasicRouting`]: Greedy SWAP insertion for connectivity
+//! - [`passes::SabreRouting`]: SWAP insertion with lookahead (SABRE)
+//! - [`passes::SwapNetworkRouting`]: Swap networks for dense layers of
+//!   commuting interactions, on a line chosen by [`passes::SwapNetworkLayout`]
+//!
+//! Both can execute second-neighbour CXs through a bridge instead of a
+//! SWAP; see [`bridge`].
 //!
 //! ## Translation Passes
 //! - [`passes::BasisTranslation`]: Convert to target gate set (IQM: PRX+CZ, IBM: SX+RZ+CX)
+//! - [`passes::UnitarySynthesis`]: Resynthesize two-qubit unitaries with at most
+//!   three CX/CZ via the KAK decomposition
+//! - [`passes::GateDirection`]: Flip CX/ECR (and swap symmetric gates) that act
+//!   against an edge's native direction
 //!
 //! ## Optimization Passes
 //! - [`passes::Optimize1qGates`]: Merge consecutive 1-qubit gates via ZYZ decomposition
 //! - [`passes::CancelCX`]: Cancel adjacent CX·CX pairs
-//! - [`passes::CommutativeCancellation`]: Merge commuting rotation gates
+//! - [`passes::CommutativeCancellation`]: Cancel or merge gate pairs separated
+//!   by gates they commute with (see [`commutation`]), then reorder for depth
+//! - [`passes::PhasePolynomialOptimization`]: Merge Z rotations on the same
+//!   CX parity, T-par style
+//! - [`passes::TemplateOptimization`]: Rewrite partial matches of identity
+//!   templates (CX conjugations, Toffoli pairs, phase gadgets)
+//! - [`passes::RemoveFinalMeasurements`] / [`passes::RestoreFinalMeasurements`]:
+//!   Take trailing measurements out of the way of optimization and append
+//!   them again over the final layout
 //!
 //! # Custom Passes
 //!
@@ -101,18 +140,53 @@
 //!     }
 //! }
 //! ```
+//!
+//! To run a custom pass inside the preset pipeline, give it to
+//! [`PassManagerBuilder::with_pass_at`], or register it by name in a
+//! [`PassRegistry`] so configuration or a plugin library can select it;
+//! see [`registry`].
 
+pub mod adaptive;
+pub mod ancilla;
+pub mod batch;
+pub mod bridge;
+pub mod budget;
+pub mod cancel;
+pub mod commutation;
+pub mod equivalence;
 pub mod error;
+pub mod error_weights;
+pub mod flow;
+pub mod gridsynth;
 pub mod manager;
+pub mod multiprogram;
 pub mod pass;
 pub mod property;
+pub mod registry;
+mod rng;
+pub mod transpile;
 pub mod unitary;
 
 // Built-in passes
 pub mod passes;
 
+pub use adaptive::{AdaptiveOptimization, AdaptiveSelection, OptimizationLevel};
+pub use ancilla::AncillaPool;
+pub use batch::compile_batch;
+pub use bridge::{BridgeDecision, BridgeDecisions, BridgeMode};
+pub use budget::{CompileDeadline, CompileTimings, PassOutcome, PassTiming};
+pub use cancel::CancellationToken;
+pub use commutation::{CommutationChecker, commutes};
+pub use equivalence::{
+    CheckOutcome, EquivalenceMethod, PassCheck, Verification, VerificationReport,
+};
 pub use error::{CompileError, CompileResult};
+pub use error_weights::ErrorWeights;
+pub use flow::{Conditional, FixedPoint};
 pub use manager::{PassManager, PassManagerBuilder};
+pub use multiprogram::{MultiProgram, MultiProgramming, ProgramSlot};
 pub use pass::{AnalysisPass, Pass, PassKind, TransformationPass};
 pub use passes::agnostic::NoiseInjectionPass;
-pub use property::{BasisGates, CouplingMap, Layout, PropertySet};
+pub use property::{BasisGates, CouplingMap, Layout, PropertySet, TargetPatch};
+pub use registry::{PassRegistry, PipelineStage};
+pub use transpile::{CompileTarget, transpile};
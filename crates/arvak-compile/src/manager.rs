@@ -1,27 +1,60 @@
 //! Pass manager for orchestrating compilation.
 
+use std::time::{Duration, Instant};
+
 use tracing::{debug, info, instrument};
 
 use arvak_ir::CircuitDag;
+use arvak_ir::noise::NoiseProfile;
 
-use crate::error::CompileResult;
+use crate::adaptive::AdaptiveOptimization;
+use crate::bridge::BridgeMode;
+use crate::budget::{CompileDeadline, CompileTimings, PassOutcome, PassTiming};
+use crate::cancel::CancellationToken;
+use crate::equivalence::{Verification, VerificationReport, run_verified};
+use crate::error::{CompileError, CompileResult};
+use crate::error_weights::ErrorWeights;
+use crate::flow::{Conditional, FixedPoint};
 use crate::pass::Pass;
 use crate::passes::{
-    BasicRouting, BasisTranslation, MeasurementBarrierVerification, OneQubitBasis, Optimize1qGates,
-    SabreRouting, TrivialLayout,
+    BasicRouting, BasisTranslation, CliffordTSynthesis, CommutativeCancellation, ConsolidateBlocks,
+    GateDirection, MeasurementBarrierVerification, OneQubitBasis, Optimize1qGates,
+    PhasePolynomialOptimization, RemoveFinalMeasurements, RestoreFinalMeasurements, SabreRouting,
+    SwapNetworkLayout, SwapNetworkPlan, SwapNetworkRouting, TrivialLayout, UnitarySynthesis,
 };
-use crate::property::{BasisGates, CouplingMap, PropertySet};
+use crate::property::{BasisGates, CouplingMap, PropertySet, TargetPatch};
+use crate::registry::{PassRegistry, PipelineStage};
+
+/// SABRE routing trials at optimization level 2 and above.
+const SABRE_TRIALS: usize = 8;
 
 /// Manages and executes a sequence of compilation passes.
 pub struct PassManager {
     /// The passes to execute, in order.
     passes: Vec<Box<dyn Pass>>,
+    /// Wall-clock budget for optimization passes.
+    time_budget: Option<Duration>,
+    /// Guarantee identical output for identical input.
+    deterministic: bool,
+    /// Stops the run once cancelled.
+    cancellation: Option<CancellationToken>,
+    /// Check equivalence after every transformation pass.
+    verification: Option<Verification>,
 }
 
 impl PassManager {
+    /// Seed used in deterministic mode when the properties have none.
+    pub const DEFAULT_SEED: u64 = 0;
+
     /// Create a new empty pass manager.
     pub fn new() -> Self {
-        Self { passes: vec![] }
+        Self {
+            passes: vec![],
+            time_budget: None,
+            deterministic: false,
+            cancellation: None,
+            verification: None,
+        }
     }
 
     /// Add a pass to the manager.
@@ -29,7 +62,78 @@ impl PassManager {
         self.passes.push(Box::new(pass));
     }
 
+    /// Bound the wall-clock time of a run.
+    ///
+    /// Once `budget` has passed, optimization passes stop with the best
+    /// circuit found so far and later ones are skipped; required passes
+    /// still run. See [`budget`](crate::budget).
+    #[must_use]
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Get the time budget, if any.
+    pub fn time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    /// Guarantee that two runs on the same circuit and properties produce
+    /// byte-for-byte the same output.
+    ///
+    /// Passes that make random choices are seeded with
+    /// [`PropertySet::seed`], or [`DEFAULT_SEED`](Self::DEFAULT_SEED) if it
+    /// is unset, and the time budget is ignored: where a wall-clock deadline
+    /// cuts optimization short depends on machine load.
+    #[must_use]
+    pub fn with_deterministic(mut self, enabled: bool) -> Self {
+        self.deterministic = enabled;
+        self
+    }
+
+    /// Check whether deterministic mode is enabled.
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Stop a run with [`CompileError::Cancelled`](crate::CompileError::Cancelled)
+    /// once `token` is cancelled.
+    ///
+    /// The token is checked before every pass and inside long loops such as
+    /// the routing search. See [`cancel`](crate::cancel).
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Get the cancellation token, if any.
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Check after every transformation pass that the circuit is still
+    /// equivalent to what it was before the pass.
+    ///
+    /// The first pass that changes the circuit's semantics fails the run
+    /// with [`CompileError::Miscompiled`](crate::CompileError::Miscompiled).
+    /// Afterwards `properties` holds a [`VerificationReport`]. Pass timings
+    /// include the time spent checking. See [`equivalence`](crate::equivalence).
+    #[must_use]
+    pub fn with_verification(mut self, verification: Verification) -> Self {
+        self.verification = Some(verification);
+        self
+    }
+
+    /// Get the verification settings, if verification mode is on.
+    pub fn verification(&self) -> Option<&Verification> {
+        self.verification.as_ref()
+    }
+
     /// Run all passes on the given DAG.
+    ///
+    /// Afterwards `properties` holds the [`CompileTimings`] of the run, and
+    /// the [`VerificationReport`] in verification mode.
     #[instrument(skip(self, dag, properties))]
     pub fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
         info!(
@@ -38,18 +142,90 @@ impl PassManager {
             dag.num_qubits()
         );
 
+        let time_budget = if self.deterministic {
+            if self.time_budget.is_some() {
+                info!("Deterministic mode: ignoring the time budget");
+            }
+            properties.seed.get_or_insert(Self::DEFAULT_SEED);
+            None
+        } else {
+            self.time_budget
+        };
+
+        match time_budget {
+            Some(budget) => properties.insert(CompileDeadline::new(budget)),
+            None => {
+                properties.remove::<CompileDeadline>();
+            }
+        }
+        match &self.cancellation {
+            Some(token) => properties.insert(token.clone()),
+            None => {
+                properties.remove::<CancellationToken>();
+            }
+        }
+        properties.remove::<VerificationReport>();
+        match self.verification {
+            Some(verification) => {
+                properties.insert(verification);
+                properties.insert(VerificationReport::default());
+            }
+            None => {
+                properties.remove::<Verification>();
+            }
+        }
+        let mut timings = CompileTimings {
+            budget: time_budget,
+            passes: Vec::with_capacity(self.passes.len()),
+        };
+
         for pass in &self.passes {
-            if pass.should_run(dag, properties) {
+            properties.check_cancelled()?;
+            let start = Instant::now();
+            let outcome = if pass.is_optimization() && properties.budget_exhausted() {
+                debug!("Skipping pass {}: time budget spent", pass.name());
+                PassOutcome::SkippedOverBudget
+            } else if pass.should_run(dag, properties) {
                 debug!("Running pass: {}", pass.name());
-                pass.run(dag, properties)?;
+                run_verified(pass.as_ref(), dag, properties)?;
                 // Avoid calling dag.depth() here — it performs a full topological
                 // sort (O(V+E)) on every pass and is only used for debug logging.
                 debug!("Pass {} completed, ops: {}", pass.name(), dag.num_ops());
+                if pass.is_optimization() && properties.budget_exhausted() {
+                    PassOutcome::CutShort
+                } else {
+                    PassOutcome::Completed
+                }
             } else {
                 debug!("Skipping pass: {}", pass.name());
-            }
+                PassOutcome::Skipped
+            };
+            timings.passes.push(PassTiming {
+                name: pass.name().to_string(),
+                elapsed: start.elapsed(),
+                outcome,
+            });
         }
 
+        properties.remove::<CompileDeadline>();
+        properties.remove::<CancellationToken>();
+        properties.remove::<Verification>();
+        if let Some(report) = properties.get::<VerificationReport>() {
+            info!(
+                "Verification: {} of {} transformation passes verified",
+                report.verified(),
+                report.checks.len()
+            );
+        }
+        if timings.budget_exhausted() {
+            info!(
+                "Time budget of {:?} spent after {:?}; optimization stopped early",
+                time_budget.unwrap_or_default(),
+                timings.total()
+            );
+        }
+        properties.insert(timings);
+
         info!(
             "Pass manager completed, final depth: {}, ops: {}",
             dag.depth(),
@@ -82,6 +258,26 @@ pub struct PassManagerBuilder {
     optimization_level: u8,
     /// Target properties.
     properties: PropertySet,
+    /// Let routing bridge CX gates through idle physical qubits.
+    ancilla_routing: bool,
+    /// Weigh routing SWAPs by the noise profile's error rates.
+    error_weighted_routing: bool,
+    /// Wall-clock budget for the built pass manager.
+    time_budget: Option<Duration>,
+    /// Build a deterministic pass manager.
+    deterministic: bool,
+    /// Cancellation token for the built pass manager.
+    cancellation: Option<CancellationToken>,
+    /// Verification settings for the built pass manager.
+    verification: Option<Verification>,
+    /// Reuse measured qubits via mid-circuit reset.
+    qubit_reuse: bool,
+    /// Route dense interaction layers through swap networks.
+    swap_networks: bool,
+    /// Approximation error per rotation on Clifford+T targets.
+    synthesis_epsilon: f64,
+    /// External passes and the stages they run at, in insertion order.
+    external_passes: Vec<(PipelineStage, Box<dyn Pass>)>,
 }
 
 impl PassManagerBuilder {
@@ -90,6 +286,16 @@ impl PassManagerBuilder {
         Self {
             optimization_level: 1,
             properties: PropertySet::new(),
+            ancilla_routing: false,
+            error_weighted_routing: false,
+            time_budget: None,
+            deterministic: false,
+            cancellation: None,
+            verification: None,
+            qubit_reuse: false,
+            swap_networks: false,
+            synthesis_epsilon: CliffordTSynthesis::DEFAULT_EPSILON,
+            external_passes: Vec::new(),
         }
     }
 
@@ -98,13 +304,35 @@ impl PassManagerBuilder {
     /// - Level 0: No optimization, only required transformations
     /// - Level 1: Light optimization (default)
     /// - Level 2: Moderate optimization
-    /// - Level 3: Heavy optimization
+    /// - Level 3: Heavy optimization, including commutation-aware
+    ///   cancellation and two-qubit block resynthesis
     #[must_use]
     pub fn with_optimization_level(mut self, level: u8) -> Self {
         self.optimization_level = level.min(3);
         self
     }
 
+    /// Choose the optimization level for `dag` adaptively.
+    ///
+    /// Call after setting the target. The chosen
+    /// [`AdaptiveSelection`](crate::AdaptiveSelection) is stored in the
+    /// returned property set.
+    #[must_use]
+    pub fn with_adaptive_optimization(
+        mut self,
+        dag: &CircuitDag,
+        adaptive: &AdaptiveOptimization,
+    ) -> Self {
+        let selection = adaptive.select(dag, &self.properties);
+        info!(
+            "Adaptive optimization: level {} ({}), estimated {:?} of {:?} budget",
+            selection.level, selection.reason, selection.estimated_compile_time, selection.budget
+        );
+        self.optimization_level = selection.level;
+        self.properties.insert(selection);
+        self
+    }
+
     /// Set the target properties.
     #[must_use]
     pub fn with_properties(mut self, properties: PropertySet) -> Self {
@@ -120,9 +348,205 @@ impl PassManagerBuilder {
         self
     }
 
+    /// Attach the device's noise profile; read by the
+    /// [`ErrorBudget`](crate::passes::ErrorBudget) and
+    /// [`NoiseInjectionPass`](crate::NoiseInjectionPass) passes.
+    #[must_use]
+    pub fn with_noise_profile(mut self, profile: NoiseProfile) -> Self {
+        self.properties.insert(profile);
+        self
+    }
+
+    /// Allow routing to use idle physical qubits as ancillas.
+    ///
+    /// Opt-in; only affects SABRE routing (optimization level >= 1). See
+    /// [`SabreRouting::with_ancilla_bridging`].
+    #[must_use]
+    pub fn with_ancilla_routing(mut self, enabled: bool) -> Self {
+        self.ancilla_routing = enabled;
+        self
+    }
+
+    /// Weigh routing SWAPs by calibrated error rates instead of distance
+    /// alone.
+    ///
+    /// Opt-in; takes the coupler and readout errors of the noise profile
+    /// (see [`with_noise_profile`](Self::with_noise_profile)) as
+    /// [`ErrorWeights`] for both routing passes. Has no effect without a
+    /// noise profile. Pair it with a patch from
+    /// [`TargetPatch::from_noise_profile`] to place qubits by the same data.
+    #[must_use]
+    pub fn with_error_weighted_routing(mut self, enabled: bool) -> Self {
+        self.error_weighted_routing = enabled;
+        self
+    }
+
+    /// Let routing execute CXs between second neighbours through a bridge
+    /// of four CXs instead of inserting a SWAP.
+    ///
+    /// Applies to both routing passes, which record each choice in
+    /// [`BridgeDecisions`](crate::BridgeDecisions). See [`crate::bridge`].
+    #[must_use]
+    pub fn with_bridge_gates(mut self, mode: BridgeMode) -> Self {
+        self.properties.insert(mode);
+        self
+    }
+
+    /// Route circuits with a dense layer of commuting two-qubit
+    /// interactions, such as QAOA cost layers, through swap networks on a
+    /// line of qubits instead of inserting SWAPs gate by gate.
+    ///
+    /// Opt-in; circuits without such a layer, or devices without a long
+    /// enough line, are laid out and routed as usual. See
+    /// [`SwapNetworkLayout`].
+    #[must_use]
+    pub fn with_swap_networks(mut self, enabled: bool) -> Self {
+        self.swap_networks = enabled;
+        self
+    }
+
+    /// Reuse measured qubits through mid-circuit reset before layout.
+    ///
+    /// Opt-in; shrinks the qubit count of sequential circuits so they fit
+    /// small devices. See [`QubitReuse`](crate::passes::QubitReuse).
+    #[must_use]
+    pub fn with_qubit_reuse(mut self, enabled: bool) -> Self {
+        self.qubit_reuse = enabled;
+        self
+    }
+
+    /// Set the approximation error per rotation on Clifford+T targets; see
+    /// [`CliffordTSynthesis::with_epsilon`].
+    #[must_use]
+    pub fn with_synthesis_epsilon(mut self, epsilon: f64) -> Self {
+        self.synthesis_epsilon = epsilon;
+        self
+    }
+
+    /// Confine compilation to a subset of physical qubits.
+    ///
+    /// Layout only places qubits on the patch, and the coupling map is
+    /// restricted to edges inside it at build time so routing never leaves
+    /// the patch. Use [`TargetPatch::best`] to pick the patch from
+    /// calibration data.
+    ///
+    /// Must be called after [`with_target`](Self::with_target). Fails with
+    /// [`CompileError::MissingCouplingMap`] if no target is set, or with
+    /// [`CompileError::InvalidConfiguration`] if a patch qubit is outside
+    /// the device.
+    pub fn with_target_patch(mut self, physical_qubits: &[u32]) -> CompileResult<Self> {
+        let patch = TargetPatch::new(physical_qubits);
+        let coupling_map = self
+            .properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+        patch.validate(coupling_map)?;
+        self.properties.insert(patch);
+        Ok(self)
+    }
+
+    /// Bound the wall-clock time of the built pass manager; see
+    /// [`PassManager::with_time_budget`].
+    #[must_use]
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Seed passes that make random choices; see [`PropertySet::seed`].
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.properties.seed = Some(seed);
+        self
+    }
+
+    /// Build a deterministic pass manager; see
+    /// [`PassManager::with_deterministic`].
+    #[must_use]
+    pub fn with_deterministic(mut self, enabled: bool) -> Self {
+        self.deterministic = enabled;
+        self
+    }
+
+    /// Let `token` cancel the built pass manager; see
+    /// [`PassManager::with_cancellation`].
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Build a pass manager in verification mode; see
+    /// [`PassManager::with_verification`].
+    #[must_use]
+    pub fn with_verification(mut self, verification: Verification) -> Self {
+        self.verification = Some(verification);
+        self
+    }
+
+    /// Run `pass` at `stage` of the preset pipeline.
+    ///
+    /// Passes given for the same stage run in the order they were added.
+    #[must_use]
+    pub fn with_pass_at(mut self, stage: PipelineStage, pass: impl Pass + 'static) -> Self {
+        self.external_passes.push((stage, Box::new(pass)));
+        self
+    }
+
+    /// Create the pass registered as `name` in `registry` from `options`
+    /// and run it at `stage`; see [`crate::registry`].
+    ///
+    /// Fails if no pass is registered as `name`, or if its factory rejects
+    /// the options.
+    pub fn with_registered_pass(
+        mut self,
+        registry: &PassRegistry,
+        name: &str,
+        stage: PipelineStage,
+        options: &serde_json::Value,
+    ) -> CompileResult<Self> {
+        let pass = registry.create(name, options)?;
+        debug!("Inserting external pass '{}' at {}", name, stage);
+        self.external_passes.push((stage, pass));
+        Ok(self)
+    }
+
+    /// Move the external passes for `stage` into `pm`.
+    fn add_external_passes(&mut self, pm: &mut PassManager, stage: PipelineStage) {
+        let (passes, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut self.external_passes)
+            .into_iter()
+            .partition(|(s, _)| *s == stage);
+        self.external_passes = rest;
+        pm.passes.extend(passes.into_iter().map(|(_, pass)| pass));
+    }
+
     /// Build the pass manager and return it with the properties.
-    pub fn build(self) -> (PassManager, PropertySet) {
-        let mut pm = PassManager::new();
+    pub fn build(mut self) -> (PassManager, PropertySet) {
+        let mut pm = PassManager::new().with_deterministic(self.deterministic);
+        pm.time_budget = self.time_budget;
+        pm.cancellation = self.cancellation.take();
+        pm.verification = self.verification;
+
+        if let (Some(patch), Some(coupling_map)) = (
+            self.properties.get::<TargetPatch>(),
+            self.properties.coupling_map.as_ref(),
+        ) {
+            let restricted = coupling_map.restricted_to(patch.qubits());
+            self.properties.coupling_map = Some(restricted);
+        }
+
+        if self.error_weighted_routing && self.properties.get::<ErrorWeights>().is_none() {
+            if let Some(profile) = self.properties.get::<NoiseProfile>() {
+                let weights = ErrorWeights::from_noise_profile(profile);
+                self.properties.insert(weights);
+            }
+        }
+
+        // Qubit reuse changes the number of qubits, so it runs before layout.
+        if self.qubit_reuse {
+            pm.add_pass(crate::passes::QubitReuse::new());
+        }
 
         // Add layout pass if we have a coupling map.
         // The routing passes only understand 1q/2q operations; expand
@@ -130,65 +554,153 @@ impl PassManagerBuilder {
         if self.properties.coupling_map.is_some() {
             pm.add_pass(crate::passes::Unroll3q);
         }
+        self.add_external_passes(&mut pm, PipelineStage::PreLayout);
 
-        // Level >= 2 uses DenseLayout (topology-aware placement),
-        // otherwise TrivialLayout (identity mapping).
+        // Level >= 1 first looks for a perfect (SWAP-free) layout with VF2.
+        // If there is none, a circuit with a dense interaction layer is put
+        // on a line for a swap network when enabled, level >= 2 uses
+        // DenseLayout (topology-aware placement), otherwise TrivialLayout
+        // (identity mapping).
         if self.properties.coupling_map.is_some() {
+            if self.optimization_level >= 1 {
+                pm.add_pass(crate::passes::VF2Layout::new());
+            }
+            if self.swap_networks {
+                pm.add_pass(SwapNetworkLayout::new());
+            }
             if self.optimization_level >= 2 {
                 pm.add_pass(crate::passes::DenseLayout);
             } else {
                 pm.add_pass(TrivialLayout);
             }
         }
+        self.add_external_passes(&mut pm, PipelineStage::PostLayout);
 
         // Add routing if we have a coupling map.
-        // Level >= 1 uses SABRE (bidirectional heuristic with lookahead),
-        // level 0 uses BasicRouting (greedy shortest-path).
+        // Level >= 1 uses SABRE (heuristic with lookahead), with several
+        // trials from level 2; level 0 uses BasicRouting (greedy shortest-path).
+        // Circuits placed for a swap network are routed along their line
+        // instead.
         if self.properties.coupling_map.is_some() {
-            if self.optimization_level >= 1 {
-                pm.add_pass(SabreRouting::new());
+            let router: Box<dyn Pass> = if self.optimization_level >= 1 {
+                let trials = if self.optimization_level >= 2 {
+                    SABRE_TRIALS
+                } else {
+                    1
+                };
+                Box::new(
+                    SabreRouting::new()
+                        .with_ancilla_bridging(self.ancilla_routing)
+                        .with_trials(trials),
+                )
+            } else {
+                Box::new(BasicRouting)
+            };
+            if self.swap_networks {
+                pm.add_pass(SwapNetworkRouting);
+                pm.add_pass(
+                    Conditional::new(|_, props| props.get::<SwapNetworkPlan>().is_none())
+                        .with_boxed_pass(router),
+                );
             } else {
-                pm.add_pass(BasicRouting);
+                pm.passes.push(router);
             }
         }
+        self.add_external_passes(&mut pm, PipelineStage::PostRouting);
 
-        // NOTE: ConsolidateBlocks is currently NOT part of the default
-        // level-3 pipeline. It replaces 2-qubit blocks with CustomGate
-        // matrices, but KAK local-factor extraction (needed to re-synthesize
-        // entangling matrices into basis gates) is not implemented yet —
-        // BasisTranslation rejects such gates rather than emitting a wrong
-        // circuit. Re-enable here once `Unitary4x4::kak_decompose` extracts
-        // the local factors (see WeylDecomposition::to_circuit).
+        // Level 3 merges runs of gates on a qubit pair into one unitary when
+        // its KAK decomposition needs fewer entangling gates, and
+        // resynthesizes it with CX (CZ on CZ-native targets such as IQM).
+        if self.optimization_level >= 3 {
+            pm.add_pass(ConsolidateBlocks);
+            pm.add_pass(UnitarySynthesis);
+        }
 
-        // Add basis translation if we have basis gates
-        if self.properties.basis_gates.is_some() {
+        // Add basis translation if we have basis gates. Clifford+T targets
+        // have no continuous rotations, so rotations are approximated there.
+        let clifford_t = self
+            .properties
+            .basis_gates
+            .as_ref()
+            .is_some_and(BasisGates::is_clifford_t);
+        if clifford_t {
+            pm.add_pass(CliffordTSynthesis::new().with_epsilon(self.synthesis_epsilon));
+        } else if self.properties.basis_gates.is_some() {
             pm.add_pass(BasisTranslation);
         }
 
-        // Add optimization passes based on level
+        // Directional couplings (e.g. IBM Eagle ECR): flip two-qubit gates
+        // that routing placed against an edge's native direction. This runs
+        // after translation, which may introduce new two-qubit gates, and
+        // re-translates the single-qubit gates it inserts.
+        if self
+            .properties
+            .coupling_map
+            .as_ref()
+            .is_some_and(CouplingMap::is_directed)
+        {
+            pm.add_pass(GateDirection);
+            if self.properties.basis_gates.is_some() {
+                pm.add_pass(BasisTranslation);
+            }
+        }
+        self.add_external_passes(&mut pm, PipelineStage::PostTranslation);
+
+        // Add optimization passes based on level. Level 3 first merges Z
+        // rotations on equal CX parities, then cancels and merges gates
+        // across commuting operations, and repeats the stage until the
+        // circuit stops changing: resynthesized single-qubit runs can expose
+        // new cancellations, and the reverse. Final measurements are set
+        // aside for the stage so they do not act as barriers.
         if self.optimization_level >= 1 {
-            // Use ZSX decomposition for IBM/Heron targets (RZ + SX native basis),
-            // otherwise default to ZYZ.
-            let use_zsx = self
-                .properties
-                .basis_gates
-                .as_ref()
-                .is_some_and(|b| b.contains("sx") && b.contains("rz"));
-            if use_zsx {
-                pm.add_pass(Optimize1qGates::with_basis(OneQubitBasis::ZSX));
+            pm.add_pass(RemoveFinalMeasurements);
+
+            let mut stage: Vec<Box<dyn Pass>> = Vec::new();
+            if self.optimization_level >= 3 {
+                stage.push(Box::new(PhasePolynomialOptimization));
+                stage.push(Box::new(CommutativeCancellation));
+            }
+
+            // Resynthesizing runs of Clifford+T gates as ZYZ rotations would
+            // turn exact gates into approximations, so Clifford+T targets only
+            // re-synthesize what earlier passes left outside the basis.
+            if clifford_t {
+                stage.push(Box::new(
+                    CliffordTSynthesis::new().with_epsilon(self.synthesis_epsilon),
+                ));
             } else {
-                pm.add_pass(Optimize1qGates::new());
+                // Use ZSX decomposition for IBM/Heron targets (RZ + SX native
+                // basis), otherwise default to ZYZ.
+                let use_zsx = self
+                    .properties
+                    .basis_gates
+                    .as_ref()
+                    .is_some_and(|b| b.contains("sx") && b.contains("rz"));
+                if use_zsx {
+                    stage.push(Box::new(Optimize1qGates::with_basis(OneQubitBasis::ZSX)));
+                } else {
+                    stage.push(Box::new(Optimize1qGates::new()));
+                }
+
+                // Optimize1qGates resynthesizes runs in its own working basis
+                // (ZYZ / ZSX), which can reintroduce gates the target does not
+                // support (e.g. rz/ry for IQM's prx+cz set). Re-translate so the
+                // pipeline output is basis-conformant again; for targets whose
+                // basis matches the working basis this is a no-op.
+                if self.properties.basis_gates.is_some() {
+                    stage.push(Box::new(BasisTranslation));
+                }
             }
 
-            // Optimize1qGates resynthesizes runs in its own working basis
-            // (ZYZ / ZSX), which can reintroduce gates the target does not
-            // support (e.g. rz/ry for IQM's prx+cz set). Re-translate so the
-            // pipeline output is basis-conformant again; for targets whose
-            // basis matches the working basis this is a no-op.
-            if self.properties.basis_gates.is_some() {
-                pm.add_pass(BasisTranslation);
+            if self.optimization_level >= 3 {
+                pm.add_pass(FixedPoint::from_passes(stage));
+            } else {
+                pm.passes.extend(stage);
             }
+
+            pm.add_pass(RestoreFinalMeasurements);
         }
+        self.add_external_passes(&mut pm, PipelineStage::PostOptimization);
 
         // Always add measurement barrier verification as the final pass
         // to catch any correctness violations from optimization passes.
@@ -209,7 +721,7 @@ impl Default for PassManagerBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arvak_ir::{Circuit, QubitId};
+    use arvak_ir::{Circuit, ClbitId, QubitId};
 
     #[test]
     fn test_empty_pass_manager() {
@@ -244,4 +756,562 @@ mod tests {
         assert!(props.coupling_map.is_some());
         assert!(props.basis_gates.is_some());
     }
+
+    #[test]
+    fn test_pass_manager_level3_resynthesizes_blocks() {
+        // Four entangling layers on one pair collapse to at most three CZ.
+        let mut circuit = Circuit::with_size("ansatz", 2, 0);
+        for layer in 0..4 {
+            let theta = 0.3 + f64::from(layer) * 0.4;
+            circuit.ry(theta, QubitId(0)).unwrap();
+            circuit.ry(-theta, QubitId(1)).unwrap();
+            circuit.cx(QubitId(0), QubitId(1)).unwrap();
+            circuit.rz(theta, QubitId(1)).unwrap();
+        }
+
+        let cz_count = |level: u8| {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(level)
+                .with_target(CouplingMap::linear(2), BasisGates::iqm())
+                .build();
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+            for (_, inst) in dag.topological_ops() {
+                assert!(["prx", "cz"].contains(&inst.name()), "{}", inst.name());
+            }
+            dag.topological_ops()
+                .filter(|(_, inst)| inst.name() == "cz")
+                .count()
+        };
+        assert_eq!(cz_count(2), 4);
+        assert!(cz_count(3) <= 3);
+    }
+
+    #[test]
+    fn test_pass_manager_level3_cancels_across_commuting_gates() {
+        // The CX pair is separated by a rotation on the control.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.rz(0.4, QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+        let cx_count = |level: u8| {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(level)
+                .with_target(CouplingMap::linear(2), BasisGates::heron())
+                .build();
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+            dag.topological_ops()
+                .filter(|(_, inst)| inst.name() == "cx" || inst.name() == "cz")
+                .count()
+        };
+        assert_eq!(cx_count(2), 2);
+        assert_eq!(cx_count(3), 0);
+    }
+
+    #[test]
+    fn test_pass_manager_respects_directed_edges() {
+        // ECR is native only on 0 → 1 and 1 → 2; the circuit needs both
+        // edges in the reverse direction.
+        let map = CouplingMap::from_directed_edge_list(3, &[(0, 1), (1, 2)]);
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(2)).unwrap();
+        circuit.cx(QubitId(2), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(0)).unwrap();
+
+        for level in 0..=3 {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(level)
+                .with_target(map.clone(), BasisGates::eagle())
+                .build();
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+
+            let basis = BasisGates::eagle();
+            for (_, inst) in dag.topological_ops() {
+                assert!(
+                    basis.contains(inst.name()),
+                    "level {level}: {}",
+                    inst.name()
+                );
+                if inst.qubits.len() == 2 {
+                    let (a, b) = (inst.qubits[0].0, inst.qubits[1].0);
+                    assert!(
+                        map.supports_direction(a, b),
+                        "level {level}: {} on {a} → {b}",
+                        inst.name()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pass_manager_clifford_t_target() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.rz(0.3, QubitId(2)).unwrap();
+        circuit.t(QubitId(1)).unwrap();
+        circuit.crz(1.1, QubitId(1), QubitId(2)).unwrap();
+
+        for level in 0..=3 {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(level)
+                .with_synthesis_epsilon(1e-3)
+                .with_target(CouplingMap::linear(3), BasisGates::clifford_t())
+                .build();
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+
+            let basis = BasisGates::clifford_t();
+            for (_, inst) in dag.topological_ops() {
+                assert!(
+                    basis.contains(inst.name()),
+                    "level {level}: {}",
+                    inst.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pass_manager_qubit_reuse_fits_small_device() {
+        // Eight rounds of a measured qubit pair on a five-qubit device.
+        let mut circuit = Circuit::with_size("rounds", 16, 16);
+        for round in 0..8 {
+            let (a, b) = (QubitId(2 * round), QubitId(2 * round + 1));
+            circuit.h(a).unwrap().cx(a, b).unwrap();
+            circuit.measure(a, ClbitId(2 * round)).unwrap();
+            circuit.measure(b, ClbitId(2 * round + 1)).unwrap();
+        }
+
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_qubit_reuse(true)
+            .with_target(CouplingMap::star(5), BasisGates::iqm())
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        assert!(dag.num_qubits() <= 5);
+        assert_eq!(dag.num_clbits(), 16);
+        let reuse = props.get::<crate::passes::QubitReuseResult>().unwrap();
+        assert_eq!(reuse.qubits_after, 2);
+    }
+
+    #[test]
+    fn test_pass_manager_error_weighted_routing() {
+        // Ring 0-1-2-3-0 whose couplers at qubit 1 are poor; cx(0, 2)
+        // needs one SWAP over qubit 1 or qubit 3.
+        let ring = CouplingMap::from_edge_list(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let mut profile = NoiseProfile::new();
+        profile.coupler_errors = Some(vec![
+            (0, 1, 0.05),
+            (1, 2, 0.05),
+            (2, 3, 0.005),
+            (3, 0, 0.005),
+        ]);
+        let mut circuit = Circuit::with_size("test", 4, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let uses_qubit_1 = |enabled: bool| {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(0)
+                .with_target(ring.clone(), BasisGates::iqm())
+                .with_noise_profile(profile.clone())
+                .with_error_weighted_routing(enabled)
+                .build();
+            assert_eq!(props.get::<ErrorWeights>().is_some(), enabled);
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+            dag.topological_ops()
+                .any(|(_, inst)| inst.qubits.contains(&QubitId(1)))
+        };
+
+        assert!(uses_qubit_1(false));
+        assert!(!uses_qubit_1(true));
+    }
+
+    #[test]
+    fn test_pass_manager_target_patch() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+
+        for level in 0..=3 {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(level)
+                .with_target(CouplingMap::linear(10), BasisGates::iqm())
+                .with_target_patch(&[6, 7, 8, 9])
+                .unwrap()
+                .build();
+
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+
+            for (_, inst) in dag.topological_ops() {
+                for q in &inst.qubits {
+                    assert!(
+                        (6..10).contains(&q.0),
+                        "level {level}: gate on q{} outside patch",
+                        q.0
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pass_manager_target_patch_out_of_range() {
+        let result = PassManagerBuilder::new()
+            .with_target(CouplingMap::linear(5), BasisGates::iqm())
+            .with_target_patch(&[3, 4, 5]);
+        assert!(matches!(result, Err(CompileError::InvalidConfiguration(_))));
+
+        let result = PassManagerBuilder::new().with_target_patch(&[0, 1]);
+        assert!(matches!(result, Err(CompileError::MissingCouplingMap)));
+    }
+
+    #[test]
+    fn test_pass_manager_time_budget() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_target(CouplingMap::linear(3), BasisGates::iqm())
+            .build();
+        let mut dag = circuit.clone().into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+        let timings = props.get::<CompileTimings>().unwrap();
+        assert_eq!(timings.passes.len(), pm.len());
+        assert!(!timings.budget_exhausted());
+
+        // With the budget spent up front, optimization is skipped but the
+        // required passes still produce a routed, translated circuit.
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_target(CouplingMap::linear(3), BasisGates::iqm())
+            .with_time_budget(Duration::ZERO)
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let timings = props.get::<CompileTimings>().unwrap();
+        assert_eq!(timings.budget, Some(Duration::ZERO));
+        let outcome = |name: &str| {
+            timings
+                .passes
+                .iter()
+                .find(|p| p.name == name)
+                .unwrap()
+                .outcome
+        };
+        assert_eq!(outcome("Optimize1qGates"), PassOutcome::SkippedOverBudget);
+        assert_eq!(outcome("SabreRouting"), PassOutcome::Completed);
+        assert!(props.get::<CompileDeadline>().is_none());
+        for (_, inst) in dag.topological_ops() {
+            assert!(["prx", "cz"].contains(&inst.name()), "{}", inst.name());
+        }
+    }
+
+    #[test]
+    fn test_pass_manager_deterministic() {
+        let mut circuit = Circuit::with_size("test", 5, 0);
+        for (a, b) in [(0, 4), (1, 3), (4, 2), (0, 3), (2, 1)] {
+            circuit.h(QubitId(a)).unwrap();
+            circuit.cx(QubitId(a), QubitId(b)).unwrap();
+        }
+
+        let compile = || {
+            let (pm, mut props) = PassManagerBuilder::new()
+                .with_optimization_level(3)
+                .with_target(CouplingMap::linear(5), BasisGates::iqm())
+                .with_time_budget(Duration::ZERO)
+                .with_deterministic(true)
+                .build();
+            let mut dag = circuit.clone().into_dag();
+            pm.run(&mut dag, &mut props).unwrap();
+
+            // The zero budget is ignored rather than cutting optimization
+            // short at a load-dependent point.
+            let timings = props.get::<CompileTimings>().unwrap();
+            assert_eq!(timings.budget, None);
+            assert!(!timings.budget_exhausted());
+            assert_eq!(props.seed, Some(PassManager::DEFAULT_SEED));
+            dag.to_bytes()
+        };
+        assert_eq!(compile(), compile());
+    }
+
+    #[test]
+    fn test_pass_manager_verification() {
+        let mut circuit = Circuit::with_size("test", 4, 0);
+        for (a, b) in [(0, 3), (1, 2), (3, 1), (0, 2)] {
+            circuit.h(QubitId(a)).unwrap();
+            circuit.t(QubitId(b)).unwrap();
+            circuit.cx(QubitId(a), QubitId(b)).unwrap();
+        }
+
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_optimization_level(3)
+            .with_target(CouplingMap::linear(4), BasisGates::iqm())
+            .with_verification(Verification::new())
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let report = props.get::<VerificationReport>().unwrap();
+        assert!(report.checks.iter().any(|c| c.pass == "SabreRouting"));
+        assert!(report.checks.iter().any(|c| c.pass == "Optimize1qGates"));
+        assert_eq!(report.skipped().count(), 0, "{report:?}");
+        assert!(props.get::<Verification>().is_none());
+    }
+
+    #[test]
+    fn test_pass_manager_cancellation() {
+        let token = CancellationToken::new();
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_target(CouplingMap::linear(3), BasisGates::iqm())
+            .with_cancellation(token.clone())
+            .build();
+        assert!(pm.cancellation().is_some());
+
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+        assert!(props.get::<CancellationToken>().is_none());
+
+        token.cancel();
+        let mut dag = Circuit::with_size("test", 3, 0).into_dag();
+        assert!(matches!(
+            pm.run(&mut dag, &mut props),
+            Err(crate::CompileError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_pass_manager_compiles_control_flow() {
+        use arvak_ir::ClassicalCondition;
+
+        let mut then_body = Circuit::with_size("then", 3, 1);
+        then_body.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut else_body = Circuit::with_size("else", 3, 1);
+        else_body.h(QubitId(2)).unwrap();
+
+        let mut circuit = Circuit::with_size("dynamic", 3, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(0)).unwrap();
+        circuit
+            .if_else(
+                ClassicalCondition::on_clbit(ClbitId(0), 1),
+                &then_body,
+                Some(&else_body),
+            )
+            .unwrap();
+        circuit.cx(QubitId(2), QubitId(0)).unwrap();
+
+        let coupling_map = CouplingMap::linear(3);
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_optimization_level(2)
+            .with_target(coupling_map.clone(), BasisGates::iqm())
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let mut blocks = 0;
+        for (_, inst) in dag.topological_ops() {
+            inst.walk(&mut |inst| {
+                if inst.is_control_flow() {
+                    blocks += 1;
+                    return;
+                }
+                if inst.is_gate() {
+                    assert!(["prx", "cz"].contains(&inst.name()), "{}", inst.name());
+                }
+                if inst.qubits.len() == 2 {
+                    let (a, b) = (inst.qubits[0].0, inst.qubits[1].0);
+                    assert!(
+                        coupling_map.is_connected(a, b),
+                        "{} on ({a}, {b})",
+                        inst.name()
+                    );
+                }
+            });
+        }
+        assert_eq!(blocks, 1);
+    }
+
+    #[test]
+    fn test_compiled_qasm_records_layout() {
+        use crate::property::Layout;
+        use arvak_qasm3::{EmitOptions, LayoutPragmas, emit_with_options, parse_layout};
+
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_optimization_level(0)
+            .with_target(CouplingMap::linear(3), BasisGates::universal())
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let initial = props.initial_layout.clone().unwrap();
+        let routed = props.layout.clone().unwrap();
+        let pragmas = LayoutPragmas::new(initial.iter()).with_final_layout(routed.iter());
+        let qasm = emit_with_options(
+            &Circuit::from_dag(dag),
+            &EmitOptions::new().with_layout(pragmas),
+        )
+        .unwrap();
+
+        let parsed = parse_layout(&qasm).unwrap().unwrap();
+        assert!(parsed.routing_swaps > 0, "{qasm}");
+        let read_initial: Layout = parsed.initial.iter().copied().collect();
+        let read_final: Layout = parsed.final_layout.iter().copied().collect();
+        for q in 0..3 {
+            let q = QubitId(q);
+            assert_eq!(read_initial.get_physical(q), initial.get_physical(q));
+            assert_eq!(read_final.get_physical(q), routed.get_physical(q));
+        }
+    }
+
+    #[test]
+    fn test_pass_manager_swap_networks() {
+        let n = 6;
+        let mut circuit = Circuit::with_size("qaoa", n, n);
+        for q in 0..n {
+            circuit.h(QubitId(q)).unwrap();
+        }
+        for a in 0..n {
+            for b in a + 1..n {
+                circuit.rzz(0.5, QubitId(a), QubitId(b)).unwrap();
+            }
+        }
+        for q in 0..n {
+            circuit.rx(0.8, QubitId(q)).unwrap();
+            circuit.measure(QubitId(q), ClbitId(q)).unwrap();
+        }
+
+        let coupling_map = CouplingMap::grid(3, 3);
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_optimization_level(2)
+            .with_target(coupling_map.clone(), BasisGates::iqm())
+            .with_swap_networks(true)
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let plan = props.get::<SwapNetworkPlan>().unwrap();
+        assert_eq!(plan.line.len(), 6);
+        let mut measures = 0;
+        for (_, inst) in dag.topological_ops() {
+            if inst.qubits.len() == 2 {
+                assert!(coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0));
+            }
+            if inst.is_measure() {
+                assert!(plan.line.contains(&inst.qubits[0].0));
+                measures += 1;
+            }
+        }
+        assert_eq!(measures, 6);
+    }
+
+    #[test]
+    fn test_pass_manager_runs_external_passes_at_stages() {
+        use crate::pass::PassKind;
+
+        /// Records the stage it ran at and whether the circuit was laid out
+        /// and within the target basis by then.
+        struct Probe(String);
+
+        #[derive(Default)]
+        struct Probes(Vec<(String, bool, bool)>);
+
+        impl Pass for Probe {
+            fn name(&self) -> &str {
+                &self.0
+            }
+            fn kind(&self) -> PassKind {
+                PassKind::Analysis
+            }
+            fn run(&self, dag: &mut CircuitDag, props: &mut PropertySet) -> CompileResult<()> {
+                let basis = props.basis_gates.as_ref().unwrap();
+                let in_basis = dag
+                    .topological_ops()
+                    .all(|(_, inst)| !inst.is_gate() || basis.contains(inst.name()));
+                let laid_out = props.layout.is_some();
+                if props.get::<Probes>().is_none() {
+                    props.insert(Probes::default());
+                }
+                let probes = props.get_mut::<Probes>().unwrap();
+                probes.0.push((self.0.clone(), laid_out, in_basis));
+                Ok(())
+            }
+        }
+
+        let mut registry = PassRegistry::new();
+        registry.register("probe", "Record the pipeline stage", |options| {
+            let label = options["label"].as_str().unwrap_or_default();
+            Ok(Box::new(Probe(label.to_string())))
+        });
+        let probe = |label: &str| Probe(label.to_string());
+
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_optimization_level(1)
+            .with_target(CouplingMap::linear(3), BasisGates::iqm())
+            .with_pass_at(PipelineStage::PostOptimization, probe("post-optimization"))
+            .with_pass_at(PipelineStage::PreLayout, probe("pre-layout"))
+            .with_pass_at(PipelineStage::PostTranslation, probe("post-translation"))
+            .with_registered_pass(
+                &registry,
+                "probe",
+                PipelineStage::PostRouting,
+                &serde_json::json!({ "label": "post-routing" }),
+            )
+            .unwrap()
+            .build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let probes = &props.get::<Probes>().unwrap().0;
+        let labels: Vec<&str> = probes.iter().map(|(label, _, _)| label.as_str()).collect();
+        let flags: Vec<(bool, bool)> = probes.iter().map(|&(_, l, b)| (l, b)).collect();
+        assert_eq!(
+            labels,
+            [
+                "pre-layout",
+                "post-routing",
+                "post-translation",
+                "post-optimization"
+            ]
+        );
+        assert_eq!(
+            flags,
+            [(false, false), (true, false), (true, true), (true, true)]
+        );
+
+        assert!(
+            PassManagerBuilder::new()
+                .with_registered_pass(
+                    &registry,
+                    "missing",
+                    PipelineStage::PreLayout,
+                    &serde_json::Value::Null
+                )
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,320 @@
+//! Multi-programming: several small circuits on one large device.
+//!
+//! A five-qubit circuit on a 54-qubit device leaves most of the chip idle,
+//! while providers bill per job and per shot. [`MultiProgramming`] places
+//! independent circuits on disjoint patches of the device, compiles each
+//! within its patch, and merges the results into one circuit that runs as a
+//! single job. The classical bits of each circuit follow those of the one
+//! before, so [`MultiProgram::split_bitstring`] cuts every measured
+//! bitstring back into one per circuit; `arvak_hal::multiprogram` does the
+//! same for whole result counts.
+//!
+//! Patches are picked largest circuit first, each the connected region with
+//! the lowest readout error in the target's noise profile (see
+//! [`TargetPatch::best_excluding`]). By default an idle qubit separates
+//! neighbouring patches, so no coupler joins two circuits and crosstalk
+//! between them stays low.
+//!
+//! ```rust
+//! use arvak_compile::{BasisGates, CompileTarget, CouplingMap, MultiProgramming};
+//! use arvak_ir::Circuit;
+//!
+//! let target = CompileTarget::new(CouplingMap::grid(6, 9), BasisGates::iqm());
+//! let circuits: Vec<Circuit> = (0..4).map(|_| Circuit::bell().unwrap()).collect();
+//!
+//! let program = MultiProgramming::new(target).compile(&circuits).unwrap();
+//! assert_eq!(program.programs().len(), 4);
+//! assert_eq!(program.circuit().num_clbits(), 8);
+//!
+//! // Clbit 0 is the rightmost bit; the first circuit owns clbits 0 and 1.
+//! let parts = program.split_bitstring("00000011");
+//! assert_eq!(parts, ["11", "00", "00", "00"]);
+//! ```
+
+use rayon::prelude::*;
+use tracing::debug;
+
+use arvak_ir::{Circuit, ClbitId};
+
+use crate::error::{CompileError, CompileResult};
+use crate::property::TargetPatch;
+use crate::transpile::{CompileTarget, compile};
+
+/// Compiles several independent circuits onto disjoint patches of one
+/// device.
+pub struct MultiProgramming {
+    /// The device shared by all circuits.
+    target: CompileTarget,
+    /// Optimization level for each circuit.
+    optimization_level: u8,
+    /// Keep an idle qubit between neighbouring patches.
+    isolation: bool,
+}
+
+impl MultiProgramming {
+    /// Multi-program `target` at optimization level 2, with isolated
+    /// patches.
+    pub fn new(target: CompileTarget) -> Self {
+        Self {
+            target,
+            optimization_level: 2,
+            isolation: true,
+        }
+    }
+
+    /// Set the optimization level each circuit is compiled at.
+    #[must_use]
+    pub fn with_optimization_level(mut self, level: u8) -> Self {
+        self.optimization_level = level;
+        self
+    }
+
+    /// Keep (the default) or drop the idle qubits between patches.
+    ///
+    /// Without isolation more circuits fit on the device, but gates on
+    /// neighbouring patches may disturb each other.
+    #[must_use]
+    pub fn with_isolation(mut self, enabled: bool) -> Self {
+        self.isolation = enabled;
+        self
+    }
+
+    /// Place, compile and merge `circuits`.
+    ///
+    /// Fails if the device has no room for a circuit's patch, or if a
+    /// circuit does not compile.
+    pub fn compile(&self, circuits: &[Circuit]) -> CompileResult<MultiProgram> {
+        let patches = self.allocate(circuits)?;
+        let level = self.optimization_level;
+        let compiled: Vec<Circuit> = circuits
+            .par_iter()
+            .zip(&patches)
+            .map(|(circuit, patch)| {
+                let builder = self
+                    .target
+                    .builder(level)
+                    .with_target_patch(patch.qubits())?;
+                compile(builder, circuit.clone())
+            })
+            .collect::<CompileResult<_>>()?;
+
+        let num_qubits = self.target.coupling_map.num_qubits();
+        let num_clbits: usize = compiled.iter().map(Circuit::num_clbits).sum();
+        #[allow(clippy::cast_possible_truncation)]
+        let mut circuit = Circuit::with_size("multiprogram", num_qubits, num_clbits as u32);
+        let mut programs = Vec::with_capacity(compiled.len());
+        let mut clbit_offset = 0;
+        for ((source, compiled), patch) in circuits.iter().zip(&compiled).zip(patches) {
+            for (_, inst) in compiled.dag().topological_ops() {
+                if let Some(q) = inst.qubits.iter().find(|q| !patch.contains(q.0)) {
+                    return Err(CompileError::PassFailed {
+                        name: "MultiProgramming".into(),
+                        reason: format!(
+                            "circuit '{}' left its patch on qubit {}",
+                            source.name(),
+                            q.0
+                        ),
+                    });
+                }
+            }
+            let qubit_map: Vec<_> = compiled.qubits().iter().map(|q| q.id).collect();
+            #[allow(clippy::cast_possible_truncation)]
+            let clbit_map: Vec<_> = (clbit_offset..clbit_offset + compiled.num_clbits())
+                .map(|c| ClbitId(c as u32))
+                .collect();
+            circuit
+                .compose(compiled, &qubit_map, &clbit_map)
+                .map_err(CompileError::Ir)?;
+            programs.push(ProgramSlot {
+                name: source.name().to_string(),
+                patch,
+                clbit_offset,
+                num_clbits: compiled.num_clbits(),
+            });
+            clbit_offset += compiled.num_clbits();
+        }
+        debug!(
+            "Multi-programmed {} circuits on {} of {} qubits",
+            programs.len(),
+            programs.iter().map(|p| p.patch.len()).sum::<usize>(),
+            num_qubits
+        );
+
+        Ok(MultiProgram { circuit, programs })
+    }
+
+    /// Choose a patch for every circuit, largest circuit first.
+    fn allocate(&self, circuits: &[Circuit]) -> CompileResult<Vec<TargetPatch>> {
+        let coupling_map = &self.target.coupling_map;
+        let errors = self
+            .target
+            .noise_profile
+            .as_ref()
+            .and_then(|profile| profile.readout_errors.as_deref())
+            .unwrap_or_default();
+
+        let mut order: Vec<usize> = (0..circuits.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(circuits[i].num_qubits()));
+
+        let mut patches = vec![None; circuits.len()];
+        let mut excluded: Vec<u32> = Vec::new();
+        for i in order {
+            let size = circuits[i].num_qubits().max(1);
+            let patch = TargetPatch::best_excluding(coupling_map, errors, size, &excluded)
+                .ok_or_else(|| {
+                    CompileError::InvalidConfiguration(format!(
+                        "no free {size}-qubit patch left for circuit '{}'",
+                        circuits[i].name()
+                    ))
+                })?;
+            excluded.extend_from_slice(patch.qubits());
+            if self.isolation {
+                for &q in patch.qubits() {
+                    excluded.extend(coupling_map.neighbors(q));
+                }
+            }
+            patches[i] = Some(patch);
+        }
+        Ok(patches.into_iter().flatten().collect())
+    }
+}
+
+/// Several circuits merged into one, from [`MultiProgramming::compile`].
+#[derive(Debug, Clone)]
+pub struct MultiProgram {
+    circuit: Circuit,
+    programs: Vec<ProgramSlot>,
+}
+
+/// Where one of the circuits of a [`MultiProgram`] lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramSlot {
+    /// Name of the input circuit.
+    pub name: String,
+    /// Physical qubits the circuit was compiled onto.
+    pub patch: TargetPatch,
+    /// Index of the circuit's first classical bit in the merged circuit.
+    pub clbit_offset: usize,
+    /// Number of classical bits of the circuit.
+    pub num_clbits: usize,
+}
+
+impl MultiProgram {
+    /// The merged circuit to submit.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// One slot per input circuit, in input order.
+    pub fn programs(&self) -> &[ProgramSlot] {
+        &self.programs
+    }
+
+    /// Cut a bitstring of the merged circuit into one per input circuit.
+    ///
+    /// The rightmost bit is classical bit 0, as in HAL counts. Whitespace
+    /// between registers is ignored, and a short bitstring is padded with
+    /// leading zeros.
+    pub fn split_bitstring(&self, bitstring: &str) -> Vec<String> {
+        let bits: Vec<char> = bitstring.chars().filter(|c| !c.is_whitespace()).collect();
+        // Bit `c` counted from the right, zero past the end.
+        let bit = |c: usize| {
+            bits.len()
+                .checked_sub(c + 1)
+                .map_or('0', |position| bits[position])
+        };
+        self.programs
+            .iter()
+            .map(|slot| {
+                (slot.clbit_offset..slot.clbit_offset + slot.num_clbits)
+                    .rev()
+                    .map(&bit)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{BasisGates, CouplingMap};
+    use arvak_ir::QubitId;
+
+    fn ghz(n: u32) -> Circuit {
+        let mut circuit = Circuit::with_size(format!("ghz{n}"), n, n);
+        circuit.h(QubitId(0)).unwrap();
+        for q in 1..n {
+            circuit.cx(QubitId(q - 1), QubitId(q)).unwrap();
+        }
+        for q in 0..n {
+            circuit.measure(QubitId(q), ClbitId(q)).unwrap();
+        }
+        circuit
+    }
+
+    #[test]
+    fn test_multiprogram_disjoint_isolated_patches() {
+        let coupling_map = CouplingMap::linear(12);
+        let target = CompileTarget::new(coupling_map.clone(), BasisGates::iqm());
+        let circuits = [ghz(2), ghz(4), ghz(3)];
+
+        let program = MultiProgramming::new(target).compile(&circuits).unwrap();
+        let slots = program.programs();
+        assert_eq!(slots.len(), 3);
+        assert_eq!(
+            slots.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ["ghz2", "ghz4", "ghz3"]
+        );
+        assert_eq!(
+            slots.iter().map(|s| s.clbit_offset).collect::<Vec<_>>(),
+            [0, 2, 6]
+        );
+        for (i, a) in slots.iter().enumerate() {
+            assert_eq!(a.patch.len(), circuits[i].num_qubits());
+            for b in &slots[i + 1..] {
+                for &p in a.patch.qubits() {
+                    assert!(!b.patch.contains(p));
+                    assert!(coupling_map.neighbors(p).all(|n| !b.patch.contains(n)));
+                }
+            }
+        }
+
+        let circuit = program.circuit();
+        assert_eq!(circuit.num_qubits(), 12);
+        assert_eq!(circuit.num_clbits(), 9);
+        let measures = circuit
+            .dag()
+            .topological_ops()
+            .filter(|(_, inst)| inst.is_measure())
+            .count();
+        assert_eq!(measures, 9);
+    }
+
+    #[test]
+    fn test_multiprogram_split_bitstring() {
+        let target = CompileTarget::new(CouplingMap::linear(12), BasisGates::iqm());
+        let program = MultiProgramming::new(target)
+            .compile(&[ghz(2), ghz(3)])
+            .unwrap();
+        // Clbits 0-1 belong to the first circuit, 2-4 to the second.
+        assert_eq!(program.split_bitstring("110 01"), ["01", "110"]);
+        assert_eq!(program.split_bitstring("11"), ["11", "000"]);
+    }
+
+    #[test]
+    fn test_multiprogram_out_of_room() {
+        let target = CompileTarget::new(CouplingMap::linear(5), BasisGates::iqm());
+        let circuits = [ghz(3), ghz(2)];
+        assert!(matches!(
+            MultiProgramming::new(target.clone()).compile(&circuits),
+            Err(CompileError::InvalidConfiguration(_))
+        ));
+        // Without the idle qubit between them, both fit.
+        let program = MultiProgramming::new(target)
+            .with_isolation(false)
+            .compile(&circuits)
+            .unwrap();
+        assert_eq!(program.circuit().num_clbits(), 5);
+    }
+}
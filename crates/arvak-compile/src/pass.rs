@@ -40,6 +40,27 @@ pub trait Pass: Send + Sync {
     fn should_run(&self, _dag: &CircuitDag, _properties: &PropertySet) -> bool {
         true
     }
+
+    /// Whether this pass only improves the circuit.
+    ///
+    /// The pass manager skips optimization passes once its time budget is
+    /// spent, and they should stop early when
+    /// [`PropertySet::budget_exhausted`] turns `true`, keeping the
+    /// circuit they have so far. Passes the output depends on (layout,
+    /// routing, translation) keep the default `false` and always run.
+    fn is_optimization(&self) -> bool {
+        false
+    }
+
+    /// Whether this pass maps the circuit from logical qubits onto the
+    /// physical qubits chosen by [`PropertySet::layout`].
+    ///
+    /// Verification mode compares the output of such a pass with its input
+    /// through the layout before and after it; see
+    /// [`equivalence`](crate::equivalence).
+    fn is_routing(&self) -> bool {
+        false
+    }
 }
 
 /// Marker trait for analysis passes.
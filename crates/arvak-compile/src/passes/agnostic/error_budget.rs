@@ -0,0 +1,253 @@
+//! Error budget analysis: which operations dominate predicted infidelity.
+//!
+//! Reads a [`NoiseProfile`] from the [`PropertySet`] and groups the
+//! circuit's gates and measurements by operation and qubits. Each group's
+//! contribution is its per-operation error rate times its number of
+//! occurrences (readout error for measurements). The ranked table is stored
+//! as an [`ErrorBudgetReport`] in the property set.
+//!
+//! Readout errors are looked up by qubit index, so run the pass after layout
+//! and routing, when the DAG's qubits are physical qubits.
+
+use std::collections::BTreeMap;
+
+use arvak_ir::CircuitDag;
+use arvak_ir::instruction::InstructionKind;
+use arvak_ir::noise::NoiseProfile;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CompileResult;
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+
+/// Operation name used for readout entries.
+const MEASURE: &str = "measure";
+
+/// One row of the error budget: an operation on specific qubits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBudgetEntry {
+    /// Gate name, or `measure` for readout.
+    pub operation: String,
+    /// Qubits the operation acts on, in operand order.
+    pub qubits: Vec<u32>,
+    /// Number of occurrences in the circuit.
+    pub count: usize,
+    /// Error probability of a single occurrence.
+    pub error_rate: f64,
+    /// `count × error_rate`.
+    pub contribution: f64,
+    /// Share of the total contribution (0.0 to 1.0).
+    pub fraction: f64,
+}
+
+/// Outcome of [`ErrorBudget`], stored in the property set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBudgetReport {
+    /// Entries ranked by contribution, largest first.
+    pub entries: Vec<ErrorBudgetEntry>,
+    /// Sum of all contributions.
+    pub total_error: f64,
+    /// Predicted success probability: the product of `(1 - error_rate)`
+    /// over every occurrence.
+    pub estimated_fidelity: f64,
+    /// Gates the noise profile has no error rate for, sorted. They are not
+    /// part of the budget.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unknown_gates: Vec<String>,
+}
+
+impl ErrorBudgetReport {
+    /// The `n` entries with the largest contribution.
+    pub fn top(&self, n: usize) -> &[ErrorBudgetEntry] {
+        &self.entries[..n.min(self.entries.len())]
+    }
+}
+
+/// Rank gates and measurements by their contribution to the predicted
+/// infidelity.
+///
+/// Analysis only; the circuit is not modified. Operations with a zero error
+/// rate are left out of the table. Does nothing without a non-empty noise
+/// profile.
+pub struct ErrorBudget;
+
+impl Pass for ErrorBudget {
+    fn name(&self) -> &'static str {
+        "ErrorBudget"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Analysis
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties
+            .get::<NoiseProfile>()
+            .is_some_and(|p| !p.is_empty())
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let Some(profile) = properties.get::<NoiseProfile>() else {
+            return Ok(());
+        };
+        let report = error_budget(dag, profile);
+        properties.insert(report);
+        Ok(())
+    }
+}
+
+/// Build the error budget of `dag` under `profile`.
+fn error_budget(dag: &CircuitDag, profile: &NoiseProfile) -> ErrorBudgetReport {
+    // (operation, qubits) -> (count, error rate)
+    let mut groups: BTreeMap<(String, Vec<u32>), (usize, f64)> = BTreeMap::new();
+    let mut unknown_gates = Vec::new();
+
+    let mut record = |operation: &str, qubits: Vec<u32>, rate: f64| {
+        if rate > 0.0 {
+            groups
+                .entry((operation.to_string(), qubits))
+                .or_insert((0, rate))
+                .0 += 1;
+        }
+    };
+
+    for (_, inst) in dag.topological_ops() {
+        match &inst.kind {
+            InstructionKind::Gate(gate) => {
+                let name = gate.name();
+                match profile.gate_error(name) {
+                    Some(rate) => record(name, inst.qubits.iter().map(|q| q.0).collect(), rate),
+                    None => unknown_gates.push(name.to_string()),
+                }
+            }
+            InstructionKind::Measure => {
+                for qubit in &inst.qubits {
+                    if let Some(rate) = profile.qubit_readout_error(qubit.0 as usize) {
+                        record(MEASURE, vec![qubit.0], rate);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    unknown_gates.sort_unstable();
+    unknown_gates.dedup();
+
+    let mut estimated_fidelity = 1.0;
+    let mut entries: Vec<ErrorBudgetEntry> = groups
+        .into_iter()
+        .map(|((operation, qubits), (count, error_rate))| {
+            estimated_fidelity *= (1.0 - error_rate).powi(i32::try_from(count).unwrap_or(i32::MAX));
+            ErrorBudgetEntry {
+                operation,
+                qubits,
+                count,
+                error_rate,
+                contribution: count as f64 * error_rate,
+                fraction: 0.0,
+            }
+        })
+        .collect();
+
+    let total_error: f64 = entries.iter().map(|e| e.contribution).sum();
+    for entry in &mut entries {
+        entry.fraction = entry.contribution / total_error;
+    }
+    // Stable sort keeps ties in (operation, qubits) order.
+    entries.sort_by(|a, b| b.contribution.total_cmp(&a.contribution));
+
+    ErrorBudgetReport {
+        entries,
+        total_error,
+        estimated_fidelity,
+        unknown_gates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::{Circuit, ClbitId, QubitId};
+
+    fn iqm_profile() -> NoiseProfile {
+        let mut profile = NoiseProfile::new();
+        profile.gate_errors.insert("prx".into(), 0.001);
+        profile.gate_errors.insert("cz".into(), 0.01);
+        profile.readout_errors = Some(vec![0.02, 0.03, 0.05]);
+        profile
+    }
+
+    fn run(circuit: Circuit, profile: Option<NoiseProfile>) -> Option<ErrorBudgetReport> {
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        if let Some(profile) = profile {
+            props.insert(profile);
+        }
+        if ErrorBudget.should_run(&dag, &props) {
+            ErrorBudget.run(&mut dag, &mut props).unwrap();
+        }
+        props.get::<ErrorBudgetReport>().cloned()
+    }
+
+    #[test]
+    fn test_no_profile() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cz(QubitId(0), QubitId(1)).unwrap();
+        assert!(run(circuit.clone(), None).is_none());
+        assert!(run(circuit, Some(NoiseProfile::new())).is_none());
+    }
+
+    #[test]
+    fn test_ranked_budget() {
+        let mut circuit = Circuit::with_size("test", 3, 2);
+        circuit.prx(0.5, 0.0, QubitId(0)).unwrap();
+        for _ in 0..3 {
+            circuit.cz(QubitId(0), QubitId(1)).unwrap();
+        }
+        circuit.cz(QubitId(1), QubitId(2)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.measure(QubitId(2), ClbitId(1)).unwrap();
+
+        let report = run(circuit, Some(iqm_profile())).unwrap();
+
+        let rows: Vec<(&str, &[u32], usize)> = report
+            .entries
+            .iter()
+            .map(|e| (e.operation.as_str(), e.qubits.as_slice(), e.count))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("measure", &[2][..], 1),
+                ("cz", &[0, 1][..], 3),
+                ("measure", &[0][..], 1),
+                ("cz", &[1, 2][..], 1),
+                ("prx", &[0][..], 1),
+            ]
+        );
+        assert!((report.entries[1].contribution - 0.03).abs() < 1e-12);
+        assert!((report.total_error - 0.111).abs() < 1e-12);
+        let fraction: f64 = report.entries.iter().map(|e| e.fraction).sum();
+        assert!((fraction - 1.0).abs() < 1e-12);
+
+        let expected = 0.999 * 0.99_f64.powi(4) * 0.98 * 0.95;
+        assert!((report.estimated_fidelity - expected).abs() < 1e-12);
+        assert_eq!(report.top(2).len(), 2);
+        assert_eq!(report.top(10).len(), 5);
+        assert!(report.unknown_gates.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_gates_reported() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cz(QubitId(0), QubitId(1)).unwrap();
+        circuit.h(QubitId(1)).unwrap();
+
+        let report = run(circuit, Some(iqm_profile())).unwrap();
+
+        assert_eq!(report.unknown_gates, ["h"]);
+        assert_eq!(report.entries.len(), 1);
+        assert!((report.estimated_fidelity - 0.99).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,285 @@
+//! Defer final measurements across optimization.
+//!
+//! The optimization passes treat measurements as barriers: gates are never
+//! merged, cancelled or resynthesized across them, and a multi-qubit
+//! measurement ties together wires that are otherwise independent. Nearly
+//! every circuit ends in a layer of measurements, so
+//! [`RemoveFinalMeasurements`] takes out the measurements that nothing
+//! follows and records them in the [`PropertySet`] as
+//! [`FinalMeasurements`], and [`RestoreFinalMeasurements`] appends them
+//! again once optimization is done:
+//!
+//! ```rust
+//! use arvak_compile::PassManager;
+//! use arvak_compile::passes::{Optimize1qGates, RemoveFinalMeasurements, RestoreFinalMeasurements};
+//! use arvak_compile::PropertySet;
+//! use arvak_ir::{Circuit, ClbitId, QubitId};
+//!
+//! let mut circuit = Circuit::with_size("bell", 2, 2);
+//! circuit.h(QubitId(0)).unwrap();
+//! circuit.cx(QubitId(0), QubitId(1)).unwrap();
+//! circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+//! circuit.measure(QubitId(1), ClbitId(1)).unwrap();
+//!
+//! let mut pm = PassManager::new();
+//! pm.add_pass(RemoveFinalMeasurements);
+//! pm.add_pass(Optimize1qGates::new());
+//! pm.add_pass(RestoreFinalMeasurements);
+//!
+//! let mut dag = circuit.into_dag();
+//! pm.run(&mut dag, &mut PropertySet::new()).unwrap();
+//! assert_eq!(dag.topological_ops().filter(|(_, inst)| inst.is_measure()).count(), 2);
+//! ```
+//!
+//! Run the removal either before layout or after routing. Measurements
+//! removed before layout are restored on the physical qubits the final
+//! layout places their logical qubits on; measurements removed after
+//! routing are restored on the qubits they were taken from.
+
+use tracing::debug;
+
+use arvak_ir::{CircuitDag, Instruction, QubitId};
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+
+/// Measurements taken out by [`RemoveFinalMeasurements`], in circuit order.
+#[derive(Debug, Clone, Default)]
+pub struct FinalMeasurements {
+    /// The removed measurement instructions.
+    pub measurements: Vec<Instruction>,
+    /// Whether a layout was assigned when they were removed. If not, their
+    /// qubits are logical and are mapped through the final layout on
+    /// restore.
+    pub had_layout: bool,
+}
+
+/// Remove measurements that are the last operation on all of their wires.
+///
+/// A measurement whose qubit is gated, reset or measured again later, or
+/// whose classical bit is read or overwritten later, stays in place.
+pub struct RemoveFinalMeasurements;
+
+impl Pass for RemoveFinalMeasurements {
+    fn name(&self) -> &'static str {
+        "RemoveFinalMeasurements"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let graph = dag.graph();
+        let final_nodes: Vec<_> = dag
+            .topological_ops()
+            .filter(|(node, inst)| {
+                inst.is_measure()
+                    && graph
+                        .neighbors_directed(*node, petgraph::Direction::Outgoing)
+                        .all(|next| graph[next].is_output())
+            })
+            .map(|(node, _)| node)
+            .collect();
+
+        if final_nodes.is_empty() {
+            return Ok(());
+        }
+
+        // `remove_op` moves the last node into the freed slot, so remove in
+        // descending index order to keep the remaining indices valid.
+        let mut by_index = final_nodes.clone();
+        by_index.sort_unstable_by(|a, b| b.cmp(a));
+        let mut removed = Vec::with_capacity(by_index.len());
+        for node in by_index {
+            removed.push((node, dag.remove_op(node).map_err(CompileError::Ir)?));
+        }
+        let mut measurements = Vec::with_capacity(removed.len());
+        for node in final_nodes {
+            if let Some(position) = removed.iter().position(|(n, _)| *n == node) {
+                measurements.push(removed.swap_remove(position).1);
+            }
+        }
+
+        debug!("Deferred {} final measurement(s)", measurements.len());
+        let previous = properties.remove::<FinalMeasurements>();
+        let mut deferred = FinalMeasurements {
+            measurements,
+            had_layout: properties.layout.is_some(),
+        };
+        // Measurements deferred by an earlier run come after the ones
+        // removed now.
+        if let Some(previous) = previous {
+            deferred.measurements.extend(previous.measurements);
+        }
+        properties.insert(deferred);
+        Ok(())
+    }
+}
+
+/// Append the measurements removed by [`RemoveFinalMeasurements`].
+///
+/// Does nothing if none were removed.
+pub struct RestoreFinalMeasurements;
+
+impl Pass for RestoreFinalMeasurements {
+    fn name(&self) -> &'static str {
+        "RestoreFinalMeasurements"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let Some(deferred) = properties.remove::<FinalMeasurements>() else {
+            return Ok(());
+        };
+
+        let layout = if deferred.had_layout {
+            None
+        } else {
+            properties.layout.as_ref()
+        };
+
+        for mut measurement in deferred.measurements {
+            if let Some(layout) = layout {
+                for qubit in &mut measurement.qubits {
+                    let physical =
+                        layout
+                            .get_physical(*qubit)
+                            .ok_or_else(|| CompileError::PassFailed {
+                                name: self.name().into(),
+                                reason: format!("no physical qubit for measured qubit {}", qubit.0),
+                            })?;
+                    *qubit = QubitId(physical);
+                }
+            }
+            dag.apply(measurement).map_err(CompileError::Ir)?;
+        }
+        Ok(())
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.get::<FinalMeasurements>().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::{CancelCX, Optimize1qGates};
+    use crate::property::Layout;
+    use arvak_ir::{Circuit, ClbitId};
+
+    fn measurements(dag: &CircuitDag) -> Vec<(Vec<QubitId>, Vec<ClbitId>)> {
+        dag.topological_ops()
+            .filter(|(_, inst)| inst.is_measure())
+            .map(|(_, inst)| (inst.qubits.clone(), inst.clbits.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_removes_only_final_measurements() {
+        let mut circuit = Circuit::with_size("mid", 2, 3);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.x(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(1)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+
+        RemoveFinalMeasurements.run(&mut dag, &mut props).unwrap();
+
+        // The mid-circuit measurement stays.
+        assert_eq!(
+            measurements(&dag),
+            vec![(vec![QubitId(0)], vec![ClbitId(0)])]
+        );
+        let deferred = props.get::<FinalMeasurements>().unwrap();
+        assert_eq!(deferred.measurements.len(), 2);
+        assert!(!deferred.had_layout);
+
+        RestoreFinalMeasurements.run(&mut dag, &mut props).unwrap();
+        assert_eq!(measurements(&dag).len(), 3);
+        assert!(props.get::<FinalMeasurements>().is_none());
+        dag.verify_integrity().unwrap();
+    }
+
+    #[test]
+    fn test_optimize_between_remove_and_restore() {
+        let mut circuit = Circuit::with_size("tail", 2, 2);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure_all().unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+
+        RemoveFinalMeasurements.run(&mut dag, &mut props).unwrap();
+        CancelCX.run(&mut dag, &mut props).unwrap();
+        Optimize1qGates::new().run(&mut dag, &mut props).unwrap();
+        RestoreFinalMeasurements.run(&mut dag, &mut props).unwrap();
+
+        let ops: Vec<_> = dag
+            .topological_ops()
+            .map(|(_, i)| i.name().to_string())
+            .collect();
+        assert_eq!(ops, vec!["measure"]);
+        assert_eq!(
+            measurements(&dag),
+            vec![(vec![QubitId(0), QubitId(1)], vec![ClbitId(0), ClbitId(1)])]
+        );
+    }
+
+    #[test]
+    fn test_restores_over_final_layout() {
+        let mut circuit = Circuit::with_size("layout", 2, 2);
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        RemoveFinalMeasurements.run(&mut dag, &mut props).unwrap();
+
+        // Routing placed logical 0 on physical 1 and the reverse.
+        let mut layout = Layout::new();
+        layout.add(QubitId(0), 1);
+        layout.add(QubitId(1), 0);
+        props.layout = Some(layout);
+        RestoreFinalMeasurements.run(&mut dag, &mut props).unwrap();
+
+        let mut restored = measurements(&dag);
+        restored.sort_by_key(|(qubits, _)| qubits[0].0);
+        assert_eq!(
+            restored,
+            vec![
+                (vec![QubitId(0)], vec![ClbitId(1)]),
+                (vec![QubitId(1)], vec![ClbitId(0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keeps_measurements_read_later() {
+        let mut circuit = Circuit::with_size("feedback", 2, 1);
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+
+        RemoveFinalMeasurements.run(&mut dag, &mut props).unwrap();
+
+        // The first measurement's bit is overwritten by the second.
+        assert_eq!(
+            measurements(&dag),
+            vec![(vec![QubitId(0)], vec![ClbitId(0)])]
+        );
+        assert_eq!(
+            props.get::<FinalMeasurements>().unwrap().measurements.len(),
+            1
+        );
+    }
+}
@@ -4,16 +4,25 @@
 //! target-specific properties (coupling map, basis gates). They are safe
 //! to run on any circuit regardless of the target hardware.
 
+pub mod error_budget;
+pub mod final_measurements;
 pub mod noise_injection;
 pub mod optimization;
+pub mod qubit_reuse;
 pub mod unroll_3q;
 pub mod verification;
 pub mod verify_compilation;
 
+pub use error_budget::{ErrorBudget, ErrorBudgetEntry, ErrorBudgetReport};
+pub use final_measurements::{
+    FinalMeasurements, RemoveFinalMeasurements, RestoreFinalMeasurements,
+};
 pub use noise_injection::NoiseInjectionPass;
 pub use optimization::{
     CancelCX, CommutativeCancellation, ConsolidateBlocks, OneQubitBasis, Optimize1qGates,
+    PhasePolynomialOptimization, TemplateOptimization,
 };
+pub use qubit_reuse::{QubitReuse, QubitReuseResult};
 pub use unroll_3q::Unroll3q;
 pub use verification::{MeasurementBarrierVerification, VerificationResult};
 pub use verify_compilation::VerifyCompilation;
@@ -8,8 +8,9 @@ use arvak_ir::parameter::ParameterExpression;
 use arvak_ir::qubit::QubitId;
 use petgraph::Direction;
 use petgraph::visit::EdgeRef;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
+use crate::commutation::instructions_commute;
 use crate::error::{CompileError, CompileResult};
 use crate::pass::{Pass, PassKind};
 use crate::property::PropertySet;
@@ -17,6 +18,9 @@ use crate::unitary::Unitary2x2;
 
 use super::EPSILON;
 
+/// Maximum number of gates a rotation is commuted past to find its partner.
+const MAX_COMMUTATION_LOOKAHEAD: usize = 32;
+
 /// CX cancellation pass.
 ///
 /// Cancels pairs of adjacent CX gates on the same qubits.
@@ -39,7 +43,8 @@ impl CancelCX {
         let graph = dag.graph();
 
         for (node_idx, inst) in dag.topological_ops() {
-            if processed.contains(&node_idx) {
+            // Conditioned gates may not execute, so they never cancel.
+            if processed.contains(&node_idx) || inst.is_conditional() {
                 continue;
             }
 
@@ -67,6 +72,7 @@ impl CancelCX {
                         if let InstructionKind::Gate(succ_gate) = &succ_inst.kind {
                             if let GateKind::Standard(StandardGate::CX) = &succ_gate.kind {
                                 if succ_inst.qubits.len() == 2
+                                    && !succ_inst.is_conditional()
                                     && succ_inst.qubits[0] == control
                                     && succ_inst.qubits[1] == target
                                 {
@@ -139,11 +145,18 @@ impl Pass for CancelCX {
         PassKind::Transformation
     }
 
-    fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
+    fn is_optimization(&self) -> bool {
+        true
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
         // Keep cancelling until no more pairs found.
         // Bound iterations to avoid pathological cases.
         const MAX_ITERATIONS: usize = 100;
         for _ in 0..MAX_ITERATIONS {
+            if properties.budget_exhausted() {
+                break;
+            }
             let pairs = self.find_cancellable_pairs(dag);
             if pairs.is_empty() {
                 break;
@@ -171,181 +184,359 @@ impl Pass for CancelCX {
 
 /// Commutative cancellation pass.
 ///
-/// Uses gate commutation rules to move gates past each other and enable
-/// additional cancellations.
+/// Cancels or merges pairs of gates on the same qubits that are separated
+/// only by gates they commute with, as decided by [`crate::commutation`]:
 ///
-/// Commutation rules used:
-/// - CZ commutes with RZ on either qubit
-/// - CX commutes with RZ on control, RX on target
-/// - Diagonal gates commute with each other
-/// - Same-type rotation gates can be merged (RZ(a) · RZ(b) = RZ(a+b))
+/// - self-inverse gates cancel (`H·H`, `CX·CX`, `CZ·CZ`, `CCX·CCX`, ...)
+/// - inverse pairs cancel (`S·S†`, `T·T†`, `SX·SX†`)
+/// - phase gates combine (`S·S = Z`, `T·T = S`)
+/// - rotations about the same axis add their angles (`RZ(a)·RZ(b) =
+///   RZ(a+b)`, likewise RX, RY, P, PRX with equal phase, the controlled
+///   rotations and RXX/RYY/RZZ)
+///
+/// Afterwards, gates are moved to the earliest layer their commutation
+/// relations allow, which shortens the circuit when a gate commutes with a
+/// long chain in front of it. The reordering is kept only if it reduces
+/// the depth.
 pub struct CommutativeCancellation;
 
+/// Result of combining two gates.
+enum Merged {
+    /// The pair is the identity.
+    Cancel,
+    /// The pair equals a single gate.
+    Gate(StandardGate),
+}
+
 impl CommutativeCancellation {
     /// Create a new commutative cancellation pass.
     pub fn new() -> Self {
         Self
     }
 
-    /// Check if two gates commute.
-    ///
-    /// TODO: This function is reserved for future commutative analysis where gates
-    /// can be reordered to enable additional cancellations. Currently unused.
-    #[allow(dead_code, clippy::match_same_arms)]
-    fn gates_commute(
-        gate1: &StandardGate,
-        qubits1: &[QubitId],
-        gate2: &StandardGate,
-        qubits2: &[QubitId],
-    ) -> bool {
-        // Gates on disjoint qubits always commute
-        let shared: Vec<_> = qubits1.iter().filter(|q| qubits2.contains(q)).collect();
-        if shared.is_empty() {
-            return true;
+    /// Combine `first` followed by `second`, both unconditioned standard
+    /// gates. `None` if the pair does not simplify.
+    fn merge(first: &Instruction, second: &Instruction) -> Option<Merged> {
+        let (a, b) = (Self::standard(first)?, Self::standard(second)?);
+        let same_qubits = first.qubits == second.qubits;
+        let symmetric = Self::is_symmetric(a)
+            && first.qubits.len() == 2
+            && first.qubits.iter().rev().eq(&second.qubits);
+        if !same_qubits && !symmetric {
+            return None;
+        }
+        if symmetric && !same_qubits && std::mem::discriminant(a) != std::mem::discriminant(b) {
+            return None;
         }
 
-        // Check specific commutation rules
-        match (gate1, gate2) {
-            // Diagonal gates commute with each other
-            (g1, g2) if Self::is_diagonal(g1) && Self::is_diagonal(g2) => true,
-
-            // CZ commutes with Rz on either qubit
-            (StandardGate::CZ, StandardGate::Rz(_)) | (StandardGate::Rz(_), StandardGate::CZ) => {
-                true
+        use StandardGate as G;
+        let angle =
+            |p: &ParameterExpression, q: &ParameterExpression| Some(p.as_f64()? + q.as_f64()?);
+        match (a, b) {
+            (G::H, G::H)
+            | (G::X, G::X)
+            | (G::Y, G::Y)
+            | (G::Z, G::Z)
+            | (G::CX, G::CX)
+            | (G::CY, G::CY)
+            | (G::CZ, G::CZ)
+            | (G::CH, G::CH)
+            | (G::Swap, G::Swap)
+            | (G::CCX, G::CCX)
+            | (G::CSwap, G::CSwap)
+            | (G::ECR, G::ECR)
+            | (G::S, G::Sdg)
+            | (G::Sdg, G::S)
+            | (G::T, G::Tdg)
+            | (G::Tdg, G::T)
+            | (G::SX, G::SXdg)
+            | (G::SXdg, G::SX) => Some(Merged::Cancel),
+            (G::MCX(n), G::MCX(m)) | (G::MCZ(n), G::MCZ(m)) if n == m => Some(Merged::Cancel),
+            (G::S, G::S) | (G::Sdg, G::Sdg) => Some(Merged::Gate(G::Z)),
+            (G::T, G::T) => Some(Merged::Gate(G::S)),
+            (G::Tdg, G::Tdg) => Some(Merged::Gate(G::Sdg)),
+            // Period 2π up to global phase.
+            (G::Rx(p), G::Rx(q)) => Self::rotation(angle(p, q)?, 2.0, G::Rx),
+            (G::Ry(p), G::Ry(q)) => Self::rotation(angle(p, q)?, 2.0, G::Ry),
+            (G::Rz(p), G::Rz(q)) => Self::rotation(angle(p, q)?, 2.0, G::Rz),
+            (G::P(p), G::P(q)) => Self::rotation(angle(p, q)?, 2.0, G::P),
+            (G::CP(p), G::CP(q)) => Self::rotation(angle(p, q)?, 2.0, G::CP),
+            (G::RXX(p), G::RXX(q)) => Self::rotation(angle(p, q)?, 2.0, G::RXX),
+            (G::RYY(p), G::RYY(q)) => Self::rotation(angle(p, q)?, 2.0, G::RYY),
+            (G::RZZ(p), G::RZZ(q)) => Self::rotation(angle(p, q)?, 2.0, G::RZZ),
+            (G::MCP(n, p), G::MCP(m, q)) if n == m => {
+                let n = *n;
+                Self::rotation(angle(p, q)?, 2.0, |sum| G::MCP(n, sum))
             }
-            (StandardGate::CZ, StandardGate::Z) | (StandardGate::Z, StandardGate::CZ) => true,
-            (StandardGate::CZ, StandardGate::S) | (StandardGate::S, StandardGate::CZ) => true,
-            (StandardGate::CZ, StandardGate::T) | (StandardGate::T, StandardGate::CZ) => true,
-
-            // CX commutes with Rz on control
-            (StandardGate::CX, StandardGate::Rz(_)) | (StandardGate::Rz(_), StandardGate::CX) => {
-                // Only if Rz is on the control qubit of CX
-                // This requires more context - skip for now
-                false
+            // A controlled rotation by 2π is a Z on the control, not a
+            // global phase: the period is 4π.
+            (G::CRx(p), G::CRx(q)) => Self::rotation(angle(p, q)?, 4.0, G::CRx),
+            (G::CRy(p), G::CRy(q)) => Self::rotation(angle(p, q)?, 4.0, G::CRy),
+            (G::CRz(p), G::CRz(q)) => Self::rotation(angle(p, q)?, 4.0, G::CRz),
+            (G::PRX(t1, phi1), G::PRX(t2, phi2)) => {
+                let (phi1, phi2) = (phi1.as_f64()?, phi2.as_f64()?);
+                if Unitary2x2::normalize_angle(phi1 - phi2).abs() >= EPSILON {
+                    return None;
+                }
+                let phi = ParameterExpression::constant(phi1);
+                Self::rotation(angle(t1, t2)?, 2.0, |theta| G::PRX(theta, phi))
             }
+            _ => None,
+        }
+    }
 
-            _ => false,
+    /// A rotation by `sum`, reduced modulo `period · π`; cancels at zero.
+    fn rotation(
+        sum: f64,
+        period: f64,
+        make: impl FnOnce(ParameterExpression) -> StandardGate,
+    ) -> Option<Merged> {
+        let half = period / 2.0;
+        // Reduce into [-half·π, half·π].
+        let reduced = half * Unitary2x2::normalize_angle(sum / half);
+        if reduced.abs() < EPSILON {
+            Some(Merged::Cancel)
+        } else {
+            Some(Merged::Gate(make(ParameterExpression::constant(reduced))))
         }
     }
 
-    /// Check if a gate is diagonal (only affects phases, not populations).
-    ///
-    /// Used by `gates_commute` for determining commutation relationships.
-    /// TODO: Reserved for future commutative analysis. Currently unused.
-    #[allow(dead_code)]
-    fn is_diagonal(gate: &StandardGate) -> bool {
+    /// Two-qubit gates whose qubits can be exchanged.
+    fn is_symmetric(gate: &StandardGate) -> bool {
         matches!(
             gate,
-            StandardGate::I
-                | StandardGate::Z
-                | StandardGate::S
-                | StandardGate::Sdg
-                | StandardGate::T
-                | StandardGate::Tdg
-                | StandardGate::Rz(_)
-                | StandardGate::P(_)
-                | StandardGate::CZ
+            StandardGate::CZ
+                | StandardGate::Swap
+                | StandardGate::CP(_)
+                | StandardGate::RXX(_)
+                | StandardGate::RYY(_)
+                | StandardGate::RZZ(_)
         )
     }
 
-    /// Merge two same-type rotation gates.
-    fn merge_rotations(gate1: &StandardGate, gate2: &StandardGate) -> Option<StandardGate> {
-        match (gate1, gate2) {
-            (StandardGate::Rz(p1), StandardGate::Rz(p2)) => {
-                let a1 = p1.as_f64()?;
-                let a2 = p2.as_f64()?;
-                let sum = Unitary2x2::normalize_angle(a1 + a2);
-                if sum.abs() < EPSILON {
-                    None // Cancels to identity
-                } else {
-                    Some(StandardGate::Rz(ParameterExpression::constant(sum)))
-                }
-            }
-            (StandardGate::Rx(p1), StandardGate::Rx(p2)) => {
-                let a1 = p1.as_f64()?;
-                let a2 = p2.as_f64()?;
-                let sum = Unitary2x2::normalize_angle(a1 + a2);
-                if sum.abs() < EPSILON {
-                    None
-                } else {
-                    Some(StandardGate::Rx(ParameterExpression::constant(sum)))
-                }
-            }
-            (StandardGate::Ry(p1), StandardGate::Ry(p2)) => {
-                let a1 = p1.as_f64()?;
-                let a2 = p2.as_f64()?;
-                let sum = Unitary2x2::normalize_angle(a1 + a2);
-                if sum.abs() < EPSILON {
-                    None
-                } else {
-                    Some(StandardGate::Ry(ParameterExpression::constant(sum)))
-                }
-            }
+    /// The standard gate of an unconditioned gate instruction.
+    fn standard(inst: &Instruction) -> Option<&StandardGate> {
+        match &inst.kind {
+            InstructionKind::Gate(gate) if gate.condition.is_none() => match &gate.kind {
+                GateKind::Standard(std_gate) => Some(std_gate),
+                _ => None,
+            },
             _ => None,
         }
     }
 
-    /// Find mergeable rotation pairs.
-    /// Returns (node1, node2, Option<`merged_gate`>) where None means both gates cancel.
-    #[allow(clippy::similar_names, clippy::unused_self)]
-    fn find_mergeable_rotations(
+    /// Find pairs of gates that cancel or merge.
+    /// Returns (kept, removed, merged) where the merged gate replaces the
+    /// kept node, and `None` means both gates cancel.
+    #[allow(clippy::unused_self)]
+    fn find_mergeable_pairs(
         &self,
         dag: &CircuitDag,
     ) -> Vec<(NodeIndex, NodeIndex, Option<StandardGate>)> {
+        let position: FxHashMap<NodeIndex, usize> = dag
+            .topological_ops()
+            .enumerate()
+            .map(|(pos, (idx, _))| (idx, pos))
+            .collect();
         let mut merges = Vec::new();
         let mut processed: FxHashSet<NodeIndex> = FxHashSet::default();
 
         for (node_idx, inst) in dag.topological_ops() {
-            if processed.contains(&node_idx) {
+            if processed.contains(&node_idx) || Self::standard(inst).is_none() {
                 continue;
             }
 
-            if let InstructionKind::Gate(gate) = &inst.kind {
-                if let GateKind::Standard(std_gate) = &gate.kind {
-                    // Only process rotation gates
-                    if !matches!(
-                        std_gate,
-                        StandardGate::Rx(_) | StandardGate::Ry(_) | StandardGate::Rz(_)
-                    ) {
-                        continue;
+            // Walk along the first qubit past gates this one commutes with.
+            let wire = WireId::Qubit(inst.qubits[0]);
+            let mut current = node_idx;
+            let mut steps = 0;
+            while let Some((succ_node, succ_inst)) = Self::next_op(dag, current, wire) {
+                if processed.contains(&succ_node) {
+                    break;
+                }
+                if let Some(merged) = Self::merge(inst, succ_inst) {
+                    if let Some(keep_first) =
+                        Self::can_bring_together(dag, &position, node_idx, succ_node)
+                    {
+                        let merged = match merged {
+                            Merged::Cancel => None,
+                            Merged::Gate(gate) => Some(gate),
+                        };
+                        // The merged gate replaces the gate that stays put.
+                        let (keep, remove) = if keep_first {
+                            (node_idx, succ_node)
+                        } else {
+                            (succ_node, node_idx)
+                        };
+                        merges.push((keep, remove, merged));
+                        processed.insert(node_idx);
+                        processed.insert(succ_node);
+                        break;
                     }
+                }
+                steps += 1;
+                if steps >= MAX_COMMUTATION_LOOKAHEAD || !instructions_commute(inst, succ_inst) {
+                    break;
+                }
+                current = succ_node;
+            }
+        }
 
-                    let qubit = inst.qubits[0];
-                    let wire = WireId::Qubit(qubit);
+        merges
+    }
 
-                    // Find immediate successor on this wire
-                    let graph = dag.graph();
-                    for edge in graph.edges_directed(node_idx, Direction::Outgoing) {
-                        if edge.weight().wire != wire {
-                            continue;
-                        }
+    /// Whether the operations between `first` and `second` (descendants of
+    /// `first` that are ancestors of `second`) all commute with one of the
+    /// two, so the pair can be made adjacent.
+    ///
+    /// Returns `Some(true)` if `second` can move back to `first`, and
+    /// `Some(false)` if only `first` can move forward to `second`.
+    fn can_bring_together(
+        dag: &CircuitDag,
+        position: &FxHashMap<NodeIndex, usize>,
+        first: NodeIndex,
+        second: NodeIndex,
+    ) -> Option<bool> {
+        let graph = dag.graph();
+        let (lo, hi) = (position[&first], position[&second]);
+        let in_window = |node: NodeIndex| position.get(&node).is_some_and(|&p| lo < p && p < hi);
+
+        let mut after_first = FxHashSet::default();
+        let mut stack = vec![first];
+        while let Some(node) = stack.pop() {
+            for succ in graph.neighbors_directed(node, Direction::Outgoing) {
+                if in_window(succ) && after_first.insert(succ) {
+                    stack.push(succ);
+                }
+            }
+        }
+        let mut between = Vec::new();
+        let mut seen = FxHashSet::default();
+        let mut stack = vec![second];
+        while let Some(node) = stack.pop() {
+            for pred in graph.neighbors_directed(node, Direction::Incoming) {
+                if after_first.contains(&pred) && seen.insert(pred) {
+                    between.push(pred);
+                    stack.push(pred);
+                }
+            }
+        }
 
-                        let succ_node = edge.target();
-                        if let DagNode::Op(succ_inst) = &graph[succ_node] {
-                            if let InstructionKind::Gate(succ_gate) = &succ_inst.kind {
-                                if let GateKind::Standard(succ_std) = &succ_gate.kind {
-                                    // Check if they're the same type of rotation
-                                    let same_type = matches!(
-                                        (std_gate, succ_std),
-                                        (StandardGate::Rz(_), StandardGate::Rz(_))
-                                            | (StandardGate::Rx(_), StandardGate::Rx(_))
-                                            | (StandardGate::Ry(_), StandardGate::Ry(_))
-                                    );
-                                    if same_type {
-                                        let merged = Self::merge_rotations(std_gate, succ_std);
-                                        merges.push((node_idx, succ_node, merged));
-                                        processed.insert(node_idx);
-                                        processed.insert(succ_node);
-                                    }
-                                }
-                            }
+        let inst = |node: NodeIndex| dag.get_instruction(node).expect("op node");
+        let (a, b) = (inst(first), inst(second));
+        if between.iter().all(|&n| instructions_commute(b, inst(n))) {
+            Some(true)
+        } else if between.iter().all(|&n| instructions_commute(a, inst(n))) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// The operation following `node` on `wire`, if any.
+    fn next_op(
+        dag: &CircuitDag,
+        node: NodeIndex,
+        wire: WireId,
+    ) -> Option<(NodeIndex, &Instruction)> {
+        let graph = dag.graph();
+        graph
+            .edges_directed(node, Direction::Outgoing)
+            .find(|edge| edge.weight().wire == wire)
+            .and_then(|edge| match &graph[edge.target()] {
+                DagNode::Op(succ_inst) => Some((edge.target(), succ_inst)),
+                _ => None,
+            })
+    }
+
+    /// Move every operation to the earliest layer allowed by the operations
+    /// it does not commute with. Returns `None` unless the depth shrinks.
+    fn reorder_for_depth(dag: &CircuitDag) -> CompileResult<Option<CircuitDag>> {
+        struct Wire<'a> {
+            /// Recent operations with their layers, sorted by layer.
+            recent: Vec<(&'a Instruction, usize)>,
+            /// Highest layer of operations no longer in `recent`.
+            floor: usize,
+            occupied: FxHashSet<usize>,
+        }
+
+        let mut wires: FxHashMap<WireId, Wire<'_>> = FxHashMap::default();
+        let mut placed: Vec<(usize, usize, &Instruction)> = Vec::new();
+
+        for (pos, (_, inst)) in dag.topological_ops().enumerate() {
+            let ids: Vec<WireId> = inst
+                .qubits
+                .iter()
+                .map(|&q| WireId::Qubit(q))
+                .chain(
+                    inst.clbits
+                        .iter()
+                        .chain(inst.condition_clbits())
+                        .map(|&c| WireId::Clbit(c)),
+                )
+                .collect();
+
+            // Lowest admissible layer: above every non-commuting operation.
+            let mut bound = 0;
+            for id in &ids {
+                if let Some(wire) = wires.get(id) {
+                    bound = bound.max(wire.floor);
+                    // Highest layer first: the first conflict sets the bound.
+                    for &(other, layer) in wire.recent.iter().rev() {
+                        if layer <= bound {
+                            break;
+                        }
+                        if !instructions_commute(inst, other) {
+                            bound = layer;
+                            break;
                         }
                     }
                 }
             }
+            let mut layer = bound + 1;
+            while ids
+                .iter()
+                .any(|id| wires.get(id).is_some_and(|w| w.occupied.contains(&layer)))
+            {
+                layer += 1;
+            }
+
+            for id in ids {
+                let wire = wires.entry(id).or_insert_with(|| Wire {
+                    recent: Vec::new(),
+                    floor: 0,
+                    occupied: FxHashSet::default(),
+                });
+                wire.occupied.insert(layer);
+                let at = wire.recent.partition_point(|&(_, l)| l <= layer);
+                wire.recent.insert(at, (inst, layer));
+                if wire.recent.len() > MAX_COMMUTATION_LOOKAHEAD {
+                    let (_, lowest) = wire.recent.remove(0);
+                    wire.floor = wire.floor.max(lowest);
+                }
+            }
+            placed.push((layer, pos, inst));
         }
 
-        merges
+        let depth = placed.iter().map(|&(layer, ..)| layer).max().unwrap_or(0);
+        if depth >= dag.depth() {
+            return Ok(None);
+        }
+
+        placed.sort_unstable_by_key(|&(layer, pos, _)| (layer, pos));
+        let mut new_dag = CircuitDag::new();
+        for qubit in dag.qubits().collect::<Vec<_>>() {
+            new_dag.add_qubit(qubit);
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+        for (_, _, inst) in placed {
+            new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
+        }
+        Ok(Some(new_dag))
     }
 }
 
@@ -364,42 +555,46 @@ impl Pass for CommutativeCancellation {
         PassKind::Transformation
     }
 
-    fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
-        // Find and merge adjacent same-type rotations.
-        // Process one merge per iteration to avoid stale NodeIndex references.
-        // petgraph's remove_node uses swap-remove, which invalidates the last
-        // node's index. Processing one merge at a time and re-discovering
-        // ensures all NodeIndex values are fresh.
+    fn is_optimization(&self) -> bool {
+        true
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        // Find and merge gate pairs. The pairs found in one sweep are
+        // disjoint, so all of them are applied before re-discovering.
+        // Merged gates are written first, while every index is still valid;
+        // nodes are then removed by descending index (see `CancelCX`).
         // Bound iterations to avoid pathological cases.
         const MAX_ITERATIONS: usize = 100;
         for _ in 0..MAX_ITERATIONS {
-            let merges = self.find_mergeable_rotations(dag);
+            if properties.budget_exhausted() {
+                break;
+            }
+            let merges = self.find_mergeable_pairs(dag);
             if merges.is_empty() {
                 break;
             }
 
-            // Process only the first merge, then re-discover.
-            let (node1, node2, merged) = &merges[0];
-
-            if let Some(gate) = merged {
-                // Replace first node with merged gate, then remove second.
-                // Order matters: update node1 first while both indices are
-                // still valid, then remove node2.
-                let gate = gate.clone();
-                if let Some(inst) = dag.get_instruction_mut(*node1) {
-                    *inst = Instruction::single_qubit_gate(gate, inst.qubits[0]);
-                }
-                dag.remove_op(*node2).map_err(CompileError::Ir)?;
-            } else {
-                // Gates cancel - remove both (higher index first to avoid
-                // swap-remove invalidation).
-                let (first, second) = if node1.index() > node2.index() {
-                    (*node1, *node2)
+            let mut removed = Vec::new();
+            for (keep, remove, merged) in merges {
+                if let Some(gate) = merged {
+                    if let Some(inst) = dag.get_instruction_mut(keep) {
+                        *inst = Instruction::gate(gate, inst.qubits.clone());
+                    }
                 } else {
-                    (*node2, *node1)
-                };
-                dag.remove_op(first).map_err(CompileError::Ir)?;
-                dag.remove_op(second).map_err(CompileError::Ir)?;
+                    removed.push(keep);
+                }
+                removed.push(remove);
+            }
+            removed.sort_unstable_by_key(|node| std::cmp::Reverse(node.index()));
+            for node in removed {
+                dag.remove_op(node).map_err(CompileError::Ir)?;
+            }
+        }
+
+        if !properties.budget_exhausted() {
+            if let Some(reordered) = Self::reorder_for_depth(dag)? {
+                *dag = reordered;
             }
         }
 
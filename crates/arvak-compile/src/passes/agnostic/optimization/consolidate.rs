@@ -6,6 +6,7 @@
 //! carrying the precomputed unitary.
 
 use num_complex::Complex64;
+use petgraph::Direction;
 use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::debug;
 
@@ -43,8 +44,11 @@ impl Pass for ConsolidateBlocks {
         PassKind::Transformation
     }
 
-    fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
         for _ in 0..MAX_ITERATIONS {
+            if properties.budget_exhausted() {
+                break;
+            }
             let block = find_best_block(dag);
             let Some(block) = block else {
                 break;
@@ -68,6 +72,10 @@ impl Pass for ConsolidateBlocks {
     fn should_run(&self, _dag: &CircuitDag, _properties: &PropertySet) -> bool {
         true
     }
+
+    fn is_optimization(&self) -> bool {
+        true
+    }
 }
 
 /// A two-qubit block identified for consolidation.
@@ -114,6 +122,9 @@ fn find_best_block(dag: &CircuitDag) -> Option<TwoQubitBlock> {
         if inst.qubits.len() != 2 {
             continue;
         }
+        if !matches!(&inst.kind, InstructionKind::Gate(g) if g.condition.is_none()) {
+            continue;
+        }
         if visited_2q.contains(node_idx) {
             continue;
         }
@@ -186,9 +197,12 @@ fn find_best_block(dag: &CircuitDag) -> Option<TwoQubitBlock> {
 
 /// Grow a block of consecutive gates on the qubit pair (q0, q1).
 ///
-/// Starting from a seed two-qubit gate at topo index `seed`, expand in both
-/// directions collecting gates that act exclusively on {q0} or {q0, q1} or {q1},
-/// stopping at barriers, measurements, resets, or gates involving other qubits.
+/// Starting from a seed two-qubit gate at topo index `seed`, walk both qubit
+/// timelines outwards, collecting single-qubit gates on q0 or q1 and
+/// two-qubit gates on the pair. A two-qubit gate is only collected once it is
+/// the next operation on both timelines, so the block never reaches past a
+/// barrier, measurement, reset, conditioned gate, or gate involving another
+/// qubit on either wire.
 fn grow_block(
     seed: usize,
     q0: QubitId,
@@ -205,79 +219,70 @@ fn grow_block(
     let q0_pos = q0_ops.iter().position(|&i| i == seed).unwrap_or(0);
     let q1_pos = q1_ops.iter().position(|&i| i == seed).unwrap_or(0);
 
-    let mut block_set: FxHashSet<usize> = FxHashSet::default();
-    block_set.insert(seed);
-
-    // Expand forward on q0.
-    for &idx in &q0_ops[(q0_pos + 1)..] {
-        if !can_include_in_block(idx, &pair, topo_ops) {
-            break;
-        }
-        block_set.insert(idx);
-    }
-
-    // Expand forward on q1.
-    for &idx in &q1_ops[(q1_pos + 1)..] {
-        if !can_include_in_block(idx, &pair, topo_ops) {
-            break;
-        }
-        block_set.insert(idx);
-    }
-
-    // Expand backward on q0.
-    for &idx in q0_ops[..q0_pos].iter().rev() {
-        if !can_include_in_block(idx, &pair, topo_ops) {
-            break;
-        }
-        block_set.insert(idx);
-    }
+    let mut block = vec![seed];
+
+    // Expand forward.
+    collect_run(
+        q0_ops[(q0_pos + 1)..].iter().copied(),
+        q1_ops[(q1_pos + 1)..].iter().copied(),
+        &pair,
+        topo_ops,
+        &mut block,
+    );
+
+    // Expand backward.
+    collect_run(
+        q0_ops[..q0_pos].iter().rev().copied(),
+        q1_ops[..q1_pos].iter().rev().copied(),
+        &pair,
+        topo_ops,
+        &mut block,
+    );
 
-    // Expand backward on q1.
-    for &idx in q1_ops[..q1_pos].iter().rev() {
-        if !can_include_in_block(idx, &pair, topo_ops) {
-            break;
-        }
-        block_set.insert(idx);
-    }
+    // Return in topological order.
+    block.sort_unstable();
+    block
+}
 
-    // Post-process: trim block so it never spans a non-gate operation
-    // (barrier, measurement, reset) on either qubit.  The per-qubit expansion
-    // above can miss a non-gate on the *other* qubit because it only walks
-    // that qubit's timeline.  Scan the full topo range of the block and
-    // truncate at the first boundary in each direction from the seed.
-    if block_set.len() > 1 {
-        let min_idx = block_set.iter().copied().min().unwrap_or(seed);
-        let max_idx = block_set.iter().copied().max().unwrap_or(seed);
-
-        // Forward boundary: first non-gate on either qubit after seed.
-        let mut fwd_boundary = max_idx + 1;
-        for (idx, (_, inst)) in topo_ops.iter().enumerate().take(max_idx + 1).skip(seed + 1) {
-            if !matches!(inst.kind, InstructionKind::Gate(_))
-                && inst.qubits.iter().any(|q| pair.contains(q))
-            {
-                fwd_boundary = idx;
-                break;
+/// Collect the gates that continue a block along two qubit timelines
+/// (`wire0`, `wire1`), both walking away from the seed in the same direction.
+fn collect_run(
+    wire0: impl Iterator<Item = usize>,
+    wire1: impl Iterator<Item = usize>,
+    pair: &FxHashSet<QubitId>,
+    topo_ops: &[(NodeIndex, Instruction)],
+    block: &mut Vec<usize>,
+) {
+    let mut wire0 = wire0.peekable();
+    let mut wire1 = wire1.peekable();
+    loop {
+        let next0 = wire0
+            .peek()
+            .copied()
+            .filter(|&idx| can_include_in_block(idx, pair, topo_ops));
+        let next1 = wire1
+            .peek()
+            .copied()
+            .filter(|&idx| can_include_in_block(idx, pair, topo_ops));
+        let is_1q = |idx: usize| topo_ops[idx].1.qubits.len() == 1;
+
+        match (next0, next1) {
+            (Some(idx), _) if is_1q(idx) => {
+                block.push(idx);
+                wire0.next();
             }
-        }
-
-        // Backward boundary: first non-gate on either qubit before seed.
-        let mut bwd_boundary = min_idx;
-        for (idx, (_, inst)) in topo_ops.iter().enumerate().take(seed).skip(min_idx).rev() {
-            if !matches!(inst.kind, InstructionKind::Gate(_))
-                && inst.qubits.iter().any(|q| pair.contains(q))
-            {
-                bwd_boundary = idx + 1;
-                break;
+            (_, Some(idx)) if is_1q(idx) => {
+                block.push(idx);
+                wire1.next();
+            }
+            (Some(a), Some(b)) if a == b => {
+                block.push(a);
+                wire0.next();
+                wire1.next();
             }
+            _ => break,
         }
-
-        block_set.retain(|&idx| idx >= bwd_boundary && idx < fwd_boundary);
     }
-
-    // Return in topological order.
-    let mut result: Vec<usize> = block_set.into_iter().collect();
-    result.sort_unstable();
-    result
 }
 
 /// Check if a gate at the given topo index can be included in a block
@@ -289,8 +294,12 @@ fn can_include_in_block(
 ) -> bool {
     let (_, inst) = &topo_ops[idx];
 
-    // Only gates can be consolidated; barriers, measurements, etc. are boundaries.
-    if !matches!(inst.kind, InstructionKind::Gate(_)) {
+    // Only unconditioned gates can be consolidated; barriers, measurements,
+    // etc. are boundaries.
+    let InstructionKind::Gate(gate) = &inst.kind else {
+        return false;
+    };
+    if gate.condition.is_some() {
         return false;
     }
 
@@ -327,7 +336,7 @@ fn compute_block_unitary(
             GateKind::Standard(sg) => {
                 if inst.qubits.len() == 1 {
                     // Single-qubit gate: tensor with identity on the other qubit.
-                    let u2 = gate_1q_to_unitary(sg)?;
+                    let u2 = Unitary2x2::from_gate(sg)?;
                     if inst.qubits[0] == q0 {
                         Unitary4x4::kron(&u2, &Unitary2x2::identity())
                     } else {
@@ -335,7 +344,7 @@ fn compute_block_unitary(
                     }
                 } else if inst.qubits.len() == 2 {
                     // Two-qubit gate.
-                    let u4 = gate_2q_to_unitary(sg)?;
+                    let u4 = Unitary4x4::from_gate(sg)?;
                     // If the qubit order is reversed (q1, q0), we need to swap.
                     if inst.qubits[0] == q0 && inst.qubits[1] == q1 {
                         u4
@@ -350,6 +359,8 @@ fn compute_block_unitary(
                     return None; // 3+ qubit gates not supported.
                 }
             }
+            // Modified gates are left for basis translation to expand.
+            GateKind::Modified(_) => return None,
             GateKind::Custom(custom) => {
                 if let Some(ref matrix) = custom.matrix {
                     if matrix.len() == 4 && inst.qubits.len() == 1 {
@@ -384,88 +395,9 @@ fn compute_block_unitary(
     Some(result.data)
 }
 
-/// Convert a single-qubit `StandardGate` to its 2×2 unitary.
-fn gate_1q_to_unitary(gate: &StandardGate) -> Option<Unitary2x2> {
-    match gate {
-        StandardGate::I => Some(Unitary2x2::identity()),
-        StandardGate::X => Some(Unitary2x2::x()),
-        StandardGate::Y => Some(Unitary2x2::y()),
-        StandardGate::Z => Some(Unitary2x2::z()),
-        StandardGate::H => Some(Unitary2x2::h()),
-        StandardGate::S => Some(Unitary2x2::s()),
-        StandardGate::Sdg => Some(Unitary2x2::sdg()),
-        StandardGate::T => Some(Unitary2x2::t()),
-        StandardGate::Tdg => Some(Unitary2x2::tdg()),
-        StandardGate::SX => Some(Unitary2x2::sx()),
-        StandardGate::SXdg => Some(Unitary2x2::sxdg()),
-        StandardGate::Rx(p) => p.as_f64().map(Unitary2x2::rx),
-        StandardGate::Ry(p) => p.as_f64().map(Unitary2x2::ry),
-        StandardGate::Rz(p) => p.as_f64().map(Unitary2x2::rz),
-        StandardGate::P(p) => p.as_f64().map(Unitary2x2::p),
-        StandardGate::U(theta, phi, lambda) => {
-            let t = theta.as_f64()?;
-            let p = phi.as_f64()?;
-            let l = lambda.as_f64()?;
-            Some(Unitary2x2::u(t, p, l))
-        }
-        StandardGate::PRX(theta, phi) => {
-            let t = theta.as_f64()?;
-            let p = phi.as_f64()?;
-            Some(Unitary2x2::rz(p) * Unitary2x2::rx(t) * Unitary2x2::rz(-p))
-        }
-        _ => None,
-    }
-}
-
-/// Convert a two-qubit `StandardGate` to its 4×4 unitary.
-///
-/// Returns `None` for parameterized gates whose parameters can't be resolved.
-fn gate_2q_to_unitary(gate: &StandardGate) -> Option<Unitary4x4> {
-    let o = Complex64::new(1.0, 0.0);
-    let z = Complex64::new(0.0, 0.0);
-    let i = Complex64::new(0.0, 1.0);
-    let m = Complex64::new(-1.0, 0.0);
-
-    match gate {
-        StandardGate::CX => Some(Unitary4x4 {
-            data: [o, z, z, z, z, o, z, z, z, z, z, o, z, z, o, z],
-        }),
-        StandardGate::CY => Some(Unitary4x4 {
-            data: [o, z, z, z, z, o, z, z, z, z, z, -i, z, z, i, z],
-        }),
-        StandardGate::CZ => Some(Unitary4x4 {
-            data: [o, z, z, z, z, o, z, z, z, z, o, z, z, z, z, m],
-        }),
-        StandardGate::Swap => Some(Unitary4x4 {
-            data: [o, z, z, z, z, z, o, z, z, o, z, z, z, z, z, o],
-        }),
-        StandardGate::ISwap => Some(Unitary4x4 {
-            data: [o, z, z, z, z, z, i, z, z, i, z, z, z, z, z, o],
-        }),
-        StandardGate::CH => {
-            let s = Complex64::new(1.0 / 2.0_f64.sqrt(), 0.0);
-            Some(Unitary4x4 {
-                data: [o, z, z, z, z, o, z, z, z, z, s, s, z, z, s, -s],
-            })
-        }
-        StandardGate::ECR => {
-            let s = Complex64::new(1.0 / 2.0_f64.sqrt(), 0.0);
-            let si = Complex64::new(0.0, 1.0 / 2.0_f64.sqrt());
-            Some(Unitary4x4 {
-                data: [z, z, s, si, z, z, si, s, s, -si, z, z, -si, s, z, z],
-            })
-        }
-        _ => None,
-    }
-}
-
 /// The 4×4 SWAP matrix for reordering qubit arguments.
 fn swap_4x4() -> Unitary4x4 {
-    let o = Complex64::new(1.0, 0.0);
-    let z = Complex64::new(0.0, 0.0);
-    Unitary4x4 {
-        data: [o, z, z, z, z, z, o, z, z, o, z, z, z, z, z, o],
-    }
+    Unitary4x4::from_gate(&StandardGate::Swap).expect("SWAP has a matrix")
 }
 
 /// Replace a block of gates with a single `CustomGate` carrying the unitary.
@@ -474,14 +406,19 @@ fn swap_4x4() -> Unitary4x4 {
 /// approach (remove + `dag.apply()`) fails because `apply` appends at wire
 /// ends, placing the consolidated gate AFTER all remaining gates instead of
 /// at the block's original position.
+///
+/// The rebuild emits every ancestor of the block first, then the
+/// replacement, then the remaining operations. Emitting the replacement at
+/// the block's first node in topological order is not enough: that node may
+/// precede a gate the block depends on through its other qubit. Blocks are
+/// contiguous on both wires, so no path leaves the block and re-enters it,
+/// and this order respects every dependency.
 fn replace_block(dag: &mut CircuitDag, block: &TwoQubitBlock) -> CompileResult<()> {
     let custom = CustomGate::new("consolidated_2q", 2).with_matrix(block.unitary.to_vec());
     let replacement = Instruction::gate(Gate::custom(custom), [block.q0, block.q1]);
 
     let block_nodes: FxHashSet<NodeIndex> = block.nodes.iter().copied().collect();
-
-    // Find the first block node in topological order — the replacement goes here.
-    let first_block_node = block.nodes.iter().copied().min();
+    let ancestors = ancestors_of(dag, &block.nodes);
 
     let mut new_dag = CircuitDag::new();
     for qubit in dag.qubits().collect::<Vec<_>>() {
@@ -493,31 +430,37 @@ fn replace_block(dag: &mut CircuitDag, block: &TwoQubitBlock) -> CompileResult<(
     new_dag.set_global_phase(dag.global_phase());
     new_dag.set_level(dag.level());
 
-    let mut replacement_emitted = false;
     for (idx, inst) in dag.topological_ops() {
-        if block_nodes.contains(&idx) {
-            // Emit the replacement at the position of the first block node.
-            if !replacement_emitted && Some(idx) == first_block_node {
-                new_dag
-                    .apply(replacement.clone())
-                    .map_err(CompileError::Ir)?;
-                replacement_emitted = true;
-            }
-            // Skip all block nodes (they are replaced by the single custom gate).
-            continue;
+        if ancestors.contains(&idx) {
+            new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
         }
-        new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
     }
-
-    // Safety: if first_block_node was not encountered (shouldn't happen), emit at end.
-    if !replacement_emitted {
-        new_dag.apply(replacement).map_err(CompileError::Ir)?;
+    new_dag.apply(replacement).map_err(CompileError::Ir)?;
+    for (idx, inst) in dag.topological_ops() {
+        if !ancestors.contains(&idx) && !block_nodes.contains(&idx) {
+            new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
+        }
     }
 
     *dag = new_dag;
     Ok(())
 }
 
+/// All nodes outside `nodes` from which one of `nodes` can be reached.
+pub(super) fn ancestors_of(dag: &CircuitDag, nodes: &[NodeIndex]) -> FxHashSet<NodeIndex> {
+    let graph = dag.graph();
+    let mut ancestors = FxHashSet::default();
+    let mut stack = nodes.to_vec();
+    while let Some(node) = stack.pop() {
+        for pred in graph.neighbors_directed(node, Direction::Incoming) {
+            if !nodes.contains(&pred) && ancestors.insert(pred) {
+                stack.push(pred);
+            }
+        }
+    }
+    ancestors
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -610,20 +553,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_consolidate_interleaved_gate_ends_block() {
+        // The CX on (1, 2) sits between the two CX on (0, 1) on q1, so they
+        // must not be merged even though they are adjacent on q0.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let expected = circuit.to_matrix().unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+
+        ConsolidateBlocks.run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(dag.num_ops(), 3);
+        let actual = dag.to_matrix().unwrap();
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_consolidate_keeps_dependency_on_other_wire() {
+        // The block on (0, 1) starts with the X on q1, which comes before
+        // the CX on (2, 0) in topological order; the merged gate must still
+        // be placed after that CX.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.x(QubitId(1)).unwrap();
+        circuit.h(QubitId(2)).unwrap();
+        circuit.cx(QubitId(2), QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let expected = circuit.to_matrix().unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+
+        ConsolidateBlocks.run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(dag.num_ops(), 3);
+        let actual = dag.to_matrix().unwrap();
+        let phase = actual
+            .iter()
+            .zip(&expected)
+            .find(|(_, e)| e.norm() > 1e-6)
+            .map(|(a, e)| a / e)
+            .unwrap();
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e * phase).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_consolidate_skips_conditioned_gates() {
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .conditioned_on(arvak_ir::ClbitId(0), 1)
+            .unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+
+        ConsolidateBlocks.run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(dag.num_ops(), 2, "Conditioned CX must not be merged");
+    }
+
     #[test]
     fn test_kak_cnot_count_correct() {
         // Verify the KAK decomposition gives correct CNOT counts.
-        let cx_u = gate_2q_to_unitary(&StandardGate::CX).unwrap();
+        let cx_u = Unitary4x4::from_gate(&StandardGate::CX).unwrap();
         assert!(cx_u.kak_decompose().num_cnots <= 1, "CX: expected ≤1 CNOT");
 
-        let swap_u = gate_2q_to_unitary(&StandardGate::Swap).unwrap();
+        let swap_u = Unitary4x4::from_gate(&StandardGate::Swap).unwrap();
         assert_eq!(
             swap_u.kak_decompose().num_cnots,
             3,
             "SWAP: expected 3 CNOTs"
         );
 
-        let cz_u = gate_2q_to_unitary(&StandardGate::CZ).unwrap();
+        let cz_u = Unitary4x4::from_gate(&StandardGate::CZ).unwrap();
         assert!(cz_u.kak_decompose().num_cnots <= 1, "CZ: expected ≤1 CNOT");
     }
 
@@ -753,18 +766,18 @@ mod tests {
     #[test]
     fn test_kak_ecr_and_ch() {
         // Verify KAK CNOT counts for ECR and CH gates.
-        let ecr_u = gate_2q_to_unitary(&StandardGate::ECR).unwrap();
+        let ecr_u = Unitary4x4::from_gate(&StandardGate::ECR).unwrap();
         let ecr_cnots = ecr_u.kak_decompose().num_cnots;
         assert!(ecr_cnots <= 2, "ECR: expected ≤2 CNOTs, got {ecr_cnots}");
 
-        let ch_u = gate_2q_to_unitary(&StandardGate::CH).unwrap();
+        let ch_u = Unitary4x4::from_gate(&StandardGate::CH).unwrap();
         let ch_cnots = ch_u.kak_decompose().num_cnots;
         assert!(ch_cnots <= 2, "CH: expected ≤2 CNOTs, got {ch_cnots}");
     }
 
     #[test]
     fn test_kak_iswap() {
-        let iswap_u = gate_2q_to_unitary(&StandardGate::ISwap).unwrap();
+        let iswap_u = Unitary4x4::from_gate(&StandardGate::ISwap).unwrap();
         let cnots = iswap_u.kak_decompose().num_cnots;
         assert!(cnots <= 2, "iSWAP: expected ≤2 CNOTs, got {cnots}");
     }
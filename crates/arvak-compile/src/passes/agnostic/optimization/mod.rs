@@ -3,6 +3,8 @@
 mod cancel;
 mod consolidate;
 mod optimize_1q;
+mod phase_polynomial;
+mod template;
 
 #[cfg(test)]
 mod tests;
@@ -10,6 +12,8 @@ mod tests;
 pub use cancel::{CancelCX, CommutativeCancellation};
 pub use consolidate::ConsolidateBlocks;
 pub use optimize_1q::{OneQubitBasis, Optimize1qGates};
+pub use phase_polynomial::PhasePolynomialOptimization;
+pub use template::TemplateOptimization;
 
 /// Tolerance for angle comparisons.
 pub(super) const EPSILON: f64 = 1e-10;
@@ -1,7 +1,6 @@
 //! Single-qubit gate optimization pass.
 
 use std::f64::consts::PI;
-use std::sync::LazyLock;
 
 use arvak_ir::CircuitDag;
 use arvak_ir::dag::NodeIndex;
@@ -59,59 +58,6 @@ impl Optimize1qGates {
         Self { basis }
     }
 
-    /// Get the unitary matrix for a single-qubit gate.
-    ///
-    /// Constant gates (I, X, Y, Z, H, S, Sdg, T, Tdg, SX, SXdg) use
-    /// pre-computed cached matrices to avoid recomputing trig functions.
-    fn gate_to_unitary(gate: &StandardGate) -> Option<Unitary2x2> {
-        // Pre-computed unitaries for constant gates (computed once, reused forever).
-        static U_I: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::identity);
-        static U_X: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::x);
-        static U_Y: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::y);
-        static U_Z: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::z);
-        static U_H: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::h);
-        static U_S: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::s);
-        static U_SDG: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::sdg);
-        static U_T: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::t);
-        static U_TDG: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::tdg);
-        static U_SX: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::sx);
-        static U_SXDG: LazyLock<Unitary2x2> = LazyLock::new(Unitary2x2::sxdg);
-
-        match gate {
-            StandardGate::I => Some(*U_I),
-            StandardGate::X => Some(*U_X),
-            StandardGate::Y => Some(*U_Y),
-            StandardGate::Z => Some(*U_Z),
-            StandardGate::H => Some(*U_H),
-            StandardGate::S => Some(*U_S),
-            StandardGate::Sdg => Some(*U_SDG),
-            StandardGate::T => Some(*U_T),
-            StandardGate::Tdg => Some(*U_TDG),
-            StandardGate::SX => Some(*U_SX),
-            StandardGate::SXdg => Some(*U_SXDG),
-            StandardGate::Rx(p) => p.as_f64().map(Unitary2x2::rx),
-            StandardGate::Ry(p) => p.as_f64().map(Unitary2x2::ry),
-            StandardGate::Rz(p) => p.as_f64().map(Unitary2x2::rz),
-            StandardGate::P(p) => p.as_f64().map(Unitary2x2::p),
-            StandardGate::U(theta, phi, lambda) => {
-                let t = theta.as_f64()?;
-                let p = phi.as_f64()?;
-                let l = lambda.as_f64()?;
-                Some(Unitary2x2::u(t, p, l))
-            }
-            StandardGate::PRX(theta, phi) => {
-                // PRX(θ, φ) = RZ(φ) · RX(θ) · RZ(-φ)
-                let t = theta.as_f64()?;
-                let p = phi.as_f64()?;
-                let rz_phi = Unitary2x2::rz(p);
-                let rx_theta = Unitary2x2::rx(t);
-                let rz_neg_phi = Unitary2x2::rz(-p);
-                Some(rz_phi * rx_theta * rz_neg_phi)
-            }
-            _ => None, // Multi-qubit gates
-        }
-    }
-
     /// Decompose a unitary to gates based on the target basis.
     fn decompose_unitary(&self, unitary: &Unitary2x2) -> Vec<StandardGate> {
         let (alpha, beta, gamma, _phase) = unitary.zyz_decomposition();
@@ -230,12 +176,14 @@ impl Optimize1qGates {
             let mut current_run: Vec<NodeIndex> = Vec::new();
 
             for &(node_idx, inst) in ops {
-                // Check if this is a single-qubit gate on exactly this qubit
+                // Check if this is a single-qubit gate on exactly this qubit.
+                // Conditioned gates may not execute, so they end the run.
                 if inst.qubits.len() == 1 && !visited.contains(&node_idx) {
                     if let InstructionKind::Gate(gate) = &inst.kind {
                         if let GateKind::Standard(std_gate) = &gate.kind {
                             if std_gate.num_qubits() == 1
-                                && Self::gate_to_unitary(std_gate).is_some()
+                                && gate.condition.is_none()
+                                && Unitary2x2::from_gate(std_gate).is_some()
                             {
                                 current_run.push(node_idx);
                                 continue;
@@ -305,7 +253,11 @@ impl Pass for Optimize1qGates {
         PassKind::Transformation
     }
 
-    fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
+    fn is_optimization(&self) -> bool {
+        true
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
         // Process one run at a time, re-discovering runs after each modification.
         // petgraph's remove_node uses swap-remove, which invalidates the last
         // node's NodeIndex. Re-discovering after each modification ensures all
@@ -314,9 +266,14 @@ impl Pass for Optimize1qGates {
         // Only strictly-improving replacements (fewer gates than the run) are
         // applied. Each application removes at least one op, so the loop
         // terminates after at most `num_ops` iterations — no iteration cap
-        // that could leave the circuit in a half-optimized state.
+        // that could leave the circuit in a half-optimized state. Only the
+        // time budget stops it early, and every intermediate circuit is
+        // equivalent to the input.
         let max_iterations = dag.num_ops().max(1);
         for _ in 0..max_iterations {
+            if properties.budget_exhausted() {
+                break;
+            }
             let runs = self.find_1q_runs(dag);
 
             let mut applied = false;
@@ -334,7 +291,7 @@ impl Pass for Optimize1qGates {
                     if let Some(inst) = dag.get_instruction(node_idx) {
                         if let InstructionKind::Gate(gate) = &inst.kind {
                             if let GateKind::Standard(std_gate) = &gate.kind {
-                                if let Some(u) = Self::gate_to_unitary(std_gate) {
+                                if let Some(u) = Unitary2x2::from_gate(std_gate) {
                                     combined = u * combined;
                                 }
                             }
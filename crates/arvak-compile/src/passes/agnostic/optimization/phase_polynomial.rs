@@ -0,0 +1,343 @@
+//! Phase-polynomial rotation merging.
+//!
+//! A circuit of CX gates and Z rotations maps each basis state `|x⟩` to
+//! `e^{i·p(x)} |A·x⟩`, where `A` is a linear reversible map and the phase
+//! polynomial `p` sums one term `θ·(x_i ⊕ x_j ⊕ …)` per rotation. Rotations
+//! on the same parity are a single term, however far apart they sit in the
+//! circuit, so they can be merged into one gate. This is the rotation
+//! merging step of T-par (Amy, Maslov & Mosca, 2014).
+//!
+//! Other gates do not end the analysis: a gate that is not a CX, SWAP or
+//! diagonal gate gives its qubits fresh variables, and rotations on parities
+//! that still only involve older variables keep merging across it (the
+//! path-sum argument of T-par). Measurements, resets, barriers, delays,
+//! noise, conditioned gates and control flow are hard boundaries.
+
+use std::f64::consts::PI;
+
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use arvak_ir::{
+    CircuitDag, GateKind, Instruction, InstructionKind, NodeIndex, ParameterExpression, QubitId,
+    StandardGate,
+};
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+
+use super::EPSILON;
+
+/// Merge Z rotations that act on the same parity of the circuit's inputs.
+///
+/// Z, S, S†, T, T†, P and RZ gates with numeric angles are phase terms.
+/// Each term's parity is tracked through CX and SWAP gates; a rotation
+/// whose parity was already seen is folded into the first gate on it, and
+/// terms that add up to a multiple of 2π are removed. The global phase is
+/// adjusted so the circuit unitary is unchanged exactly.
+///
+/// Merging rotations leaves the CX ladders that surrounded them adjacent,
+/// as in repeated steps of a Trotterized Hamiltonian; [`CommutativeCancellation`]
+/// then removes them, so the two run in the same optimization stage.
+///
+/// [`CommutativeCancellation`]: super::CommutativeCancellation
+pub struct PhasePolynomialOptimization;
+
+impl PhasePolynomialOptimization {
+    /// Create a new phase-polynomial optimization pass.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PhasePolynomialOptimization {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for PhasePolynomialOptimization {
+    fn name(&self) -> &'static str {
+        "PhasePolynomialOptimization"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn is_optimization(&self) -> bool {
+        true
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        if properties.budget_exhausted() {
+            return Ok(());
+        }
+        let terms = collect_terms(dag);
+
+        // Rewrite anchors first, while every index is still valid, then
+        // remove nodes by descending index (see `CancelCX`).
+        let mut removed = Vec::new();
+        let mut phase = dag.global_phase();
+        let mut merged = 0usize;
+        for term in terms {
+            let zero = is_multiple_of_2pi(term.angle);
+            if term.nodes.len() < 2 && !zero {
+                continue;
+            }
+            merged += term.nodes.len();
+            phase += term.offset;
+            let anchor = term.nodes[0];
+            if zero {
+                removed.push(anchor);
+            } else {
+                let (gate, offset) = phase_gate(term.angle, term.all_rz);
+                phase -= offset;
+                if let Some(inst) = dag.get_instruction_mut(anchor) {
+                    *inst =
+                        Instruction::single_qubit_gate(gate, term.qubit).with_metadata_from(inst);
+                }
+            }
+            removed.extend_from_slice(&term.nodes[1..]);
+        }
+        if merged == 0 {
+            return Ok(());
+        }
+
+        removed.sort_unstable_by_key(|node| std::cmp::Reverse(node.index()));
+        let count = removed.len();
+        for node in removed {
+            dag.remove_op(node).map_err(CompileError::Ir)?;
+        }
+        dag.set_global_phase(normalize(phase));
+        debug!("Merged {merged} phase rotations, removing {count} gates");
+
+        Ok(())
+    }
+}
+
+/// A parity of circuit variables, as a bitset without trailing zero words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Parity(Vec<u64>);
+
+impl Parity {
+    fn variable(index: usize) -> Self {
+        let mut words = vec![0; index / 64 + 1];
+        words[index / 64] = 1 << (index % 64);
+        Self(words)
+    }
+
+    fn xor_assign(&mut self, other: &Parity) {
+        if self.0.len() < other.0.len() {
+            self.0.resize(other.0.len(), 0);
+        }
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a ^= b;
+        }
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+}
+
+/// All rotations found on one parity between two hard boundaries.
+struct Term {
+    /// The rotation gates, the first of which receives the merged angle.
+    nodes: Vec<NodeIndex>,
+    /// Qubit of the first rotation.
+    qubit: QubitId,
+    /// Sum of the rotation angles, in the `P(θ)` convention.
+    angle: f64,
+    /// Sum of the global phases by which the gates differ from `P(θ)`.
+    offset: f64,
+    /// Whether every rotation is an RZ gate.
+    all_rz: bool,
+}
+
+/// How an instruction affects the tracked parities.
+enum Effect {
+    /// A phase rotation `P(θ)` up to a global phase `e^{i·offset}`.
+    Phase { angle: f64, offset: f64, rz: bool },
+    /// `CX(control, target)`.
+    Cx,
+    /// `SWAP`.
+    Swap,
+    /// A diagonal gate that is not merged; parities are unchanged.
+    Diagonal,
+    /// Any other unitary gate; its qubits get fresh variables.
+    Opaque,
+    /// A non-unitary or scheduling instruction that ends every term.
+    Boundary,
+}
+
+fn effect(inst: &Instruction) -> Effect {
+    let InstructionKind::Gate(gate) = &inst.kind else {
+        return Effect::Boundary;
+    };
+    if gate.condition.is_some() {
+        return Effect::Boundary;
+    }
+    let GateKind::Standard(std_gate) = &gate.kind else {
+        return Effect::Opaque;
+    };
+    let phase = |angle: f64| Effect::Phase {
+        angle,
+        offset: 0.0,
+        rz: false,
+    };
+    match std_gate {
+        StandardGate::Z => phase(PI),
+        StandardGate::S => phase(PI / 2.0),
+        StandardGate::Sdg => phase(-PI / 2.0),
+        StandardGate::T => phase(PI / 4.0),
+        StandardGate::Tdg => phase(-PI / 4.0),
+        StandardGate::P(theta) => theta.as_f64().map_or(Effect::Diagonal, phase),
+        StandardGate::Rz(theta) => theta
+            .as_f64()
+            .map_or(Effect::Diagonal, |angle| Effect::Phase {
+                angle,
+                offset: -angle / 2.0,
+                rz: true,
+            }),
+        StandardGate::CX => Effect::Cx,
+        StandardGate::Swap => Effect::Swap,
+        StandardGate::I
+        | StandardGate::CZ
+        | StandardGate::CRz(_)
+        | StandardGate::CP(_)
+        | StandardGate::RZZ(_)
+        | StandardGate::MCZ(_)
+        | StandardGate::MCP(..) => Effect::Diagonal,
+        _ => Effect::Opaque,
+    }
+}
+
+/// The parity held by each qubit wire.
+#[derive(Default)]
+struct Wires {
+    parities: FxHashMap<QubitId, Parity>,
+    next_variable: usize,
+}
+
+impl Wires {
+    fn get(&mut self, qubit: QubitId) -> Parity {
+        if let Some(parity) = self.parities.get(&qubit) {
+            return parity.clone();
+        }
+        self.refresh(qubit)
+    }
+
+    /// Give `qubit` a new variable.
+    fn refresh(&mut self, qubit: QubitId) -> Parity {
+        let parity = Parity::variable(self.next_variable);
+        self.next_variable += 1;
+        self.parities.insert(qubit, parity.clone());
+        parity
+    }
+}
+
+/// Walk the DAG in topological order and group its rotations by parity.
+fn collect_terms(dag: &CircuitDag) -> Vec<Term> {
+    let mut wires = Wires::default();
+    let mut terms: Vec<Term> = Vec::new();
+    let mut open: FxHashMap<Parity, usize> = FxHashMap::default();
+
+    for (node, inst) in dag.topological_ops() {
+        match effect(inst) {
+            Effect::Phase { angle, offset, rz } => {
+                let qubit = inst.qubits[0];
+                let parity = wires.get(qubit);
+                if let Some(&i) = open.get(&parity) {
+                    let term = &mut terms[i];
+                    term.nodes.push(node);
+                    term.angle += angle;
+                    term.offset += offset;
+                    term.all_rz &= rz;
+                } else {
+                    open.insert(parity, terms.len());
+                    terms.push(Term {
+                        nodes: vec![node],
+                        qubit,
+                        angle,
+                        offset,
+                        all_rz: rz,
+                    });
+                }
+            }
+            Effect::Cx => {
+                let (control, target) = (inst.qubits[0], inst.qubits[1]);
+                let control_parity = wires.get(control);
+                let mut parity = wires.get(target);
+                parity.xor_assign(&control_parity);
+                wires.parities.insert(target, parity);
+            }
+            Effect::Swap => {
+                let (a, b) = (inst.qubits[0], inst.qubits[1]);
+                let (pa, pb) = (wires.get(a), wires.get(b));
+                wires.parities.insert(a, pb);
+                wires.parities.insert(b, pa);
+            }
+            Effect::Diagonal => {}
+            Effect::Opaque => {
+                for &q in &inst.qubits {
+                    wires.refresh(q);
+                }
+            }
+            Effect::Boundary => {
+                open.clear();
+                for &q in &inst.qubits {
+                    wires.refresh(q);
+                }
+            }
+        }
+    }
+    terms
+}
+
+/// The gate for a merged rotation of `angle` and the global phase by which
+/// it differs from `P(angle)`.
+///
+/// Quarter and eighth turns become exact Clifford+T gates unless every
+/// merged rotation was an RZ; RZ stays RZ so rotation-basis targets keep
+/// their native gate.
+fn phase_gate(angle: f64, all_rz: bool) -> (StandardGate, f64) {
+    let angle = normalize(angle);
+    if all_rz {
+        return (
+            StandardGate::Rz(ParameterExpression::constant(angle)),
+            -angle / 2.0,
+        );
+    }
+    let eighths = angle / (PI / 4.0);
+    let gate = if (eighths - eighths.round()).abs() < EPSILON {
+        match eighths.round() as i64 {
+            1 => Some(StandardGate::T),
+            -1 => Some(StandardGate::Tdg),
+            2 => Some(StandardGate::S),
+            -2 => Some(StandardGate::Sdg),
+            4 => Some(StandardGate::Z),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    (
+        gate.unwrap_or_else(|| StandardGate::P(ParameterExpression::constant(angle))),
+        0.0,
+    )
+}
+
+/// Reduce an angle to `(-π, π]`.
+fn normalize(angle: f64) -> f64 {
+    let reduced = angle.rem_euclid(2.0 * PI);
+    if reduced > PI {
+        reduced - 2.0 * PI
+    } else {
+        reduced
+    }
+}
+
+fn is_multiple_of_2pi(angle: f64) -> bool {
+    normalize(angle).abs() < EPSILON
+}
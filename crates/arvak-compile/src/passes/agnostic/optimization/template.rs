@@ -0,0 +1,883 @@
+//! Template matching optimization pass.
+//!
+//! A template is a short circuit that composes to the identity. If a
+//! circuit contains more than half of a template (a contiguous run of its
+//! gates, read cyclically), that run equals the inverse of the rest of the
+//! template, which has fewer gates. For example `H·CX·H·CZ = I` on the
+//! target qubit, so `H·CX·H` can be replaced by a single `CZ`.
+//!
+//! Matches may be separated by gates that commute with the matched gates
+//! that follow them; those gates are moved behind the replacement. This is
+//! what distinguishes template matching from [`CancelCX`](super::CancelCX),
+//! which only cancels literally adjacent pairs.
+
+use std::sync::OnceLock;
+
+use petgraph::Direction;
+use petgraph::visit::EdgeRef;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use arvak_ir::dag::{DagNode, WireId};
+use arvak_ir::parameter::ParameterExpression;
+use arvak_ir::{
+    CircuitDag, Gate, GateKind, Instruction, InstructionKind, NodeIndex, QubitId, StandardGate,
+};
+
+use crate::commutation::instructions_commute;
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+use crate::unitary::Unitary2x2;
+
+use super::EPSILON;
+use super::consolidate::ancestors_of;
+
+/// Maximum number of iterations to avoid pathological cases.
+const MAX_ITERATIONS: usize = 100;
+
+/// Maximum number of gates skipped on a wire while looking for the next
+/// gate of a template.
+const MAX_TEMPLATE_LOOKAHEAD: usize = 16;
+
+/// Rewrite circuit fragments that match part of a template from the
+/// built-in library.
+///
+/// The library covers CX cancellation and conjugation identities
+/// (`H⊗H·CX·H⊗H = CX` reversed, `H·CX·H = CZ`, X and Z propagation through
+/// CX), Toffoli cancellation, self-inverse and `S`/`T` pairs, and phase
+/// gadget reorientation (`CX·Rz·CX` on either qubit). Only unconditioned
+/// standard gates with constant angles are matched; every rewrite strictly
+/// reduces the gate count.
+pub struct TemplateOptimization;
+
+impl TemplateOptimization {
+    /// Create a new template optimization pass.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TemplateOptimization {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for TemplateOptimization {
+    fn name(&self) -> &'static str {
+        "TemplateOptimization"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn is_optimization(&self) -> bool {
+        true
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        // One rewrite per iteration: the DAG is rebuilt, so node indices
+        // from the previous search are stale.
+        for _ in 0..MAX_ITERATIONS {
+            if properties.budget_exhausted() {
+                break;
+            }
+            let Some(found) = find_match(dag) else {
+                break;
+            };
+            debug!(
+                "Template {}: {} gates → {}",
+                found.template,
+                found.nodes.len(),
+                found.replacement.len()
+            );
+            apply_match(dag, &found)?;
+        }
+        Ok(())
+    }
+}
+
+/// A gate of a template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    H,
+    X,
+    Z,
+    S,
+    Sdg,
+    T,
+    Tdg,
+    Cx,
+    Cz,
+    Ccx,
+    /// `Rz(θ)` with the template's free angle.
+    Rz,
+    /// `Rz(-θ)`.
+    RzNeg,
+}
+
+impl Op {
+    fn inverse(self) -> Self {
+        match self {
+            Op::S => Op::Sdg,
+            Op::Sdg => Op::S,
+            Op::T => Op::Tdg,
+            Op::Tdg => Op::T,
+            Op::Rz => Op::RzNeg,
+            Op::RzNeg => Op::Rz,
+            op => op,
+        }
+    }
+
+    /// Whether `gate` is this op, binding or checking the free angle.
+    fn matches(self, gate: &StandardGate, theta: &mut Option<f64>) -> bool {
+        let sign = match (self, gate) {
+            (Op::H, StandardGate::H)
+            | (Op::X, StandardGate::X)
+            | (Op::Z, StandardGate::Z)
+            | (Op::S, StandardGate::S)
+            | (Op::Sdg, StandardGate::Sdg)
+            | (Op::T, StandardGate::T)
+            | (Op::Tdg, StandardGate::Tdg)
+            | (Op::Cx, StandardGate::CX)
+            | (Op::Cz, StandardGate::CZ)
+            | (Op::Ccx, StandardGate::CCX) => return true,
+            (Op::Rz, StandardGate::Rz(_)) => 1.0,
+            (Op::RzNeg, StandardGate::Rz(_)) => -1.0,
+            _ => return false,
+        };
+        let StandardGate::Rz(angle) = gate else {
+            return false;
+        };
+        let Some(angle) = angle.as_f64() else {
+            return false;
+        };
+        let angle = sign * angle;
+        match theta {
+            Some(bound) => Unitary2x2::normalize_angle(angle - *bound).abs() < EPSILON,
+            None => {
+                *theta = Some(angle);
+                true
+            }
+        }
+    }
+
+    fn build(self, theta: Option<f64>) -> StandardGate {
+        let angle = |sign: f64| ParameterExpression::constant(sign * theta.unwrap_or(0.0));
+        match self {
+            Op::H => StandardGate::H,
+            Op::X => StandardGate::X,
+            Op::Z => StandardGate::Z,
+            Op::S => StandardGate::S,
+            Op::Sdg => StandardGate::Sdg,
+            Op::T => StandardGate::T,
+            Op::Tdg => StandardGate::Tdg,
+            Op::Cx => StandardGate::CX,
+            Op::Cz => StandardGate::CZ,
+            Op::Ccx => StandardGate::CCX,
+            Op::Rz => StandardGate::Rz(angle(1.0)),
+            Op::RzNeg => StandardGate::Rz(angle(-1.0)),
+        }
+    }
+}
+
+/// A gate on template qubits.
+type TemplateGate = (Op, &'static [usize]);
+
+/// An identity circuit, in circuit order.
+struct Template {
+    name: &'static str,
+    num_qubits: usize,
+    gates: &'static [TemplateGate],
+}
+
+/// The built-in template library.
+const TEMPLATES: &[Template] = &[
+    Template {
+        name: "cx_cx",
+        num_qubits: 2,
+        gates: &[(Op::Cx, &[0, 1]), (Op::Cx, &[0, 1])],
+    },
+    Template {
+        name: "cz_cz",
+        num_qubits: 2,
+        gates: &[(Op::Cz, &[0, 1]), (Op::Cz, &[0, 1])],
+    },
+    Template {
+        name: "ccx_ccx",
+        num_qubits: 3,
+        gates: &[(Op::Ccx, &[0, 1, 2]), (Op::Ccx, &[0, 1, 2])],
+    },
+    Template {
+        name: "ccx_swapped_controls",
+        num_qubits: 3,
+        gates: &[(Op::Ccx, &[0, 1, 2]), (Op::Ccx, &[1, 0, 2])],
+    },
+    Template {
+        name: "h_h",
+        num_qubits: 1,
+        gates: &[(Op::H, &[0]), (Op::H, &[0])],
+    },
+    Template {
+        name: "x_x",
+        num_qubits: 1,
+        gates: &[(Op::X, &[0]), (Op::X, &[0])],
+    },
+    Template {
+        name: "z_z",
+        num_qubits: 1,
+        gates: &[(Op::Z, &[0]), (Op::Z, &[0])],
+    },
+    Template {
+        name: "s_sdg",
+        num_qubits: 1,
+        gates: &[(Op::S, &[0]), (Op::Sdg, &[0])],
+    },
+    Template {
+        name: "t_tdg",
+        num_qubits: 1,
+        gates: &[(Op::T, &[0]), (Op::Tdg, &[0])],
+    },
+    // H on the target turns CX into CZ.
+    Template {
+        name: "cx_to_cz",
+        num_qubits: 2,
+        gates: &[
+            (Op::H, &[1]),
+            (Op::Cx, &[0, 1]),
+            (Op::H, &[1]),
+            (Op::Cz, &[0, 1]),
+        ],
+    },
+    // H on both qubits reverses CX.
+    Template {
+        name: "cx_reverse",
+        num_qubits: 2,
+        gates: &[
+            (Op::H, &[0]),
+            (Op::H, &[1]),
+            (Op::Cx, &[0, 1]),
+            (Op::H, &[0]),
+            (Op::H, &[1]),
+            (Op::Cx, &[1, 0]),
+        ],
+    },
+    // X on the control propagates to the target.
+    Template {
+        name: "cx_x_control",
+        num_qubits: 2,
+        gates: &[
+            (Op::X, &[0]),
+            (Op::Cx, &[0, 1]),
+            (Op::X, &[0]),
+            (Op::X, &[1]),
+            (Op::Cx, &[0, 1]),
+        ],
+    },
+    // Z on the target propagates to the control.
+    Template {
+        name: "cx_z_target",
+        num_qubits: 2,
+        gates: &[
+            (Op::Z, &[1]),
+            (Op::Cx, &[0, 1]),
+            (Op::Z, &[1]),
+            (Op::Z, &[0]),
+            (Op::Cx, &[0, 1]),
+        ],
+    },
+    // A ZZ phase gadget can be built on either qubit.
+    Template {
+        name: "phase_gadget",
+        num_qubits: 2,
+        gates: &[
+            (Op::Cx, &[0, 1]),
+            (Op::Rz, &[1]),
+            (Op::Cx, &[0, 1]),
+            (Op::Cx, &[1, 0]),
+            (Op::RzNeg, &[0]),
+            (Op::Cx, &[1, 0]),
+        ],
+    },
+];
+
+/// A rewrite derived from a template: `pattern` equals `replacement`.
+struct Rule {
+    template: &'static str,
+    num_qubits: usize,
+    pattern: Vec<TemplateGate>,
+    replacement: Vec<TemplateGate>,
+}
+
+/// All rewrites of the library, largest gate-count reduction first.
+///
+/// For a template `g1…gL` and a cyclic run `g1…gk` with `k > L/2`, the run
+/// equals `gL†…g(k+1)†`. The inverse of each template is an identity too
+/// and contributes its own rules.
+fn rules() -> &'static [Rule] {
+    static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let mut rules: Vec<Rule> = Vec::new();
+        for template in TEMPLATES {
+            let inverse: Vec<TemplateGate> = template
+                .gates
+                .iter()
+                .rev()
+                .map(|&(op, qubits)| (op.inverse(), qubits))
+                .collect();
+            for gates in [template.gates.to_vec(), inverse] {
+                let len = gates.len();
+                for start in 0..len {
+                    let rotated: Vec<TemplateGate> =
+                        (0..len).map(|i| gates[(start + i) % len]).collect();
+                    for k in len / 2 + 1..=len {
+                        let pattern = rotated[..k].to_vec();
+                        let replacement: Vec<TemplateGate> = rotated[k..]
+                            .iter()
+                            .rev()
+                            .map(|&(op, qubits)| (op.inverse(), qubits))
+                            .collect();
+                        let is_new = !rules.iter().any(|r| r.pattern == pattern);
+                        if is_new && is_matchable(&pattern, &replacement) {
+                            rules.push(Rule {
+                                template: template.name,
+                                num_qubits: template.num_qubits,
+                                pattern,
+                                replacement,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        rules.sort_by_key(|r| std::cmp::Reverse(r.pattern.len() - r.replacement.len()));
+        rules
+    })
+}
+
+/// Whether the pattern's gates are connected through shared qubits (so
+/// the matcher can reach all of them from the first), and the replacement
+/// only uses qubits and angles bound by the pattern.
+fn is_matchable(pattern: &[TemplateGate], replacement: &[TemplateGate]) -> bool {
+    let mut seen: Vec<usize> = pattern[0].1.to_vec();
+    let mut reached = vec![false; pattern.len()];
+    reached[0] = true;
+    let mut grew = true;
+    while grew {
+        grew = false;
+        for (i, &(_, qubits)) in pattern.iter().enumerate() {
+            if !reached[i] && qubits.iter().any(|q| seen.contains(q)) {
+                reached[i] = true;
+                seen.extend_from_slice(qubits);
+                grew = true;
+            }
+        }
+    }
+    let has_angle =
+        |gates: &[TemplateGate]| gates.iter().any(|(op, _)| matches!(op, Op::Rz | Op::RzNeg));
+    reached.iter().all(|&r| r)
+        && replacement
+            .iter()
+            .all(|(_, qubits)| qubits.iter().all(|q| seen.contains(q)))
+        && (!has_angle(replacement) || has_angle(pattern))
+}
+
+/// A rule matched against the DAG.
+struct Match {
+    template: &'static str,
+    /// Matched nodes, in pattern order.
+    nodes: Vec<NodeIndex>,
+    /// Replacement instructions on circuit qubits.
+    replacement: Vec<Instruction>,
+}
+
+/// The unconditioned standard gate of `inst`, if any.
+fn standard_gate(inst: &Instruction) -> Option<&StandardGate> {
+    match &inst.kind {
+        InstructionKind::Gate(Gate {
+            kind: GateKind::Standard(gate),
+            condition: None,
+            ..
+        }) => Some(gate),
+        _ => None,
+    }
+}
+
+/// Find the first rewrite in topological order.
+fn find_match(dag: &CircuitDag) -> Option<Match> {
+    let position: FxHashMap<NodeIndex, usize> = dag
+        .topological_ops()
+        .enumerate()
+        .map(|(pos, (idx, _))| (idx, pos))
+        .collect();
+
+    for (node, inst) in dag.topological_ops() {
+        let Some(gate) = standard_gate(inst) else {
+            continue;
+        };
+        for rule in rules() {
+            let (op, _) = rule.pattern[0];
+            if !op.matches(gate, &mut None) {
+                continue;
+            }
+            if let Some(found) = match_rule(dag, &position, node, rule) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Try to match `rule` with its first gate at `start`.
+///
+/// Pattern gates are searched forward on a qubit that an earlier gate
+/// already mapped. A gate on qubits not mapped yet (the second `H` of
+/// `H⊗H·CX`) is deferred until a later gate maps one of them, and then
+/// searched backward from that gate.
+fn match_rule(
+    dag: &CircuitDag,
+    position: &FxHashMap<NodeIndex, usize>,
+    start: NodeIndex,
+    rule: &'static Rule,
+) -> Option<Match> {
+    let pattern = &rule.pattern;
+    let mut mapping: Vec<Option<QubitId>> = vec![None; rule.num_qubits];
+    let mut theta = None;
+    let mut nodes: Vec<Option<NodeIndex>> = vec![None; pattern.len()];
+    let mut pending: Vec<usize> = Vec::new();
+
+    for (i, &(op, qubits)) in pattern.iter().enumerate() {
+        let candidate = if i == 0 {
+            start
+        } else if let Some(anchor) = qubits.iter().find_map(|&q| mapping[q]) {
+            // Continue after the latest matched gate on the anchor qubit.
+            let from = nodes[..i]
+                .iter()
+                .rev()
+                .flatten()
+                .find(|&&n| on_qubit(dag, n, anchor))
+                .copied()?;
+            find_adjacent(dag, from, anchor, Direction::Outgoing, &nodes, |inst| {
+                bind(inst, op, qubits, &mapping, &mut theta.clone())
+            })?
+        } else {
+            pending.push(i);
+            continue;
+        };
+        let inst = dag.get_instruction(candidate)?;
+        if !bind_into(inst, op, qubits, &mut mapping, &mut theta) {
+            return None;
+        }
+        nodes[i] = Some(candidate);
+
+        // Resolve deferred gates that now have a mapped qubit.
+        while let Some(slot) = pending
+            .iter()
+            .position(|&p| pattern[p].1.iter().any(|&q| mapping[q].is_some()))
+        {
+            let p = pending.remove(slot);
+            let (op, qubits) = pattern[p];
+            let anchor = qubits.iter().find_map(|&q| mapping[q])?;
+            // Walk back from the earliest later matched gate on the anchor.
+            let from = nodes[p + 1..]
+                .iter()
+                .flatten()
+                .find(|&&n| on_qubit(dag, n, anchor))
+                .copied()?;
+            let found = find_adjacent(dag, from, anchor, Direction::Incoming, &nodes, |inst| {
+                bind(inst, op, qubits, &mapping, &mut theta.clone())
+            })?;
+            let inst = dag.get_instruction(found)?;
+            if !bind_into(inst, op, qubits, &mut mapping, &mut theta) {
+                return None;
+            }
+            nodes[p] = Some(found);
+        }
+    }
+
+    let nodes: Vec<NodeIndex> = nodes.into_iter().collect::<Option<_>>()?;
+    if !is_valid_match(dag, position, &nodes) {
+        return None;
+    }
+
+    let replacement = rule
+        .replacement
+        .iter()
+        .map(|&(op, qubits)| {
+            Instruction::gate(
+                op.build(theta),
+                qubits
+                    .iter()
+                    .map(|&q| mapping[q].expect("checked by is_matchable")),
+            )
+        })
+        .collect();
+    Some(Match {
+        template: rule.template,
+        nodes,
+        replacement,
+    })
+}
+
+/// Whether the operation at `node` acts on `qubit`.
+fn on_qubit(dag: &CircuitDag, node: NodeIndex, qubit: QubitId) -> bool {
+    dag.get_instruction(node)
+        .is_some_and(|inst| inst.qubits.contains(&qubit))
+}
+
+/// The first gate from `from` along `qubit` in `direction` that satisfies
+/// `accept`, skipping at most [`MAX_TEMPLATE_LOOKAHEAD`] other gates. Stops
+/// at non-gate operations and at gates already matched.
+fn find_adjacent(
+    dag: &CircuitDag,
+    from: NodeIndex,
+    qubit: QubitId,
+    direction: Direction,
+    matched: &[Option<NodeIndex>],
+    accept: impl Fn(&Instruction) -> bool,
+) -> Option<NodeIndex> {
+    let graph = dag.graph();
+    let wire = WireId::Qubit(qubit);
+    let mut current = from;
+    for _ in 0..=MAX_TEMPLATE_LOOKAHEAD {
+        let edge = graph
+            .edges_directed(current, direction)
+            .find(|edge| edge.weight().wire == wire)?;
+        let next = match direction {
+            Direction::Outgoing => edge.target(),
+            Direction::Incoming => edge.source(),
+        };
+        let DagNode::Op(inst) = &graph[next] else {
+            return None;
+        };
+        if matched.contains(&Some(next)) || !matches!(inst.kind, InstructionKind::Gate(_)) {
+            return None;
+        }
+        if accept(inst) {
+            return Some(next);
+        }
+        current = next;
+    }
+    None
+}
+
+/// Whether `inst` is `op` on `qubits` under `mapping`.
+fn bind(
+    inst: &Instruction,
+    op: Op,
+    qubits: &[usize],
+    mapping: &[Option<QubitId>],
+    theta: &mut Option<f64>,
+) -> bool {
+    let mut mapping = mapping.to_vec();
+    bind_into(inst, op, qubits, &mut mapping, theta)
+}
+
+/// Like [`bind`], but records new qubit assignments in `mapping`. CZ is
+/// symmetric and binds in either qubit order.
+fn bind_into(
+    inst: &Instruction,
+    op: Op,
+    qubits: &[usize],
+    mapping: &mut [Option<QubitId>],
+    theta: &mut Option<f64>,
+) -> bool {
+    let Some(gate) = standard_gate(inst) else {
+        return false;
+    };
+    if inst.qubits.len() != qubits.len() || !op.matches(gate, theta) {
+        return false;
+    }
+    if bind_qubits(qubits, inst.qubits.iter().copied(), mapping) {
+        return true;
+    }
+    op == Op::Cz && bind_qubits(qubits, inst.qubits.iter().rev().copied(), mapping)
+}
+
+/// Map `qubits` to `circuit_qubits`, consistently with `mapping`. Leaves
+/// `mapping` unchanged on failure.
+fn bind_qubits(
+    qubits: &[usize],
+    circuit_qubits: impl Iterator<Item = QubitId>,
+    mapping: &mut [Option<QubitId>],
+) -> bool {
+    let mut updated = mapping.to_vec();
+    for (&template_qubit, circuit_qubit) in qubits.iter().zip(circuit_qubits) {
+        match updated[template_qubit] {
+            Some(mapped) if mapped != circuit_qubit => return false,
+            Some(_) => {}
+            None => {
+                if updated.contains(&Some(circuit_qubit)) {
+                    return false;
+                }
+                updated[template_qubit] = Some(circuit_qubit);
+            }
+        }
+    }
+    mapping.copy_from_slice(&updated);
+    true
+}
+
+/// Whether the matched gates can be brought together.
+///
+/// Matched gates sharing a qubit must appear in pattern order. Every other
+/// gate between two matched gates is moved behind the replacement, so it
+/// must commute with each matched gate that follows it.
+fn is_valid_match(
+    dag: &CircuitDag,
+    position: &FxHashMap<NodeIndex, usize>,
+    nodes: &[NodeIndex],
+) -> bool {
+    let inst = |node: NodeIndex| dag.get_instruction(node).expect("matched op node");
+    for (i, &a) in nodes.iter().enumerate() {
+        for &b in &nodes[i + 1..] {
+            let shares_qubit = inst(a).qubits.iter().any(|q| inst(b).qubits.contains(q));
+            if shares_qubit && position[&a] > position[&b] {
+                return false;
+            }
+        }
+    }
+
+    between(dag, nodes).into_iter().all(|skipped| {
+        nodes
+            .iter()
+            .filter(|&&m| position[&m] > position[&skipped])
+            .all(|&m| instructions_commute(inst(skipped), inst(m)))
+    })
+}
+
+/// Nodes outside `nodes` that are both a descendant and an ancestor of
+/// matched nodes.
+fn between(dag: &CircuitDag, nodes: &[NodeIndex]) -> FxHashSet<NodeIndex> {
+    let ancestors = ancestors_of(dag, nodes);
+    let graph = dag.graph();
+    let mut descendants = FxHashSet::default();
+    let mut stack = nodes.to_vec();
+    while let Some(node) = stack.pop() {
+        for succ in graph.neighbors_directed(node, Direction::Outgoing) {
+            if ancestors.contains(&succ) && descendants.insert(succ) {
+                stack.push(succ);
+            }
+        }
+    }
+    descendants
+}
+
+/// Rebuild the DAG with the matched gates replaced: ancestors of the match
+/// that do not depend on it first, then the replacement, then the rest.
+fn apply_match(dag: &mut CircuitDag, found: &Match) -> CompileResult<()> {
+    let matched: FxHashSet<NodeIndex> = found.nodes.iter().copied().collect();
+    let skipped = between(dag, &found.nodes);
+    let before: FxHashSet<NodeIndex> = ancestors_of(dag, &found.nodes)
+        .into_iter()
+        .filter(|node| !skipped.contains(node))
+        .collect();
+
+    let mut new_dag = CircuitDag::new();
+    for qubit in dag.qubits().collect::<Vec<_>>() {
+        new_dag.add_qubit(qubit);
+    }
+    for clbit in dag.clbits().collect::<Vec<_>>() {
+        new_dag.add_clbit(clbit);
+    }
+    new_dag.set_global_phase(dag.global_phase());
+    new_dag.set_level(dag.level());
+
+    for (idx, inst) in dag.topological_ops() {
+        if before.contains(&idx) {
+            new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
+        }
+    }
+    for inst in &found.replacement {
+        new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
+    }
+    for (idx, inst) in dag.topological_ops() {
+        if !before.contains(&idx) && !matched.contains(&idx) {
+            new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
+        }
+    }
+
+    *dag = new_dag;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::{Circuit, SplitMix64};
+    use num_complex::Complex64;
+
+    fn optimize(circuit: &Circuit) -> CircuitDag {
+        let mut dag = circuit.clone().into_dag();
+        TemplateOptimization
+            .run(&mut dag, &mut PropertySet::new())
+            .unwrap();
+        let expected = circuit.to_matrix().unwrap();
+        let actual = dag.to_matrix().unwrap();
+        assert_equiv(&expected, &actual);
+        dag
+    }
+
+    /// Equal up to global phase.
+    fn assert_equiv(expected: &[Complex64], actual: &[Complex64]) {
+        let (i, _) = expected
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.norm().total_cmp(&b.1.norm()))
+            .unwrap();
+        let phase = actual[i] / expected[i];
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e * phase).norm() < 1e-9);
+        }
+    }
+
+    fn gate_names(dag: &CircuitDag) -> Vec<&str> {
+        dag.topological_ops()
+            .filter_map(|(_, inst)| inst.as_gate().map(Gate::name))
+            .collect()
+    }
+
+    #[test]
+    fn test_rules_reduce_gate_count() {
+        for rule in rules() {
+            assert!(rule.pattern.len() > rule.replacement.len());
+        }
+    }
+
+    #[test]
+    fn test_cx_cancels_through_commuting_gates() {
+        // RZ on the control and X on the target commute with CX; CancelCX
+        // misses this pair.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.rz(0.3, QubitId(0)).unwrap();
+        circuit.x(QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+        let dag = optimize(&circuit);
+        let mut names = gate_names(&dag);
+        names.sort_unstable();
+        assert_eq!(names, ["rz", "x"]);
+    }
+
+    #[test]
+    fn test_cx_does_not_cancel_through_blocking_gate() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+        let dag = optimize(&circuit);
+        assert_eq!(dag.num_ops(), 3);
+    }
+
+    #[test]
+    fn test_h_cx_h_becomes_cz() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(2)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.h(QubitId(2)).unwrap();
+
+        let dag = optimize(&circuit);
+        assert_eq!(gate_names(&dag), ["cz"]);
+    }
+
+    #[test]
+    fn test_cx_reversed_by_hadamards() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.h(QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.h(QubitId(0)).unwrap();
+        circuit.h(QubitId(1)).unwrap();
+
+        let dag = optimize(&circuit);
+        let (_, inst) = dag.topological_ops().next().unwrap();
+        assert_eq!(dag.num_ops(), 1);
+        assert_eq!(inst.qubits, [QubitId(1), QubitId(0)]);
+    }
+
+    #[test]
+    fn test_toffoli_pair_cancels() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.ccx(QubitId(0), QubitId(1), QubitId(2)).unwrap();
+        circuit.t(QubitId(0)).unwrap();
+        circuit.ccx(QubitId(1), QubitId(0), QubitId(2)).unwrap();
+
+        let dag = optimize(&circuit);
+        assert_eq!(gate_names(&dag), ["t"]);
+    }
+
+    #[test]
+    fn test_phase_gadget_pair_merges() {
+        // Two ZZ gadgets with opposite orientation and angles cancel.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.rz(0.7, QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(0)).unwrap();
+        circuit.rz(-0.7, QubitId(0)).unwrap();
+        circuit.cx(QubitId(1), QubitId(0)).unwrap();
+
+        let dag = optimize(&circuit);
+        assert_eq!(dag.num_ops(), 0);
+    }
+
+    #[test]
+    fn test_x_propagates_through_cx() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.x(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.x(QubitId(0)).unwrap();
+
+        let dag = optimize(&circuit);
+        assert_eq!(gate_names(&dag), ["cx", "x"]);
+    }
+
+    #[test]
+    fn test_random_circuits_stay_equivalent() {
+        let mut rng = SplitMix64::new(0x2545_f491_4f6c_dd1d);
+        let mut reduced = 0;
+        for _ in 0..200 {
+            let mut circuit = Circuit::with_size("random", 3, 0);
+            for _ in 0..12 {
+                let a = rng.index(3);
+                let b = (a + 1 + rng.index(2)) % 3;
+                let (a, b) = (QubitId(a as u32), QubitId(b as u32));
+                match rng.index(8) {
+                    0 => circuit.h(a),
+                    1 => circuit.x(a),
+                    2 => circuit.z(a),
+                    3 => circuit.t(a),
+                    4 => circuit.tdg(a),
+                    5 => circuit.cz(a, b),
+                    6 => circuit.rz(0.25 * rng.index(4) as f64, a),
+                    _ => circuit.cx(a, b),
+                }
+                .unwrap();
+            }
+            let before = circuit.dag().num_ops();
+            if optimize(&circuit).num_ops() < before {
+                reduced += 1;
+            }
+        }
+        assert!(reduced > 50, "only {reduced} circuits reduced");
+    }
+
+    #[test]
+    fn test_symbolic_angles_untouched() {
+        let theta = ParameterExpression::symbol("theta");
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.rz(theta, QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+        TemplateOptimization
+            .run(&mut dag, &mut PropertySet::new())
+            .unwrap();
+        assert_eq!(dag.num_ops(), 4);
+    }
+}
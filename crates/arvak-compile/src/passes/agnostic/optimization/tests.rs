@@ -2,13 +2,13 @@
 
 use std::f64::consts::PI;
 
-use arvak_ir::Circuit;
-use arvak_ir::qubit::QubitId;
+use arvak_ir::qubit::{ClbitId, QubitId};
+use arvak_ir::{Circuit, SplitMix64};
 
 use crate::pass::Pass;
 use crate::property::PropertySet;
 
-use super::{CancelCX, CommutativeCancellation, Optimize1qGates};
+use super::{CancelCX, CommutativeCancellation, Optimize1qGates, PhasePolynomialOptimization};
 
 #[test]
 fn test_optimize_1q_hh_cancels() {
@@ -121,6 +121,34 @@ fn test_commutative_rz_cancellation() {
     assert!(dag.num_ops() <= 1);
 }
 
+#[test]
+fn test_commutative_rz_merge_through_cx_control() {
+    // Rz on the control commutes with CX; Rz on the target does not.
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.rz(PI / 4.0, QubitId(0)).unwrap();
+    circuit.rz(PI / 4.0, QubitId(1)).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.cz(QubitId(0), QubitId(1)).unwrap();
+    circuit.rz(PI / 4.0, QubitId(0)).unwrap();
+    circuit.rz(PI / 4.0, QubitId(1)).unwrap();
+    let before = circuit.to_matrix().unwrap();
+    let mut dag = circuit.into_dag();
+
+    let mut props = PropertySet::new();
+    CommutativeCancellation::new()
+        .run(&mut dag, &mut props)
+        .unwrap();
+
+    assert_eq!(dag.num_ops(), 5);
+    let after = dag.to_matrix().unwrap();
+    assert!(
+        before
+            .iter()
+            .zip(&after)
+            .all(|(a, b)| (a - b).norm() < 1e-9)
+    );
+}
+
 #[test]
 fn test_resource_noise_blocks_optimization() {
     use arvak_ir::noise::NoiseModel;
@@ -286,3 +314,345 @@ fn test_zyz_decomposition_roundtrip() {
         );
     }
 }
+
+// ========================================================================
+// Classically conditioned gates are optimization barriers
+// ========================================================================
+
+#[test]
+fn test_optimize_1q_conditioned_gate_breaks_run() {
+    // H · X(if c0) · H must not fuse: the X only runs on some shots.
+    let mut circuit = Circuit::with_size("test", 1, 1);
+    circuit.h(QubitId(0)).unwrap();
+    circuit.x(QubitId(0)).unwrap();
+    circuit.conditioned_on(ClbitId(0), 1).unwrap();
+    circuit.h(QubitId(0)).unwrap();
+    let mut dag = circuit.into_dag();
+
+    let mut props = PropertySet::new();
+    Optimize1qGates::new().run(&mut dag, &mut props).unwrap();
+
+    assert_eq!(dag.num_ops(), 3);
+    assert_eq!(
+        dag.topological_ops()
+            .filter(|(_, inst)| inst.is_conditional())
+            .count(),
+        1,
+        "conditioned X must survive unchanged"
+    );
+}
+
+#[test]
+fn test_cancel_cx_conditioned_not_cancelled() {
+    // CX · CX(if c0) · CX: the conditioned CX may not run, so nothing cancels.
+    let mut circuit = Circuit::with_size("test", 2, 1);
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.conditioned_on(ClbitId(0), 1).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    let mut dag = circuit.into_dag();
+
+    let mut props = PropertySet::new();
+    CancelCX::new().run(&mut dag, &mut props).unwrap();
+
+    assert_eq!(dag.num_ops(), 3);
+}
+
+// ========================================================================
+// Commutation-aware cancellation of general gate pairs
+// ========================================================================
+
+/// Run `CommutativeCancellation` and check the result against the input
+/// unitary (up to global phase).
+fn commutative_cancel(circuit: &Circuit) -> arvak_ir::CircuitDag {
+    let expected = circuit.to_matrix().unwrap();
+    let mut dag = circuit.clone().into_dag();
+    CommutativeCancellation::new()
+        .run(&mut dag, &mut PropertySet::new())
+        .unwrap();
+    let actual = dag.to_matrix().unwrap();
+    let (i, _) = expected
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.norm().total_cmp(&b.1.norm()))
+        .unwrap();
+    let phase = actual[i] / expected[i];
+    assert!(
+        actual
+            .iter()
+            .zip(&expected)
+            .all(|(a, e)| (a - e * phase).norm() < 1e-9)
+    );
+    dag
+}
+
+#[test]
+fn test_commutative_cx_cancels_through_control_rotation() {
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.rz(0.4, QubitId(0)).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+    let dag = commutative_cancel(&circuit);
+    assert_eq!(dag.num_ops(), 1);
+}
+
+#[test]
+fn test_commutative_cz_cancels_reversed_through_phase() {
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.cz(QubitId(0), QubitId(1)).unwrap();
+    circuit.t(QubitId(1)).unwrap();
+    circuit.cz(QubitId(1), QubitId(0)).unwrap();
+
+    let dag = commutative_cancel(&circuit);
+    assert_eq!(dag.num_ops(), 1);
+}
+
+#[test]
+fn test_commutative_phase_gates_combine() {
+    // S·CZ·S on the same qubit → Z·CZ; T·T† cancels across the CX control.
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.s(QubitId(0)).unwrap();
+    circuit.cz(QubitId(0), QubitId(1)).unwrap();
+    circuit.s(QubitId(0)).unwrap();
+    circuit.t(QubitId(1)).unwrap();
+    circuit.cx(QubitId(1), QubitId(0)).unwrap();
+    circuit.tdg(QubitId(1)).unwrap();
+
+    let dag = commutative_cancel(&circuit);
+    let mut names: Vec<&str> = dag
+        .topological_ops()
+        .filter_map(|(_, inst)| inst.as_gate().map(arvak_ir::Gate::name))
+        .collect();
+    names.sort_unstable();
+    assert_eq!(names, ["cx", "cz", "z"]);
+}
+
+#[test]
+fn test_commutative_controlled_rotation_keeps_4pi_period() {
+    // CRz(π)·CRz(π) = CRz(2π) = Z on the control, not the identity.
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.crz(PI, QubitId(0), QubitId(1)).unwrap();
+    circuit.crz(PI, QubitId(0), QubitId(1)).unwrap();
+
+    let dag = commutative_cancel(&circuit);
+    assert_eq!(dag.num_ops(), 1);
+}
+
+#[test]
+fn test_commutative_symbolic_rotations_kept() {
+    let mut circuit = Circuit::with_size("test", 1, 0);
+    circuit
+        .rz(arvak_ir::ParameterExpression::symbol("a"), QubitId(0))
+        .unwrap();
+    circuit
+        .rz(arvak_ir::ParameterExpression::symbol("b"), QubitId(0))
+        .unwrap();
+    let mut dag = circuit.into_dag();
+    CommutativeCancellation::new()
+        .run(&mut dag, &mut PropertySet::new())
+        .unwrap();
+    assert_eq!(dag.num_ops(), 2);
+}
+
+#[test]
+fn test_commutative_reorder_reduces_depth() {
+    // Rz on q1 commutes with the CZ and moves into the first layer.
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.h(QubitId(0)).unwrap();
+    circuit.cz(QubitId(0), QubitId(1)).unwrap();
+    circuit.rz(0.3, QubitId(1)).unwrap();
+    assert_eq!(circuit.depth(), 3);
+
+    let dag = commutative_cancel(&circuit);
+    assert_eq!(dag.num_ops(), 3);
+    assert_eq!(dag.depth(), 2);
+}
+
+#[test]
+fn test_commutative_reorder_respects_classical_wires() {
+    // The conditioned X reads c0 and must stay after the measurement.
+    use arvak_ir::ClbitId;
+
+    let mut circuit = Circuit::with_size("test", 2, 1);
+    circuit.h(QubitId(0)).unwrap();
+    circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+    circuit
+        .x(QubitId(1))
+        .unwrap()
+        .conditioned_on(ClbitId(0), 1)
+        .unwrap();
+    let mut dag = circuit.into_dag();
+    CommutativeCancellation::new()
+        .run(&mut dag, &mut PropertySet::new())
+        .unwrap();
+    let order: Vec<bool> = dag
+        .topological_ops()
+        .map(|(_, inst)| inst.is_measure())
+        .collect();
+    assert_eq!(order, [false, true, false]);
+}
+
+#[test]
+fn test_commutative_random_circuits_stay_equivalent() {
+    let mut rng = SplitMix64::new(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..200 {
+        let mut circuit = Circuit::with_size("random", 3, 0);
+        for _ in 0..14 {
+            let a = rng.index(3);
+            let b = (a + 1 + rng.index(2)) % 3;
+            let (a, b) = (QubitId(a as u32), QubitId(b as u32));
+            let angle = PI / 4.0 * rng.index(8) as f64;
+            match rng.index(11) {
+                0 => circuit.h(a),
+                1 => circuit.x(a),
+                2 => circuit.s(a),
+                3 => circuit.tdg(a),
+                4 => circuit.sx(a),
+                5 => circuit.cz(a, b),
+                6 => circuit.rz(angle, a),
+                7 => circuit.rx(angle, a),
+                8 => circuit.crz(angle, a, b),
+                9 => circuit.rzz(angle, a, b),
+                _ => circuit.cx(a, b),
+            }
+            .unwrap();
+        }
+        commutative_cancel(&circuit);
+    }
+}
+
+// ========================================================================
+// Phase-polynomial rotation merging
+// ========================================================================
+
+/// Run `PhasePolynomialOptimization` and check the result against the
+/// input unitary, including the global phase.
+fn phase_fold(circuit: &Circuit) -> arvak_ir::CircuitDag {
+    let expected = circuit.to_matrix().unwrap();
+    let mut dag = circuit.clone().into_dag();
+    PhasePolynomialOptimization::new()
+        .run(&mut dag, &mut PropertySet::new())
+        .unwrap();
+    let actual = dag.to_matrix().unwrap();
+    assert!(
+        actual
+            .iter()
+            .zip(&expected)
+            .all(|(a, e)| (a - e).norm() < 1e-9)
+    );
+    dag
+}
+
+fn count_named(dag: &arvak_ir::CircuitDag, name: &str) -> usize {
+    dag.topological_ops()
+        .filter(|(_, inst)| inst.name() == name)
+        .count()
+}
+
+#[test]
+fn test_phase_polynomial_merges_equal_parities() {
+    // Both RZ act on x0 ⊕ x1.
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.rz(0.3, QubitId(1)).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+    circuit.rz(0.5, QubitId(1)).unwrap();
+    circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+    let dag = phase_fold(&circuit);
+    assert_eq!(count_named(&dag, "rz"), 1);
+    assert_eq!(dag.num_ops(), 5);
+}
+
+#[test]
+fn test_phase_polynomial_follows_swap_and_combines_t() {
+    let mut circuit = Circuit::with_size("test", 2, 0);
+    circuit.t(QubitId(0)).unwrap();
+    circuit.swap(QubitId(0), QubitId(1)).unwrap();
+    circuit.t(QubitId(1)).unwrap();
+
+    let dag = phase_fold(&circuit);
+    assert_eq!(count_named(&dag, "t"), 0);
+    assert_eq!(count_named(&dag, "s"), 1);
+}
+
+#[test]
+fn test_phase_polynomial_stops_at_hadamard() {
+    let mut circuit = Circuit::with_size("test", 1, 0);
+    circuit.t(QubitId(0)).unwrap();
+    circuit.h(QubitId(0)).unwrap();
+    circuit.t(QubitId(0)).unwrap();
+
+    let dag = phase_fold(&circuit);
+    assert_eq!(dag.num_ops(), 3);
+}
+
+#[test]
+fn test_phase_polynomial_removes_full_turns() {
+    let mut circuit = Circuit::with_size("test", 1, 0);
+    circuit.rz(0.0, QubitId(0)).unwrap();
+    circuit.rz(PI, QubitId(0)).unwrap();
+    circuit.rz(PI, QubitId(0)).unwrap();
+
+    let dag = phase_fold(&circuit);
+    assert_eq!(dag.num_ops(), 0);
+    assert!((dag.global_phase().abs() - PI).abs() < 1e-9);
+}
+
+#[test]
+fn test_phase_polynomial_collapses_trotter_steps() {
+    // Two Trotter steps of the commuting Hamiltonian Z0·Z1 + Z1·Z2: the
+    // second step's rotations fold into the first, and its CX ladders then
+    // cancel against the first step's.
+    let mut circuit = Circuit::with_size("trotter", 3, 0);
+    for _ in 0..2 {
+        for (a, b) in [(0, 1), (1, 2)] {
+            circuit.cx(QubitId(a), QubitId(b)).unwrap();
+            circuit.rz(0.2, QubitId(b)).unwrap();
+            circuit.cx(QubitId(a), QubitId(b)).unwrap();
+        }
+    }
+
+    let mut dag = phase_fold(&circuit);
+    assert_eq!(count_named(&dag, "rz"), 2);
+    assert_eq!(count_named(&dag, "cx"), 8);
+
+    CommutativeCancellation::new()
+        .run(&mut dag, &mut PropertySet::new())
+        .unwrap();
+    assert_eq!(count_named(&dag, "cx"), 4);
+}
+
+#[test]
+fn test_phase_polynomial_random_circuits_keep_unitary() {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |n: u64| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) % n
+    };
+    for _ in 0..200 {
+        let mut circuit = Circuit::with_size("random", 3, 0);
+        for _ in 0..16 {
+            let a = QubitId(next(3) as u32);
+            let b = QubitId(((u64::from(a.0) + 1 + next(2)) % 3) as u32);
+            let angle = PI / 8.0 * next(16) as f64;
+            match next(10) {
+                0 => circuit.h(a),
+                1 => circuit.t(a),
+                2 => circuit.s(a),
+                3 => circuit.p(angle, a),
+                4 => circuit.swap(a, b),
+                5 => circuit.cz(a, b),
+                6 | 7 => circuit.rz(angle, a),
+                8 => circuit.rzz(angle, a, b),
+                _ => circuit.cx(a, b),
+            }
+            .unwrap();
+        }
+        phase_fold(&circuit);
+    }
+}
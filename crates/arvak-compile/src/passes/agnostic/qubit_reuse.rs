@@ -0,0 +1,316 @@
+//! Reuse measured qubits through mid-circuit reset.
+//!
+//! A qubit whose last operation is a measurement is free afterwards: a
+//! reset returns it to |0⟩, and a qubit that has not been used yet can take
+//! its place. [`QubitReuse`] reschedules the circuit so that new qubits are
+//! started as late as possible, hands them freed wires, and inserts the
+//! resets. Deep sequential circuits (e.g. one measured ancilla per round)
+//! then fit devices with far fewer qubits than the circuit declares.
+//!
+//! By default only qubits that end in a measurement share wires, on either
+//! side of the reset. A qubit that is never measured is free after its last
+//! operation too: resetting it traces it out, which no measurement on the
+//! remaining qubits can tell apart. This only holds when measurement counts
+//! are all that is read from the circuit, so it is opt-in via
+//! [`QubitReuse::with_unmeasured_reuse`].
+//!
+//! Classical bits are unchanged, so measurement results keep their meaning.
+//! Run the pass before layout; it renumbers the qubits it keeps.
+
+use std::collections::BTreeSet;
+
+use arvak_ir::{CircuitDag, DagNode, Instruction, InstructionKind, NodeIndex, QubitId};
+use petgraph::Direction;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+
+/// Outcome of [`QubitReuse`], stored in the property set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QubitReuseResult {
+    /// New qubit for every qubit of the input circuit that has operations.
+    pub mapping: FxHashMap<QubitId, QubitId>,
+    /// Qubits in the input circuit.
+    pub qubits_before: usize,
+    /// Qubits after reuse.
+    pub qubits_after: usize,
+    /// Resets inserted to recycle measured qubits.
+    pub resets_inserted: usize,
+}
+
+/// Qubit reuse pass: map later qubits onto measured ones via reset.
+///
+/// Leaves the circuit unchanged when no qubit can be reused. Qubits without
+/// operations are dropped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QubitReuse {
+    /// Also move qubits whose last operation is not a measurement onto
+    /// shared wires.
+    reuse_unmeasured: bool,
+}
+
+impl QubitReuse {
+    /// Create a pass that recycles measured qubits only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also let qubits whose last operation is not a measurement give up
+    /// their wire after it, or take over a freed wire.
+    ///
+    /// Their final state is discarded, so only use this when the circuit's
+    /// result is its measurement counts, not its statevector.
+    #[must_use]
+    pub fn with_unmeasured_reuse(mut self, enabled: bool) -> Self {
+        self.reuse_unmeasured = enabled;
+        self
+    }
+}
+
+impl Pass for QubitReuse {
+    fn name(&self) -> &'static str {
+        "QubitReuse"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let ops: Vec<(NodeIndex, &Instruction)> = dag.topological_ops().collect();
+        let position: FxHashMap<NodeIndex, usize> = ops
+            .iter()
+            .enumerate()
+            .map(|(i, (node, _))| (*node, i))
+            .collect();
+
+        // Operation-level dependencies over all wires.
+        let graph = dag.graph();
+        let neighbours = |node: NodeIndex, direction: Direction| -> Vec<usize> {
+            let mut found: Vec<usize> = graph
+                .neighbors_directed(node, direction)
+                .filter(|n| matches!(graph[*n], DagNode::Op(_)))
+                .map(|n| position[&n])
+                .collect();
+            found.sort_unstable();
+            found.dedup();
+            found
+        };
+        let successors: Vec<Vec<usize>> = ops
+            .iter()
+            .map(|(node, _)| neighbours(*node, Direction::Outgoing))
+            .collect();
+        let mut pending: Vec<usize> = ops
+            .iter()
+            .map(|(node, _)| neighbours(*node, Direction::Incoming).len())
+            .collect();
+
+        // A qubit takes part in reuse if its last operation is a measurement,
+        // or unconditionally when discarding final states is allowed. It
+        // frees its wire after that last operation.
+        let mut last_op: FxHashMap<QubitId, usize> = FxHashMap::default();
+        for (i, (_, inst)) in ops.iter().enumerate() {
+            for &qubit in &inst.qubits {
+                last_op.insert(qubit, i);
+            }
+        }
+        let reusable: FxHashSet<QubitId> = last_op
+            .iter()
+            .filter(|(_, i)| {
+                self.reuse_unmeasured || matches!(ops[**i].1.kind, InstructionKind::Measure)
+            })
+            .map(|(&qubit, _)| qubit)
+            .collect();
+        let frees: FxHashSet<(usize, QubitId)> = reusable
+            .iter()
+            .map(|&qubit| (last_op[&qubit], qubit))
+            .collect();
+
+        let mut ready: BTreeSet<usize> = (0..ops.len()).filter(|&i| pending[i] == 0).collect();
+        let mut mapping: FxHashMap<QubitId, QubitId> = FxHashMap::default();
+        let mut free: BTreeSet<u32> = BTreeSet::new();
+        let mut next_qubit = 0u32;
+        let mut schedule: Vec<Instruction> = Vec::with_capacity(ops.len());
+        let mut resets_inserted = 0;
+
+        while let Some(i) = Self::pick(&ready, &ops, &mapping) {
+            ready.remove(&i);
+            let inst = ops[i].1;
+            for &qubit in &inst.qubits {
+                if mapping.contains_key(&qubit) {
+                    continue;
+                }
+                let reused = if reusable.contains(&qubit) {
+                    free.pop_first()
+                } else {
+                    None
+                };
+                let target = if let Some(reused) = reused {
+                    schedule.push(Instruction::reset(QubitId(reused)));
+                    resets_inserted += 1;
+                    reused
+                } else {
+                    next_qubit += 1;
+                    next_qubit - 1
+                };
+                mapping.insert(qubit, QubitId(target));
+            }
+            schedule.push(inst.remap_wires(&|q| mapping[&q], &|c| c));
+            for &qubit in &inst.qubits {
+                if frees.contains(&(i, qubit)) {
+                    free.insert(mapping[&qubit].0);
+                }
+            }
+            for &succ in &successors[i] {
+                pending[succ] -= 1;
+                if pending[succ] == 0 {
+                    ready.insert(succ);
+                }
+            }
+        }
+
+        let qubits_before = dag.num_qubits();
+        debug!(
+            "QubitReuse: {} -> {} qubits, {} resets",
+            qubits_before, next_qubit, resets_inserted
+        );
+        if resets_inserted == 0 {
+            return Ok(());
+        }
+
+        let mut new_dag = CircuitDag::new();
+        for q in 0..next_qubit {
+            new_dag.add_qubit(QubitId(q));
+        }
+        for c in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(c);
+        }
+        for inst in schedule {
+            new_dag.apply(inst).map_err(CompileError::Ir)?;
+        }
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+        *dag = new_dag;
+
+        properties.insert(QubitReuseResult {
+            mapping,
+            qubits_before,
+            qubits_after: next_qubit as usize,
+            resets_inserted,
+        });
+        Ok(())
+    }
+}
+
+impl QubitReuse {
+    /// Next operation to schedule: the earliest ready one needing the fewest
+    /// new qubits, preferring operations that continue on active qubits.
+    fn pick(
+        ready: &BTreeSet<usize>,
+        ops: &[(NodeIndex, &Instruction)],
+        mapping: &FxHashMap<QubitId, QubitId>,
+    ) -> Option<usize> {
+        ready.iter().copied().min_by_key(|&i| {
+            let qubits = &ops[i].1.qubits;
+            let new = qubits.iter().filter(|q| !mapping.contains_key(q)).count();
+            let continues = new < qubits.len();
+            (new, !continues, i)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::{Circuit, ClbitId};
+
+    fn count_resets(dag: &CircuitDag) -> usize {
+        dag.topological_ops()
+            .filter(|(_, inst)| inst.is_reset())
+            .count()
+    }
+
+    #[test]
+    fn test_sequential_circuit_uses_one_qubit() {
+        let mut circuit = Circuit::with_size("rounds", 4, 4);
+        for i in 0..4 {
+            circuit.h(QubitId(i)).unwrap();
+            circuit.measure(QubitId(i), ClbitId(i)).unwrap();
+        }
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        QubitReuse::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(dag.num_qubits(), 1);
+        assert_eq!(dag.num_clbits(), 4);
+        assert_eq!(count_resets(&dag), 3);
+        let result = props.get::<QubitReuseResult>().unwrap();
+        assert_eq!((result.qubits_before, result.qubits_after), (4, 1));
+        assert!(result.mapping.values().all(|&q| q == QubitId(0)));
+    }
+
+    #[test]
+    fn test_reuse_after_entangled_pair() {
+        // Bell pair on q0, q1 measured, then q2 reuses one of them.
+        let mut circuit = Circuit::with_size("partial", 3, 3);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(1)).unwrap();
+        circuit.x(QubitId(2)).unwrap();
+        circuit.measure(QubitId(2), ClbitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        QubitReuse::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(dag.num_qubits(), 2);
+        assert_eq!(count_resets(&dag), 1);
+        dag.verify_integrity().unwrap();
+    }
+
+    #[test]
+    fn test_no_reuse_leaves_circuit_unchanged() {
+        // Every qubit is still in use when the first one could be measured.
+        let mut circuit = Circuit::with_size("ring", 3, 3);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(2), QubitId(0)).unwrap();
+        circuit.measure_all().unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        QubitReuse::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(dag.num_qubits(), 3);
+        assert_eq!(count_resets(&dag), 0);
+        assert!(props.get::<QubitReuseResult>().is_none());
+    }
+
+    #[test]
+    fn test_unmeasured_reuse_is_opt_in() {
+        // q0 is done after its H but never measured.
+        let mut circuit = Circuit::with_size("discard", 2, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.x(QubitId(1)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(0)).unwrap();
+
+        let mut dag = circuit.clone().into_dag();
+        let mut props = PropertySet::new();
+        QubitReuse::new().run(&mut dag, &mut props).unwrap();
+        assert_eq!(dag.num_qubits(), 2);
+        assert!(props.get::<QubitReuseResult>().is_none());
+
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        QubitReuse::new()
+            .with_unmeasured_reuse(true)
+            .run(&mut dag, &mut props)
+            .unwrap();
+        assert_eq!(dag.num_qubits(), 1);
+        assert_eq!(count_resets(&dag), 1);
+        dag.verify_integrity().unwrap();
+    }
+}
@@ -5,19 +5,20 @@
 //! `SabreRouting` does not track them at all. Following the standard
 //! pipeline design (cf. Qiskit's `Unroll3qOrMore`), everything wider than
 //! two qubits is expanded into one- and two-qubit standard gates before
-//! layout/routing runs.
+//! layout/routing runs. Gates inside if/else and loop bodies are expanded
+//! too, since routing recurses into them.
 //!
 //! Found by property-based fuzzing (2026-07-08): a bare `ccx` compiled at
 //! optimization level 0 produced CX gates on uncoupled qubit pairs.
 
-use arvak_ir::{CircuitDag, GateKind, InstructionKind};
+use arvak_ir::{CircuitDag, GateKind, Instruction, InstructionKind};
 
 use crate::error::{CompileError, CompileResult};
 use crate::pass::{Pass, PassKind};
 use crate::passes::target::decompose_to_simpler;
 use crate::property::PropertySet;
 
-/// Expand >=3-qubit standard gates into 1q/2q gates.
+/// Expand >=3-qubit standard and modified gates into 1q/2q gates.
 pub struct Unroll3q;
 
 impl Pass for Unroll3q {
@@ -30,9 +31,13 @@ impl Pass for Unroll3q {
     }
 
     fn run(&self, dag: &mut CircuitDag, _properties: &mut PropertySet) -> CompileResult<()> {
-        let needs_unroll = dag.topological_ops().any(|(_, inst)| {
-            matches!(&inst.kind, InstructionKind::Gate(_)) && inst.qubits.len() >= 3
-        });
+        let mut needs_unroll = false;
+        for (_, inst) in dag.topological_ops() {
+            inst.walk(&mut |inst| {
+                needs_unroll |=
+                    matches!(&inst.kind, InstructionKind::Gate(_)) && inst.qubits.len() >= 3;
+            });
+        }
         if !needs_unroll {
             return Ok(());
         }
@@ -45,38 +50,12 @@ impl Pass for Unroll3q {
             new_dag.add_clbit(c);
         }
 
+        let mut ops = Vec::new();
         for (_, inst) in dag.topological_ops() {
-            let wide_standard = match &inst.kind {
-                InstructionKind::Gate(g) if inst.qubits.len() >= 3 => match &g.kind {
-                    GateKind::Standard(std_gate) => Some(std_gate.clone()),
-                    GateKind::Custom(_) => {
-                        return Err(CompileError::PassFailed {
-                            name: "Unroll3q".into(),
-                            reason: format!(
-                                "cannot decompose {}-qubit custom gate '{}' for routing",
-                                inst.qubits.len(),
-                                g.name()
-                            ),
-                        });
-                    }
-                },
-                _ => None,
-            };
-
-            if let Some(std_gate) = wide_standard {
-                let steps = decompose_to_simpler(&std_gate, &inst.qubits).ok_or_else(|| {
-                    CompileError::PassFailed {
-                        name: "Unroll3q".into(),
-                        reason: format!("no decomposition for wide gate '{std_gate:?}'"),
-                    }
-                })?;
-                for step in steps {
-                    debug_assert!(step.qubits.len() <= 2);
-                    new_dag.apply(step).map_err(CompileError::Ir)?;
-                }
-            } else {
-                new_dag.apply(inst.clone()).map_err(CompileError::Ir)?;
-            }
+            unroll(&mut ops, inst, inst)?;
+        }
+        for inst in ops {
+            new_dag.apply(inst).map_err(CompileError::Ir)?;
         }
 
         new_dag.set_global_phase(dag.global_phase());
@@ -85,3 +64,67 @@ impl Pass for Unroll3q {
         Ok(())
     }
 }
+
+/// Append `inst` to `out`, decomposing it first if it is a gate on three or
+/// more qubits, or unrolling its bodies if it is a control-flow block.
+/// Gates it decomposes into carry the metadata of `source`.
+fn unroll(
+    out: &mut Vec<Instruction>,
+    inst: &Instruction,
+    source: &Instruction,
+) -> CompileResult<()> {
+    if inst.is_control_flow() {
+        let mut block_inst = inst.clone();
+        for block in block_inst.blocks_mut() {
+            let mut unrolled = Vec::with_capacity(block.len());
+            for nested in block.iter() {
+                unroll(&mut unrolled, nested, nested)?;
+            }
+            *block = unrolled;
+        }
+        out.push(block_inst);
+        return Ok(());
+    }
+    let gate = match &inst.kind {
+        InstructionKind::Gate(g) if inst.qubits.len() >= 3 => g,
+        _ => {
+            out.push(inst.clone());
+            return Ok(());
+        }
+    };
+
+    match &gate.kind {
+        GateKind::Standard(std_gate) => {
+            let steps = decompose_to_simpler(std_gate, &inst.qubits).ok_or_else(|| {
+                CompileError::PassFailed {
+                    name: "Unroll3q".into(),
+                    reason: format!("no decomposition for wide gate '{std_gate:?}'"),
+                }
+            })?;
+            for step in steps {
+                debug_assert!(step.qubits.len() <= 2);
+                out.push(step.with_metadata_from(source));
+            }
+        }
+        GateKind::Custom(_) => {
+            return Err(CompileError::PassFailed {
+                name: "Unroll3q".into(),
+                reason: format!(
+                    "cannot decompose {}-qubit custom gate '{}' for routing",
+                    inst.qubits.len(),
+                    gate.name()
+                ),
+            });
+        }
+        // Expanded into plain gates, which are unrolled in turn.
+        GateKind::Modified(modified) => {
+            for mut step in modified.expand(&inst.qubits)? {
+                if let Some(g) = step.gate_mut() {
+                    g.condition.clone_from(&gate.condition);
+                }
+                unroll(out, &step.with_metadata_from(source), source)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -24,7 +24,7 @@
 use num_complex::Complex64;
 use tracing::warn;
 
-use arvak_ir::{CircuitDag, InstructionKind, QubitId, StandardGate};
+use arvak_ir::{CircuitDag, GateKind, Instruction, InstructionKind, QubitId};
 
 use crate::error::{CompileError, CompileResult};
 use crate::pass::{Pass, PassKind};
@@ -127,6 +127,15 @@ impl Pass for VerifyCompilation {
             return Ok(());
         }
 
+        // Branches depend on measurement outcomes, which a single
+        // statevector cannot represent.
+        let has_control_flow =
+            |d: &CircuitDag| d.topological_ops().any(|(_, inst)| inst.is_control_flow());
+        if has_control_flow(&self.before) || has_control_flow(dag) {
+            warn!("VerifyCompilation: circuit has control flow, skipping verification");
+            return Ok(());
+        }
+
         // If initial_layout exists, routing has remapped the DAG to physical
         // qubit IDs. We must permute the input/output accordingly.
         let has_layout = properties.initial_layout.is_some();
@@ -279,27 +288,27 @@ fn simulate_dag(
     let dim = 1 << num_qubits;
     let mut sv = vec![Complex64::new(0.0, 0.0); dim];
     sv[input_state % dim] = Complex64::new(1.0, 0.0);
+    simulate_ops(
+        dag.topological_ops().map(|(_, inst)| inst),
+        num_qubits,
+        &mut sv,
+    )?;
+    Ok(sv)
+}
 
-    for (_, inst) in dag.topological_ops() {
+/// Apply `ops` to the statevector `sv` of `num_qubits` qubits.
+///
+/// Measurements, resets and other non-unitary operations are skipped.
+pub(crate) fn simulate_ops<'a>(
+    ops: impl IntoIterator<Item = &'a Instruction>,
+    num_qubits: usize,
+    sv: &mut [Complex64],
+) -> CompileResult<()> {
+    for inst in ops {
         match &inst.kind {
-            InstructionKind::Gate(gate) => match &gate.kind {
-                arvak_ir::GateKind::Standard(std_gate) => {
-                    apply_standard_gate(&mut sv, num_qubits, std_gate, &inst.qubits)?;
-                }
-                arvak_ir::GateKind::Custom(custom) => {
-                    if let Some(ref matrix) = custom.matrix {
-                        apply_custom_unitary(&mut sv, num_qubits, matrix, &inst.qubits)?;
-                    } else {
-                        return Err(CompileError::PassFailed {
-                            name: "VerifyCompilation".into(),
-                            reason: format!(
-                                "custom gate '{}' has no unitary matrix for verification",
-                                custom.name
-                            ),
-                        });
-                    }
-                }
-            },
+            InstructionKind::Gate(gate) => {
+                apply_gate(sv, num_qubits, &gate.kind, &inst.qubits)?;
+            }
             InstructionKind::Barrier | InstructionKind::Delay { .. } => {
                 // No-ops for simulation.
             }
@@ -312,374 +321,114 @@ fn simulate_dag(
             InstructionKind::NoiseChannel { .. } => {
                 // Skip noise channels for equivalence checking.
             }
+            InstructionKind::IfElse { .. } | InstructionKind::WhileLoop { .. } => {
+                return Err(CompileError::PassFailed {
+                    name: "VerifyCompilation".into(),
+                    reason: format!("'{}' cannot be simulated as a statevector", inst.name()),
+                });
+            }
         }
     }
 
-    Ok(sv)
+    Ok(())
 }
 
-/// Apply a standard gate to the statevector.
-#[allow(clippy::too_many_lines)]
-fn apply_standard_gate(
+/// Apply a gate to the statevector; modified gates are expanded first.
+///
+/// Matrices come from the gate matrix registry in `arvak_ir`
+/// ([`StandardGate::matrix`](arvak_ir::StandardGate::matrix)).
+fn apply_gate(
     sv: &mut [Complex64],
     num_qubits: usize,
-    gate: &StandardGate,
+    kind: &GateKind,
     qubits: &[QubitId],
 ) -> CompileResult<()> {
-    match gate {
-        // Single-qubit gates.
-        StandardGate::I => {}
-        StandardGate::X => apply_1q(sv, num_qubits, qubits[0].0, &PAULI_X),
-        StandardGate::Y => apply_1q(sv, num_qubits, qubits[0].0, &PAULI_Y),
-        StandardGate::Z => apply_1q(sv, num_qubits, qubits[0].0, &PAULI_Z),
-        StandardGate::H => apply_1q(sv, num_qubits, qubits[0].0, &HADAMARD),
-        StandardGate::S => {
-            let m = [
-                Complex64::new(1.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 1.0),
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::Sdg => {
-            let m = [
-                Complex64::new(1.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, -1.0),
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::T => {
-            let phase = Complex64::from_polar(1.0, std::f64::consts::FRAC_PI_4);
-            let m = [
-                Complex64::new(1.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                phase,
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::Tdg => {
-            let phase = Complex64::from_polar(1.0, -std::f64::consts::FRAC_PI_4);
-            let m = [
-                Complex64::new(1.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                phase,
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::SX => {
-            let half = Complex64::new(0.5, 0.0);
-            let half_i = Complex64::new(0.0, 0.5);
-            let m = [half + half_i, half - half_i, half - half_i, half + half_i];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::SXdg => {
-            let half = Complex64::new(0.5, 0.0);
-            let half_i = Complex64::new(0.0, 0.5);
-            let m = [half - half_i, half + half_i, half + half_i, half - half_i];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::Rx(theta) => {
-            let t = theta.as_f64().ok_or_else(|| CompileError::PassFailed {
+    let matrix = match kind {
+        GateKind::Standard(std_gate) => {
+            std_gate.matrix().map_err(|_| CompileError::PassFailed {
                 name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in Rx, cannot verify".into(),
-            })?;
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = Complex64::new(0.0, -(t / 2.0).sin());
-            let m = [c, s, s, c];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::Ry(theta) => {
-            let t = theta.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in Ry, cannot verify".into(),
-            })?;
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = Complex64::new((t / 2.0).sin(), 0.0);
-            let m = [c, -s, s, c];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::Rz(theta) => {
-            let t = theta.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in Rz, cannot verify".into(),
-            })?;
-            let m = [
-                Complex64::from_polar(1.0, -t / 2.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::from_polar(1.0, t / 2.0),
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::P(lambda) => {
-            let l = lambda.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in P, cannot verify".into(),
-            })?;
-            let m = [
-                Complex64::new(1.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::from_polar(1.0, l),
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::U(theta, phi, lambda) => {
-            let t = theta.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in U, cannot verify".into(),
-            })?;
-            let p = phi.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in U, cannot verify".into(),
-            })?;
-            let l = lambda.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in U, cannot verify".into(),
-            })?;
-            let c = (t / 2.0).cos();
-            let s = (t / 2.0).sin();
-            let m = [
-                Complex64::new(c, 0.0),
-                -Complex64::from_polar(s, l),
-                Complex64::from_polar(s, p),
-                Complex64::from_polar(c, p + l),
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-        StandardGate::PRX(theta, phi) => {
-            let t = theta.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in PRX, cannot verify".into(),
-            })?;
-            let p = phi.as_f64().ok_or_else(|| CompileError::PassFailed {
-                name: "VerifyCompilation".into(),
-                reason: "symbolic parameter in PRX, cannot verify".into(),
-            })?;
-            // PRX(θ, φ) = Rz(φ) · Rx(θ) · Rz(-φ)
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = (t / 2.0).sin();
-            let m = [
-                c,
-                Complex64::new(0.0, -s) * Complex64::from_polar(1.0, -p),
-                Complex64::new(0.0, -s) * Complex64::from_polar(1.0, p),
-                c,
-            ];
-            apply_1q(sv, num_qubits, qubits[0].0, &m);
-        }
-
-        // Two-qubit gates.
-        StandardGate::CX => apply_cx(sv, num_qubits, qubits[0].0, qubits[1].0),
-        StandardGate::CY => {
-            // CY = |0⟩⟨0| ⊗ I + |1⟩⟨1| ⊗ Y
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &PAULI_Y);
-        }
-        StandardGate::CZ => {
-            // CZ = |0⟩⟨0| ⊗ I + |1⟩⟨1| ⊗ Z
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &PAULI_Z);
-        }
-        StandardGate::CH => {
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &HADAMARD);
-        }
-        StandardGate::Swap => apply_swap(sv, num_qubits, qubits[0].0, qubits[1].0),
-        StandardGate::ECR => {
-            // ECR in |q0,q1⟩ basis (q0=MSB), matching gate.rs definition:
-            // ECR = (1/√2) [[0, 0, 1, i], [0, 0, i, 1], [1, -i, 0, 0], [-i, 1, 0, 0]]
-            let s = 1.0 / 2.0_f64.sqrt();
-            let ecr = [
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(s, 0.0),
-                Complex64::new(0.0, s),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, s),
-                Complex64::new(s, 0.0),
-                Complex64::new(s, 0.0),
-                Complex64::new(0.0, -s),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, -s),
-                Complex64::new(s, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-            ];
-            apply_2q_unitary(sv, num_qubits, qubits[0].0, qubits[1].0, &ecr);
-        }
-        StandardGate::ISwap => {
-            // |01⟩ → i|10⟩, |10⟩ → i|01⟩ (symmetric in qubit order).
-            let z = Complex64::new(0.0, 0.0);
-            let o = Complex64::new(1.0, 0.0);
-            let i = Complex64::new(0.0, 1.0);
-            let m = [o, z, z, z, z, z, i, z, z, i, z, z, z, z, z, o];
-            apply_2q_unitary(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        }
-        StandardGate::CRx(theta) => {
-            let t = param_f64(theta, "CRx")?;
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = Complex64::new(0.0, -(t / 2.0).sin());
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &[c, s, s, c]);
-        }
-        StandardGate::CRy(theta) => {
-            let t = param_f64(theta, "CRy")?;
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = Complex64::new((t / 2.0).sin(), 0.0);
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &[c, -s, s, c]);
-        }
-        StandardGate::CRz(theta) => {
-            let t = param_f64(theta, "CRz")?;
-            let m = [
-                Complex64::from_polar(1.0, -t / 2.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::from_polar(1.0, t / 2.0),
-            ];
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        }
-        StandardGate::CP(lambda) => {
-            let l = param_f64(lambda, "CP")?;
-            let m = [
-                Complex64::new(1.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::new(0.0, 0.0),
-                Complex64::from_polar(1.0, l),
-            ];
-            apply_controlled_1q(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        }
-        StandardGate::RXX(theta) => {
-            let t = param_f64(theta, "RXX")?;
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = Complex64::new(0.0, -(t / 2.0).sin());
-            let z = Complex64::new(0.0, 0.0);
-            // exp(-i θ/2 X⊗X), symmetric in qubit order.
-            let m = [c, z, z, s, z, c, s, z, z, s, c, z, s, z, z, c];
-            apply_2q_unitary(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        }
-        StandardGate::RYY(theta) => {
-            let t = param_f64(theta, "RYY")?;
-            let c = Complex64::new((t / 2.0).cos(), 0.0);
-            let s = Complex64::new(0.0, (t / 2.0).sin());
-            let ns = -s;
-            let z = Complex64::new(0.0, 0.0);
-            // exp(-i θ/2 Y⊗Y): the |00⟩↔|11⟩ block gets +i sin, |01⟩↔|10⟩ gets −i sin.
-            let m = [c, z, z, s, z, c, ns, z, z, ns, c, z, s, z, z, c];
-            apply_2q_unitary(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        }
-        StandardGate::RZZ(theta) => {
-            let t = param_f64(theta, "RZZ")?;
-            let e_neg = Complex64::from_polar(1.0, -t / 2.0);
-            let e_pos = Complex64::from_polar(1.0, t / 2.0);
-            let z = Complex64::new(0.0, 0.0);
-            // exp(-i θ/2 Z⊗Z) = diag(e^{-iθ/2}, e^{iθ/2}, e^{iθ/2}, e^{-iθ/2}).
-            let m = [
-                e_neg, z, z, z, z, e_pos, z, z, z, z, e_pos, z, z, z, z, e_neg,
-            ];
-            apply_2q_unitary(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        }
-
-        // Three-qubit gates.
-        StandardGate::CCX => {
-            let c1 = 1usize << qubits[0].0;
-            let c2 = 1usize << qubits[1].0;
-            let tgt = 1usize << qubits[2].0;
-            let dim = 1usize << num_qubits;
-            for i in 0..dim {
-                if i & c1 != 0 && i & c2 != 0 && i & tgt == 0 {
-                    sv.swap(i, i | tgt);
-                }
-            }
+                reason: format!("symbolic parameter in {}, cannot verify", std_gate.name()),
+            })?
         }
-        StandardGate::CSwap => {
-            let ctrl = 1usize << qubits[0].0;
-            let m1 = 1usize << qubits[1].0;
-            let m2 = 1usize << qubits[2].0;
-            let dim = 1usize << num_qubits;
-            for i in 0..dim {
-                if i & ctrl != 0 && i & m1 != 0 && i & m2 == 0 {
-                    sv.swap(i, (i & !m1) | m2);
+        GateKind::Custom(custom) => custom.matrix().map_err(|_| CompileError::PassFailed {
+            name: "VerifyCompilation".into(),
+            reason: format!(
+                "custom gate '{}' has no unitary matrix for verification",
+                custom.name
+            ),
+        })?,
+        GateKind::Modified(modified) => {
+            for step in modified.expand(qubits)? {
+                if let InstructionKind::Gate(gate) = &step.kind {
+                    apply_gate(sv, num_qubits, &gate.kind, &step.qubits)?;
                 }
             }
+            return Ok(());
         }
-    }
-
-    Ok(())
+    };
+    apply_unitary(sv, num_qubits, &matrix, qubits)
 }
 
-/// Extract a concrete f64 from a gate parameter, or fail verification.
-fn param_f64(p: &arvak_ir::ParameterExpression, gate_name: &str) -> CompileResult<f64> {
-    p.as_f64().ok_or_else(|| CompileError::PassFailed {
-        name: "VerifyCompilation".into(),
-        reason: format!("symbolic parameter in {gate_name}, cannot verify"),
-    })
-}
-
-/// Apply a custom unitary matrix to the statevector.
-fn apply_custom_unitary(
+/// Apply a `2^k × 2^k` unitary to `k` qubits, the first most significant.
+fn apply_unitary(
     sv: &mut [Complex64],
     num_qubits: usize,
     matrix: &[Complex64],
     qubits: &[QubitId],
 ) -> CompileResult<()> {
-    if qubits.len() == 1 {
-        let m: [Complex64; 4] = matrix.try_into().map_err(|_| CompileError::PassFailed {
-            name: "VerifyCompilation".into(),
-            reason: "custom 1-qubit gate matrix must have 4 elements".into(),
-        })?;
-        apply_1q(sv, num_qubits, qubits[0].0, &m);
-        Ok(())
-    } else if qubits.len() == 2 {
-        let m: [Complex64; 16] = matrix.try_into().map_err(|_| CompileError::PassFailed {
+    let k = qubits.len();
+    let m = 1usize << k;
+    if matrix.len() != m * m {
+        return Err(CompileError::PassFailed {
             name: "VerifyCompilation".into(),
-            reason: "custom 2-qubit gate matrix must have 16 elements".into(),
-        })?;
-        apply_2q_unitary(sv, num_qubits, qubits[0].0, qubits[1].0, &m);
-        Ok(())
-    } else {
-        Err(CompileError::PassFailed {
-            name: "VerifyCompilation".into(),
-            reason: format!(
-                "custom gate on {} qubits not supported for verification",
-                qubits.len()
-            ),
-        })
+            reason: format!("{}-element gate matrix on {k} qubits", matrix.len()),
+        });
     }
+    match qubits {
+        [q] => apply_1q(
+            sv,
+            num_qubits,
+            q.0,
+            matrix.try_into().expect("length checked"),
+        ),
+        [q1, q2] => apply_2q_unitary(
+            sv,
+            num_qubits,
+            q1.0,
+            q2.0,
+            matrix.try_into().expect("length checked"),
+        ),
+        _ => {
+            // Local index bit k-1-j is qubit j.
+            let masks: Vec<usize> = qubits.iter().map(|q| 1usize << q.0).collect();
+            let target_mask = masks.iter().fold(0, |acc, mask| acc | mask);
+            let offsets: Vec<usize> = (0..m)
+                .map(|local| {
+                    (0..k)
+                        .filter(|j| local & (1 << (k - 1 - j)) != 0)
+                        .fold(0, |acc, j| acc | masks[j])
+                })
+                .collect();
+            let mut amplitudes = vec![Complex64::new(0.0, 0.0); m];
+            for base in (0..sv.len()).filter(|i| i & target_mask == 0) {
+                for (amplitude, offset) in amplitudes.iter_mut().zip(&offsets) {
+                    *amplitude = sv[base | offset];
+                }
+                for (row, offset) in offsets.iter().enumerate() {
+                    sv[base | offset] = matrix[row * m..(row + 1) * m]
+                        .iter()
+                        .zip(&amplitudes)
+                        .map(|(a, b)| a * b)
+                        .sum();
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 // ---- Statevector primitives ----
 
-const PAULI_X: [Complex64; 4] = [
-    Complex64::new(0.0, 0.0),
-    Complex64::new(1.0, 0.0),
-    Complex64::new(1.0, 0.0),
-    Complex64::new(0.0, 0.0),
-];
-
-const PAULI_Y: [Complex64; 4] = [
-    Complex64::new(0.0, 0.0),
-    Complex64::new(0.0, -1.0),
-    Complex64::new(0.0, 1.0),
-    Complex64::new(0.0, 0.0),
-];
-
-const PAULI_Z: [Complex64; 4] = [
-    Complex64::new(1.0, 0.0),
-    Complex64::new(0.0, 0.0),
-    Complex64::new(0.0, 0.0),
-    Complex64::new(-1.0, 0.0),
-];
-
-const HADAMARD: [Complex64; 4] = [
-    Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0),
-    Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0),
-    Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0),
-    Complex64::new(-std::f64::consts::FRAC_1_SQRT_2, 0.0),
-];
-
 /// Apply a 2x2 unitary to qubit `q` in the statevector.
 fn apply_1q(sv: &mut [Complex64], num_qubits: usize, q: u32, matrix: &[Complex64; 4]) {
     let q = q as usize;
@@ -698,65 +447,6 @@ fn apply_1q(sv: &mut [Complex64], num_qubits: usize, q: u32, matrix: &[Complex64
     }
 }
 
-/// Apply a CNOT gate (control, target).
-fn apply_cx(sv: &mut [Complex64], num_qubits: usize, control: u32, target: u32) {
-    let ctrl = control as usize;
-    let tgt = target as usize;
-    let dim = 1 << num_qubits;
-    let ctrl_mask = 1 << ctrl;
-    let tgt_mask = 1 << tgt;
-
-    for i in 0..dim {
-        if i & ctrl_mask != 0 && i & tgt_mask == 0 {
-            let j = i | tgt_mask;
-            sv.swap(i, j);
-        }
-    }
-}
-
-/// Apply a controlled single-qubit gate.
-fn apply_controlled_1q(
-    sv: &mut [Complex64],
-    num_qubits: usize,
-    control: u32,
-    target: u32,
-    matrix: &[Complex64; 4],
-) {
-    let ctrl = control as usize;
-    let tgt = target as usize;
-    let dim = 1 << num_qubits;
-    let ctrl_mask = 1 << ctrl;
-    let tgt_mask = 1 << tgt;
-
-    for i in 0..dim {
-        // Only act when control is |1⟩ and target is |0⟩.
-        if i & ctrl_mask != 0 && i & tgt_mask == 0 {
-            let j = i | tgt_mask;
-            let a = sv[i];
-            let b = sv[j];
-            sv[i] = matrix[0] * a + matrix[1] * b;
-            sv[j] = matrix[2] * a + matrix[3] * b;
-        }
-    }
-}
-
-/// Apply a SWAP gate.
-fn apply_swap(sv: &mut [Complex64], num_qubits: usize, q1: u32, q2: u32) {
-    let q1 = q1 as usize;
-    let q2 = q2 as usize;
-    let dim = 1 << num_qubits;
-    let mask1 = 1 << q1;
-    let mask2 = 1 << q2;
-
-    for i in 0..dim {
-        // Swap |...0...1...⟩ with |...1...0...⟩ where the bits differ at q1 and q2.
-        if i & mask1 != 0 && i & mask2 == 0 {
-            let j = (i & !mask1) | mask2;
-            sv.swap(i, j);
-        }
-    }
-}
-
 /// Apply a 4x4 unitary to a two-qubit gate (q1, q2) in the statevector.
 /// Matrix is in row-major order in the computational basis of (q1, q2):
 /// |00⟩, |01⟩, |10⟩, |11⟩.
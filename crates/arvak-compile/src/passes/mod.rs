@@ -9,10 +9,14 @@ pub mod target;
 
 // Re-exports for backward compatibility
 pub use agnostic::{
-    CancelCX, CommutativeCancellation, ConsolidateBlocks, MeasurementBarrierVerification,
-    OneQubitBasis, Optimize1qGates, Unroll3q, VerificationResult, VerifyCompilation,
+    CancelCX, CommutativeCancellation, ConsolidateBlocks, ErrorBudget, ErrorBudgetEntry,
+    ErrorBudgetReport, FinalMeasurements, MeasurementBarrierVerification, OneQubitBasis,
+    Optimize1qGates, PhasePolynomialOptimization, QubitReuse, QubitReuseResult,
+    RemoveFinalMeasurements, RestoreFinalMeasurements, TemplateOptimization, Unroll3q,
+    VerificationResult, VerifyCompilation,
 };
 pub use target::{
-    BasicRouting, BasisTranslation, DenseLayout, NeutralAtomRouting, SabreRouting, TrivialLayout,
-    ZoneAssignment,
+    BasicRouting, BasisTranslation, CliffordTSynthesis, DenseLayout, GateDirection,
+    NeutralAtomRouting, SabreRouting, SwapNetworkLayout, SwapNetworkPlan, SwapNetworkRouting,
+    TrivialLayout, UnitarySynthesis, VF2Layout, ZoneAssignment,
 };
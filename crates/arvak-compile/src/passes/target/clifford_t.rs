@@ -0,0 +1,367 @@
+//! Clifford+T synthesis for discrete gate sets.
+//!
+//! Fault-tolerant targets execute a discrete gate set, typically Clifford
+//! gates plus `T`. Rotations by multiples of π/4 translate exactly (see
+//! [`BasisTranslation`](super::BasisTranslation)); every other single-qubit
+//! gate is written as `Rz(α)·Ry(β)·Rz(γ)` and each rotation is approximated
+//! with [`approximate_rz`], using `Ry(β) = S·H·Rz(β)·H·Sdg`. Multi-qubit
+//! gates are first decomposed into CX and single-qubit gates.
+
+use num_complex::Complex64;
+
+use arvak_ir::{CircuitDag, GateKind, Instruction, QubitId, StandardGate};
+
+use crate::error::{CompileError, CompileResult};
+use crate::gridsynth::{MIN_EPSILON, approximate_rz};
+use crate::pass::{Pass, PassKind};
+use crate::property::{BasisGates, PropertySet};
+use crate::unitary::Unitary2x2;
+
+use super::translation::{decompose_to_simpler, translate_gate};
+use super::unitary_synthesis::{Entangler, synthesize_2q};
+
+/// A Y rotation smaller than this is dropped, merging the Z rotations
+/// around it.
+const IDENTITY_TOLERANCE: f64 = 1e-12;
+
+/// Compile circuits to a Clifford+T basis, approximating rotations that
+/// have no exact Clifford+T circuit.
+///
+/// Each approximated `Rz` or `Ry` rotation is within operator-norm distance
+/// `epsilon` of the original, up to global phase; a general single-qubit
+/// gate takes up to three rotations. Gates that are exact in Clifford+T
+/// (`H`, `S`, `T`, `Rz(π/2)`, `CZ`, ...) are translated exactly. A
+/// classical condition on a gate is carried over to every gate it is
+/// replaced with.
+///
+/// Runs when the basis is Clifford+T ([`BasisGates::is_clifford_t`]).
+pub struct CliffordTSynthesis {
+    /// Approximation error per rotation.
+    epsilon: f64,
+}
+
+impl CliffordTSynthesis {
+    /// Default approximation error per rotation.
+    pub const DEFAULT_EPSILON: f64 = 1e-6;
+
+    /// Create a Clifford+T synthesis pass with the default error.
+    pub fn new() -> Self {
+        Self {
+            epsilon: Self::DEFAULT_EPSILON,
+        }
+    }
+
+    /// Set the approximation error per rotation, in
+    /// `[MIN_EPSILON, 1)`. Smaller errors cost about `3·log₂(1/ε)` T gates
+    /// per rotation.
+    #[must_use]
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// The approximation error per rotation.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Replace `inst`, a gate outside `basis`, with gates of `basis`.
+    fn synthesize(
+        &self,
+        inst: &Instruction,
+        basis: &BasisGates,
+    ) -> CompileResult<Vec<Instruction>> {
+        match translate_gate(inst, basis) {
+            Err(CompileError::GateNotInBasis(_)) => {}
+            result => return result,
+        }
+        let gate = inst.as_gate().expect("only gates are synthesized");
+
+        if let [qubit] = inst.qubits[..] {
+            let matrix = kind_matrix(&gate.kind)?;
+            let unitary = Unitary2x2::new(matrix[0], matrix[1], matrix[2], matrix[3]);
+            return self.synthesize_1q(&unitary, qubit, basis);
+        }
+
+        let steps = match &gate.kind {
+            GateKind::Standard(std_gate) => decompose_to_simpler(std_gate, &inst.qubits),
+            GateKind::Custom(custom) if custom.definition.is_some() => {
+                Some(custom.expand(&inst.qubits)?)
+            }
+            GateKind::Custom(custom) => custom
+                .matrix
+                .as_deref()
+                .filter(|m| m.len() == 16 && inst.qubits.len() == 2)
+                .map(|m| synthesize_2q(m, inst.qubits[0], inst.qubits[1], Entangler::Cx)),
+            GateKind::Modified(modified) => Some(modified.expand(&inst.qubits)?),
+        }
+        .ok_or_else(|| CompileError::GateNotInBasis(gate.name().to_string()))?;
+
+        let mut out = Vec::new();
+        for step in steps {
+            if step.as_gate().is_some_and(|g| basis.contains(g.name())) {
+                out.push(step);
+            } else {
+                out.extend(self.synthesize(&step, basis)?);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Clifford+T gates for a single-qubit unitary, up to global phase.
+    fn synthesize_1q(
+        &self,
+        unitary: &Unitary2x2,
+        qubit: QubitId,
+        basis: &BasisGates,
+    ) -> CompileResult<Vec<Instruction>> {
+        // unitary = Rz(alpha)·Ry(beta)·Rz(gamma), so Rz(gamma) comes first.
+        let (alpha, beta, gamma, _) = unitary.zyz_decomposition();
+        let mut gates = Vec::new();
+        if beta.abs() < IDENTITY_TOLERANCE {
+            gates.extend(self.rz(alpha + gamma)?);
+        } else {
+            gates.extend(self.rz(gamma)?);
+            // Ry(β) = S·H·Rz(β)·H·Sdg
+            gates.extend([StandardGate::Sdg, StandardGate::H]);
+            gates.extend(self.rz(beta)?);
+            gates.extend([StandardGate::H, StandardGate::S]);
+            gates.extend(self.rz(alpha)?);
+        }
+
+        let mut out = Vec::new();
+        for gate in gates {
+            let inst = Instruction::single_qubit_gate(gate, qubit);
+            if basis.contains(inst.name()) {
+                out.push(inst);
+            } else {
+                out.extend(translate_gate(&inst, basis)?);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Clifford+T gates for `Rz(theta)`, up to global phase.
+    fn rz(&self, theta: f64) -> CompileResult<Vec<StandardGate>> {
+        Ok(approximate_rz(theta, self.epsilon)?.gates)
+    }
+}
+
+impl Default for CliffordTSynthesis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for CliffordTSynthesis {
+    fn name(&self) -> &'static str {
+        "CliffordTSynthesis"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let basis = properties
+            .basis_gates
+            .as_ref()
+            .ok_or(CompileError::MissingBasisGates)?;
+        if !(MIN_EPSILON..1.0).contains(&self.epsilon) {
+            return Err(CompileError::InvalidConfiguration(format!(
+                "Clifford+T approximation error {} is outside [{MIN_EPSILON}, 1)",
+                self.epsilon
+            )));
+        }
+
+        let mut new_dag = CircuitDag::new();
+        for qubit in dag.qubits().collect::<Vec<_>>() {
+            new_dag.add_qubit(qubit);
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+
+        for (_, inst) in dag.topological_ops() {
+            let Some(gate) = inst.as_gate().filter(|g| !basis.contains(g.name())) else {
+                new_dag.apply(inst.clone())?;
+                continue;
+            };
+            for mut step in self.synthesize(inst, basis)? {
+                if let Some(g) = step.gate_mut() {
+                    if gate.condition.is_some() {
+                        g.condition.clone_from(&gate.condition);
+                    }
+                }
+                new_dag.apply(step.with_metadata_from(inst))?;
+            }
+        }
+
+        *dag = new_dag;
+        Ok(())
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties
+            .basis_gates
+            .as_ref()
+            .is_some_and(BasisGates::is_clifford_t)
+    }
+}
+
+/// The unitary of a single-qubit gate, ignoring any classical condition.
+fn kind_matrix(kind: &GateKind) -> CompileResult<Vec<Complex64>> {
+    Ok(match kind {
+        GateKind::Standard(gate) => gate.matrix()?,
+        GateKind::Custom(custom) => custom.matrix()?,
+        GateKind::Modified(modified) => modified.matrix()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use arvak_ir::{Circuit, ClassicalCondition, ClbitId, Gate};
+
+    fn compile(circuit: Circuit, pass: &CliffordTSynthesis) -> CircuitDag {
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        props.basis_gates = Some(BasisGates::clifford_t());
+        pass.run(&mut dag, &mut props).unwrap();
+        dag
+    }
+
+    /// The single-qubit unitary of a circuit of gates in circuit order.
+    fn product(dag: &CircuitDag) -> Unitary2x2 {
+        dag.topological_ops()
+            .fold(Unitary2x2::identity(), |acc, (_, inst)| {
+                let Some(Gate {
+                    kind: GateKind::Standard(gate),
+                    ..
+                }) = inst.as_gate()
+                else {
+                    panic!("unexpected instruction {inst:?}");
+                };
+                Unitary2x2::from_gate(gate).unwrap() * acc
+            })
+    }
+
+    /// Operator-norm distance up to global phase, for 2×2 unitaries.
+    fn distance(a: &Unitary2x2, b: &Unitary2x2) -> f64 {
+        let trace: Complex64 = (a.dagger() * *b).data[0] + (a.dagger() * *b).data[3];
+        // For U = a†b with eigenvalues e^{i(φ±δ)}, |tr U| = 2|cos δ|.
+        let cos = (trace.norm() / 2.0).min(1.0);
+        (2.0 - 2.0 * cos).max(0.0).sqrt()
+    }
+
+    fn all_in_basis(dag: &CircuitDag) -> bool {
+        let basis = BasisGates::clifford_t();
+        dag.topological_ops()
+            .all(|(_, inst)| inst.as_gate().is_none_or(|g| basis.contains(g.name())))
+    }
+
+    #[test]
+    fn test_exact_gates_are_not_approximated() {
+        let mut circuit = Circuit::with_size("exact", 2, 0);
+        circuit
+            .rz(PI / 2.0, QubitId(0))
+            .unwrap()
+            .rx(PI / 4.0, QubitId(0))
+            .unwrap()
+            .cz(QubitId(0), QubitId(1))
+            .unwrap();
+        let dag = compile(circuit, &CliffordTSynthesis::new());
+
+        assert!(all_in_basis(&dag));
+        // S, then H·T·H, then H·CX·H.
+        assert_eq!(dag.num_ops(), 7);
+    }
+
+    #[test]
+    fn test_rotations_are_within_epsilon() {
+        for epsilon in [1e-2, 1e-5] {
+            let pass = CliffordTSynthesis::new().with_epsilon(epsilon);
+            for (gate, target) in [
+                (StandardGate::Rz(0.3.into()), Unitary2x2::rz(0.3)),
+                (StandardGate::Rx((-1.2).into()), Unitary2x2::rx(-1.2)),
+                (StandardGate::Ry(2.0.into()), Unitary2x2::ry(2.0)),
+            ] {
+                let mut circuit = Circuit::with_size("rotation", 1, 0);
+                circuit
+                    .dag_mut()
+                    .apply(Instruction::single_qubit_gate(gate, QubitId(0)))
+                    .unwrap();
+                let dag = compile(circuit, &pass);
+                assert!(all_in_basis(&dag));
+                assert!(distance(&product(&dag), &target) <= epsilon + 1e-9);
+            }
+        }
+
+        // A general single-qubit gate takes up to three rotations.
+        let epsilon = 1e-3;
+        let mut circuit = Circuit::with_size("u", 1, 0);
+        circuit.u(0.4, 1.1, -0.7, QubitId(0)).unwrap();
+        let dag = compile(circuit, &CliffordTSynthesis::new().with_epsilon(epsilon));
+        assert!(all_in_basis(&dag));
+        let target = Unitary2x2::u(0.4, 1.1, -0.7);
+        assert!(distance(&product(&dag), &target) <= 3.0 * epsilon + 1e-9);
+    }
+
+    #[test]
+    fn test_multi_qubit_gates_are_decomposed() {
+        let mut circuit = Circuit::with_size("multi", 3, 0);
+        circuit
+            .crz(0.5, QubitId(0), QubitId(1))
+            .unwrap()
+            .ccx(QubitId(0), QubitId(1), QubitId(2))
+            .unwrap()
+            .rzz(1.0, QubitId(1), QubitId(2))
+            .unwrap();
+        let dag = compile(circuit, &CliffordTSynthesis::new().with_epsilon(1e-3));
+        assert!(all_in_basis(&dag));
+    }
+
+    #[test]
+    fn test_condition_is_carried_over() {
+        let mut rz = Instruction::single_qubit_gate(StandardGate::Rz(0.3.into()), QubitId(0));
+        if let Some(gate) = rz.gate_mut() {
+            gate.condition = Some(ClassicalCondition::on_clbit(ClbitId(0), 1));
+        }
+        let mut circuit = Circuit::with_size("conditional", 1, 1);
+        circuit.dag_mut().apply(rz).unwrap();
+        let dag = compile(circuit, &CliffordTSynthesis::new().with_epsilon(1e-2));
+        assert!(dag.num_ops() > 1);
+        assert!(
+            dag.topological_ops()
+                .all(|(_, inst)| inst.as_gate().unwrap().condition.is_some())
+        );
+    }
+
+    #[test]
+    fn test_invalid_epsilon_is_rejected() {
+        let mut circuit = Circuit::with_size("rz", 1, 0);
+        circuit.rz(0.3, QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        props.basis_gates = Some(BasisGates::clifford_t());
+        let result = CliffordTSynthesis::new()
+            .with_epsilon(0.0)
+            .run(&mut dag, &mut props);
+        assert!(matches!(result, Err(CompileError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_should_run_only_for_clifford_t() {
+        let dag = CircuitDag::new();
+        let mut props = PropertySet::new();
+        assert!(!CliffordTSynthesis::new().should_run(&dag, &props));
+        props.basis_gates = Some(BasisGates::ibm());
+        assert!(!CliffordTSynthesis::new().should_run(&dag, &props));
+        props.basis_gates = Some(BasisGates::clifford_t());
+        assert!(CliffordTSynthesis::new().should_run(&dag, &props));
+    }
+}
@@ -0,0 +1,250 @@
+//! Gate direction fixing for directional couplings.
+//!
+//! Some devices implement a two-qubit gate in one direction only per edge:
+//! IBM's cross-resonance ECR gates, for example, drive one fixed qubit of
+//! each pair. Routing treats edges as undirected, so after basis translation
+//! a gate may act from `b` to `a` on an edge that only supports `a → b`.
+//! [`GateDirection`] rewrites such gates into the native direction:
+//!
+//! - `CX(a, b) = H(a)·H(b) · CX(b, a) · H(a)·H(b)`
+//! - `ECR(a, b) = RY(-π/2)(a)·RY(π/2)(b) · ECR(b, a) · H(a)·H(b)`
+//! - symmetric gates (`CZ`, `SWAP`, `iSWAP`, `CP`, `RXX`, `RYY`, `RZZ`)
+//!   have their operands swapped.
+//!
+//! The inserted single-qubit gates are not in the target basis; the pass
+//! manager runs [`BasisTranslation`](super::BasisTranslation) again
+//! afterwards.
+
+use std::f64::consts::FRAC_PI_2;
+
+use arvak_ir::{CircuitDag, Gate, GateKind, Instruction, QubitId, StandardGate};
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::property::PropertySet;
+
+/// Flip two-qubit gates that act against the native direction of an edge.
+///
+/// Requires a coupling map with directed edges (see
+/// [`CouplingMap::add_directed_edge`](crate::CouplingMap::add_directed_edge))
+/// and a routed circuit, i.e. qubits are physical. Gates inside if/else and
+/// loop bodies are flipped as well.
+pub struct GateDirection;
+
+impl Pass for GateDirection {
+    fn name(&self) -> &'static str {
+        "GateDirection"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let coupling_map = properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+
+        let reversed = |inst: &Instruction| {
+            inst.as_gate().is_some()
+                && inst.qubits.len() == 2
+                && coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0)
+                && !coupling_map.supports_direction(inst.qubits[0].0, inst.qubits[1].0)
+        };
+        let mut any_reversed = false;
+        for (_, inst) in dag.topological_ops() {
+            inst.walk(&mut |inst| any_reversed |= reversed(inst));
+        }
+        if !any_reversed {
+            return Ok(());
+        }
+
+        let mut new_dag = CircuitDag::new();
+        for qubit in dag.qubits().collect::<Vec<_>>() {
+            new_dag.add_qubit(qubit);
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+
+        for (_, inst) in dag.topological_ops() {
+            for native in fix_direction(inst, &reversed)? {
+                new_dag.apply(native)?;
+            }
+        }
+
+        *dag = new_dag;
+        Ok(())
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties
+            .coupling_map
+            .as_ref()
+            .is_some_and(crate::CouplingMap::is_directed)
+    }
+}
+
+/// Flip `inst` if `reversed` says it acts against its edge, recursing into
+/// the bodies of control-flow blocks.
+fn fix_direction(
+    inst: &Instruction,
+    reversed: &impl Fn(&Instruction) -> bool,
+) -> CompileResult<Vec<Instruction>> {
+    if inst.is_control_flow() {
+        let mut fixed = inst.clone();
+        for block in fixed.blocks_mut() {
+            let mut body = Vec::with_capacity(block.len());
+            for nested in block.iter() {
+                body.extend(fix_direction(nested, reversed)?);
+            }
+            *block = body;
+        }
+        Ok(vec![fixed])
+    } else if reversed(inst) {
+        flip(inst)
+    } else {
+        Ok(vec![inst.clone()])
+    }
+}
+
+/// Single-qubit gates placed around a flipped two-qubit gate.
+type Fixup = Vec<(StandardGate, QubitId)>;
+
+/// Rewrite the two-qubit gate `inst` so that it acts from its second qubit
+/// to its first, with the same unitary.
+fn flip(inst: &Instruction) -> CompileResult<Vec<Instruction>> {
+    let gate = inst.as_gate().expect("flip is only called on gates");
+    let (a, b) = (inst.qubits[0], inst.qubits[1]);
+
+    let GateKind::Standard(std_gate) = &gate.kind else {
+        return Err(unflippable(gate));
+    };
+    let (before, after): (Fixup, Fixup) = match std_gate {
+        StandardGate::CX => (
+            vec![(StandardGate::H, a), (StandardGate::H, b)],
+            vec![(StandardGate::H, a), (StandardGate::H, b)],
+        ),
+        StandardGate::ECR => (
+            vec![
+                (StandardGate::Ry((-FRAC_PI_2).into()), a),
+                (StandardGate::Ry(FRAC_PI_2.into()), b),
+            ],
+            vec![(StandardGate::H, a), (StandardGate::H, b)],
+        ),
+        StandardGate::CZ
+        | StandardGate::Swap
+        | StandardGate::ISwap
+        | StandardGate::CP(_)
+        | StandardGate::RXX(_)
+        | StandardGate::RYY(_)
+        | StandardGate::RZZ(_) => (vec![], vec![]),
+        _ => return Err(unflippable(gate)),
+    };
+
+    // Inserted gates inherit the condition, so a conditioned gate is still
+    // applied (or skipped) as a whole.
+    let single = |(g, q): (StandardGate, QubitId)| {
+        let mut step = Instruction::single_qubit_gate(g, q).with_origin("GateDirection");
+        if let Some(inserted) = step.gate_mut() {
+            inserted.condition.clone_from(&gate.condition);
+        }
+        step.with_metadata_from(inst)
+    };
+    let mut native = inst.clone();
+    native.qubits = vec![b, a];
+
+    let mut out: Vec<Instruction> = before.into_iter().map(single).collect();
+    out.push(native);
+    out.extend(after.into_iter().map(single));
+    Ok(out)
+}
+
+fn unflippable(gate: &Gate) -> CompileError {
+    CompileError::PassFailed {
+        name: "GateDirection".into(),
+        reason: format!(
+            "'{}' is not supported in this direction and cannot be reversed",
+            gate.name()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::CouplingMap;
+    use arvak_ir::Circuit;
+    use num_complex::Complex64;
+
+    /// Whether `a` and `b` agree up to a global phase.
+    fn equivalent(a: &[Complex64], b: &[Complex64]) -> bool {
+        let overlap: Complex64 = a.iter().zip(b).map(|(x, y)| x.conj() * y).sum();
+        (overlap.norm() - a.len().isqrt() as f64).abs() < 1e-9
+    }
+
+    fn run(circuit: &Circuit, map: CouplingMap) -> CompileResult<CircuitDag> {
+        let mut dag = circuit.dag().clone();
+        let mut props = PropertySet::new();
+        props.coupling_map = Some(map);
+        GateDirection.run(&mut dag, &mut props)?;
+        Ok(dag)
+    }
+
+    #[test]
+    fn test_reversed_gates_match_native_direction() {
+        let map = CouplingMap::from_directed_edge_list(2, &[(0, 1)]);
+        for gate in [
+            StandardGate::CX,
+            StandardGate::ECR,
+            StandardGate::CZ,
+            StandardGate::RZZ(0.3.into()),
+        ] {
+            let mut circuit = Circuit::with_size("t", 2, 0);
+            circuit
+                .dag_mut()
+                .apply(Instruction::two_qubit_gate(
+                    gate.clone(),
+                    QubitId(1),
+                    QubitId(0),
+                ))
+                .unwrap();
+            let dag = run(&circuit, map.clone()).unwrap();
+
+            for (_, inst) in dag.topological_ops() {
+                if inst.qubits.len() == 2 {
+                    assert_eq!(inst.qubits, vec![QubitId(0), QubitId(1)], "{}", gate.name());
+                }
+            }
+            assert!(
+                equivalent(&circuit.to_matrix().unwrap(), &dag.to_matrix().unwrap()),
+                "{} changed the unitary",
+                gate.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_native_and_bidirectional_edges_are_untouched() {
+        let map = CouplingMap::from_directed_edge_list(3, &[(0, 1), (1, 2), (2, 1)]);
+        let mut circuit = Circuit::with_size("t", 3, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(2), QubitId(1)).unwrap();
+        let dag = run(&circuit, map).unwrap();
+        assert_eq!(dag.num_ops(), 2);
+    }
+
+    #[test]
+    fn test_unflippable_gate_is_an_error() {
+        let map = CouplingMap::from_directed_edge_list(2, &[(0, 1)]);
+        let mut circuit = Circuit::with_size("t", 2, 0);
+        circuit.cy(QubitId(1), QubitId(0)).unwrap();
+        assert!(matches!(
+            run(&circuit, map),
+            Err(CompileError::PassFailed { .. })
+        ));
+    }
+}
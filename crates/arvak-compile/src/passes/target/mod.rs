@@ -0,0 +1,30 @@
+//! Target-specific compilation passes.
+//!
+//! These passes require target hardware properties from the `PropertySet`
+//! (coupling map, basis gates, layout) and produce hardware-compatible
+//! circuits for specific quantum devices.
+
+pub mod clifford_t;
+pub mod dense_layout;
+pub mod gate_direction;
+pub mod layout;
+pub mod neutral_atom_routing;
+pub mod routing;
+pub mod sabre_routing;
+pub mod swap_network;
+pub mod translation;
+pub mod unitary_synthesis;
+pub mod vf2_layout;
+
+pub use clifford_t::CliffordTSynthesis;
+pub use dense_layout::DenseLayout;
+pub use gate_direction::GateDirection;
+pub use layout::TrivialLayout;
+pub use neutral_atom_routing::{NeutralAtomRouting, ZoneAssignment};
+pub use routing::BasicRouting;
+pub use sabre_routing::SabreRouting;
+pub use swap_network::{SwapNetworkLayout, SwapNetworkPlan, SwapNetworkRouting};
+pub use translation::BasisTranslation;
+pub(crate) use translation::decompose_to_simpler;
+pub use unitary_synthesis::UnitarySynthesis;
+pub use vf2_layout::VF2Layout;
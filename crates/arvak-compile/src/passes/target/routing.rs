@@ -0,0 +1,578 @@
+//! Routing passes for inserting SWAP gates.
+
+use rustc_hash::FxHashSet;
+
+use arvak_ir::{CircuitDag, Instruction, InstructionKind, QubitId, StandardGate};
+
+use crate::bridge::{
+    BridgeDecision, BridgeDecisions, BridgeMode, bridge_cx, bridge_middle, prefer_bridge,
+};
+use crate::cancel::CancellationToken;
+use crate::error::{CompileError, CompileResult};
+use crate::error_weights::{ErrorCosts, ErrorWeights};
+use crate::pass::{Pass, PassKind};
+use crate::property::{CouplingMap, Layout, PropertySet};
+
+/// Basic routing pass.
+///
+/// Inserts SWAP gates to satisfy connectivity constraints.
+/// Rebuilds the DAG from scratch in topological order, inserting
+/// SWAP chains immediately before each non-adjacent two-qubit gate.
+///
+/// The output DAG uses **physical** qubit wire labels: every instruction's
+/// qubit operands are remapped from logical IDs to physical positions via
+/// the current layout. SWAP gates use physical wire labels directly so
+/// the emitted circuit is ready for hardware execution.
+///
+/// If/else and loop bodies are routed from the layout at the start of the
+/// block, and each body ends by undoing its SWAPs, so the layout after the
+/// block is the same whichever branch runs and however often a loop
+/// iterates.
+///
+/// With [`ErrorWeights`] in the property set, each SWAP chain follows the
+/// cheapest path by calibrated error instead of the shortest one; see
+/// [`crate::error_weights`]. With a [`BridgeMode`], a CX between second
+/// neighbours may run through a bridge instead; see [`crate::bridge`].
+pub struct BasicRouting;
+
+impl Pass for BasicRouting {
+    fn name(&self) -> &'static str {
+        "BasicRouting"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let coupling_map = properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+
+        let costs = properties
+            .get::<ErrorWeights>()
+            .and_then(|weights| weights.costs(coupling_map));
+        let cancel = properties.get::<CancellationToken>().cloned();
+        let bridges = properties.get::<BridgeMode>().copied().unwrap_or_default();
+
+        let layout = properties
+            .layout
+            .as_mut()
+            .ok_or(CompileError::MissingLayout)?;
+
+        // Collect all instructions in topological order from the original DAG
+        let ops: Vec<Instruction> = dag
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+        let mut router = SwapRouter::new(
+            coupling_map,
+            costs.as_ref(),
+            cancel.as_ref(),
+            "BasicRouting",
+            &ops,
+        )
+        .with_bridges(bridges);
+        let routed = router.route(ops, layout, &mut Vec::new())?;
+        let decisions = router.into_decisions();
+
+        // Build a new DAG with physical qubit wires spanning the whole
+        // device. Wires are labelled by physical position; covering
+        // 0..num_qubits keeps the circuit's qubit count consistent with the
+        // highest referenced index (emitters declare `qubit[n]`).
+        let mut new_dag = CircuitDag::new();
+        for physical in 0..coupling_map.num_qubits() {
+            new_dag.add_qubit(QubitId(physical));
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+
+        for inst in routed {
+            // Ensure physical wires exist (handles ancilla paths)
+            for &q in &inst.qubits {
+                new_dag.add_qubit(q);
+            }
+            new_dag.apply(inst).map_err(CompileError::Ir)?;
+        }
+
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+        *dag = new_dag;
+
+        if bridges != BridgeMode::Never {
+            properties.insert(BridgeDecisions { decisions });
+        }
+
+        Ok(())
+    }
+
+    fn is_routing(&self) -> bool {
+        true
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.coupling_map.is_some() && properties.layout.is_some()
+    }
+}
+
+/// Routes instructions by inserting a SWAP chain before each non-adjacent
+/// two-qubit gate, or a bridge for a second-neighbour CX, recursing into
+/// control-flow blocks.
+///
+/// This is [`BasicRouting`]'s algorithm; [`SabreRouting`](super::SabreRouting)
+/// uses it for the bodies of control-flow blocks.
+pub(crate) struct SwapRouter<'a> {
+    coupling_map: &'a CouplingMap,
+    costs: Option<&'a ErrorCosts>,
+    cancel: Option<&'a CancellationToken>,
+    /// Pass name recorded as the origin of inserted SWAPs.
+    origin: &'static str,
+    /// Logical qubits measured anywhere in the circuit.
+    measured: FxHashSet<QubitId>,
+    /// When to bridge second-neighbour CXs.
+    bridges: BridgeMode,
+    /// Bridge-or-SWAP choices made so far.
+    decisions: Vec<BridgeDecision>,
+}
+
+impl<'a> SwapRouter<'a> {
+    pub(crate) fn new(
+        coupling_map: &'a CouplingMap,
+        costs: Option<&'a ErrorCosts>,
+        cancel: Option<&'a CancellationToken>,
+        origin: &'static str,
+        ops: &[Instruction],
+    ) -> Self {
+        let mut measured = FxHashSet::default();
+        for inst in ops {
+            inst.walk(&mut |inst| {
+                if inst.is_measure() {
+                    measured.extend(inst.qubits.iter().copied());
+                }
+            });
+        }
+        Self {
+            coupling_map,
+            costs,
+            cancel,
+            origin,
+            measured,
+            bridges: BridgeMode::Never,
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Bridge second-neighbour CXs according to `mode`.
+    #[must_use]
+    pub(crate) fn with_bridges(mut self, mode: BridgeMode) -> Self {
+        self.bridges = mode;
+        self
+    }
+
+    /// The bridge-or-SWAP choices made while routing.
+    pub(crate) fn into_decisions(self) -> Vec<BridgeDecision> {
+        self.decisions
+    }
+
+    /// Route `ops` from `layout` onto physical qubits, updating `layout` and
+    /// appending the SWAPs inserted at this level (not inside blocks) to
+    /// `swaps`.
+    #[allow(clippy::similar_names)]
+    pub(crate) fn route(
+        &mut self,
+        ops: impl IntoIterator<Item = Instruction>,
+        layout: &mut Layout,
+        swaps: &mut Vec<(u32, u32)>,
+    ) -> CompileResult<Vec<Instruction>> {
+        let mut routed = Vec::new();
+        for inst in ops {
+            if self.cancel.is_some_and(CancellationToken::is_cancelled) {
+                return Err(CompileError::Cancelled);
+            }
+            if inst.is_control_flow() {
+                routed.push(self.route_control_flow(&inst, layout)?);
+                continue;
+            }
+            if inst.qubits.len() == 2 {
+                let q0 = inst.qubits[0];
+                let q1 = inst.qubits[1];
+                let p0 = layout.get_physical(q0).ok_or(CompileError::MissingLayout)?;
+                let p1 = layout.get_physical(q1).ok_or(CompileError::MissingLayout)?;
+
+                if !self.coupling_map.is_connected(p0, p1) {
+                    if let Some(middle) = self.bridge(&inst, p0, p1) {
+                        routed.extend(bridge_cx(&inst, p0, middle, p1, self.origin));
+                        continue;
+                    }
+                    let path = match self.costs {
+                        Some(costs) => {
+                            costs.swap_path(self.coupling_map, p0, p1, self.measured.contains(&q0))
+                        }
+                        None => self.coupling_map.shortest_path(p0, p1),
+                    }
+                    .ok_or(CompileError::RoutingFailed {
+                        qubit1: p0,
+                        qubit2: p1,
+                    })?;
+
+                    // Insert SWAPs along the path (except the last edge which is the gate).
+                    // SWAPs use physical wire labels so they operate on the correct
+                    // hardware qubits.
+                    for w in path.windows(2).take(path.len() - 2) {
+                        routed.push(self.swap(w[0], w[1]));
+                        layout.swap(w[0], w[1]);
+                        swaps.push((w[0], w[1]));
+                    }
+                }
+            }
+
+            // Remap instruction qubits from logical to physical positions.
+            let mut remapped = inst;
+            remapped.qubits = remapped
+                .qubits
+                .iter()
+                .map(|&q| {
+                    let p = layout.get_physical(q).ok_or(CompileError::MissingLayout)?;
+                    Ok(QubitId(p))
+                })
+                .collect::<CompileResult<Vec<_>>>()?;
+            routed.push(remapped);
+        }
+        Ok(routed)
+    }
+
+    /// Route the bodies of the control-flow instruction `inst` from
+    /// `layout`. Every body ends with its SWAPs undone in reverse, so the
+    /// layout after the block is `layout` again.
+    pub(crate) fn route_control_flow(
+        &mut self,
+        inst: &Instruction,
+        layout: &Layout,
+    ) -> CompileResult<Instruction> {
+        let mut routed = inst.clone();
+        for block in routed.blocks_mut() {
+            let mut body_layout = layout.clone();
+            let mut swaps = Vec::new();
+            let mut body = self.route(block.drain(..), &mut body_layout, &mut swaps)?;
+            for &(a, b) in swaps.iter().rev() {
+                body.push(self.swap(a, b));
+            }
+            *block = body;
+        }
+        // The bodies now address physical qubits; rebuild the block so its
+        // operands follow them.
+        let routed = match routed.kind {
+            InstructionKind::IfElse {
+                condition,
+                then_body,
+                else_body,
+            } => Instruction::if_else(condition, then_body, else_body),
+            InstructionKind::WhileLoop {
+                condition,
+                body,
+                max_iterations,
+            } => Instruction::while_loop(condition, body, max_iterations),
+            _ => unreachable!("route_control_flow is only called on control flow"),
+        };
+        Ok(routed.with_metadata_from(inst))
+    }
+
+    /// The middle qubit to bridge `inst` through from `p0` to `p1`, if it
+    /// should be bridged. Without lookahead a SWAP only wins on errors.
+    fn bridge(&mut self, inst: &Instruction, p0: u32, p1: u32) -> Option<u32> {
+        if self.bridges == BridgeMode::Never {
+            return None;
+        }
+        let middle = bridge_middle(inst, p0, p1, self.coupling_map)?;
+        let bridged = prefer_bridge(self.bridges, self.costs, p0, middle, p1, |_, _| 0.0);
+        self.decisions.push(BridgeDecision {
+            control: p0,
+            middle,
+            target: p1,
+            bridged,
+        });
+        bridged.then_some(middle)
+    }
+
+    fn swap(&self, a: u32, b: u32) -> Instruction {
+        Instruction::two_qubit_gate(StandardGate::Swap, QubitId(a), QubitId(b))
+            .with_origin(self.origin)
+    }
+}
+
+/// Find shortest path between two physical qubits (legacy BFS, kept for tests).
+#[cfg(test)]
+fn find_path(
+    coupling_map: &crate::property::CouplingMap,
+    from: u32,
+    to: u32,
+) -> CompileResult<Vec<u32>> {
+    use rustc_hash::FxHashMap;
+    use std::collections::VecDeque;
+
+    if from == to {
+        return Ok(vec![from]);
+    }
+
+    let mut visited = FxHashMap::default();
+    let mut queue = VecDeque::new();
+
+    visited.insert(from, None);
+    queue.push_back(from);
+
+    while let Some(current) = queue.pop_front() {
+        for neighbor in coupling_map.neighbors(current) {
+            if visited.contains_key(&neighbor) {
+                continue;
+            }
+
+            visited.insert(neighbor, Some(current));
+
+            if neighbor == to {
+                // Reconstruct path
+                let mut path = vec![to];
+                let mut node = to;
+                while let Some(Some(prev)) = visited.get(&node) {
+                    path.push(*prev);
+                    node = *prev;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+
+            queue.push_back(neighbor);
+        }
+    }
+
+    Err(CompileError::RoutingFailed {
+        qubit1: from,
+        qubit2: to,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::TrivialLayout;
+    use crate::property::{BasisGates, CouplingMap, Layout};
+    use arvak_ir::{Circuit, InstructionKind, QubitId};
+
+    #[test]
+    fn test_basic_routing_connected() {
+        // Create a circuit with a CX on adjacent qubits
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        BasicRouting.run(&mut dag, &mut props).unwrap();
+
+        // No SWAPs needed, ops count should be the same
+        assert_eq!(dag.num_ops(), 2);
+    }
+
+    #[test]
+    fn test_basic_routing_needs_swap() {
+        // Create a circuit with a CX on non-adjacent qubits (q0, q2)
+        // On linear(5): 0-1-2-3-4, so q0 and q2 are distance 2 apart.
+        // The router should insert a SWAP before the CX.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        BasicRouting.run(&mut dag, &mut props).unwrap();
+
+        // Should have inserted at least one SWAP
+        assert!(dag.num_ops() > 1);
+
+        // Verify SWAPs appear BEFORE the CX in topological order
+        let ops: Vec<_> = dag
+            .topological_ops()
+            .filter_map(|(_, inst)| {
+                if let InstructionKind::Gate(gate) = &inst.kind {
+                    Some(gate.name().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Find last SWAP and first CX — SWAPs must come before CX
+        let last_swap = ops.iter().rposition(|name| name == "swap");
+        let first_cx = ops.iter().position(|name| name == "cx");
+        assert!(last_swap.is_some(), "expected at least one SWAP gate");
+        assert!(first_cx.is_some(), "expected CX gate in output");
+        assert!(
+            last_swap.unwrap() < first_cx.unwrap(),
+            "SWAP gates must appear before CX in topological order, got ops: {ops:?}"
+        );
+        assert!(
+            dag.topological_ops()
+                .filter(|(_, inst)| inst.name() == "swap")
+                .all(|(_, inst)| inst.origin() == Some("BasicRouting"))
+        );
+
+        // Verify all two-qubit gates use adjacent physical qubits.
+        // Since the output uses physical wire labels, QubitId values
+        // are physical positions — check adjacency directly.
+        let coupling_map = props.coupling_map.as_ref().unwrap();
+        for (_, inst) in dag.topological_ops() {
+            if inst.qubits.len() == 2 {
+                assert!(
+                    coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0),
+                    "two-qubit gate on non-adjacent physical qubits ({}, {})",
+                    inst.qubits[0].0,
+                    inst.qubits[1].0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_routing_bv_pattern() {
+        // BV-style circuit: CX from q0 to q3 on linear(5)
+        // Path: 0-1-2-3, needs 2 SWAPs to bring q0 adjacent to q3
+        let mut circuit = Circuit::with_size("bv_test", 4, 0);
+        circuit.cx(QubitId(0), QubitId(3)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        BasicRouting.run(&mut dag, &mut props).unwrap();
+
+        // Since the output uses physical wire labels, check adjacency directly.
+        let coupling_map = props.coupling_map.as_ref().unwrap();
+        for (_, inst) in dag.topological_ops() {
+            if inst.qubits.len() == 2 {
+                assert!(
+                    coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0),
+                    "two-qubit gate on non-adjacent physical qubits ({}, {})",
+                    inst.qubits[0].0,
+                    inst.qubits[1].0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_routing_with_ancilla() {
+        // Sparse coupling map: 0-1, 1-2, 2-3 (linear 4-qubit)
+        // Circuit uses only q0 and q1 (mapped to physical 0 and 3, distance 3).
+        // Path 0→1→2→3 traverses physical qubits 1 and 2 which have no
+        // logical qubits mapped — the router must add physical wires.
+        let mut circuit = Circuit::with_size("ancilla_test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        // Manually set up a layout where logical q0→physical 0, q1→physical 3
+        // (skipping physical 1 and 2 — no logical qubits there)
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(4), BasisGates::iqm());
+        let mut layout = Layout::new();
+        layout.add(QubitId(0), 0);
+        layout.add(QubitId(1), 3);
+        props.layout = Some(layout);
+
+        BasicRouting.run(&mut dag, &mut props).unwrap();
+
+        // Should have added physical wires for intermediary qubits 1 and 2
+        assert!(
+            dag.num_qubits() > 2,
+            "expected physical wires for SWAP path, got {} qubits",
+            dag.num_qubits()
+        );
+
+        // Verify SWAPs were inserted
+        let ops: Vec<_> = dag
+            .topological_ops()
+            .filter_map(|(_, inst)| {
+                if let InstructionKind::Gate(gate) = &inst.kind {
+                    Some(gate.name().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let swap_count = ops.iter().filter(|n| *n == "swap").count();
+        assert!(
+            swap_count >= 2,
+            "expected at least 2 SWAPs, got {swap_count}"
+        );
+        assert!(ops.iter().any(|n| n == "cx"), "expected CX gate in output");
+
+        // Verify all two-qubit gates are on adjacent physical qubits
+        let coupling_map = props.coupling_map.as_ref().unwrap();
+        for (_, inst) in dag.topological_ops() {
+            if inst.qubits.len() == 2 {
+                assert!(
+                    coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0),
+                    "two-qubit gate on non-adjacent physical qubits ({}, {})",
+                    inst.qubits[0].0,
+                    inst.qubits[1].0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let coupling_map = CouplingMap::linear(5);
+
+        // Precomputed shortest path
+        let path = coupling_map.shortest_path(0, 4).unwrap();
+        assert_eq!(path, vec![0, 1, 2, 3, 4]);
+
+        let path = coupling_map.shortest_path(2, 2).unwrap();
+        assert_eq!(path, vec![2]);
+
+        // Legacy BFS fallback
+        let path = find_path(&coupling_map, 0, 4).unwrap();
+        assert_eq!(path, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_basic_routing_restores_layout_after_block() {
+        use arvak_ir::{ClassicalCondition, ClbitId};
+
+        // CX(q0, q2) inside the branch needs a SWAP on linear(3). The branch
+        // undoes it, so the CX after the block runs on the entry layout.
+        let mut then_body = Circuit::with_size("then", 3, 1);
+        then_body.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut circuit = Circuit::with_size("test", 3, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .if_then(ClassicalCondition::on_clbit(ClbitId(0), 1), &then_body)
+            .unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        BasicRouting.run(&mut dag, &mut props).unwrap();
+
+        let (_, block) = dag
+            .topological_ops()
+            .find(|(_, inst)| inst.is_if_else())
+            .unwrap();
+        let body = block.blocks()[0];
+        let names: Vec<&str> = body.iter().map(Instruction::name).collect();
+        assert_eq!(names, ["swap", "cx", "swap"]);
+        assert_eq!(body[1].qubits, vec![QubitId(1), QubitId(2)]);
+        assert_eq!(block.qubits, vec![QubitId(0), QubitId(1), QubitId(2)]);
+
+        let top_level: Vec<&str> = dag.topological_ops().map(|(_, i)| i.name()).collect();
+        assert!(!top_level.contains(&"swap"), "{top_level:?}");
+        let layout = props.layout.unwrap();
+        assert_eq!(layout.get_physical(QubitId(0)), Some(0));
+        assert_eq!(layout.get_physical(QubitId(2)), Some(2));
+    }
+}
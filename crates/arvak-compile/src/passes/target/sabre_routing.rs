@@ -0,0 +1,1604 @@
+//! SABRE routing pass for inserting SWAP gates.
+//!
+//! Implements the SABRE algorithm (SWAP-based Bidirectional heuristic search)
+//! from Li et al., "Tackling the Qubit Mapping Problem for NISQ-Era Quantum
+//! Devices" (ASPLOS 2019).
+//!
+//! Key ideas:
+//! - Maintain a "front layer" of executable gates (all dependencies satisfied)
+//! - Score candidate SWAPs by how much they reduce the total distance of
+//!   front-layer gates, with a lookahead into upcoming gates
+//! - Optionally ([`SabreRouting::with_trials`]) route several times with
+//!   different tie-breaking and keep the cheapest result
+//!
+//! SWAP candidates with equal score are broken by the
+//! [`PropertySet::seed`] when one is set, and by physical qubit order
+//! otherwise, so routing is reproducible either way. Further trials draw
+//! their tie-breaking seeds from the same seed.
+//!
+//! Optionally ([`SabreRouting::with_ancilla_bridging`]), a CX whose
+//! operands are separated only by idle physical qubits is executed in
+//! place through those qubits as ancillas instead of being brought
+//! together with SWAPs. Qubits another pass holds in the
+//! [`AncillaPool`](crate::AncillaPool) are never used as bridges.
+//!
+//! With [`ErrorWeights`](crate::ErrorWeights) in the property set, the
+//! heuristic measures distance by calibrated error as well as hops, adds
+//! each candidate SWAP's own error cost and the readout cost of moving a
+//! measured qubit, and trials are compared by estimated error; see
+//! [`crate::error_weights`].
+//!
+//! With a [`BridgeMode`](crate::BridgeMode) in the property set, a
+//! front-layer CX between second neighbours may run through a bridge
+//! instead of waiting for a SWAP; the SWAP is preferred when the lookahead
+//! shows it also brings upcoming gates together. See [`crate::bridge`].
+//!
+//! The circuit is split at control-flow blocks: SABRE routes the stretches
+//! between them, and each block's bodies are routed like
+//! [`BasicRouting`](super::BasicRouting) does, ending with the layout the
+//! block started with.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use arvak_ir::{CircuitDag, Instruction, QubitId, StandardGate};
+
+use crate::ancilla::AncillaPool;
+use crate::bridge::{
+    BridgeDecision, BridgeDecisions, BridgeMode, bridge_cx, bridge_middle, prefer_bridge,
+};
+use crate::cancel::CancellationToken;
+use crate::error::{CompileError, CompileResult};
+use crate::error_weights::{ErrorCosts, ErrorWeights};
+use crate::pass::{Pass, PassKind};
+use crate::passes::target::routing::SwapRouter;
+use crate::property::{CouplingMap, Layout, PropertySet};
+use crate::rng::SeededRng;
+
+/// Scores closer than this count as a tie between SWAP candidates.
+const SCORE_TIE_TOLERANCE: f64 = 1e-9;
+
+/// SABRE routing pass.
+///
+/// Uses the SABRE heuristic to insert fewer SWAP gates than [`BasicRouting`].
+/// The algorithm considers a "front layer" of ready gates and a lookahead
+/// "extended set" when choosing which SWAP to insert. With more than one
+/// trial it keeps the routing with the lowest two-qubit gate count.
+///
+/// [`BasicRouting`]: super::BasicRouting
+pub struct SabreRouting {
+    /// Weight for the extended set in the cost function (0.0–1.0).
+    /// Higher values give more weight to lookahead.
+    extended_set_weight: f64,
+    /// Maximum size of the extended set (number of gates to look ahead).
+    extended_set_size: usize,
+    /// Route distant CX gates through idle physical qubits (opt-in).
+    ancilla_bridging: bool,
+    /// Number of routing trials; the cheapest is kept.
+    trials: usize,
+}
+
+impl SabreRouting {
+    /// Create a new SABRE routing pass with default parameters.
+    pub fn new() -> Self {
+        Self {
+            extended_set_weight: 0.5,
+            extended_set_size: 20,
+            ancilla_bridging: false,
+            trials: 1,
+        }
+    }
+
+    /// Create a SABRE routing pass with custom parameters.
+    pub fn with_params(extended_set_weight: f64, extended_set_size: usize) -> Self {
+        Self {
+            extended_set_weight,
+            extended_set_size,
+            ancilla_bridging: false,
+            trials: 1,
+        }
+    }
+
+    /// Enable or disable ancilla-assisted routing.
+    ///
+    /// When enabled and every qubit on the shortest path between the two
+    /// operands of a front-layer CX is idle (no logical qubit mapped to it),
+    /// the CX is executed through those qubits as ancillas using
+    /// `2d - 1` CX gates for distance `d`, instead of `d - 1` SWAPs plus the
+    /// CX (`3d - 2` CX gates). The layout is left unchanged.
+    #[must_use]
+    pub fn with_ancilla_bridging(mut self, enabled: bool) -> Self {
+        self.ancilla_bridging = enabled;
+        self
+    }
+
+    /// Set the number of routing trials (at least one).
+    ///
+    /// The first trial breaks ties like a single run; each further trial
+    /// breaks them with its own seed, drawn from [`PropertySet::seed`]. The
+    /// routing with the fewest two-qubit gates, counting a SWAP as three, is
+    /// kept (with [`ErrorWeights`], the lowest estimated error), and the
+    /// earliest wins a tie, so the result never costs more than a single
+    /// trial. Trials stop early once no SWAP was needed.
+    #[must_use]
+    pub fn with_trials(mut self, trials: usize) -> Self {
+        self.trials = trials.max(1);
+        self
+    }
+}
+
+impl Default for SabreRouting {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A two-qubit gate with its logical qubits and original instruction.
+#[derive(Clone)]
+struct TwoQubitGate {
+    /// Logical qubit operands (control, target).
+    q0: QubitId,
+    q1: QubitId,
+    /// Index in the gate list.
+    index: usize,
+}
+
+/// Find a path of idle physical qubits that can carry `inst` from `p0` to `p1`.
+///
+/// Returns the shortest path `p0 → … → p1` when `inst` is a CX and every
+/// interior qubit on the path has no logical qubit mapped to it. Unmapped
+/// physical qubits are only ever touched by SWAPs (which exchange them with
+/// other `|0⟩` or freshly vacated positions) and by bridge ladders (which
+/// restore them), so they are guaranteed to be in `|0⟩`. Qubits in
+/// `reserved` are held by other passes and are not used.
+fn idle_bridge_path(
+    inst: &Instruction,
+    p0: u32,
+    p1: u32,
+    layout: &Layout,
+    coupling_map: &CouplingMap,
+    reserved: &FxHashSet<u32>,
+) -> Option<Vec<u32>> {
+    let is_cx = matches!(
+        &inst.kind,
+        arvak_ir::InstructionKind::Gate(g)
+            if matches!(g.kind, arvak_ir::GateKind::Standard(StandardGate::CX))
+    );
+    if !is_cx {
+        return None;
+    }
+    let path = coupling_map.shortest_path(p0, p1)?;
+    if path.len() < 3 {
+        return None;
+    }
+    path[1..path.len() - 1]
+        .iter()
+        .all(|&p| layout.get_logical(p).is_none() && !reserved.contains(&p))
+        .then_some(path)
+}
+
+/// Expand a CX over `path` into a CX ladder through `|0⟩` ancillas.
+///
+/// The control value is copied down the chain of ancillas with CX gates,
+/// the final ancilla drives the original CX (which keeps its label and
+/// condition) onto the target, and the copy chain is uncomputed so every
+/// ancilla is returned to `|0⟩`. This is the measurement-free form of a
+/// teleported long-range CX, so `VerifyCompilation` can check it directly.
+fn bridge_ladder(inst: &Instruction, path: &[u32]) -> Vec<Instruction> {
+    let fan_out: Vec<Instruction> = path[..path.len() - 1]
+        .windows(2)
+        .map(|w| Instruction::two_qubit_gate(StandardGate::CX, QubitId(w[0]), QubitId(w[1])))
+        .collect();
+
+    let mut central = inst.clone();
+    central.qubits = vec![QubitId(path[path.len() - 2]), QubitId(path[path.len() - 1])];
+
+    let mut ladder = fan_out.clone();
+    ladder.push(central);
+    ladder.extend(fan_out.into_iter().rev());
+    ladder
+}
+
+/// Run one direction of the SABRE algorithm.
+///
+/// Returns the sequence of emitted instructions (with physical qubit labels)
+/// and the number of inserted SWAPs. With `bridging`, distant CX gates are
+/// bridged through idle qubits other than the reserved ones it holds. With
+/// `costs`, SWAPs are scored by calibrated error as well as distance.
+/// Second-neighbour CXs are bridged according to `bridges`, and every
+/// choice is appended to `decisions`. Control-flow blocks split `ops` into
+/// stretches routed one after another.
+/// Fails with [`CompileError::Cancelled`] once `cancel` is cancelled.
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
+fn sabre_pass(
+    ops: &[Instruction],
+    initial_layout: &Layout,
+    coupling_map: &CouplingMap,
+    extended_set_weight: f64,
+    extended_set_size: usize,
+    bridging: Option<&FxHashSet<u32>>,
+    bridges: BridgeMode,
+    costs: Option<&ErrorCosts>,
+    seed: Option<u64>,
+    cancel: Option<&CancellationToken>,
+    decisions: &mut Vec<BridgeDecision>,
+) -> CompileResult<(Vec<Instruction>, Layout, usize)> {
+    let mut layout = initial_layout.clone();
+    let mut rng = seed.map(SeededRng::new);
+    let mut emitted: Vec<Instruction> = Vec::new();
+    let mut swap_count: usize = 0;
+
+    // SABRE only tracks 1q and 2q operations; a wider gate would be
+    // silently dropped from the output. Fail loudly instead — the default
+    // pipeline runs Unroll3q before routing.
+    if let Some(wide) = ops.iter().find(|inst| {
+        matches!(&inst.kind, arvak_ir::InstructionKind::Gate(_)) && inst.qubits.len() >= 3
+    }) {
+        return Err(CompileError::PassFailed {
+            name: "SabreRouting".into(),
+            reason: format!(
+                "cannot route {}-qubit gate; run Unroll3q before routing",
+                wide.qubits.len()
+            ),
+        });
+    }
+
+    if let Some(i) = ops.iter().position(Instruction::is_control_flow) {
+        let route = |ops: &[Instruction], layout: &Layout, decisions: &mut Vec<BridgeDecision>| {
+            sabre_pass(
+                ops,
+                layout,
+                coupling_map,
+                extended_set_weight,
+                extended_set_size,
+                bridging,
+                bridges,
+                costs,
+                seed,
+                cancel,
+                decisions,
+            )
+        };
+        let (mut emitted, layout, before) = route(&ops[..i], initial_layout, &mut *decisions)?;
+        let mut router =
+            SwapRouter::new(coupling_map, costs, cancel, "SabreRouting", ops).with_bridges(bridges);
+        let block = router.route_control_flow(&ops[i], &layout)?;
+        decisions.extend(router.into_decisions());
+        let mut block_swaps = 0;
+        block.walk(&mut |inst| {
+            if inst.origin() == Some("SabreRouting") && inst.name() == "swap" {
+                block_swaps += 1;
+            }
+        });
+        emitted.push(block);
+        let (rest, layout, after) = route(&ops[i + 1..], &layout, &mut *decisions)?;
+        emitted.extend(rest);
+        return Ok((emitted, layout, before + block_swaps + after));
+    }
+
+    // Routing distance, by calibrated error when there are costs.
+    let distance = |p0: u32, p1: u32| match costs {
+        Some(costs) => costs.distance(p0, p1),
+        None => f64::from(coupling_map.distance(p0, p1).unwrap_or(u32::MAX)),
+    };
+    let measured: FxHashSet<QubitId> = ops
+        .iter()
+        .filter(|inst| inst.is_measure())
+        .flat_map(|inst| inst.qubits.iter().copied())
+        .collect();
+
+    // Build dependency graph: for each two-qubit gate, track which gates
+    // must execute before it (on the same qubit).
+    let num_ops = ops.len();
+
+    // For each qubit, track which two-qubit gate indices touch it, in order.
+    let mut qubit_gate_list: FxHashMap<QubitId, Vec<usize>> = FxHashMap::default();
+    let mut two_qubit_gates: Vec<TwoQubitGate> = Vec::new();
+    let mut gate_index_map: Vec<Option<usize>> = vec![None; num_ops];
+
+    for (i, inst) in ops.iter().enumerate() {
+        if inst.qubits.len() == 2 {
+            let tq_idx = two_qubit_gates.len();
+            two_qubit_gates.push(TwoQubitGate {
+                q0: inst.qubits[0],
+                q1: inst.qubits[1],
+                index: i,
+            });
+            gate_index_map[i] = Some(tq_idx);
+            qubit_gate_list
+                .entry(inst.qubits[0])
+                .or_default()
+                .push(tq_idx);
+            qubit_gate_list
+                .entry(inst.qubits[1])
+                .or_default()
+                .push(tq_idx);
+        }
+    }
+
+    if two_qubit_gates.is_empty() {
+        // No two-qubit gates — just remap and emit everything.
+        for inst in ops {
+            let mut remapped = inst.clone();
+            remapped.qubits = remapped
+                .qubits
+                .iter()
+                .map(|&q| {
+                    let p = layout.get_physical(q).ok_or(CompileError::MissingLayout)?;
+                    Ok(QubitId(p))
+                })
+                .collect::<CompileResult<Vec<_>>>()?;
+            emitted.push(remapped);
+        }
+        return Ok((emitted, layout, 0));
+    }
+
+    // Track which two-qubit gate is "next" on each qubit.
+    let mut qubit_gate_cursor: FxHashMap<QubitId, usize> = FxHashMap::default();
+    for &q in qubit_gate_list.keys() {
+        qubit_gate_cursor.insert(q, 0);
+    }
+
+    // Dependencies: a two-qubit gate is "ready" when it is the next gate
+    // on both of its qubits.
+    let mut resolved = vec![false; two_qubit_gates.len()];
+
+    // Track which original ops have been emitted.
+    let mut emitted_ops = vec![false; num_ops];
+
+    let is_gate_ready =
+        |tq_idx: usize, qubit_gate_cursor: &FxHashMap<QubitId, usize>, resolved: &[bool]| -> bool {
+            if resolved[tq_idx] {
+                return false;
+            }
+            let gate = &two_qubit_gates[tq_idx];
+            let cursor_q0 = qubit_gate_cursor.get(&gate.q0).copied().unwrap_or(0);
+            let cursor_q1 = qubit_gate_cursor.get(&gate.q1).copied().unwrap_or(0);
+            let list_q0 = &qubit_gate_list[&gate.q0];
+            let list_q1 = &qubit_gate_list[&gate.q1];
+            cursor_q0 < list_q0.len()
+                && list_q0[cursor_q0] == tq_idx
+                && cursor_q1 < list_q1.len()
+                && list_q1[cursor_q1] == tq_idx
+        };
+
+    // Build initial front layer.
+    let mut front_layer: Vec<usize> = Vec::new();
+    for tq_idx in 0..two_qubit_gates.len() {
+        if is_gate_ready(tq_idx, &qubit_gate_cursor, &resolved) {
+            front_layer.push(tq_idx);
+        }
+    }
+
+    // Helper: emit all single-qubit (and non-gate) ops that are "ready".
+    //
+    // A single-qubit op at position `i` is ready when no unresolved
+    // two-qubit gate on the same qubit appears earlier in the ops list.
+    // This avoids the cursor-based approach which can miss 1q gates that
+    // appear after a 2q gate on an *independent* qubit in topological order.
+    let emit_ready_1q_ops = |emitted: &mut Vec<Instruction>,
+                             emitted_ops: &mut [bool],
+                             layout: &Layout|
+     -> CompileResult<()> {
+        for i in 0..num_ops {
+            if emitted_ops[i] {
+                continue;
+            }
+            let inst = &ops[i];
+            if inst.qubits.len() >= 2 {
+                continue;
+            }
+            // Check: is there an unresolved 2q gate on the same qubit
+            // that appears before this op in the original ordering?
+            let blocked = inst.qubits.iter().any(|q| {
+                (0..i).any(|j| {
+                    !emitted_ops[j] && ops[j].qubits.len() >= 2 && ops[j].qubits.contains(q)
+                })
+            });
+            if blocked {
+                continue;
+            }
+            let mut remapped = inst.clone();
+            remapped.qubits = remapped
+                .qubits
+                .iter()
+                .map(|&q| {
+                    let p = layout.get_physical(q).ok_or(CompileError::MissingLayout)?;
+                    Ok(QubitId(p))
+                })
+                .collect::<CompileResult<Vec<_>>>()?;
+            emitted.push(remapped);
+            emitted_ops[i] = true;
+        }
+        Ok(())
+    };
+
+    // Oscillation / termination protection.
+    //
+    // Plain min-score SWAP selection can cycle: the best SWAP of one
+    // iteration is undone by the best SWAP of the next, forever (observed
+    // on Qrisp ripple-carry adders, see tests/sabre_termination.rs).
+    // Two counter-measures, following the SABRE literature:
+    //  * a decay penalty on recently swapped qubits breaks score ties and
+    //    discourages immediate undo;
+    //  * a stagnation escape guarantees progress: after too many SWAPs
+    //    without executing a single gate, the first front-layer gate is
+    //    routed greedily along the shortest path.
+    const DECAY_STEP: f64 = 0.01;
+    let mut decay: Vec<f64> = vec![1.0; coupling_map.num_qubits() as usize];
+    let mut swaps_since_progress: usize = 0;
+    let escape_threshold = (coupling_map.num_qubits() as usize).max(8) * 2;
+
+    // Bridge-or-SWAP lookahead: the distance of the next unresolved
+    // two-qubit gates other than `skip`, weighted like the extended set.
+    let upcoming_cost = |layout: &Layout, resolved: &[bool], skip: usize| -> f64 {
+        let cost: f64 = (0..two_qubit_gates.len())
+            .filter(|&tq_idx| !resolved[tq_idx] && tq_idx != skip)
+            .take(extended_set_size)
+            .map(|tq_idx| {
+                let gate = &two_qubit_gates[tq_idx];
+                let p0 = layout.get_physical(gate.q0).unwrap_or(0);
+                let p1 = layout.get_physical(gate.q1).unwrap_or(0);
+                distance(p0, p1)
+            })
+            .sum();
+        extended_set_weight * cost
+    };
+    // Gates for which a SWAP was preferred over a bridge; not weighed again.
+    let mut declined: FxHashSet<usize> = FxHashSet::default();
+
+    // Main loop.
+    while !front_layer.is_empty() {
+        if cancel.is_some_and(CancellationToken::is_cancelled) {
+            return Err(CompileError::Cancelled);
+        }
+
+        // Emit any ready single-qubit ops.
+        emit_ready_1q_ops(&mut emitted, &mut emitted_ops, &layout)?;
+
+        let mut progressed = false;
+
+        // Try to execute gates in the front layer that are already adjacent.
+        let mut executed_any = true;
+        while executed_any {
+            executed_any = false;
+            let mut remaining_front: Vec<usize> = Vec::new();
+            for &tq_idx in &front_layer {
+                let gate = &two_qubit_gates[tq_idx];
+                let p0 = layout
+                    .get_physical(gate.q0)
+                    .ok_or(CompileError::MissingLayout)?;
+                let p1 = layout
+                    .get_physical(gate.q1)
+                    .ok_or(CompileError::MissingLayout)?;
+
+                let bridge = match bridging {
+                    Some(reserved) if !coupling_map.is_connected(p0, p1) => {
+                        idle_bridge_path(&ops[gate.index], p0, p1, &layout, coupling_map, reserved)
+                    }
+                    _ => None,
+                };
+                let cx_bridge = if bridge.is_none()
+                    && bridges != BridgeMode::Never
+                    && !declined.contains(&tq_idx)
+                    && !coupling_map.is_connected(p0, p1)
+                {
+                    bridge_middle(&ops[gate.index], p0, p1, coupling_map).filter(|&middle| {
+                        let now = upcoming_cost(&layout, &resolved, tq_idx);
+                        let lookahead = |a: u32, b: u32| {
+                            let mut trial_layout = layout.clone();
+                            trial_layout.swap(a, b);
+                            upcoming_cost(&trial_layout, &resolved, tq_idx) - now
+                        };
+                        let bridged = prefer_bridge(bridges, costs, p0, middle, p1, lookahead);
+                        decisions.push(BridgeDecision {
+                            control: p0,
+                            middle,
+                            target: p1,
+                            bridged,
+                        });
+                        if !bridged {
+                            declined.insert(tq_idx);
+                        }
+                        bridged
+                    })
+                } else {
+                    None
+                };
+
+                if coupling_map.is_connected(p0, p1) || bridge.is_some() || cx_bridge.is_some() {
+                    // Execute this gate, through idle ancillas or a bridge.
+                    if let Some(path) = bridge {
+                        emitted.extend(bridge_ladder(&ops[gate.index], &path));
+                    } else if let Some(middle) = cx_bridge {
+                        emitted.extend(bridge_cx(&ops[gate.index], p0, middle, p1, "SabreRouting"));
+                    } else {
+                        let mut remapped = ops[gate.index].clone();
+                        remapped.qubits = vec![QubitId(p0), QubitId(p1)];
+                        emitted.push(remapped);
+                    }
+                    emitted_ops[gate.index] = true;
+
+                    // Mark resolved and advance cursors.
+                    resolved[tq_idx] = true;
+                    if let Some(c) = qubit_gate_cursor.get_mut(&gate.q0) {
+                        *c += 1;
+                    }
+                    if let Some(c) = qubit_gate_cursor.get_mut(&gate.q1) {
+                        *c += 1;
+                    }
+                    executed_any = true;
+                    progressed = true;
+                } else {
+                    remaining_front.push(tq_idx);
+                }
+            }
+            front_layer = remaining_front;
+
+            // After resolving some gates, emit any 1q ops that are now
+            // unblocked. This is critical: without this, 1q gates that
+            // become ready between successive 2q executions (e.g., H(q1)
+            // freed by CX(q0,q1)) would be deferred past the next 2q gate
+            // (e.g., CX(q1,q2)), violating the original dependency order.
+            if executed_any {
+                emit_ready_1q_ops(&mut emitted, &mut emitted_ops, &layout)?;
+            }
+
+            // After resolving some gates, new gates may become ready.
+            if executed_any {
+                for tq_idx in 0..two_qubit_gates.len() {
+                    if !resolved[tq_idx]
+                        && !front_layer.contains(&tq_idx)
+                        && is_gate_ready(tq_idx, &qubit_gate_cursor, &resolved)
+                    {
+                        front_layer.push(tq_idx);
+                    }
+                }
+            }
+        }
+
+        // If the front layer is empty, we're done.
+        if front_layer.is_empty() {
+            break;
+        }
+
+        if progressed {
+            decay.fill(1.0);
+            swaps_since_progress = 0;
+        }
+
+        // Stagnation escape: too many SWAPs without executing any gate
+        // means the heuristic is oscillating. Route the first front-layer
+        // gate greedily along the shortest path — that guarantees it
+        // executes on the next iteration, so the loop always terminates.
+        if swaps_since_progress >= escape_threshold {
+            let gate = &two_qubit_gates[front_layer[0]];
+            let p0 = layout
+                .get_physical(gate.q0)
+                .ok_or(CompileError::MissingLayout)?;
+            let p1 = layout
+                .get_physical(gate.q1)
+                .ok_or(CompileError::MissingLayout)?;
+            let path = coupling_map
+                .shortest_path(p0, p1)
+                .ok_or(CompileError::RoutingFailed {
+                    qubit1: p0,
+                    qubit2: p1,
+                })?;
+            for w in path.windows(2).take(path.len().saturating_sub(2)) {
+                emitted.push(
+                    Instruction::two_qubit_gate(StandardGate::Swap, QubitId(w[0]), QubitId(w[1]))
+                        .with_origin("SabreRouting"),
+                );
+                layout.swap(w[0], w[1]);
+                swap_count += 1;
+            }
+            decay.fill(1.0);
+            swaps_since_progress = 0;
+            continue;
+        }
+
+        // Build the extended set: next gates after the front layer.
+        let mut extended_set: Vec<usize> = Vec::new();
+        {
+            let front_set: FxHashSet<usize> = front_layer.iter().copied().collect();
+            for tq_idx in 0..two_qubit_gates.len() {
+                if !resolved[tq_idx]
+                    && !front_set.contains(&tq_idx)
+                    && extended_set.len() < extended_set_size
+                {
+                    // Check if this gate depends only on front-layer or resolved gates.
+                    let gate = &two_qubit_gates[tq_idx];
+                    let q0_next = qubit_gate_cursor.get(&gate.q0).copied().unwrap_or(0);
+                    let q1_next = qubit_gate_cursor.get(&gate.q1).copied().unwrap_or(0);
+                    let q0_list = qubit_gate_list.get(&gate.q0);
+                    let q1_list = qubit_gate_list.get(&gate.q1);
+
+                    let q0_close = q0_list.is_none_or(|list| {
+                        q0_next < list.len() && {
+                            let blocking = list[q0_next];
+                            front_set.contains(&blocking) || blocking == tq_idx
+                        }
+                    });
+                    let q1_close = q1_list.is_none_or(|list| {
+                        q1_next < list.len() && {
+                            let blocking = list[q1_next];
+                            front_set.contains(&blocking) || blocking == tq_idx
+                        }
+                    });
+
+                    if q0_close || q1_close {
+                        extended_set.push(tq_idx);
+                    }
+                }
+            }
+        }
+
+        // Score each candidate SWAP and pick the best.
+        // Candidates: any SWAP on an edge where at least one endpoint
+        // has a mapped qubit involved in a front-layer gate.
+        let mut best_swaps: Vec<(u32, u32)> = Vec::new();
+        let mut best_score = f64::MAX;
+
+        // Collect physical qubits involved in front-layer gates.
+        let mut front_physical: FxHashSet<u32> = FxHashSet::default();
+        for &tq_idx in &front_layer {
+            let gate = &two_qubit_gates[tq_idx];
+            if let Some(p) = layout.get_physical(gate.q0) {
+                front_physical.insert(p);
+            }
+            if let Some(p) = layout.get_physical(gate.q1) {
+                front_physical.insert(p);
+            }
+        }
+
+        // Evaluate candidate SWAPs in physical qubit order, so ties do not
+        // depend on hash-set iteration order.
+        let mut front_physical: Vec<u32> = front_physical.into_iter().collect();
+        front_physical.sort_unstable();
+        for &phys in &front_physical {
+            for neighbor in coupling_map.neighbors(phys) {
+                // Score = front_layer_cost + weight * extended_set_cost
+                // after applying this candidate SWAP.
+
+                // Tentatively swap in layout.
+                let mut trial_layout = layout.clone();
+                trial_layout.swap(phys, neighbor);
+
+                let front_cost: f64 = front_layer
+                    .iter()
+                    .map(|&tq_idx| {
+                        let gate = &two_qubit_gates[tq_idx];
+                        let p0 = trial_layout.get_physical(gate.q0).unwrap_or(0);
+                        let p1 = trial_layout.get_physical(gate.q1).unwrap_or(0);
+                        distance(p0, p1)
+                    })
+                    .sum();
+
+                let extended_cost: f64 = extended_set
+                    .iter()
+                    .map(|&tq_idx| {
+                        let gate = &two_qubit_gates[tq_idx];
+                        let p0 = trial_layout.get_physical(gate.q0).unwrap_or(0);
+                        let p1 = trial_layout.get_physical(gate.q1).unwrap_or(0);
+                        distance(p0, p1)
+                    })
+                    .sum();
+
+                // The decay penalty on recently swapped qubits breaks the
+                // exact score ties that cause SWAP/un-SWAP oscillation.
+                let mut score = (front_cost + extended_set_weight * extended_cost)
+                    * decay[phys as usize]
+                    * decay[neighbor as usize];
+
+                // The SWAP's own error, and the readout cost of moving
+                // measured qubits. Added outside the decay, so uniform
+                // errors leave the ranking unchanged.
+                if let Some(costs) = costs {
+                    let readout_shift = |from: u32, to: u32| {
+                        layout
+                            .get_logical(from)
+                            .filter(|q| measured.contains(q))
+                            .map_or(0.0, |_| costs.readout(to) - costs.readout(from))
+                    };
+                    score += costs.swap(phys, neighbor)
+                        + readout_shift(phys, neighbor)
+                        + readout_shift(neighbor, phys);
+                }
+
+                if score < best_score - SCORE_TIE_TOLERANCE {
+                    best_score = score;
+                    best_swaps.clear();
+                    best_swaps.push((phys, neighbor));
+                } else if score <= best_score + SCORE_TIE_TOLERANCE {
+                    best_swaps.push((phys, neighbor));
+                }
+            }
+        }
+
+        // Apply the best SWAP, breaking ties with the seed if there is one.
+        if best_swaps.is_empty() {
+            return Err(CompileError::PassFailed {
+                name: "SabreRouting".into(),
+                reason: "no valid SWAP candidate found".into(),
+            });
+        }
+        let choice = rng.as_mut().map_or(0, |rng| rng.index(best_swaps.len()));
+        let (sp1, sp2) = best_swaps[choice];
+
+        emitted.push(
+            Instruction::two_qubit_gate(StandardGate::Swap, QubitId(sp1), QubitId(sp2))
+                .with_origin("SabreRouting"),
+        );
+        layout.swap(sp1, sp2);
+        swap_count += 1;
+        decay[sp1 as usize] += DECAY_STEP;
+        decay[sp2 as usize] += DECAY_STEP;
+        swaps_since_progress += 1;
+    }
+
+    // Emit any remaining ops (1q gates after the last 2q gate on their qubit).
+    emit_ready_1q_ops(&mut emitted, &mut emitted_ops, &layout)?;
+
+    Ok((emitted, layout, swap_count))
+}
+
+/// Estimated error of a routing: the scaled cost of its SWAPs, and a third
+/// of a SWAP's for every other two-qubit gate.
+fn routing_error(ops: &[Instruction], costs: &ErrorCosts) -> f64 {
+    ops.iter()
+        .filter(|inst| inst.qubits.len() == 2)
+        .map(|inst| {
+            let swap = costs.swap(inst.qubits[0].0, inst.qubits[1].0);
+            match &inst.kind {
+                arvak_ir::InstructionKind::Gate(g)
+                    if matches!(g.kind, arvak_ir::GateKind::Standard(StandardGate::Swap)) =>
+                {
+                    swap
+                }
+                _ => swap / 3.0,
+            }
+        })
+        .sum()
+}
+
+/// Two-qubit gate count of a routing, counting each SWAP as three CXs.
+fn two_qubit_cost(ops: &[Instruction]) -> usize {
+    ops.iter()
+        .filter(|inst| inst.qubits.len() == 2)
+        .map(|inst| match &inst.kind {
+            arvak_ir::InstructionKind::Gate(g)
+                if matches!(g.kind, arvak_ir::GateKind::Standard(StandardGate::Swap)) =>
+            {
+                3
+            }
+            _ => 1,
+        })
+        .sum()
+}
+
+impl Pass for SabreRouting {
+    fn name(&self) -> &'static str {
+        "SabreRouting"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    #[allow(clippy::similar_names)]
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let coupling_map = properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+
+        let layout = properties
+            .layout
+            .as_ref()
+            .ok_or(CompileError::MissingLayout)?;
+
+        // Clone the initial layout (L0) before borrowing expires.
+        // Needed for correct layout tracking when reverse direction wins.
+        let layout_l0 = layout.clone();
+
+        // Collect all instructions in topological order.
+        let ops: Vec<Instruction> = dag
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+
+        // Run the forward pass only, once per trial.
+        //
+        // Note: the original SABRE paper describes a bidirectional approach
+        // (forward + reverse, pick best), but the reverse direction requires
+        // careful layout tracking (the reversed circuit's initial_layout and
+        // final layout are different from the forward pass). For correctness,
+        // we use only the forward pass. The forward heuristic with lookahead
+        // already produces good results.
+        let reserved: FxHashSet<u32> = properties
+            .get::<AncillaPool>()
+            .map(|pool| pool.allocated().map(|(q, _)| q).collect())
+            .unwrap_or_default();
+        let bridging = self.ancilla_bridging.then_some(&reserved);
+        let costs = properties
+            .get::<ErrorWeights>()
+            .and_then(|weights| weights.costs(coupling_map));
+        let bridges = properties.get::<BridgeMode>().copied().unwrap_or_default();
+
+        let lower_bound = ops.iter().filter(|inst| inst.qubits.len() == 2).count();
+        let mut trial_seeds = SeededRng::new(properties.seed.unwrap_or(0));
+        let mut best: Option<(f64, Vec<Instruction>, Layout, Vec<BridgeDecision>)> = None;
+        for trial in 0..self.trials {
+            let seed = if trial == 0 {
+                properties.seed
+            } else {
+                Some(trial_seeds.next_u64())
+            };
+            let mut decisions = Vec::new();
+            let (routed, routed_layout, _swaps) = sabre_pass(
+                &ops,
+                &layout_l0,
+                coupling_map,
+                self.extended_set_weight,
+                self.extended_set_size,
+                bridging,
+                bridges,
+                costs.as_ref(),
+                seed,
+                properties.get::<CancellationToken>(),
+                &mut decisions,
+            )?;
+            let gate_count = two_qubit_cost(&routed);
+            #[allow(clippy::cast_precision_loss)]
+            let cost = costs
+                .as_ref()
+                .map_or(gate_count as f64, |costs| routing_error(&routed, costs));
+            if best
+                .as_ref()
+                .is_none_or(|(best_cost, ..)| cost < *best_cost)
+            {
+                best = Some((cost, routed, routed_layout, decisions));
+            }
+            if gate_count <= lower_bound {
+                break;
+            }
+        }
+        let (_, chosen_ops, fwd_layout, decisions) = best.expect("at least one trial");
+
+        let new_initial = layout_l0.clone();
+        let new_final = fwd_layout;
+
+        // Build the new DAG with physical qubit wires spanning the whole
+        // device, so the circuit's qubit count stays consistent with the
+        // highest referenced physical index (emitters declare `qubit[n]`).
+        let mut new_dag = CircuitDag::new();
+        for p in 0..coupling_map.num_qubits() {
+            new_dag.add_qubit(QubitId(p));
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+
+        for inst in chosen_ops {
+            new_dag.apply(inst).map_err(CompileError::Ir)?;
+        }
+
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+        *dag = new_dag;
+
+        // Update layouts.
+        properties.initial_layout = Some(new_initial);
+        properties.layout = Some(new_final);
+        if bridges != BridgeMode::Never {
+            properties.insert(BridgeDecisions { decisions });
+        }
+
+        Ok(())
+    }
+
+    fn is_routing(&self) -> bool {
+        true
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.coupling_map.is_some() && properties.layout.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::TrivialLayout;
+    use crate::property::{BasisGates, CouplingMap};
+    use arvak_ir::{Circuit, InstructionKind, QubitId};
+
+    /// Helper: count SWAP gates in a DAG.
+    fn count_swaps(dag: &CircuitDag) -> usize {
+        dag.topological_ops()
+            .filter(
+                |(_, inst)| matches!(&inst.kind, InstructionKind::Gate(g) if g.name() == "swap"),
+            )
+            .count()
+    }
+
+    /// Helper: verify all two-qubit gates act on adjacent physical qubits.
+    fn assert_all_adjacent(dag: &CircuitDag, coupling_map: &CouplingMap) {
+        for (_, inst) in dag.topological_ops() {
+            if inst.qubits.len() == 2 {
+                assert!(
+                    coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0),
+                    "two-qubit gate {} on non-adjacent physical qubits ({}, {})",
+                    match &inst.kind {
+                        InstructionKind::Gate(g) => g.name().to_string(),
+                        _ => "unknown".into(),
+                    },
+                    inst.qubits[0].0,
+                    inst.qubits[1].0,
+                );
+            }
+        }
+    }
+
+    /// Helper: count gates of a given name.
+    fn count_gates(dag: &CircuitDag, name: &str) -> usize {
+        dag.topological_ops()
+            .filter(|(_, inst)| matches!(&inst.kind, InstructionKind::Gate(g) if g.name() == name))
+            .count()
+    }
+
+    #[test]
+    fn test_sabre_adjacent_no_swap() {
+        // CX on adjacent qubits (q0, q1) on linear(5) — no SWAP needed.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_swaps(&dag), 0, "no SWAPs needed for adjacent qubits");
+        assert_eq!(count_gates(&dag, "h"), 1, "H gate preserved");
+        assert_eq!(count_gates(&dag, "cx"), 1, "CX gate preserved");
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_distance_2() {
+        // CX on q0, q2 on linear(5) — distance 2, needs exactly 1 SWAP.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        // Must have exactly 1 SWAP to bring them adjacent.
+        assert_eq!(
+            count_swaps(&dag),
+            1,
+            "expected 1 SWAP for distance-2 qubits"
+        );
+        assert_eq!(count_gates(&dag, "cx"), 1, "CX gate preserved");
+        for (_, inst) in dag.topological_ops() {
+            let expected = (inst.name() == "swap").then_some("SabreRouting");
+            assert_eq!(inst.origin(), expected, "origin of {}", inst.name());
+        }
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_distance_3() {
+        // CX on q0, q3 on linear(5) — distance 3, needs 2 SWAPs.
+        let mut circuit = Circuit::with_size("test", 4, 0);
+        circuit.cx(QubitId(0), QubitId(3)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(
+            count_swaps(&dag),
+            2,
+            "expected 2 SWAPs for distance-3 qubits"
+        );
+        assert_eq!(count_gates(&dag, "cx"), 1, "CX gate preserved");
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_fewer_swaps_than_basic() {
+        // Circuit with multiple CX gates — SABRE should use the same or
+        // fewer SWAPs than BasicRouting because the lookahead prevents
+        // greedy local decisions that cause downstream problems.
+        //
+        // On linear(5): CX(0,4), CX(1,3), CX(0,2)
+        // BasicRouting processes greedily: 3+1+? SWAPs
+        // SABRE can reuse layout changes from earlier SWAPs.
+        let mut circuit = Circuit::with_size("test", 5, 0);
+        circuit.cx(QubitId(0), QubitId(4)).unwrap();
+        circuit.cx(QubitId(1), QubitId(3)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut dag_sabre = circuit.dag().clone();
+        let mut dag_basic = circuit.into_dag();
+
+        // Run BasicRouting.
+        let mut props_basic =
+            PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag_basic, &mut props_basic).unwrap();
+        crate::passes::BasicRouting
+            .run(&mut dag_basic, &mut props_basic)
+            .unwrap();
+        let basic_swaps = count_swaps(&dag_basic);
+
+        // Run SabreRouting.
+        let mut props_sabre =
+            PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag_sabre, &mut props_sabre).unwrap();
+        SabreRouting::new()
+            .run(&mut dag_sabre, &mut props_sabre)
+            .unwrap();
+        let sabre_swaps = count_swaps(&dag_sabre);
+
+        // SABRE should use at most as many SWAPs as BasicRouting.
+        assert!(
+            sabre_swaps <= basic_swaps,
+            "SABRE ({sabre_swaps} SWAPs) should be <= BasicRouting ({basic_swaps} SWAPs)"
+        );
+
+        // Both must produce correct (adjacent) output.
+        assert_all_adjacent(&dag_basic, props_basic.coupling_map.as_ref().unwrap());
+        assert_all_adjacent(&dag_sabre, props_sabre.coupling_map.as_ref().unwrap());
+
+        // Both must preserve all 3 CX gates.
+        assert_eq!(count_gates(&dag_basic, "cx"), 3);
+        assert_eq!(count_gates(&dag_sabre, "cx"), 3);
+    }
+
+    #[test]
+    fn test_sabre_preserves_single_qubit_gates() {
+        // Circuit with H + CX(non-adjacent) + Z — all gates must be preserved.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.z(QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_gates(&dag, "h"), 1, "H gate preserved");
+        assert_eq!(count_gates(&dag, "cx"), 1, "CX gate preserved");
+        assert_eq!(count_gates(&dag, "z"), 1, "Z gate preserved");
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_no_two_qubit_gates() {
+        // Circuit with only single-qubit gates — no SWAPs needed.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.x(QubitId(1)).unwrap();
+        circuit.z(QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_swaps(&dag), 0);
+        assert_eq!(dag.num_ops(), 3);
+    }
+
+    #[test]
+    fn test_sabre_star_topology() {
+        // Star topology: center q0 connected to all others.
+        // CX(1,2) requires routing through center.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::star(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        // Star: 1-0, 2-0 => distance(1,2)=2, need 1 SWAP.
+        assert_eq!(count_swaps(&dag), 1);
+        assert_eq!(count_gates(&dag, "cx"), 1);
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_full_connectivity_no_swaps() {
+        // Full connectivity — no SWAPs ever needed.
+        let mut circuit = Circuit::with_size("test", 4, 0);
+        circuit.cx(QubitId(0), QubitId(3)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::full(4), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_swaps(&dag), 0);
+        assert_eq!(count_gates(&dag, "cx"), 2);
+    }
+
+    #[test]
+    fn test_sabre_with_measurements() {
+        // Circuit with measurements — must be preserved through routing.
+        let mut circuit = Circuit::with_size("test", 3, 3);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.measure(QubitId(0), arvak_ir::ClbitId(0)).unwrap();
+        circuit.measure(QubitId(2), arvak_ir::ClbitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        let measure_count = dag
+            .topological_ops()
+            .filter(|(_, inst)| inst.is_measure())
+            .count();
+        assert_eq!(measure_count, 2, "measurements preserved");
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_ghz_circuit() {
+        // GHZ(5) on linear(5): H(0), CX(0,1), CX(1,2), CX(2,3), CX(3,4)
+        // All CX pairs are adjacent on linear topology — no SWAPs needed.
+        let circuit = Circuit::ghz(5).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(
+            count_swaps(&dag),
+            0,
+            "GHZ on matching topology needs 0 SWAPs"
+        );
+        assert_eq!(count_gates(&dag, "h"), 1);
+        assert_eq!(count_gates(&dag, "cx"), 4);
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_sabre_ghz_on_star_needs_swaps() {
+        // GHZ(4) on star(5): H(0), CX(0,1), CX(1,2), CX(2,3)
+        // Star: 0 is center. CX(1,2) and CX(2,3) are not adjacent — need SWAPs.
+        let circuit = Circuit::ghz(4).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::star(5), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert!(count_swaps(&dag) > 0, "GHZ on star topology needs SWAPs");
+        assert_eq!(count_gates(&dag, "cx"), 3, "all CX gates preserved");
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+    }
+
+    // ====================================================================
+    // MQT-inspired: grid topology routing
+    // (MQT test_heuristic: tests on real IBM topologies with grid structure)
+    // ====================================================================
+
+    #[test]
+    fn test_sabre_grid_2x3() {
+        // 2×3 grid (6 qubits):
+        //  0 - 1 - 2
+        //  |   |   |
+        //  3 - 4 - 5
+        let grid = CouplingMap::from_edge_list(
+            6,
+            &[(0, 1), (1, 2), (3, 4), (4, 5), (0, 3), (1, 4), (2, 5)],
+        );
+
+        // CX(0,5): diagonal, distance 2 on grid.
+        let mut circuit = Circuit::with_size("test", 6, 0);
+        circuit.cx(QubitId(0), QubitId(5)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(grid.clone(), BasisGates::ibm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert!(
+            count_swaps(&dag) >= 1,
+            "Grid routing needs SWAPs for diagonal qubits"
+        );
+        assert_eq!(count_gates(&dag, "cx"), 1, "CX gate preserved");
+        assert_all_adjacent(&dag, &grid);
+    }
+
+    #[test]
+    fn test_sabre_grid_adjacent_no_swap() {
+        // Adjacent qubits on grid need no SWAPs.
+        let grid = CouplingMap::from_edge_list(
+            6,
+            &[(0, 1), (1, 2), (3, 4), (4, 5), (0, 3), (1, 4), (2, 5)],
+        );
+
+        // CX(0,3) and CX(1,4): both adjacent on grid, no SWAPs.
+        let mut circuit = Circuit::with_size("test", 6, 0);
+        circuit.cx(QubitId(0), QubitId(3)).unwrap();
+        circuit.cx(QubitId(1), QubitId(4)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(grid.clone(), BasisGates::ibm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_swaps(&dag), 0, "Adjacent grid qubits need no SWAPs");
+        assert_all_adjacent(&dag, &grid);
+    }
+
+    // ====================================================================
+    // MQT-inspired: all 2q gates adjacent after routing (architecture constraint)
+    // (MQT test_heuristic: layout validity verification)
+    // ====================================================================
+
+    #[test]
+    fn test_sabre_multi_cx_all_adjacent() {
+        // Complex circuit with many distant CX gates.
+        // After routing, every 2q gate must be on adjacent physical qubits.
+        let mut circuit = Circuit::with_size("test", 5, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(4)).unwrap();
+        circuit.cx(QubitId(1), QubitId(3)).unwrap();
+        circuit.cx(QubitId(2), QubitId(4)).unwrap();
+        circuit.cx(QubitId(0), QubitId(3)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let coupling = CouplingMap::linear(5);
+        let mut props = PropertySet::new().with_target(coupling.clone(), BasisGates::ibm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        // The core invariant: every 2q gate acts on adjacent physical qubits.
+        assert_all_adjacent(&dag, &coupling);
+        assert_eq!(count_gates(&dag, "cx"), 4, "all CX gates preserved");
+    }
+
+    #[test]
+    fn test_sabre_ancilla_bridge_through_idle_qubits() {
+        // Logical q0 → physical 0, q1 → physical 3 on linear(4): physical
+        // 1 and 2 are idle, so the CX is bridged through them instead of
+        // moving the operands with SWAPs.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+        let snapshot = crate::passes::VerifyCompilation::snapshot(&dag).with_num_trials(4);
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(4), BasisGates::iqm());
+        let mut layout = Layout::new();
+        layout.add(QubitId(0), 0);
+        layout.add(QubitId(1), 3);
+        props.layout = Some(layout.clone());
+        props.initial_layout = Some(layout);
+
+        SabreRouting::new()
+            .with_ancilla_bridging(true)
+            .run(&mut dag, &mut props)
+            .unwrap();
+
+        assert_eq!(count_swaps(&dag), 0, "bridged CX must not insert SWAPs");
+        assert_eq!(count_gates(&dag, "cx"), 5, "distance 3 bridge uses 2d-1 CX");
+        assert_eq!(
+            props.layout.as_ref().unwrap().get_physical(QubitId(1)),
+            Some(3)
+        );
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+        snapshot.run(&mut dag, &mut props).unwrap();
+    }
+
+    #[test]
+    fn test_sabre_ancilla_bridge_skips_occupied_path() {
+        // Trivial layout on linear(3): the middle qubit holds logical q1, so
+        // it cannot serve as an ancilla and SWAP routing is used instead.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.x(QubitId(1)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        let mut dag = circuit.into_dag();
+        let snapshot = crate::passes::VerifyCompilation::snapshot(&dag);
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new()
+            .with_ancilla_bridging(true)
+            .run(&mut dag, &mut props)
+            .unwrap();
+
+        assert_eq!(count_swaps(&dag), 1);
+        assert_eq!(count_gates(&dag, "cx"), 1);
+        snapshot.run(&mut dag, &mut props).unwrap();
+    }
+
+    #[test]
+    fn test_sabre_ancilla_bridge_skips_allocated_ancillas() {
+        // The idle qubits between the operands are held by another pass.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+        let snapshot = crate::passes::VerifyCompilation::snapshot(&dag);
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        let mut layout = Layout::new();
+        layout.add(QubitId(0), 0);
+        layout.add(QubitId(1), 2);
+        props.layout = Some(layout.clone());
+        props.initial_layout = Some(layout);
+        assert_eq!(props.ancilla_pool().unwrap().request("Other"), Some(1));
+
+        SabreRouting::new()
+            .with_ancilla_bridging(true)
+            .run(&mut dag, &mut props)
+            .unwrap();
+
+        assert_eq!(count_swaps(&dag), 1);
+        snapshot.run(&mut dag, &mut props).unwrap();
+    }
+
+    #[test]
+    fn test_sabre_seeded_tie_breaking() {
+        // cx(0, 2) on linear(3): swapping either endpoint towards the
+        // middle scores the same, so the seed decides.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.h(QubitId(1)).unwrap();
+
+        let route = |seed: Option<u64>| {
+            let mut dag = circuit.clone().into_dag();
+            let mut props =
+                PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+            props.seed = seed;
+            TrivialLayout.run(&mut dag, &mut props).unwrap();
+            SabreRouting::new().run(&mut dag, &mut props).unwrap();
+            assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+            dag.to_bytes()
+        };
+
+        assert_eq!(route(None), route(None));
+        assert_eq!(route(Some(3)), route(Some(3)));
+        let routings: FxHashSet<Vec<u8>> = (0..16).map(|seed| route(Some(seed))).collect();
+        assert_eq!(routings.len(), 2);
+    }
+
+    /// IBM Falcon 27-qubit heavy-hex coupling map.
+    fn heavy_hex_27() -> CouplingMap {
+        CouplingMap::from_edge_list(
+            27,
+            &[
+                (0, 1),
+                (1, 2),
+                (1, 4),
+                (2, 3),
+                (3, 5),
+                (4, 7),
+                (5, 8),
+                (6, 7),
+                (7, 10),
+                (8, 9),
+                (8, 11),
+                (10, 12),
+                (11, 14),
+                (12, 13),
+                (12, 15),
+                (13, 14),
+                (14, 16),
+                (15, 18),
+                (16, 19),
+                (17, 18),
+                (18, 21),
+                (19, 20),
+                (19, 22),
+                (21, 23),
+                (22, 25),
+                (23, 24),
+                (24, 25),
+                (25, 26),
+            ],
+        )
+    }
+
+    /// Route `circuit` with `trials` and return the routed DAG.
+    fn route_with_trials(
+        circuit: &Circuit,
+        coupling_map: &CouplingMap,
+        trials: usize,
+    ) -> CircuitDag {
+        let mut dag = circuit.clone().into_dag();
+        let mut props = PropertySet::new()
+            .with_target(coupling_map.clone(), BasisGates::ibm())
+            .with_seed(11);
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new()
+            .with_trials(trials)
+            .run(&mut dag, &mut props)
+            .unwrap();
+        assert_all_adjacent(&dag, coupling_map);
+        dag
+    }
+
+    #[test]
+    fn test_sabre_trials_never_worse() {
+        let mut single_total = 0;
+        let mut trials_total = 0;
+        for (coupling_map, n) in [(CouplingMap::star(20), 20), (heavy_hex_27(), 27)] {
+            for seed in 0..4 {
+                let circuit = Circuit::random(n, 8, seed).unwrap();
+                let single = route_with_trials(&circuit, &coupling_map, 1);
+                let trials = route_with_trials(&circuit, &coupling_map, 8);
+
+                // Random layers contain SWAPs of their own.
+                let ops = |dag: &CircuitDag| dag.num_ops() - count_swaps(dag);
+                assert_eq!(ops(&single), ops(circuit.dag()));
+                assert_eq!(ops(&trials), ops(circuit.dag()));
+                let inserted = |dag: &CircuitDag| count_swaps(dag) - count_swaps(circuit.dag());
+                assert!(inserted(&trials) <= inserted(&single));
+                single_total += inserted(&single);
+                trials_total += inserted(&trials);
+
+                // Reproducible for a fixed seed.
+                let again = route_with_trials(&circuit, &coupling_map, 8);
+                assert_eq!(again.to_bytes(), trials.to_bytes());
+            }
+        }
+        assert!(
+            trials_total < single_total,
+            "trials ({trials_total} SWAPs) should beat a single run ({single_total} SWAPs)"
+        );
+    }
+
+    #[test]
+    fn test_sabre_trials_preserve_semantics() {
+        let circuit = Circuit::random(5, 10, 3).unwrap();
+        let mut dag = circuit.into_dag();
+        let snapshot = crate::passes::VerifyCompilation::snapshot(&dag).with_num_trials(4);
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::universal());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new()
+            .with_trials(8)
+            .run(&mut dag, &mut props)
+            .unwrap();
+        snapshot.run(&mut dag, &mut props).unwrap();
+    }
+
+    #[test]
+    fn test_sabre_error_weights_avoid_poor_couplers() {
+        // Ring 0-1-2-3-0: cx(0, 2) can meet over qubit 1 or qubit 3, and
+        // distance alone picks qubit 1. Its couplers are the poor ones.
+        let ring = CouplingMap::from_edge_list(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let mut circuit = Circuit::with_size("test", 4, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let route = |weights: Option<ErrorWeights>| {
+            let mut dag = circuit.clone().into_dag();
+            let mut props = PropertySet::new().with_target(ring.clone(), BasisGates::iqm());
+            if let Some(weights) = weights {
+                props.insert(weights);
+            }
+            TrivialLayout.run(&mut dag, &mut props).unwrap();
+            SabreRouting::new().run(&mut dag, &mut props).unwrap();
+            assert_eq!(count_swaps(&dag), 1);
+            dag.topological_ops()
+                .any(|(_, inst)| inst.qubits.contains(&QubitId(1)))
+        };
+
+        assert!(route(None));
+        let weights = ErrorWeights::new()
+            .with_coupler_error(0, 1, 0.05)
+            .with_coupler_error(1, 2, 0.05)
+            .with_coupler_error(2, 3, 0.005)
+            .with_coupler_error(3, 0, 0.005);
+        assert!(!route(Some(weights)));
+    }
+
+    #[test]
+    fn test_sabre_routes_loop_body_and_restores_layout() {
+        use arvak_ir::{ClassicalCondition, ClbitId};
+
+        let mut body = Circuit::with_size("body", 3, 1);
+        body.cx(QubitId(0), QubitId(2)).unwrap();
+        body.measure(QubitId(0), ClbitId(0)).unwrap();
+        let mut circuit = Circuit::with_size("test", 3, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), &body, 4)
+            .unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        let (_, block) = dag
+            .topological_ops()
+            .find(|(_, inst)| inst.is_while_loop())
+            .unwrap();
+        let names: Vec<&str> = block.blocks()[0].iter().map(Instruction::name).collect();
+        assert_eq!(names, ["swap", "cx", "measure", "swap"]);
+        assert_eq!(count_swaps(&dag), 0);
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+        let layout = props.layout.unwrap();
+        assert_eq!(layout.get_physical(QubitId(0)), Some(0));
+        assert_eq!(layout.get_physical(QubitId(1)), Some(1));
+    }
+
+    #[test]
+    fn test_sabre_bridges_when_swap_does_not_help() {
+        // A lone CX(q0, q2): a SWAP gains nothing later, so it is bridged
+        // and the layout stays put.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        circuit.h(QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        props.insert(BridgeMode::WhenCheaper);
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_swaps(&dag), 0);
+        assert_eq!(count_gates(&dag, "cx"), 4);
+        assert_all_adjacent(&dag, props.coupling_map.as_ref().unwrap());
+        let decisions = props.get::<BridgeDecisions>().unwrap();
+        assert_eq!(
+            decisions.decisions,
+            [BridgeDecision {
+                control: 0,
+                middle: 1,
+                target: 2,
+                bridged: true,
+            }]
+        );
+        assert_eq!(props.layout.unwrap().get_physical(QubitId(2)), Some(2));
+    }
+
+    #[test]
+    fn test_sabre_swaps_when_lookahead_gains() {
+        // Three CX(q0, q2): one SWAP makes all of them adjacent, which
+        // beats bridging each.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        for _ in 0..3 {
+            circuit.cx(QubitId(0), QubitId(2)).unwrap();
+            circuit.h(QubitId(0)).unwrap();
+        }
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        props.insert(BridgeMode::WhenCheaper);
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+
+        assert_eq!(count_swaps(&dag), 1);
+        assert_eq!(count_gates(&dag, "cx"), 3);
+        let decisions = props.get::<BridgeDecisions>().unwrap();
+        assert_eq!((decisions.bridged(), decisions.swapped()), (0, 1));
+
+        // Without a mode nothing is recorded.
+        let mut dag = {
+            let mut circuit = Circuit::with_size("test", 3, 0);
+            circuit.cx(QubitId(0), QubitId(2)).unwrap();
+            circuit.into_dag()
+        };
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::iqm());
+        TrivialLayout.run(&mut dag, &mut props).unwrap();
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+        assert!(props.get::<BridgeDecisions>().is_none());
+        assert_eq!(count_swaps(&dag), 1);
+    }
+}
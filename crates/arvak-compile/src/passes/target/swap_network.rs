@@ -0,0 +1,651 @@
+//! Swap-network routing for dense layers of commuting interactions.
+//!
+//! QAOA on dense graphs and Trotter steps of all-to-all Hamiltonians apply
+//! a two-qubit interaction to most pairs of qubits. The interactions are
+//! diagonal (`rzz`, `cz`, `cp`, `crz`), so they commute and can run in any
+//! order. SWAP insertion one gate at a time moves qubits back and forth
+//! without a plan and grows quickly with the number of pairs. A linear swap
+//! network (Kivlichan et al., PRL 120, 110501) does better: with the qubits
+//! on a line, `n` rounds of SWAPs on alternating even and odd neighbour
+//! pairs bring every pair next to each other exactly once, for depth `n`
+//! and at most `n(n-1)/2` SWAPs however the layer is ordered.
+//!
+//! [`SwapNetworkLayout`] looks for such a layer before layout. When one
+//! touches at least [`min_density`](SwapNetworkLayout::with_min_density)
+//! of all qubit pairs and the device has a line of physical qubits long
+//! enough, it places the circuit on that line and records a
+//! [`SwapNetworkPlan`]. [`SwapNetworkRouting`] then routes the circuit
+//! along the line: dense layers through swap networks, the remaining
+//! two-qubit gates by moving one operand along the line. Without a plan the
+//! usual layout and routing passes apply, which
+//! [`PassManagerBuilder::with_swap_networks`] arranges.
+//!
+//! ```rust
+//! use arvak_compile::passes::{SwapNetworkLayout, SwapNetworkPlan, SwapNetworkRouting};
+//! use arvak_compile::{CouplingMap, PassManager, PropertySet};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let mut circuit = Circuit::with_size("qaoa", 5, 0);
+//! for a in 0..5 {
+//!     for b in a + 1..5 {
+//!         circuit.rzz(0.4, QubitId(a), QubitId(b)).unwrap();
+//!     }
+//! }
+//!
+//! let mut pm = PassManager::new();
+//! pm.add_pass(SwapNetworkLayout::new());
+//! pm.add_pass(SwapNetworkRouting);
+//! let mut props = PropertySet::new();
+//! props.coupling_map = Some(CouplingMap::grid(2, 3));
+//! let mut dag = circuit.into_dag();
+//! pm.run(&mut dag, &mut props).unwrap();
+//!
+//! assert_eq!(props.get::<SwapNetworkPlan>().unwrap().line.len(), 5);
+//! ```
+//!
+//! [`PassManagerBuilder::with_swap_networks`]: crate::PassManagerBuilder::with_swap_networks
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use arvak_ir::{
+    CircuitDag, CircuitLevel, ClbitId, GateKind, Instruction, InstructionKind, QubitId,
+    StandardGate,
+};
+
+use crate::commutation::is_z_diagonal;
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::property::{CouplingMap, Layout, PropertySet};
+
+/// Search steps allowed when looking for a line of physical qubits.
+const LINE_SEARCH_BUDGET: usize = 100_000;
+
+/// The line of physical qubits chosen by [`SwapNetworkLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapNetworkPlan {
+    /// Physical qubits in line order; consecutive qubits are coupled.
+    pub line: Vec<u32>,
+    /// Fraction of all qubit pairs touched by the densest interaction
+    /// layer.
+    pub density: f64,
+    /// Layers touching at least this fraction of pairs are routed through
+    /// a swap network.
+    pub min_density: f64,
+}
+
+/// Layout pass placing circuits with a dense interaction layer on a line.
+///
+/// Runs only while no layout is set, and leaves the layout unset when the
+/// circuit has no layer of at least the minimum density, contains control
+/// flow, or the device has no line of enough qubits.
+pub struct SwapNetworkLayout {
+    /// Fraction of qubit pairs a layer must touch to use a swap network.
+    min_density: f64,
+}
+
+impl SwapNetworkLayout {
+    /// Default minimum fraction of qubit pairs in a layer.
+    pub const DEFAULT_MIN_DENSITY: f64 = 0.5;
+
+    /// Create the pass with the default minimum density.
+    pub fn new() -> Self {
+        Self {
+            min_density: Self::DEFAULT_MIN_DENSITY,
+        }
+    }
+
+    /// Use a swap network only for layers touching at least `density` of
+    /// all qubit pairs.
+    #[must_use]
+    pub fn with_min_density(mut self, density: f64) -> Self {
+        self.min_density = density;
+        self
+    }
+}
+
+impl Default for SwapNetworkLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for SwapNetworkLayout {
+    fn name(&self) -> &'static str {
+        "SwapNetworkLayout"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Analysis
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let coupling_map = properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+
+        let ops: Vec<Instruction> = dag
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+        if ops.iter().any(Instruction::is_control_flow) {
+            return Ok(());
+        }
+        let mut qubits: Vec<QubitId> = dag.qubits().collect();
+        qubits.sort_by_key(|q| q.0);
+        let n = qubits.len();
+        if n < 3 {
+            return Ok(());
+        }
+
+        let density = segments(ops)
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Layer(layer) => Some(layer_density(layer, n)),
+                Segment::Op(_) => None,
+            })
+            .fold(0.0, f64::max);
+        if density < self.min_density {
+            debug!(
+                "SwapNetworkLayout: densest layer touches {:.0}% of pairs, below {:.0}%",
+                density * 100.0,
+                self.min_density * 100.0
+            );
+            return Ok(());
+        }
+        let Some(line) = find_line(coupling_map, n) else {
+            debug!("SwapNetworkLayout: no line of {n} physical qubits");
+            return Ok(());
+        };
+
+        let layout: Layout = qubits.iter().copied().zip(line.iter().copied()).collect();
+        debug!(
+            "SwapNetworkLayout: layer density {:.2}, line {:?}",
+            density, line
+        );
+        properties.initial_layout = Some(layout.clone());
+        properties.layout = Some(layout);
+        properties.insert(SwapNetworkPlan {
+            line,
+            density,
+            min_density: self.min_density,
+        });
+        dag.set_level(CircuitLevel::Physical);
+        Ok(())
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.layout.is_none() && properties.coupling_map.is_some()
+    }
+}
+
+/// Routing pass along the line chosen by [`SwapNetworkLayout`].
+///
+/// Dense interaction layers run as swap networks; other two-qubit gates on
+/// qubits that are not coupled move their first operand along the line.
+/// Like the other routing passes, the output addresses physical qubits and
+/// the layout is updated to where each qubit ends up.
+pub struct SwapNetworkRouting;
+
+impl Pass for SwapNetworkRouting {
+    fn name(&self) -> &'static str {
+        "SwapNetworkRouting"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let coupling_map = properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+        let plan = properties
+            .get::<SwapNetworkPlan>()
+            .cloned()
+            .ok_or_else(|| CompileError::PassFailed {
+                name: self.name().into(),
+                reason: "no swap network plan; run SwapNetworkLayout first".into(),
+            })?;
+        let mut router = LineRouter {
+            coupling_map,
+            line: &plan.line,
+            position: plan.line.iter().enumerate().map(|(i, &p)| (p, i)).collect(),
+            routed: Vec::new(),
+            networks: 0,
+        };
+        let min_density = plan.min_density;
+        let n = plan.line.len();
+
+        let layout = properties
+            .layout
+            .as_mut()
+            .ok_or(CompileError::MissingLayout)?;
+
+        let ops: Vec<Instruction> = dag
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+        for segment in segments(ops) {
+            match segment {
+                Segment::Layer(layer) if layer_density(&layer, n) >= min_density => {
+                    router.network(layer, layout)?;
+                }
+                Segment::Layer(layer) => {
+                    for inst in layer {
+                        router.route(inst, layout)?;
+                    }
+                }
+                Segment::Op(inst) => router.route(*inst, layout)?,
+            }
+        }
+        debug!(
+            "SwapNetworkRouting: {} swap network(s) on {} qubits",
+            router.networks, n
+        );
+
+        let mut new_dag = CircuitDag::new();
+        for physical in 0..coupling_map.num_qubits() {
+            new_dag.add_qubit(QubitId(physical));
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+        for inst in router.routed {
+            new_dag.apply(inst).map_err(CompileError::Ir)?;
+        }
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+        *dag = new_dag;
+        Ok(())
+    }
+
+    fn is_routing(&self) -> bool {
+        true
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.layout.is_some() && properties.get::<SwapNetworkPlan>().is_some()
+    }
+}
+
+/// A stretch of the circuit in routing order.
+enum Segment {
+    /// An instruction routed on its own.
+    Op(Box<Instruction>),
+    /// Commuting diagonal two-qubit gates that may run in any order.
+    Layer(Vec<Instruction>),
+}
+
+/// Whether `inst` is an unconditioned gate diagonal in the computational
+/// basis.
+fn is_diagonal(inst: &Instruction) -> bool {
+    let InstructionKind::Gate(gate) = &inst.kind else {
+        return false;
+    };
+    gate.condition.is_none()
+        && matches!(&gate.kind, GateKind::Standard(standard) if is_z_diagonal(standard))
+}
+
+/// Split `ops` into interaction layers and the instructions around them.
+///
+/// Diagonal two-qubit gates join the current layer. An instruction on
+/// qubits outside the layer is moved ahead of it, and diagonal
+/// single-qubit gates commute with it. Anything else on a qubit of the
+/// layer is deferred until after the layer, along with every later
+/// instruction sharing a qubit or clbit with a deferred one, so that a
+/// gate on one qubit does not split the layer for the others. The
+/// deferred instructions are then split the same way.
+fn segments(ops: Vec<Instruction>) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut pending = ops;
+    while !pending.is_empty() {
+        let mut layer: Vec<Instruction> = Vec::new();
+        let mut layer_qubits: FxHashSet<QubitId> = FxHashSet::default();
+        let mut deferred: Vec<Instruction> = Vec::new();
+        let mut blocked_qubits: FxHashSet<QubitId> = FxHashSet::default();
+        let mut blocked_clbits: FxHashSet<ClbitId> = FxHashSet::default();
+        for inst in pending {
+            let clbits = || inst.clbits.iter().chain(inst.condition_clbits());
+            let blocked = inst.qubits.iter().any(|q| blocked_qubits.contains(q))
+                || clbits().any(|c| blocked_clbits.contains(c));
+            let diagonal = is_diagonal(&inst);
+            if !blocked && diagonal && inst.qubits.len() == 2 {
+                layer_qubits.extend(inst.qubits.iter().copied());
+                layer.push(inst);
+                continue;
+            }
+            let commutes = diagonal && inst.qubits.len() == 1;
+            if blocked || (!commutes && inst.qubits.iter().any(|q| layer_qubits.contains(q))) {
+                blocked_qubits.extend(inst.qubits.iter().copied());
+                blocked_clbits.extend(clbits().copied());
+                deferred.push(inst);
+                continue;
+            }
+            segments.push(Segment::Op(Box::new(inst)));
+        }
+        if !layer.is_empty() {
+            segments.push(Segment::Layer(layer));
+        }
+        pending = deferred;
+    }
+    segments
+}
+
+/// Fraction of the `n(n-1)/2` pairs of `n` qubits that `layer` touches.
+#[allow(clippy::cast_precision_loss)]
+fn layer_density(layer: &[Instruction], n: usize) -> f64 {
+    let pairs: FxHashSet<(u32, u32)> = layer.iter().map(pair).collect();
+    pairs.len() as f64 / (n * (n - 1) / 2).max(1) as f64
+}
+
+/// The unordered qubit pair of a two-qubit instruction.
+fn pair(inst: &Instruction) -> (u32, u32) {
+    let (a, b) = (inst.qubits[0].0, inst.qubits[1].0);
+    (a.min(b), a.max(b))
+}
+
+/// A simple path of `n` coupled physical qubits, if the search finds one.
+///
+/// Depth-first search from the least connected qubits, always extending
+/// to the neighbour with the fewest free neighbours (Warnsdorff's rule),
+/// which finds snake-shaped lines on grids and heavy-hex lattices without
+/// backtracking.
+pub(crate) fn find_line(coupling_map: &CouplingMap, n: usize) -> Option<Vec<u32>> {
+    let num_qubits = coupling_map.num_qubits();
+    if n == 0 || n > num_qubits as usize {
+        return None;
+    }
+    let mut starts: Vec<u32> = (0..num_qubits).collect();
+    starts.sort_by_key(|&q| (coupling_map.neighbors(q).count(), q));
+
+    let mut budget = LINE_SEARCH_BUDGET;
+    for start in starts {
+        let mut path = vec![start];
+        let mut on_path = vec![false; num_qubits as usize];
+        on_path[start as usize] = true;
+        if extend_line(coupling_map, n, &mut path, &mut on_path, &mut budget) {
+            return Some(path);
+        }
+        if budget == 0 {
+            break;
+        }
+    }
+    None
+}
+
+fn extend_line(
+    coupling_map: &CouplingMap,
+    n: usize,
+    path: &mut Vec<u32>,
+    on_path: &mut [bool],
+    budget: &mut usize,
+) -> bool {
+    if path.len() == n {
+        return true;
+    }
+    if *budget == 0 {
+        return false;
+    }
+    *budget -= 1;
+
+    let last = path[path.len() - 1];
+    let free = |q: u32, on_path: &[bool]| {
+        coupling_map
+            .neighbors(q)
+            .filter(|&r| !on_path[r as usize])
+            .count()
+    };
+    let mut next: Vec<u32> = coupling_map
+        .neighbors(last)
+        .filter(|&q| !on_path[q as usize])
+        .collect();
+    next.sort_by_key(|&q| (free(q, on_path), q));
+    next.dedup();
+    for q in next {
+        path.push(q);
+        on_path[q as usize] = true;
+        if extend_line(coupling_map, n, path, on_path, budget) {
+            return true;
+        }
+        on_path[q as usize] = false;
+        path.pop();
+    }
+    false
+}
+
+/// Routes instructions with all logical qubits on a line of physical
+/// qubits.
+struct LineRouter<'a> {
+    coupling_map: &'a CouplingMap,
+    line: &'a [u32],
+    /// Index of each line qubit in `line`.
+    position: FxHashMap<u32, usize>,
+    /// Routed instructions on physical qubits.
+    routed: Vec<Instruction>,
+    /// Number of swap networks inserted.
+    networks: usize,
+}
+
+impl LineRouter<'_> {
+    /// Route one instruction, moving its first operand along the line
+    /// until it is coupled to the second.
+    fn route(&mut self, inst: Instruction, layout: &mut Layout) -> CompileResult<()> {
+        if inst.qubits.len() == 2 {
+            let p0 = physical(layout, inst.qubits[0])?;
+            let p1 = physical(layout, inst.qubits[1])?;
+            if !self.coupling_map.is_connected(p0, p1) {
+                let (mut i, j) = match (self.position.get(&p0), self.position.get(&p1)) {
+                    (Some(&i), Some(&j)) => (i, j),
+                    _ => {
+                        return Err(CompileError::RoutingFailed {
+                            qubit1: p0,
+                            qubit2: p1,
+                        });
+                    }
+                };
+                while i.abs_diff(j) > 1 {
+                    let k = if i < j { i + 1 } else { i - 1 };
+                    self.swap(i.min(k), layout);
+                    i = k;
+                }
+            }
+        }
+        self.emit(inst, layout)
+    }
+
+    /// Run the commuting gates of `layer` through a swap network.
+    fn network(&mut self, layer: Vec<Instruction>, layout: &mut Layout) -> CompileResult<()> {
+        let mut pending: FxHashMap<(u32, u32), Vec<Instruction>> = FxHashMap::default();
+        for inst in layer {
+            pending.entry(pair(&inst)).or_default().push(inst);
+        }
+        self.networks += 1;
+
+        let n = self.line.len();
+        'rounds: for round in 0..n {
+            for i in (round % 2..n.saturating_sub(1)).step_by(2) {
+                let a = logical(layout, self.line[i])?;
+                let b = logical(layout, self.line[i + 1])?;
+                let key = (a.0.min(b.0), a.0.max(b.0));
+                for inst in pending.remove(&key).unwrap_or_default() {
+                    self.emit(inst, layout)?;
+                }
+                if pending.is_empty() {
+                    break 'rounds;
+                }
+                self.swap(i, layout);
+            }
+        }
+        // Every pair meets within n rounds; only gates on qubits off the
+        // line could be left.
+        if let Some(inst) = pending.into_values().flatten().next() {
+            return Err(CompileError::RoutingFailed {
+                qubit1: inst.qubits[0].0,
+                qubit2: inst.qubits[1].0,
+            });
+        }
+        Ok(())
+    }
+
+    /// Swap the qubits at line positions `i` and `i + 1`.
+    fn swap(&mut self, i: usize, layout: &mut Layout) {
+        let (a, b) = (self.line[i], self.line[i + 1]);
+        self.routed.push(
+            Instruction::two_qubit_gate(StandardGate::Swap, QubitId(a), QubitId(b))
+                .with_origin("SwapNetworkRouting"),
+        );
+        layout.swap(a, b);
+    }
+
+    /// Append `inst` with its qubits mapped to physical qubits.
+    fn emit(&mut self, mut inst: Instruction, layout: &Layout) -> CompileResult<()> {
+        inst.qubits = inst
+            .qubits
+            .iter()
+            .map(|&q| physical(layout, q).map(QubitId))
+            .collect::<CompileResult<_>>()?;
+        self.routed.push(inst);
+        Ok(())
+    }
+}
+
+fn physical(layout: &Layout, logical: QubitId) -> CompileResult<u32> {
+    layout
+        .get_physical(logical)
+        .ok_or(CompileError::MissingLayout)
+}
+
+fn logical(layout: &Layout, physical: u32) -> CompileResult<QubitId> {
+    layout
+        .get_logical(physical)
+        .ok_or(CompileError::MissingLayout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PassManager;
+    use crate::equivalence::{CheckOutcome, Verification, VerificationReport};
+    use arvak_ir::Circuit;
+
+    fn all_to_all(n: u32) -> Circuit {
+        let mut circuit = Circuit::with_size("qaoa", n, n);
+        for q in 0..n {
+            circuit.h(QubitId(q)).unwrap();
+        }
+        for a in 0..n {
+            for b in a + 1..n {
+                circuit.rzz(0.3, QubitId(a), QubitId(b)).unwrap();
+            }
+        }
+        for q in 0..n {
+            circuit.rx(0.7, QubitId(q)).unwrap();
+        }
+        circuit
+    }
+
+    fn swaps(dag: &CircuitDag) -> usize {
+        dag.topological_ops()
+            .filter(|(_, inst)| inst.name() == "swap")
+            .count()
+    }
+
+    fn route(circuit: Circuit, coupling_map: CouplingMap) -> (CircuitDag, PropertySet) {
+        let mut pm = PassManager::new();
+        pm.add_pass(SwapNetworkLayout::new());
+        pm.add_pass(SwapNetworkRouting);
+        let mut props = PropertySet::new();
+        props.coupling_map = Some(coupling_map);
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+        (dag, props)
+    }
+
+    #[test]
+    fn test_swap_network_routes_all_pairs() {
+        let n = 6;
+        let coupling_map = CouplingMap::grid(2, 3);
+        let (dag, props) = route(all_to_all(n), coupling_map.clone());
+
+        let plan = props.get::<SwapNetworkPlan>().unwrap();
+        assert!((plan.density - 1.0).abs() < 1e-12);
+        for w in plan.line.windows(2) {
+            assert!(coupling_map.is_connected(w[0], w[1]));
+        }
+        for (_, inst) in dag.topological_ops() {
+            if inst.qubits.len() == 2 {
+                assert!(coupling_map.is_connected(inst.qubits[0].0, inst.qubits[1].0));
+            }
+        }
+        let rzz = dag
+            .topological_ops()
+            .filter(|(_, inst)| inst.name() == "rzz")
+            .count();
+        assert_eq!(rzz, 15);
+        assert!(swaps(&dag) <= 15);
+    }
+
+    #[test]
+    fn test_swap_network_preserves_circuit() {
+        let mut pm = PassManager::new().with_verification(Verification::new());
+        pm.add_pass(SwapNetworkLayout::new());
+        pm.add_pass(SwapNetworkRouting);
+        let mut props = PropertySet::new();
+        props.coupling_map = Some(CouplingMap::linear(5));
+        let mut dag = all_to_all(5).into_dag();
+        pm.run(&mut dag, &mut props).unwrap();
+
+        let report = props.get::<VerificationReport>().unwrap();
+        assert!(report.checks.iter().any(|check| {
+            check.pass == "SwapNetworkRouting" && matches!(check.outcome, CheckOutcome::Verified(_))
+        }));
+        assert!(swaps(&dag) <= 10);
+    }
+
+    #[test]
+    fn test_swap_network_skips_sparse_circuits() {
+        let mut circuit = Circuit::with_size("ring", 6, 0);
+        for q in 0..6 {
+            circuit.rzz(0.3, QubitId(q), QubitId((q + 1) % 6)).unwrap();
+        }
+        let (_, props) = route(circuit, CouplingMap::linear(6));
+        assert!(props.get::<SwapNetworkPlan>().is_none());
+        assert!(props.layout.is_none());
+    }
+
+    #[test]
+    fn test_segments_defer_gates_on_layer_qubits() {
+        let mut circuit = Circuit::with_size("split", 4, 1);
+        circuit.rzz(0.3, QubitId(0), QubitId(1)).unwrap();
+        circuit.rx(0.7, QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.rzz(0.3, QubitId(2), QubitId(3)).unwrap();
+        circuit.rzz(0.3, QubitId(1), QubitId(2)).unwrap();
+        circuit.rzz(0.3, QubitId(0), QubitId(3)).unwrap();
+        let ops: Vec<Instruction> = circuit
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+
+        let shape: Vec<String> = segments(ops)
+            .iter()
+            .map(|segment| match segment {
+                Segment::Op(inst) => inst.name().to_string(),
+                Segment::Layer(layer) => format!("layer {}", layer.len()),
+            })
+            .collect();
+        assert_eq!(shape, ["layer 3", "rx", "measure", "layer 1"]);
+    }
+
+    #[test]
+    fn test_find_line() {
+        let line = find_line(&CouplingMap::grid(3, 3), 9).unwrap();
+        assert_eq!(line.len(), 9);
+        assert!(find_line(&CouplingMap::star(5), 3).is_some());
+        assert!(find_line(&CouplingMap::star(5), 4).is_none());
+    }
+}
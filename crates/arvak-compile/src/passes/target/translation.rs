@@ -0,0 +1,1885 @@
+//! Basis translation passes.
+
+use std::f64::consts::PI;
+
+use arvak_ir::{
+    CircuitDag, Gate, GateKind, Instruction, InstructionKind, ParameterExpression, StandardGate,
+};
+
+use num_complex::Complex64;
+
+use crate::error::{CompileError, CompileResult};
+use crate::gridsynth::{phase_gates, t_power_of};
+use crate::pass::{Pass, PassKind};
+use crate::passes::target::unitary_synthesis::{Entangler, synthesize_2q};
+use crate::property::PropertySet;
+
+/// Basis translation pass.
+///
+/// Translates gates to the target basis gate set.
+/// Currently supports translation to:
+/// - IQM basis: PRX + CZ
+/// - IBM basis: RZ + SX + X + CX
+/// - IBM Heron basis: RZ + SX + X + CZ
+/// - Neutral-atom basis: RZ + RX + RY + CZ (planqc, PASQAL digital mode)
+/// - Clifford+T basis: H + S + T + CX, for rotations by multiples of π/4
+///   (other angles need [`CliffordTSynthesis`](super::CliffordTSynthesis))
+///
+/// Gates inside if/else and loop bodies are translated as well.
+pub struct BasisTranslation;
+
+impl Pass for BasisTranslation {
+    fn name(&self) -> &'static str {
+        "BasisTranslation"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let basis_gates = properties
+            .basis_gates
+            .as_ref()
+            .ok_or(CompileError::MissingBasisGates)?;
+
+        // Rebuild the DAG from scratch to guarantee correct gate ordering.
+        // The old approach used `substitute_node` which appends replacements at
+        // wire ends instead of at the original node position, producing wrong
+        // circuits whenever a non-final gate is translated (e.g. H before CX).
+        let mut new_dag = CircuitDag::new();
+        for qubit in dag.qubits().collect::<Vec<_>>() {
+            new_dag.add_qubit(qubit);
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+
+        for (_idx, inst) in dag.topological_ops() {
+            for r in translate_instruction(inst, basis_gates)? {
+                new_dag.apply(r)?;
+            }
+        }
+
+        *dag = new_dag;
+        Ok(())
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.basis_gates.is_some()
+    }
+}
+
+/// Translate one instruction to the target basis, recursing into the bodies
+/// of control-flow blocks. Instructions that are not gates are kept.
+fn translate_instruction(
+    inst: &Instruction,
+    basis: &crate::property::BasisGates,
+) -> CompileResult<Vec<Instruction>> {
+    if inst.is_control_flow() {
+        let mut translated = inst.clone();
+        for block in translated.blocks_mut() {
+            let mut body = Vec::with_capacity(block.len());
+            for nested in block.iter() {
+                body.extend(translate_instruction(nested, basis)?);
+            }
+            *block = body;
+        }
+        return Ok(vec![translated]);
+    }
+    match inst.as_gate() {
+        Some(gate) if !is_in_basis(gate, basis) => Ok(translate_gate(inst, basis)?
+            .into_iter()
+            .map(|r| r.with_metadata_from(inst))
+            .collect()),
+        _ => Ok(vec![inst.clone()]),
+    }
+}
+
+/// Check if a gate is in the target basis.
+fn is_in_basis(gate: &Gate, basis: &crate::property::BasisGates) -> bool {
+    basis.contains(gate.name())
+}
+
+/// Translate a gate instruction to the target basis.
+#[allow(
+    clippy::similar_names,
+    clippy::cast_possible_truncation,
+    clippy::no_effect_underscore_binding
+)]
+pub(crate) fn translate_gate(
+    instruction: &Instruction,
+    basis: &crate::property::BasisGates,
+) -> CompileResult<Vec<Instruction>> {
+    let InstructionKind::Gate(gate) = &instruction.kind else {
+        return Ok(vec![instruction.clone()]);
+    };
+
+    if instruction.qubits.is_empty() {
+        return Ok(vec![instruction.clone()]);
+    }
+
+    let _qubit = instruction.qubits[0];
+
+    // Check if it's IQM basis (PRX + CZ)
+    let is_iqm = basis.contains("prx") && basis.contains("cz");
+    // Check if it's IBM basis (RZ + SX + X + CX)
+    let is_ibm = basis.contains("rz") && basis.contains("sx") && basis.contains("cx");
+    // Check if it's IBM Eagle basis (RZ + SX + X + ECR, no CX, no CZ)
+    let is_eagle = basis.contains("rz") && basis.contains("sx") && basis.contains("ecr") && !is_ibm;
+    // Check if it's IBM Heron basis (RZ + SX + X + CZ, no CX, no ECR)
+    let is_heron = basis.contains("rz")
+        && basis.contains("sx")
+        && basis.contains("cz")
+        && !is_ibm
+        && !is_eagle;
+    // Check if it's neutral-atom basis (RZ + RX + RY + CZ; no SX, no PRX)
+    // Used for planqc (Rydberg CZ, global rotations) and PASQAL digital mode.
+    let is_neutral_atom = basis.contains("rz")
+        && basis.contains("rx")
+        && basis.contains("ry")
+        && basis.contains("cz")
+        && !basis.contains("sx")
+        && !is_iqm;
+
+    match &gate.kind {
+        GateKind::Standard(std_gate) => {
+            let direct = if is_iqm {
+                translate_to_iqm(std_gate, &instruction.qubits)
+            } else if is_ibm {
+                translate_to_ibm(std_gate, &instruction.qubits)
+            } else if is_eagle {
+                translate_to_eagle(std_gate, &instruction.qubits)
+            } else if is_heron {
+                translate_to_heron(std_gate, &instruction.qubits)
+            } else if is_neutral_atom {
+                translate_to_neutral_atom(std_gate, &instruction.qubits)
+            } else if basis.is_clifford_t() {
+                translate_to_clifford_t(std_gate, &instruction.qubits, basis)
+            } else {
+                // Unknown basis combination — fail explicitly rather than
+                // silently passing the gate through (CLAUDE.md: never skip
+                // unsupported operations).
+                Err(CompileError::GateNotInBasis(format!(
+                    "{} (basis unrecognised)",
+                    gate.name()
+                )))
+            };
+
+            let translated = match direct {
+                Ok(translated) => translated,
+                // Gate has no target-specific rule: decompose it into
+                // simpler standard gates and translate those recursively.
+                Err(CompileError::GateNotInBasis(original)) => {
+                    let Some(steps) = decompose_to_simpler(std_gate, &instruction.qubits) else {
+                        return Err(CompileError::GateNotInBasis(original));
+                    };
+                    let mut out = Vec::new();
+                    for step in steps {
+                        if let InstructionKind::Gate(g) = &step.kind {
+                            if is_in_basis(g, basis) {
+                                out.push(step);
+                                continue;
+                            }
+                        }
+                        out.extend(translate_gate(&step, basis)?);
+                    }
+                    out
+                }
+                Err(e) => return Err(e),
+            };
+            Ok(with_condition(translated, gate))
+        }
+        GateKind::Custom(custom) => {
+            // Custom gates with a definition are expanded and their body
+            // translated recursively; a condition carries over to every gate.
+            if custom.definition.is_some() {
+                let mut out = Vec::new();
+                for mut step in custom.expand(&instruction.qubits)? {
+                    if let Some(g) = step.gate_mut() {
+                        if gate.condition.is_some() {
+                            g.condition.clone_from(&gate.condition);
+                        }
+                        if is_in_basis(g, basis) {
+                            out.push(step);
+                            continue;
+                        }
+                    }
+                    out.extend(translate_gate(&step, basis)?);
+                }
+                return Ok(out);
+            }
+
+            // Custom 2-qubit gates with a unitary matrix (e.g. from ConsolidateBlocks)
+            // are decomposed via KAK into CX + Rz/Ry, then translated to the target basis.
+            if let Some(ref matrix) = custom.matrix {
+                if matrix.len() == 16 && instruction.qubits.len() == 2 {
+                    return decompose_custom_2q(matrix, &instruction.qubits, basis)
+                        .map(|translated| with_condition(translated, gate));
+                }
+            }
+            Err(CompileError::GateNotInBasis(gate.name().to_string()))
+        }
+        // Modified gates are expanded into plain gates, translated like the
+        // body of a custom gate.
+        GateKind::Modified(modified) => {
+            let mut out = Vec::new();
+            for mut step in modified.expand(&instruction.qubits)? {
+                if let Some(g) = step.gate_mut() {
+                    if gate.condition.is_some() {
+                        g.condition.clone_from(&gate.condition);
+                    }
+                    if is_in_basis(g, basis) {
+                        out.push(step);
+                        continue;
+                    }
+                }
+                out.extend(translate_gate(&step, basis)?);
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Give every gate in `translated` the classical condition of `gate`, so a
+/// conditioned gate stays conditioned in the target basis.
+fn with_condition(mut translated: Vec<Instruction>, gate: &Gate) -> Vec<Instruction> {
+    if gate.condition.is_some() {
+        for inst in &mut translated {
+            if let Some(g) = inst.gate_mut() {
+                g.condition.clone_from(&gate.condition);
+            }
+        }
+    }
+    translated
+}
+
+/// Decompose a standard gate without a target-specific translation rule into
+/// simpler standard gates (Rz/Ry/Rx/H/S/T/X/CX) that every target translator
+/// handles. Returns `None` for gates with no known decomposition.
+///
+/// All decompositions are exact up to global phase, which is unobservable.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn decompose_to_simpler(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+) -> Option<Vec<Instruction>> {
+    use ParameterExpression as P;
+    let half = |p: &P| p.clone() / P::constant(2.0);
+    let single = Instruction::single_qubit_gate;
+    let two = Instruction::two_qubit_gate;
+
+    let q0 = *qubits.first()?;
+
+    Some(match gate {
+        // P(lambda) == Rz(lambda) up to global phase.
+        StandardGate::P(lambda) => vec![single(StandardGate::Rz(lambda.clone()), q0)],
+
+        // U(theta, phi, lambda) == Rz(phi) . Ry(theta) . Rz(lambda) up to
+        // global phase; circuit order applies Rz(lambda) first.
+        StandardGate::U(theta, phi, lambda) => vec![
+            single(StandardGate::Rz(lambda.clone()), q0),
+            single(StandardGate::Ry(theta.clone()), q0),
+            single(StandardGate::Rz(phi.clone()), q0),
+        ],
+
+        // SX == Rx(pi/2), SXdg == Rx(-pi/2), both up to global phase.
+        StandardGate::SX => vec![single(StandardGate::Rx(P::constant(PI / 2.0)), q0)],
+        StandardGate::SXdg => vec![single(StandardGate::Rx(P::constant(-PI / 2.0)), q0)],
+
+        // PRX(theta, phi) == Rz(phi) . Rx(theta) . Rz(-phi)  (exact).
+        StandardGate::PRX(theta, phi) => vec![
+            single(StandardGate::Rz(-phi.clone()), q0),
+            single(StandardGate::Rx(theta.clone()), q0),
+            single(StandardGate::Rz(phi.clone()), q0),
+        ],
+
+        StandardGate::CY => {
+            let t = *qubits.get(1)?;
+            vec![
+                single(StandardGate::Sdg, t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::S, t),
+            ]
+        }
+
+        StandardGate::CH => {
+            let t = *qubits.get(1)?;
+            vec![
+                single(StandardGate::S, t),
+                single(StandardGate::H, t),
+                single(StandardGate::T, t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::Tdg, t),
+                single(StandardGate::H, t),
+                single(StandardGate::Sdg, t),
+            ]
+        }
+
+        StandardGate::CRz(theta) => {
+            let t = *qubits.get(1)?;
+            vec![
+                single(StandardGate::Rz(half(theta)), t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::Rz(-half(theta)), t),
+                two(StandardGate::CX, q0, t),
+            ]
+        }
+
+        StandardGate::CRy(theta) => {
+            let t = *qubits.get(1)?;
+            vec![
+                single(StandardGate::Ry(half(theta)), t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::Ry(-half(theta)), t),
+                two(StandardGate::CX, q0, t),
+            ]
+        }
+
+        // CRx == (I x H) . CRz . (I x H)
+        StandardGate::CRx(theta) => {
+            let t = *qubits.get(1)?;
+            vec![
+                single(StandardGate::H, t),
+                single(StandardGate::Rz(half(theta)), t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::Rz(-half(theta)), t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::H, t),
+            ]
+        }
+
+        StandardGate::CP(lambda) => {
+            let t = *qubits.get(1)?;
+            vec![
+                single(StandardGate::Rz(half(lambda)), q0),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::Rz(-half(lambda)), t),
+                two(StandardGate::CX, q0, t),
+                single(StandardGate::Rz(half(lambda)), t),
+            ]
+        }
+
+        StandardGate::RZZ(theta) => {
+            let q1 = *qubits.get(1)?;
+            vec![
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::Rz(theta.clone()), q1),
+                two(StandardGate::CX, q0, q1),
+            ]
+        }
+
+        // RXX == (H x H) . RZZ . (H x H)
+        StandardGate::RXX(theta) => {
+            let q1 = *qubits.get(1)?;
+            vec![
+                single(StandardGate::H, q0),
+                single(StandardGate::H, q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::Rz(theta.clone()), q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::H, q0),
+                single(StandardGate::H, q1),
+            ]
+        }
+
+        // RYY == (Rx(pi/2) x Rx(pi/2)) . RZZ . (Rx(-pi/2) x Rx(-pi/2))
+        StandardGate::RYY(theta) => {
+            let q1 = *qubits.get(1)?;
+            let quarter_turn = P::constant(PI / 2.0);
+            vec![
+                single(StandardGate::Rx(quarter_turn.clone()), q0),
+                single(StandardGate::Rx(quarter_turn.clone()), q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::Rz(theta.clone()), q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::Rx(-quarter_turn.clone()), q0),
+                single(StandardGate::Rx(-quarter_turn), q1),
+            ]
+        }
+
+        StandardGate::ISwap => {
+            let q1 = *qubits.get(1)?;
+            vec![
+                single(StandardGate::S, q0),
+                single(StandardGate::S, q1),
+                single(StandardGate::H, q0),
+                two(StandardGate::CX, q0, q1),
+                two(StandardGate::CX, q1, q0),
+                single(StandardGate::H, q1),
+            ]
+        }
+
+        // ECR == RZX(pi/4) . (X x I) . RZX(-pi/4) up to global phase,
+        // with RZX(t) == (I x H) . CX . (I x Rz(t)) . CX . (I x H).
+        StandardGate::ECR => {
+            let q1 = *qubits.get(1)?;
+            let eighth_turn = P::constant(PI / 4.0);
+            vec![
+                single(StandardGate::H, q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::Rz(eighth_turn.clone()), q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::H, q1),
+                single(StandardGate::X, q0),
+                single(StandardGate::H, q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::Rz(-eighth_turn), q1),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::H, q1),
+            ]
+        }
+
+        // Standard 6-CX Toffoli decomposition.
+        StandardGate::CCX => {
+            let q1 = *qubits.get(1)?;
+            let q2 = *qubits.get(2)?;
+            vec![
+                single(StandardGate::H, q2),
+                two(StandardGate::CX, q1, q2),
+                single(StandardGate::Tdg, q2),
+                two(StandardGate::CX, q0, q2),
+                single(StandardGate::T, q2),
+                two(StandardGate::CX, q1, q2),
+                single(StandardGate::Tdg, q2),
+                two(StandardGate::CX, q0, q2),
+                single(StandardGate::T, q1),
+                single(StandardGate::T, q2),
+                single(StandardGate::H, q2),
+                two(StandardGate::CX, q0, q1),
+                single(StandardGate::T, q0),
+                single(StandardGate::Tdg, q1),
+                two(StandardGate::CX, q0, q1),
+            ]
+        }
+
+        // Fredkin via Toffoli: CSwap(c, a, b) == CX(b,a) . CCX(c,a,b) . CX(b,a)
+        StandardGate::CSwap => {
+            let q1 = *qubits.get(1)?;
+            let q2 = *qubits.get(2)?;
+            let mut steps = vec![two(StandardGate::CX, q2, q1)];
+            steps.extend(decompose_to_simpler(&StandardGate::CCX, qubits)?);
+            steps.push(two(StandardGate::CX, q2, q1));
+            steps
+        }
+
+        // Multi-controlled gates: controls first, target last.
+        StandardGate::MCX(_) => {
+            let (&target, controls) = qubits.split_last()?;
+            decompose_mcx(controls, target)?
+        }
+        StandardGate::MCZ(_) => {
+            let (&target, controls) = qubits.split_last()?;
+            decompose_mcp(&P::constant(PI), controls, target)?
+        }
+        StandardGate::MCP(_, lambda) => {
+            let (&target, controls) = qubits.split_last()?;
+            decompose_mcp(lambda, controls, target)?
+        }
+
+        _ => return None,
+    })
+}
+
+/// Decompose a multi-controlled X into one- and two-qubit gates.
+///
+/// MCX == (I x H) . MCZ . (I x H); up to two controls use `x`, `cx` and the
+/// Toffoli decomposition directly.
+fn decompose_mcx(
+    controls: &[arvak_ir::QubitId],
+    target: arvak_ir::QubitId,
+) -> Option<Vec<Instruction>> {
+    Some(match controls {
+        [] => vec![Instruction::single_qubit_gate(StandardGate::X, target)],
+        [c] => vec![Instruction::two_qubit_gate(StandardGate::CX, *c, target)],
+        [c1, c2] => decompose_to_simpler(&StandardGate::CCX, &[*c1, *c2, target])?,
+        _ => {
+            let mut steps = vec![Instruction::single_qubit_gate(StandardGate::H, target)];
+            steps.extend(decompose_mcp(
+                &ParameterExpression::constant(PI),
+                controls,
+                target,
+            )?);
+            steps.push(Instruction::single_qubit_gate(StandardGate::H, target));
+            steps
+        }
+    })
+}
+
+/// Decompose a multi-controlled phase gate into one- and two-qubit gates.
+///
+/// Ancilla-free recursion on the last control `c` (Barenco et al. 1995,
+/// Lemma 7.5 with V = P(λ/2)):
+///
+/// C^n P(λ) == C^(n-1) P(λ/2) . C^(n-1) X(c) . CP(-λ/2)(c, t) . C^(n-1) X(c) . CP(λ/2)(c, t)
+///
+/// The gate count grows exponentially with the number of controls, which is
+/// acceptable for the small control counts of typical oracles.
+fn decompose_mcp(
+    lambda: &ParameterExpression,
+    controls: &[arvak_ir::QubitId],
+    target: arvak_ir::QubitId,
+) -> Option<Vec<Instruction>> {
+    let Some((&last, rest)) = controls.split_last() else {
+        return Some(vec![Instruction::single_qubit_gate(
+            StandardGate::P(lambda.clone()),
+            target,
+        )]);
+    };
+    if rest.is_empty() {
+        return Some(vec![Instruction::two_qubit_gate(
+            StandardGate::CP(lambda.clone()),
+            last,
+            target,
+        )]);
+    }
+
+    let half = lambda.clone() / ParameterExpression::constant(2.0);
+    let mut steps = vec![Instruction::two_qubit_gate(
+        StandardGate::CP(half.clone()),
+        last,
+        target,
+    )];
+    steps.extend(decompose_mcx(rest, last)?);
+    steps.push(Instruction::two_qubit_gate(
+        StandardGate::CP(-half.clone()),
+        last,
+        target,
+    ));
+    steps.extend(decompose_mcx(rest, last)?);
+    steps.extend(decompose_mcp(&half, rest, target)?);
+    Some(steps)
+}
+
+/// Decompose a custom 2-qubit gate (with a 4×4 unitary matrix) into standard
+/// gates via KAK decomposition, then translate each to the target basis.
+fn decompose_custom_2q(
+    matrix: &[Complex64],
+    qubits: &[arvak_ir::QubitId],
+    basis: &crate::property::BasisGates,
+) -> CompileResult<Vec<Instruction>> {
+    let entangler = Entangler::for_basis(Some(basis));
+    let mut result = Vec::new();
+    for inst in synthesize_2q(matrix, qubits[0], qubits[1], entangler) {
+        if let InstructionKind::Gate(g) = &inst.kind {
+            if is_in_basis(g, basis) {
+                result.push(inst);
+                continue;
+            }
+        }
+        result.extend(translate_gate(&inst, basis)?);
+    }
+    Ok(result)
+}
+
+/// Translate a standard gate to IQM basis (PRX + CZ).
+fn translate_to_iqm(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+) -> CompileResult<Vec<Instruction>> {
+    let q0 = qubits[0];
+
+    Ok(match gate {
+        // Identity - no operation needed
+        StandardGate::I => vec![],
+
+        // X = PRX(π, 0)
+        StandardGate::X => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX(PI.into(), 0.0.into()),
+            q0,
+        )],
+
+        // Y = PRX(π, π/2)
+        StandardGate::Y => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX(PI.into(), (PI / 2.0).into()),
+            q0,
+        )],
+
+        // Z = PRX(π, 0) · PRX(π, π/2) · PRX(π, 0) = phase gate
+        // Simplified: Z can be implemented via virtual Z (absorbed into subsequent PRX)
+        // For now, decompose as: PRX(π, π/2) · PRX(π, 0)
+        StandardGate::Z => vec![
+            Instruction::single_qubit_gate(StandardGate::PRX(PI.into(), (PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::PRX(PI.into(), 0.0.into()), q0),
+        ],
+
+        // H = PRX(π, 0) · PRX(π/2, π/2)  (up to global phase -i)
+        //
+        // Derivation: PRX(θ, φ) = [[cos(θ/2), -i·e^{-iφ}·sin(θ/2)],
+        //                           [-i·e^{iφ}·sin(θ/2), cos(θ/2)]]
+        //
+        // PRX(π/2, π/2) = (1/√2) [[1, -1], [1, 1]]
+        // PRX(π,   0  ) =         [[0, -i], [-i, 0]]
+        //
+        // Product (right-to-left, PRX(π/2,π/2) applied first):
+        //   PRX(π,0) · PRX(π/2,π/2) = (1/√2) [[-i,-i],[-i,i]] = -i·H  ✓
+        //
+        // Global phase -i is unobservable and cancels correctly in multi-qubit
+        // gates (e.g. CX = H·CZ·H acquires phase (-i)² = -1, still unitary).
+        StandardGate::H => vec![
+            Instruction::single_qubit_gate(
+                StandardGate::PRX((PI / 2.0).into(), (PI / 2.0).into()),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::PRX(PI.into(), 0.0.into()), q0),
+        ],
+
+        // Rx(θ) = PRX(θ, 0)
+        StandardGate::Rx(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX(theta.clone(), 0.0.into()),
+            q0,
+        )],
+
+        // Ry(θ) = PRX(θ, π/2)
+        StandardGate::Ry(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX(theta.clone(), (PI / 2.0).into()),
+            q0,
+        )],
+
+        // Rz(θ) = virtual Z (absorbed) or PRX decomposition
+        // Rz(θ) can be commuted through PRX gates, but for correctness:
+        // Rz(θ) = PRX(π, θ/2) · PRX(π, 0)   [matrix notation, right applied first]
+        //
+        // Application order therefore is PRX(π, 0) FIRST, then PRX(π, θ/2).
+        // The previous emission order was reversed and implemented Rz(−θ)
+        // (verified numerically).
+        StandardGate::Rz(theta) => {
+            let half_theta = theta.clone() / ParameterExpression::constant(2.0);
+            vec![
+                Instruction::single_qubit_gate(StandardGate::PRX(PI.into(), 0.0.into()), q0),
+                Instruction::single_qubit_gate(StandardGate::PRX(PI.into(), half_theta), q0),
+            ]
+        }
+
+        // S/Sdg/T/Tdg are Z-rotations up to global phase: reuse the Rz path.
+        StandardGate::S => translate_to_iqm(&StandardGate::Rz((PI / 2.0).into()), qubits)?,
+        StandardGate::Sdg => translate_to_iqm(&StandardGate::Rz((-PI / 2.0).into()), qubits)?,
+        StandardGate::T => translate_to_iqm(&StandardGate::Rz((PI / 4.0).into()), qubits)?,
+        StandardGate::Tdg => translate_to_iqm(&StandardGate::Rz((-PI / 4.0).into()), qubits)?,
+
+        // SX = Rx(π/2) up to global phase = PRX(π/2, 0)
+        StandardGate::SX => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX((PI / 2.0).into(), 0.0.into()),
+            q0,
+        )],
+        // SXdg = Rx(-π/2) up to global phase = PRX(-π/2, 0)
+        StandardGate::SXdg => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX((-PI / 2.0).into(), 0.0.into()),
+            q0,
+        )],
+
+        // CX = H · CZ · H (on target)
+        StandardGate::CX => {
+            let q1 = qubits[1];
+            // H on target (decompose once)
+            let h_gates = translate_to_iqm(&StandardGate::H, &[q1])?;
+            let mut result = Vec::with_capacity(h_gates.len() * 2 + 1);
+            result.extend_from_slice(&h_gates);
+            // CZ
+            result.push(Instruction::two_qubit_gate(StandardGate::CZ, q0, q1));
+            // H on target (reuse decomposition)
+            result.extend_from_slice(&h_gates);
+            result
+        }
+
+        // CZ is native
+        StandardGate::CZ => {
+            let q1 = qubits[1];
+            vec![Instruction::two_qubit_gate(StandardGate::CZ, q0, q1)]
+        }
+
+        // PRX is native
+        StandardGate::PRX(theta, phi) => vec![Instruction::single_qubit_gate(
+            StandardGate::PRX(theta.clone(), phi.clone()),
+            q0,
+        )],
+
+        // SWAP = CX(a,b) · CX(b,a) · CX(a,b), each CX = H(target) · CZ · H(target)
+        StandardGate::Swap => {
+            let q1 = qubits[1];
+            let h0 = translate_to_iqm(&StandardGate::H, &[q0])?;
+            let h1 = translate_to_iqm(&StandardGate::H, &[q1])?;
+            let cz = Instruction::two_qubit_gate(StandardGate::CZ, q0, q1);
+
+            // CX(q0,q1) = H(q1) · CZ · H(q1)
+            // CX(q1,q0) = H(q0) · CZ · H(q0)
+            // CX(q0,q1) = H(q1) · CZ · H(q1)
+            let mut result = Vec::with_capacity(h0.len() * 2 + h1.len() * 4 + 3);
+            result.extend_from_slice(&h1);
+            result.push(cz.clone());
+            result.extend_from_slice(&h1);
+            result.extend_from_slice(&h0);
+            result.push(cz.clone());
+            result.extend_from_slice(&h0);
+            result.extend_from_slice(&h1);
+            result.push(cz);
+            result.extend_from_slice(&h1);
+            result
+        }
+
+        // Other gates - return error for now
+        other => {
+            return Err(CompileError::GateNotInBasis(format!("{other:?}")));
+        }
+    })
+}
+
+/// Translate a standard gate to IBM basis (RZ + SX + X + CX).
+fn translate_to_ibm(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+) -> CompileResult<Vec<Instruction>> {
+    let q0 = qubits[0];
+
+    Ok(match gate {
+        // Identity
+        StandardGate::I => vec![],
+
+        // X is native
+        StandardGate::X => vec![Instruction::single_qubit_gate(StandardGate::X, q0)],
+
+        // Y = Rz(π) · X
+        StandardGate::Y => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q0),
+            Instruction::single_qubit_gate(StandardGate::X, q0),
+        ],
+
+        // Z = Rz(π)
+        StandardGate::Z => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(PI.into()),
+            q0,
+        )],
+
+        // H = Rz(π/2) · SX · Rz(π/2)
+        StandardGate::H => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+        ],
+
+        // SX is native
+        StandardGate::SX => vec![Instruction::single_qubit_gate(StandardGate::SX, q0)],
+
+        // Rx(θ) = RZ(π/2) · SX · RZ(θ+π) · SX · RZ(π/2) up to global phase
+        // (U3 form; verified numerically — the previous middle angle θ with
+        // outer −π/2 rotations equals X at θ=0, not identity).
+        StandardGate::Rx(theta) => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(
+                StandardGate::Rz(theta.clone() + ParameterExpression::constant(PI)),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+        ],
+
+        // Ry(θ) = SXdg · Rz(θ) · SX with SXdg expanded as X · SX
+        // (exact: SX^3 = X·SX), since sxdg is not in the IBM basis.
+        StandardGate::Ry(theta) => vec![
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz(theta.clone()), q0),
+            Instruction::single_qubit_gate(StandardGate::X, q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+        ],
+
+        // Rz is native
+        StandardGate::Rz(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(theta.clone()),
+            q0,
+        )],
+
+        // CX is native
+        StandardGate::CX => {
+            let q1 = qubits[1];
+            vec![Instruction::two_qubit_gate(StandardGate::CX, q0, q1)]
+        }
+
+        // CZ = H · CX · H (on target)
+        StandardGate::CZ => {
+            let q1 = qubits[1];
+            let h_gates = translate_to_ibm(&StandardGate::H, &[q1])?;
+            let mut result = h_gates.clone();
+            result.push(Instruction::two_qubit_gate(StandardGate::CX, q0, q1));
+            result.extend(h_gates);
+            result
+        }
+
+        // SWAP = CX(a,b) · CX(b,a) · CX(a,b)
+        StandardGate::Swap => {
+            let q1 = qubits[1];
+            vec![
+                Instruction::two_qubit_gate(StandardGate::CX, q0, q1),
+                Instruction::two_qubit_gate(StandardGate::CX, q1, q0),
+                Instruction::two_qubit_gate(StandardGate::CX, q0, q1),
+            ]
+        }
+
+        // S = Rz(π/2)
+        StandardGate::S => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 2.0).into()),
+            q0,
+        )],
+
+        // Sdg = Rz(-π/2)
+        StandardGate::Sdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 2.0).into()),
+            q0,
+        )],
+
+        // T = Rz(π/4)
+        StandardGate::T => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 4.0).into()),
+            q0,
+        )],
+
+        // Tdg = Rz(-π/4)
+        StandardGate::Tdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 4.0).into()),
+            q0,
+        )],
+
+        // Other gates
+        other => {
+            return Err(CompileError::GateNotInBasis(format!("{other:?}")));
+        }
+    })
+}
+
+/// Translate a standard gate to IBM Eagle basis (RZ + SX + X + ECR).
+///
+/// Eagle is the 127-qubit processor family (ibm_brussels, ibm_strasbourg, etc.).
+/// Single-qubit decompositions are identical to the IBM/Heron basis.
+/// Two-qubit: ECR is native; CX decomposes as:
+///   `CX(q0,q1) = X(q0) · SX(q1) · ECR(q0,q1) · RZ(π/2,q0) · RZ(π/2,q1)`
+/// CZ is decomposed via CZ = H(q1) · CX · H(q1).
+fn translate_to_eagle(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+) -> CompileResult<Vec<Instruction>> {
+    let q0 = qubits[0];
+
+    Ok(match gate {
+        // Single-qubit gates — same as IBM/Heron basis
+        StandardGate::I => vec![],
+        StandardGate::X => vec![Instruction::single_qubit_gate(StandardGate::X, q0)],
+        StandardGate::Y => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q0),
+            Instruction::single_qubit_gate(StandardGate::X, q0),
+        ],
+        StandardGate::Z => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(PI.into()),
+            q0,
+        )],
+        StandardGate::H => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+        ],
+        StandardGate::SX => vec![Instruction::single_qubit_gate(StandardGate::SX, q0)],
+        // Rx(θ) = RZ(π/2) · SX · RZ(θ+π) · SX · RZ(π/2) up to global phase
+        // (U3 form; verified numerically — the previous middle angle θ with
+        // outer −π/2 rotations equals X at θ=0, not identity).
+        StandardGate::Rx(theta) => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(
+                StandardGate::Rz(theta.clone() + ParameterExpression::constant(PI)),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+        ],
+        // Ry(θ) = RZ(π) · SX · RZ(θ+π) · SX up to global phase
+        // (verified numerically — the previous form SX·RZ(θ)·SX·RZ(−π) is
+        // not RY(θ) for generic θ).
+        StandardGate::Ry(theta) => vec![
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(
+                StandardGate::Rz(theta.clone() + ParameterExpression::constant(PI)),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q0),
+        ],
+        StandardGate::Rz(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(theta.clone()),
+            q0,
+        )],
+
+        // ECR is native on Eagle
+        StandardGate::ECR => {
+            let q1 = qubits[1];
+            vec![Instruction::two_qubit_gate(StandardGate::ECR, q0, q1)]
+        }
+
+        // CX(q0→q1) = (X·S on q0) ⊗ (SX† on q1) · ECR(q0,q1)
+        //
+        // In the |q0,q1⟩ (q0 = high bit) convention used by gate.rs and the
+        // verification simulator, the ECR matrix equals Qiskit's ECR after the
+        // endianness conversion, and CX requires only POST-rotations:
+        //   q0: S = RZ(π/2), then X
+        //   q1: SX† = RZ(π)·SX·RZ(π) (up to global phase; SX† is not native)
+        // Verified numerically. The previous sequence (RZ(π/2) pre-rotations,
+        // X/SX post) did not equal CX in any convention.
+        StandardGate::CX => {
+            let q1 = qubits[1];
+            vec![
+                Instruction::two_qubit_gate(StandardGate::ECR, q0, q1),
+                Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+                Instruction::single_qubit_gate(StandardGate::X, q0),
+                Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q1),
+                Instruction::single_qubit_gate(StandardGate::SX, q1),
+                Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q1),
+            ]
+        }
+
+        // CZ = H(q1) · CX(q0,q1) · H(q1), H from Eagle basis (Rz·SX·Rz)
+        StandardGate::CZ => {
+            let q1 = qubits[1];
+            let h_gates = translate_to_eagle(&StandardGate::H, &[q1])?;
+            let cx_gates = translate_to_eagle(&StandardGate::CX, qubits)?;
+            let mut result = h_gates.clone();
+            result.extend(cx_gates);
+            result.extend(h_gates);
+            result
+        }
+
+        // SWAP = CX(a,b) · CX(b,a) · CX(a,b), each CX decomposed to ECR basis
+        StandardGate::Swap => {
+            let q1 = qubits[1];
+            let mut result = translate_to_eagle(&StandardGate::CX, &[q0, q1])?;
+            result.extend(translate_to_eagle(&StandardGate::CX, &[q1, q0])?);
+            result.extend(translate_to_eagle(&StandardGate::CX, &[q0, q1])?);
+            result
+        }
+
+        // S = Rz(π/2)
+        StandardGate::S => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 2.0).into()),
+            q0,
+        )],
+
+        // Sdg = Rz(-π/2)
+        StandardGate::Sdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 2.0).into()),
+            q0,
+        )],
+
+        // T = Rz(π/4)
+        StandardGate::T => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 4.0).into()),
+            q0,
+        )],
+
+        // Tdg = Rz(-π/4)
+        StandardGate::Tdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 4.0).into()),
+            q0,
+        )],
+
+        other => {
+            return Err(CompileError::GateNotInBasis(format!("{other:?}")));
+        }
+    })
+}
+
+/// Translate a standard gate to IBM Heron basis (RZ + SX + X + CZ).
+///
+/// Single-qubit decompositions are identical to the IBM basis.
+/// Two-qubit: CX is decomposed as H · CZ · H on the target qubit.
+fn translate_to_heron(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+) -> CompileResult<Vec<Instruction>> {
+    let q0 = qubits[0];
+
+    Ok(match gate {
+        // Single-qubit gates — same as IBM basis
+        StandardGate::I => vec![],
+        StandardGate::X => vec![Instruction::single_qubit_gate(StandardGate::X, q0)],
+        StandardGate::Y => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q0),
+            Instruction::single_qubit_gate(StandardGate::X, q0),
+        ],
+        StandardGate::Z => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(PI.into()),
+            q0,
+        )],
+        StandardGate::H => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+        ],
+        StandardGate::SX => vec![Instruction::single_qubit_gate(StandardGate::SX, q0)],
+        // Rx(θ) = RZ(π/2) · SX · RZ(θ+π) · SX · RZ(π/2) up to global phase
+        // (U3 form; verified numerically — the previous middle angle θ with
+        // outer −π/2 rotations equals X at θ=0, not identity).
+        StandardGate::Rx(theta) => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(
+                StandardGate::Rz(theta.clone() + ParameterExpression::constant(PI)),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz((PI / 2.0).into()), q0),
+        ],
+        // Ry(θ) = RZ(π) · SX · RZ(θ+π) · SX up to global phase.
+        // Uses only native gates (no SXdg which IBM rejects).
+        // (Verified numerically — the previous form SX·RZ(θ)·SX·RZ(−π) is
+        // not RY(θ) for generic θ.)
+        StandardGate::Ry(theta) => vec![
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(
+                StandardGate::Rz(theta.clone() + ParameterExpression::constant(PI)),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::SX, q0),
+            Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q0),
+        ],
+        StandardGate::Rz(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(theta.clone()),
+            q0,
+        )],
+
+        // S/Sdg/T/Tdg are Z-rotations up to global phase (same as IBM basis).
+        StandardGate::S => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 2.0).into()),
+            q0,
+        )],
+        StandardGate::Sdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 2.0).into()),
+            q0,
+        )],
+        StandardGate::T => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 4.0).into()),
+            q0,
+        )],
+        StandardGate::Tdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 4.0).into()),
+            q0,
+        )],
+
+        // CZ is native on Heron
+        StandardGate::CZ => {
+            let q1 = qubits[1];
+            vec![Instruction::two_qubit_gate(StandardGate::CZ, q0, q1)]
+        }
+
+        // CX = H(target) · CZ · H(target)
+        StandardGate::CX => {
+            let q1 = qubits[1];
+            let h_gates = translate_to_heron(&StandardGate::H, &[q1])?;
+            let mut result = Vec::with_capacity(h_gates.len() * 2 + 1);
+            result.extend_from_slice(&h_gates);
+            result.push(Instruction::two_qubit_gate(StandardGate::CZ, q0, q1));
+            result.extend_from_slice(&h_gates);
+            result
+        }
+
+        // RZZ(θ) = CX(a,b) · Rz(θ,b) · CX(a,b)
+        // CX on Heron = H(b) · CZ · H(b), H = Rz(π/2) · SX · Rz(π/2)
+        StandardGate::RZZ(theta) => {
+            let q1 = qubits[1];
+            let h1 = translate_to_heron(&StandardGate::H, &[q1])?;
+            let cz = Instruction::two_qubit_gate(StandardGate::CZ, q0, q1);
+            // CX(a,b) = H(b) · CZ(a,b) · H(b)
+            // RZZ(θ,a,b) = CX · Rz(θ,b) · CX
+            //            = H(b)·CZ·H(b) · Rz(θ,b) · H(b)·CZ·H(b)
+            let mut result = Vec::with_capacity(h1.len() * 4 + 3);
+            result.extend_from_slice(&h1); // H(b) before first CZ
+            result.push(cz.clone()); // CZ
+            result.extend_from_slice(&h1); // H(b) after first CZ → Rz(θ) before next H
+            result.push(Instruction::single_qubit_gate(
+                StandardGate::Rz(theta.clone()),
+                q1,
+            ));
+            result.extend_from_slice(&h1); // H(b) before second CZ
+            result.push(cz); // CZ
+            result.extend_from_slice(&h1); // H(b) after second CZ
+            result
+        }
+
+        // SWAP = CX(a,b) · CX(b,a) · CX(a,b), each CX decomposed to H·CZ·H
+        StandardGate::Swap => {
+            let q1 = qubits[1];
+            let h0 = translate_to_heron(&StandardGate::H, &[q0])?;
+            let h1 = translate_to_heron(&StandardGate::H, &[q1])?;
+            let cz = Instruction::two_qubit_gate(StandardGate::CZ, q0, q1);
+
+            // CX(q0,q1) = H(q1) · CZ · H(q1)
+            // CX(q1,q0) = H(q0) · CZ · H(q0)
+            // CX(q0,q1) = H(q1) · CZ · H(q1)
+            let mut result = Vec::with_capacity(h0.len() * 2 + h1.len() * 4 + 3);
+            result.extend_from_slice(&h1);
+            result.push(cz.clone());
+            result.extend_from_slice(&h1);
+            result.extend_from_slice(&h0);
+            result.push(cz.clone());
+            result.extend_from_slice(&h0);
+            result.extend_from_slice(&h1);
+            result.push(cz);
+            result.extend_from_slice(&h1);
+            result
+        }
+
+        other => {
+            return Err(CompileError::GateNotInBasis(format!("{other:?}")));
+        }
+    })
+}
+
+/// Translate a standard gate to neutral-atom basis (RZ + RX + RY + CZ).
+///
+/// Used for planqc (Strontium-88 Rydberg, global rotations) and PASQAL digital mode.
+/// Native gates: `rz`, `rx`, `ry` (single-qubit), `cz` (Rydberg two-qubit).
+///
+/// # Decompositions
+///
+/// - `H = Ry(π/2) · Rz(π)` (applied right-to-left: Rz first, then Ry) → −i·H (global phase)
+/// - `CX = H(target) · CZ · H(target)` (same as IQM/Heron)
+/// - `PRX(θ, φ) = Rz(φ) · Rx(θ) · Rz(−φ)` (axis rotation in XY plane)
+///
+/// # Caution
+///
+/// Decompositions are derived from standard quantum gate algebra.
+/// They must be verified against planqc documentation once the QDMI driver
+/// and calibration data are available. Do NOT derive from trial-and-error
+/// against calibration data or hardware probing.
+fn translate_to_neutral_atom(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+) -> CompileResult<Vec<Instruction>> {
+    let q0 = qubits[0];
+
+    Ok(match gate {
+        // Identity — no operation
+        StandardGate::I => vec![],
+
+        // X = Rx(π)
+        StandardGate::X => vec![Instruction::single_qubit_gate(
+            StandardGate::Rx(PI.into()),
+            q0,
+        )],
+
+        // Y = Ry(π)
+        StandardGate::Y => vec![Instruction::single_qubit_gate(
+            StandardGate::Ry(PI.into()),
+            q0,
+        )],
+
+        // Z = Rz(π)
+        StandardGate::Z => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(PI.into()),
+            q0,
+        )],
+
+        // H = Ry(π/2) · Rz(π)  (Rz applied first, then Ry)
+        //
+        // Derivation:
+        //   Rz(π)   = diag(e^{-iπ/2}, e^{iπ/2}) = -i·diag(1,-1) (up to phase)
+        //   Ry(π/2) = [[1/√2, -1/√2], [1/√2, 1/√2]]
+        //
+        //   Ry(π/2) · Rz(π) = (1/√2)[[-i,-i],[-i,i]] = -i · H  ✓
+        //
+        // Global phase -i is unobservable. Consistent with IQM H decomposition.
+        StandardGate::H => vec![
+            Instruction::single_qubit_gate(StandardGate::Rz(PI.into()), q0),
+            Instruction::single_qubit_gate(StandardGate::Ry((PI / 2.0).into()), q0),
+        ],
+
+        // S = Rz(π/2)
+        StandardGate::S => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 2.0).into()),
+            q0,
+        )],
+
+        // Sdg = Rz(-π/2)
+        StandardGate::Sdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 2.0).into()),
+            q0,
+        )],
+
+        // T = Rz(π/4)
+        StandardGate::T => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((PI / 4.0).into()),
+            q0,
+        )],
+
+        // Tdg = Rz(-π/4)
+        StandardGate::Tdg => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz((-PI / 4.0).into()),
+            q0,
+        )],
+
+        // Rx is native
+        StandardGate::Rx(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::Rx(theta.clone()),
+            q0,
+        )],
+
+        // Ry is native
+        StandardGate::Ry(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::Ry(theta.clone()),
+            q0,
+        )],
+
+        // Rz is native
+        StandardGate::Rz(theta) => vec![Instruction::single_qubit_gate(
+            StandardGate::Rz(theta.clone()),
+            q0,
+        )],
+
+        // CZ is native (Rydberg interaction)
+        StandardGate::CZ => {
+            let q1 = qubits[1];
+            vec![Instruction::two_qubit_gate(StandardGate::CZ, q0, q1)]
+        }
+
+        // CX = H(target) · CZ · H(target), where H = Rz(π) then Ry(π/2)
+        StandardGate::CX => {
+            let q1 = qubits[1];
+            let h_gates = translate_to_neutral_atom(&StandardGate::H, &[q1])?;
+            let mut result = Vec::with_capacity(h_gates.len() * 2 + 1);
+            result.extend_from_slice(&h_gates);
+            result.push(Instruction::two_qubit_gate(StandardGate::CZ, q0, q1));
+            result.extend_from_slice(&h_gates);
+            result
+        }
+
+        // SWAP = CX(a,b) · CX(b,a) · CX(a,b), each CX = H(target)·CZ·H(target)
+        StandardGate::Swap => {
+            let q1 = qubits[1];
+            let h0 = translate_to_neutral_atom(&StandardGate::H, &[q0])?;
+            let h1 = translate_to_neutral_atom(&StandardGate::H, &[q1])?;
+            let cz = Instruction::two_qubit_gate(StandardGate::CZ, q0, q1);
+
+            let mut result = Vec::with_capacity(h0.len() * 2 + h1.len() * 4 + 3);
+            result.extend_from_slice(&h1);
+            result.push(cz.clone());
+            result.extend_from_slice(&h1);
+            result.extend_from_slice(&h0);
+            result.push(cz.clone());
+            result.extend_from_slice(&h0);
+            result.extend_from_slice(&h1);
+            result.push(cz);
+            result.extend_from_slice(&h1);
+            result
+        }
+
+        // PRX(θ, φ) = Rz(φ) · Rx(θ) · Rz(−φ)  [matrix notation, applied right-to-left]
+        //
+        // Circuit order (applied left-to-right): [Rz(−φ), Rx(θ), Rz(φ)]
+        // Check: PRX(θ, 0) = Rz(0)·Rx(θ)·Rz(0) = Rx(θ) ✓
+        //        PRX(θ, π/2) = Rz(π/2)·Rx(θ)·Rz(−π/2) = Ry(θ) ✓ (standard identity)
+        StandardGate::PRX(theta, phi) => vec![
+            Instruction::single_qubit_gate(
+                StandardGate::Rz(ParameterExpression::constant(-1.0) * phi.clone()),
+                q0,
+            ),
+            Instruction::single_qubit_gate(StandardGate::Rx(theta.clone()), q0),
+            Instruction::single_qubit_gate(StandardGate::Rz(phi.clone()), q0),
+        ],
+
+        other => {
+            return Err(CompileError::GateNotInBasis(format!("{other:?}")));
+        }
+    })
+}
+
+/// Translate a standard gate to a Clifford+T basis (H + S + T + CX).
+///
+/// Only rotations by multiples of π/4 have an exact Clifford+T circuit;
+/// other angles are rejected with a pointer to
+/// [`CliffordTSynthesis`](super::CliffordTSynthesis), which approximates
+/// them.
+fn translate_to_clifford_t(
+    gate: &StandardGate,
+    qubits: &[arvak_ir::QubitId],
+    basis: &crate::property::BasisGates,
+) -> CompileResult<Vec<Instruction>> {
+    let q0 = qubits[0];
+    let single = Instruction::single_qubit_gate;
+    let h = |q| single(StandardGate::H, q);
+    // The power of T implementing a rotation by `angle`, if it has one.
+    let rotation = |angle: &ParameterExpression| {
+        angle.as_f64().and_then(t_power_of).ok_or_else(|| {
+            CompileError::GateNotInBasis(format!(
+                "{} (angle is not a multiple of π/4; run CliffordTSynthesis)",
+                gate.name()
+            ))
+        })
+    };
+    let x = |q| {
+        if basis.contains("x") {
+            vec![single(StandardGate::X, q)]
+        } else {
+            let mut out = vec![h(q)];
+            out.extend(t_power(4, q, basis));
+            out.push(h(q));
+            out
+        }
+    };
+
+    Ok(match gate {
+        StandardGate::I => vec![],
+        StandardGate::X => x(q0),
+        // Y = i·X·Z
+        StandardGate::Y if basis.contains("y") => vec![single(StandardGate::Y, q0)],
+        StandardGate::Y => {
+            let mut out = t_power(4, q0, basis);
+            out.extend(x(q0));
+            out
+        }
+        StandardGate::Z => t_power(4, q0, basis),
+        StandardGate::H => vec![h(q0)],
+        StandardGate::S => t_power(2, q0, basis),
+        StandardGate::Sdg => t_power(6, q0, basis),
+        StandardGate::T => t_power(1, q0, basis),
+        StandardGate::Tdg => t_power(7, q0, basis),
+        // SX = H·S·H and SXdg = H·Sdg·H, up to global phase.
+        StandardGate::SX | StandardGate::SXdg => {
+            let power = if matches!(gate, StandardGate::SX) {
+                2
+            } else {
+                6
+            };
+            let mut out = vec![h(q0)];
+            out.extend(t_power(power, q0, basis));
+            out.push(h(q0));
+            out
+        }
+        // Rz(nπ/4) = T^n and P(nπ/4) = T^n, up to global phase.
+        StandardGate::Rz(theta) | StandardGate::P(theta) => t_power(rotation(theta)?, q0, basis),
+        // Rx(θ) = H·Rz(θ)·H
+        StandardGate::Rx(theta) => {
+            let mut out = vec![h(q0)];
+            out.extend(t_power(rotation(theta)?, q0, basis));
+            out.push(h(q0));
+            out
+        }
+        // Ry(θ) = S·Rx(θ)·Sdg, so Sdg is applied first.
+        StandardGate::Ry(theta) => {
+            let n = rotation(theta)?;
+            let mut out = t_power(6, q0, basis);
+            out.push(h(q0));
+            out.extend(t_power(n, q0, basis));
+            out.push(h(q0));
+            out.extend(t_power(2, q0, basis));
+            out
+        }
+        StandardGate::CX => vec![Instruction::two_qubit_gate(StandardGate::CX, q0, qubits[1])],
+        // CZ = (I ⊗ H)·CX·(I ⊗ H)
+        StandardGate::CZ => vec![
+            h(qubits[1]),
+            Instruction::two_qubit_gate(StandardGate::CX, q0, qubits[1]),
+            h(qubits[1]),
+        ],
+        StandardGate::Swap => vec![
+            Instruction::two_qubit_gate(StandardGate::CX, q0, qubits[1]),
+            Instruction::two_qubit_gate(StandardGate::CX, qubits[1], q0),
+            Instruction::two_qubit_gate(StandardGate::CX, q0, qubits[1]),
+        ],
+        other => {
+            return Err(CompileError::GateNotInBasis(format!("{other:?}")));
+        }
+    })
+}
+
+/// The phase gate `T^n = diag(1, e^{inπ/4})` from the gates of `basis`,
+/// falling back to repeated `T` when `s`, `z`, `sdg` or `tdg` is missing.
+fn t_power(
+    n: i64,
+    qubit: arvak_ir::QubitId,
+    basis: &crate::property::BasisGates,
+) -> Vec<Instruction> {
+    let gates = phase_gates(n);
+    let gates = if gates.iter().all(|g| basis.contains(g.name())) {
+        gates
+    } else {
+        vec![StandardGate::T; usize::try_from(n.rem_euclid(8)).expect("n mod 8 < 8")]
+    };
+    gates
+        .into_iter()
+        .map(|g| Instruction::single_qubit_gate(g, qubit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{BasisGates, CouplingMap};
+    use crate::unitary::Unitary2x2;
+    use arvak_ir::{Circuit, ClbitId, QubitId};
+
+    // DEBT-03 resolved: the ECR matrix documented in gate.rs (and implemented in
+    // the VerifyCompilation simulator) is Qiskit's ECR correctly converted to
+    // Arvak's big-endian |q0,q1⟩ (q0 = MSB) convention — there is no convention
+    // clash. The CX→ECR decomposition is unitary-verified end-to-end in
+    // tests/unitary_equivalence.rs via the VerifyCompilation pass.
+
+    /// Verify that the IQM H decomposition is unitarily correct.
+    ///
+    /// Previously this was PRX(π/2, -π/2) · PRX(π, π/4) which produced
+    /// (e^{iπ/4}/√2) [[1,-1],[-i,-i]] — not H, not even up to global phase —
+    /// causing all IQM circuits with H gates to compute wrong states.
+    ///
+    /// The correct decomposition is PRX(π/2, π/2) · PRX(π, 0) which gives -i·H.
+    /// Global phase -i is unobservable in measurement outcomes.
+    #[test]
+    fn test_iqm_h_unitary_correct() {
+        // PRX(θ, φ) = RZ(φ) · RX(θ) · RZ(-φ)
+        let prx = |theta: f64, phi: f64| -> Unitary2x2 {
+            Unitary2x2::rz(phi) * Unitary2x2::rx(theta) * Unitary2x2::rz(-phi)
+        };
+
+        // Arvak decomposition: PRX(π/2, π/2) applied first, then PRX(π, 0)
+        let u = prx(PI, 0.0) * prx(PI / 2.0, PI / 2.0);
+        let h = Unitary2x2::h();
+
+        // Must be equal up to global phase: u† · H must be a scalar multiple of I
+        let product = u.dagger() * h;
+        let [a, b, c, d] = product.data;
+        let eps = 1e-10;
+        // If u = phase * H, then u† · H = conj(phase) * I, so b and c are zero
+        assert!(
+            b.norm() < eps && c.norm() < eps,
+            "IQM H decomposition PRX(π,0)·PRX(π/2,π/2) is not H up to global phase: \
+             off-diagonal elements [{b}, {c}] should be zero"
+        );
+        // Diagonal elements must be equal (both equal conj(phase))
+        assert!((a - d).norm() < eps, "Diagonal elements differ: [{a}, {d}]");
+        // Must be unitary: diagonal element has |phase| == 1
+        assert!(
+            (a.norm() - 1.0).abs() < eps,
+            "Phase magnitude is not 1: |{a}| = {}",
+            a.norm()
+        );
+    }
+
+    #[test]
+    fn test_translation_keeps_condition() {
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .x(QubitId(1))
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::star(5), BasisGates::iqm());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        let gates: Vec<&Gate> = dag
+            .topological_ops()
+            .filter_map(|(_, inst)| inst.as_gate())
+            .collect();
+        assert!(!gates.is_empty());
+        for gate in gates {
+            assert_eq!(gate.name(), "prx");
+            let condition = gate.condition.as_ref().unwrap();
+            assert_eq!(condition.clbits, [ClbitId(0)]);
+            assert_eq!(condition.value, 1);
+        }
+    }
+
+    #[test]
+    fn test_eagle_translation_h() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.h(QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(127), BasisGates::eagle());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+        // H = Rz · SX · Rz = 3 gates
+        assert_eq!(dag.num_ops(), 3);
+    }
+
+    #[test]
+    fn test_clifford_t_translation() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.rz(PI / 2.0, QubitId(0)).unwrap();
+        circuit.sx(QubitId(1)).unwrap();
+        circuit.cz(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(2), BasisGates::clifford_t());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+        // S, then H·S·H, then H·CX·H.
+        assert_eq!(dag.num_ops(), 7);
+
+        // Other angles need approximation.
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.rz(0.3, QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+        assert!(matches!(
+            BasisTranslation.run(&mut dag, &mut props),
+            Err(CompileError::GateNotInBasis(_))
+        ));
+    }
+
+    #[test]
+    fn test_eagle_translation_cx() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(127), BasisGates::eagle());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+        // CX = ECR + [RZ, X](q0) + [RZ, SX, RZ](q1) = 6 gates
+        assert_eq!(dag.num_ops(), 6);
+    }
+
+    #[test]
+    fn test_iqm_translation_h() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.h(QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::star(5), BasisGates::iqm());
+
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // H decomposes to 2 PRX gates
+        assert_eq!(dag.num_ops(), 2);
+    }
+
+    #[test]
+    fn test_iqm_translation_cx() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::star(5), BasisGates::iqm());
+
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // CX = H · CZ · H, where H = 2 PRX gates
+        // So CX = 2 PRX + CZ + 2 PRX = 5 gates
+        assert_eq!(dag.num_ops(), 5);
+    }
+
+    #[test]
+    fn test_ibm_translation_h() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.h(QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(5), BasisGates::ibm());
+
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // H = Rz · SX · Rz = 3 gates
+        assert_eq!(dag.num_ops(), 3);
+    }
+
+    #[test]
+    fn test_heron_translation_h() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.h(QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(133), BasisGates::heron());
+
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // H = Rz · SX · Rz = 3 gates (same as IBM single-qubit)
+        assert_eq!(dag.num_ops(), 3);
+    }
+
+    #[test]
+    fn test_heron_translation_cx() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(133), BasisGates::heron());
+
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // CX = H(t) · CZ · H(t), where H = 3 gates
+        // So CX = 3 + 1 + 3 = 7 gates
+        assert_eq!(dag.num_ops(), 7);
+    }
+
+    /// Verify that BasisTranslation preserves gate ordering in multi-gate circuits.
+    ///
+    /// This is a regression test for a bug where `substitute_node` appended
+    /// replacement gates at wire ends instead of at the original position,
+    /// placing the H decomposition AFTER the CX in a Bell state circuit.
+    #[test]
+    fn test_bell_state_ordering() {
+        // Build: H(q0) -> CX(q0,q1) -> Measure(q0) -> Measure(q1)
+        let mut circuit = Circuit::with_size("test", 2, 2);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.measure(QubitId(0), arvak_ir::ClbitId(0)).unwrap();
+        circuit.measure(QubitId(1), arvak_ir::ClbitId(1)).unwrap();
+
+        let mut dag = circuit.into_dag();
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::linear(133), BasisGates::heron());
+
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // Collect gates in topological order and verify:
+        // 1. All single-qubit gates on q[0] from H decomposition come BEFORE CZ
+        // 2. Measurements come LAST
+        let ops: Vec<_> = dag
+            .topological_ops()
+            .map(|(_, inst)| {
+                let name = match &inst.kind {
+                    InstructionKind::Gate(g) => g.name().to_string(),
+                    InstructionKind::Measure => "measure".to_string(),
+                    _ => "other".to_string(),
+                };
+                (name, inst.qubits.clone())
+            })
+            .collect();
+
+        // Find position of CZ gate
+        let cz_pos = ops.iter().position(|(name, _)| name == "cz").unwrap();
+        // Find position of first measurement
+        let meas_pos = ops.iter().position(|(name, _)| name == "measure").unwrap();
+
+        // H decomposition on q[0] (rz, sx, rz) must come before CZ
+        // CZ must come before measurements
+        assert!(
+            cz_pos > 0,
+            "CZ should not be the first gate (H decomposition must precede it)"
+        );
+        assert!(
+            cz_pos < meas_pos,
+            "CZ at position {cz_pos} must come before measurement at position {meas_pos}"
+        );
+
+        // Verify the first gates on q[0] are from H decomposition (rz/sx)
+        let q0_gates_before_cz: Vec<_> = ops[..cz_pos]
+            .iter()
+            .filter(|(_, qubits)| qubits.contains(&QubitId(0)))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert!(
+            !q0_gates_before_cz.is_empty(),
+            "H decomposition gates on q[0] must precede CZ"
+        );
+        assert!(
+            q0_gates_before_cz.iter().all(|n| *n == "rz" || *n == "sx"),
+            "Gates before CZ on q[0] should be rz/sx (H decomposition), got: {q0_gates_before_cz:?}"
+        );
+    }
+
+    /// Verify that the neutral-atom H decomposition is unitarily correct.
+    ///
+    /// H = Ry(π/2) · Rz(π) must equal H up to global phase.
+    #[test]
+    fn test_neutral_atom_h_unitary_correct() {
+        // H = Ry(π/2) · Rz(π)  [Rz applied first, then Ry]
+        let u = Unitary2x2::ry(PI / 2.0) * Unitary2x2::rz(PI);
+        let h = Unitary2x2::h();
+
+        // Must be equal up to global phase: u† · H must be a scalar multiple of I
+        let product = u.dagger() * h;
+        let [a, b, c, d] = product.data;
+        let eps = 1e-10;
+        assert!(
+            b.norm() < eps && c.norm() < eps,
+            "Neutral-atom H decomposition Ry(π/2)·Rz(π) is not H up to global phase: \
+             off-diagonal [{b}, {c}] should be zero"
+        );
+        assert!((a - d).norm() < eps, "Diagonal elements differ: [{a}, {d}]");
+        assert!(
+            (a.norm() - 1.0).abs() < eps,
+            "Phase magnitude is not 1: |{a}| = {}",
+            a.norm()
+        );
+    }
+
+    /// Verify that the neutral-atom PRX decomposition is unitarily correct.
+    ///
+    /// PRX(θ, φ) = Rz(φ) · Rx(θ) · Rz(−φ) [matrix form; circuit vec = Rz(−φ), Rx(θ), Rz(φ)].
+    /// Spot-checks: PRX(θ, 0) = Rx(θ), PRX(θ, π/2) = Ry(θ).
+    #[test]
+    fn test_neutral_atom_prx_decomposition_correct() {
+        // Matrix multiplication order (right = applied first):
+        // Rz(phi) * Rx(theta) * Rz(-phi) = Rz(phi)·Rx(theta)·Rz(-phi)
+        let prx_decomp = |theta: f64, phi: f64| -> Unitary2x2 {
+            Unitary2x2::rz(phi) * Unitary2x2::rx(theta) * Unitary2x2::rz(-phi)
+        };
+        let eps = 1e-10;
+
+        // PRX(π/3, 0) = Rx(π/3)
+        let u = prx_decomp(PI / 3.0, 0.0);
+        let rx = Unitary2x2::rx(PI / 3.0);
+        let diff = u.dagger() * rx;
+        let [a, b, c, _d] = diff.data;
+        assert!(
+            b.norm() < eps && c.norm() < eps,
+            "PRX(π/3, 0) should equal Rx(π/3): off-diagonal [{b}, {c}]"
+        );
+        assert!(
+            (a.norm() - 1.0).abs() < eps,
+            "Phase not unitary: {}",
+            a.norm()
+        );
+
+        // PRX(π/3, π/2) = Ry(π/3)
+        let u2 = prx_decomp(PI / 3.0, PI / 2.0);
+        let ry = Unitary2x2::ry(PI / 3.0);
+        let diff2 = u2.dagger() * ry;
+        let [a2, b2, c2, _d2] = diff2.data;
+        assert!(
+            b2.norm() < eps && c2.norm() < eps,
+            "PRX(π/3, π/2) should equal Ry(π/3): off-diagonal [{b2}, {c2}]"
+        );
+        assert!(
+            (a2.norm() - 1.0).abs() < eps,
+            "Phase not unitary: {}",
+            a2.norm()
+        );
+    }
+
+    #[test]
+    fn test_neutral_atom_translation_h() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.h(QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::full(5), BasisGates::neutral_atom());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // H = Rz(π) · Ry(π/2) = 2 gates
+        assert_eq!(dag.num_ops(), 2);
+    }
+
+    #[test]
+    fn test_neutral_atom_translation_cx() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::full(5), BasisGates::neutral_atom());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // CX = H(target) · CZ · H(target), where H = 2 gates
+        // So CX = 2 + 1 + 2 = 5 gates
+        assert_eq!(dag.num_ops(), 5);
+    }
+
+    #[test]
+    fn test_neutral_atom_translation_x() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit.x(QubitId(0)).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props =
+            PropertySet::new().with_target(CouplingMap::full(1), BasisGates::neutral_atom());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        // X = Rx(π) = 1 gate
+        assert_eq!(dag.num_ops(), 1);
+        let (_, inst) = dag.topological_ops().next().unwrap();
+        let InstructionKind::Gate(g) = &inst.kind else {
+            panic!("expected gate")
+        };
+        assert_eq!(g.name(), "rx");
+    }
+
+    #[test]
+    fn test_custom_gate_definition_expanded() {
+        use arvak_ir::{CustomGate, GateDefinition};
+
+        // zz(t) = CX · Rz(t) · CX, wrapped in a second custom gate.
+        let mut zz_body = Circuit::with_size("zz", 2, 0);
+        zz_body
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .rz(ParameterExpression::symbol("t"), QubitId(1))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap();
+        let zz = CustomGate::new("zz", 2)
+            .with_params(vec![ParameterExpression::symbol("a")])
+            .with_definition(GateDefinition::from_circuit(vec!["t".into()], &zz_body).unwrap())
+            .unwrap();
+
+        let mut layer_body = Circuit::with_size("layer", 2, 0);
+        layer_body.h(QubitId(0)).unwrap();
+        layer_body.gate(zz, [QubitId(0), QubitId(1)]).unwrap();
+        let layer = CustomGate::new("layer", 2)
+            .with_params(vec![ParameterExpression::constant(0.3)])
+            .with_definition(GateDefinition::from_circuit(vec!["a".into()], &layer_body).unwrap())
+            .unwrap();
+
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit.gate(layer, [QubitId(1), QubitId(0)]).unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(2), BasisGates::iqm());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        let basis = BasisGates::iqm();
+        assert!(dag.num_ops() > 3);
+        for (_, inst) in dag.topological_ops() {
+            let gate = inst.as_gate().unwrap();
+            assert!(basis.contains(gate.name()), "{} not in basis", gate.name());
+            for param in gate.parameters() {
+                assert!(!param.is_symbolic());
+            }
+        }
+    }
+
+    #[test]
+    fn test_modified_gate_expanded() {
+        use arvak_ir::Gate;
+
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit
+            .h(QubitId(0))
+            .unwrap()
+            .gate(
+                Gate::standard(StandardGate::H).power(-1.0).controlled(2),
+                [QubitId(0), QubitId(1), QubitId(2)],
+            )
+            .unwrap()
+            .gate(
+                Gate::standard(StandardGate::Ry(0.4.into())).controlled(1),
+                [QubitId(2), QubitId(1)],
+            )
+            .unwrap();
+        let expected = circuit.to_matrix().unwrap();
+        let mut dag = circuit.into_dag();
+
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(3), BasisGates::ibm());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        let basis = BasisGates::ibm();
+        for (_, inst) in dag.topological_ops() {
+            let name = inst.as_gate().unwrap().name();
+            assert!(basis.contains(name), "{name} not in basis");
+        }
+
+        // Equal up to global phase.
+        let actual = dag.to_matrix().unwrap();
+        let (i, _) = expected
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.norm().total_cmp(&b.1.norm()))
+            .unwrap();
+        let phase = actual[i] / expected[i];
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e * phase).norm() < 1e-9);
+        }
+    }
+}
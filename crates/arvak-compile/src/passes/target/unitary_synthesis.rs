@@ -0,0 +1,302 @@
+//! Two-qubit unitary synthesis via the KAK decomposition.
+//!
+//! Replaces custom two-qubit gates that carry a 4×4 matrix (user-defined
+//! unitaries, or blocks merged by `ConsolidateBlocks`) with at most three
+//! entangling gates of the target basis and single-qubit rotations.
+//! `BasisTranslation` then lowers the rotations to the native gate set.
+
+use num_complex::Complex64;
+
+use arvak_ir::{CircuitDag, GateKind, Instruction, InstructionKind, QubitId, StandardGate};
+
+use crate::error::CompileResult;
+use crate::pass::{Pass, PassKind};
+use crate::property::{BasisGates, PropertySet};
+use crate::unitary::{TwoQubitGateOp, Unitary4x4};
+
+/// Resynthesize two-qubit unitary gates with the minimum number of CX or CZ
+/// gates.
+///
+/// The entangling gate is CX when the target basis contains `cx` (or no
+/// basis is set), and CZ when it contains `cz` but not `cx`. Each unitary is
+/// decomposed with [`Unitary4x4::kak_decompose`], which uses 0–3 entangling
+/// gates depending on its Weyl chamber coordinates. The result equals the
+/// original gate up to global phase; a classical condition on the gate is
+/// carried over to every gate it is replaced with.
+pub struct UnitarySynthesis;
+
+impl Pass for UnitarySynthesis {
+    fn name(&self) -> &'static str {
+        "UnitarySynthesis"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Transformation
+    }
+
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let entangler = Entangler::for_basis(properties.basis_gates.as_ref());
+
+        let mut new_dag = CircuitDag::new();
+        for qubit in dag.qubits().collect::<Vec<_>>() {
+            new_dag.add_qubit(qubit);
+        }
+        for clbit in dag.clbits().collect::<Vec<_>>() {
+            new_dag.add_clbit(clbit);
+        }
+
+        for (_, inst) in dag.topological_ops() {
+            let Some(matrix) = unitary_2q(inst) else {
+                new_dag.apply(inst.clone())?;
+                continue;
+            };
+            let condition = inst.as_gate().and_then(|g| g.condition.clone());
+            for mut step in synthesize_2q(matrix, inst.qubits[0], inst.qubits[1], entangler) {
+                if let Some(g) = step.gate_mut() {
+                    g.condition.clone_from(&condition);
+                }
+                new_dag.apply(step.with_metadata_from(inst))?;
+            }
+        }
+
+        new_dag.set_global_phase(dag.global_phase());
+        new_dag.set_level(dag.level());
+        *dag = new_dag;
+        Ok(())
+    }
+
+    fn should_run(&self, dag: &CircuitDag, _properties: &PropertySet) -> bool {
+        dag.topological_ops()
+            .any(|(_, inst)| unitary_2q(inst).is_some())
+    }
+}
+
+/// The entangling gate synthesized circuits are built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Entangler {
+    Cx,
+    Cz,
+}
+
+impl Entangler {
+    pub(crate) fn for_basis(basis: Option<&BasisGates>) -> Self {
+        match basis {
+            Some(b) if !b.contains("cx") && b.contains("cz") => Self::Cz,
+            _ => Self::Cx,
+        }
+    }
+}
+
+/// The 4×4 matrix of a custom two-qubit gate, if it has one.
+fn unitary_2q(inst: &Instruction) -> Option<&[Complex64]> {
+    let InstructionKind::Gate(gate) = &inst.kind else {
+        return None;
+    };
+    let GateKind::Custom(custom) = &gate.kind else {
+        return None;
+    };
+    custom
+        .matrix
+        .as_deref()
+        .filter(|m| m.len() == 16 && inst.qubits.len() == 2)
+}
+
+/// Synthesize a 4×4 unitary (row-major, `q0` as the most significant qubit)
+/// into RZ/RY rotations and at most three `entangler` gates on `q0`, `q1`.
+pub(crate) fn synthesize_2q(
+    matrix: &[Complex64],
+    q0: QubitId,
+    q1: QubitId,
+    entangler: Entangler,
+) -> Vec<Instruction> {
+    let mut data = [Complex64::new(0.0, 0.0); 16];
+    data.copy_from_slice(matrix);
+    let kak = Unitary4x4 { data }.kak_decompose();
+
+    let qubit = |index: u8| if index == 0 { q0 } else { q1 };
+    let mut out = Vec::new();
+    for op in kak.to_circuit() {
+        match op {
+            TwoQubitGateOp::Rz(q, angle) => out.push(Instruction::single_qubit_gate(
+                StandardGate::Rz(angle.into()),
+                qubit(q),
+            )),
+            TwoQubitGateOp::Ry(q, angle) => out.push(Instruction::single_qubit_gate(
+                StandardGate::Ry(angle.into()),
+                qubit(q),
+            )),
+            TwoQubitGateOp::Cx => push_cx(&mut out, entangler, q0, q1),
+            TwoQubitGateOp::CxReverse => push_cx(&mut out, entangler, q1, q0),
+        }
+    }
+    out
+}
+
+/// Append a CX, built from `entangler`.
+fn push_cx(out: &mut Vec<Instruction>, entangler: Entangler, control: QubitId, target: QubitId) {
+    match entangler {
+        Entangler::Cx => out.push(Instruction::two_qubit_gate(
+            StandardGate::CX,
+            control,
+            target,
+        )),
+        // CX = (I ⊗ H) · CZ · (I ⊗ H)
+        Entangler::Cz => {
+            out.push(Instruction::single_qubit_gate(StandardGate::H, target));
+            out.push(Instruction::two_qubit_gate(
+                StandardGate::CZ,
+                control,
+                target,
+            ));
+            out.push(Instruction::single_qubit_gate(StandardGate::H, target));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::BasisTranslation;
+    use crate::property::CouplingMap;
+    use arvak_ir::{Circuit, ClbitId, CustomGate, Gate};
+
+    fn custom_2q(name: &str, matrix: Vec<Complex64>) -> Gate {
+        Gate::custom(CustomGate::new(name, 2).with_matrix(matrix))
+    }
+
+    fn iswap() -> Vec<Complex64> {
+        let (z, o, i) = (
+            Complex64::new(0.0, 0.0),
+            Complex64::new(1.0, 0.0),
+            Complex64::new(0.0, 1.0),
+        );
+        vec![o, z, z, z, z, z, i, z, z, i, z, z, z, z, z, o]
+    }
+
+    /// A two-qubit unitary needing three CNOTs.
+    fn generic() -> Vec<Complex64> {
+        let mut circuit = Circuit::with_size("u", 2, 0);
+        circuit
+            .rx(0.3, QubitId(0))
+            .unwrap()
+            .ry(1.1, QubitId(1))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .rz(0.7, QubitId(0))
+            .unwrap()
+            .ry(-0.4, QubitId(1))
+            .unwrap()
+            .cx(QubitId(1), QubitId(0))
+            .unwrap()
+            .rx(0.9, QubitId(1))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .ry(0.2, QubitId(0))
+            .unwrap();
+        // `to_matrix` maps qubit i to bit i; a gate's matrix lists its first
+        // qubit as the most significant bit.
+        let little = circuit.to_matrix().unwrap();
+        let swap_bits = |k: usize| ((k & 1) << 1) | (k >> 1);
+        (0..16)
+            .map(|k| little[swap_bits(k / 4) * 4 + swap_bits(k % 4)])
+            .collect()
+    }
+
+    fn count(dag: &CircuitDag, name: &str) -> usize {
+        dag.topological_ops()
+            .filter(|(_, inst)| inst.as_gate().is_some_and(|g| g.name() == name))
+            .count()
+    }
+
+    fn assert_equiv(expected: &[Complex64], actual: &[Complex64]) {
+        let (i, _) = expected
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.norm().total_cmp(&b.1.norm()))
+            .unwrap();
+        let phase = actual[i] / expected[i];
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e * phase).norm() < 1e-9);
+        }
+    }
+
+    /// Synthesize `gate` on qubits (2, 0) of a 3-qubit circuit with `basis`
+    /// and check the result against the original.
+    fn synthesize(gate: Gate, basis: Option<BasisGates>) -> CircuitDag {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(1)).unwrap();
+        circuit.gate(gate, [QubitId(2), QubitId(0)]).unwrap();
+        let expected = circuit.to_matrix().unwrap();
+
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new();
+        if let Some(basis) = basis {
+            props = props.with_target(CouplingMap::full(3), basis);
+        }
+        UnitarySynthesis.run(&mut dag, &mut props).unwrap();
+        assert!(!UnitarySynthesis.should_run(&dag, &props));
+        assert_equiv(&expected, &dag.to_matrix().unwrap());
+        dag
+    }
+
+    #[test]
+    fn test_minimum_cx_count() {
+        let dag = synthesize(custom_2q("iswap", iswap()), None);
+        assert_eq!(count(&dag, "cx"), 2);
+
+        let dag = synthesize(custom_2q("u", generic()), None);
+        assert_eq!(count(&dag, "cx"), 3);
+    }
+
+    #[test]
+    fn test_cz_target() {
+        let dag = synthesize(custom_2q("u", generic()), Some(BasisGates::iqm()));
+        assert_eq!(count(&dag, "cz"), 3);
+        assert_eq!(count(&dag, "cx"), 0);
+    }
+
+    #[test]
+    fn test_basis_translation_synthesizes_custom_2q() {
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit
+            .gate(custom_2q("u", generic()), [QubitId(0), QubitId(1)])
+            .unwrap();
+        let expected = circuit.to_matrix().unwrap();
+
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new().with_target(CouplingMap::full(2), BasisGates::iqm());
+        BasisTranslation.run(&mut dag, &mut props).unwrap();
+
+        let basis = BasisGates::iqm();
+        for (_, inst) in dag.topological_ops() {
+            let name = inst.as_gate().unwrap().name();
+            assert!(basis.contains(name), "{name} not in basis");
+        }
+        assert_eq!(count(&dag, "cz"), 3);
+        assert_equiv(&expected, &dag.to_matrix().unwrap());
+    }
+
+    #[test]
+    fn test_condition_carried_over() {
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .gate(custom_2q("iswap", iswap()), [QubitId(0), QubitId(1)])
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap();
+
+        let mut dag = circuit.into_dag();
+        UnitarySynthesis
+            .run(&mut dag, &mut PropertySet::new())
+            .unwrap();
+        let gates: Vec<_> = dag
+            .topological_ops()
+            .filter_map(|(_, inst)| inst.as_gate())
+            .collect();
+        assert!(gates.len() > 2);
+        assert!(gates.iter().all(|g| g.condition.is_some()));
+    }
+}
@@ -0,0 +1,425 @@
+//! VF2 layout pass for SWAP-free qubit placement.
+//!
+//! Searches for a *perfect* layout: an embedding of the circuit's
+//! interaction graph (logical qubits, joined when a two-qubit gate acts on
+//! them) into the coupling map, so every two-qubit gate already acts on
+//! coupled physical qubits and routing inserts no SWAPs. This is subgraph
+//! monomorphism, solved by VF2-style backtracking (Cordella et al., 2004):
+//!
+//! 1. Logical qubits are matched in a connected order — most interactions
+//!    first, then always the qubit with the most already-matched partners —
+//!    so each step only tries the free neighbors of a matched partner.
+//! 2. A candidate must have at least as many couplings as the logical qubit
+//!    has partners, and be coupled to every matched partner.
+//!
+//! When no embedding exists, or the search exceeds its call limit, the pass
+//! leaves the layout unset for the next layout pass to fill in. If a
+//! [`TargetPatch`] is set, only patch qubits are placement candidates.
+
+use rustc_hash::FxHashSet;
+
+use arvak_ir::{CircuitDag, CircuitLevel, QubitId};
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::{Pass, PassKind};
+use crate::passes::target::layout::flattened_ops;
+use crate::property::{CouplingMap, Layout, PropertySet, TargetPatch};
+
+/// VF2 perfect layout pass.
+///
+/// Sets a layout under which no SWAPs are needed, if one exists. The
+/// trivial layout (or the patch in order) is kept when it is already
+/// perfect, so circuits that fit the device as written are not relabeled.
+/// Run it before [`DenseLayout`] or [`TrivialLayout`], which only run when
+/// no layout was found.
+///
+/// [`DenseLayout`]: super::DenseLayout
+/// [`TrivialLayout`]: super::TrivialLayout
+pub struct VF2Layout {
+    /// Maximum number of search states before giving up.
+    call_limit: usize,
+}
+
+impl VF2Layout {
+    /// Create a VF2 layout pass with the default call limit.
+    pub fn new() -> Self {
+        Self {
+            call_limit: 100_000,
+        }
+    }
+
+    /// Set the maximum number of search states before giving up.
+    ///
+    /// Subgraph matching is exponential in the worst case; the limit bounds
+    /// the time spent on circuits that have no perfect layout.
+    #[must_use]
+    pub fn with_call_limit(mut self, call_limit: usize) -> Self {
+        self.call_limit = call_limit;
+        self
+    }
+}
+
+impl Default for VF2Layout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Undirected interaction graph over logical qubit indices.
+struct InteractionGraph {
+    /// Distinct partners of each logical qubit, ascending.
+    partners: Vec<Vec<usize>>,
+}
+
+impl InteractionGraph {
+    /// Build the graph, or `None` if a gate acts on more than two qubits.
+    fn from_dag(dag: &CircuitDag, logical: &[QubitId]) -> Option<Self> {
+        let index = |q: QubitId| logical.iter().position(|&l| l == q);
+        let mut edges: FxHashSet<(usize, usize)> = FxHashSet::default();
+        for inst in flattened_ops(dag) {
+            match inst.qubits.len() {
+                0 | 1 => {}
+                2 => {
+                    let a = index(inst.qubits[0])?;
+                    let b = index(inst.qubits[1])?;
+                    edges.insert((a.min(b), a.max(b)));
+                }
+                _ if inst.is_gate() => return None,
+                _ => {}
+            }
+        }
+
+        let mut partners = vec![Vec::new(); logical.len()];
+        for &(a, b) in &edges {
+            partners[a].push(b);
+            partners[b].push(a);
+        }
+        for list in &mut partners {
+            list.sort_unstable();
+        }
+        Some(Self { partners })
+    }
+
+    /// Interacting qubits in matching order: each next qubit has the most
+    /// already-ordered partners, ties broken by more partners, then index.
+    fn matching_order(&self) -> Vec<usize> {
+        let n = self.partners.len();
+        let mut ordered = vec![false; n];
+        let mut matched_partners = vec![0usize; n];
+        let mut order = Vec::new();
+        loop {
+            let next = (0..n)
+                .filter(|&q| !ordered[q] && !self.partners[q].is_empty())
+                .max_by_key(|&q| (matched_partners[q], self.partners[q].len(), n - q));
+            let Some(q) = next else { break };
+            ordered[q] = true;
+            order.push(q);
+            for &partner in &self.partners[q] {
+                matched_partners[partner] += 1;
+            }
+        }
+        order
+    }
+}
+
+/// Backtracking search state.
+struct Search<'a> {
+    graph: &'a InteractionGraph,
+    coupling_map: &'a CouplingMap,
+    order: Vec<usize>,
+    /// Physical qubits that may be used.
+    allowed: Vec<bool>,
+    /// Physical qubit of each logical qubit, once matched.
+    mapping: Vec<Option<u32>>,
+    used: Vec<bool>,
+    calls: usize,
+    call_limit: usize,
+}
+
+impl Search<'_> {
+    /// Match `order[depth..]`; `None` when the call limit is exceeded.
+    fn extend(&mut self, depth: usize) -> Option<bool> {
+        if depth == self.order.len() {
+            return Some(true);
+        }
+        self.calls += 1;
+        if self.calls > self.call_limit {
+            return None;
+        }
+
+        let logical = self.order[depth];
+        let partners = &self.graph.partners[logical];
+        let matched: Vec<u32> = partners.iter().filter_map(|&p| self.mapping[p]).collect();
+        let candidates: Vec<u32> = match matched.first() {
+            Some(&anchor) => self.coupling_map.neighbors(anchor).collect(),
+            None => (0..self.coupling_map.num_qubits()).collect(),
+        };
+
+        for physical in candidates {
+            let feasible = self.allowed[physical as usize]
+                && !self.used[physical as usize]
+                && self.coupling_map.neighbors(physical).count() >= partners.len()
+                && matched
+                    .iter()
+                    .all(|&p| self.coupling_map.is_connected(physical, p));
+            if !feasible {
+                continue;
+            }
+            self.mapping[logical] = Some(physical);
+            self.used[physical as usize] = true;
+            match self.extend(depth + 1) {
+                Some(false) => {}
+                found => return found,
+            }
+            self.mapping[logical] = None;
+            self.used[physical as usize] = false;
+        }
+        Some(false)
+    }
+}
+
+/// Whether `layout` puts every interacting pair on coupled qubits.
+fn is_perfect(graph: &InteractionGraph, layout: &[u32], coupling_map: &CouplingMap) -> bool {
+    graph.partners.iter().enumerate().all(|(a, partners)| {
+        partners
+            .iter()
+            .all(|&b| coupling_map.is_connected(layout[a], layout[b]))
+    })
+}
+
+impl Pass for VF2Layout {
+    fn name(&self) -> &'static str {
+        "VF2Layout"
+    }
+
+    fn kind(&self) -> PassKind {
+        PassKind::Analysis
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn run(&self, dag: &mut CircuitDag, properties: &mut PropertySet) -> CompileResult<()> {
+        let coupling_map = properties
+            .coupling_map
+            .as_ref()
+            .ok_or(CompileError::MissingCouplingMap)?;
+
+        // Candidate physical qubits: the target patch if set, else the device.
+        let candidates: Vec<u32> = match properties.get::<TargetPatch>() {
+            Some(patch) => patch.qubits().to_vec(),
+            None => (0..coupling_map.num_qubits()).collect(),
+        };
+
+        let mut logical: Vec<QubitId> = dag.qubits().collect();
+        logical.sort_unstable_by_key(|q| q.0);
+        if logical.len() > candidates.len() {
+            return Err(CompileError::CircuitTooLarge {
+                required: logical.len(),
+                available: candidates.len() as u32,
+            });
+        }
+        let Some(graph) = InteractionGraph::from_dag(dag, &logical) else {
+            return Ok(());
+        };
+
+        let trivial = &candidates[..logical.len()];
+        let physical: Vec<u32> = if is_perfect(&graph, trivial, coupling_map) {
+            trivial.to_vec()
+        } else {
+            let mut allowed = vec![false; coupling_map.num_qubits() as usize];
+            for &p in &candidates {
+                allowed[p as usize] = true;
+            }
+            let mut search = Search {
+                graph: &graph,
+                coupling_map,
+                order: graph.matching_order(),
+                allowed,
+                mapping: vec![None; logical.len()],
+                used: vec![false; coupling_map.num_qubits() as usize],
+                calls: 0,
+                call_limit: self.call_limit,
+            };
+            if search.extend(0) != Some(true) {
+                return Ok(());
+            }
+
+            // Idle qubits take the remaining candidates in order.
+            let mut free = candidates.iter().filter(|&&p| !search.used[p as usize]);
+            search
+                .mapping
+                .iter()
+                .map(|m| m.or_else(|| free.next().copied()))
+                .collect::<Option<_>>()
+                .ok_or_else(|| CompileError::PassFailed {
+                    name: "VF2Layout".into(),
+                    reason: "no available physical qubit for placement".into(),
+                })?
+        };
+
+        let mut layout = Layout::new();
+        for (&q, &p) in logical.iter().zip(&physical) {
+            layout.add(q, p);
+        }
+        properties.initial_layout = Some(layout.clone());
+        properties.layout = Some(layout);
+        dag.set_level(CircuitLevel::Physical);
+
+        Ok(())
+    }
+
+    fn should_run(&self, _dag: &CircuitDag, properties: &PropertySet) -> bool {
+        properties.layout.is_none() && properties.coupling_map.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::{SabreRouting, TrivialLayout};
+    use crate::property::BasisGates;
+    use arvak_ir::{Circuit, InstructionKind};
+
+    fn count_swaps(dag: &CircuitDag) -> usize {
+        dag.topological_ops()
+            .filter(
+                |(_, inst)| matches!(&inst.kind, InstructionKind::Gate(g) if g.name() == "swap"),
+            )
+            .count()
+    }
+
+    /// Run VF2 then SABRE; return the SWAP count and the layout VF2 found.
+    fn layout_and_route(circuit: Circuit, coupling_map: CouplingMap) -> (usize, Option<Layout>) {
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new().with_target(coupling_map, BasisGates::ibm());
+        VF2Layout::new().run(&mut dag, &mut props).unwrap();
+        let found = props.layout.clone();
+        if props.layout.is_none() {
+            TrivialLayout.run(&mut dag, &mut props).unwrap();
+        }
+        SabreRouting::new().run(&mut dag, &mut props).unwrap();
+        (count_swaps(&dag), found)
+    }
+
+    #[test]
+    fn test_vf2_ghz_chain_on_star() {
+        // A CX chain 0-1-2 embeds in a star only with qubit 1 at the hub.
+        let mut circuit = Circuit::with_size("chain", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+
+        let (swaps, layout) = layout_and_route(circuit, CouplingMap::star(5));
+        assert_eq!(swaps, 0);
+        assert_eq!(layout.unwrap().get_physical(QubitId(1)), Some(0));
+    }
+
+    #[test]
+    fn test_vf2_linear_ansatz_on_grid() {
+        // A 6-qubit linear entangler snakes through a 2x3 grid:
+        // 0 1 2
+        // 3 4 5
+        let grid = CouplingMap::from_edge_list(
+            6,
+            &[(0, 1), (1, 2), (3, 4), (4, 5), (0, 3), (1, 4), (2, 5)],
+        );
+        let mut circuit = Circuit::with_size("ansatz", 6, 0);
+        for layer in 0..2 {
+            for q in 0..6 {
+                circuit
+                    .ry(0.1 * f64::from(layer * 6 + q), QubitId(q))
+                    .unwrap();
+            }
+            for q in 0..5 {
+                circuit.cx(QubitId(q), QubitId(q + 1)).unwrap();
+            }
+        }
+        // The trivial layout puts 2 and 3 at distance 3.
+        let (swaps, layout) = layout_and_route(circuit, grid);
+        assert_eq!(swaps, 0);
+        assert!(layout.is_some());
+    }
+
+    #[test]
+    fn test_vf2_keeps_trivial_layout() {
+        let mut circuit = Circuit::with_size("chain", 4, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(2), QubitId(3)).unwrap();
+
+        let (_, layout) = layout_and_route(circuit, CouplingMap::linear(6));
+        let layout = layout.unwrap();
+        for q in 0..4 {
+            assert_eq!(layout.get_physical(QubitId(q)), Some(q));
+        }
+    }
+
+    #[test]
+    fn test_vf2_no_embedding_leaves_layout_unset() {
+        // A triangle does not embed in a line.
+        let mut circuit = Circuit::with_size("triangle", 3, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(2), QubitId(0)).unwrap();
+
+        let (swaps, layout) = layout_and_route(circuit, CouplingMap::linear(5));
+        assert!(layout.is_none());
+        assert!(swaps > 0);
+    }
+
+    #[test]
+    fn test_vf2_call_limit() {
+        // K4 in a 3x3 grid fails only after exploring the search tree.
+        let grid = CouplingMap::from_edge_list(
+            9,
+            &[
+                (0, 1),
+                (1, 2),
+                (3, 4),
+                (4, 5),
+                (6, 7),
+                (7, 8),
+                (0, 3),
+                (3, 6),
+                (1, 4),
+                (4, 7),
+                (2, 5),
+                (5, 8),
+            ],
+        );
+        let mut circuit = Circuit::with_size("k4", 4, 0);
+        for a in 0..4 {
+            for b in a + 1..4 {
+                circuit.cz(QubitId(a), QubitId(b)).unwrap();
+            }
+        }
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new().with_target(grid, BasisGates::ibm());
+        VF2Layout::new()
+            .with_call_limit(1)
+            .run(&mut dag, &mut props)
+            .unwrap();
+        assert!(props.layout.is_none());
+    }
+
+    #[test]
+    fn test_vf2_target_patch() {
+        // The chain must stay inside the patch 2-3-4 of linear(6).
+        let mut circuit = Circuit::with_size("chain", 3, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+
+        let mut dag = circuit.into_dag();
+        let mut props = PropertySet::new().with_target(CouplingMap::linear(6), BasisGates::ibm());
+        props.insert(TargetPatch::new(&[4, 2, 3]));
+        VF2Layout::new().run(&mut dag, &mut props).unwrap();
+
+        let layout = props.layout.unwrap();
+        let mut used: Vec<u32> = (0..3)
+            .map(|q| layout.get_physical(QubitId(q)).unwrap())
+            .collect();
+        assert!(CouplingMap::linear(6).is_connected(used[0], used[1]));
+        assert!(CouplingMap::linear(6).is_connected(used[1], used[2]));
+        used.sort_unstable();
+        assert_eq!(used, vec![2, 3, 4]);
+    }
+}
@@ -70,11 +70,19 @@
 //! assert_eq!(stats.gates_removed, 15);
 //! ```
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use std::any::{Any, TypeId};
+use std::path::Path;
+use std::sync::OnceLock;
 
 use arvak_ir::QubitId;
+use arvak_ir::noise::NoiseProfile;
+
+use crate::ancilla::AncillaPool;
+use crate::budget::CompileDeadline;
+use crate::cancel::CancellationToken;
+use crate::error::{CompileError, CompileResult};
 
 /// A mapping from logical qubits to physical qubits.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -169,6 +177,18 @@ impl Layout {
     }
 }
 
+impl FromIterator<(QubitId, u32)> for Layout {
+    /// Build a layout from (logical, physical) pairs, such as those read back
+    /// from a compiled QASM file's layout pragmas.
+    fn from_iter<I: IntoIterator<Item = (QubitId, u32)>>(iter: I) -> Self {
+        let mut layout = Self::new();
+        for (logical, physical) in iter {
+            layout.add(logical, physical);
+        }
+        layout
+    }
+}
+
 /// Target device coupling map.
 ///
 /// The coupling map defines which pairs of physical qubits can
@@ -176,34 +196,84 @@ impl Layout {
 ///
 /// ## Performance
 ///
-/// On construction, a distance matrix is precomputed using BFS from each
-/// node. This enables O(1) `distance()` lookups and O(distance) path
-/// reconstruction during routing, eliminating per-gate BFS.
+/// The first `distance()` or `shortest_path()` query computes all-pairs
+/// distance and predecessor matrices using BFS from each node; they are
+/// cached until an edge is added. Later queries are O(1) lookups and
+/// O(distance) path reconstruction, so routing never runs per-gate BFS.
 ///
 /// ## Deserialization
 ///
 /// After deserialization, call [`rebuild_caches()`](Self::rebuild_caches) to
-/// recompute the adjacency list and distance/predecessor matrices (which are
-/// skipped during serialization). Without this call, `distance()` will fall
-/// back to per-query BFS, and `shortest_path()` will return `None`.
+/// recompute the adjacency list (which is skipped during serialization).
+/// Without this call, `is_connected()` and `neighbors()` see no edges.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CouplingMap {
     /// List of connected qubit pairs (bidirectional).
     edges: Vec<(u32, u32)>,
     /// Number of physical qubits.
     num_qubits: u32,
+    /// Native directions of two-qubit gates as `(control, target)` pairs.
+    /// Edges with neither direction recorded are bidirectional.
+    #[serde(default, skip_serializing_if = "FxHashSet::is_empty")]
+    directions: FxHashSet<(u32, u32)>,
     /// Adjacency list for fast lookup.
     #[serde(skip)]
     adjacency: FxHashMap<u32, Vec<u32>>,
-    /// Precomputed all-pairs distance matrix. `dist_matrix[from][to]` is the
-    /// shortest-path distance, or `u32::MAX` if unreachable.
-    /// Computed lazily on first access or eagerly via `precompute_distances()`.
-    #[serde(skip)]
-    dist_matrix: Vec<Vec<u32>>,
-    /// Precomputed predecessor matrix for shortest-path reconstruction.
-    /// `pred_matrix[from][to]` is the next hop on the shortest path from→to.
+    /// All-pairs shortest paths, computed on first query and reset when
+    /// an edge is added.
     #[serde(skip)]
-    pred_matrix: Vec<Vec<u32>>,
+    paths: OnceLock<ShortestPaths>,
+}
+
+/// All-pairs shortest-path matrices of a [`CouplingMap`].
+#[derive(Debug, Clone)]
+struct ShortestPaths {
+    /// `dist[from][to]` is the shortest-path distance, or `u32::MAX` if
+    /// unreachable.
+    dist: Vec<Vec<u32>>,
+    /// `pred[from][to]` is the node before `to` on the shortest path
+    /// from→to, or `u32::MAX` if unreachable.
+    pred: Vec<Vec<u32>>,
+}
+
+impl ShortestPaths {
+    /// Run BFS from every node. The matrices cover at least `num_qubits`
+    /// nodes, and every edge endpoint.
+    fn compute(num_qubits: u32, edges: &[(u32, u32)]) -> Self {
+        let n = edges
+            .iter()
+            .map(|&(a, b)| a.max(b) as usize + 1)
+            .max()
+            .unwrap_or(0)
+            .max(num_qubits as usize);
+        let mut adjacency = vec![Vec::new(); n];
+        for &(a, b) in edges {
+            adjacency[a as usize].push(b);
+            adjacency[b as usize].push(a);
+        }
+
+        let mut dist = vec![vec![u32::MAX; n]; n];
+        let mut pred = vec![vec![u32::MAX; n]; n];
+        for src in 0..n {
+            dist[src][src] = 0;
+            let mut queue = std::collections::VecDeque::new();
+            queue.push_back(src as u32);
+
+            while let Some(current) = queue.pop_front() {
+                let cur = current as usize;
+                for &neighbor in &adjacency[cur] {
+                    let nb = neighbor as usize;
+                    if dist[src][nb] == u32::MAX {
+                        dist[src][nb] = dist[src][cur] + 1;
+                        // Predecessor on path from src to nb is current
+                        pred[src][nb] = current;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+        Self { dist, pred }
+    }
 }
 
 impl CouplingMap {
@@ -212,9 +282,9 @@ impl CouplingMap {
         Self {
             edges: vec![],
             num_qubits,
+            directions: FxHashSet::default(),
             adjacency: FxHashMap::default(),
-            dist_matrix: vec![],
-            pred_matrix: vec![],
+            paths: OnceLock::new(),
         }
     }
 
@@ -233,45 +303,36 @@ impl CouplingMap {
         self.edges.push((q1, q2));
         self.adjacency.entry(q1).or_default().push(q2);
         self.adjacency.entry(q2).or_default().push(q1);
+        self.paths.take();
     }
 
-    /// Precompute all-pairs shortest paths using BFS from each node.
-    /// Called automatically by factory methods (linear, star, full, zoned).
-    fn precompute_distances(&mut self) {
-        let n = self.num_qubits as usize;
-        self.dist_matrix = vec![vec![u32::MAX; n]; n];
-        self.pred_matrix = vec![vec![u32::MAX; n]; n];
-
-        for src in 0..n {
-            self.dist_matrix[src][src] = 0;
-            let mut queue = std::collections::VecDeque::new();
-            queue.push_back(src as u32);
-
-            while let Some(current) = queue.pop_front() {
-                let cur = current as usize;
-                for &neighbor in self.adjacency.get(&current).into_iter().flatten() {
-                    let nb = neighbor as usize;
-                    if self.dist_matrix[src][nb] == u32::MAX {
-                        self.dist_matrix[src][nb] = self.dist_matrix[src][cur] + 1;
-                        // Predecessor on path from src to nb is current
-                        self.pred_matrix[src][nb] = current;
-                        queue.push_back(neighbor);
-                    }
-                }
-            }
-        }
+    /// Add an edge on which two-qubit gates are native only from `from` to
+    /// `to` (e.g. the cross-resonance direction on IBM Eagle devices).
+    ///
+    /// Routing still treats the edge as connected both ways; the
+    /// [`GateDirection`](crate::passes::GateDirection) pass flips gates that
+    /// end up reversed. Adding both directions makes the edge bidirectional.
+    pub fn add_directed_edge(&mut self, from: u32, to: u32) {
+        self.add_edge(from, to);
+        self.directions.insert((from, to));
     }
 
     /// Rebuild the adjacency list and distance/predecessor matrices from the
-    /// edge list. Must be called after deserialization to restore O(1) distance
-    /// lookups and shortest-path reconstruction.
+    /// edge list. Must be called after deserialization to restore the
+    /// adjacency list.
     pub fn rebuild_caches(&mut self) {
         self.adjacency.clear();
         for &(q1, q2) in &self.edges {
             self.adjacency.entry(q1).or_default().push(q2);
             self.adjacency.entry(q2).or_default().push(q1);
         }
-        self.precompute_distances();
+        self.paths = OnceLock::from(ShortestPaths::compute(self.num_qubits, &self.edges));
+    }
+
+    /// The shortest-path matrices, computed on first use.
+    fn paths(&self) -> &ShortestPaths {
+        self.paths
+            .get_or_init(|| ShortestPaths::compute(self.num_qubits, &self.edges))
     }
 
     /// Check if two qubits are directly connected.
@@ -282,6 +343,20 @@ impl CouplingMap {
             .is_some_and(|neighbors| neighbors.contains(&q2))
     }
 
+    /// Check if a two-qubit gate from `from` to `to` is native, i.e. the
+    /// qubits are connected and the reverse direction is not the only one.
+    pub fn supports_direction(&self, from: u32, to: u32) -> bool {
+        self.is_connected(from, to)
+            && (self.directions.contains(&(from, to)) || !self.directions.contains(&(to, from)))
+    }
+
+    /// Whether any edge supports two-qubit gates in only one direction.
+    pub fn is_directed(&self) -> bool {
+        self.directions
+            .iter()
+            .any(|&(a, b)| !self.directions.contains(&(b, a)))
+    }
+
     /// Get the number of physical qubits.
     #[inline]
     pub fn num_qubits(&self) -> u32 {
@@ -308,7 +383,6 @@ impl CouplingMap {
         for i in 0..n.saturating_sub(1) {
             map.add_edge(i, i + 1);
         }
-        map.precompute_distances();
         map
     }
 
@@ -320,7 +394,6 @@ impl CouplingMap {
                 map.add_edge(i, j);
             }
         }
-        map.precompute_distances();
         map
     }
 
@@ -330,7 +403,6 @@ impl CouplingMap {
         for i in 1..n {
             map.add_edge(0, i);
         }
-        map.precompute_distances();
         map
     }
 
@@ -356,138 +428,296 @@ impl CouplingMap {
             }
         }
 
-        map.precompute_distances();
         map
     }
 
+    /// Create a `rows` × `cols` square grid, numbered row by row; each
+    /// qubit is coupled to its right and lower neighbour.
+    pub fn grid(rows: u32, cols: u32) -> Self {
+        let mut map = Self::new(rows * cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                let q = r * cols + c;
+                if c + 1 < cols {
+                    map.add_edge(q, q + 1);
+                }
+                if r + 1 < rows {
+                    map.add_edge(q, q + cols);
+                }
+            }
+        }
+        map
+    }
+
+    /// Create a heavy-hex lattice, the topology of IBM Falcon, Eagle and
+    /// Heron devices.
+    ///
+    /// The lattice has `distance` rows, each a chain of `2 * distance + 1`
+    /// qubits. Consecutive rows are joined by bridge qubits at every fourth
+    /// chain position, offset by two between even and odd gaps, so every
+    /// cell is a hexagon with a qubit on each edge and no qubit has more
+    /// than three neighbours. Qubits are numbered like IBM devices: a row,
+    /// then the bridges below it, then the next row. For odd `distance` that
+    /// is `(5d² + 2d − 1) / 2` qubits, e.g. 25 for `distance = 3`.
+    pub fn heavy_hex(distance: u32) -> Self {
+        let row_len = 2 * distance + 1;
+        // Chain positions of the bridges below row `r`.
+        let bridges = |r: u32| (if r % 2 == 0 { 0 } else { 2 }..row_len).step_by(4);
+
+        let mut edges = Vec::new();
+        let mut row_start = 0;
+        for r in 0..distance {
+            for i in 0..row_len - 1 {
+                edges.push((row_start + i, row_start + i + 1));
+            }
+            if r + 1 == distance {
+                row_start += row_len;
+                break;
+            }
+            let num_bridges = bridges(r).count() as u32;
+            let next_row = row_start + row_len + num_bridges;
+            for (b, pos) in (row_start + row_len..).zip(bridges(r)) {
+                edges.push((row_start + pos, b));
+                edges.push((b, next_row + pos));
+            }
+            row_start = next_row;
+        }
+        Self::from_edge_list(row_start, &edges)
+    }
+
     /// Create a coupling map from a list of edge pairs.
     pub fn from_edge_list(num_qubits: u32, edges: &[(u32, u32)]) -> Self {
         let mut map = Self::new(num_qubits);
         for &(q1, q2) in edges {
             map.add_edge(q1, q2);
         }
-        map.precompute_distances();
         map
     }
 
-    /// O(1) shortest-path distance lookup using the precomputed matrix.
-    /// Falls back to BFS if the matrix has not been precomputed.
-    pub fn distance(&self, from: u32, to: u32) -> Option<u32> {
-        if from == to {
-            return Some(0);
+    /// Read a coupling map from an edge-list file.
+    ///
+    /// The file is either a JSON array of pairs (`[[0, 1], [1, 2]]`, the
+    /// `coupling_map` format of IBM backends) or text with one pair per
+    /// line, separated by whitespace or a comma; blank lines and `#`
+    /// comments are ignored. Edges are bidirectional, and the number of
+    /// qubits is one more than the highest index.
+    pub fn from_edge_list_file(path: impl AsRef<Path>) -> CompileResult<Self> {
+        let path = path.as_ref();
+        let invalid = |reason: String| {
+            CompileError::InvalidConfiguration(format!(
+                "coupling map file {}: {reason}",
+                path.display()
+            ))
+        };
+        let contents = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+
+        let edges: Vec<(u32, u32)> = if contents.trim_start().starts_with('[') {
+            serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?
+        } else {
+            let mut edges = Vec::new();
+            for (number, line) in contents.lines().enumerate() {
+                let line = line.split('#').next().unwrap_or_default().trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let fields: Vec<&str> = line
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|f| !f.is_empty())
+                    .collect();
+                let edge = match fields.as_slice() {
+                    [a, b] => a.parse().ok().zip(b.parse().ok()),
+                    _ => None,
+                };
+                let edge = edge.ok_or_else(|| {
+                    invalid(format!(
+                        "line {}: expected two qubit indices, got '{line}'",
+                        number + 1
+                    ))
+                })?;
+                edges.push(edge);
+            }
+            edges
+        };
+
+        let num_qubits = edges.iter().map(|&(a, b)| a.max(b) + 1).max().unwrap_or(0);
+        Ok(Self::from_edge_list(num_qubits, &edges))
+    }
+
+    /// Create a coupling map from `(control, target)` pairs, such as the
+    /// `coupling_map` reported by IBM backends. A pair listed in both
+    /// directions is bidirectional; all others are native in one direction
+    /// only.
+    pub fn from_directed_edge_list(num_qubits: u32, edges: &[(u32, u32)]) -> Self {
+        let mut map = Self::new(num_qubits);
+        for &(from, to) in edges {
+            map.add_directed_edge(from, to);
         }
+        map
+    }
 
-        let (f, t) = (from as usize, to as usize);
-        if f < self.dist_matrix.len() && t < self.dist_matrix[f].len() {
-            let d = self.dist_matrix[f][t];
-            return if d == u32::MAX { None } else { Some(d) };
+    /// Restrict the map to a subset of physical qubits.
+    ///
+    /// Qubit indices are preserved; only edges with both endpoints in
+    /// `qubits` are kept, so qubits outside the subset become isolated.
+    pub fn restricted_to(&self, qubits: &[u32]) -> Self {
+        let mut map = Self::new(self.num_qubits);
+        for &(q1, q2) in &self.edges {
+            if qubits.contains(&q1) && qubits.contains(&q2) {
+                map.add_edge(q1, q2);
+            }
         }
+        map.directions = self
+            .directions
+            .iter()
+            .filter(|(a, b)| qubits.contains(a) && qubits.contains(b))
+            .copied()
+            .collect();
+        map
+    }
 
-        // Fallback BFS (for manually-constructed maps without precompute).
-        // This is O(V+E) per call — call rebuild_caches() to precompute O(1) lookups.
-        tracing::warn!(
-            from,
-            to,
-            "CouplingMap: distance matrix not precomputed, falling back to BFS. \
-             Call rebuild_caches() after deserialization to avoid performance cliffs."
-        );
-        self.distance_bfs(from, to)
+    /// Shortest-path distance between two qubits, or `None` if no path
+    /// exists. O(1) once the distance matrix is cached.
+    pub fn distance(&self, from: u32, to: u32) -> Option<u32> {
+        if from == to {
+            return Some(0);
+        }
+        let d = *self.paths().dist.get(from as usize)?.get(to as usize)?;
+        (d != u32::MAX).then_some(d)
     }
 
-    /// Reconstruct shortest path from→to using the predecessor matrix.
-    /// Falls back to BFS if the matrix has not been precomputed (e.g. for
-    /// maps built via `new()` + `add_edge()`).
+    /// Reconstruct the shortest path from→to, both endpoints included.
     /// Returns `None` if no path exists.
     pub fn shortest_path(&self, from: u32, to: u32) -> Option<Vec<u32>> {
         if from == to {
             return Some(vec![from]);
         }
-
-        let (f, t) = (from as usize, to as usize);
-        if f >= self.pred_matrix.len() || t >= self.pred_matrix[f].len() {
-            // Fallback BFS (for manually-constructed maps without precompute).
-            // O(V+E) per call — call rebuild_caches() to precompute O(1) lookups.
-            tracing::warn!(
-                from,
-                to,
-                "CouplingMap: predecessor matrix not precomputed, falling back to BFS. \
-                 Call rebuild_caches() after manual construction to avoid performance cliffs."
-            );
-            return self.shortest_path_bfs(from, to);
-        }
-
-        if self.dist_matrix[f][t] == u32::MAX {
-            return None;
-        }
+        self.distance(from, to)?;
 
         // Reconstruct from→to using predecessor chain
+        let pred = &self.paths().pred[from as usize];
         let mut path = vec![to];
         let mut current = to;
         while current != from {
-            let pred = self.pred_matrix[f][current as usize];
-            if pred == u32::MAX {
-                return None;
-            }
-            path.push(pred);
-            current = pred;
+            current = pred[current as usize];
+            path.push(current);
         }
         path.reverse();
         Some(path)
     }
+}
 
-    /// BFS fallback for shortest-path reconstruction.
-    fn shortest_path_bfs(&self, from: u32, to: u32) -> Option<Vec<u32>> {
-        let mut predecessor: FxHashMap<u32, u32> = FxHashMap::default();
-        let mut queue = std::collections::VecDeque::new();
-        queue.push_back(from);
-        predecessor.insert(from, from);
+/// A subregion of the device that compilation is confined to.
+///
+/// Stored as a custom property in the [`PropertySet`]. Layout passes only
+/// place logical qubits on patch qubits, and
+/// [`PassManagerBuilder::with_target_patch`](crate::PassManagerBuilder::with_target_patch)
+/// restricts the coupling map so routing never leaves the patch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetPatch {
+    /// Physical qubits in the patch, in placement order.
+    qubits: Vec<u32>,
+}
 
-        'search: while let Some(current) = queue.pop_front() {
-            for &neighbor in self.adjacency.get(&current).into_iter().flatten() {
-                if let std::collections::hash_map::Entry::Vacant(e) = predecessor.entry(neighbor) {
-                    e.insert(current);
-                    if neighbor == to {
-                        break 'search;
-                    }
-                    queue.push_back(neighbor);
-                }
+impl TargetPatch {
+    /// Create a patch from an explicit list of physical qubits.
+    ///
+    /// Duplicates are dropped; the first occurrence keeps its position.
+    pub fn new(qubits: &[u32]) -> Self {
+        let mut unique = Vec::with_capacity(qubits.len());
+        for &q in qubits {
+            if !unique.contains(&q) {
+                unique.push(q);
             }
         }
+        Self { qubits: unique }
+    }
 
-        if !predecessor.contains_key(&to) {
-            return None;
-        }
+    /// Select a connected `size`-qubit patch with the lowest total error.
+    ///
+    /// `qubit_errors[p]` is the error rate of physical qubit `p` (e.g. the
+    /// readout or single-qubit gate error from calibration data); missing
+    /// entries count as zero. Starting from every qubit in turn, the patch is
+    /// grown greedily by adding the lowest-error neighbour, and the cheapest
+    /// result is kept. Returns `None` if no connected region of that size
+    /// exists.
+    pub fn best(coupling_map: &CouplingMap, qubit_errors: &[f64], size: usize) -> Option<Self> {
+        Self::best_excluding(coupling_map, qubit_errors, size, &[])
+    }
 
-        let mut path = vec![to];
-        let mut current = to;
-        while current != from {
-            current = predecessor[&current];
-            path.push(current);
+    /// Like [`best`](Self::best), but never using a qubit in `excluded`,
+    /// e.g. qubits already given to another circuit.
+    pub fn best_excluding(
+        coupling_map: &CouplingMap,
+        qubit_errors: &[f64],
+        size: usize,
+        excluded: &[u32],
+    ) -> Option<Self> {
+        let error = |q: u32| qubit_errors.get(q as usize).copied().unwrap_or(0.0);
+        let mut best: Option<(f64, Vec<u32>)> = None;
+
+        for seed in (0..coupling_map.num_qubits()).filter(|q| !excluded.contains(q)) {
+            let mut patch = vec![seed];
+            let mut total = error(seed);
+            while patch.len() < size {
+                let next = patch
+                    .iter()
+                    .flat_map(|&q| coupling_map.neighbors(q))
+                    .filter(|q| !patch.contains(q) && !excluded.contains(q))
+                    .min_by(|&a, &b| error(a).total_cmp(&error(b)).then(a.cmp(&b)));
+                let Some(next) = next else { break };
+                total += error(next);
+                patch.push(next);
+            }
+
+            if patch.len() == size && best.as_ref().is_none_or(|(cost, _)| total < *cost) {
+                best = Some((total, patch));
+            }
         }
-        path.reverse();
-        Some(path)
+
+        best.map(|(_, qubits)| Self { qubits })
     }
 
-    /// BFS fallback for distance computation.
-    fn distance_bfs(&self, from: u32, to: u32) -> Option<u32> {
-        let mut visited = FxHashMap::default();
-        let mut queue = std::collections::VecDeque::new();
-        queue.push_back((from, 0u32));
-        visited.insert(from, 0u32);
+    /// Select a patch from a calibration noise profile.
+    ///
+    /// Uses the per-qubit readout errors; see [`best`](Self::best).
+    pub fn from_noise_profile(
+        coupling_map: &CouplingMap,
+        profile: &NoiseProfile,
+        size: usize,
+    ) -> Option<Self> {
+        let errors = profile.readout_errors.as_deref().unwrap_or_default();
+        Self::best(coupling_map, errors, size)
+    }
 
-        while let Some((current, dist)) = queue.pop_front() {
-            for &neighbor in self.adjacency.get(&current).into_iter().flatten() {
-                if neighbor == to {
-                    return Some(dist + 1);
-                }
-                if let std::collections::hash_map::Entry::Vacant(e) = visited.entry(neighbor) {
-                    e.insert(dist + 1);
-                    queue.push_back((neighbor, dist + 1));
-                }
-            }
+    /// Check that every patch qubit exists on the device.
+    pub fn validate(&self, coupling_map: &CouplingMap) -> CompileResult<()> {
+        let num_qubits = coupling_map.num_qubits();
+        match self.qubits.iter().find(|&&q| q >= num_qubits) {
+            Some(q) => Err(CompileError::InvalidConfiguration(format!(
+                "target patch qubit {q} is outside the {num_qubits}-qubit device"
+            ))),
+            None => Ok(()),
         }
+    }
+
+    /// Get the physical qubits in the patch.
+    pub fn qubits(&self) -> &[u32] {
+        &self.qubits
+    }
+
+    /// Check whether a physical qubit belongs to the patch.
+    pub fn contains(&self, qubit: u32) -> bool {
+        self.qubits.contains(&qubit)
+    }
 
-        None
+    /// Get the number of qubits in the patch.
+    pub fn len(&self) -> usize {
+        self.qubits.len()
+    }
+
+    /// Check if the patch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.qubits.is_empty()
     }
 }
 
@@ -549,6 +779,27 @@ impl BasisGates {
         Self::new(["rz", "rx", "ry", "cz", "measure", "barrier", "shuttle"])
     }
 
+    /// Create a Clifford+T basis (H + S + T + CX) for fault-tolerant targets.
+    ///
+    /// Arbitrary rotations are not native; they are approximated by
+    /// [`CliffordTSynthesis`](crate::passes::CliffordTSynthesis).
+    pub fn clifford_t() -> Self {
+        Self::new([
+            "h", "s", "sdg", "t", "tdg", "x", "y", "z", "cx", "id", "measure", "barrier",
+        ])
+    }
+
+    /// Whether this is a discrete Clifford+T basis: it contains `h`, `t` and
+    /// `cx` but no continuous rotation.
+    pub fn is_clifford_t(&self) -> bool {
+        self.contains("h")
+            && self.contains("t")
+            && self.contains("cx")
+            && !["rz", "rx", "ry", "p", "u", "prx"]
+                .iter()
+                .any(|g| self.contains(g))
+    }
+
     /// Create a universal basis (all standard gates).
     pub fn universal() -> Self {
         Self::new([
@@ -623,6 +874,13 @@ pub struct PropertySet {
     /// Should be set before running translation passes.
     pub basis_gates: Option<BasisGates>,
 
+    /// Seed for passes that make random choices.
+    ///
+    /// Without a seed these passes still give the same result on every
+    /// run; a seed picks a different, equally reproducible choice among
+    /// equally good options (e.g. SABRE SWAP candidates with equal score).
+    pub seed: Option<u64>,
+
     /// Custom properties storage (type-erased).
     custom: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
 }
@@ -671,6 +929,13 @@ impl PropertySet {
         self
     }
 
+    /// Set the seed for passes that make random choices.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Insert a custom property.
     pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
         self.custom.insert(TypeId::of::<T>(), Box::new(value));
@@ -697,6 +962,48 @@ impl PropertySet {
             .and_then(|v| v.downcast().ok())
             .map(|v| *v)
     }
+
+    /// Get the shared ancilla pool, creating it on first use.
+    ///
+    /// The pool starts with the physical qubits the layout leaves idle
+    /// (within the [`TargetPatch`], if there is one). Returns `None` before
+    /// a layout and coupling map are set. See [`AncillaPool`].
+    pub fn ancilla_pool(&mut self) -> Option<&mut AncillaPool> {
+        if self.get::<AncillaPool>().is_none() {
+            let pool = AncillaPool::from_layout(
+                self.layout.as_ref()?,
+                self.coupling_map.as_ref()?,
+                self.get::<TargetPatch>(),
+            );
+            self.insert(pool);
+        }
+        self.get_mut::<AncillaPool>()
+    }
+
+    /// Check whether the running pass manager's time budget is spent.
+    ///
+    /// Always `false` without a budget. Optimization passes call this
+    /// between rewrites and return early once it is `true`.
+    pub fn budget_exhausted(&self) -> bool {
+        self.get::<CompileDeadline>()
+            .is_some_and(CompileDeadline::expired)
+    }
+
+    /// Fail with [`CompileError::Cancelled`] once the running pass
+    /// manager's [`CancellationToken`] is cancelled.
+    ///
+    /// Always `Ok` without a token. Passes with long loops, such as the
+    /// routing search, call this between iterations.
+    pub fn check_cancelled(&self) -> CompileResult<()> {
+        if self
+            .get::<CancellationToken>()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            Err(CompileError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -724,8 +1031,8 @@ mod tests {
     }
 
     /// A coupling map built via `new()` + `add_edge()` (e.g. from the Python
-    /// bindings) has no precomputed predecessor matrix. `shortest_path` must
-    /// fall back to BFS instead of reporting connected qubits as unreachable
+    /// bindings) computes its predecessor matrix on first query. `shortest_path`
+    /// must not report connected qubits as unreachable
     /// (IQM reviewer bug #4: BasicRouting failed with "qubits 0 and 4 not
     /// connected" on a linear chain).
     #[test]
@@ -744,6 +1051,31 @@ mod tests {
         assert_eq!(sparse.shortest_path(0, 2), None);
     }
 
+    #[test]
+    fn test_directed_edges() {
+        let map = CouplingMap::from_directed_edge_list(4, &[(0, 1), (2, 1), (2, 3), (3, 2)]);
+        assert!(map.is_directed());
+        assert!(map.is_connected(1, 0));
+        assert_eq!(map.distance(0, 3), Some(3));
+        assert!(map.supports_direction(0, 1));
+        assert!(!map.supports_direction(1, 0));
+        assert!(!map.supports_direction(1, 2));
+        assert!(map.supports_direction(2, 3) && map.supports_direction(3, 2));
+        assert!(!map.supports_direction(0, 2));
+
+        // The bidirectional part alone is undirected.
+        let sub = map.restricted_to(&[2, 3]);
+        assert!(!sub.is_directed());
+        assert!(!map.restricted_to(&[0, 1]).supports_direction(1, 0));
+        assert!(!CouplingMap::linear(3).is_directed());
+        assert!(CouplingMap::linear(3).supports_direction(1, 0));
+
+        let json = serde_json::to_string(&map).unwrap();
+        let mut restored: CouplingMap = serde_json::from_str(&json).unwrap();
+        restored.rebuild_caches();
+        assert!(!restored.supports_direction(1, 0));
+    }
+
     #[test]
     fn test_coupling_map_linear() {
         let map = CouplingMap::linear(5);
@@ -762,6 +1094,108 @@ mod tests {
         assert_eq!(map.distance(1, 2), Some(2));
     }
 
+    #[test]
+    fn test_coupling_map_restricted_to() {
+        let map = CouplingMap::linear(6).restricted_to(&[1, 2, 3]);
+        assert_eq!(map.num_qubits(), 6);
+        assert!(map.is_connected(1, 2));
+        assert!(map.is_connected(2, 3));
+        assert!(!map.is_connected(0, 1));
+        assert!(!map.is_connected(3, 4));
+        assert_eq!(map.distance(1, 3), Some(2));
+        assert_eq!(map.distance(0, 3), None);
+    }
+
+    #[test]
+    fn test_coupling_map_grid() {
+        let map = CouplingMap::grid(3, 4);
+        assert_eq!(map.num_qubits(), 12);
+        assert_eq!(map.edges().len(), 17);
+        assert!(map.is_connected(0, 1));
+        assert!(map.is_connected(0, 4));
+        assert!(!map.is_connected(3, 4));
+        assert_eq!(map.distance(0, 11), Some(5));
+    }
+
+    #[test]
+    fn test_coupling_map_heavy_hex() {
+        let map = CouplingMap::heavy_hex(3);
+        assert_eq!(map.num_qubits(), 25);
+        // 3 chains of 6 edges, 4 bridges of 2 edges.
+        assert_eq!(map.edges().len(), 26);
+        assert!((0..25).all(|q| map.neighbors(q).count() <= 3));
+        // First gap bridges chain positions 0 and 4, the second 2 and 6.
+        assert!(map.is_connected(0, 7) && map.is_connected(7, 9));
+        assert!(map.is_connected(4, 8) && map.is_connected(8, 13));
+        assert!(map.is_connected(11, 16) && map.is_connected(16, 20));
+        assert!(map.is_connected(15, 17) && map.is_connected(17, 24));
+        // A hexagon cell: around the chain and back across the bridges.
+        assert_eq!(map.distance(0, 13), Some(6));
+        assert_eq!(CouplingMap::heavy_hex(1).num_qubits(), 3);
+    }
+
+    #[test]
+    fn test_coupling_map_from_edge_list_file() {
+        let dir = std::env::temp_dir().join(format!("arvak-coupling-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let text = dir.join("edges.txt");
+        std::fs::write(&text, "# ring\n0 1\n1, 2\n\n2 3 # closing edge\n3,0\n").unwrap();
+        let map = CouplingMap::from_edge_list_file(&text).unwrap();
+        assert_eq!(map.num_qubits(), 4);
+        assert!(map.is_connected(0, 3));
+        assert_eq!(map.distance(0, 2), Some(2));
+
+        let json = dir.join("edges.json");
+        std::fs::write(&json, "[[0, 1], [1, 0], [1, 2]]").unwrap();
+        let map = CouplingMap::from_edge_list_file(&json).unwrap();
+        assert_eq!(map.num_qubits(), 3);
+        assert_eq!(map.edges().len(), 2);
+
+        std::fs::write(&text, "0 1\n1 x\n").unwrap();
+        let err = CouplingMap::from_edge_list_file(&text).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(CouplingMap::from_edge_list_file(dir.join("missing.txt")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_distance_cache_invalidated_by_add_edge() {
+        let mut map = CouplingMap::linear(4);
+        assert_eq!(map.distance(0, 3), Some(3));
+        map.add_edge(0, 3);
+        assert_eq!(map.distance(0, 3), Some(1));
+        assert_eq!(map.shortest_path(0, 3), Some(vec![0, 3]));
+    }
+
+    #[test]
+    fn test_target_patch_best() {
+        let map = CouplingMap::linear(6);
+        let errors = [0.05, 0.04, 0.01, 0.01, 0.02, 0.09];
+        let patch = TargetPatch::best(&map, &errors, 3).unwrap();
+        let mut qubits = patch.qubits().to_vec();
+        qubits.sort_unstable();
+        assert_eq!(qubits, vec![2, 3, 4]);
+
+        assert!(TargetPatch::best(&map, &errors, 7).is_none());
+        assert_eq!(TargetPatch::new(&[3, 1, 3]).qubits(), &[3, 1]);
+
+        let patch = TargetPatch::best_excluding(&map, &errors, 2, &[2, 3]).unwrap();
+        let mut qubits = patch.qubits().to_vec();
+        qubits.sort_unstable();
+        assert_eq!(qubits, vec![0, 1]);
+        assert!(TargetPatch::best_excluding(&map, &errors, 3, &[2]).is_some());
+        assert!(TargetPatch::best_excluding(&map, &errors, 3, &[1, 4]).is_none());
+    }
+
+    #[test]
+    fn test_target_patch_validate() {
+        let map = CouplingMap::linear(4);
+        assert!(TargetPatch::new(&[1, 2, 3]).validate(&map).is_ok());
+        assert!(TargetPatch::new(&[2, 4]).validate(&map).is_err());
+    }
+
     #[test]
     fn test_basis_gates() {
         let iqm = BasisGates::iqm();
@@ -773,6 +1207,10 @@ mod tests {
         assert!(ibm.contains("cx"));
         assert!(ibm.contains("rz"));
         assert!(!ibm.contains("prx"));
+
+        assert!(BasisGates::clifford_t().is_clifford_t());
+        assert!(!ibm.is_clifford_t());
+        assert!(!BasisGates::universal().is_clifford_t());
     }
 
     #[test]
@@ -0,0 +1,288 @@
+//! Registry of named external passes.
+//!
+//! Sites often need passes of their own, such as excluding qubits that the
+//! last calibration flagged as bad. A [`PassRegistry`] maps names to pass
+//! factories, so such passes can live in a separate crate, or in a shared
+//! library loaded at runtime, and still be inserted into the preset
+//! pipeline. [`PassManagerBuilder::with_registered_pass`] creates a pass by
+//! name and runs it at one of the [`PipelineStage`]s:
+//!
+//! ```rust
+//! use arvak_compile::{
+//!     BasisGates, CompileResult, CouplingMap, Pass, PassKind, PassManagerBuilder, PassRegistry,
+//!     PipelineStage, PropertySet,
+//! };
+//! use arvak_ir::CircuitDag;
+//!
+//! /// Drops the couplers of blacklisted qubits before layout.
+//! struct QubitBlacklist(Vec<u32>);
+//!
+//! impl Pass for QubitBlacklist {
+//!     fn name(&self) -> &str { "QubitBlacklist" }
+//!     fn kind(&self) -> PassKind { PassKind::Analysis }
+//!
+//!     fn run(&self, _dag: &mut CircuitDag, props: &mut PropertySet) -> CompileResult<()> {
+//!         if let Some(map) = props.coupling_map.as_mut() {
+//!             let allowed: Vec<u32> = (0..map.num_qubits()).filter(|q| !self.0.contains(q)).collect();
+//!             *map = map.restricted_to(&allowed);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut registry = PassRegistry::new();
+//! registry.register("qubit-blacklist", "Avoid blacklisted qubits", |options| {
+//!     let qubits = serde_json::from_value(options["qubits"].clone()).unwrap_or_default();
+//!     Ok(Box::new(QubitBlacklist(qubits)))
+//! });
+//!
+//! let options = serde_json::json!({ "qubits": [0] });
+//! let (pm, _props) = PassManagerBuilder::new()
+//!     .with_target(CouplingMap::star(5), BasisGates::iqm())
+//!     .with_registered_pass(&registry, "qubit-blacklist", PipelineStage::PreLayout, &options)
+//!     .unwrap()
+//!     .build();
+//! assert!(!pm.is_empty());
+//! ```
+//!
+//! # Plugin libraries
+//!
+//! With `--features dynamic-passes`, [`PassRegistry::load_plugin`] loads a
+//! shared library that exports
+//!
+//! ```ignore
+//! #[unsafe(no_mangle)]
+//! pub extern "Rust" fn arvak_register_passes(registry: &mut PassRegistry) { ... }
+//! ```
+//!
+//! and lets it register its passes. As with backend plugins in `arvak-hal`,
+//! the library must be built with the same compiler and the same
+//! `arvak-compile` version as the host.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rustc_hash::FxHashMap;
+use tracing::debug;
+#[cfg(feature = "dynamic-passes")]
+use tracing::info;
+
+use crate::error::{CompileError, CompileResult};
+use crate::pass::Pass;
+
+/// Factory creating a registered pass from its options.
+type PassFactory = Box<dyn Fn(&serde_json::Value) -> CompileResult<Box<dyn Pass>> + Send + Sync>;
+
+/// Points of the preset pipeline where external passes can run.
+///
+/// Stages are named after the built-in passes they follow; a stage whose
+/// built-in passes are not part of the pipeline (e.g. routing without a
+/// coupling map) still runs its external passes in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PipelineStage {
+    /// Before layout, after qubit reuse and the expansion of three-qubit
+    /// gates.
+    PreLayout,
+    /// After layout, before routing.
+    PostLayout,
+    /// After routing, before block resynthesis and translation.
+    PostRouting,
+    /// After translation to the target basis and gate direction fixes.
+    PostTranslation,
+    /// After optimization, before the final measurement check.
+    PostOptimization,
+}
+
+impl PipelineStage {
+    /// All stages, in pipeline order.
+    pub const ALL: [PipelineStage; 5] = [
+        PipelineStage::PreLayout,
+        PipelineStage::PostLayout,
+        PipelineStage::PostRouting,
+        PipelineStage::PostTranslation,
+        PipelineStage::PostOptimization,
+    ];
+
+    /// The stage's name, as accepted by [`FromStr`].
+    pub fn name(self) -> &'static str {
+        match self {
+            PipelineStage::PreLayout => "pre-layout",
+            PipelineStage::PostLayout => "post-layout",
+            PipelineStage::PostRouting => "post-routing",
+            PipelineStage::PostTranslation => "post-translation",
+            PipelineStage::PostOptimization => "post-optimization",
+        }
+    }
+}
+
+impl fmt::Display for PipelineStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PipelineStage {
+    type Err = CompileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|stage| stage.name() == s)
+            .ok_or_else(|| {
+                CompileError::InvalidConfiguration(format!("unknown pipeline stage '{s}'"))
+            })
+    }
+}
+
+/// Named pass factories, for passes defined outside this crate.
+pub struct PassRegistry {
+    /// Factories and descriptions keyed by pass name.
+    factories: FxHashMap<String, (String, PassFactory)>,
+}
+
+impl PassRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            factories: FxHashMap::default(),
+        }
+    }
+
+    /// Register a pass factory under `name`, replacing any earlier one.
+    ///
+    /// The factory receives the options given to
+    /// [`PassManagerBuilder::with_registered_pass`], `null` if there are
+    /// none.
+    ///
+    /// [`PassManagerBuilder::with_registered_pass`]: crate::PassManagerBuilder::with_registered_pass
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        factory: impl Fn(&serde_json::Value) -> CompileResult<Box<dyn Pass>> + Send + Sync + 'static,
+    ) {
+        let name = name.into();
+        debug!("Registering external pass: {}", name);
+        self.factories
+            .insert(name, (description.into(), Box::new(factory)));
+    }
+
+    /// Create the pass registered as `name`.
+    pub fn create(&self, name: &str, options: &serde_json::Value) -> CompileResult<Box<dyn Pass>> {
+        let (_, factory) = self.factories.get(name).ok_or_else(|| {
+            CompileError::InvalidConfiguration(format!("no pass registered with name '{name}'"))
+        })?;
+        factory(options)
+    }
+
+    /// Check if a pass is registered as `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// The description `name` was registered with.
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.factories.get(name).map(|(d, _)| d.as_str())
+    }
+
+    /// Names of all registered passes, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.factories.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Load a plugin library and register its passes.
+    ///
+    /// Returns the names of the passes the library registered. The library
+    /// stays loaded until the process exits, since the passes it creates
+    /// can outlive the registry.
+    ///
+    /// # Safety
+    ///
+    /// The library must export `arvak_register_passes` with the signature
+    /// shown in the [module documentation](self), built with the same
+    /// compiler and `arvak-compile` version. Loading untrusted libraries is
+    /// inherently unsafe.
+    #[cfg(feature = "dynamic-passes")]
+    pub unsafe fn load_plugin(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> CompileResult<Vec<String>> {
+        type RegisterFn = unsafe extern "Rust" fn(&mut PassRegistry);
+
+        let path = path.as_ref();
+        let failed = |reason: String| CompileError::PassFailed {
+            name: "PassRegistry".into(),
+            reason: format!("plugin '{}': {reason}", path.display()),
+        };
+
+        let library =
+            unsafe { libloading::Library::new(path) }.map_err(|e| failed(e.to_string()))?;
+        let register: RegisterFn = unsafe {
+            *library
+                .get::<RegisterFn>(b"arvak_register_passes")
+                .map_err(|e| failed(format!("missing arvak_register_passes: {e}")))?
+        };
+
+        let before: Vec<String> = self.names();
+        unsafe { register(self) };
+        std::mem::forget(library);
+
+        let added: Vec<String> = self
+            .names()
+            .into_iter()
+            .filter(|name| !before.contains(name))
+            .collect();
+        info!(
+            "Loaded {} passes from plugin {}",
+            added.len(),
+            path.display()
+        );
+        Ok(added)
+    }
+}
+
+impl Default for PassRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PassRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PassRegistry")
+            .field("passes", &self.names())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::CancelCX;
+
+    #[test]
+    fn test_registry_create_by_name() {
+        let mut registry = PassRegistry::new();
+        registry.register("cancel", "Cancel CX pairs", |_| Ok(Box::new(CancelCX)));
+
+        assert!(registry.contains("cancel"));
+        assert_eq!(registry.names(), ["cancel"]);
+        assert_eq!(registry.description("cancel"), Some("Cancel CX pairs"));
+        let pass = registry.create("cancel", &serde_json::Value::Null).unwrap();
+        assert_eq!(pass.name(), CancelCX.name());
+        assert!(matches!(
+            registry.create("missing", &serde_json::Value::Null),
+            Err(CompileError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_pipeline_stage_names() {
+        for stage in PipelineStage::ALL {
+            assert_eq!(stage.name().parse::<PipelineStage>().unwrap(), stage);
+        }
+        assert!("mid-routing".parse::<PipelineStage>().is_err());
+    }
+}
@@ -0,0 +1,8 @@
+//! Seeded random numbers for passes that make random choices.
+//!
+//! Passes draw from [`arvak_ir::random::SplitMix64`], the generator behind
+//! `Circuit::random`, instead of the `rand` generators, whose streams may
+//! change between releases: a seed must select the same compiled circuit on
+//! every platform and build.
+
+pub(crate) use arvak_ir::random::SplitMix64 as SeededRng;
@@ -0,0 +1,115 @@
+//! One-call compilation for a target device.
+//!
+//! Running a [`PassManager`](crate::PassManager) takes a builder, a
+//! [`PropertySet`](crate::PropertySet) and a round trip through the DAG.
+//! [`transpile`] does all of it for a [`CompileTarget`]: the coupling map,
+//! basis gates and, optionally, the noise profile of a device. Backends
+//! describe themselves with HAL capabilities; `arvak_hal::transpile` takes
+//! those directly.
+//!
+//! ```rust
+//! use arvak_compile::{BasisGates, CompileTarget, CouplingMap, transpile};
+//! use arvak_ir::Circuit;
+//!
+//! let target = CompileTarget::new(CouplingMap::star(5), BasisGates::iqm());
+//! let compiled = transpile(&Circuit::bell().unwrap(), &target, 2).unwrap();
+//! assert!(
+//!     compiled
+//!         .dag()
+//!         .topological_ops()
+//!         .all(|(_, inst)| target.basis_gates.contains(inst.name()))
+//! );
+//! ```
+
+use arvak_ir::Circuit;
+use arvak_ir::noise::NoiseProfile;
+
+use crate::error::CompileResult;
+use crate::manager::PassManagerBuilder;
+use crate::property::{BasisGates, CouplingMap};
+
+/// The device a circuit is compiled for.
+#[derive(Debug, Clone)]
+pub struct CompileTarget {
+    /// Qubit connectivity.
+    pub coupling_map: CouplingMap,
+    /// Gates the compiled circuit may contain.
+    pub basis_gates: BasisGates,
+    /// Error rates for noise-aware passes.
+    pub noise_profile: Option<NoiseProfile>,
+}
+
+impl CompileTarget {
+    /// Create a target without a noise profile.
+    pub fn new(coupling_map: CouplingMap, basis_gates: BasisGates) -> Self {
+        Self {
+            coupling_map,
+            basis_gates,
+            noise_profile: None,
+        }
+    }
+
+    /// Attach the device's noise profile.
+    #[must_use]
+    pub fn with_noise_profile(mut self, profile: NoiseProfile) -> Self {
+        self.noise_profile = Some(profile);
+        self
+    }
+
+    /// A builder configured for this target at optimization `level`, for
+    /// settings [`transpile`] does not take.
+    pub fn builder(&self, level: u8) -> PassManagerBuilder {
+        let builder = PassManagerBuilder::new()
+            .with_optimization_level(level)
+            .with_target(self.coupling_map.clone(), self.basis_gates.clone());
+        match &self.noise_profile {
+            Some(profile) => builder.with_noise_profile(profile.clone()),
+            None => builder,
+        }
+    }
+}
+
+/// Compile `circuit` for `target` at optimization `level` (0-3).
+pub fn transpile(circuit: &Circuit, target: &CompileTarget, level: u8) -> CompileResult<Circuit> {
+    compile(target.builder(level), circuit.clone())
+}
+
+/// Build the pass manager and run it on `circuit`.
+pub(crate) fn compile(builder: PassManagerBuilder, circuit: Circuit) -> CompileResult<Circuit> {
+    let (pm, mut props) = builder.build();
+    let mut dag = circuit.into_dag();
+    pm.run(&mut dag, &mut props)?;
+    Ok(Circuit::from_dag(dag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::QubitId;
+
+    #[test]
+    fn test_transpile_routes_and_translates() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+
+        let target = CompileTarget::new(CouplingMap::linear(3), BasisGates::iqm());
+        let compiled = transpile(&circuit, &target, 1).unwrap();
+
+        for (_, inst) in compiled.dag().topological_ops() {
+            assert!(target.basis_gates.contains(inst.name()), "{}", inst.name());
+            if let [a, b] = inst.qubits[..] {
+                assert!(target.coupling_map.is_connected(a.0, b.0));
+            }
+        }
+        // The input is left as it was.
+        assert_eq!(circuit.dag().num_ops(), 2);
+    }
+
+    #[test]
+    fn test_transpile_too_wide() {
+        let circuit = Circuit::with_size("wide", 4, 0);
+        let target = CompileTarget::new(CouplingMap::linear(2), BasisGates::iqm());
+        assert!(transpile(&circuit, &target, 0).is_err());
+    }
+}
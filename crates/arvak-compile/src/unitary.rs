@@ -4,6 +4,7 @@
 //! two-qubit gate optimization, including ZYZ decomposition and KAK
 //! (Weyl chamber) decomposition.
 
+use arvak_ir::StandardGate;
 use num_complex::Complex64;
 use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
@@ -25,170 +26,101 @@ impl Unitary2x2 {
         Self { data: [a, b, c, d] }
     }
 
+    /// The matrix of a single-qubit standard gate, from the gate matrix
+    /// registry in `arvak_ir` ([`StandardGate::matrix`]).
+    ///
+    /// Returns `None` for multi-qubit gates and symbolic parameters.
+    pub fn from_gate(gate: &StandardGate) -> Option<Self> {
+        if gate.num_qubits() != 1 {
+            return None;
+        }
+        let m = gate.matrix().ok()?;
+        Some(Self::new(m[0], m[1], m[2], m[3]))
+    }
+
+    /// The matrix of a single-qubit gate whose parameters are numeric.
+    fn of(gate: &StandardGate) -> Self {
+        Self::from_gate(gate).expect("numeric single-qubit gate has a matrix")
+    }
+
     /// Create the identity matrix.
     pub fn identity() -> Self {
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(1.0, 0.0),
-        )
+        Self::of(&StandardGate::I)
     }
 
     /// Create a Hadamard matrix.
     pub fn h() -> Self {
-        let s = 1.0 / 2.0_f64.sqrt();
-        Self::new(
-            Complex64::new(s, 0.0),
-            Complex64::new(s, 0.0),
-            Complex64::new(s, 0.0),
-            Complex64::new(-s, 0.0),
-        )
+        Self::of(&StandardGate::H)
     }
 
     /// Create a Pauli-X matrix.
     pub fn x() -> Self {
-        Self::new(
-            Complex64::new(0.0, 0.0),
-            Complex64::new(1.0, 0.0),
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-        )
+        Self::of(&StandardGate::X)
     }
 
     /// Create a Pauli-Y matrix.
     pub fn y() -> Self {
-        Self::new(
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, -1.0),
-            Complex64::new(0.0, 1.0),
-            Complex64::new(0.0, 0.0),
-        )
+        Self::of(&StandardGate::Y)
     }
 
     /// Create a Pauli-Z matrix.
     pub fn z() -> Self {
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(-1.0, 0.0),
-        )
+        Self::of(&StandardGate::Z)
     }
 
     /// Create an S gate (sqrt(Z)).
     pub fn s() -> Self {
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 1.0),
-        )
+        Self::of(&StandardGate::S)
     }
 
     /// Create an S-dagger gate.
     pub fn sdg() -> Self {
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, -1.0),
-        )
+        Self::of(&StandardGate::Sdg)
     }
 
     /// Create a T gate (fourth root of Z).
     pub fn t() -> Self {
-        let phase = Complex64::from_polar(1.0, PI / 4.0);
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            phase,
-        )
+        Self::of(&StandardGate::T)
     }
 
     /// Create a T-dagger gate.
     pub fn tdg() -> Self {
-        let phase = Complex64::from_polar(1.0, -PI / 4.0);
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            phase,
-        )
+        Self::of(&StandardGate::Tdg)
     }
 
     /// Create an SX gate (sqrt(X)).
     pub fn sx() -> Self {
-        let half = Complex64::new(0.5, 0.0);
-        let half_i = Complex64::new(0.0, 0.5);
-        Self::new(half + half_i, half - half_i, half - half_i, half + half_i)
+        Self::of(&StandardGate::SX)
     }
 
     /// Create an SX-dagger gate.
     pub fn sxdg() -> Self {
-        let half = Complex64::new(0.5, 0.0);
-        let half_i = Complex64::new(0.0, 0.5);
-        Self::new(half - half_i, half + half_i, half + half_i, half - half_i)
+        Self::of(&StandardGate::SXdg)
     }
 
     /// Create an RX rotation matrix.
     pub fn rx(theta: f64) -> Self {
-        let c = (theta / 2.0).cos();
-        let s = (theta / 2.0).sin();
-        Self::new(
-            Complex64::new(c, 0.0),
-            Complex64::new(0.0, -s),
-            Complex64::new(0.0, -s),
-            Complex64::new(c, 0.0),
-        )
+        Self::of(&StandardGate::Rx(theta.into()))
     }
 
     /// Create an RY rotation matrix.
     pub fn ry(theta: f64) -> Self {
-        let c = (theta / 2.0).cos();
-        let s = (theta / 2.0).sin();
-        Self::new(
-            Complex64::new(c, 0.0),
-            Complex64::new(-s, 0.0),
-            Complex64::new(s, 0.0),
-            Complex64::new(c, 0.0),
-        )
+        Self::of(&StandardGate::Ry(theta.into()))
     }
 
     /// Create an RZ rotation matrix.
     pub fn rz(theta: f64) -> Self {
-        let exp_neg = Complex64::from_polar(1.0, -theta / 2.0);
-        let exp_pos = Complex64::from_polar(1.0, theta / 2.0);
-        Self::new(
-            exp_neg,
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            exp_pos,
-        )
+        Self::of(&StandardGate::Rz(theta.into()))
     }
 
     /// Create a phase gate P(lambda).
     pub fn p(lambda: f64) -> Self {
-        let phase = Complex64::from_polar(1.0, lambda);
-        Self::new(
-            Complex64::new(1.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            Complex64::new(0.0, 0.0),
-            phase,
-        )
+        Self::of(&StandardGate::P(lambda.into()))
     }
 
     /// Create a U gate U(theta, phi, lambda).
     pub fn u(theta: f64, phi: f64, lambda: f64) -> Self {
-        let c = (theta / 2.0).cos();
-        let s = (theta / 2.0).sin();
-        Self::new(
-            Complex64::new(c, 0.0),
-            -Complex64::from_polar(s, lambda),
-            Complex64::from_polar(s, phi),
-            Complex64::from_polar(c, phi + lambda),
-        )
+        Self::of(&StandardGate::U(theta.into(), phi.into(), lambda.into()))
     }
 
     /// Multiply this matrix by another: self * other.
@@ -380,6 +312,19 @@ impl Unitary4x4 {
         }
     }
 
+    /// The matrix of a two-qubit standard gate, first qubit most
+    /// significant, from the gate matrix registry in `arvak_ir`
+    /// ([`StandardGate::matrix`]).
+    ///
+    /// Returns `None` for other gates and symbolic parameters.
+    pub fn from_gate(gate: &StandardGate) -> Option<Self> {
+        if gate.num_qubits() != 2 {
+            return None;
+        }
+        let data = gate.matrix().ok()?.try_into().ok()?;
+        Some(Self { data })
+    }
+
     /// Get element at (row, col).
     fn get(&self, row: usize, col: usize) -> Complex64 {
         self.data[row * 4 + col]
@@ -433,11 +378,6 @@ impl Unitary4x4 {
         result
     }
 
-    /// Compute the trace of the 4x4 matrix.
-    fn trace(&self) -> Complex64 {
-        self.get(0, 0) + self.get(1, 1) + self.get(2, 2) + self.get(3, 3)
-    }
-
     /// Compute the determinant of the 4x4 matrix.
     fn det(&self) -> Complex64 {
         // Expansion by minors along the first row.
@@ -648,87 +588,117 @@ impl Unitary4x4 {
         true
     }
 
+    /// The canonical interaction Ud(tx, ty, tz) = exp(i(tx·XX + ty·YY + tz·ZZ)).
+    pub fn canonical(tx: f64, ty: f64, tz: f64) -> Self {
+        let zero = Complex64::new(0.0, 0.0);
+
+        // The canonical gate in the computational basis:
+        // Ud = exp(i(tx XX + ty YY + tz ZZ))
+        //
+        // XX + YY = 2(|01⟩⟨10| + |10⟩⟨01|) and XX - YY = 2(|00⟩⟨11| + |11⟩⟨00|) (up to signs)
+        //
+        // In computational basis {|00⟩, |01⟩, |10⟩, |11⟩}:
+        // The matrix is:
+        // [[e^{iz} cos(tx-ty),  0,  0,  i·e^{iz} sin(tx-ty)],
+        //  [0,  e^{-iz} cos(tx+ty),  i·e^{-iz} sin(tx+ty),  0],
+        //  [0,  i·e^{-iz} sin(tx+ty),  e^{-iz} cos(tx+ty),  0],
+        //  [i·e^{iz} sin(tx-ty),  0,  0,  e^{iz} cos(tx-ty)]]
+        let eiz = Complex64::from_polar(1.0, tz);
+        let emiz = Complex64::from_polar(1.0, -tz);
+        let i = Complex64::new(0.0, 1.0);
+
+        let cp = (tx + ty).cos();
+        let sp = (tx + ty).sin();
+        let cm = (tx - ty).cos();
+        let sm = (tx - ty).sin();
+
+        Self {
+            data: [
+                eiz * cm,
+                zero,
+                zero,
+                i * eiz * sm,
+                zero,
+                emiz * cp,
+                i * emiz * sp,
+                zero,
+                zero,
+                i * emiz * sp,
+                emiz * cp,
+                zero,
+                i * eiz * sm,
+                zero,
+                zero,
+                eiz * cm,
+            ],
+        }
+    }
+
+    /// Multiply every element by `factor`.
+    fn scale(&self, factor: Complex64) -> Self {
+        Self {
+            data: self.data.map(|v| v * factor),
+        }
+    }
+
     /// Perform the KAK (Weyl chamber) decomposition.
     ///
     /// Any U ∈ U(4) decomposes as:
     ///   U = e^{iφ} · (A0 ⊗ A1) · Ud(tx,ty,tz) · (B0 ⊗ B1)
     ///
-    /// The decomposition determines:
-    /// - The minimum number of CNOT gates needed (0–3) via Makhlin invariants
-    /// - The Weyl coordinates (tx, ty, tz) from eigenvalues of M^T·M
-    /// - The local single-qubit unitaries (A0, A1, B0, B1) where feasible
-    ///
-    /// For product-state unitaries (0 CNOTs), the factors A⊗B are extracted.
-    /// For entangling unitaries, the local unitary extraction may not be
-    /// exact when eigenvalues are degenerate; in that case the raw 4×4
-    /// matrix should be stored as a `CustomGate`.
+    /// In the magic basis local gates are real orthogonal matrices, so with
+    /// U' = B†·U·B the symmetric unitary U'ᵀ·U' is diagonalized by a real
+    /// orthogonal P. Its eigenphases give the interaction coefficients, and
+    /// P and U'·P·D⁻¹ map back to the local factors. The coefficients are then
+    /// moved into the Weyl chamber, which fixes the minimum CNOT count.
     pub fn kak_decompose(&self) -> WeylDecomposition {
-        let det = self.det();
-        let global_phase = det.arg() / 4.0;
-
-        // Early exit for product states (0 CNOTs).
-        if self.is_product_state() {
-            let (a0, a1) = factor_kron(self);
-            return WeylDecomposition {
-                a0,
-                a1,
-                b0: Unitary2x2::identity(),
-                b1: Unitary2x2::identity(),
-                tx: 0.0,
-                ty: 0.0,
-                tz: 0.0,
-                global_phase,
-                num_cnots: 0,
-            };
+        // Normalize to SU(4) and transform to the magic basis.
+        let su4 = self.scale(Complex64::from_polar(1.0, -self.det().arg() / 4.0));
+        let up = magic_dagger().mul(&su4).mul(&magic_basis());
+        let m2 = up.transpose().mul(&up);
+
+        let (mut p, eigenvalues) = diagonalize_symmetric_unitary(&m2);
+        if det_real(&p) < 0.0 {
+            for row in 0..4 {
+                p[row * 4] = -p[row * 4];
+            }
         }
 
-        // Normalize to SU(4) and transform to magic (Bell) basis.
-        let phase_factor = Complex64::from_polar(1.0, -global_phase);
-        let mut u_su4 = self.clone();
-        for e in &mut u_su4.data {
-            *e *= phase_factor;
+        // D = sqrt(Pᵀ·M2·P), with det(D) = 1 so that both factors are in SO(4).
+        let mut d = eigenvalues.map(Complex64::sqrt);
+        if (d[0] * d[1] * d[2] * d[3]).re < 0.0 {
+            d[0] = -d[0];
         }
-        let b_mat = magic_basis();
-        let bd_mat = magic_dagger();
-        let m = bd_mat.mul(&u_su4).mul(&b_mat);
-
-        // Compute Q = M^T · M for Makhlin invariant classification.
-        let mt = m.transpose();
-        let q = mt.mul(&m);
-        let tr_q = q.trace();
-        let q2 = q.mul(&q);
-        let tr_q2 = q2.trace();
-
-        // Makhlin invariants: G₁ = tr(Q)²/16, G₂ = (tr(Q)²-tr(Q²))/4
-        let g1 = tr_q * tr_q / 16.0;
-        let g2 = (tr_q * tr_q - tr_q2) / 4.0;
-
-        // Classify CNOT count from invariants.
-        let num_cnots = cnot_count_from_invariants(g1, g2);
-
-        // Extract Weyl coordinates from eigenvalues when non-degenerate.
-        let eigs = eigenvalues_4x4(&q);
-        let (tx, ty, tz) = extract_weyl_from_eigenvalues(&eigs, num_cnots);
-
-        // For entangling gates, ConsolidateBlocks stores the raw 4×4 matrix
-        // as a CustomGate. The local unitary extraction is only needed for
-        // synthesis, which happens in a later pass. Set identity placeholders.
-        let a0 = Unitary2x2::identity();
-        let a1 = Unitary2x2::identity();
-        let b0 = Unitary2x2::identity();
-        let b1 = Unitary2x2::identity();
-
-        WeylDecomposition {
+
+        let p = Self {
+            data: p.map(|v| Complex64::new(v, 0.0)),
+        };
+        let mut d_inv = Self::identity();
+        for (i, di) in d.iter().enumerate() {
+            d_inv.set(i, i, di.inv());
+        }
+        let k1 = up.mul(&p).mul(&d_inv);
+        let (a0, a1) = factor_kron(&magic_basis().mul(&k1).mul(&magic_dagger()));
+        let (b0, b1) = factor_kron(&magic_basis().mul(&p.transpose()).mul(&magic_dagger()));
+
+        // In the magic basis Ud(tx, ty, tz) is diagonal with phases
+        // (tx-ty+tz, tx+ty-tz, -tx-ty-tz, -tx+ty+tz).
+        let phases = d.map(Complex64::arg);
+        let mut kak = WeylDecomposition {
             a0,
             a1,
             b0,
             b1,
-            tx,
-            ty,
-            tz,
-            global_phase,
-            num_cnots,
-        }
+            tx: f64::midpoint(phases[0], phases[1]),
+            ty: f64::midpoint(phases[1], phases[3]),
+            tz: f64::midpoint(phases[0], phases[3]),
+            global_phase: 0.0,
+            num_cnots: 0,
+        };
+        kak.canonicalize();
+        kak.num_cnots = kak.min_cnots();
+        kak.global_phase = phase_difference(self, &kak.reconstruct());
+        kak
     }
 }
 
@@ -795,330 +765,115 @@ fn magic_dagger() -> Unitary4x4 {
     magic_basis().dagger()
 }
 
-/// Build the canonical interaction unitary Ud(tx, ty, tz) = exp(i(tx·XX + ty·YY + tz·ZZ)).
-///
-/// In the computational basis, this is a diagonal matrix in the Bell basis.
-/// Transformed back to the computational basis:
-///   Ud = B · diag(e^{i(tx-ty+tz)}, e^{i(-tx+ty+tz)}, e^{i(tx+ty-tz)}, e^{i(-tx-ty-tz)}) · B†
-///
-/// More directly in the computational basis:
-#[cfg(test)]
-fn canonical_unitary(tx: f64, ty: f64, tz: f64) -> Unitary4x4 {
-    let zero = Complex64::new(0.0, 0.0);
+/// Tolerance for classifying Weyl coordinates (zero, π/4).
+const WEYL_TOLERANCE: f64 = 1e-9;
 
-    // The canonical gate in the computational basis:
-    // Ud = exp(i(tx XX + ty YY + tz ZZ))
-    //
-    // XX + YY = 2(|01⟩⟨10| + |10⟩⟨01|) and XX - YY = 2(|00⟩⟨11| + |11⟩⟨00|) (up to signs)
-    //
-    // In computational basis {|00⟩, |01⟩, |10⟩, |11⟩}:
-    // The matrix is:
-    // [[e^{iz} cos(tx-ty),  0,  0,  i·e^{iz} sin(tx-ty)],
-    //  [0,  e^{-iz} cos(tx+ty),  i·e^{-iz} sin(tx+ty),  0],
-    //  [0,  i·e^{-iz} sin(tx+ty),  e^{-iz} cos(tx+ty),  0],
-    //  [i·e^{iz} sin(tx-ty),  0,  0,  e^{iz} cos(tx-ty)]]
-    let eiz = Complex64::from_polar(1.0, tz);
-    let emiz = Complex64::from_polar(1.0, -tz);
-    let i = Complex64::new(0.0, 1.0);
-
-    let cp = (tx + ty).cos();
-    let sp = (tx + ty).sin();
-    let cm = (tx - ty).cos();
-    let sm = (tx - ty).sin();
-
-    Unitary4x4 {
-        data: [
-            eiz * cm,
-            zero,
-            zero,
-            i * eiz * sm,
-            zero,
-            emiz * cp,
-            i * emiz * sp,
-            zero,
-            zero,
-            i * emiz * sp,
-            emiz * cp,
-            zero,
-            i * eiz * sm,
-            zero,
-            zero,
-            eiz * cm,
-        ],
-    }
-}
-
-/// Solve a depressed cubic t³ + pt + q = 0 using Cardano's formula.
+/// Eigen-decompose a symmetric unitary matrix with a real orthogonal basis.
 ///
-/// Returns one root. For complex coefficients this always works
-/// (no casus irreducibilis).
-fn solve_depressed_cubic(p: Complex64, q: Complex64) -> Complex64 {
-    let zero = Complex64::new(0.0, 0.0);
-
-    // If p ≈ 0: t³ = -q → t = cbrt(-q)
-    if p.norm() < 1e-14 {
-        return cbrt_complex(-q);
-    }
-
-    // Discriminant: D = q²/4 + p³/27
-    let disc = q * q / 4.0 + p * p * p / 27.0;
-    let sqrt_disc = disc.sqrt();
-
-    let s = cbrt_complex(-q / 2.0 + sqrt_disc);
-    let t = cbrt_complex(-q / 2.0 - sqrt_disc);
-
-    // Guard: if s + t gives a near-zero result and the cubic should have
-    // a non-zero root, try the other cube root branch.
-    let root = s + t;
-
-    // Verify and return. For numerical stability, do one Newton step.
-    let f = root * root * root + p * root + q;
-    let fp = root * root * 3.0 + p;
-    if fp.norm() > 1e-30 {
-        root - f / fp
-    } else {
-        if root.norm() < 1e-12 {
-            return zero;
+/// The real and imaginary parts of a symmetric unitary are commuting real
+/// symmetric matrices, so a generic real combination of them has the same
+/// eigenvectors. Returns the eigenvectors as the columns of a row-major
+/// orthogonal matrix, and the eigenvalues.
+fn diagonalize_symmetric_unitary(m: &Unitary4x4) -> ([f64; 16], [Complex64; 4]) {
+    let mut best = ([0.0; 16], [Complex64::new(0.0, 0.0); 4], f64::INFINITY);
+    // A combination is degenerate only on a measure-zero set; a few fixed
+    // angles avoid coincidences deterministically.
+    for k in 0..8 {
+        let theta = 1.0 + 0.618_033_988_75 * f64::from(k);
+        let (c, s) = (theta.cos(), theta.sin());
+        let mixed = m.data.map(|v| c * v.re + s * v.im);
+        let p = jacobi_eigenvectors(mixed);
+
+        let mut off_diagonal = 0.0;
+        let mut eigenvalues = [Complex64::new(0.0, 0.0); 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                let mut sum = Complex64::new(0.0, 0.0);
+                for r in 0..4 {
+                    for col in 0..4 {
+                        sum += p[r * 4 + i] * m.get(r, col) * p[col * 4 + j];
+                    }
+                }
+                if i == j {
+                    eigenvalues[i] = sum;
+                } else {
+                    off_diagonal += sum.norm();
+                }
+            }
+        }
+        if off_diagonal < best.2 {
+            best = (p, eigenvalues, off_diagonal);
+        }
+        if off_diagonal < 1e-12 {
+            break;
         }
-        root
-    }
-}
-
-/// Complex cube root: returns the principal cube root.
-fn cbrt_complex(z: Complex64) -> Complex64 {
-    if z.norm() < 1e-30 {
-        return Complex64::new(0.0, 0.0);
-    }
-    let r = z.norm().cbrt();
-    let theta = z.arg() / 3.0;
-    Complex64::from_polar(r, theta)
-}
-
-/// Solve a quartic λ⁴ + aλ³ + bλ² + cλ + d = 0 using Ferrari's method.
-///
-/// Returns all four roots.
-fn solve_quartic(a: Complex64, b: Complex64, c: Complex64, d: Complex64) -> [Complex64; 4] {
-    // Step 1: Reduce to depressed quartic by substituting λ = t - a/4.
-    // t⁴ + pt² + qt + r = 0
-    let a2 = a * a;
-    let a3 = a2 * a;
-    let a4 = a2 * a2;
-    let p = b - a2 * 3.0 / 8.0;
-    let q = a3 / 8.0 - a * b / 2.0 + c;
-    let r = -a4 * 3.0 / 256.0 + a2 * b / 16.0 - a * c / 4.0 + d;
-
-    let shift = -a / 4.0;
-
-    // If q ≈ 0, the depressed quartic is biquadratic: t⁴ + pt² + r = 0.
-    if q.norm() < 1e-12 {
-        let disc = p * p - r * 4.0;
-        let sqrt_disc = disc.sqrt();
-        let u1 = (-p + sqrt_disc) / 2.0;
-        let u2 = (-p - sqrt_disc) / 2.0;
-        let s1 = u1.sqrt();
-        let s2 = u2.sqrt();
-        return [s1 + shift, -s1 + shift, s2 + shift, -s2 + shift];
-    }
-
-    // Step 2: Resolvent cubic.
-    // y³ - (p)y² - 4ry + (4pr - q²) = 0
-    // In standard form: y³ + Ay² + By + C = 0 where A = -p, B = -4r, C = 4pr - q²
-    let ca = -p;
-    let cb = -r * 4.0;
-    let cc = p * r * 4.0 - q * q;
-
-    // Reduce to depressed cubic: y = t - ca/3
-    let dp = cb - ca * ca / 3.0;
-    let dq = ca * ca * ca * 2.0 / 27.0 - ca * cb / 3.0 + cc;
-
-    let t0 = solve_depressed_cubic(dp, dq);
-    let y0 = t0 - ca / 3.0;
-
-    // Step 3: Factor the depressed quartic using y0.
-    // (t² + y0)² = (y0 - p)t² - qt + (y0² - r)
-    // The RHS should be a perfect square: (√(y0-p) · t - q/(2√(y0-p)))²
-    let w2 = y0 - p;
-    let w = w2.sqrt();
-
-    // Two quadratics:
-    // t² + wt + (y0 + q/(2w)) = 0
-    // t² - wt + (y0 - q/(2w)) = 0
-    let half_q_over_w = if w.norm() > 1e-14 {
-        q / (w * 2.0)
-    } else {
-        // Degenerate: w ≈ 0 means the quartic has a special structure.
-        // Fall back: use y0² - r for the constant term.
-        (y0 * y0 - r).sqrt()
-    };
-
-    let c1 = y0 + half_q_over_w;
-    let c2 = y0 - half_q_over_w;
-
-    // Solve t² - wt + c2 = 0 and t² + wt + c1 = 0
-    let disc1 = w * w - c1 * 4.0;
-    let disc2 = w * w - c2 * 4.0;
-    let sd1 = disc1.sqrt();
-    let sd2 = disc2.sqrt();
-
-    [
-        (-w + sd1) / 2.0 + shift,
-        (-w - sd1) / 2.0 + shift,
-        (w + sd2) / 2.0 + shift,
-        (w - sd2) / 2.0 + shift,
-    ]
-}
-
-/// Eigenvalues of a 4×4 complex matrix via the characteristic polynomial
-/// solved analytically with Ferrari's quartic formula.
-fn eigenvalues_4x4(mat: &Unitary4x4) -> [Complex64; 4] {
-    // Characteristic polynomial via Newton's identities:
-    // det(A - λI) = λ⁴ - e₁λ³ + e₂λ² - e₃λ + e₄ = 0
-    let a2 = mat.mul(mat);
-    let a3 = a2.mul(mat);
-    let a4 = a3.mul(mat);
-
-    let p1 = mat.trace();
-    let p2 = a2.trace();
-    let p3 = a3.trace();
-    let p4 = a4.trace();
-
-    let e1 = p1;
-    let e2 = (e1 * p1 - p2) / 2.0;
-    let e3 = (e2 * p1 - e1 * p2 + p3) / 3.0;
-    let e4 = (e3 * p1 - e2 * p2 + e1 * p3 - p4) / 4.0;
-
-    // Rewrite as: λ⁴ + (-e₁)λ³ + (e₂)λ² + (-e₃)λ + e₄ = 0
-    solve_quartic(-e1, e2, -e3, e4)
-}
-
-/// Determine minimum CNOT count from Makhlin invariants G₁ and G₂.
-///
-/// Classification rules:
-/// - 0 CNOTs: G₁ = 1, G₂ = 3 (product state — should be caught earlier)
-/// - 1 CNOT: G₁ real, G₂ real, G₂ = 2·G₁ + 1 (ty = tz = 0)
-/// - 2 CNOTs: G₁ real and non-negative (tz = 0)
-/// - 3 CNOTs: otherwise (G₁ complex or G₁ real and negative)
-fn cnot_count_from_invariants(g1: Complex64, g2: Complex64) -> u8 {
-    let tol = 1e-4;
-
-    // 0 CNOTs: G₁ ≈ 1, G₂ ≈ 3
-    if (g1.re - 1.0).abs() < tol
-        && g1.im.abs() < tol
-        && (g2.re - 3.0).abs() < tol
-        && g2.im.abs() < tol
-    {
-        return 0;
-    }
-
-    // Check if G₁ is real.
-    let g1_real = g1.im.abs() < tol;
-    let g2_real = g2.im.abs() < tol;
-
-    // 3 CNOTs: G₁ has imaginary part, or G₁ is real and negative.
-    if !g1_real || g1.re < -tol {
-        return 3;
-    }
-
-    // G₁ is real and non-negative from here.
-    // 1 CNOT: G₂ is also real and G₂ = 2·G₁ + 1.
-    if g2_real && (g2.re - (2.0 * g1.re + 1.0)).abs() < tol {
-        return 1;
     }
-
-    // 2 CNOTs: G₁ real and non-negative, but not on the 1-CNOT line.
-    2
+    (best.0, best.1)
 }
 
-/// Extract Weyl coordinates (tx, ty, tz) from eigenvalues of Q = M^T·M.
-///
-/// Uses the CNOT count (from Makhlin invariants) to guide extraction
-/// when eigenvalues are degenerate.
-fn extract_weyl_from_eigenvalues(eigs: &[Complex64; 4], num_cnots: u8) -> (f64, f64, f64) {
-    // For degenerate cases, use the known Weyl chamber structure.
-    match num_cnots {
-        0 => return (0.0, 0.0, 0.0),
-        3 => {
-            // For 3-CNOT gates, eigenvalue extraction from phases is unreliable
-            // when eigenvalues are degenerate (e.g., SWAP has Q = scalar·I).
-            // Use the known structure: SWAP → (π/4, π/4, π/4).
-            // For general 3-CNOT gates, attempt extraction but fall back.
-            let phases = extract_sorted_phases(eigs);
-            let tx = ((phases[0] - phases[3]) / 2.0).abs();
-            let ty = ((phases[1] - phases[2]) / 2.0).abs();
-
-            // If phases are all degenerate, the eigenvalues don't distinguish
-            // the coordinates. Use the maximal Weyl point as default.
-            if tx < 1e-6 && ty < 1e-6 {
-                return (FRAC_PI_4, FRAC_PI_4, FRAC_PI_4);
+/// Eigenvectors of a real symmetric 4×4 matrix by cyclic Jacobi rotations.
+fn jacobi_eigenvectors(mut a: [f64; 16]) -> [f64; 16] {
+    let mut v = [0.0; 16];
+    for i in 0..4 {
+        v[i * 4 + i] = 1.0;
+    }
+    for _ in 0..64 {
+        let off: f64 = (0..4)
+            .flat_map(|i| (0..4).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i * 4 + j] * a[i * 4 + j])
+            .sum();
+        if off < 1e-30 {
+            break;
+        }
+        for p in 0..4 {
+            for q in (p + 1)..4 {
+                let apq = a[p * 4 + q];
+                if apq.abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q * 4 + q] - a[p * 4 + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + theta.hypot(1.0));
+                let c = 1.0 / t.hypot(1.0);
+                let s = t * c;
+                for k in 0..4 {
+                    let (akp, akq) = (a[k * 4 + p], a[k * 4 + q]);
+                    a[k * 4 + p] = c * akp - s * akq;
+                    a[k * 4 + q] = s * akp + c * akq;
+                }
+                for k in 0..4 {
+                    let (apk, aqk) = (a[p * 4 + k], a[q * 4 + k]);
+                    a[p * 4 + k] = c * apk - s * aqk;
+                    a[q * 4 + k] = s * apk + c * aqk;
+                }
+                for k in 0..4 {
+                    let (vkp, vkq) = (v[k * 4 + p], v[k * 4 + q]);
+                    v[k * 4 + p] = c * vkp - s * vkq;
+                    v[k * 4 + q] = s * vkp + c * vkq;
+                }
             }
-
-            let tz = (phases[0] + phases[3] - phases[1] - phases[2]) / 4.0;
-            return normalize_to_weyl_chamber(tx, ty, tz);
         }
-        _ => {}
     }
-
-    // For 1 and 2 CNOT cases, eigenvalues should be non-degenerate enough.
-    let phases = extract_sorted_phases(eigs);
-    let tx = ((phases[0] - phases[3]) / 2.0).abs();
-    let ty = ((phases[1] - phases[2]) / 2.0).abs();
-    let tz = (phases[0] + phases[3] - phases[1] - phases[2]) / 4.0;
-
-    normalize_to_weyl_chamber(tx, ty, tz)
-}
-
-/// Extract half-phases from eigenvalues and sort descending.
-fn extract_sorted_phases(eigs: &[Complex64; 4]) -> [f64; 4] {
-    let mut phases: [f64; 4] = [
-        eigs[0].arg() / 2.0,
-        eigs[1].arg() / 2.0,
-        eigs[2].arg() / 2.0,
-        eigs[3].arg() / 2.0,
-    ];
-
-    // Remove global phase offset: center phases around 0.
-    let sum: f64 = phases.iter().sum();
-    let offset = sum / 4.0;
-    for p in &mut phases {
-        *p -= offset;
-    }
-
-    // Sort descending.
-    phases.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
-    phases
+    v
 }
 
-/// Normalize coordinates into the Weyl chamber: π/4 ≥ tx ≥ ty ≥ |tz| ≥ 0.
-fn normalize_to_weyl_chamber(mut tx: f64, mut ty: f64, mut tz: f64) -> (f64, f64, f64) {
-    tx = normalize_weyl(tx).abs();
-    ty = normalize_weyl(ty).abs();
-    tz = normalize_weyl(tz);
-    let tz_abs = tz.abs();
-
-    // Sort: tx ≥ ty ≥ tz_abs.
-    if ty > tx {
-        std::mem::swap(&mut tx, &mut ty);
-    }
-    if tz_abs > ty {
-        ty = tz_abs;
-    }
-    if ty > tx {
-        std::mem::swap(&mut tx, &mut ty);
-    }
-
-    tx = tx.min(FRAC_PI_4);
-    ty = ty.min(tx);
-
-    (tx, ty, tz)
+/// Determinant of a real row-major 4×4 matrix.
+fn det_real(m: &[f64; 16]) -> f64 {
+    let complex = Unitary4x4 {
+        data: m.map(|v| Complex64::new(v, 0.0)),
+    };
+    complex.det().re
 }
 
-/// Normalize a Weyl coordinate to [-π/2, π/2].
-fn normalize_weyl(x: f64) -> f64 {
-    let mut v = x.rem_euclid(PI);
-    if v > FRAC_PI_2 {
-        v -= PI;
-    }
-    v
+/// Phase φ with `a ≈ e^{iφ} · b`, read off the largest entry of `b`.
+fn phase_difference(a: &Unitary4x4, b: &Unitary4x4) -> f64 {
+    let (idx, _) = b.data.iter().enumerate().fold((0, 0.0), |best, (i, v)| {
+        if v.norm() > best.1 {
+            (i, v.norm())
+        } else {
+            best
+        }
+    });
+    (a.data[idx] / b.data[idx]).arg()
 }
 
 /// Factor a 4x4 unitary that is approximately a Kronecker product A ⊗ B
@@ -1213,72 +968,42 @@ fn factor_kron(m: &Unitary4x4) -> (Unitary2x2, Unitary2x2) {
 }
 
 impl WeylDecomposition {
+    /// Rebuild `(A0 ⊗ A1) · Ud(tx, ty, tz) · (B0 ⊗ B1)`, without the global phase.
+    pub fn reconstruct(&self) -> Unitary4x4 {
+        Unitary4x4::kron(&self.a0, &self.a1)
+            .mul(&Unitary4x4::canonical(self.tx, self.ty, self.tz))
+            .mul(&Unitary4x4::kron(&self.b0, &self.b1))
+    }
+
     /// Synthesize a circuit implementing this decomposition using CNOT gates.
     ///
     /// Returns a list of `(gate, qubit)` pairs where qubit is 0 or 1,
     /// and two-qubit gates are represented as `(CX, 0)` meaning CX(q0, q1).
     ///
-    /// Uses the Vatan-Williams decomposition to achieve the minimum CNOT count.
+    /// Uses `num_cnots` CNOTs: the interaction Ud is built from a fixed
+    /// template (Vatan-Williams for three CNOTs), and the local factors are
+    /// merged into the template's first and last single-qubit layers. The
+    /// circuit equals the decomposed unitary up to global phase.
     pub fn to_circuit(&self) -> Vec<TwoQubitGateOp> {
-        let mut ops = Vec::new();
-
-        match self.num_cnots {
-            0 => {
-                // Product state: just single-qubit gates.
-                // U = (A0·B0) ⊗ (A1·B1) · phase
-                let q0_gate = self.a0.mul(&self.b0);
-                let q1_gate = self.a1.mul(&self.b1);
-                push_1q_decomposed(&mut ops, &q0_gate, 0);
-                push_1q_decomposed(&mut ops, &q1_gate, 1);
-            }
-            1 => {
-                // One CNOT: U = (A0 ⊗ A1) · CX · (B0 ⊗ B1)
-                // The interaction Ud(tx,0,0) decomposes as:
-                //   Rz(q0, -2tx) after CX, with phase adjustments in A/B.
-                push_1q_decomposed(&mut ops, &self.b0, 0);
-                push_1q_decomposed(&mut ops, &self.b1, 1);
-
-                // Absorb the interaction into the CX + Rz.
-                ops.push(TwoQubitGateOp::Cx);
-                if self.tx.abs() > EPSILON {
-                    ops.push(TwoQubitGateOp::Rz(0, -2.0 * self.tx));
-                }
+        let (layers, entanglers) = self.interaction_template();
+        let last = layers.len() - 1;
 
-                push_1q_decomposed(&mut ops, &self.a0, 0);
-                push_1q_decomposed(&mut ops, &self.a1, 1);
+        let mut ops = Vec::new();
+        for (i, (mut u0, mut u1)) in layers.into_iter().enumerate() {
+            if i == 0 {
+                u0 = u0.mul(&self.b0);
+                u1 = u1.mul(&self.b1);
             }
-            2 => {
-                // Two CNOTs.
-                push_1q_decomposed(&mut ops, &self.b0, 0);
-                push_1q_decomposed(&mut ops, &self.b1, 1);
-
-                ops.push(TwoQubitGateOp::Cx);
-                ops.push(TwoQubitGateOp::Ry(0, -2.0 * self.tx));
-                ops.push(TwoQubitGateOp::Rz(1, 2.0 * self.ty));
-                ops.push(TwoQubitGateOp::CxReverse);
-                ops.push(TwoQubitGateOp::Ry(0, 2.0 * self.tx));
-
-                push_1q_decomposed(&mut ops, &self.a0, 0);
-                push_1q_decomposed(&mut ops, &self.a1, 1);
+            if i == last {
+                u0 = self.a0.mul(&u0);
+                u1 = self.a1.mul(&u1);
             }
-            _ => {
-                // Three CNOTs: general case.
-                push_1q_decomposed(&mut ops, &self.b0, 0);
-                push_1q_decomposed(&mut ops, &self.b1, 1);
-
-                ops.push(TwoQubitGateOp::Cx);
-                ops.push(TwoQubitGateOp::Rz(0, 2.0 * self.tz));
-                ops.push(TwoQubitGateOp::Ry(1, 2.0 * self.ty));
-                ops.push(TwoQubitGateOp::CxReverse);
-                ops.push(TwoQubitGateOp::Ry(1, -2.0 * self.ty));
-                ops.push(TwoQubitGateOp::Cx);
-                ops.push(TwoQubitGateOp::Rz(0, -2.0 * self.tx));
-
-                push_1q_decomposed(&mut ops, &self.a0, 0);
-                push_1q_decomposed(&mut ops, &self.a1, 1);
+            push_1q_decomposed(&mut ops, &u0, 0);
+            push_1q_decomposed(&mut ops, &u1, 1);
+            if let Some(op) = entanglers.get(i) {
+                ops.push(op.clone());
             }
         }
-
         ops
     }
 
@@ -1286,6 +1011,165 @@ impl WeylDecomposition {
     pub fn cx_count(&self) -> u8 {
         self.num_cnots
     }
+
+    /// Single-qubit layers (first applied first) and the CNOTs between them
+    /// that implement Ud(tx, ty, tz) up to global phase.
+    fn interaction_template(&self) -> (Vec<(Unitary2x2, Unitary2x2)>, Vec<TwoQubitGateOp>) {
+        let id = Unitary2x2::identity;
+        match self.num_cnots {
+            0 => (vec![(id(), id())], vec![]),
+            // Ud(π/4, 0, 0) = (H ⊗ I) · CX · (Rz(-π/2) ⊗ Rx(-π/2)) · (H ⊗ I)
+            1 => (
+                vec![
+                    (
+                        Unitary2x2::rz(-FRAC_PI_2).mul(&Unitary2x2::h()),
+                        Unitary2x2::rx(-FRAC_PI_2),
+                    ),
+                    (Unitary2x2::h(), id()),
+                ],
+                vec![TwoQubitGateOp::Cx],
+            ),
+            // CX · (Rx(-2tx) ⊗ Rz(-2ty)) · CX = exp(i(tx·XX + ty·ZZ)), and
+            // Rx(π/2) on both qubits turns ZZ into YY.
+            2 => {
+                let w = Unitary2x2::rx(FRAC_PI_2);
+                let w_dag = w.dagger();
+                (
+                    vec![
+                        (w_dag, w_dag),
+                        (
+                            Unitary2x2::rx(-2.0 * self.tx),
+                            Unitary2x2::rz(-2.0 * self.ty),
+                        ),
+                        (w, w),
+                    ],
+                    vec![TwoQubitGateOp::Cx, TwoQubitGateOp::Cx],
+                )
+            }
+            _ => (
+                vec![
+                    (id(), Unitary2x2::rz(-FRAC_PI_2)),
+                    (
+                        Unitary2x2::rz(FRAC_PI_2 - 2.0 * self.tz),
+                        Unitary2x2::ry(2.0 * self.tx - FRAC_PI_2),
+                    ),
+                    (id(), Unitary2x2::ry(FRAC_PI_2 - 2.0 * self.ty)),
+                    (Unitary2x2::rz(FRAC_PI_2), id()),
+                ],
+                vec![
+                    TwoQubitGateOp::CxReverse,
+                    TwoQubitGateOp::Cx,
+                    TwoQubitGateOp::CxReverse,
+                ],
+            ),
+        }
+    }
+
+    /// Move (tx, ty, tz) into the Weyl chamber π/4 ≥ tx ≥ ty ≥ |tz|,
+    /// absorbing the local equivalences used into the local factors.
+    fn canonicalize(&mut self) {
+        for axis in 0..3 {
+            self.reduce(axis);
+        }
+        // Sort by magnitude, descending.
+        if self.coord(0).abs() < self.coord(1).abs() {
+            self.swap(0, 1);
+        }
+        if self.coord(1).abs() < self.coord(2).abs() {
+            self.swap(1, 2);
+        }
+        if self.coord(0).abs() < self.coord(1).abs() {
+            self.swap(0, 1);
+        }
+        // Make tx and ty non-negative by flipping signs in pairs.
+        if self.tx < 0.0 && self.ty < 0.0 {
+            self.flip(0, 1);
+        } else if self.tx < 0.0 {
+            self.flip(0, 2);
+        } else if self.ty < 0.0 {
+            self.flip(1, 2);
+        }
+    }
+
+    /// Minimum CNOT count for chamber coordinates: 0 for (0, 0, 0), 1 for
+    /// (π/4, 0, 0), 2 when tz = 0, 3 otherwise.
+    fn min_cnots(&self) -> u8 {
+        let zero = |v: f64| v.abs() < WEYL_TOLERANCE;
+        if zero(self.tx) && zero(self.ty) && zero(self.tz) {
+            0
+        } else if zero(self.tx - FRAC_PI_4) && zero(self.ty) && zero(self.tz) {
+            1
+        } else if zero(self.tz) {
+            2
+        } else {
+            3
+        }
+    }
+
+    fn coord(&self, axis: usize) -> f64 {
+        [self.tx, self.ty, self.tz][axis]
+    }
+
+    fn coord_mut(&mut self, axis: usize) -> &mut f64 {
+        match axis {
+            0 => &mut self.tx,
+            1 => &mut self.ty,
+            _ => &mut self.tz,
+        }
+    }
+
+    /// Shift a coordinate into [-π/4, π/4]:
+    /// Ud(.., t + π/2, ..) = Ud(.., t, ..) · i(P ⊗ P).
+    #[allow(clippy::cast_possible_truncation)]
+    fn reduce(&mut self, axis: usize) {
+        let turns = (self.coord(axis) / FRAC_PI_2).round();
+        if turns == 0.0 {
+            return;
+        }
+        *self.coord_mut(axis) -= turns * FRAC_PI_2;
+        if (turns as i64) % 2 != 0 {
+            let pauli = pauli(axis);
+            self.b0 = pauli.mul(&self.b0);
+            self.b1 = pauli.mul(&self.b1);
+        }
+    }
+
+    /// Negate two coordinates: conjugating by the third Pauli on qubit 0
+    /// flips the signs of the two Pauli products it anticommutes with.
+    fn flip(&mut self, i: usize, j: usize) {
+        let q = pauli(3 - i - j);
+        *self.coord_mut(i) = -self.coord(i);
+        *self.coord_mut(j) = -self.coord(j);
+        self.a0 = self.a0.mul(&q);
+        self.b0 = q.mul(&self.b0);
+    }
+
+    /// Exchange two coordinates: V ⊗ V with V·Pᵢ·V† = ±Pⱼ and V·Pⱼ·V† = ±Pᵢ
+    /// permutes the corresponding Pauli products.
+    fn swap(&mut self, i: usize, j: usize) {
+        let v = match i + j {
+            1 => Unitary2x2::s(),           // X ↔ Y
+            3 => Unitary2x2::rx(FRAC_PI_2), // Y ↔ Z
+            _ => Unitary2x2::ry(FRAC_PI_2), // X ↔ Z
+        };
+        let (ci, cj) = (self.coord(i), self.coord(j));
+        *self.coord_mut(i) = cj;
+        *self.coord_mut(j) = ci;
+        let v_dag = v.dagger();
+        self.a0 = self.a0.mul(&v_dag);
+        self.a1 = self.a1.mul(&v_dag);
+        self.b0 = v.mul(&self.b0);
+        self.b1 = v.mul(&self.b1);
+    }
+}
+
+/// Pauli X, Y or Z for Weyl axis 0, 1 or 2.
+fn pauli(axis: usize) -> Unitary2x2 {
+    match axis {
+        0 => Unitary2x2::x(),
+        1 => Unitary2x2::y(),
+        _ => Unitary2x2::z(),
+    }
 }
 
 /// Operations in a two-qubit decomposed circuit.
@@ -1457,7 +1341,7 @@ mod tests {
     /// Helper: verify KAK reconstruction matches original unitary.
     fn verify_kak_reconstruction(u: &Unitary4x4, label: &str) {
         let kak = u.kak_decompose();
-        let ud = canonical_unitary(kak.tx, kak.ty, kak.tz);
+        let ud = Unitary4x4::canonical(kak.tx, kak.ty, kak.tz);
         let left = Unitary4x4::kron(&kak.a0, &kak.a1);
         let right = Unitary4x4::kron(&kak.b0, &kak.b1);
         let reconstructed = left.mul(&ud).mul(&right);
@@ -1468,52 +1352,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_quartic_solver_known_roots() {
-        // (x-1)(x-2)(x-3)(x-4) = x⁴ - 10x³ + 35x² - 50x + 24
-        let roots = solve_quartic(
-            Complex64::new(-10.0, 0.0),
-            Complex64::new(35.0, 0.0),
-            Complex64::new(-50.0, 0.0),
-            Complex64::new(24.0, 0.0),
-        );
-        let mut real_roots: Vec<f64> = roots.iter().map(|r| r.re).collect();
-        real_roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        assert!(approx_eq(real_roots[0], 1.0), "root 0: {}", real_roots[0]);
-        assert!(approx_eq(real_roots[1], 2.0), "root 1: {}", real_roots[1]);
-        assert!(approx_eq(real_roots[2], 3.0), "root 2: {}", real_roots[2]);
-        assert!(approx_eq(real_roots[3], 4.0), "root 3: {}", real_roots[3]);
-    }
-
-    #[test]
-    fn test_quartic_solver_degenerate() {
-        // (x-1)⁴ = x⁴ - 4x³ + 6x² - 4x + 1
-        let roots = solve_quartic(
-            Complex64::new(-4.0, 0.0),
-            Complex64::new(6.0, 0.0),
-            Complex64::new(-4.0, 0.0),
-            Complex64::new(1.0, 0.0),
-        );
-        for (i, r) in roots.iter().enumerate() {
-            assert!(
-                (r.re - 1.0).abs() < 1e-3 && r.im.abs() < 1e-3,
-                "degenerate root {i}: {r:?}"
-            );
-        }
-    }
-
-    #[test]
-    fn test_eigenvalues_identity() {
-        let id = Unitary4x4::identity();
-        let eigs = eigenvalues_4x4(&id);
-        for (i, e) in eigs.iter().enumerate() {
-            assert!(
-                (e.re - 1.0).abs() < 1e-6 && e.im.abs() < 1e-6,
-                "identity eigenvalue {i}: {e:?}"
-            );
-        }
-    }
-
     #[test]
     fn test_4x4_identity() {
         let id = Unitary4x4::identity();
@@ -1593,7 +1431,7 @@ mod tests {
 
     #[test]
     fn test_canonical_unitary_identity() {
-        let ud = canonical_unitary(0.0, 0.0, 0.0);
+        let ud = Unitary4x4::canonical(0.0, 0.0, 0.0);
         let id = Unitary4x4::identity();
         assert!(ud.equiv(&id), "Ud(0,0,0) should be identity");
     }
@@ -1602,7 +1440,7 @@ mod tests {
     fn test_canonical_unitary_cx() {
         // CX ∼ exp(i·π/4·XX) up to local unitaries.
         // Ud(π/4, 0, 0) should have the right entanglement structure.
-        let ud = canonical_unitary(FRAC_PI_4, 0.0, 0.0);
+        let ud = Unitary4x4::canonical(FRAC_PI_4, 0.0, 0.0);
         // This should be equivalent to CX up to local unitaries,
         // meaning it's entangling but not identity.
         assert!(
@@ -1610,4 +1448,138 @@ mod tests {
             "Ud(π/4,0,0) should not be a product state"
         );
     }
+
+    /// Helper: unitary of a synthesized two-qubit circuit.
+    fn ops_unitary(ops: &[TwoQubitGateOp]) -> Unitary4x4 {
+        let swap = swap_unitary();
+        ops.iter().fold(Unitary4x4::identity(), |acc, op| {
+            let gate = match *op {
+                TwoQubitGateOp::Rz(0, a) => {
+                    Unitary4x4::kron(&Unitary2x2::rz(a), &Unitary2x2::identity())
+                }
+                TwoQubitGateOp::Rz(_, a) => {
+                    Unitary4x4::kron(&Unitary2x2::identity(), &Unitary2x2::rz(a))
+                }
+                TwoQubitGateOp::Ry(0, a) => {
+                    Unitary4x4::kron(&Unitary2x2::ry(a), &Unitary2x2::identity())
+                }
+                TwoQubitGateOp::Ry(_, a) => {
+                    Unitary4x4::kron(&Unitary2x2::identity(), &Unitary2x2::ry(a))
+                }
+                TwoQubitGateOp::Cx => cx_unitary(),
+                TwoQubitGateOp::CxReverse => swap.mul(&cx_unitary()).mul(&swap),
+            };
+            gate.mul(&acc)
+        })
+    }
+
+    /// Helper: a pseudo-random two-qubit unitary from layers of rotations
+    /// and CXs.
+    fn random_unitary(rng: &mut crate::rng::SeededRng) -> Unitary4x4 {
+        #[allow(clippy::cast_precision_loss)]
+        let mut angle = || (rng.next_u64() % 10_000) as f64 / 10_000.0 * 2.0 * PI;
+        let mut u = Unitary4x4::identity();
+        for _ in 0..3 {
+            let l0 = Unitary2x2::rz(angle()) * Unitary2x2::ry(angle()) * Unitary2x2::rz(angle());
+            let l1 = Unitary2x2::rz(angle()) * Unitary2x2::ry(angle()) * Unitary2x2::rz(angle());
+            u = cx_unitary().mul(&Unitary4x4::kron(&l0, &l1)).mul(&u);
+        }
+        u
+    }
+
+    fn named_unitaries() -> Vec<(&'static str, Unitary4x4, u8)> {
+        let z = Complex64::new(0.0, 0.0);
+        let o = Complex64::new(1.0, 0.0);
+        let i = Complex64::new(0.0, 1.0);
+        let iswap = Unitary4x4 {
+            data: [o, z, z, z, z, z, i, z, z, i, z, z, z, z, z, o],
+        };
+        let e = Complex64::from_polar(1.0, 0.7);
+        let cphase = Unitary4x4 {
+            data: [o, z, z, z, z, o, z, z, z, z, o, z, z, z, z, e],
+        };
+        vec![
+            ("identity", Unitary4x4::identity(), 0),
+            (
+                "H⊗X",
+                Unitary4x4::kron(&Unitary2x2::h(), &Unitary2x2::x()),
+                0,
+            ),
+            ("CX", cx_unitary(), 1),
+            ("CZ", cz_unitary(), 1),
+            ("CP(0.7)", cphase, 2),
+            ("iSWAP", iswap, 2),
+            ("SWAP", swap_unitary(), 3),
+            (
+                "Ud(0.5, 0.3, -0.2)",
+                Unitary4x4::canonical(0.5, 0.3, -0.2),
+                3,
+            ),
+            (
+                "Ud(1.9, -0.4, 2.6)",
+                Unitary4x4::canonical(1.9, -0.4, 2.6),
+                3,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_kak_reconstruction_entangling() {
+        let mut rng = crate::rng::SeededRng::new(7);
+        let mut cases = named_unitaries();
+        for _ in 0..50 {
+            cases.push(("random", random_unitary(&mut rng), 3));
+        }
+        for (label, u, _) in &cases {
+            verify_kak_reconstruction(u, label);
+
+            let kak = u.kak_decompose();
+            let eps = 1e-9;
+            assert!(
+                FRAC_PI_4 + eps >= kak.tx && kak.tx + eps >= kak.ty && kak.ty + eps >= kak.tz.abs(),
+                "{label}: ({}, {}, {}) outside the Weyl chamber",
+                kak.tx,
+                kak.ty,
+                kak.tz
+            );
+            let phased = kak
+                .reconstruct()
+                .scale(Complex64::from_polar(1.0, kak.global_phase));
+            for (a, b) in u.data.iter().zip(&phased.data) {
+                assert!((a - b).norm() < 1e-9, "{label}: global phase mismatch");
+            }
+        }
+    }
+
+    #[test]
+    fn test_kak_synthesis_uses_minimum_cnots() {
+        for (label, u, expected) in named_unitaries() {
+            let kak = u.kak_decompose();
+            assert_eq!(kak.num_cnots, expected, "{label}");
+
+            let ops = kak.to_circuit();
+            let cnots = ops
+                .iter()
+                .filter(|op| matches!(op, TwoQubitGateOp::Cx | TwoQubitGateOp::CxReverse))
+                .count();
+            assert_eq!(cnots, usize::from(expected), "{label}");
+            assert!(ops_unitary(&ops).equiv(&u), "{label} synthesis is wrong");
+        }
+    }
+
+    #[test]
+    fn test_kak_synthesis_random() {
+        let mut rng = crate::rng::SeededRng::new(11);
+        for _ in 0..100 {
+            let u = random_unitary(&mut rng);
+            let kak = u.kak_decompose();
+            let ops = kak.to_circuit();
+            let cnots = ops
+                .iter()
+                .filter(|op| matches!(op, TwoQubitGateOp::Cx | TwoQubitGateOp::CxReverse))
+                .count();
+            assert_eq!(cnots, usize::from(kak.num_cnots));
+            assert!(ops_unitary(&ops).equiv(&u));
+        }
+    }
 }
@@ -189,6 +189,20 @@ fn sandwich_3q(add_gate: impl FnOnce(&mut Circuit)) -> Circuit {
     c
 }
 
+fn sandwich_4q(add_gate: impl FnOnce(&mut Circuit)) -> Circuit {
+    let mut c = Circuit::with_size("t", 4, 0);
+    c.ry(0.3, QubitId(0)).unwrap();
+    c.rx(0.5, QubitId(1)).unwrap();
+    c.ry(0.9, QubitId(2)).unwrap();
+    c.rx(0.4, QubitId(3)).unwrap();
+    add_gate(&mut c);
+    c.ry(0.7, QubitId(0)).unwrap();
+    c.rx(1.1, QubitId(1)).unwrap();
+    c.ry(0.2, QubitId(2)).unwrap();
+    c.ry(0.6, QubitId(3)).unwrap();
+    c
+}
+
 const ALL_BASES: [BasisCase; 5] = [
     (BasisGates::ibm, "ibm"),
     (BasisGates::eagle, "eagle"),
@@ -278,6 +292,22 @@ fn test_decomposed_3q_gates_unitary_equivalent_all_bases() {
     }
 }
 
+#[test]
+fn test_decomposed_multi_controlled_gates_unitary_equivalent_all_bases() {
+    let controls = [QubitId(0), QubitId(1), QubitId(2)];
+    let t = QubitId(3);
+    for (basis, name) in ALL_BASES {
+        let circuit = sandwich_4q(move |c| c.mcx(&controls, t).map(|_| ()).unwrap());
+        assert_translation_preserves_semantics(&circuit, basis(), &format!("{name}/mcx"));
+
+        let circuit = sandwich_4q(move |c| c.mcz(&controls, t).map(|_| ()).unwrap());
+        assert_translation_preserves_semantics(&circuit, basis(), &format!("{name}/mcz"));
+
+        let circuit = sandwich_4q(move |c| c.mcp(THETA, &controls, t).map(|_| ()).unwrap());
+        assert_translation_preserves_semantics(&circuit, basis(), &format!("{name}/mcp"));
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Optimize1qGates
 // ---------------------------------------------------------------------------
@@ -0,0 +1,63 @@
+//! Admin endpoints for failure forensics.
+
+use std::sync::Arc;
+
+use arvak_hal::{DeadLetterQueue, FailureBundle};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// GET /api/admin/failures - List failure bundles, most recent first.
+pub async fn list_failures(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<FailureBundle>>, ApiError> {
+    let queue = dead_letter(&state)?;
+    queue
+        .list()
+        .map(Json)
+        .map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+/// GET /api/admin/failures/:id - Get the failure bundle of a job.
+pub async fn get_failure(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<FailureBundle>, ApiError> {
+    let queue = dead_letter(&state)?;
+    queue
+        .get(&id)
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("No failure bundle for job: {id}")))
+}
+
+/// DELETE /api/admin/failures/:id - Discard the failure bundle of a job.
+pub async fn delete_failure(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let queue = dead_letter(&state)?;
+    if !queue
+        .remove(&id)
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+    {
+        return Err(ApiError::NotFound(format!(
+            "No failure bundle for job: {id}"
+        )));
+    }
+    Ok(Json(serde_json::json!({
+        "deleted": true,
+        "id": id
+    })))
+}
+
+fn dead_letter(state: &AppState) -> Result<&DeadLetterQueue, ApiError> {
+    state
+        .dead_letter
+        .as_ref()
+        .ok_or_else(|| ApiError::Internal("No dead-letter queue configured".into()))
+}
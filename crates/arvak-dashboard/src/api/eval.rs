@@ -77,6 +77,9 @@ pub struct SchedulerView {
     pub fitness_score: f64,
     pub recommended_walltime: u64,
     pub batch_capacity: u32,
+    pub node_hours: f64,
+    pub energy_kwh: f64,
+    pub co2_kg: f64,
     pub assessment: String,
 }
 
@@ -170,6 +173,8 @@ pub struct InputView {
     pub total_ops: usize,
     pub depth: usize,
     pub content_hash: String,
+    pub workload: String,
+    pub clifford_fraction: f64,
 }
 
 /// Compilation summary.
@@ -237,6 +242,8 @@ pub async fn evaluate(
         total_ops: report.input.total_ops,
         depth: report.input.depth,
         content_hash: report.input.content_hash.clone(),
+        workload: report.input.structure.workload.to_string(),
+        clifford_fraction: report.input.structure.clifford_fraction,
     };
 
     let compile_time_us = eval_time.as_micros() as u64;
@@ -308,6 +315,9 @@ pub async fn evaluate(
         fitness_score: sched.fitness_score,
         recommended_walltime: sched.walltime.recommended_walltime,
         batch_capacity: sched.walltime.batch_capacity,
+        node_hours: sched.energy.node_hours,
+        energy_kwh: sched.energy.energy_kwh,
+        co2_kg: sched.energy.co2_kg,
         assessment: sched.assessment.clone(),
     });
 
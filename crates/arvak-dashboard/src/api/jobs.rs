@@ -2,6 +2,7 @@
 
 use std::sync::Arc;
 
+use arvak_hal::{JobPhase, Provenance};
 use arvak_sched::{
     CircuitSpec, JobFilter, Priority, ScheduledJob, ScheduledJobId, ScheduledJobStatus,
 };
@@ -9,6 +10,7 @@ use axum::{
     Json,
     extract::{Path, Query, State},
 };
+use tracing::debug;
 
 use crate::dto::{
     CreateJobRequest, HistogramBar, JobDetails, JobListParams, JobSummary, ResultHistogram,
@@ -72,7 +74,31 @@ pub async fn get_job(
         .map_err(|e| ApiError::Internal(e.to_string()))?
         .ok_or_else(|| ApiError::NotFound(format!("Job not found: {id}")))?;
 
-    Ok(Json(job_to_details(&job)))
+    let mut details = job_to_details(&job);
+    details.phase = backend_phase(&state, &job).await;
+    Ok(Json(details))
+}
+
+/// Ask the backend where a job's quantum job is, if it is on one.
+async fn backend_phase(state: &AppState, job: &ScheduledJob) -> Option<JobPhase> {
+    let quantum_job_id = match &job.status {
+        ScheduledJobStatus::QuantumSubmitted { quantum_job_id, .. }
+        | ScheduledJobStatus::QuantumRunning { quantum_job_id, .. } => quantum_job_id,
+        _ => return None,
+    };
+    let backend = state
+        .backends
+        .read()
+        .await
+        .get(job.matched_backend.as_ref()?)
+        .cloned()?;
+    match backend.phase(quantum_job_id).await {
+        Ok(phase) => phase,
+        Err(e) => {
+            debug!("Failed to get phase of job {}: {e}", job.id);
+            None
+        }
+    }
 }
 
 /// POST /api/jobs - Create a new job.
@@ -94,7 +120,8 @@ pub async fn create_job(
     // Create the job
     let mut job = ScheduledJob::new(&req.name, circuit)
         .with_shots(req.shots)
-        .with_priority(Priority::new(req.priority));
+        .with_priority(Priority::new(req.priority))
+        .with_provenance(Provenance::source(&req.qasm));
 
     // Set matched backend if specified
     if let Some(backend) = req.backend {
@@ -247,6 +274,7 @@ fn job_to_details(job: &ScheduledJob) -> JobDetails {
         name: job.name.clone(),
         status: job.status.name().to_string(),
         status_details,
+        phase: None,
         backend: job.matched_backend.clone(),
         shots: job.shots,
         priority: job.priority.value(),
@@ -260,6 +288,7 @@ fn job_to_details(job: &ScheduledJob) -> JobDetails {
             .iter()
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect(),
+        artifact_id: job.provenance.as_ref().map(|p| p.artifact_id.to_string()),
     }
 }
 
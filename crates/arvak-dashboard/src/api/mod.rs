@@ -1,8 +1,11 @@
 //! API endpoint handlers.
 
+pub mod admin;
 pub mod backends;
 pub mod circuits;
 pub mod eval;
 pub mod health;
 pub mod jobs;
+pub mod runs;
+pub mod telemetry;
 pub mod vqe;
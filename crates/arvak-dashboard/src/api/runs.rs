@@ -0,0 +1,53 @@
+//! Live run endpoints for iterative hybrid algorithms.
+
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::dto::{IterationRecord, PublishIterationRequest, RunDetails, RunSummary};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// GET /api/runs - List live runs, most recently updated first.
+pub async fn list_runs(State(state): State<Arc<AppState>>) -> Json<Vec<RunSummary>> {
+    Json(state.runs.list().await)
+}
+
+/// GET /api/runs/:id - Get a run with its iteration history.
+pub async fn get_run(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<RunDetails>, ApiError> {
+    state
+        .runs
+        .get(&id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Run not found: {id}")))
+}
+
+/// POST /api/runs/:id/iterations - Publish metrics for one iteration.
+pub async fn publish_iteration(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<PublishIterationRequest>,
+) -> Result<Json<IterationRecord>, ApiError> {
+    state.runs.publish(&id, req).await.map(Json)
+}
+
+/// DELETE /api/runs/:id - Remove a run.
+pub async fn delete_run(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if !state.runs.remove(&id).await {
+        return Err(ApiError::NotFound(format!("Run not found: {id}")));
+    }
+    Ok(Json(serde_json::json!({
+        "deleted": true,
+        "id": id
+    })))
+}
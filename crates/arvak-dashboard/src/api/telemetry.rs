@@ -0,0 +1,54 @@
+//! Cooling telemetry endpoints.
+
+use std::sync::Arc;
+
+use arvak_hal::TelemetrySample;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+
+use crate::dto::{PublishTelemetryRequest, TelemetryParams, TelemetrySeries, TelemetrySummary};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// GET /api/telemetry - Latest telemetry and active alerts per backend.
+pub async fn list_telemetry(State(state): State<Arc<AppState>>) -> Json<Vec<TelemetrySummary>> {
+    Json(state.telemetry.list().await)
+}
+
+/// GET /api/telemetry/:backend - Get a backend's telemetry series.
+pub async fn get_telemetry(
+    State(state): State<Arc<AppState>>,
+    Path(backend): Path<String>,
+    Query(params): Query<TelemetryParams>,
+) -> Result<Json<TelemetrySeries>, ApiError> {
+    state
+        .telemetry
+        .get(&backend, params.since)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("No telemetry for backend '{backend}'")))
+}
+
+/// POST /api/telemetry/:backend - Push a telemetry reading for a
+/// configured backend.
+pub async fn publish_telemetry(
+    State(state): State<Arc<AppState>>,
+    Path(backend): Path<String>,
+    Json(req): Json<PublishTelemetryRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if !state.backends.read().await.contains_key(&backend) {
+        return Err(ApiError::NotFound(format!("Backend '{backend}' not found")));
+    }
+    let sample = TelemetrySample {
+        timestamp: req.timestamp.unwrap_or_else(Utc::now),
+        metrics: req.metrics,
+    };
+    let recorded = state.telemetry.record(&backend, sample).await?;
+    Ok(Json(serde_json::json!({
+        "backend": backend,
+        "recorded": recorded
+    })))
+}
@@ -2,10 +2,13 @@
 //!
 //! These types bridge internal Arvak structures to JSON-serializable API responses.
 
-use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use arvak_ir::{Circuit, CircuitDag, Instruction, InstructionKind, QubitId};
+use arvak_hal::{JobPhase, TelemetrySample, TelemetryThreshold};
+use arvak_ir::{Circuit, CircuitDag, Instruction, InstructionKind};
 
 // ============================================================================
 // Circuit Visualization DTOs
@@ -268,6 +271,10 @@ pub struct JobDetails {
     pub status: String,
     /// Status details.
     pub status_details: Option<String>,
+    /// Where the quantum job is on its backend, e.g. `calibrating`, while
+    /// it runs there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<JobPhase>,
     /// Backend used.
     pub backend: Option<String>,
     /// Number of shots.
@@ -286,6 +293,9 @@ pub struct JobDetails {
     pub completed_at: Option<String>,
     /// Job metadata.
     pub metadata: std::collections::HashMap<String, String>,
+    /// Artifact id of the submitted circuit, for `arvak trace`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_id: Option<String>,
 }
 
 /// Query parameters for listing jobs.
@@ -342,6 +352,149 @@ pub struct ResultStatistics {
     pub most_frequent_count: u64,
 }
 
+// ============================================================================
+// Live Run DTOs
+// ============================================================================
+
+/// Metrics published by an iterative runner (VQE, QAOA, ...) for one
+/// optimizer iteration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PublishIterationRequest {
+    /// Iteration number (defaults to the run's next iteration).
+    pub iteration: Option<u64>,
+    /// Objective value (energy or cost) at this iteration.
+    pub energy: f64,
+    /// Gradient norm, for gradient-based optimizers.
+    pub gradient_norm: Option<f64>,
+    /// Current variational parameters.
+    #[serde(default)]
+    pub parameters: Vec<f64>,
+    /// Algorithm name (e.g., "vqe", "qaoa").
+    pub algorithm: Option<String>,
+}
+
+/// Publish message sent by a runner over the WebSocket.
+#[derive(Debug, Deserialize)]
+pub struct RunIterationMessage {
+    /// Run the metrics belong to.
+    pub run_id: String,
+    /// The iteration metrics.
+    #[serde(flatten)]
+    pub metrics: PublishIterationRequest,
+}
+
+/// A recorded iteration of a live run.
+#[derive(Debug, Clone, Serialize)]
+pub struct IterationRecord {
+    /// Iteration number.
+    pub iteration: u64,
+    /// Objective value (energy or cost).
+    pub energy: f64,
+    /// Gradient norm, if published.
+    pub gradient_norm: Option<f64>,
+    /// Variational parameters.
+    pub parameters: Vec<f64>,
+    /// Time the iteration was received (ISO 8601).
+    pub timestamp: String,
+}
+
+/// Live run summary for list view.
+#[derive(Debug, Serialize)]
+pub struct RunSummary {
+    /// Run ID.
+    pub id: String,
+    /// Algorithm name.
+    pub algorithm: Option<String>,
+    /// Number of iterations published.
+    pub iterations: u64,
+    /// Energy of the latest iteration.
+    pub latest_energy: f64,
+    /// Lowest energy seen so far.
+    pub best_energy: f64,
+    /// Time of the first iteration (ISO 8601).
+    pub created_at: String,
+    /// Time of the latest iteration (ISO 8601).
+    pub updated_at: String,
+}
+
+/// Live run with its iteration history.
+#[derive(Debug, Serialize)]
+pub struct RunDetails {
+    /// Run ID.
+    pub id: String,
+    /// Algorithm name.
+    pub algorithm: Option<String>,
+    /// Lowest energy seen so far.
+    pub best_energy: f64,
+    /// Time of the first iteration (ISO 8601).
+    pub created_at: String,
+    /// Time of the latest iteration (ISO 8601).
+    pub updated_at: String,
+    /// Retained iterations, oldest first.
+    pub iterations: Vec<IterationRecord>,
+}
+
+// ============================================================================
+// Telemetry DTOs
+// ============================================================================
+
+/// Cooling telemetry reading pushed by an external agent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PublishTelemetryRequest {
+    /// When the reading was taken (defaults to the time it is received).
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Metric name to value, e.g. `mixing_chamber_mk`.
+    pub metrics: BTreeMap<String, f64>,
+}
+
+/// Query parameters for a backend's telemetry series.
+#[derive(Debug, Deserialize, Default)]
+pub struct TelemetryParams {
+    /// Only return samples taken after this time.
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// A telemetry reading outside its configured threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryAlert {
+    /// Backend the reading belongs to.
+    pub backend: String,
+    /// Metric that crossed the threshold.
+    pub metric: String,
+    /// The offending reading.
+    pub value: f64,
+    /// The threshold crossed.
+    pub threshold: TelemetryThreshold,
+    /// Time of the first offending reading (ISO 8601).
+    pub raised_at: String,
+}
+
+/// Latest telemetry of a backend, for the fleet view.
+#[derive(Debug, Serialize)]
+pub struct TelemetrySummary {
+    /// Backend name.
+    pub backend: String,
+    /// Most recent reading.
+    pub latest: Option<TelemetrySample>,
+    /// Number of retained readings.
+    pub samples: usize,
+    /// Thresholds currently violated.
+    pub alerts: Vec<TelemetryAlert>,
+}
+
+/// Telemetry time series of a backend.
+#[derive(Debug, Serialize)]
+pub struct TelemetrySeries {
+    /// Backend name.
+    pub backend: String,
+    /// Retained readings, oldest first.
+    pub samples: Vec<TelemetrySample>,
+    /// Thresholds currently violated.
+    pub alerts: Vec<TelemetryAlert>,
+    /// All configured thresholds, for drawing limits.
+    pub thresholds: Vec<TelemetryThreshold>,
+}
+
 // ============================================================================
 // Conversion implementations
 // ============================================================================
@@ -368,40 +521,17 @@ impl CircuitVisualization {
 /// This groups operations by their depth (earliest time they can execute)
 /// for rendering as a circuit diagram.
 fn circuit_to_layers(dag: &CircuitDag) -> Vec<CircuitLayer> {
-    let mut layers: Vec<CircuitLayer> = vec![];
-    let mut qubit_depth: FxHashMap<QubitId, usize> = FxHashMap::default();
-
-    for (_node_idx, instruction) in dag.topological_ops() {
-        // Calculate which layer this operation belongs to
-        // (must be after all operations on its qubits)
-        let op_depth = instruction
-            .qubits
-            .iter()
-            .map(|q| qubit_depth.get(q).copied().unwrap_or(0))
-            .max()
-            .unwrap_or(0);
-
-        // Ensure we have enough layers
-        while layers.len() <= op_depth {
-            layers.push(CircuitLayer {
-                depth: layers.len(),
-                operations: vec![],
-            });
-        }
-
-        // Convert instruction to view
-        let op_view = instruction_to_view(instruction);
-
-        // Add to layer
-        layers[op_depth].operations.push(op_view);
-
-        // Update qubit depths for subsequent operations
-        for q in &instruction.qubits {
-            qubit_depth.insert(*q, op_depth + 1);
-        }
-    }
-
-    layers
+    dag.layers()
+        .into_iter()
+        .enumerate()
+        .map(|(depth, layer)| CircuitLayer {
+            depth,
+            operations: layer
+                .into_iter()
+                .map(|(_, instruction)| instruction_to_view(instruction))
+                .collect(),
+        })
+        .collect()
 }
 
 /// Convert an Instruction to an `OperationView` for visualization.
@@ -422,6 +552,17 @@ fn instruction_to_view(instruction: &Instruction) -> OperationView {
         InstructionKind::NoiseChannel { model, role } => {
             (format!("noise_{role}"), format!("N({})", model.name()))
         }
+        InstructionKind::IfElse { condition, .. } => {
+            ("if_else".to_string(), format!("if(={})", condition.value))
+        }
+        InstructionKind::WhileLoop {
+            condition,
+            max_iterations,
+            ..
+        } => (
+            "while_loop".to_string(),
+            format!("while(={})×{max_iterations}", condition.value),
+        ),
     };
 
     OperationView {
@@ -442,14 +583,15 @@ fn format_gate_label(gate: &arvak_ir::Gate) -> String {
     match &gate.kind {
         GateKind::Standard(std_gate) => format_standard_gate_label(std_gate),
         GateKind::Custom(custom) => custom.name.clone(),
+        GateKind::Modified(modified) => modified.name().to_string(),
     }
 }
 
 /// Format a `StandardGate` label with parameters.
 fn format_standard_gate_label(gate: &arvak_ir::StandardGate) -> String {
     use arvak_ir::StandardGate::{
-        CCX, CH, CP, CRx, CRy, CRz, CSwap, CX, CY, CZ, ECR, H, I, ISwap, P, PRX, RXX, RYY, RZZ, Rx,
-        Ry, Rz, S, SX, SXdg, Sdg, Swap, T, Tdg, U, X, Y, Z,
+        CCX, CH, CP, CRx, CRy, CRz, CSwap, CX, CY, CZ, ECR, H, I, ISwap, MCP, MCX, MCZ, P, PRX,
+        RXX, RYY, RZZ, Rx, Ry, Rz, S, SX, SXdg, Sdg, Swap, T, Tdg, U, X, Y, Z,
     };
 
     match gate {
@@ -473,6 +615,8 @@ fn format_standard_gate_label(gate: &arvak_ir::StandardGate) -> String {
         ISwap => "iSWAP".to_string(),
         CCX => "CCX".to_string(),
         CSwap => "CSWAP".to_string(),
+        MCX(n) => format!("C{n}X"),
+        MCZ(n) => format!("C{n}Z"),
 
         // Parameterized gates
         Rx(p) => format!("RX({})", format_param(p)),
@@ -492,6 +636,7 @@ fn format_standard_gate_label(gate: &arvak_ir::StandardGate) -> String {
         RXX(p) => format!("RXX({})", format_param(p)),
         RYY(p) => format!("RYY({})", format_param(p)),
         RZZ(p) => format!("RZZ({})", format_param(p)),
+        MCP(n, p) => format!("C{n}P({})", format_param(p)),
         PRX(t, p) => format!("PRX({},{})", format_param(t), format_param(p)),
         ECR => "ECR".to_string(),
     }
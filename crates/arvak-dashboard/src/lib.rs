@@ -7,6 +7,8 @@
 //! - Monitor backend status and capabilities
 //! - Track job execution (Phase 2)
 //! - Analyze execution results (Phase 3)
+//! - Stream per-iteration metrics of live VQE/QAOA runs over WebSocket
+//! - Plot cooling telemetry of the fleet, with threshold alerts
 //!
 //! # Quick Start
 //!
@@ -29,14 +31,20 @@ pub mod api;
 pub mod dto;
 pub mod error;
 pub mod processor;
+pub mod runs;
 pub mod server;
 pub mod state;
+pub mod telemetry;
 pub mod ws;
 
 pub use dto::{
     BackendDetails, BackendSummary, CircuitVisualization, CompilationStats, CompileRequest,
-    CompileResponse, HealthResponse, VisualizeRequest,
+    CompileResponse, HealthResponse, IterationRecord, PublishIterationRequest,
+    PublishTelemetryRequest, RunDetails, RunSummary, TelemetryAlert, TelemetrySeries,
+    TelemetrySummary, VisualizeRequest,
 };
 pub use error::ApiError;
+pub use runs::RunRegistry;
 pub use server::create_router;
 pub use state::{AppState, DashboardConfig};
+pub use telemetry::TelemetryRegistry;
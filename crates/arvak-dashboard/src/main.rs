@@ -24,6 +24,21 @@ async fn main() -> anyhow::Result<()> {
             .parse()
             .map_err(|e| anyhow::anyhow!("Invalid ARVAK_BIND address '{bind}': {e}"))?;
     }
+    if let Ok(secs) = std::env::var("ARVAK_TELEMETRY_INTERVAL") {
+        let secs: u64 = secs
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid ARVAK_TELEMETRY_INTERVAL '{secs}': {e}"))?;
+        anyhow::ensure!(secs > 0, "ARVAK_TELEMETRY_INTERVAL must be positive");
+        config.telemetry_interval = std::time::Duration::from_secs(secs);
+    }
+    if let Ok(thresholds) = std::env::var("ARVAK_TELEMETRY_THRESHOLDS") {
+        config.telemetry_thresholds = thresholds
+            .split(',')
+            .filter(|t| !t.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow::anyhow!("Invalid ARVAK_TELEMETRY_THRESHOLDS: {e}"))?;
+    }
     let bind_addr = config.bind_address;
 
     // Create job store (in-memory SQLite)
@@ -32,8 +47,17 @@ async fn main() -> anyhow::Result<()> {
     );
     tracing::info!("Initialized in-memory job store");
 
+    // Failed jobs are filed in the scheduler's dead-letter queue
+    let dead_letter =
+        arvak_hal::DeadLetterQueue::new(arvak_sched::SchedulerConfig::default().dead_letter_dir());
+    tracing::info!("Dead-letter queue at {}", dead_letter.dir().display());
+
     // Create application state
-    let state = Arc::new(AppState::with_config(config).with_store(store));
+    let state = Arc::new(
+        AppState::with_config(config)
+            .with_store(store)
+            .with_dead_letter(dead_letter),
+    );
 
     // Optionally register the simulator backend if the feature is enabled
     #[cfg(feature = "with-simulator")]
@@ -44,12 +68,33 @@ async fn main() -> anyhow::Result<()> {
         tracing::info!("Registered simulator backend");
     }
 
+    // Register the backends of a configuration document, if one is given
+    if let Ok(path) = std::env::var("ARVAK_BACKENDS_CONFIG") {
+        #[allow(unused_mut)]
+        let mut registry = arvak_hal::BackendRegistry::new();
+        #[cfg(feature = "with-simulator")]
+        registry.register::<arvak_adapter_sim::SimulatorBackend>("simulator");
+        let registry = registry.with_config(&path)?;
+        for name in registry.instance_names() {
+            if let Some(backend) = registry.instance(&name) {
+                tracing::info!("Registered configured backend '{name}'");
+                state.register_backend_as(name, backend).await;
+            }
+        }
+    }
+
     // Start background job processor
     let processor_state = state.clone();
     tokio::spawn(async move {
         arvak_dashboard::processor::run_job_processor(processor_state).await;
     });
 
+    // Start background telemetry poller
+    let telemetry_state = state.clone();
+    tokio::spawn(async move {
+        arvak_dashboard::telemetry::run_telemetry_poller(telemetry_state).await;
+    });
+
     // Create the router
     let app = create_router(state);
 
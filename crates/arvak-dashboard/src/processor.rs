@@ -3,11 +3,15 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use arvak_sched::{JobFilter, ScheduledJobStatus};
+use arvak_hal::{Backend, FailureBundle, HeartbeatSender};
+use arvak_sched::{JobFilter, ScheduledJob, ScheduledJobStatus};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
 use tokio::time;
 use tracing::{error, info, warn};
 
 use crate::state::AppState;
+use crate::ws::events::DashboardEvent;
 
 /// Run the background job processor loop.
 ///
@@ -91,11 +95,13 @@ pub async fn run_job_processor(state: Arc<AppState>) {
                 Ok(id) => id,
                 Err(e) => {
                     error!("Failed to submit job {} to backend: {}", job_id, e);
+                    let reason = format!("Backend submit error: {e}");
+                    record_failure(&state, &job, backend.as_ref(), &reason, None, &e);
                     let _ = store
                         .update_status(
                             &job_id,
                             ScheduledJobStatus::Failed {
-                                reason: format!("Backend submit error: {e}"),
+                                reason,
                                 slurm_job_id: None,
                                 quantum_job_id: None,
                             },
@@ -117,9 +123,20 @@ pub async fn run_job_processor(state: Arc<AppState>) {
                 )
                 .await;
 
-            // Retrieve result
-            match backend.result(&quantum_job_id).await {
-                Ok(result) => {
+            // Wait for the result, forwarding heartbeats to WebSocket clients
+            let heartbeats = HeartbeatSender::new();
+            let forward = forward_heartbeats(&heartbeats, job_id.to_string(), state.runs.sender());
+            let outcome = backend
+                .wait_with_heartbeats(&quantum_job_id, &heartbeats)
+                .await;
+            drop(heartbeats);
+            let _ = forward.await;
+
+            match outcome {
+                Ok(mut result) => {
+                    if let Some(provenance) = job.submission_provenance(backend.name()) {
+                        provenance.attach(&mut result);
+                    }
                     if let Err(e) = store.save_result(&job_id, &result).await {
                         error!("Failed to save result for job {}: {}", job_id, e);
                     }
@@ -138,11 +155,20 @@ pub async fn run_job_processor(state: Arc<AppState>) {
                 }
                 Err(e) => {
                     error!("Failed to get result for job {}: {}", job_id, e);
+                    let reason = format!("Backend result error: {e}");
+                    record_failure(
+                        &state,
+                        &job,
+                        backend.as_ref(),
+                        &reason,
+                        Some(&quantum_job_id.0),
+                        &e,
+                    );
                     let _ = store
                         .update_status(
                             &job_id,
                             ScheduledJobStatus::Failed {
-                                reason: format!("Backend result error: {e}"),
+                                reason,
                                 slurm_job_id: Some(slurm_job_id),
                                 quantum_job_id: Some(quantum_job_id),
                             },
@@ -153,3 +179,61 @@ pub async fn run_job_processor(state: Arc<AppState>) {
         }
     }
 }
+
+/// Publish the heartbeats sent on `heartbeats` as events of `job_id`
+/// until the sender is dropped.
+fn forward_heartbeats(
+    heartbeats: &HeartbeatSender,
+    job_id: String,
+    events: broadcast::Sender<DashboardEvent>,
+) -> JoinHandle<()> {
+    let mut received = heartbeats.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match received.recv().await {
+                Ok(heartbeat) => {
+                    let _ = events.send(DashboardEvent::JobHeartbeat {
+                        job_id: job_id.clone(),
+                        heartbeat,
+                    });
+                }
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// File a forensics bundle for a job that failed on `backend`, if the
+/// state has a dead-letter queue.
+fn record_failure(
+    state: &AppState,
+    job: &ScheduledJob,
+    backend: &dyn Backend,
+    reason: &str,
+    provider_job_id: Option<&str>,
+    error: &arvak_hal::HalError,
+) {
+    let Some(queue) = &state.dead_letter else {
+        return;
+    };
+
+    let mut bundle = FailureBundle::new(job.id.to_string(), reason)
+        .with_name(&job.name)
+        .with_backend(backend.name())
+        .with_target(backend.capabilities().clone())
+        .with_provider_error(format!("{error:?}"))
+        .with_trace([format!("{} created", job.created_at.to_rfc3339())]);
+    if let Some(id) = provider_job_id {
+        bundle = bundle.with_provider_job_id(id);
+    }
+    for spec in &job.circuits {
+        if let Ok(qasm) = spec.source() {
+            bundle = bundle.with_circuit(qasm);
+        }
+    }
+
+    if let Err(e) = queue.record(&bundle) {
+        warn!("Failed to record failure bundle for job {}: {}", job.id, e);
+    }
+}
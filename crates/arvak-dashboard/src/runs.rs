@@ -0,0 +1,216 @@
+//! Live run registry for iterative hybrid algorithms.
+//!
+//! Iterative runners (VQE, QAOA, ...) publish per-iteration metrics to the
+//! dashboard, either with `POST /api/runs/{id}/iterations` or as messages
+//! on the `/api/ws` WebSocket. The registry keeps a bounded history per run,
+//! so clients that connect late can load the full convergence curve, and
+//! broadcasts every iteration to connected WebSocket clients.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
+use tokio::sync::{RwLock, broadcast};
+
+use crate::dto::{IterationRecord, PublishIterationRequest, RunDetails, RunSummary};
+use crate::error::ApiError;
+use crate::ws::events::DashboardEvent;
+
+/// Maximum number of runs kept; the least recently updated run is evicted.
+pub const MAX_RUNS: usize = 64;
+
+/// Maximum iterations kept per run; the oldest iterations are dropped.
+pub const MAX_ITERATIONS_PER_RUN: usize = 10_000;
+
+/// Maximum number of parameters per published iteration.
+pub const MAX_PARAMETERS: usize = 4096;
+
+/// Maximum run ID length.
+const MAX_RUN_ID_LEN: usize = 64;
+
+/// Capacity of the event broadcast channel.
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+struct Run {
+    algorithm: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// Registry-wide update sequence number, for recency ordering.
+    sequence: u64,
+    total_iterations: u64,
+    best_energy: f64,
+    iterations: VecDeque<IterationRecord>,
+}
+
+/// Registry of live runs and the event channel that streams them.
+pub struct RunRegistry {
+    runs: RwLock<FxHashMap<String, Run>>,
+    events: broadcast::Sender<DashboardEvent>,
+    sequence: AtomicU64,
+}
+
+impl RunRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            runs: RwLock::new(FxHashMap::default()),
+            events,
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    /// Subscribe to dashboard events.
+    pub fn subscribe(&self) -> broadcast::Receiver<DashboardEvent> {
+        self.events.subscribe()
+    }
+
+    /// The sender of the dashboard event channel, for other publishers.
+    pub(crate) fn sender(&self) -> broadcast::Sender<DashboardEvent> {
+        self.events.clone()
+    }
+
+    /// Record one iteration of a run, creating the run on its first
+    /// iteration, and broadcast it to subscribers.
+    pub async fn publish(
+        &self,
+        run_id: &str,
+        metrics: PublishIterationRequest,
+    ) -> Result<IterationRecord, ApiError> {
+        validate_run_id(run_id)?;
+        if !metrics.energy.is_finite() {
+            return Err(ApiError::BadRequest("energy must be finite".to_string()));
+        }
+        if metrics.gradient_norm.is_some_and(|g| !g.is_finite()) {
+            return Err(ApiError::BadRequest(
+                "gradient_norm must be finite".to_string(),
+            ));
+        }
+        if metrics.parameters.len() > MAX_PARAMETERS {
+            return Err(ApiError::BadRequest(format!(
+                "Too many parameters: {} (max {MAX_PARAMETERS})",
+                metrics.parameters.len()
+            )));
+        }
+
+        let now = Utc::now();
+        let mut runs = self.runs.write().await;
+        if !runs.contains_key(run_id) && runs.len() >= MAX_RUNS {
+            let oldest = runs
+                .iter()
+                .min_by_key(|(_, run)| run.sequence)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                runs.remove(&oldest);
+                let _ = self
+                    .events
+                    .send(DashboardEvent::RunRemoved { run_id: oldest });
+            }
+        }
+        let run = runs.entry(run_id.to_string()).or_insert_with(|| Run {
+            algorithm: None,
+            created_at: now,
+            updated_at: now,
+            sequence: 0,
+            total_iterations: 0,
+            best_energy: f64::INFINITY,
+            iterations: VecDeque::new(),
+        });
+
+        if metrics.algorithm.is_some() {
+            run.algorithm = metrics.algorithm;
+        }
+        let record = IterationRecord {
+            iteration: metrics.iteration.unwrap_or(run.total_iterations),
+            energy: metrics.energy,
+            gradient_norm: metrics.gradient_norm,
+            parameters: metrics.parameters,
+            timestamp: now.to_rfc3339(),
+        };
+        run.updated_at = now;
+        run.sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        run.total_iterations += 1;
+        run.best_energy = run.best_energy.min(record.energy);
+        if run.iterations.len() >= MAX_ITERATIONS_PER_RUN {
+            run.iterations.pop_front();
+        }
+        run.iterations.push_back(record.clone());
+        drop(runs);
+
+        // Sending only fails when nobody is subscribed.
+        let _ = self.events.send(DashboardEvent::RunIteration {
+            run_id: run_id.to_string(),
+            record: record.clone(),
+        });
+        Ok(record)
+    }
+
+    /// List runs, most recently updated first.
+    pub async fn list(&self) -> Vec<RunSummary> {
+        let runs = self.runs.read().await;
+        let mut summaries: Vec<(u64, RunSummary)> = runs
+            .iter()
+            .map(|(id, run)| {
+                let summary = RunSummary {
+                    id: id.clone(),
+                    algorithm: run.algorithm.clone(),
+                    iterations: run.total_iterations,
+                    latest_energy: run.iterations.back().map_or(f64::NAN, |r| r.energy),
+                    best_energy: run.best_energy,
+                    created_at: run.created_at.to_rfc3339(),
+                    updated_at: run.updated_at.to_rfc3339(),
+                };
+                (run.sequence, summary)
+            })
+            .collect();
+        summaries.sort_by(|a, b| b.0.cmp(&a.0));
+        summaries.into_iter().map(|(_, summary)| summary).collect()
+    }
+
+    /// Get a run with its retained iteration history.
+    pub async fn get(&self, run_id: &str) -> Option<RunDetails> {
+        let runs = self.runs.read().await;
+        runs.get(run_id).map(|run| RunDetails {
+            id: run_id.to_string(),
+            algorithm: run.algorithm.clone(),
+            best_energy: run.best_energy,
+            created_at: run.created_at.to_rfc3339(),
+            updated_at: run.updated_at.to_rfc3339(),
+            iterations: run.iterations.iter().cloned().collect(),
+        })
+    }
+
+    /// Remove a run. Returns `false` if it did not exist.
+    pub async fn remove(&self, run_id: &str) -> bool {
+        let removed = self.runs.write().await.remove(run_id).is_some();
+        if removed {
+            let _ = self.events.send(DashboardEvent::RunRemoved {
+                run_id: run_id.to_string(),
+            });
+        }
+        removed
+    }
+}
+
+impl Default for RunRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run IDs are chosen by the runner, e.g. a SLURM job ID or a UUID.
+fn validate_run_id(run_id: &str) -> Result<(), ApiError> {
+    let valid = !run_id.is_empty()
+        && run_id.len() <= MAX_RUN_ID_LEN
+        && run_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "Invalid run ID '{run_id}': use 1-{MAX_RUN_ID_LEN} characters from [A-Za-z0-9._-]"
+        )))
+    }
+}
@@ -16,6 +16,7 @@ use tower_http::{
 
 use crate::api;
 use crate::state::AppState;
+use crate::ws;
 
 // Embed static files at compile time
 const INDEX_HTML: &str = include_str!("../static/index.html");
@@ -42,6 +43,26 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         )
         .route("/jobs/{id}/result", get(api::jobs::get_job_result))
         .route("/vqe/demo", get(api::vqe::vqe_demo))
+        // Live run routes
+        .route("/runs", get(api::runs::list_runs))
+        .route(
+            "/runs/{id}",
+            get(api::runs::get_run).delete(api::runs::delete_run),
+        )
+        .route("/runs/{id}/iterations", post(api::runs::publish_iteration))
+        // Cooling telemetry routes
+        .route("/telemetry", get(api::telemetry::list_telemetry))
+        .route(
+            "/telemetry/{backend}",
+            get(api::telemetry::get_telemetry).post(api::telemetry::publish_telemetry),
+        )
+        // Admin routes
+        .route("/admin/failures", get(api::admin::list_failures))
+        .route(
+            "/admin/failures/{id}",
+            get(api::admin::get_failure).delete(api::admin::delete_failure),
+        )
+        .route("/ws", get(ws::ws_handler))
         // Evaluator route
         .route("/eval", post(api::eval::evaluate));
 
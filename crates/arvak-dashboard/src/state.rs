@@ -2,12 +2,16 @@
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use arvak_hal::Backend;
+use arvak_hal::{Backend, DeadLetterQueue, TelemetryThreshold};
 use arvak_sched::StateStore;
 use rustc_hash::FxHashMap;
 use tokio::sync::RwLock;
 
+use crate::runs::RunRegistry;
+use crate::telemetry::TelemetryRegistry;
+
 /// Dashboard configuration.
 #[derive(Debug, Clone)]
 pub struct DashboardConfig {
@@ -17,6 +21,10 @@ pub struct DashboardConfig {
     pub default_backend: Option<String>,
     /// Maximum qubits for circuit visualization (performance limit).
     pub max_circuit_qubits: usize,
+    /// How often backends are polled for cooling telemetry.
+    pub telemetry_interval: Duration,
+    /// Telemetry thresholds that raise alerts.
+    pub telemetry_thresholds: Vec<TelemetryThreshold>,
 }
 
 impl Default for DashboardConfig {
@@ -25,6 +33,8 @@ impl Default for DashboardConfig {
             bind_address: ([127, 0, 0, 1], 3000).into(),
             default_backend: None,
             max_circuit_qubits: 50,
+            telemetry_interval: Duration::from_secs(10),
+            telemetry_thresholds: Vec::new(),
         }
     }
 }
@@ -37,24 +47,31 @@ pub struct AppState {
     pub config: DashboardConfig,
     /// Job store for persistence (optional).
     pub store: Option<Arc<dyn StateStore>>,
+    /// Live runs published by iterative algorithms.
+    pub runs: Arc<RunRegistry>,
+    /// Cooling telemetry of the backends.
+    pub telemetry: Arc<TelemetryRegistry>,
+    /// Forensics bundles of failed jobs (optional).
+    pub dead_letter: Option<DeadLetterQueue>,
 }
 
 impl AppState {
     /// Create a new application state with default configuration.
     pub fn new() -> Self {
-        Self {
-            backends: Arc::new(RwLock::new(FxHashMap::default())),
-            config: DashboardConfig::default(),
-            store: None,
-        }
+        Self::with_config(DashboardConfig::default())
     }
 
     /// Create application state with custom configuration.
     pub fn with_config(config: DashboardConfig) -> Self {
+        let runs = RunRegistry::new();
+        let telemetry = TelemetryRegistry::new(config.telemetry_thresholds.clone(), runs.sender());
         Self {
             backends: Arc::new(RwLock::new(FxHashMap::default())),
             config,
             store: None,
+            runs: Arc::new(runs),
+            telemetry: Arc::new(telemetry),
+            dead_letter: None,
         }
     }
 
@@ -64,9 +81,20 @@ impl AppState {
         self
     }
 
-    /// Register a backend.
+    /// Set the dead-letter queue failed jobs are recorded in.
+    pub fn with_dead_letter(mut self, queue: DeadLetterQueue) -> Self {
+        self.dead_letter = Some(queue);
+        self
+    }
+
+    /// Register a backend under its own name.
     pub async fn register_backend(&self, backend: Arc<dyn Backend>) {
         let name = backend.name().to_string();
+        self.register_backend_as(name, backend).await;
+    }
+
+    /// Register a backend under a configured name.
+    pub async fn register_backend_as(&self, name: String, backend: Arc<dyn Backend>) {
         let mut backends = self.backends.write().await;
         backends.insert(name, backend);
     }
@@ -0,0 +1,209 @@
+//! Cooling telemetry time series and threshold alerts.
+//!
+//! Samples reach the dashboard two ways: the background poller asks every
+//! registered backend for [`Backend::telemetry`](arvak_hal::Backend::telemetry),
+//! and external agents with access to the cryostat push readings with
+//! `POST /api/telemetry/{backend}`. The registry keeps a bounded series per
+//! backend, checks each reading against the configured thresholds, and
+//! broadcasts samples and alert changes to WebSocket clients.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use arvak_hal::{TelemetrySample, TelemetryThreshold};
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
+use tokio::sync::{RwLock, broadcast};
+use tokio::time;
+use tracing::{debug, warn};
+
+use crate::dto::{TelemetryAlert, TelemetrySeries, TelemetrySummary};
+use crate::error::ApiError;
+use crate::state::AppState;
+use crate::ws::events::DashboardEvent;
+
+/// Maximum samples kept per backend (a day at the default poll interval);
+/// the oldest samples are dropped.
+pub const MAX_SAMPLES_PER_BACKEND: usize = 8640;
+
+/// Maximum number of metrics per sample.
+pub const MAX_METRICS: usize = 64;
+
+#[derive(Default)]
+struct Series {
+    samples: VecDeque<TelemetrySample>,
+    /// Active alerts, at most one per threshold.
+    alerts: Vec<(usize, TelemetryAlert)>,
+}
+
+/// Registry of per-backend telemetry series.
+pub struct TelemetryRegistry {
+    series: RwLock<FxHashMap<String, Series>>,
+    thresholds: Vec<TelemetryThreshold>,
+    events: broadcast::Sender<DashboardEvent>,
+}
+
+impl TelemetryRegistry {
+    /// Create an empty registry that checks `thresholds` and broadcasts on
+    /// `events`.
+    pub fn new(
+        thresholds: Vec<TelemetryThreshold>,
+        events: broadcast::Sender<DashboardEvent>,
+    ) -> Self {
+        Self {
+            series: RwLock::new(FxHashMap::default()),
+            thresholds,
+            events,
+        }
+    }
+
+    /// The configured thresholds.
+    pub fn thresholds(&self) -> &[TelemetryThreshold] {
+        &self.thresholds
+    }
+
+    /// Record a sample of `backend`, raise or clear alerts, and broadcast
+    /// it.
+    ///
+    /// Returns `false` without recording when the sample is not newer than
+    /// the latest one, e.g. a backend returning the same cached reading.
+    pub async fn record(&self, backend: &str, sample: TelemetrySample) -> Result<bool, ApiError> {
+        if sample.metrics.len() > MAX_METRICS {
+            return Err(ApiError::BadRequest(format!(
+                "Too many metrics: {} (max {MAX_METRICS})",
+                sample.metrics.len()
+            )));
+        }
+        if let Some((name, _)) = sample.metrics.iter().find(|(_, v)| !v.is_finite()) {
+            return Err(ApiError::BadRequest(format!(
+                "Metric '{name}' must be finite"
+            )));
+        }
+
+        let mut all = self.series.write().await;
+        let series = all.entry(backend.to_string()).or_default();
+        if series
+            .samples
+            .back()
+            .is_some_and(|latest| latest.timestamp >= sample.timestamp)
+        {
+            return Ok(false);
+        }
+
+        let mut events = Vec::new();
+        for (index, threshold) in self.thresholds.iter().enumerate() {
+            let active = series.alerts.iter().position(|(i, _)| *i == index);
+            match (threshold.violation(&sample), active) {
+                (Some(value), None) => {
+                    let alert = TelemetryAlert {
+                        backend: backend.to_string(),
+                        metric: threshold.metric.clone(),
+                        value,
+                        threshold: threshold.clone(),
+                        raised_at: sample.timestamp.to_rfc3339(),
+                    };
+                    warn!("Telemetry alert on '{backend}': {} = {value}", threshold);
+                    events.push(DashboardEvent::TelemetryAlertRaised {
+                        alert: alert.clone(),
+                    });
+                    series.alerts.push((index, alert));
+                }
+                (None, Some(position)) if sample.get(&threshold.metric).is_some() => {
+                    let (_, alert) = series.alerts.remove(position);
+                    events.push(DashboardEvent::TelemetryAlertCleared {
+                        backend: backend.to_string(),
+                        metric: alert.metric,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if series.samples.len() >= MAX_SAMPLES_PER_BACKEND {
+            series.samples.pop_front();
+        }
+        series.samples.push_back(sample.clone());
+        drop(all);
+
+        // Sending only fails when nobody is subscribed.
+        let _ = self.events.send(DashboardEvent::Telemetry {
+            backend: backend.to_string(),
+            sample,
+        });
+        for event in events {
+            let _ = self.events.send(event);
+        }
+        Ok(true)
+    }
+
+    /// Latest reading and active alerts of every backend with telemetry,
+    /// by name.
+    pub async fn list(&self) -> Vec<TelemetrySummary> {
+        let all = self.series.read().await;
+        let mut summaries: Vec<TelemetrySummary> = all
+            .iter()
+            .map(|(backend, series)| TelemetrySummary {
+                backend: backend.clone(),
+                latest: series.samples.back().cloned(),
+                samples: series.samples.len(),
+                alerts: series.alerts.iter().map(|(_, a)| a.clone()).collect(),
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.backend.cmp(&b.backend));
+        summaries
+    }
+
+    /// The retained series of `backend`, optionally only samples taken
+    /// after `since`.
+    pub async fn get(
+        &self,
+        backend: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Option<TelemetrySeries> {
+        let all = self.series.read().await;
+        all.get(backend).map(|series| TelemetrySeries {
+            backend: backend.to_string(),
+            samples: series
+                .samples
+                .iter()
+                .filter(|s| since.is_none_or(|since| s.timestamp > since))
+                .cloned()
+                .collect(),
+            alerts: series.alerts.iter().map(|(_, a)| a.clone()).collect(),
+            thresholds: self.thresholds.clone(),
+        })
+    }
+}
+
+/// Poll every registered backend for telemetry at the configured interval.
+///
+/// Backends without telemetry return `None` and are skipped; errors are
+/// logged and retried on the next tick.
+// TODO: Accept a CancellationToken for graceful shutdown
+pub async fn run_telemetry_poller(state: Arc<AppState>) {
+    let mut interval = time::interval(state.config.telemetry_interval);
+
+    loop {
+        interval.tick().await;
+
+        let backends: Vec<_> = state
+            .backends
+            .read()
+            .await
+            .iter()
+            .map(|(name, backend)| (name.clone(), Arc::clone(backend)))
+            .collect();
+
+        for (name, backend) in backends {
+            match backend.telemetry().await {
+                Ok(Some(sample)) => {
+                    if let Err(e) = state.telemetry.record(&name, sample).await {
+                        warn!("Rejected telemetry from backend '{name}': {e}");
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => debug!("Failed to poll telemetry of backend '{name}': {e}"),
+            }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! WebSocket event types for real-time updates.
-//!
-//! Placeholder for Phase 3 implementation.
 
 use serde::Serialize;
 
+use arvak_hal::{Heartbeat, TelemetrySample};
+
+use crate::dto::{IterationRecord, TelemetryAlert};
+
 /// Events sent to WebSocket clients.
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -12,6 +14,31 @@ pub enum DashboardEvent {
     JobStatusChanged { job_id: String, status: String },
     /// Job completed with results.
     JobCompleted { job_id: String },
+    /// A running job sent a heartbeat from its backend.
+    JobHeartbeat {
+        job_id: String,
+        heartbeat: Heartbeat,
+    },
     /// Backend availability changed.
     BackendStatusChanged { backend: String, available: bool },
+    /// A runner published metrics for one iteration of a live run.
+    RunIteration {
+        run_id: String,
+        record: IterationRecord,
+    },
+    /// A live run was removed.
+    RunRemoved { run_id: String },
+    /// A backend reported a cooling telemetry reading.
+    Telemetry {
+        backend: String,
+        sample: TelemetrySample,
+    },
+    /// A telemetry reading crossed a threshold.
+    TelemetryAlertRaised { alert: TelemetryAlert },
+    /// A metric is back within its threshold.
+    TelemetryAlertCleared { backend: String, metric: String },
+    /// The client fell behind and missed events; it should reload run history.
+    Lagged { skipped: u64 },
+    /// A message sent by the client was rejected.
+    Error { message: String },
 }
@@ -1,5 +1,73 @@
 //! WebSocket handlers for real-time updates.
 //!
-//! This module will be expanded in Phase 3 to provide real-time job status updates.
+//! Clients connected to `/api/ws` receive every [`DashboardEvent`] as a JSON
+//! text message. Runners may also publish iteration metrics on the same
+//! socket by sending [`RunIterationMessage`] JSON; rejected messages are
+//! answered with an `error` event.
 
 pub mod events;
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::dto::RunIterationMessage;
+use crate::state::AppState;
+use events::DashboardEvent;
+
+/// GET /api/ws - Upgrade to a WebSocket streaming dashboard events.
+pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<Arc<AppState>>) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
+    let mut events = state.runs.subscribe();
+
+    loop {
+        let event = tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => DashboardEvent::Lagged { skipped },
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match publish(&state, text.as_str()).await {
+                    Ok(()) => continue,
+                    Err(message) => DashboardEvent::Error { message },
+                },
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        let text = match serde_json::to_string(&event) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!("Failed to serialize dashboard event: {e}");
+                continue;
+            }
+        };
+        if socket.send(Message::Text(text.into())).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Publish a runner's iteration message.
+async fn publish(state: &AppState, text: &str) -> Result<(), String> {
+    let message: RunIterationMessage =
+        serde_json::from_str(text).map_err(|e| format!("Invalid message: {e}"))?;
+    state
+        .runs
+        .publish(&message.run_id, message.metrics)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
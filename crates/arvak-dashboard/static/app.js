@@ -134,6 +134,36 @@ const api = {
         return res.json();
     },
 
+    async listRuns() {
+        const res = await fetch('/api/runs');
+        if (!res.ok) throw new Error('Failed to fetch live runs');
+        return res.json();
+    },
+
+    async getRun(id) {
+        const res = await fetch(`/api/runs/${encodeURIComponent(id)}`);
+        if (!res.ok) {
+            const error = await res.json().catch(() => ({}));
+            throw new Error(error.message || 'Failed to fetch run');
+        }
+        return res.json();
+    },
+
+    async listTelemetry() {
+        const res = await fetch('/api/telemetry');
+        if (!res.ok) throw new Error('Failed to fetch telemetry');
+        return res.json();
+    },
+
+    async getTelemetry(backend) {
+        const res = await fetch(`/api/telemetry/${encodeURIComponent(backend)}`);
+        if (!res.ok) {
+            const error = await res.json().catch(() => ({}));
+            throw new Error(error.message || 'Failed to fetch telemetry');
+        }
+        return res.json();
+    },
+
     async getVqeDemo() {
         const res = await fetch('/api/vqe/demo');
         if (!res.ok) {
@@ -704,6 +734,8 @@ function showView(viewName) {
         loadJobs();
     } else if (viewName === 'vqe') {
         loadVqe();
+    } else if (viewName === 'runs') {
+        loadRuns();
     } else if (viewName === 'eval') {
         // Eval view loads on demand via button
     } else if (viewName === 'nathan') {
@@ -899,18 +931,239 @@ async function loadBackends() {
                         ? backend.native_gates.map(g => `<span class="tag">${escapeHtml(g)}</span>`).join('')
                         : '<span class="tag">universal</span>'}
                 </div>
+                <div class="telemetry" data-backend="${escapeHtml(backend.name)}"></div>
             `;
-            card.addEventListener('click', () => showBackendTopology(backend.name));
+            card.addEventListener('click', () => {
+                showBackendTopology(backend.name);
+                if (telemetry.series.has(backend.name)) selectTelemetryBackend(backend.name);
+            });
             grid.appendChild(card);
         });
 
         container.innerHTML = '';
         container.appendChild(grid);
+        await loadTelemetry();
     } catch (error) {
         showError(container, error.message);
     }
 }
 
+// ============================================================================
+// Cooling Telemetry
+// ============================================================================
+
+// Mirrors MAX_SAMPLES_PER_BACKEND on the server.
+const MAX_TELEMETRY_SAMPLES = 8640;
+
+const telemetry = {
+    // backend -> { samples: [samples] | null until loaded, thresholds: [] }
+    series: new Map(),
+    // `${backend}/${metric}` -> alert
+    alerts: new Map(),
+    selected: null,
+    metric: null,
+    renderPending: false,
+};
+
+async function loadTelemetry() {
+    connectRunStream();
+    try {
+        const summaries = await api.listTelemetry();
+        telemetry.alerts.clear();
+        for (const summary of summaries) {
+            if (!telemetry.series.has(summary.backend)) {
+                telemetry.series.set(summary.backend, { samples: null, thresholds: [] });
+            }
+            for (const alert of summary.alerts) {
+                telemetry.alerts.set(`${alert.backend}/${alert.metric}`, alert);
+            }
+        }
+        updateTelemetryCards();
+        const selected = telemetry.selected || (summaries[0] && summaries[0].backend);
+        if (selected) await selectTelemetryBackend(selected);
+    } catch (error) {
+        console.warn('Failed to load telemetry', error);
+    }
+}
+
+function handleTelemetryEvent(event) {
+    if (event.type === 'telemetry') {
+        let series = telemetry.series.get(event.backend);
+        if (!series) {
+            series = { samples: [], thresholds: [] };
+            telemetry.series.set(event.backend, series);
+            if (!telemetry.selected) selectTelemetryBackend(event.backend);
+        }
+        if (series.samples) {
+            series.samples.push(event.sample);
+            if (series.samples.length > MAX_TELEMETRY_SAMPLES) series.samples.shift();
+        }
+    } else if (event.type === 'telemetry_alert_raised') {
+        telemetry.alerts.set(`${event.alert.backend}/${event.alert.metric}`, event.alert);
+    } else if (event.type === 'telemetry_alert_cleared') {
+        telemetry.alerts.delete(`${event.backend}/${event.metric}`);
+    }
+    scheduleTelemetryRender();
+}
+
+async function selectTelemetryBackend(backend) {
+    telemetry.selected = backend;
+    try {
+        const details = await api.getTelemetry(backend);
+        telemetry.series.set(backend, { samples: details.samples, thresholds: details.thresholds });
+        scheduleTelemetryRender();
+    } catch (error) {
+        showError(document.getElementById('telemetry-chart-container'), error.message);
+    }
+}
+
+function scheduleTelemetryRender() {
+    if (telemetry.renderPending) return;
+    telemetry.renderPending = true;
+    requestAnimationFrame(() => {
+        telemetry.renderPending = false;
+        if (state.currentView === 'backends') {
+            updateTelemetryCards();
+            renderSelectedTelemetry();
+        }
+    });
+}
+
+function updateTelemetryCards() {
+    document.querySelectorAll('.backend-card .telemetry').forEach(el => {
+        const backend = el.dataset.backend;
+        const series = telemetry.series.get(backend);
+        const latest = series && series.samples && series.samples[series.samples.length - 1];
+        const alerting = [...telemetry.alerts.values()].some(a => a.backend === backend);
+        el.closest('.backend-card').classList.toggle('alerting', alerting);
+        if (!latest) {
+            el.innerHTML = '';
+            return;
+        }
+        const mixingChamber = latest.metrics.mixing_chamber_mk;
+        el.innerHTML = mixingChamber == null
+            ? `<strong>Telemetry:</strong> ${formatTime(latest.timestamp)}`
+            : `<strong>Mixing chamber:</strong> ${mixingChamber.toFixed(1)} mK`;
+    });
+}
+
+function renderSelectedTelemetry() {
+    const panel = document.getElementById('telemetry-container');
+    if (telemetry.series.size === 0) {
+        panel.style.display = 'none';
+        return;
+    }
+    panel.style.display = 'block';
+
+    document.getElementById('telemetry-alerts').innerHTML = [...telemetry.alerts.values()]
+        .map(a => `<div class="telemetry-alert">${escapeHtml(a.backend)}: ${escapeHtml(a.metric)} = ${a.value} (limit ${escapeHtml(formatThreshold(a.threshold))}) since ${formatTime(a.raised_at)}</div>`)
+        .join('');
+
+    const backendSelect = document.getElementById('telemetry-backend');
+    backendSelect.innerHTML = [...telemetry.series.keys()].map(name => {
+        const selected = name === telemetry.selected ? ' selected' : '';
+        return `<option value="${escapeHtml(name)}"${selected}>${escapeHtml(name)}</option>`;
+    }).join('');
+
+    const container = document.getElementById('telemetry-chart-container');
+    const series = telemetry.series.get(telemetry.selected);
+    if (!series || !series.samples || series.samples.length === 0) {
+        container.innerHTML = '<p class="placeholder">No telemetry yet.</p>';
+        return;
+    }
+
+    const metrics = [...new Set(series.samples.flatMap(s => Object.keys(s.metrics)))].sort();
+    if (!metrics.includes(telemetry.metric)) {
+        telemetry.metric = metrics.includes('mixing_chamber_mk') ? 'mixing_chamber_mk' : metrics[0];
+    }
+    document.getElementById('telemetry-metric').innerHTML = metrics.map(m => {
+        const selected = m === telemetry.metric ? ' selected' : '';
+        return `<option value="${escapeHtml(m)}"${selected}>${escapeHtml(m)}</option>`;
+    }).join('');
+
+    const points = series.samples
+        .filter(s => s.metrics[telemetry.metric] != null)
+        .map(s => ({ time: new Date(s.timestamp), value: s.metrics[telemetry.metric] }));
+    const limits = series.thresholds
+        .filter(t => t.metric === telemetry.metric)
+        .flatMap(t => [t.min, t.max])
+        .filter(v => v != null);
+    renderTelemetryChart(container, points, telemetry.metric, limits);
+}
+
+function formatThreshold(t) {
+    if (t.min != null && t.max != null) return `${t.min}..${t.max}`;
+    return t.max != null ? `> ${t.max}` : `< ${t.min}`;
+}
+
+function renderTelemetryChart(container, points, metric, limits) {
+    container.innerHTML = '';
+
+    const margin = { top: 20, right: 30, bottom: 50, left: 80 };
+    const width = Math.min(container.clientWidth || 700, 900) - margin.left - margin.right;
+    const height = 300 - margin.top - margin.bottom;
+
+    const svg = d3.select(container)
+        .append('svg')
+        .attr('width', width + margin.left + margin.right)
+        .attr('height', height + margin.top + margin.bottom)
+        .append('g')
+        .attr('transform', `translate(${margin.left},${margin.top})`);
+
+    const x = d3.scaleTime()
+        .domain(d3.extent(points, d => d.time))
+        .range([0, width]);
+
+    const [yMin, yMax] = d3.extent([...points.map(d => d.value), ...limits]);
+    const pad = (yMax - yMin) * 0.05 || 0.01;
+    const y = d3.scaleLinear()
+        .domain([yMin - pad, yMax + pad])
+        .range([height, 0]);
+
+    limits.forEach(limit => {
+        svg.append('line')
+            .attr('class', 'telemetry-threshold')
+            .attr('x1', 0)
+            .attr('y1', y(limit))
+            .attr('x2', width)
+            .attr('y2', y(limit));
+    });
+
+    const line = d3.line()
+        .x(d => x(d.time))
+        .y(d => y(d.value));
+
+    svg.append('path')
+        .datum(points)
+        .attr('class', 'vqe-line')
+        .attr('fill', 'none')
+        .attr('d', line);
+
+    svg.append('g')
+        .attr('class', 'axis')
+        .attr('transform', `translate(0,${height})`)
+        .call(d3.axisBottom(x).ticks(8));
+
+    svg.append('text')
+        .attr('class', 'axis-label')
+        .attr('x', width / 2)
+        .attr('y', height + margin.bottom - 8)
+        .style('text-anchor', 'middle')
+        .text('Time');
+
+    svg.append('g')
+        .attr('class', 'axis')
+        .call(d3.axisLeft(y).ticks(8));
+
+    svg.append('text')
+        .attr('class', 'axis-label')
+        .attr('transform', 'rotate(-90)')
+        .attr('y', -margin.left + 15)
+        .attr('x', -(height / 2))
+        .style('text-anchor', 'middle')
+        .text(metric);
+}
+
 async function showBackendTopology(name) {
     const detailContainer = document.getElementById('backend-detail-container');
     const topoContainer = document.getElementById('backend-topology-container');
@@ -1045,7 +1298,7 @@ async function viewJobDetails(jobId) {
                     </div>
                     <div class="detail-item">
                         <span class="label">Status</span>
-                        <span class="value">${escapeHtml(job.status)}${job.status_details ? ' - ' + escapeHtml(job.status_details) : ''}</span>
+                        <span class="value">${escapeHtml(job.status)}${job.phase ? ' (' + escapeHtml(job.phase) + ')' : ''}${job.status_details ? ' - ' + escapeHtml(job.status_details) : ''}</span>
                     </div>
                     <div class="detail-item">
                         <span class="label">Backend</span>
@@ -1055,6 +1308,10 @@ async function viewJobDetails(jobId) {
                         <span class="label">Shots</span>
                         <span class="value">${escapeHtml(String(job.shots))}</span>
                     </div>
+                    <div class="detail-item">
+                        <span class="label">Heartbeat</span>
+                        <span class="value" data-heartbeat-job="${escapeHtml(job.id)}">-</span>
+                    </div>
                     <div class="detail-item">
                         <span class="label">Priority</span>
                         <span class="value">${escapeHtml(String(job.priority))}</span>
@@ -1417,6 +1674,292 @@ function renderVQEChart(container, data) {
         .text('Energy (Ha)');
 }
 
+// ============================================================================
+// Live Runs View Controller
+// ============================================================================
+
+// Mirrors MAX_ITERATIONS_PER_RUN on the server.
+const MAX_RUN_ITERATIONS = 10000;
+
+const liveRuns = {
+    socket: null,
+    retryDelay: 1000,
+    // run id -> { algorithm, iterations: [records] | null until loaded }
+    runs: new Map(),
+    selected: null,
+    renderPending: false,
+};
+
+async function loadRuns() {
+    connectRunStream();
+    try {
+        const summaries = await api.listRuns();
+        for (const summary of summaries) {
+            if (!liveRuns.runs.has(summary.id)) {
+                liveRuns.runs.set(summary.id, { algorithm: summary.algorithm, iterations: null });
+            }
+        }
+        updateRunSelect();
+        const selected = liveRuns.selected || (summaries[0] && summaries[0].id);
+        if (selected) {
+            await selectRun(selected);
+        }
+    } catch (error) {
+        showError(document.getElementById('runs-chart-container'), error.message);
+    }
+}
+
+function connectRunStream() {
+    if (liveRuns.socket) return;
+
+    const protocol = location.protocol === 'https:' ? 'wss:' : 'ws:';
+    const socket = new WebSocket(`${protocol}//${location.host}/api/ws`);
+    liveRuns.socket = socket;
+
+    socket.addEventListener('open', () => {
+        liveRuns.retryDelay = 1000;
+        setRunConnection(true);
+        // Catch up on iterations published while disconnected
+        if (liveRuns.selected) selectRun(liveRuns.selected);
+    });
+    socket.addEventListener('message', msg => {
+        try {
+            handleRunEvent(JSON.parse(msg.data));
+        } catch (e) {
+            console.warn('Invalid dashboard event', e);
+        }
+    });
+    socket.addEventListener('close', () => {
+        liveRuns.socket = null;
+        setRunConnection(false);
+        setTimeout(connectRunStream, liveRuns.retryDelay);
+        liveRuns.retryDelay = Math.min(liveRuns.retryDelay * 2, 30000);
+    });
+}
+
+function setRunConnection(live) {
+    const badge = document.getElementById('runs-connection');
+    badge.textContent = live ? 'live' : 'reconnecting';
+    badge.classList.toggle('live', live);
+}
+
+function handleRunEvent(event) {
+    if (event.type === 'run_iteration') {
+        let run = liveRuns.runs.get(event.run_id);
+        if (!run) {
+            run = { algorithm: null, iterations: [] };
+            liveRuns.runs.set(event.run_id, run);
+            updateRunSelect();
+            if (!liveRuns.selected) selectRun(event.run_id);
+        }
+        if (run.iterations) {
+            run.iterations.push(event.record);
+            if (run.iterations.length > MAX_RUN_ITERATIONS) run.iterations.shift();
+        }
+        if (event.run_id === liveRuns.selected) scheduleRunRender();
+    } else if (event.type === 'run_removed') {
+        liveRuns.runs.delete(event.run_id);
+        updateRunSelect();
+        if (event.run_id === liveRuns.selected) {
+            liveRuns.selected = null;
+            const next = liveRuns.runs.keys().next().value;
+            if (next) {
+                selectRun(next);
+            } else {
+                scheduleRunRender();
+            }
+        }
+    } else if (event.type === 'job_heartbeat') {
+        handleJobHeartbeat(event);
+    } else if (event.type.startsWith('telemetry')) {
+        handleTelemetryEvent(event);
+    } else if (event.type === 'lagged') {
+        if (liveRuns.selected) selectRun(liveRuns.selected);
+        if (telemetry.selected) selectTelemetryBackend(telemetry.selected);
+    }
+}
+
+function handleJobHeartbeat(event) {
+    for (const el of document.querySelectorAll('[data-heartbeat-job]')) {
+        if (el.dataset.heartbeatJob !== event.job_id) continue;
+        const beat = event.heartbeat;
+        const position = beat.queue_position ? ` (position ${beat.queue_position})` : '';
+        const minutes = Math.floor(beat.elapsed_secs / 60);
+        el.textContent = `${beat.phase}${position}, ${minutes} min, ${formatTime(beat.at)}`;
+    }
+}
+
+function updateRunSelect() {
+    const select = document.getElementById('runs-select');
+    select.innerHTML = [...liveRuns.runs.entries()].map(([id, run]) => {
+        const label = run.algorithm ? `${id} (${run.algorithm})` : id;
+        const selected = id === liveRuns.selected ? ' selected' : '';
+        return `<option value="${escapeHtml(id)}"${selected}>${escapeHtml(label)}</option>`;
+    }).join('');
+}
+
+async function selectRun(id) {
+    liveRuns.selected = id;
+    updateRunSelect();
+    try {
+        const details = await api.getRun(id);
+        liveRuns.runs.set(id, { algorithm: details.algorithm, iterations: details.iterations });
+        updateRunSelect();
+        scheduleRunRender();
+    } catch (error) {
+        showError(document.getElementById('runs-chart-container'), error.message);
+    }
+}
+
+function scheduleRunRender() {
+    if (liveRuns.renderPending) return;
+    liveRuns.renderPending = true;
+    requestAnimationFrame(() => {
+        liveRuns.renderPending = false;
+        if (state.currentView === 'runs') renderSelectedRun();
+    });
+}
+
+function renderSelectedRun() {
+    const container = document.getElementById('runs-chart-container');
+    const legend = document.getElementById('runs-legend');
+    const info = document.getElementById('runs-info');
+    const run = liveRuns.runs.get(liveRuns.selected);
+
+    if (!run || !run.iterations || run.iterations.length === 0) {
+        container.innerHTML = '<p class="placeholder">No live runs yet. Runners publish metrics to POST /api/runs/{id}/iterations or the /api/ws WebSocket.</p>';
+        legend.innerHTML = '';
+        info.innerHTML = '';
+        return;
+    }
+
+    const iterations = run.iterations;
+    const latest = iterations[iterations.length - 1];
+    const best = d3.min(iterations, d => d.energy);
+    renderRunChart(container, iterations, best);
+
+    legend.innerHTML = `
+        <span class="vqe-legend-item">
+            <span class="vqe-legend-swatch" style="background: var(--accent);"></span>
+            Energy
+        </span>
+        <span class="vqe-legend-item">
+            <span class="vqe-legend-swatch vqe-legend-dashed" style="background: var(--success);"></span>
+            Best: ${best.toFixed(6)}
+        </span>
+    `;
+
+    const gradient = latest.gradient_norm == null ? '-' : latest.gradient_norm.toExponential(3);
+    info.innerHTML = `
+        <div class="detail-item">
+            <span class="label">Algorithm</span>
+            <span class="value">${escapeHtml(run.algorithm || '-')}</span>
+        </div>
+        <div class="detail-item">
+            <span class="label">Iteration</span>
+            <span class="value">${latest.iteration}</span>
+        </div>
+        <div class="detail-item">
+            <span class="label">Energy</span>
+            <span class="value">${latest.energy.toFixed(7)}</span>
+        </div>
+        <div class="detail-item">
+            <span class="label">Best Energy</span>
+            <span class="value">${best.toFixed(7)}</span>
+        </div>
+        <div class="detail-item">
+            <span class="label">Gradient Norm</span>
+            <span class="value">${gradient}</span>
+        </div>
+        <div class="detail-item">
+            <span class="label">Parameters</span>
+            <span class="value">${latest.parameters.length}</span>
+        </div>
+        <div class="detail-item">
+            <span class="label">Last Update</span>
+            <span class="value">${formatTime(latest.timestamp)}</span>
+        </div>
+    `;
+}
+
+function renderRunChart(container, iterations, best) {
+    container.innerHTML = '';
+
+    const margin = { top: 20, right: 30, bottom: 50, left: 80 };
+    const width = Math.min(container.clientWidth || 700, 900) - margin.left - margin.right;
+    const height = 350 - margin.top - margin.bottom;
+
+    const svg = d3.select(container)
+        .append('svg')
+        .attr('width', width + margin.left + margin.right)
+        .attr('height', height + margin.top + margin.bottom)
+        .append('g')
+        .attr('transform', `translate(${margin.left},${margin.top})`);
+
+    const x = d3.scaleLinear()
+        .domain(d3.extent(iterations, d => d.iteration))
+        .range([0, width]);
+
+    const [yMin, yMax] = d3.extent(iterations, d => d.energy);
+    const pad = (yMax - yMin) * 0.05 || 0.01;
+    const y = d3.scaleLinear()
+        .domain([yMin - pad, yMax + pad])
+        .range([height, 0]);
+
+    svg.append('line')
+        .attr('class', 'runs-best')
+        .attr('x1', 0)
+        .attr('y1', y(best))
+        .attr('x2', width)
+        .attr('y2', y(best));
+
+    const line = d3.line()
+        .x(d => x(d.iteration))
+        .y(d => y(d.energy));
+
+    svg.append('path')
+        .datum(iterations)
+        .attr('class', 'vqe-line')
+        .attr('fill', 'none')
+        .attr('d', line);
+
+    // Dots only while they stay readable
+    if (iterations.length <= 200) {
+        svg.selectAll('.vqe-dot')
+            .data(iterations)
+            .enter()
+            .append('circle')
+            .attr('class', 'vqe-dot')
+            .attr('cx', d => x(d.iteration))
+            .attr('cy', d => y(d.energy))
+            .attr('r', 3);
+    }
+
+    svg.append('g')
+        .attr('class', 'axis')
+        .attr('transform', `translate(0,${height})`)
+        .call(d3.axisBottom(x).ticks(Math.min(iterations.length, 12)).tickFormat(d3.format('d')));
+
+    svg.append('text')
+        .attr('class', 'axis-label')
+        .attr('x', width / 2)
+        .attr('y', height + margin.bottom - 8)
+        .style('text-anchor', 'middle')
+        .text('Iteration');
+
+    svg.append('g')
+        .attr('class', 'axis')
+        .call(d3.axisLeft(y).ticks(8));
+
+    svg.append('text')
+        .attr('class', 'axis-label')
+        .attr('transform', 'rotate(-90)')
+        .attr('y', -margin.left + 15)
+        .attr('x', -(height / 2))
+        .style('text-anchor', 'middle')
+        .text('Energy');
+}
+
 async function cancelJob(jobId) {
     if (!confirm('Are you sure you want to cancel this job?')) {
         return;
@@ -1508,6 +2051,8 @@ function renderEvalReport(container, r) {
     html += evalCard('Qubits', r.input.num_qubits);
     html += evalCard('Input Depth', r.input.depth);
     html += evalCard('Input Gates', r.input.total_ops);
+    html += evalCard('Workload', r.input.workload,
+        '', `${(r.input.clifford_fraction * 100).toFixed(0)}% Clifford`);
     html += evalCard('Compiled Depth', r.compilation.compiled_depth,
         r.compilation.depth_delta < 0 ? 'improved' : r.compilation.depth_delta > 0 ? 'degraded' : '',
         `${r.compilation.depth_delta >= 0 ? '+' : ''}${r.compilation.depth_delta}`);
@@ -1573,7 +2118,7 @@ function renderEvalReport(container, r) {
         html += '<div class="eval-coverage-grid">';
         html += evalMetric('Quantum Phases', r.orchestration.quantum_phases);
         html += evalMetric('Classical Phases', r.orchestration.classical_phases);
-        html += evalMetric('Critical Path Cost', r.orchestration.critical_path_cost.toFixed(1));
+        html += evalMetric('Critical Path (µs)', r.orchestration.critical_path_cost.toFixed(2));
         html += evalMetric('Max Parallel', r.orchestration.max_parallel_quantum);
         html += evalMetric('Parallelism', r.orchestration.parallelism_ratio.toFixed(2));
         html += evalMetric('Purely Quantum', r.orchestration.is_purely_quantum ? 'Yes' : 'No');
@@ -1596,6 +2141,8 @@ function renderEvalReport(container, r) {
         html += evalMetric('Fitness Score', r.scheduler.fitness_score.toFixed(2));
         html += evalMetric('Walltime (rec)', `${r.scheduler.recommended_walltime}s`);
         html += evalMetric('Batch Capacity', r.scheduler.batch_capacity);
+        html += evalMetric('Energy (est)', `${r.scheduler.energy_kwh.toFixed(4)} kWh`);
+        html += evalMetric('CO2e (est)', `${r.scheduler.co2_kg.toFixed(4)} kg`);
         html += '</div>';
         html += `<div class="eval-assessment">${escapeHtml(r.scheduler.assessment)}</div>`;
         html += '</div>';
@@ -1807,6 +2354,12 @@ document.addEventListener('DOMContentLoaded', async () => {
     document.getElementById('refresh-jobs-btn').addEventListener('click', loadJobs);
     document.getElementById('eval-run-btn').addEventListener('click', runEvaluation);
     document.getElementById('eval-export-btn').addEventListener('click', exportEvalJson);
+    document.getElementById('runs-select').addEventListener('change', e => selectRun(e.target.value));
+    document.getElementById('telemetry-backend').addEventListener('change', e => selectTelemetryBackend(e.target.value));
+    document.getElementById('telemetry-metric').addEventListener('change', e => {
+        telemetry.metric = e.target.value;
+        renderSelectedTelemetry();
+    });
 
     // Allow Ctrl+Enter to visualize
     document.getElementById('qasm-input').addEventListener('keydown', e => {
@@ -1816,7 +2369,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     });
 
     // Handle hash-based routing
-    const validViews = ['circuits', 'backends', 'jobs', 'eval', 'vqe', 'nathan'];
+    const validViews = ['circuits', 'backends', 'jobs', 'eval', 'vqe', 'runs', 'nathan'];
     const hashView = location.hash.replace('#', '');
     showView(validViews.includes(hashView) ? hashView : 'circuits');
 
@@ -3,7 +3,7 @@
 use std::sync::Arc;
 
 use arvak_adapter_sim::SimulatorBackend;
-use arvak_dashboard::{AppState, DashboardConfig, create_router};
+use arvak_dashboard::{AppState, DashboardConfig, PublishIterationRequest, create_router};
 use arvak_sched::SqliteStore;
 use axum_test::TestServer;
 use serde_json::{Value, json};
@@ -585,6 +585,309 @@ async fn test_eval_invalid_qasm() {
     assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
 }
 
+// ============================================================================
+// Live runs
+// ============================================================================
+
+#[tokio::test]
+async fn test_publish_iterations_and_get_history() {
+    let server = test_server(test_state());
+    for (i, energy) in [-0.5, -1.1, -0.9].iter().enumerate() {
+        let response = server
+            .post("/api/runs/h2-vqe/iterations")
+            .json(&json!({
+                "energy": energy,
+                "gradient_norm": 0.1 / (i as f64 + 1.0),
+                "parameters": [0.1, 0.2],
+                "algorithm": "vqe"
+            }))
+            .await;
+        response.assert_status_ok();
+        let body: Value = response.json();
+        assert_eq!(body["iteration"], i);
+    }
+
+    let response = server.get("/api/runs/h2-vqe").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["algorithm"], "vqe");
+    assert_eq!(body["best_energy"], -1.1);
+    let iterations = body["iterations"].as_array().unwrap();
+    assert_eq!(iterations.len(), 3);
+    assert_eq!(iterations[2]["energy"], -0.9);
+    assert_eq!(iterations[2]["parameters"], json!([0.1, 0.2]));
+
+    let response = server.get("/api/runs").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body[0]["id"], "h2-vqe");
+    assert_eq!(body[0]["iterations"], 3);
+    assert_eq!(body[0]["latest_energy"], -0.9);
+}
+
+#[tokio::test]
+async fn test_publish_broadcasts_iteration_event() {
+    let state = test_state();
+    let mut events = state.runs.subscribe();
+    let server = test_server(state);
+
+    server
+        .post("/api/runs/maxcut/iterations")
+        .json(&json!({ "iteration": 7, "energy": -2.5, "algorithm": "qaoa" }))
+        .await
+        .assert_status_ok();
+
+    let event = serde_json::to_value(events.recv().await.unwrap()).unwrap();
+    assert_eq!(event["type"], "run_iteration");
+    assert_eq!(event["run_id"], "maxcut");
+    assert_eq!(event["record"]["iteration"], 7);
+    assert_eq!(event["record"]["energy"], -2.5);
+}
+
+#[tokio::test]
+async fn test_publish_rejects_invalid_run_id() {
+    let server = test_server(test_state());
+    let response = server
+        .post("/api/runs/bad%20id/iterations")
+        .json(&json!({ "energy": -1.0 }))
+        .await;
+    response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_nonexistent_run_returns_404() {
+    let server = test_server(test_state());
+    let response = server.get("/api/runs/missing").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_delete_run() {
+    let server = test_server(test_state());
+    server
+        .post("/api/runs/r1/iterations")
+        .json(&json!({ "energy": 0.0 }))
+        .await
+        .assert_status_ok();
+
+    server.delete("/api/runs/r1").await.assert_status_ok();
+    server.get("/api/runs/r1").await.assert_status_not_found();
+    server
+        .delete("/api/runs/r1")
+        .await
+        .assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_run_history_is_bounded() {
+    let state = test_state();
+    let total = arvak_dashboard::runs::MAX_ITERATIONS_PER_RUN + 5;
+    for i in 0..total {
+        let metrics: PublishIterationRequest =
+            serde_json::from_value(json!({ "energy": -(i as f64) })).unwrap();
+        state.runs.publish("long", metrics).await.unwrap();
+    }
+
+    let run = state.runs.get("long").await.unwrap();
+    assert_eq!(run.iterations.len(), total - 5);
+    assert_eq!(run.iterations[0].iteration, 5);
+    assert_eq!(state.runs.list().await[0].iterations, total as u64);
+}
+
+#[tokio::test]
+async fn test_least_recently_updated_run_is_evicted() {
+    let state = test_state();
+    let max_runs = arvak_dashboard::runs::MAX_RUNS;
+    for i in 0..=max_runs {
+        let metrics: PublishIterationRequest =
+            serde_json::from_value(json!({ "energy": 0.0 })).unwrap();
+        state
+            .runs
+            .publish(&format!("run-{i}"), metrics)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(state.runs.list().await.len(), max_runs);
+    assert!(state.runs.get("run-0").await.is_none());
+    assert!(state.runs.get(&format!("run-{max_runs}")).await.is_some());
+}
+
+// ============================================================================
+// Cooling telemetry
+// ============================================================================
+
+async fn telemetry_state(thresholds: &[&str]) -> Arc<AppState> {
+    let config = DashboardConfig {
+        telemetry_thresholds: thresholds.iter().map(|t| t.parse().unwrap()).collect(),
+        ..DashboardConfig::default()
+    };
+    let state = Arc::new(AppState::with_config(config));
+    state
+        .register_backend_as("fridge".to_string(), Arc::new(SimulatorBackend::new()))
+        .await;
+    state
+}
+
+#[tokio::test]
+async fn test_publish_telemetry_and_get_series() {
+    let server = test_server(telemetry_state(&[]).await);
+    for (minute, temperature) in [(0, 11.0), (1, 12.5)] {
+        let response = server
+            .post("/api/telemetry/fridge")
+            .json(&json!({
+                "timestamp": format!("2026-10-01T12:0{minute}:00Z"),
+                "metrics": { "mixing_chamber_mk": temperature, "plate_4k_k": 3.9 }
+            }))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["recorded"], true);
+    }
+
+    // A reading not newer than the latest is ignored.
+    let response = server
+        .post("/api/telemetry/fridge")
+        .json(&json!({
+            "timestamp": "2026-10-01T12:00:30Z",
+            "metrics": { "mixing_chamber_mk": 99.0 }
+        }))
+        .await;
+    assert_eq!(response.json::<Value>()["recorded"], false);
+
+    let body: Value = server.get("/api/telemetry/fridge").await.json();
+    let samples = body["samples"].as_array().unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[1]["metrics"]["mixing_chamber_mk"], 12.5);
+
+    let body: Value = server
+        .get("/api/telemetry/fridge")
+        .add_query_param("since", "2026-10-01T12:00:00Z")
+        .await
+        .json();
+    assert_eq!(body["samples"].as_array().unwrap().len(), 1);
+
+    let body: Value = server.get("/api/telemetry").await.json();
+    assert_eq!(body[0]["backend"], "fridge");
+    assert_eq!(body[0]["samples"], 2);
+    assert_eq!(body[0]["latest"]["metrics"]["plate_4k_k"], 3.9);
+}
+
+#[tokio::test]
+async fn test_telemetry_threshold_alerts() {
+    let state = telemetry_state(&["mixing_chamber_mk>20"]).await;
+    let mut events = state.runs.subscribe();
+    let server = test_server(state);
+
+    for (second, temperature) in [(0, 15.0), (1, 24.0), (2, 26.0)] {
+        server
+            .post("/api/telemetry/fridge")
+            .json(&json!({
+                "timestamp": format!("2026-10-01T12:00:0{second}Z"),
+                "metrics": { "mixing_chamber_mk": temperature }
+            }))
+            .await
+            .assert_status_ok();
+    }
+
+    let body: Value = server.get("/api/telemetry/fridge").await.json();
+    let alerts = body["alerts"].as_array().unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["value"], 24.0);
+    assert_eq!(alerts[0]["threshold"]["max"], 20.0);
+    assert_eq!(body["thresholds"][0]["metric"], "mixing_chamber_mk");
+
+    server
+        .post("/api/telemetry/fridge")
+        .json(&json!({
+            "timestamp": "2026-10-01T12:00:03Z",
+            "metrics": { "mixing_chamber_mk": 14.0 }
+        }))
+        .await
+        .assert_status_ok();
+    let body: Value = server.get("/api/telemetry").await.json();
+    assert!(body[0]["alerts"].as_array().unwrap().is_empty());
+
+    let mut types = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        types.push(serde_json::to_value(event).unwrap()["type"].clone());
+    }
+    assert_eq!(
+        types,
+        [
+            "telemetry",
+            "telemetry",
+            "telemetry_alert_raised",
+            "telemetry",
+            "telemetry",
+            "telemetry_alert_cleared"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_publish_telemetry_rejects_invalid() {
+    let server = test_server(telemetry_state(&[]).await);
+    server
+        .post("/api/telemetry/unknown")
+        .json(&json!({ "metrics": { "mixing_chamber_mk": 10.0 } }))
+        .await
+        .assert_status_not_found();
+    server
+        .get("/api/telemetry/fridge")
+        .await
+        .assert_status_not_found();
+
+    let metrics: serde_json::Map<String, Value> = (0..=arvak_dashboard::telemetry::MAX_METRICS)
+        .map(|i| (format!("m{i}"), json!(1.0)))
+        .collect();
+    server
+        .post("/api/telemetry/fridge")
+        .json(&json!({ "metrics": metrics }))
+        .await
+        .assert_status(axum::http::StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// Failure forensics
+// ============================================================================
+
+#[tokio::test]
+async fn test_admin_failures() {
+    let dir = std::env::temp_dir().join(format!("arvak-dashboard-dlq-{}", std::process::id()));
+    let queue = arvak_hal::DeadLetterQueue::new(&dir);
+    queue
+        .record(
+            &arvak_hal::FailureBundle::new("job-1", "Backend result error: timeout")
+                .with_backend("iqm")
+                .with_circuit(SIMPLE_QASM),
+        )
+        .unwrap();
+    let state = Arc::new(AppState::with_config(DashboardConfig::default()).with_dead_letter(queue));
+    let server = test_server(state);
+
+    let response = server.get("/api/admin/failures").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["job_id"], "job-1");
+
+    let response = server.get("/api/admin/failures/job-1").await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body["backend"], "iqm");
+    assert_eq!(body["circuits"][0], SIMPLE_QASM);
+
+    server
+        .delete("/api/admin/failures/job-1")
+        .await
+        .assert_status_ok();
+    server
+        .get("/api/admin/failures/job-1")
+        .await
+        .assert_status(axum::http::StatusCode::NOT_FOUND);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
 // ============================================================================
 // Static file serving
 // ============================================================================
@@ -23,6 +23,10 @@ pub enum EvalError {
     /// Serialization error.
     #[error("Export error: {0}")]
     Export(String),
+
+    /// Plugin registration or analysis error.
+    #[error("Plugin error: {0}")]
+    Plugin(String),
 }
 
 impl From<arvak_qasm3::ParseError> for EvalError {
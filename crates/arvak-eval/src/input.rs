@@ -10,6 +10,7 @@ use arvak_ir::Circuit;
 use arvak_ir::instruction::InstructionKind;
 
 use crate::error::{EvalError, EvalResult};
+use crate::structure::CircuitStructure;
 
 /// Structural metrics extracted from a circuit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +47,8 @@ pub struct InputAnalysis {
     pub content_hash: String,
     /// Structural metrics of the input circuit.
     pub structural_metrics: StructuralMetrics,
+    /// Circuit structure and workload class.
+    pub structure: CircuitStructure,
 }
 
 impl InputAnalysis {
@@ -60,11 +63,13 @@ impl InputAnalysis {
 
         // Extract structural metrics
         let structural_metrics = extract_metrics(&circuit);
+        let structure = CircuitStructure::analyze(&circuit);
 
         Ok(Self {
             circuit,
             content_hash,
             structural_metrics,
+            structure,
         })
     }
 
@@ -83,6 +88,7 @@ impl InputAnalysis {
             measurements: self.structural_metrics.measurements,
             barriers: self.structural_metrics.barriers,
             has_parameters: self.structural_metrics.has_parameters,
+            structure: self.structure,
         }
     }
 }
@@ -114,6 +120,9 @@ pub struct InputReport {
     pub barriers: usize,
     /// Whether circuit has parameterized gates.
     pub has_parameters: bool,
+    /// Circuit structure and workload class.
+    #[serde(default)]
+    pub structure: CircuitStructure,
 }
 
 /// Extract structural metrics from a circuit.
@@ -255,4 +264,14 @@ cx q[2], q[3];
         assert_eq!(analysis.structural_metrics.single_qubit_gates, 1);
         assert_eq!(analysis.structural_metrics.two_qubit_gates, 3);
     }
+
+    #[test]
+    fn test_structure_in_report() {
+        let report = InputAnalysis::analyze(BELL_QASM).unwrap().into_report();
+        assert_eq!(report.structure.clifford_fraction, 1.0);
+        assert_eq!(
+            report.structure.workload,
+            crate::structure::WorkloadClass::Sampling
+        );
+    }
 }
@@ -10,10 +10,15 @@
 //! reports covering:
 //!
 //! - **Input Analysis**: Parsing, validation, and content hashing
+//! - **Structure Detection**: Clifford fraction, repeated layers, workload class
 //! - **Compilation Observation**: Pass-wise metrics with before/after deltas
+//! - **Error Budget**: Ranked per-operation contributions to predicted
+//!   infidelity, given a noise profile
 //! - **Orchestration Analysis**: Hybrid DAG, critical path, batchability (v0.2)
+//! - **Timing Model**: Instruction durations for critical-path costs in microseconds
 //! - **Emitter Compliance**: Native gate coverage, loss documentation (v0.3)
 //! - **Benchmark Loading**: Standard circuit workloads (GHZ, QFT, etc.) (v0.3)
+//! - **Plugins**: Custom analyzers contributing namespaced report sections
 //! - **Metrics Aggregation**: Compilation + Orchestration + Emitter deltas
 //! - **Reproducibility**: CLI snapshots, versioning, and deterministic exports
 //!
@@ -46,11 +51,15 @@ pub mod input;
 pub mod metrics;
 pub mod observer;
 pub mod orchestration;
+pub mod plugin;
 pub mod report;
 pub mod reproducibility;
 pub mod scheduler_context;
+pub mod structure;
+pub mod timing;
 
 pub use error::{EvalError, EvalResult};
+pub use plugin::{EvalPlugin, PluginContext, PluginSection};
 pub use report::EvalReport;
 
 use benchmark::{BenchmarkLoader, BenchmarkSuite};
@@ -62,10 +71,16 @@ use observer::CompilationObserver;
 use orchestration::OrchestrationAnalyzer;
 use reproducibility::ReproducibilityInfo;
 use scheduler_context::{SchedulerConstraints, SchedulerContext};
+use timing::TimingModel;
 
-use arvak_compile::{BasisGates, CouplingMap, PassManagerBuilder};
-use arvak_hal::{Capabilities, GateSet, Topology};
-use tracing::info;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use arvak_compile::passes::{ErrorBudget, ErrorBudgetReport};
+use arvak_compile::{AdaptiveOptimization, AdaptiveSelection, Pass, PassManagerBuilder};
+use arvak_hal::{Capabilities, GateSet, PassManagerBuilderExt, Provenance, Topology};
+use arvak_ir::noise::NoiseProfile;
+use tracing::{debug, info};
 
 /// Evaluation profile controlling compilation target and observation depth.
 #[derive(Debug, Clone)]
@@ -74,6 +89,17 @@ pub struct EvalConfig {
     pub profile: String,
     /// Optimization level for compilation (0-3).
     pub optimization_level: u8,
+    /// Choose the optimization level per circuit within this compile-time
+    /// budget instead of using `optimization_level`.
+    pub compile_budget: Option<Duration>,
+    /// Wall-clock limit for compilation; optimization passes stop early
+    /// once it is spent.
+    pub time_budget: Option<Duration>,
+    /// Seed for compiler passes that make random choices.
+    pub seed: Option<u64>,
+    /// Compile deterministically: seed every random choice and ignore
+    /// `time_budget`.
+    pub deterministic: bool,
     /// Target backend name (iqm, ibm, simulator).
     pub target: String,
     /// Number of qubits on target device.
@@ -90,6 +116,9 @@ pub struct EvalConfig {
     pub benchmark: Option<String>,
     /// Number of qubits for benchmark circuit generation.
     pub benchmark_qubits: Option<usize>,
+    /// Device noise profile; enables the error budget of the compiled
+    /// circuit.
+    pub noise_profile: Option<NoiseProfile>,
 }
 
 impl Default for EvalConfig {
@@ -97,6 +126,10 @@ impl Default for EvalConfig {
         Self {
             profile: "default".into(),
             optimization_level: 1,
+            compile_budget: None,
+            time_budget: None,
+            seed: None,
+            deterministic: false,
             target: "iqm".into(),
             target_qubits: 20,
             export: ExportConfig::default(),
@@ -105,6 +138,7 @@ impl Default for EvalConfig {
             emit_target: None,
             benchmark: None,
             benchmark_qubits: None,
+            noise_profile: None,
         }
     }
 }
@@ -123,35 +157,44 @@ impl EvalConfig {
                 is_simulator: false,
                 features: vec!["dynamic_circuits".into()],
                 noise_profile: None,
+                decoherence: None,
             },
             "simulator" => Capabilities::simulator(self.target_qubits),
             // Default to IQM
             _ => Capabilities::iqm(&self.target, self.target_qubits),
         }
     }
-
-    /// Build coupling map and basis gates for compilation.
-    fn build_target_properties(&self) -> (CouplingMap, BasisGates) {
-        match self.target.as_str() {
-            "ibm" => (CouplingMap::linear(self.target_qubits), BasisGates::ibm()),
-            "simulator" => (
-                CouplingMap::full(self.target_qubits),
-                BasisGates::universal(),
-            ),
-            _ => (CouplingMap::star(self.target_qubits), BasisGates::iqm()),
-        }
-    }
 }
 
 /// The main evaluator orchestrating all modules.
 pub struct Evaluator {
     config: EvalConfig,
+    plugins: Vec<Box<dyn EvalPlugin>>,
 }
 
 impl Evaluator {
     /// Create a new evaluator with the given configuration.
     pub fn new(config: EvalConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Register a plugin whose section is added to every report.
+    ///
+    /// Fails if the namespace is malformed or already registered.
+    pub fn register_plugin(&mut self, plugin: impl EvalPlugin + 'static) -> EvalResult<()> {
+        let namespace = plugin.namespace();
+        plugin::validate_namespace(namespace)?;
+        if self.plugins.iter().any(|p| p.namespace() == namespace) {
+            return Err(EvalError::Plugin(format!(
+                "namespace '{namespace}' is already registered"
+            )));
+        }
+        debug!("Registering eval plugin: {}", namespace);
+        self.plugins.push(Box::new(plugin));
+        Ok(())
     }
 
     /// Evaluate an `OpenQASM` 3.0 source string.
@@ -168,15 +211,35 @@ impl Evaluator {
             input_analysis.structural_metrics.total_ops,
             input_analysis.structural_metrics.depth,
         );
+        info!(
+            "Structure: {} workload, {:.0}% Clifford, {:?} parameters",
+            input_analysis.structure.workload,
+            input_analysis.structure.clifford_fraction * 100.0,
+            input_analysis.structure.parameter_binding,
+        );
 
         // 2. Compilation observation
-        let (coupling_map, basis_gates) = self.config.build_target_properties();
-        let (pm, mut props) = PassManagerBuilder::new()
+        let mut dag = circuit.into_dag();
+        let mut builder = PassManagerBuilder::new()
             .with_optimization_level(self.config.optimization_level)
-            .with_target(coupling_map, basis_gates)
+            .with_capabilities(&self.config.target_capabilities());
+        if let Some(budget) = self.config.compile_budget {
+            // Variational workloads are recompiled rarely and run often.
+            let adaptive = AdaptiveOptimization::new(budget)
+                .with_variational(input_analysis.structure.workload.is_variational());
+            builder = builder.with_adaptive_optimization(&dag, &adaptive);
+        }
+        if let Some(budget) = self.config.time_budget {
+            builder = builder.with_time_budget(budget);
+        }
+        if let Some(seed) = self.config.seed {
+            builder = builder.with_seed(seed);
+        }
+        let (pm, mut props) = builder
+            .with_deterministic(self.config.deterministic)
             .build();
+        let selection = props.get::<AdaptiveSelection>().cloned();
 
-        let mut dag = circuit.into_dag();
         let observer = CompilationObserver::observe(&pm, &mut dag, &mut props)?;
 
         info!(
@@ -185,18 +248,39 @@ impl Evaluator {
             observer.final_metrics.depth,
         );
 
+        // Error budget of the compiled circuit (with a noise profile).
+        let error_budget = match &self.config.noise_profile {
+            Some(profile) => {
+                props.insert(profile.clone());
+                if ErrorBudget.should_run(&dag, &props) {
+                    ErrorBudget.run(&mut dag, &mut props)?;
+                }
+                let budget = props.get::<ErrorBudgetReport>().cloned();
+                if let Some(budget) = &budget {
+                    info!(
+                        "Error budget: estimated fidelity {:.4}, {} entries",
+                        budget.estimated_fidelity,
+                        budget.entries.len(),
+                    );
+                }
+                budget
+            }
+            None => None,
+        };
+
         // 3. Target capabilities (for emitter analysis)
         let capabilities = self.config.target_capabilities();
 
         // 4. Orchestration analysis (optional)
         let (orchestration_report, scheduler_fitness) = if self.config.orchestration {
-            let orch = OrchestrationAnalyzer::analyze(
+            let orch = OrchestrationAnalyzer::analyze_with_timing(
                 &observer.final_dag,
                 input_analysis.structural_metrics.num_qubits,
+                &TimingModel::for_target(&self.config.target),
             );
 
             info!(
-                "Orchestration: {} quantum phases, {} classical phases, critical path cost {:.1}",
+                "Orchestration: {} quantum phases, {} classical phases, critical path cost {:.2} µs",
                 orch.summary.quantum_phases,
                 orch.summary.classical_phases,
                 orch.critical_path.total_cost,
@@ -211,12 +295,12 @@ impl Evaluator {
                 _ => SchedulerConstraints::lrz(), // Default to LRZ
             };
 
-            let fitness = SchedulerContext::evaluate(
+            let fitness = SchedulerContext::evaluate_with_circuit_time(
                 input_analysis.structural_metrics.num_qubits,
-                input_analysis.structural_metrics.depth,
-                input_analysis.structural_metrics.total_ops,
+                orch.critical_path.total_cost * 1e-6,
                 &constraints,
-            );
+            )
+            .with_workload(input_analysis.structure.workload);
 
             info!(
                 "Scheduler: {} fitness={:.2}, batch_capacity={}",
@@ -280,7 +364,28 @@ impl Evaluator {
             None
         };
 
-        // 7. Metrics aggregation (unified: compilation + orchestration + emitter)
+        // 7. Plugin sections
+        let mut extensions = BTreeMap::new();
+        if !self.plugins.is_empty() {
+            let context = PluginContext {
+                profile: &self.config.profile,
+                target: &self.config.target,
+                capabilities: &capabilities,
+                num_qubits: input_analysis.structural_metrics.num_qubits,
+                dag: &observer.final_dag,
+            };
+            for plugin in &self.plugins {
+                let section = plugin::run_plugin(plugin.as_ref(), &context)?;
+                info!(
+                    "Plugin: {} (schema {})",
+                    plugin.namespace(),
+                    section.schema_version
+                );
+                extensions.insert(plugin.namespace().to_string(), section);
+            }
+        }
+
+        // 8. Metrics aggregation (unified: compilation + orchestration + emitter)
         let aggregated = MetricsAggregator::aggregate_full(
             &input_analysis,
             &observer,
@@ -290,21 +395,34 @@ impl Evaluator {
             emitter_report.as_ref(),
         );
 
-        // 8. Reproducibility
+        // 9. Reproducibility and provenance
         let reproducibility = ReproducibilityInfo::capture(cli_args);
+        let provenance = Provenance::source(qasm_source).compiled(&observer.final_dag);
 
-        // 9. Build report
+        // 10. Build report
         let report = EvalReport {
             schema_version: "0.3.0".into(),
             timestamp: chrono::Utc::now(),
             profile: self.config.profile.clone(),
             input: input_analysis.into_report(),
-            compilation: observer.into_report(),
+            compilation: {
+                let mut compilation = observer.into_report();
+                compilation.optimization_level = Some(
+                    selection
+                        .as_ref()
+                        .map_or(self.config.optimization_level, |s| s.level),
+                );
+                compilation.adaptive_reason = selection.map(|s| s.reason);
+                compilation
+            },
             metrics: aggregated,
+            error_budget,
             orchestration: orchestration_report,
             scheduler: scheduler_fitness,
             emitter: emitter_report,
             benchmark: benchmark_info,
+            provenance: Some(provenance),
+            extensions,
             reproducibility,
         };
 
@@ -353,6 +471,46 @@ c = measure q;
         assert!(report.orchestration.is_none());
         assert!(report.emitter.is_none());
         assert!(report.benchmark.is_none());
+        assert!(report.error_budget.is_none());
+
+        let provenance = report.provenance.unwrap();
+        assert_eq!(provenance.stage, arvak_hal::provenance::Stage::Compiled);
+        assert_eq!(
+            provenance.parent(),
+            Some(&Provenance::source(BELL_QASM).artifact_id)
+        );
+    }
+
+    #[test]
+    fn test_evaluator_error_budget() {
+        let mut profile = NoiseProfile::new();
+        profile.gate_errors.insert("prx".into(), 0.001);
+        profile.gate_errors.insert("cz".into(), 0.01);
+        profile.readout_errors = Some(vec![0.02; 5]);
+        let config = EvalConfig {
+            target: "iqm".into(),
+            target_qubits: 5,
+            noise_profile: Some(profile),
+            ..Default::default()
+        };
+        let evaluator = Evaluator::new(config);
+        let report = evaluator.evaluate(BELL_QASM, &[]).unwrap();
+
+        let budget = report.error_budget.unwrap();
+        assert!(budget.unknown_gates.is_empty());
+        assert_eq!(budget.top(2).len(), 2);
+        assert!(budget.top(2).iter().all(|e| e.operation == "measure"));
+        let cz: usize = budget
+            .entries
+            .iter()
+            .filter(|e| e.operation == "cz")
+            .map(|e| e.count)
+            .sum();
+        assert_eq!(cz, 1);
+        assert!(budget.estimated_fidelity > 0.9 && budget.estimated_fidelity < 0.97);
+
+        let json = serde_json::to_value(budget).unwrap();
+        assert!(json["entries"][0]["contribution"].is_number());
     }
 
     #[test]
@@ -370,6 +528,25 @@ c = measure q;
         assert!(report.metrics.compilation_effect.is_some());
     }
 
+    #[test]
+    fn test_evaluator_adaptive_level() {
+        let config = EvalConfig {
+            target: "iqm".into(),
+            target_qubits: 5,
+            compile_budget: Some(Duration::from_secs(2)),
+            ..Default::default()
+        };
+        let evaluator = Evaluator::new(config);
+
+        // Star coupling: q[0]-q[1] fits, q[1]-q[2] needs routing.
+        let report = evaluator.evaluate(BELL_QASM, &[]).unwrap();
+        assert_eq!(report.compilation.optimization_level, Some(1));
+        let qasm = "OPENQASM 3.0;\nqubit[3] q;\nh q[1];\ncx q[1], q[2];\n";
+        let report = evaluator.evaluate(qasm, &[]).unwrap();
+        assert_eq!(report.compilation.optimization_level, Some(2));
+        assert!(report.compilation.adaptive_reason.is_some());
+    }
+
     #[test]
     fn test_evaluator_with_orchestration() {
         let config = EvalConfig {
@@ -545,4 +722,44 @@ c = measure q;
         assert!(json.contains("critical_path"));
         assert!(json.contains("batchability"));
     }
+
+    struct GateCountPlugin;
+
+    impl EvalPlugin for GateCountPlugin {
+        fn namespace(&self) -> &'static str {
+            "site.gate-count"
+        }
+
+        fn schema_version(&self) -> &'static str {
+            "1.0.0"
+        }
+
+        fn analyze(&self, context: &PluginContext<'_>) -> EvalResult<serde_json::Value> {
+            Ok(serde_json::json!({
+                "target": context.target,
+                "ops": context.dag.num_ops(),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_evaluator_plugin_section() {
+        let config = EvalConfig {
+            target: "simulator".into(),
+            target_qubits: 5,
+            ..Default::default()
+        };
+        let mut evaluator = Evaluator::new(config);
+        evaluator.register_plugin(GateCountPlugin).unwrap();
+        assert!(evaluator.register_plugin(GateCountPlugin).is_err());
+
+        let report = evaluator.evaluate(BELL_QASM, &[]).unwrap();
+        let section = &report.extensions["site.gate-count"];
+        assert_eq!(section.schema_version, "1.0.0");
+        assert_eq!(section.data["target"], "simulator");
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: EvalReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.extensions, report.extensions);
+    }
 }
@@ -35,7 +35,7 @@ pub struct OrchestrationEffect {
     pub quantum_phases: usize,
     /// Number of classical phases.
     pub classical_phases: usize,
-    /// Critical path cost (abstract time units).
+    /// Critical path cost (microseconds).
     pub critical_path_cost: f64,
     /// Critical path length (number of nodes).
     pub critical_path_length: usize,
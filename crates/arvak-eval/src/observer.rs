@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use tracing::debug;
 
-use arvak_compile::{PassManager, PropertySet};
+use arvak_compile::{CompileTimings, PassManager, PassOutcome, PropertySet};
 use arvak_ir::CircuitDag;
 use arvak_ir::instruction::InstructionKind;
 
@@ -28,6 +28,10 @@ pub struct CircuitSnapshot {
     pub multi_qubit_gates: usize,
     /// Gate counts by name.
     pub gate_counts: BTreeMap<String, usize>,
+    /// Gate counts by name for gates a pass inserted, keyed by that pass
+    /// (the instruction's `origin` metadata), e.g. SWAPs added by routing.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inserted_by: BTreeMap<String, BTreeMap<String, usize>>,
 }
 
 impl CircuitSnapshot {
@@ -38,11 +42,19 @@ impl CircuitSnapshot {
         let mut two_qubit_gates = 0usize;
         let mut multi_qubit_gates = 0usize;
         let mut total_ops = 0usize;
+        let mut inserted_by: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
 
         for (_idx, inst) in dag.topological_ops() {
             total_ops += 1;
             if let InstructionKind::Gate(gate) = &inst.kind {
                 let name = gate.name().to_string();
+                if let Some(origin) = inst.origin() {
+                    *inserted_by
+                        .entry(origin.to_string())
+                        .or_default()
+                        .entry(name.clone())
+                        .or_insert(0) += 1;
+                }
                 *gate_counts.entry(name).or_insert(0) += 1;
 
                 match gate.num_qubits() {
@@ -60,6 +72,7 @@ impl CircuitSnapshot {
             two_qubit_gates,
             multi_qubit_gates,
             gate_counts,
+            inserted_by,
         }
     }
 }
@@ -106,10 +119,30 @@ pub struct PassRecord {
     pub delta: SnapshotDelta,
 }
 
+/// Wall-clock time of a single pass and its share of the time budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassTimingRecord {
+    /// Name of the pass.
+    pub pass_name: String,
+    /// Wall-clock time spent in the pass, in milliseconds.
+    pub elapsed_ms: f64,
+    /// Fraction of the time budget the pass consumed, if one was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_fraction: Option<f64>,
+    /// How the pass ended.
+    pub outcome: PassOutcome,
+}
+
 /// Result of observing the full compilation pipeline.
 pub struct CompilationObserver {
     /// Individual pass records.
     pub pass_records: Vec<PassRecord>,
+    /// Per-pass wall-clock times from the pass manager.
+    pub pass_timings: Vec<PassTimingRecord>,
+    /// Time budget of the pass manager, in milliseconds.
+    pub time_budget_ms: Option<f64>,
+    /// Seed the passes ran with, if any.
+    pub seed: Option<u64>,
     /// Snapshot of the circuit before any passes.
     pub initial_metrics: CircuitSnapshot,
     /// Snapshot of the circuit after all passes.
@@ -145,6 +178,18 @@ impl CompilationObserver {
 
         let after_all = CircuitSnapshot::capture(dag);
 
+        let timings = props.get::<CompileTimings>().cloned().unwrap_or_default();
+        let pass_timings = timings
+            .passes
+            .iter()
+            .map(|p| PassTimingRecord {
+                pass_name: p.name.clone(),
+                elapsed_ms: p.elapsed.as_secs_f64() * 1000.0,
+                budget_fraction: timings.budget_fraction(p.elapsed),
+                outcome: p.outcome,
+            })
+            .collect();
+
         // Record the full pipeline as a single observation.
         // Future versions can hook into individual passes.
         let delta = SnapshotDelta::compute(&before_all, &after_all);
@@ -165,6 +210,9 @@ impl CompilationObserver {
 
         Ok(Self {
             pass_records,
+            pass_timings,
+            time_budget_ms: timings.budget.map(|b| b.as_secs_f64() * 1000.0),
+            seed: props.seed,
             initial_metrics,
             final_metrics: after_all,
             final_dag: dag.clone(),
@@ -179,6 +227,11 @@ impl CompilationObserver {
             final_snapshot: self.final_metrics,
             overall_delta,
             passes: self.pass_records,
+            optimization_level: None,
+            adaptive_reason: None,
+            pass_timings: self.pass_timings,
+            time_budget_ms: self.time_budget_ms,
+            seed: self.seed,
         }
     }
 }
@@ -194,13 +247,29 @@ pub struct CompilationReport {
     pub overall_delta: SnapshotDelta,
     /// Per-pass records.
     pub passes: Vec<PassRecord>,
+    /// Optimization level the pipeline was built with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimization_level: Option<u8>,
+    /// Why the level was chosen, when it was chosen adaptively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_reason: Option<String>,
+    /// Wall-clock time of each pass.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pass_timings: Vec<PassTimingRecord>,
+    /// Time budget of the compilation, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_budget_ms: Option<f64>,
+    /// Seed the compilation ran with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arvak_compile::PassManagerBuilder;
+    use arvak_compile::{BasisGates, CouplingMap, PassManagerBuilder};
     use arvak_ir::{Circuit, QubitId};
+    use std::time::Duration;
 
     #[test]
     fn test_circuit_snapshot() {
@@ -223,6 +292,7 @@ mod tests {
             two_qubit_gates: 4,
             multi_qubit_gates: 0,
             gate_counts: BTreeMap::new(),
+            inserted_by: BTreeMap::new(),
         };
         let after = CircuitSnapshot {
             depth: 3,
@@ -231,6 +301,7 @@ mod tests {
             two_qubit_gates: 4,
             multi_qubit_gates: 0,
             gate_counts: BTreeMap::new(),
+            inserted_by: BTreeMap::new(),
         };
         let delta = SnapshotDelta::compute(&before, &after);
         assert_eq!(delta.depth_delta, -2);
@@ -252,5 +323,57 @@ mod tests {
 
         assert!(!observer.pass_records.is_empty());
         assert_eq!(observer.initial_metrics.depth, observer.final_metrics.depth);
+        assert_eq!(observer.pass_timings.len(), pm.len());
+        assert!(observer.time_budget_ms.is_none());
+        assert!(observer.seed.is_none());
+    }
+
+    #[test]
+    fn test_inserted_by_routing() {
+        // A triangle of interactions cannot be laid out on a line.
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(2)).unwrap();
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_target(CouplingMap::linear(3), BasisGates::iqm())
+            .build();
+
+        let mut dag = circuit.into_dag();
+        let observer = CompilationObserver::observe(&pm, &mut dag, &mut props).unwrap();
+
+        assert!(observer.initial_metrics.inserted_by.is_empty());
+        // The routing SWAP is translated to three CZs (plus 1q gates) that
+        // keep their attribution.
+        let inserted = &observer.final_metrics.inserted_by["SabreRouting"];
+        assert_eq!(inserted.get("cz"), Some(&3));
+    }
+
+    #[test]
+    fn test_compilation_observer_time_budget() {
+        let circuit = Circuit::bell().unwrap();
+        let (pm, mut props) = PassManagerBuilder::new()
+            .with_target(CouplingMap::linear(2), BasisGates::iqm())
+            .with_time_budget(Duration::from_secs(60))
+            .build();
+
+        let mut dag = circuit.into_dag();
+        let report = CompilationObserver::observe(&pm, &mut dag, &mut props)
+            .unwrap()
+            .into_report();
+
+        assert_eq!(report.time_budget_ms, Some(60_000.0));
+        let total: f64 = report
+            .pass_timings
+            .iter()
+            .filter_map(|p| p.budget_fraction)
+            .sum();
+        assert!(total < 1.0);
+        assert!(
+            report
+                .pass_timings
+                .iter()
+                .all(|p| p.outcome != PassOutcome::CutShort)
+        );
     }
 }
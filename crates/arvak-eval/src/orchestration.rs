@@ -18,12 +18,18 @@
 //!
 //! Quantum nodes carry circuit metrics (depth, gate counts).
 //! Classical nodes carry estimated processing time.
+//!
+//! Costs are wall-time estimates in microseconds from a [`TimingModel`]:
+//! a quantum phase costs its duration-weighted critical path, a classical
+//! phase the readout plus one classical processing step.
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-use arvak_ir::CircuitDag;
 use arvak_ir::instruction::InstructionKind;
+use arvak_ir::{CircuitDag, QubitId};
+
+use crate::timing::TimingModel;
 
 /// Type of node in the hybrid DAG.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,7 +64,7 @@ pub struct HybridNode {
     pub gate_count: Option<usize>,
     /// Number of qubits involved (quantum nodes only).
     pub num_qubits: Option<usize>,
-    /// Estimated cost in abstract time units.
+    /// Estimated duration in microseconds.
     pub estimated_cost: f64,
 }
 
@@ -98,7 +104,7 @@ pub struct HybridDag {
 pub struct CriticalPath {
     /// Indices of nodes on the critical path.
     pub node_indices: Vec<usize>,
-    /// Total cost of the critical path.
+    /// Total duration of the critical path in microseconds.
     pub total_cost: f64,
     /// Number of quantum phases on the critical path.
     pub quantum_phases: usize,
@@ -134,6 +140,9 @@ pub struct OrchestrationReport {
     pub batchability: BatchabilityAnalysis,
     /// Summary statistics.
     pub summary: OrchestrationSummary,
+    /// Timing model the costs were estimated with.
+    #[serde(default)]
+    pub timing: TimingModel,
 }
 
 /// High-level orchestration summary.
@@ -149,7 +158,7 @@ pub struct OrchestrationSummary {
     pub classical_phases: usize,
     /// DAG depth (longest path in node count).
     pub dag_depth: usize,
-    /// Estimated total cost.
+    /// Estimated total duration in microseconds.
     pub estimated_total_cost: f64,
 }
 
@@ -163,8 +172,20 @@ impl OrchestrationAnalyzer {
     /// linear DAG: \[quantum\] -> \[classical_readout\].
     ///
     /// Circuits with measurements mid-circuit produce interleaved phases.
+    /// Costs use the default [`TimingModel`]; see
+    /// [`analyze_with_timing`](Self::analyze_with_timing).
     pub fn analyze(circuit_dag: &CircuitDag, num_qubits: usize) -> OrchestrationReport {
-        let hybrid_dag = Self::build_hybrid_dag(circuit_dag, num_qubits);
+        Self::analyze_with_timing(circuit_dag, num_qubits, &TimingModel::default())
+    }
+
+    /// Analyze a compiled circuit DAG with the instruction durations of
+    /// `timing`.
+    pub fn analyze_with_timing(
+        circuit_dag: &CircuitDag,
+        num_qubits: usize,
+        timing: &TimingModel,
+    ) -> OrchestrationReport {
+        let hybrid_dag = Self::build_hybrid_dag(circuit_dag, num_qubits, timing);
         let critical_path = Self::compute_critical_path(&hybrid_dag);
         let batchability = Self::analyze_batchability(&hybrid_dag);
 
@@ -190,6 +211,7 @@ impl OrchestrationAnalyzer {
             critical_path,
             batchability,
             summary,
+            timing: timing.clone(),
         }
     }
 
@@ -198,47 +220,42 @@ impl OrchestrationAnalyzer {
     /// Strategy: Walk through the circuit in topological order. Group consecutive
     /// gate operations into quantum phases. Each measurement triggers a transition
     /// to a classical phase (readout/processing), followed by a new quantum phase
-    /// if more gates follow.
-    fn build_hybrid_dag(circuit_dag: &CircuitDag, num_qubits: usize) -> HybridDag {
+    /// if more gates follow. Each quantum phase is scheduled as soon as possible
+    /// per qubit, so its cost is the duration of its longest chain of operations.
+    fn build_hybrid_dag(
+        circuit_dag: &CircuitDag,
+        num_qubits: usize,
+        timing: &TimingModel,
+    ) -> HybridDag {
         let mut nodes = Vec::new();
         let mut edges = Vec::new();
 
-        let mut current_gate_count = 0usize;
-        let mut current_depth_estimate = 0usize;
-        let mut saw_measurement = false;
+        let mut phase = PhaseSchedule::default();
         let mut phase_index = 0usize;
+        let classical_cost = timing.measurement_us + timing.classical_step_us;
 
         // Walk through operations and segment into phases
         for (_idx, inst) in circuit_dag.topological_ops() {
             match &inst.kind {
                 InstructionKind::Gate(_) => {
-                    // If we just finished a classical phase, start new quantum phase
-                    if saw_measurement && current_gate_count == 0 {
-                        // Already pushed the classical node; this gate starts a new quantum phase
-                    }
-                    current_gate_count += 1;
-                    current_depth_estimate += 1;
-                    saw_measurement = false;
+                    phase.gate_count += 1;
+                    phase.schedule(&inst.qubits, timing.duration_us(inst), true);
                 }
                 InstructionKind::Measure => {
-                    if current_gate_count > 0 || nodes.is_empty() {
+                    if phase.gate_count > 0 || nodes.is_empty() {
                         // Finalize current quantum phase
                         let q_node = HybridNode {
                             index: phase_index,
                             kind: HybridNodeKind::Quantum,
                             label: format!("q{}", nodes.len() / 2),
-                            depth: Some(current_depth_estimate),
-                            gate_count: Some(current_gate_count),
+                            depth: Some(phase.depth()),
+                            gate_count: Some(phase.gate_count),
                             num_qubits: Some(num_qubits),
-                            estimated_cost: estimate_quantum_cost(
-                                current_depth_estimate,
-                                current_gate_count,
-                            ),
+                            estimated_cost: phase.duration_us(),
                         };
                         nodes.push(q_node);
                         phase_index += 1;
-                        current_gate_count = 0;
-                        current_depth_estimate = 0;
+                        phase = PhaseSchedule::default();
                     }
 
                     // Add classical readout phase
@@ -249,7 +266,7 @@ impl OrchestrationAnalyzer {
                         depth: None,
                         gate_count: None,
                         num_qubits: None,
-                        estimated_cost: CLASSICAL_READOUT_COST,
+                        estimated_cost: classical_cost,
                     };
 
                     // Edge: quantum -> classical (measurement results)
@@ -263,18 +280,22 @@ impl OrchestrationAnalyzer {
 
                     nodes.push(c_node);
                     phase_index += 1;
-                    saw_measurement = true;
                 }
                 InstructionKind::Barrier => {
-                    // Barriers don't create new phases but add to depth
-                    current_depth_estimate += 1;
+                    // Barriers synchronize their qubits without taking time
+                    phase.schedule(&inst.qubits, 0.0, false);
+                }
+                InstructionKind::NoiseChannel { .. } => {}
+                _ => {
+                    // Resets, delays, shuttles and feed-forward blocks take
+                    // time on their qubits but are not gates.
+                    phase.schedule(&inst.qubits, timing.duration_us(inst), true);
                 }
-                _ => {}
             }
         }
 
         // Finalize any remaining quantum operations
-        if current_gate_count > 0 {
+        if phase.gate_count > 0 {
             let q_node = HybridNode {
                 index: phase_index,
                 kind: HybridNodeKind::Quantum,
@@ -285,10 +306,10 @@ impl OrchestrationAnalyzer {
                         .filter(|n| n.kind == HybridNodeKind::Quantum)
                         .count()
                 ),
-                depth: Some(current_depth_estimate),
-                gate_count: Some(current_gate_count),
+                depth: Some(phase.depth()),
+                gate_count: Some(phase.gate_count),
                 num_qubits: Some(num_qubits),
-                estimated_cost: estimate_quantum_cost(current_depth_estimate, current_gate_count),
+                estimated_cost: phase.duration_us(),
             };
 
             // If there was a preceding classical phase, add parameter edge
@@ -461,20 +482,45 @@ impl OrchestrationAnalyzer {
     }
 }
 
-/// Abstract cost unit for quantum circuit execution.
-/// Based on depth and gate count as rough proxy for wall time.
-fn estimate_quantum_cost(depth: usize, gate_count: usize) -> f64 {
-    // Each gate layer ~1 microsecond, plus overhead
-    let gate_time = depth as f64 * 1.0;
-    let overhead = gate_count as f64 * 0.01;
-    gate_time + overhead + QUANTUM_SETUP_COST
+/// As-soon-as-possible schedule of the operations in one quantum phase.
+#[derive(Default)]
+struct PhaseSchedule {
+    /// Gates in the phase.
+    gate_count: usize,
+    /// Per qubit: layers so far and the time (µs) the qubit becomes free.
+    wires: BTreeMap<u32, (usize, f64)>,
 }
 
-/// Fixed cost for setting up a quantum execution (calibration, compilation, etc.).
-const QUANTUM_SETUP_COST: f64 = 10.0;
+impl PhaseSchedule {
+    /// Schedule an operation on `qubits` once all of them are free.
+    fn schedule(&mut self, qubits: &[QubitId], duration_us: f64, is_layer: bool) {
+        let (layer, start) = qubits
+            .iter()
+            .filter_map(|q| self.wires.get(&q.0))
+            .fold((0, 0.0f64), |(layer, time), &(l, t)| {
+                (layer.max(l), time.max(t))
+            });
+        let end = (layer + usize::from(is_layer), start + duration_us);
+        for &qubit in qubits {
+            self.wires.insert(qubit.0, end);
+        }
+    }
 
-/// Fixed cost for classical readout and basic post-processing.
-const CLASSICAL_READOUT_COST: f64 = 1.0;
+    fn depth(&self) -> usize {
+        self.wires
+            .values()
+            .map(|&(layer, _)| layer)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn duration_us(&self) -> f64 {
+        self.wires
+            .values()
+            .map(|&(_, time)| time)
+            .fold(0.0, f64::max)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -575,4 +621,23 @@ mod tests {
         assert!(report.critical_path.total_cost > 0.0);
         assert!(report.critical_path.quantum_phases >= 1);
     }
+
+    #[test]
+    fn test_critical_path_cost_in_microseconds() {
+        let mut circuit = Circuit::with_size("bell", 2, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+
+        let dag = circuit.into_dag();
+        let report = OrchestrationAnalyzer::analyze_with_timing(&dag, 2, &TimingModel::iqm());
+
+        // H (0.02 µs) then CX (0.04 µs), then readout (1.5 µs) and one
+        // classical step (1.0 µs).
+        let quantum = &report.dag.nodes[0];
+        assert_eq!(quantum.depth, Some(2));
+        assert!((quantum.estimated_cost - 0.06).abs() < 1e-9);
+        assert!((report.critical_path.total_cost - 2.56).abs() < 1e-9);
+        assert_eq!(report.timing.name, "iqm");
+    }
 }
@@ -0,0 +1,107 @@
+//! Plugin hooks: custom analyzers contributing report sections.
+//!
+//! Downstream crates implement [`EvalPlugin`] and register it with
+//! [`Evaluator::register_plugin`](crate::Evaluator::register_plugin). After
+//! the built-in modules have run, every plugin analyzes the compiled DAG and
+//! its output is stored in [`EvalReport::extensions`](crate::EvalReport)
+//! under the plugin's namespace, together with the plugin's own schema
+//! version:
+//!
+//! ```json
+//! "extensions": {
+//!   "lrz.compliance": { "schema_version": "1.0.0", "data": { ... } }
+//! }
+//! ```
+
+use arvak_hal::Capabilities;
+use arvak_ir::CircuitDag;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{EvalError, EvalResult};
+
+/// Maximum namespace length.
+const MAX_NAMESPACE_LEN: usize = 64;
+
+/// A custom analyzer that appends a section to the evaluation report.
+pub trait EvalPlugin: Send + Sync {
+    /// Namespace of the report section, e.g. `lrz.compliance`.
+    ///
+    /// Must be unique among registered plugins and consist of lowercase
+    /// ASCII letters, digits, `.`, `-` and `_`.
+    fn namespace(&self) -> &str;
+
+    /// Version of the section's schema, bumped when its layout changes.
+    fn schema_version(&self) -> &str;
+
+    /// Analyze the compiled circuit and produce the section's content.
+    fn analyze(&self, context: &PluginContext<'_>) -> EvalResult<serde_json::Value>;
+}
+
+/// What a plugin gets to see of an evaluation.
+#[derive(Debug)]
+pub struct PluginContext<'a> {
+    /// Evaluation profile name.
+    pub profile: &'a str,
+    /// Compilation target name.
+    pub target: &'a str,
+    /// Capabilities of the compilation target.
+    pub capabilities: &'a Capabilities,
+    /// Number of qubits in the input circuit.
+    pub num_qubits: usize,
+    /// The circuit after compilation.
+    pub dag: &'a CircuitDag,
+}
+
+/// A plugin-contributed report section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginSection {
+    /// Schema version reported by the plugin.
+    pub schema_version: String,
+    /// Section content.
+    pub data: serde_json::Value,
+}
+
+/// Check that a plugin namespace is well-formed.
+pub(crate) fn validate_namespace(namespace: &str) -> EvalResult<()> {
+    let valid = !namespace.is_empty()
+        && namespace.len() <= MAX_NAMESPACE_LEN
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(EvalError::Plugin(format!(
+            "invalid namespace '{namespace}': use 1-{MAX_NAMESPACE_LEN} characters from [a-z0-9._-]"
+        )))
+    }
+}
+
+/// Run a plugin and wrap its output in a section.
+pub(crate) fn run_plugin(
+    plugin: &dyn EvalPlugin,
+    context: &PluginContext<'_>,
+) -> EvalResult<PluginSection> {
+    let data = plugin
+        .analyze(context)
+        .map_err(|e| EvalError::Plugin(format!("plugin '{}' failed: {e}", plugin.namespace())))?;
+    Ok(PluginSection {
+        schema_version: plugin.schema_version().to_string(),
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_namespace() {
+        assert!(validate_namespace("lrz.compliance").is_ok());
+        assert!(validate_namespace("site_checks-v2").is_ok());
+        assert!(validate_namespace("").is_err());
+        assert!(validate_namespace("LRZ").is_err());
+        assert!(validate_namespace("lrz compliance").is_err());
+        assert!(validate_namespace(&"a".repeat(MAX_NAMESPACE_LEN + 1)).is_err());
+    }
+}
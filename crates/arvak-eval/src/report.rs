@@ -2,6 +2,10 @@
 //!
 //! The top-level report combining all evaluation module outputs.
 
+use std::collections::BTreeMap;
+
+use arvak_compile::passes::ErrorBudgetReport;
+use arvak_hal::Provenance;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +15,7 @@ use crate::input::InputReport;
 use crate::metrics::AggregatedMetrics;
 use crate::observer::CompilationReport;
 use crate::orchestration::OrchestrationReport;
+use crate::plugin::PluginSection;
 use crate::reproducibility::ReproducibilityInfo;
 use crate::scheduler_context::SchedulerFitness;
 
@@ -29,6 +34,10 @@ pub struct EvalReport {
     pub compilation: CompilationReport,
     /// Aggregated metrics.
     pub metrics: AggregatedMetrics,
+    /// Ranked error budget of the compiled circuit (present when a noise
+    /// profile is configured).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_budget: Option<ErrorBudgetReport>,
     /// Orchestration analysis (present when --orchestration is used).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub orchestration: Option<OrchestrationReport>,
@@ -41,6 +50,13 @@ pub struct EvalReport {
     /// Benchmark circuit info (present when --benchmark is used).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub benchmark: Option<BenchmarkCircuit>,
+    /// Content-hash ids of the evaluated source and its compiled circuit,
+    /// shared with compiled artifacts, jobs and results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Sections contributed by registered plugins, keyed by namespace.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extensions: BTreeMap<String, PluginSection>,
     /// Reproducibility information.
     pub reproducibility: ReproducibilityInfo,
 }
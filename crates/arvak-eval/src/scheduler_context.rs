@@ -1,12 +1,15 @@
 //! Scheduler Context: LRZ reference constraints, walltime mapping, batch limits.
 //!
 //! Models the constraints of HPC scheduler environments (SLURM/PBS)
-//! that affect quantum circuit execution planning.
+//! that affect quantum circuit execution planning, including a rough
+//! node-hour and energy estimate for the host job.
 //!
 //! Reference: LRZ SuperMUC-NG / LUMI `q_fiqci` partition.
 
 use serde::{Deserialize, Serialize};
 
+use crate::structure::WorkloadClass;
+
 /// Scheduler constraints for a target HPC environment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchedulerConstraints {
@@ -26,6 +29,12 @@ pub struct SchedulerConstraints {
     pub supports_array_jobs: bool,
     /// Estimated queue wait time in seconds (typical).
     pub typical_queue_wait_seconds: u64,
+    /// Typical power draw of one host node in watts (0 = not modeled).
+    #[serde(default)]
+    pub node_power_watts: f64,
+    /// Grid carbon intensity in g CO₂e/kWh (0 = not modeled).
+    #[serde(default)]
+    pub carbon_intensity_g_per_kwh: f64,
 }
 
 impl SchedulerConstraints {
@@ -42,6 +51,8 @@ impl SchedulerConstraints {
             max_qubits: 20,
             supports_array_jobs: true,
             typical_queue_wait_seconds: 120,
+            node_power_watts: 500.0,
+            carbon_intensity_g_per_kwh: 350.0, // German grid average
         }
     }
 
@@ -58,6 +69,8 @@ impl SchedulerConstraints {
             max_qubits: 5,
             supports_array_jobs: true,
             typical_queue_wait_seconds: 60,
+            node_power_watts: 500.0,
+            carbon_intensity_g_per_kwh: 20.0, // Hydropower supply
         }
     }
 
@@ -72,6 +85,8 @@ impl SchedulerConstraints {
             max_qubits: 30,
             supports_array_jobs: false,
             typical_queue_wait_seconds: 0,
+            node_power_watts: 0.0,
+            carbon_intensity_g_per_kwh: 0.0,
         }
     }
 }
@@ -91,6 +106,20 @@ pub struct WalltimeEstimate {
     pub batch_capacity: u32,
 }
 
+/// Estimated resource usage of the host job for one circuit execution.
+///
+/// Assumes a single node for the estimated total runtime. Sites report the
+/// measured values through SLURM accounting after the job has run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnergyEstimate {
+    /// Node-hours consumed.
+    pub node_hours: f64,
+    /// Energy in kilowatt-hours.
+    pub energy_kwh: f64,
+    /// Emissions in kg CO₂-equivalent.
+    pub co2_kg: f64,
+}
+
 /// Scheduler fitness assessment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchedulerFitness {
@@ -98,30 +127,74 @@ pub struct SchedulerFitness {
     pub constraints: SchedulerConstraints,
     /// Walltime estimate.
     pub walltime: WalltimeEstimate,
+    /// Energy estimate for the host job.
+    #[serde(default)]
+    pub energy: EnergyEstimate,
     /// Whether the circuit's qubit count fits the device.
     pub qubits_fit: bool,
     /// Whether batching is recommended for this workload.
     pub batch_recommended: bool,
     /// Recommended batch size (circuits per job).
     pub recommended_batch_size: u32,
+    /// Workload class of the circuit, if known.
+    #[serde(default)]
+    pub workload: WorkloadClass,
     /// Overall fitness (0.0 = incompatible, 1.0 = perfect fit).
     pub fitness_score: f64,
     /// Human-readable assessment.
     pub assessment: String,
 }
 
+impl SchedulerFitness {
+    /// Adjust the assessment for the circuit's workload class.
+    ///
+    /// Variational workloads re-run the circuit once per optimizer step, so
+    /// each job slot should hold as many iterations as fit rather than
+    /// queueing one job per step.
+    #[must_use]
+    pub fn with_workload(mut self, workload: WorkloadClass) -> Self {
+        self.workload = workload;
+        if workload.is_variational() && self.qubits_fit && self.walltime.fits_walltime {
+            self.batch_recommended = true;
+            self.recommended_batch_size = self.walltime.batch_capacity;
+            self.assessment.push_str(&format!(
+                ". {} workload: run up to {} optimizer iterations per job slot \
+                 instead of queueing each (~{}s wait)",
+                workload, self.walltime.batch_capacity, self.constraints.typical_queue_wait_seconds
+            ));
+        }
+        self
+    }
+}
+
 /// Context for scheduler-aware evaluation.
 pub struct SchedulerContext;
 
 impl SchedulerContext {
     /// Evaluate scheduler fitness for a circuit workload.
+    ///
+    /// Approximates the circuit's execution time as one microsecond per
+    /// layer; use [`Self::evaluate_with_circuit_time`] when a duration-aware
+    /// estimate (e.g. the orchestration critical path) is available.
     pub fn evaluate(
         num_qubits: usize,
         circuit_depth: usize,
-        total_ops: usize,
+        // TODO: Incorporate total_ops into walltime estimate
+        _total_ops: usize,
         constraints: &SchedulerConstraints,
     ) -> SchedulerFitness {
-        let walltime = Self::estimate_walltime(circuit_depth, total_ops, constraints);
+        Self::evaluate_with_circuit_time(num_qubits, circuit_depth as f64 * 1e-6, constraints)
+    }
+
+    /// Evaluate scheduler fitness given the circuit's estimated execution
+    /// time per shot, in seconds.
+    pub fn evaluate_with_circuit_time(
+        num_qubits: usize,
+        circuit_seconds: f64,
+        constraints: &SchedulerConstraints,
+    ) -> SchedulerFitness {
+        let walltime = Self::estimate_walltime(circuit_seconds, constraints);
+        let energy = Self::estimate_energy(&walltime, constraints);
         let qubits_fit = num_qubits as u32 <= constraints.max_qubits;
 
         let batch_recommended = walltime.batch_capacity > 1;
@@ -139,9 +212,11 @@ impl SchedulerContext {
         SchedulerFitness {
             constraints: constraints.clone(),
             walltime,
+            energy,
             qubits_fit,
             batch_recommended,
             recommended_batch_size,
+            workload: WorkloadClass::Unknown,
             fitness_score,
             assessment,
         }
@@ -149,17 +224,15 @@ impl SchedulerContext {
 
     /// Estimate walltime for a circuit.
     fn estimate_walltime(
-        circuit_depth: usize,
-        // TODO: Incorporate total_ops into walltime estimate
-        _total_ops: usize,
+        circuit_seconds: f64,
         constraints: &SchedulerConstraints,
     ) -> WalltimeEstimate {
         // Rough model:
-        // - Each gate layer: ~1 microsecond on real hardware
+        // - Circuit execution: as estimated by the caller
         // - Compilation overhead: ~5 seconds
         // - Setup/calibration: ~10 seconds
         // - Readout: ~1 second per 1000 shots (assume 1024)
-        let gate_time_seconds = circuit_depth as f64 * 1e-6;
+        let gate_time_seconds = circuit_seconds;
         let compilation_overhead = 5.0;
         let setup_overhead = 10.0;
         let readout_time = 1.0;
@@ -193,6 +266,22 @@ impl SchedulerContext {
         }
     }
 
+    /// Estimate node-hours and energy for one node over the estimated runtime.
+    fn estimate_energy(
+        walltime: &WalltimeEstimate,
+        constraints: &SchedulerConstraints,
+    ) -> EnergyEstimate {
+        let node_hours = walltime.total_seconds / 3600.0;
+        let energy_kwh = node_hours * constraints.node_power_watts / 1000.0;
+        let co2_kg = energy_kwh * constraints.carbon_intensity_g_per_kwh / 1000.0;
+
+        EnergyEstimate {
+            node_hours,
+            energy_kwh,
+            co2_kg,
+        }
+    }
+
     /// Compute an overall fitness score.
     fn compute_fitness(
         qubits_fit: bool,
@@ -312,6 +401,34 @@ mod tests {
         assert!(fitness.walltime.fits_walltime);
     }
 
+    #[test]
+    fn test_energy_estimate() {
+        let constraints = SchedulerConstraints::lrz();
+        let fitness = SchedulerContext::evaluate(5, 10, 15, &constraints);
+
+        let expected_hours = fitness.walltime.total_seconds / 3600.0;
+        assert!((fitness.energy.node_hours - expected_hours).abs() < 1e-12);
+        assert!((fitness.energy.energy_kwh - expected_hours * 0.5).abs() < 1e-12);
+        assert!(fitness.energy.co2_kg > 0.0);
+
+        let sim = SchedulerContext::evaluate(5, 10, 15, &SchedulerConstraints::simulator());
+        assert_eq!(sim.energy.energy_kwh, 0.0);
+    }
+
+    #[test]
+    fn test_variational_workload_batches_iterations() {
+        let constraints = SchedulerConstraints::lrz();
+        let fitness = SchedulerContext::evaluate(5, 10, 15, &constraints);
+        let sampling = fitness.clone().with_workload(WorkloadClass::Sampling);
+        assert_eq!(sampling.assessment, fitness.assessment);
+
+        let vqe = fitness.with_workload(WorkloadClass::VqeLike);
+        assert_eq!(vqe.workload, WorkloadClass::VqeLike);
+        assert!(vqe.batch_recommended);
+        assert_eq!(vqe.recommended_batch_size, vqe.walltime.batch_capacity);
+        assert!(vqe.assessment.contains("vqe-like workload"));
+    }
+
     #[test]
     fn test_fitness_score_range() {
         let constraints = SchedulerConstraints::lrz();
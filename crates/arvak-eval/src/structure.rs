@@ -0,0 +1,466 @@
+//! Structure Module: Clifford content, layer repetition, and workload classification.
+//!
+//! Looks past gate counts at how the input circuit is built: how much of it
+//! is Clifford, whether it repeats a layer of gates, whether its parameters
+//! are still free, and where it measures. From these it classifies the
+//! workload, so pass selection and scheduler heuristics can treat an
+//! iterated variational ansatz differently from a one-shot sampling job.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use arvak_ir::instruction::InstructionKind;
+use arvak_ir::{Circuit, DagNode, GateKind, ParameterExpression, QubitId, StandardGate};
+
+/// Longest layer (in gates) searched for repetitions.
+const MAX_LAYER_GATES: usize = 1024;
+
+/// Whether the circuit's gate parameters are bound to numbers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterBinding {
+    /// No gate takes a parameter.
+    #[default]
+    Unparameterized,
+    /// All gate parameters are numeric.
+    Bound,
+    /// Some gate parameters are free symbols.
+    Symbolic,
+}
+
+/// Where the circuit measures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeasurementPattern {
+    /// No measurements.
+    #[default]
+    None,
+    /// Every qubit is measured at the end.
+    Full,
+    /// Some qubits are measured at the end.
+    Partial,
+    /// Operations follow a measurement on its qubit or bit.
+    MidCircuit,
+}
+
+/// Coarse workload class inferred from circuit structure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkloadClass {
+    /// Parameterized rotations and entanglers in a repeated or symbolic
+    /// ansatz, as in VQE.
+    VqeLike,
+    /// Hadamard initialization, ZZ cost terms and an X-rotation mixer.
+    QaoaLike,
+    /// A fixed circuit whose measurement outcomes are sampled.
+    Sampling,
+    /// None of the above.
+    #[default]
+    Unknown,
+}
+
+impl WorkloadClass {
+    /// Whether the circuit is re-run for each step of a classical optimizer.
+    pub fn is_variational(self) -> bool {
+        matches!(self, WorkloadClass::VqeLike | WorkloadClass::QaoaLike)
+    }
+}
+
+impl std::fmt::Display for WorkloadClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkloadClass::VqeLike => write!(f, "vqe-like"),
+            WorkloadClass::QaoaLike => write!(f, "qaoa-like"),
+            WorkloadClass::Sampling => write!(f, "sampling"),
+            WorkloadClass::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// A block of gates repeated back to back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepeatedLayer {
+    /// Gates in one repetition.
+    pub gates_per_layer: usize,
+    /// Number of consecutive repetitions.
+    pub repetitions: usize,
+    /// Fraction of all gates covered by the repetitions.
+    pub coverage: f64,
+}
+
+/// Structural features of a circuit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CircuitStructure {
+    /// Number of Clifford gates (including rotations by multiples of π/2).
+    pub clifford_gates: usize,
+    /// Fraction of gates that are Clifford (0 for a circuit without gates).
+    pub clifford_fraction: f64,
+    /// Whether gate parameters are bound.
+    pub parameter_binding: ParameterBinding,
+    /// Names of free parameters.
+    pub free_parameters: Vec<String>,
+    /// Longest back-to-back repetition of a gate layer, ignoring parameter
+    /// values.
+    pub repeated_layer: Option<RepeatedLayer>,
+    /// Where the circuit measures.
+    pub measurement: MeasurementPattern,
+    /// Number of distinct qubits measured.
+    pub measured_qubits: usize,
+    /// Inferred workload class.
+    pub workload: WorkloadClass,
+}
+
+impl CircuitStructure {
+    /// Analyze the structure of a circuit.
+    pub fn analyze(circuit: &Circuit) -> Self {
+        let dag = circuit.dag();
+
+        // Gates in program order: node indices follow insertion order in a
+        // freshly built circuit, and the layer search needs the order the
+        // author wrote, not an arbitrary topological one.
+        let mut ops: Vec<_> = dag.topological_ops().collect();
+        ops.sort_by_key(|(idx, _)| *idx);
+
+        let mut gates = Vec::new();
+        let mut clifford_gates = 0usize;
+        let mut has_parameters = false;
+        let mut measured = HashSet::new();
+        let mut mid_circuit = false;
+
+        for (idx, inst) in &ops {
+            match &inst.kind {
+                InstructionKind::Gate(gate) => {
+                    if let GateKind::Standard(std_gate) = &gate.kind {
+                        if is_clifford(std_gate) {
+                            clifford_gates += 1;
+                        }
+                    }
+                    has_parameters |= !gate.parameters().is_empty();
+                    mid_circuit |= gate.condition.is_some();
+                    gates.push((gate, inst.qubits.as_slice()));
+                }
+                InstructionKind::Measure => {
+                    measured.extend(inst.qubits.iter().copied());
+                    mid_circuit |= dag.graph().neighbors(*idx).any(|next| {
+                        matches!(&dag.graph()[next],
+                            DagNode::Op(op) if !matches!(op.kind, InstructionKind::Barrier))
+                    });
+                }
+                InstructionKind::IfElse { .. } | InstructionKind::WhileLoop { .. } => {
+                    mid_circuit = true;
+                }
+                _ => {}
+            }
+        }
+
+        let free_parameters = circuit.parameters();
+        let parameter_binding = if !free_parameters.is_empty() {
+            ParameterBinding::Symbolic
+        } else if has_parameters {
+            ParameterBinding::Bound
+        } else {
+            ParameterBinding::Unparameterized
+        };
+
+        let measurement = if mid_circuit && !measured.is_empty() {
+            MeasurementPattern::MidCircuit
+        } else if measured.is_empty() {
+            MeasurementPattern::None
+        } else if measured.len() == circuit.num_qubits() {
+            MeasurementPattern::Full
+        } else {
+            MeasurementPattern::Partial
+        };
+
+        let shapes: Vec<_> = gates
+            .iter()
+            .map(|(gate, qubits)| (gate.name(), *qubits))
+            .collect();
+        let repeated_layer = find_repeated_layer(&shapes);
+
+        let mut structure = Self {
+            clifford_gates,
+            clifford_fraction: if gates.is_empty() {
+                0.0
+            } else {
+                clifford_gates as f64 / gates.len() as f64
+            },
+            parameter_binding,
+            free_parameters,
+            repeated_layer,
+            measurement,
+            measured_qubits: measured.len(),
+            workload: WorkloadClass::Unknown,
+        };
+        structure.workload = classify(&structure, circuit, &gates);
+        structure
+    }
+}
+
+/// Whether a standard gate is a Clifford gate for its current parameters.
+fn is_clifford(gate: &StandardGate) -> bool {
+    match gate {
+        StandardGate::I
+        | StandardGate::X
+        | StandardGate::Y
+        | StandardGate::Z
+        | StandardGate::H
+        | StandardGate::S
+        | StandardGate::Sdg
+        | StandardGate::SX
+        | StandardGate::SXdg
+        | StandardGate::CX
+        | StandardGate::CY
+        | StandardGate::CZ
+        | StandardGate::Swap
+        | StandardGate::ISwap
+        | StandardGate::ECR => true,
+        StandardGate::Rx(p)
+        | StandardGate::Ry(p)
+        | StandardGate::Rz(p)
+        | StandardGate::P(p)
+        | StandardGate::RXX(p)
+        | StandardGate::RYY(p)
+        | StandardGate::RZZ(p) => is_multiple_of(p, FRAC_PI_2),
+        StandardGate::CP(p) => is_multiple_of(p, PI),
+        StandardGate::U(a, b, c) => [a, b, c].iter().all(|p| is_multiple_of(p, FRAC_PI_2)),
+        StandardGate::PRX(a, b) => is_multiple_of(a, FRAC_PI_2) && is_multiple_of(b, FRAC_PI_2),
+        _ => false,
+    }
+}
+
+/// Whether a bound parameter is an integer multiple of `step`.
+fn is_multiple_of(param: &ParameterExpression, step: f64) -> bool {
+    param
+        .as_f64()
+        .is_some_and(|v| ((v / step).round() * step - v).abs() < 1e-9)
+}
+
+/// Whether a gate is a rotation that is not a Clifford, so it carries
+/// variational freedom.
+fn is_variational_rotation(gate: &StandardGate) -> bool {
+    !gate.parameters().is_empty() && !is_clifford(gate)
+}
+
+/// Find the longest back-to-back repetition of a block of at least two
+/// gates, comparing gate names and operands.
+///
+/// For each period `p`, a run of positions where `shapes[i] == shapes[i + p]`
+/// of length `len` is `len / p + 1` repetitions of the block at its start.
+fn find_repeated_layer<T: PartialEq>(shapes: &[T]) -> Option<RepeatedLayer> {
+    let n = shapes.len();
+    let mut best: Option<(usize, usize)> = None;
+
+    for period in 1..=(n / 2).min(MAX_LAYER_GATES) {
+        let mut run = 0;
+        for i in 0..=(n - period) {
+            if i + period < n && shapes[i] == shapes[i + period] {
+                run += 1;
+                continue;
+            }
+            let repetitions = run / period + 1;
+            let covered = repetitions * period;
+            if repetitions >= 2 && best.is_none_or(|(p, r)| covered > p * r) {
+                best = Some((period, repetitions));
+            }
+            run = 0;
+        }
+    }
+
+    // A single gate repeated is not a layer.
+    best.filter(|&(period, _)| period >= 2)
+        .map(|(gates_per_layer, repetitions)| RepeatedLayer {
+            gates_per_layer,
+            repetitions,
+            coverage: (gates_per_layer * repetitions) as f64 / n as f64,
+        })
+}
+
+/// Classify the workload from its structure.
+///
+/// - QAOA-like: every used qubit starts with a Hadamard, every qubit gets an
+///   X-rotation mixer, and there are ZZ cost terms (`rzz`, or `rz` between
+///   entanglers).
+/// - VQE-like: non-Clifford rotations and entanglers, with free parameters
+///   or a repeated layer.
+/// - Sampling: anything else that is measured.
+fn classify(
+    structure: &CircuitStructure,
+    circuit: &Circuit,
+    gates: &[(&arvak_ir::Gate, &[QubitId])],
+) -> WorkloadClass {
+    let mut first_gate: HashMap<QubitId, &str> = HashMap::new();
+    let mut mixed = HashSet::new();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut rotations = 0usize;
+    let mut entanglers = 0usize;
+
+    for (gate, qubits) in gates {
+        let name = gate.name();
+        for q in *qubits {
+            first_gate.entry(*q).or_insert(name);
+        }
+        *counts.entry(name).or_default() += 1;
+        if let GateKind::Standard(std_gate) = &gate.kind {
+            if is_variational_rotation(std_gate) {
+                rotations += 1;
+                if matches!(std_gate, StandardGate::Rx(_)) {
+                    mixed.extend(qubits.iter().copied());
+                }
+            }
+        }
+        if qubits.len() >= 2 {
+            entanglers += 1;
+        }
+    }
+    let count = |name: &str| counts.get(name).copied().unwrap_or(0);
+
+    let hadamard_init = !first_gate.is_empty() && first_gate.values().all(|&name| name == "h");
+    let has_mixer = circuit.num_qubits() > 0 && mixed.len() == circuit.num_qubits();
+    let has_cost = count("rzz") > 0
+        || count("rz") > 0 && (count("cx") > 0 || count("cz") > 0)
+        || count("cp") > 0
+        || count("crz") > 0;
+
+    if hadamard_init && has_mixer && has_cost {
+        WorkloadClass::QaoaLike
+    } else if rotations > 0
+        && entanglers > 0
+        && (structure.parameter_binding == ParameterBinding::Symbolic
+            || structure.repeated_layer.is_some())
+    {
+        WorkloadClass::VqeLike
+    } else if structure.measurement != MeasurementPattern::None {
+        WorkloadClass::Sampling
+    } else {
+        WorkloadClass::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn structure(qasm: &str) -> CircuitStructure {
+        CircuitStructure::analyze(&arvak_qasm3::parse(qasm).unwrap())
+    }
+
+    #[test]
+    fn test_clifford_fraction() {
+        let s = structure(
+            r"
+OPENQASM 3.0;
+qubit[2] q;
+h q[0];
+cx q[0], q[1];
+rz(pi/2) q[1];
+t q[0];
+",
+        );
+        assert_eq!(s.clifford_gates, 3);
+        assert!((s.clifford_fraction - 0.75).abs() < 1e-12);
+        assert_eq!(s.parameter_binding, ParameterBinding::Bound);
+    }
+
+    #[test]
+    fn test_ghz_is_sampling() {
+        let s = structure(
+            r"
+OPENQASM 3.0;
+qubit[3] q;
+bit[3] c;
+h q[0];
+cx q[0], q[1];
+cx q[1], q[2];
+c = measure q;
+",
+        );
+        assert_eq!(s.clifford_fraction, 1.0);
+        assert_eq!(s.parameter_binding, ParameterBinding::Unparameterized);
+        assert_eq!(s.measurement, MeasurementPattern::Full);
+        assert_eq!(s.measured_qubits, 3);
+        assert!(s.repeated_layer.is_none());
+        assert_eq!(s.workload, WorkloadClass::Sampling);
+    }
+
+    #[test]
+    fn test_vqe_ansatz() {
+        let s = structure(
+            r"
+OPENQASM 3.0;
+qubit[3] q;
+bit[3] c;
+ry(theta_0) q[0];
+ry(theta_1) q[1];
+ry(theta_2) q[2];
+cx q[0], q[1];
+cx q[1], q[2];
+ry(theta_3) q[0];
+ry(theta_4) q[1];
+ry(theta_5) q[2];
+cx q[0], q[1];
+cx q[1], q[2];
+c = measure q;
+",
+        );
+        assert_eq!(s.parameter_binding, ParameterBinding::Symbolic);
+        assert_eq!(s.free_parameters.len(), 6);
+        assert_eq!(
+            s.repeated_layer,
+            Some(RepeatedLayer {
+                gates_per_layer: 5,
+                repetitions: 2,
+                coverage: 1.0,
+            })
+        );
+        assert_eq!(s.workload, WorkloadClass::VqeLike);
+        assert!(s.workload.is_variational());
+    }
+
+    #[test]
+    fn test_qaoa() {
+        let s = structure(
+            r"
+OPENQASM 3.0;
+qubit[3] q;
+bit[3] c;
+h q[0];
+h q[1];
+h q[2];
+rzz(0.4) q[0], q[1];
+rzz(0.4) q[1], q[2];
+rx(0.7) q[0];
+rx(0.7) q[1];
+rx(0.7) q[2];
+rzz(0.9) q[0], q[1];
+rzz(0.9) q[1], q[2];
+rx(0.3) q[0];
+rx(0.3) q[1];
+rx(0.3) q[2];
+c = measure q;
+",
+        );
+        let layer = s.repeated_layer.as_ref().unwrap();
+        assert_eq!((layer.gates_per_layer, layer.repetitions), (5, 2));
+        assert_eq!(s.workload, WorkloadClass::QaoaLike);
+    }
+
+    #[test]
+    fn test_mid_circuit_measurement() {
+        let s = structure(
+            r"
+OPENQASM 3.0;
+qubit[2] q;
+bit[2] c;
+h q[0];
+c[0] = measure q[0];
+if (c[0]) x q[1];
+",
+        );
+        assert_eq!(s.measurement, MeasurementPattern::MidCircuit);
+
+        let s = structure("OPENQASM 3.0; qubit[2] q; bit c; h q[0]; c = measure q[0];");
+        assert_eq!(s.measurement, MeasurementPattern::Partial);
+    }
+}
@@ -0,0 +1,163 @@
+//! Timing Model: instruction durations for orchestration cost estimates.
+//!
+//! Converts a compiled circuit into wall time so that critical-path costs,
+//! batchability, and scheduler fitness are expressed in microseconds rather
+//! than abstract units. Presets use published typical values for each
+//! target; they are estimates, not calibration data.
+
+use serde::{Deserialize, Serialize};
+
+use arvak_ir::instruction::{Instruction, InstructionKind};
+
+/// Typical durations of circuit instructions on a target, in microseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimingModel {
+    /// Name of the timing profile.
+    pub name: String,
+    /// Single-qubit gate duration.
+    pub single_qubit_gate_us: f64,
+    /// Two-qubit gate duration (also charged per qubit pair of larger gates).
+    pub two_qubit_gate_us: f64,
+    /// Measurement (readout) duration.
+    pub measurement_us: f64,
+    /// Active reset duration.
+    pub reset_us: f64,
+    /// Classical processing step between quantum phases (feed-forward latency).
+    pub classical_step_us: f64,
+    /// Whether Z rotations are virtual (frame changes, zero duration).
+    pub virtual_z: bool,
+    /// Length of one device time unit (`dt`) in microseconds. When set,
+    /// gates annotated with a duration and delays use it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dt_us: Option<f64>,
+}
+
+impl TimingModel {
+    /// IQM superconducting devices (Garnet-class).
+    pub fn iqm() -> Self {
+        Self {
+            name: "iqm".into(),
+            single_qubit_gate_us: 0.02,
+            two_qubit_gate_us: 0.04,
+            measurement_us: 1.5,
+            reset_us: 1.0,
+            classical_step_us: 1.0,
+            virtual_z: true,
+            dt_us: None,
+        }
+    }
+
+    /// IBM Heron devices.
+    pub fn ibm() -> Self {
+        Self {
+            name: "ibm".into(),
+            single_qubit_gate_us: 0.036,
+            two_qubit_gate_us: 0.068,
+            measurement_us: 1.56,
+            reset_us: 1.0,
+            classical_step_us: 1.0,
+            virtual_z: true,
+            dt_us: Some(2.0 / 9.0 / 1000.0),
+        }
+    }
+
+    /// Timing profile for an evaluation target name (iqm, ibm, simulator).
+    ///
+    /// Simulators have no physical timing; they use the IQM profile so the
+    /// estimates stay comparable across targets.
+    pub fn for_target(target: &str) -> Self {
+        match target {
+            "ibm" => Self::ibm(),
+            _ => Self::iqm(),
+        }
+    }
+
+    /// Estimated duration of an instruction in microseconds.
+    ///
+    /// Barriers and noise channels take no time. If/else blocks are charged
+    /// their more expensive branch, executed sequentially.
+    pub fn duration_us(&self, instruction: &Instruction) -> f64 {
+        match &instruction.kind {
+            InstructionKind::Gate(gate) => {
+                if let Some((dt, dt_us)) = gate.duration.zip(self.dt_us) {
+                    return dt as f64 * dt_us;
+                }
+                if self.virtual_z
+                    && matches!(gate.name(), "rz" | "z" | "s" | "sdg" | "t" | "tdg" | "p")
+                {
+                    return 0.0;
+                }
+                match instruction.qubits.len() {
+                    0 => 0.0,
+                    1 => self.single_qubit_gate_us,
+                    n => (n - 1) as f64 * self.two_qubit_gate_us,
+                }
+            }
+            InstructionKind::Measure => self.measurement_us,
+            InstructionKind::Reset => self.reset_us,
+            InstructionKind::Delay { duration } => {
+                self.dt_us.map_or(0.0, |dt_us| *duration as f64 * dt_us)
+            }
+            InstructionKind::Shuttle { .. } => self.two_qubit_gate_us,
+            InstructionKind::IfElse { .. } => instruction
+                .blocks()
+                .into_iter()
+                .map(|block| block.iter().map(|inst| self.duration_us(inst)).sum::<f64>())
+                .fold(0.0, f64::max),
+            // Worst case: every allowed iteration runs.
+            InstructionKind::WhileLoop {
+                body,
+                max_iterations,
+                ..
+            } => {
+                f64::from(*max_iterations)
+                    * body.iter().map(|inst| self.duration_us(inst)).sum::<f64>()
+            }
+            InstructionKind::Barrier | InstructionKind::NoiseChannel { .. } => 0.0,
+        }
+    }
+}
+
+impl Default for TimingModel {
+    fn default() -> Self {
+        Self::iqm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::{ClbitId, QubitId, StandardGate};
+
+    #[test]
+    fn test_instruction_durations() {
+        let model = TimingModel::iqm();
+        let h = Instruction::single_qubit_gate(StandardGate::H, QubitId(0));
+        let rz = Instruction::single_qubit_gate(StandardGate::Rz(0.5.into()), QubitId(0));
+        let cz = Instruction::two_qubit_gate(StandardGate::CZ, QubitId(0), QubitId(1));
+        let measure = Instruction::measure(QubitId(0), ClbitId(0));
+
+        assert!((model.duration_us(&h) - 0.02).abs() < 1e-12);
+        assert!(model.duration_us(&rz).abs() < 1e-12);
+        assert!((model.duration_us(&cz) - 0.04).abs() < 1e-12);
+        assert!((model.duration_us(&measure) - 1.5).abs() < 1e-12);
+        // Without a dt length, delays cannot be converted.
+        assert!(
+            model
+                .duration_us(&Instruction::delay(QubitId(0), 900))
+                .abs()
+                < 1e-12
+        );
+
+        let ibm = TimingModel::for_target("ibm");
+        assert!((ibm.duration_us(&Instruction::delay(QubitId(0), 900)) - 0.2).abs() < 1e-9);
+
+        // Loops count every allowed iteration.
+        let retry = Instruction::while_loop(
+            arvak_ir::ClassicalCondition::on_clbit(ClbitId(0), 1),
+            vec![h, measure],
+            4,
+        );
+        assert!((model.duration_us(&retry) - 4.0 * 1.52).abs() < 1e-9);
+    }
+}
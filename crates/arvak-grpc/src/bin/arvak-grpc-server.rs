@@ -31,9 +31,24 @@
 //! - Stops accepting new requests
 //! - Waits for in-flight requests to complete (with timeout)
 //! - Shuts down gRPC and HTTP servers cleanly
+//!
+//! # Admin Service
+//!
+//! When `ARVAK_ADMIN_API_KEY` (or `server.admin_api_key`) is set, the
+//! `ArvakAdminService` is served on the same port. Callers authenticate with
+//! the `x-admin-key` metadata header. Draining the node through it makes
+//! `/health/ready` report not ready.
+//!
+//! # Fault Injection
+//!
+//! Builds with `--features chaos` read a fault scenario from the file named
+//! by `ARVAK_CHAOS_SCENARIO` and inject its backend errors, storage delays
+//! and dropped status updates. For recovery testing only.
 
+use arvak_grpc::proto::arvak_admin_service_server::ArvakAdminServiceServer;
 use arvak_grpc::proto::arvak_service_server::ArvakServiceServer;
-use arvak_grpc::server::{RequestIdInterceptor, TimingLayer};
+use arvak_grpc::server::interceptors::constant_time_eq;
+use arvak_grpc::server::{AdminAuthInterceptor, RequestIdInterceptor, TimingLayer};
 use arvak_grpc::{
     ArvakServiceImpl, Config, HealthState, Metrics, TracingConfig, TracingFormat, init_tracing,
     start_health_server,
@@ -44,20 +59,6 @@ use tonic::transport::Server;
 use tower::ServiceBuilder;
 use tracing::{error, info, warn};
 
-/// Constant-time string comparison to prevent timing side-channel attacks.
-///
-/// The length check does leak length information, but that is standard practice
-/// — you cannot hide length without padding both values.
-fn constant_time_eq(a: &str, b: &str) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-    a.bytes()
-        .zip(b.bytes())
-        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
-        == 0
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
@@ -92,30 +93,81 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create service with resource limits
     use arvak_grpc::server::{JobStore, backend_registry::create_default_registry};
-    #[allow(unused_mut)] // mut is needed when the ibm feature is enabled
     let mut registry = create_default_registry();
 
-    #[cfg(feature = "ibm")]
-    {
-        use arvak_grpc::server::backend_registry::register_ibm_backends;
-        register_ibm_backends(&mut registry).await;
-    }
+    // A backend configuration document replaces the environment-driven
+    // defaults below.
+    let configured = config.backends.configured()?;
+    if configured.backends.is_empty() {
+        #[cfg(feature = "ibm")]
+        {
+            use arvak_grpc::server::backend_registry::register_ibm_backends;
+            register_ibm_backends(&mut registry).await;
+        }
 
-    #[cfg(feature = "quantinuum")]
-    {
-        use arvak_grpc::server::backend_registry::register_quantinuum_backends;
-        register_quantinuum_backends(&mut registry).await;
+        #[cfg(feature = "quantinuum")]
+        {
+            use arvak_grpc::server::backend_registry::register_quantinuum_backends;
+            register_quantinuum_backends(&mut registry).await;
+        }
+
+        #[cfg(feature = "aqt")]
+        {
+            use arvak_grpc::server::backend_registry::register_aqt_backends;
+            register_aqt_backends(&mut registry).await;
+        }
+    } else {
+        use arvak_grpc::server::backend_registry::register_configured_backends;
+        register_configured_backends(&mut registry, &configured)?;
     }
 
-    #[cfg(feature = "aqt")]
-    {
-        use arvak_grpc::server::backend_registry::register_aqt_backends;
-        register_aqt_backends(&mut registry).await;
+    #[allow(unused_mut)]
+    let mut job_store = JobStore::new();
+
+    // Fault injection for recovery testing; only in builds with `--features chaos`.
+    #[cfg(feature = "chaos")]
+    if let Ok(path) = std::env::var("ARVAK_CHAOS_SCENARIO") {
+        use arvak_grpc::storage::{ChaosStorage, MemoryStorage};
+        use arvak_hal::chaos::FaultInjector;
+
+        let injector = Arc::new(FaultInjector::from_file(&path)?);
+        warn!("Chaos mode: injecting faults from scenario {}", path);
+        registry.inject_faults(&injector);
+        job_store = JobStore::with_storage(Arc::new(ChaosStorage::new(
+            Arc::new(MemoryStorage::new()),
+            injector,
+        )));
     }
 
-    let service = ArvakServiceImpl::with_limits(JobStore::new(), registry, config.limits.clone());
+    let mut service = ArvakServiceImpl::with_limits(job_store, registry, config.limits.clone());
+    if let Some(policy) = config.signing.policy()? {
+        info!(
+            "Circuit signing enforced on {:?} ({} trusted keys)",
+            config.signing.required_backends,
+            policy.num_trusted_keys()
+        );
+        service = service.with_signing_policy(policy);
+    }
     let backend_registry = service.backends();
 
+    // Background retention sweeps for finished jobs
+    let retention = &config.storage.retention;
+    let retention_handle = match retention.policy() {
+        Some(mut policy) => {
+            policy.archive_cipher = arvak_hal::encryption::PayloadCipher::from_env()?;
+            info!(
+                "Job retention enabled: max age {:?} days, max jobs {:?}, sweep every {}s",
+                retention.max_age_days, retention.max_jobs, retention.sweep_interval_seconds
+            );
+            Some(arvak_grpc::storage::retention::spawn_sweeper(
+                service.job_storage(),
+                policy,
+                std::time::Duration::from_secs(retention.sweep_interval_seconds),
+            ))
+        }
+        None => None,
+    };
+
     // Set up graceful shutdown
     let shutdown_signal = Arc::new(Notify::new());
     let shutdown_signal_clone = shutdown_signal.clone();
@@ -132,10 +184,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         || config.observability.http_server.metrics_enabled
     {
         let http_addr = config.http_address()?;
-        let health_state = HealthState {
-            backends: backend_registry.clone(),
-            metrics: Metrics,
-        };
+        let health_state =
+            HealthState::new(backend_registry.clone(), Metrics).with_control(service.control());
 
         info!("Starting HTTP server on {}", http_addr);
         if config.observability.http_server.health_enabled {
@@ -183,6 +233,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
              Set ARVAK_API_KEY or deploy behind a reverse proxy."
         );
     }
+    // Admin service shares state with the client service; only served with an admin key
+    let admin_service = config.server.admin_api_key.clone().map(|admin_key| {
+        info!("Admin service enabled");
+        tonic::service::interceptor::InterceptedService::new(
+            ArvakAdminServiceServer::new(service.admin_service()),
+            AdminAuthInterceptor::new(admin_key),
+        )
+    });
+
     // Build service with message size limits, then wrap with interceptor
     let grpc_service = ArvakServiceServer::new(service)
         .max_decoding_message_size(max_message_size)
@@ -202,7 +261,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     .get("x-api-key")
                     .and_then(|v| v.to_str().ok());
                 match provided {
-                    Some(k) if constant_time_eq(k, expected_key) => Ok(req),
+                    Some(k) if constant_time_eq(k.as_bytes(), expected_key.as_bytes()) => Ok(req),
                     _ => Err(tonic::Status::unauthenticated("Invalid or missing API key")),
                 }
             } else {
@@ -226,6 +285,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .layer(ServiceBuilder::new().layer(TimingLayer::new()).into_inner())
         .add_service(reflection_service)
         .add_service(service_with_interceptor)
+        .add_optional_service(admin_service)
         .serve_with_shutdown(grpc_addr, async move {
             shutdown_signal.notified().await;
             info!("Shutdown signal received, initiating graceful shutdown");
@@ -252,6 +312,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    if let Some(handle) = retention_handle {
+        handle.abort();
+    }
+
     // Wait for HTTP server to shut down (with timeout)
     if let Some(handle) = http_handle {
         info!("Waiting for HTTP server to shut down");
@@ -6,8 +6,10 @@
 //! # Configuration (environment variables)
 //!
 //! - `ARVAK_API_KEY`       — Bearer token for authentication (optional but recommended)
+//! - `ARVAK_ADMIN_API_KEY` — Bearer token for `/v1/admin/*` (admin routes disabled if unset)
 //! - `ARVAK_CORS_ORIGINS`  — Comma-separated allowed origins, or `*` (default `*`)
 //! - `ARVAK_REST_ADDRESS`  — Listen address (default `127.0.0.1:8080`)
+//! - `ARVAK_BACKENDS_CONFIG` — Backend configuration file (TOML or JSON)
 //! - `ARVAK_LOG_LEVEL`     — Tracing filter (default `info`)
 //!
 //! # Usage
@@ -15,18 +17,29 @@
 //! ```bash
 //! ARVAK_API_KEY=secret ARVAK_REST_ADDRESS=127.0.0.1:8080 arvak-rest-gateway
 //! ```
+//!
+//! The OpenAPI document is served at `/v1/openapi.json` and rendered at
+//! `/v1/docs`. `arvak-rest-gateway --openapi` prints it and exits.
 
+use arvak_grpc::rest::openapi::ApiDoc;
 use arvak_grpc::rest::{AppState, auth::AuthState, rest_router};
 use arvak_grpc::server::JobStore;
-use arvak_grpc::server::backend_registry::create_default_registry;
-use arvak_grpc::{Metrics, init_default_tracing};
+use arvak_grpc::server::backend_registry::{create_default_registry, register_configured_backends};
+use arvak_grpc::{ControlState, Metrics, init_default_tracing};
+use arvak_hal::BackendsConfig;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Notify;
 use tracing::{info, warn};
+use utoipa::OpenApi;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::args().skip(1).any(|arg| arg == "--openapi") {
+        println!("{}", ApiDoc::openapi().to_pretty_json()?);
+        return Ok(());
+    }
+
     // Load .env if present
     let _ = dotenvy::dotenv();
 
@@ -37,6 +50,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Read configuration from environment
     let api_key = std::env::var("ARVAK_API_KEY").ok();
+    let admin_key = std::env::var("ARVAK_ADMIN_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty());
     let cors_origins = std::env::var("ARVAK_CORS_ORIGINS").unwrap_or_else(|_| "*".to_string());
     let listen_addr =
         std::env::var("ARVAK_REST_ADDRESS").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
@@ -49,10 +65,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
              Deploy behind an authenticating reverse proxy."
         );
     }
+    if admin_key.is_some() {
+        if admin_key == api_key {
+            return Err("ARVAK_ADMIN_API_KEY must differ from ARVAK_API_KEY".into());
+        }
+        info!("Admin API enabled under /v1/admin");
+    }
 
     // Build shared state — reuses the same components as the gRPC server
     let job_store = Arc::new(JobStore::new());
-    let backends = Arc::new(create_default_registry());
+    let mut registry = create_default_registry();
+    if let Ok(path) = std::env::var("ARVAK_BACKENDS_CONFIG") {
+        register_configured_backends(&mut registry, &BackendsConfig::from_file(&path)?)?;
+        info!("Backends configured from {path}");
+    }
+    let backends = Arc::new(registry);
     let metrics = Metrics::new();
 
     for backend_id in backends.list() {
@@ -65,8 +92,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         metrics,
         resources: None,
         abort_handles: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+        control: ControlState::new(),
         auth: AuthState {
             api_key: api_key.map(Arc::new),
+            admin_key: admin_key.map(Arc::new),
         },
     };
 
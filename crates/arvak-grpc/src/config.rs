@@ -10,10 +10,13 @@
 //! 2. Configuration file
 //! 3. Default values
 
+use arvak_hal::config::{AdapterConfig, BackendsConfig};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::net::SocketAddr;
 use std::path::Path;
+use utoipa::ToSchema;
 
 /// Complete server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +37,10 @@ pub struct Config {
     /// Resource limits and quotas
     #[serde(default)]
     pub limits: ResourceLimits,
+
+    /// Circuit signature verification
+    #[serde(default)]
+    pub signing: SigningConfig,
 }
 
 /// gRPC server settings.
@@ -66,6 +73,11 @@ pub struct ServerConfig {
     /// Optional API key for authentication (set via ARVAK_API_KEY)
     #[serde(default)]
     pub api_key: Option<String>,
+
+    /// Key for the admin service (set via ARVAK_ADMIN_API_KEY).
+    /// The admin service is only served when this is set.
+    #[serde(default)]
+    pub admin_api_key: Option<String>,
 }
 
 impl fmt::Debug for ServerConfig {
@@ -78,6 +90,7 @@ impl fmt::Debug for ServerConfig {
             .field("shutdown_timeout_seconds", &self.shutdown_timeout_seconds)
             .field("max_message_size_bytes", &self.max_message_size_bytes)
             .field("api_key", &"[REDACTED]")
+            .field("admin_api_key", &"[REDACTED]")
             .finish()
     }
 }
@@ -95,6 +108,10 @@ pub struct StorageConfig {
     /// Maximum number of database connections
     #[serde(default = "default_db_pool_size")]
     pub pool_size: u32,
+
+    /// Retention of finished jobs
+    #[serde(default)]
+    pub retention: RetentionConfig,
 }
 
 impl fmt::Debug for StorageConfig {
@@ -103,10 +120,92 @@ impl fmt::Debug for StorageConfig {
             .field("backend", &self.backend)
             .field("connection_string", &"[REDACTED]")
             .field("pool_size", &self.pool_size)
+            .field("retention", &self.retention)
             .finish()
     }
 }
 
+/// Retention of finished jobs in storage.
+///
+/// Disabled unless `max_age_days` or `max_jobs` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Remove finished jobs older than this many days
+    pub max_age_days: Option<u64>,
+
+    /// Keep at most this many jobs, evicting the oldest finished ones
+    pub max_jobs: Option<usize>,
+
+    /// Archive removed jobs to this directory instead of only deleting them
+    pub archive_dir: Option<String>,
+
+    /// Interval between background sweeps
+    #[serde(default = "default_retention_sweep_interval")]
+    pub sweep_interval_seconds: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age_days: None,
+            max_jobs: None,
+            archive_dir: None,
+            sweep_interval_seconds: default_retention_sweep_interval(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// The storage retention policy, or `None` if retention is disabled.
+    ///
+    /// The archive cipher is left unset; the server takes it from
+    /// `ARVAK_STORAGE_KEY`.
+    pub fn policy(&self) -> Option<crate::storage::RetentionPolicy> {
+        let policy = crate::storage::RetentionPolicy {
+            max_age: self
+                .max_age_days
+                .map(|days| std::time::Duration::from_secs(days * 86_400)),
+            max_jobs: self.max_jobs,
+            archive_dir: self.archive_dir.as_ref().map(Into::into),
+            archive_cipher: None,
+        };
+        (!policy.is_unbounded()).then_some(policy)
+    }
+}
+
+/// Circuit signature verification.
+///
+/// Disabled unless trusted keys or protected backends are listed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Base64-encoded Ed25519 public keys whose signatures are accepted
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
+
+    /// Backends that only run circuits signed by a trusted key
+    #[serde(default)]
+    pub required_backends: Vec<String>,
+}
+
+impl SigningConfig {
+    /// The signature policy, or `None` if signing is disabled.
+    pub fn policy(&self) -> Result<Option<arvak_hal::SignaturePolicy>, ConfigError> {
+        if self.trusted_keys.is_empty() && self.required_backends.is_empty() {
+            return Ok(None);
+        }
+        let mut policy = arvak_hal::SignaturePolicy::new();
+        for key in &self.trusted_keys {
+            policy = policy
+                .with_trusted_key(key)
+                .map_err(|e| ConfigError::ValidationError(format!("signing.trusted_keys: {e}")))?;
+        }
+        for backend in &self.required_backends {
+            policy = policy.require_for_backend(backend.clone());
+        }
+        Ok(Some(policy))
+    }
+}
+
 /// Observability configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObservabilityConfig {
@@ -170,24 +269,33 @@ pub struct BackendConfigs {
     #[serde(default = "default_true")]
     pub simulator_enabled: bool,
 
-    /// Custom backend configurations
+    /// Typed backend configuration document (TOML or JSON), as read by the
+    /// CLI. Entries in `custom` replace entries of the same name.
     #[serde(default)]
-    pub custom: std::collections::HashMap<String, BackendConfig>,
-}
-
-/// Individual backend configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BackendConfig {
-    /// Backend type/adapter
-    pub backend_type: String,
+    pub config_file: Option<String>,
 
-    /// Backend-specific settings as JSON
+    /// Backends configured inline, keyed by name, with the same typed
+    /// sections as the configuration document.
     #[serde(default)]
-    pub settings: serde_json::Value,
+    pub custom: BTreeMap<String, AdapterConfig>,
+}
+
+impl BackendConfigs {
+    /// All configured backends: the configuration document merged with the
+    /// inline sections.
+    pub fn configured(&self) -> Result<BackendsConfig, ConfigError> {
+        let mut configured = match &self.config_file {
+            Some(path) => BackendsConfig::from_file(path)
+                .map_err(|e| ConfigError::ValidationError(e.to_string()))?,
+            None => BackendsConfig::default(),
+        };
+        configured.backends.extend(self.custom.clone());
+        Ok(configured)
+    }
 }
 
 /// Resource limits and quotas.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ResourceLimits {
     /// Maximum concurrent jobs across all backends
     #[serde(default = "default_max_concurrent_jobs")]
@@ -251,6 +359,10 @@ fn default_db_pool_size() -> u32 {
     10
 }
 
+fn default_retention_sweep_interval() -> u64 {
+    3600
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -318,11 +430,13 @@ impl Default for Config {
                 shutdown_timeout_seconds: default_shutdown_timeout(),
                 max_message_size_bytes: default_max_message_size(),
                 api_key: None,
+                admin_api_key: None,
             },
             storage: StorageConfig {
                 backend: default_storage_type(),
                 connection_string: None,
                 pool_size: default_db_pool_size(),
+                retention: RetentionConfig::default(),
             },
             observability: ObservabilityConfig {
                 http_server: HttpServerConfig {
@@ -342,6 +456,7 @@ impl Default for Config {
             },
             backends: BackendConfigs::default(),
             limits: ResourceLimits::default(),
+            signing: SigningConfig::default(),
         }
     }
 }
@@ -406,6 +521,14 @@ impl Config {
         if let Ok(key) = std::env::var("ARVAK_API_KEY") {
             config.server.api_key = Some(key);
         }
+        if let Ok(key) = std::env::var("ARVAK_ADMIN_API_KEY") {
+            config.server.admin_api_key = Some(key);
+        }
+
+        // Backend configuration document
+        if let Ok(path) = std::env::var("ARVAK_BACKENDS_CONFIG") {
+            config.backends.config_file = Some(path);
+        }
 
         // Storage configuration
         if let Ok(backend) = std::env::var("ARVAK_STORAGE_TYPE") {
@@ -414,6 +537,16 @@ impl Config {
         if let Ok(conn) = std::env::var("ARVAK_STORAGE_CONNECTION") {
             config.storage.connection_string = Some(conn);
         }
+        if let Ok(days) = std::env::var("ARVAK_RETENTION_DAYS") {
+            if let Ok(val) = days.parse() {
+                config.storage.retention.max_age_days = Some(val);
+            }
+        }
+        if let Ok(max) = std::env::var("ARVAK_RETENTION_MAX_JOBS") {
+            if let Ok(val) = max.parse() {
+                config.storage.retention.max_jobs = Some(val);
+            }
+        }
 
         // HTTP server configuration
         if let Ok(addr) = std::env::var("ARVAK_HTTP_ADDRESS") {
@@ -519,6 +652,14 @@ impl Config {
         if let Ok(key) = std::env::var("ARVAK_API_KEY") {
             self.server.api_key = Some(key);
         }
+        if let Ok(key) = std::env::var("ARVAK_ADMIN_API_KEY") {
+            self.server.admin_api_key = Some(key);
+        }
+
+        // Backends
+        if let Ok(v) = std::env::var("ARVAK_BACKENDS_CONFIG") {
+            self.backends.config_file = Some(v);
+        }
 
         // Storage
         if let Ok(v) = std::env::var("ARVAK_STORAGE_TYPE") {
@@ -527,6 +668,16 @@ impl Config {
         if let Ok(v) = std::env::var("ARVAK_STORAGE_CONNECTION") {
             self.storage.connection_string = Some(v);
         }
+        if let Ok(v) = std::env::var("ARVAK_RETENTION_DAYS") {
+            if let Ok(val) = v.parse() {
+                self.storage.retention.max_age_days = Some(val);
+            }
+        }
+        if let Ok(v) = std::env::var("ARVAK_RETENTION_MAX_JOBS") {
+            if let Ok(val) = v.parse() {
+                self.storage.retention.max_jobs = Some(val);
+            }
+        }
 
         // Observability — HTTP server
         if let Ok(v) = std::env::var("ARVAK_HTTP_ADDRESS") {
@@ -611,6 +762,12 @@ impl Config {
             }
         }
 
+        if self.storage.retention.sweep_interval_seconds == 0 {
+            return Err(ConfigError::ValidationError(
+                "retention.sweep_interval_seconds must be greater than 0".to_string(),
+            ));
+        }
+
         // Validate log level
         match self.observability.logging.level.as_str() {
             "trace" | "debug" | "info" | "warn" | "error" => {}
@@ -638,6 +795,15 @@ impl Config {
             ));
         }
 
+        // Validate signing keys
+        self.signing.policy()?;
+
+        // Validate typed backend sections, including credentials
+        self.backends
+            .configured()?
+            .validate()
+            .map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+
         // Validate API key is non-empty if provided
         if let Some(ref key) = self.server.api_key {
             if key.is_empty() {
@@ -648,6 +814,21 @@ impl Config {
             }
         }
 
+        // Validate admin key: non-empty and distinct from the client key
+        if let Some(ref key) = self.server.admin_api_key {
+            if key.is_empty() {
+                return Err(ConfigError::ValidationError(
+                    "admin_api_key must not be empty; omit the field to disable the admin service"
+                        .to_string(),
+                ));
+            }
+            if self.server.api_key.as_ref() == Some(key) {
+                return Err(ConfigError::ValidationError(
+                    "admin_api_key must differ from api_key".to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -714,10 +895,63 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_admin_key() {
+        let mut config = Config::default();
+        config.server.admin_api_key = Some(String::new());
+        assert!(config.validate().is_err());
+
+        config.server.api_key = Some("shared".to_string());
+        config.server.admin_api_key = Some("shared".to_string());
+        assert!(config.validate().is_err());
+
+        config.server.admin_api_key = Some("admin".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_signing_config() {
+        let mut config = Config::default();
+        assert!(config.signing.policy().unwrap().is_none());
+
+        let key = arvak_hal::CircuitSigner::from_bytes(&[3; 32])
+            .unwrap()
+            .public_key();
+        let yaml = format!("trusted_keys: [\"{key}\"]\nrequired_backends: [iqm-garnet]\n");
+        config.signing = serde_yaml_ng::from_str(&yaml).unwrap();
+        let policy = config.signing.policy().unwrap().unwrap();
+        assert_eq!(policy.num_trusted_keys(), 1);
+        assert!(policy.requires_signature("iqm-garnet"));
+        assert!(config.validate().is_ok());
+
+        config.signing.trusted_keys.push("not-a-key".to_string());
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_grpc_address_parsing() {
         let config = Config::default();
         let addr = config.grpc_address().unwrap();
         assert_eq!(addr.port(), 50051);
     }
+
+    #[test]
+    fn test_retention_config() {
+        let mut config = Config::default();
+        assert!(config.storage.retention.policy().is_none());
+
+        config.storage.retention.max_age_days = Some(30);
+        let policy = config.storage.retention.policy().unwrap();
+        assert_eq!(
+            policy.max_age,
+            Some(std::time::Duration::from_secs(30 * 86_400))
+        );
+
+        let parsed: RetentionConfig = serde_yaml_ng::from_str("max_jobs: 1000\n").unwrap();
+        assert_eq!(parsed.max_jobs, Some(1000));
+        assert_eq!(parsed.sweep_interval_seconds, 3600);
+
+        config.storage.retention.sweep_interval_seconds = 0;
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,112 @@
+//! Runtime control state for operator actions.
+//!
+//! Tracks the switches the admin API flips on a running server:
+//! - Draining: the node stops accepting new jobs while in-flight jobs finish
+//! - Paused backends: submissions to a paused backend are rejected
+//!
+//! The state is shared between the gRPC service, the REST gateway and the
+//! health endpoints, so a drained node also reports itself as not ready.
+
+use rustc_hash::FxHashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+use crate::error::{Error, Result};
+
+/// Shared operator-controlled server state.
+#[derive(Clone, Default)]
+pub struct ControlState {
+    draining: Arc<AtomicBool>,
+    paused_backends: Arc<RwLock<FxHashSet<String>>>,
+}
+
+impl ControlState {
+    /// Create a control state that accepts jobs on every backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start or stop draining the node.
+    pub fn set_draining(&self, draining: bool) {
+        self.draining.store(draining, Ordering::SeqCst);
+    }
+
+    /// Check if the node is draining.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Pause or resume a backend. Returns `true` if the state changed.
+    pub async fn set_backend_paused(&self, backend_id: &str, paused: bool) -> bool {
+        let mut set = self.paused_backends.write().await;
+        if paused {
+            set.insert(backend_id.to_string())
+        } else {
+            set.remove(backend_id)
+        }
+    }
+
+    /// Check if a backend is paused.
+    pub async fn is_backend_paused(&self, backend_id: &str) -> bool {
+        self.paused_backends.read().await.contains(backend_id)
+    }
+
+    /// List paused backend IDs in sorted order.
+    pub async fn paused_backends(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.paused_backends.read().await.iter().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Check that a new job may be submitted to `backend_id`.
+    pub async fn check_accepting(&self, backend_id: &str) -> Result<()> {
+        if self.is_draining() {
+            return Err(Error::Unavailable(
+                "node is draining and not accepting new jobs".to_string(),
+            ));
+        }
+        if self.is_backend_paused(backend_id).await {
+            return Err(Error::Unavailable(format!(
+                "backend '{backend_id}' is paused"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_rejects_submissions() {
+        let control = ControlState::new();
+        assert!(control.check_accepting("simulator").await.is_ok());
+
+        control.set_draining(true);
+        assert!(matches!(
+            control.check_accepting("simulator").await,
+            Err(Error::Unavailable(_))
+        ));
+
+        control.set_draining(false);
+        assert!(control.check_accepting("simulator").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_pause_backend() {
+        let control = ControlState::new();
+
+        assert!(control.set_backend_paused("simulator", true).await);
+        assert!(!control.set_backend_paused("simulator", true).await);
+        assert_eq!(control.paused_backends().await, vec!["simulator"]);
+        assert!(control.check_accepting("simulator").await.is_err());
+        assert!(control.check_accepting("other").await.is_ok());
+
+        // Clones share state.
+        let clone = control.clone();
+        assert!(clone.set_backend_paused("simulator", false).await);
+        assert!(control.check_accepting("simulator").await.is_ok());
+    }
+}
@@ -45,6 +45,18 @@ pub enum Error {
     #[error("Storage error: {0}")]
     StorageError(String),
 
+    /// The operation is not valid in the current job or server state.
+    #[error("Invalid state: {0}")]
+    InvalidState(String),
+
+    /// The circuit signature is missing, invalid or untrusted.
+    #[error("Signature rejected: {0}")]
+    Signature(#[from] arvak_hal::SignatureError),
+
+    /// The server is not accepting this request right now.
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+
     /// Internal error.
     #[error("Internal error: {0}")]
     Internal(String),
@@ -77,6 +89,15 @@ impl From<Error> for Status {
             Error::StorageError(msg) => {
                 Status::internal(format!("[transient] Storage error: {msg}"))
             }
+            Error::InvalidState(msg) => Status::failed_precondition(format!("[permanent] {msg}")),
+            Error::Signature(e) => match e {
+                arvak_hal::SignatureError::InvalidKey(_)
+                | arvak_hal::SignatureError::InvalidSignature(_) => {
+                    Status::invalid_argument(format!("[permanent] {e}"))
+                }
+                _ => Status::permission_denied(format!("[permanent] {e}")),
+            },
+            Error::Unavailable(msg) => Status::unavailable(format!("[transient] {msg}")),
             Error::Internal(msg) => Status::internal(format!("[permanent] {msg}")),
         }
     }
@@ -88,6 +109,13 @@ impl From<arvak_qasm3::ParseError> for Error {
     }
 }
 
+#[cfg(feature = "sqlite")]
+impl From<arvak_hal::EncryptionError> for Error {
+    fn from(err: arvak_hal::EncryptionError) -> Self {
+        Error::StorageError(err.to_string())
+    }
+}
+
 #[cfg(feature = "sqlite")]
 impl From<rusqlite::Error> for Error {
     fn from(err: rusqlite::Error) -> Self {
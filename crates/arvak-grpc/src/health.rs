@@ -1,10 +1,11 @@
 //! Health check and metrics HTTP endpoints.
 //!
 //! This module provides HTTP endpoints for monitoring:
-//! - /health - Basic liveness check
-//! - /health/ready - Readiness check with backend validation
+//! - /health - Basic liveness check, with the features the server was built with
+//! - /health/ready - Readiness check with backend validation (not ready while draining)
 //! - /metrics - Prometheus metrics in text format
 
+use arvak_hal::BuildInfo;
 use axum::{
     Json, Router,
     extract::State,
@@ -16,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::SystemTime;
 
+use crate::control::ControlState;
 use crate::metrics::Metrics;
 use crate::server::BackendRegistry;
 
@@ -40,11 +42,23 @@ fn get_uptime_seconds() -> u64 {
 pub struct HealthState {
     pub backends: Arc<BackendRegistry>,
     pub metrics: Metrics,
+    pub control: ControlState,
 }
 
 impl HealthState {
     pub fn new(backends: Arc<BackendRegistry>, metrics: Metrics) -> Self {
-        Self { backends, metrics }
+        Self {
+            backends,
+            metrics,
+            control: ControlState::new(),
+        }
+    }
+
+    /// Report readiness from the given control state (drain, paused backends).
+    #[must_use]
+    pub fn with_control(mut self, control: ControlState) -> Self {
+        self.control = control;
+        self
     }
 }
 
@@ -54,12 +68,17 @@ pub struct HealthResponse {
     pub status: String,
     pub version: String,
     pub uptime_seconds: u64,
+    /// Features, adapters and storage backends compiled into the server.
+    #[serde(default)]
+    pub build: BuildInfo,
 }
 
 /// Response for /health/ready endpoint.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReadinessResponse {
     pub ready: bool,
+    #[serde(default)]
+    pub draining: bool,
     pub backends: Vec<BackendStatus>,
     pub active_jobs: u64,
     pub queued_jobs: u64,
@@ -81,6 +100,7 @@ async fn health_handler() -> impl IntoResponse {
         status: "healthy".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         uptime_seconds: get_uptime_seconds(),
+        build: crate::describe_build(BuildInfo::current()),
     };
 
     Json(response)
@@ -89,29 +109,31 @@ async fn health_handler() -> impl IntoResponse {
 /// Handler for GET /health/ready
 ///
 /// Returns readiness status, indicating whether the server is ready to accept
-/// traffic. Checks backend availability and service capacity.
+/// traffic. Checks backend availability and service capacity. A draining node
+/// reports not ready so load balancers stop routing new work to it.
 async fn readiness_handler(State(state): State<HealthState>) -> Response {
     // Check all backends
     let backend_ids = state.backends.list();
-    let backends: Vec<BackendStatus> = backend_ids
-        .iter()
-        .map(|id| {
-            let available = state.backends.get(id).is_ok();
-            BackendStatus {
-                backend_id: id.clone(),
-                available,
-            }
-        })
-        .collect();
+    let mut backends = Vec::with_capacity(backend_ids.len());
+    for id in backend_ids {
+        let available =
+            state.backends.get(&id).is_ok() && !state.control.is_backend_paused(&id).await;
+        backends.push(BackendStatus {
+            backend_id: id,
+            available,
+        });
+    }
 
     // Get current job metrics
     let snapshot = state.metrics.snapshot();
 
-    // Consider ready if at least one backend is available
-    let ready = backends.iter().any(|b| b.available);
+    // Consider ready if not draining and at least one backend is available
+    let draining = state.control.is_draining();
+    let ready = !draining && backends.iter().any(|b| b.available);
 
     let response = ReadinessResponse {
         ready,
+        draining,
         backends,
         active_jobs: snapshot.active_jobs,
         queued_jobs: snapshot.queued_jobs,
@@ -187,17 +209,20 @@ mod tests {
             status: "healthy".to_string(),
             version: "1.0.0".to_string(),
             uptime_seconds: 42,
+            build: crate::describe_build(BuildInfo::current()),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("healthy"));
         assert!(json.contains("1.0.0"));
+        assert!(json.contains("arvak-grpc"));
     }
 
     #[test]
     fn test_readiness_response() {
         let response = ReadinessResponse {
             ready: true,
+            draining: false,
             backends: vec![BackendStatus {
                 backend_id: "simulator".to_string(),
                 available: true,
@@ -11,6 +11,7 @@
 //! │                    Arvak gRPC Service                        │
 //! │                                                               │
 //! │  - ArvakServiceImpl (server/service.rs)                      │
+//! │  - ArvakAdminServiceImpl (server/admin.rs)                   │
 //! │  - JobStore (server/job_store.rs)                            │
 //! │  - BackendRegistry (server/backend_registry.rs)              │
 //! │  - Async job execution                                       │
@@ -44,6 +45,7 @@
 //! ```
 
 pub mod config;
+pub mod control;
 pub mod error;
 pub mod health;
 pub mod metrics;
@@ -55,19 +57,48 @@ pub mod storage;
 pub mod tracing_config;
 
 // Re-export commonly used types
-pub use config::{Config, ConfigError, ResourceLimits};
+pub use config::{Config, ConfigError, ResourceLimits, RetentionConfig};
+pub use control::ControlState;
 pub use error::{Error, Result};
 pub use health::{HealthState, start_health_server};
 pub use metrics::Metrics;
-pub use resource_manager::{ResourceError, ResourceManager, ResourceStats};
-pub use server::{ArvakServiceImpl, BackendRegistry, JobStore};
-pub use storage::{JobStorage, MemoryStorage, StoredJob};
+pub use resource_manager::{ResourceError, ResourceLimitsUpdate, ResourceManager, ResourceStats};
+pub use server::{ArvakAdminServiceImpl, ArvakServiceImpl, BackendRegistry, JobStore};
+pub use storage::{JobStorage, MemoryStorage, RetentionPolicy, StoredJob};
 
 #[cfg(feature = "sqlite")]
 pub use storage::SqliteStorage;
 
 pub use tracing_config::{TracingConfig, TracingFormat, init_default_tracing, init_tracing};
 
+/// Add this crate's features, adapters and storage backends to `info`.
+pub fn describe_build(info: arvak_hal::BuildInfo) -> arvak_hal::BuildInfo {
+    let adapters = [
+        ("aqt", cfg!(feature = "aqt")),
+        ("braket", cfg!(feature = "braket")),
+        ("ibm", cfg!(feature = "ibm")),
+        ("quandela", cfg!(feature = "quandela")),
+        ("quantinuum", cfg!(feature = "quantinuum")),
+        ("simulator", cfg!(feature = "simulator")),
+    ];
+    let mut info = info.with_component(
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        adapters.into_iter().chain([
+            ("chaos", cfg!(feature = "chaos")),
+            ("sqlite", cfg!(feature = "sqlite")),
+        ]),
+    );
+    for (adapter, _) in adapters.into_iter().filter(|(_, enabled)| *enabled) {
+        info = info.with_adapter(adapter);
+    }
+    info = info.with_storage_backend("memory");
+    if cfg!(feature = "sqlite") {
+        info = info.with_storage_backend("sqlite");
+    }
+    info
+}
+
 /// Encoded file descriptor set for gRPC reflection.
 pub const FILE_DESCRIPTOR_SET: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/arvak_descriptor.bin"));
@@ -6,17 +6,22 @@
 //! - Job timeouts
 //! - Result size limits
 //! - Rate limiting per client
+//!
+//! Limits can be adjusted at runtime with [`ResourceManager::update_limits`];
+//! the new values apply to the next check.
 
 use crate::config::ResourceLimits;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLockReadGuard};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use utoipa::ToSchema;
 
 /// Resource manager for tracking and enforcing limits.
 #[derive(Clone)]
 pub struct ResourceManager {
-    limits: ResourceLimits,
+    limits: Arc<std::sync::RwLock<ResourceLimits>>,
     state: Arc<RwLock<ResourceState>>,
 }
 
@@ -70,7 +75,33 @@ impl ResourceManager {
             });
         }
 
-        Self { limits, state }
+        Self {
+            limits: Arc::new(std::sync::RwLock::new(limits)),
+            state,
+        }
+    }
+
+    fn current(&self) -> RwLockReadGuard<'_, ResourceLimits> {
+        self.limits.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the limits currently in force.
+    pub fn limits(&self) -> ResourceLimits {
+        self.current().clone()
+    }
+
+    /// Apply a partial update to the limits and return the new values.
+    ///
+    /// Every field set in `update` must be non-zero; on error nothing is
+    /// changed. Jobs already queued or running are not affected.
+    pub fn update_limits(
+        &self,
+        update: &ResourceLimitsUpdate,
+    ) -> Result<ResourceLimits, ResourceError> {
+        update.validate()?;
+        let mut limits = self.limits.write().unwrap_or_else(PoisonError::into_inner);
+        update.apply(&mut limits);
+        Ok(limits.clone())
     }
 
     /// Check if a new job can be accepted (queued).
@@ -78,20 +109,21 @@ impl ResourceManager {
     /// Returns Ok(()) if the job can be accepted, or Err with a reason if rejected.
     pub async fn check_can_submit(&self, client_ip: Option<&str>) -> Result<(), ResourceError> {
         let state = self.state.read().await;
+        let limits = self.current();
 
         // Check concurrent job limit
-        if state.running_jobs >= self.limits.max_concurrent_jobs {
+        if state.running_jobs >= limits.max_concurrent_jobs {
             return Err(ResourceError::ConcurrencyLimitReached {
                 current: state.running_jobs,
-                limit: self.limits.max_concurrent_jobs,
+                limit: limits.max_concurrent_jobs,
             });
         }
 
         // Check queue limit
-        if state.queued_jobs >= self.limits.max_queued_jobs {
+        if state.queued_jobs >= limits.max_queued_jobs {
             return Err(ResourceError::QueueFull {
                 current: state.queued_jobs,
-                limit: self.limits.max_queued_jobs,
+                limit: limits.max_queued_jobs,
             });
         }
 
@@ -103,11 +135,11 @@ impl ResourceManager {
 
                 // If within current window, check count
                 if now.duration_since(rate_state.window_start) < window
-                    && rate_state.count >= self.limits.rate_limit_rps
+                    && rate_state.count >= limits.rate_limit_rps
                 {
                     return Err(ResourceError::RateLimitExceeded {
                         current_rps: rate_state.count,
-                        limit_rps: self.limits.rate_limit_rps,
+                        limit_rps: limits.rate_limit_rps,
                     });
                 }
             }
@@ -175,19 +207,20 @@ impl ResourceManager {
         num_qubits: usize,
         num_gates: usize,
     ) -> Result<(), ResourceError> {
-        if num_qubits > self.limits.max_circuit_qubits {
+        let limits = self.current();
+        if num_qubits > limits.max_circuit_qubits {
             return Err(ResourceError::CircuitTooLarge {
                 reason: format!(
                     "{num_qubits} qubits exceeds limit of {}",
-                    self.limits.max_circuit_qubits
+                    limits.max_circuit_qubits
                 ),
             });
         }
-        if num_gates > self.limits.max_circuit_gates {
+        if num_gates > limits.max_circuit_gates {
             return Err(ResourceError::CircuitTooLarge {
                 reason: format!(
                     "{num_gates} gates exceeds limit of {}",
-                    self.limits.max_circuit_gates
+                    limits.max_circuit_gates
                 ),
             });
         }
@@ -196,15 +229,16 @@ impl ResourceManager {
 
     /// Get the compilation timeout duration.
     pub fn compilation_timeout(&self) -> Duration {
-        Duration::from_secs(self.limits.compilation_timeout_seconds)
+        Duration::from_secs(self.current().compilation_timeout_seconds)
     }
 
     /// Check if the result size is within limits.
     pub fn check_result_size(&self, size_bytes: usize) -> Result<(), ResourceError> {
-        if size_bytes > self.limits.max_result_size_bytes {
+        let limit_bytes = self.current().max_result_size_bytes;
+        if size_bytes > limit_bytes {
             Err(ResourceError::ResultTooLarge {
                 size_bytes,
-                limit_bytes: self.limits.max_result_size_bytes,
+                limit_bytes,
             })
         } else {
             Ok(())
@@ -213,17 +247,18 @@ impl ResourceManager {
 
     /// Get the job timeout duration.
     pub fn job_timeout(&self) -> Duration {
-        Duration::from_secs(self.limits.job_timeout_seconds)
+        Duration::from_secs(self.current().job_timeout_seconds)
     }
 
     /// Get current resource usage statistics.
     pub async fn stats(&self) -> ResourceStats {
         let state = self.state.read().await;
+        let limits = self.current();
         ResourceStats {
             running_jobs: state.running_jobs,
             queued_jobs: state.queued_jobs,
-            max_concurrent_jobs: self.limits.max_concurrent_jobs,
-            max_queued_jobs: self.limits.max_queued_jobs,
+            max_concurrent_jobs: limits.max_concurrent_jobs,
+            max_queued_jobs: limits.max_queued_jobs,
         }
     }
 
@@ -249,6 +284,81 @@ pub struct ResourceStats {
     pub max_queued_jobs: usize,
 }
 
+/// Partial update of [`ResourceLimits`]; unset fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct ResourceLimitsUpdate {
+    pub max_concurrent_jobs: Option<usize>,
+    pub max_queued_jobs: Option<usize>,
+    pub job_timeout_seconds: Option<u64>,
+    pub max_result_size_bytes: Option<usize>,
+    pub rate_limit_rps: Option<u32>,
+    pub max_circuit_qubits: Option<usize>,
+    pub max_circuit_gates: Option<usize>,
+    pub compilation_timeout_seconds: Option<u64>,
+}
+
+impl ResourceLimitsUpdate {
+    /// Returns `true` if the update does not change anything.
+    pub fn is_empty(&self) -> bool {
+        self.fields().iter().all(|(_, value)| value.is_none())
+    }
+
+    fn fields(&self) -> [(&'static str, Option<u64>); 8] {
+        let widen = |v: Option<usize>| v.map(|v| v as u64);
+        [
+            ("max_concurrent_jobs", widen(self.max_concurrent_jobs)),
+            ("max_queued_jobs", widen(self.max_queued_jobs)),
+            ("job_timeout_seconds", self.job_timeout_seconds),
+            ("max_result_size_bytes", widen(self.max_result_size_bytes)),
+            ("rate_limit_rps", self.rate_limit_rps.map(u64::from)),
+            ("max_circuit_qubits", widen(self.max_circuit_qubits)),
+            ("max_circuit_gates", widen(self.max_circuit_gates)),
+            (
+                "compilation_timeout_seconds",
+                self.compilation_timeout_seconds,
+            ),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), ResourceError> {
+        match self.fields().iter().find(|(_, value)| *value == Some(0)) {
+            Some((name, _)) => Err(ResourceError::InvalidLimit {
+                name,
+                reason: "must be greater than zero".to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn apply(&self, limits: &mut ResourceLimits) {
+        if let Some(v) = self.max_concurrent_jobs {
+            limits.max_concurrent_jobs = v;
+        }
+        if let Some(v) = self.max_queued_jobs {
+            limits.max_queued_jobs = v;
+        }
+        if let Some(v) = self.job_timeout_seconds {
+            limits.job_timeout_seconds = v;
+        }
+        if let Some(v) = self.max_result_size_bytes {
+            limits.max_result_size_bytes = v;
+        }
+        if let Some(v) = self.rate_limit_rps {
+            limits.rate_limit_rps = v;
+        }
+        if let Some(v) = self.max_circuit_qubits {
+            limits.max_circuit_qubits = v;
+        }
+        if let Some(v) = self.max_circuit_gates {
+            limits.max_circuit_gates = v;
+        }
+        if let Some(v) = self.compilation_timeout_seconds {
+            limits.compilation_timeout_seconds = v;
+        }
+    }
+}
+
 /// Resource limit errors.
 #[derive(Debug, thiserror::Error)]
 pub enum ResourceError {
@@ -275,6 +385,12 @@ pub enum ResourceError {
 
     #[error("Job timeout exceeded")]
     Timeout,
+
+    #[error("Invalid limit {name}: {reason}")]
+    InvalidLimit { name: &'static str, reason: String },
+
+    #[error("Resource limits are not enabled on this server")]
+    LimitsDisabled,
 }
 
 #[cfg(test)]
@@ -371,4 +487,49 @@ mod tests {
         let manager = ResourceManager::new(test_limits());
         assert_eq!(manager.job_timeout(), Duration::from_secs(60));
     }
+
+    #[tokio::test]
+    async fn test_update_limits_at_runtime() {
+        let manager = ResourceManager::new(test_limits());
+        manager.job_submitted(None).await;
+        manager.job_submitted(None).await;
+
+        let update = ResourceLimitsUpdate {
+            max_queued_jobs: Some(2),
+            job_timeout_seconds: Some(120),
+            ..Default::default()
+        };
+        let limits = manager.update_limits(&update).unwrap();
+        assert_eq!(limits.max_queued_jobs, 2);
+        assert_eq!(limits.max_concurrent_jobs, 10);
+        assert_eq!(manager.job_timeout(), Duration::from_secs(120));
+        assert!(matches!(
+            manager.check_can_submit(None).await,
+            Err(ResourceError::QueueFull { limit: 2, .. })
+        ));
+
+        // Clones share the updated limits.
+        assert_eq!(manager.clone().stats().await.max_queued_jobs, 2);
+    }
+
+    #[test]
+    fn test_update_limits_rejects_zero() {
+        let manager = ResourceManager::new(test_limits());
+        let update = ResourceLimitsUpdate {
+            max_queued_jobs: Some(5),
+            max_concurrent_jobs: Some(0),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            manager.update_limits(&update),
+            Err(ResourceError::InvalidLimit {
+                name: "max_concurrent_jobs",
+                ..
+            })
+        ));
+        assert_eq!(manager.limits().max_queued_jobs, 100);
+        assert!(ResourceLimitsUpdate::default().is_empty());
+        assert!(!update.is_empty());
+    }
 }
@@ -0,0 +1,264 @@
+//! Admin endpoints for the REST gateway (`/v1/admin/*`).
+//!
+//! Thin JSON wrappers around [`ArvakAdminServiceImpl`]; authenticated with
+//! the admin key by [`auth::admin_auth`](super::auth::admin_auth).
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+
+use crate::error::Error;
+use crate::resource_manager::{ResourceError, ResourceLimitsUpdate};
+
+use super::types::{
+    DrainRequest, DrainResponse, ErrorCode, ErrorResponse, PauseBackendResponse, RequeueJobResponse,
+};
+use super::{ApiJson, AppState, auth, error_response, job_status_string};
+
+/// Routes under `/v1/admin`, guarded by the admin key.
+pub(super) fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/v1/admin/drain", post(drain_handler))
+        .route("/v1/admin/backends/:id/pause", post(pause_backend_handler))
+        .route(
+            "/v1/admin/backends/:id/resume",
+            post(resume_backend_handler),
+        )
+        .route("/v1/admin/jobs/:id/requeue", post(requeue_job_handler))
+        .route(
+            "/v1/admin/limits",
+            get(get_limits_handler).patch(update_limits_handler),
+        )
+        .route("/v1/admin/metrics", get(metrics_snapshot_handler))
+        .route_layer(middleware::from_fn(auth::admin_auth))
+}
+
+fn admin_error(err: &Error) -> Response {
+    let error_code = match err {
+        Error::JobNotFound(_) => ErrorCode::JobNotFound,
+        Error::BackendNotFound(_) => ErrorCode::BackendNotFound,
+        Error::InvalidState(_) => ErrorCode::InvalidState,
+        Error::Unavailable(_) => ErrorCode::Unavailable,
+        _ => ErrorCode::Internal,
+    };
+    error_response(error_code, err.to_string())
+}
+
+fn limits_error(err: &ResourceError) -> Response {
+    let error_code = match err {
+        ResourceError::InvalidLimit { .. } => ErrorCode::InvalidLimit,
+        _ => ErrorCode::LimitsDisabled,
+    };
+    error_response(error_code, err.to_string())
+}
+
+/// Stop (or resume) accepting new jobs.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/drain",
+    tag = "admin",
+    security(("admin_key" = [])),
+    request_body = DrainRequest,
+    responses(
+        (status = 200, description = "The node's drain state and load", body = DrainResponse),
+        (status = 400, description = "`invalid_request`", body = ErrorResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn drain_handler(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<DrainRequest>,
+) -> impl IntoResponse {
+    state.admin().set_draining(req.drain);
+    let snapshot = state.metrics.snapshot();
+    Json(DrainResponse {
+        draining: state.control.is_draining(),
+        active_jobs: snapshot.active_jobs,
+        queued_jobs: snapshot.queued_jobs,
+    })
+}
+
+async fn set_backend_paused(state: &AppState, id: String, paused: bool) -> Response {
+    match state.admin().set_backend_paused(&id, paused).await {
+        Ok(()) => Json(PauseBackendResponse {
+            backend_id: id,
+            paused,
+        })
+        .into_response(),
+        Err(e) => admin_error(&e),
+    }
+}
+
+/// Reject submissions to a backend and report it unavailable.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/backends/{id}/pause",
+    tag = "admin",
+    security(("admin_key" = [])),
+    params(("id" = String, Path, description = "Backend ID")),
+    responses(
+        (status = 200, description = "The backend is paused", body = PauseBackendResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+        (status = 404, description = "`backend_not_found`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn pause_backend_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    set_backend_paused(&state, id, true).await
+}
+
+/// Accept submissions to a paused backend again.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/backends/{id}/resume",
+    tag = "admin",
+    security(("admin_key" = [])),
+    params(("id" = String, Path, description = "Backend ID")),
+    responses(
+        (status = 200, description = "The backend is resumed", body = PauseBackendResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+        (status = 404, description = "`backend_not_found`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn resume_backend_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    set_backend_paused(&state, id, false).await
+}
+
+/// Re-run a failed, cancelled or orphaned queued job.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/jobs/{id}/requeue",
+    tag = "admin",
+    security(("admin_key" = [])),
+    params(("id" = String, Path, description = "Job ID")),
+    responses(
+        (status = 202, description = "The job is queued again", body = RequeueJobResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+        (status = 404, description = "`job_not_found` or `backend_not_found`", body = ErrorResponse),
+        (status = 409, description = "`invalid_state`", body = ErrorResponse),
+        (status = 503, description = "`unavailable`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn requeue_job_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Response> {
+    let job_id = arvak_hal::job::JobId::new(id);
+    let status = state
+        .admin()
+        .requeue_job(&job_id)
+        .await
+        .map_err(|e| admin_error(&e))?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(RequeueJobResponse {
+            job_id: job_id.0,
+            status: job_status_string(&status),
+        }),
+    ))
+}
+
+/// The resource limits in force.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/limits",
+    tag = "admin",
+    security(("admin_key" = [])),
+    responses(
+        (status = 200, description = "Current limits", body = crate::config::ResourceLimits),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+        (status = 409, description = "`limits_disabled`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn get_limits_handler(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, Response> {
+    let resources = state
+        .resources
+        .as_ref()
+        .ok_or_else(|| limits_error(&ResourceError::LimitsDisabled))?;
+    Ok(Json(resources.limits()))
+}
+
+/// Change resource limits at runtime; unset fields are left unchanged.
+#[utoipa::path(
+    patch,
+    path = "/v1/admin/limits",
+    tag = "admin",
+    security(("admin_key" = [])),
+    request_body = ResourceLimitsUpdate,
+    responses(
+        (status = 200, description = "The updated limits", body = crate::config::ResourceLimits),
+        (status = 400, description = "`invalid_request` or `invalid_limit`", body = ErrorResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+        (status = 409, description = "`limits_disabled`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn update_limits_handler(
+    State(state): State<AppState>,
+    ApiJson(update): ApiJson<ResourceLimitsUpdate>,
+) -> Result<impl IntoResponse, Response> {
+    let limits = state
+        .admin()
+        .update_limits(&update)
+        .map_err(|e| limits_error(&e))?;
+    Ok(Json(limits))
+}
+
+/// Load, control state, limits and the Prometheus exposition.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/metrics",
+    tag = "admin",
+    security(("admin_key" = [])),
+    responses(
+        (status = 200, description = "Point-in-time snapshot", body = crate::server::AdminSnapshot),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 403, description = "`admin_disabled`", body = ErrorResponse),
+    )
+)]
+pub(super) async fn metrics_snapshot_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.admin().snapshot().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_error_status() {
+        let resp = admin_error(&Error::InvalidState("running".to_string()));
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let resp = admin_error(&Error::Unavailable("draining".to_string()));
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let resp = admin_error(&Error::JobNotFound("x".to_string()));
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_drain_request_defaults_to_drain() {
+        let req: DrainRequest = serde_json::from_str("{}").unwrap();
+        assert!(req.drain);
+        let req: DrainRequest = serde_json::from_str(r#"{"drain": false}"#).unwrap();
+        assert!(!req.drain);
+    }
+}
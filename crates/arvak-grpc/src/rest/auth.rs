@@ -1,29 +1,26 @@
 //! Bearer token authentication middleware for the REST gateway.
+//!
+//! Client routes check the API key; `/v1/admin/*` routes check a separate
+//! admin key and are disabled when none is configured.
 
-use axum::{
-    extract::Request,
-    http::StatusCode,
-    middleware::Next,
-    response::{IntoResponse, Response},
-};
+use axum::{extract::Request, middleware::Next, response::Response};
 use std::sync::Arc;
 
+use super::error_response;
+use super::types::ErrorCode;
+use crate::server::interceptors::constant_time_eq;
+
 /// Shared authentication state.
 #[derive(Clone)]
 pub struct AuthState {
     /// Expected API key. `None` means authentication is disabled.
     pub api_key: Option<Arc<String>>,
+    /// Expected admin key. `None` means the admin routes are disabled.
+    pub admin_key: Option<Arc<String>>,
 }
 
-/// Constant-time string comparison to prevent timing side-channel attacks.
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-    a.iter()
-        .zip(b.iter())
-        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
-        == 0
+fn unauthorized(error: &str) -> Response {
+    error_response(ErrorCode::Unauthorized, error)
 }
 
 /// Axum middleware that validates `Authorization: Bearer <token>`.
@@ -35,6 +32,27 @@ pub async fn bearer_auth(request: Request, next: Next) -> Response {
         None => return next.run(request).await, // auth disabled
     };
 
+    check_bearer(request, next, expected_key, "Invalid API key").await
+}
+
+/// Axum middleware that validates `Authorization: Bearer <admin key>`.
+///
+/// Unlike [`bearer_auth`], a missing key does not disable the check: admin
+/// routes answer `403 Forbidden` until an admin key is configured.
+pub async fn admin_auth(request: Request, next: Next) -> Response {
+    let auth_state = request.extensions().get::<AuthState>().cloned();
+
+    let Some(expected_key) = auth_state.and_then(|s| s.admin_key) else {
+        return error_response(
+            ErrorCode::AdminDisabled,
+            "Admin API is disabled (no admin key configured)",
+        );
+    };
+
+    check_bearer(request, next, &expected_key, "Invalid admin key").await
+}
+
+async fn check_bearer(request: Request, next: Next, expected_key: &str, invalid: &str) -> Response {
     let header = request
         .headers()
         .get("authorization")
@@ -42,28 +60,12 @@ pub async fn bearer_auth(request: Request, next: Next) -> Response {
 
     let provided = match header.and_then(|h| h.strip_prefix("Bearer ")) {
         Some(token) if !token.is_empty() => token,
-        _ => {
-            return (
-                StatusCode::UNAUTHORIZED,
-                axum::Json(super::types::ErrorResponse {
-                    error: "Missing or malformed Authorization header".to_string(),
-                    code: 401,
-                }),
-            )
-                .into_response();
-        }
+        _ => return unauthorized("Missing or malformed Authorization header"),
     };
 
     if constant_time_eq(provided.as_bytes(), expected_key.as_bytes()) {
         next.run(request).await
     } else {
-        (
-            StatusCode::UNAUTHORIZED,
-            axum::Json(super::types::ErrorResponse {
-                error: "Invalid API key".to_string(),
-                code: 401,
-            }),
-        )
-            .into_response()
+        unauthorized(invalid)
     }
 }
@@ -3,25 +3,36 @@
 //! Provides a JSON/HTTP interface that proxies to the existing in-process
 //! compilation pipeline and job execution engine. Designed for environments
 //! (such as Bloomberg BQuant) where gRPC is unavailable.
+//!
+//! Operator endpoints live under `/v1/admin` (see [`admin`]) and require the
+//! admin key instead of the client API key.
+//!
+//! The OpenAPI document is served at `/v1/openapi.json` and rendered at
+//! `/v1/docs` (see [`openapi`]). Every error body carries one of the
+//! [`ErrorCode`]s of the catalogue.
 
+pub mod admin;
 pub mod auth;
+pub mod openapi;
 pub mod types;
 
 use axum::{
-    Json, Router,
-    extract::{Path, State},
+    Json, Router, async_trait,
+    extract::{FromRequest, Path, Request, State},
     http::{HeaderValue, Method, StatusCode},
     middleware,
     response::{IntoResponse, Response},
     routing::{delete, get, post},
 };
+use serde::de::DeserializeOwned;
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 use tracing::info;
 
+use crate::control::ControlState;
 use crate::resource_manager::ResourceManager;
 use crate::server::service::circuit_utils;
-use crate::server::{BackendRegistry, JobStore};
+use crate::server::{ArvakAdminServiceImpl, BackendRegistry, JobStore};
 
 use auth::AuthState;
 use types::*;
@@ -37,9 +48,24 @@ pub struct AppState {
     pub resources: Option<crate::resource_manager::ResourceManager>,
     pub abort_handles:
         Arc<tokio::sync::RwLock<std::collections::HashMap<String, tokio::task::AbortHandle>>>,
+    pub control: ControlState,
     pub auth: AuthState,
 }
 
+impl AppState {
+    /// Admin operations over this gateway's shared state.
+    pub fn admin(&self) -> ArvakAdminServiceImpl {
+        ArvakAdminServiceImpl {
+            job_store: self.job_store.clone(),
+            backends: self.backends.clone(),
+            metrics: self.metrics.clone(),
+            resources: self.resources.clone(),
+            abort_handles: self.abort_handles.clone(),
+            control: self.control.clone(),
+        }
+    }
+}
+
 // ── Router construction ───────────────────────────────────────────────────
 
 /// Build the Axum router for the REST gateway.
@@ -58,6 +84,8 @@ pub fn rest_router(state: AppState, cors_origins: &str) -> Router {
         .route("/v1/jobs/:id/result", get(get_job_result_handler))
         .route("/v1/jobs/:id", delete(cancel_job_handler))
         .layer(middleware::from_fn(auth::bearer_auth))
+        .merge(admin::admin_routes())
+        .merge(openapi::openapi_routes())
         .layer(cors)
         .layer(axum::Extension(state.auth.clone()))
         .with_state(state)
@@ -65,7 +93,13 @@ pub fn rest_router(state: AppState, cors_origins: &str) -> Router {
 
 fn build_cors_layer(origins: &str) -> CorsLayer {
     let layer = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
         .allow_headers([
             axum::http::header::CONTENT_TYPE,
             axum::http::header::AUTHORIZATION,
@@ -84,17 +118,42 @@ fn build_cors_layer(origins: &str) -> CorsLayer {
 
 // ── Helpers ───────────────────────────────────────────────────────────────
 
-fn error_response(status: StatusCode, msg: impl Into<String>) -> Response {
+fn error_response(error_code: ErrorCode, msg: impl Into<String>) -> Response {
+    let status = error_code.status();
     (
         status,
         Json(ErrorResponse {
             error: msg.into(),
             code: status.as_u16(),
+            error_code,
         }),
     )
         .into_response()
 }
 
+/// JSON body extractor whose rejections use the gateway's error body
+/// (`invalid_request`) instead of axum's plain-text ones.
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(req, state).await {
+            Ok(Json(value)) => Ok(ApiJson(value)),
+            Err(rejection) => Err(error_response(
+                ErrorCode::InvalidRequest,
+                rejection.body_text(),
+            )),
+        }
+    }
+}
+
 fn job_status_string(status: &arvak_hal::job::JobStatus) -> String {
     match status {
         arvak_hal::job::JobStatus::Queued => "queued".to_string(),
@@ -108,13 +167,37 @@ fn job_status_string(status: &arvak_hal::job::JobStatus) -> String {
 
 // ── Handlers ──────────────────────────────────────────────────────────────
 
+/// Service health and build information.
+#[utoipa::path(
+    get,
+    path = "/v1/health",
+    tag = "service",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "The gateway is up", body = HealthResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+    )
+)]
 async fn health_handler() -> impl IntoResponse {
     Json(HealthResponse {
         status: "healthy".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        build: crate::describe_build(arvak_hal::BuildInfo::current()),
     })
 }
 
+/// List the registered backends.
+#[utoipa::path(
+    get,
+    path = "/v1/backends",
+    tag = "backends",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "All registered backends", body = ListBackendsResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 500, description = "`internal`", body = ErrorResponse),
+    )
+)]
 async fn list_backends_handler(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, Response> {
@@ -122,12 +205,14 @@ async fn list_backends_handler(
     let mut backends = Vec::new();
 
     for id in backend_ids {
-        let backend = state.backends.get(&id).map_err(|_| {
-            error_response(StatusCode::INTERNAL_SERVER_ERROR, "Backend lookup failed")
-        })?;
+        let backend = state
+            .backends
+            .get(&id)
+            .map_err(|_| error_response(ErrorCode::Internal, "Backend lookup failed"))?;
 
         let caps = backend.capabilities();
-        let is_available = backend.availability().await.is_ok_and(|a| a.is_available);
+        let is_available = !state.control.is_backend_paused(&id).await
+            && backend.availability().await.is_ok_and(|a| a.is_available);
 
         let mut supported_gates = caps.gate_set.single_qubit.clone();
         supported_gates.extend(caps.gate_set.two_qubit.clone());
@@ -146,17 +231,33 @@ async fn list_backends_handler(
     Ok(Json(ListBackendsResponse { backends }))
 }
 
+/// Describe one backend.
+#[utoipa::path(
+    get,
+    path = "/v1/backends/{id}",
+    tag = "backends",
+    security(("api_key" = [])),
+    params(("id" = String, Path, description = "Backend ID")),
+    responses(
+        (status = 200, description = "Backend capabilities", body = BackendDetailResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 404, description = "`backend_not_found`", body = ErrorResponse),
+    )
+)]
 async fn get_backend_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, Response> {
-    let backend = state
-        .backends
-        .get(&id)
-        .map_err(|_| error_response(StatusCode::NOT_FOUND, format!("Backend not found: {id}")))?;
+    let backend = state.backends.get(&id).map_err(|_| {
+        error_response(
+            ErrorCode::BackendNotFound,
+            format!("Backend not found: {id}"),
+        )
+    })?;
 
     let caps = backend.capabilities();
-    let is_available = backend.availability().await.is_ok_and(|a| a.is_available);
+    let is_available = !state.control.is_backend_paused(&id).await
+        && backend.availability().await.is_ok_and(|a| a.is_available);
     let topology_json = serde_json::to_string(&caps.topology).unwrap_or_else(|e| {
         tracing::warn!(backend_id = %id, error = %e, "Failed to serialize topology; returning empty object");
         "{}".to_string()
@@ -190,18 +291,34 @@ async fn get_backend_handler(
     }))
 }
 
+/// Compile a QASM3 circuit for a backend without running it.
+#[utoipa::path(
+    post,
+    path = "/v1/compile",
+    tag = "jobs",
+    security(("api_key" = [])),
+    request_body = CompileRequest,
+    responses(
+        (status = 200, description = "The compiled circuit", body = CompileResponse),
+        (status = 400, description = "`invalid_request`, `qasm_parse_error` or `circuit_too_complex`", body = ErrorResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 404, description = "`backend_not_found`", body = ErrorResponse),
+        (status = 500, description = "`compilation_failed` or `internal`", body = ErrorResponse),
+    )
+)]
 async fn compile_handler(
     State(state): State<AppState>,
-    Json(req): Json<CompileRequest>,
+    ApiJson(req): ApiJson<CompileRequest>,
 ) -> Result<impl IntoResponse, Response> {
     // Parse QASM3
-    let circuit = arvak_qasm3::parse(&req.qasm3)
-        .map_err(|e| error_response(StatusCode::BAD_REQUEST, format!("QASM3 parse error: {e}")))?;
+    let circuit = arvak_qasm3::parse(&req.qasm3).map_err(|e| {
+        error_response(ErrorCode::QasmParseError, format!("QASM3 parse error: {e}"))
+    })?;
 
     // Resolve backend for compilation target
     let backend = state.backends.get(&req.backend_id).map_err(|_| {
         error_response(
-            StatusCode::NOT_FOUND,
+            ErrorCode::BackendNotFound,
             format!("Backend not found: {}", req.backend_id),
         )
     })?;
@@ -209,7 +326,7 @@ async fn compile_handler(
     // Pre-flight: reject circuits that exceed complexity limits
     if let Some(ref resources) = state.resources {
         circuit_utils::validate_circuit_complexity(&circuit, Some(resources))
-            .map_err(|e| error_response(StatusCode::BAD_REQUEST, e.message().to_string()))?;
+            .map_err(|e| error_response(ErrorCode::CircuitTooComplex, e.message().to_string()))?;
     }
 
     // Compile (CPU-bound work on spawn_blocking via circuit_utils)
@@ -226,7 +343,7 @@ async fn compile_handler(
     .await
     .map_err(|e| {
         error_response(
-            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::CompilationFailed,
             format!("Compilation failed: {e}"),
         )
     })?;
@@ -237,12 +354,8 @@ async fn compile_handler(
         gate_count: compiled.dag().num_ops(),
     };
 
-    let compiled_qasm3 = arvak_qasm3::emit(&compiled).map_err(|e| {
-        error_response(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("QASM3 emit error: {e}"),
-        )
-    })?;
+    let compiled_qasm3 = arvak_qasm3::emit(&compiled)
+        .map_err(|e| error_response(ErrorCode::Internal, format!("QASM3 emit error: {e}")))?;
 
     Ok(Json(CompileResponse {
         compiled_qasm3,
@@ -250,26 +363,54 @@ async fn compile_handler(
     }))
 }
 
+/// Compile a QASM3 circuit and run it on a backend.
+#[utoipa::path(
+    post,
+    path = "/v1/jobs",
+    tag = "jobs",
+    security(("api_key" = [])),
+    request_body = SubmitJobRequest,
+    responses(
+        (status = 201, description = "The job was queued", body = SubmitJobResponse),
+        (status = 400, description = "`invalid_request`, `qasm_parse_error`, `invalid_parameters` or `circuit_too_complex`", body = ErrorResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 404, description = "`backend_not_found`", body = ErrorResponse),
+        (status = 429, description = "`rate_limited`", body = ErrorResponse),
+        (status = 500, description = "`compilation_failed` or `internal`", body = ErrorResponse),
+        (status = 503, description = "`unavailable`", body = ErrorResponse),
+    )
+)]
 async fn submit_job_handler(
     State(state): State<AppState>,
-    Json(req): Json<SubmitJobRequest>,
+    ApiJson(req): ApiJson<SubmitJobRequest>,
 ) -> Result<impl IntoResponse, Response> {
+    // Reject new work while draining or when the backend is paused
+    state
+        .control
+        .check_accepting(&req.backend_id)
+        .await
+        .map_err(|e| error_response(ErrorCode::Unavailable, e.to_string()))?;
+
     // Check resource limits
     if let Some(ref resources) = state.resources {
         resources
             .check_can_submit(None)
             .await
-            .map_err(|e| error_response(StatusCode::TOO_MANY_REQUESTS, e.to_string()))?;
+            .map_err(|e| error_response(ErrorCode::RateLimited, e.to_string()))?;
     }
 
-    // Parse QASM3
-    let circuit = arvak_qasm3::parse(&req.qasm3)
-        .map_err(|e| error_response(StatusCode::BAD_REQUEST, format!("QASM3 parse error: {e}")))?;
+    // Parse QASM3 and bind its input parameters
+    let circuit = arvak_qasm3::parse(&req.qasm3).map_err(|e| {
+        error_response(ErrorCode::QasmParseError, format!("QASM3 parse error: {e}"))
+    })?;
+    let circuit =
+        circuit_utils::bind_parameters(circuit, &req.parameters.clone().unwrap_or_default())
+            .map_err(|e| error_response(ErrorCode::InvalidParameters, e.to_string()))?;
 
     // Resolve backend
     let backend = state.backends.get(&req.backend_id).map_err(|_| {
         error_response(
-            StatusCode::NOT_FOUND,
+            ErrorCode::BackendNotFound,
             format!("Backend not found: {}", req.backend_id),
         )
     })?;
@@ -277,7 +418,7 @@ async fn submit_job_handler(
     // Pre-flight: reject circuits that exceed complexity limits
     if let Some(ref resources) = state.resources {
         circuit_utils::validate_circuit_complexity(&circuit, Some(resources))
-            .map_err(|e| error_response(StatusCode::BAD_REQUEST, e.message().to_string()))?;
+            .map_err(|e| error_response(ErrorCode::CircuitTooComplex, e.message().to_string()))?;
     }
 
     // Compile
@@ -294,7 +435,7 @@ async fn submit_job_handler(
     .await
     .map_err(|e| {
         error_response(
-            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::CompilationFailed,
             format!("Compilation failed: {e}"),
         )
     })?;
@@ -304,12 +445,7 @@ async fn submit_job_handler(
         .job_store
         .create_job(circuit, req.backend_id.clone(), req.shots, req.parameters)
         .await
-        .map_err(|e| {
-            error_response(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Job creation failed: {e}"),
-            )
-        })?;
+        .map_err(|e| error_response(ErrorCode::Internal, format!("Job creation failed: {e}")))?;
 
     info!(job_id = %job_id.0, backend = %req.backend_id, shots = req.shots, "REST job submitted");
     state.metrics.record_job_submitted(&req.backend_id);
@@ -335,6 +471,19 @@ async fn submit_job_handler(
     ))
 }
 
+/// Status of a job.
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/{id}",
+    tag = "jobs",
+    security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "The job's status", body = JobStatusResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 404, description = "`job_not_found`", body = ErrorResponse),
+    )
+)]
 async fn get_job_status_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -342,7 +491,7 @@ async fn get_job_status_handler(
     let job_id = arvak_hal::job::JobId::new(id);
     let job = state.job_store.get_job(&job_id).await.map_err(|_| {
         error_response(
-            StatusCode::NOT_FOUND,
+            ErrorCode::JobNotFound,
             format!("Job not found: {}", job_id.0),
         )
     })?;
@@ -355,6 +504,10 @@ async fn get_job_status_handler(
     Ok(Json(JobStatusResponse {
         job_id: job.id.0,
         status: job_status_string(&job.status),
+        phase: job
+            .phase
+            .or_else(|| arvak_hal::job::JobPhase::from_status(&job.status))
+            .map(|phase| phase.to_string()),
         backend_id: job.backend_id,
         shots: job.shots,
         submitted_at: job.submitted_at.timestamp(),
@@ -364,6 +517,21 @@ async fn get_job_status_handler(
     }))
 }
 
+/// Measurement counts of a completed job.
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/{id}/result",
+    tag = "jobs",
+    security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "The job's result", body = JobResultResponse),
+        (status = 202, description = "`job_not_completed`: poll again later", body = ErrorResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 404, description = "`job_not_found`", body = ErrorResponse),
+        (status = 500, description = "`internal`", body = ErrorResponse),
+    )
+)]
 async fn get_job_result_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -374,18 +542,11 @@ async fn get_job_result_handler(
         let msg = e.to_string();
         if msg.contains("not completed") {
             // Job exists but hasn't finished yet — 202 Accepted signals "try again later"
-            (
-                StatusCode::ACCEPTED,
-                axum::Json(types::ErrorResponse {
-                    error: msg,
-                    code: 202,
-                }),
-            )
-                .into_response()
+            error_response(ErrorCode::JobNotCompleted, msg)
         } else if msg.contains("not found") {
-            error_response(StatusCode::NOT_FOUND, msg)
+            error_response(ErrorCode::JobNotFound, msg)
         } else {
-            error_response(StatusCode::INTERNAL_SERVER_ERROR, msg)
+            error_response(ErrorCode::Internal, msg)
         }
     })?;
 
@@ -402,6 +563,20 @@ async fn get_job_result_handler(
     }))
 }
 
+/// Cancel a job that has not finished.
+#[utoipa::path(
+    delete,
+    path = "/v1/jobs/{id}",
+    tag = "jobs",
+    security(("api_key" = [])),
+    params(("id" = String, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Whether the job was cancelled", body = CancelJobResponse),
+        (status = 401, description = "`unauthorized`", body = ErrorResponse),
+        (status = 404, description = "`job_not_found`", body = ErrorResponse),
+        (status = 500, description = "`internal`", body = ErrorResponse),
+    )
+)]
 async fn cancel_job_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -410,7 +585,7 @@ async fn cancel_job_handler(
 
     let job = state.job_store.get_job(&job_id).await.map_err(|_| {
         error_response(
-            StatusCode::NOT_FOUND,
+            ErrorCode::JobNotFound,
             format!("Job not found: {}", job_id.0),
         )
     })?;
@@ -434,12 +609,7 @@ async fn cancel_job_handler(
         .job_store
         .update_status(&job_id, arvak_hal::job::JobStatus::Cancelled)
         .await
-        .map_err(|e| {
-            error_response(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Cancel failed: {e}"),
-            )
-        })?;
+        .map_err(|e| error_response(ErrorCode::Internal, format!("Cancel failed: {e}")))?;
 
     Ok(Json(CancelJobResponse {
         success: true,
@@ -456,6 +626,7 @@ mod tests {
         let resp = HealthResponse {
             status: "healthy".to_string(),
             version: "1.0.0".to_string(),
+            build: arvak_hal::BuildInfo::default(),
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("healthy"));
@@ -466,9 +637,11 @@ mod tests {
         let resp = ErrorResponse {
             error: "not found".to_string(),
             code: 404,
+            error_code: ErrorCode::JobNotFound,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("404"));
+        assert!(json.contains(r#""error_code":"job_not_found""#));
     }
 
     #[test]
@@ -0,0 +1,181 @@
+//! OpenAPI document of the REST gateway.
+//!
+//! [`ApiDoc`] is derived at compile time from the handlers' `utoipa::path`
+//! annotations and the request/response types, so it follows the code. The
+//! gateway serves it at `/v1/openapi.json` and renders it with Swagger UI at
+//! `/v1/docs`. Both routes are public: the document describes the API, not
+//! the deployment. `arvak-rest-gateway --openapi` prints it without starting
+//! a server, e.g. to generate client SDKs.
+//!
+//! The description of the `ErrorCode` schema is the error catalogue: every
+//! code with its HTTP status and meaning.
+
+use axum::{Json, Router, response::Html, routing::get};
+use utoipa::openapi::schema::Schema;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::openapi::{OpenApi as OpenApiDocument, RefOr};
+use utoipa::{Modify, OpenApi};
+
+use super::AppState;
+use super::types::{ErrorCode, ErrorResponse};
+
+/// The gateway's OpenAPI 3.1 document.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Arvak REST Gateway",
+        description = "JSON/HTTP interface to Arvak's compilation pipeline and job execution."
+    ),
+    paths(
+        super::health_handler,
+        super::list_backends_handler,
+        super::get_backend_handler,
+        super::compile_handler,
+        super::submit_job_handler,
+        super::get_job_status_handler,
+        super::get_job_result_handler,
+        super::cancel_job_handler,
+        super::admin::drain_handler,
+        super::admin::pause_backend_handler,
+        super::admin::resume_backend_handler,
+        super::admin::requeue_job_handler,
+        super::admin::get_limits_handler,
+        super::admin::update_limits_handler,
+        super::admin::metrics_snapshot_handler,
+    ),
+    components(schemas(ErrorCode, ErrorResponse)),
+    modifiers(&SecuritySchemes, &ErrorCatalogue),
+    tags(
+        (name = "service", description = "Health and build information"),
+        (name = "backends", description = "Registered backends and their capabilities"),
+        (name = "jobs", description = "Compilation and job execution"),
+        (name = "admin", description = "Operator endpoints, authenticated with the admin key"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Bearer schemes for the client and admin keys.
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        for name in ["api_key", "admin_key"] {
+            components.add_security_scheme(
+                name,
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+        }
+    }
+}
+
+/// Writes the error catalogue into the `ErrorCode` schema.
+struct ErrorCatalogue;
+
+impl Modify for ErrorCatalogue {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let Some(components) = openapi.components.as_mut() else {
+            return;
+        };
+        if let Some(RefOr::T(Schema::Object(schema))) = components.schemas.get_mut("ErrorCode") {
+            schema.description = Some(error_catalogue());
+        }
+    }
+}
+
+/// The error catalogue as a Markdown table.
+pub fn error_catalogue() -> String {
+    let mut table = String::from(
+        "Stable machine-readable error code.\n\n| Code | Status | Meaning |\n|---|---|---|\n",
+    );
+    for code in ErrorCode::ALL {
+        table.push_str(&format!(
+            "| `{code}` | {} | {} |\n",
+            code.status().as_u16(),
+            code.description()
+        ));
+    }
+    table
+}
+
+/// Public routes serving the document and its Swagger UI.
+pub(super) fn openapi_routes() -> Router<AppState> {
+    Router::new()
+        .route("/v1/openapi.json", get(openapi_handler))
+        .route("/v1/docs", get(swagger_ui_handler))
+}
+
+async fn openapi_handler() -> Json<OpenApiDocument> {
+    Json(ApiDoc::openapi())
+}
+
+async fn swagger_ui_handler() -> Html<&'static str> {
+    Html(SWAGGER_UI)
+}
+
+/// Swagger UI page loading the document relative to `/v1/docs`, so it keeps
+/// working behind a reverse proxy that adds a path prefix.
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Arvak REST Gateway</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({ url: "openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_covers_all_routes() {
+        let doc = ApiDoc::openapi();
+        let paths: Vec<&str> = doc.paths.paths.keys().map(String::as_str).collect();
+        for path in [
+            "/v1/health",
+            "/v1/backends",
+            "/v1/backends/{id}",
+            "/v1/compile",
+            "/v1/jobs",
+            "/v1/jobs/{id}",
+            "/v1/jobs/{id}/result",
+            "/v1/admin/drain",
+            "/v1/admin/backends/{id}/pause",
+            "/v1/admin/backends/{id}/resume",
+            "/v1/admin/jobs/{id}/requeue",
+            "/v1/admin/limits",
+            "/v1/admin/metrics",
+        ] {
+            assert!(paths.contains(&path), "{path} missing from the document");
+        }
+
+        let json = doc.to_json().unwrap();
+        assert!(json.contains(r#""openapi":"3.1"#));
+        assert!(json.contains("admin_key"));
+    }
+
+    #[test]
+    fn test_error_catalogue_lists_every_code() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schema = &doc["components"]["schemas"]["ErrorCode"];
+        let values = schema["enum"].as_array().unwrap();
+        assert_eq!(values.len(), ErrorCode::ALL.len());
+
+        let description = schema["description"].as_str().unwrap();
+        for code in ErrorCode::ALL {
+            // The Display name is the serialized one.
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+            assert!(description.contains(&format!("`{code}`")));
+        }
+        assert_eq!(ErrorCode::JobNotCompleted.status().as_u16(), 202);
+    }
+}
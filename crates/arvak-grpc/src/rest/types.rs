@@ -1,12 +1,14 @@
 //! JSON request/response types for the REST gateway.
 
+use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::ToSchema;
 
 // ── Requests ──────────────────────────────────────────────────────────────
 
 /// POST /v1/compile
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CompileRequest {
     /// OpenQASM 3 circuit string.
     pub qasm3: String,
@@ -18,7 +20,7 @@ pub struct CompileRequest {
 }
 
 /// POST /v1/jobs
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SubmitJobRequest {
     /// OpenQASM 3 circuit string.
     pub qasm3: String,
@@ -30,12 +32,24 @@ pub struct SubmitJobRequest {
     /// Optimization level (0–3).
     #[serde(default = "default_optimization_level")]
     pub optimization_level: u32,
-    /// Optional parameter bindings for parametric circuits (DEBT-25).
-    /// Keys are OpenQASM 3.0 `input float[64]` parameter names.
+    /// Values for the circuit's OpenQASM 3.0 `input float[64]` parameters,
+    /// bound into the circuit before compilation.
     #[serde(default)]
     pub parameters: Option<std::collections::HashMap<String, f64>>,
 }
 
+/// POST /v1/admin/drain
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DrainRequest {
+    /// `true` stops accepting new jobs, `false` resumes.
+    #[serde(default = "default_drain")]
+    pub drain: bool,
+}
+
+fn default_drain() -> bool {
+    true
+}
+
 fn default_shots() -> u32 {
     1024
 }
@@ -47,14 +61,16 @@ fn default_optimization_level() -> u32 {
 // ── Responses ─────────────────────────────────────────────────────────────
 
 /// GET /v1/health
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub version: String,
+    #[schema(value_type = Object)]
+    pub build: arvak_hal::BuildInfo,
 }
 
 /// Backend summary in list response.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BackendSummary {
     pub backend_id: String,
     pub name: String,
@@ -65,7 +81,7 @@ pub struct BackendSummary {
 }
 
 /// GET /v1/backends
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ListBackendsResponse {
     pub backends: Vec<BackendSummary>,
 }
@@ -76,14 +92,16 @@ pub struct ListBackendsResponse {
 /// follow HAL Contract v2 §10 / §4.1 (structured JSON). The flat
 /// `max_qubits` / `supported_gates` / `*_json` fields predate that shape
 /// and are kept for backward compatibility — prefer the structured ones.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BackendDetailResponse {
     pub backend_id: String,
     pub name: String,
     pub is_available: bool,
     // ── HAL Contract v2 §4.1 shape ────────────────────────────────
     pub num_qubits: u32,
+    #[schema(value_type = Object)]
     pub gate_set: arvak_hal::capability::GateSet,
+    #[schema(value_type = Object)]
     pub topology: arvak_hal::capability::Topology,
     pub max_shots: u32,
     /// Maximum gate operations per circuit; `null` = no limit.
@@ -92,6 +110,7 @@ pub struct BackendDetailResponse {
     pub is_simulator: bool,
     pub features: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
     pub noise_profile: Option<arvak_hal::capability::NoiseProfile>,
     // ── Legacy flat fields (pre-§10; deprecated) ──────────────────
     pub max_qubits: u32,
@@ -103,13 +122,13 @@ pub struct BackendDetailResponse {
 }
 
 /// POST /v1/compile response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CompileResponse {
     pub compiled_qasm3: String,
     pub stats: CompileStats,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CompileStats {
     pub num_qubits: u32,
     pub depth: u32,
@@ -117,16 +136,19 @@ pub struct CompileStats {
 }
 
 /// POST /v1/jobs response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SubmitJobResponse {
     pub job_id: String,
 }
 
 /// GET /v1/jobs/{id} response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct JobStatusResponse {
     pub job_id: String,
     pub status: String,
+    /// Where an unfinished job is, e.g. `"transferring"` or `"calibrating"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
     pub backend_id: String,
     pub shots: u32,
     pub submitted_at: i64,
@@ -139,7 +161,7 @@ pub struct JobStatusResponse {
 }
 
 /// GET /v1/jobs/{id}/result response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct JobResultResponse {
     pub job_id: String,
     pub counts: HashMap<String, u64>,
@@ -149,15 +171,166 @@ pub struct JobResultResponse {
 }
 
 /// DELETE /v1/jobs/{id} response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CancelJobResponse {
     pub success: bool,
     pub message: String,
 }
 
-/// Generic error body.
-#[derive(Debug, Serialize)]
+/// POST /v1/admin/drain response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DrainResponse {
+    pub draining: bool,
+    pub active_jobs: u64,
+    pub queued_jobs: u64,
+}
+
+/// POST /v1/admin/backends/{id}/pause and /resume response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PauseBackendResponse {
+    pub backend_id: String,
+    pub paused: bool,
+}
+
+/// POST /v1/admin/jobs/{id}/requeue response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequeueJobResponse {
+    pub job_id: String,
+    pub status: String,
+}
+
+/// Error body of every failed request.
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorResponse {
+    /// Human-readable message; its wording may change between releases.
     pub error: String,
+    /// HTTP status code.
     pub code: u16,
+    /// Stable machine-readable error code.
+    pub error_code: ErrorCode,
+}
+
+// ── Error catalogue ───────────────────────────────────────────────────────
+
+/// Machine-readable error codes returned in [`ErrorResponse::error_code`].
+///
+/// Each code always comes with the same HTTP status. Clients should branch
+/// on the code rather than on the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidRequest,
+    QasmParseError,
+    InvalidParameters,
+    CircuitTooComplex,
+    InvalidLimit,
+    Unauthorized,
+    AdminDisabled,
+    BackendNotFound,
+    JobNotFound,
+    JobNotCompleted,
+    InvalidState,
+    LimitsDisabled,
+    RateLimited,
+    Unavailable,
+    CompilationFailed,
+    Internal,
+}
+
+impl ErrorCode {
+    /// Every error code, in catalogue order.
+    pub const ALL: [ErrorCode; 16] = [
+        ErrorCode::InvalidRequest,
+        ErrorCode::QasmParseError,
+        ErrorCode::InvalidParameters,
+        ErrorCode::CircuitTooComplex,
+        ErrorCode::InvalidLimit,
+        ErrorCode::Unauthorized,
+        ErrorCode::AdminDisabled,
+        ErrorCode::BackendNotFound,
+        ErrorCode::JobNotFound,
+        ErrorCode::JobNotCompleted,
+        ErrorCode::InvalidState,
+        ErrorCode::LimitsDisabled,
+        ErrorCode::RateLimited,
+        ErrorCode::Unavailable,
+        ErrorCode::CompilationFailed,
+        ErrorCode::Internal,
+    ];
+
+    /// The code as it appears in JSON.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::QasmParseError => "qasm_parse_error",
+            ErrorCode::InvalidParameters => "invalid_parameters",
+            ErrorCode::CircuitTooComplex => "circuit_too_complex",
+            ErrorCode::InvalidLimit => "invalid_limit",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::AdminDisabled => "admin_disabled",
+            ErrorCode::BackendNotFound => "backend_not_found",
+            ErrorCode::JobNotFound => "job_not_found",
+            ErrorCode::JobNotCompleted => "job_not_completed",
+            ErrorCode::InvalidState => "invalid_state",
+            ErrorCode::LimitsDisabled => "limits_disabled",
+            ErrorCode::RateLimited => "rate_limited",
+            ErrorCode::Unavailable => "unavailable",
+            ErrorCode::CompilationFailed => "compilation_failed",
+            ErrorCode::Internal => "internal",
+        }
+    }
+
+    /// HTTP status the code is returned with.
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::InvalidRequest
+            | ErrorCode::QasmParseError
+            | ErrorCode::InvalidParameters
+            | ErrorCode::CircuitTooComplex
+            | ErrorCode::InvalidLimit => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::AdminDisabled => StatusCode::FORBIDDEN,
+            ErrorCode::BackendNotFound | ErrorCode::JobNotFound => StatusCode::NOT_FOUND,
+            // Not a failure: the result is not ready yet.
+            ErrorCode::JobNotCompleted => StatusCode::ACCEPTED,
+            ErrorCode::InvalidState | ErrorCode::LimitsDisabled => StatusCode::CONFLICT,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::CompilationFailed | ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// When the code is returned.
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorCode::InvalidRequest => {
+                "The request body is not valid JSON or does not match the endpoint's schema"
+            }
+            ErrorCode::QasmParseError => "The `qasm3` source does not parse",
+            ErrorCode::InvalidParameters => {
+                "`parameters` cannot be bound to the circuit's `input` declarations"
+            }
+            ErrorCode::CircuitTooComplex => {
+                "The circuit exceeds the gateway's qubit, gate or depth limits"
+            }
+            ErrorCode::InvalidLimit => "A resource limit update has an invalid value",
+            ErrorCode::Unauthorized => "The bearer token is missing, malformed or wrong",
+            ErrorCode::AdminDisabled => "Admin endpoints are disabled: no admin key is configured",
+            ErrorCode::BackendNotFound => "No backend with the given ID is registered",
+            ErrorCode::JobNotFound => "No job with the given ID exists",
+            ErrorCode::JobNotCompleted => "The job has not finished yet; poll again later",
+            ErrorCode::InvalidState => "The job's state does not allow the operation",
+            ErrorCode::LimitsDisabled => "The gateway runs without resource limits",
+            ErrorCode::RateLimited => "Too many jobs are running or queued; retry later",
+            ErrorCode::Unavailable => "The gateway is draining or the backend is paused",
+            ErrorCode::CompilationFailed => "Compiling the circuit for the backend failed",
+            ErrorCode::Internal => "An unexpected server-side error",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
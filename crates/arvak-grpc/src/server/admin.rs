@@ -0,0 +1,337 @@
+//! Admin gRPC service for operating a running server.
+//!
+//! Exposes drain/resume, backend pause, job requeue, runtime resource-limit
+//! changes and a metrics snapshot. The operations are plain async methods on
+//! [`ArvakAdminServiceImpl`] so the REST gateway can reuse them; the
+//! `ArvakAdminService` trait impl maps them onto the protobuf types.
+//!
+//! The admin service carries no authentication of its own. Mount it behind
+//! [`AdminAuthInterceptor`](super::AdminAuthInterceptor) with a key distinct
+//! from the client API key.
+
+use arvak_hal::job::{JobId, JobStatus};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::config::ResourceLimits;
+use crate::control::ControlState;
+use crate::error::{Error, Result};
+use crate::metrics::Metrics;
+use crate::proto::{
+    DrainNodeRequest, DrainNodeResponse, GetMetricsSnapshotRequest, GetMetricsSnapshotResponse,
+    PauseBackendRequest, PauseBackendResponse, RequeueJobRequest, RequeueJobResponse,
+    ResourceLimitsInfo, UpdateResourceLimitsRequest, UpdateResourceLimitsResponse,
+    arvak_admin_service_server,
+};
+use crate::resource_manager::{ResourceError, ResourceLimitsUpdate, ResourceManager};
+use crate::server::service::job_execution::{spawn_job_execution, to_proto_state};
+use crate::server::{BackendRegistry, JobStore};
+
+/// Point-in-time view of server load and control state.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AdminSnapshot {
+    /// Unix timestamp (seconds) the snapshot was taken.
+    pub timestamp: i64,
+    /// Jobs currently running.
+    pub active_jobs: u64,
+    /// Jobs waiting to run.
+    pub queued_jobs: u64,
+    /// Whether the node is draining.
+    pub draining: bool,
+    /// Backends that are not accepting submissions.
+    pub paused_backends: Vec<String>,
+    /// Limits in force, if the server enforces resource limits.
+    pub limits: Option<ResourceLimits>,
+    /// Full Prometheus exposition.
+    pub prometheus_text: String,
+}
+
+/// Arvak admin gRPC service implementation.
+///
+/// Create one with [`ArvakServiceImpl::admin_service`](super::ArvakServiceImpl::admin_service)
+/// so it shares state with the client-facing service.
+#[derive(Clone)]
+pub struct ArvakAdminServiceImpl {
+    pub(crate) job_store: Arc<JobStore>,
+    pub(crate) backends: Arc<BackendRegistry>,
+    pub(crate) metrics: Metrics,
+    pub(crate) resources: Option<ResourceManager>,
+    pub(crate) abort_handles: Arc<tokio::sync::RwLock<HashMap<String, tokio::task::AbortHandle>>>,
+    pub(crate) control: ControlState,
+}
+
+impl ArvakAdminServiceImpl {
+    /// Start (`true`) or stop (`false`) draining the node.
+    pub fn set_draining(&self, drain: bool) {
+        if drain != self.control.is_draining() {
+            info!(drain, "Node drain state changed");
+        }
+        self.control.set_draining(drain);
+    }
+
+    /// Pause or resume submissions to a backend.
+    pub async fn set_backend_paused(&self, backend_id: &str, paused: bool) -> Result<()> {
+        if !self.backends.contains(backend_id) {
+            return Err(Error::BackendNotFound(backend_id.to_string()));
+        }
+        if self.control.set_backend_paused(backend_id, paused).await {
+            info!(backend_id, paused, "Backend pause state changed");
+        }
+        self.metrics.set_backend_available(backend_id, !paused);
+        Ok(())
+    }
+
+    /// Reset a job to `Queued` and execute it again.
+    ///
+    /// Only failed and cancelled jobs, or queued jobs with no execution task
+    /// (e.g. left over from a previous process), can be requeued.
+    pub async fn requeue_job(&self, job_id: &JobId) -> Result<JobStatus> {
+        let job = self.job_store.get_job(job_id).await?;
+        let scheduled = self.abort_handles.read().await.contains_key(&job_id.0);
+        let requeueable = match job.status {
+            JobStatus::Failed(_) | JobStatus::Cancelled => true,
+            JobStatus::Queued => !scheduled,
+            _ => false,
+        };
+        if !requeueable {
+            return Err(Error::InvalidState(format!(
+                "job {} is {} and cannot be requeued",
+                job_id.0, job.status
+            )));
+        }
+
+        self.control.check_accepting(&job.backend_id).await?;
+        let backend = self.backends.get(&job.backend_id)?;
+        if let Some(ref resources) = self.resources {
+            resources
+                .check_can_submit(None)
+                .await
+                .map_err(|e| Error::Unavailable(e.to_string()))?;
+        }
+
+        let job = self.job_store.requeue(job_id).await?;
+        info!(job_id = %job_id.0, backend_id = %job.backend_id, "Job requeued");
+
+        self.metrics.record_job_submitted(&job.backend_id);
+        if let Some(ref resources) = self.resources {
+            resources.job_submitted(None).await;
+        }
+
+        spawn_job_execution(
+            self.job_store.clone(),
+            backend,
+            job_id.clone(),
+            self.metrics.clone(),
+            self.resources.clone(),
+            self.abort_handles.clone(),
+        )
+        .await;
+
+        Ok(job.status)
+    }
+
+    /// Apply a partial update to the resource limits.
+    pub fn update_limits(
+        &self,
+        update: &ResourceLimitsUpdate,
+    ) -> std::result::Result<ResourceLimits, ResourceError> {
+        let resources = self
+            .resources
+            .as_ref()
+            .ok_or(ResourceError::LimitsDisabled)?;
+        let limits = resources.update_limits(update)?;
+        info!(?limits, "Resource limits updated");
+        Ok(limits)
+    }
+
+    /// Take a snapshot of server load and control state.
+    pub async fn snapshot(&self) -> AdminSnapshot {
+        let metrics = self.metrics.snapshot();
+        AdminSnapshot {
+            timestamp: Utc::now().timestamp(),
+            active_jobs: metrics.active_jobs,
+            queued_jobs: metrics.queued_jobs,
+            draining: self.control.is_draining(),
+            paused_backends: self.control.paused_backends().await,
+            limits: self.resources.as_ref().map(ResourceManager::limits),
+            prometheus_text: self.metrics.export().unwrap_or_default(),
+        }
+    }
+}
+
+fn limits_to_proto(limits: &ResourceLimits) -> ResourceLimitsInfo {
+    ResourceLimitsInfo {
+        max_concurrent_jobs: limits.max_concurrent_jobs as u64,
+        max_queued_jobs: limits.max_queued_jobs as u64,
+        job_timeout_seconds: limits.job_timeout_seconds,
+        max_result_size_bytes: limits.max_result_size_bytes as u64,
+        rate_limit_rps: limits.rate_limit_rps,
+        max_circuit_qubits: limits.max_circuit_qubits as u64,
+        max_circuit_gates: limits.max_circuit_gates as u64,
+        compilation_timeout_seconds: limits.compilation_timeout_seconds,
+    }
+}
+
+/// Convert protobuf limits to an update; zero fields are left unchanged.
+fn limits_from_proto(info: &ResourceLimitsInfo) -> ResourceLimitsUpdate {
+    let size = |v: u64| (v > 0).then(|| usize::try_from(v).unwrap_or(usize::MAX));
+    let secs = |v: u64| (v > 0).then_some(v);
+    ResourceLimitsUpdate {
+        max_concurrent_jobs: size(info.max_concurrent_jobs),
+        max_queued_jobs: size(info.max_queued_jobs),
+        job_timeout_seconds: secs(info.job_timeout_seconds),
+        max_result_size_bytes: size(info.max_result_size_bytes),
+        rate_limit_rps: (info.rate_limit_rps > 0).then_some(info.rate_limit_rps),
+        max_circuit_qubits: size(info.max_circuit_qubits),
+        max_circuit_gates: size(info.max_circuit_gates),
+        compilation_timeout_seconds: secs(info.compilation_timeout_seconds),
+    }
+}
+
+#[tonic::async_trait]
+impl arvak_admin_service_server::ArvakAdminService for ArvakAdminServiceImpl {
+    async fn drain_node(
+        &self,
+        request: Request<DrainNodeRequest>,
+    ) -> std::result::Result<Response<DrainNodeResponse>, Status> {
+        self.set_draining(request.into_inner().drain);
+        let snapshot = self.metrics.snapshot();
+        Ok(Response::new(DrainNodeResponse {
+            draining: self.control.is_draining(),
+            active_jobs: snapshot.active_jobs,
+            queued_jobs: snapshot.queued_jobs,
+        }))
+    }
+
+    async fn pause_backend(
+        &self,
+        request: Request<PauseBackendRequest>,
+    ) -> std::result::Result<Response<PauseBackendResponse>, Status> {
+        let req = request.into_inner();
+        self.set_backend_paused(&req.backend_id, req.paused)
+            .await
+            .map_err(Status::from)?;
+        Ok(Response::new(PauseBackendResponse {
+            backend_id: req.backend_id,
+            paused: req.paused,
+        }))
+    }
+
+    async fn requeue_job(
+        &self,
+        request: Request<RequeueJobRequest>,
+    ) -> std::result::Result<Response<RequeueJobResponse>, Status> {
+        let job_id = JobId::new(request.into_inner().job_id);
+        let status = ArvakAdminServiceImpl::requeue_job(self, &job_id)
+            .await
+            .map_err(Status::from)?;
+        Ok(Response::new(RequeueJobResponse {
+            job_id: job_id.0,
+            state: to_proto_state(&status) as i32,
+        }))
+    }
+
+    async fn update_resource_limits(
+        &self,
+        request: Request<UpdateResourceLimitsRequest>,
+    ) -> std::result::Result<Response<UpdateResourceLimitsResponse>, Status> {
+        let update = request
+            .into_inner()
+            .limits
+            .as_ref()
+            .map(limits_from_proto)
+            .unwrap_or_default();
+        let limits = self.update_limits(&update).map_err(|e| match e {
+            ResourceError::InvalidLimit { .. } => Status::invalid_argument(e.to_string()),
+            _ => Status::failed_precondition(e.to_string()),
+        })?;
+        Ok(Response::new(UpdateResourceLimitsResponse {
+            limits: Some(limits_to_proto(&limits)),
+        }))
+    }
+
+    async fn get_metrics_snapshot(
+        &self,
+        _request: Request<GetMetricsSnapshotRequest>,
+    ) -> std::result::Result<Response<GetMetricsSnapshotResponse>, Status> {
+        let snapshot = self.snapshot().await;
+        Ok(Response::new(GetMetricsSnapshotResponse {
+            timestamp: snapshot.timestamp,
+            active_jobs: snapshot.active_jobs,
+            queued_jobs: snapshot.queued_jobs,
+            draining: snapshot.draining,
+            paused_backends: snapshot.paused_backends,
+            limits: snapshot.limits.as_ref().map(limits_to_proto),
+            prometheus_text: snapshot.prometheus_text,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::ArvakServiceImpl;
+    use arvak_ir::circuit::Circuit;
+
+    #[test]
+    fn test_limits_from_proto_skips_zero() {
+        let update = limits_from_proto(&ResourceLimitsInfo {
+            max_queued_jobs: 5,
+            rate_limit_rps: 20,
+            ..Default::default()
+        });
+        assert_eq!(update.max_queued_jobs, Some(5));
+        assert_eq!(update.rate_limit_rps, Some(20));
+        assert_eq!(update.max_concurrent_jobs, None);
+        assert_eq!(update.job_timeout_seconds, None);
+    }
+
+    #[tokio::test]
+    async fn test_pause_unknown_backend() {
+        let admin = ArvakServiceImpl::new().admin_service();
+        let result = admin.set_backend_paused("nonexistent", true).await;
+        assert!(matches!(result, Err(Error::BackendNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_requeue_rejects_completed_job() {
+        let service = ArvakServiceImpl::new();
+        let admin = service.admin_service();
+        let job_id = service
+            .job_store
+            .create_job(
+                Circuit::with_size("test", 1, 0),
+                "simulator".into(),
+                10,
+                None,
+            )
+            .await
+            .unwrap();
+        service
+            .job_store
+            .update_status(&job_id, JobStatus::Completed)
+            .await
+            .unwrap();
+
+        let result = admin.requeue_job(&job_id).await;
+        assert!(matches!(result, Err(Error::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_update_limits_requires_resource_manager() {
+        let admin = ArvakServiceImpl::new().admin_service();
+        let update = ResourceLimitsUpdate {
+            max_queued_jobs: Some(5),
+            ..Default::default()
+        };
+        assert!(matches!(
+            admin.update_limits(&update),
+            Err(ResourceError::LimitsDisabled)
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Backend registry for managing available quantum backends.
 
 use arvak_hal::backend::Backend;
+use arvak_hal::config::BackendsConfig;
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 
@@ -9,6 +10,8 @@ use crate::error::{Error, Result};
 /// Registry of available backends.
 pub struct BackendRegistry {
     backends: FxHashMap<String, Arc<dyn Backend>>,
+    /// Alternative IDs, mapping each alias to a backend ID.
+    aliases: FxHashMap<String, String>,
 }
 
 impl BackendRegistry {
@@ -16,18 +19,28 @@ impl BackendRegistry {
     pub fn new() -> Self {
         Self {
             backends: FxHashMap::default(),
+            aliases: FxHashMap::default(),
         }
     }
 
     /// Register a backend.
     pub fn register(&mut self, id: String, backend: Arc<dyn Backend>) {
+        self.aliases.remove(&id);
         self.backends.insert(id, backend);
     }
 
-    /// Get a backend by ID.
+    /// Register an alternative ID for backend `id`.
+    ///
+    /// Aliases resolve in [`get`](Self::get) but are not listed.
+    pub fn register_alias(&mut self, alias: String, id: String) {
+        self.aliases.insert(alias, id);
+    }
+
+    /// Get a backend by ID or alias.
     pub fn get(&self, id: &str) -> Result<Arc<dyn Backend>> {
         self.backends
             .get(id)
+            .or_else(|| self.backends.get(self.aliases.get(id)?))
             .cloned()
             .ok_or_else(|| Error::BackendNotFound(id.to_string()))
     }
@@ -37,9 +50,21 @@ impl BackendRegistry {
         self.backends.keys().cloned().collect()
     }
 
-    /// Check if a backend exists.
+    /// Check if a backend exists under an ID or alias.
     pub fn contains(&self, id: &str) -> bool {
-        self.backends.contains_key(id)
+        self.get(id).is_ok()
+    }
+
+    /// Wrap every registered backend so its calls fail as `injector`
+    /// decides.
+    #[cfg(feature = "chaos")]
+    pub fn inject_faults(&mut self, injector: &Arc<arvak_hal::chaos::FaultInjector>) {
+        for backend in self.backends.values_mut() {
+            *backend = Arc::new(arvak_hal::chaos::ChaosBackend::new(
+                backend.clone(),
+                injector.clone(),
+            ));
+        }
     }
 }
 
@@ -66,6 +91,42 @@ pub fn create_default_registry() -> BackendRegistry {
     registry
 }
 
+/// Register the backends of a typed configuration document under their
+/// configured names and aliases.
+///
+/// Sections may use any adapter compiled into the server. An invalid
+/// section, or one naming another adapter, is an error and nothing is
+/// registered.
+pub fn register_configured_backends(
+    registry: &mut BackendRegistry,
+    config: &BackendsConfig,
+) -> Result<()> {
+    #[allow(unused_mut)]
+    let mut configured = arvak_hal::BackendRegistry::new();
+    #[cfg(feature = "simulator")]
+    configured.register::<arvak_adapter_sim::SimulatorBackend>("simulator");
+    #[cfg(feature = "ibm")]
+    configured.register::<arvak_adapter_ibm::IbmBackend>("ibm");
+    #[cfg(feature = "quantinuum")]
+    configured.register::<arvak_adapter_quantinuum::QuantinuumBackend>("quantinuum");
+    #[cfg(feature = "aqt")]
+    configured.register::<arvak_adapter_aqt::AqtBackend>("aqt");
+    #[cfg(feature = "quandela")]
+    configured.register::<arvak_adapter_quandela::QuandelaBackend>("quandela");
+
+    configured.instantiate(config)?;
+    for name in configured.instance_names() {
+        if let Some(backend) = configured.instance(&name) {
+            tracing::info!("Registered configured backend '{name}'");
+            registry.register(name, backend);
+        }
+    }
+    for (alias, name) in configured.instance_aliases() {
+        registry.register_alias(alias.to_string(), name.to_string());
+    }
+    Ok(())
+}
+
 /// Register AWS Braket backends (async due to credential resolution).
 ///
 /// Attempts to connect to the SV1 simulator. Logs a warning and skips
@@ -183,6 +244,33 @@ mod tests {
         assert!(matches!(result, Err(Error::BackendNotFound(_))));
     }
 
+    #[test]
+    fn test_register_configured_backends() {
+        let config = BackendsConfig::from_toml_str(
+            "[backends.local-4]\nadapter = \"simulator\"\nmax_qubits = 4\n\n\
+             [aliases]\nsmall = \"local-4\"\n",
+        )
+        .unwrap();
+        let mut registry = BackendRegistry::new();
+        let result = register_configured_backends(&mut registry, &config);
+
+        #[cfg(feature = "simulator")]
+        {
+            result.unwrap();
+            let backend = registry.get("local-4").unwrap();
+            assert_eq!(backend.capabilities().num_qubits, 4);
+            assert_eq!(registry.get("small").unwrap().capabilities().num_qubits, 4);
+            assert_eq!(registry.list(), vec!["local-4"]);
+        }
+        #[cfg(not(feature = "simulator"))]
+        assert!(result.is_err());
+
+        let unavailable =
+            BackendsConfig::from_toml_str("[backends.cloud]\nadapter = \"ddsim\"\n").unwrap();
+        assert!(register_configured_backends(&mut registry, &unavailable).is_err());
+        assert!(!registry.contains("cloud"));
+    }
+
     #[test]
     fn test_list_backends() {
         let registry = create_default_registry();
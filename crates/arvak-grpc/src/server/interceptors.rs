@@ -3,6 +3,7 @@
 //! This module provides interceptors for:
 //! - Request ID generation and propagation
 //! - Request/response logging
+//! - Admin key authentication for the admin service
 //!
 //! For production authentication and rate limiting, deploy behind
 //! nginx or Envoy with mTLS. See DEPLOYMENT.md for details.
@@ -102,6 +103,59 @@ impl tonic::service::Interceptor for LoggingInterceptor {
     }
 }
 
+/// Admin key metadata key.
+pub const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+/// Authentication interceptor for the admin service.
+///
+/// Requires the `x-admin-key` metadata value to match the configured admin
+/// key. Keep the admin key distinct from the client API key so ordinary
+/// clients cannot drain the node or change limits.
+#[derive(Clone)]
+pub struct AdminAuthInterceptor {
+    admin_key: std::sync::Arc<String>,
+}
+
+impl AdminAuthInterceptor {
+    pub fn new(admin_key: impl Into<String>) -> Self {
+        Self {
+            admin_key: std::sync::Arc::new(admin_key.into()),
+        }
+    }
+}
+
+impl tonic::service::Interceptor for AdminAuthInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let provided = request
+            .metadata()
+            .get(ADMIN_KEY_HEADER)
+            .and_then(|v| v.to_str().ok());
+
+        match provided {
+            Some(key) if constant_time_eq(key.as_bytes(), self.admin_key.as_bytes()) => Ok(request),
+            _ => {
+                warn!("Rejected admin request with invalid or missing admin key");
+                Err(Status::unauthenticated("Invalid or missing admin key"))
+            }
+        }
+    }
+}
+
+/// Constant-time key comparison to prevent timing side-channel attacks.
+///
+/// Shared by the gRPC interceptors, the REST gateway and the server binary.
+/// The length check leaks the key length, which cannot be hidden without
+/// padding both values.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
 /// Error interceptor for handling and logging errors.
 ///
 /// Logs errors with appropriate severity and adds structured error information.
@@ -152,4 +206,24 @@ mod tests {
         let request_id = request.extensions().get::<RequestId>();
         assert!(request_id.is_some());
     }
+
+    #[test]
+    fn test_admin_auth() {
+        let mut interceptor = AdminAuthInterceptor::new("admin-secret");
+
+        let status = interceptor.call(Request::new(())).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert(ADMIN_KEY_HEADER, "wrong".parse().unwrap());
+        assert!(interceptor.call(request).is_err());
+
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert(ADMIN_KEY_HEADER, "admin-secret".parse().unwrap());
+        assert!(interceptor.call(request).is_ok());
+    }
 }
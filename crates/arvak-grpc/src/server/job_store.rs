@@ -3,8 +3,9 @@
 //! This module provides a wrapper around pluggable storage backends.
 //! The actual storage implementation can be in-memory, `SQLite`, `PostgreSQL`, etc.
 
-use arvak_hal::job::{JobId, JobStatus};
+use arvak_hal::job::{JobId, JobPhase, JobStatus};
 use arvak_hal::result::ExecutionResult;
+use arvak_hal::signing::CircuitSignature;
 use arvak_ir::circuit::Circuit;
 use chrono::Utc;
 use std::sync::Arc;
@@ -31,6 +32,11 @@ impl JobStore {
         Self { storage }
     }
 
+    /// Get the underlying storage backend.
+    pub fn storage(&self) -> Arc<dyn JobStorage> {
+        self.storage.clone()
+    }
+
     /// Create a new job and return its ID.
     pub async fn create_job(
         &self,
@@ -38,6 +44,34 @@ impl JobStore {
         backend_id: String,
         shots: u32,
         parameters: Option<std::collections::HashMap<String, f64>>,
+    ) -> Result<JobId> {
+        self.insert_job(circuit, backend_id, shots, parameters, None)
+            .await
+    }
+
+    /// Create a new job whose circuit carries a verified signature.
+    ///
+    /// `parameters` records the input values already bound into `circuit`.
+    pub async fn create_signed_job(
+        &self,
+        circuit: Circuit,
+        backend_id: String,
+        shots: u32,
+        parameters: std::collections::HashMap<String, f64>,
+        signature: Option<CircuitSignature>,
+    ) -> Result<JobId> {
+        let parameters = (!parameters.is_empty()).then_some(parameters);
+        self.insert_job(circuit, backend_id, shots, parameters, signature)
+            .await
+    }
+
+    async fn insert_job(
+        &self,
+        circuit: Circuit,
+        backend_id: String,
+        shots: u32,
+        parameters: Option<std::collections::HashMap<String, f64>>,
+        signature: Option<CircuitSignature>,
     ) -> Result<JobId> {
         let job_id = JobId::new(uuid::Uuid::new_v4().to_string());
 
@@ -47,11 +81,13 @@ impl JobStore {
             backend_id,
             shots,
             status: JobStatus::Queued,
+            phase: None,
             submitted_at: Utc::now(),
             started_at: None,
             completed_at: None,
             result: None,
             parameters,
+            signature,
         };
 
         self.storage.store_job(&job).await?;
@@ -64,6 +100,11 @@ impl JobStore {
         self.storage.update_status(job_id, status).await
     }
 
+    /// Record the phase a job's backend reports.
+    pub async fn update_phase(&self, job_id: &JobId, phase: JobPhase) -> Result<()> {
+        self.storage.update_phase(job_id, phase).await
+    }
+
     /// Store job result.
     pub async fn store_result(&self, job_id: &JobId, result: ExecutionResult) -> Result<()> {
         self.storage.store_result(job_id, result).await
@@ -77,6 +118,23 @@ impl JobStore {
             .ok_or_else(|| crate::error::Error::JobNotFound(job_id.0.clone()))
     }
 
+    /// Reset a job to `Queued` so it can be executed again.
+    ///
+    /// Clears the previous result and timestamps; the submission time is set
+    /// to now so queue-time metrics reflect the new run.
+    pub async fn requeue(&self, job_id: &JobId) -> Result<StoredJob> {
+        let mut job = self.get_job(job_id).await?;
+        job.status = JobStatus::Queued;
+        job.phase = None;
+        job.submitted_at = Utc::now();
+        job.started_at = None;
+        job.completed_at = None;
+        job.result = None;
+
+        self.storage.store_job(&job).await?;
+        Ok(job)
+    }
+
     /// Get job result by ID.
     pub async fn get_result(&self, job_id: &JobId) -> Result<ExecutionResult> {
         self.storage.get_result(job_id).await
@@ -136,6 +194,29 @@ mod tests {
         assert!(job.completed_at.is_some());
     }
 
+    #[tokio::test]
+    async fn test_requeue_resets_job() {
+        let store = JobStore::new();
+        let circuit = Circuit::with_size("test", 2, 0);
+        let job_id = store
+            .create_job(circuit, "simulator".to_string(), 1000, None)
+            .await
+            .unwrap();
+        store
+            .update_status(&job_id, JobStatus::Failed("boom".to_string()))
+            .await
+            .unwrap();
+
+        let job = store.requeue(&job_id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+
+        let job = store.get_job(&job_id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        assert!(job.started_at.is_none());
+        assert!(job.completed_at.is_none());
+        assert_eq!(job.shots, 1000);
+    }
+
     #[tokio::test]
     async fn test_job_not_found() {
         let store = JobStore::new();
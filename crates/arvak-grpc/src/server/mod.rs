@@ -1,13 +1,15 @@
 //! gRPC server components.
 
+pub mod admin;
 pub mod backend_registry;
 pub mod interceptors;
 pub mod job_store;
 pub mod middleware;
 pub mod service;
 
+pub use admin::{AdminSnapshot, ArvakAdminServiceImpl};
 pub use backend_registry::BackendRegistry;
-pub use interceptors::{LoggingInterceptor, RequestIdInterceptor};
+pub use interceptors::{AdminAuthInterceptor, LoggingInterceptor, RequestIdInterceptor};
 pub use job_store::JobStore;
 pub use middleware::{ConnectionInfoLayer, TimingLayer};
 pub use service::ArvakServiceImpl;
@@ -3,8 +3,8 @@
 use tonic::{Request, Response, Status};
 
 use crate::proto::{
-    BackendInfo, GetBackendInfoRequest, GetBackendInfoResponse, ListBackendsRequest,
-    ListBackendsResponse,
+    BackendInfo, BuildInfo, ComponentBuild, GetBackendInfoRequest, GetBackendInfoResponse,
+    ListBackendsRequest, ListBackendsResponse,
 };
 
 use super::super::ArvakServiceImpl;
@@ -22,7 +22,8 @@ impl ArvakServiceImpl {
 
             let caps = backend.capabilities();
 
-            let is_available = backend.availability().await.is_ok_and(|a| a.is_available);
+            let is_available = !self.control.is_backend_paused(&id).await
+                && backend.availability().await.is_ok_and(|a| a.is_available);
 
             let topology_json =
                 serde_json::to_string(&caps.topology).unwrap_or_else(|_| "{}".to_string());
@@ -56,7 +57,8 @@ impl ArvakServiceImpl {
 
         let caps = backend.capabilities();
 
-        let is_available = backend.availability().await.is_ok_and(|a| a.is_available);
+        let is_available = !self.control.is_backend_paused(&req.backend_id).await
+            && backend.availability().await.is_ok_and(|a| a.is_available);
 
         let topology_json =
             serde_json::to_string(&caps.topology).unwrap_or_else(|_| "{}".to_string());
@@ -78,6 +80,26 @@ impl ArvakServiceImpl {
 
         Ok(Response::new(GetBackendInfoResponse {
             backend: Some(backend_info),
+            build: Some(build_info()),
         }))
     }
 }
+
+/// Build info of this server in its protobuf form.
+fn build_info() -> BuildInfo {
+    let info = crate::describe_build(arvak_hal::BuildInfo::current());
+    BuildInfo {
+        git_commit: info.git_commit.unwrap_or_default(),
+        components: info
+            .components
+            .into_iter()
+            .map(|c| ComponentBuild {
+                name: c.name,
+                version: c.version,
+                features: c.features,
+            })
+            .collect(),
+        adapters: info.adapters,
+        storage_backends: info.storage_backends,
+    }
+}
@@ -1,14 +1,16 @@
 //! Circuit parsing and compilation utilities shared across gRPC service modules.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::error::Error;
 use crate::error::Result;
-use crate::proto::{CircuitPayload, circuit_payload};
+use crate::proto::{self, CircuitPayload, circuit_payload};
 use crate::resource_manager::ResourceManager;
-use arvak_compile::{BasisGates, CouplingMap, PassManagerBuilder};
+use arvak_compile::{CancellationToken, CompileTarget, compile_batch_with};
 use arvak_hal::backend::Backend;
-use arvak_hal::capability::{Capabilities, TopologyKind};
+use arvak_hal::signing::{CircuitSignature, SignaturePolicy};
+use arvak_hal::Target;
 use arvak_ir::circuit::Circuit;
 
 /// Parse circuit from protobuf payload (static version for use in async contexts).
@@ -30,6 +32,51 @@ pub(crate) fn parse_circuit_static(payload: Option<CircuitPayload>) -> Result<Ci
     }
 }
 
+/// Bind a submission's `input` parameter values into its circuit.
+///
+/// Binding happens at submission, before compilation, so every backend
+/// receives a numeric circuit. A circuit with parameters left unbound is
+/// rejected here rather than failing later on the backend.
+pub(crate) fn bind_parameters(
+    circuit: Circuit,
+    parameters: &HashMap<String, f64>,
+) -> Result<Circuit> {
+    if parameters.is_empty() && circuit.parameters().is_empty() {
+        return Ok(circuit);
+    }
+    circuit
+        .bind_parameters(parameters)
+        .map_err(|e| Error::InvalidCircuit(e.to_string()))
+}
+
+/// Check a submitted circuit's signature against the signing policy.
+///
+/// The signature covers the payload text as submitted. Returns the signature
+/// to store with the job; without a policy, signatures are not checked and
+/// not stored.
+pub(crate) fn verify_signature(
+    policy: Option<&SignaturePolicy>,
+    backend_id: &str,
+    payload: Option<&CircuitPayload>,
+    signature: Option<proto::CircuitSignature>,
+) -> Result<Option<CircuitSignature>> {
+    let Some(policy) = policy else {
+        return Ok(None);
+    };
+    let artifact = match payload.and_then(|p| p.format.as_ref()) {
+        Some(circuit_payload::Format::Qasm3(text) | circuit_payload::Format::ArvakIrJson(text)) => {
+            text.as_str()
+        }
+        None => "",
+    };
+    let signature = signature.map(|s| CircuitSignature {
+        public_key: s.public_key,
+        signature: s.signature,
+    });
+    policy.check(backend_id, artifact, signature.as_ref())?;
+    Ok(signature)
+}
+
 /// Validate circuit complexity against resource limits.
 ///
 /// Must be called before compilation to reject oversized circuits early.
@@ -45,13 +92,27 @@ pub(crate) fn validate_circuit_complexity(
     Ok(())
 }
 
+/// Cancels a compilation when dropped.
+///
+/// A blocking compile keeps running after the future awaiting it is dropped,
+/// whether by the timeout or by a client that went away. Holding this guard
+/// in that future stops the compile at its next cancellation check.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
 /// Compile a circuit for a specific backend's capabilities.
 ///
 /// If `optimization_level` is 0, returns the circuit unchanged (backwards compatible).
 /// Levels 1-3 enable compilation with the corresponding optimization level.
 ///
 /// Compilation runs on a blocking thread with a configurable timeout to prevent
-/// DoS via excessively complex circuits.
+/// DoS via excessively complex circuits. On timeout, or when the calling
+/// request is dropped, the compilation is cancelled.
 pub(crate) async fn compile_for_backend(
     circuit: Circuit,
     backend: &dyn Backend,
@@ -62,28 +123,23 @@ pub(crate) async fn compile_for_backend(
         return Ok(circuit);
     }
 
-    let caps = backend.capabilities();
-    let coupling_map = build_coupling_map(caps);
-    let basis_gates = build_basis_gates(caps);
-
     let level = u8::try_from(optimization_level.min(3)).unwrap_or(3);
 
-    let (pm, mut props) = PassManagerBuilder::new()
-        .with_optimization_level(level)
-        .with_target(coupling_map, basis_gates)
-        .build();
-
-    let mut dag = circuit.into_dag();
+    let target = CompileTarget::from(Target::from_backend(backend));
+    let token = CancellationToken::new();
+    let _cancel_on_drop = CancelOnDrop(token.clone());
 
     // Run compilation on blocking thread (CPU-bound work per CLAUDE.md rules)
     let compile_fut = tokio::task::spawn_blocking(move || {
-        let result = pm.run(&mut dag, &mut props);
-        (result.map(|()| dag), props)
+        let (pm, mut props) = target.builder(level).with_cancellation(token).build();
+        let mut dag = circuit.into_dag();
+        pm.run(&mut dag, &mut props)?;
+        Ok::<_, arvak_compile::CompileError>(Circuit::from_dag(dag))
     });
 
     // Apply compilation timeout (default: 30s) to prevent DoS via complex circuits
     let timeout = compilation_timeout.unwrap_or(Duration::from_secs(30));
-    let (dag_result, _props) = tokio::time::timeout(timeout, compile_fut)
+    let compiled = tokio::time::timeout(timeout, compile_fut)
         .await
         .map_err(|_| {
             tonic::Status::deadline_exceeded(format!(
@@ -93,54 +149,69 @@ pub(crate) async fn compile_for_backend(
         })?
         .map_err(|e| tonic::Status::internal(format!("Compilation task failed: {e}")))?;
 
-    let dag = dag_result
-        .map_err(|e| tonic::Status::internal(format!("Circuit compilation failed: {e}")))?;
-
-    Ok(Circuit::from_dag(dag))
+    compiled.map_err(|e| tonic::Status::internal(format!("Circuit compilation failed: {e}")))
 }
 
-/// Build a [`CouplingMap`] from backend capabilities.
-fn build_coupling_map(caps: &Capabilities) -> CouplingMap {
-    match &caps.topology.kind {
-        TopologyKind::Linear => CouplingMap::linear(caps.num_qubits),
-        TopologyKind::Star => CouplingMap::star(caps.num_qubits),
-        TopologyKind::FullyConnected => CouplingMap::full(caps.num_qubits),
-        TopologyKind::NeutralAtom { zones } => CouplingMap::zoned(caps.num_qubits, *zones),
-        // Grid, Custom, and any future variants: build from edge list
-        _ => {
-            let mut map = CouplingMap::new(caps.num_qubits);
-            for &(a, b) in &caps.topology.edges {
-                map.add_edge(a, b);
+/// Compile a batch of circuits for a backend, each at its own optimization
+/// level, in parallel.
+///
+/// As in [`compile_for_backend`], level 0 leaves a circuit unchanged.
+/// Circuits of the same level are compiled together with
+/// [`compile_batch_with`] on a blocking thread, and cancelled together.
+/// The timeout applies per circuit, so the batch as a whole may take
+/// `timeout × circuits`. Results are in input order; the first failing
+/// circuit fails the batch.
+pub(crate) async fn compile_batch_for_backend(
+    circuits: Vec<(Circuit, u32)>,
+    backend: &dyn Backend,
+    compilation_timeout: Option<Duration>,
+) -> std::result::Result<Vec<Circuit>, tonic::Status> {
+    if circuits.iter().all(|(_, level)| *level == 0) {
+        return Ok(circuits.into_iter().map(|(circuit, _)| circuit).collect());
+    }
+
+    let target = CompileTarget::from(Target::from_backend(backend));
+    let count = u32::try_from(circuits.len()).unwrap_or(u32::MAX);
+    let token = CancellationToken::new();
+    let _cancel_on_drop = CancelOnDrop(token.clone());
+
+    // Run compilation on blocking thread (CPU-bound work per CLAUDE.md rules)
+    let compile_fut = tokio::task::spawn_blocking(move || {
+        let mut slots: Vec<Option<Circuit>> = Vec::with_capacity(circuits.len());
+        let mut levels = Vec::with_capacity(circuits.len());
+        for (circuit, level) in circuits {
+            slots.push(Some(circuit));
+            levels.push(u8::try_from(level.min(3)).unwrap_or(3));
+        }
+        for level in 1..=3 {
+            let indices: Vec<usize> = (0..slots.len()).filter(|&i| levels[i] == level).collect();
+            if indices.is_empty() {
+                continue;
+            }
+            let batch = indices.iter().filter_map(|&i| slots[i].take()).collect();
+            let results = compile_batch_with(batch, || {
+                target.builder(level).with_cancellation(token.clone())
+            });
+            for (&i, result) in indices.iter().zip(results) {
+                slots[i] = Some(result.map_err(|e| (i, e))?);
             }
-            map.rebuild_caches();
-            map
         }
-    }
-}
+        Ok(slots.into_iter().flatten().collect())
+    });
 
-/// Build [`BasisGates`] from backend capabilities.
-///
-/// Uses the `native` gate list when non-empty (hardware backends), so the
-/// compiler decomposes non-native gates (e.g. `h` → `rz·sx·rz` on IBM Heron).
-/// Falls back to all supported gates for simulators (empty `native` list).
-fn build_basis_gates(caps: &Capabilities) -> BasisGates {
-    let mut gates: Vec<String> = if caps.gate_set.native.is_empty() {
-        // Simulator: all supported gates are native — no decomposition needed.
-        let mut g = caps.gate_set.single_qubit.clone();
-        g.extend(caps.gate_set.two_qubit.iter().cloned());
-        g.extend(caps.gate_set.three_qubit.iter().cloned());
-        g
-    } else {
-        // Hardware: compile only to truly native gates; non-native gates get
-        // decomposed by the BasisTranslation pass.
-        caps.gate_set.native.clone()
-    };
-    // Always include measurement and barrier
-    if !gates.iter().any(|g| g == "measure") {
-        gates.push("measure".to_string());
-    }
-    if !gates.iter().any(|g| g == "barrier") {
-        gates.push("barrier".to_string());
-    }
-    BasisGates::new(gates)
+    let timeout = compilation_timeout
+        .unwrap_or(Duration::from_secs(30))
+        .saturating_mul(count);
+    tokio::time::timeout(timeout, compile_fut)
+        .await
+        .map_err(|_| {
+            tonic::Status::deadline_exceeded(format!(
+                "Batch compilation timed out after {}s",
+                timeout.as_secs()
+            ))
+        })?
+        .map_err(|e| tonic::Status::internal(format!("Compilation task failed: {e}")))?
+        .map_err(|(i, e): (usize, arvak_compile::CompileError)| {
+            tonic::Status::internal(format!("Circuit compilation failed for job {i}: {e}"))
+        })
 }
@@ -1,13 +1,17 @@
 //! Job execution logic for the gRPC service.
 
 use arvak_hal::backend::Backend;
-use arvak_hal::job::{JobId, JobStatus};
+use arvak_hal::error::HalResult;
+use arvak_hal::heartbeat::HeartbeatSender;
+use arvak_hal::job::{JobId, JobPhase as HalJobPhase, JobStatus};
+use arvak_hal::result::ExecutionResult;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{error, info, instrument, warn};
+use std::time::Duration;
+use tracing::{debug, error, info, instrument, warn};
 
 use crate::metrics::Metrics;
-use crate::proto::JobState;
+use crate::proto::{JobPhase, JobState};
 use crate::resource_manager::ResourceManager;
 use crate::server::JobStore;
 
@@ -34,9 +38,12 @@ pub(super) async fn execute_job_sync(
     let backend_id = job.backend_id.clone();
     let submitted_at = job.submitted_at;
 
-    // Update to RUNNING
-    if let Err(e) = job_store.update_status(&job_id, JobStatus::Running).await {
-        error!("Failed to update job status to running: {}", e);
+    // The job is on its way to the backend; its phases are tracked from here.
+    if let Err(e) = job_store
+        .update_phase(&job_id, HalJobPhase::Transferring)
+        .await
+    {
+        error!("Failed to update job phase to transferring: {}", e);
         metrics.record_job_failed(&backend_id, "status_update_error");
         if let Some(ref resources) = resources {
             resources.job_cancelled_queued().await;
@@ -72,38 +79,40 @@ pub(super) async fn execute_job_sync(
             tokio::time::sleep(backoff).await;
         }
 
-        match backend
-            .submit(&job.circuit, job.shots, job.parameters.as_ref())
-            .await
-        {
-            Ok(backend_job_id) => match backend.wait(&backend_job_id).await {
-                Ok(result) => {
-                    let duration = chrono::Utc::now()
-                        .signed_duration_since(execution_start)
-                        .num_milliseconds()
-                        .max(0) as u64;
+        // Parameters were bound into the circuit at submission.
+        match backend.submit(&job.circuit, job.shots, None).await {
+            Ok(backend_job_id) => {
+                match wait_tracking_phase(&job_store, backend.as_ref(), &job_id, &backend_job_id)
+                    .await
+                {
+                    Ok(result) => {
+                        let duration = chrono::Utc::now()
+                            .signed_duration_since(execution_start)
+                            .num_milliseconds()
+                            .max(0) as u64;
 
-                    if let Err(e) = job_store.store_result(&job_id, result).await {
-                        error!("Failed to store job result: {}", e);
-                        metrics.record_job_failed(&backend_id, "storage_error");
-                    } else {
-                        metrics.record_job_completed(&backend_id, duration);
+                        if let Err(e) = job_store.store_result(&job_id, result).await {
+                            error!("Failed to store job result: {}", e);
+                            metrics.record_job_failed(&backend_id, "storage_error");
+                        } else {
+                            metrics.record_job_completed(&backend_id, duration);
+                        }
+                        if let Some(ref resources) = resources {
+                            resources.job_completed().await;
+                        }
+                        return;
                     }
-                    if let Some(ref resources) = resources {
-                        resources.job_completed().await;
+                    Err(e) if e.is_transient() && attempt + 1 < max_attempts => {
+                        warn!(job_id = %job_id.0, error = %e, "Transient wait failure");
+                        last_error = Some(format!("Backend wait failed: {e}"));
+                        continue;
+                    }
+                    Err(e) => {
+                        last_error = Some(format!("Backend wait failed: {e}"));
+                        break;
                     }
-                    return;
-                }
-                Err(e) if e.is_transient() && attempt + 1 < max_attempts => {
-                    warn!(job_id = %job_id.0, error = %e, "Transient wait failure");
-                    last_error = Some(format!("Backend wait failed: {e}"));
-                    continue;
-                }
-                Err(e) => {
-                    last_error = Some(format!("Backend wait failed: {e}"));
-                    break;
                 }
-            },
+            }
             Err(e) if e.is_transient() && attempt + 1 < max_attempts => {
                 warn!(job_id = %job_id.0, error = %e, "Transient submit failure");
                 last_error = Some(format!("Backend submit failed: {e}"));
@@ -127,8 +136,81 @@ pub(super) async fn execute_job_sync(
     }
 }
 
+/// How often the backend is asked for a job's phase while it is awaited.
+const PHASE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Wait for `backend_job_id` on `backend`, recording the phases its
+/// heartbeats report on `job_id` in the store.
+///
+/// Phases never move a running job back to queued. A job that finished
+/// before it was seen running is marked running once, so its start time is
+/// recorded.
+async fn wait_tracking_phase(
+    job_store: &JobStore,
+    backend: &dyn Backend,
+    job_id: &JobId,
+    backend_job_id: &JobId,
+) -> HalResult<ExecutionResult> {
+    let heartbeats = HeartbeatSender::new().with_poll_interval(PHASE_POLL_INTERVAL);
+    let mut received = heartbeats.subscribe();
+    let wait = backend.wait_with_heartbeats(backend_job_id, &heartbeats);
+    tokio::pin!(wait);
+    let mut current: Option<HalJobPhase> = None;
+    let is_running = |phase: Option<HalJobPhase>| {
+        phase.is_some_and(|phase| matches!(phase.status(), JobStatus::Running))
+    };
+
+    loop {
+        let heartbeat = tokio::select! {
+            result = &mut wait => {
+                if result.is_ok() && !is_running(current) {
+                    if let Err(e) = job_store.update_phase(job_id, HalJobPhase::Running).await {
+                        warn!(job_id = %job_id.0, error = %e, "Failed to record job phase");
+                    }
+                }
+                return result;
+            }
+            heartbeat = received.recv() => heartbeat,
+        };
+
+        // Lagging behind only skips intermediate heartbeats.
+        let Ok(heartbeat) = heartbeat else { continue };
+        debug!(
+            job_id = %job_id.0,
+            kind = ?heartbeat.kind,
+            phase = heartbeat.phase.as_str(),
+            queue_position = ?heartbeat.queue_position,
+            elapsed_secs = heartbeat.elapsed_secs,
+            "Job heartbeat"
+        );
+
+        let phase = heartbeat.phase;
+        if current == Some(phase) || (is_running(current) && !is_running(Some(phase))) {
+            continue;
+        }
+        match job_store.update_phase(job_id, phase).await {
+            Ok(()) => current = Some(phase),
+            Err(e) => warn!(job_id = %job_id.0, error = %e, "Failed to record job phase"),
+        }
+    }
+}
+
+/// Convert a job's HAL phase to protobuf `JobPhase`, deriving the coarse
+/// phase from its status when the backend reported none.
+pub(crate) fn to_proto_phase(status: &JobStatus, phase: Option<HalJobPhase>) -> JobPhase {
+    match phase.or_else(|| HalJobPhase::from_status(status)) {
+        Some(HalJobPhase::Validating) => JobPhase::Validating,
+        Some(HalJobPhase::Compiling) => JobPhase::Compiling,
+        Some(HalJobPhase::Transferring) => JobPhase::Transferring,
+        Some(HalJobPhase::Queued) => JobPhase::Queued,
+        Some(HalJobPhase::Calibrating) => JobPhase::Calibrating,
+        Some(HalJobPhase::Running) => JobPhase::Running,
+        None => JobPhase::Unspecified,
+    }
+}
+
 /// Convert HAL `JobStatus` to protobuf `JobState`.
-pub(super) fn to_proto_state(status: &JobStatus) -> JobState {
+pub(crate) fn to_proto_state(status: &JobStatus) -> JobState {
     match status {
         JobStatus::Queued => JobState::Queued,
         JobStatus::Running => JobState::Running,
@@ -167,9 +249,12 @@ pub(crate) async fn spawn_job_execution(
 
         info!(backend_id = %backend_id, "Starting job execution");
 
-        // Update to RUNNING
-        if let Err(e) = job_store.update_status(&job_id, JobStatus::Running).await {
-            error!("Failed to update job status to running: {}", e);
+        // The job is on its way to the backend; its phases are tracked from here.
+        if let Err(e) = job_store
+            .update_phase(&job_id, HalJobPhase::Transferring)
+            .await
+        {
+            error!("Failed to update job phase to transferring: {}", e);
             metrics.record_job_failed(&backend_id, "status_update_error");
             if let Some(ref resources) = resources {
                 resources.job_cancelled_queued().await;
@@ -204,11 +289,16 @@ pub(crate) async fn spawn_job_execution(
                 tokio::time::sleep(backoff).await;
             }
 
-            match backend
-                .submit(&job.circuit, job.shots, job.parameters.as_ref())
+            // Parameters were bound into the circuit at submission.
+            match backend.submit(&job.circuit, job.shots, None).await {
+                Ok(backend_job_id) => match wait_tracking_phase(
+                    &job_store,
+                    backend.as_ref(),
+                    &job_id,
+                    &backend_job_id,
+                )
                 .await
-            {
-                Ok(backend_job_id) => match backend.wait(&backend_job_id).await {
+                {
                     Ok(result) => {
                         let duration = chrono::Utc::now()
                             .signed_duration_since(execution_start)
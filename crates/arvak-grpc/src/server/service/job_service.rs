@@ -15,9 +15,10 @@ use crate::resource_manager::ResourceManager;
 
 use super::super::ArvakServiceImpl;
 use super::circuit_utils::{
-    compile_for_backend, parse_circuit_static, validate_circuit_complexity,
+    bind_parameters, compile_batch_for_backend, compile_for_backend, parse_circuit_static,
+    validate_circuit_complexity, verify_signature,
 };
-use super::job_execution::{execute_job_sync, spawn_job_execution, to_proto_state};
+use super::job_execution::{execute_job_sync, spawn_job_execution, to_proto_phase, to_proto_state};
 
 // Type aliases for streaming types
 type WatchJobStream = std::pin::Pin<
@@ -47,6 +48,12 @@ impl ArvakServiceImpl {
 
         tracing::Span::current().record("backend_id", req.backend_id.as_str());
 
+        // Reject new work while draining or when the backend is paused
+        self.control
+            .check_accepting(&req.backend_id)
+            .await
+            .map_err(Status::from)?;
+
         // Check resource limits if manager is configured
         if let Some(ref resources) = self.resources {
             resources
@@ -55,8 +62,18 @@ impl ArvakServiceImpl {
                 .map_err(|e| Status::resource_exhausted(e.to_string()))?;
         }
 
-        // Parse circuit
+        // Only signed circuits may run on protected backends
+        let signature = verify_signature(
+            self.signing.as_deref(),
+            &req.backend_id,
+            req.circuit.as_ref(),
+            req.signature,
+        )
+        .map_err(Status::from)?;
+
+        // Parse circuit and bind its input parameters
         let circuit = self.parse_circuit(req.circuit).map_err(Status::from)?;
+        let circuit = bind_parameters(circuit, &req.parameters).map_err(Status::from)?;
 
         // Pre-flight: reject circuits that exceed complexity limits
         validate_circuit_complexity(&circuit, self.resources.as_ref())?;
@@ -83,7 +100,13 @@ impl ArvakServiceImpl {
         // job_submitted() so the resource slot is never reserved.
         let job_id = self
             .job_store
-            .create_job(circuit, req.backend_id.clone(), req.shots, None)
+            .create_signed_job(
+                circuit,
+                req.backend_id.clone(),
+                req.shots,
+                req.parameters,
+                signature,
+            )
             .await
             .map_err(|e| {
                 // create_job failed — resource was checked but never incremented; nothing to roll back.
@@ -131,13 +154,52 @@ impl ArvakServiceImpl {
 
         let req = request.into_inner();
 
+        // Reject new work while draining or when the backend is paused
+        self.control
+            .check_accepting(&req.backend_id)
+            .await
+            .map_err(Status::from)?;
+
         // Validate backend exists
         let backend = self.backends.get(&req.backend_id).map_err(Status::from)?;
 
+        // Parse and check every job before compiling any of them
+        let mut circuits = Vec::with_capacity(req.jobs.len());
+        let mut jobs = Vec::with_capacity(req.jobs.len());
+        for batch_job in req.jobs {
+            let signature = verify_signature(
+                self.signing.as_deref(),
+                &req.backend_id,
+                batch_job.circuit.as_ref(),
+                batch_job.signature,
+            )
+            .map_err(Status::from)?;
+
+            let circuit = self
+                .parse_circuit(batch_job.circuit)
+                .map_err(Status::from)?;
+            let circuit = bind_parameters(circuit, &batch_job.parameters).map_err(Status::from)?;
+
+            // Pre-flight: reject circuits that exceed complexity limits
+            validate_circuit_complexity(&circuit, self.resources.as_ref())?;
+
+            circuits.push((circuit, batch_job.optimization_level));
+            jobs.push((batch_job.shots, batch_job.parameters, signature));
+        }
+
+        // Compile all circuits for the target backend in parallel (no-op for
+        // jobs with optimization_level == 0)
+        let compilation_timeout = self
+            .resources
+            .as_ref()
+            .map(ResourceManager::compilation_timeout);
+        let circuits =
+            compile_batch_for_backend(circuits, backend.as_ref(), compilation_timeout).await?;
+
         let mut job_ids = Vec::new();
 
         // Submit each job
-        for batch_job in req.jobs {
+        for (circuit, (shots, parameters, signature)) in circuits.into_iter().zip(jobs) {
             // Check resource limits per job if manager is configured
             if let Some(ref resources) = self.resources {
                 resources
@@ -146,29 +208,15 @@ impl ArvakServiceImpl {
                     .map_err(|e| Status::resource_exhausted(e.to_string()))?;
             }
 
-            let circuit = self
-                .parse_circuit(batch_job.circuit)
-                .map_err(Status::from)?;
-
-            // Pre-flight: reject circuits that exceed complexity limits
-            validate_circuit_complexity(&circuit, self.resources.as_ref())?;
-
-            // Compile circuit for target backend (no-op when optimization_level == 0)
-            let compilation_timeout = self
-                .resources
-                .as_ref()
-                .map(ResourceManager::compilation_timeout);
-            let circuit = compile_for_backend(
-                circuit,
-                backend.as_ref(),
-                batch_job.optimization_level,
-                compilation_timeout,
-            )
-            .await?;
-
             let job_id = self
                 .job_store
-                .create_job(circuit, req.backend_id.clone(), batch_job.shots, None)
+                .create_signed_job(
+                    circuit,
+                    req.backend_id.clone(),
+                    shots,
+                    parameters,
+                    signature,
+                )
                 .await
                 .map_err(Status::from)?;
 
@@ -231,6 +279,8 @@ impl ArvakServiceImpl {
             backend_id: job.backend_id,
             shots: job.shots,
             error_message,
+            signer_public_key: job.signature.map(|s| s.public_key).unwrap_or_default(),
+            phase: to_proto_phase(&job.status, job.phase) as i32,
         };
 
         // Record RPC duration
@@ -284,6 +334,7 @@ impl ArvakServiceImpl {
                             state: to_proto_state(&job.status) as i32,
                             timestamp: chrono::Utc::now().timestamp(),
                             error_message,
+                            phase: to_proto_phase(&job.status, job.phase) as i32,
                         };
 
                         // Send update
@@ -407,6 +458,8 @@ impl ArvakServiceImpl {
         let backends = self.backends.clone();
         let metrics = self.metrics.clone();
         let resources = self.resources.clone();
+        let control = self.control.clone();
+        let signing = self.signing.clone();
 
         // Spawn task to handle incoming submissions
         tokio::spawn(async move {
@@ -415,8 +468,44 @@ impl ArvakServiceImpl {
                     Ok(submission) => {
                         let client_request_id = submission.client_request_id.clone();
 
+                        // Reject new work while draining or when the backend is paused
+                        if let Err(e) = control.check_accepting(&submission.backend_id).await {
+                            let _ = tx
+                                .send(Ok(BatchJobResult {
+                                    job_id: String::new(),
+                                    client_request_id,
+                                    result: Some(batch_job_result::Result::Error(e.to_string())),
+                                }))
+                                .await;
+                            continue;
+                        }
+
+                        // Only signed circuits may run on protected backends
+                        let signature = match verify_signature(
+                            signing.as_deref(),
+                            &submission.backend_id,
+                            submission.circuit.as_ref(),
+                            submission.signature,
+                        ) {
+                            Ok(s) => s,
+                            Err(e) => {
+                                let _ = tx
+                                    .send(Ok(BatchJobResult {
+                                        job_id: String::new(),
+                                        client_request_id,
+                                        result: Some(batch_job_result::Result::Error(
+                                            e.to_string(),
+                                        )),
+                                    }))
+                                    .await;
+                                continue;
+                            }
+                        };
+
                         // Parse circuit
-                        let circuit = match parse_circuit_static(submission.circuit) {
+                        let circuit = match parse_circuit_static(submission.circuit)
+                            .and_then(|c| bind_parameters(c, &submission.parameters))
+                        {
                             Ok(c) => c,
                             Err(e) => {
                                 let _ = tx
@@ -491,11 +580,12 @@ impl ArvakServiceImpl {
 
                         // Create job
                         match job_store
-                            .create_job(
+                            .create_signed_job(
                                 circuit,
                                 submission.backend_id.clone(),
                                 submission.shots,
-                                None,
+                                submission.parameters,
+                                signature,
                             )
                             .await
                         {
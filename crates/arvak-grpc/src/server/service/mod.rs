@@ -5,12 +5,14 @@ pub(crate) mod circuit_utils;
 pub(crate) mod job_execution;
 mod job_service;
 
+use arvak_hal::signing::SignaturePolicy;
 use arvak_ir::circuit::Circuit;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
 use crate::config::ResourceLimits;
+use crate::control::ControlState;
 use crate::error::Result;
 use crate::metrics::Metrics;
 use crate::proto::{
@@ -22,7 +24,7 @@ use crate::proto::{
     arvak_service_server,
 };
 use crate::resource_manager::ResourceManager;
-use crate::server::{BackendRegistry, JobStore};
+use crate::server::{ArvakAdminServiceImpl, BackendRegistry, JobStore};
 
 use circuit_utils::parse_circuit_static;
 
@@ -33,6 +35,8 @@ pub struct ArvakServiceImpl {
     pub(crate) metrics: Metrics,
     pub(crate) resources: Option<ResourceManager>,
     pub(crate) abort_handles: Arc<tokio::sync::RwLock<HashMap<String, tokio::task::AbortHandle>>>,
+    pub(crate) control: ControlState,
+    pub(crate) signing: Option<Arc<SignaturePolicy>>,
 }
 
 impl ArvakServiceImpl {
@@ -51,6 +55,8 @@ impl ArvakServiceImpl {
             metrics,
             resources: None,
             abort_handles: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            control: ControlState::new(),
+            signing: None,
         }
     }
 
@@ -65,6 +71,16 @@ impl ArvakServiceImpl {
         service
     }
 
+    /// Check circuit signatures against `policy`.
+    ///
+    /// Backends the policy protects then only accept circuits signed by a
+    /// trusted key; verified signatures are stored with their jobs.
+    #[must_use]
+    pub fn with_signing_policy(mut self, policy: SignaturePolicy) -> Self {
+        self.signing = Some(Arc::new(policy));
+        self
+    }
+
     /// Create a new service with default components.
     pub fn new() -> Self {
         use crate::server::backend_registry::create_default_registry;
@@ -76,6 +92,31 @@ impl ArvakServiceImpl {
         self.backends.clone()
     }
 
+    /// Get the job storage backend (e.g. for retention sweeps).
+    pub fn job_storage(&self) -> Arc<dyn crate::storage::JobStorage> {
+        self.job_store.storage()
+    }
+
+    /// Get the operator control state (drain, paused backends).
+    pub fn control(&self) -> ControlState {
+        self.control.clone()
+    }
+
+    /// Create the admin service for this service.
+    ///
+    /// The admin service shares the job store, backends, resource limits and
+    /// control state, so operator actions take effect immediately.
+    pub fn admin_service(&self) -> ArvakAdminServiceImpl {
+        ArvakAdminServiceImpl {
+            job_store: self.job_store.clone(),
+            backends: self.backends.clone(),
+            metrics: self.metrics.clone(),
+            resources: self.resources.clone(),
+            abort_handles: self.abort_handles.clone(),
+            control: self.control.clone(),
+        }
+    }
+
     /// Parse circuit from protobuf payload.
     fn parse_circuit(&self, payload: Option<CircuitPayload>) -> Result<Circuit> {
         parse_circuit_static(payload)
@@ -0,0 +1,150 @@
+//! Fault-injecting storage wrapper for recovery tests.
+//!
+//! `ChaosStorage` wraps another [`JobStorage`] and, as the scenario of its
+//! [`FaultInjector`] dictates, delays writes and silently drops status
+//! updates. Reads always go straight to the wrapped storage.
+
+use arvak_hal::chaos::FaultInjector;
+use arvak_hal::job::{JobId, JobPhase, JobStatus};
+use arvak_hal::result::ExecutionResult;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::{JobFilter, JobStorage, StoredJob};
+use crate::error::Result;
+
+/// Storage that injects delays and lost status updates.
+pub struct ChaosStorage {
+    inner: Arc<dyn JobStorage>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosStorage {
+    /// Wrap `inner`, drawing faults from `injector`.
+    pub fn new(inner: Arc<dyn JobStorage>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+
+    async fn delay(&self) {
+        if let Some(delay) = self.injector.storage_delay() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl JobStorage for ChaosStorage {
+    async fn store_job(&self, job: &StoredJob) -> Result<()> {
+        self.delay().await;
+        self.inner.store_job(job).await
+    }
+
+    async fn get_job(&self, job_id: &JobId) -> Result<Option<StoredJob>> {
+        self.inner.get_job(job_id).await
+    }
+
+    async fn update_status(&self, job_id: &JobId, status: JobStatus) -> Result<()> {
+        self.delay().await;
+        if self.injector.drop_status_update() {
+            return Ok(());
+        }
+        self.inner.update_status(job_id, status).await
+    }
+
+    async fn update_phase(&self, job_id: &JobId, phase: JobPhase) -> Result<()> {
+        self.delay().await;
+        if self.injector.drop_status_update() {
+            return Ok(());
+        }
+        self.inner.update_phase(job_id, phase).await
+    }
+
+    async fn store_result(&self, job_id: &JobId, result: ExecutionResult) -> Result<()> {
+        self.delay().await;
+        self.inner.store_result(job_id, result).await
+    }
+
+    async fn list_jobs(&self, filter: JobFilter) -> Result<Vec<StoredJob>> {
+        self.inner.list_jobs(filter).await
+    }
+
+    async fn delete_job(&self, job_id: &JobId) -> Result<()> {
+        self.inner.delete_job(job_id).await
+    }
+
+    async fn get_result(&self, job_id: &JobId) -> Result<ExecutionResult> {
+        self.inner.get_result(job_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use arvak_hal::chaos::{DroppedStatusUpdates, FaultScenario, StorageDelay};
+    use arvak_ir::circuit::Circuit;
+    use chrono::Utc;
+
+    fn job(id: &str) -> StoredJob {
+        StoredJob {
+            id: JobId::new(id.to_string()),
+            circuit: Circuit::with_size("test", 2, 0),
+            backend_id: "simulator".to_string(),
+            shots: 100,
+            status: JobStatus::Queued,
+            phase: None,
+            submitted_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            result: None,
+            parameters: None,
+            signature: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_status_updates() {
+        let injector = Arc::new(FaultInjector::new(FaultScenario {
+            dropped_status_updates: Some(DroppedStatusUpdates { probability: 1.0 }),
+            ..Default::default()
+        }));
+        let inner = Arc::new(MemoryStorage::new());
+        let storage = ChaosStorage::new(inner.clone(), injector.clone());
+
+        let job = job("chaos-1");
+        storage.store_job(&job).await.unwrap();
+        storage
+            .update_status(&job.id, JobStatus::Running)
+            .await
+            .unwrap();
+
+        let stored = inner.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Queued);
+        assert_eq!(injector.counts().dropped_status_updates, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_delayed_writes_still_land() {
+        let injector = Arc::new(FaultInjector::new(FaultScenario {
+            storage_delay: Some(StorageDelay {
+                probability: 1.0,
+                millis: 500,
+            }),
+            ..Default::default()
+        }));
+        let storage = ChaosStorage::new(Arc::new(MemoryStorage::new()), injector.clone());
+
+        let job = job("chaos-2");
+        let start = tokio::time::Instant::now();
+        storage.store_job(&job).await.unwrap();
+        storage
+            .update_status(&job.id, JobStatus::Running)
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() >= std::time::Duration::from_millis(1000));
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Running);
+        assert_eq!(injector.counts().storage_delays, 2);
+    }
+}
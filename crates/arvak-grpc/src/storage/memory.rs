@@ -3,7 +3,7 @@
 //! This implementation uses `Arc<RwLock<FxHashMap>>` for thread-safe in-memory
 //! storage. Jobs are lost when the server restarts.
 
-use arvak_hal::job::{JobId, JobStatus};
+use arvak_hal::job::{JobId, JobPhase, JobStatus};
 use arvak_hal::result::ExecutionResult;
 use async_trait::async_trait;
 use chrono::Utc;
@@ -94,6 +94,7 @@ impl JobStorage for MemoryStorage {
             .ok_or_else(|| Error::JobNotFound(job_id.0.clone()))?;
 
         job.status = status.clone();
+        job.phase = None;
 
         // Update timestamps based on status
         match status {
@@ -114,6 +115,22 @@ impl JobStorage for MemoryStorage {
         Ok(())
     }
 
+    async fn update_phase(&self, job_id: &JobId, phase: JobPhase) -> Result<()> {
+        let mut jobs = self.jobs.write().await;
+
+        let job = jobs
+            .get_mut(&job_id.0)
+            .ok_or_else(|| Error::JobNotFound(job_id.0.clone()))?;
+
+        job.status = phase.status();
+        job.phase = Some(phase);
+        if matches!(job.status, JobStatus::Running) && job.started_at.is_none() {
+            job.started_at = Some(Utc::now());
+        }
+
+        Ok(())
+    }
+
     async fn store_result(&self, job_id: &JobId, result: ExecutionResult) -> Result<()> {
         let mut jobs = self.jobs.write().await;
 
@@ -123,6 +140,7 @@ impl JobStorage for MemoryStorage {
 
         job.result = Some(result);
         job.status = JobStatus::Completed;
+        job.phase = None;
 
         if job.completed_at.is_none() {
             job.completed_at = Some(Utc::now());
@@ -200,11 +218,13 @@ mod tests {
             backend_id: "simulator".to_string(),
             shots: 1000,
             status: JobStatus::Queued,
+            phase: None,
             submitted_at: Utc::now(),
             started_at: None,
             completed_at: None,
             result: None,
             parameters: None,
+            signature: None,
         };
 
         storage.store_job(&job).await.unwrap();
@@ -214,6 +234,50 @@ mod tests {
         assert_eq!(retrieved.unwrap().id, job.id);
     }
 
+    #[tokio::test]
+    async fn test_update_phase() {
+        let storage = MemoryStorage::new();
+
+        let job = StoredJob {
+            id: JobId::new("test-phase".to_string()),
+            circuit: Circuit::with_size("test", 2, 0),
+            backend_id: "simulator".to_string(),
+            shots: 1000,
+            status: JobStatus::Queued,
+            phase: None,
+            submitted_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            result: None,
+            parameters: None,
+            signature: None,
+        };
+        storage.store_job(&job).await.unwrap();
+
+        storage
+            .update_phase(&job.id, JobPhase::Validating)
+            .await
+            .unwrap();
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Queued);
+        assert_eq!(stored.phase, Some(JobPhase::Validating));
+
+        storage
+            .update_phase(&job.id, JobPhase::Running)
+            .await
+            .unwrap();
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Running);
+        assert!(stored.started_at.is_some());
+
+        storage
+            .update_status(&job.id, JobStatus::Completed)
+            .await
+            .unwrap();
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.phase, None);
+    }
+
     #[tokio::test]
     async fn test_update_status_with_timestamps() {
         let storage = MemoryStorage::new();
@@ -224,11 +288,13 @@ mod tests {
             backend_id: "simulator".to_string(),
             shots: 1000,
             status: JobStatus::Queued,
+            phase: None,
             submitted_at: Utc::now(),
             started_at: None,
             completed_at: None,
             result: None,
             parameters: None,
+            signature: None,
         };
 
         storage.store_job(&job).await.unwrap();
@@ -270,11 +336,13 @@ mod tests {
                 } else {
                     JobStatus::Completed
                 },
+                phase: None,
                 submitted_at: Utc::now(),
                 started_at: None,
                 completed_at: None,
                 result: None,
                 parameters: None,
+                signature: None,
             };
             storage.store_job(&job).await.unwrap();
         }
@@ -305,11 +373,13 @@ mod tests {
             backend_id: "simulator".to_string(),
             shots: 1000,
             status: JobStatus::Queued,
+            phase: None,
             submitted_at: Utc::now(),
             started_at: None,
             completed_at: None,
             result: None,
             parameters: None,
+            signature: None,
         };
 
         storage.store_job(&job).await.unwrap();
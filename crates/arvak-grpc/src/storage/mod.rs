@@ -5,9 +5,14 @@
 //!
 //! - `MemoryStorage`: In-memory storage (no persistence)
 //! - `SqliteStorage`: `SQLite` database for single-node deployments
+//! - `ChaosStorage`: fault-injecting wrapper for recovery tests (`chaos` feature)
+//!
+//! Retention (age limits, size caps, archiving) works against any backend;
+//! see [`retention`].
 
-use arvak_hal::job::{JobId, JobStatus};
+use arvak_hal::job::{JobId, JobPhase, JobStatus};
 use arvak_hal::result::ExecutionResult;
+use arvak_hal::signing::CircuitSignature;
 use arvak_ir::circuit::Circuit;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -15,13 +20,19 @@ use chrono::{DateTime, Utc};
 use crate::error::Result;
 
 pub mod memory;
+pub mod retention;
 
+#[cfg(feature = "chaos")]
+pub mod chaos;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
 // Re-exports
 pub use memory::MemoryStorage;
+pub use retention::{RetentionPolicy, SweepReport};
 
+#[cfg(feature = "chaos")]
+pub use chaos::ChaosStorage;
 #[cfg(feature = "sqlite")]
 pub use sqlite::SqliteStorage;
 
@@ -33,13 +44,18 @@ pub struct StoredJob {
     pub backend_id: String,
     pub shots: u32,
     pub status: JobStatus,
+    /// Fine-grained phase reported by the backend, if any. Always maps onto
+    /// `status`; cleared by [`JobStorage::update_status`].
+    pub phase: Option<JobPhase>,
     pub submitted_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub result: Option<ExecutionResult>,
-    /// Optional parameter bindings for parametric circuits (DEBT-25).
+    /// Parameter values bound into `circuit` at submission, if any.
     /// Keys are OpenQASM 3.0 `input float[64]` parameter names.
     pub parameters: Option<std::collections::HashMap<String, f64>>,
+    /// Verified signature over the submitted circuit, if it was signed.
+    pub signature: Option<CircuitSignature>,
 }
 
 /// Filter for querying jobs.
@@ -121,6 +137,31 @@ pub(crate) fn job_status_from_string(s: &str) -> Result<JobStatus> {
     }
 }
 
+/// Convert a job's state to its storage string: the phase name if a phase
+/// is set, otherwise the status string.
+#[cfg(feature = "sqlite")]
+pub(crate) fn job_state_to_string(status: &JobStatus, phase: Option<JobPhase>) -> String {
+    match phase {
+        Some(phase) => phase.as_str().to_string(),
+        None => job_status_to_string(status),
+    }
+}
+
+/// Parse a storage string written by [`job_state_to_string`] back to a
+/// status and phase.
+///
+/// Plain status strings parse without a phase, so rows written before
+/// phases existed read back unchanged.
+#[cfg(feature = "sqlite")]
+pub(crate) fn job_state_from_string(s: &str) -> Result<(JobStatus, Option<JobPhase>)> {
+    match job_status_from_string(s) {
+        Ok(status) => Ok((status, None)),
+        Err(e) => JobPhase::parse(s)
+            .map(|phase| (phase.status(), Some(phase)))
+            .ok_or(e),
+    }
+}
+
 /// Trait for job storage backends.
 ///
 /// Implementations must be thread-safe (Send + Sync) and support async operations.
@@ -140,6 +181,15 @@ pub trait JobStorage: Send + Sync {
     /// based on the new status.
     async fn update_status(&self, job_id: &JobId, status: JobStatus) -> Result<()>;
 
+    /// Record the fine-grained phase of an unfinished job, and set its
+    /// status to the one the phase is reported as.
+    ///
+    /// The default only updates the status, for backends that do not keep
+    /// phases.
+    async fn update_phase(&self, job_id: &JobId, phase: JobPhase) -> Result<()> {
+        self.update_status(job_id, phase.status()).await
+    }
+
     /// Store the result of a completed job.
     ///
     /// This method should also update the job status to Completed and set
@@ -0,0 +1,381 @@
+//! Retention policies for job storage backends.
+//!
+//! Finished jobs (and their results) otherwise stay in storage forever. A
+//! [`RetentionPolicy`] removes terminal jobs older than a maximum age and
+//! evicts the oldest terminal jobs when a size cap is exceeded; queued and
+//! running jobs are never touched. Removed jobs can be archived as
+//! `<job-id>.json` files before deletion, sealed with the policy's
+//! `archive_cipher` when one is set.
+//!
+//! [`sweep`] runs one pass against any [`JobStorage`]; [`spawn_sweeper`]
+//! repeats it on an interval for the lifetime of the server.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arvak_hal::encryption::{self, PayloadCipher};
+use arvak_hal::job::{JobId, JobStatus};
+use arvak_hal::result::ExecutionResult;
+use arvak_hal::retention::{RemovalReason, select_for_removal};
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use super::{JobFilter, JobStorage, StoredJob};
+use crate::error::{Error, Result};
+
+/// Limits on how long and how many jobs a storage backend keeps.
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    /// Terminal jobs finished longer ago than this are removed.
+    pub max_age: Option<Duration>,
+
+    /// Maximum number of jobs kept in storage.
+    pub max_jobs: Option<usize>,
+
+    /// Directory removed jobs are archived to before deletion.
+    pub archive_dir: Option<PathBuf>,
+
+    /// Cipher for archive files.
+    pub archive_cipher: Option<PayloadCipher>,
+}
+
+impl RetentionPolicy {
+    /// Returns `true` if the policy never removes anything.
+    pub fn is_unbounded(&self) -> bool {
+        self.max_age.is_none() && self.max_jobs.is_none()
+    }
+}
+
+/// Outcome of a retention sweep.
+#[derive(Debug, Clone, Default)]
+pub struct SweepReport {
+    /// Jobs removed because they exceeded the maximum age.
+    pub expired: Vec<JobId>,
+    /// Jobs evicted to bring storage under the size cap.
+    pub evicted: Vec<JobId>,
+    /// Number of jobs written to the archive directory.
+    pub archived: usize,
+    /// Whether this was a dry run (nothing was archived or deleted).
+    pub dry_run: bool,
+}
+
+impl SweepReport {
+    /// Total number of jobs selected for removal.
+    pub fn removed(&self) -> usize {
+        self.expired.len() + self.evicted.len()
+    }
+}
+
+/// Run one retention pass over `storage`.
+///
+/// With `dry_run` the report lists the jobs that would be removed without
+/// archiving or deleting anything. When archiving, results are loaded through
+/// [`JobStorage::get_result`] because some backends (SQLite) keep them out of
+/// [`JobStorage::get_job`]; a failed lookup aborts the sweep before the job
+/// is deleted.
+pub async fn sweep(
+    storage: &dyn JobStorage,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> Result<SweepReport> {
+    let mut report = SweepReport {
+        dry_run,
+        ..Default::default()
+    };
+    if policy.is_unbounded() {
+        return Ok(report);
+    }
+
+    let jobs = storage
+        .list_jobs(JobFilter::new().with_limit(usize::MAX))
+        .await?;
+    let terminal = jobs
+        .iter()
+        .filter(|job| job.status.is_terminal())
+        .map(|job| (job, job.completed_at.unwrap_or(job.submitted_at)))
+        .collect();
+
+    let mut selected = Vec::new();
+    for (job, _, reason) in select_for_removal(
+        terminal,
+        jobs.len(),
+        policy.max_age,
+        policy.max_jobs,
+        Utc::now(),
+    ) {
+        match reason {
+            RemovalReason::Expired => report.expired.push(job.id.clone()),
+            RemovalReason::OverCapacity => report.evicted.push(job.id.clone()),
+        }
+        selected.push(job.id.clone());
+    }
+
+    if dry_run || selected.is_empty() {
+        return Ok(report);
+    }
+
+    if let Some(dir) = &policy.archive_dir {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| Error::StorageError(format!("archive directory: {e}")))?;
+    }
+
+    for job_id in selected {
+        if let Some(dir) = &policy.archive_dir {
+            if let Some(job) = storage.get_job(&job_id).await? {
+                let result = match (&job.status, &job.result) {
+                    (JobStatus::Completed, None) => Some(storage.get_result(&job_id).await?),
+                    _ => job.result.clone(),
+                };
+                archive_job(dir, &job, result.as_ref(), policy.archive_cipher.as_ref()).await?;
+                report.archived += 1;
+            }
+        }
+        storage.delete_job(&job_id).await?;
+    }
+
+    Ok(report)
+}
+
+/// Write a job and its result to `<dir>/<job-id>.json`.
+///
+/// The circuit is omitted for backends that only keep an empty placeholder.
+async fn archive_job(
+    dir: &std::path::Path,
+    job: &StoredJob,
+    result: Option<&ExecutionResult>,
+    cipher: Option<&PayloadCipher>,
+) -> Result<()> {
+    let timestamp = |t: Option<DateTime<Utc>>| t.map(|t| t.to_rfc3339());
+    let circuit_qasm = if job.circuit.num_qubits() > 0 {
+        arvak_qasm3::emit(&job.circuit).ok()
+    } else {
+        None
+    };
+    let record = serde_json::json!({
+        "job_id": job.id.0,
+        "backend_id": job.backend_id,
+        "shots": job.shots,
+        "status": format!("{:?}", job.status),
+        "submitted_at": job.submitted_at.to_rfc3339(),
+        "started_at": timestamp(job.started_at),
+        "completed_at": timestamp(job.completed_at),
+        "circuit_qasm": circuit_qasm,
+        "signature": job.signature,
+        "result": result,
+    });
+    let contents = encryption::seal_with(cipher, serde_json::to_string_pretty(&record)?)?;
+
+    let path = dir.join(format!("{}.json", job.id.0));
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| Error::StorageError(format!("failed to archive {}: {e}", path.display())))
+}
+
+/// Spawn a background task that applies `policy` to `storage` every
+/// `interval`.
+///
+/// Failed sweeps are logged and retried on the next tick. Abort the returned
+/// handle to stop the sweeper.
+pub fn spawn_sweeper(
+    storage: Arc<dyn JobStorage>,
+    policy: RetentionPolicy,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match sweep(storage.as_ref(), &policy, false).await {
+                Ok(report) if report.removed() > 0 => info!(
+                    "Retention sweep removed {} job(s): {} expired, {} evicted, {} archived",
+                    report.removed(),
+                    report.expired.len(),
+                    report.evicted.len(),
+                    report.archived
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Retention sweep failed: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use arvak_ir::circuit::Circuit;
+
+    fn job(id: &str, status: JobStatus, age_days: i64) -> StoredJob {
+        let at = Utc::now() - chrono::Duration::days(age_days);
+        StoredJob {
+            id: JobId::new(id.to_string()),
+            circuit: Circuit::with_size("test", 1, 1),
+            backend_id: "simulator".to_string(),
+            shots: 100,
+            status,
+            phase: None,
+            submitted_at: at,
+            started_at: Some(at),
+            completed_at: Some(at),
+            result: None,
+            parameters: None,
+            signature: None,
+        }
+    }
+
+    async fn storage_with_jobs() -> MemoryStorage {
+        let storage = MemoryStorage::new();
+        storage
+            .store_job(&job("old", JobStatus::Completed, 40))
+            .await
+            .unwrap();
+        storage
+            .store_job(&job("recent", JobStatus::Cancelled, 5))
+            .await
+            .unwrap();
+        storage
+            .store_job(&job("queued", JobStatus::Queued, 60))
+            .await
+            .unwrap();
+        storage
+    }
+
+    #[tokio::test]
+    async fn test_sweep_by_age_with_dry_run() {
+        let storage = storage_with_jobs().await;
+        let policy = RetentionPolicy {
+            max_age: Some(Duration::from_secs(30 * 86_400)),
+            ..Default::default()
+        };
+
+        let report = sweep(&storage, &policy, true).await.unwrap();
+        assert_eq!(report.expired, vec![JobId::new("old".to_string())]);
+        assert!(
+            storage
+                .get_job(&JobId::new("old".to_string()))
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        let report = sweep(&storage, &policy, false).await.unwrap();
+        assert_eq!(report.removed(), 1);
+        assert!(
+            storage
+                .get_job(&JobId::new("old".to_string()))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // Queued jobs are kept regardless of age.
+        assert!(
+            storage
+                .get_job(&JobId::new("queued".to_string()))
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sweep_size_cap_archives() {
+        let storage = storage_with_jobs().await;
+        let dir = std::env::temp_dir().join(format!("arvak-retention-{}", uuid::Uuid::new_v4()));
+        let policy = RetentionPolicy {
+            max_jobs: Some(2),
+            archive_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+
+        let report = sweep(&storage, &policy, false).await.unwrap();
+        assert_eq!(report.evicted, vec![JobId::new("old".to_string())]);
+        assert_eq!(report.archived, 1);
+
+        let archived: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(dir.join("old.json")).unwrap()).unwrap();
+        assert_eq!(archived["backend_id"], "simulator");
+        assert!(
+            archived["circuit_qasm"]
+                .as_str()
+                .unwrap()
+                .contains("OPENQASM")
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sweep_archives_sqlite_results_sealed() {
+        use crate::storage::SqliteStorage;
+        use arvak_hal::result::Counts;
+
+        let storage = SqliteStorage::new(":memory:").unwrap();
+        storage
+            .store_job(&job("done", JobStatus::Running, 40))
+            .await
+            .unwrap();
+        storage
+            .store_result(
+                &JobId::new("done".to_string()),
+                ExecutionResult::new(Counts::from_pairs([("0", 60), ("1", 40)]), 100),
+            )
+            .await
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("arvak-retention-{}", uuid::Uuid::new_v4()));
+        let cipher = PayloadCipher::new(&[7; 32]).unwrap();
+        let policy = RetentionPolicy {
+            max_jobs: Some(0),
+            archive_dir: Some(dir.clone()),
+            archive_cipher: Some(cipher.clone()),
+            ..Default::default()
+        };
+
+        let report = sweep(&storage, &policy, false).await.unwrap();
+        assert_eq!(report.archived, 1);
+
+        let sealed = std::fs::read_to_string(dir.join("done.json")).unwrap();
+        assert!(!sealed.contains("\"counts\""));
+        let archived: serde_json::Value =
+            serde_json::from_str(&cipher.open(&sealed).unwrap()).unwrap();
+        assert_eq!(archived["result"]["shots"], 100);
+        // SQLite keeps only a placeholder circuit.
+        assert!(archived["circuit_qasm"].is_null());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sweep_keeps_job_when_result_lookup_fails() {
+        use crate::storage::SqliteStorage;
+
+        let storage = SqliteStorage::new(":memory:").unwrap();
+        storage
+            .store_job(&job("lost", JobStatus::Completed, 40))
+            .await
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("arvak-retention-{}", uuid::Uuid::new_v4()));
+        let policy = RetentionPolicy {
+            max_jobs: Some(0),
+            archive_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+
+        assert!(sweep(&storage, &policy, false).await.is_err());
+        assert!(
+            storage
+                .get_job(&JobId::new("lost".to_string()))
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -5,11 +5,14 @@
 //! - Result storage with efficient serialization
 //! - Job filtering and querying
 //! - Automatic schema migrations
+//! - Optional AES-256-GCM encryption of stored results
 
 use crate::error::{Error, Result};
 use crate::storage::{JobFilter, JobStorage, StoredJob};
-use arvak_hal::job::{JobId, JobStatus};
+use arvak_hal::encryption::{self, PayloadCipher};
+use arvak_hal::job::{JobId, JobPhase, JobStatus};
 use arvak_hal::result::ExecutionResult;
+use arvak_hal::signing::CircuitSignature;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, OptionalExtension, params};
@@ -21,9 +24,13 @@ use tokio::task;
 ///
 /// Uses rusqlite with a connection pool for thread-safe access.
 /// Jobs and results are stored in separate tables for efficient querying.
+///
+/// With [`with_cipher`](Self::with_cipher), result counts and metadata are
+/// encrypted before they are written.
 #[derive(Clone)]
 pub struct SqliteStorage {
     connection: Arc<Mutex<Connection>>,
+    cipher: Option<Arc<PayloadCipher>>,
 }
 
 impl SqliteStorage {
@@ -47,6 +54,7 @@ impl SqliteStorage {
 
         let storage = Self {
             connection: Arc::new(Mutex::new(conn)),
+            cipher: None,
         };
 
         storage.init_schema()?;
@@ -54,6 +62,16 @@ impl SqliteStorage {
         Ok(storage)
     }
 
+    /// Encrypt stored results with the given cipher.
+    ///
+    /// Use [`PayloadCipher::from_env`] to take the key from
+    /// `ARVAK_STORAGE_KEY`, or [`PayloadCipher::new`] for a KMS-provided key.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: PayloadCipher) -> Self {
+        self.cipher = Some(Arc::new(cipher));
+        self
+    }
+
     /// Initialize the database schema.
     fn init_schema(&self) -> Result<()> {
         let conn = self
@@ -72,11 +90,22 @@ impl SqliteStorage {
                 submitted_at INTEGER NOT NULL,
                 started_at INTEGER,
                 completed_at INTEGER,
-                error_message TEXT
+                error_message TEXT,
+                signature_json TEXT
             )",
             [],
         )?;
 
+        // Databases created before circuit signing lack the signature column
+        let has_signature: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('jobs') WHERE name = 'signature_json'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_signature {
+            conn.execute("ALTER TABLE jobs ADD COLUMN signature_json TEXT", [])?;
+        }
+
         // Results table (separate for efficiency)
         conn.execute(
             "CREATE TABLE IF NOT EXISTS job_results (
@@ -130,8 +159,35 @@ impl SqliteStorage {
         super::job_status_to_string(status)
     }
 
-    fn string_to_status(s: &str) -> Result<JobStatus> {
-        super::job_status_from_string(s)
+    fn string_to_state(s: &str) -> Result<(JobStatus, Option<JobPhase>)> {
+        super::job_state_from_string(s)
+    }
+
+    /// Status strings matching `status`: its own and those of the phases
+    /// reported as it.
+    fn status_strings(status: &JobStatus) -> Vec<String> {
+        let own = Self::status_to_string(status);
+        let mut strings: Vec<String> = JobPhase::ALL
+            .into_iter()
+            .filter(|phase| phase.status() == *status && phase.as_str() != own)
+            .map(|phase| phase.as_str().to_string())
+            .collect();
+        strings.push(own);
+        strings
+    }
+
+    /// Parse the stored signature column inside a row mapper.
+    fn read_signature(json: Option<String>) -> rusqlite::Result<Option<CircuitSignature>> {
+        json.map(|json| {
+            serde_json::from_str(&json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    8,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        })
+        .transpose()
     }
 }
 
@@ -147,18 +203,23 @@ impl JobStorage for SqliteStorage {
                 .map_err(|_| Error::StorageError("database lock poisoned".into()))?;
 
             let circuit_json = Self::serialize_circuit(&job.circuit)?;
-            let status_str = Self::status_to_string(&job.status);
+            let status_str = super::job_state_to_string(&job.status, job.phase);
             let error_msg = if let JobStatus::Failed(msg) = &job.status {
                 Some(msg.as_str())
             } else {
                 None
             };
+            let signature_json = job
+                .signature
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?;
 
             conn.execute(
                 "INSERT OR REPLACE INTO jobs (
                     job_id, circuit_json, backend_id, shots, status,
-                    submitted_at, started_at, completed_at, error_message
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    submitted_at, started_at, completed_at, error_message, signature_json
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
                 params![
                     job.id.0,
                     circuit_json,
@@ -169,6 +230,7 @@ impl JobStorage for SqliteStorage {
                     job.started_at.map(|t| t.timestamp()),
                     job.completed_at.map(|t| t.timestamp()),
                     error_msg,
+                    signature_json,
                 ],
             )?;
 
@@ -190,7 +252,7 @@ impl JobStorage for SqliteStorage {
             let result = conn
                 .query_row(
                     "SELECT job_id, circuit_json, backend_id, shots, status,
-                            submitted_at, started_at, completed_at
+                            submitted_at, started_at, completed_at, signature_json
                      FROM jobs WHERE job_id = ?1",
                     params![job_id.0],
                     |row| {
@@ -199,12 +261,13 @@ impl JobStorage for SqliteStorage {
                             .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
 
                         let status_str: String = row.get(4)?;
-                        let status = Self::string_to_status(&status_str)
+                        let (status, phase) = Self::string_to_state(&status_str)
                             .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
 
                         let submitted_ts: i64 = row.get(5)?;
                         let started_ts: Option<i64> = row.get(6)?;
                         let completed_ts: Option<i64> = row.get(7)?;
+                        let signature = Self::read_signature(row.get(8)?)?;
 
                         Ok(StoredJob {
                             id: job_id.clone(),
@@ -212,6 +275,7 @@ impl JobStorage for SqliteStorage {
                             backend_id: row.get(2)?,
                             shots: row.get(3)?,
                             status,
+                            phase,
                             submitted_at: DateTime::from_timestamp(submitted_ts, 0)
                                 .unwrap_or_else(|| Utc::now()),
                             started_at: started_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
@@ -219,6 +283,7 @@ impl JobStorage for SqliteStorage {
                                 .and_then(|ts| DateTime::from_timestamp(ts, 0)),
                             result: None, // Results are stored separately
                             parameters: None,
+                            signature,
                         })
                     },
                 )
@@ -281,9 +346,34 @@ impl JobStorage for SqliteStorage {
         .map_err(|e| Error::StorageError(format!("task join error: {}", e)))?
     }
 
+    async fn update_phase(&self, job_id: &JobId, phase: JobPhase) -> Result<()> {
+        let job_id = job_id.clone();
+        let conn = self.connection.clone();
+
+        task::spawn_blocking(move || {
+            let conn = conn
+                .lock()
+                .map_err(|_| Error::StorageError("database lock poisoned".into()))?;
+
+            // Keep the time the job first started running across phases.
+            let started_at =
+                matches!(phase.status(), JobStatus::Running).then(|| Utc::now().timestamp());
+            conn.execute(
+                "UPDATE jobs SET status = ?1, started_at = COALESCE(started_at, ?2)
+                 WHERE job_id = ?3",
+                params![phase.as_str(), started_at, job_id.0],
+            )?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| Error::StorageError(format!("task join error: {}", e)))?
+    }
+
     async fn store_result(&self, job_id: &JobId, result: ExecutionResult) -> Result<()> {
         let job_id = job_id.clone();
         let conn = self.connection.clone();
+        let cipher = self.cipher.clone();
 
         task::spawn_blocking(move || {
             let conn = conn
@@ -293,14 +383,16 @@ impl JobStorage for SqliteStorage {
             // Serialize counts to JSON
             let counts_json = serde_json::to_string(&result.counts)
                 .map_err(|e| Error::StorageError(format!("Failed to serialize counts: {}", e)))?;
+            let counts_json = encryption::seal_with(cipher.as_deref(), counts_json)?;
 
             // Serialize metadata to JSON (or null if Value::Null)
             let metadata_json = if result.metadata.is_null() {
                 None
             } else {
-                Some(serde_json::to_string(&result.metadata).map_err(|e| {
+                let json = serde_json::to_string(&result.metadata).map_err(|e| {
                     Error::StorageError(format!("Failed to serialize metadata: {}", e))
-                })?)
+                })?;
+                Some(encryption::seal_with(cipher.as_deref(), json)?)
             };
 
             conn.execute(
@@ -331,6 +423,7 @@ impl JobStorage for SqliteStorage {
     async fn get_result(&self, job_id: &JobId) -> Result<ExecutionResult> {
         let job_id = job_id.clone();
         let conn = self.connection.clone();
+        let cipher = self.cipher.clone();
 
         task::spawn_blocking(move || {
             let conn = conn
@@ -342,12 +435,15 @@ impl JobStorage for SqliteStorage {
                  FROM job_results WHERE job_id = ?1",
                 params![job_id.0],
                 |row| {
-                    let counts_json: String = row.get(0)?;
+                    let counts_json = encryption::open_with(cipher.as_deref(), row.get(0)?)
+                        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
                     let counts = serde_json::from_str(&counts_json)
                         .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
 
                     let metadata_json: Option<String> = row.get(3)?;
                     let metadata = if let Some(json) = metadata_json {
+                        let json = encryption::open_with(cipher.as_deref(), json)
+                            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
                         serde_json::from_str(&json)
                             .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?
                     } else {
@@ -382,7 +478,8 @@ impl JobStorage for SqliteStorage {
             // Build query based on filter
             let mut query = String::from(
                 "SELECT job_id, circuit_json, backend_id, shots, status,
-                                                 submitted_at, started_at, completed_at
+                                                 submitted_at, started_at, completed_at,
+                                                 signature_json
                                           FROM jobs WHERE 1=1",
             );
             let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
@@ -396,8 +493,13 @@ impl JobStorage for SqliteStorage {
                         params.push(Box::new("failed:%".to_string()));
                     }
                     _ => {
-                        query.push_str(" AND status = ?");
-                        params.push(Box::new(Self::status_to_string(&status)));
+                        let strings = Self::status_strings(&status);
+                        query.push_str(" AND status IN (");
+                        query.push_str(&vec!["?"; strings.len()].join(", "));
+                        query.push(')');
+                        for string in strings {
+                            params.push(Box::new(string));
+                        }
                     }
                 }
             }
@@ -418,7 +520,9 @@ impl JobStorage for SqliteStorage {
             }
 
             query.push_str(" ORDER BY submitted_at DESC LIMIT ?");
-            params.push(Box::new(filter.limit as i64));
+            // SQLite reads a negative limit as "no limit", which is what an
+            // unbounded filter (`usize::MAX`) asks for.
+            params.push(Box::new(i64::try_from(filter.limit).unwrap_or(-1)));
 
             let mut stmt = conn.prepare(&query)?;
             let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
@@ -431,12 +535,13 @@ impl JobStorage for SqliteStorage {
 
                     let job_id: String = row.get(0)?;
                     let status_str: String = row.get(4)?;
-                    let status = Self::string_to_status(&status_str)
+                    let (status, phase) = Self::string_to_state(&status_str)
                         .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
 
                     let submitted_ts: i64 = row.get(5)?;
                     let started_ts: Option<i64> = row.get(6)?;
                     let completed_ts: Option<i64> = row.get(7)?;
+                    let signature = Self::read_signature(row.get(8)?)?;
 
                     Ok(StoredJob {
                         id: JobId::new(job_id),
@@ -444,12 +549,14 @@ impl JobStorage for SqliteStorage {
                         backend_id: row.get(2)?,
                         shots: row.get(3)?,
                         status,
+                        phase,
                         submitted_at: DateTime::from_timestamp(submitted_ts, 0)
                             .unwrap_or_else(|| Utc::now()),
                         started_at: started_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
                         completed_at: completed_ts.and_then(|ts| DateTime::from_timestamp(ts, 0)),
                         result: None,
                         parameters: None,
+                        signature,
                     })
                 })?
                 .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -498,10 +605,16 @@ mod tests {
             backend_id: "simulator".to_string(),
             shots: 1000,
             status: JobStatus::Queued,
+            phase: None,
             submitted_at: Utc::now(),
             started_at: None,
             completed_at: None,
             result: None,
+            parameters: None,
+            signature: Some(CircuitSignature {
+                public_key: "cHVibGlj".to_string(),
+                signature: "c2lnbmF0dXJl".to_string(),
+            }),
         };
 
         // Store job
@@ -513,6 +626,7 @@ mod tests {
         let retrieved = retrieved.unwrap();
         assert_eq!(retrieved.id, job_id);
         assert_eq!(retrieved.backend_id, "simulator");
+        assert_eq!(retrieved.signature, job.signature);
 
         // Update status
         storage
@@ -563,10 +677,13 @@ mod tests {
                 } else {
                     JobStatus::Queued
                 },
+                phase: None,
                 submitted_at: Utc::now(),
                 started_at: None,
                 completed_at: None,
                 result: None,
+                parameters: None,
+                signature: None,
             };
             storage.store_job(&job).await.unwrap();
         }
@@ -595,4 +712,106 @@ mod tests {
         let jobs = storage.list_jobs(filter).await.unwrap();
         assert_eq!(jobs.len(), 3); // 0, 2, 4
     }
+
+    #[tokio::test]
+    async fn test_sqlite_storage_phases() {
+        let storage = SqliteStorage::new(":memory:").unwrap();
+        let job = StoredJob {
+            id: JobId::new("job-phase".to_string()),
+            circuit: Circuit::new("test"),
+            backend_id: "sim".to_string(),
+            shots: 100,
+            status: JobStatus::Queued,
+            phase: None,
+            submitted_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            result: None,
+            parameters: None,
+            signature: None,
+        };
+        storage.store_job(&job).await.unwrap();
+
+        storage
+            .update_phase(&job.id, JobPhase::Transferring)
+            .await
+            .unwrap();
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Queued);
+        assert_eq!(stored.phase, Some(JobPhase::Transferring));
+        assert!(stored.started_at.is_none());
+
+        // Phases are found by the status they are reported as.
+        let queued = storage
+            .list_jobs(JobFilter::new().with_state(JobStatus::Queued))
+            .await
+            .unwrap();
+        assert_eq!(queued.len(), 1);
+
+        storage
+            .update_phase(&job.id, JobPhase::Calibrating)
+            .await
+            .unwrap();
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Running);
+        assert!(stored.started_at.is_some());
+
+        storage
+            .update_status(&job.id, JobStatus::Cancelled)
+            .await
+            .unwrap();
+        let stored = storage.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Cancelled);
+        assert_eq!(stored.phase, None);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_storage_encrypted_results() {
+        use arvak_hal::result::Counts;
+
+        let cipher = PayloadCipher::new(&[42; 32]).unwrap();
+        let storage = SqliteStorage::new(":memory:").unwrap().with_cipher(cipher);
+
+        let job_id = JobId::new("secret-job".to_string());
+        let job = StoredJob {
+            id: job_id.clone(),
+            circuit: Circuit::new("test"),
+            backend_id: "simulator".to_string(),
+            shots: 100,
+            status: JobStatus::Queued,
+            phase: None,
+            submitted_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            result: None,
+            parameters: None,
+            signature: None,
+        };
+        storage.store_job(&job).await.unwrap();
+
+        let result = ExecutionResult {
+            counts: Counts::from_pairs([("0101", 100)]),
+            shots: 100,
+            execution_time_ms: None,
+            metadata: serde_json::json!({"device": "secret"}),
+        };
+        storage.store_result(&job_id, result).await.unwrap();
+
+        {
+            let conn = storage.connection.lock().unwrap();
+            let (counts_json, metadata_json): (String, String) = conn
+                .query_row(
+                    "SELECT counts_json, metadata_json FROM job_results",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .unwrap();
+            assert!(!counts_json.contains("0101"));
+            assert!(!metadata_json.contains("secret"));
+        }
+
+        let loaded = storage.get_result(&job_id).await.unwrap();
+        assert_eq!(loaded.counts.get("0101"), 100);
+        assert_eq!(loaded.metadata["device"], "secret");
+    }
 }
@@ -0,0 +1,130 @@
+//! Recovery tests under injected faults.
+//!
+//! Run with `cargo test -p arvak-grpc --features chaos`.
+
+#![cfg(feature = "chaos")]
+
+mod strict_backend;
+
+use std::sync::Arc;
+
+use arvak_grpc::proto::{arvak_service_client::ArvakServiceClient, *};
+use arvak_grpc::server::{ArvakServiceImpl, BackendRegistry, JobStore};
+use arvak_hal::chaos::{BackendFaults, BackendOperation, FaultInjector, FaultScenario};
+use tonic::Request;
+use tonic::transport::Server;
+
+use strict_backend::StrictBackend;
+
+const TEST_QASM: &str = r"
+OPENQASM 3.0;
+qubit[2] q;
+h q[0];
+cx q[0], q[1];
+";
+
+/// Start a server whose strict backend fails submissions as `injector` decides.
+async fn start_chaos_test_server(injector: &Arc<FaultInjector>) -> String {
+    let mut registry = BackendRegistry::new();
+    registry.register("strict".to_string(), Arc::new(StrictBackend::new()));
+    registry.inject_faults(injector);
+
+    let service = ArvakServiceImpl::with_components(JobStore::new(), registry);
+    let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(arvak_grpc::proto::arvak_service_server::ArvakServiceServer::new(service))
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    format!("http://{addr}")
+}
+
+fn submit_faults(probability: f64, transient: bool) -> Arc<FaultInjector> {
+    Arc::new(FaultInjector::new(FaultScenario {
+        // With seed 3 the first draw fails at p = 0.5 and the second passes.
+        seed: 3,
+        backend_errors: Some(BackendFaults {
+            probability,
+            operations: vec![BackendOperation::Submit],
+            backends: Vec::new(),
+            transient,
+        }),
+        ..Default::default()
+    }))
+}
+
+/// Submit a job and poll until it finishes, for up to ten seconds.
+async fn run_job(client: &mut ArvakServiceClient<tonic::transport::Channel>) -> Job {
+    let job_id = client
+        .submit_job(Request::new(SubmitJobRequest {
+            circuit: Some(CircuitPayload {
+                format: Some(circuit_payload::Format::Qasm3(TEST_QASM.to_string())),
+            }),
+            backend_id: "strict".to_string(),
+            shots: 1024,
+            optimization_level: 1,
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .job_id;
+
+    for _ in 0..100 {
+        let job = client
+            .get_job_status(Request::new(GetJobStatusRequest {
+                job_id: job_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .job
+            .unwrap();
+        let state = JobState::try_from(job.state).unwrap();
+        if state == JobState::Completed || state == JobState::Failed {
+            return job;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+    panic!("job {job_id} did not finish");
+}
+
+/// A transient submit failure is retried and the job still completes.
+#[tokio::test]
+async fn test_transient_backend_error_is_retried() {
+    let injector = submit_faults(0.5, true);
+    let addr = start_chaos_test_server(&injector).await;
+    let mut client = ArvakServiceClient::connect(addr).await.unwrap();
+
+    let job = run_job(&mut client).await;
+
+    assert_eq!(
+        JobState::try_from(job.state).unwrap(),
+        JobState::Completed,
+        "job failed: {}",
+        job.error_message
+    );
+    assert_eq!(injector.counts().backend_errors, 1);
+}
+
+/// A permanent submit failure fails the job without retries.
+#[tokio::test]
+async fn test_permanent_backend_error_fails_job() {
+    let injector = submit_faults(1.0, false);
+    let addr = start_chaos_test_server(&injector).await;
+    let mut client = ArvakServiceClient::connect(addr).await.unwrap();
+
+    let job = run_job(&mut client).await;
+
+    assert_eq!(JobState::try_from(job.state).unwrap(), JobState::Failed);
+    assert!(job.error_message.contains("injected fault"));
+    assert_eq!(injector.counts().backend_errors, 1);
+}
@@ -75,10 +75,16 @@ async fn test_get_backend_info() {
         .await
         .unwrap();
 
-    let backend = response.into_inner().backend.unwrap();
+    let response = response.into_inner();
+    let backend = response.backend.unwrap();
     assert_eq!(backend.backend_id, "simulator");
     assert!(backend.is_available);
     assert!(!backend.supported_gates.is_empty());
+
+    let build = response.build.unwrap();
+    assert!(build.components.iter().any(|c| c.name == "arvak-grpc"));
+    assert!(build.adapters.contains(&"simulator".to_string()));
+    assert!(build.storage_backends.contains(&"memory".to_string()));
 }
 
 #[tokio::test]
@@ -284,6 +290,69 @@ async fn test_invalid_circuit() {
     assert_eq!(err.code(), tonic::Code::InvalidArgument);
 }
 
+#[tokio::test]
+async fn test_submit_binds_input_parameters() {
+    let addr = start_test_server().await;
+    let mut client = ArvakServiceClient::connect(addr).await.unwrap();
+
+    let qasm = "OPENQASM 3.0; input float[64] theta; qubit q; bit c; rx(theta) q; c = measure q;";
+    let request = |parameters: &[(&str, f64)]| SubmitJobRequest {
+        circuit: Some(CircuitPayload {
+            format: Some(circuit_payload::Format::Qasm3(qasm.to_string())),
+        }),
+        backend_id: "simulator".to_string(),
+        shots: 100,
+        parameters: parameters
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), *v))
+            .collect(),
+        ..Default::default()
+    };
+
+    // Unbound parameters are rejected at submission.
+    let err = client
+        .submit_job(Request::new(request(&[])))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    assert!(err.message().contains("theta"));
+
+    let job_id = client
+        .submit_job(Request::new(request(&[("theta", std::f64::consts::PI)])))
+        .await
+        .unwrap()
+        .into_inner()
+        .job_id;
+
+    let mut completed = false;
+    for _ in 0..20 {
+        let job = client
+            .get_job_status(Request::new(GetJobStatusRequest {
+                job_id: job_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .job
+            .unwrap();
+        if JobState::try_from(job.state).unwrap() == JobState::Completed {
+            completed = true;
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+    assert!(completed, "Job should complete within 2 seconds");
+
+    let result = client
+        .get_job_result(Request::new(GetJobResultRequest { job_id }))
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .unwrap();
+    assert_eq!(result.counts.get("1"), Some(&100));
+}
+
 #[tokio::test]
 async fn test_job_not_found() {
     let addr = start_test_server().await;
@@ -588,6 +657,7 @@ async fn test_submit_with_compilation() {
             backend_id: "strict".to_string(),
             shots: 1024,
             optimization_level: 1,
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -624,6 +694,70 @@ async fn test_submit_with_compilation() {
     assert!(completed, "Compiled job should complete on strict backend");
 }
 
+/// Submit a batch at different optimization levels to the strict backend.
+/// The circuits are compiled together, and every job must still come out
+/// in prx+cz in the order submitted.
+#[tokio::test]
+async fn test_submit_batch_with_compilation() {
+    let addr = start_strict_test_server().await;
+    let mut client = ArvakServiceClient::connect(addr).await.unwrap();
+
+    let jobs = [1, 3, 2, 1, 3]
+        .into_iter()
+        .zip(1..)
+        .map(|(optimization_level, i)| BatchJobRequest {
+            circuit: Some(CircuitPayload {
+                format: Some(circuit_payload::Format::Qasm3(TEST_QASM.to_string())),
+            }),
+            shots: 100 * i,
+            optimization_level,
+            ..Default::default()
+        })
+        .collect();
+    let response = client
+        .submit_batch(Request::new(SubmitBatchRequest {
+            backend_id: "strict".to_string(),
+            jobs,
+        }))
+        .await
+        .unwrap();
+
+    let job_ids = response.into_inner().job_ids;
+    assert_eq!(job_ids.len(), 5);
+
+    for (job_id, i) in job_ids.iter().zip(1..) {
+        let mut completed = false;
+        for _ in 0..20 {
+            let response = client
+                .get_job_status(Request::new(GetJobStatusRequest {
+                    job_id: job_id.clone(),
+                }))
+                .await
+                .unwrap();
+
+            let job = response.into_inner().job.unwrap();
+            let state = JobState::try_from(job.state).unwrap();
+            assert_eq!(job.shots, 100 * i, "jobs must keep their submission order");
+
+            if state == JobState::Completed {
+                completed = true;
+                break;
+            }
+            assert!(
+                state != JobState::Failed,
+                "Job failed unexpectedly: {}",
+                job.error_message
+            );
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        assert!(
+            completed,
+            "Compiled batch job should complete on strict backend"
+        );
+    }
+}
+
 /// Submit h+cx circuit to strict backend WITHOUT compilation (optimization_level=0).
 /// The strict backend should reject the unsupported h and cx gates.
 #[tokio::test]
@@ -639,6 +773,7 @@ async fn test_submit_without_compilation_rejects_unsupported_gates() {
             backend_id: "strict".to_string(),
             shots: 1024,
             optimization_level: 0, // No compilation
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -696,6 +831,7 @@ async fn test_submit_default_backwards_compatible() {
             backend_id: "simulator".to_string(),
             shots: 100,
             optimization_level: 0, // Explicit default — no compilation
+            ..Default::default()
         }))
         .await
         .unwrap();
@@ -729,3 +865,277 @@ async fn test_submit_default_backwards_compatible() {
         "Default submission to simulator should still work"
     );
 }
+
+/// Start a server with the admin service mounted behind the admin key.
+async fn start_admin_test_server(job_store: JobStore) -> String {
+    use arvak_grpc::proto::arvak_admin_service_server::ArvakAdminServiceServer;
+    use arvak_grpc::server::AdminAuthInterceptor;
+    use arvak_grpc::server::backend_registry::create_default_registry;
+
+    let service = ArvakServiceImpl::with_components(job_store, create_default_registry());
+    let admin = tonic::service::interceptor::InterceptedService::new(
+        ArvakAdminServiceServer::new(service.admin_service()),
+        AdminAuthInterceptor::new("admin-secret"),
+    );
+    let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(arvak_grpc::proto::arvak_service_server::ArvakServiceServer::new(service))
+            .add_service(admin)
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    format!("http://{addr}")
+}
+
+fn admin_request<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("x-admin-key", "admin-secret".parse().unwrap());
+    request
+}
+
+/// Draining and pausing reject new submissions until reverted; admin calls
+/// without the admin key are refused.
+#[tokio::test]
+async fn test_admin_drain_and_pause() {
+    use arvak_grpc::proto::arvak_admin_service_client::ArvakAdminServiceClient;
+
+    let addr = start_admin_test_server(JobStore::new()).await;
+    let mut client = ArvakServiceClient::connect(addr.clone()).await.unwrap();
+    let mut admin = ArvakAdminServiceClient::connect(addr).await.unwrap();
+
+    let submit = || {
+        Request::new(SubmitJobRequest {
+            circuit: Some(CircuitPayload {
+                format: Some(circuit_payload::Format::Qasm3(TEST_QASM.to_string())),
+            }),
+            backend_id: "simulator".to_string(),
+            shots: 100,
+            optimization_level: 0,
+            ..Default::default()
+        })
+    };
+
+    let status = admin
+        .drain_node(Request::new(DrainNodeRequest { drain: true }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+    let response = admin
+        .drain_node(admin_request(DrainNodeRequest { drain: true }))
+        .await
+        .unwrap();
+    assert!(response.into_inner().draining);
+
+    let status = client.submit_job(submit()).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+
+    admin
+        .drain_node(admin_request(DrainNodeRequest { drain: false }))
+        .await
+        .unwrap();
+    admin
+        .pause_backend(admin_request(PauseBackendRequest {
+            backend_id: "simulator".to_string(),
+            paused: true,
+        }))
+        .await
+        .unwrap();
+
+    let status = client.submit_job(submit()).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+
+    let backend = client
+        .get_backend_info(Request::new(GetBackendInfoRequest {
+            backend_id: "simulator".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .backend
+        .unwrap();
+    assert!(!backend.is_available);
+
+    let snapshot = admin
+        .get_metrics_snapshot(admin_request(GetMetricsSnapshotRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(!snapshot.draining);
+    assert_eq!(snapshot.paused_backends, vec!["simulator".to_string()]);
+    assert!(snapshot.limits.is_none());
+
+    admin
+        .pause_backend(admin_request(PauseBackendRequest {
+            backend_id: "simulator".to_string(),
+            paused: false,
+        }))
+        .await
+        .unwrap();
+    assert!(client.submit_job(submit()).await.is_ok());
+}
+
+/// A failed job can be requeued and runs to completion; completed and
+/// unknown jobs cannot be requeued.
+#[tokio::test]
+async fn test_admin_requeue_failed_job() {
+    use arvak_grpc::proto::arvak_admin_service_client::ArvakAdminServiceClient;
+    use arvak_hal::job::JobStatus;
+
+    let job_store = JobStore::new();
+    let job_id = job_store
+        .create_job(
+            arvak_qasm3::parse(TEST_QASM).unwrap(),
+            "simulator".to_string(),
+            100,
+            None,
+        )
+        .await
+        .unwrap();
+    job_store
+        .update_status(&job_id, JobStatus::Failed("transient outage".to_string()))
+        .await
+        .unwrap();
+
+    let addr = start_admin_test_server(job_store).await;
+    let mut client = ArvakServiceClient::connect(addr.clone()).await.unwrap();
+    let mut admin = ArvakAdminServiceClient::connect(addr).await.unwrap();
+
+    let response = admin
+        .requeue_job(admin_request(RequeueJobRequest {
+            job_id: job_id.0.clone(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.state, JobState::Queued as i32);
+
+    let mut completed = false;
+    for _ in 0..20 {
+        let job = client
+            .get_job_status(Request::new(GetJobStatusRequest {
+                job_id: job_id.0.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .job
+            .unwrap();
+        if JobState::try_from(job.state).unwrap() == JobState::Completed {
+            completed = true;
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+    assert!(completed, "Requeued job should run to completion");
+
+    let status = admin
+        .requeue_job(admin_request(RequeueJobRequest {
+            job_id: job_id.0.clone(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    let status = admin
+        .requeue_job(admin_request(RequeueJobRequest {
+            job_id: "nonexistent".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+}
+
+/// Start a test server that only runs signed circuits on the simulator.
+async fn start_signing_test_server(trusted_key: &str) -> String {
+    let policy = arvak_hal::SignaturePolicy::new()
+        .with_trusted_key(trusted_key)
+        .unwrap()
+        .require_for_backend("simulator");
+    let service = ArvakServiceImpl::new().with_signing_policy(policy);
+    let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(arvak_grpc::proto::arvak_service_server::ArvakServiceServer::new(service))
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn test_signed_circuits_required() {
+    let signer = arvak_hal::CircuitSigner::from_bytes(&[9; 32]).unwrap();
+    let addr = start_signing_test_server(&signer.public_key()).await;
+    let mut client = ArvakServiceClient::connect(addr).await.unwrap();
+
+    let submit = |qasm: &str, signature: Option<arvak_hal::CircuitSignature>| {
+        Request::new(SubmitJobRequest {
+            circuit: Some(CircuitPayload {
+                format: Some(circuit_payload::Format::Qasm3(qasm.to_string())),
+            }),
+            backend_id: "simulator".to_string(),
+            shots: 100,
+            signature: signature.map(|s| CircuitSignature {
+                public_key: s.public_key,
+                signature: s.signature,
+            }),
+            ..Default::default()
+        })
+    };
+
+    // Unsigned and untrusted circuits are refused
+    let status = client
+        .submit_job(submit(TEST_QASM, None))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::PermissionDenied);
+    let stranger = arvak_hal::CircuitSigner::from_bytes(&[10; 32]).unwrap();
+    let status = client
+        .submit_job(submit(TEST_QASM, Some(stranger.sign(TEST_QASM))))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+    // A signature over a different circuit does not verify
+    let tampered = format!("{TEST_QASM}x q[0];\n");
+    let status = client
+        .submit_job(submit(&tampered, Some(signer.sign(TEST_QASM))))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // Signed circuits run, and the signer is recorded with the job
+    let job_id = client
+        .submit_job(submit(TEST_QASM, Some(signer.sign(TEST_QASM))))
+        .await
+        .unwrap()
+        .into_inner()
+        .job_id;
+    let job = client
+        .get_job_status(Request::new(GetJobStatusRequest { job_id }))
+        .await
+        .unwrap()
+        .into_inner()
+        .job
+        .unwrap();
+    assert_eq!(job.signer_public_key, signer.public_key());
+}
@@ -0,0 +1,30 @@
+//! Records the git commit for `BuildInfo::current`.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=ARVAK_GIT_COMMIT");
+
+    let head = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../.git/HEAD");
+    if head.exists() {
+        println!("cargo:rerun-if-changed={}", head.display());
+    }
+
+    let commit = std::env::var("ARVAK_GIT_COMMIT").ok().or_else(|| {
+        let output = Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let commit = String::from_utf8(output.stdout).ok()?;
+        Some(commit.trim().to_string())
+    });
+
+    if let Some(commit) = commit.filter(|c| !c.is_empty()) {
+        println!("cargo:rustc-env=ARVAK_GIT_COMMIT={commit}");
+    }
+}
@@ -0,0 +1,501 @@
+//! Hardware attestation from physically unclonable device fingerprints.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Manufacturing variation gives every qubit its own readout bias: the rate
+//! at which a prepared `|0⟩` reads as 1 and a prepared `|1⟩` reads as 0.
+//! The pattern across qubits is stable between calibrations but different
+//! on every chip, so it works as a physically unclonable function (PUF).
+//!
+//! A [`PufEnrollment`] records the [`DeviceFingerprint`] of a backend once,
+//! on hardware known to be genuine. Later, [`PufEnrollment::attest`] runs
+//! the same [`PufChallenge`] again and compares the fresh fingerprint: an
+//! [`Attestation`] is verified when the two are within the enrollment's
+//! tolerance. Attestations are recorded in result metadata under
+//! [`ATTESTATION_METADATA_KEY`], so a result shows which hardware it ran
+//! on.
+//!
+//! ```rust
+//! use arvak_hal::Counts;
+//! use arvak_hal::attestation::{AttestationStatus, DeviceFingerprint, PufEnrollment};
+//!
+//! // Qubit 1 reads a prepared |0⟩ as 1 in 4% of the shots.
+//! let zeros = Counts::from_pairs([("00".to_string(), 960), ("10".to_string(), 40)]);
+//! let ones = Counts::from_pairs([("11".to_string(), 1000)]);
+//! let fingerprint = DeviceFingerprint::from_counts(2, &zeros, &ones);
+//! assert_eq!(fingerprint.zero_flip, vec![0.0, 0.04]);
+//!
+//! let enrollment = PufEnrollment::new("garnet", fingerprint.clone());
+//! let attestation = enrollment.verify(&fingerprint);
+//! assert_eq!(attestation.status, AttestationStatus::Verified);
+//! ```
+
+use std::fmt;
+
+use arvak_ir::{Circuit, QubitId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::Backend;
+use crate::error::{HalError, HalResult};
+use crate::result::{Counts, ExecutionResult};
+use crate::shots::run_split;
+
+/// Key under which [`Attestation::attach`] stores the record in
+/// [`ExecutionResult::metadata`].
+pub const ATTESTATION_METADATA_KEY: &str = "attestation";
+
+/// The circuits that read out a device fingerprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PufChallenge {
+    /// Qubits measured, starting at qubit 0.
+    pub qubits: u32,
+    /// Shots per challenge circuit.
+    pub shots: u32,
+}
+
+impl PufChallenge {
+    /// Default number of qubits measured.
+    pub const DEFAULT_QUBITS: u32 = 16;
+    /// Default shots per challenge circuit.
+    pub const DEFAULT_SHOTS: u32 = 2000;
+
+    /// Create a challenge over the first `qubits` qubits.
+    pub fn new(qubits: u32, shots: u32) -> Self {
+        Self { qubits, shots }
+    }
+
+    /// The default challenge for a backend with `num_qubits` qubits.
+    pub fn for_qubits(num_qubits: u32) -> Self {
+        Self::new(num_qubits.min(Self::DEFAULT_QUBITS), Self::DEFAULT_SHOTS)
+    }
+
+    /// The two challenge circuits: all qubits measured in `|0⟩`, and all
+    /// qubits flipped to `|1⟩` and measured.
+    pub fn circuits(&self) -> HalResult<(Circuit, Circuit)> {
+        let build = |flip: bool| -> arvak_ir::IrResult<Circuit> {
+            let name = if flip { "puf_ones" } else { "puf_zeros" };
+            let mut circuit = Circuit::with_size(name, self.qubits, self.qubits);
+            if flip {
+                for q in 0..self.qubits {
+                    circuit.x(QubitId(q))?;
+                }
+            }
+            circuit.measure_all()?;
+            Ok(circuit)
+        };
+        let invalid = |e: arvak_ir::IrError| HalError::InvalidCircuit(e.to_string());
+        Ok((
+            build(false).map_err(invalid)?,
+            build(true).map_err(invalid)?,
+        ))
+    }
+}
+
+/// Per-qubit readout bias of a device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceFingerprint {
+    /// Fraction of shots in which a prepared `|0⟩` read as 1, per qubit.
+    pub zero_flip: Vec<f64>,
+    /// Fraction of shots in which a prepared `|1⟩` read as 0, per qubit.
+    pub one_flip: Vec<f64>,
+}
+
+impl DeviceFingerprint {
+    /// Derive the fingerprint of `qubits` qubits from the counts of the two
+    /// [`PufChallenge`] circuits.
+    pub fn from_counts(qubits: u32, zeros: &Counts, ones: &Counts) -> Self {
+        Self {
+            zero_flip: flip_rates(qubits, zeros, '1'),
+            one_flip: flip_rates(qubits, ones, '0'),
+        }
+    }
+
+    /// Run `challenge` on `backend` and derive its fingerprint.
+    pub async fn measure<B: Backend + ?Sized>(
+        backend: &B,
+        challenge: &PufChallenge,
+    ) -> HalResult<Self> {
+        let available = backend.capabilities().num_qubits;
+        if challenge.qubits == 0 || challenge.qubits > available {
+            return Err(HalError::InvalidCircuit(format!(
+                "PUF challenge needs {} qubits, backend '{}' has {available}",
+                challenge.qubits,
+                backend.name()
+            )));
+        }
+        let (zeros, ones) = challenge.circuits()?;
+        let zeros = run_split(backend, &zeros, challenge.shots, None).await?;
+        let ones = run_split(backend, &ones, challenge.shots, None).await?;
+        Ok(Self::from_counts(
+            challenge.qubits,
+            &zeros.counts,
+            &ones.counts,
+        ))
+    }
+
+    /// Number of qubits covered.
+    pub fn num_qubits(&self) -> usize {
+        self.zero_flip.len()
+    }
+
+    /// Root-mean-square difference of the flip rates, or `None` if the
+    /// fingerprints cover different qubits.
+    pub fn distance(&self, other: &Self) -> Option<f64> {
+        if self.zero_flip.len() != other.zero_flip.len()
+            || self.one_flip.len() != other.one_flip.len()
+        {
+            return None;
+        }
+        let diffs: Vec<f64> = self
+            .zero_flip
+            .iter()
+            .zip(&other.zero_flip)
+            .chain(self.one_flip.iter().zip(&other.one_flip))
+            .map(|(a, b)| a - b)
+            .collect();
+        if diffs.is_empty() {
+            return Some(0.0);
+        }
+        Some((diffs.iter().map(|d| d * d).sum::<f64>() / diffs.len() as f64).sqrt())
+    }
+}
+
+/// Fraction of shots in which each qubit read `flipped`.
+fn flip_rates(qubits: u32, counts: &Counts, flipped: char) -> Vec<f64> {
+    let total = counts.total_shots();
+    let mut flips = vec![0_u64; qubits as usize];
+    for (bitstring, &count) in counts.iter() {
+        // Rightmost bit is qubit 0.
+        for (flip, bit) in flips.iter_mut().zip(bitstring.chars().rev()) {
+            if bit == flipped {
+                *flip += count;
+            }
+        }
+    }
+    flips
+        .into_iter()
+        .map(|f| {
+            if total == 0 {
+                0.0
+            } else {
+                f as f64 / total as f64
+            }
+        })
+        .collect()
+}
+
+/// A backend's fingerprint, recorded on hardware known to be genuine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PufEnrollment {
+    /// Backend name.
+    pub backend: String,
+    /// Challenge the fingerprint was measured with.
+    pub challenge: PufChallenge,
+    /// The enrolled fingerprint.
+    pub fingerprint: DeviceFingerprint,
+    /// When the device was enrolled.
+    pub enrolled_at: DateTime<Utc>,
+    /// Largest [`DeviceFingerprint::distance`] still accepted.
+    pub tolerance: f64,
+}
+
+impl PufEnrollment {
+    /// Default tolerance: two percentage points RMS, above the shot noise
+    /// of the default challenge and the drift between calibrations.
+    pub const DEFAULT_TOLERANCE: f64 = 0.02;
+
+    /// Enroll a fingerprint measured with the default challenge.
+    pub fn new(backend: impl Into<String>, fingerprint: DeviceFingerprint) -> Self {
+        let qubits = u32::try_from(fingerprint.num_qubits()).unwrap_or(u32::MAX);
+        Self {
+            backend: backend.into(),
+            challenge: PufChallenge::new(qubits, PufChallenge::DEFAULT_SHOTS),
+            fingerprint,
+            enrolled_at: Utc::now(),
+            tolerance: Self::DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Measure and enroll `backend`'s fingerprint.
+    pub async fn enroll<B: Backend + ?Sized>(
+        backend: &B,
+        challenge: PufChallenge,
+    ) -> HalResult<Self> {
+        let fingerprint = DeviceFingerprint::measure(backend, &challenge).await?;
+        Ok(Self {
+            challenge,
+            ..Self::new(backend.name(), fingerprint)
+        })
+    }
+
+    /// Set the tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Compare an observed fingerprint with the enrolled one.
+    pub fn verify(&self, observed: &DeviceFingerprint) -> Attestation {
+        let distance = self.fingerprint.distance(observed);
+        let status = match distance {
+            Some(d) if d <= self.tolerance => AttestationStatus::Verified,
+            _ => AttestationStatus::Mismatch,
+        };
+        Attestation {
+            backend: self.backend.clone(),
+            status,
+            distance,
+            tolerance: self.tolerance,
+            enrolled_at: self.enrolled_at,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Run the enrolled challenge on `backend` and verify the result.
+    pub async fn attest<B: Backend + ?Sized>(&self, backend: &B) -> HalResult<Attestation> {
+        let observed = DeviceFingerprint::measure(backend, &self.challenge).await?;
+        Ok(self.verify(&observed))
+    }
+}
+
+/// Outcome of an attestation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationStatus {
+    /// The device matches its enrollment.
+    Verified,
+    /// The device does not match its enrollment.
+    Mismatch,
+}
+
+impl fmt::Display for AttestationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Verified => f.write_str("verified"),
+            Self::Mismatch => f.write_str("mismatch"),
+        }
+    }
+}
+
+/// Result of checking a backend against its enrollment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attestation {
+    /// Backend name, as enrolled.
+    pub backend: String,
+    /// The outcome.
+    pub status: AttestationStatus,
+    /// Distance to the enrolled fingerprint; `None` if the fingerprints
+    /// cover different qubits.
+    pub distance: Option<f64>,
+    /// Tolerance of the enrollment.
+    pub tolerance: f64,
+    /// When the device was enrolled.
+    pub enrolled_at: DateTime<Utc>,
+    /// When the attestation was made.
+    pub checked_at: DateTime<Utc>,
+}
+
+impl Attestation {
+    /// Whether the device matches its enrollment.
+    pub fn is_verified(&self) -> bool {
+        self.status == AttestationStatus::Verified
+    }
+
+    /// Record this attestation in `result`'s metadata under
+    /// [`ATTESTATION_METADATA_KEY`], keeping any other metadata.
+    pub fn attach(&self, result: &mut ExecutionResult) {
+        let value = serde_json::to_value(self).expect("attestation serializes");
+        match &mut result.metadata {
+            serde_json::Value::Object(map) => {
+                map.insert(ATTESTATION_METADATA_KEY.into(), value);
+            }
+            metadata => *metadata = serde_json::json!({ ATTESTATION_METADATA_KEY: value }),
+        }
+    }
+
+    /// Read the attestation recorded in `result`'s metadata, if any.
+    pub fn from_result(result: &ExecutionResult) -> Option<Self> {
+        result
+            .metadata
+            .get(ATTESTATION_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+impl fmt::Display for Attestation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.backend, self.status)?;
+        match self.distance {
+            Some(d) => write!(f, " (distance {d:.4}, tolerance {:.4})", self.tolerance),
+            None => f.write_str(" (fingerprint covers different qubits)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::backend::{BackendAvailability, ValidationResult};
+    use crate::capability::Capabilities;
+    use crate::job::{JobId, JobStatus};
+
+    /// Mock whose qubits misread with fixed per-qubit rates.
+    struct BiasedBackend {
+        capabilities: Capabilities,
+        /// Per qubit: (P(read 1 | 0), P(read 0 | 1)).
+        bias: Vec<(f64, f64)>,
+        jobs: std::sync::Mutex<Vec<(bool, u32)>>,
+    }
+
+    impl BiasedBackend {
+        fn new(bias: Vec<(f64, f64)>) -> Self {
+            Self {
+                capabilities: Capabilities::simulator(bias.len() as u32),
+                bias,
+                jobs: std::sync::Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Backend for BiasedBackend {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn name(&self) -> &str {
+            "biased"
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn availability(&self) -> HalResult<BackendAvailability> {
+            Ok(BackendAvailability::always_available())
+        }
+        async fn validate(&self, _c: &Circuit, _shots: u32) -> HalResult<ValidationResult> {
+            Ok(ValidationResult::Valid)
+        }
+        async fn submit(
+            &self,
+            circuit: &Circuit,
+            shots: u32,
+            _parameters: Option<&HashMap<String, f64>>,
+        ) -> HalResult<JobId> {
+            let mut jobs = self.jobs.lock().unwrap();
+            jobs.push((circuit.name() == "puf_ones", shots));
+            Ok(JobId::new(format!("job-{}", jobs.len() - 1)))
+        }
+        async fn status(&self, _id: &JobId) -> HalResult<JobStatus> {
+            Ok(JobStatus::Completed)
+        }
+        async fn result(&self, id: &JobId) -> HalResult<ExecutionResult> {
+            let index: usize = id.0.trim_start_matches("job-").parse().unwrap();
+            let (ones, shots) = self.jobs.lock().unwrap()[index];
+            // Each qubit misreads in its own block of leading shots.
+            let mut counts: HashMap<String, u64> = HashMap::new();
+            for shot in 0..shots {
+                let bits: String = self
+                    .bias
+                    .iter()
+                    .rev()
+                    .map(|&(zero_flip, one_flip)| {
+                        let rate = if ones { one_flip } else { zero_flip };
+                        let flipped = f64::from(shot) < rate * f64::from(shots);
+                        if ones != flipped { '1' } else { '0' }
+                    })
+                    .collect();
+                *counts.entry(bits).or_default() += 1;
+            }
+            Ok(ExecutionResult::new(Counts::from_pairs(counts), shots))
+        }
+        async fn cancel(&self, _id: &JobId) -> HalResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_from_counts() {
+        // "01": qubit 0 reads 1, qubit 1 reads 0.
+        let zeros = Counts::from_pairs([("00".to_string(), 90), ("01".to_string(), 10)]);
+        let ones = Counts::from_pairs([("11".to_string(), 80), ("01".to_string(), 20)]);
+        let fingerprint = DeviceFingerprint::from_counts(2, &zeros, &ones);
+        assert_eq!(fingerprint.zero_flip, vec![0.1, 0.0]);
+        assert_eq!(fingerprint.one_flip, vec![0.0, 0.2]);
+
+        assert_eq!(fingerprint.distance(&fingerprint), Some(0.0));
+        let other = DeviceFingerprint::from_counts(3, &zeros, &ones);
+        assert_eq!(fingerprint.distance(&other), None);
+    }
+
+    #[test]
+    fn test_verify() {
+        let enrolled = DeviceFingerprint {
+            zero_flip: vec![0.01, 0.03],
+            one_flip: vec![0.02, 0.05],
+        };
+        let enrollment = PufEnrollment::new("garnet", enrolled.clone());
+        assert_eq!(enrollment.challenge.qubits, 2);
+
+        let drifted = DeviceFingerprint {
+            zero_flip: vec![0.012, 0.028],
+            one_flip: vec![0.025, 0.045],
+        };
+        assert!(enrollment.verify(&drifted).is_verified());
+
+        // Same average error, different pattern: another chip.
+        let swapped = DeviceFingerprint {
+            zero_flip: vec![0.03, 0.01],
+            one_flip: vec![0.05, 0.02],
+        };
+        let attestation = enrollment.verify(&swapped);
+        assert_eq!(attestation.status, AttestationStatus::Mismatch);
+        assert!(attestation.to_string().contains("mismatch"));
+        assert!(
+            enrollment
+                .clone()
+                .with_tolerance(0.05)
+                .verify(&swapped)
+                .is_verified()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enroll_and_attest() {
+        let genuine = BiasedBackend::new(vec![(0.01, 0.04), (0.05, 0.02), (0.0, 0.08)]);
+        let enrollment = PufEnrollment::enroll(&genuine, PufChallenge::new(3, 1000))
+            .await
+            .unwrap();
+        assert_eq!(enrollment.backend, "biased");
+        assert!((enrollment.fingerprint.zero_flip[1] - 0.05).abs() < 1e-9);
+        assert!((enrollment.fingerprint.one_flip[2] - 0.08).abs() < 1e-9);
+
+        let attestation = enrollment.attest(&genuine).await.unwrap();
+        assert!(attestation.is_verified());
+
+        let impostor = BiasedBackend::new(vec![(0.05, 0.02), (0.01, 0.04), (0.08, 0.0)]);
+        assert!(!enrollment.attest(&impostor).await.unwrap().is_verified());
+
+        let too_small = BiasedBackend::new(vec![(0.0, 0.0)]);
+        assert!(enrollment.attest(&too_small).await.is_err());
+    }
+
+    #[test]
+    fn test_attach_to_result() {
+        let enrollment = PufEnrollment::new(
+            "sim",
+            DeviceFingerprint {
+                zero_flip: vec![0.0],
+                one_flip: vec![0.0],
+            },
+        );
+        let attestation = enrollment.verify(&enrollment.fingerprint);
+        let mut result = ExecutionResult::new(Counts::new(), 10);
+        assert!(Attestation::from_result(&result).is_none());
+
+        attestation.attach(&mut result);
+        assert_eq!(Attestation::from_result(&result), Some(attestation));
+    }
+}
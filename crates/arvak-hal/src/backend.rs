@@ -32,11 +32,20 @@
 //! | `result()` | async | yes | `HalResult<ExecutionResult>` |
 //! | `cancel()` | async | yes | `HalResult<()>` |
 //! | `wait()` | async | provided | `HalResult<ExecutionResult>` |
+//! | `supports_observables()` | sync | provided (Arvak extension) | `bool` |
+//! | `submit_observable(circuit, observables, shots)` | async | provided (Arvak extension) | `HalResult<Vec<f64>>` |
+//! | `validation_report(circuit, shots)` | sync | provided (Arvak extension) | `ValidationReport` |
+//! | `prepare(circuit)` | async | provided (Arvak extension) | `HalResult<Circuit>` |
+//! | `telemetry()` | async | provided (Arvak extension) | `HalResult<Option<TelemetrySample>>` |
+//! | `phase()` | async | provided (Arvak extension) | `HalResult<Option<JobPhase>>` |
+//! | `queue_position()` | async | provided (Arvak extension) | `HalResult<Option<u32>>` |
+//! | `wait_with_heartbeats(job_id, heartbeats)` | async | provided (Arvak extension) | `HalResult<ExecutionResult>` |
 
 use std::fmt;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // HAL Contract v2 §5: Circuit type is implementation-defined.
@@ -45,8 +54,12 @@ use arvak_ir::Circuit;
 
 use crate::capability::Capabilities;
 use crate::error::HalResult;
-use crate::job::{JobId, JobStatus};
+use crate::heartbeat::{Heartbeat, HeartbeatSender, HeartbeatTracker};
+use crate::job::{JobId, JobPhase, JobStatus};
+use crate::negotiation::ValidationReport;
+use crate::observable::{EXPECTATION_VALUES_FEATURE, Observable};
 use crate::result::ExecutionResult;
+use crate::telemetry::TelemetrySample;
 
 /// Arvak extension — not part of HAL Contract v2 spec.
 /// Configuration for a backend instance.
@@ -164,7 +177,8 @@ pub trait Backend: Send + Sync {
     /// concrete float values.  Backends that do not support parametric circuits
     /// MUST return `HalError::Unsupported` when `parameters` is `Some(_)` with
     /// at least one entry.  Backends that do support it bind the values before
-    /// dispatching to hardware.
+    /// dispatching to hardware, e.g. with
+    /// [`negotiation::bind_parameters`](crate::negotiation::bind_parameters).
     ///
     /// Note: the HAL Contract v2.3 spec models parameter binding as a separate
     /// provided method `submit_with_parameters()`; Arvak folds it into
@@ -215,6 +229,146 @@ pub trait Backend: Send + Sync {
 
         Err(HalError::Timeout(job_id.0.clone()))
     }
+
+    /// Whether the backend computes expectation values natively, i.e.
+    /// advertises the `"expectation_values"` capability feature.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    fn supports_observables(&self) -> bool {
+        self.capabilities()
+            .features
+            .iter()
+            .any(|f| f == EXPECTATION_VALUES_FEATURE)
+    }
+
+    /// Compute the expectation value of each observable in the state
+    /// prepared by `circuit`, in the order given.
+    ///
+    /// Backends that advertise the `"expectation_values"` feature MUST
+    /// implement this; `shots` is the sampling budget and MAY be ignored by
+    /// exact backends. The default returns `HalError::Unsupported`. Callers
+    /// normally go through [`crate::observable::estimate`], which falls back
+    /// to sampling.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    async fn submit_observable(
+        &self,
+        circuit: &Circuit,
+        observables: &[Observable],
+        shots: u32,
+    ) -> HalResult<Vec<f64>> {
+        use crate::error::HalError;
+
+        let _ = (circuit, observables, shots);
+        Err(HalError::Unsupported(format!(
+            "backend '{}' does not compute expectation values natively",
+            self.name()
+        )))
+    }
+
+    /// Structured counterpart of [`validate`](Self::validate): every
+    /// violated constraint with what it would take to fix it.
+    ///
+    /// The default checks against [`capabilities`](Self::capabilities).
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    fn validation_report(&self, circuit: &Circuit, shots: u32) -> ValidationReport {
+        ValidationReport::check(circuit, shots, self.capabilities())
+    }
+
+    /// Return `circuit` fixed up to pass validation on this backend:
+    /// unsupported gates decomposed and two-qubit gates routed onto the
+    /// topology. Shots are not considered; split them with
+    /// [`crate::shots::run_split`].
+    ///
+    /// The default compiles with [`crate::negotiation::prepare`] on the
+    /// calling task. Fails with `HalError::InvalidCircuit` when a violation
+    /// cannot be fixed, e.g. too many qubits.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    async fn prepare(&self, circuit: &Circuit) -> HalResult<Circuit> {
+        crate::negotiation::prepare(circuit, self.capabilities())
+    }
+
+    /// The latest cooling telemetry reading, for backends with access to
+    /// cryostat data. Called periodically by monitors such as the
+    /// dashboard; implementations SHOULD return a cached reading rather
+    /// than block on the device.
+    ///
+    /// The default returns `Ok(None)`: no telemetry available.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    async fn telemetry(&self) -> HalResult<Option<TelemetrySample>> {
+        Ok(None)
+    }
+
+    /// Fine-grained phase of an unfinished job, or `None` once it has
+    /// reached a terminal status. Backends whose provider reports
+    /// validation, compilation, upload or calibration separately SHOULD
+    /// override this; the returned phase MUST map onto the job's
+    /// [`status`](Self::status).
+    ///
+    /// The default derives the coarse phase from `status()`.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    async fn phase(&self, job_id: &JobId) -> HalResult<Option<JobPhase>> {
+        Ok(JobPhase::from_status(&self.status(job_id).await?))
+    }
+
+    /// Position of a queued job in the provider's queue, counting from 1,
+    /// or `None` if the provider does not say.
+    ///
+    /// The default returns `Ok(None)`.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    async fn queue_position(&self, job_id: &JobId) -> HalResult<Option<u32>> {
+        let _ = job_id;
+        Ok(None)
+    }
+
+    /// Like [`wait`](Self::wait), but publish a [`Heartbeat`] on
+    /// `heartbeats` whenever the job is queued, moves in the queue,
+    /// changes phase or starts, and at least once per heartbeat interval
+    /// until it finishes.
+    ///
+    /// The default runs `wait()` while polling [`phase`](Self::phase) and
+    /// [`queue_position`](Self::queue_position) at the sender's poll
+    /// interval. Backends whose provider pushes job events MAY override it
+    /// and feed a [`HeartbeatTracker`] themselves.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    async fn wait_with_heartbeats(
+        &self,
+        job_id: &JobId,
+        heartbeats: &HeartbeatSender,
+    ) -> HalResult<ExecutionResult> {
+        let wait = self.wait(job_id);
+        tokio::pin!(wait);
+        let mut poll = tokio::time::interval(heartbeats.poll_interval());
+        let mut tracker = HeartbeatTracker::new(heartbeats.interval());
+
+        loop {
+            let phase = tokio::select! {
+                result = &mut wait => return result,
+                _ = poll.tick() => self.phase(job_id).await,
+            };
+            let Ok(Some(phase)) = phase else { continue };
+            let queue_position = self.queue_position(job_id).await.ok().flatten();
+
+            let now = tokio::time::Instant::now();
+            if let Some(kind) = tracker.observe(phase, queue_position, now) {
+                heartbeats.send(Heartbeat {
+                    job_id: job_id.clone(),
+                    backend: self.name().to_string(),
+                    kind,
+                    phase,
+                    queue_position,
+                    elapsed_secs: tracker.elapsed(now).as_secs(),
+                    at: Utc::now(),
+                });
+            }
+        }
+    }
 }
 
 /// Backend availability information.
@@ -232,6 +386,9 @@ pub struct BackendAvailability {
     pub estimated_wait: Option<Duration>,
     /// Human-readable status message.
     pub status_message: Option<String>,
+    /// Scheduled maintenance windows (if known), from the provider API or
+    /// a site maintenance calendar.
+    pub maintenance_windows: Vec<MaintenanceWindow>,
 }
 
 impl BackendAvailability {
@@ -244,6 +401,7 @@ impl BackendAvailability {
             queue_depth: Some(0),
             estimated_wait: Some(Duration::ZERO),
             status_message: None,
+            maintenance_windows: Vec::new(),
         }
     }
 
@@ -254,7 +412,98 @@ impl BackendAvailability {
             queue_depth: None,
             estimated_wait: None,
             status_message: Some(reason.into()),
+            maintenance_windows: Vec::new(),
+        }
+    }
+
+    /// Add scheduled maintenance windows.
+    #[must_use]
+    pub fn with_maintenance_windows(
+        mut self,
+        windows: impl IntoIterator<Item = MaintenanceWindow>,
+    ) -> Self {
+        for window in windows {
+            if !self.maintenance_windows.contains(&window) {
+                self.maintenance_windows.push(window);
+            }
+        }
+        self.maintenance_windows.sort_by_key(|w| w.start);
+        self
+    }
+
+    /// The maintenance window in effect at `time`, if any.
+    pub fn maintenance_at(&self, time: DateTime<Utc>) -> Option<&MaintenanceWindow> {
+        self.maintenance_windows.iter().find(|w| w.contains(time))
+    }
+
+    /// The first maintenance window that overlaps a job starting at `start`
+    /// and running for `duration`, if any.
+    pub fn maintenance_conflict(
+        &self,
+        start: DateTime<Utc>,
+        duration: Duration,
+    ) -> Option<&MaintenanceWindow> {
+        let end = start + chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX);
+        self.maintenance_windows
+            .iter()
+            .find(|w| w.overlaps(start, end))
+    }
+}
+
+/// A scheduled period during which a backend does not run jobs.
+///
+/// Arvak extension — not part of HAL Contract v2 spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// Start of the window.
+    pub start: DateTime<Utc>,
+    /// End of the window (exclusive).
+    pub end: DateTime<Utc>,
+    /// Reason for the maintenance, e.g. "recalibration".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    /// Create a maintenance window from `start` to `end`.
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            end,
+            reason: None,
+        }
+    }
+
+    /// Set the reason for the maintenance.
+    #[must_use]
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Whether `time` falls inside the window.
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start <= time && time < self.end
+    }
+
+    /// Whether the window overlaps the interval `[start, end)`.
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.start < end && start < self.end
+    }
+}
+
+impl fmt::Display for MaintenanceWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} – {}",
+            self.start.format("%Y-%m-%d %H:%M UTC"),
+            self.end.format("%Y-%m-%d %H:%M UTC")
+        )?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({reason})")?;
         }
+        Ok(())
     }
 }
 
@@ -303,6 +552,37 @@ mod tests {
         assert_eq!(avail.status_message, Some("maintenance".to_string()));
     }
 
+    #[test]
+    fn test_maintenance_conflict() {
+        let t0 = DateTime::parse_from_rfc3339("2026-11-02T06:00:00Z")
+            .unwrap()
+            .to_utc();
+        let window = MaintenanceWindow::new(t0, t0 + chrono::Duration::hours(8))
+            .with_reason("cryostat warm-up");
+        let avail = BackendAvailability::always_available()
+            .with_maintenance_windows([window.clone(), window.clone()]);
+        assert_eq!(avail.maintenance_windows.len(), 1);
+
+        let hour = Duration::from_secs(3600);
+        let before = t0 - chrono::Duration::hours(2);
+        assert!(avail.maintenance_conflict(before, hour).is_none());
+        assert_eq!(avail.maintenance_conflict(before, 3 * hour), Some(&window));
+        assert!(
+            avail
+                .maintenance_at(t0 + chrono::Duration::hours(1))
+                .is_some()
+        );
+        assert!(
+            avail
+                .maintenance_at(t0 + chrono::Duration::hours(8))
+                .is_none()
+        );
+        assert_eq!(
+            window.to_string(),
+            "2026-11-02 06:00 UTC – 2026-11-02 14:00 UTC (cryostat warm-up)"
+        );
+    }
+
     #[test]
     fn test_validation_result_is_valid() {
         assert!(ValidationResult::Valid.is_valid());
@@ -354,6 +634,97 @@ mod tests {
         }
     }
 
+    /// Mock whose job waits in the queue for 20s, moving from position 3
+    /// to 2 after 10s, then runs for 10s.
+    struct QueueingBackend {
+        capabilities: Capabilities,
+        created: tokio::time::Instant,
+    }
+
+    impl QueueingBackend {
+        fn elapsed_secs(&self) -> u64 {
+            self.created.elapsed().as_secs()
+        }
+    }
+
+    #[async_trait]
+    impl Backend for QueueingBackend {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn name(&self) -> &str {
+            "queueing"
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn availability(&self) -> HalResult<BackendAvailability> {
+            Ok(BackendAvailability::always_available())
+        }
+        async fn validate(&self, _c: &Circuit, _shots: u32) -> HalResult<ValidationResult> {
+            Ok(ValidationResult::Valid)
+        }
+        async fn submit(
+            &self,
+            _c: &Circuit,
+            _shots: u32,
+            _parameters: Option<&std::collections::HashMap<String, f64>>,
+        ) -> HalResult<JobId> {
+            Ok(JobId::new("queued-job"))
+        }
+        async fn status(&self, _id: &JobId) -> HalResult<JobStatus> {
+            Ok(match self.elapsed_secs() {
+                0..20 => JobStatus::Queued,
+                20..30 => JobStatus::Running,
+                _ => JobStatus::Completed,
+            })
+        }
+        async fn result(&self, _id: &JobId) -> HalResult<ExecutionResult> {
+            Ok(ExecutionResult::new(crate::result::Counts::new(), 0))
+        }
+        async fn cancel(&self, _id: &JobId) -> HalResult<()> {
+            Ok(())
+        }
+        async fn queue_position(&self, _id: &JobId) -> HalResult<Option<u32>> {
+            Ok(match self.elapsed_secs() {
+                0..10 => Some(3),
+                10..20 => Some(2),
+                _ => None,
+            })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_with_heartbeats() {
+        use crate::heartbeat::HeartbeatKind;
+
+        let backend = QueueingBackend {
+            capabilities: Capabilities::simulator(2),
+            created: tokio::time::Instant::now(),
+        };
+        let heartbeats = HeartbeatSender::new().with_interval(Duration::from_secs(60));
+        let mut rx = heartbeats.subscribe();
+
+        backend
+            .wait_with_heartbeats(&JobId::new("queued-job"), &heartbeats)
+            .await
+            .unwrap();
+
+        let mut received = Vec::new();
+        while let Ok(heartbeat) = rx.try_recv() {
+            received.push((heartbeat.kind, heartbeat.queue_position));
+        }
+        assert_eq!(
+            received,
+            vec![
+                (HeartbeatKind::Queued, Some(3)),
+                (
+                    HeartbeatKind::PositionChanged { previous: Some(3) },
+                    Some(2)
+                ),
+                (HeartbeatKind::Started, None),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_wait_surfaces_result_expired() {
         let backend = ExpiredBackend {
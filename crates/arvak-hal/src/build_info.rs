@@ -0,0 +1,180 @@
+//! What a build of Arvak was compiled with.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Cargo features decide which adapters, storage backends and test hooks end
+//! up in a binary, and they are chosen per crate. [`BuildInfo`] collects them
+//! in one place: each crate adds its own [`ComponentBuild`] on top of
+//! [`BuildInfo::current`], and binaries report the result from
+//! `arvak version --features`, the gRPC `GetBackendInfo` call and `/health`.
+//!
+//! ```rust
+//! use arvak_hal::BuildInfo;
+//!
+//! let info = BuildInfo::current()
+//!     .with_component("arvak-demo", "0.1.0", [("fast", true), ("slow", false)])
+//!     .with_adapter("simulator")
+//!     .with_storage_backend("memory");
+//!
+//! assert!(info.has_feature("arvak-demo/fast"));
+//! assert!(!info.has_feature("arvak-demo/slow"));
+//! assert_eq!(info.adapters, vec!["simulator"]);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Features one crate was compiled with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentBuild {
+    /// Crate name, e.g. `arvak-grpc`.
+    pub name: String,
+    /// Crate version.
+    pub version: String,
+    /// Enabled cargo features, sorted.
+    pub features: Vec<String>,
+}
+
+/// Features, adapters and storage backends compiled into a binary.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Short git commit the workspace was built from, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    /// One entry per crate that reported itself.
+    #[serde(default)]
+    pub components: Vec<ComponentBuild>,
+    /// Backend adapters linked into the binary.
+    #[serde(default)]
+    pub adapters: Vec<String>,
+    /// Job or state storage backends available to the binary.
+    #[serde(default)]
+    pub storage_backends: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Build info for this workspace, starting with `arvak-hal` itself.
+    ///
+    /// The git commit comes from `ARVAK_GIT_COMMIT` at build time, or from
+    /// `git rev-parse` when building inside a checkout.
+    pub fn current() -> Self {
+        Self {
+            git_commit: option_env!("ARVAK_GIT_COMMIT").map(str::to_string),
+            ..Self::default()
+        }
+        .with_component(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            [
+                ("chaos", cfg!(feature = "chaos")),
+                ("dynamic-backends", cfg!(feature = "dynamic-backends")),
+            ],
+        )
+    }
+
+    /// Add a crate, keeping only the features that are enabled.
+    ///
+    /// Reporting the same crate twice replaces the earlier entry.
+    #[must_use]
+    pub fn with_component<'a>(
+        mut self,
+        name: &str,
+        version: &str,
+        features: impl IntoIterator<Item = (&'a str, bool)>,
+    ) -> Self {
+        let mut features: Vec<String> = features
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(feature, _)| feature.to_string())
+            .collect();
+        features.sort();
+        self.components.retain(|c| c.name != name);
+        self.components.push(ComponentBuild {
+            name: name.to_string(),
+            version: version.to_string(),
+            features,
+        });
+        self
+    }
+
+    /// Add a compiled-in backend adapter.
+    #[must_use]
+    pub fn with_adapter(mut self, adapter: &str) -> Self {
+        push_unique(&mut self.adapters, adapter);
+        self
+    }
+
+    /// Add an available storage backend.
+    #[must_use]
+    pub fn with_storage_backend(mut self, backend: &str) -> Self {
+        push_unique(&mut self.storage_backends, backend);
+        self
+    }
+
+    /// All enabled features as `crate/feature`.
+    pub fn features(&self) -> Vec<String> {
+        self.components
+            .iter()
+            .flat_map(|c| c.features.iter().map(move |f| format!("{}/{f}", c.name)))
+            .collect()
+    }
+
+    /// Whether `feature`, written as `crate/feature`, is enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        feature.split_once('/').is_some_and(|(name, feature)| {
+            self.components
+                .iter()
+                .any(|c| c.name == name && c.features.iter().any(|f| f == feature))
+        })
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, item: &str) {
+    if !list.iter().any(|existing| existing == item) {
+        list.push(item.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_reports_hal() {
+        let info = BuildInfo::current();
+        let hal = &info.components[0];
+        assert_eq!(hal.name, "arvak-hal");
+        assert_eq!(hal.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.has_feature("arvak-hal/chaos"), cfg!(feature = "chaos"));
+    }
+
+    #[test]
+    fn test_components_adapters_and_storage() {
+        let info = BuildInfo::default()
+            .with_component("arvak-grpc", "1.0.0", [("sqlite", true), ("ibm", true)])
+            .with_component("arvak-sched", "1.0.0", [("chaos", false)])
+            .with_adapter("simulator")
+            .with_adapter("simulator")
+            .with_storage_backend("memory")
+            .with_storage_backend("sqlite");
+
+        assert_eq!(info.features(), vec!["arvak-grpc/ibm", "arvak-grpc/sqlite"]);
+        assert!(!info.has_feature("arvak-sched/chaos"));
+        assert!(!info.has_feature("sqlite"));
+        assert_eq!(info.adapters, vec!["simulator"]);
+        assert_eq!(info.storage_backends, vec!["memory", "sqlite"]);
+
+        let replaced = info.with_component("arvak-grpc", "1.0.0", [("sqlite", false)]);
+        assert_eq!(replaced.components.len(), 2);
+        assert!(!replaced.has_feature("arvak-grpc/sqlite"));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let info = BuildInfo::current().with_adapter("simulator");
+        let json = serde_json::to_string(&info).unwrap();
+        let back: BuildInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, info);
+        let empty: BuildInfo = serde_json::from_str("{}").unwrap();
+        assert_eq!(empty, BuildInfo::default());
+    }
+}
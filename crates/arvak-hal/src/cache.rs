@@ -0,0 +1,350 @@
+//! Response cache for provider metadata.
+//!
+//! Cloud adapters describe their device (qubit count, coupling map, status)
+//! from provider API responses. Fetching these on every backend construction
+//! is slow and counts against provider rate limits, so adapters share a
+//! [`ResponseCache`]: an in-memory map backed by JSON files on disk, with a
+//! freshness TTL.
+//!
+//! Entries older than the TTL are not discarded. When the provider cannot be
+//! reached, [`ResponseCache::get_or_snapshot`] falls back to the last stored
+//! response, so a backend can still be constructed offline from the most
+//! recent known device description.
+//!
+//! ```rust
+//! use arvak_hal::ResponseCache;
+//!
+//! let cache = ResponseCache::in_memory();
+//! cache.insert("example/qubits", &20u32).unwrap();
+//! assert_eq!(cache.get::<u32>("example/qubits"), Some(20));
+//! ```
+
+use std::fmt::Display;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{HalError, HalResult};
+
+/// How long a cached response counts as fresh.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// A stored response with the time it was fetched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    value: serde_json::Value,
+    fetched_at: DateTime<Utc>,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        let age = Utc::now().signed_duration_since(self.fetched_at);
+        age.to_std().map_or(true, |age| age < ttl)
+    }
+}
+
+/// Shared cache for provider API responses (memory + disk, with TTL).
+///
+/// Keys are free-form strings; adapters use `"<provider>/<endpoint>/<name>"`
+/// so that entries from different providers and accounts never collide.
+/// Values are stored as JSON, so any `Serialize + DeserializeOwned` type can
+/// be cached.
+#[derive(Debug)]
+pub struct ResponseCache {
+    /// Directory for the on-disk snapshot; `None` keeps entries in memory only.
+    dir: Option<PathBuf>,
+    /// Freshness window for [`get`](Self::get) and [`get_or_fetch`](Self::get_or_fetch).
+    ttl: Duration,
+    entries: Mutex<FxHashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    /// A cache that lives only as long as this value.
+    pub fn in_memory() -> Self {
+        Self {
+            dir: None,
+            ttl: DEFAULT_TTL,
+            entries: Mutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// A cache persisted as one JSON file per key under `dir`.
+    pub fn on_disk(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+            ..Self::in_memory()
+        }
+    }
+
+    /// The process-wide cache used by the cloud adapters.
+    ///
+    /// Persisted under `<cache dir>/arvak/responses` (e.g.
+    /// `~/.cache/arvak/responses` on Linux), or in memory only if the
+    /// platform has no cache directory.
+    pub fn global() -> Arc<Self> {
+        static GLOBAL: OnceLock<Arc<ResponseCache>> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| {
+                Arc::new(match dirs::cache_dir() {
+                    Some(dir) => Self::on_disk(dir.join("arvak/responses")),
+                    None => Self::in_memory(),
+                })
+            })
+            .clone()
+    }
+
+    /// Set how long entries count as fresh.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The freshness window.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The on-disk directory, if any.
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// The cached value for `key`, if it is younger than the TTL.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let entry = self.entry(key)?;
+        if !entry.is_fresh(self.ttl) {
+            return None;
+        }
+        serde_json::from_value(entry.value).ok()
+    }
+
+    /// The last stored value for `key` regardless of age, with the time it
+    /// was fetched.
+    pub fn snapshot<T: DeserializeOwned>(&self, key: &str) -> Option<(T, DateTime<Utc>)> {
+        let entry = self.entry(key)?;
+        let value = serde_json::from_value(entry.value).ok()?;
+        Some((value, entry.fetched_at))
+    }
+
+    /// Store `value` under `key`, fetched now.
+    pub fn insert<T: Serialize>(&self, key: &str, value: &T) -> HalResult<()> {
+        let entry = CacheEntry {
+            value: serde_json::to_value(value)?,
+            fetched_at: Utc::now(),
+        };
+        if let Some(path) = self.path(key) {
+            // A failed disk write only costs an extra fetch next time.
+            if let Err(e) = write_entry(&path, &entry) {
+                tracing::warn!(key, path = %path.display(), "failed to persist cached response: {e}");
+            }
+        }
+        self.lock().insert(key.to_string(), entry);
+        Ok(())
+    }
+
+    /// Drop the entry for `key` from memory and disk.
+    pub fn invalidate(&self, key: &str) -> HalResult<()> {
+        self.lock().remove(key);
+        if let Some(path) = self.path(key) {
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| {
+                    HalError::Configuration(format!("Failed to remove cached response: {e}"))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop every entry from memory and disk.
+    pub fn clear(&self) -> HalResult<()> {
+        self.lock().clear();
+        if let Some(ref dir) = self.dir {
+            if dir.exists() {
+                std::fs::remove_dir_all(dir).map_err(|e| {
+                    HalError::Configuration(format!("Failed to clear response cache: {e}"))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the fresh cached value, or fetch and store a new one.
+    ///
+    /// Fetch errors are returned as-is. Use this for data that must be
+    /// current, such as operational status.
+    pub async fn get_or_fetch<T, E, F, Fut>(&self, key: &str, fetch: F) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.get(key) {
+            return Ok(value);
+        }
+        self.refresh(key, fetch).await
+    }
+
+    /// Like [`get_or_fetch`](Self::get_or_fetch), but if the fetch fails,
+    /// fall back to the last stored value regardless of its age.
+    ///
+    /// The error is returned only when no snapshot exists. Use this for
+    /// device descriptions that rarely change, so backends can be
+    /// constructed while the provider is unreachable.
+    pub async fn get_or_snapshot<T, E, F, Fut>(&self, key: &str, fetch: F) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        E: Display,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        match self.get_or_fetch(key, fetch).await {
+            Ok(value) => Ok(value),
+            Err(e) => match self.snapshot(key) {
+                Some((value, fetched_at)) => {
+                    tracing::warn!(
+                        key,
+                        %fetched_at,
+                        "fetch failed, using cached snapshot: {e}"
+                    );
+                    Ok(value)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Fetch a new value and store it, ignoring any cached entry.
+    pub async fn refresh<T, E, F, Fut>(&self, key: &str, fetch: F) -> Result<T, E>
+    where
+        T: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let value = fetch().await?;
+        if let Err(e) = self.insert(key, &value) {
+            tracing::warn!(key, "failed to cache response: {e}");
+        }
+        Ok(value)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FxHashMap<String, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The entry for `key` from memory, loading it from disk on a miss.
+    fn entry(&self, key: &str) -> Option<CacheEntry> {
+        if let Some(entry) = self.lock().get(key) {
+            return Some(entry.clone());
+        }
+        let path = self.path(key)?;
+        let content = std::fs::read_to_string(path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        self.lock().insert(key.to_string(), entry.clone());
+        Some(entry)
+    }
+
+    /// File for `key`: every character outside `[A-Za-z0-9._-]` becomes `_`.
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Some(self.dir.as_ref()?.join(format!("{name}.json")))
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+fn write_entry(path: &Path, entry: &CacheEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(entry)?;
+    // Write to a sibling file and rename, so readers never see a partial entry.
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "arvak-response-cache-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_caches_until_ttl() {
+        let cache = ResponseCache::in_memory();
+        let first: Result<u32, String> = cache.get_or_fetch("k", || async { Ok(1) }).await;
+        let second: Result<u32, String> = cache.get_or_fetch("k", || async { Ok(2) }).await;
+        assert_eq!(first, Ok(1));
+        assert_eq!(second, Ok(1));
+
+        let refreshed: Result<u32, String> = cache.refresh("k", || async { Ok(3) }).await;
+        assert_eq!(refreshed, Ok(3));
+        assert_eq!(cache.get::<u32>("k"), Some(3));
+
+        let expired = ResponseCache::in_memory().with_ttl(Duration::ZERO);
+        expired.insert("k", &1u32).unwrap();
+        assert_eq!(expired.get::<u32>("k"), None);
+        assert_eq!(expired.snapshot::<u32>("k").map(|(v, _)| v), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_fallback_when_fetch_fails() {
+        let cache = ResponseCache::in_memory().with_ttl(Duration::ZERO);
+        let offline = || async { Err::<u32, _>("provider unreachable".to_string()) };
+
+        assert!(cache.get_or_snapshot("k", offline).await.is_err());
+
+        cache.insert("k", &7u32).unwrap();
+        assert_eq!(cache.get_or_snapshot("k", offline).await, Ok(7));
+        // Strict lookups and explicit refreshes never serve stale data.
+        assert!(cache.get_or_fetch("k", offline).await.is_err());
+        assert!(cache.refresh("k", offline).await.is_err());
+    }
+
+    #[test]
+    fn test_entries_persist_on_disk() {
+        let dir = temp_dir("persist");
+        let key = "ibm/https://example.com/ibm_torino";
+
+        ResponseCache::on_disk(&dir)
+            .insert(key, &vec![1u32, 2, 3])
+            .unwrap();
+        let reopened = ResponseCache::on_disk(&dir);
+        assert_eq!(reopened.get::<Vec<u32>>(key), Some(vec![1, 2, 3]));
+        assert!(std::fs::read_dir(&dir).unwrap().count() == 1);
+
+        reopened.invalidate(key).unwrap();
+        assert_eq!(ResponseCache::on_disk(&dir).get::<Vec<u32>>(key), None);
+
+        reopened.insert(key, &0u32).unwrap();
+        reopened.clear().unwrap();
+        assert!(!dir.exists());
+    }
+}
@@ -18,6 +18,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::decoherence::DecoherenceMonitor;
+
 // ── Re-exported from HAL Contract spec ──────────────────────────────────────
 pub use hal_contract::capability::{NoiseProfile, Topology, TopologyKind};
 
@@ -30,7 +32,7 @@ pub use hal_contract::capability::{NoiseProfile, Topology, TopologyKind};
 ///
 /// # HAL Contract v2
 ///
-/// All fields are defined by the spec (§4.1, v2.1+).
+/// All fields except `decoherence` are defined by the spec (§4.1, v2.1+).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Capabilities {
     /// Name of the backend.
@@ -58,6 +60,12 @@ pub struct Capabilities {
     /// Device-wide noise averages (gate layer, visible to QEC).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub noise_profile: Option<NoiseProfile>,
+    /// Recent T1/T2 samples and quiet windows, for coherence budget
+    /// checks during validation.
+    ///
+    /// Arvak extension — not part of HAL Contract v2 spec.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoherence: Option<DecoherenceMonitor>,
 }
 
 impl Capabilities {
@@ -73,6 +81,7 @@ impl Capabilities {
             is_simulator: true,
             features: vec!["statevector".into(), "unitary".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -88,6 +97,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec![],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -118,6 +128,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec!["dynamic_circuits".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -136,6 +147,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec!["ion_trap".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -155,6 +167,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec!["ion_trap".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -172,6 +185,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec!["ion_trap".into(), "mid_circuit_measurement".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -190,6 +204,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec!["photonic".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -205,6 +220,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec!["shuttling".into(), "zoned".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -223,6 +239,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec![],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -238,6 +255,7 @@ impl Capabilities {
             is_simulator: false,
             features: vec![],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -253,6 +271,7 @@ impl Capabilities {
             is_simulator: true,
             features: vec!["braket_simulator".into()],
             noise_profile: None,
+            decoherence: None,
         }
     }
 
@@ -267,6 +286,12 @@ impl Capabilities {
         self.noise_profile = Some(profile);
         self
     }
+
+    /// Attach a decoherence monitor to these capabilities.
+    pub fn with_decoherence_monitor(mut self, monitor: DecoherenceMonitor) -> Self {
+        self.decoherence = Some(monitor);
+        self
+    }
 }
 
 /// Gate set supported by a backend.
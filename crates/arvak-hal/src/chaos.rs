@@ -0,0 +1,497 @@
+//! Fault injection for testing recovery under flaky infrastructure.
+//!
+//! HPC quantum deployments fail in mundane ways: a provider API returns
+//! 503 for a minute, a shared filesystem stalls, a status poll is lost.
+//! A [`FaultScenario`] describes such failures and their rates; a
+//! [`FaultInjector`] draws from it reproducibly (seeded), and wrappers at
+//! each seam inject the faults it decides on. [`ChaosBackend`] wraps a
+//! [`Backend`]; the gRPC server and the scheduler wrap their job stores.
+//!
+//! This module requires `--features chaos` and is meant for tests and
+//! staging; never enable it in production builds.
+//!
+//! A scenario file (TOML, or JSON for `.json` files):
+//!
+//! ```toml
+//! seed = 7
+//!
+//! [backend_errors]
+//! probability = 0.2
+//! operations = ["submit", "status"]
+//! backends = ["iqm"]
+//!
+//! [storage_delay]
+//! probability = 0.5
+//! millis = 200
+//!
+//! [dropped_status_updates]
+//! probability = 0.1
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use arvak_ir::{Circuit, SplitMix64};
+
+use crate::backend::{Backend, BackendAvailability, ValidationResult};
+use crate::capability::Capabilities;
+use crate::error::{HalError, HalResult};
+use crate::job::{JobId, JobPhase, JobStatus};
+use crate::negotiation::ValidationReport;
+use crate::observable::Observable;
+use crate::result::ExecutionResult;
+use crate::telemetry::TelemetrySample;
+
+/// Backend calls that can be made to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendOperation {
+    /// [`Backend::submit`].
+    Submit,
+    /// [`Backend::status`].
+    Status,
+    /// [`Backend::result`].
+    Result,
+    /// [`Backend::cancel`].
+    Cancel,
+}
+
+/// Random errors returned by backend calls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendFaults {
+    /// Probability that an affected call fails.
+    pub probability: f64,
+    /// Calls that can fail; all when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operations: Vec<BackendOperation>,
+    /// Backends that are affected; all when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<String>,
+    /// Fail with the transient `BackendUnavailable` (default), or with a
+    /// permanent `Backend` error.
+    #[serde(default = "default_transient")]
+    pub transient: bool,
+}
+
+fn default_transient() -> bool {
+    true
+}
+
+/// Delays before job store writes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageDelay {
+    /// Probability that a write is delayed.
+    pub probability: f64,
+    /// Length of the delay in milliseconds.
+    pub millis: u64,
+}
+
+/// Status updates that never reach the job store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DroppedStatusUpdates {
+    /// Probability that a status update is dropped.
+    pub probability: f64,
+}
+
+/// The faults to inject and how often.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FaultScenario {
+    /// Seed for fault decisions; the same seed and call sequence inject the
+    /// same faults.
+    #[serde(default)]
+    pub seed: u64,
+    /// Random backend errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_errors: Option<BackendFaults>,
+    /// Delayed job store writes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_delay: Option<StorageDelay>,
+    /// Dropped job status updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropped_status_updates: Option<DroppedStatusUpdates>,
+}
+
+impl FaultScenario {
+    /// Parse a TOML document.
+    pub fn from_toml_str(source: &str) -> HalResult<Self> {
+        let scenario: Self = toml::from_str(source)
+            .map_err(|e| HalError::Configuration(format!("Invalid fault scenario: {e}")))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Parse a JSON document.
+    pub fn from_json_str(source: &str) -> HalResult<Self> {
+        let scenario: Self = serde_json::from_str(source)
+            .map_err(|e| HalError::Configuration(format!("Invalid fault scenario: {e}")))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Load a scenario file: JSON for `.json` files, TOML otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> HalResult<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            HalError::Configuration(format!(
+                "Failed to read fault scenario {}: {e}",
+                path.display()
+            ))
+        })?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json_str(&source)
+        } else {
+            Self::from_toml_str(&source)
+        }
+    }
+
+    fn validate(&self) -> HalResult<()> {
+        let probabilities = [
+            self.backend_errors.as_ref().map(|f| f.probability),
+            self.storage_delay.as_ref().map(|f| f.probability),
+            self.dropped_status_updates.as_ref().map(|f| f.probability),
+        ];
+        if let Some(p) = probabilities
+            .into_iter()
+            .flatten()
+            .find(|p| !(0.0..=1.0).contains(p))
+        {
+            return Err(HalError::Configuration(format!(
+                "Fault probability {p} is outside [0, 1]"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Number of faults injected so far, by kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaultCounts {
+    /// Backend calls that were failed.
+    pub backend_errors: u64,
+    /// Job store writes that were delayed.
+    pub storage_delays: u64,
+    /// Status updates that were dropped.
+    pub dropped_status_updates: u64,
+}
+
+/// Decides, call by call, which faults of a [`FaultScenario`] to inject.
+///
+/// Shared between wrappers through an `Arc`; decisions come from one
+/// seeded stream, so a single-threaded test sees the same faults on
+/// every run.
+#[derive(Debug)]
+pub struct FaultInjector {
+    scenario: FaultScenario,
+    rng: Mutex<SplitMix64>,
+    backend_errors: AtomicU64,
+    storage_delays: AtomicU64,
+    dropped_status_updates: AtomicU64,
+}
+
+impl FaultInjector {
+    /// Create an injector for `scenario`.
+    pub fn new(scenario: FaultScenario) -> Self {
+        Self {
+            rng: Mutex::new(SplitMix64::new(scenario.seed)),
+            scenario,
+            backend_errors: AtomicU64::new(0),
+            storage_delays: AtomicU64::new(0),
+            dropped_status_updates: AtomicU64::new(0),
+        }
+    }
+
+    /// Create an injector from a scenario file; see
+    /// [`FaultScenario::from_file`].
+    pub fn from_file(path: impl AsRef<Path>) -> HalResult<Self> {
+        FaultScenario::from_file(path).map(Self::new)
+    }
+
+    /// The scenario being injected.
+    pub fn scenario(&self) -> &FaultScenario {
+        &self.scenario
+    }
+
+    /// The error to fail `operation` on `backend` with, if it should fail.
+    pub fn backend_error(&self, backend: &str, operation: BackendOperation) -> Option<HalError> {
+        let faults = self.scenario.backend_errors.as_ref()?;
+        let affected = (faults.operations.is_empty() || faults.operations.contains(&operation))
+            && (faults.backends.is_empty() || faults.backends.iter().any(|b| b == backend));
+        if !affected || !self.chance(faults.probability) {
+            return None;
+        }
+        self.backend_errors.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("chaos: failing {operation:?} on backend '{backend}'");
+        let message = format!("injected fault in {operation:?}");
+        Some(if faults.transient {
+            HalError::BackendUnavailable(message)
+        } else {
+            HalError::Backend(message)
+        })
+    }
+
+    /// How long to stall the next job store write, if at all.
+    pub fn storage_delay(&self) -> Option<Duration> {
+        let delay = self.scenario.storage_delay.as_ref()?;
+        if !self.chance(delay.probability) {
+            return None;
+        }
+        self.storage_delays.fetch_add(1, Ordering::Relaxed);
+        Some(Duration::from_millis(delay.millis))
+    }
+
+    /// Whether to silently drop the next status update.
+    pub fn drop_status_update(&self) -> bool {
+        let Some(dropped) = &self.scenario.dropped_status_updates else {
+            return false;
+        };
+        if !self.chance(dropped.probability) {
+            return false;
+        }
+        self.dropped_status_updates.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("chaos: dropping status update");
+        true
+    }
+
+    /// Faults injected so far.
+    pub fn counts(&self) -> FaultCounts {
+        FaultCounts {
+            backend_errors: self.backend_errors.load(Ordering::Relaxed),
+            storage_delays: self.storage_delays.load(Ordering::Relaxed),
+            dropped_status_updates: self.dropped_status_updates.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Draw `true` with probability `p`.
+    fn chance(&self, p: f64) -> bool {
+        if p <= 0.0 {
+            return false;
+        }
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        rng.unit() < p
+    }
+}
+
+/// A [`Backend`] whose calls fail as a [`FaultInjector`] decides.
+///
+/// Everything else is passed through to the wrapped backend.
+pub struct ChaosBackend {
+    inner: Arc<dyn Backend>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosBackend {
+    /// Wrap `inner`, injecting faults from `injector`.
+    pub fn new(inner: Arc<dyn Backend>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+
+    fn fault(&self, operation: BackendOperation) -> HalResult<()> {
+        match self.injector.backend_error(self.inner.name(), operation) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for ChaosBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> &Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn availability(&self) -> HalResult<BackendAvailability> {
+        self.inner.availability().await
+    }
+
+    async fn validate(&self, circuit: &Circuit, shots: u32) -> HalResult<ValidationResult> {
+        self.inner.validate(circuit, shots).await
+    }
+
+    async fn submit(
+        &self,
+        circuit: &Circuit,
+        shots: u32,
+        parameters: Option<&HashMap<String, f64>>,
+    ) -> HalResult<JobId> {
+        self.fault(BackendOperation::Submit)?;
+        self.inner.submit(circuit, shots, parameters).await
+    }
+
+    async fn status(&self, job_id: &JobId) -> HalResult<JobStatus> {
+        self.fault(BackendOperation::Status)?;
+        self.inner.status(job_id).await
+    }
+
+    async fn result(&self, job_id: &JobId) -> HalResult<ExecutionResult> {
+        self.fault(BackendOperation::Result)?;
+        self.inner.result(job_id).await
+    }
+
+    async fn cancel(&self, job_id: &JobId) -> HalResult<()> {
+        self.fault(BackendOperation::Cancel)?;
+        self.inner.cancel(job_id).await
+    }
+
+    fn supports_observables(&self) -> bool {
+        self.inner.supports_observables()
+    }
+
+    async fn submit_observable(
+        &self,
+        circuit: &Circuit,
+        observables: &[Observable],
+        shots: u32,
+    ) -> HalResult<Vec<f64>> {
+        self.fault(BackendOperation::Submit)?;
+        self.inner
+            .submit_observable(circuit, observables, shots)
+            .await
+    }
+
+    fn validation_report(&self, circuit: &Circuit, shots: u32) -> ValidationReport {
+        self.inner.validation_report(circuit, shots)
+    }
+
+    async fn prepare(&self, circuit: &Circuit) -> HalResult<Circuit> {
+        self.inner.prepare(circuit).await
+    }
+
+    async fn telemetry(&self) -> HalResult<Option<TelemetrySample>> {
+        self.inner.telemetry().await
+    }
+
+    async fn phase(&self, job_id: &JobId) -> HalResult<Option<JobPhase>> {
+        self.fault(BackendOperation::Status)?;
+        self.inner.phase(job_id).await
+    }
+
+    async fn queue_position(&self, job_id: &JobId) -> HalResult<Option<u32>> {
+        self.fault(BackendOperation::Status)?;
+        self.inner.queue_position(job_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+        seed = 7
+
+        [backend_errors]
+        probability = 1.0
+        operations = ["submit"]
+        backends = ["flaky"]
+
+        [dropped_status_updates]
+        probability = 0.5
+    "#;
+
+    struct EchoBackend {
+        name: String,
+        capabilities: Capabilities,
+    }
+
+    #[async_trait]
+    impl Backend for EchoBackend {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn availability(&self) -> HalResult<BackendAvailability> {
+            Ok(BackendAvailability::always_available())
+        }
+        async fn validate(&self, _c: &Circuit, _shots: u32) -> HalResult<ValidationResult> {
+            Ok(ValidationResult::Valid)
+        }
+        async fn submit(
+            &self,
+            _c: &Circuit,
+            _shots: u32,
+            _parameters: Option<&HashMap<String, f64>>,
+        ) -> HalResult<JobId> {
+            Ok(JobId::new("job"))
+        }
+        async fn status(&self, _id: &JobId) -> HalResult<JobStatus> {
+            Ok(JobStatus::Completed)
+        }
+        async fn result(&self, id: &JobId) -> HalResult<ExecutionResult> {
+            Err(HalError::JobNotFound(id.0.clone()))
+        }
+        async fn cancel(&self, _id: &JobId) -> HalResult<()> {
+            Ok(())
+        }
+    }
+
+    fn chaos_backend(name: &str, injector: &Arc<FaultInjector>) -> ChaosBackend {
+        let inner = Arc::new(EchoBackend {
+            name: name.to_string(),
+            capabilities: Capabilities::simulator(2),
+        });
+        ChaosBackend::new(inner, Arc::clone(injector))
+    }
+
+    #[tokio::test]
+    async fn test_chaos_backend_fails_selected_calls() {
+        let injector = Arc::new(FaultInjector::new(
+            FaultScenario::from_toml_str(SCENARIO).unwrap(),
+        ));
+        let circuit = Circuit::with_size("c", 1, 0);
+
+        let flaky = chaos_backend("flaky", &injector);
+        let err = flaky.submit(&circuit, 10, None).await.unwrap_err();
+        assert!(err.is_transient());
+        assert!(flaky.status(&JobId::new("job")).await.is_ok());
+
+        let steady = chaos_backend("steady", &injector);
+        assert!(steady.submit(&circuit, 10, None).await.is_ok());
+        assert_eq!(injector.counts().backend_errors, 1);
+    }
+
+    #[test]
+    fn test_decisions_are_seeded() {
+        let scenario = FaultScenario::from_toml_str(SCENARIO).unwrap();
+        let draws = |injector: &FaultInjector| -> Vec<bool> {
+            (0..64).map(|_| injector.drop_status_update()).collect()
+        };
+        let first = draws(&FaultInjector::new(scenario.clone()));
+        let second = draws(&FaultInjector::new(scenario));
+        assert_eq!(first, second);
+        assert!(first.iter().any(|&d| d) && first.iter().any(|&d| !d));
+    }
+
+    #[test]
+    fn test_no_faults_without_scenario_sections() {
+        let injector = FaultInjector::new(FaultScenario::default());
+        assert!(
+            injector
+                .backend_error("any", BackendOperation::Submit)
+                .is_none()
+        );
+        assert!(injector.storage_delay().is_none());
+        assert!(!injector.drop_status_update());
+        assert_eq!(injector.counts(), FaultCounts::default());
+    }
+
+    #[test]
+    fn test_rejects_invalid_probability() {
+        let err = FaultScenario::from_json_str(
+            r#"{"storage_delay": {"probability": 1.5, "millis": 10}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("outside [0, 1]"));
+    }
+}
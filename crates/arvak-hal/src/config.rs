@@ -0,0 +1,892 @@
+//! Typed backend configuration.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! A single document configures every backend a deployment uses. Each entry
+//! under `backends` names a backend instance and selects its adapter with the
+//! `adapter` key; the remaining keys are the adapter's typed section. The same
+//! document is read by the CLI, the gRPC server, and
+//! [`BackendRegistry`](crate::BackendRegistry), in TOML or JSON:
+//!
+//! ```toml
+//! [backends.garnet]
+//! adapter = "iqm"
+//! target = "garnet"
+//!
+//! [backends.local]
+//! adapter = "simulator"
+//! max_qubits = 24
+//! ```
+//!
+//! Unknown keys are rejected, and every error names the offending field
+//! (e.g. `backends.garnet.token`). Credentials may be left out of the
+//! document; they then fall back to the adapter's usual environment variable
+//! (`IQM_TOKEN` above). They may also reference a variable or file by name
+//! (see [`Secret`]), so the document itself can be checked in:
+//!
+//! ```toml
+//! [backends.garnet]
+//! adapter = "iqm"
+//! target = "garnet"
+//! token = { env = "GARNET_TOKEN" }
+//!
+//! [backends.torino]
+//! adapter = "ibm"
+//! backend = "ibm_torino"
+//! token = { file = "/run/secrets/ibm-token" }
+//!
+//! [aliases]
+//! default = "garnet"
+//! ```
+//!
+//! The `aliases` table gives backends alternative names; every consumer
+//! resolves them the same way.
+//!
+//! ```rust
+//! use arvak_hal::config::{AdapterConfig, BackendsConfig};
+//!
+//! let config = BackendsConfig::from_toml_str(
+//!     r#"
+//!     [backends.local]
+//!     adapter = "simulator"
+//!     max_qubits = 24
+//!     "#,
+//! )
+//! .unwrap();
+//! assert!(matches!(config.get("local"), Some(AdapterConfig::Simulator(_))));
+//!
+//! let backend = config.resolve("local").unwrap();
+//! assert_eq!(backend.extra["max_qubits"], 24);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::backend::BackendConfig;
+use crate::error::HalError;
+
+/// Errors from loading or validating a backend configuration document.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The document could not be read.
+    #[error("Failed to read backend configuration {path}: {message}")]
+    Io {
+        /// File that failed to load.
+        path: String,
+        /// Underlying error.
+        message: String,
+    },
+
+    /// The document is not valid TOML/JSON or does not match the schema.
+    #[error("Invalid backend configuration at {path}: {message}")]
+    Parse {
+        /// Field path where parsing failed.
+        path: String,
+        /// Underlying error.
+        message: String,
+    },
+
+    /// A required field is missing from the document and the environment.
+    #[error("Missing backend configuration field {path}{}", env_hint(.env.as_deref()))]
+    MissingField {
+        /// Field path, e.g. `backends.garnet.token`.
+        path: String,
+        /// Environment variable that may supply the field instead.
+        env: Option<String>,
+    },
+
+    /// A field is present but has an invalid value.
+    #[error("Invalid backend configuration field {path}: {message}")]
+    InvalidField {
+        /// Field path, e.g. `backends.local.max_qubits`.
+        path: String,
+        /// Why the value was rejected.
+        message: String,
+    },
+
+    /// No backend with this name is configured.
+    #[error("No backend named '{0}' in the backend configuration")]
+    UnknownBackend(String),
+}
+
+fn env_hint(env: Option<&str>) -> String {
+    env.map(|var| format!(" (or set {var})"))
+        .unwrap_or_default()
+}
+
+impl From<ConfigError> for HalError {
+    fn from(e: ConfigError) -> Self {
+        Self::Configuration(e.to_string())
+    }
+}
+
+/// Credential read from a configuration document.
+///
+/// Written inline or as a reference that is resolved when the backend is
+/// built:
+///
+/// ```toml
+/// token = "inline-token"
+/// token = { env = "GARNET_TOKEN" }
+/// token = { file = "/run/secrets/garnet-token" }
+/// ```
+///
+/// Inline values are redacted from `Debug` output; credentials are never
+/// serialized.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(
+    untagged,
+    expecting = "a string, { env = \"VARIABLE\" } or { file = \"path\" }"
+)]
+pub enum Secret {
+    /// The credential itself.
+    Value(String),
+    /// Read from an environment variable.
+    Env {
+        /// Variable name.
+        env: String,
+    },
+    /// Read from a file; surrounding whitespace is trimmed.
+    File {
+        /// File path.
+        file: PathBuf,
+    },
+}
+
+impl Secret {
+    /// Wrap an inline credential.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self::Value(value.into())
+    }
+
+    /// Reference an environment variable.
+    pub fn from_env(var: impl Into<String>) -> Self {
+        Self::Env { env: var.into() }
+    }
+
+    /// Reference a file.
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Self::File { file: path.into() }
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(_) => f.write_str("[REDACTED]"),
+            Self::Env { env } => write!(f, "env:{env}"),
+            Self::File { file } => write!(f, "file:{}", file.display()),
+        }
+    }
+}
+
+/// A backend configuration document: named backend instances and aliases.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackendsConfig {
+    /// Backend instances keyed by name.
+    #[serde(default)]
+    pub backends: BTreeMap<String, AdapterConfig>,
+    /// Alternative names, mapping each alias to a backend name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl BackendsConfig {
+    /// Parse a TOML document.
+    pub fn from_toml_str(source: &str) -> Result<Self, ConfigError> {
+        Self::parse(toml::Deserializer::new(source))
+    }
+
+    /// Parse a JSON document.
+    pub fn from_json_str(source: &str) -> Result<Self, ConfigError> {
+        Self::parse(&mut serde_json::Deserializer::from_str(source))
+    }
+
+    /// Load a document from disk: JSON for `.json` files, TOML otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json_str(&source)
+        } else {
+            Self::from_toml_str(&source)
+        }
+    }
+
+    fn parse<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, ConfigError> {
+        serde_path_to_error::deserialize(deserializer).map_err(|e| ConfigError::Parse {
+            path: e.path().to_string(),
+            message: e.inner().to_string(),
+        })
+    }
+
+    /// Add or replace a backend instance.
+    #[must_use]
+    pub fn with_backend(mut self, name: impl Into<String>, adapter: AdapterConfig) -> Self {
+        self.backends.insert(name.into(), adapter);
+        self
+    }
+
+    /// Add or replace an alias for backend `target`.
+    #[must_use]
+    pub fn with_alias(mut self, alias: impl Into<String>, target: impl Into<String>) -> Self {
+        self.aliases.insert(alias.into(), target.into());
+        self
+    }
+
+    /// The backend `name` refers to, following aliases.
+    pub fn canonical_name<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        if self.backends.contains_key(name) {
+            return Some(name);
+        }
+        self.aliases
+            .get(name)
+            .map(String::as_str)
+            .filter(|target| self.backends.contains_key(*target))
+    }
+
+    /// The section configuring backend `name`, following aliases.
+    pub fn get(&self, name: &str) -> Option<&AdapterConfig> {
+        self.backends.get(self.canonical_name(name)?)
+    }
+
+    /// Names of the configured backends, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.backends.keys().map(String::as_str)
+    }
+
+    /// Check every section, including credentials supplied by the
+    /// environment, and every alias.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (alias, target) in &self.aliases {
+            let message = if self.backends.contains_key(alias) {
+                format!("'{alias}' is already a backend name")
+            } else if !self.backends.contains_key(target) {
+                format!("no backend named '{target}'")
+            } else {
+                continue;
+            };
+            return Err(ConfigError::InvalidField {
+                path: format!("aliases.{alias}"),
+                message,
+            });
+        }
+        for name in self.backends.keys() {
+            self.resolve(name)?;
+        }
+        Ok(())
+    }
+
+    /// Build the [`BackendConfig`] for backend `name`, as consumed by
+    /// [`BackendFactory`](crate::BackendFactory) implementations.
+    ///
+    /// Aliases resolve to the backend they name, and the returned config
+    /// carries that backend's name.
+    pub fn resolve(&self, name: &str) -> Result<BackendConfig, ConfigError> {
+        let name = self
+            .canonical_name(name)
+            .ok_or_else(|| ConfigError::UnknownBackend(name.to_string()))?;
+        self.backends[name].to_backend_config(name)
+    }
+}
+
+/// Typed configuration section of one backend, selected by `adapter`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "adapter", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum AdapterConfig {
+    /// Local statevector simulator (`arvak-adapter-sim`).
+    Simulator(SimulatorConfig),
+    /// MQT decision-diagram simulator (`arvak-adapter-ddsim`).
+    Ddsim(DdsimConfig),
+    /// IQM Resonance (`arvak-adapter-iqm`).
+    Iqm(IqmConfig),
+    /// IBM Quantum (`arvak-adapter-ibm`).
+    Ibm(IbmConfig),
+    /// NVIDIA CUDA-Q (`arvak-adapter-cudaq`).
+    Cudaq(CudaqConfig),
+    /// Quantinuum (`arvak-adapter-quantinuum`).
+    Quantinuum(QuantinuumConfig),
+    /// AQT (`arvak-adapter-aqt`).
+    Aqt(AqtConfig),
+    /// `IonQ` (`arvak-adapter-ionq`).
+    Ionq(IonqConfig),
+    /// Scaleway `QaaS` (`arvak-adapter-scaleway`).
+    Scaleway(ScalewayConfig),
+    /// Quandela Cloud (`arvak-adapter-quandela`).
+    Quandela(QuandelaConfig),
+    /// QDMI device (`arvak-adapter-qdmi`).
+    Qdmi(QdmiConfig),
+}
+
+impl AdapterConfig {
+    /// Adapter name, as registered in a [`BackendRegistry`](crate::BackendRegistry).
+    pub fn adapter(&self) -> &'static str {
+        match self {
+            Self::Simulator(_) => "simulator",
+            Self::Ddsim(_) => "ddsim",
+            Self::Iqm(_) => "iqm",
+            Self::Ibm(_) => "ibm",
+            Self::Cudaq(_) => "cudaq",
+            Self::Quantinuum(_) => "quantinuum",
+            Self::Aqt(_) => "aqt",
+            Self::Ionq(_) => "ionq",
+            Self::Scaleway(_) => "scaleway",
+            Self::Quandela(_) => "quandela",
+            Self::Qdmi(_) => "qdmi",
+        }
+    }
+
+    /// Validate the section and build the [`BackendConfig`] for backend
+    /// `name`, taking omitted credentials from the environment.
+    pub fn to_backend_config(&self, name: &str) -> Result<BackendConfig, ConfigError> {
+        self.resolve_with(name, &|var| std::env::var(var).ok())
+    }
+
+    fn resolve_with(
+        &self,
+        name: &str,
+        env: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<BackendConfig, ConfigError> {
+        let fields = Fields {
+            prefix: format!("backends.{name}"),
+            env,
+        };
+        let mut config = BackendConfig::new(name);
+        match self {
+            Self::Simulator(section) => {
+                put(
+                    &mut config,
+                    "max_qubits",
+                    fields.positive("max_qubits", section.max_qubits)?,
+                );
+                put(&mut config, "seed", section.seed);
+            }
+            Self::Ddsim(section) => {
+                put(
+                    &mut config,
+                    "max_qubits",
+                    fields.positive("max_qubits", section.max_qubits)?,
+                );
+            }
+            Self::Iqm(section) => {
+                config.endpoint = fields.url("endpoint", section.endpoint.as_deref())?;
+                config.token =
+                    Some(fields.required(section.token.as_ref(), "token", "IQM_TOKEN")?);
+                put(&mut config, "target", section.target.clone());
+            }
+            Self::Ibm(section) => {
+                config.endpoint = fields.url("endpoint", section.endpoint.as_deref())?;
+                config.token =
+                    Some(fields.required(section.token.as_ref(), "token", "IBM_QUANTUM_TOKEN")?);
+                put(&mut config, "backend", section.backend.clone());
+                put(&mut config, "instance", section.instance.clone());
+            }
+            Self::Cudaq(section) => {
+                config.endpoint = fields.url("endpoint", section.endpoint.as_deref())?;
+                config.token =
+                    Some(fields.required(section.token.as_ref(), "token", "CUDAQ_API_TOKEN")?);
+                put(&mut config, "target", section.target.clone());
+            }
+            Self::Quantinuum(section) => {
+                let email =
+                    fields.required_plain(section.email.as_deref(), "email", "QUANTINUUM_EMAIL")?;
+                let password = fields.required(
+                    section.password.as_ref(),
+                    "password",
+                    "QUANTINUUM_PASSWORD",
+                )?;
+                // The password travels as the token so it stays redacted.
+                config.token = Some(password);
+                put(&mut config, "target", section.target.clone());
+                put(&mut config, "email", Some(email));
+            }
+            Self::Aqt(section) => {
+                let portal_url = section
+                    .portal_url
+                    .clone()
+                    .or_else(|| (fields.env)("AQT_PORTAL_URL"));
+                config.endpoint = fields.url("portal_url", portal_url.as_deref())?;
+                // Offline simulators accept an empty token.
+                config.token = Some(
+                    fields
+                        .secret(section.token.as_ref(), "token")?
+                        .or_else(|| (fields.env)("AQT_TOKEN"))
+                        .unwrap_or_default(),
+                );
+                put(&mut config, "workspace", section.workspace.clone());
+                put(&mut config, "resource", section.resource.clone());
+            }
+            Self::Ionq(section) => {
+                let api_url = section
+                    .api_url
+                    .clone()
+                    .or_else(|| (fields.env)("IONQ_API_URL"));
+                config.endpoint = fields.url("api_url", api_url.as_deref())?;
+                config.token =
+                    Some(fields.required(section.api_key.as_ref(), "api_key", "IONQ_API_KEY")?);
+                put(&mut config, "backend", section.backend.clone());
+            }
+            Self::Scaleway(section) => {
+                config.endpoint = fields.url("endpoint", section.endpoint.as_deref())?;
+                config.token = Some(fields.required(
+                    section.secret_key.as_ref(),
+                    "secret_key",
+                    "SCALEWAY_SECRET_KEY",
+                )?);
+                let project_id = fields.required_plain(
+                    section.project_id.as_deref(),
+                    "project_id",
+                    "SCALEWAY_PROJECT_ID",
+                )?;
+                let session_id = fields.required_plain(
+                    section.session_id.as_deref(),
+                    "session_id",
+                    "SCALEWAY_SESSION_ID",
+                )?;
+                let platform = section
+                    .platform
+                    .clone()
+                    .or_else(|| (fields.env)("SCALEWAY_PLATFORM"));
+                put(&mut config, "project_id", Some(project_id));
+                put(&mut config, "session_id", Some(session_id));
+                put(&mut config, "platform", platform);
+            }
+            Self::Quandela(section) => {
+                put(&mut config, "platform", section.platform.clone());
+            }
+            Self::Qdmi(section) => {
+                config.endpoint.clone_from(&section.endpoint);
+                config.token = fields.secret(section.token.as_ref(), "token")?;
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Field lookup for one section, producing errors with the full path.
+struct Fields<'a> {
+    prefix: String,
+    env: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl Fields<'_> {
+    fn path(&self, field: &str) -> String {
+        format!("{}.{field}", self.prefix)
+    }
+
+    /// Resolve a credential, following `env`/`file` references.
+    fn secret(&self, value: Option<&Secret>, field: &str) -> Result<Option<String>, ConfigError> {
+        match value {
+            None => Ok(None),
+            Some(Secret::Value(value)) => Ok(Some(value.clone())),
+            Some(Secret::Env { env }) => (self.env)(env)
+                .filter(|v| !v.is_empty())
+                .map(Some)
+                .ok_or_else(|| ConfigError::MissingField {
+                    path: self.path(field),
+                    env: Some(env.clone()),
+                }),
+            Some(Secret::File { file }) => std::fs::read_to_string(file)
+                .map(|v| Some(v.trim().to_string()))
+                .map_err(|e| ConfigError::InvalidField {
+                    path: self.path(field),
+                    message: format!("cannot read {}: {e}", file.display()),
+                }),
+        }
+    }
+
+    fn required(
+        &self,
+        value: Option<&Secret>,
+        field: &str,
+        var: &str,
+    ) -> Result<String, ConfigError> {
+        let value = self.secret(value, field)?;
+        self.required_plain(value.as_deref(), field, var)
+    }
+
+    fn required_plain(
+        &self,
+        value: Option<&str>,
+        field: &str,
+        var: &str,
+    ) -> Result<String, ConfigError> {
+        value
+            .map(str::to_string)
+            .or_else(|| (self.env)(var))
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| ConfigError::MissingField {
+                path: self.path(field),
+                env: Some(var.to_string()),
+            })
+    }
+
+    fn url(&self, field: &str, value: Option<&str>) -> Result<Option<String>, ConfigError> {
+        match value {
+            Some(url) if !(url.starts_with("https://") || url.starts_with("http://")) => {
+                Err(ConfigError::InvalidField {
+                    path: self.path(field),
+                    message: format!("'{url}' is not an http(s) URL"),
+                })
+            }
+            _ => Ok(value.map(str::to_string)),
+        }
+    }
+
+    fn positive(&self, field: &str, value: Option<u32>) -> Result<Option<u64>, ConfigError> {
+        match value {
+            Some(0) => Err(ConfigError::InvalidField {
+                path: self.path(field),
+                message: "must be greater than 0".into(),
+            }),
+            _ => Ok(value.map(u64::from)),
+        }
+    }
+}
+
+/// Set `extra[key]` when a value is given.
+fn put(config: &mut BackendConfig, key: &str, value: Option<impl Into<serde_json::Value>>) {
+    if let Some(value) = value {
+        config.extra.insert(key.into(), value.into());
+    }
+}
+
+/// Local statevector simulator.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimulatorConfig {
+    /// Largest circuit accepted (default 20).
+    pub max_qubits: Option<u32>,
+    /// Seed for reproducible sampling.
+    pub seed: Option<u64>,
+}
+
+/// MQT decision-diagram simulator.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DdsimConfig {
+    /// Largest circuit accepted.
+    pub max_qubits: Option<u32>,
+}
+
+/// IQM Resonance.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IqmConfig {
+    /// API endpoint (default: IQM Resonance).
+    pub endpoint: Option<String>,
+    /// API token; falls back to `IQM_TOKEN`.
+    #[serde(skip_serializing)]
+    pub token: Option<Secret>,
+    /// Quantum computer alias, e.g. `garnet`.
+    pub target: Option<String>,
+}
+
+/// IBM Quantum.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IbmConfig {
+    /// Backend name (default `ibm_torino`).
+    pub backend: Option<String>,
+    /// API endpoint (default: IBM Quantum legacy API).
+    pub endpoint: Option<String>,
+    /// API token; falls back to `IBM_QUANTUM_TOKEN`.
+    #[serde(skip_serializing)]
+    pub token: Option<Secret>,
+    /// Instance (hub/group/project).
+    pub instance: Option<String>,
+}
+
+/// NVIDIA CUDA-Q.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CudaqConfig {
+    /// API endpoint (default: NVIDIA Quantum Cloud).
+    pub endpoint: Option<String>,
+    /// API token; falls back to `CUDAQ_API_TOKEN`.
+    #[serde(skip_serializing)]
+    pub token: Option<Secret>,
+    /// Simulation target, e.g. `tensornet`.
+    pub target: Option<String>,
+}
+
+/// Quantinuum.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuantinuumConfig {
+    /// Machine name (default `H2-1LE`).
+    pub target: Option<String>,
+    /// Account email; falls back to `QUANTINUUM_EMAIL`.
+    pub email: Option<String>,
+    /// Account password; falls back to `QUANTINUUM_PASSWORD`.
+    #[serde(skip_serializing)]
+    pub password: Option<Secret>,
+}
+
+/// AQT.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AqtConfig {
+    /// Workspace (default `default`).
+    pub workspace: Option<String>,
+    /// Resource within the workspace (default: offline noiseless simulator).
+    pub resource: Option<String>,
+    /// API token; falls back to `AQT_TOKEN`, may be empty for offline simulators.
+    #[serde(skip_serializing)]
+    pub token: Option<Secret>,
+    /// Portal URL; falls back to `AQT_PORTAL_URL`.
+    pub portal_url: Option<String>,
+}
+
+/// `IonQ`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IonqConfig {
+    /// `IonQ` backend, e.g. `qpu.aria-1` (default `simulator`).
+    pub backend: Option<String>,
+    /// API key; falls back to `IONQ_API_KEY`.
+    #[serde(skip_serializing)]
+    pub api_key: Option<Secret>,
+    /// API URL; falls back to `IONQ_API_URL`.
+    pub api_url: Option<String>,
+}
+
+/// Scaleway `QaaS`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScalewayConfig {
+    /// API endpoint (default: Scaleway `QaaS`).
+    pub endpoint: Option<String>,
+    /// Secret key; falls back to `SCALEWAY_SECRET_KEY`.
+    #[serde(skip_serializing)]
+    pub secret_key: Option<Secret>,
+    /// Project; falls back to `SCALEWAY_PROJECT_ID`.
+    pub project_id: Option<String>,
+    /// Session for job submission; falls back to `SCALEWAY_SESSION_ID`.
+    pub session_id: Option<String>,
+    /// Platform, e.g. `QPU-GARNET-20PQ`; falls back to `SCALEWAY_PLATFORM`.
+    pub platform: Option<String>,
+}
+
+/// Quandela Cloud.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuandelaConfig {
+    /// Platform, e.g. `sim:belenos` (default `sim:ascella`).
+    pub platform: Option<String>,
+}
+
+/// QDMI device.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QdmiConfig {
+    /// Device endpoint; the session is opened when this or `token` is set.
+    pub endpoint: Option<String>,
+    /// Session token.
+    #[serde(skip_serializing)]
+    pub token: Option<Secret>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"
+        [backends.garnet]
+        adapter = "iqm"
+        token = "secret-token"
+        target = "garnet"
+
+        [backends.local]
+        adapter = "simulator"
+        max_qubits = 24
+        seed = 7
+    "#;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_toml_and_json_documents() {
+        let config = BackendsConfig::from_toml_str(DOCUMENT).unwrap();
+        assert_eq!(config.names().collect::<Vec<_>>(), vec!["garnet", "local"]);
+
+        let garnet = config.resolve("garnet").unwrap();
+        assert_eq!(garnet.name, "garnet");
+        assert_eq!(garnet.token.as_deref(), Some("secret-token"));
+        assert_eq!(garnet.extra["target"], "garnet");
+        let local = config.resolve("local").unwrap();
+        assert_eq!(local.extra["max_qubits"], 24);
+        assert_eq!(local.extra["seed"], 7);
+
+        let json = BackendsConfig::from_json_str(
+            r#"{"backends": {"local": {"adapter": "ddsim", "max_qubits": 30}}}"#,
+        )
+        .unwrap();
+        assert_eq!(json.get("local").unwrap().adapter(), "ddsim");
+        assert!(matches!(
+            json.resolve("garnet"),
+            Err(ConfigError::UnknownBackend(_))
+        ));
+    }
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        let config = BackendsConfig::from_toml_str(DOCUMENT).unwrap();
+        assert!(!format!("{config:?}").contains("secret-token"));
+        assert!(
+            !serde_json::to_string(&config)
+                .unwrap()
+                .contains("secret-token")
+        );
+    }
+
+    #[test]
+    fn test_errors_name_the_field() {
+        let err = BackendsConfig::from_toml_str(
+            r#"
+            [backends.local]
+            adapter = "simulator"
+            max_qbits = 24
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::Parse { path, message }
+                if path == "backends.local" && message.contains("max_qbits")),
+            "{err}"
+        );
+
+        let err = BackendsConfig::from_toml_str("[backends.x]\nadapter = \"qpu\"\n").unwrap_err();
+        assert!(err.to_string().contains("backends.x"), "{err}");
+
+        let section = AdapterConfig::Iqm(IqmConfig::default());
+        let err = section.resolve_with("garnet", &no_env).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing backend configuration field backends.garnet.token (or set IQM_TOKEN)"
+        );
+
+        let section = AdapterConfig::Simulator(SimulatorConfig {
+            max_qubits: Some(0),
+            seed: None,
+        });
+        assert!(matches!(
+            section.resolve_with("local", &no_env),
+            Err(ConfigError::InvalidField { path, .. }) if path == "backends.local.max_qubits"
+        ));
+
+        let section = AdapterConfig::Iqm(IqmConfig {
+            endpoint: Some("resonance.meetiqm.com".into()),
+            token: Some(Secret::new("t")),
+            target: None,
+        });
+        assert!(matches!(
+            section.resolve_with("garnet", &no_env),
+            Err(ConfigError::InvalidField { path, .. }) if path == "backends.garnet.endpoint"
+        ));
+    }
+
+    #[test]
+    fn test_environment_fallback() {
+        let env = |var: &str| match var {
+            "SCALEWAY_SECRET_KEY" => Some("sk".to_string()),
+            "SCALEWAY_SESSION_ID" => Some("session".to_string()),
+            _ => None,
+        };
+        let mut section = ScalewayConfig {
+            project_id: Some("project".into()),
+            ..ScalewayConfig::default()
+        };
+        let config = AdapterConfig::Scaleway(section.clone())
+            .resolve_with("scw", &env)
+            .unwrap();
+        assert_eq!(config.token.as_deref(), Some("sk"));
+        assert_eq!(config.extra["session_id"], "session");
+        assert!(!config.extra.contains_key("platform"));
+
+        // Values in the document take precedence over the environment.
+        section.secret_key = Some(Secret::new("from-file"));
+        let config = AdapterConfig::Scaleway(section)
+            .resolve_with("scw", &env)
+            .unwrap();
+        assert_eq!(config.token.as_deref(), Some("from-file"));
+    }
+
+    #[test]
+    fn test_credential_references() {
+        let config = BackendsConfig::from_toml_str(
+            r#"
+            [backends.torino]
+            adapter = "ibm"
+            token = { env = "TORINO_TOKEN" }
+            "#,
+        )
+        .unwrap();
+        let section = config.get("torino").unwrap();
+        assert!(format!("{section:?}").contains("env:TORINO_TOKEN"));
+
+        let env = |var: &str| (var == "TORINO_TOKEN").then(|| "ibm-token".to_string());
+        let resolved = section.resolve_with("torino", &env).unwrap();
+        assert_eq!(resolved.token.as_deref(), Some("ibm-token"));
+        let err = section.resolve_with("torino", &no_env).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing backend configuration field backends.torino.token (or set TORINO_TOKEN)"
+        );
+
+        let path = std::env::temp_dir().join(format!("arvak-secret-{}", std::process::id()));
+        std::fs::write(&path, "file-token\n").unwrap();
+        let section = AdapterConfig::Qdmi(QdmiConfig {
+            endpoint: None,
+            token: Some(Secret::from_file(&path)),
+        });
+        let resolved = section.resolve_with("qdmi", &no_env).unwrap();
+        assert_eq!(resolved.token.as_deref(), Some("file-token"));
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            section.resolve_with("qdmi", &no_env),
+            Err(ConfigError::InvalidField { path, .. }) if path == "backends.qdmi.token"
+        ));
+
+        let err = BackendsConfig::from_toml_str(
+            "[backends.q]\nadapter = \"qdmi\"\ntoken = { vault = \"x\" }\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("backends.q"), "{err}");
+    }
+
+    #[test]
+    fn test_aliases() {
+        let config =
+            BackendsConfig::from_toml_str(&format!("{DOCUMENT}\n[aliases]\ndefault = \"local\"\n"))
+                .unwrap();
+        assert_eq!(config.canonical_name("default"), Some("local"));
+        assert_eq!(config.resolve("default").unwrap().name, "local");
+        assert!(config.get("default").is_some());
+        assert!(config.get("other").is_none());
+
+        let dangling = config.clone().with_alias("hw", "torino");
+        assert!(matches!(
+            dangling.validate(),
+            Err(ConfigError::InvalidField { path, .. }) if path == "aliases.hw"
+        ));
+        let shadowing = config.with_alias("garnet", "local");
+        assert_eq!(shadowing.canonical_name("garnet"), Some("garnet"));
+        assert!(matches!(
+            shadowing.validate(),
+            Err(ConfigError::InvalidField { path, .. }) if path == "aliases.garnet"
+        ));
+    }
+}
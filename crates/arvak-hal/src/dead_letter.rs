@@ -0,0 +1,306 @@
+//! Dead-letter queue of failed jobs with forensics bundles.
+//!
+//! Intermittent hardware failures are hard to diagnose after the fact: by
+//! the time someone looks, the device has been recalibrated and the logs
+//! have rotated. When a job fails, orchestrators capture a
+//! [`FailureBundle`] — what was submitted, to which target, the error the
+//! provider returned and the events leading up to it — and file it in a
+//! [`DeadLetterQueue`], a directory with one JSON file per job.
+//!
+//! ```rust
+//! use arvak_hal::{DeadLetterQueue, FailureBundle};
+//!
+//! let dir = std::env::temp_dir().join(format!("arvak-dlq-doc-{}", std::process::id()));
+//! let queue = DeadLetterQueue::new(&dir);
+//!
+//! let bundle = FailureBundle::new("job-1", "Backend wait failed: calibration lost")
+//!     .with_backend("iqm")
+//!     .with_circuit("OPENQASM 3.0;\nqubit[1] q;\nh q[0];")
+//!     .with_trace(["submitted", "running"]);
+//! queue.record(&bundle).unwrap();
+//!
+//! assert_eq!(queue.get("job-1").unwrap().unwrap().backend.as_deref(), Some("iqm"));
+//! # std::fs::remove_dir_all(&dir).unwrap();
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::capability::Capabilities;
+use crate::error::{HalError, HalResult};
+
+/// Maximum number of trace events kept in a bundle; older events are
+/// dropped.
+pub const MAX_TRACE_EVENTS: usize = 50;
+
+/// Arvak extension — not part of HAL Contract v2 spec.
+/// Everything known about a failed job, for post-hoc diagnosis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureBundle {
+    /// Orchestrator job id.
+    pub job_id: String,
+    /// Human-readable job name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Backend the job was sent to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Job id assigned by the provider or batch system, if it got that far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_job_id: Option<String>,
+    /// When the failure was recorded.
+    pub failed_at: DateTime<Utc>,
+    /// Why the job failed, as reported to the user.
+    pub reason: String,
+    /// The circuits as submitted to the backend, in OpenQASM 3.
+    #[serde(default)]
+    pub circuits: Vec<String>,
+    /// Capabilities of the target backend when the job failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Capabilities>,
+    /// Raw error returned by the provider or batch system.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_error: Option<String>,
+    /// The last events before the failure, oldest first.
+    #[serde(default)]
+    pub trace: Vec<String>,
+}
+
+impl FailureBundle {
+    /// Create a bundle for `job_id`, failed now for `reason`.
+    pub fn new(job_id: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            job_id: job_id.into(),
+            name: None,
+            backend: None,
+            provider_job_id: None,
+            failed_at: Utc::now(),
+            reason: reason.into(),
+            circuits: Vec::new(),
+            target: None,
+            provider_error: None,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Set the job name.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the backend name.
+    #[must_use]
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.backend = Some(backend.into());
+        self
+    }
+
+    /// Set the provider-side job id.
+    #[must_use]
+    pub fn with_provider_job_id(mut self, id: impl Into<String>) -> Self {
+        self.provider_job_id = Some(id.into());
+        self
+    }
+
+    /// Add a submitted circuit.
+    #[must_use]
+    pub fn with_circuit(mut self, qasm: impl Into<String>) -> Self {
+        self.circuits.push(qasm.into());
+        self
+    }
+
+    /// Set the target backend's capabilities.
+    #[must_use]
+    pub fn with_target(mut self, capabilities: Capabilities) -> Self {
+        self.target = Some(capabilities);
+        self
+    }
+
+    /// Set the raw provider error.
+    #[must_use]
+    pub fn with_provider_error(mut self, error: impl Into<String>) -> Self {
+        self.provider_error = Some(error.into());
+        self
+    }
+
+    /// Set the trace, keeping the last [`MAX_TRACE_EVENTS`] events.
+    #[must_use]
+    pub fn with_trace<I, S>(mut self, events: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trace = events.into_iter().map(Into::into).collect();
+        let excess = self.trace.len().saturating_sub(MAX_TRACE_EVENTS);
+        self.trace.drain(..excess);
+        self
+    }
+}
+
+/// Arvak extension — not part of HAL Contract v2 spec.
+/// Directory of [`FailureBundle`]s, one `<job id>.json` file per job.
+#[derive(Debug, Clone)]
+pub struct DeadLetterQueue {
+    dir: PathBuf,
+}
+
+impl DeadLetterQueue {
+    /// A queue stored in `dir`, created on the first record.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The directory bundles are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File a bundle, replacing any earlier one for the same job.
+    pub fn record(&self, bundle: &FailureBundle) -> HalResult<PathBuf> {
+        let path = self.path(&bundle.job_id);
+        let json = serde_json::to_string_pretty(bundle)?;
+        let write = || -> std::io::Result<()> {
+            std::fs::create_dir_all(&self.dir)?;
+            // Write to a sibling file and rename, so readers never see a
+            // partial bundle.
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, json)?;
+            std::fs::rename(tmp, &path)
+        };
+        write()
+            .map_err(|e| HalError::Configuration(format!("Failed to write failure bundle: {e}")))?;
+        Ok(path)
+    }
+
+    /// The bundle of `job_id`, if one was recorded.
+    pub fn get(&self, job_id: &str) -> HalResult<Option<FailureBundle>> {
+        let path = self.path(job_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_bundle(&path).map(Some)
+    }
+
+    /// All recorded bundles, most recent failure first.
+    pub fn list(&self) -> HalResult<Vec<FailureBundle>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| {
+            HalError::Configuration(format!("Failed to read dead-letter queue: {e}"))
+        })?;
+        let mut bundles = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                bundles.push(read_bundle(&path)?);
+            }
+        }
+        bundles.sort_by_key(|b| std::cmp::Reverse(b.failed_at));
+        Ok(bundles)
+    }
+
+    /// Delete the bundle of `job_id`. Returns whether one existed.
+    pub fn remove(&self, job_id: &str) -> HalResult<bool> {
+        let path = self.path(job_id);
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(&path).map_err(|e| {
+            HalError::Configuration(format!("Failed to remove failure bundle: {e}"))
+        })?;
+        Ok(true)
+    }
+
+    fn path(&self, job_id: &str) -> PathBuf {
+        // Job ids come from users and providers; keep them inside the
+        // queue directory.
+        let file: String = job_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{file}.json"))
+    }
+}
+
+fn read_bundle(path: &Path) -> HalResult<FailureBundle> {
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        HalError::Configuration(format!(
+            "Failed to read failure bundle {}: {e}",
+            path.display()
+        ))
+    })?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_queue(name: &str) -> DeadLetterQueue {
+        let dir = std::env::temp_dir().join(format!(
+            "arvak-dlq-{name}-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        DeadLetterQueue::new(dir)
+    }
+
+    #[test]
+    fn test_record_get_list_remove() {
+        let queue = temp_queue("roundtrip");
+        assert!(queue.list().unwrap().is_empty());
+
+        let mut older =
+            FailureBundle::new("job-a", "timeout").with_provider_error("{\"code\":504}");
+        older.failed_at = Utc::now() - chrono::Duration::minutes(5);
+        queue.record(&older).unwrap();
+        queue
+            .record(&FailureBundle::new("job-b", "calibration lost").with_backend("iqm"))
+            .unwrap();
+
+        let bundle = queue.get("job-a").unwrap().unwrap();
+        assert_eq!(bundle.provider_error.as_deref(), Some("{\"code\":504}"));
+        assert!(queue.get("job-c").unwrap().is_none());
+
+        let ids: Vec<_> = queue
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.job_id)
+            .collect();
+        assert_eq!(ids, vec!["job-b", "job-a"]);
+
+        assert!(queue.remove("job-a").unwrap());
+        assert!(!queue.remove("job-a").unwrap());
+        std::fs::remove_dir_all(queue.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_trace_keeps_last_events() {
+        let bundle =
+            FailureBundle::new("job", "failed").with_trace((0..80).map(|i| format!("event {i}")));
+        assert_eq!(bundle.trace.len(), MAX_TRACE_EVENTS);
+        assert_eq!(bundle.trace.first().unwrap(), "event 30");
+        assert_eq!(bundle.trace.last().unwrap(), "event 79");
+    }
+
+    #[test]
+    fn test_job_id_stays_in_directory() {
+        let queue = DeadLetterQueue::new("/tmp/dlq");
+        assert_eq!(
+            queue.path("../etc/passwd"),
+            Path::new("/tmp/dlq/___etc_passwd.json")
+        );
+    }
+}
@@ -0,0 +1,525 @@
+//! Coherence budgets from live T1/T2 data.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! The [`NoiseProfile`](crate::NoiseProfile) in [`Capabilities`] is a
+//! static average. Coherence drifts between calibrations, and some devices
+//! have quiet windows where it is known to be stable. A
+//! [`DecoherenceMonitor`] keeps recent T1/T2 samples and quiet windows, and
+//! [`DecoherenceMonitor::assess`] compares a circuit's estimated duration
+//! against the coherence expected when it runs. [`ValidationReport`]
+//! consults the monitor attached to [`Capabilities::decoherence`], turning
+//! a tight budget into a warning and an exceeded one into a blocking
+//! [`ValidationIssue::CoherenceBudget`].
+//!
+//! ```rust
+//! use arvak_hal::Capabilities;
+//! use arvak_hal::decoherence::{CoherenceSample, CoherenceVerdict, DecoherenceMonitor};
+//! use arvak_ir::Circuit;
+//! use chrono::Utc;
+//!
+//! let monitor = DecoherenceMonitor::default().with_sample(CoherenceSample {
+//!     timestamp: Utc::now(),
+//!     t1_us: 40.0,
+//!     t2_us: 25.0,
+//! });
+//! let assessment = monitor.assess(&Circuit::bell().unwrap(), Utc::now()).unwrap();
+//! assert_eq!(assessment.verdict, CoherenceVerdict::Ok);
+//!
+//! let caps = Capabilities::iqm("Garnet", 5).with_decoherence_monitor(monitor);
+//! assert!(caps.decoherence.is_some());
+//! ```
+//!
+//! [`Capabilities`]: crate::Capabilities
+//! [`Capabilities::decoherence`]: crate::Capabilities::decoherence
+//! [`ValidationReport`]: crate::ValidationReport
+//! [`ValidationIssue::CoherenceBudget`]: crate::ValidationIssue::CoherenceBudget
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use arvak_ir::Circuit;
+use arvak_ir::instruction::InstructionKind;
+
+/// Samples kept by [`DecoherenceMonitor::record`]; older ones are dropped.
+pub const MAX_SAMPLES: usize = 256;
+
+/// How far ahead of the latest sample a falling trend is extrapolated.
+const MAX_PROJECTION_HOURS: f64 = 1.0;
+
+/// Typical operation durations, in microseconds, for estimating how long a
+/// circuit runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GateDurations {
+    /// Single-qubit gate.
+    pub single_qubit_us: f64,
+    /// Two-qubit (or wider) gate.
+    pub two_qubit_us: f64,
+    /// Measurement.
+    pub measurement_us: f64,
+    /// Reset.
+    pub reset_us: f64,
+}
+
+impl GateDurations {
+    /// Superconducting transmons (IQM, IBM, Rigetti).
+    pub fn superconducting() -> Self {
+        Self {
+            single_qubit_us: 0.05,
+            two_qubit_us: 0.3,
+            measurement_us: 1.5,
+            reset_us: 1.0,
+        }
+    }
+
+    /// Trapped ions (AQT, IonQ, Quantinuum).
+    pub fn trapped_ion() -> Self {
+        Self {
+            single_qubit_us: 10.0,
+            two_qubit_us: 200.0,
+            measurement_us: 300.0,
+            reset_us: 50.0,
+        }
+    }
+
+    /// Estimated wall time of `circuit` in microseconds.
+    ///
+    /// Operations on disjoint qubits run in parallel, so this is the
+    /// critical path through the circuit. Barriers synchronize their
+    /// qubits; delays in device units are not counted.
+    pub fn circuit_duration_us(&self, circuit: &Circuit) -> f64 {
+        let mut clocks = vec![0.0_f64; circuit.num_qubits()];
+        for (_, inst) in circuit.dag().topological_ops() {
+            let duration = match &inst.kind {
+                InstructionKind::Gate(_) if inst.qubits.len() > 1 => self.two_qubit_us,
+                InstructionKind::Gate(_) => self.single_qubit_us,
+                InstructionKind::Measure => self.measurement_us,
+                InstructionKind::Reset => self.reset_us,
+                _ => 0.0,
+            };
+            let start = inst
+                .qubits
+                .iter()
+                .filter_map(|q| clocks.get(q.0 as usize))
+                .fold(0.0_f64, |a, &b| a.max(b));
+            for qubit in &inst.qubits {
+                if let Some(clock) = clocks.get_mut(qubit.0 as usize) {
+                    *clock = start + duration;
+                }
+            }
+        }
+        clocks.into_iter().fold(0.0, f64::max)
+    }
+}
+
+impl Default for GateDurations {
+    fn default() -> Self {
+        Self::superconducting()
+    }
+}
+
+/// Device-wide average coherence times at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CoherenceSample {
+    /// When the sample was taken.
+    pub timestamp: DateTime<Utc>,
+    /// Average T1 in microseconds.
+    pub t1_us: f64,
+    /// Average T2 in microseconds.
+    pub t2_us: f64,
+}
+
+/// A period in which coherence is expected to be stable, e.g. between
+/// calibration runs or outside cryostat cycling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietWindow {
+    /// Start of the window.
+    pub start: DateTime<Utc>,
+    /// End of the window.
+    pub end: DateTime<Utc>,
+}
+
+impl QuietWindow {
+    /// Check if `at` falls inside the window.
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// Rate of change of coherence, fitted over the recorded samples.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CoherenceTrend {
+    /// Change of T1 in microseconds per hour.
+    pub t1_us_per_hour: f64,
+    /// Change of T2 in microseconds per hour.
+    pub t2_us_per_hour: f64,
+}
+
+impl CoherenceTrend {
+    /// Whether T1 or T2 is decreasing.
+    pub fn is_falling(&self) -> bool {
+        self.t1_us_per_hour < 0.0 || self.t2_us_per_hour < 0.0
+    }
+}
+
+/// Outcome of a coherence budget check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoherenceVerdict {
+    /// Well within the coherence budget.
+    Ok,
+    /// Uses a large share of the coherence time; results will be noisy.
+    Warn,
+    /// Likely to decohere before it finishes.
+    Reject,
+}
+
+/// A circuit's estimated duration set against the expected coherence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoherenceAssessment {
+    /// Estimated circuit duration in microseconds.
+    pub duration_us: f64,
+    /// T1 expected at execution time, in microseconds.
+    pub t1_us: f64,
+    /// T2 expected at execution time, in microseconds.
+    pub t2_us: f64,
+    /// `duration_us` as a fraction of the shorter of T1 and T2.
+    pub fraction: f64,
+    /// Trend over the recorded samples, when there are at least two.
+    pub trend: Option<CoherenceTrend>,
+    /// Whether execution time falls in a quiet window; `None` if the
+    /// monitor has no windows.
+    pub in_quiet_window: Option<bool>,
+    /// The next quiet window after execution time, if any.
+    pub next_quiet_window: Option<QuietWindow>,
+    /// The outcome.
+    pub verdict: CoherenceVerdict,
+}
+
+impl CoherenceAssessment {
+    /// The shorter of T1 and T2, which bounds the budget.
+    pub fn coherence_us(&self) -> f64 {
+        self.t1_us.min(self.t2_us)
+    }
+}
+
+/// Recent coherence data for a backend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecoherenceMonitor {
+    /// Samples in time order, at most [`MAX_SAMPLES`].
+    pub samples: Vec<CoherenceSample>,
+    /// Periods of stable coherence.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quiet_windows: Vec<QuietWindow>,
+    /// Operation durations used to estimate circuit duration.
+    #[serde(default)]
+    pub gate_durations: GateDurations,
+    /// Fraction of the coherence time from which a circuit gets a warning.
+    pub warn_fraction: f64,
+    /// Fraction of the coherence time from which a circuit is rejected.
+    pub reject_fraction: f64,
+}
+
+impl DecoherenceMonitor {
+    /// Default warning threshold: 10% of the coherence time.
+    pub const DEFAULT_WARN_FRACTION: f64 = 0.1;
+    /// Default rejection threshold: half the coherence time.
+    pub const DEFAULT_REJECT_FRACTION: f64 = 0.5;
+
+    /// Create an empty monitor with the given operation durations.
+    pub fn new(gate_durations: GateDurations) -> Self {
+        Self {
+            samples: Vec::new(),
+            quiet_windows: Vec::new(),
+            gate_durations,
+            warn_fraction: Self::DEFAULT_WARN_FRACTION,
+            reject_fraction: Self::DEFAULT_REJECT_FRACTION,
+        }
+    }
+
+    /// Add a sample.
+    #[must_use]
+    pub fn with_sample(mut self, sample: CoherenceSample) -> Self {
+        self.record(sample);
+        self
+    }
+
+    /// Add a quiet window.
+    #[must_use]
+    pub fn with_quiet_window(mut self, window: QuietWindow) -> Self {
+        self.quiet_windows.push(window);
+        self.quiet_windows.sort_by_key(|w| w.start);
+        self
+    }
+
+    /// Set the warning and rejection thresholds, as fractions of the
+    /// shorter of T1 and T2.
+    #[must_use]
+    pub fn with_thresholds(mut self, warn_fraction: f64, reject_fraction: f64) -> Self {
+        self.warn_fraction = warn_fraction;
+        self.reject_fraction = reject_fraction.max(warn_fraction);
+        self
+    }
+
+    /// Record a sample, keeping time order and the last [`MAX_SAMPLES`].
+    pub fn record(&mut self, sample: CoherenceSample) {
+        let index = self
+            .samples
+            .partition_point(|s| s.timestamp <= sample.timestamp);
+        self.samples.insert(index, sample);
+        if self.samples.len() > MAX_SAMPLES {
+            let excess = self.samples.len() - MAX_SAMPLES;
+            self.samples.drain(..excess);
+        }
+    }
+
+    /// The most recent sample.
+    pub fn latest(&self) -> Option<&CoherenceSample> {
+        self.samples.last()
+    }
+
+    /// Least-squares trend over the samples; `None` with fewer than two
+    /// samples or when they share one timestamp.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn trend(&self) -> Option<CoherenceTrend> {
+        let first = self.samples.first()?;
+        let hours: Vec<f64> = self
+            .samples
+            .iter()
+            .map(|s| (s.timestamp - first.timestamp).num_milliseconds() as f64 / 3_600_000.0)
+            .collect();
+        let n = hours.len() as f64;
+        let mean_h = hours.iter().sum::<f64>() / n;
+        let var_h: f64 = hours.iter().map(|h| (h - mean_h).powi(2)).sum();
+        if self.samples.len() < 2 || var_h <= 0.0 {
+            return None;
+        }
+        let slope = |value: fn(&CoherenceSample) -> f64| {
+            let mean_v = self.samples.iter().map(value).sum::<f64>() / n;
+            hours
+                .iter()
+                .zip(&self.samples)
+                .map(|(h, s)| (h - mean_h) * (value(s) - mean_v))
+                .sum::<f64>()
+                / var_h
+        };
+        Some(CoherenceTrend {
+            t1_us_per_hour: slope(|s| s.t1_us),
+            t2_us_per_hour: slope(|s| s.t2_us),
+        })
+    }
+
+    /// T1 and T2 expected at `at`, in microseconds.
+    ///
+    /// Starts from the latest sample and follows a falling trend for up to
+    /// an hour past it. A rising trend is not extrapolated: the budget
+    /// never assumes coherence better than last measured.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn coherence_at(&self, at: DateTime<Utc>) -> Option<(f64, f64)> {
+        let latest = self.latest()?;
+        let hours = ((at - latest.timestamp).num_milliseconds() as f64 / 3_600_000.0)
+            .clamp(0.0, MAX_PROJECTION_HOURS);
+        let (t1_rate, t2_rate) = self
+            .trend()
+            .map_or((0.0, 0.0), |t| (t.t1_us_per_hour, t.t2_us_per_hour));
+        let project = |value: f64, rate: f64| (value + rate.min(0.0) * hours).max(0.0);
+        Some((
+            project(latest.t1_us, t1_rate),
+            project(latest.t2_us, t2_rate),
+        ))
+    }
+
+    /// The quiet window containing `at`, or else the next one after it.
+    fn quiet_window(&self, at: DateTime<Utc>) -> (Option<bool>, Option<QuietWindow>) {
+        if self.quiet_windows.is_empty() {
+            return (None, None);
+        }
+        let inside = self.quiet_windows.iter().any(|w| w.contains(at));
+        let next = self.quiet_windows.iter().find(|w| w.start > at).copied();
+        (Some(inside), next)
+    }
+
+    /// Check `circuit`, run at `at`, against the coherence budget.
+    ///
+    /// The circuit is rejected when its estimated duration reaches
+    /// [`reject_fraction`](Self::reject_fraction) of the shorter of T1 and
+    /// T2 expected at `at`, and warned about from
+    /// [`warn_fraction`](Self::warn_fraction). Outside the quiet windows,
+    /// when the monitor has any, both thresholds are halved. Returns `None`
+    /// without samples.
+    pub fn assess(&self, circuit: &Circuit, at: DateTime<Utc>) -> Option<CoherenceAssessment> {
+        let (t1_us, t2_us) = self.coherence_at(at)?;
+        let duration_us = self.gate_durations.circuit_duration_us(circuit);
+        let coherence = t1_us.min(t2_us);
+        let fraction = if coherence > 0.0 {
+            duration_us / coherence
+        } else {
+            f64::INFINITY
+        };
+        let (in_quiet_window, next_quiet_window) = self.quiet_window(at);
+        let scale = if in_quiet_window == Some(false) {
+            0.5
+        } else {
+            1.0
+        };
+        let verdict = if fraction >= self.reject_fraction * scale {
+            CoherenceVerdict::Reject
+        } else if fraction >= self.warn_fraction * scale {
+            CoherenceVerdict::Warn
+        } else {
+            CoherenceVerdict::Ok
+        };
+        Some(CoherenceAssessment {
+            duration_us,
+            t1_us,
+            t2_us,
+            fraction,
+            trend: self.trend(),
+            in_quiet_window,
+            next_quiet_window,
+            verdict,
+        })
+    }
+
+    /// The first quiet window, starting at or after `after`, in which a
+    /// circuit of `duration_us` fits entirely.
+    pub fn next_window_for(&self, duration_us: f64, after: DateTime<Utc>) -> Option<QuietWindow> {
+        #[allow(clippy::cast_possible_truncation)]
+        let duration = Duration::microseconds(duration_us.ceil() as i64);
+        self.quiet_windows
+            .iter()
+            .find(|w| w.end > after && w.end - w.start.max(after) >= duration)
+            .copied()
+    }
+}
+
+impl Default for DecoherenceMonitor {
+    fn default() -> Self {
+        Self::new(GateDurations::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_ir::QubitId;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(1_800_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    fn sample(minutes: i64, t1_us: f64, t2_us: f64) -> CoherenceSample {
+        CoherenceSample {
+            timestamp: at(minutes),
+            t1_us,
+            t2_us,
+        }
+    }
+
+    /// `layers` rounds of CZ on (0, 1), each between single-qubit gates.
+    fn deep_circuit(layers: usize) -> Circuit {
+        let mut circuit = Circuit::with_size("deep", 2, 2);
+        for _ in 0..layers {
+            circuit.x(QubitId(0)).unwrap();
+            circuit.cz(QubitId(0), QubitId(1)).unwrap();
+        }
+        circuit.measure_all().unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_circuit_duration_critical_path() {
+        let durations = GateDurations::superconducting();
+        let mut circuit = Circuit::with_size("parallel", 3, 0);
+        circuit.x(QubitId(0)).unwrap();
+        circuit.x(QubitId(1)).unwrap();
+        circuit.x(QubitId(1)).unwrap();
+        circuit.cz(QubitId(0), QubitId(2)).unwrap();
+        // q1 runs its two gates in parallel with q0's x and the CZ.
+        let expected = durations.single_qubit_us + durations.two_qubit_us;
+        assert!((durations.circuit_duration_us(&circuit) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trend_and_projection() {
+        let monitor = DecoherenceMonitor::default()
+            .with_sample(sample(60, 30.0, 20.0))
+            .with_sample(sample(0, 40.0, 30.0));
+        assert_eq!(monitor.latest().unwrap().timestamp, at(60));
+
+        let trend = monitor.trend().unwrap();
+        assert!((trend.t1_us_per_hour + 10.0).abs() < 1e-9);
+        assert!(trend.is_falling());
+
+        // Falling trends are followed for at most an hour.
+        let (t1, t2) = monitor.coherence_at(at(90)).unwrap();
+        assert!((t1 - 25.0).abs() < 1e-9);
+        assert!((t2 - 15.0).abs() < 1e-9);
+        let (t1, _) = monitor.coherence_at(at(600)).unwrap();
+        assert!((t1 - 20.0).abs() < 1e-9);
+
+        // Rising trends are not.
+        let rising = DecoherenceMonitor::default()
+            .with_sample(sample(0, 20.0, 20.0))
+            .with_sample(sample(60, 30.0, 30.0));
+        assert_eq!(rising.coherence_at(at(120)), Some((30.0, 30.0)));
+    }
+
+    #[test]
+    fn test_assess_verdicts() {
+        let monitor = DecoherenceMonitor::default().with_sample(sample(0, 100.0, 50.0));
+        assert!(
+            DecoherenceMonitor::default()
+                .assess(&deep_circuit(1), at(0))
+                .is_none()
+        );
+
+        let ok = monitor.assess(&deep_circuit(1), at(0)).unwrap();
+        assert_eq!(ok.verdict, CoherenceVerdict::Ok);
+        assert!((ok.coherence_us() - 50.0).abs() < 1e-12);
+
+        // 20 layers: 20 * 0.35 + 1.5 = 8.5 µs, 17% of T2.
+        let warn = monitor.assess(&deep_circuit(20), at(0)).unwrap();
+        assert_eq!(warn.verdict, CoherenceVerdict::Warn);
+        assert!((warn.duration_us - 8.5).abs() < 1e-9);
+
+        // 80 layers: 29.5 µs, 59% of T2.
+        let reject = monitor.assess(&deep_circuit(80), at(0)).unwrap();
+        assert_eq!(reject.verdict, CoherenceVerdict::Reject);
+    }
+
+    #[test]
+    fn test_quiet_windows_tighten_budget() {
+        let window = QuietWindow {
+            start: at(60),
+            end: at(120),
+        };
+        let monitor = DecoherenceMonitor::default()
+            .with_sample(sample(0, 100.0, 100.0))
+            .with_quiet_window(window);
+        // 20 layers: 8.5% of T2, under the 10% threshold but over 5%.
+        let circuit = deep_circuit(20);
+
+        let inside = monitor.assess(&circuit, at(90)).unwrap();
+        assert_eq!(inside.in_quiet_window, Some(true));
+        assert_eq!(inside.verdict, CoherenceVerdict::Ok);
+
+        let outside = monitor.assess(&circuit, at(0)).unwrap();
+        assert_eq!(outside.in_quiet_window, Some(false));
+        assert_eq!(outside.next_quiet_window, Some(window));
+        assert_eq!(outside.verdict, CoherenceVerdict::Warn);
+
+        assert_eq!(monitor.next_window_for(8.5, at(0)), Some(window));
+        assert_eq!(monitor.next_window_for(8.5, at(120)), None);
+    }
+
+    #[test]
+    fn test_record_keeps_last_samples() {
+        let mut monitor = DecoherenceMonitor::default();
+        for minute in 0..(MAX_SAMPLES as i64 + 10) {
+            monitor.record(sample(minute, 50.0, 40.0));
+        }
+        assert_eq!(monitor.samples.len(), MAX_SAMPLES);
+        assert_eq!(monitor.samples[0].timestamp, at(10));
+    }
+}
@@ -0,0 +1,221 @@
+//! At-rest encryption for stored circuit payloads and results.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Job stores persist circuits and results as JSON text. For deployments
+//! where circuit structure is proprietary, a store can be given a
+//! [`PayloadCipher`] and will then seal those payloads with AES-256-GCM
+//! before writing them. Sealed values are self-describing text of the form
+//!
+//! ```text
+//! arvak-enc:v1:<base64(nonce || ciphertext || tag)>
+//! ```
+//!
+//! so they fit in the existing `TEXT` columns and files. Values written
+//! before encryption was enabled carry no prefix and are read back as-is.
+//!
+//! The 256-bit key is either taken from the `ARVAK_STORAGE_KEY` environment
+//! variable (base64) via [`PayloadCipher::from_env`], or fetched from a KMS
+//! by the caller and passed to [`PayloadCipher::new`].
+
+use std::fmt;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use thiserror::Error;
+
+/// Environment variable holding the base64-encoded 256-bit storage key.
+pub const STORAGE_KEY_ENV: &str = "ARVAK_STORAGE_KEY";
+
+/// Prefix marking a sealed payload.
+const SEALED_PREFIX: &str = "arvak-enc:v1:";
+
+/// Length of an AES-256 key in bytes.
+const KEY_LEN: usize = 32;
+
+/// Length of an AES-GCM nonce in bytes.
+const NONCE_LEN: usize = 12;
+
+/// Errors from sealing or opening stored payloads.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EncryptionError {
+    /// The key is malformed or has the wrong length.
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+
+    /// Encryption failed.
+    #[error("Failed to encrypt payload")]
+    Encrypt,
+
+    /// The payload is corrupt or was sealed with a different key.
+    #[error("Failed to decrypt payload: {0}")]
+    Decrypt(String),
+
+    /// The payload is sealed but no key was configured.
+    #[error("Payload is encrypted but no storage key is configured (set {STORAGE_KEY_ENV})")]
+    MissingKey,
+}
+
+/// AES-256-GCM cipher for payloads at rest.
+///
+/// Every call to [`seal`](Self::seal) uses a fresh random nonce, so sealing
+/// the same plaintext twice yields different ciphertexts.
+#[derive(Clone)]
+pub struct PayloadCipher {
+    cipher: Aes256Gcm,
+}
+
+impl PayloadCipher {
+    /// Create a cipher from a raw 256-bit key, e.g. one unwrapped by a KMS.
+    pub fn new(key: &[u8]) -> Result<Self, EncryptionError> {
+        if key.len() != KEY_LEN {
+            return Err(EncryptionError::InvalidKey(format!(
+                "expected {KEY_LEN} bytes, got {}",
+                key.len()
+            )));
+        }
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        })
+    }
+
+    /// Create a cipher from a base64-encoded 256-bit key.
+    pub fn from_base64(key: &str) -> Result<Self, EncryptionError> {
+        let bytes = BASE64
+            .decode(key.trim())
+            .map_err(|e| EncryptionError::InvalidKey(e.to_string()))?;
+        Self::new(&bytes)
+    }
+
+    /// Create a cipher from the `ARVAK_STORAGE_KEY` environment variable.
+    ///
+    /// Returns `Ok(None)` when the variable is unset or empty, so callers can
+    /// leave encryption disabled by default.
+    pub fn from_env() -> Result<Option<Self>, EncryptionError> {
+        match std::env::var(STORAGE_KEY_ENV) {
+            Ok(key) if !key.trim().is_empty() => Self::from_base64(&key).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Encrypt a payload into its sealed text form.
+    pub fn seal(&self, plaintext: &str) -> Result<String, EncryptionError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        blob.extend_from_slice(&nonce);
+        blob.extend_from_slice(&ciphertext);
+        Ok(format!("{SEALED_PREFIX}{}", BASE64.encode(blob)))
+    }
+
+    /// Decrypt a stored payload.
+    ///
+    /// Payloads without the sealed prefix were written before encryption was
+    /// enabled and are returned unchanged.
+    pub fn open(&self, stored: &str) -> Result<String, EncryptionError> {
+        let Some(encoded) = stored.strip_prefix(SEALED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+
+        let blob = BASE64
+            .decode(encoded)
+            .map_err(|e| EncryptionError::Decrypt(e.to_string()))?;
+        if blob.len() < NONCE_LEN {
+            return Err(EncryptionError::Decrypt("payload too short".into()));
+        }
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::Decrypt("authentication failed".into()))?;
+
+        String::from_utf8(plaintext).map_err(|e| EncryptionError::Decrypt(e.to_string()))
+    }
+}
+
+impl fmt::Debug for PayloadCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadCipher")
+            .field("key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Returns `true` if a stored payload is sealed.
+pub fn is_sealed(stored: &str) -> bool {
+    stored.starts_with(SEALED_PREFIX)
+}
+
+/// Seal a payload if a cipher is configured, otherwise return it unchanged.
+pub fn seal_with(
+    cipher: Option<&PayloadCipher>,
+    plaintext: String,
+) -> Result<String, EncryptionError> {
+    match cipher {
+        Some(cipher) => cipher.seal(&plaintext),
+        None => Ok(plaintext),
+    }
+}
+
+/// Open a stored payload, failing if it is sealed and no cipher is configured.
+pub fn open_with(
+    cipher: Option<&PayloadCipher>,
+    stored: String,
+) -> Result<String, EncryptionError> {
+    match cipher {
+        Some(cipher) => cipher.open(&stored),
+        None if is_sealed(&stored) => Err(EncryptionError::MissingKey),
+        None => Ok(stored),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let cipher = PayloadCipher::new(&KEY).unwrap();
+        let payload = r#"{"counts":{"00":512,"11":512}}"#;
+
+        let sealed = cipher.seal(payload).unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("counts"));
+        assert_ne!(sealed, cipher.seal(payload).unwrap(), "nonce must be fresh");
+        assert_eq!(cipher.open(&sealed).unwrap(), payload);
+
+        // Unsealed legacy payloads pass through.
+        assert_eq!(cipher.open(payload).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_key_and_missing_key() {
+        let sealed = PayloadCipher::new(&KEY).unwrap().seal("secret").unwrap();
+
+        let other = PayloadCipher::new(&[8; 32]).unwrap();
+        assert!(matches!(
+            other.open(&sealed),
+            Err(EncryptionError::Decrypt(_))
+        ));
+        assert!(matches!(
+            open_with(None, sealed),
+            Err(EncryptionError::MissingKey)
+        ));
+    }
+
+    #[test]
+    fn test_key_validation() {
+        assert!(PayloadCipher::new(&[0; 16]).is_err());
+        assert!(PayloadCipher::from_base64("not base64!").is_err());
+        assert!(PayloadCipher::from_base64(&BASE64.encode(KEY)).is_ok());
+    }
+}
@@ -0,0 +1,330 @@
+//! Heartbeats from long-running jobs.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! A hardware job can sit in a provider's queue for hours. Instead of
+//! staying silent until it finishes,
+//! [`Backend::wait_with_heartbeats`](crate::Backend::wait_with_heartbeats)
+//! publishes a [`Heartbeat`] on a [`HeartbeatSender`] whenever the job
+//! enters the queue, moves up in it, changes phase or starts running, and
+//! at least once per interval while nothing changes. Any number of
+//! consumers — a CLI spinner, a job executor, a dashboard — subscribe to
+//! the same sender.
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use arvak_hal::heartbeat::{HeartbeatKind, HeartbeatTracker};
+//! use arvak_hal::JobPhase;
+//! use tokio::time::Instant;
+//!
+//! let mut tracker = HeartbeatTracker::new(Duration::from_secs(60));
+//! let start = Instant::now();
+//!
+//! assert_eq!(tracker.observe(JobPhase::Queued, Some(12), start), Some(HeartbeatKind::Queued));
+//! assert_eq!(
+//!     tracker.observe(JobPhase::Queued, Some(3), start + Duration::from_secs(5)),
+//!     Some(HeartbeatKind::PositionChanged { previous: Some(12) })
+//! );
+//! assert_eq!(tracker.observe(JobPhase::Queued, Some(3), start + Duration::from_secs(10)), None);
+//! assert_eq!(
+//!     tracker.observe(JobPhase::Queued, Some(3), start + Duration::from_secs(70)),
+//!     Some(HeartbeatKind::StillWaiting)
+//! );
+//! ```
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+use crate::job::{JobId, JobPhase};
+
+/// Heartbeats a slow subscriber may fall behind by before it misses some.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Default time between heartbeats while nothing changes.
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default time between polls of the provider.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Why a heartbeat was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeartbeatKind {
+    /// The job was first seen waiting in the provider's queue.
+    Queued,
+    /// The job's position in the queue changed.
+    PositionChanged {
+        /// Position before the change, if it was known.
+        previous: Option<u32>,
+    },
+    /// The job moved to another phase before starting to run.
+    PhaseChanged {
+        /// Phase before the change, or `None` on the first observation.
+        previous: Option<JobPhase>,
+    },
+    /// The job started running on the device.
+    Started,
+    /// Nothing changed for a whole heartbeat interval.
+    StillWaiting,
+}
+
+/// One sign of life from an unfinished job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    /// Provider job ID.
+    pub job_id: JobId,
+    /// Name of the backend running the job.
+    pub backend: String,
+    /// Why the heartbeat was sent.
+    pub kind: HeartbeatKind,
+    /// Phase the job is in.
+    pub phase: JobPhase,
+    /// Position in the provider's queue, if the provider reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<u32>,
+    /// Seconds since waiting for the job began.
+    pub elapsed_secs: u64,
+    /// Time the heartbeat was sent.
+    pub at: DateTime<Utc>,
+}
+
+impl Heartbeat {
+    /// Short human-readable description, e.g. `queued (position 3)`.
+    pub fn describe(&self) -> String {
+        match (self.phase, self.queue_position) {
+            (JobPhase::Queued, Some(position)) => format!("queued (position {position})"),
+            (phase, _) => phase.as_str().to_string(),
+        }
+    }
+}
+
+/// Publishes heartbeats to every subscriber.
+///
+/// Cloning yields another handle to the same channel. Heartbeats sent
+/// while nobody is subscribed are dropped.
+#[derive(Debug, Clone)]
+pub struct HeartbeatSender {
+    sender: broadcast::Sender<Heartbeat>,
+    interval: Duration,
+    poll_interval: Duration,
+}
+
+impl HeartbeatSender {
+    /// Create a sender with the default intervals.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            interval: DEFAULT_HEARTBEAT_INTERVAL,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Send a heartbeat at least this often while nothing changes.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Poll the provider this often.
+    #[must_use]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Time between heartbeats while nothing changes.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Time between polls of the provider.
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Receive every heartbeat sent from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<Heartbeat> {
+        self.sender.subscribe()
+    }
+
+    /// Publish a heartbeat.
+    pub fn send(&self, heartbeat: Heartbeat) {
+        // An error only means nobody is listening.
+        let _ = self.sender.send(heartbeat);
+    }
+}
+
+impl Default for HeartbeatSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decides from successive observations of a job when a heartbeat is due.
+///
+/// Backends that override `wait_with_heartbeats`, e.g. to follow a
+/// provider's push notifications, can feed it whatever they observe.
+#[derive(Debug, Clone)]
+pub struct HeartbeatTracker {
+    interval: Duration,
+    started_at: Option<Instant>,
+    last_sent: Option<Instant>,
+    phase: Option<JobPhase>,
+    queue_position: Option<u32>,
+    running: bool,
+}
+
+impl HeartbeatTracker {
+    /// Track a job, sending a heartbeat at least every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            started_at: None,
+            last_sent: None,
+            phase: None,
+            queue_position: None,
+            running: false,
+        }
+    }
+
+    /// Record an observation made at `now` and return the kind of
+    /// heartbeat it calls for, if any.
+    pub fn observe(
+        &mut self,
+        phase: JobPhase,
+        queue_position: Option<u32>,
+        now: Instant,
+    ) -> Option<HeartbeatKind> {
+        self.started_at.get_or_insert(now);
+        let previous_phase = self.phase.replace(phase);
+        let previous_position = std::mem::replace(&mut self.queue_position, queue_position);
+
+        let kind = if phase == JobPhase::Running && !self.running {
+            self.running = true;
+            Some(HeartbeatKind::Started)
+        } else if previous_phase != Some(phase) {
+            if phase == JobPhase::Queued && previous_phase.is_none() {
+                Some(HeartbeatKind::Queued)
+            } else {
+                Some(HeartbeatKind::PhaseChanged {
+                    previous: previous_phase,
+                })
+            }
+        } else if queue_position.is_some() && queue_position != previous_position {
+            Some(HeartbeatKind::PositionChanged {
+                previous: previous_position,
+            })
+        } else if self
+            .last_sent
+            .is_some_and(|sent| now.duration_since(sent) >= self.interval)
+        {
+            Some(HeartbeatKind::StillWaiting)
+        } else {
+            None
+        };
+
+        if kind.is_some() {
+            self.last_sent = Some(now);
+        }
+        kind
+    }
+
+    /// Time since the first observation.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.started_at
+            .map_or(Duration::ZERO, |start| now.duration_since(start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_follows_job_through_phases() {
+        let mut tracker = HeartbeatTracker::new(Duration::from_secs(30));
+        let t0 = Instant::now();
+        let at = |secs| t0 + Duration::from_secs(secs);
+
+        assert_eq!(
+            tracker.observe(JobPhase::Validating, None, at(0)),
+            Some(HeartbeatKind::PhaseChanged { previous: None })
+        );
+        assert_eq!(
+            tracker.observe(JobPhase::Queued, Some(5), at(5)),
+            Some(HeartbeatKind::PhaseChanged {
+                previous: Some(JobPhase::Validating)
+            })
+        );
+        assert_eq!(
+            tracker.observe(JobPhase::Queued, Some(4), at(10)),
+            Some(HeartbeatKind::PositionChanged { previous: Some(5) })
+        );
+        assert_eq!(tracker.observe(JobPhase::Queued, Some(4), at(20)), None);
+        assert_eq!(
+            tracker.observe(JobPhase::Queued, Some(4), at(40)),
+            Some(HeartbeatKind::StillWaiting)
+        );
+        assert_eq!(
+            tracker.observe(JobPhase::Running, None, at(45)),
+            Some(HeartbeatKind::Started)
+        );
+        assert_eq!(tracker.observe(JobPhase::Running, None, at(50)), None);
+        assert_eq!(
+            tracker.observe(JobPhase::Running, None, at(75)),
+            Some(HeartbeatKind::StillWaiting)
+        );
+        assert_eq!(tracker.elapsed(at(75)), Duration::from_secs(75));
+    }
+
+    #[test]
+    fn test_started_is_sent_once() {
+        let mut tracker = HeartbeatTracker::new(Duration::from_secs(30));
+        let t0 = Instant::now();
+
+        assert_eq!(
+            tracker.observe(JobPhase::Running, None, t0),
+            Some(HeartbeatKind::Started)
+        );
+        assert_eq!(
+            tracker.observe(JobPhase::Calibrating, None, t0 + Duration::from_secs(1)),
+            Some(HeartbeatKind::PhaseChanged {
+                previous: Some(JobPhase::Running)
+            })
+        );
+        assert_eq!(
+            tracker.observe(JobPhase::Running, None, t0 + Duration::from_secs(2)),
+            Some(HeartbeatKind::PhaseChanged {
+                previous: Some(JobPhase::Calibrating)
+            })
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let heartbeat = Heartbeat {
+            job_id: JobId::new("job-1"),
+            backend: "iqm".to_string(),
+            kind: HeartbeatKind::Queued,
+            phase: JobPhase::Queued,
+            queue_position: Some(3),
+            elapsed_secs: 0,
+            at: Utc::now(),
+        };
+        assert_eq!(heartbeat.describe(), "queued (position 3)");
+        assert_eq!(
+            Heartbeat {
+                phase: JobPhase::Running,
+                ..heartbeat
+            }
+            .describe(),
+            "running"
+        );
+    }
+}
@@ -0,0 +1,496 @@
+//! Importing results recorded by other toolchains.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Results collected with provider SDKs before a site moved to Arvak are
+//! stored in the providers' own JSON formats. The parsers here turn them
+//! into [`ExecutionResult`]s, so they can be analysed, mitigated and
+//! benchmarked like results of Arvak jobs:
+//!
+//! | Format | Source | Bitstring keys |
+//! |--------|--------|----------------|
+//! | [`ResultFormat::Qiskit`] | `Result.to_dict()` | Hex (`"0x3"`) or binary, clbit 0 rightmost |
+//! | [`ResultFormat::Braket`] | `GateModelTaskResult` JSON | Qubit 0 leftmost |
+//! | [`ResultFormat::Iqm`] | IQM client `RunResult` measurements or Resonance `measurement_counts` | First measurement key leftmost |
+//!
+//! Keys are rewritten to the HAL convention, with the lowest-indexed bit
+//! rightmost. Every imported result records where it came from under the
+//! `"import"` key of its metadata:
+//!
+//! ```json
+//! {
+//!   "import": {
+//!     "format": "qiskit",
+//!     "backend": "ibm_brisbane",
+//!     "job_id": "cqx7...",
+//!     "experiment": "bell"
+//!   }
+//! }
+//! ```
+//!
+//! ```rust
+//! use arvak_hal::import::{ResultFormat, import_results};
+//!
+//! let json = r#"{
+//!     "backend_name": "aer_simulator",
+//!     "results": [{"shots": 100, "data": {"counts": {"0x0": 48, "0x3": 52}},
+//!                  "header": {"name": "bell", "memory_slots": 2}}]
+//! }"#;
+//! let results = import_results(json, None).unwrap();
+//! assert_eq!(ResultFormat::detect(&serde_json::from_str(json).unwrap()), Some(ResultFormat::Qiskit));
+//! assert_eq!(results[0].counts.get("11"), 52);
+//! ```
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde_json::{Map, Value, json};
+
+use crate::error::{HalError, HalResult};
+use crate::result::{Counts, ExecutionResult};
+
+/// Metadata key under which imported results record their origin.
+pub const IMPORT_METADATA_KEY: &str = "import";
+
+/// A provider-native result format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultFormat {
+    /// Qiskit `Result` dictionaries, one result per experiment.
+    Qiskit,
+    /// Amazon Braket gate-model task results.
+    Braket,
+    /// IQM measurement results, one result per circuit.
+    Iqm,
+}
+
+impl ResultFormat {
+    /// The format's name, as accepted by [`FromStr`].
+    pub fn name(self) -> &'static str {
+        match self {
+            ResultFormat::Qiskit => "qiskit",
+            ResultFormat::Braket => "braket",
+            ResultFormat::Iqm => "iqm",
+        }
+    }
+
+    /// Guess the format of a parsed result document from its shape.
+    pub fn detect(document: &Value) -> Option<Self> {
+        match document {
+            Value::Array(blocks) => blocks
+                .iter()
+                .all(|block| block.get("counts").is_some())
+                .then_some(ResultFormat::Iqm),
+            Value::Object(object) => {
+                if object.contains_key("braketSchemaHeader")
+                    || object.contains_key("taskMetadata")
+                    || object.contains_key("measuredQubits")
+                {
+                    Some(ResultFormat::Braket)
+                } else if object.get("results").is_some_and(Value::is_array) {
+                    Some(ResultFormat::Qiskit)
+                } else if object.get("measurements").is_some_and(Value::is_array) {
+                    Some(ResultFormat::Iqm)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ResultFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ResultFormat {
+    type Err = HalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "qiskit" => Ok(ResultFormat::Qiskit),
+            "braket" => Ok(ResultFormat::Braket),
+            "iqm" => Ok(ResultFormat::Iqm),
+            _ => Err(HalError::Configuration(format!(
+                "unknown result format '{s}' (expected qiskit, braket or iqm)"
+            ))),
+        }
+    }
+}
+
+/// Parse a provider result document into one result per circuit.
+///
+/// Without a `format` it is detected with [`ResultFormat::detect`].
+pub fn import_results(json: &str, format: Option<ResultFormat>) -> HalResult<Vec<ExecutionResult>> {
+    let document: Value = serde_json::from_str(json)?;
+    let format = match format {
+        Some(format) => format,
+        None => ResultFormat::detect(&document).ok_or_else(|| {
+            HalError::Configuration("cannot tell the result format; specify it".into())
+        })?,
+    };
+    match format {
+        ResultFormat::Qiskit => from_qiskit(&document),
+        ResultFormat::Braket => from_braket(&document).map(|result| vec![result]),
+        ResultFormat::Iqm => from_iqm(&document),
+    }
+}
+
+/// Read a provider result file; see [`import_results`].
+pub fn import_file(
+    path: impl AsRef<Path>,
+    format: Option<ResultFormat>,
+) -> HalResult<Vec<ExecutionResult>> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        HalError::Configuration(format!(
+            "Failed to read result file '{}': {e}",
+            path.display()
+        ))
+    })?;
+    import_results(&json, format)
+}
+
+/// Convert a Qiskit `Result.to_dict()` document, one result per
+/// experiment.
+pub fn from_qiskit(document: &Value) -> HalResult<Vec<ExecutionResult>> {
+    let experiments = document
+        .get("results")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid(ResultFormat::Qiskit, "missing 'results' list"))?;
+
+    experiments
+        .iter()
+        .enumerate()
+        .map(|(index, experiment)| {
+            let header = experiment.get("header");
+            let width = header
+                .and_then(|h| h.get("memory_slots"))
+                .and_then(Value::as_u64)
+                .map_or(0, |slots| slots as usize);
+            let raw = experiment
+                .pointer("/data/counts")
+                .and_then(Value::as_object)
+                .ok_or_else(|| {
+                    invalid(
+                        ResultFormat::Qiskit,
+                        format!("experiment {index} has no 'data.counts'"),
+                    )
+                })?;
+
+            let mut counts = Counts::new();
+            for (key, count) in raw {
+                let bits = match key.strip_prefix("0x") {
+                    Some(hex) => {
+                        let value = u128::from_str_radix(hex, 16).map_err(|_| {
+                            invalid(ResultFormat::Qiskit, format!("invalid count key '{key}'"))
+                        })?;
+                        format!("{value:0width$b}", width = width.max(1))
+                    }
+                    None => key.chars().filter(|c| !c.is_whitespace()).collect(),
+                };
+                counts.insert(bits, count_value(ResultFormat::Qiskit, key, count)?);
+            }
+
+            let shots = experiment
+                .get("shots")
+                .and_then(Value::as_u64)
+                .unwrap_or_else(|| counts.total_shots());
+            let mut result = ExecutionResult::new(counts, shots_u32(shots));
+            result.execution_time_ms = experiment
+                .get("time_taken")
+                .and_then(Value::as_f64)
+                .map(seconds_to_ms);
+            result.metadata = provenance(
+                ResultFormat::Qiskit,
+                [
+                    ("backend", document.get("backend_name")),
+                    ("job_id", document.get("job_id")),
+                    ("experiment", header.and_then(|h| h.get("name"))),
+                ],
+            );
+            Ok(result)
+        })
+        .collect()
+}
+
+/// Convert a Braket gate-model task result.
+///
+/// Uses `measurementCounts` when present, then the per-shot
+/// `measurements`, then `measurementProbabilities` scaled by the task's
+/// shot count.
+pub fn from_braket(document: &Value) -> HalResult<ExecutionResult> {
+    let reverse = |bits: &str| -> String { bits.chars().rev().collect() };
+    let metadata = document.get("taskMetadata");
+    let task_shots = metadata
+        .and_then(|m| m.get("shots"))
+        .and_then(Value::as_u64);
+
+    let mut counts = Counts::new();
+    if let Some(raw) = document.get("measurementCounts").and_then(Value::as_object) {
+        for (key, count) in raw {
+            counts.insert(reverse(key), count_value(ResultFormat::Braket, key, count)?);
+        }
+    } else if let Some(shots) = document.get("measurements").and_then(Value::as_array) {
+        for shot in shots {
+            let bits = shot
+                .as_array()
+                .ok_or_else(|| invalid(ResultFormat::Braket, "measurement is not a list"))?;
+            let key: String = bits
+                .iter()
+                .rev()
+                .map(|b| if b.as_u64() == Some(0) { '0' } else { '1' })
+                .collect();
+            counts.insert(key, 1);
+        }
+    } else if let Some(probabilities) = document
+        .get("measurementProbabilities")
+        .and_then(Value::as_object)
+    {
+        let shots = task_shots.ok_or_else(|| {
+            invalid(
+                ResultFormat::Braket,
+                "probabilities without 'taskMetadata.shots'",
+            )
+        })?;
+        for (key, probability) in probabilities {
+            let probability = probability.as_f64().unwrap_or_default();
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let count = (probability * shots as f64).round().max(0.0) as u64;
+            if count > 0 {
+                counts.insert(reverse(key), count);
+            }
+        }
+    } else {
+        return Err(invalid(ResultFormat::Braket, "no measurement data"));
+    }
+
+    let shots = task_shots.unwrap_or_else(|| counts.total_shots());
+    let mut result = ExecutionResult::new(counts, shots_u32(shots));
+    result.metadata = provenance(
+        ResultFormat::Braket,
+        [
+            ("backend", metadata.and_then(|m| m.get("deviceId"))),
+            ("job_id", metadata.and_then(|m| m.get("id"))),
+            ("measured_qubits", document.get("measuredQubits")),
+        ],
+    );
+    Ok(result)
+}
+
+/// Convert IQM measurements, one result per circuit.
+///
+/// Accepts a client `RunResult` (`{"measurements": [{key: [[bit], ...]}]}`,
+/// one list of shots per measurement key) and the Resonance
+/// `measurement_counts` artifact (`[{"measurement_keys": [...], "counts":
+/// {...}}]`). Measurement keys are ordered as the IQM client names them,
+/// by the numbers in their names, and the first ends up rightmost.
+pub fn from_iqm(document: &Value) -> HalResult<Vec<ExecutionResult>> {
+    if let Some(blocks) = document.as_array() {
+        return blocks
+            .iter()
+            .map(|block| {
+                let raw = block
+                    .get("counts")
+                    .and_then(Value::as_object)
+                    .ok_or_else(|| invalid(ResultFormat::Iqm, "block without 'counts'"))?;
+                let mut counts = Counts::new();
+                for (key, count) in raw {
+                    let bits: String = key.chars().rev().collect();
+                    counts.insert(bits, count_value(ResultFormat::Iqm, key, count)?);
+                }
+                let shots = counts.total_shots();
+                let mut result = ExecutionResult::new(counts, shots_u32(shots));
+                result.metadata = provenance(
+                    ResultFormat::Iqm,
+                    [("measurement_keys", block.get("measurement_keys"))],
+                );
+                Ok(result)
+            })
+            .collect();
+    }
+
+    let circuits = document
+        .get("measurements")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid(ResultFormat::Iqm, "missing 'measurements' list"))?;
+    let job_id = document.pointer("/metadata/job_id").or(document.get("id"));
+    circuits
+        .iter()
+        .map(|circuit| {
+            let keys = circuit
+                .as_object()
+                .ok_or_else(|| invalid(ResultFormat::Iqm, "circuit measurements are not a map"))?;
+            let mut ordered: Vec<(&String, &Value)> = keys.iter().collect();
+            ordered.sort_by_key(|(key, _)| key_order(key));
+
+            // Bits of every shot, in key order.
+            let mut shots: Vec<String> = Vec::new();
+            for (key, values) in &ordered {
+                let values = values.as_array().ok_or_else(|| {
+                    invalid(ResultFormat::Iqm, format!("key '{key}' has no shot list"))
+                })?;
+                if shots.is_empty() {
+                    shots = vec![String::new(); values.len()];
+                } else if shots.len() != values.len() {
+                    return Err(invalid(
+                        ResultFormat::Iqm,
+                        format!(
+                            "key '{key}' has {} shots, expected {}",
+                            values.len(),
+                            shots.len()
+                        ),
+                    ));
+                }
+                for (bits, value) in shots.iter_mut().zip(values) {
+                    let measured = match value {
+                        Value::Array(bits) => bits.clone(),
+                        single => vec![single.clone()],
+                    };
+                    for bit in measured {
+                        bits.push(if bit.as_u64() == Some(0) { '0' } else { '1' });
+                    }
+                }
+            }
+
+            let mut counts = Counts::new();
+            for bits in shots {
+                counts.insert(bits.chars().rev().collect::<String>(), 1);
+            }
+            let shots = counts.total_shots();
+            let mut result = ExecutionResult::new(counts, shots_u32(shots));
+            let keys: Vec<Value> = ordered.iter().map(|(key, _)| json!(key)).collect();
+            let keys = Value::Array(keys);
+            result.metadata = provenance(
+                ResultFormat::Iqm,
+                [("job_id", job_id), ("measurement_keys", Some(&keys))],
+            );
+            Ok(result)
+        })
+        .collect()
+}
+
+/// Sort key placing `c_0_0_10` after `c_0_0_9`.
+fn key_order(key: &str) -> Vec<(u64, String)> {
+    key.split('_')
+        .map(|part| match part.parse() {
+            Ok(number) => (number, String::new()),
+            Err(_) => (0, part.to_string()),
+        })
+        .collect()
+}
+
+/// The `"import"` metadata for a result, skipping absent fields.
+fn provenance<'a>(
+    format: ResultFormat,
+    fields: impl IntoIterator<Item = (&'static str, Option<&'a Value>)>,
+) -> Value {
+    let mut entry = Map::new();
+    entry.insert("format".into(), json!(format.name()));
+    for (name, value) in fields {
+        if let Some(value) = value.filter(|v| !v.is_null()) {
+            entry.insert(name.into(), value.clone());
+        }
+    }
+    json!({ IMPORT_METADATA_KEY: entry })
+}
+
+fn count_value(format: ResultFormat, key: &str, count: &Value) -> HalResult<u64> {
+    count
+        .as_u64()
+        .ok_or_else(|| invalid(format, format!("count of '{key}' is not a whole number")))
+}
+
+fn shots_u32(shots: u64) -> u32 {
+    u32::try_from(shots).unwrap_or(u32::MAX)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn seconds_to_ms(seconds: f64) -> u64 {
+    (seconds * 1000.0).round().max(0.0) as u64
+}
+
+fn invalid(format: ResultFormat, reason: impl fmt::Display) -> HalError {
+    HalError::Configuration(format!("Invalid {format} result: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_qiskit_result() {
+        let json = r#"{
+            "backend_name": "ibm_brisbane",
+            "job_id": "job-1",
+            "results": [
+                {"shots": 1000, "time_taken": 0.25,
+                 "data": {"counts": {"0x0": 490, "0x1": 10, "0x3": 500}},
+                 "header": {"name": "bell", "memory_slots": 2}},
+                {"shots": 10, "data": {"counts": {"1 01": 10}},
+                 "header": {"name": "split"}}
+            ]
+        }"#;
+        let results = import_results(json, None).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].counts.get("01"), 10);
+        assert_eq!(results[0].counts.get("11"), 500);
+        assert_eq!(results[0].shots, 1000);
+        assert_eq!(results[0].execution_time_ms, Some(250));
+        assert_eq!(results[0].metadata["import"]["experiment"], "bell");
+        assert_eq!(results[0].metadata["import"]["backend"], "ibm_brisbane");
+        assert_eq!(results[1].counts.get("101"), 10);
+    }
+
+    #[test]
+    fn test_import_braket_result() {
+        let json = r#"{
+            "braketSchemaHeader": {"name": "braket.task_result.gate_model_task_result", "version": "1"},
+            "measurements": [[0, 1], [0, 1], [1, 1]],
+            "measuredQubits": [0, 1],
+            "taskMetadata": {"id": "arn:task", "shots": 3, "deviceId": "arn:sv1"}
+        }"#;
+        let results = import_results(json, None).unwrap();
+        // Qubit 0 measured 0, qubit 1 measured 1: "10" in HAL order.
+        assert_eq!(results[0].counts.get("10"), 2);
+        assert_eq!(results[0].counts.get("11"), 1);
+        assert_eq!(results[0].metadata["import"]["backend"], "arn:sv1");
+
+        let counts = r#"{"measurementCounts": {"01": 7}, "measuredQubits": [0, 1]}"#;
+        let results = import_results(counts, Some(ResultFormat::Braket)).unwrap();
+        assert_eq!(results[0].counts.get("10"), 7);
+        assert_eq!(results[0].shots, 7);
+    }
+
+    #[test]
+    fn test_import_iqm_result() {
+        let json = r#"{
+            "status": "ready",
+            "measurements": [{
+                "c_0_0_1": [[1], [0], [1]],
+                "c_0_0_0": [[0], [0], [1]]
+            }],
+            "metadata": {"job_id": "iqm-1"}
+        }"#;
+        let results = import_results(json, None).unwrap();
+        assert_eq!(results[0].counts.get("10"), 1);
+        assert_eq!(results[0].counts.get("00"), 1);
+        assert_eq!(results[0].counts.get("11"), 1);
+        assert_eq!(results[0].metadata["import"]["job_id"], "iqm-1");
+
+        let artifact = r#"[{"measurement_keys": ["c_0_0_0", "c_0_0_1"], "counts": {"01": 4}}]"#;
+        let results = import_results(artifact, None).unwrap();
+        assert_eq!(results[0].counts.get("10"), 4);
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_documents() {
+        assert!(import_results(r#"{"foo": 1}"#, None).is_err());
+        assert!(import_results("[1, 2]", Some(ResultFormat::Iqm)).is_err());
+        assert!("cirq".parse::<ResultFormat>().is_err());
+        assert_eq!("IQM".parse::<ResultFormat>().unwrap(), ResultFormat::Iqm);
+    }
+}
@@ -22,15 +22,113 @@
 //!   is terminal for execution but may transition to `ResultExpired` when
 //!   the backend purges results.
 //! - `result()` is only valid when status is `Completed`.
+//!
+//! # Job phases (Arvak extension)
+//!
+//! HPC and cloud backends spend long stretches validating, compiling,
+//! staging and calibrating before a job executes, all of which the contract
+//! reports as `Queued` or `Running`. [`JobPhase`] names where an unfinished
+//! job actually is; every phase maps onto exactly one contract status, so
+//! clients that only understand [`JobStatus`] are unaffected.
 
 // ── Re-exported from HAL Contract spec ──────────────────────────────────────
 pub use hal_contract::job::{JobId, JobStatus};
 
 // ── Arvak extension ─────────────────────────────────────────────────────────
 
+use std::fmt;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Arvak extension — not part of HAL Contract v2 spec.
+/// Fine-grained phase of a job that has not finished yet.
+///
+/// Phases refine the non-terminal contract statuses: `Validating`,
+/// `Compiling`, `Transferring` and `Queued` are reported as
+/// [`JobStatus::Queued`], `Calibrating` and `Running` as
+/// [`JobStatus::Running`]. Backends report only the phases they can
+/// observe, in the order their provider goes through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPhase {
+    /// The provider is checking the circuit against the device.
+    Validating,
+    /// The circuit is being compiled or transpiled for the device.
+    Compiling,
+    /// The circuit or its data is being uploaded to the provider.
+    Transferring,
+    /// Waiting in the provider's queue.
+    Queued,
+    /// The device is being calibrated before the job executes.
+    Calibrating,
+    /// Executing on the device.
+    Running,
+}
+
+impl JobPhase {
+    /// All phases, in the order a job typically goes through them.
+    pub const ALL: [JobPhase; 6] = [
+        JobPhase::Validating,
+        JobPhase::Compiling,
+        JobPhase::Transferring,
+        JobPhase::Queued,
+        JobPhase::Calibrating,
+        JobPhase::Running,
+    ];
+
+    /// Lowercase name of the phase, as used in status strings.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Validating => "validating",
+            Self::Compiling => "compiling",
+            Self::Transferring => "transferring",
+            Self::Queued => "queued",
+            Self::Calibrating => "calibrating",
+            Self::Running => "running",
+        }
+    }
+
+    /// Parse a name returned by [`as_str`](Self::as_str).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "validating" => Some(Self::Validating),
+            "compiling" => Some(Self::Compiling),
+            "transferring" => Some(Self::Transferring),
+            "queued" => Some(Self::Queued),
+            "calibrating" => Some(Self::Calibrating),
+            "running" => Some(Self::Running),
+            _ => None,
+        }
+    }
+
+    /// The contract status this phase is reported as.
+    pub fn status(self) -> JobStatus {
+        match self {
+            Self::Validating | Self::Compiling | Self::Transferring | Self::Queued => {
+                JobStatus::Queued
+            }
+            Self::Calibrating | Self::Running => JobStatus::Running,
+        }
+    }
+
+    /// The coarse phase of a contract status, or `None` for terminal
+    /// statuses.
+    pub fn from_status(status: &JobStatus) -> Option<Self> {
+        match status {
+            JobStatus::Queued => Some(Self::Queued),
+            JobStatus::Running => Some(Self::Running),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for JobPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Arvak extension — not part of HAL Contract v2 spec.
 /// A job with metadata for orchestration tracking.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +137,9 @@ pub struct Job {
     pub id: JobId,
     /// Current status.
     pub status: JobStatus,
+    /// Fine-grained phase while the job is unfinished, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<JobPhase>,
     /// Number of shots requested.
     pub shots: u32,
     /// Time the job was created.
@@ -61,6 +162,7 @@ impl Job {
         Self {
             id: id.into(),
             status: JobStatus::Queued,
+            phase: None,
             shots,
             created_at: Some(Utc::now()),
             started_at: None,
@@ -75,9 +177,15 @@ impl Job {
         self
     }
 
-    /// Update the status.
+    /// Update the status. A phase that no longer matches it is cleared.
     pub fn with_status(mut self, status: JobStatus) -> Self {
         self.status = status;
+        if self
+            .phase
+            .is_some_and(|phase| phase.status() != self.status)
+        {
+            self.phase = None;
+        }
         if matches!(self.status, JobStatus::Running) && self.started_at.is_none() {
             self.started_at = Some(Utc::now());
         }
@@ -86,6 +194,13 @@ impl Job {
         }
         self
     }
+
+    /// Update the phase, and the status to the one it is reported as.
+    pub fn with_phase(mut self, phase: JobPhase) -> Self {
+        self = self.with_status(phase.status());
+        self.phase = Some(phase);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -110,4 +225,34 @@ mod tests {
         assert_eq!(job.backend, Some("simulator".to_string()));
         assert!(job.created_at.is_some());
     }
+
+    #[test]
+    fn test_job_phase_maps_to_status() {
+        assert_eq!(JobPhase::Transferring.status(), JobStatus::Queued);
+        assert_eq!(JobPhase::Calibrating.status(), JobStatus::Running);
+        assert_eq!(
+            JobPhase::from_status(&JobStatus::Running),
+            Some(JobPhase::Running)
+        );
+        assert_eq!(JobPhase::from_status(&JobStatus::Completed), None);
+        assert_eq!(
+            JobPhase::parse(JobPhase::Validating.as_str()),
+            Some(JobPhase::Validating)
+        );
+        assert_eq!(
+            serde_json::to_string(&JobPhase::Calibrating).unwrap(),
+            "\"calibrating\""
+        );
+    }
+
+    #[test]
+    fn test_job_with_phase() {
+        let job = Job::new("job-123", 100).with_phase(JobPhase::Calibrating);
+        assert_eq!(job.status, JobStatus::Running);
+        assert!(job.started_at.is_some());
+
+        let job = job.with_status(JobStatus::Completed);
+        assert_eq!(job.phase, None);
+        assert!(job.finished_at.is_some());
+    }
 }
@@ -10,6 +10,23 @@
 //! - [`Capabilities`] to describe hardware features and constraints
 //! - Authentication support for various providers (API tokens, OIDC)
 //! - Unified result handling via [`ExecutionResult`] and [`Counts`]
+//! - Expectation-value estimation via [`estimate`], offloaded to backends
+//!   that compute [`Observable`]s natively, with shot-noise error bars from
+//!   [`estimate_with_errors`]
+//! - Structured validation via [`ValidationReport`], with automatic fix-ups
+//!   through [`Backend::prepare`]
+//! - A shared [`ResponseCache`] for provider device metadata, with offline
+//!   fallback to the last known snapshot
+//! - Content-hash [`Provenance`] chains linking sources, compiled circuits,
+//!   submissions and results
+//! - Hardware [`attestation`] against enrolled device fingerprints
+//! - Live cooling [`telemetry`] with threshold checks
+//! - A [`DeadLetterQueue`] of [`FailureBundle`]s for diagnosing failed jobs
+//! - Multi-programmed jobs via [`run_multiprogram`], with counts split per
+//!   circuit
+//! - [`import_results`] for Qiskit, Braket and IQM result JSON recorded
+//!   outside Arvak
+//! - Seeded fault injection for recovery tests (`chaos` feature)
 //!
 //! # Supported Backends
 //!
@@ -125,20 +142,63 @@
 //! }
 //! ```
 
+pub mod attestation;
 pub mod auth;
 pub mod backend;
+pub mod build_info;
+pub mod cache;
 pub mod capability;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod config;
+pub mod dead_letter;
+pub mod decoherence;
+pub mod encryption;
 pub mod error;
+pub mod heartbeat;
+pub mod import;
 pub mod job;
+pub mod multiprogram;
+pub mod negotiation;
+pub mod observable;
 pub mod plugin;
+pub mod provenance;
 pub mod registry;
 pub mod result;
+pub mod retention;
+pub mod shots;
+pub mod signing;
+pub mod target;
+pub mod telemetry;
 
+pub use attestation::{Attestation, PufEnrollment};
 pub use auth::{CachedToken, EnvTokenProvider, OidcAuth, OidcConfig, TokenProvider};
-pub use backend::{Backend, BackendAvailability, BackendConfig, BackendFactory, ValidationResult};
+pub use backend::{
+    Backend, BackendAvailability, BackendConfig, BackendFactory, MaintenanceWindow,
+    ValidationResult,
+};
+pub use build_info::{BuildInfo, ComponentBuild};
+pub use cache::ResponseCache;
 pub use capability::{Capabilities, GateSet, NoiseProfile, Topology, TopologyKind};
+pub use config::{AdapterConfig, BackendsConfig};
+pub use dead_letter::{DeadLetterQueue, FailureBundle};
+pub use decoherence::{CoherenceAssessment, CoherenceVerdict, DecoherenceMonitor};
+pub use encryption::{EncryptionError, PayloadCipher};
 pub use error::{HalError, HalResult};
-pub use job::{Job, JobId, JobStatus};
+pub use heartbeat::{Heartbeat, HeartbeatKind, HeartbeatSender};
+pub use import::{ResultFormat, import_file, import_results};
+pub use job::{Job, JobId, JobPhase, JobStatus};
+pub use multiprogram::{demultiplex, run_multiprogram};
+pub use negotiation::{ValidationIssue, ValidationReport};
+pub use observable::{
+    EXPECTATION_VALUES_FEATURE, Estimate, Estimates, Observable, Pauli, PauliTerm, estimate,
+    estimate_with_errors, parity_covariance, parity_estimate,
+};
 pub use plugin::{BackendPlugin, PluginInfo};
+pub use provenance::{ArtifactId, Provenance};
 pub use registry::BackendRegistry;
 pub use result::{Counts, ExecutionResult};
+pub use shots::{run_split, run_split_with_heartbeats, split_shots};
+pub use signing::{CircuitSignature, CircuitSigner, SignatureError, SignaturePolicy};
+pub use target::{PassManagerBuilderExt, Target, transpile};
+pub use telemetry::{TelemetrySample, TelemetryThreshold};
@@ -0,0 +1,105 @@
+//! Running multi-programmed jobs.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! [`MultiProgramming`](arvak_compile::MultiProgramming) merges several
+//! small circuits into one circuit on disjoint patches of a large device.
+//! [`run_multiprogram`] submits it as one job and returns one
+//! [`ExecutionResult`] per input circuit, in input order; [`demultiplex`]
+//! splits counts that were obtained some other way. Each result records
+//! its circuit under the `"multiprogram"` key of its metadata:
+//!
+//! ```json
+//! {
+//!   "multiprogram": {
+//!     "index": 1,
+//!     "name": "ghz3",
+//!     "patch": [5, 6, 7],
+//!     "programs": 4
+//!   }
+//! }
+//! ```
+
+use arvak_compile::MultiProgram;
+
+use crate::backend::Backend;
+use crate::error::HalResult;
+use crate::result::{Counts, ExecutionResult};
+use crate::shots::run_split;
+
+/// Metadata key under which [`run_multiprogram`] records each circuit.
+pub const MULTIPROGRAM_METADATA_KEY: &str = "multiprogram";
+
+/// Split the counts of a multi-programmed job into counts per circuit.
+///
+/// Every circuit ran for every shot, so each returned [`Counts`] has the
+/// job's total shot count.
+pub fn demultiplex(program: &MultiProgram, counts: &Counts) -> Vec<Counts> {
+    let mut split = vec![Counts::new(); program.programs().len()];
+    for (bitstring, &count) in counts.iter() {
+        for (counts, bits) in split.iter_mut().zip(program.split_bitstring(bitstring)) {
+            counts.insert(bits, count);
+        }
+    }
+    split
+}
+
+/// Run a multi-programmed job and return one result per circuit.
+///
+/// Shots beyond the backend's `max_shots` are split across jobs as by
+/// [`run_split`].
+pub async fn run_multiprogram<B: Backend + ?Sized>(
+    backend: &B,
+    program: &MultiProgram,
+    shots: u32,
+) -> HalResult<Vec<ExecutionResult>> {
+    let result = run_split(backend, program.circuit(), shots, None).await?;
+    let results = demultiplex(program, &result.counts)
+        .into_iter()
+        .zip(program.programs())
+        .enumerate()
+        .map(|(index, (counts, slot))| {
+            let mut split = ExecutionResult::new(counts, result.shots);
+            split.execution_time_ms = result.execution_time_ms;
+            split.metadata = serde_json::json!({
+                MULTIPROGRAM_METADATA_KEY: {
+                    "index": index,
+                    "name": slot.name,
+                    "patch": slot.patch.qubits(),
+                    "programs": program.programs().len(),
+                }
+            });
+            split
+        })
+        .collect();
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arvak_compile::{BasisGates, CompileTarget, CouplingMap, MultiProgramming};
+    use arvak_ir::Circuit;
+
+    #[test]
+    fn test_demultiplex_counts() {
+        let target = CompileTarget::new(CouplingMap::linear(8), BasisGates::iqm());
+        let circuits = [Circuit::bell().unwrap(), Circuit::bell().unwrap()];
+        let program = MultiProgramming::new(target).compile(&circuits).unwrap();
+
+        let counts = Counts::from_pairs([
+            ("0000".to_string(), 400),
+            ("0011".to_string(), 100),
+            ("1100".to_string(), 300),
+            ("1111".to_string(), 200),
+        ]);
+        let split = demultiplex(&program, &counts);
+        assert_eq!(split.len(), 2);
+
+        assert_eq!(split[0].get("00"), 700);
+        assert_eq!(split[0].get("11"), 300);
+        assert_eq!(split[1].get("00"), 500);
+        assert_eq!(split[1].get("11"), 500);
+        assert!(split.iter().all(|c| c.total_shots() == 1000));
+    }
+}
@@ -0,0 +1,688 @@
+//! Structured validation and automatic fix-ups.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! [`ValidationResult`] reports problems as free text. A
+//! [`ValidationReport`] lists them as [`ValidationIssue`]s an orchestrator
+//! can act on: unsupported gates with the native gates they decompose into,
+//! two-qubit gates on uncoupled qubits with the affected edges, and shot
+//! counts with the batches [`run_split`](crate::shots::run_split) would
+//! submit. [`prepare`] (behind [`Backend::prepare`](crate::Backend::prepare))
+//! applies the circuit fix-ups by compiling for the backend's gate set and
+//! topology:
+//!
+//! ```rust
+//! use arvak_hal::Capabilities;
+//! use arvak_hal::negotiation::{ValidationIssue, ValidationReport, prepare};
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let caps = Capabilities::iqm("Garnet", 5);
+//! let mut circuit = Circuit::with_size("bell", 2, 0);
+//! circuit.h(QubitId(0)).unwrap().cx(QubitId(0), QubitId(1)).unwrap();
+//!
+//! let report = ValidationReport::check(&circuit, 1000, &caps);
+//! assert!(matches!(
+//!     &report.issues[0],
+//!     ValidationIssue::UnsupportedGate { gate, .. } if gate == "h"
+//! ));
+//! assert!(report.is_fixable());
+//!
+//! let fixed = prepare(&circuit, &caps).unwrap();
+//! assert!(ValidationReport::check(&fixed, 1000, &caps).is_valid());
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
+use arvak_compile::{BasisGates, CouplingMap, PassManagerBuilder};
+use arvak_ir::{Circuit, Instruction, QubitId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ValidationResult;
+use crate::capability::{Capabilities, TopologyKind};
+use crate::decoherence::CoherenceVerdict;
+use crate::error::{HalError, HalResult};
+use crate::shots::split_shots;
+use crate::target::transpile;
+
+/// A backend constraint a circuit or shot count violates.
+///
+/// Arvak extension — not part of HAL Contract v2 spec.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValidationIssue {
+    /// The circuit needs more qubits than the backend has.
+    TooManyQubits {
+        /// Qubits in the circuit.
+        required: u32,
+        /// Qubits on the backend.
+        available: u32,
+    },
+    /// The circuit has more operations than the backend accepts.
+    TooManyOperations {
+        /// Operations in the circuit.
+        operations: usize,
+        /// `Capabilities::max_circuit_ops`.
+        max: u32,
+    },
+    /// A gate outside the backend's gate set.
+    UnsupportedGate {
+        /// Gate name (OpenQASM 3 naming).
+        gate: String,
+        /// Number of occurrences in the circuit.
+        count: usize,
+        /// Native gates one occurrence compiles to, or `None` if the
+        /// compiler cannot decompose it for this backend.
+        decomposition: Option<Vec<String>>,
+    },
+    /// Two-qubit gates on a pair of qubits the topology does not couple.
+    ConnectivityViolation {
+        /// The uncoupled qubit pair, lower index first.
+        edge: (u32, u32),
+        /// Number of gates on the pair.
+        count: usize,
+        /// Whether the topology connects the pair through other qubits,
+        /// so routing can insert SWAPs.
+        routable: bool,
+    },
+    /// More shots than one job may request.
+    ShotsExceeded {
+        /// Requested shots.
+        requested: u32,
+        /// `Capabilities::max_shots`.
+        max_shots: u32,
+        /// Shots per job when split.
+        batches: Vec<u32>,
+    },
+    /// The circuit's estimated duration takes a large share of the
+    /// coherence time expected now (see [`crate::decoherence`]). A blocking
+    /// issue when it will likely decohere, a warning otherwise.
+    CoherenceBudget {
+        /// Estimated circuit duration in microseconds.
+        duration_us: f64,
+        /// The shorter of the expected T1 and T2, in microseconds.
+        coherence_us: f64,
+        /// Start of the next quiet window, when the backend reports them.
+        next_quiet_window: Option<DateTime<Utc>>,
+    },
+}
+
+impl ValidationIssue {
+    /// Whether [`prepare`] (for circuit issues) or
+    /// [`run_split`](crate::shots::run_split) (for shots) can resolve it.
+    pub fn is_fixable(&self) -> bool {
+        match self {
+            Self::TooManyQubits { .. }
+            | Self::TooManyOperations { .. }
+            | Self::CoherenceBudget { .. } => false,
+            Self::UnsupportedGate { decomposition, .. } => decomposition.is_some(),
+            Self::ConnectivityViolation { routable, .. } => *routable,
+            Self::ShotsExceeded { batches, .. } => !batches.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyQubits {
+                required,
+                available,
+            } => write!(
+                f,
+                "Circuit requires {required} qubits but backend only has {available}"
+            ),
+            Self::TooManyOperations { operations, max } => write!(
+                f,
+                "Circuit has {operations} operations but backend allows at most {max}"
+            ),
+            Self::UnsupportedGate {
+                gate,
+                count,
+                decomposition,
+            } => {
+                write!(f, "Unsupported gate: {gate} ({count}x)")?;
+                match decomposition {
+                    Some(gates) => write!(f, ", decomposes to {}", gates.join(" · ")),
+                    None => write!(f, ", no decomposition for this backend"),
+                }
+            }
+            Self::ConnectivityViolation {
+                edge: (a, b),
+                count,
+                routable,
+            } => {
+                write!(f, "Qubits {a} and {b} are not coupled ({count} gates)")?;
+                if !routable {
+                    write!(f, " and not connected by the topology")?;
+                }
+                Ok(())
+            }
+            Self::ShotsExceeded {
+                requested,
+                max_shots,
+                batches,
+            } => write!(
+                f,
+                "Requested {requested} shots but backend allows at most {max_shots}; \
+                 split into {} jobs",
+                batches.len()
+            ),
+            Self::CoherenceBudget {
+                duration_us,
+                coherence_us,
+                next_quiet_window,
+            } => {
+                write!(
+                    f,
+                    "Estimated duration {duration_us:.1} µs is {:.0}% of the expected \
+                     coherence time ({coherence_us:.1} µs)",
+                    duration_us / coherence_us * 100.0
+                )?;
+                if let Some(start) = next_quiet_window {
+                    write!(f, "; next quiet window starts {}", start.to_rfc3339())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// All constraints a circuit and shot count violate on a backend.
+///
+/// Arvak extension — not part of HAL Contract v2 spec.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Issues found, in the order checked.
+    pub issues: Vec<ValidationIssue>,
+    /// Problems that do not block submission but likely degrade results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Check `circuit` and `shots` against `capabilities`.
+    pub fn check(circuit: &Circuit, shots: u32, capabilities: &Capabilities) -> Self {
+        let mut report = Self::check_circuit(circuit, capabilities);
+        if shots > capabilities.max_shots {
+            let batches = if capabilities.max_shots == 0 {
+                Vec::new()
+            } else {
+                split_shots(shots, capabilities.max_shots)
+            };
+            report.issues.push(ValidationIssue::ShotsExceeded {
+                requested: shots,
+                max_shots: capabilities.max_shots,
+                batches,
+            });
+        }
+        report
+    }
+
+    /// Check `circuit` against `capabilities`, ignoring shots.
+    ///
+    /// Circuit qubit `i` is taken to be device qubit `i`, as on submission.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn check_circuit(circuit: &Circuit, capabilities: &Capabilities) -> Self {
+        let mut issues = Vec::new();
+
+        let num_qubits = circuit.num_qubits();
+        if num_qubits > capabilities.num_qubits as usize {
+            issues.push(ValidationIssue::TooManyQubits {
+                required: num_qubits as u32,
+                available: capabilities.num_qubits,
+            });
+        }
+
+        if let Some(max) = capabilities.max_circuit_ops {
+            let operations = circuit.dag().num_ops();
+            if operations > max as usize {
+                issues.push(ValidationIssue::TooManyOperations { operations, max });
+            }
+        }
+
+        // Occurrences per unsupported gate and per uncoupled pair, in order
+        // of first appearance.
+        let mut unsupported: Vec<(&Instruction, usize)> = Vec::new();
+        let mut uncoupled: Vec<((u32, u32), usize)> = Vec::new();
+        let fully_connected = matches!(capabilities.topology.kind, TopologyKind::FullyConnected);
+        for (_, inst) in circuit.dag().topological_ops() {
+            let Some(gate) = inst.as_gate() else {
+                continue;
+            };
+            let name = gate.name();
+            if !capabilities.gate_set.contains(name) {
+                match unsupported
+                    .iter_mut()
+                    .find(|(first, _)| first.name() == name)
+                {
+                    Some((_, count)) => *count += 1,
+                    None => unsupported.push((inst, 1)),
+                }
+            }
+            if let [a, b] = inst.qubits[..] {
+                let edge = (a.0.min(b.0), a.0.max(b.0));
+                if !fully_connected && !capabilities.topology.is_connected(edge.0, edge.1) {
+                    match uncoupled.iter_mut().find(|(e, _)| *e == edge) {
+                        Some((_, count)) => *count += 1,
+                        None => uncoupled.push((edge, 1)),
+                    }
+                }
+            }
+        }
+
+        for (inst, count) in unsupported {
+            issues.push(ValidationIssue::UnsupportedGate {
+                gate: inst.name().to_string(),
+                count,
+                decomposition: decompose(inst, capabilities),
+            });
+        }
+        if !uncoupled.is_empty() {
+            let map = coupling_map(capabilities);
+            for (edge, count) in uncoupled {
+                issues.push(ValidationIssue::ConnectivityViolation {
+                    edge,
+                    count,
+                    routable: map.distance(edge.0, edge.1).is_some(),
+                });
+            }
+        }
+
+        let mut warnings = Vec::new();
+        if let Some(assessment) = capabilities
+            .decoherence
+            .as_ref()
+            .and_then(|monitor| monitor.assess(circuit, Utc::now()))
+        {
+            let issue = ValidationIssue::CoherenceBudget {
+                duration_us: assessment.duration_us,
+                coherence_us: assessment.coherence_us(),
+                next_quiet_window: assessment.next_quiet_window.map(|w| w.start),
+            };
+            match assessment.verdict {
+                CoherenceVerdict::Reject => issues.push(issue),
+                CoherenceVerdict::Warn => warnings.push(issue),
+                CoherenceVerdict::Ok => {}
+            }
+        }
+
+        Self { issues, warnings }
+    }
+
+    /// Whether there are no issues. Warnings do not count.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Whether every issue can be fixed up.
+    pub fn is_fixable(&self) -> bool {
+        self.issues.iter().all(ValidationIssue::is_fixable)
+    }
+
+    /// The HAL Contract form of the report.
+    ///
+    /// Fixable circuit issues give `RequiresTranspilation`. Anything else,
+    /// including a shot count a single job cannot take, gives `Invalid`.
+    pub fn to_result(&self) -> ValidationResult {
+        if self.is_valid() {
+            return ValidationResult::Valid;
+        }
+        let blocking = |issue: &ValidationIssue| {
+            !issue.is_fixable() || matches!(issue, ValidationIssue::ShotsExceeded { .. })
+        };
+        if self.issues.iter().any(blocking) {
+            ValidationResult::Invalid {
+                reasons: self.issues.iter().map(ToString::to_string).collect(),
+            }
+        } else {
+            ValidationResult::RequiresTranspilation {
+                details: self.to_string(),
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Coupling map of a backend's topology.
+pub fn coupling_map(capabilities: &Capabilities) -> CouplingMap {
+    let n = capabilities.num_qubits;
+    match &capabilities.topology.kind {
+        TopologyKind::Linear => CouplingMap::linear(n),
+        TopologyKind::Star => CouplingMap::star(n),
+        TopologyKind::FullyConnected => CouplingMap::full(n),
+        TopologyKind::NeutralAtom { zones } => CouplingMap::zoned(n, *zones),
+        // Grid, Custom, and any future variants: build from edge list
+        _ => {
+            let mut map = CouplingMap::new(n);
+            for &(a, b) in &capabilities.topology.edges {
+                map.add_edge(a, b);
+            }
+            map.rebuild_caches();
+            map
+        }
+    }
+}
+
+/// Basis gates to compile to for a backend.
+///
+/// Uses the `native` gate list when non-empty (hardware backends), so the
+/// compiler decomposes non-native gates (e.g. `h` → `rz·sx·rz` on IBM Heron).
+/// Falls back to all supported gates for simulators (empty `native` list).
+pub fn basis_gates(capabilities: &Capabilities) -> BasisGates {
+    let gate_set = &capabilities.gate_set;
+    let mut gates: Vec<String> = if gate_set.native.is_empty() {
+        // Simulator: all supported gates are native — no decomposition needed.
+        let mut g = gate_set.single_qubit.clone();
+        g.extend(gate_set.two_qubit.iter().cloned());
+        g.extend(gate_set.three_qubit.iter().cloned());
+        g
+    } else {
+        // Hardware: compile only to truly native gates; non-native gates get
+        // decomposed by the BasisTranslation pass.
+        gate_set.native.clone()
+    };
+    // Always include measurement and barrier
+    if !gates.iter().any(|g| g == "measure") {
+        gates.push("measure".to_string());
+    }
+    if !gates.iter().any(|g| g == "barrier") {
+        gates.push("barrier".to_string());
+    }
+    BasisGates::new(gates)
+}
+
+/// Native gate names `inst` compiles to, without routing.
+#[allow(clippy::cast_possible_truncation)]
+fn decompose(inst: &Instruction, capabilities: &Capabilities) -> Option<Vec<String>> {
+    let width = inst.qubits.len() as u32;
+    let mut single = Circuit::with_size("decomposition", width, 0);
+    let mut gate = inst.clone();
+    gate.qubits = (0..width).map(QubitId).collect();
+    single.dag_mut().apply(gate).ok()?;
+
+    let (pm, mut props) = PassManagerBuilder::new()
+        .with_optimization_level(0)
+        .with_target(CouplingMap::full(width.max(1)), basis_gates(capabilities))
+        .build();
+    let mut dag = single.into_dag();
+    pm.run(&mut dag, &mut props).ok()?;
+
+    let names: Vec<String> = dag
+        .topological_ops()
+        .map(|(_, inst)| inst.name().to_string())
+        .collect();
+    names
+        .iter()
+        .all(|name| capabilities.gate_set.contains(name))
+        .then_some(names)
+}
+
+/// Fix up `circuit` to run on a backend with `capabilities`.
+///
+/// A circuit without issues is returned unchanged. Unsupported gates and
+/// connectivity violations are fixed by compiling at optimization level 1
+/// for the backend's native gates and topology, which may relabel qubits;
+/// measurements keep their classical bits, so counts are unaffected. Fails
+/// with `InvalidCircuit` listing the issues when any cannot be fixed.
+pub fn prepare(circuit: &Circuit, capabilities: &Capabilities) -> HalResult<Circuit> {
+    let report = ValidationReport::check_circuit(circuit, capabilities);
+    if report.is_valid() {
+        return Ok(circuit.clone());
+    }
+    if !report.is_fixable() {
+        return Err(HalError::InvalidCircuit(report.to_string()));
+    }
+
+    let fixed = transpile(circuit, capabilities, 1)
+        .map_err(|e| HalError::InvalidCircuit(format!("fix-up compilation failed: {e}")))?;
+
+    let remaining = ValidationReport::check_circuit(&fixed, capabilities);
+    if remaining.is_valid() {
+        Ok(fixed)
+    } else {
+        Err(HalError::InvalidCircuit(format!(
+            "fix-up left issues: {remaining}"
+        )))
+    }
+}
+
+/// Bind `parameters` into `circuit` before submission.
+///
+/// `parameters` maps the names of OpenQASM 3.0 `input float[64]`
+/// declarations to values, as passed to [`Backend::submit`](crate::Backend::submit).
+/// Without parameters the circuit is returned as is. Fails with
+/// `InvalidCircuit` naming any parameter left without a value, so a
+/// parametric circuit never reaches a backend half-bound.
+pub fn bind_parameters<'a>(
+    circuit: &'a Circuit,
+    parameters: Option<&HashMap<String, f64>>,
+) -> HalResult<Cow<'a, Circuit>> {
+    match parameters {
+        Some(values) if !values.is_empty() => circuit
+            .bind_parameters(values)
+            .map(Cow::Owned)
+            .map_err(|e| HalError::InvalidCircuit(e.to_string())),
+        _ => Ok(Cow::Borrowed(circuit)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capability::Topology;
+
+    fn bell() -> Circuit {
+        let mut circuit = Circuit::with_size("bell", 2, 2);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.measure_all().unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_valid_circuit() {
+        let caps = Capabilities::simulator(4);
+        let report = ValidationReport::check(&bell(), 1000, &caps);
+        assert!(report.is_valid());
+        assert_eq!(report.to_result(), ValidationResult::Valid);
+        let prepared = prepare(&bell(), &caps).unwrap();
+        assert_eq!(prepared.dag().num_ops(), bell().dag().num_ops());
+    }
+
+    #[test]
+    fn test_unsupported_gates_with_decompositions() {
+        let caps = Capabilities::iqm("Garnet", 5);
+        let mut circuit = bell();
+        circuit.h(QubitId(1)).unwrap();
+        let report = ValidationReport::check(&circuit, 1000, &caps);
+
+        assert_eq!(report.issues.len(), 2);
+        let ValidationIssue::UnsupportedGate {
+            gate,
+            count,
+            decomposition,
+        } = &report.issues[0]
+        else {
+            panic!("expected an unsupported gate, got {:?}", report.issues[0]);
+        };
+        assert_eq!((gate.as_str(), *count), ("h", 2));
+        assert!(decomposition.as_ref().unwrap().iter().all(|g| g == "prx"));
+        assert!(matches!(
+            &report.issues[1],
+            ValidationIssue::UnsupportedGate { gate, decomposition: Some(_), .. } if gate == "cx"
+        ));
+        assert!(matches!(
+            report.to_result(),
+            ValidationResult::RequiresTranspilation { .. }
+        ));
+
+        let fixed = prepare(&circuit, &caps).unwrap();
+        assert!(ValidationReport::check_circuit(&fixed, &caps).is_valid());
+    }
+
+    #[test]
+    fn test_connectivity_violations() {
+        let caps = Capabilities::simulator(4).with_topology(Topology::linear(4));
+        let mut circuit = Circuit::with_size("far", 4, 0);
+        circuit.cx(QubitId(0), QubitId(3)).unwrap();
+        circuit.cz(QubitId(3), QubitId(0)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+
+        let report = ValidationReport::check(&circuit, 10, &caps);
+        assert_eq!(
+            report.issues,
+            vec![ValidationIssue::ConnectivityViolation {
+                edge: (0, 3),
+                count: 2,
+                routable: true,
+            }]
+        );
+        assert_eq!(
+            report.to_string(),
+            "Qubits 0 and 3 are not coupled (2 gates)"
+        );
+
+        let fixed = prepare(&circuit, &caps).unwrap();
+        assert!(ValidationReport::check_circuit(&fixed, &caps).is_valid());
+
+        // Disconnected topology: nothing to route through.
+        let caps = Capabilities::simulator(4).with_topology(Topology::custom(vec![(0, 1)]));
+        let report = ValidationReport::check_circuit(&circuit, &caps);
+        assert!(!report.is_fixable());
+        assert!(matches!(
+            prepare(&circuit, &caps),
+            Err(HalError::InvalidCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn test_shots_split_plan() {
+        let mut caps = Capabilities::simulator(4);
+        caps.max_shots = 1000;
+        let report = ValidationReport::check(&bell(), 2500, &caps);
+        assert_eq!(
+            report.issues,
+            vec![ValidationIssue::ShotsExceeded {
+                requested: 2500,
+                max_shots: 1000,
+                batches: vec![1000, 1000, 500],
+            }]
+        );
+        assert!(report.is_fixable());
+        // A single job still cannot take the shots.
+        assert!(matches!(
+            report.to_result(),
+            ValidationResult::Invalid { .. }
+        ));
+        // The circuit itself needs no fix-up.
+        assert!(prepare(&bell(), &caps).is_ok());
+    }
+
+    #[test]
+    fn test_unfixable_issues() {
+        let caps = Capabilities::iqm("Garnet", 1);
+        let report = ValidationReport::check(&bell(), 10, &caps);
+        assert!(matches!(
+            report.issues[0],
+            ValidationIssue::TooManyQubits {
+                required: 2,
+                available: 1
+            }
+        ));
+        assert!(!report.is_fixable());
+        let ValidationResult::Invalid { reasons } = report.to_result() else {
+            panic!("expected invalid");
+        };
+        assert_eq!(
+            reasons[0],
+            "Circuit requires 2 qubits but backend only has 1"
+        );
+        assert!(prepare(&bell(), &caps).is_err());
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let issue = ValidationIssue::ConnectivityViolation {
+            edge: (0, 3),
+            count: 1,
+            routable: true,
+        };
+        let json = serde_json::to_value(&issue).unwrap();
+        assert_eq!(json["kind"], "connectivity_violation");
+        assert_eq!(json["edge"], serde_json::json!([0, 3]));
+    }
+
+    #[test]
+    fn test_bind_parameters() {
+        let mut circuit = Circuit::with_size("ansatz", 1, 0);
+        circuit
+            .ry(arvak_ir::ParameterExpression::symbol("theta"), QubitId(0))
+            .unwrap();
+
+        assert!(matches!(
+            bind_parameters(&circuit, None).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        let values = HashMap::from([("theta".to_string(), 0.5)]);
+        let bound = bind_parameters(&circuit, Some(&values)).unwrap();
+        assert!(bound.parameters().is_empty());
+
+        let wrong = HashMap::from([("phi".to_string(), 0.5)]);
+        let err = bind_parameters(&circuit, Some(&wrong)).unwrap_err();
+        assert!(matches!(err, HalError::InvalidCircuit(msg) if msg.contains("theta")));
+    }
+
+    #[test]
+    fn test_coherence_budget() {
+        use crate::decoherence::{CoherenceSample, DecoherenceMonitor};
+
+        // Bell: 0.05 + 0.3 + 1.5 = 1.85 µs with the default durations.
+        let monitor = |t2_us| {
+            DecoherenceMonitor::default().with_sample(CoherenceSample {
+                timestamp: Utc::now(),
+                t1_us: 100.0,
+                t2_us,
+            })
+        };
+        let caps = Capabilities::simulator(4);
+
+        let report = ValidationReport::check(&bell(), 100, &caps);
+        assert!(report.warnings.is_empty());
+
+        let ok = caps.clone().with_decoherence_monitor(monitor(100.0));
+        let report = ValidationReport::check(&bell(), 100, &ok);
+        assert!(report.is_valid());
+        assert!(report.warnings.is_empty());
+
+        let tight = caps.clone().with_decoherence_monitor(monitor(10.0));
+        let report = ValidationReport::check(&bell(), 100, &tight);
+        assert!(report.is_valid());
+        assert!(matches!(
+            report.warnings.as_slice(),
+            [ValidationIssue::CoherenceBudget { coherence_us, .. }] if (*coherence_us - 10.0).abs() < 1e-6
+        ));
+
+        let exceeded = caps.with_decoherence_monitor(monitor(3.0));
+        let report = ValidationReport::check(&bell(), 100, &exceeded);
+        assert!(!report.is_fixable());
+        assert!(matches!(
+            report.to_result(),
+            ValidationResult::Invalid { .. }
+        ));
+        assert!(report.to_string().contains("coherence time"));
+    }
+}
@@ -0,0 +1,663 @@
+//! Pauli observables and expectation-value estimation.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Some targets (CUDA-Q observe mode, analog simulators, statevector
+//! simulators) compute expectation values natively instead of returning
+//! counts. Such backends advertise the [`EXPECTATION_VALUES_FEATURE`] flag in
+//! `Capabilities::features` and implement [`Backend::submit_observable`].
+//!
+//! [`estimate`] is the estimator entry point: it offloads to the backend when
+//! the flag is present, and otherwise samples the circuit once per group of
+//! qubit-wise commuting Pauli terms, rotating each measured qubit into the
+//! Z basis and averaging the parity of the measured bits.
+//!
+//! [`estimate_with_errors`] returns the same values together with their
+//! covariance, so derived quantities such as energy differences carry error
+//! bars: terms measured in the same basis are correlated, terms measured in
+//! different jobs are independent. [`parity_estimate`] and
+//! [`parity_covariance`] give the same statistics for counts obtained some
+//! other way.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use arvak_ir::{Circuit, QubitId};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::backend::Backend;
+use crate::error::{HalError, HalResult};
+use crate::result::Counts;
+use crate::shots::run_split;
+
+/// Capability flag for backends that implement
+/// [`Backend::submit_observable`].
+pub const EXPECTATION_VALUES_FEATURE: &str = "expectation_values";
+
+/// A single-qubit Pauli operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Pauli {
+    /// Pauli X.
+    X,
+    /// Pauli Y.
+    Y,
+    /// Pauli Z.
+    Z,
+}
+
+impl fmt::Display for Pauli {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::X => write!(f, "X"),
+            Self::Y => write!(f, "Y"),
+            Self::Z => write!(f, "Z"),
+        }
+    }
+}
+
+/// A weighted Pauli string, e.g. `0.5 · Z0 Z1`.
+///
+/// Qubits not listed act as identity; an empty string is a constant offset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PauliTerm {
+    /// Real coefficient.
+    pub coeff: f64,
+    /// `(qubit, operator)` pairs, sorted by qubit.
+    pub paulis: Vec<(u32, Pauli)>,
+}
+
+impl PauliTerm {
+    /// Create a term; the operators are sorted by qubit.
+    pub fn new(coeff: f64, paulis: impl IntoIterator<Item = (u32, Pauli)>) -> Self {
+        let mut paulis: Vec<_> = paulis.into_iter().collect();
+        paulis.sort_by_key(|&(qubit, _)| qubit);
+        Self { coeff, paulis }
+    }
+
+    /// Whether the term is a constant (acts as identity on every qubit).
+    pub fn is_identity(&self) -> bool {
+        self.paulis.is_empty()
+    }
+}
+
+impl fmt::Display for PauliTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.coeff)?;
+        if self.paulis.is_empty() {
+            return Ok(());
+        }
+        write!(f, " *")?;
+        for (qubit, pauli) in &self.paulis {
+            write!(f, " {pauli}{qubit}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A Hermitian observable written as a real linear combination of Pauli
+/// strings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Observable {
+    /// Terms of the sum.
+    pub terms: Vec<PauliTerm>,
+}
+
+impl Observable {
+    /// Create an observable from its terms.
+    pub fn new(terms: Vec<PauliTerm>) -> Self {
+        Self { terms }
+    }
+
+    /// Add a term.
+    #[must_use]
+    pub fn with_term(mut self, coeff: f64, paulis: impl IntoIterator<Item = (u32, Pauli)>) -> Self {
+        self.terms.push(PauliTerm::new(coeff, paulis));
+        self
+    }
+
+    /// Highest qubit index acted on, or `None` for a constant observable.
+    pub fn max_qubit(&self) -> Option<u32> {
+        self.terms
+            .iter()
+            .filter_map(|term| term.paulis.last().map(|&(qubit, _)| qubit))
+            .max()
+    }
+
+    /// Check that every coefficient is finite and every term acts on
+    /// distinct qubits below `num_qubits`.
+    pub fn validate(&self, num_qubits: usize) -> HalResult<()> {
+        for term in &self.terms {
+            if !term.coeff.is_finite() {
+                return Err(HalError::InvalidCircuit(format!(
+                    "observable term '{term}' has a non-finite coefficient"
+                )));
+            }
+            if term.paulis.windows(2).any(|w| w[0].0 == w[1].0) {
+                return Err(HalError::InvalidCircuit(format!(
+                    "observable term '{term}' acts twice on the same qubit"
+                )));
+            }
+            if let Some(&(qubit, _)) = term.paulis.iter().find(|&&(q, _)| q as usize >= num_qubits)
+            {
+                return Err(HalError::InvalidCircuit(format!(
+                    "observable term '{term}' acts on qubit {qubit} but the circuit has {num_qubits} qubits"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An estimated value with its standard error.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    /// The point estimate.
+    pub value: f64,
+    /// Standard error of the estimate, `None` if the backend computed the
+    /// value without reporting its uncertainty.
+    pub std_error: Option<f64>,
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.std_error {
+            Some(std_error) => write!(f, "{} ± {}", self.value, std_error),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// Expectation values of several observables, with the covariance of their
+/// estimates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Estimates {
+    /// Expectation value of each observable.
+    pub values: Vec<f64>,
+    /// Covariance matrix of the estimates, `None` if the backend computed
+    /// the values natively.
+    pub covariance: Option<Vec<Vec<f64>>>,
+}
+
+impl Estimates {
+    /// The estimate of the `index`-th observable.
+    pub fn get(&self, index: usize) -> Option<Estimate> {
+        let value = *self.values.get(index)?;
+        let std_error = self
+            .covariance
+            .as_ref()
+            .map(|covariance| covariance[index][index].max(0.0).sqrt());
+        Some(Estimate { value, std_error })
+    }
+
+    /// The estimates of all observables, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Estimate> + '_ {
+        (0..self.values.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Estimate `Σ weights[i] · values[i]`, e.g. the difference of two
+    /// energies, with the error propagated through the covariance.
+    ///
+    /// Missing weights count as zero.
+    pub fn combine(&self, weights: &[f64]) -> Estimate {
+        let value = weights.iter().zip(&self.values).map(|(w, v)| w * v).sum();
+        let std_error = self.covariance.as_ref().map(|covariance| {
+            let variance: f64 = weights
+                .iter()
+                .zip(covariance)
+                .map(|(wa, row)| wa * weights.iter().zip(row).map(|(wb, c)| wb * c).sum::<f64>())
+                .sum();
+            variance.max(0.0).sqrt()
+        });
+        Estimate { value, std_error }
+    }
+}
+
+/// Estimate the expectation value of each observable in the state prepared
+/// by `circuit`.
+///
+/// Backends advertising [`EXPECTATION_VALUES_FEATURE`] evaluate the
+/// observables natively through [`Backend::submit_observable`]. Otherwise
+/// the terms of all observables are grouped by qubit-wise commuting
+/// measurement basis, and each group is sampled with `shots` shots (split
+/// across jobs as needed, see [`run_split`]).
+///
+/// `circuit` must not contain measurements: it prepares the state, and the
+/// sampling fallback appends the basis rotations and measurements itself.
+pub async fn estimate<B: Backend + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    observables: &[Observable],
+    shots: u32,
+) -> HalResult<Vec<f64>> {
+    Ok(estimate_with_errors(backend, circuit, observables, shots)
+        .await?
+        .values)
+}
+
+/// Like [`estimate`], but also returns the covariance of the estimates.
+///
+/// The covariance is the shot-noise covariance of the sampling fallback; it
+/// is `None` when the backend evaluated the observables natively.
+pub async fn estimate_with_errors<B: Backend + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    observables: &[Observable],
+    shots: u32,
+) -> HalResult<Estimates> {
+    for observable in observables {
+        observable.validate(circuit.num_qubits())?;
+    }
+
+    if backend.supports_observables() {
+        debug!(
+            "Offloading {} observables to '{}'",
+            observables.len(),
+            backend.name()
+        );
+        let values = backend
+            .submit_observable(circuit, observables, shots)
+            .await?;
+        if values.len() != observables.len() {
+            return Err(HalError::Backend(format!(
+                "backend '{}' returned {} expectation values for {} observables",
+                backend.name(),
+                values.len(),
+                observables.len()
+            )));
+        }
+        return Ok(Estimates {
+            values,
+            covariance: None,
+        });
+    }
+
+    sample_expectations(backend, circuit, observables, shots).await
+}
+
+/// Estimate `⟨P⟩` of a Pauli string from counts measured in its eigenbasis.
+///
+/// The standard error is `sqrt((1 - ⟨P⟩²) / shots)`. Bitstrings follow the
+/// HAL convention: the rightmost character is qubit 0.
+pub fn parity_estimate(counts: &Counts, paulis: &[(u32, Pauli)]) -> Estimate {
+    let (means, covariance) = sample_moments(counts, &[vec![(1.0, paulis)]]);
+    Estimate {
+        value: means[0],
+        std_error: Some(covariance[0][0].max(0.0).sqrt()),
+    }
+}
+
+/// Covariance of the estimates of `⟨P⟩` and `⟨Q⟩` from the same counts.
+///
+/// Both strings must be measured in the basis the counts were taken in,
+/// i.e. they commute qubit-wise.
+pub fn parity_covariance(counts: &Counts, p: &[(u32, Pauli)], q: &[(u32, Pauli)]) -> f64 {
+    let (_, covariance) = sample_moments(counts, &[vec![(1.0, p)], vec![(1.0, q)]]);
+    covariance[0][1]
+}
+
+/// Sampling fallback of [`estimate_with_errors`].
+async fn sample_expectations<B: Backend + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    observables: &[Observable],
+    shots: u32,
+) -> HalResult<Estimates> {
+    if shots == 0 {
+        return Err(HalError::InvalidShots(
+            "sampling expectation values requires at least one shot".into(),
+        ));
+    }
+    if circuit
+        .dag()
+        .topological_ops()
+        .any(|(_, inst)| inst.is_measure())
+    {
+        return Err(HalError::InvalidCircuit(
+            "circuit must not contain measurements when estimating expectation values".into(),
+        ));
+    }
+
+    let bases: Vec<&[(u32, Pauli)]> = observables
+        .iter()
+        .flat_map(|o| &o.terms)
+        .filter(|term| !term.is_identity())
+        .map(|term| term.paulis.as_slice())
+        .collect();
+    let groups = group_commuting(&bases);
+    debug!(
+        "Sampling {} Pauli terms in {} measurement bases on '{}'",
+        bases.len(),
+        groups.len(),
+        backend.name()
+    );
+
+    let mut group_counts = Vec::with_capacity(groups.len());
+    for basis in &groups {
+        let measured = basis_circuit(circuit, basis)?;
+        let result = run_split(backend, &measured, shots, None).await?;
+        group_counts.push(result.counts);
+    }
+
+    let group_of = |term: &PauliTerm| {
+        groups
+            .iter()
+            .position(|basis| {
+                term.paulis
+                    .iter()
+                    .all(|(qubit, pauli)| basis.get(qubit) == Some(pauli))
+            })
+            .expect("every term belongs to a measurement group")
+    };
+
+    // Identity terms are exact; each group adds the mean and covariance of
+    // the terms it measured. Groups are sampled in separate jobs, so their
+    // estimates are independent and their covariances add.
+    let mut values: Vec<f64> = observables
+        .iter()
+        .map(|o| {
+            o.terms
+                .iter()
+                .filter(|t| t.is_identity())
+                .map(|t| t.coeff)
+                .sum()
+        })
+        .collect();
+    let mut covariance = vec![vec![0.0; observables.len()]; observables.len()];
+    for (group, counts) in group_counts.iter().enumerate() {
+        let weighted: Vec<WeightedTerms<'_>> = observables
+            .iter()
+            .map(|o| {
+                o.terms
+                    .iter()
+                    .filter(|t| !t.is_identity() && group_of(t) == group)
+                    .map(|t| (t.coeff, t.paulis.as_slice()))
+                    .collect()
+            })
+            .collect();
+        let (means, group_covariance) = sample_moments(counts, &weighted);
+        for (value, mean) in values.iter_mut().zip(&means) {
+            *value += mean;
+        }
+        for (row, group_row) in covariance.iter_mut().zip(&group_covariance) {
+            for (entry, group_entry) in row.iter_mut().zip(group_row) {
+                *entry += group_entry;
+            }
+        }
+    }
+    Ok(Estimates {
+        values,
+        covariance: Some(covariance),
+    })
+}
+
+/// Greedily merge Pauli strings into qubit-wise commuting measurement bases.
+fn group_commuting(bases: &[&[(u32, Pauli)]]) -> Vec<BTreeMap<u32, Pauli>> {
+    let mut groups: Vec<BTreeMap<u32, Pauli>> = Vec::new();
+    for &paulis in bases {
+        match groups
+            .iter_mut()
+            .find(|basis| qubit_wise_commutes(basis, paulis))
+        {
+            Some(basis) => basis.extend(paulis.iter().copied()),
+            None => groups.push(paulis.iter().copied().collect()),
+        }
+    }
+    groups
+}
+
+/// Two Pauli strings commute qubit-wise if they agree wherever both act.
+fn qubit_wise_commutes(basis: &BTreeMap<u32, Pauli>, paulis: &[(u32, Pauli)]) -> bool {
+    paulis
+        .iter()
+        .all(|(qubit, pauli)| basis.get(qubit).is_none_or(|p| p == pauli))
+}
+
+/// Append the rotations into `basis` and measure every qubit.
+fn basis_circuit(circuit: &Circuit, basis: &BTreeMap<u32, Pauli>) -> HalResult<Circuit> {
+    let ir_err = |e: arvak_ir::IrError| HalError::InvalidCircuit(e.to_string());
+    let mut measured = circuit.clone();
+    for (&qubit, pauli) in basis {
+        let qubit = QubitId(qubit);
+        match pauli {
+            Pauli::X => {
+                measured.h(qubit).map_err(ir_err)?;
+            }
+            Pauli::Y => {
+                measured.sdg(qubit).map_err(ir_err)?;
+                measured.h(qubit).map_err(ir_err)?;
+            }
+            Pauli::Z => {}
+        }
+    }
+    measured.measure_all().map_err(ir_err)?;
+    Ok(measured)
+}
+
+/// Pauli strings with their coefficients, summed into one sampled quantity.
+type WeightedTerms<'a> = Vec<(f64, &'a [(u32, Pauli)])>;
+
+/// Means over `counts` of per-shot weighted parity sums, and the
+/// covariance of those means.
+///
+/// The per-shot value of quantity `a` is `Σ c · (-1)^parity` over the
+/// `(c, paulis)` terms in `weighted[a]`. Bitstrings follow the HAL
+/// convention: the rightmost character is qubit 0.
+fn sample_moments(counts: &Counts, weighted: &[WeightedTerms<'_>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = weighted.len();
+    let mut means = vec![0.0; n];
+    let mut covariance = vec![vec![0.0; n]; n];
+    let total = counts.total_shots();
+    if total == 0 {
+        return (means, covariance);
+    }
+
+    let mut shot = vec![0.0; n];
+    for (bitstring, &count) in counts.iter() {
+        let bits = bitstring.as_bytes();
+        for (value, terms) in shot.iter_mut().zip(weighted) {
+            *value = terms
+                .iter()
+                .map(|(coeff, paulis)| coeff * parity(bits, paulis))
+                .sum();
+        }
+        let weight = count as f64 / total as f64;
+        for (a, &x) in shot.iter().enumerate() {
+            means[a] += weight * x;
+            for (entry, &y) in covariance[a].iter_mut().zip(&shot) {
+                *entry += weight * x * y;
+            }
+        }
+    }
+
+    // Second moments to the covariance of the sample means.
+    for (a, row) in covariance.iter_mut().enumerate() {
+        for (b, entry) in row.iter_mut().enumerate() {
+            *entry = (*entry - means[a] * means[b]) / total as f64;
+        }
+    }
+    (means, covariance)
+}
+
+/// `(-1)^parity` of the measured bits of the term's qubits.
+fn parity(bits: &[u8], paulis: &[(u32, Pauli)]) -> f64 {
+    let odd = paulis
+        .iter()
+        .filter(|&&(qubit, _)| {
+            bits.len()
+                .checked_sub(qubit as usize + 1)
+                .is_some_and(|i| bits[i] == b'1')
+        })
+        .count()
+        % 2
+        == 1;
+    if odd { -1.0 } else { 1.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use crate::backend::{BackendAvailability, ValidationResult};
+    use crate::capability::Capabilities;
+    use crate::job::{JobId, JobStatus};
+    use crate::result::ExecutionResult;
+
+    /// Mock that returns a fixed histogram for every job, or fixed
+    /// expectation values when it advertises native support.
+    struct MockBackend {
+        capabilities: Capabilities,
+        counts: Counts,
+        jobs: Mutex<u32>,
+    }
+
+    impl MockBackend {
+        fn new(counts: Counts, native: bool) -> Self {
+            let mut capabilities = Capabilities::simulator(2);
+            if native {
+                capabilities
+                    .features
+                    .push(EXPECTATION_VALUES_FEATURE.into());
+            }
+            Self {
+                capabilities,
+                counts,
+                jobs: Mutex::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Backend for MockBackend {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn name(&self) -> &str {
+            "mock"
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn availability(&self) -> HalResult<BackendAvailability> {
+            Ok(BackendAvailability::always_available())
+        }
+        async fn validate(&self, _c: &Circuit, _shots: u32) -> HalResult<ValidationResult> {
+            Ok(ValidationResult::Valid)
+        }
+        async fn submit(
+            &self,
+            _c: &Circuit,
+            _shots: u32,
+            _parameters: Option<&HashMap<String, f64>>,
+        ) -> HalResult<JobId> {
+            *self.jobs.lock().unwrap() += 1;
+            Ok(JobId::new("job"))
+        }
+        async fn status(&self, _id: &JobId) -> HalResult<JobStatus> {
+            Ok(JobStatus::Completed)
+        }
+        async fn result(&self, _id: &JobId) -> HalResult<ExecutionResult> {
+            let shots = u32::try_from(self.counts.total_shots()).unwrap();
+            Ok(ExecutionResult::new(self.counts.clone(), shots))
+        }
+        async fn cancel(&self, _id: &JobId) -> HalResult<()> {
+            Ok(())
+        }
+        async fn submit_observable(
+            &self,
+            _c: &Circuit,
+            observables: &[Observable],
+            _shots: u32,
+        ) -> HalResult<Vec<f64>> {
+            Ok(vec![42.0; observables.len()])
+        }
+    }
+
+    fn bell_counts() -> Counts {
+        Counts::from_pairs([("00".to_string(), 600), ("11".to_string(), 400)])
+    }
+
+    #[tokio::test]
+    async fn test_estimate_by_sampling() {
+        let backend = MockBackend::new(bell_counts(), false);
+        let circuit = Circuit::with_size("state", 2, 0);
+        let zz = Observable::default().with_term(0.5, [(0, Pauli::Z), (1, Pauli::Z)]);
+        let z0 = Observable::default()
+            .with_term(1.0, [(0, Pauli::Z)])
+            .with_term(-2.0, []);
+        let xx = Observable::default().with_term(1.0, [(0, Pauli::X), (1, Pauli::X)]);
+
+        let values = estimate(&backend, &circuit, &[zz, z0, xx], 1000)
+            .await
+            .unwrap();
+        assert!((values[0] - 0.5).abs() < 1e-12);
+        assert!((values[1] - (0.2 - 2.0)).abs() < 1e-12);
+        assert!((values[2] - 1.0).abs() < 1e-12);
+        // ZZ and Z0 share a basis; XX needs its own.
+        assert_eq!(*backend.jobs.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_with_errors() {
+        let backend = MockBackend::new(bell_counts(), false);
+        let circuit = Circuit::with_size("state", 2, 0);
+        let z0 = Observable::default().with_term(1.0, [(0, Pauli::Z)]);
+        let z1 = Observable::default().with_term(2.0, [(1, Pauli::Z)]);
+        let zz = Observable::default().with_term(1.0, [(0, Pauli::Z), (1, Pauli::Z)]);
+
+        let estimates = estimate_with_errors(&backend, &circuit, &[z0, z1, zz], 1000)
+            .await
+            .unwrap();
+        // ⟨Z0⟩ = 0.2 from 1000 shots: variance (1 - 0.04) / 1000.
+        let variance: f64 = 0.96 / 1000.0;
+        let z0 = estimates.get(0).unwrap();
+        assert!((z0.value - 0.2).abs() < 1e-12);
+        assert!((z0.std_error.unwrap() - variance.sqrt()).abs() < 1e-12);
+        // ZZ is +1 on every shot.
+        assert!(estimates.get(2).unwrap().std_error.unwrap() < 1e-12);
+
+        // Z0 and Z1 are perfectly correlated, so 2·Z0 - Z1 has no error.
+        let covariance = estimates.covariance.as_ref().unwrap();
+        assert!((covariance[0][1] - 2.0 * variance).abs() < 1e-12);
+        let difference = estimates.combine(&[2.0, -1.0]);
+        assert!(difference.value.abs() < 1e-12);
+        assert!(difference.std_error.unwrap() < 1e-9);
+    }
+
+    #[test]
+    fn test_parity_statistics() {
+        let counts = bell_counts();
+        let z0 = [(0, Pauli::Z)];
+        let z1 = [(1, Pauli::Z)];
+        let estimate = parity_estimate(&counts, &z0);
+        assert!((estimate.value - 0.2).abs() < 1e-12);
+        assert!((estimate.std_error.unwrap() - (0.96f64 / 1000.0).sqrt()).abs() < 1e-12);
+        assert!((parity_covariance(&counts, &z0, &z1) - 0.96 / 1000.0).abs() < 1e-12);
+        assert_eq!(parity_estimate(&Counts::new(), &z0).to_string(), "0 ± 0");
+    }
+
+    #[tokio::test]
+    async fn test_estimate_offloads_when_supported() {
+        let backend = MockBackend::new(bell_counts(), true);
+        let circuit = Circuit::with_size("state", 2, 0);
+        let z0 = Observable::default().with_term(1.0, [(0, Pauli::Z)]);
+
+        let values = estimate(&backend, &circuit, &[z0.clone()], 1000)
+            .await
+            .unwrap();
+        assert_eq!(values, vec![42.0]);
+        let estimates = estimate_with_errors(&backend, &circuit, &[z0], 1000)
+            .await
+            .unwrap();
+        assert_eq!(estimates.get(0).unwrap().std_error, None);
+        assert_eq!(*backend.jobs.lock().unwrap(), 0);
+
+        let out_of_range = Observable::default().with_term(1.0, [(2, Pauli::Z)]);
+        assert!(matches!(
+            estimate(&backend, &circuit, &[out_of_range], 1000).await,
+            Err(HalError::InvalidCircuit(_))
+        ));
+    }
+}
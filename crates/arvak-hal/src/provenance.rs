@@ -0,0 +1,316 @@
+//! Result provenance: content-hash chains from source to result.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Every stage a circuit passes through gets an [`ArtifactId`]: a SHA-256
+//! over the stage's content and the id of the stage before it. The chain
+//! source → compiled → submission therefore pins down exactly which
+//! program, compiled how, ran where. Eval reports, compiled artifacts,
+//! scheduler jobs and execution results all carry the same [`Provenance`]
+//! record, so a result can be traced back to the source it came from.
+//!
+//! ```rust
+//! use arvak_hal::provenance::{Provenance, Stage};
+//! use arvak_ir::Circuit;
+//!
+//! let qasm = "OPENQASM 3.0;\nqubit[2] q;\nh q[0];\ncx q[0], q[1];\n";
+//! let source = Provenance::source(qasm);
+//! let compiled = source.compiled(Circuit::bell().unwrap().dag());
+//! let submission = compiled.submission("simulator", 1000, None);
+//!
+//! assert_eq!(submission.stage, Stage::Submission);
+//! assert_eq!(submission.parent(), Some(&compiled.artifact_id));
+//! assert!(submission.contains(&source.artifact_id));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use arvak_ir::CircuitDag;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::result::ExecutionResult;
+
+/// Key under which [`Provenance::attach`] stores the record in
+/// [`ExecutionResult::metadata`].
+pub const PROVENANCE_METADATA_KEY: &str = "provenance";
+
+/// Domain separator, bumped if the hashed content ever changes.
+const DOMAIN: &[u8] = b"arvak-provenance-v1\0";
+
+/// Content hash identifying one artifact in a provenance chain.
+///
+/// Displayed as 64 lowercase hex digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ArtifactId(String);
+
+impl ArtifactId {
+    fn derive(stage: Stage, parent: Option<&ArtifactId>, content: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(DOMAIN);
+        hasher.update(stage.as_str().as_bytes());
+        hasher.update([0]);
+        if let Some(parent) = parent {
+            hasher.update(parent.0.as_bytes());
+        }
+        hasher.update([0]);
+        hasher.update(content);
+        let hex = hasher
+            .finalize()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, b| {
+                let _ = write!(hex, "{b:02x}");
+                hex
+            });
+        Self(hex)
+    }
+
+    /// The full hex digest.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The first 12 hex digits, for display.
+    pub fn short(&self) -> &str {
+        &self.0[..12]
+    }
+
+    /// Check if `prefix` is a prefix of this id, ignoring case.
+    pub fn matches_prefix(&self, prefix: &str) -> bool {
+        !prefix.is_empty()
+            && prefix.len() <= self.0.len()
+            && self.0[..prefix.len()].eq_ignore_ascii_case(prefix)
+    }
+}
+
+impl fmt::Display for ArtifactId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for ArtifactId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            Ok(Self(s.to_ascii_lowercase()))
+        } else {
+            Err(format!("invalid artifact id '{s}': expected 64 hex digits"))
+        }
+    }
+}
+
+/// The stage of a circuit an artifact id refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    /// The program as written (QASM source).
+    Source,
+    /// The circuit after compilation.
+    Compiled,
+    /// A compiled circuit submitted with a backend and shot count.
+    Submission,
+}
+
+impl Stage {
+    /// Lowercase name, as serialized.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Stage::Source => "source",
+            Stage::Compiled => "compiled",
+            Stage::Submission => "submission",
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An artifact id together with the ids it was derived from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Id of this artifact.
+    pub artifact_id: ArtifactId,
+    /// Stage of this artifact.
+    pub stage: Stage,
+    /// Ids of the earlier stages, source first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ancestors: Vec<ArtifactId>,
+}
+
+impl Provenance {
+    /// Start a chain from program source text.
+    pub fn source(source: &str) -> Self {
+        Self {
+            artifact_id: ArtifactId::derive(Stage::Source, None, source.as_bytes()),
+            stage: Stage::Source,
+            ancestors: Vec::new(),
+        }
+    }
+
+    /// Start a chain from a compiled circuit whose source is unknown.
+    pub fn compiled_only(dag: &CircuitDag) -> Self {
+        Self {
+            artifact_id: ArtifactId::derive(Stage::Compiled, None, &dag.to_bytes()),
+            stage: Stage::Compiled,
+            ancestors: Vec::new(),
+        }
+    }
+
+    /// Derive the id of `dag`, compiled from this artifact.
+    #[must_use]
+    pub fn compiled(&self, dag: &CircuitDag) -> Self {
+        self.child(Stage::Compiled, &dag.to_bytes())
+    }
+
+    /// Derive the id of a submission of this artifact to `backend`.
+    ///
+    /// Parameter bindings are part of the content, so each point of a
+    /// sweep gets its own id.
+    #[must_use]
+    pub fn submission(
+        &self,
+        backend: &str,
+        shots: u32,
+        parameters: Option<&HashMap<String, f64>>,
+    ) -> Self {
+        let mut content = format!("{backend}\0{shots}");
+        if let Some(parameters) = parameters {
+            let mut bindings: Vec<_> = parameters.iter().collect();
+            bindings.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, value) in bindings {
+                let _ = write!(content, "\0{name}={value:e}");
+            }
+        }
+        self.child(Stage::Submission, content.as_bytes())
+    }
+
+    fn child(&self, stage: Stage, content: &[u8]) -> Self {
+        let mut ancestors = self.ancestors.clone();
+        ancestors.push(self.artifact_id.clone());
+        Self {
+            artifact_id: ArtifactId::derive(stage, Some(&self.artifact_id), content),
+            stage,
+            ancestors,
+        }
+    }
+
+    /// Id of the artifact this one was derived from.
+    pub fn parent(&self) -> Option<&ArtifactId> {
+        self.ancestors.last()
+    }
+
+    /// Check if `id` is this artifact or one of its ancestors.
+    pub fn contains(&self, id: &ArtifactId) -> bool {
+        &self.artifact_id == id || self.ancestors.contains(id)
+    }
+
+    /// All ids of the chain, source first, ending with this artifact.
+    pub fn chain(&self) -> impl Iterator<Item = &ArtifactId> {
+        self.ancestors
+            .iter()
+            .chain(std::iter::once(&self.artifact_id))
+    }
+
+    /// Record this provenance in `result`'s metadata under
+    /// [`PROVENANCE_METADATA_KEY`], keeping any other metadata.
+    pub fn attach(&self, result: &mut ExecutionResult) {
+        let value = serde_json::to_value(self).expect("provenance serializes");
+        match &mut result.metadata {
+            serde_json::Value::Object(map) => {
+                map.insert(PROVENANCE_METADATA_KEY.into(), value);
+            }
+            metadata => *metadata = serde_json::json!({ PROVENANCE_METADATA_KEY: value }),
+        }
+    }
+
+    /// Read the provenance recorded in `result`'s metadata, if any.
+    pub fn from_result(result: &ExecutionResult) -> Option<Self> {
+        result
+            .metadata
+            .get(PROVENANCE_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::Counts;
+    use arvak_ir::Circuit;
+
+    #[test]
+    fn test_chain() {
+        let source = Provenance::source("OPENQASM 3.0;");
+        assert_eq!(source.stage, Stage::Source);
+        assert!(source.parent().is_none());
+        assert_eq!(source.artifact_id.as_str().len(), 64);
+
+        let compiled = source.compiled(Circuit::bell().unwrap().dag());
+        let submission = compiled.submission("sim", 100, None);
+        assert_eq!(submission.ancestors.len(), 2);
+        assert_eq!(submission.parent(), Some(&compiled.artifact_id));
+        assert!(submission.contains(&source.artifact_id));
+        assert!(!compiled.contains(&submission.artifact_id));
+        assert_eq!(
+            submission.chain().collect::<Vec<_>>(),
+            vec![
+                &source.artifact_id,
+                &compiled.artifact_id,
+                &submission.artifact_id
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ids_are_deterministic() {
+        let a = Provenance::source("h q[0];");
+        assert_eq!(a, Provenance::source("h q[0];"));
+        assert_ne!(a, Provenance::source("x q[0];"));
+
+        // The same content under a different parent gets a different id.
+        let b = Provenance::source("x q[0];");
+        let dag = Circuit::bell().unwrap().into_dag();
+        assert_ne!(a.compiled(&dag).artifact_id, b.compiled(&dag).artifact_id);
+
+        let params = HashMap::from([("theta".to_string(), 0.5), ("phi".to_string(), 1.0)]);
+        assert_eq!(
+            a.submission("sim", 10, Some(&params)),
+            a.submission("sim", 10, Some(&params))
+        );
+        assert_ne!(
+            a.submission("sim", 10, Some(&params)).artifact_id,
+            a.submission("sim", 20, Some(&params)).artifact_id
+        );
+    }
+
+    #[test]
+    fn test_artifact_id_parsing() {
+        let id = Provenance::source("x").artifact_id;
+        let parsed: ArtifactId = id.as_str().to_uppercase().parse().unwrap();
+        assert_eq!(parsed, id);
+        assert!(id.matches_prefix(&id.short().to_uppercase()));
+        assert!(!id.matches_prefix(""));
+        assert!("abc".parse::<ArtifactId>().is_err());
+    }
+
+    #[test]
+    fn test_attach_to_result() {
+        let provenance = Provenance::source("x").submission("sim", 10, None);
+        let mut result = ExecutionResult::new(Counts::new(), 10);
+        assert!(Provenance::from_result(&result).is_none());
+
+        provenance.attach(&mut result);
+        result.metadata["other"] = serde_json::json!(1);
+        provenance.attach(&mut result);
+        assert_eq!(Provenance::from_result(&result), Some(provenance));
+        assert_eq!(result.metadata["other"], 1);
+    }
+}
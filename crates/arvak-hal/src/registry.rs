@@ -2,6 +2,21 @@
 //!
 //! The [`BackendRegistry`] provides a central point for discovering and
 //! creating backend instances, including dynamically loaded plugins.
+//!
+//! A registry can also hold the backend instances themselves, built from a
+//! [backend configuration document](crate::config), so deployments choose
+//! their backends at runtime rather than through compile-time wiring:
+//!
+//! ```ignore
+//! let registry = BackendRegistry::new()
+//!     .with_adapter::<SimulatorBackend>("simulator")
+//!     .with_adapter::<IqmBackend>("iqm")
+//!     .with_config("backends.toml")?;
+//! let backend = registry.instance("default").unwrap();
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
 
 use rustc_hash::FxHashMap;
 use tracing::debug;
@@ -9,6 +24,7 @@ use tracing::debug;
 use tracing::{info, warn};
 
 use crate::backend::{Backend, BackendConfig, BackendFactory};
+use crate::config::BackendsConfig;
 use crate::error::{HalError, HalResult};
 use crate::plugin::PluginInfo;
 
@@ -27,6 +43,10 @@ pub struct BackendRegistry {
     plugins: Vec<crate::plugin::LoadedPlugin>,
     /// Plugin metadata (available without dynamic-backends feature).
     plugin_infos: Vec<PluginInfo>,
+    /// Backend instances keyed by name.
+    instances: FxHashMap<String, Arc<dyn Backend>>,
+    /// Alternative instance names, mapping each alias to an instance name.
+    aliases: FxHashMap<String, String>,
 }
 
 impl BackendRegistry {
@@ -37,9 +57,41 @@ impl BackendRegistry {
             #[cfg(feature = "dynamic-backends")]
             plugins: Vec::new(),
             plugin_infos: Vec::new(),
+            instances: FxHashMap::default(),
+            aliases: FxHashMap::default(),
         }
     }
 
+    /// Build a registry from a backend configuration file.
+    ///
+    /// Adapters come from the plugins in the default plugin directory (with
+    /// the `dynamic-backends` feature). To use adapters linked into the
+    /// binary, register them first and call [`with_config`](Self::with_config).
+    pub fn from_config(path: impl AsRef<Path>) -> HalResult<Self> {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "dynamic-backends")]
+        registry.load_plugins()?;
+        registry.with_config(path)
+    }
+
+    /// Instantiate the backends of a configuration file, see
+    /// [`instantiate`](Self::instantiate).
+    pub fn with_config(mut self, path: impl AsRef<Path>) -> HalResult<Self> {
+        self.instantiate(&BackendsConfig::from_file(path)?)?;
+        Ok(self)
+    }
+
+    /// Register a built-in backend factory, builder style.
+    #[must_use]
+    pub fn with_adapter<B>(mut self, name: impl Into<String>) -> Self
+    where
+        B: BackendFactory + Backend + 'static,
+    {
+        self.register::<B>(name);
+        self
+    }
+
     /// Register a built-in backend factory.
     pub fn register<B>(&mut self, name: impl Into<String>)
     where
@@ -152,6 +204,90 @@ impl BackendRegistry {
         )))
     }
 
+    /// Create backend `name` from a typed configuration document.
+    ///
+    /// The section's adapter selects the factory, so adapters must be
+    /// registered under their [`AdapterConfig::adapter`] names.
+    ///
+    /// [`AdapterConfig::adapter`]: crate::config::AdapterConfig::adapter
+    pub fn create_configured(
+        &self,
+        config: &BackendsConfig,
+        name: &str,
+    ) -> HalResult<Box<dyn Backend>> {
+        let backend_config = config.resolve(name)?;
+        let adapter = config.backends[&backend_config.name].adapter();
+        if !self.has_backend(adapter) {
+            return Err(HalError::Configuration(format!(
+                "backends.{name}.adapter: adapter '{adapter}' is not available in this build"
+            )));
+        }
+        self.create(adapter, backend_config)
+    }
+
+    /// Create every backend in a typed configuration document, by name.
+    ///
+    /// The whole document is validated before any backend is created.
+    pub fn create_all_configured(
+        &self,
+        config: &BackendsConfig,
+    ) -> HalResult<Vec<(String, Box<dyn Backend>)>> {
+        config.validate()?;
+        config
+            .names()
+            .map(|name| Ok((name.to_string(), self.create_configured(config, name)?)))
+            .collect()
+    }
+
+    /// Create every backend in a configuration document and add it as an
+    /// instance, together with the document's aliases.
+    ///
+    /// Nothing is added unless every backend is created successfully.
+    pub fn instantiate(&mut self, config: &BackendsConfig) -> HalResult<()> {
+        let created = self.create_all_configured(config)?;
+        for (name, backend) in created {
+            self.add_instance(name, Arc::from(backend));
+        }
+        for (alias, target) in &config.aliases {
+            self.aliases.insert(alias.clone(), target.clone());
+        }
+        Ok(())
+    }
+
+    /// Add or replace a backend instance.
+    pub fn add_instance(&mut self, name: impl Into<String>, backend: Arc<dyn Backend>) {
+        let name = name.into();
+        debug!("Adding backend instance: {}", name);
+        self.aliases.remove(&name);
+        self.instances.insert(name, backend);
+    }
+
+    /// The backend instance `name` refers to, following aliases.
+    pub fn instance(&self, name: &str) -> Option<Arc<dyn Backend>> {
+        self.instances
+            .get(name)
+            .or_else(|| self.instances.get(self.aliases.get(name)?))
+            .cloned()
+    }
+
+    /// Names of all backend instances, sorted.
+    pub fn instance_names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.instances.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Instance aliases as `(alias, instance name)` pairs, sorted by alias.
+    pub fn instance_aliases(&self) -> Vec<(&str, &str)> {
+        let mut aliases: Vec<_> = self
+            .aliases
+            .iter()
+            .map(|(alias, target)| (alias.as_str(), target.as_str()))
+            .collect();
+        aliases.sort_unstable();
+        aliases
+    }
+
     /// List all available backend names.
     pub fn available_backends(&self) -> Vec<String> {
         let mut names: Vec<_> = self.builtins.keys().cloned().collect();
@@ -182,6 +318,63 @@ impl Default for BackendRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capability::Capabilities;
+    use crate::job::{JobId, JobStatus};
+    use crate::result::ExecutionResult;
+    use arvak_ir::Circuit;
+    use async_trait::async_trait;
+
+    /// Mock that only reports the name it was configured with.
+    struct NamedBackend {
+        name: String,
+        capabilities: Capabilities,
+    }
+
+    #[async_trait]
+    impl Backend for NamedBackend {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn availability(&self) -> HalResult<crate::backend::BackendAvailability> {
+            Ok(crate::backend::BackendAvailability::always_available())
+        }
+        async fn validate(
+            &self,
+            _c: &Circuit,
+            _shots: u32,
+        ) -> HalResult<crate::backend::ValidationResult> {
+            Ok(crate::backend::ValidationResult::Valid)
+        }
+        async fn submit(
+            &self,
+            _c: &Circuit,
+            _shots: u32,
+            _parameters: Option<&std::collections::HashMap<String, f64>>,
+        ) -> HalResult<JobId> {
+            Err(HalError::Unsupported("mock".into()))
+        }
+        async fn status(&self, _id: &JobId) -> HalResult<JobStatus> {
+            Err(HalError::Unsupported("mock".into()))
+        }
+        async fn result(&self, _id: &JobId) -> HalResult<ExecutionResult> {
+            Err(HalError::Unsupported("mock".into()))
+        }
+        async fn cancel(&self, _id: &JobId) -> HalResult<()> {
+            Ok(())
+        }
+    }
+
+    impl BackendFactory for NamedBackend {
+        fn from_config(config: BackendConfig) -> HalResult<Self> {
+            Ok(Self {
+                name: config.name,
+                capabilities: Capabilities::simulator(2),
+            })
+        }
+    }
 
     #[test]
     fn test_empty_registry() {
@@ -208,6 +401,67 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_create_configured() {
+        let mut registry = BackendRegistry::new();
+        registry.register_factory("simulator", |config| {
+            Err(HalError::BackendUnavailable(format!(
+                "{} with {} qubits",
+                config.name, config.extra["max_qubits"]
+            )))
+        });
+        let config = BackendsConfig::from_toml_str(
+            "[backends.local]\nadapter = \"simulator\"\nmax_qubits = 4\n\n\
+             [backends.sv]\nadapter = \"ddsim\"\n",
+        )
+        .unwrap();
+
+        let Err(err) = registry.create_configured(&config, "local") else {
+            panic!("factory should have been called");
+        };
+        assert_eq!(
+            err.to_string(),
+            "Backend not available: local with 4 qubits"
+        );
+        let Err(err) = registry.create_configured(&config, "sv") else {
+            panic!("ddsim is not registered");
+        };
+        assert!(matches!(err, HalError::Configuration(msg) if msg.contains("backends.sv.adapter")));
+        assert!(registry.create_configured(&config, "missing").is_err());
+    }
+
+    #[test]
+    fn test_instances_from_config() {
+        let path = std::env::temp_dir().join(format!("arvak-backends-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[backends.local]\nadapter = \"simulator\"\n\n\
+             [backends.fast]\nadapter = \"simulator\"\nmax_qubits = 8\n\n\
+             [aliases]\ndefault = \"local\"\n",
+        )
+        .unwrap();
+        let registry = BackendRegistry::new()
+            .with_adapter::<NamedBackend>("simulator")
+            .with_config(&path)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(registry.instance_names(), vec!["fast", "local"]);
+        assert_eq!(registry.instance_aliases(), vec![("default", "local")]);
+        assert_eq!(registry.instance("default").unwrap().name(), "local");
+        assert_eq!(registry.instance("fast").unwrap().name(), "fast");
+        assert!(registry.instance("missing").is_none());
+
+        // A broken document leaves the registry untouched.
+        let mut registry = registry;
+        let broken = BackendsConfig::default().with_backend(
+            "sv",
+            crate::config::AdapterConfig::Ddsim(Default::default()),
+        );
+        assert!(registry.instantiate(&broken).is_err());
+        assert_eq!(registry.instance_names(), vec!["fast", "local"]);
+    }
+
     #[test]
     fn test_available_backends_sorted() {
         let mut registry = BackendRegistry::new();
@@ -0,0 +1,88 @@
+//! Retention selection shared by the job stores.
+//!
+//! The gRPC job storage and the scheduler's state store both bound
+//! themselves by age and by size. [`select_for_removal`] decides which
+//! terminal jobs go; each store archives and deletes them its own way.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Why a job was selected for removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalReason {
+    /// Finished longer ago than the maximum age.
+    Expired,
+    /// Evicted to bring the store under its size cap.
+    OverCapacity,
+}
+
+/// Select the terminal jobs to remove from a store holding `total` jobs.
+///
+/// `terminal` pairs each terminal job with the time it finished; queued and
+/// running jobs are never candidates, but they count towards `max_jobs`.
+/// Jobs that finished more than `max_age` before `now` expire. If the store
+/// is still over `max_jobs`, the oldest remaining terminal jobs follow. The
+/// selected jobs are returned oldest first.
+pub fn select_for_removal<T>(
+    mut terminal: Vec<(T, DateTime<Utc>)>,
+    total: usize,
+    max_age: Option<Duration>,
+    max_jobs: Option<usize>,
+    now: DateTime<Utc>,
+) -> Vec<(T, DateTime<Utc>, RemovalReason)> {
+    terminal.sort_by_key(|(_, finished_at)| *finished_at);
+
+    let cutoff = max_age
+        .and_then(|age| chrono::Duration::from_std(age).ok())
+        .and_then(|age| now.checked_sub_signed(age));
+    let mut excess = max_jobs.map_or(0, |max| total.saturating_sub(max));
+
+    let mut selected = Vec::new();
+    for (job, finished_at) in terminal {
+        let reason = if cutoff.is_some_and(|cutoff| finished_at < cutoff) {
+            RemovalReason::Expired
+        } else if excess > 0 {
+            RemovalReason::OverCapacity
+        } else {
+            continue;
+        };
+        excess = excess.saturating_sub(1);
+        selected.push((job, finished_at, reason));
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_for_removal() {
+        let now = Utc::now();
+        let days_ago = |days| now - chrono::Duration::days(days);
+        let terminal = vec![
+            ("recent", days_ago(5)),
+            ("old", days_ago(40)),
+            ("newest", days_ago(1)),
+        ];
+        let month = Some(Duration::from_secs(30 * 86_400));
+
+        let selected = select_for_removal(terminal.clone(), 4, month, None, now);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(
+            (selected[0].0, selected[0].2),
+            ("old", RemovalReason::Expired)
+        );
+
+        // Capacity 2 of 4 jobs: "old" expires and "recent" is evicted.
+        let selected = select_for_removal(terminal.clone(), 4, month, Some(2), now);
+        let names: Vec<_> = selected.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(names, ["old", "recent"]);
+        assert_eq!(selected[1].2, RemovalReason::OverCapacity);
+
+        assert!(select_for_removal(terminal, 4, None, None, now).is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+//! Shot splitting for jobs larger than a backend's `max_shots`.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Providers cap the number of shots per job (`Capabilities::max_shots`).
+//! [`run_split`] runs a circuit with any shot count by submitting it in
+//! batches that respect the backend's limit and aggregating the counts into
+//! a single [`ExecutionResult`]; [`run_split_with_heartbeats`] does the same
+//! while publishing heartbeats for each batch. When more than one batch was needed, the
+//! split is recorded under the `"shot_split"` key of the result metadata:
+//!
+//! ```json
+//! {
+//!   "shot_split": {
+//!     "max_shots": 10000,
+//!     "batches": [10000, 10000, 5000],
+//!     "job_ids": ["job-1", "job-2", "job-3"]
+//!   }
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use arvak_ir::Circuit;
+use tracing::debug;
+
+use crate::backend::Backend;
+use crate::error::{HalError, HalResult};
+use crate::heartbeat::HeartbeatSender;
+use crate::job::JobId;
+use crate::result::{Counts, ExecutionResult};
+
+/// Metadata key under which [`run_split`] records the batch split.
+pub const SHOT_SPLIT_METADATA_KEY: &str = "shot_split";
+
+/// Split `shots` into batches of at most `max_shots`.
+///
+/// All batches are full except possibly the last. A shot count that already
+/// fits (including zero) yields a single batch.
+///
+/// # Panics
+///
+/// Panics if `max_shots` is zero.
+pub fn split_shots(shots: u32, max_shots: u32) -> Vec<u32> {
+    assert!(max_shots > 0, "max_shots must be positive");
+    if shots <= max_shots {
+        return vec![shots];
+    }
+
+    let mut batches = vec![max_shots; (shots / max_shots) as usize];
+    let remainder = shots % max_shots;
+    if remainder > 0 {
+        batches.push(remainder);
+    }
+    batches
+}
+
+/// Run a circuit, splitting the shots across several jobs if they exceed the
+/// backend's `max_shots`.
+///
+/// Batches are submitted and awaited one after another so a provider's
+/// per-user queue limits are never exceeded. Counts are summed across
+/// batches; `execution_time_ms` is the sum of the batch times when every
+/// batch reports one. A job that fits in one batch is submitted unchanged
+/// and its result returned as-is.
+pub async fn run_split<B: Backend + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    shots: u32,
+    parameters: Option<&HashMap<String, f64>>,
+) -> HalResult<ExecutionResult> {
+    run_batches(backend, circuit, shots, parameters, None).await
+}
+
+/// [`run_split`], waiting for each batch with
+/// [`Backend::wait_with_heartbeats`] so long queue times are visible.
+pub async fn run_split_with_heartbeats<B: Backend + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    shots: u32,
+    parameters: Option<&HashMap<String, f64>>,
+    heartbeats: &HeartbeatSender,
+) -> HalResult<ExecutionResult> {
+    run_batches(backend, circuit, shots, parameters, Some(heartbeats)).await
+}
+
+async fn wait_for<B: Backend + ?Sized>(
+    backend: &B,
+    job_id: &JobId,
+    heartbeats: Option<&HeartbeatSender>,
+) -> HalResult<ExecutionResult> {
+    match heartbeats {
+        Some(heartbeats) => backend.wait_with_heartbeats(job_id, heartbeats).await,
+        None => backend.wait(job_id).await,
+    }
+}
+
+async fn run_batches<B: Backend + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    shots: u32,
+    parameters: Option<&HashMap<String, f64>>,
+    heartbeats: Option<&HeartbeatSender>,
+) -> HalResult<ExecutionResult> {
+    let max_shots = backend.capabilities().max_shots;
+    if max_shots == 0 {
+        return Err(HalError::InvalidShots(format!(
+            "backend '{}' reports max_shots = 0",
+            backend.name()
+        )));
+    }
+
+    let batches = split_shots(shots, max_shots);
+    if batches.len() == 1 {
+        let job_id = backend.submit(circuit, shots, parameters).await?;
+        return wait_for(backend, &job_id, heartbeats).await;
+    }
+
+    debug!(
+        "Splitting {} shots into {} batches (max_shots = {}) on '{}'",
+        shots,
+        batches.len(),
+        max_shots,
+        backend.name()
+    );
+
+    let mut counts = Counts::new();
+    let mut execution_time_ms = Some(0);
+    let mut job_ids = Vec::with_capacity(batches.len());
+    let mut metadata = serde_json::Value::Null;
+
+    for &batch in &batches {
+        let job_id = backend.submit(circuit, batch, parameters).await?;
+        let result = wait_for(backend, &job_id, heartbeats).await?;
+
+        for (bitstring, &count) in result.counts.iter() {
+            counts.insert(bitstring.clone(), count);
+        }
+        execution_time_ms = execution_time_ms
+            .zip(result.execution_time_ms)
+            .map(|(a, b)| a + b);
+        if metadata.is_null() {
+            metadata = result.metadata;
+        }
+        job_ids.push(job_id.0);
+    }
+
+    let split = serde_json::json!({
+        "max_shots": max_shots,
+        "batches": batches,
+        "job_ids": job_ids,
+    });
+    match &mut metadata {
+        serde_json::Value::Object(map) => {
+            map.insert(SHOT_SPLIT_METADATA_KEY.into(), split);
+        }
+        _ => metadata = serde_json::json!({ SHOT_SPLIT_METADATA_KEY: split }),
+    }
+
+    let mut result = ExecutionResult::new(counts, shots);
+    result.execution_time_ms = execution_time_ms;
+    result.metadata = metadata;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use crate::backend::{BackendAvailability, ValidationResult};
+    use crate::capability::Capabilities;
+    use crate::job::{JobId, JobStatus};
+
+    #[test]
+    fn test_split_shots() {
+        assert_eq!(split_shots(100, 1000), vec![100]);
+        assert_eq!(split_shots(1000, 1000), vec![1000]);
+        assert_eq!(split_shots(2500, 1000), vec![1000, 1000, 500]);
+        assert_eq!(split_shots(3000, 1000), vec![1000, 1000, 1000]);
+        assert_eq!(split_shots(0, 1000), vec![0]);
+    }
+
+    /// Mock that rejects jobs above `max_shots` and returns all-zero counts.
+    struct LimitedBackend {
+        capabilities: Capabilities,
+        submitted: Mutex<Vec<u32>>,
+    }
+
+    impl LimitedBackend {
+        fn new(max_shots: u32) -> Self {
+            let mut capabilities = Capabilities::simulator(1);
+            capabilities.max_shots = max_shots;
+            Self {
+                capabilities,
+                submitted: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Backend for LimitedBackend {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn name(&self) -> &str {
+            "limited"
+        }
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+        async fn availability(&self) -> HalResult<BackendAvailability> {
+            Ok(BackendAvailability::always_available())
+        }
+        async fn validate(&self, _c: &Circuit, _shots: u32) -> HalResult<ValidationResult> {
+            Ok(ValidationResult::Valid)
+        }
+        async fn submit(
+            &self,
+            _c: &Circuit,
+            shots: u32,
+            _parameters: Option<&HashMap<String, f64>>,
+        ) -> HalResult<JobId> {
+            if shots > self.capabilities.max_shots {
+                return Err(HalError::InvalidShots(format!("{shots} > max_shots")));
+            }
+            let mut submitted = self.submitted.lock().unwrap();
+            submitted.push(shots);
+            Ok(JobId::new(format!("job-{}", submitted.len() - 1)))
+        }
+        async fn status(&self, _id: &JobId) -> HalResult<JobStatus> {
+            Ok(JobStatus::Completed)
+        }
+        async fn result(&self, id: &JobId) -> HalResult<ExecutionResult> {
+            let index: usize = id.0.trim_start_matches("job-").parse().unwrap();
+            let shots = self.submitted.lock().unwrap()[index];
+            let mut counts = Counts::new();
+            counts.insert("0", shots.into());
+            Ok(ExecutionResult::new(counts, shots).with_execution_time(10))
+        }
+        async fn cancel(&self, _id: &JobId) -> HalResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_split_aggregates_batches() {
+        let backend = LimitedBackend::new(1000);
+        let circuit = Circuit::with_size("test", 1, 1);
+
+        let result = run_split(&backend, &circuit, 2500, None).await.unwrap();
+
+        assert_eq!(*backend.submitted.lock().unwrap(), vec![1000, 1000, 500]);
+        assert_eq!(result.shots, 2500);
+        assert_eq!(result.counts.get("0"), 2500);
+        assert_eq!(result.execution_time_ms, Some(30));
+
+        let split = &result.metadata[SHOT_SPLIT_METADATA_KEY];
+        assert_eq!(split["max_shots"], 1000);
+        assert_eq!(split["batches"], serde_json::json!([1000, 1000, 500]));
+        assert_eq!(split["job_ids"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_split_single_batch_unchanged() {
+        let backend = LimitedBackend::new(1000);
+        let circuit = Circuit::with_size("test", 1, 1);
+
+        let result = run_split(&backend, &circuit, 800, None).await.unwrap();
+
+        assert_eq!(*backend.submitted.lock().unwrap(), vec![800]);
+        assert_eq!(result.counts.get("0"), 800);
+        assert!(result.metadata.get(SHOT_SPLIT_METADATA_KEY).is_none());
+    }
+}
@@ -0,0 +1,315 @@
+//! Ed25519 signing of circuit artifacts.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Shared deployments can restrict billed hardware to circuits vetted by an
+//! approved party. The party signs the circuit artifact — the OpenQASM 3 or
+//! IR JSON text exactly as it is submitted — with a [`CircuitSigner`], and
+//! sends the resulting [`CircuitSignature`] along with the job. Servers and
+//! schedulers check it against a [`SignaturePolicy`]: on the backends the
+//! policy protects, only circuits signed by a trusted key are accepted.
+//!
+//! Keys and signatures are exchanged as base64 text. Signatures cover the
+//! artifact prefixed with a fixed context string, so they cannot be replayed
+//! as signatures over other Arvak data.
+//!
+//! ```rust
+//! use arvak_hal::signing::{CircuitSigner, SignaturePolicy};
+//!
+//! let signer = CircuitSigner::from_bytes(&[7; 32]).unwrap();
+//! let qasm = "OPENQASM 3.0; qubit[1] q; h q[0];";
+//! let signature = signer.sign(qasm);
+//!
+//! let policy = SignaturePolicy::new()
+//!     .with_trusted_key(&signer.public_key())
+//!     .unwrap()
+//!     .require_for_backend("iqm-garnet");
+//! assert!(policy.check("iqm-garnet", qasm, Some(&signature)).is_ok());
+//! assert!(policy.check("iqm-garnet", qasm, None).is_err());
+//! assert!(policy.check("simulator", qasm, None).is_ok());
+//! ```
+
+use std::fmt;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use rand_core::OsRng;
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Context prepended to every signed artifact.
+const SIGNATURE_CONTEXT: &[u8] = b"arvak-circuit:v1:";
+
+/// Errors from signing or verifying circuit artifacts.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SignatureError {
+    /// A key is malformed or has the wrong length.
+    #[error("Invalid signing key: {0}")]
+    InvalidKey(String),
+
+    /// The signature is malformed or does not match the artifact.
+    #[error("Invalid circuit signature: {0}")]
+    InvalidSignature(String),
+
+    /// The signature is valid but its key is not trusted.
+    #[error("Circuit signed by untrusted key {0}")]
+    UntrustedKey(String),
+
+    /// The backend only accepts signed circuits.
+    #[error("Backend '{0}' requires a signed circuit")]
+    Missing(String),
+}
+
+/// Signature over a circuit artifact, stored alongside the job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitSignature {
+    /// Base64-encoded Ed25519 public key of the signer.
+    pub public_key: String,
+    /// Base64-encoded Ed25519 signature.
+    pub signature: String,
+}
+
+impl CircuitSignature {
+    /// Check that the signature is valid for `artifact`.
+    ///
+    /// This only checks the cryptography; whether the key is trusted is up
+    /// to a [`SignaturePolicy`].
+    pub fn verify(&self, artifact: &str) -> Result<(), SignatureError> {
+        let key = decode_public_key(&self.public_key)?;
+        let bytes: [u8; Signature::BYTE_SIZE] = BASE64
+            .decode(self.signature.trim())
+            .map_err(|e| SignatureError::InvalidSignature(e.to_string()))?
+            .try_into()
+            .map_err(|bytes: Vec<u8>| {
+                SignatureError::InvalidSignature(format!(
+                    "expected {} bytes, got {}",
+                    Signature::BYTE_SIZE,
+                    bytes.len()
+                ))
+            })?;
+        key.verify_strict(&message(artifact), &Signature::from_bytes(&bytes))
+            .map_err(|_| {
+                SignatureError::InvalidSignature("signature does not match circuit".into())
+            })
+    }
+}
+
+/// Ed25519 key that signs circuit artifacts.
+#[derive(Clone)]
+pub struct CircuitSigner {
+    key: SigningKey,
+}
+
+impl CircuitSigner {
+    /// Generate a new random key.
+    pub fn generate() -> Self {
+        Self {
+            key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    /// Create a signer from a raw 32-byte secret key.
+    pub fn from_bytes(secret: &[u8]) -> Result<Self, SignatureError> {
+        let secret: [u8; ed25519_dalek::SECRET_KEY_LENGTH] = secret.try_into().map_err(|_| {
+            SignatureError::InvalidKey(format!(
+                "expected {} bytes, got {}",
+                ed25519_dalek::SECRET_KEY_LENGTH,
+                secret.len()
+            ))
+        })?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&secret),
+        })
+    }
+
+    /// Create a signer from a base64-encoded secret key.
+    pub fn from_base64(secret: &str) -> Result<Self, SignatureError> {
+        let bytes = BASE64
+            .decode(secret.trim())
+            .map_err(|e| SignatureError::InvalidKey(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// The secret key as base64, for storing a generated key.
+    pub fn secret_key(&self) -> String {
+        BASE64.encode(self.key.to_bytes())
+    }
+
+    /// The public key as base64, for adding to a [`SignaturePolicy`].
+    pub fn public_key(&self) -> String {
+        BASE64.encode(self.key.verifying_key().to_bytes())
+    }
+
+    /// Sign a circuit artifact.
+    pub fn sign(&self, artifact: &str) -> CircuitSignature {
+        CircuitSignature {
+            public_key: self.public_key(),
+            signature: BASE64.encode(self.key.sign(&message(artifact)).to_bytes()),
+        }
+    }
+}
+
+impl fmt::Debug for CircuitSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitSigner")
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Which keys are trusted, and which backends only run signed circuits.
+///
+/// A signature that accompanies a job must be valid and from a trusted key
+/// on every backend, so stored signatures always identify an approved
+/// signer. Backends added with
+/// [`require_for_backend`](Self::require_for_backend) additionally reject
+/// unsigned circuits.
+#[derive(Debug, Clone, Default)]
+pub struct SignaturePolicy {
+    trusted_keys: FxHashSet<[u8; ed25519_dalek::PUBLIC_KEY_LENGTH]>,
+    required_backends: FxHashSet<String>,
+}
+
+impl SignaturePolicy {
+    /// Create a policy with no trusted keys and no protected backends.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust a base64-encoded public key.
+    pub fn with_trusted_key(mut self, public_key: &str) -> Result<Self, SignatureError> {
+        let key = decode_public_key(public_key)?;
+        self.trusted_keys.insert(key.to_bytes());
+        Ok(self)
+    }
+
+    /// Only accept signed circuits on `backend_id`.
+    #[must_use]
+    pub fn require_for_backend(mut self, backend_id: impl Into<String>) -> Self {
+        self.required_backends.insert(backend_id.into());
+        self
+    }
+
+    /// Whether `backend_id` only accepts signed circuits.
+    pub fn requires_signature(&self, backend_id: &str) -> bool {
+        self.required_backends.contains(backend_id)
+    }
+
+    /// Number of trusted keys.
+    pub fn num_trusted_keys(&self) -> usize {
+        self.trusted_keys.len()
+    }
+
+    /// Check whether a circuit artifact may run on `backend_id`.
+    pub fn check(
+        &self,
+        backend_id: &str,
+        artifact: &str,
+        signature: Option<&CircuitSignature>,
+    ) -> Result<(), SignatureError> {
+        let Some(signature) = signature else {
+            return if self.requires_signature(backend_id) {
+                Err(SignatureError::Missing(backend_id.to_string()))
+            } else {
+                Ok(())
+            };
+        };
+        signature.verify(artifact)?;
+        let key = decode_public_key(&signature.public_key)?;
+        if self.trusted_keys.contains(&key.to_bytes()) {
+            Ok(())
+        } else {
+            Err(SignatureError::UntrustedKey(signature.public_key.clone()))
+        }
+    }
+}
+
+/// The signed message: context followed by the artifact.
+fn message(artifact: &str) -> Vec<u8> {
+    [SIGNATURE_CONTEXT, artifact.as_bytes()].concat()
+}
+
+fn decode_public_key(public_key: &str) -> Result<VerifyingKey, SignatureError> {
+    let bytes: [u8; ed25519_dalek::PUBLIC_KEY_LENGTH] = BASE64
+        .decode(public_key.trim())
+        .map_err(|e| SignatureError::InvalidKey(e.to_string()))?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| {
+            SignatureError::InvalidKey(format!(
+                "expected {} bytes, got {}",
+                ed25519_dalek::PUBLIC_KEY_LENGTH,
+                bytes.len()
+            ))
+        })?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| SignatureError::InvalidKey(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QASM: &str = "OPENQASM 3.0;\nqubit[2] q;\nh q[0];\ncx q[0], q[1];\n";
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = CircuitSigner::generate();
+        let signature = signer.sign(QASM);
+        assert!(signature.verify(QASM).is_ok());
+        assert!(matches!(
+            signature.verify("OPENQASM 3.0;\nqubit[2] q;\n"),
+            Err(SignatureError::InvalidSignature(_))
+        ));
+
+        let restored = CircuitSigner::from_base64(&signer.secret_key()).unwrap();
+        assert_eq!(restored.public_key(), signer.public_key());
+        assert!(!format!("{signer:?}").contains(&signer.secret_key()));
+    }
+
+    #[test]
+    fn test_policy() {
+        let trusted = CircuitSigner::from_bytes(&[1; 32]).unwrap();
+        let other = CircuitSigner::from_bytes(&[2; 32]).unwrap();
+        let policy = SignaturePolicy::new()
+            .with_trusted_key(&trusted.public_key())
+            .unwrap()
+            .require_for_backend("hardware");
+
+        assert!(
+            policy
+                .check("hardware", QASM, Some(&trusted.sign(QASM)))
+                .is_ok()
+        );
+        assert!(matches!(
+            policy.check("hardware", QASM, None),
+            Err(SignatureError::Missing(_))
+        ));
+        assert!(matches!(
+            policy.check("hardware", QASM, Some(&other.sign(QASM))),
+            Err(SignatureError::UntrustedKey(_))
+        ));
+        assert!(policy.check("simulator", QASM, None).is_ok());
+        assert!(
+            policy
+                .check("simulator", QASM, Some(&other.sign(QASM)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_key_validation() {
+        assert!(CircuitSigner::from_bytes(&[0; 16]).is_err());
+        assert!(
+            SignaturePolicy::new()
+                .with_trusted_key("not base64!")
+                .is_err()
+        );
+        assert!(
+            SignaturePolicy::new()
+                .with_trusted_key(&BASE64.encode([0u8; 16]))
+                .is_err()
+        );
+    }
+}
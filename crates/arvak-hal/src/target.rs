@@ -0,0 +1,228 @@
+//! Compilation targets derived from backend capabilities.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! A [`Target`] is what the compiler needs to know about a device: its
+//! coupling map, the basis gates to translate to, and error rates for
+//! noise-aware passes. Building it from [`Capabilities`] keeps compilation
+//! in step with what the backend reports, instead of each caller picking a
+//! topology by name. [`PassManagerBuilderExt`] adds the matching builder
+//! methods:
+//!
+//! ```rust
+//! use arvak_compile::PassManagerBuilder;
+//! use arvak_hal::{Capabilities, PassManagerBuilderExt};
+//! use arvak_ir::Circuit;
+//!
+//! let caps = Capabilities::iqm("Garnet", 5);
+//! let (pm, mut props) = PassManagerBuilder::new()
+//!     .with_optimization_level(2)
+//!     .with_capabilities(&caps)
+//!     .build();
+//!
+//! let mut dag = Circuit::bell().unwrap().into_dag();
+//! pm.run(&mut dag, &mut props).unwrap();
+//! ```
+//!
+//! [`transpile`] does the same in one call.
+
+use arvak_compile::{BasisGates, CompileResult, CompileTarget, CouplingMap, PassManagerBuilder};
+use arvak_ir::Circuit;
+use arvak_ir::noise::NoiseProfile;
+
+use crate::backend::Backend;
+use crate::capability::Capabilities;
+use crate::negotiation;
+
+/// The device description a circuit is compiled for.
+#[derive(Debug, Clone)]
+pub struct Target {
+    /// Qubit connectivity.
+    pub coupling_map: CouplingMap,
+    /// Gates the compiled circuit may contain.
+    pub basis_gates: BasisGates,
+    /// Gate and readout error rates, when the backend reports them.
+    pub noise_profile: Option<NoiseProfile>,
+}
+
+impl Target {
+    /// Derive the target from a backend's capabilities.
+    ///
+    /// The coupling map follows [`negotiation::coupling_map`] and the basis
+    /// gates [`negotiation::basis_gates`]. The device-wide averages of the
+    /// capability noise profile become per-gate and per-qubit error rates:
+    /// every basis gate gets the single- or two-qubit infidelity of its
+    /// arity, and every qubit the average T1, T2 and readout error.
+    pub fn from_capabilities(capabilities: &Capabilities) -> Self {
+        let basis_gates = negotiation::basis_gates(capabilities);
+        let noise_profile = capabilities
+            .noise_profile
+            .as_ref()
+            .map(|averages| {
+                let gate_set = &capabilities.gate_set;
+                let num_qubits = capabilities.num_qubits as usize;
+                let mut profile = NoiseProfile::new();
+                for gate in basis_gates.gates() {
+                    let fidelity = if gate_set.single_qubit.contains(gate) {
+                        averages.single_qubit_fidelity
+                    } else if gate_set.two_qubit.contains(gate) {
+                        averages.two_qubit_fidelity
+                    } else {
+                        None
+                    };
+                    if let Some(fidelity) = fidelity {
+                        profile.gate_errors.insert(gate.clone(), 1.0 - fidelity);
+                    }
+                }
+                profile.t1 = averages.t1.map(|t1| vec![t1; num_qubits]);
+                profile.t2 = averages.t2.map(|t2| vec![t2; num_qubits]);
+                profile.readout_errors = averages
+                    .readout_fidelity
+                    .map(|fidelity| vec![1.0 - fidelity; num_qubits]);
+                profile
+            })
+            .filter(|profile| !profile.is_empty());
+
+        Self {
+            coupling_map: negotiation::coupling_map(capabilities),
+            basis_gates,
+            noise_profile,
+        }
+    }
+
+    /// Derive the target from a backend; see
+    /// [`from_capabilities`](Self::from_capabilities).
+    pub fn from_backend(backend: &dyn Backend) -> Self {
+        Self::from_capabilities(backend.capabilities())
+    }
+
+    /// Number of physical qubits.
+    pub fn num_qubits(&self) -> u32 {
+        self.coupling_map.num_qubits()
+    }
+}
+
+impl From<&Capabilities> for Target {
+    fn from(capabilities: &Capabilities) -> Self {
+        Self::from_capabilities(capabilities)
+    }
+}
+
+impl From<Target> for CompileTarget {
+    fn from(target: Target) -> Self {
+        Self {
+            coupling_map: target.coupling_map,
+            basis_gates: target.basis_gates,
+            noise_profile: target.noise_profile,
+        }
+    }
+}
+
+/// Compile `circuit` at optimization `level` for a backend with
+/// `capabilities`.
+///
+/// Shorthand for [`arvak_compile::transpile`] with the
+/// [`Target`] derived from `capabilities`.
+pub fn transpile(
+    circuit: &Circuit,
+    capabilities: &Capabilities,
+    level: u8,
+) -> CompileResult<Circuit> {
+    let target = Target::from_capabilities(capabilities).into();
+    arvak_compile::transpile(circuit, &target, level)
+}
+
+/// Configure a [`PassManagerBuilder`] from HAL capabilities.
+pub trait PassManagerBuilderExt: Sized {
+    /// Compile for `target`: its coupling map, basis gates and, when
+    /// present, its noise profile.
+    #[must_use]
+    fn with_hal_target(self, target: Target) -> Self;
+
+    /// Compile for a backend with `capabilities`; see
+    /// [`Target::from_capabilities`].
+    #[must_use]
+    fn with_capabilities(self, capabilities: &Capabilities) -> Self {
+        self.with_hal_target(Target::from_capabilities(capabilities))
+    }
+
+    /// Compile for `backend`; see [`Target::from_backend`].
+    #[must_use]
+    fn with_backend(self, backend: &dyn Backend) -> Self {
+        self.with_hal_target(Target::from_backend(backend))
+    }
+}
+
+impl PassManagerBuilderExt for PassManagerBuilder {
+    fn with_hal_target(self, target: Target) -> Self {
+        let builder = self.with_target(target.coupling_map, target.basis_gates);
+        match target.noise_profile {
+            Some(profile) => builder.with_noise_profile(profile),
+            None => builder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capability::{NoiseProfile as DeviceNoise, Topology};
+
+    #[test]
+    fn test_target_from_capabilities() {
+        let caps = Capabilities::iqm("Garnet", 5).with_topology(Topology::linear(5));
+        let target = Target::from_capabilities(&caps);
+
+        assert_eq!(target.num_qubits(), 5);
+        assert!(target.coupling_map.is_connected(0, 1));
+        assert!(!target.coupling_map.is_connected(0, 2));
+        assert!(target.basis_gates.contains("prx"));
+        assert!(target.basis_gates.contains("cz"));
+        assert!(target.basis_gates.contains("measure"));
+        assert!(target.noise_profile.is_none());
+    }
+
+    #[test]
+    fn test_target_noise_profile() {
+        let caps = Capabilities::iqm("Garnet", 3).with_noise_profile(DeviceNoise {
+            t1: Some(40.0),
+            single_qubit_fidelity: Some(0.999),
+            two_qubit_fidelity: Some(0.99),
+            readout_fidelity: Some(0.97),
+            ..DeviceNoise::new()
+        });
+        let profile = Target::from_capabilities(&caps).noise_profile.unwrap();
+
+        assert!((profile.gate_error("prx").unwrap() - 0.001).abs() < 1e-12);
+        assert!((profile.gate_error("cz").unwrap() - 0.01).abs() < 1e-12);
+        assert!(profile.gate_error("measure").is_none());
+        assert_eq!(profile.qubit_t1(2), Some(40.0));
+        assert!(profile.t2.is_none());
+        assert!((profile.qubit_readout_error(1).unwrap() - 0.03).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_builder_with_capabilities() {
+        let caps = Capabilities::iqm("Garnet", 5).with_noise_profile(DeviceNoise {
+            two_qubit_fidelity: Some(0.99),
+            ..DeviceNoise::new()
+        });
+        let (_, props) = PassManagerBuilder::new().with_capabilities(&caps).build();
+
+        assert_eq!(props.coupling_map.as_ref().unwrap().num_qubits(), 5);
+        assert!(props.basis_gates.as_ref().unwrap().contains("prx"));
+        assert!(props.get::<NoiseProfile>().is_some());
+    }
+
+    #[test]
+    fn test_transpile_for_capabilities() {
+        let caps = Capabilities::iqm("Garnet", 5).with_topology(Topology::linear(5));
+        let compiled = transpile(&Circuit::ghz(4).unwrap(), &caps, 2).unwrap();
+        assert!(
+            compiled
+                .dag()
+                .topological_ops()
+                .all(|(_, inst)| ["prx", "cz", "measure"].contains(&inst.name()))
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Live cooling telemetry.
+//!
+//! Arvak extension — not part of HAL Contract v2 spec.
+//!
+//! Capabilities describe a device as it was calibrated; the cryostat keeps
+//! drifting in between. A [`TelemetrySample`] is one reading of named
+//! metrics (stage temperatures, pressures, compressor power) at a point in
+//! time. Backends with access to such data return the latest reading from
+//! [`Backend::telemetry`](crate::Backend::telemetry); others can have an
+//! external agent push samples to whoever collects them. A
+//! [`TelemetryThreshold`] flags readings outside their safe range:
+//!
+//! ```rust
+//! use arvak_hal::telemetry::{MIXING_CHAMBER_MK, TelemetrySample, TelemetryThreshold};
+//! use chrono::Utc;
+//!
+//! let sample = TelemetrySample::new(Utc::now()).with_metric(MIXING_CHAMBER_MK, 31.0);
+//! let threshold: TelemetryThreshold = "mixing_chamber_mk>25".parse().unwrap();
+//! assert_eq!(threshold.violation(&sample), Some(31.0));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Mixing chamber temperature in millikelvin.
+pub const MIXING_CHAMBER_MK: &str = "mixing_chamber_mk";
+/// Still plate temperature in millikelvin.
+pub const STILL_MK: &str = "still_mk";
+/// 4 K plate temperature in kelvin.
+pub const PLATE_4K_K: &str = "plate_4k_k";
+/// 50 K plate temperature in kelvin.
+pub const PLATE_50K_K: &str = "plate_50k_k";
+/// Pulse tube compressor power in kilowatts.
+pub const COMPRESSOR_KW: &str = "compressor_kw";
+
+/// One reading of a device's cooling metrics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetrySample {
+    /// When the reading was taken.
+    pub timestamp: DateTime<Utc>,
+    /// Metric name to value. Well-known names are the constants of this
+    /// module; backends may report others.
+    pub metrics: BTreeMap<String, f64>,
+}
+
+impl TelemetrySample {
+    /// Create a sample without metrics.
+    pub fn new(timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    /// Add a metric reading.
+    #[must_use]
+    pub fn with_metric(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.metrics.insert(name.into(), value);
+        self
+    }
+
+    /// The value of metric `name`, if reported.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.metrics.get(name).copied()
+    }
+}
+
+/// Safe range of one telemetry metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryThreshold {
+    /// Metric the threshold applies to.
+    pub metric: String,
+    /// Readings below this value violate the threshold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Readings above this value violate the threshold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+impl TelemetryThreshold {
+    /// Readings of `metric` above `max` violate the threshold.
+    pub fn above(metric: impl Into<String>, max: f64) -> Self {
+        Self {
+            metric: metric.into(),
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    /// Readings of `metric` below `min` violate the threshold.
+    pub fn below(metric: impl Into<String>, min: f64) -> Self {
+        Self {
+            metric: metric.into(),
+            min: Some(min),
+            max: None,
+        }
+    }
+
+    /// The reading of `sample` that violates this threshold, if any.
+    ///
+    /// A sample without the metric never violates it.
+    pub fn violation(&self, sample: &TelemetrySample) -> Option<f64> {
+        let value = sample.get(&self.metric)?;
+        let low = self.min.is_some_and(|min| value < min);
+        let high = self.max.is_some_and(|max| value > max);
+        (low || high).then_some(value)
+    }
+}
+
+impl fmt::Display for TelemetryThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => write!(f, "{min}<{}<{max}", self.metric),
+            (Some(min), None) => write!(f, "{}<{min}", self.metric),
+            (None, Some(max)) => write!(f, "{}>{max}", self.metric),
+            (None, None) => f.write_str(&self.metric),
+        }
+    }
+}
+
+/// Parses `metric>max` or `metric<min`, the inverse of `Display` for
+/// one-sided thresholds.
+impl FromStr for TelemetryThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (metric, value, above) = if let Some((metric, value)) = s.split_once('>') {
+            (metric, value, true)
+        } else if let Some((metric, value)) = s.split_once('<') {
+            (metric, value, false)
+        } else {
+            return Err(format!(
+                "invalid threshold '{s}': expected 'metric>max' or 'metric<min'"
+            ));
+        };
+        let metric = metric.trim();
+        if metric.is_empty() {
+            return Err(format!("invalid threshold '{s}': missing metric name"));
+        }
+        let value: f64 = value
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid threshold '{s}': {e}"))?;
+        Ok(if above {
+            Self::above(metric, value)
+        } else {
+            Self::below(metric, value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_violation() {
+        let sample = TelemetrySample::new(Utc::now())
+            .with_metric(MIXING_CHAMBER_MK, 12.0)
+            .with_metric(COMPRESSOR_KW, 7.5);
+
+        assert_eq!(
+            TelemetryThreshold::above(MIXING_CHAMBER_MK, 10.0).violation(&sample),
+            Some(12.0)
+        );
+        assert_eq!(
+            TelemetryThreshold::above(MIXING_CHAMBER_MK, 20.0).violation(&sample),
+            None
+        );
+        assert_eq!(
+            TelemetryThreshold::below(COMPRESSOR_KW, 8.0).violation(&sample),
+            Some(7.5)
+        );
+        assert_eq!(
+            TelemetryThreshold::above(PLATE_4K_K, 4.5).violation(&sample),
+            None
+        );
+    }
+
+    #[test]
+    fn test_threshold_parsing() {
+        let above: TelemetryThreshold = "mixing_chamber_mk > 25".parse().unwrap();
+        assert_eq!(above, TelemetryThreshold::above(MIXING_CHAMBER_MK, 25.0));
+        assert_eq!(above.to_string(), "mixing_chamber_mk>25");
+
+        let below: TelemetryThreshold = "compressor_kw<6.5".parse().unwrap();
+        assert_eq!(below, TelemetryThreshold::below(COMPRESSOR_KW, 6.5));
+        assert_eq!(below.to_string().parse::<TelemetryThreshold>(), Ok(below));
+
+        assert!("mixing_chamber_mk".parse::<TelemetryThreshold>().is_err());
+        assert!(">3".parse::<TelemetryThreshold>().is_err());
+        assert!("x>hot".parse::<TelemetryThreshold>().is_err());
+    }
+}
@@ -123,12 +123,39 @@ fn bench_circuit_depth(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark binary serialization round trips
+fn bench_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialization");
+
+    for num_qubits in &[10, 50, 100] {
+        let circuit = Circuit::ghz(*num_qubits).unwrap();
+        let bytes = circuit.to_bytes();
+        group.bench_with_input(
+            BenchmarkId::new("to_bytes", num_qubits),
+            &circuit,
+            |b, circuit| {
+                b.iter(|| black_box(circuit.to_bytes()));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("from_bytes", num_qubits),
+            &bytes,
+            |b, bytes| {
+                b.iter(|| black_box(Circuit::from_bytes(bytes).unwrap()));
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_circuit_creation,
     bench_gate_addition,
     bench_ghz_circuit,
     bench_circuit_depth,
+    bench_serialization,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,1162 @@
+//! Compact, versioned binary serialization of circuits.
+//!
+//! [`Circuit::to_bytes`] and [`Circuit::from_bytes`] (and the same pair on
+//! [`CircuitDag`]) store circuits in a few bytes per gate, for the many
+//! circuits persisted by schedulers and job stores where JSON is too large
+//! and too slow.
+//!
+//! # Layout
+//!
+//! All integers are unsigned LEB128 varints unless noted; floats are
+//! little-endian `f64`; strings are indices into a string table that is
+//! written once per file.
+//!
+//! ```text
+//! magic "ARVK" | version: u16 LE | min reader version: u16 LE
+//! string table: count, then (byte length, UTF-8 bytes) per string
+//! circuit record: name, level, global phase, qubits, clbits
+//! instruction count, then one record per instruction in topological order
+//! ```
+//!
+//! A record is its byte length followed by its fields.
+//!
+//! # Compatibility
+//!
+//! Readers accept every older version. A newer writer may append fields to
+//! the end of any record, or sections after the instructions, and older
+//! readers skip them. Changes that older readers cannot ignore, such as new
+//! instruction kinds or gates, raise the minimum reader version in the
+//! header so those readers fail with a clear error instead of misreading
+//! the circuit.
+//!
+//! Version 2 adds gate durations, appended to the instruction record.
+//! Version 3 adds while loops; circuits containing one require a version 3
+//! reader, all others are still written for version 1 readers.
+//! Version 4 adds instruction metadata, appended to the instruction record
+//! as a count followed by (key, value) string pairs.
+//! Version 5 adds controlled and powered gates; circuits containing one
+//! require a version 5 reader.
+
+use rustc_hash::FxHashMap;
+
+use num_complex::Complex64;
+
+use crate::circuit::Circuit;
+use crate::dag::{CircuitDag, CircuitLevel};
+use crate::error::{IrError, IrResult};
+use crate::gate::{ClassicalCondition, CustomGate, Gate, GateDefinition, GateKind, StandardGate};
+use crate::instruction::{Instruction, InstructionKind, InstructionMetadata};
+use crate::modifier::ModifiedGate;
+use crate::noise::{NoiseModel, NoiseRole};
+use crate::parameter::ParameterExpression;
+use crate::qubit::{Clbit, ClbitId, Qubit, QubitId};
+
+/// Magic bytes at the start of every encoded circuit.
+pub const MAGIC: &[u8; 4] = b"ARVK";
+
+/// Format version written by this build.
+pub const FORMAT_VERSION: u16 = 5;
+
+/// Oldest reader version that can decode what this build writes.
+const MIN_READER_VERSION: u16 = 1;
+
+/// Oldest reader version that can decode while loops.
+const WHILE_LOOP_READER_VERSION: u16 = 3;
+
+/// Oldest reader version that can decode modified gates.
+const MODIFIED_GATE_READER_VERSION: u16 = 5;
+
+/// Maximum nesting of expressions, control-flow blocks and gate definitions.
+const MAX_DEPTH: usize = 512;
+
+// Instruction kind tags.
+const KIND_STANDARD_GATE: u8 = 0;
+const KIND_CUSTOM_GATE: u8 = 1;
+const KIND_MEASURE: u8 = 2;
+const KIND_RESET: u8 = 3;
+const KIND_BARRIER: u8 = 4;
+const KIND_DELAY: u8 = 5;
+const KIND_SHUTTLE: u8 = 6;
+const KIND_NOISE: u8 = 7;
+const KIND_IF_ELSE: u8 = 8;
+const KIND_WHILE_LOOP: u8 = 9;
+const KIND_MODIFIED_GATE: u8 = 10;
+
+// Parameter expression tags.
+const EXPR_CONSTANT: u8 = 0;
+const EXPR_SYMBOL: u8 = 1;
+const EXPR_PI: u8 = 2;
+const EXPR_NEG: u8 = 3;
+const EXPR_ADD: u8 = 4;
+const EXPR_SUB: u8 = 5;
+const EXPR_MUL: u8 = 6;
+const EXPR_DIV: u8 = 7;
+
+// Flag bits.
+const HAS_REGISTER: u8 = 1;
+const HAS_INDEX: u8 = 2;
+const HAS_LABEL: u8 = 1;
+const HAS_CONDITION: u8 = 2;
+const HAS_DURATION: u8 = 4;
+const HAS_MATRIX: u8 = 1;
+const HAS_DEFINITION: u8 = 2;
+
+/// Stable code of a standard gate in the format.
+fn standard_gate_code(gate: &StandardGate) -> u8 {
+    match gate {
+        StandardGate::I => 0,
+        StandardGate::X => 1,
+        StandardGate::Y => 2,
+        StandardGate::Z => 3,
+        StandardGate::H => 4,
+        StandardGate::S => 5,
+        StandardGate::Sdg => 6,
+        StandardGate::T => 7,
+        StandardGate::Tdg => 8,
+        StandardGate::SX => 9,
+        StandardGate::SXdg => 10,
+        StandardGate::Rx(_) => 11,
+        StandardGate::Ry(_) => 12,
+        StandardGate::Rz(_) => 13,
+        StandardGate::P(_) => 14,
+        StandardGate::U(..) => 15,
+        StandardGate::CX => 16,
+        StandardGate::CY => 17,
+        StandardGate::CZ => 18,
+        StandardGate::CH => 19,
+        StandardGate::Swap => 20,
+        StandardGate::ISwap => 21,
+        StandardGate::CRx(_) => 22,
+        StandardGate::CRy(_) => 23,
+        StandardGate::CRz(_) => 24,
+        StandardGate::CP(_) => 25,
+        StandardGate::RXX(_) => 26,
+        StandardGate::RYY(_) => 27,
+        StandardGate::RZZ(_) => 28,
+        StandardGate::CCX => 29,
+        StandardGate::CSwap => 30,
+        StandardGate::MCX(_) => 31,
+        StandardGate::MCZ(_) => 32,
+        StandardGate::MCP(..) => 33,
+        StandardGate::PRX(..) => 34,
+        StandardGate::ECR => 35,
+    }
+}
+
+/// Encode a circuit's wires and operations.
+pub(crate) fn encode(name: &str, qubits: &[Qubit], clbits: &[Clbit], dag: &CircuitDag) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    let mut body = Vec::with_capacity(16 + 8 * dag.num_ops());
+
+    encoder.record(&mut body, |enc, out| {
+        enc.string(out, name);
+        out.push(match dag.level() {
+            CircuitLevel::Logical => 0,
+            CircuitLevel::Physical => 1,
+        });
+        out.extend_from_slice(&dag.global_phase().to_le_bytes());
+        write_varint(out, qubits.len() as u64);
+        for qubit in qubits {
+            enc.wire(out, qubit.id.0, qubit.register.as_deref(), qubit.index);
+        }
+        write_varint(out, clbits.len() as u64);
+        for clbit in clbits {
+            enc.wire(out, clbit.id.0, clbit.register.as_deref(), clbit.index);
+        }
+    });
+    write_varint(&mut body, dag.num_ops() as u64);
+    for (_, inst) in dag.topological_ops() {
+        encoder.instruction(&mut body, inst);
+    }
+
+    let mut out = Vec::with_capacity(body.len() + 64);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&encoder.min_reader.max(MIN_READER_VERSION).to_le_bytes());
+    write_varint(&mut out, encoder.table.len() as u64);
+    for s in &encoder.table {
+        write_varint(&mut out, s.len() as u64);
+        out.extend_from_slice(s.as_bytes());
+    }
+    out.extend_from_slice(&body);
+    out
+}
+
+/// A decoded circuit, before it is assembled into a [`Circuit`].
+pub(crate) struct Decoded {
+    pub name: String,
+    pub qubits: Vec<Qubit>,
+    pub clbits: Vec<Clbit>,
+    pub dag: CircuitDag,
+}
+
+/// Decode bytes written by [`encode`].
+pub(crate) fn decode(bytes: &[u8]) -> IrResult<Decoded> {
+    let mut reader = Reader::new(bytes);
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(invalid("not an Arvak binary circuit"));
+    }
+    let _version = reader.u16_le()?;
+    let min_reader = reader.u16_le()?;
+    if min_reader > FORMAT_VERSION {
+        return Err(invalid(format!(
+            "written for format version {min_reader} or later, this build reads up to {FORMAT_VERSION}"
+        )));
+    }
+
+    let num_strings = reader.len()?;
+    let mut strings = Vec::with_capacity(num_strings);
+    for _ in 0..num_strings {
+        let len = reader.len()?;
+        let s = std::str::from_utf8(reader.take(len)?)
+            .map_err(|_| invalid("string table entry is not UTF-8"))?;
+        strings.push(s);
+    }
+    let mut decoder = Decoder { strings, depth: 0 };
+
+    let mut header = reader.record()?;
+    let name = decoder.string(&mut header)?.to_string();
+    let level = match header.u8()? {
+        0 => CircuitLevel::Logical,
+        1 => CircuitLevel::Physical,
+        tag => return Err(invalid(format!("unknown circuit level {tag}"))),
+    };
+    let global_phase = header.f64()?;
+    let mut qubits = Vec::new();
+    for _ in 0..header.len()? {
+        let (id, register, index) = decoder.wire(&mut header)?;
+        qubits.push(Qubit {
+            id: QubitId(id),
+            register,
+            index,
+        });
+    }
+    let mut clbits = Vec::new();
+    for _ in 0..header.len()? {
+        let (id, register, index) = decoder.wire(&mut header)?;
+        clbits.push(Clbit {
+            id: ClbitId(id),
+            register,
+            index,
+        });
+    }
+
+    let mut dag = CircuitDag::new();
+    for qubit in &qubits {
+        dag.add_qubit(qubit.id);
+    }
+    for clbit in &clbits {
+        dag.add_clbit(clbit.id);
+    }
+    if dag.num_qubits() != qubits.len() || dag.num_clbits() != clbits.len() {
+        return Err(invalid("duplicate wire"));
+    }
+    dag.set_level(level);
+    dag.set_global_phase(global_phase);
+    for _ in 0..reader.len()? {
+        dag.apply(decoder.instruction(&mut reader)?)?;
+    }
+
+    Ok(Decoded {
+        name,
+        qubits,
+        clbits,
+        dag,
+    })
+}
+
+impl Circuit {
+    /// Serialize the circuit to the compact binary format.
+    ///
+    /// See the [`binary`](crate::binary) module for the layout and its
+    /// compatibility guarantees.
+    ///
+    /// ```
+    /// use arvak_ir::Circuit;
+    ///
+    /// let circuit = Circuit::bell()?;
+    /// let restored = Circuit::from_bytes(&circuit.to_bytes())?;
+    /// assert_eq!(restored.name(), "bell");
+    /// assert_eq!(restored.dag().num_ops(), circuit.dag().num_ops());
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(self.name(), self.qubits(), self.clbits(), self.dag())
+    }
+
+    /// Deserialize a circuit written by [`Circuit::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> IrResult<Self> {
+        let decoded = decode(bytes)?;
+        Ok(Circuit::from_parts(
+            decoded.name,
+            decoded.qubits,
+            decoded.clbits,
+            decoded.dag,
+        ))
+    }
+}
+
+impl CircuitDag {
+    /// Serialize the DAG to the compact binary format.
+    ///
+    /// The result can also be read back with [`Circuit::from_bytes`], which
+    /// names the circuit `"circuit"` like [`Circuit::from_dag`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut qubits: Vec<Qubit> = self.qubits().map(Qubit::new).collect();
+        qubits.sort_by_key(|q| q.id.0);
+        let mut clbits: Vec<Clbit> = self.clbits().map(Clbit::new).collect();
+        clbits.sort_by_key(|c| c.id.0);
+        encode("circuit", &qubits, &clbits, self)
+    }
+
+    /// Deserialize a DAG written by [`CircuitDag::to_bytes`] or
+    /// [`Circuit::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> IrResult<Self> {
+        Ok(decode(bytes)?.dag)
+    }
+}
+
+fn invalid(message: impl Into<String>) -> IrError {
+    IrError::InvalidBinary(message.into())
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Writes records and interns strings into the string table.
+#[derive(Default)]
+struct Encoder<'a> {
+    indices: FxHashMap<&'a str, u64>,
+    table: Vec<&'a str>,
+    /// Oldest reader version required by what has been written.
+    min_reader: u16,
+}
+
+impl<'a> Encoder<'a> {
+    /// Write a length-prefixed record filled in by `f`.
+    fn record(&mut self, out: &mut Vec<u8>, f: impl FnOnce(&mut Self, &mut Vec<u8>)) {
+        let start = out.len();
+        f(self, out);
+        let mut prefix = Vec::with_capacity(2);
+        write_varint(&mut prefix, (out.len() - start) as u64);
+        out.splice(start..start, prefix);
+    }
+
+    fn string(&mut self, out: &mut Vec<u8>, s: &'a str) {
+        let next = self.table.len() as u64;
+        let index = *self.indices.entry(s).or_insert_with(|| {
+            self.table.push(s);
+            next
+        });
+        write_varint(out, index);
+    }
+
+    fn wire(&mut self, out: &mut Vec<u8>, id: u32, register: Option<&'a str>, index: Option<u32>) {
+        write_varint(out, u64::from(id));
+        let mut flags = 0;
+        if register.is_some() {
+            flags |= HAS_REGISTER;
+        }
+        if index.is_some() {
+            flags |= HAS_INDEX;
+        }
+        out.push(flags);
+        if let Some(register) = register {
+            self.string(out, register);
+        }
+        if let Some(index) = index {
+            write_varint(out, u64::from(index));
+        }
+    }
+
+    fn instruction(&mut self, out: &mut Vec<u8>, inst: &'a Instruction) {
+        self.record(out, |enc, out| {
+            match &inst.kind {
+                InstructionKind::Gate(gate) => {
+                    enc.gate_kind(out, &gate.kind);
+                    let mut flags = 0;
+                    if gate.label.is_some() {
+                        flags |= HAS_LABEL;
+                    }
+                    if gate.condition.is_some() {
+                        flags |= HAS_CONDITION;
+                    }
+                    if gate.duration.is_some() {
+                        flags |= HAS_DURATION;
+                    }
+                    out.push(flags);
+                    if let Some(label) = &gate.label {
+                        enc.string(out, label);
+                    }
+                    if let Some(condition) = &gate.condition {
+                        enc.condition(out, condition);
+                    }
+                }
+                InstructionKind::Measure => out.push(KIND_MEASURE),
+                InstructionKind::Reset => out.push(KIND_RESET),
+                InstructionKind::Barrier => out.push(KIND_BARRIER),
+                InstructionKind::Delay { duration } => {
+                    out.push(KIND_DELAY);
+                    write_varint(out, *duration);
+                }
+                InstructionKind::Shuttle { from_zone, to_zone } => {
+                    out.push(KIND_SHUTTLE);
+                    write_varint(out, u64::from(*from_zone));
+                    write_varint(out, u64::from(*to_zone));
+                }
+                InstructionKind::NoiseChannel { model, role } => {
+                    out.push(KIND_NOISE);
+                    enc.noise_model(out, model);
+                    out.push(match role {
+                        NoiseRole::Deficit => 0,
+                        NoiseRole::Resource => 1,
+                    });
+                }
+                InstructionKind::IfElse {
+                    condition,
+                    then_body,
+                    else_body,
+                } => {
+                    out.push(KIND_IF_ELSE);
+                    enc.condition(out, condition);
+                    for body in [then_body, else_body] {
+                        write_varint(out, body.len() as u64);
+                        for inst in body {
+                            enc.instruction(out, inst);
+                        }
+                    }
+                }
+                InstructionKind::WhileLoop {
+                    condition,
+                    body,
+                    max_iterations,
+                } => {
+                    enc.min_reader = enc.min_reader.max(WHILE_LOOP_READER_VERSION);
+                    out.push(KIND_WHILE_LOOP);
+                    enc.condition(out, condition);
+                    write_varint(out, u64::from(*max_iterations));
+                    write_varint(out, body.len() as u64);
+                    for inst in body {
+                        enc.instruction(out, inst);
+                    }
+                }
+            }
+            write_varint(out, inst.qubits.len() as u64);
+            for qubit in &inst.qubits {
+                write_varint(out, u64::from(qubit.0));
+            }
+            write_varint(out, inst.clbits.len() as u64);
+            for clbit in &inst.clbits {
+                write_varint(out, u64::from(clbit.0));
+            }
+            if let InstructionKind::Gate(Gate {
+                duration: Some(duration),
+                ..
+            }) = &inst.kind
+            {
+                write_varint(out, *duration);
+            }
+            if !inst.metadata.is_empty() {
+                write_varint(out, inst.metadata.len() as u64);
+                for (key, value) in &inst.metadata {
+                    enc.string(out, key);
+                    enc.string(out, value);
+                }
+            }
+        });
+    }
+
+    /// Write a gate kind tag followed by the gate.
+    fn gate_kind(&mut self, out: &mut Vec<u8>, kind: &'a GateKind) {
+        match kind {
+            GateKind::Standard(standard) => {
+                out.push(KIND_STANDARD_GATE);
+                self.standard_gate(out, standard);
+            }
+            GateKind::Custom(custom) => {
+                out.push(KIND_CUSTOM_GATE);
+                self.custom_gate(out, custom);
+            }
+            GateKind::Modified(modified) => {
+                self.min_reader = self.min_reader.max(MODIFIED_GATE_READER_VERSION);
+                out.push(KIND_MODIFIED_GATE);
+                write_varint(out, u64::from(modified.num_controls()));
+                out.extend_from_slice(&modified.power().to_le_bytes());
+                self.gate_kind(out, modified.base());
+            }
+        }
+    }
+
+    fn standard_gate(&mut self, out: &mut Vec<u8>, gate: &'a StandardGate) {
+        out.push(standard_gate_code(gate));
+        if let StandardGate::MCX(controls)
+        | StandardGate::MCZ(controls)
+        | StandardGate::MCP(controls, _) = gate
+        {
+            write_varint(out, u64::from(*controls));
+        }
+        for param in gate.parameters() {
+            self.expr(out, param);
+        }
+    }
+
+    fn custom_gate(&mut self, out: &mut Vec<u8>, gate: &'a CustomGate) {
+        self.string(out, &gate.name);
+        write_varint(out, u64::from(gate.num_qubits));
+        write_varint(out, gate.params.len() as u64);
+        for param in &gate.params {
+            self.expr(out, param);
+        }
+        let mut flags = 0;
+        if gate.matrix.is_some() {
+            flags |= HAS_MATRIX;
+        }
+        if gate.definition.is_some() {
+            flags |= HAS_DEFINITION;
+        }
+        out.push(flags);
+        if let Some(matrix) = &gate.matrix {
+            write_varint(out, matrix.len() as u64);
+            for entry in matrix {
+                out.extend_from_slice(&entry.re.to_le_bytes());
+                out.extend_from_slice(&entry.im.to_le_bytes());
+            }
+        }
+        if let Some(definition) = &gate.definition {
+            write_varint(out, definition.params.len() as u64);
+            for param in &definition.params {
+                self.string(out, param);
+            }
+            write_varint(out, definition.body.len() as u64);
+            for inst in &definition.body {
+                self.instruction(out, inst);
+            }
+        }
+    }
+
+    fn condition(&mut self, out: &mut Vec<u8>, condition: &'a ClassicalCondition) {
+        self.string(out, &condition.register);
+        write_varint(out, condition.value);
+        write_varint(out, condition.clbits.len() as u64);
+        for clbit in &condition.clbits {
+            write_varint(out, u64::from(clbit.0));
+        }
+    }
+
+    fn noise_model(&mut self, out: &mut Vec<u8>, model: &'a NoiseModel) {
+        let (tag, value) = match model {
+            NoiseModel::Depolarizing { p } => (0, *p),
+            NoiseModel::AmplitudeDamping { gamma } => (1, *gamma),
+            NoiseModel::PhaseDamping { gamma } => (2, *gamma),
+            NoiseModel::BitFlip { p } => (3, *p),
+            NoiseModel::PhaseFlip { p } => (4, *p),
+            NoiseModel::ReadoutError { p } => (5, *p),
+            NoiseModel::Custom { name, params } => {
+                out.push(6);
+                self.string(out, name);
+                write_varint(out, params.len() as u64);
+                for (key, value) in params {
+                    self.string(out, key);
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+                return;
+            }
+        };
+        out.push(tag);
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn expr(&mut self, out: &mut Vec<u8>, expr: &'a ParameterExpression) {
+        match expr {
+            ParameterExpression::Constant(value) => {
+                out.push(EXPR_CONSTANT);
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            ParameterExpression::Symbol(name) => {
+                out.push(EXPR_SYMBOL);
+                self.string(out, name);
+            }
+            ParameterExpression::Pi => out.push(EXPR_PI),
+            ParameterExpression::Neg(e) => {
+                out.push(EXPR_NEG);
+                self.expr(out, e);
+            }
+            ParameterExpression::Add(a, b)
+            | ParameterExpression::Sub(a, b)
+            | ParameterExpression::Mul(a, b)
+            | ParameterExpression::Div(a, b) => {
+                out.push(match expr {
+                    ParameterExpression::Add(..) => EXPR_ADD,
+                    ParameterExpression::Sub(..) => EXPR_SUB,
+                    ParameterExpression::Mul(..) => EXPR_MUL,
+                    _ => EXPR_DIV,
+                });
+                self.expr(out, a);
+                self.expr(out, b);
+            }
+        }
+    }
+}
+
+/// A bounds-checked cursor over encoded bytes.
+struct Reader<'b> {
+    bytes: &'b [u8],
+}
+
+impl<'b> Reader<'b> {
+    fn new(bytes: &'b [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, n: usize) -> IrResult<&'b [u8]> {
+        if n > self.bytes.len() {
+            return Err(invalid("unexpected end of data"));
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> IrResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16_le(&mut self) -> IrResult<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn f64(&mut self) -> IrResult<f64> {
+        let bytes = self.take(8)?;
+        Ok(f64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn varint(&mut self) -> IrResult<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("varint too long"))
+    }
+
+    fn u32(&mut self) -> IrResult<u32> {
+        u32::try_from(self.varint()?).map_err(|_| invalid("integer out of range"))
+    }
+
+    /// A count or length; bounded by the remaining bytes, since every
+    /// element takes at least one.
+    fn len(&mut self) -> IrResult<usize> {
+        let len = self.varint()?;
+        if len > self.bytes.len() as u64 {
+            return Err(invalid("length exceeds the remaining data"));
+        }
+        Ok(len as usize)
+    }
+
+    /// Whether every byte has been read.
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Split off the next record; fields it has beyond those this build
+    /// reads are skipped with it.
+    fn record(&mut self) -> IrResult<Reader<'b>> {
+        let len = self.len()?;
+        Ok(Reader::new(self.take(len)?))
+    }
+}
+
+/// Decodes records against the string table.
+struct Decoder<'b> {
+    strings: Vec<&'b str>,
+    depth: usize,
+}
+
+impl<'b> Decoder<'b> {
+    fn string(&self, r: &mut Reader<'_>) -> IrResult<&'b str> {
+        let index = r.varint()?;
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.strings.get(i).copied())
+            .ok_or_else(|| invalid(format!("string index {index} out of range")))
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> IrResult<T>) -> IrResult<T> {
+        if self.depth >= MAX_DEPTH {
+            return Err(invalid("nesting too deep"));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn wire(&self, r: &mut Reader<'_>) -> IrResult<(u32, Option<String>, Option<u32>)> {
+        let id = r.u32()?;
+        let flags = r.u8()?;
+        let register = if flags & HAS_REGISTER != 0 {
+            Some(self.string(r)?.to_string())
+        } else {
+            None
+        };
+        let index = if flags & HAS_INDEX != 0 {
+            Some(r.u32()?)
+        } else {
+            None
+        };
+        Ok((id, register, index))
+    }
+
+    fn instruction(&mut self, r: &mut Reader<'_>) -> IrResult<Instruction> {
+        let mut r = r.record()?;
+        self.nested(|dec| {
+            let mut has_duration = false;
+            let mut kind = match r.u8()? {
+                tag @ (KIND_STANDARD_GATE | KIND_CUSTOM_GATE | KIND_MODIFIED_GATE) => {
+                    let kind = dec.gate_kind(&mut r, tag)?;
+                    let flags = r.u8()?;
+                    has_duration = flags & HAS_DURATION != 0;
+                    let label = if flags & HAS_LABEL != 0 {
+                        Some(dec.string(&mut r)?.to_string())
+                    } else {
+                        None
+                    };
+                    let condition = if flags & HAS_CONDITION != 0 {
+                        Some(dec.condition(&mut r)?)
+                    } else {
+                        None
+                    };
+                    InstructionKind::Gate(Gate {
+                        kind,
+                        label,
+                        condition,
+                        duration: None,
+                    })
+                }
+                KIND_MEASURE => InstructionKind::Measure,
+                KIND_RESET => InstructionKind::Reset,
+                KIND_BARRIER => InstructionKind::Barrier,
+                KIND_DELAY => InstructionKind::Delay {
+                    duration: r.varint()?,
+                },
+                KIND_SHUTTLE => InstructionKind::Shuttle {
+                    from_zone: r.u32()?,
+                    to_zone: r.u32()?,
+                },
+                KIND_NOISE => InstructionKind::NoiseChannel {
+                    model: dec.noise_model(&mut r)?,
+                    role: match r.u8()? {
+                        0 => NoiseRole::Deficit,
+                        1 => NoiseRole::Resource,
+                        tag => return Err(invalid(format!("unknown noise role {tag}"))),
+                    },
+                },
+                KIND_IF_ELSE => {
+                    let condition = dec.condition(&mut r)?;
+                    let then_body = dec.instructions(&mut r)?;
+                    let else_body = dec.instructions(&mut r)?;
+                    InstructionKind::IfElse {
+                        condition,
+                        then_body,
+                        else_body,
+                    }
+                }
+                KIND_WHILE_LOOP => InstructionKind::WhileLoop {
+                    condition: dec.condition(&mut r)?,
+                    max_iterations: r.u32()?,
+                    body: dec.instructions(&mut r)?,
+                },
+                tag => return Err(invalid(format!("unknown instruction kind {tag}"))),
+            };
+
+            let mut qubits = Vec::new();
+            for _ in 0..r.len()? {
+                qubits.push(QubitId(r.u32()?));
+            }
+            let mut clbits = Vec::new();
+            for _ in 0..r.len()? {
+                clbits.push(ClbitId(r.u32()?));
+            }
+            if has_duration {
+                if let InstructionKind::Gate(gate) = &mut kind {
+                    gate.duration = Some(r.varint()?);
+                }
+            }
+            let mut metadata = InstructionMetadata::new();
+            if !r.is_empty() {
+                for _ in 0..r.len()? {
+                    let key = dec.string(&mut r)?.to_string();
+                    metadata.insert(key, dec.string(&mut r)?.to_string());
+                }
+            }
+            Ok(Instruction {
+                kind,
+                qubits,
+                clbits,
+                metadata,
+            })
+        })
+    }
+
+    fn instructions(&mut self, r: &mut Reader<'_>) -> IrResult<Vec<Instruction>> {
+        let len = r.len()?;
+        let mut body = Vec::with_capacity(len);
+        for _ in 0..len {
+            body.push(self.instruction(r)?);
+        }
+        Ok(body)
+    }
+
+    /// Read the gate for a gate kind `tag`.
+    fn gate_kind(&mut self, r: &mut Reader<'_>, tag: u8) -> IrResult<GateKind> {
+        Ok(match tag {
+            KIND_STANDARD_GATE => GateKind::Standard(self.standard_gate(r)?),
+            KIND_CUSTOM_GATE => GateKind::Custom(self.custom_gate(r)?),
+            KIND_MODIFIED_GATE => {
+                let num_controls = r.u32()?;
+                let power = r.f64()?;
+                let base_tag = r.u8()?;
+                let base = self.nested(|dec| dec.gate_kind(r, base_tag))?;
+                ModifiedGate::new(base, num_controls, power).into()
+            }
+            tag => return Err(invalid(format!("unknown gate kind {tag}"))),
+        })
+    }
+
+    fn standard_gate(&mut self, r: &mut Reader<'_>) -> IrResult<StandardGate> {
+        Ok(match r.u8()? {
+            0 => StandardGate::I,
+            1 => StandardGate::X,
+            2 => StandardGate::Y,
+            3 => StandardGate::Z,
+            4 => StandardGate::H,
+            5 => StandardGate::S,
+            6 => StandardGate::Sdg,
+            7 => StandardGate::T,
+            8 => StandardGate::Tdg,
+            9 => StandardGate::SX,
+            10 => StandardGate::SXdg,
+            11 => StandardGate::Rx(self.expr(r)?),
+            12 => StandardGate::Ry(self.expr(r)?),
+            13 => StandardGate::Rz(self.expr(r)?),
+            14 => StandardGate::P(self.expr(r)?),
+            15 => StandardGate::U(self.expr(r)?, self.expr(r)?, self.expr(r)?),
+            16 => StandardGate::CX,
+            17 => StandardGate::CY,
+            18 => StandardGate::CZ,
+            19 => StandardGate::CH,
+            20 => StandardGate::Swap,
+            21 => StandardGate::ISwap,
+            22 => StandardGate::CRx(self.expr(r)?),
+            23 => StandardGate::CRy(self.expr(r)?),
+            24 => StandardGate::CRz(self.expr(r)?),
+            25 => StandardGate::CP(self.expr(r)?),
+            26 => StandardGate::RXX(self.expr(r)?),
+            27 => StandardGate::RYY(self.expr(r)?),
+            28 => StandardGate::RZZ(self.expr(r)?),
+            29 => StandardGate::CCX,
+            30 => StandardGate::CSwap,
+            31 => StandardGate::MCX(r.u32()?),
+            32 => StandardGate::MCZ(r.u32()?),
+            33 => StandardGate::MCP(r.u32()?, self.expr(r)?),
+            34 => StandardGate::PRX(self.expr(r)?, self.expr(r)?),
+            35 => StandardGate::ECR,
+            code => return Err(invalid(format!("unknown standard gate {code}"))),
+        })
+    }
+
+    fn custom_gate(&mut self, r: &mut Reader<'_>) -> IrResult<CustomGate> {
+        let name = self.string(r)?.to_string();
+        let num_qubits = r.u32()?;
+        let mut params = Vec::new();
+        for _ in 0..r.len()? {
+            params.push(self.expr(r)?);
+        }
+        let flags = r.u8()?;
+        let matrix = if flags & HAS_MATRIX != 0 {
+            let len = r.len()?;
+            let mut matrix = Vec::with_capacity(len);
+            for _ in 0..len {
+                matrix.push(Complex64::new(r.f64()?, r.f64()?));
+            }
+            Some(matrix)
+        } else {
+            None
+        };
+        let definition = if flags & HAS_DEFINITION != 0 {
+            let mut formal = Vec::new();
+            for _ in 0..r.len()? {
+                formal.push(self.string(r)?.to_string());
+            }
+            let body = self.instructions(r)?;
+            Some(Box::new(GateDefinition {
+                params: formal,
+                body,
+            }))
+        } else {
+            None
+        };
+        Ok(CustomGate {
+            name,
+            num_qubits,
+            params,
+            matrix,
+            definition,
+        })
+    }
+
+    fn condition(&self, r: &mut Reader<'_>) -> IrResult<ClassicalCondition> {
+        let register = self.string(r)?.to_string();
+        let value = r.varint()?;
+        let mut clbits = Vec::new();
+        for _ in 0..r.len()? {
+            clbits.push(ClbitId(r.u32()?));
+        }
+        Ok(ClassicalCondition {
+            register,
+            value,
+            clbits,
+        })
+    }
+
+    fn noise_model(&self, r: &mut Reader<'_>) -> IrResult<NoiseModel> {
+        Ok(match r.u8()? {
+            0 => NoiseModel::Depolarizing { p: r.f64()? },
+            1 => NoiseModel::AmplitudeDamping { gamma: r.f64()? },
+            2 => NoiseModel::PhaseDamping { gamma: r.f64()? },
+            3 => NoiseModel::BitFlip { p: r.f64()? },
+            4 => NoiseModel::PhaseFlip { p: r.f64()? },
+            5 => NoiseModel::ReadoutError { p: r.f64()? },
+            6 => {
+                let name = self.string(r)?.to_string();
+                let mut params = std::collections::BTreeMap::new();
+                for _ in 0..r.len()? {
+                    params.insert(self.string(r)?.to_string(), r.f64()?);
+                }
+                NoiseModel::Custom { name, params }
+            }
+            tag => return Err(invalid(format!("unknown noise model {tag}"))),
+        })
+    }
+
+    fn expr(&mut self, r: &mut Reader<'_>) -> IrResult<ParameterExpression> {
+        self.nested(|dec| {
+            let tag = r.u8()?;
+            Ok(match tag {
+                EXPR_CONSTANT => ParameterExpression::Constant(r.f64()?),
+                EXPR_SYMBOL => ParameterExpression::Symbol(dec.string(r)?.to_string()),
+                EXPR_PI => ParameterExpression::Pi,
+                EXPR_NEG => ParameterExpression::Neg(Box::new(dec.expr(r)?)),
+                EXPR_ADD | EXPR_SUB | EXPR_MUL | EXPR_DIV => {
+                    let a = Box::new(dec.expr(r)?);
+                    let b = Box::new(dec.expr(r)?);
+                    match tag {
+                        EXPR_ADD => ParameterExpression::Add(a, b),
+                        EXPR_SUB => ParameterExpression::Sub(a, b),
+                        EXPR_MUL => ParameterExpression::Mul(a, b),
+                        _ => ParameterExpression::Div(a, b),
+                    }
+                }
+                tag => return Err(invalid(format!("unknown expression {tag}"))),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag::WireId;
+    use std::collections::{BTreeMap, HashMap};
+
+    /// The operations on each wire, in order; equal for equivalent DAGs
+    /// even when their topological orders differ.
+    fn ops(dag: &CircuitDag) -> HashMap<WireId, Vec<Instruction>> {
+        let mut wires: HashMap<WireId, Vec<Instruction>> = HashMap::new();
+        for (_, inst) in dag.topological_ops() {
+            let qubits = inst.qubits.iter().map(|&q| WireId::Qubit(q));
+            let clbits = inst.clbits.iter().map(|&c| WireId::Clbit(c));
+            for wire in qubits.chain(clbits) {
+                wires.entry(wire).or_default().push(inst.clone());
+            }
+        }
+        wires
+    }
+
+    fn sample() -> Circuit {
+        let mut circuit = Circuit::new("sample");
+        let q = circuit.add_qreg("q", 3);
+        let c = circuit.add_creg("c", 2);
+        let theta = ParameterExpression::symbol("theta");
+
+        let mut body = Circuit::with_size("zz", 2, 0);
+        body.cx(QubitId(0), QubitId(1)).unwrap();
+        body.rz(ParameterExpression::symbol("t"), QubitId(1))
+            .unwrap();
+        body.cx(QubitId(0), QubitId(1)).unwrap();
+        let zz = CustomGate::new("zz", 2)
+            .with_params(vec![theta.clone() * ParameterExpression::constant(2.0)])
+            .with_definition(GateDefinition::from_circuit(vec!["t".into()], &body).unwrap())
+            .unwrap();
+
+        circuit
+            .h(q[0])
+            .unwrap()
+            .u(theta.clone(), ParameterExpression::pi(), -theta, q[1])
+            .unwrap()
+            .mcp(ParameterExpression::constant(0.25), &[q[0], q[1]], q[2])
+            .unwrap()
+            .measure(q[0], c[0])
+            .unwrap()
+            .noise_channel(
+                NoiseModel::Custom {
+                    name: "leakage".into(),
+                    params: BTreeMap::from([("rate".into(), 0.01)]),
+                },
+                NoiseRole::Resource,
+                q[1],
+            )
+            .unwrap()
+            .delay(q[0], 40)
+            .unwrap();
+        let dag = circuit.dag_mut();
+        dag.apply(Instruction::gate(zz, [q[1], q[2]])).unwrap();
+        dag.apply(Instruction::gate(
+            Gate::standard(StandardGate::X)
+                .with_label("flip")
+                .with_condition(ClassicalCondition::on_clbit(c[0], 1))
+                .with_duration(32),
+            [q[2]],
+        ))
+        .unwrap();
+        dag.apply(
+            Instruction::two_qubit_gate(StandardGate::Swap, q[1], q[2])
+                .with_origin("SabreRouting")
+                .with_label("swap-0"),
+        )
+        .unwrap();
+        circuit.dag_mut().set_global_phase(0.5);
+        circuit.dag_mut().set_level(CircuitLevel::Physical);
+        circuit
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let circuit = sample();
+        let restored = Circuit::from_bytes(&circuit.to_bytes()).unwrap();
+
+        assert_eq!(restored.name(), "sample");
+        assert_eq!(restored.qubits(), circuit.qubits());
+        assert_eq!(restored.clbits(), circuit.clbits());
+        assert_eq!(restored.dag().global_phase(), 0.5);
+        assert_eq!(restored.dag().level(), CircuitLevel::Physical);
+        assert_eq!(ops(restored.dag()), ops(circuit.dag()));
+
+        let dag = CircuitDag::from_bytes(&circuit.dag().to_bytes()).unwrap();
+        assert_eq!(ops(&dag), ops(circuit.dag()));
+    }
+
+    #[test]
+    fn test_while_loop_requires_version_3_reader() {
+        let mut attempt = Circuit::with_size("attempt", 2, 1);
+        attempt.h(QubitId(1)).unwrap();
+        attempt.measure(QubitId(1), ClbitId(0)).unwrap();
+        let mut circuit = Circuit::with_size("rus", 2, 1);
+        circuit
+            .while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), &attempt, 12)
+            .unwrap();
+
+        let bytes = circuit.to_bytes();
+        assert_eq!(bytes[6..8], WHILE_LOOP_READER_VERSION.to_le_bytes());
+        let restored = Circuit::from_bytes(&bytes).unwrap();
+        assert_eq!(ops(restored.dag()), ops(circuit.dag()));
+
+        // Circuits without loops stay readable by version 1 readers.
+        assert_eq!(sample().to_bytes()[6..8], MIN_READER_VERSION.to_le_bytes());
+    }
+
+    #[test]
+    fn test_modified_gate_roundtrip() {
+        let mut circuit = Circuit::with_size("qpe", 3, 0);
+        circuit
+            .gate(
+                Gate::standard(StandardGate::T).power(4.0).controlled(1),
+                [QubitId(0), QubitId(2)],
+            )
+            .unwrap()
+            .gate(
+                Gate::standard(StandardGate::H).controlled(2).power(-1.0),
+                [QubitId(0), QubitId(1), QubitId(2)],
+            )
+            .unwrap();
+
+        let bytes = circuit.to_bytes();
+        assert_eq!(bytes[6..8], MODIFIED_GATE_READER_VERSION.to_le_bytes());
+        let restored = Circuit::from_bytes(&bytes).unwrap();
+        assert_eq!(ops(restored.dag()), ops(circuit.dag()));
+    }
+
+    #[test]
+    fn test_smaller_than_json() {
+        let circuit = Circuit::ghz(20).unwrap();
+        let insts: Vec<_> = circuit.dag().topological_ops().map(|(_, i)| i).collect();
+        let json = serde_json::to_vec(&insts).unwrap();
+        let bytes = circuit.to_bytes();
+        assert!(
+            bytes.len() * 5 < json.len(),
+            "{} vs {}",
+            bytes.len(),
+            json.len()
+        );
+    }
+
+    #[test]
+    fn test_rejects_corrupt_input() {
+        let bytes = sample().to_bytes();
+        for end in 0..bytes.len() {
+            assert!(Circuit::from_bytes(&bytes[..end]).is_err(), "prefix {end}");
+        }
+        assert!(Circuit::from_bytes(b"JSON{}").is_err());
+    }
+
+    #[test]
+    fn test_forward_compatibility() {
+        let circuit = Circuit::bell().unwrap();
+
+        // A newer writer appending fields to a record (after an empty
+        // metadata list) and a section at the end is still readable.
+        let mut encoder = Encoder::default();
+        let mut body = Vec::new();
+        let inst = Instruction::single_qubit_gate(StandardGate::H, QubitId(0));
+        encoder.instruction(&mut body, &inst);
+        body[0] += 3;
+        body.extend_from_slice(&[0x00, 0xAA, 0xBB]);
+        let mut decoder = Decoder {
+            strings: vec![],
+            depth: 0,
+        };
+        let mut reader = Reader::new(&body);
+        assert_eq!(decoder.instruction(&mut reader).unwrap(), inst);
+        assert!(reader.bytes.is_empty());
+
+        let mut bytes = circuit.to_bytes();
+        bytes[4..6].copy_from_slice(&7u16.to_le_bytes());
+        bytes.extend_from_slice(b"future section");
+        let restored = Circuit::from_bytes(&bytes).unwrap();
+        assert_eq!(ops(restored.dag()), ops(circuit.dag()));
+
+        // One that requires a newer reader is refused.
+        bytes[6..8].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = Circuit::from_bytes(&bytes).unwrap_err();
+        assert!(err.to_string().contains("format version"));
+    }
+}
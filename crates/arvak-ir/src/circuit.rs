@@ -1,8 +1,13 @@
 //! High-level circuit builder API.
 
+use std::collections::{BTreeSet, HashMap};
+use std::ops::{Bound, RangeBounds};
+
+use rustc_hash::FxHashMap;
+
 use crate::dag::CircuitDag;
-use crate::error::IrResult;
-use crate::gate::{Gate, StandardGate};
+use crate::error::{IrError, IrResult};
+use crate::gate::{ClassicalCondition, Gate, StandardGate};
 use crate::instruction::Instruction;
 use crate::noise::{NoiseModel, NoiseRole};
 use crate::parameter::ParameterExpression;
@@ -462,6 +467,77 @@ impl Circuit {
         Ok(self)
     }
 
+    // =========================================================================
+    // Multi-controlled gates
+    // =========================================================================
+
+    /// Apply an X gate controlled on all of `controls`.
+    ///
+    /// With zero, one or two controls this applies `x`, `cx` or `ccx`;
+    /// larger gates are kept as a single [`StandardGate::MCX`] so that
+    /// compiler passes can choose how to decompose them.
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, QubitId};
+    ///
+    /// let mut circuit = Circuit::with_size("mcx", 4, 0);
+    /// circuit
+    ///     .mcx(&[QubitId(0), QubitId(1), QubitId(2)], QubitId(3))
+    ///     .unwrap();
+    /// assert_eq!(circuit.depth(), 1);
+    /// ```
+    pub fn mcx(&mut self, controls: &[QubitId], target: QubitId) -> IrResult<&mut Self> {
+        let gate = match controls.len() {
+            0 => StandardGate::X,
+            1 => StandardGate::CX,
+            2 => StandardGate::CCX,
+            n => StandardGate::MCX(num_controls(n)),
+        };
+        self.apply_controlled(gate, controls, target)
+    }
+
+    /// Apply a Z gate controlled on all of `controls`.
+    ///
+    /// With zero or one control this applies `z` or `cz`.
+    pub fn mcz(&mut self, controls: &[QubitId], target: QubitId) -> IrResult<&mut Self> {
+        let gate = match controls.len() {
+            0 => StandardGate::Z,
+            1 => StandardGate::CZ,
+            n => StandardGate::MCZ(num_controls(n)),
+        };
+        self.apply_controlled(gate, controls, target)
+    }
+
+    /// Apply a phase gate P(λ) controlled on all of `controls`.
+    ///
+    /// With zero or one control this applies `p` or `cp`.
+    pub fn mcp(
+        &mut self,
+        lambda: impl Into<ParameterExpression>,
+        controls: &[QubitId],
+        target: QubitId,
+    ) -> IrResult<&mut Self> {
+        let lambda = lambda.into();
+        let gate = match controls.len() {
+            0 => StandardGate::P(lambda),
+            1 => StandardGate::CP(lambda),
+            n => StandardGate::MCP(num_controls(n), lambda),
+        };
+        self.apply_controlled(gate, controls, target)
+    }
+
+    /// Apply a controlled gate on `controls` followed by `target`.
+    fn apply_controlled(
+        &mut self,
+        gate: StandardGate,
+        controls: &[QubitId],
+        target: QubitId,
+    ) -> IrResult<&mut Self> {
+        let qubits = controls.iter().copied().chain(std::iter::once(target));
+        self.dag.apply(Instruction::gate(gate, qubits))?;
+        Ok(self)
+    }
+
     // =========================================================================
     // Other operations
     // =========================================================================
@@ -526,6 +602,409 @@ impl Circuit {
         Ok(self)
     }
 
+    // =========================================================================
+    // Classical feedforward
+    // =========================================================================
+
+    /// Condition the most recently added gate on a classical bit.
+    ///
+    /// The gate only executes if `clbit == value` at runtime. The DAG records
+    /// the classical dependency, so the gate stays after the measurement that
+    /// writes `clbit`:
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, ClbitId, QubitId};
+    ///
+    /// let mut circuit = Circuit::with_size("feedforward", 2, 1);
+    /// circuit.h(QubitId(0))?;
+    /// circuit.measure(QubitId(0), ClbitId(0))?;
+    /// circuit.x(QubitId(1))?.conditioned_on(ClbitId(0), 1)?;
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    pub fn conditioned_on(&mut self, clbit: ClbitId, value: u64) -> IrResult<&mut Self> {
+        self.dag
+            .condition_last_op(ClassicalCondition::on_clbit(clbit, value))?;
+        Ok(self)
+    }
+
+    /// Condition the most recently added gate on a group of classical bits
+    /// (least-significant first) holding `value`.
+    pub fn conditioned_on_bits(
+        &mut self,
+        clbits: impl IntoIterator<Item = ClbitId>,
+        value: u64,
+    ) -> IrResult<&mut Self> {
+        self.dag
+            .condition_last_op(ClassicalCondition::on_clbits(clbits, value))?;
+        Ok(self)
+    }
+
+    /// Annotate the most recently added gate with its duration in
+    /// device-specific units (`dt`), for scheduling.
+    pub fn lasting(&mut self, duration: u64) -> IrResult<&mut Self> {
+        self.dag.set_last_op_duration(duration)?;
+        Ok(self)
+    }
+
+    /// Append an if/else block on `condition`.
+    ///
+    /// The bodies are ordinary circuits over the same qubits and classical
+    /// bits as `self` (typically built with [`Circuit::with_size`]); their
+    /// operations are copied in topological order:
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, ClassicalCondition, ClbitId, QubitId};
+    ///
+    /// let mut circuit = Circuit::with_size("branch", 2, 1);
+    /// circuit.h(QubitId(0))?.measure(QubitId(0), ClbitId(0))?;
+    ///
+    /// let mut then_body = Circuit::with_size("then", 2, 1);
+    /// then_body.x(QubitId(1))?;
+    /// let mut else_body = Circuit::with_size("else", 2, 1);
+    /// else_body.h(QubitId(1))?;
+    ///
+    /// circuit.if_else(
+    ///     ClassicalCondition::on_clbit(ClbitId(0), 1),
+    ///     &then_body,
+    ///     Some(&else_body),
+    /// )?;
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    pub fn if_else(
+        &mut self,
+        condition: ClassicalCondition,
+        then_body: &Circuit,
+        else_body: Option<&Circuit>,
+    ) -> IrResult<&mut Self> {
+        let body = |c: &Circuit| -> Vec<Instruction> {
+            c.dag.topological_ops().map(|(_, i)| i.clone()).collect()
+        };
+        let else_body = else_body.map(body).unwrap_or_default();
+        self.dag
+            .apply(Instruction::if_else(condition, body(then_body), else_body))?;
+        Ok(self)
+    }
+
+    /// Append an `if` block without an `else` branch.
+    pub fn if_then(
+        &mut self,
+        condition: ClassicalCondition,
+        then_body: &Circuit,
+    ) -> IrResult<&mut Self> {
+        self.if_else(condition, then_body, None)
+    }
+
+    /// Append a loop that runs `body` while `condition` holds, at most
+    /// `max_iterations` times.
+    ///
+    /// The body is a circuit over the same wires as `self`, as for
+    /// [`Circuit::if_else`]. A repeat-until-success step retries until a
+    /// flag qubit measures 0:
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, ClassicalCondition, ClbitId, QubitId};
+    ///
+    /// let mut attempt = Circuit::with_size("attempt", 2, 1);
+    /// attempt.reset(QubitId(1))?;
+    /// attempt.h(QubitId(1))?.cx(QubitId(1), QubitId(0))?;
+    /// attempt.measure(QubitId(1), ClbitId(0))?;
+    ///
+    /// let mut circuit = Circuit::with_size("rus", 2, 1);
+    /// circuit.compose(&attempt, &[QubitId(0), QubitId(1)], &[ClbitId(0)])?;
+    /// circuit.while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), &attempt, 10)?;
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    pub fn while_loop(
+        &mut self,
+        condition: ClassicalCondition,
+        body: &Circuit,
+        max_iterations: u32,
+    ) -> IrResult<&mut Self> {
+        let body = body.dag.topological_ops().map(|(_, i)| i.clone()).collect();
+        self.dag
+            .apply(Instruction::while_loop(condition, body, max_iterations))?;
+        Ok(self)
+    }
+
+    // =========================================================================
+    // Composition
+    // =========================================================================
+
+    /// Append the operations of `other`, wiring its qubits and classical
+    /// bits onto `self`.
+    ///
+    /// `qubit_map[i]` is the qubit of `self` that receives `other`'s `i`-th
+    /// qubit (and likewise for `clbit_map`). Gates are copied with their
+    /// parameters, so symbolic angles from both circuits stay unbound, and
+    /// `other`'s global phase is added to this circuit's:
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, ClbitId, QubitId};
+    ///
+    /// let mut oracle = Circuit::with_size("oracle", 2, 0);
+    /// oracle.cz(QubitId(0), QubitId(1))?;
+    ///
+    /// let mut circuit = Circuit::with_size("search", 3, 0);
+    /// circuit.compose(&oracle, &[QubitId(2), QubitId(0)], &[])?;
+    /// assert_eq!(circuit.dag().num_ops(), 1);
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    ///
+    /// Fails without modifying `self` if a map does not match `other`'s
+    /// size, names a wire `self` does not have, or repeats a wire.
+    pub fn compose(
+        &mut self,
+        other: &Circuit,
+        qubit_map: &[QubitId],
+        clbit_map: &[ClbitId],
+    ) -> IrResult<&mut Self> {
+        if qubit_map.len() != other.num_qubits() {
+            return Err(IrError::RegisterSizeMismatch {
+                wire: "qubit",
+                expected: other.num_qubits(),
+                got: qubit_map.len(),
+            });
+        }
+        if clbit_map.len() != other.num_clbits() {
+            return Err(IrError::RegisterSizeMismatch {
+                wire: "clbit",
+                expected: other.num_clbits(),
+                got: clbit_map.len(),
+            });
+        }
+
+        let mut qubits = FxHashMap::default();
+        for (from, &to) in other.qubits.iter().zip(qubit_map) {
+            if !self.qubits.iter().any(|q| q.id == to) {
+                return Err(IrError::QubitNotFound {
+                    qubit: to,
+                    gate_name: None,
+                });
+            }
+            if qubits.values().any(|&q| q == to) {
+                return Err(IrError::DuplicateQubit {
+                    qubit: to,
+                    gate_name: None,
+                });
+            }
+            qubits.insert(from.id, to);
+        }
+        let mut clbits = FxHashMap::default();
+        for (from, &to) in other.clbits.iter().zip(clbit_map) {
+            if !self.clbits.iter().any(|c| c.id == to) {
+                return Err(IrError::ClbitNotFound {
+                    clbit: to,
+                    gate_name: None,
+                });
+            }
+            if clbits.values().any(|&c| c == to) {
+                return Err(IrError::DuplicateClbit(to));
+            }
+            clbits.insert(from.id, to);
+        }
+
+        // Every wire `other` uses is one of its own, so the lookups hold.
+        let qubit_map = |q: QubitId| qubits[&q];
+        let clbit_map = |c: ClbitId| clbits[&c];
+        for (_, inst) in other.dag.topological_ops() {
+            self.dag.apply(inst.remap_wires(&qubit_map, &clbit_map))?;
+        }
+        self.dag
+            .set_global_phase(self.dag.global_phase() + other.dag.global_phase());
+        Ok(self)
+    }
+
+    /// Append `other` onto the leading qubits and classical bits of `self`.
+    ///
+    /// Shorthand for [`Circuit::compose`] with `other`'s `i`-th wire mapped
+    /// to the `i`-th wire of `self`; fails with
+    /// [`IrError::RegisterSizeMismatch`] if `self` is the smaller circuit.
+    pub fn append(&mut self, other: &Circuit) -> IrResult<&mut Self> {
+        let qubit_map: Vec<_> = self
+            .qubits
+            .iter()
+            .take(other.num_qubits())
+            .map(|q| q.id)
+            .collect();
+        let clbit_map: Vec<_> = self
+            .clbits
+            .iter()
+            .take(other.num_clbits())
+            .map(|c| c.id)
+            .collect();
+        self.compose(other, &qubit_map, &clbit_map)
+    }
+
+    /// Build the inverse (adjoint) circuit.
+    ///
+    /// The result has the same qubits and classical bits, applies the
+    /// inverse of every instruction in reverse order and negates the global
+    /// phase, so `circuit` followed by `circuit.inverse()?` is the identity.
+    /// Useful for uncomputation and mirror / Loschmidt-echo benchmarks:
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, QubitId};
+    ///
+    /// let mut circuit = Circuit::with_size("prep", 2, 0);
+    /// circuit.h(QubitId(0))?.s(QubitId(0))?.cx(QubitId(0), QubitId(1))?;
+    ///
+    /// let mut echo = circuit.clone();
+    /// echo.append(&circuit.inverse()?)?;
+    /// assert_eq!(echo.dag().num_ops(), 6);
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    ///
+    /// Fails with [`IrError::NonInvertible`] if the circuit contains a
+    /// measurement, reset, noise channel or a gate without a known inverse.
+    pub fn inverse(&self) -> IrResult<Circuit> {
+        let mut dag = CircuitDag::new();
+        for qubit in &self.qubits {
+            dag.add_qubit(qubit.id);
+        }
+        for clbit in &self.clbits {
+            dag.add_clbit(clbit.id);
+        }
+
+        let ops: Vec<&Instruction> = self.dag.topological_ops().map(|(_, i)| i).collect();
+        for inst in ops.into_iter().rev() {
+            dag.apply(inst.inverse()?)?;
+        }
+        dag.set_global_phase(-self.dag.global_phase());
+
+        Ok(Self {
+            name: format!("{}_dg", self.name),
+            qubits: self.qubits.clone(),
+            clbits: self.clbits.clone(),
+            dag,
+            next_qubit_id: self.next_qubit_id,
+            next_clbit_id: self.next_clbit_id,
+        })
+    }
+
+    /// Extract the sub-circuit between two depths.
+    ///
+    /// Keeps the operations of [`CircuitDag::layers`] whose index falls in
+    /// `range`, on the same qubits and classical bits, so consecutive slices
+    /// appended together rebuild the circuit. The global phase goes to the
+    /// slice that starts at depth 0. A range past the end of the circuit
+    /// yields an empty circuit.
+    ///
+    /// ```
+    /// use arvak_ir::Circuit;
+    ///
+    /// let circuit = Circuit::ghz(3)?;
+    /// assert_eq!(circuit.depth(), 4);
+    ///
+    /// let mut rebuilt = circuit.slice(..2)?;
+    /// rebuilt.append(&circuit.slice(2..)?)?;
+    /// assert_eq!(rebuilt.dag().num_ops(), circuit.dag().num_ops());
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> IrResult<Circuit> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let mut dag = CircuitDag::new();
+        for qubit in &self.qubits {
+            dag.add_qubit(qubit.id);
+        }
+        for clbit in &self.clbits {
+            dag.add_clbit(clbit.id);
+        }
+
+        for (depth, layer) in self.dag.layers().into_iter().enumerate() {
+            if !range.contains(&depth) {
+                continue;
+            }
+            for (_, inst) in layer {
+                dag.apply(inst.clone())?;
+            }
+        }
+        if start == 0 {
+            dag.set_global_phase(self.dag.global_phase());
+        }
+        dag.set_level(self.dag.level());
+
+        Ok(Self {
+            name: self.name.clone(),
+            qubits: self.qubits.clone(),
+            clbits: self.clbits.clone(),
+            dag,
+            next_qubit_id: self.next_qubit_id,
+            next_clbit_id: self.next_clbit_id,
+        })
+    }
+
+    // =========================================================================
+    // Parameters
+    // =========================================================================
+
+    /// Names of the unbound symbolic parameters in this circuit.
+    ///
+    /// Sorted by name, with numeric suffixes compared as numbers so the
+    /// elements of a [`ParameterVector`](crate::ParameterVector) come out in
+    /// index order (`theta_2` before `theta_10`). This is the order
+    /// [`Circuit::assign`] binds values in.
+    pub fn parameters(&self) -> Vec<String> {
+        let mut symbols = BTreeSet::new();
+        for (_, inst) in self.dag.topological_ops() {
+            inst.walk(&mut |inst| {
+                if let Some(gate) = inst.as_gate() {
+                    for param in gate.parameters() {
+                        symbols.extend(param.symbols());
+                    }
+                }
+            });
+        }
+        let mut symbols: Vec<String> = symbols.into_iter().collect();
+        symbols.sort_by(|a, b| parameter_sort_key(a).cmp(&parameter_sort_key(b)));
+        symbols
+    }
+
+    /// Bind symbolic parameters by name, returning a new numeric circuit.
+    ///
+    /// Values for names that do not occur in the circuit are ignored. Fails
+    /// with [`IrError::UnboundParameters`], listing the missing names, if any
+    /// parameter has no value.
+    pub fn bind_parameters(&self, values: &HashMap<String, f64>) -> IrResult<Circuit> {
+        let unbound: Vec<String> = self
+            .parameters()
+            .into_iter()
+            .filter(|name| !values.contains_key(name))
+            .collect();
+        if !unbound.is_empty() {
+            return Err(IrError::UnboundParameters(unbound));
+        }
+
+        let mut bound = self.clone();
+        let nodes: Vec<_> = bound.dag.topological_ops().map(|(idx, _)| idx).collect();
+        for node in nodes {
+            if let Some(inst) = bound.dag.get_instruction_mut(node) {
+                inst.bind_parameters(values);
+            }
+        }
+        Ok(bound)
+    }
+
+    /// Bind parameters positionally, in the order of
+    /// [`Circuit::parameters`], returning a new numeric circuit.
+    ///
+    /// Fails with [`IrError::ParameterCountMismatch`] unless there is exactly
+    /// one value per parameter.
+    pub fn assign(&self, values: &[f64]) -> IrResult<Circuit> {
+        let names = self.parameters();
+        if names.len() != values.len() {
+            return Err(IrError::ParameterCountMismatch {
+                expected: names.len(),
+                got: values.len(),
+            });
+        }
+        let values: HashMap<String, f64> = names.into_iter().zip(values.iter().copied()).collect();
+        self.bind_parameters(&values)
+    }
+
     // =========================================================================
     // Noise channels
     // =========================================================================
@@ -619,6 +1098,34 @@ impl Circuit {
         }
     }
 
+    /// Assemble a circuit whose `qubits` and `clbits` are exactly the DAG's
+    /// wires.
+    pub(crate) fn from_parts(
+        name: String,
+        qubits: Vec<Qubit>,
+        clbits: Vec<Clbit>,
+        dag: CircuitDag,
+    ) -> Self {
+        let next_qubit_id = qubits
+            .iter()
+            .map(|q| q.id.0.saturating_add(1))
+            .max()
+            .unwrap_or(0);
+        let next_clbit_id = clbits
+            .iter()
+            .map(|c| c.id.0.saturating_add(1))
+            .max()
+            .unwrap_or(0);
+        Self {
+            name,
+            qubits,
+            clbits,
+            dag,
+            next_qubit_id,
+            next_clbit_id,
+        }
+    }
+
     /// Get the qubits in the circuit.
     pub fn qubits(&self) -> &[Qubit] {
         &self.qubits
@@ -718,9 +1225,27 @@ impl Clone for Circuit {
     }
 }
 
+/// Control count of a multi-controlled gate; oversized counts fail the
+/// DAG's arity check.
+fn num_controls(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+/// Sort key splitting a trailing `_<digits>` suffix off a parameter name,
+/// so vector elements order by index.
+fn parameter_sort_key(name: &str) -> (&str, Option<u64>, &str) {
+    if let Some((prefix, index)) = name.rsplit_once('_') {
+        if let Ok(index) = index.parse() {
+            return (prefix, Some(index), name);
+        }
+    }
+    (name, None, name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::gate::GateKind;
     use std::f64::consts::PI;
 
     #[test]
@@ -808,4 +1333,537 @@ mod tests {
 
         assert_eq!(circuit.depth(), 3); // H, CX, parallel measures
     }
+
+    #[test]
+    fn test_conditioned_on_orders_after_measurement() {
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .x(QubitId(1))
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap();
+
+        // Without the classical wire, X(q1) would run in the first layer.
+        assert_eq!(circuit.depth(), 3);
+        circuit.dag().verify_integrity().unwrap();
+
+        let ops: Vec<_> = circuit.dag().topological_ops().map(|(_, i)| i).collect();
+        assert!(ops[1].is_measure());
+        let gate = ops[2].as_gate().unwrap();
+        assert_eq!(
+            gate.condition,
+            Some(ClassicalCondition::on_clbit(ClbitId(0), 1))
+        );
+        assert_eq!(ops[2].condition_clbits(), &[ClbitId(0)]);
+        assert!(ops[2].clbits.is_empty());
+    }
+
+    #[test]
+    fn test_teleportation_feedforward() {
+        let mut circuit = Circuit::with_size("teleport", 3, 2);
+        circuit.h(QubitId(1)).unwrap();
+        circuit.cx(QubitId(1), QubitId(2)).unwrap();
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(1)).unwrap();
+        circuit
+            .x(QubitId(2))
+            .unwrap()
+            .conditioned_on(ClbitId(1), 1)
+            .unwrap()
+            .z(QubitId(2))
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap();
+
+        assert_eq!(circuit.dag().num_ops(), 8);
+        circuit.dag().verify_integrity().unwrap();
+        let conditional = circuit
+            .dag()
+            .topological_ops()
+            .filter(|(_, i)| i.is_conditional())
+            .count();
+        assert_eq!(conditional, 2);
+    }
+
+    #[test]
+    fn test_conditioned_on_errors() {
+        let mut circuit = Circuit::with_size("test", 1, 1);
+        assert!(matches!(
+            circuit.conditioned_on(ClbitId(0), 1),
+            Err(crate::IrError::InvalidCondition(_))
+        ));
+
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        assert!(matches!(
+            circuit.conditioned_on(ClbitId(0), 1),
+            Err(crate::IrError::InvalidCondition(_))
+        ));
+
+        circuit.x(QubitId(0)).unwrap();
+        assert!(matches!(
+            circuit.conditioned_on(ClbitId(0), 2),
+            Err(crate::IrError::InvalidCondition(_))
+        ));
+        assert!(matches!(
+            circuit.conditioned_on(ClbitId(5), 1),
+            Err(crate::IrError::ClbitNotFound { .. })
+        ));
+        // The gate is left unconditioned after a rejected condition.
+        assert!(
+            !circuit
+                .dag()
+                .topological_ops()
+                .any(|(_, i)| i.is_conditional())
+        );
+    }
+
+    #[test]
+    fn test_if_else_block() {
+        let mut circuit = Circuit::with_size("test", 2, 2);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+
+        let mut then_body = Circuit::with_size("then", 2, 2);
+        then_body.x(QubitId(1)).unwrap();
+        then_body.measure(QubitId(1), ClbitId(1)).unwrap();
+        let mut else_body = Circuit::with_size("else", 2, 2);
+        else_body.h(QubitId(1)).unwrap();
+
+        circuit
+            .if_else(
+                ClassicalCondition::on_clbit(ClbitId(0), 1),
+                &then_body,
+                Some(&else_body),
+            )
+            .unwrap();
+
+        // The block reads c[0], so it follows the measurement.
+        assert_eq!(circuit.depth(), 3);
+        circuit.dag().verify_integrity().unwrap();
+
+        let (_, block) = circuit.dag().topological_ops().last().unwrap();
+        assert!(block.is_if_else());
+        assert_eq!(block.qubits, vec![QubitId(1)]);
+        assert_eq!(block.clbits, vec![ClbitId(1)]);
+        let blocks = block.blocks();
+        assert_eq!(blocks[0].len(), 2);
+        assert_eq!(blocks[1].len(), 1);
+    }
+
+    #[test]
+    fn test_if_else_rejects_invalid_blocks() {
+        let mut circuit = Circuit::with_size("test", 1, 1);
+
+        // Body addresses a qubit the circuit does not have.
+        let mut wide = Circuit::with_size("then", 2, 1);
+        wide.x(QubitId(1)).unwrap();
+        assert!(matches!(
+            circuit.if_then(ClassicalCondition::on_clbit(ClbitId(0), 1), &wide),
+            Err(crate::IrError::QubitNotFound { .. })
+        ));
+
+        // Condition value wider than the compared bits.
+        let mut body = Circuit::with_size("then", 1, 1);
+        body.x(QubitId(0)).unwrap();
+        assert!(matches!(
+            circuit.if_then(ClassicalCondition::on_clbit(ClbitId(0), 2), &body),
+            Err(crate::IrError::InvalidCondition(_))
+        ));
+
+        // Hand-built block whose body escapes its declared operands.
+        let mut escaping = Instruction::if_else(
+            ClassicalCondition::on_clbit(ClbitId(0), 1),
+            vec![Instruction::single_qubit_gate(
+                crate::gate::StandardGate::X,
+                QubitId(0),
+            )],
+            vec![],
+        );
+        escaping.qubits.clear();
+        assert!(matches!(
+            circuit.dag_mut().apply(escaping),
+            Err(crate::IrError::InvalidDag(_))
+        ));
+        assert_eq!(circuit.dag().num_ops(), 0);
+    }
+
+    #[test]
+    fn test_while_loop_block() {
+        let mut attempt = Circuit::with_size("attempt", 2, 1);
+        attempt.h(QubitId(1)).unwrap();
+        attempt.measure(QubitId(1), ClbitId(0)).unwrap();
+
+        let mut circuit = Circuit::with_size("rus", 2, 1);
+        circuit.x(QubitId(0)).unwrap();
+        circuit.measure(QubitId(1), ClbitId(0)).unwrap();
+        circuit
+            .while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), &attempt, 5)
+            .unwrap();
+        circuit.dag().verify_integrity().unwrap();
+
+        let (_, block) = circuit
+            .dag()
+            .topological_ops()
+            .find(|(_, inst)| inst.is_while_loop())
+            .unwrap();
+        assert_eq!(block.qubits, vec![QubitId(1)]);
+        assert_eq!(block.clbits, vec![ClbitId(0)]);
+        assert_eq!(block.blocks()[0].len(), 2);
+        assert!(circuit.inverse().is_err());
+
+        // A loop must be allowed to run at least once.
+        assert!(matches!(
+            circuit.while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), &attempt, 0),
+            Err(crate::IrError::InvalidDag(_))
+        ));
+    }
+
+    #[test]
+    fn test_multi_controlled_gates() {
+        let mut circuit = Circuit::with_size("mc", 5, 0);
+        let c = [QubitId(0), QubitId(1), QubitId(2)];
+        circuit.mcx(&c, QubitId(4)).unwrap();
+        circuit.mcz(&c[..2], QubitId(3)).unwrap();
+        circuit.mcp(PI / 4.0, &c, QubitId(4)).unwrap();
+        circuit.mcx(&c[..1], QubitId(3)).unwrap();
+
+        let names: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.name().to_string())
+            .collect();
+        assert_eq!(names.len(), 4);
+        assert!(names.contains(&"mcx".to_string()));
+        assert!(names.contains(&"mcz".to_string()));
+        assert!(names.contains(&"mcp".to_string()));
+        assert!(names.contains(&"cx".to_string()));
+
+        // Controls must not overlap with the target.
+        assert!(circuit.mcx(&c, QubitId(2)).is_err());
+    }
+
+    #[test]
+    fn test_compose_remaps_wires() {
+        let mut fragment = Circuit::with_size("fragment", 2, 1);
+        fragment.cx(QubitId(0), QubitId(1)).unwrap();
+        fragment.measure(QubitId(1), ClbitId(0)).unwrap();
+        fragment
+            .x(QubitId(0))
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap();
+
+        let mut circuit = Circuit::with_size("main", 3, 2);
+        circuit
+            .compose(&fragment, &[QubitId(2), QubitId(0)], &[ClbitId(1)])
+            .unwrap();
+
+        let ops: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+        assert_eq!(ops.len(), 3);
+        assert_eq!(ops[0].qubits, vec![QubitId(2), QubitId(0)]);
+        assert_eq!(ops[1].qubits, vec![QubitId(0)]);
+        assert_eq!(ops[1].clbits, vec![ClbitId(1)]);
+        assert_eq!(ops[2].qubits, vec![QubitId(2)]);
+        assert_eq!(ops[2].condition_clbits(), &[ClbitId(1)]);
+        circuit.dag().verify_integrity().unwrap();
+    }
+
+    #[test]
+    fn test_compose_if_else_block() {
+        let mut then_body = Circuit::with_size("then", 2, 1);
+        then_body.x(QubitId(1)).unwrap();
+        let mut fragment = Circuit::with_size("fragment", 2, 1);
+        fragment.measure(QubitId(0), ClbitId(0)).unwrap();
+        fragment
+            .if_then(ClassicalCondition::on_clbit(ClbitId(0), 1), &then_body)
+            .unwrap();
+
+        let mut circuit = Circuit::with_size("main", 4, 2);
+        circuit
+            .compose(&fragment, &[QubitId(2), QubitId(3)], &[ClbitId(1)])
+            .unwrap();
+
+        let (_, block) = circuit.dag().topological_ops().last().unwrap();
+        assert_eq!(block.qubits, vec![QubitId(3)]);
+        assert_eq!(block.condition_clbits(), &[ClbitId(1)]);
+        assert_eq!(block.blocks()[0][0].qubits, vec![QubitId(3)]);
+    }
+
+    #[test]
+    fn test_append_preserves_parameters() {
+        let mut layer = Circuit::with_size("layer", 2, 0);
+        layer
+            .ry(ParameterExpression::symbol("theta"), QubitId(0))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap();
+        layer.dag_mut().set_global_phase(PI / 4.0);
+
+        let mut ansatz = Circuit::with_size("ansatz", 3, 0);
+        ansatz
+            .rz(ParameterExpression::symbol("phi"), QubitId(2))
+            .unwrap();
+        ansatz.append(&layer).unwrap().append(&layer).unwrap();
+
+        assert_eq!(ansatz.dag().num_ops(), 5);
+        let symbols: Vec<_> = ansatz
+            .dag()
+            .topological_ops()
+            .filter_map(|(_, inst)| inst.as_gate())
+            .flat_map(|gate| match &gate.kind {
+                crate::gate::GateKind::Standard(g) => g
+                    .parameters()
+                    .into_iter()
+                    .flat_map(ParameterExpression::symbols)
+                    .collect(),
+                crate::gate::GateKind::Custom(_) | crate::gate::GateKind::Modified(_) => {
+                    vec![]
+                }
+            })
+            .collect();
+        assert_eq!(symbols.iter().filter(|s| *s == "theta").count(), 2);
+        assert_eq!(symbols.iter().filter(|s| *s == "phi").count(), 1);
+        assert!((ansatz.dag().global_phase() - PI / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mut circuit = Circuit::with_size("prep", 2, 1);
+        circuit
+            .h(QubitId(0))
+            .unwrap()
+            .t(QubitId(0))
+            .unwrap()
+            .rx(PI / 3.0, QubitId(1))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap();
+        circuit.dag_mut().set_global_phase(PI / 8.0);
+
+        let inverse = circuit.inverse().unwrap();
+        assert_eq!(inverse.name(), "prep_dg");
+        assert_eq!(inverse.num_qubits(), 2);
+        assert_eq!(inverse.num_clbits(), 1);
+        assert!((inverse.dag().global_phase() + PI / 8.0).abs() < 1e-12);
+
+        let gates: Vec<_> = inverse
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.as_gate().unwrap().kind.clone())
+            .collect();
+        assert_eq!(gates[0], GateKind::Standard(StandardGate::CX));
+        assert!(gates.contains(&GateKind::Standard(StandardGate::Tdg)));
+        let rx = gates.iter().find_map(|g| match g {
+            GateKind::Standard(StandardGate::Rx(theta)) => theta.as_f64(),
+            _ => None,
+        });
+        assert!((rx.unwrap() + PI / 3.0).abs() < 1e-12);
+        // On qubit 0 the order is reversed: Tdg before H.
+        let position = |gate: StandardGate| {
+            gates
+                .iter()
+                .position(|g| *g == GateKind::Standard(gate.clone()))
+                .unwrap()
+        };
+        assert!(position(StandardGate::Tdg) < position(StandardGate::H));
+
+        // Inverting twice restores the original gates.
+        let twice = inverse.inverse().unwrap();
+        assert_eq!(twice.dag().num_ops(), 4);
+        assert!((twice.dag().global_phase() - PI / 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_slice() {
+        let mut circuit = Circuit::with_size("slices", 3, 3);
+        circuit
+            .h(QubitId(0))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .cx(QubitId(1), QubitId(2))
+            .unwrap()
+            .measure_all()
+            .unwrap();
+        circuit.dag_mut().set_global_phase(PI / 4.0);
+        assert_eq!(circuit.depth(), 4);
+
+        let middle = circuit.slice(1..3).unwrap();
+        assert_eq!(middle.num_qubits(), 3);
+        assert_eq!(middle.num_clbits(), 3);
+        assert_eq!(middle.depth(), 2);
+        assert!(
+            middle
+                .dag()
+                .topological_ops()
+                .all(|(_, inst)| inst.name() == "cx")
+        );
+        assert!(middle.dag().global_phase().abs() < 1e-12);
+
+        let mut rebuilt = circuit.slice(..1).unwrap();
+        assert!((rebuilt.dag().global_phase() - PI / 4.0).abs() < 1e-12);
+        rebuilt.append(&middle).unwrap();
+        rebuilt.append(&circuit.slice(3..).unwrap()).unwrap();
+        assert_eq!(rebuilt.depth(), circuit.depth());
+        assert_eq!(rebuilt.dag().num_ops(), circuit.dag().num_ops());
+
+        assert_eq!(circuit.slice(10..).unwrap().dag().num_ops(), 0);
+        assert_eq!(circuit.slice(..).unwrap().dag().num_ops(), 4);
+    }
+
+    #[test]
+    fn test_inverse_symbolic_and_if_else() {
+        let mut then_body = Circuit::with_size("then", 2, 1);
+        then_body.s(QubitId(1)).unwrap().h(QubitId(1)).unwrap();
+
+        let mut circuit = Circuit::with_size("branch", 2, 1);
+        circuit
+            .ry(ParameterExpression::symbol("theta"), QubitId(0))
+            .unwrap()
+            .if_then(ClassicalCondition::on_clbit(ClbitId(0), 1), &then_body)
+            .unwrap();
+
+        let inverse = circuit.inverse().unwrap();
+        let ops: Vec<_> = inverse
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+        let block = ops.iter().find(|inst| inst.is_if_else()).unwrap();
+        let body = block.blocks()[0];
+        assert_eq!(body[0].name(), "h");
+        assert_eq!(body[1].name(), "sdg");
+        let ry = ops.iter().find_map(Instruction::as_gate).unwrap();
+        assert!(matches!(&ry.kind, GateKind::Standard(g) if g.is_parameterized()));
+    }
+
+    #[test]
+    fn test_inverse_rejects_non_unitary() {
+        let bell = Circuit::bell().unwrap();
+        assert!(matches!(
+            bell.inverse(),
+            Err(crate::IrError::NonInvertible(name)) if name == "measure"
+        ));
+
+        let mut circuit = Circuit::with_size("iswap", 2, 0);
+        circuit.iswap(QubitId(0), QubitId(1)).unwrap();
+        assert!(matches!(
+            circuit.inverse(),
+            Err(crate::IrError::NonInvertible(_))
+        ));
+    }
+
+    #[test]
+    fn test_compose_rejects_invalid_maps() {
+        let fragment = Circuit::with_size("fragment", 2, 1);
+        let mut circuit = Circuit::with_size("main", 3, 1);
+
+        assert!(matches!(
+            circuit.compose(&fragment, &[QubitId(0)], &[ClbitId(0)]),
+            Err(crate::IrError::RegisterSizeMismatch { wire: "qubit", .. })
+        ));
+        assert!(matches!(
+            circuit.compose(&fragment, &[QubitId(0), QubitId(1)], &[]),
+            Err(crate::IrError::RegisterSizeMismatch { wire: "clbit", .. })
+        ));
+        assert!(matches!(
+            circuit.compose(&fragment, &[QubitId(0), QubitId(7)], &[ClbitId(0)]),
+            Err(crate::IrError::QubitNotFound { .. })
+        ));
+        assert!(matches!(
+            circuit.compose(&fragment, &[QubitId(1), QubitId(1)], &[ClbitId(0)]),
+            Err(crate::IrError::DuplicateQubit { .. })
+        ));
+
+        // The host circuit is smaller than the fragment.
+        let mut small = Circuit::with_size("small", 1, 1);
+        assert!(matches!(
+            small.append(&fragment),
+            Err(crate::IrError::RegisterSizeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bind_parameters() {
+        let theta = crate::ParameterVector::new("theta", 2);
+        let mut then_body = Circuit::with_size("then", 2, 1);
+        then_body
+            .rz(ParameterExpression::symbol("phi"), QubitId(1))
+            .unwrap();
+
+        let mut circuit = Circuit::with_size("ansatz", 2, 1);
+        circuit
+            .ry(theta.get(0).unwrap(), QubitId(0))
+            .unwrap()
+            .rx(
+                theta.get(1).unwrap() * ParameterExpression::constant(2.0),
+                QubitId(1),
+            )
+            .unwrap()
+            .if_then(ClassicalCondition::on_clbit(ClbitId(0), 1), &then_body)
+            .unwrap();
+        assert_eq!(circuit.parameters(), vec!["phi", "theta_0", "theta_1"]);
+
+        let mut values = theta.bindings(&[0.5, 0.75]).unwrap();
+        assert!(matches!(
+            circuit.bind_parameters(&values),
+            Err(IrError::UnboundParameters(names)) if names == ["phi"]
+        ));
+
+        values.insert("phi".into(), PI);
+        let bound = circuit.bind_parameters(&values).unwrap();
+        assert!(bound.parameters().is_empty());
+        // The original circuit is unchanged.
+        assert_eq!(circuit.parameters().len(), 3);
+
+        let mut angles = Vec::new();
+        for (_, inst) in bound.dag().topological_ops() {
+            inst.walk(&mut |inst| {
+                if let Some(gate) = inst.as_gate() {
+                    angles.extend(gate.parameters().into_iter().cloned());
+                }
+            });
+        }
+        assert_eq!(angles.len(), 3);
+        assert!(angles.contains(&ParameterExpression::constant(0.5)));
+        assert!(angles.contains(&ParameterExpression::constant(1.5)));
+        assert!(angles.contains(&ParameterExpression::constant(PI)));
+    }
+
+    #[test]
+    fn test_assign_in_index_order() {
+        let theta = crate::ParameterVector::new("theta", 12);
+        let mut circuit = Circuit::with_size("layers", 1, 0);
+        for param in theta.iter() {
+            circuit.rz(param, QubitId(0)).unwrap();
+        }
+        let names = circuit.parameters();
+        assert_eq!(names[2], "theta_2");
+        assert_eq!(names[10], "theta_10");
+
+        let values: Vec<f64> = (0..12).map(f64::from).collect();
+        let bound = circuit.assign(&values).unwrap();
+        let angles: Vec<f64> = bound
+            .dag()
+            .topological_ops()
+            .filter_map(|(_, inst)| inst.as_gate()?.parameters()[0].as_f64())
+            .collect();
+        assert_eq!(angles, values);
+
+        assert!(matches!(
+            circuit.assign(&[1.0]),
+            Err(IrError::ParameterCountMismatch {
+                expected: 12,
+                got: 1
+            })
+        ));
+    }
 }
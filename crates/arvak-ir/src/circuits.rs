@@ -0,0 +1,600 @@
+//! Library of standard circuit constructions.
+//!
+//! Each function builds a unitary circuit without measurements, checked
+//! against its defining matrix or truth table in this module's tests.
+//! Integers held in a register are little-endian like basis-state indices
+//! (see [`Circuit::to_matrix`]): qubit `i` of the register is bit `i`.
+//!
+//! ```rust
+//! use arvak_ir::circuits;
+//!
+//! // |a⟩|b⟩ → |a⟩|a + b mod 8⟩ on two 3-qubit registers.
+//! let adder = circuits::draper_adder(3).unwrap();
+//! assert_eq!(adder.num_qubits(), 6);
+//! ```
+
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+
+use crate::circuit::Circuit;
+use crate::error::{IrError, IrResult};
+use crate::gate::StandardGate;
+use crate::parameter::ParameterExpression;
+use crate::qubit::QubitId;
+
+/// Rotation angles below this are dropped.
+const TOLERANCE: f64 = 1e-12;
+
+/// GHZ state `(|0…0⟩ + |1…1⟩)/√2` on `n` qubits in logarithmic depth.
+///
+/// Every entangled qubit fans out to a fresh one per layer, so the depth is
+/// `1 + ⌈log₂ n⌉` rather than the `n` of the CX chain in [`Circuit::ghz`].
+pub fn ghz(n: u32) -> IrResult<Circuit> {
+    let mut circuit = Circuit::with_size("ghz", n, 0);
+    if n == 0 {
+        return Ok(circuit);
+    }
+
+    circuit.h(QubitId(0))?;
+    let mut entangled = 1;
+    while entangled < n {
+        let fresh = entangled.min(n - entangled);
+        for i in 0..fresh {
+            circuit.cx(QubitId(i), QubitId(entangled + i))?;
+        }
+        entangled += fresh;
+    }
+    Ok(circuit)
+}
+
+/// Quantum Fourier transform on `n` qubits.
+///
+/// Maps `|x⟩` to `Σ_y e^{2πi·xy/2ⁿ} |y⟩ / √2ⁿ`: the unitary is the discrete
+/// Fourier matrix. Without `swaps` the final bit reversal is left out, so
+/// bit `i` of `y` is on qubit `n - 1 - i`; this saves `⌊n/2⌋` SWAPs when the
+/// consumer can relabel qubits, as [`draper_adder`] does.
+///
+/// [`Circuit::qft`] is the textbook circuit with qubit 0 as the most
+/// significant bit, which is the Fourier matrix only in that reading.
+pub fn qft(n: u32, swaps: bool) -> IrResult<Circuit> {
+    let mut circuit = Circuit::with_size("qft", n, 0);
+    for j in (0..n).rev() {
+        circuit.h(QubitId(j))?;
+        for k in (0..j).rev() {
+            circuit.cp(
+                PI / f64::from(1u32 << (j - k).min(31)),
+                QubitId(k),
+                QubitId(j),
+            )?;
+        }
+    }
+    if swaps {
+        for i in 0..n / 2 {
+            circuit.swap(QubitId(i), QubitId(n - 1 - i))?;
+        }
+    }
+    Ok(circuit)
+}
+
+/// Inverse quantum Fourier transform; the adjoint of [`qft`] with the same
+/// `swaps` setting.
+pub fn inverse_qft(n: u32, swaps: bool) -> IrResult<Circuit> {
+    let mut circuit = Circuit::with_size("iqft", n, 0);
+    circuit.append(&qft(n, swaps)?.inverse()?)?;
+    Ok(circuit)
+}
+
+/// Multi-controlled X as a ladder of Toffolis over clean ancillas.
+///
+/// Qubits `0..k` are the controls, qubit `k` the target and the `k - 2`
+/// qubits after it ancillas, which must start in `|0⟩` and are returned to
+/// it. The ladder uses `2k - 3` Toffolis for `k ≥ 2` controls; one control
+/// is a CX and none an X.
+pub fn mcx_ladder(num_controls: u32) -> IrResult<Circuit> {
+    let k = num_controls;
+    let ancillas = k.saturating_sub(2);
+    let mut circuit = Circuit::with_size("mcx_ladder", k + 1 + ancillas, 0);
+    let control = QubitId;
+    let target = QubitId(k);
+    let ancilla = |i: u32| QubitId(k + 1 + i);
+
+    match k {
+        0 => {
+            circuit.x(target)?;
+        }
+        1 => {
+            circuit.cx(control(0), target)?;
+        }
+        _ => {
+            // Ancilla i holds the AND of controls 0..=i+1.
+            let mut compute = Vec::new();
+            let mut and = (control(0), control(1));
+            for i in 0..ancillas {
+                compute.push((and.0, and.1, ancilla(i)));
+                and = (control(i + 2), ancilla(i));
+            }
+
+            for &(a, b, t) in &compute {
+                circuit.ccx(a, b, t)?;
+            }
+            circuit.ccx(and.0, and.1, target)?;
+            for &(a, b, t) in compute.iter().rev() {
+                circuit.ccx(a, b, t)?;
+            }
+        }
+    }
+    Ok(circuit)
+}
+
+/// Cuccaro ripple-carry adder:
+/// `|c⟩|a⟩|b⟩|z⟩ → |c⟩|a⟩|a + b + c mod 2ⁿ⟩|z ⊕ carry⟩`.
+///
+/// Qubit 0 is the carry-in `c`, qubits `1..=n` hold `a`, qubits
+/// `n+1..=2n` hold `b` and qubit `2n+1` receives the carry-out. Uses `2n`
+/// Toffolis and no other ancillas (Cuccaro et al., quant-ph/0410184).
+pub fn cuccaro_adder(n: u32) -> IrResult<Circuit> {
+    if n == 0 {
+        return Err(IrError::InvalidArgument(
+            "an adder needs at least one bit".into(),
+        ));
+    }
+    let mut circuit = Circuit::with_size("cuccaro_adder", 2 * n + 2, 0);
+    let carry_in = QubitId(0);
+    let a = |i: u32| QubitId(1 + i);
+    let b = |i: u32| QubitId(1 + n + i);
+    let carry_out = QubitId(2 * n + 1);
+
+    // MAJ leaves the carry into the next bit on `a`; UMA undoes it and
+    // writes the sum bit to `b`.
+    let maj = |circuit: &mut Circuit, c: QubitId, b: QubitId, a: QubitId| -> IrResult<()> {
+        circuit.cx(a, b)?.cx(a, c)?.ccx(c, b, a)?;
+        Ok(())
+    };
+    let uma = |circuit: &mut Circuit, c: QubitId, b: QubitId, a: QubitId| -> IrResult<()> {
+        circuit.ccx(c, b, a)?.cx(a, c)?.cx(c, b)?;
+        Ok(())
+    };
+
+    maj(&mut circuit, carry_in, b(0), a(0))?;
+    for i in 1..n {
+        maj(&mut circuit, a(i - 1), b(i), a(i))?;
+    }
+    circuit.cx(a(n - 1), carry_out)?;
+    for i in (1..n).rev() {
+        uma(&mut circuit, a(i - 1), b(i), a(i))?;
+    }
+    uma(&mut circuit, carry_in, b(0), a(0))?;
+    Ok(circuit)
+}
+
+/// Draper adder: `|a⟩|b⟩ → |a⟩|a + b mod 2ⁿ⟩` in Fourier space.
+///
+/// Qubits `0..n` hold `a` and `n..2n` hold `b`. `b` is Fourier transformed,
+/// `a` is added with `n(n+1)/2` controlled phases and the transform is
+/// undone; no ancillas (Draper, quant-ph/0008033).
+pub fn draper_adder(n: u32) -> IrResult<Circuit> {
+    if n == 0 {
+        return Err(IrError::InvalidArgument(
+            "an adder needs at least one bit".into(),
+        ));
+    }
+    let mut circuit = Circuit::with_size("draper_adder", 2 * n, 0);
+    let b: Vec<QubitId> = (n..2 * n).map(QubitId).collect();
+
+    // Without the swaps, bit j of the transformed `b` is on b[n-1-j].
+    circuit.compose(&qft(n, false)?, &b, &[])?;
+    for i in 0..n {
+        for j in 0..n - i {
+            let angle = 2.0 * PI / f64::from(1u32 << (n - i - j).min(31));
+            circuit.cp(angle, QubitId(i), b[(n - 1 - j) as usize])?;
+        }
+    }
+    circuit.compose(&inverse_qft(n, false)?, &b, &[])?;
+    Ok(circuit)
+}
+
+/// Prepare `Σ_x amplitudes[x] |x⟩` from `|0…0⟩`; see
+/// [`Circuit::prepare_state`].
+pub fn state_preparation(amplitudes: &[Complex64]) -> IrResult<Circuit> {
+    Circuit::prepare_state(amplitudes)
+}
+
+impl Circuit {
+    /// Prepare `Σ_x amplitudes[x] |x⟩` from `|0…0⟩`, global phase included.
+    ///
+    /// There must be `2ⁿ` amplitudes of unit norm. Magnitudes are set one
+    /// qubit at a time, most significant first, by rotations uniformly
+    /// controlled on the qubits already set; a second pass of uniformly
+    /// controlled Rz rotations applies the phases (Möttönen et al.,
+    /// quant-ph/0407010). A uniformly controlled rotation on `k` controls
+    /// costs at most `2ᵏ` rotations and `2ᵏ` CXs; controls the angles do
+    /// not depend on are dropped, and vanishing rotations are left out
+    /// together with the CXs that then cancel.
+    ///
+    /// ```rust
+    /// use arvak_ir::Circuit;
+    /// use num_complex::Complex64;
+    ///
+    /// let h = std::f64::consts::FRAC_1_SQRT_2;
+    /// let bell = [h, 0.0, 0.0, h].map(Complex64::from);
+    /// let circuit = Circuit::prepare_state(&bell).unwrap();
+    /// assert_eq!(circuit.num_qubits(), 2);
+    /// ```
+    pub fn prepare_state(amplitudes: &[Complex64]) -> IrResult<Self> {
+        Self::prepare_state_approx(amplitudes, 0.0)
+    }
+
+    /// Prepare a state like [`Circuit::prepare_state`], approximating
+    /// rotation angles to `threshold` radians.
+    ///
+    /// Uniformly controlled rotations ignore controls that change their
+    /// angles by less than `threshold`, and rotations smaller than
+    /// `threshold` are left out. Changing a rotation angle by `δ` moves the
+    /// prepared state by at most `δ/2`, so small thresholds stay close to
+    /// the target while nearly separable states, such as smooth amplitude
+    /// encodings, lose most of their CXs.
+    pub fn prepare_state_approx(amplitudes: &[Complex64], threshold: f64) -> IrResult<Self> {
+        if !amplitudes.len().is_power_of_two() {
+            return Err(IrError::InvalidArgument(format!(
+                "state preparation needs a power-of-two number of amplitudes, got {}",
+                amplitudes.len()
+            )));
+        }
+        let norm: f64 = amplitudes.iter().map(Complex64::norm_sqr).sum();
+        if (norm - 1.0).abs() > 1e-8 {
+            return Err(IrError::InvalidArgument(format!(
+                "amplitudes are not normalized (norm² = {norm})"
+            )));
+        }
+
+        let n = amplitudes.len().trailing_zeros();
+        let threshold = threshold.max(TOLERANCE);
+        let mut circuit = Self::with_size("state_prep", n, 0);
+        let controls = |t: u32| -> Vec<QubitId> { (t + 1..n).map(QubitId).collect() };
+
+        // weights[t][p]: squared norm of the amplitudes whose bits t.. are p.
+        let mut weights = vec![
+            amplitudes
+                .iter()
+                .map(Complex64::norm_sqr)
+                .collect::<Vec<_>>(),
+        ];
+        for t in 0..n as usize {
+            let next = weights[t].chunks(2).map(|w| w[0] + w[1]).collect();
+            weights.push(next);
+        }
+        for t in (0..n).rev() {
+            let angles: Vec<f64> = weights[t as usize]
+                .chunks(2)
+                .map(|w| 2.0 * w[1].sqrt().atan2(w[0].sqrt()))
+                .collect();
+            uniformly_controlled(
+                &mut circuit,
+                StandardGate::Ry,
+                &angles,
+                &controls(t),
+                QubitId(t),
+                threshold,
+            )?;
+        }
+
+        let mut phases: Vec<f64> = amplitudes.iter().map(|a| a.arg()).collect();
+        for t in 0..n {
+            let angles: Vec<f64> = phases.chunks(2).map(|p| p[1] - p[0]).collect();
+            uniformly_controlled(
+                &mut circuit,
+                StandardGate::Rz,
+                &angles,
+                &controls(t),
+                QubitId(t),
+                threshold,
+            )?;
+            phases = phases
+                .chunks(2)
+                .map(|p| f64::midpoint(p[0], p[1]))
+                .collect();
+        }
+        circuit.dag_mut().set_global_phase(phases[0]);
+        Ok(circuit)
+    }
+}
+
+/// Rotate `target` by `angles[p]` when `controls` hold `p` (bit `j` of `p`
+/// is `controls[j]`), as `2ᵏ` rotations interleaved with CXs whose controls
+/// follow a Gray code.
+///
+/// Controls whose value changes no angle by `threshold` or more are dropped
+/// first, halving the cost each, and rotations below `threshold` are left
+/// out. The CXs all share the target and commute, so those between two
+/// emitted rotations collapse to the controls flipped an odd number of times.
+fn uniformly_controlled(
+    circuit: &mut Circuit,
+    rotation: fn(ParameterExpression) -> StandardGate,
+    angles: &[f64],
+    controls: &[QubitId],
+    target: QubitId,
+    threshold: f64,
+) -> IrResult<()> {
+    let mut angles = angles.to_vec();
+    let mut controls = controls.to_vec();
+    for j in (0..controls.len()).rev() {
+        let bit = 1 << j;
+        // Angle pairs that differ only in control j, as (p, p | bit).
+        let pairs = (0..angles.len() / 2).map(|q| {
+            let p = (q & (bit - 1)) | ((q >> j) << (j + 1));
+            (p, p | bit)
+        });
+        if pairs
+            .clone()
+            .all(|(p0, p1)| (angles[p0] - angles[p1]).abs() < threshold)
+        {
+            angles = pairs
+                .map(|(p0, p1)| f64::midpoint(angles[p0], angles[p1]))
+                .collect();
+            controls.remove(j);
+        }
+    }
+
+    // Before step i the CXs have flipped the target's frame by the parity
+    // of the controls in gray(i), which negates the rotation angle.
+    let size = angles.len();
+    let mut pending = vec![false; controls.len()];
+    for i in 0..size {
+        let gray = i ^ (i >> 1);
+        let angle = angles
+            .iter()
+            .enumerate()
+            .map(|(p, a)| {
+                if (p & gray).count_ones() % 2 == 0 {
+                    *a
+                } else {
+                    -a
+                }
+            })
+            .sum::<f64>()
+            / size as f64;
+        if angle.abs() >= threshold {
+            flush_flips(circuit, &mut pending, &controls, target)?;
+            circuit.gate(rotation(angle.into()), [target])?;
+        }
+        if !controls.is_empty() {
+            let flip = if i + 1 == size {
+                controls.len() - 1
+            } else {
+                (i + 1).trailing_zeros() as usize
+            };
+            pending[flip] = !pending[flip];
+        }
+    }
+    // The Gray code cycles back to zero, so the flips still pending are
+    // exactly those that restore the target's frame.
+    flush_flips(circuit, &mut pending, &controls, target)
+}
+
+/// Emit a CX onto `target` from every control flipped an odd number of times.
+fn flush_flips(
+    circuit: &mut Circuit,
+    pending: &mut [bool],
+    controls: &[QubitId],
+    target: QubitId,
+) -> IrResult<()> {
+    for (flip, &control) in pending.iter_mut().zip(controls) {
+        if *flip {
+            circuit.cx(control, target)?;
+            *flip = false;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The state `circuit` maps basis state `input` to.
+    fn column(circuit: &Circuit, input: usize) -> Vec<Complex64> {
+        let u = circuit.to_matrix().unwrap();
+        let dim = 1 << circuit.num_qubits();
+        (0..dim).map(|row| u[row * dim + input]).collect()
+    }
+
+    /// The basis state a classical circuit maps each input to.
+    fn outputs(circuit: &Circuit) -> Vec<usize> {
+        let u = circuit.to_matrix().unwrap();
+        let dim = 1 << circuit.num_qubits();
+        (0..dim)
+            .map(|input| {
+                let output = (0..dim)
+                    .find(|&row| u[row * dim + input].norm() > 0.5)
+                    .unwrap();
+                let amplitude = u[output * dim + input];
+                assert!((amplitude - 1.0).norm() < 1e-9, "{amplitude}");
+                output
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ghz() {
+        for n in 1..=7u32 {
+            let circuit = ghz(n).unwrap();
+            let state = column(&circuit, 0);
+            let h = std::f64::consts::FRAC_1_SQRT_2;
+            assert!((state[0] - h).norm() < 1e-9);
+            assert!((state[(1 << n) - 1] - h).norm() < 1e-9);
+            let log = (u32::BITS - (n - 1).leading_zeros()) as usize;
+            assert_eq!(circuit.depth(), 1 + log, "n = {n}");
+        }
+        assert_eq!(ghz(0).unwrap().num_qubits(), 0);
+    }
+
+    #[test]
+    fn test_qft_is_fourier_matrix() {
+        for n in 1..=4u32 {
+            let dim = 1usize << n;
+            let reverse = |y: usize| (0..n).fold(0, |r, i| r | ((y >> i) & 1) << (n - 1 - i));
+            let with_swaps = qft(n, true).unwrap().to_matrix().unwrap();
+            let without = qft(n, false).unwrap().to_matrix().unwrap();
+            let inverse = inverse_qft(n, true).unwrap().to_matrix().unwrap();
+            for y in 0..dim {
+                for x in 0..dim {
+                    let phase = 2.0 * PI * (x * y) as f64 / dim as f64;
+                    let expected = Complex64::from_polar(1.0 / (dim as f64).sqrt(), phase);
+                    assert!((with_swaps[y * dim + x] - expected).norm() < 1e-9);
+                    assert!((without[reverse(y) * dim + x] - expected).norm() < 1e-9);
+                    assert!((inverse[x * dim + y] - expected.conj()).norm() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mcx_ladder() {
+        for k in 0..=5u32 {
+            let circuit = mcx_ladder(k).unwrap();
+            let outputs = outputs(&circuit);
+            assert_eq!(circuit.num_qubits() as u32, k + 1 + k.saturating_sub(2));
+            if k >= 2 {
+                assert_eq!(circuit.dag().num_ops() as u32, 2 * k - 3);
+            }
+            // Every control and target value, ancillas clean.
+            let all = (1usize << k) - 1;
+            for (input, &output) in outputs.iter().enumerate().take(1 << (k + 1)) {
+                let flip = if input & all == all { 1 << k } else { 0 };
+                assert_eq!(output, input ^ flip, "k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_cuccaro_adder() {
+        for n in 1..=3u32 {
+            let circuit = cuccaro_adder(n).unwrap();
+            let outputs = outputs(&circuit);
+            let size = 1usize << n;
+            for a in 0..size {
+                for b in 0..size {
+                    for c in 0..2 {
+                        let input = c | a << 1 | b << (n + 1);
+                        let sum = a + b + c;
+                        let expected =
+                            c | a << 1 | (sum % size) << (n + 1) | (sum / size) << (2 * n + 1);
+                        assert_eq!(outputs[input], expected, "{a} + {b} + {c}");
+                    }
+                }
+            }
+        }
+        assert!(cuccaro_adder(0).is_err());
+    }
+
+    #[test]
+    fn test_draper_adder() {
+        for n in 1..=4u32 {
+            let circuit = draper_adder(n).unwrap();
+            let outputs = outputs(&circuit);
+            let size = 1usize << n;
+            for a in 0..size {
+                for b in 0..size {
+                    let expected = a | ((a + b) % size) << n;
+                    assert_eq!(outputs[a | b << n], expected, "{a} + {b}");
+                }
+            }
+        }
+        assert!(draper_adder(0).is_err());
+    }
+
+    #[test]
+    fn test_state_preparation() {
+        let c = Complex64::new;
+        let norm = |v: Vec<Complex64>| {
+            let s = v.iter().map(Complex64::norm_sqr).sum::<f64>().sqrt();
+            v.into_iter().map(|a| a / s).collect::<Vec<_>>()
+        };
+        let states = [
+            vec![c(0.0, 1.0)],
+            norm(vec![c(1.0, 0.0), c(0.0, -1.0)]),
+            norm(vec![c(0.3, 0.1), c(-0.2, 0.5), c(0.0, 0.0), c(0.7, -0.4)]),
+            norm(
+                (0..8)
+                    .map(|i| c(f64::from(i).cos(), f64::from(i * i).sin()))
+                    .collect(),
+            ),
+            (0..8)
+                .map(|i| c(if i == 5 { 1.0 } else { 0.0 }, 0.0))
+                .collect(),
+        ];
+        for amplitudes in states {
+            let circuit = state_preparation(&amplitudes).unwrap();
+            for (actual, expected) in column(&circuit, 0).iter().zip(&amplitudes) {
+                assert!((actual - expected).norm() < 1e-9, "{actual} != {expected}");
+            }
+        }
+
+        assert!(state_preparation(&[c(1.0, 0.0); 3]).is_err());
+        assert!(state_preparation(&[c(1.0, 0.0); 2]).is_err());
+    }
+
+    fn count_cx(circuit: &Circuit) -> usize {
+        circuit
+            .dag()
+            .topological_ops()
+            .filter(|(_, inst)| inst.name() == "cx")
+            .count()
+    }
+
+    #[test]
+    fn test_prepare_state_product_needs_no_cx() {
+        // (|0⟩ + i|1⟩)/√2 ⊗ (0.6|0⟩ + 0.8|1⟩) ⊗ (0.28|0⟩ - 0.96i|1⟩),
+        // qubit 0 rightmost.
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let factors = [
+            [Complex64::new(0.28, 0.0), Complex64::new(0.0, -0.96)],
+            [Complex64::new(0.6, 0.0), Complex64::new(0.8, 0.0)],
+            [Complex64::new(h, 0.0), Complex64::new(0.0, h)],
+        ];
+        let amplitudes: Vec<Complex64> = (0..8)
+            .map(|x| (0..3).map(|q| factors[q][(x >> q) & 1]).product())
+            .collect();
+
+        let circuit = Circuit::prepare_state(&amplitudes).unwrap();
+        assert_eq!(count_cx(&circuit), 0);
+        for (actual, expected) in column(&circuit, 0).iter().zip(&amplitudes) {
+            assert!((actual - expected).norm() < 1e-9, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_prepare_state_approx() {
+        // A Gaussian amplitude encoding with a linear phase ramp.
+        let mut amplitudes: Vec<Complex64> = (0..16)
+            .map(|x| {
+                let x = f64::from(x);
+                Complex64::from_polar((-(x - 7.5).powi(2) / 32.0).exp(), 0.1 * x)
+            })
+            .collect();
+        let norm = amplitudes
+            .iter()
+            .map(Complex64::norm_sqr)
+            .sum::<f64>()
+            .sqrt();
+        for a in &mut amplitudes {
+            *a /= norm;
+        }
+
+        let exact = Circuit::prepare_state(&amplitudes).unwrap();
+        for (actual, expected) in column(&exact, 0).iter().zip(&amplitudes) {
+            assert!((actual - expected).norm() < 1e-9, "{actual} != {expected}");
+        }
+        let approx = Circuit::prepare_state_approx(&amplitudes, 0.2).unwrap();
+        assert!(count_cx(&approx) < count_cx(&exact));
+
+        let overlap: Complex64 = column(&approx, 0)
+            .iter()
+            .zip(&amplitudes)
+            .map(|(actual, expected)| expected.conj() * actual)
+            .sum();
+        assert!(overlap.norm_sqr() > 0.99, "fidelity {}", overlap.norm_sqr());
+        assert!(overlap.norm_sqr() < 1.0 - 1e-9);
+    }
+}
@@ -0,0 +1,504 @@
+//! Clifford tableaux.
+//!
+//! A [`CliffordTableau`] stores a Clifford operation by its action on the
+//! Pauli group, in the form of Aaronson and Gottesman: for every qubit `i`
+//! the images of `X_i` (destabilizer `i`) and `Z_i` (stabilizer `i`), each
+//! as X and Z bits per qubit plus a sign. Applying a gate updates the
+//! tableau in `O(n)` time, so Clifford circuits of any width can be
+//! simulated, compared and resynthesized without dense matrices.
+//!
+//! Tableaux ignore global phase. Pauli labels list qubit 0 rightmost, like
+//! HAL bitstrings.
+//!
+//! ```rust
+//! use arvak_ir::{Circuit, CliffordTableau};
+//!
+//! let bell = Circuit::bell().unwrap();
+//! // Measurements are not Clifford gates; build the unitary part.
+//! let mut circuit = Circuit::with_size("bell", 2, 0);
+//! circuit.h(arvak_ir::QubitId(0)).unwrap();
+//! circuit.cx(arvak_ir::QubitId(0), arvak_ir::QubitId(1)).unwrap();
+//!
+//! let tableau = CliffordTableau::from_circuit(&circuit).unwrap();
+//! assert_eq!(tableau.stabilizer_label(0), "+XX");
+//! assert_eq!(tableau.stabilizer_label(1), "+ZZ");
+//! assert!(CliffordTableau::from_circuit(&bell).is_err());
+//!
+//! let resynthesized = tableau.to_circuit().unwrap();
+//! assert_eq!(CliffordTableau::from_circuit(&resynthesized).unwrap(), tableau);
+//! ```
+
+use std::f64::consts::FRAC_PI_2;
+
+use rustc_hash::FxHashMap;
+
+use crate::circuit::Circuit;
+use crate::error::{IrError, IrResult};
+use crate::gate::{GateKind, StandardGate};
+use crate::instruction::{Instruction, InstructionKind};
+use crate::parameter::ParameterExpression;
+use crate::qubit::QubitId;
+
+/// Tolerance for recognizing rotation angles as multiples of π/2.
+const ANGLE_TOLERANCE: f64 = 1e-9;
+
+/// Stabilizer tableau of an `n`-qubit Clifford operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliffordTableau {
+    num_qubits: usize,
+    /// X bits, row-major: rows `0..n` are destabilizers, `n..2n` stabilizers.
+    x: Vec<bool>,
+    /// Z bits, same layout as `x`.
+    z: Vec<bool>,
+    /// Sign of each row (`true` for −1).
+    phase: Vec<bool>,
+}
+
+impl CliffordTableau {
+    /// The identity on `num_qubits` qubits.
+    pub fn identity(num_qubits: usize) -> Self {
+        let rows = 2 * num_qubits;
+        let mut tableau = Self {
+            num_qubits,
+            x: vec![false; rows * num_qubits],
+            z: vec![false; rows * num_qubits],
+            phase: vec![false; rows],
+        };
+        for i in 0..num_qubits {
+            tableau.x[i * num_qubits + i] = true;
+            tableau.z[(num_qubits + i) * num_qubits + i] = true;
+        }
+        tableau
+    }
+
+    /// The tableau of a Clifford circuit.
+    ///
+    /// Barriers, delays, shuttles and noise annotations are ignored. Fails
+    /// with [`IrError::NotClifford`] on measurements, resets, control flow,
+    /// conditioned or custom gates, and non-Clifford gates. Rotations are
+    /// accepted when their angle is a multiple of π/2.
+    pub fn from_circuit(circuit: &Circuit) -> IrResult<Self> {
+        let index: FxHashMap<QubitId, usize> = circuit
+            .qubits()
+            .iter()
+            .enumerate()
+            .map(|(i, q)| (q.id, i))
+            .collect();
+        let mut tableau = Self::identity(circuit.num_qubits());
+        for (_, inst) in circuit.dag().topological_ops() {
+            let qubits = inst
+                .qubits
+                .iter()
+                .map(|q| {
+                    index.get(q).copied().ok_or(IrError::QubitNotFound {
+                        qubit: *q,
+                        gate_name: Some(inst.name().to_string()),
+                    })
+                })
+                .collect::<IrResult<Vec<usize>>>()?;
+            tableau.apply_instruction(inst, &qubits)?;
+        }
+        Ok(tableau)
+    }
+
+    /// Number of qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Label of stabilizer `i` (the image of `Z_i`), e.g. `"-XZ"`.
+    pub fn stabilizer_label(&self, i: usize) -> String {
+        self.row_label(self.num_qubits + i)
+    }
+
+    /// Label of destabilizer `i` (the image of `X_i`).
+    pub fn destabilizer_label(&self, i: usize) -> String {
+        self.row_label(i)
+    }
+
+    /// Apply a standard Clifford gate on the given qubit indices.
+    pub fn apply_gate(&mut self, gate: &StandardGate, qubits: &[usize]) -> IrResult<()> {
+        let not_clifford = || IrError::NotClifford(gate.name().to_string());
+        let expected = gate.num_qubits() as usize;
+        if qubits.len() != expected {
+            return Err(IrError::QubitCountMismatch {
+                gate_name: gate.name().to_string(),
+                expected: gate.num_qubits(),
+                got: u32::try_from(qubits.len()).unwrap_or(u32::MAX),
+            });
+        }
+        if let Some(&q) = qubits.iter().find(|&&q| q >= self.num_qubits) {
+            return Err(IrError::QubitNotFound {
+                qubit: QubitId(u32::try_from(q).unwrap_or(u32::MAX)),
+                gate_name: Some(gate.name().to_string()),
+            });
+        }
+
+        match gate {
+            StandardGate::I => {}
+            StandardGate::X => self.x_gate(qubits[0]),
+            StandardGate::Y => {
+                self.z_gate(qubits[0]);
+                self.x_gate(qubits[0]);
+            }
+            StandardGate::Z => self.z_gate(qubits[0]),
+            StandardGate::H => self.h(qubits[0]),
+            StandardGate::S => self.s(qubits[0]),
+            StandardGate::Sdg => self.sdg(qubits[0]),
+            StandardGate::SX => {
+                self.h(qubits[0]);
+                self.s(qubits[0]);
+                self.h(qubits[0]);
+            }
+            StandardGate::SXdg => {
+                self.h(qubits[0]);
+                self.sdg(qubits[0]);
+                self.h(qubits[0]);
+            }
+            StandardGate::Rz(theta) | StandardGate::P(theta) => {
+                let turns = quarter_turns(theta).ok_or_else(not_clifford)?;
+                self.rz_quarter_turns(qubits[0], turns);
+            }
+            StandardGate::Rx(theta) => {
+                let turns = quarter_turns(theta).ok_or_else(not_clifford)?;
+                self.h(qubits[0]);
+                self.rz_quarter_turns(qubits[0], turns);
+                self.h(qubits[0]);
+            }
+            StandardGate::Ry(theta) => {
+                // Ry = S · Rx · S†
+                let turns = quarter_turns(theta).ok_or_else(not_clifford)?;
+                self.sdg(qubits[0]);
+                self.h(qubits[0]);
+                self.rz_quarter_turns(qubits[0], turns);
+                self.h(qubits[0]);
+                self.s(qubits[0]);
+            }
+            StandardGate::CX => self.cx(qubits[0], qubits[1]),
+            StandardGate::CY => {
+                self.sdg(qubits[1]);
+                self.cx(qubits[0], qubits[1]);
+                self.s(qubits[1]);
+            }
+            StandardGate::CZ => {
+                self.h(qubits[1]);
+                self.cx(qubits[0], qubits[1]);
+                self.h(qubits[1]);
+            }
+            StandardGate::Swap => self.swap(qubits[0], qubits[1]),
+            StandardGate::ISwap => {
+                let (a, b) = (qubits[0], qubits[1]);
+                self.s(a);
+                self.s(b);
+                self.h(a);
+                self.cx(a, b);
+                self.cx(b, a);
+                self.h(b);
+            }
+            _ => return Err(not_clifford()),
+        }
+        Ok(())
+    }
+
+    /// Synthesize a circuit of H, S, S†, X, Z, CX and SWAP gates.
+    ///
+    /// Uses the Aaronson–Gottesman reduction: the tableau is reduced to the
+    /// identity one qubit at a time and the reducing gates are inverted.
+    pub fn to_circuit(&self) -> IrResult<Circuit> {
+        let n = self.num_qubits;
+        let mut work = self.clone();
+        let mut reduction: Vec<(StandardGate, Vec<usize>)> = Vec::new();
+        let mut record = |work: &mut Self, gate: StandardGate, qubits: Vec<usize>| {
+            match (&gate, qubits.as_slice()) {
+                (StandardGate::H, &[q]) => work.h(q),
+                (StandardGate::S, &[q]) => work.s(q),
+                (StandardGate::X, &[q]) => work.x_gate(q),
+                (StandardGate::Z, &[q]) => work.z_gate(q),
+                (StandardGate::CX, &[c, t]) => work.cx(c, t),
+                (StandardGate::Swap, &[a, b]) => work.swap(a, b),
+                _ => unreachable!("reduction only uses H, S, X, Z, CX and SWAP"),
+            }
+            reduction.push((gate, qubits));
+        };
+
+        for q in 0..n {
+            // Destabilizer q gets an X on qubit q.
+            if !work.x_at(q, q) {
+                if let Some(j) = (q..n).find(|&j| work.x_at(q, j)) {
+                    record(&mut work, StandardGate::Swap, vec![j, q]);
+                } else if let Some(j) = (q..n).find(|&j| work.z_at(q, j)) {
+                    record(&mut work, StandardGate::H, vec![j]);
+                    if j != q {
+                        record(&mut work, StandardGate::Swap, vec![j, q]);
+                    }
+                }
+            }
+
+            // Destabilizer q becomes X_q.
+            for j in q + 1..n {
+                if work.x_at(q, j) {
+                    record(&mut work, StandardGate::CX, vec![q, j]);
+                }
+            }
+            if (q..n).any(|j| work.z_at(q, j)) {
+                if !work.z_at(q, q) {
+                    record(&mut work, StandardGate::S, vec![q]);
+                }
+                for j in q + 1..n {
+                    if work.z_at(q, j) {
+                        record(&mut work, StandardGate::CX, vec![j, q]);
+                    }
+                }
+                record(&mut work, StandardGate::S, vec![q]);
+            }
+
+            // Stabilizer q becomes Z_q.
+            let row = n + q;
+            for j in q + 1..n {
+                if work.z_at(row, j) {
+                    record(&mut work, StandardGate::CX, vec![j, q]);
+                }
+            }
+            if (q..n).any(|j| work.x_at(row, j)) {
+                record(&mut work, StandardGate::H, vec![q]);
+                for j in q + 1..n {
+                    if work.x_at(row, j) {
+                        record(&mut work, StandardGate::CX, vec![q, j]);
+                    }
+                }
+                if work.z_at(row, q) {
+                    record(&mut work, StandardGate::S, vec![q]);
+                }
+                record(&mut work, StandardGate::H, vec![q]);
+            }
+        }
+        for q in 0..n {
+            if work.phase[q] {
+                record(&mut work, StandardGate::Z, vec![q]);
+            }
+            if work.phase[n + q] {
+                record(&mut work, StandardGate::X, vec![q]);
+            }
+        }
+        debug_assert_eq!(work, Self::identity(n));
+
+        // The reduction maps the tableau to the identity; its inverse
+        // implements the tableau.
+        let mut circuit = Circuit::with_size("clifford", u32::try_from(n).unwrap_or(u32::MAX), 0);
+        for (gate, qubits) in reduction.into_iter().rev() {
+            let gate = match gate {
+                StandardGate::S => StandardGate::Sdg,
+                gate => gate,
+            };
+            let qubits = qubits
+                .into_iter()
+                .map(|q| QubitId(u32::try_from(q).unwrap_or(u32::MAX)));
+            circuit.dag_mut().apply(Instruction::gate(gate, qubits))?;
+        }
+        Ok(circuit)
+    }
+
+    fn apply_instruction(&mut self, inst: &Instruction, qubits: &[usize]) -> IrResult<()> {
+        match &inst.kind {
+            InstructionKind::Gate(gate) => {
+                if gate.condition.is_some() {
+                    return Err(IrError::NotClifford(format!(
+                        "conditioned '{}'",
+                        gate.name()
+                    )));
+                }
+                match &gate.kind {
+                    GateKind::Standard(std_gate) => self.apply_gate(std_gate, qubits),
+                    GateKind::Custom(custom) => Err(IrError::NotClifford(custom.name.clone())),
+                    GateKind::Modified(modified) => match modified.to_standard() {
+                        Some(std_gate) => self.apply_gate(&std_gate, qubits),
+                        None => Err(IrError::NotClifford(modified.name().to_string())),
+                    },
+                }
+            }
+            InstructionKind::Barrier
+            | InstructionKind::Delay { .. }
+            | InstructionKind::Shuttle { .. }
+            | InstructionKind::NoiseChannel { .. } => Ok(()),
+            InstructionKind::Measure
+            | InstructionKind::Reset
+            | InstructionKind::IfElse { .. }
+            | InstructionKind::WhileLoop { .. } => {
+                Err(IrError::NotClifford(inst.name().to_string()))
+            }
+        }
+    }
+
+    fn x_at(&self, row: usize, qubit: usize) -> bool {
+        self.x[row * self.num_qubits + qubit]
+    }
+
+    fn z_at(&self, row: usize, qubit: usize) -> bool {
+        self.z[row * self.num_qubits + qubit]
+    }
+
+    fn row_label(&self, row: usize) -> String {
+        let sign = if self.phase[row] { '-' } else { '+' };
+        let paulis =
+            (0..self.num_qubits)
+                .rev()
+                .map(|q| match (self.x_at(row, q), self.z_at(row, q)) {
+                    (false, false) => 'I',
+                    (true, false) => 'X',
+                    (true, true) => 'Y',
+                    (false, true) => 'Z',
+                });
+        std::iter::once(sign).chain(paulis).collect()
+    }
+
+    fn h(&mut self, q: usize) {
+        let n = self.num_qubits;
+        for row in 0..2 * n {
+            let i = row * n + q;
+            self.phase[row] ^= self.x[i] && self.z[i];
+            std::mem::swap(&mut self.x[i], &mut self.z[i]);
+        }
+    }
+
+    fn s(&mut self, q: usize) {
+        let n = self.num_qubits;
+        for row in 0..2 * n {
+            let i = row * n + q;
+            self.phase[row] ^= self.x[i] && self.z[i];
+            self.z[i] ^= self.x[i];
+        }
+    }
+
+    fn sdg(&mut self, q: usize) {
+        self.z_gate(q);
+        self.s(q);
+    }
+
+    fn z_gate(&mut self, q: usize) {
+        let n = self.num_qubits;
+        for row in 0..2 * n {
+            self.phase[row] ^= self.x[row * n + q];
+        }
+    }
+
+    fn x_gate(&mut self, q: usize) {
+        let n = self.num_qubits;
+        for row in 0..2 * n {
+            self.phase[row] ^= self.z[row * n + q];
+        }
+    }
+
+    fn rz_quarter_turns(&mut self, q: usize, turns: u8) {
+        match turns {
+            1 => self.s(q),
+            2 => self.z_gate(q),
+            3 => self.sdg(q),
+            _ => {}
+        }
+    }
+
+    fn cx(&mut self, control: usize, target: usize) {
+        let n = self.num_qubits;
+        for row in 0..2 * n {
+            let (c, t) = (row * n + control, row * n + target);
+            self.phase[row] ^= self.x[c] && self.z[t] && (self.x[t] == self.z[c]);
+            self.x[t] ^= self.x[c];
+            self.z[c] ^= self.z[t];
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        let n = self.num_qubits;
+        for row in 0..2 * n {
+            self.x.swap(row * n + a, row * n + b);
+            self.z.swap(row * n + a, row * n + b);
+        }
+    }
+}
+
+/// A numeric angle as a number of quarter turns (0-3), if it is a multiple
+/// of π/2.
+fn quarter_turns(theta: &ParameterExpression) -> Option<u8> {
+    let turns = theta.as_f64()? / FRAC_PI_2;
+    let rounded = turns.round();
+    if (turns - rounded).abs() > ANGLE_TOLERANCE {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(rounded.rem_euclid(4.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::RandomCircuitOptions;
+    use num_complex::Complex64;
+
+    /// Whether two unitaries are equal up to global phase.
+    fn equal_up_to_phase(a: &[Complex64], b: &[Complex64]) -> bool {
+        let Some((pa, pb)) = a.iter().zip(b).find(|(x, _)| x.norm() > 1e-6) else {
+            return false;
+        };
+        let phase = pb / pa;
+        a.iter().zip(b).all(|(x, y)| (x * phase - y).norm() < 1e-9)
+    }
+
+    #[test]
+    fn test_single_qubit_images() {
+        let mut circuit = Circuit::with_size("c", 1, 0);
+        circuit.h(QubitId(0)).unwrap().s(QubitId(0)).unwrap();
+        let tableau = CliffordTableau::from_circuit(&circuit).unwrap();
+        // S·H maps X → Z and Z → Y.
+        assert_eq!(tableau.destabilizer_label(0), "+Z");
+        assert_eq!(tableau.stabilizer_label(0), "+Y");
+
+        let mut circuit = Circuit::with_size("c", 1, 0);
+        circuit.rx(std::f64::consts::PI, QubitId(0)).unwrap();
+        let tableau = CliffordTableau::from_circuit(&circuit).unwrap();
+        assert_eq!(tableau.stabilizer_label(0), "-Z");
+
+        let mut circuit = Circuit::with_size("c", 1, 0);
+        circuit.t(QubitId(0)).unwrap();
+        assert!(matches!(
+            CliffordTableau::from_circuit(&circuit),
+            Err(IrError::NotClifford(_))
+        ));
+    }
+
+    #[test]
+    fn test_round_trip_matches_unitary() {
+        // Gates outside the random generator's Clifford set.
+        let (q0, q1) = (QubitId(0), QubitId(1));
+        let mut rotations = Circuit::with_size("rotations", 3, 0);
+        rotations
+            .ry(FRAC_PI_2, q0)
+            .unwrap()
+            .rx(-FRAC_PI_2, q1)
+            .unwrap()
+            .rz(3.0 * FRAC_PI_2, q0)
+            .unwrap()
+            .p(FRAC_PI_2, q1)
+            .unwrap()
+            .iswap(q0, q1)
+            .unwrap()
+            .cz(q1, QubitId(2))
+            .unwrap();
+
+        let options = RandomCircuitOptions::default().with_clifford_only(true);
+        let random =
+            (0..20).map(|seed| Circuit::random_with_options(3, 12, seed, &options).unwrap());
+        for circuit in std::iter::once(rotations).chain(random) {
+            let tableau = CliffordTableau::from_circuit(&circuit).unwrap();
+            let synthesized = tableau.to_circuit().unwrap();
+
+            assert_eq!(
+                CliffordTableau::from_circuit(&synthesized).unwrap(),
+                tableau
+            );
+            assert!(equal_up_to_phase(
+                &circuit.to_matrix().unwrap(),
+                &synthesized.to_matrix().unwrap()
+            ));
+        }
+    }
+}
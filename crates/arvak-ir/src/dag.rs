@@ -7,6 +7,7 @@ use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{IrError, IrResult};
+use crate::gate::ClassicalCondition;
 use crate::instruction::{Instruction, InstructionKind};
 use crate::qubit::{ClbitId, QubitId};
 
@@ -14,6 +15,9 @@ use crate::qubit::{ClbitId, QubitId};
 pub type NodeIndex = PetNodeIndex<u32>;
 
 /// A node in the circuit DAG.
+// Op nodes dominate every circuit, so boxing them would only add an
+// allocation per operation to shrink the few wire endpoints.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DagNode {
     /// Input node for a wire.
@@ -138,6 +142,8 @@ pub struct CircuitDag {
     global_phase: f64,
     /// Abstraction level of the circuit.
     level: CircuitLevel,
+    /// The most recently applied operation, if it is still in the DAG.
+    last_op: Option<NodeIndex>,
 }
 
 impl CircuitDag {
@@ -152,6 +158,7 @@ impl CircuitDag {
             wire_front: FxHashMap::default(),
             global_phase: 0.0,
             level: CircuitLevel::Logical,
+            last_op: None,
         }
     }
 
@@ -206,6 +213,12 @@ impl CircuitDag {
             }
         }
 
+        // Validate control-flow bodies against the block's operands
+        if instruction.is_control_flow() {
+            check_control_flow(&instruction)?;
+            validate_block(&instruction)?;
+        }
+
         // Validate qubits exist
         for &qubit in &instruction.qubits {
             if !self.qubit_inputs.contains_key(&qubit) {
@@ -216,8 +229,17 @@ impl CircuitDag {
             }
         }
 
+        // Classical wires: written bits plus bits read by a condition, so a
+        // conditioned gate is ordered after the measurement that sets them.
+        let mut clbits = instruction.clbits.clone();
+        for &clbit in instruction.condition_clbits() {
+            if !clbits.contains(&clbit) {
+                clbits.push(clbit);
+            }
+        }
+
         // Validate classical bits exist
-        for &clbit in &instruction.clbits {
+        for &clbit in &clbits {
             if !self.clbit_inputs.contains_key(&clbit) {
                 return Err(IrError::ClbitNotFound {
                     clbit,
@@ -268,7 +290,7 @@ impl CircuitDag {
         }
 
         // Connect classical wires — same O(1) approach.
-        for &clbit in &instruction.clbits {
+        for &clbit in &clbits {
             let out_node = self.clbit_outputs[&clbit];
             let wire = WireId::Clbit(clbit);
 
@@ -291,9 +313,56 @@ impl CircuitDag {
             self.wire_front.insert(wire, op_node);
         }
 
+        self.last_op = Some(op_node);
         Ok(op_node)
     }
 
+    /// Attach a classical condition to the most recently applied gate.
+    ///
+    /// The gate is re-applied with the condition so the classical bits it
+    /// reads become wires of the node. Since nothing was applied after it,
+    /// its position on the quantum wires is unchanged. Returns the new node
+    /// index.
+    pub fn condition_last_op(&mut self, condition: ClassicalCondition) -> IrResult<NodeIndex> {
+        let node = self
+            .last_op
+            .ok_or_else(|| IrError::InvalidCondition("no operation to condition".into()))?;
+        if !self.get_instruction(node).is_some_and(Instruction::is_gate) {
+            return Err(IrError::InvalidCondition(
+                "only gates can be classically conditioned".into(),
+            ));
+        }
+        check_condition_width(&condition)?;
+        if let Some(&clbit) = condition
+            .clbits
+            .iter()
+            .find(|c| !self.clbit_inputs.contains_key(c))
+        {
+            return Err(IrError::ClbitNotFound {
+                clbit,
+                gate_name: None,
+            });
+        }
+
+        let mut instruction = self.remove_op(node)?;
+        if let Some(gate) = instruction.gate_mut() {
+            gate.condition = Some(condition);
+        }
+        self.apply(instruction)
+    }
+
+    /// Annotate the most recently applied gate with its duration in
+    /// device-specific units (`dt`).
+    pub fn set_last_op_duration(&mut self, duration: u64) -> IrResult<()> {
+        let gate = self
+            .last_op
+            .and_then(|node| self.get_instruction_mut(node))
+            .and_then(Instruction::gate_mut)
+            .ok_or_else(|| IrError::InvalidDag("only gates carry a duration".into()))?;
+        gate.duration = Some(duration);
+        Ok(())
+    }
+
     /// Iterate over operations in topological order.
     ///
     /// # Panics
@@ -380,6 +449,12 @@ impl CircuitDag {
 
         self.graph.remove_node(node);
 
+        if self.last_op == Some(node) {
+            self.last_op = None;
+        } else if self.last_op == Some(last_idx) {
+            self.last_op = Some(node);
+        }
+
         // Helper to remap indices after petgraph's swap-remove.
         let fix = |idx: NodeIndex| -> NodeIndex {
             if last_idx != node && idx == last_idx {
@@ -437,19 +512,144 @@ impl CircuitDag {
     }
 
     /// Substitute a node with a sequence of instructions.
+    ///
+    /// The replacement is applied at the end of the wires, not at the
+    /// node's position; use [`substitute_node_with_dag`](Self::substitute_node_with_dag)
+    /// to replace a node in place. The replacement inherits the node's
+    /// metadata, keeping entries it already has.
     pub fn substitute_node(
         &mut self,
         node: NodeIndex,
         replacement: impl IntoIterator<Item = Instruction>,
     ) -> IrResult<Vec<NodeIndex>> {
-        let _old = self.remove_op(node)?;
+        let old = self.remove_op(node)?;
         let mut new_nodes = vec![];
         for inst in replacement {
-            new_nodes.push(self.apply(inst)?);
+            new_nodes.push(self.apply(inst.with_metadata_from(&old))?);
         }
         Ok(new_nodes)
     }
 
+    /// Replace an operation node in place with the operations of `subdag`.
+    ///
+    /// `wire_map` maps every qubit and classical wire of `subdag` to a
+    /// distinct wire of `node` of the same kind. The sub-DAG's operations
+    /// are spliced between the node's predecessors and successors on those
+    /// wires; wires of `node` that nothing maps onto are reconnected straight
+    /// through. The sub-DAG's global phase is added to this DAG's, and its
+    /// operations inherit the node's metadata, keeping entries they already
+    /// have.
+    ///
+    /// Returns the new nodes in topological order. Like
+    /// [`remove_op`](Self::remove_op), this invalidates the `NodeIndex` of
+    /// the DAG's last node, so re-fetch indices after each substitution.
+    ///
+    /// Fails without modifying the DAG if `node` is not an operation or if
+    /// `wire_map` does not cover `subdag` with distinct wires of `node`.
+    pub fn substitute_node_with_dag(
+        &mut self,
+        node: NodeIndex,
+        subdag: &CircuitDag,
+        wire_map: &FxHashMap<WireId, WireId>,
+    ) -> IrResult<Vec<NodeIndex>> {
+        if !self.graph.node_weight(node).is_some_and(DagNode::is_op) {
+            return Err(IrError::InvalidNode);
+        }
+
+        // Neighbours of `node` on each of its wires.
+        let preds: FxHashMap<WireId, NodeIndex> = self
+            .graph
+            .edges_directed(node, Direction::Incoming)
+            .map(|e| (e.weight().wire, e.source()))
+            .collect();
+        let succs: FxHashMap<WireId, NodeIndex> = self
+            .graph
+            .edges_directed(node, Direction::Outgoing)
+            .map(|e| (e.weight().wire, e.target()))
+            .collect();
+
+        let sub_wires = subdag
+            .qubits()
+            .map(WireId::Qubit)
+            .chain(subdag.clbits().map(WireId::Clbit));
+        let mut targets = rustc_hash::FxHashSet::default();
+        for wire in sub_wires {
+            let target = *wire_map.get(&wire).ok_or_else(|| {
+                IrError::InvalidDag(format!("wire map does not cover sub-DAG wire {wire:?}"))
+            })?;
+            let same_kind = matches!(
+                (wire, target),
+                (WireId::Qubit(_), WireId::Qubit(_)) | (WireId::Clbit(_), WireId::Clbit(_))
+            );
+            if !same_kind || !preds.contains_key(&target) {
+                return Err(IrError::InvalidDag(format!(
+                    "sub-DAG wire {wire:?} maps to {target:?}, which is not a matching wire of the node"
+                )));
+            }
+            if !targets.insert(target) {
+                return Err(IrError::InvalidDag(format!(
+                    "sub-DAG wires map onto {target:?} more than once"
+                )));
+            }
+        }
+
+        // `remove_op` swap-removes: the last node takes the index of `node`.
+        let last_idx = NodeIndex::new(self.graph.node_count() - 1);
+        let fix = |idx: NodeIndex| if idx == last_idx { node } else { idx };
+        let old = self.remove_op(node)?;
+
+        // Cut the pred → succ edges `remove_op` reconnected on mapped wires.
+        let mut front: FxHashMap<WireId, NodeIndex> = FxHashMap::default();
+        for &wire in &targets {
+            let (pred, succ) = (fix(preds[&wire]), fix(succs[&wire]));
+            let edge = self
+                .graph
+                .edges_connecting(pred, succ)
+                .find(|e| e.weight().wire == wire)
+                .map(|e| e.id())
+                .ok_or_else(|| {
+                    IrError::InvalidDag(format!("missing reconnected edge on wire {wire:?}"))
+                })?;
+            self.graph.remove_edge(edge);
+            front.insert(wire, pred);
+        }
+
+        let map_wire = |wire: WireId| wire_map[&wire];
+        let qubit_map = |q: QubitId| match map_wire(WireId::Qubit(q)) {
+            WireId::Qubit(q) => q,
+            WireId::Clbit(_) => unreachable!("wire kinds checked above"),
+        };
+        let clbit_map = |c: ClbitId| match map_wire(WireId::Clbit(c)) {
+            WireId::Clbit(c) => c,
+            WireId::Qubit(_) => unreachable!("wire kinds checked above"),
+        };
+
+        let mut new_nodes = Vec::with_capacity(subdag.num_ops());
+        for (sub_node, inst) in subdag.topological_ops() {
+            let op_node = self.graph.add_node(DagNode::Op(
+                inst.remap_wires(&qubit_map, &clbit_map)
+                    .with_metadata_from(&old),
+            ));
+            for edge in subdag.graph.edges_directed(sub_node, Direction::Incoming) {
+                let wire = map_wire(edge.weight().wire);
+                self.graph.add_edge(front[&wire], op_node, DagEdge { wire });
+                front.insert(wire, op_node);
+            }
+            new_nodes.push(op_node);
+        }
+
+        for (wire, last) in front {
+            let succ = fix(succs[&wire]);
+            self.graph.add_edge(last, succ, DagEdge { wire });
+            if matches!(self.graph[succ], DagNode::Out(_)) {
+                self.wire_front.insert(wire, last);
+            }
+        }
+        self.global_phase += subdag.global_phase;
+
+        Ok(new_nodes)
+    }
+
     /// Get the number of qubits.
     #[inline]
     pub fn num_qubits(&self) -> usize {
@@ -510,6 +710,52 @@ impl CircuitDag {
         max_depth
     }
 
+    /// Group operations into layers (moments).
+    ///
+    /// Operation `op` lands in layer `d - 1`, where `d` is the length of the
+    /// longest chain of operations ending at `op` along any qubit or
+    /// classical wire — so every layer acts on disjoint wires, each layer
+    /// only depends on earlier ones, and `layers().len() == depth()`.
+    /// Within a layer, operations are ordered by node index, i.e. the
+    /// order in which they were applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the DAG contains a cycle. See [`topological_ops`](Self::topological_ops)
+    /// for details.
+    pub fn layers(&self) -> Vec<Vec<(NodeIndex, &Instruction)>> {
+        let mut depths: FxHashMap<NodeIndex, usize> =
+            FxHashMap::with_capacity_and_hasher(self.graph.node_count(), rustc_hash::FxBuildHasher);
+        let mut layers: Vec<Vec<(NodeIndex, &Instruction)>> = Vec::new();
+
+        for node in petgraph::algo::toposort(&self.graph, None)
+            .expect("DAG contains a cycle — this is a bug in DAG construction; call verify_integrity() to diagnose")
+        {
+            let max_pred_depth = self
+                .graph
+                .edges_directed(node, Direction::Incoming)
+                .map(|e| depths.get(&e.source()).copied().unwrap_or(0))
+                .max()
+                .unwrap_or(0);
+
+            let node_depth = if let DagNode::Op(inst) = &self.graph[node] {
+                if layers.len() <= max_pred_depth {
+                    layers.resize_with(max_pred_depth + 1, Vec::new);
+                }
+                layers[max_pred_depth].push((node, inst));
+                max_pred_depth + 1
+            } else {
+                max_pred_depth
+            };
+            depths.insert(node, node_depth);
+        }
+
+        for layer in &mut layers {
+            layer.sort_unstable_by_key(|&(node, _)| node);
+        }
+        layers
+    }
+
     /// Iterate over qubits.
     pub fn qubits(&self) -> impl Iterator<Item = QubitId> + '_ {
         self.qubit_inputs.keys().copied()
@@ -709,10 +955,81 @@ impl Clone for CircuitDag {
             wire_front: self.wire_front.clone(),
             global_phase: self.global_phase,
             level: self.level,
+            last_op: self.last_op,
         }
     }
 }
 
+/// Check that a condition's value fits in the bits it compares against.
+fn check_condition_width(condition: &ClassicalCondition) -> IrResult<()> {
+    let width = condition.clbits.len();
+    if width > 0 && width < 64 && condition.value >> width != 0 {
+        return Err(IrError::InvalidCondition(format!(
+            "value {} does not fit in {} bit(s)",
+            condition.value, width
+        )));
+    }
+    Ok(())
+}
+
+/// Check the condition of a control-flow block and the bound of a loop.
+fn check_control_flow(block: &Instruction) -> IrResult<()> {
+    if let Some(condition) = block.condition() {
+        check_condition_width(condition)?;
+    }
+    if let InstructionKind::WhileLoop {
+        max_iterations: 0, ..
+    } = block.kind
+    {
+        return Err(IrError::InvalidDag(
+            "while loop must allow at least one iteration".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Recursively validate the bodies of a control-flow instruction: nested
+/// gates must have the right arity and nested operations may only touch
+/// the block's own qubits and classical bits.
+fn validate_block(block: &Instruction) -> IrResult<()> {
+    for inst in block.blocks().into_iter().flatten() {
+        if let InstructionKind::Gate(gate) = &inst.kind {
+            let expected = gate.num_qubits() as usize;
+            if expected != inst.qubits.len() {
+                return Err(IrError::QubitCountMismatch {
+                    gate_name: gate.name().to_string(),
+                    expected: expected as u32,
+                    got: inst.qubits.len() as u32,
+                });
+            }
+        }
+        if let Some(qubit) = inst.qubits.iter().find(|q| !block.qubits.contains(q)) {
+            return Err(IrError::InvalidDag(format!(
+                "'{}' in {} body uses qubit {qubit:?} outside the block",
+                inst.name(),
+                block.name()
+            )));
+        }
+        if let Some(clbit) = inst
+            .clbits
+            .iter()
+            .chain(inst.condition_clbits())
+            .find(|c| !block.clbits.contains(c) && !block.condition_clbits().contains(c))
+        {
+            return Err(IrError::InvalidDag(format!(
+                "'{}' in {} body uses classical bit {clbit:?} outside the block",
+                inst.name(),
+                block.name()
+            )));
+        }
+        if inst.is_control_flow() {
+            check_control_flow(inst)?;
+        }
+        validate_block(inst)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -783,6 +1100,129 @@ mod tests {
         assert_eq!(dag.depth(), 1);
     }
 
+    #[test]
+    fn test_layers() {
+        let mut dag = CircuitDag::new();
+        dag.add_qubit(QubitId(0));
+        dag.add_qubit(QubitId(1));
+        dag.add_qubit(QubitId(2));
+        dag.add_clbit(ClbitId(0));
+
+        dag.apply(Instruction::single_qubit_gate(StandardGate::H, QubitId(0)))
+            .unwrap();
+        dag.apply(Instruction::single_qubit_gate(StandardGate::X, QubitId(2)))
+            .unwrap();
+        dag.apply(Instruction::two_qubit_gate(
+            StandardGate::CX,
+            QubitId(0),
+            QubitId(1),
+        ))
+        .unwrap();
+        dag.apply(Instruction::measure(QubitId(0), ClbitId(0)))
+            .unwrap();
+        // Shares no qubit with the measurement but reads its classical bit.
+        dag.apply(Instruction::measure(QubitId(2), ClbitId(0)))
+            .unwrap();
+
+        let names: Vec<Vec<&str>> = dag
+            .layers()
+            .iter()
+            .map(|layer| layer.iter().map(|(_, inst)| inst.name()).collect())
+            .collect();
+        assert_eq!(
+            names,
+            vec![vec!["h", "x"], vec!["cx"], vec!["measure"], vec!["measure"]]
+        );
+        assert_eq!(dag.layers().len(), dag.depth());
+        assert!(CircuitDag::new().layers().is_empty());
+    }
+
+    #[test]
+    fn test_substitute_node_with_dag() {
+        let mut dag = CircuitDag::new();
+        dag.add_qubit(QubitId(0));
+        dag.add_qubit(QubitId(1));
+        dag.add_clbit(ClbitId(0));
+        dag.apply(Instruction::single_qubit_gate(StandardGate::H, QubitId(0)))
+            .unwrap();
+        let cx = dag
+            .apply(
+                Instruction::two_qubit_gate(StandardGate::CX, QubitId(0), QubitId(1))
+                    .with_label("entangle"),
+            )
+            .unwrap();
+        dag.apply(Instruction::measure(QubitId(1), ClbitId(0)))
+            .unwrap();
+
+        // CX = (I ⊗ H) · CZ · (I ⊗ H), built on its own wires.
+        let mut cz = CircuitDag::new();
+        cz.add_qubit(QubitId(5));
+        cz.add_qubit(QubitId(7));
+        cz.apply(Instruction::single_qubit_gate(StandardGate::H, QubitId(7)))
+            .unwrap();
+        cz.apply(
+            Instruction::two_qubit_gate(StandardGate::CZ, QubitId(5), QubitId(7)).with_label("cz"),
+        )
+        .unwrap();
+        cz.apply(Instruction::single_qubit_gate(StandardGate::H, QubitId(7)))
+            .unwrap();
+        cz.set_global_phase(0.25);
+
+        let mut wire_map = FxHashMap::default();
+        wire_map.insert(WireId::Qubit(QubitId(5)), WireId::Qubit(QubitId(0)));
+        wire_map.insert(WireId::Qubit(QubitId(7)), WireId::Qubit(QubitId(1)));
+
+        // Wires that are not wires of the node are rejected untouched.
+        let mut bad_map = wire_map.clone();
+        bad_map.insert(WireId::Qubit(QubitId(7)), WireId::Clbit(ClbitId(0)));
+        assert!(dag.substitute_node_with_dag(cx, &cz, &bad_map).is_err());
+        assert!(
+            dag.substitute_node_with_dag(cx, &cz, &FxHashMap::default())
+                .is_err()
+        );
+        assert_eq!(dag.num_ops(), 3);
+
+        let new_nodes = dag.substitute_node_with_dag(cx, &cz, &wire_map).unwrap();
+        assert_eq!(new_nodes.len(), 3);
+        dag.verify_integrity().unwrap();
+
+        // The replacement inherits the node's metadata without overriding
+        // its own.
+        let labels: Vec<_> = new_nodes
+            .iter()
+            .map(|&n| dag.get_instruction(n).unwrap().label())
+            .collect();
+        assert_eq!(labels, [Some("entangle"), Some("cz"), Some("entangle")]);
+        assert!((dag.global_phase() - 0.25).abs() < 1e-12);
+
+        // The decomposition sits between the H and the measurement.
+        let ops: Vec<(&str, Vec<QubitId>)> = dag
+            .topological_ops()
+            .map(|(_, inst)| (inst.name(), inst.qubits.clone()))
+            .collect();
+        let position = |name: &str, qubits: &[QubitId]| {
+            ops.iter()
+                .position(|(n, q)| *n == name && q.as_slice() == qubits)
+                .unwrap()
+        };
+        let cz_at = position("cz", &[QubitId(0), QubitId(1)]);
+        assert!(position("h", &[QubitId(0)]) < cz_at);
+        assert!(cz_at < position("measure", &[QubitId(1)]));
+        assert_eq!(dag.num_ops(), 5);
+        // h(q0) ∥ h(q1), cz, h(q1), measure
+        assert_eq!(dag.depth(), 4);
+
+        // The wire front follows the spliced nodes.
+        dag.apply(Instruction::single_qubit_gate(StandardGate::X, QubitId(0)))
+            .unwrap();
+        dag.verify_integrity().unwrap();
+        let x_layer = dag
+            .layers()
+            .iter()
+            .position(|layer| layer.iter().any(|(_, inst)| inst.name() == "x"));
+        assert_eq!(x_layer, Some(2));
+    }
+
     #[test]
     fn test_gate_arity_mismatch() {
         let mut dag = CircuitDag::new();
@@ -52,6 +52,10 @@ pub enum IrError {
     #[error("Cannot perform operation on parameterized circuit")]
     ParameterizedCircuit,
 
+    /// Invalid classical condition.
+    #[error("Invalid classical condition: {0}")]
+    InvalidCondition(String),
+
     /// Duplicate qubit in operation.
     #[error("Duplicate qubit {qubit:?} in operation{}", format_gate_context(.gate_name))]
     DuplicateQubit {
@@ -60,6 +64,62 @@ pub enum IrError {
         /// Optional gate name for context.
         gate_name: Option<String>,
     },
+
+    /// Duplicate classical bit in a wire map.
+    #[error("Duplicate classical bit {0:?} in wire map")]
+    DuplicateClbit(ClbitId),
+
+    /// Instruction has no inverse (non-unitary, or no known adjoint).
+    #[error("Cannot invert '{0}'")]
+    NonInvertible(String),
+
+    /// Wire map does not cover the circuit being composed.
+    #[error("Cannot compose circuit with {expected} {wire}s using a map of {got}")]
+    RegisterSizeMismatch {
+        /// The kind of wire (`"qubit"` or `"clbit"`).
+        wire: &'static str,
+        /// Number of wires in the composed circuit.
+        expected: usize,
+        /// Number of entries in the map.
+        got: usize,
+    },
+
+    /// Binding left symbolic parameters in the circuit.
+    #[error("Parameters left unbound: {}", .0.join(", "))]
+    UnboundParameters(Vec<String>),
+
+    /// Custom gate definition is missing or malformed.
+    #[error("Invalid gate definition: {0}")]
+    InvalidGateDefinition(String),
+
+    /// Number of values does not match the number of parameters.
+    #[error("Expected {expected} parameter values, got {got}")]
+    ParameterCountMismatch {
+        /// Number of parameters.
+        expected: usize,
+        /// Number of values supplied.
+        got: usize,
+    },
+
+    /// Binary circuit data is malformed or from an incompatible version.
+    #[error("Invalid binary circuit: {0}")]
+    InvalidBinary(String),
+
+    /// A unitary matrix cannot be built for the operation or circuit.
+    #[error("No unitary: {0}")]
+    NotUnitary(String),
+
+    /// Operation is not a Clifford gate.
+    #[error("Not a Clifford operation: {0}")]
+    NotClifford(String),
+
+    /// A controlled or powered gate cannot be decomposed.
+    #[error("Unsupported gate modifier: {0}")]
+    UnsupportedModifier(String),
+
+    /// A library construction was given arguments it cannot build.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
 }
 
 /// Helper function to format optional gate context.
@@ -0,0 +1,277 @@
+//! Exact complex numbers for gate matrices.
+//!
+//! Every standard gate without parameters has a matrix whose entries lie in
+//! the ring `ℤ[√2, i] / 2^k`: ±1, ±i, 1/√2, (1 ± i)/2, e^{±iπ/4}, and so on.
+//! [`ExactComplex`] represents these numbers without rounding, so identities
+//! such as `H·H = I` or `T·T = S` hold exactly and can be checked with `==`.
+//! [`StandardGate::exact_matrix`](crate::StandardGate::exact_matrix) returns
+//! them; [`StandardGate::matrix`](crate::StandardGate::matrix) rounds them to
+//! `f64`.
+//!
+//! ```rust
+//! use arvak_ir::exact::ExactComplex;
+//!
+//! let r = ExactComplex::FRAC_1_SQRT_2;
+//! assert_eq!(r * r + r * r, ExactComplex::ONE);
+//! assert_eq!(ExactComplex::I * ExactComplex::I, -ExactComplex::ONE);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_complex::Complex64;
+
+/// An exact complex number `((a + b√2) + (c + d√2)·i) / 2^k`.
+///
+/// The representation is kept reduced (not all of `a, b, c, d` even when
+/// `k > 0`), so equal numbers compare equal.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExactComplex {
+    /// `[a, b]`: the real part is `(a + b√2) / 2^k`.
+    re: [i64; 2],
+    /// `[c, d]`: the imaginary part is `(c + d√2) / 2^k`.
+    im: [i64; 2],
+    /// Power of two in the denominator.
+    k: u32,
+}
+
+impl ExactComplex {
+    /// `0`.
+    pub const ZERO: Self = Self::from_parts([0, 0], [0, 0], 0);
+    /// `1`.
+    pub const ONE: Self = Self::from_parts([1, 0], [0, 0], 0);
+    /// `i`.
+    pub const I: Self = Self::from_parts([0, 0], [1, 0], 0);
+    /// `1/√2`.
+    pub const FRAC_1_SQRT_2: Self = Self::from_parts([0, 1], [0, 0], 1);
+
+    /// `((re[0] + re[1]√2) + (im[0] + im[1]√2)·i) / 2^k`, reduced.
+    pub const fn new(re: [i64; 2], im: [i64; 2], k: u32) -> Self {
+        Self::from_parts(re, im, k).reduced()
+    }
+
+    const fn from_parts(re: [i64; 2], im: [i64; 2], k: u32) -> Self {
+        Self { re, im, k }
+    }
+
+    const fn reduced(mut self) -> Self {
+        while self.k > 0
+            && self.re[0] % 2 == 0
+            && self.re[1] % 2 == 0
+            && self.im[0] % 2 == 0
+            && self.im[1] % 2 == 0
+        {
+            self.re = [self.re[0] / 2, self.re[1] / 2];
+            self.im = [self.im[0] / 2, self.im[1] / 2];
+            self.k -= 1;
+        }
+        self
+    }
+
+    /// `e^{iπ·n/4}`, the eighth roots of unity.
+    pub const fn root_of_unity(n: i64) -> Self {
+        match n.rem_euclid(8) {
+            0 => Self::ONE,
+            1 => Self::from_parts([0, 1], [0, 1], 1),
+            2 => Self::I,
+            3 => Self::from_parts([0, -1], [0, 1], 1),
+            4 => Self::from_parts([-1, 0], [0, 0], 0),
+            5 => Self::from_parts([0, -1], [0, -1], 1),
+            6 => Self::from_parts([0, 0], [-1, 0], 0),
+            _ => Self::from_parts([0, 1], [0, -1], 1),
+        }
+    }
+
+    /// Complex conjugate.
+    #[must_use]
+    pub const fn conj(self) -> Self {
+        Self::from_parts(self.re, [-self.im[0], -self.im[1]], self.k)
+    }
+
+    /// Whether this is `0`.
+    pub const fn is_zero(self) -> bool {
+        self.re[0] == 0 && self.re[1] == 0 && self.im[0] == 0 && self.im[1] == 0
+    }
+
+    /// The nearest `f64` complex number.
+    pub fn to_complex64(self) -> Complex64 {
+        let scale = 0.5_f64.powi(i32::try_from(self.k).unwrap_or(i32::MAX));
+        let part = |[a, b]: [i64; 2]| (a as f64 + b as f64 * std::f64::consts::SQRT_2) * scale;
+        Complex64::new(part(self.re), part(self.im))
+    }
+
+    /// Both operands over the larger common denominator.
+    fn aligned(self, other: Self) -> (Self, Self, u32) {
+        let k = self.k.max(other.k);
+        let scale = |x: Self| {
+            let f = 1i64 << (k - x.k);
+            Self::from_parts([x.re[0] * f, x.re[1] * f], [x.im[0] * f, x.im[1] * f], k)
+        };
+        (scale(self), scale(other), k)
+    }
+}
+
+impl Default for ExactComplex {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<ExactComplex> for Complex64 {
+    fn from(value: ExactComplex) -> Self {
+        value.to_complex64()
+    }
+}
+
+impl Add for ExactComplex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (a, b, k) = self.aligned(other);
+        Self::new(
+            [a.re[0] + b.re[0], a.re[1] + b.re[1]],
+            [a.im[0] + b.im[0], a.im[1] + b.im[1]],
+            k,
+        )
+    }
+}
+
+impl Sub for ExactComplex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Neg for ExactComplex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_parts(
+            [-self.re[0], -self.re[1]],
+            [-self.im[0], -self.im[1]],
+            self.k,
+        )
+    }
+}
+
+impl Mul for ExactComplex {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        // (a + b√2)(c + d√2) = (ac + 2bd) + (ad + bc)√2
+        let real = |[a, b]: [i64; 2], [c, d]: [i64; 2]| [a * c + 2 * b * d, a * d + b * c];
+        let sub = |x: [i64; 2], y: [i64; 2]| [x[0] - y[0], x[1] - y[1]];
+        let add = |x: [i64; 2], y: [i64; 2]| [x[0] + y[0], x[1] + y[1]];
+        Self::new(
+            sub(real(self.re, other.re), real(self.im, other.im)),
+            add(real(self.re, other.im), real(self.im, other.re)),
+            self.k + other.k,
+        )
+    }
+}
+
+impl fmt::Debug for ExactComplex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ExactComplex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let part = |[a, b]: [i64; 2]| {
+            let root = match b {
+                1 => "√2".to_string(),
+                -1 => "-√2".to_string(),
+                b => format!("{b}√2"),
+            };
+            match (a, b) {
+                (a, 0) => a.to_string(),
+                (0, _) => root,
+                (a, b) if b > 0 => format!("{a}+{root}"),
+                (a, _) => format!("{a}{root}"),
+            }
+        };
+        let num = match (self.re, self.im) {
+            (_, [0, 0]) => part(self.re),
+            ([0, 0], im) => format!("({})i", part(im)),
+            (re, im) => format!("{} + ({})i", part(re), part(im)),
+        };
+        if self.k == 0 {
+            f.write_str(&num)
+        } else {
+            write!(f, "({num})/{}", 1u64 << self.k)
+        }
+    }
+}
+
+/// `U† · U == I`, checked exactly. `matrix` is row-major and square.
+pub fn is_unitary(matrix: &[ExactComplex]) -> bool {
+    let dim = matrix.len().isqrt();
+    if dim * dim != matrix.len() {
+        return false;
+    }
+    (0..dim).all(|r| {
+        (0..dim).all(|c| {
+            let dot = (0..dim).fold(ExactComplex::ZERO, |acc, k| {
+                acc + matrix[k * dim + r].conj() * matrix[k * dim + c]
+            });
+            dot == if r == c {
+                ExactComplex::ONE
+            } else {
+                ExactComplex::ZERO
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_is_exact() {
+        let r = ExactComplex::FRAC_1_SQRT_2;
+        assert_eq!(r * r, ExactComplex::new([1, 0], [0, 0], 1));
+        assert_eq!(r * r + r * r, ExactComplex::ONE);
+        assert_eq!(r - r, ExactComplex::ZERO);
+        assert!((r - r).is_zero());
+        assert_eq!(
+            ExactComplex::new([4, 2], [0, -6], 1),
+            ExactComplex::new([2, 1], [0, -3], 0)
+        );
+        assert_eq!(
+            (ExactComplex::ONE + ExactComplex::I).conj(),
+            ExactComplex::ONE - ExactComplex::I
+        );
+    }
+
+    #[test]
+    fn test_roots_of_unity() {
+        let w = ExactComplex::root_of_unity(1);
+        let mut power = ExactComplex::ONE;
+        for n in 0..16 {
+            assert_eq!(power, ExactComplex::root_of_unity(n), "ω^{n}");
+            let expected = Complex64::from_polar(1.0, std::f64::consts::FRAC_PI_4 * n as f64);
+            assert!((power.to_complex64() - expected).norm() < 1e-12);
+            power = power * w;
+        }
+        assert_eq!(ExactComplex::root_of_unity(-1), w.conj());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ExactComplex::FRAC_1_SQRT_2.to_string(), "(√2)/2");
+        assert_eq!(
+            ExactComplex::root_of_unity(3).to_string(),
+            "(-√2 + (√2)i)/2"
+        );
+        assert_eq!((-ExactComplex::I).to_string(), "(-1)i");
+        assert_eq!(
+            ExactComplex::new([1, 3], [0, 0], 2).to_string(),
+            "(1+3√2)/4"
+        );
+    }
+}
@@ -1,9 +1,16 @@
 //! Quantum gate types.
 
+use std::collections::HashMap;
+
 use num_complex::Complex64;
 use serde::{Deserialize, Serialize};
 
+use crate::circuit::Circuit;
+use crate::error::{IrError, IrResult};
+use crate::instruction::{Instruction, InstructionKind};
+use crate::modifier::ModifiedGate;
 use crate::parameter::ParameterExpression;
+use crate::qubit::{ClbitId, QubitId};
 
 /// Standard gates with known semantics.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -84,6 +91,14 @@ pub enum StandardGate {
     /// Fredkin gate (CSWAP).
     CSwap,
 
+    // Multi-controlled gates (controls first, target last)
+    /// X gate with the given number of controls.
+    MCX(u32),
+    /// Z gate with the given number of controls.
+    MCZ(u32),
+    /// Phase gate with the given number of controls.
+    MCP(u32, ParameterExpression),
+
     // IQM native gates
     /// Phased RX gate: PRX(θ, φ) = RZ(φ) · RX(θ) · RZ(-φ).
     PRX(ParameterExpression, ParameterExpression),
@@ -132,6 +147,9 @@ impl StandardGate {
             StandardGate::RZZ(_) => "rzz",
             StandardGate::CCX => "ccx",
             StandardGate::CSwap => "cswap",
+            StandardGate::MCX(_) => "mcx",
+            StandardGate::MCZ(_) => "mcz",
+            StandardGate::MCP(_, _) => "mcp",
             StandardGate::PRX(_, _) => "prx",
             StandardGate::ECR => "ecr",
         }
@@ -175,6 +193,21 @@ impl StandardGate {
             | StandardGate::ECR => 2,
 
             StandardGate::CCX | StandardGate::CSwap => 3,
+
+            StandardGate::MCX(n) | StandardGate::MCZ(n) | StandardGate::MCP(n, _) => {
+                n.saturating_add(1)
+            }
+        }
+    }
+
+    /// Get the number of control qubits of a multi-controlled gate.
+    ///
+    /// Returns `None` for all other gates, including fixed-arity controlled
+    /// gates such as `CX` and `CCX`.
+    pub fn num_controls(&self) -> Option<u32> {
+        match self {
+            StandardGate::MCX(n) | StandardGate::MCZ(n) | StandardGate::MCP(n, _) => Some(*n),
+            _ => None,
         }
     }
 
@@ -191,7 +224,8 @@ impl StandardGate {
             | StandardGate::CP(p)
             | StandardGate::RXX(p)
             | StandardGate::RYY(p)
-            | StandardGate::RZZ(p) => p.is_symbolic(),
+            | StandardGate::RZZ(p)
+            | StandardGate::MCP(_, p) => p.is_symbolic(),
 
             StandardGate::U(a, b, c) => a.is_symbolic() || b.is_symbolic() || c.is_symbolic(),
 
@@ -214,7 +248,8 @@ impl StandardGate {
             | StandardGate::CP(p)
             | StandardGate::RXX(p)
             | StandardGate::RYY(p)
-            | StandardGate::RZZ(p) => vec![p],
+            | StandardGate::RZZ(p)
+            | StandardGate::MCP(_, p) => vec![p],
 
             StandardGate::U(a, b, c) => vec![a, b, c],
 
@@ -223,15 +258,105 @@ impl StandardGate {
             _ => vec![],
         }
     }
+
+    /// Get mutable references to the parameters of this gate.
+    pub fn parameters_mut(&mut self) -> Vec<&mut ParameterExpression> {
+        match self {
+            StandardGate::Rx(p)
+            | StandardGate::Ry(p)
+            | StandardGate::Rz(p)
+            | StandardGate::P(p)
+            | StandardGate::CRx(p)
+            | StandardGate::CRy(p)
+            | StandardGate::CRz(p)
+            | StandardGate::CP(p)
+            | StandardGate::RXX(p)
+            | StandardGate::RYY(p)
+            | StandardGate::RZZ(p)
+            | StandardGate::MCP(_, p) => vec![p],
+
+            StandardGate::U(a, b, c) => vec![a, b, c],
+
+            StandardGate::PRX(theta, phi) => vec![theta, phi],
+
+            _ => vec![],
+        }
+    }
+
+    /// Get the inverse (adjoint) of this gate.
+    ///
+    /// Returns `None` for gates whose inverse is not itself a standard gate
+    /// (`ISwap`).
+    ///
+    /// - H† = H (Hadamard is self-inverse)
+    /// - S† = Sdg, T† = Tdg
+    /// - Rx(θ)† = Rx(-θ)
+    pub fn inverse(&self) -> Option<StandardGate> {
+        let gate = match self {
+            // Self-inverse gates (Hermitian)
+            StandardGate::I
+            | StandardGate::X
+            | StandardGate::Y
+            | StandardGate::Z
+            | StandardGate::H
+            | StandardGate::CX
+            | StandardGate::CY
+            | StandardGate::CZ
+            | StandardGate::CH
+            | StandardGate::Swap
+            | StandardGate::ECR
+            | StandardGate::CCX
+            | StandardGate::CSwap
+            | StandardGate::MCX(_)
+            | StandardGate::MCZ(_) => self.clone(),
+
+            // S, T and SX pair with their daggers
+            StandardGate::S => StandardGate::Sdg,
+            StandardGate::Sdg => StandardGate::S,
+            StandardGate::T => StandardGate::Tdg,
+            StandardGate::Tdg => StandardGate::T,
+            StandardGate::SX => StandardGate::SXdg,
+            StandardGate::SXdg => StandardGate::SX,
+
+            // Rotations: negate the angle
+            StandardGate::Rx(theta) => StandardGate::Rx(-theta.clone()),
+            StandardGate::Ry(theta) => StandardGate::Ry(-theta.clone()),
+            StandardGate::Rz(theta) => StandardGate::Rz(-theta.clone()),
+            StandardGate::P(lambda) => StandardGate::P(-lambda.clone()),
+            StandardGate::CRx(theta) => StandardGate::CRx(-theta.clone()),
+            StandardGate::CRy(theta) => StandardGate::CRy(-theta.clone()),
+            StandardGate::CRz(theta) => StandardGate::CRz(-theta.clone()),
+            StandardGate::CP(lambda) => StandardGate::CP(-lambda.clone()),
+            StandardGate::RXX(theta) => StandardGate::RXX(-theta.clone()),
+            StandardGate::RYY(theta) => StandardGate::RYY(-theta.clone()),
+            StandardGate::RZZ(theta) => StandardGate::RZZ(-theta.clone()),
+            StandardGate::MCP(n, lambda) => StandardGate::MCP(*n, -lambda.clone()),
+
+            // U(θ, φ, λ)† = U(-θ, -λ, -φ)
+            StandardGate::U(theta, phi, lambda) => {
+                StandardGate::U(-theta.clone(), -lambda.clone(), -phi.clone())
+            }
+
+            // PRX(θ, φ)† = PRX(-θ, φ)
+            StandardGate::PRX(theta, phi) => StandardGate::PRX(-theta.clone(), phi.clone()),
+
+            // iSWAP† conjugates the off-diagonal phases (−i instead of i); it
+            // has no standard-gate form and needs a decomposition.
+            StandardGate::ISwap => return None,
+        };
+        Some(gate)
+    }
 }
 
-/// A quantum gate, either standard or custom.
+/// A quantum gate: standard, custom, or either with modifiers.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GateKind {
     /// A standard gate with known semantics.
     Standard(StandardGate),
     /// A custom user-defined gate.
     Custom(CustomGate),
+    /// A controlled or powered gate.
+    Modified(ModifiedGate),
 }
 
 impl GateKind {
@@ -241,6 +366,7 @@ impl GateKind {
         match self {
             GateKind::Standard(g) => g.name(),
             GateKind::Custom(g) => &g.name,
+            GateKind::Modified(g) => g.name(),
         }
     }
 
@@ -250,6 +376,7 @@ impl GateKind {
         match self {
             GateKind::Standard(g) => g.num_qubits(),
             GateKind::Custom(g) => g.num_qubits,
+            GateKind::Modified(g) => g.num_qubits(),
         }
     }
 }
@@ -266,6 +393,9 @@ pub struct CustomGate {
     /// Optional unitary matrix (row-major, 2^n × 2^n).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub matrix: Option<Vec<Complex64>>,
+    /// Optional decomposition into other gates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<Box<GateDefinition>>,
 }
 
 impl CustomGate {
@@ -276,6 +406,7 @@ impl CustomGate {
             num_qubits,
             params: vec![],
             matrix: None,
+            definition: None,
         }
     }
 
@@ -305,15 +436,167 @@ impl CustomGate {
         self.matrix = Some(matrix);
         self
     }
+
+    /// Add a decomposition to the gate.
+    ///
+    /// Fails if the definition's body acts on more qubits than the gate.
+    pub fn with_definition(mut self, definition: GateDefinition) -> IrResult<Self> {
+        if definition.num_qubits() > self.num_qubits {
+            return Err(IrError::InvalidGateDefinition(format!(
+                "definition of '{}' acts on {} qubits, gate has {}",
+                self.name,
+                definition.num_qubits(),
+                self.num_qubits,
+            )));
+        }
+        self.definition = Some(Box::new(definition));
+        Ok(self)
+    }
+
+    /// Expand the gate into its definition, applied to `qubits`.
+    ///
+    /// The definition's formal parameters are replaced by the gate's
+    /// `params`, so a symbolic gate expands to symbolic gates. Fails if the
+    /// gate has no definition or `qubits` / `params` do not match it.
+    pub fn expand(&self, qubits: &[QubitId]) -> IrResult<Vec<Instruction>> {
+        let definition = self.definition.as_ref().ok_or_else(|| {
+            IrError::InvalidGateDefinition(format!("'{}' has no definition", self.name))
+        })?;
+        if qubits.len() != self.num_qubits as usize {
+            return Err(IrError::QubitCountMismatch {
+                gate_name: self.name.clone(),
+                expected: self.num_qubits,
+                got: qubits.len() as u32,
+            });
+        }
+        if definition.params.len() != self.params.len() {
+            return Err(IrError::ParameterCountMismatch {
+                expected: definition.params.len(),
+                got: self.params.len(),
+            });
+        }
+        if definition.num_qubits() > self.num_qubits {
+            return Err(IrError::InvalidGateDefinition(format!(
+                "'{}' body acts on {} qubits, gate has {}",
+                self.name,
+                definition.num_qubits(),
+                self.num_qubits
+            )));
+        }
+
+        let formals: HashMap<String, ParameterExpression> = definition
+            .params
+            .iter()
+            .cloned()
+            .zip(self.params.iter().cloned())
+            .collect();
+        let operands = |q: QubitId| qubits[q.0 as usize];
+        Ok(definition
+            .body
+            .iter()
+            .map(|inst| {
+                let mut inst = inst.remap_wires(&operands, &|c| c);
+                if let Some(gate) = inst.gate_mut() {
+                    for param in gate.parameters_mut() {
+                        *param = param.substitute(&formals).simplify();
+                    }
+                }
+                inst
+            })
+            .collect())
+    }
+}
+
+/// Decomposition of a [`CustomGate`] into other gates.
+///
+/// The body acts on the gate's local qubits `QubitId(0)`, `QubitId(1)`, …
+/// in operand order. Its gate parameters may refer to the formal parameter
+/// names in `params`, which are bound positionally to the gate's own
+/// parameters on expansion. Bodies may contain further custom gates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateDefinition {
+    /// Formal parameter names.
+    pub params: Vec<String>,
+    /// Gates and barriers over the local qubits.
+    pub body: Vec<Instruction>,
+}
+
+impl GateDefinition {
+    /// Build a definition from a circuit.
+    ///
+    /// The circuit's `i`-th qubit becomes the gate's `i`-th operand, and
+    /// symbols named in `params` become formal parameters. The circuit may
+    /// only contain unconditioned gates and barriers; its global phase is
+    /// dropped, which is unobservable for an uncontrolled gate:
+    ///
+    /// ```
+    /// use arvak_ir::{Circuit, CustomGate, GateDefinition, ParameterExpression, QubitId};
+    ///
+    /// let mut body = Circuit::with_size("zz", 2, 0);
+    /// body.cx(QubitId(0), QubitId(1))?
+    ///     .rz(ParameterExpression::symbol("t"), QubitId(1))?
+    ///     .cx(QubitId(0), QubitId(1))?;
+    ///
+    /// let zz = CustomGate::new("zz", 2)
+    ///     .with_params(vec![ParameterExpression::constant(0.5)])
+    ///     .with_definition(GateDefinition::from_circuit(vec!["t".into()], &body)?)?;
+    /// assert_eq!(zz.expand(&[QubitId(3), QubitId(1)])?.len(), 3);
+    /// # Ok::<(), arvak_ir::IrError>(())
+    /// ```
+    pub fn from_circuit(params: Vec<String>, circuit: &Circuit) -> IrResult<Self> {
+        let local: HashMap<QubitId, QubitId> = circuit
+            .qubits()
+            .iter()
+            .enumerate()
+            .map(|(i, q)| (q.id, QubitId(i as u32)))
+            .collect();
+
+        let mut body = Vec::with_capacity(circuit.dag().num_ops());
+        for (_, inst) in circuit.dag().topological_ops() {
+            let allowed = match &inst.kind {
+                InstructionKind::Gate(gate) => gate.condition.is_none(),
+                InstructionKind::Barrier => true,
+                _ => false,
+            };
+            if !allowed {
+                return Err(IrError::InvalidGateDefinition(format!(
+                    "'{}' cannot contain '{}'",
+                    circuit.name(),
+                    inst.name()
+                )));
+            }
+            body.push(inst.remap_wires(&|q| local[&q], &|c| c));
+        }
+
+        Ok(Self { params, body })
+    }
+
+    /// Number of local qubits the body acts on.
+    pub fn num_qubits(&self) -> u32 {
+        self.body
+            .iter()
+            .flat_map(|inst| &inst.qubits)
+            .map(|q| q.0 + 1)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 /// Classical condition for conditional gates.
+///
+/// A condition either names a classical register (`register == value`) or
+/// lists the individual bits it reads (`clbits`, least-significant first).
+/// Bit conditions are wired into the DAG, so a conditioned gate is always
+/// ordered after the measurements that write those bits.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassicalCondition {
-    /// The name of the classical register.
+    /// The name of the classical register (empty for bit conditions).
     pub register: String,
     /// The value to compare against.
     pub value: u64,
+    /// Classical bits read by the condition, least-significant first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clbits: Vec<ClbitId>,
 }
 
 impl ClassicalCondition {
@@ -322,6 +605,22 @@ impl ClassicalCondition {
         Self {
             register: register.into(),
             value,
+            clbits: vec![],
+        }
+    }
+
+    /// Create a condition on a single classical bit (`clbit == value`).
+    pub fn on_clbit(clbit: ClbitId, value: u64) -> Self {
+        Self::on_clbits([clbit], value)
+    }
+
+    /// Create a condition on a group of classical bits, least-significant
+    /// first.
+    pub fn on_clbits(clbits: impl IntoIterator<Item = ClbitId>, value: u64) -> Self {
+        Self {
+            register: String::new(),
+            value,
+            clbits: clbits.into_iter().collect(),
         }
     }
 }
@@ -337,6 +636,9 @@ pub struct Gate {
     /// Optional classical condition.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub condition: Option<ClassicalCondition>,
+    /// Optional duration in device-specific units (`dt`), for scheduling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
 }
 
 impl Gate {
@@ -346,6 +648,7 @@ impl Gate {
             kind: GateKind::Standard(gate),
             label: None,
             condition: None,
+            duration: None,
         }
     }
 
@@ -355,6 +658,7 @@ impl Gate {
             kind: GateKind::Custom(gate),
             label: None,
             condition: None,
+            duration: None,
         }
     }
 
@@ -372,6 +676,40 @@ impl Gate {
         self
     }
 
+    /// Annotate the gate with its duration in device-specific units (`dt`).
+    #[must_use]
+    pub fn with_duration(mut self, duration: u64) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Control the gate on `num_controls` more qubits, which precede its
+    /// operands (OpenQASM 3 `ctrl(n) @`).
+    ///
+    /// The result is a [`ModifiedGate`], decomposed only when needed. The
+    /// duration is dropped; label and condition are kept.
+    #[must_use]
+    pub fn controlled(self, num_controls: u32) -> Self {
+        self.modified(num_controls, 1.0)
+    }
+
+    /// Raise the gate to the power `k` (OpenQASM 3 `pow(k) @`); `-1.0` is
+    /// the inverse.
+    ///
+    /// See [`controlled`](Self::controlled).
+    #[must_use]
+    pub fn power(self, k: f64) -> Self {
+        self.modified(0, k)
+    }
+
+    fn modified(self, num_controls: u32, power: f64) -> Self {
+        Self {
+            kind: ModifiedGate::new(self.kind, num_controls, power).into(),
+            duration: None,
+            ..self
+        }
+    }
+
     /// Get the name of this gate.
     pub fn name(&self) -> &str {
         self.kind.name()
@@ -381,6 +719,24 @@ impl Gate {
     pub fn num_qubits(&self) -> u32 {
         self.kind.num_qubits()
     }
+
+    /// Get the parameters of this gate (standard or custom).
+    pub fn parameters(&self) -> Vec<&ParameterExpression> {
+        match &self.kind {
+            GateKind::Standard(g) => g.parameters(),
+            GateKind::Custom(g) => g.params.iter().collect(),
+            GateKind::Modified(g) => g.parameters(),
+        }
+    }
+
+    /// Get mutable references to the parameters of this gate.
+    pub fn parameters_mut(&mut self) -> Vec<&mut ParameterExpression> {
+        match &mut self.kind {
+            GateKind::Standard(g) => g.parameters_mut(),
+            GateKind::Custom(g) => g.params.iter_mut().collect(),
+            GateKind::Modified(g) => g.parameters_mut(),
+        }
+    }
 }
 
 impl From<StandardGate> for Gate {
@@ -395,6 +751,17 @@ impl From<CustomGate> for Gate {
     }
 }
 
+impl From<ModifiedGate> for Gate {
+    fn from(gate: ModifiedGate) -> Self {
+        Self {
+            kind: gate.into(),
+            label: None,
+            condition: None,
+            duration: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,10 +772,14 @@ mod tests {
         assert_eq!(StandardGate::H.num_qubits(), 1);
         assert_eq!(StandardGate::CX.num_qubits(), 2);
         assert_eq!(StandardGate::CCX.num_qubits(), 3);
+        assert_eq!(StandardGate::MCX(4).num_qubits(), 5);
+        assert_eq!(StandardGate::MCZ(3).num_controls(), Some(3));
+        assert_eq!(StandardGate::CCX.num_controls(), None);
 
         assert!(!StandardGate::H.is_parameterized());
         assert!(!StandardGate::Rx(ParameterExpression::constant(PI)).is_parameterized());
         assert!(StandardGate::Rx(ParameterExpression::symbol("theta")).is_parameterized());
+        assert!(StandardGate::MCP(3, ParameterExpression::symbol("lam")).is_parameterized());
     }
 
     #[test]
@@ -432,4 +803,67 @@ mod tests {
         assert_eq!(custom.num_qubits, 2);
         assert_eq!(custom.params.len(), 1);
     }
+
+    #[test]
+    fn test_custom_gate_expand() {
+        let mut body = Circuit::with_size("rot", 2, 0);
+        body.h(QubitId(0))
+            .unwrap()
+            .rx(
+                ParameterExpression::symbol("t") * ParameterExpression::constant(2.0),
+                QubitId(1),
+            )
+            .unwrap();
+        let definition = GateDefinition::from_circuit(vec!["t".into()], &body).unwrap();
+        assert_eq!(definition.num_qubits(), 2);
+
+        let gate = CustomGate::new("rot", 2)
+            .with_params(vec![ParameterExpression::symbol("phi")])
+            .with_definition(definition)
+            .unwrap();
+        let expanded = gate.expand(&[QubitId(5), QubitId(2)]).unwrap();
+        let h = expanded.iter().find(|inst| inst.name() == "h").unwrap();
+        let rx = expanded.iter().find(|inst| inst.name() == "rx").unwrap();
+        assert_eq!(h.qubits, vec![QubitId(5)]);
+        assert_eq!(rx.qubits, vec![QubitId(2)]);
+        let angle = rx.as_gate().unwrap().parameters()[0].clone();
+        assert_eq!(
+            angle.symbols(),
+            std::collections::HashSet::from(["phi".into()])
+        );
+        assert_eq!(angle.bind("phi", 0.25).as_f64(), Some(0.5));
+
+        assert!(matches!(
+            gate.expand(&[QubitId(0)]),
+            Err(IrError::QubitCountMismatch { .. })
+        ));
+        assert!(matches!(
+            CustomGate::new("opaque", 1).expand(&[QubitId(0)]),
+            Err(IrError::InvalidGateDefinition(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_gate_rejects_wider_definition() {
+        let mut body = Circuit::with_size("wide", 2, 0);
+        body.cx(QubitId(0), QubitId(1)).unwrap();
+        let definition = GateDefinition::from_circuit(vec![], &body).unwrap();
+        assert!(matches!(
+            CustomGate::new("narrow", 1).with_definition(definition),
+            Err(IrError::InvalidGateDefinition(_))
+        ));
+    }
+
+    #[test]
+    fn test_gate_definition_rejects_non_unitary() {
+        let mut body = Circuit::with_size("bad", 1, 1);
+        body.h(QubitId(0))
+            .unwrap()
+            .measure(QubitId(0), ClbitId(0))
+            .unwrap();
+        assert!(matches!(
+            GateDefinition::from_circuit(vec![], &body),
+            Err(IrError::InvalidGateDefinition(_))
+        ));
+    }
 }
@@ -1,8 +1,11 @@
 //! Circuit instructions combining gates with operands.
 
+use std::collections::{BTreeMap, HashMap};
+
 use serde::{Deserialize, Serialize};
 
-use crate::gate::{Gate, StandardGate};
+use crate::error::{IrError, IrResult};
+use crate::gate::{ClassicalCondition, Gate, GateKind, StandardGate};
 use crate::noise::{NoiseModel, NoiseRole};
 use crate::qubit::{ClbitId, QubitId};
 
@@ -40,8 +43,48 @@ pub enum InstructionKind {
         /// Semantic role: deficit (mitigate) or resource (preserve).
         role: NoiseRole,
     },
+    /// Classically controlled block: runs `then_body` if `condition` holds
+    /// at runtime, otherwise `else_body`.
+    ///
+    /// The bodies address the enclosing circuit's qubits and classical bits.
+    /// The instruction's `qubits` and `clbits` are the union of everything
+    /// the bodies touch, so the DAG orders the block like any other
+    /// operation on those wires.
+    IfElse {
+        /// The condition selecting the branch.
+        condition: ClassicalCondition,
+        /// Instructions executed when the condition holds.
+        then_body: Vec<Instruction>,
+        /// Instructions executed otherwise (may be empty).
+        else_body: Vec<Instruction>,
+    },
+    /// Bounded loop: runs `body` while `condition` holds at runtime, at most
+    /// `max_iterations` times.
+    ///
+    /// The condition is checked before every iteration, so the body
+    /// normally re-measures the bits it reads (repeat-until-success,
+    /// iterative phase estimation). Operands are derived from the body as
+    /// for [`InstructionKind::IfElse`].
+    WhileLoop {
+        /// The condition checked before each iteration.
+        condition: ClassicalCondition,
+        /// Instructions executed per iteration.
+        body: Vec<Instruction>,
+        /// Upper bound on the number of iterations.
+        max_iterations: u32,
+    },
 }
 
+/// Metadata key for a user-facing label.
+pub const METADATA_LABEL: &str = "label";
+/// Metadata key for the compiler pass that inserted an instruction.
+pub const METADATA_ORIGIN: &str = "origin";
+/// Metadata key for a backend pragma.
+pub const METADATA_PRAGMA: &str = "pragma";
+
+/// Key/value annotations on an instruction, sorted by key.
+pub type InstructionMetadata = BTreeMap<String, String>;
+
 /// A complete instruction with operands.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Instruction {
@@ -51,6 +94,11 @@ pub struct Instruction {
     pub qubits: Vec<QubitId>,
     /// Classical bits this instruction operates on (for measure).
     pub clbits: Vec<ClbitId>,
+    /// Annotations such as a label or the pass that inserted the
+    /// instruction. Carried through DAG transformations, serialization and
+    /// wire remapping; they never affect semantics.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: InstructionMetadata,
 }
 
 impl Instruction {
@@ -60,6 +108,7 @@ impl Instruction {
             kind: InstructionKind::Gate(gate.into()),
             qubits: qubits.into_iter().collect(),
             clbits: vec![],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -79,6 +128,7 @@ impl Instruction {
             kind: InstructionKind::Measure,
             qubits: vec![qubit],
             clbits: vec![clbit],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -102,6 +152,7 @@ impl Instruction {
             kind: InstructionKind::Measure,
             qubits,
             clbits,
+            metadata: InstructionMetadata::new(),
         })
     }
 
@@ -111,6 +162,7 @@ impl Instruction {
             kind: InstructionKind::Reset,
             qubits: vec![qubit],
             clbits: vec![],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -120,6 +172,7 @@ impl Instruction {
             kind: InstructionKind::Barrier,
             qubits: qubits.into_iter().collect(),
             clbits: vec![],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -129,6 +182,7 @@ impl Instruction {
             kind: InstructionKind::Delay { duration },
             qubits: vec![qubit],
             clbits: vec![],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -138,6 +192,7 @@ impl Instruction {
             kind: InstructionKind::Shuttle { from_zone, to_zone },
             qubits: vec![qubit],
             clbits: vec![],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -147,6 +202,7 @@ impl Instruction {
             kind: InstructionKind::NoiseChannel { model, role },
             qubits: vec![qubit],
             clbits: vec![],
+            metadata: InstructionMetadata::new(),
         }
     }
 
@@ -160,6 +216,104 @@ impl Instruction {
         Self::noise_channel(model, NoiseRole::Resource, qubit)
     }
 
+    /// Create an if/else block.
+    ///
+    /// The operands are derived from the bodies: every qubit and classical
+    /// bit (written or read by a nested condition) used in either branch.
+    pub fn if_else(
+        condition: ClassicalCondition,
+        then_body: Vec<Instruction>,
+        else_body: Vec<Instruction>,
+    ) -> Self {
+        let (qubits, clbits) = block_operands(then_body.iter().chain(&else_body));
+        Self {
+            kind: InstructionKind::IfElse {
+                condition,
+                then_body,
+                else_body,
+            },
+            qubits,
+            clbits,
+            metadata: InstructionMetadata::new(),
+        }
+    }
+
+    /// Create a bounded while loop.
+    ///
+    /// The operands are derived from the body, as for
+    /// [`Instruction::if_else`].
+    pub fn while_loop(
+        condition: ClassicalCondition,
+        body: Vec<Instruction>,
+        max_iterations: u32,
+    ) -> Self {
+        let (qubits, clbits) = block_operands(&body);
+        Self {
+            kind: InstructionKind::WhileLoop {
+                condition,
+                body,
+                max_iterations,
+            },
+            qubits,
+            clbits,
+            metadata: InstructionMetadata::new(),
+        }
+    }
+
+    /// Set a metadata entry, builder style.
+    #[must_use]
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_metadata(key, value);
+        self
+    }
+
+    /// Copy the metadata of `other` onto this instruction, keeping entries
+    /// it already has. Use this when replacing `other` with instructions
+    /// that implement it.
+    #[must_use]
+    pub fn with_metadata_from(mut self, other: &Instruction) -> Self {
+        for (key, value) in &other.metadata {
+            self.metadata
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self
+    }
+
+    /// Set a metadata entry, replacing any previous value.
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// The metadata value stored under `key`.
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Attach a label, builder style.
+    #[must_use]
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        self.with_metadata(METADATA_LABEL, label)
+    }
+
+    /// The instruction's label: its `label` metadata, or else the label of
+    /// its gate.
+    pub fn label(&self) -> Option<&str> {
+        self.metadata(METADATA_LABEL)
+            .or_else(|| self.as_gate()?.label.as_deref())
+    }
+
+    /// Record the pass that inserted this instruction, builder style.
+    #[must_use]
+    pub fn with_origin(self, pass: impl Into<String>) -> Self {
+        self.with_metadata(METADATA_ORIGIN, pass)
+    }
+
+    /// The pass that inserted this instruction, if recorded.
+    pub fn origin(&self) -> Option<&str> {
+        self.metadata(METADATA_ORIGIN)
+    }
+
     /// Check if this is a noise channel instruction.
     pub fn is_noise_channel(&self) -> bool {
         matches!(self.kind, InstructionKind::NoiseChannel { .. })
@@ -217,6 +371,228 @@ impl Instruction {
         }
     }
 
+    /// Check if this is an if/else block.
+    pub fn is_if_else(&self) -> bool {
+        matches!(self.kind, InstructionKind::IfElse { .. })
+    }
+
+    /// Check if this is a while loop.
+    pub fn is_while_loop(&self) -> bool {
+        matches!(self.kind, InstructionKind::WhileLoop { .. })
+    }
+
+    /// Check if this is a control-flow block (if/else or while loop).
+    pub fn is_control_flow(&self) -> bool {
+        self.is_if_else() || self.is_while_loop()
+    }
+
+    /// The classical condition of a conditioned gate or a control-flow
+    /// block.
+    pub fn condition(&self) -> Option<&ClassicalCondition> {
+        match &self.kind {
+            InstructionKind::Gate(g) => g.condition.as_ref(),
+            InstructionKind::IfElse { condition, .. }
+            | InstructionKind::WhileLoop { condition, .. } => Some(condition),
+            _ => None,
+        }
+    }
+
+    /// Duration of a delay or of a gate annotated with one, in
+    /// device-specific units (`dt`).
+    pub fn duration(&self) -> Option<u64> {
+        match &self.kind {
+            InstructionKind::Gate(g) => g.duration,
+            InstructionKind::Delay { duration } => Some(*duration),
+            _ => None,
+        }
+    }
+
+    /// Check if this is a classically conditioned gate or block.
+    pub fn is_conditional(&self) -> bool {
+        self.condition().is_some()
+    }
+
+    /// Classical bits read by this instruction's condition, if any.
+    pub fn condition_clbits(&self) -> &[ClbitId] {
+        self.condition().map_or(&[], |c| c.clbits.as_slice())
+    }
+
+    /// Nested instruction blocks (`then` and `else` for an if/else block,
+    /// the body of a while loop, none otherwise).
+    pub fn blocks(&self) -> Vec<&[Instruction]> {
+        match &self.kind {
+            InstructionKind::IfElse {
+                then_body,
+                else_body,
+                ..
+            } => vec![then_body, else_body],
+            InstructionKind::WhileLoop { body, .. } => vec![body],
+            _ => vec![],
+        }
+    }
+
+    /// Mutable access to the nested instruction blocks, for passes that
+    /// rewrite inside control flow.
+    ///
+    /// Rewrites must stay within the block's `qubits` and `clbits`.
+    pub fn blocks_mut(&mut self) -> Vec<&mut Vec<Instruction>> {
+        match &mut self.kind {
+            InstructionKind::IfElse {
+                then_body,
+                else_body,
+                ..
+            } => vec![then_body, else_body],
+            InstructionKind::WhileLoop { body, .. } => vec![body],
+            _ => vec![],
+        }
+    }
+
+    /// Visit this instruction and, recursively, every instruction nested in
+    /// its blocks (pre-order).
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&'a Instruction)) {
+        f(self);
+        for block in self.blocks() {
+            for inst in block {
+                inst.walk(f);
+            }
+        }
+    }
+
+    /// Get the inverse of this instruction.
+    ///
+    /// Gates are replaced by their adjoint (keeping label, condition and
+    /// duration), barriers and delays are kept, shuttles move back, and
+    /// if/else blocks invert both bodies: the condition only reads classical
+    /// bits, so the same branch is taken. Modified gates negate their power.
+    /// Measurements, resets, noise channels, custom gates, while loops and
+    /// `ISwap` fail with [`IrError::NonInvertible`].
+    pub fn inverse(&self) -> IrResult<Instruction> {
+        let kind = match &self.kind {
+            InstructionKind::Gate(gate) => {
+                let kind = match &gate.kind {
+                    GateKind::Standard(g) => g.inverse().map(GateKind::Standard),
+                    GateKind::Custom(_) => None,
+                    GateKind::Modified(g) => Some(g.inverse().into()),
+                }
+                .ok_or_else(|| IrError::NonInvertible(gate.name().to_string()))?;
+                InstructionKind::Gate(Gate {
+                    kind,
+                    label: gate.label.clone(),
+                    condition: gate.condition.clone(),
+                    duration: gate.duration,
+                })
+            }
+            InstructionKind::Barrier | InstructionKind::Delay { .. } => self.kind.clone(),
+            InstructionKind::Shuttle { from_zone, to_zone } => InstructionKind::Shuttle {
+                from_zone: *to_zone,
+                to_zone: *from_zone,
+            },
+            InstructionKind::IfElse {
+                condition,
+                then_body,
+                else_body,
+            } => {
+                let invert = |body: &[Instruction]| -> IrResult<Vec<Instruction>> {
+                    body.iter().rev().map(Instruction::inverse).collect()
+                };
+                InstructionKind::IfElse {
+                    condition: condition.clone(),
+                    then_body: invert(then_body)?,
+                    else_body: invert(else_body)?,
+                }
+            }
+            InstructionKind::Measure
+            | InstructionKind::Reset
+            | InstructionKind::NoiseChannel { .. }
+            | InstructionKind::WhileLoop { .. } => {
+                return Err(IrError::NonInvertible(self.name().to_string()));
+            }
+        };
+
+        Ok(Instruction {
+            kind,
+            qubits: self.qubits.clone(),
+            clbits: self.clbits.clone(),
+            metadata: self.metadata.clone(),
+        })
+    }
+
+    /// Copy this instruction onto other wires.
+    ///
+    /// Every qubit and classical bit is rewritten through the maps,
+    /// including bits read by conditions and operands of nested blocks.
+    pub fn remap_wires(
+        &self,
+        qubit_map: &impl Fn(QubitId) -> QubitId,
+        clbit_map: &impl Fn(ClbitId) -> ClbitId,
+    ) -> Instruction {
+        let remap_condition = |condition: &ClassicalCondition| ClassicalCondition {
+            clbits: condition.clbits.iter().map(|&c| clbit_map(c)).collect(),
+            ..condition.clone()
+        };
+
+        let kind = match &self.kind {
+            InstructionKind::Gate(gate) => {
+                let mut gate = gate.clone();
+                gate.condition = gate.condition.as_ref().map(remap_condition);
+                InstructionKind::Gate(gate)
+            }
+            InstructionKind::IfElse {
+                condition,
+                then_body,
+                else_body,
+            } => {
+                let body = |b: &[Instruction]| -> Vec<Instruction> {
+                    b.iter()
+                        .map(|inst| inst.remap_wires(qubit_map, clbit_map))
+                        .collect()
+                };
+                return Instruction::if_else(
+                    remap_condition(condition),
+                    body(then_body),
+                    body(else_body),
+                )
+                .with_metadata_from(self);
+            }
+            InstructionKind::WhileLoop {
+                condition,
+                body,
+                max_iterations,
+            } => {
+                let body = body
+                    .iter()
+                    .map(|inst| inst.remap_wires(qubit_map, clbit_map))
+                    .collect();
+                return Instruction::while_loop(remap_condition(condition), body, *max_iterations)
+                    .with_metadata_from(self);
+            }
+            kind => kind.clone(),
+        };
+
+        Instruction {
+            kind,
+            qubits: self.qubits.iter().map(|&q| qubit_map(q)).collect(),
+            clbits: self.clbits.iter().map(|&c| clbit_map(c)).collect(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Bind symbolic gate parameters in place, including gates nested in
+    /// blocks. Fully bound expressions are folded to constants; symbols
+    /// without a value are left in place.
+    pub fn bind_parameters(&mut self, values: &HashMap<String, f64>) {
+        if let Some(gate) = self.gate_mut() {
+            for param in gate.parameters_mut() {
+                *param = param.bind_all(values).simplify();
+            }
+        }
+        for block in self.blocks_mut() {
+            for inst in block {
+                inst.bind_parameters(values);
+            }
+        }
+    }
+
     /// Get the name of the instruction.
     pub fn name(&self) -> &str {
         match &self.kind {
@@ -230,10 +606,31 @@ impl Instruction {
                 NoiseRole::Deficit => "noise_deficit",
                 NoiseRole::Resource => "noise_resource",
             },
+            InstructionKind::IfElse { .. } => "if_else",
+            InstructionKind::WhileLoop { .. } => "while_loop",
         }
     }
 }
 
+/// Operands of a control-flow block: every qubit and classical bit (written
+/// or read by a nested condition) used in its bodies, sorted.
+fn block_operands<'a>(
+    bodies: impl IntoIterator<Item = &'a Instruction>,
+) -> (Vec<QubitId>, Vec<ClbitId>) {
+    let mut qubits = Vec::new();
+    let mut clbits = Vec::new();
+    for inst in bodies {
+        qubits.extend_from_slice(&inst.qubits);
+        clbits.extend_from_slice(&inst.clbits);
+        clbits.extend_from_slice(inst.condition_clbits());
+    }
+    qubits.sort_unstable_by_key(|q| q.0);
+    qubits.dedup();
+    clbits.sort_unstable_by_key(|c| c.0);
+    clbits.dedup();
+    (qubits, clbits)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,6 +643,22 @@ mod tests {
         assert_eq!(inst.name(), "h");
     }
 
+    #[test]
+    fn test_duration() {
+        assert_eq!(Instruction::delay(QubitId(0), 160).duration(), Some(160));
+        let h = Instruction::single_qubit_gate(StandardGate::H, QubitId(0));
+        assert_eq!(h.duration(), None);
+
+        let sx = Instruction::gate(
+            Gate::standard(StandardGate::SX).with_duration(32),
+            [QubitId(0)],
+        );
+        assert_eq!(sx.duration(), Some(32));
+        assert_eq!(sx.inverse().unwrap().duration(), Some(32));
+        let moved = sx.remap_wires(&|_| QubitId(3), &|c| c);
+        assert_eq!(moved.duration(), Some(32));
+    }
+
     #[test]
     fn test_measure_instruction() {
         let inst = Instruction::measure(QubitId(0), ClbitId(0));
@@ -292,4 +705,98 @@ mod tests {
             _ => panic!("Expected Shuttle"),
         }
     }
+
+    #[test]
+    fn test_if_else_instruction() {
+        let then_body = vec![
+            Instruction::single_qubit_gate(StandardGate::X, QubitId(2)),
+            Instruction::measure(QubitId(2), ClbitId(1)),
+        ];
+        let else_body = vec![Instruction::two_qubit_gate(
+            StandardGate::CX,
+            QubitId(1),
+            QubitId(0),
+        )];
+        let inst = Instruction::if_else(
+            ClassicalCondition::on_clbit(ClbitId(0), 1),
+            then_body,
+            else_body,
+        );
+
+        assert!(inst.is_if_else());
+        assert!(inst.is_conditional());
+        assert_eq!(inst.name(), "if_else");
+        assert_eq!(inst.qubits, vec![QubitId(0), QubitId(1), QubitId(2)]);
+        assert_eq!(inst.clbits, vec![ClbitId(1)]);
+        assert_eq!(inst.condition_clbits(), &[ClbitId(0)]);
+        assert_eq!(inst.blocks().len(), 2);
+
+        let mut names = vec![];
+        inst.walk(&mut |i| names.push(i.name().to_string()));
+        assert_eq!(names, vec!["if_else", "x", "measure", "cx"]);
+    }
+
+    #[test]
+    fn test_while_loop_instruction() {
+        // Repeat-until-success: retry while the flag reads 1.
+        let body = vec![
+            Instruction::reset(QubitId(1)),
+            Instruction::single_qubit_gate(StandardGate::H, QubitId(1)),
+            Instruction::two_qubit_gate(StandardGate::CX, QubitId(1), QubitId(0)),
+            Instruction::measure(QubitId(1), ClbitId(0)),
+        ];
+        let inst = Instruction::while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), body, 8);
+
+        assert!(inst.is_while_loop());
+        assert!(inst.is_control_flow());
+        assert!(!inst.is_if_else());
+        assert_eq!(inst.name(), "while_loop");
+        assert_eq!(inst.qubits, vec![QubitId(0), QubitId(1)]);
+        assert_eq!(inst.clbits, vec![ClbitId(0)]);
+        assert_eq!(inst.condition_clbits(), &[ClbitId(0)]);
+        assert_eq!(inst.blocks().len(), 1);
+        assert!(matches!(inst.inverse(), Err(IrError::NonInvertible(_))));
+
+        let moved = inst.remap_wires(&|q| QubitId(q.0 + 2), &|c| ClbitId(c.0 + 1));
+        assert_eq!(moved.qubits, vec![QubitId(2), QubitId(3)]);
+        assert_eq!(moved.condition_clbits(), &[ClbitId(1)]);
+        assert!(matches!(
+            moved.kind,
+            InstructionKind::WhileLoop {
+                max_iterations: 8,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_metadata() {
+        let inst = Instruction::two_qubit_gate(StandardGate::Swap, QubitId(0), QubitId(1))
+            .with_origin("SabreRouting")
+            .with_metadata(METADATA_PRAGMA, "keep");
+        assert_eq!(inst.origin(), Some("SabreRouting"));
+        assert_eq!(inst.metadata(METADATA_PRAGMA), Some("keep"));
+        assert_eq!(inst.label(), None);
+
+        // Metadata follows the instruction through rewrites.
+        let moved = inst.remap_wires(&|q| QubitId(q.0 + 1), &|c| c);
+        assert_eq!(moved.metadata, inst.metadata);
+        assert_eq!(inst.inverse().unwrap().origin(), Some("SabreRouting"));
+
+        // Gate labels are instruction labels too.
+        let labeled = Instruction::gate(
+            Gate::standard(StandardGate::H).with_label("prep"),
+            [QubitId(0)],
+        );
+        assert_eq!(labeled.label(), Some("prep"));
+        assert_eq!(labeled.with_label("override").label(), Some("override"));
+
+        // Empty metadata is left out of JSON, so older documents still parse.
+        let plain = Instruction::single_qubit_gate(StandardGate::H, QubitId(0));
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("metadata"));
+        let json = serde_json::to_string(&inst).unwrap();
+        let restored: Instruction = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, inst);
+    }
 }
@@ -14,11 +14,26 @@
 //! - **Qubits and Classical Bits**: [`QubitId`], [`ClbitId`] for addressing quantum
 //!   and classical registers
 //! - **Gates**: [`StandardGate`] for built-in gates (H, X, CX, etc.) and [`CustomGate`]
-//!   for user-defined operations
-//! - **Parameters**: [`ParameterExpression`] for symbolic parameters in variational circuits
+//!   for user-defined operations; [`Gate::controlled`] and [`Gate::power`]
+//!   wrap either in a [`ModifiedGate`]
+//! - **Parameters**: [`ParameterExpression`] for symbolic parameters in variational circuits,
+//!   and [`ParameterVector`] for binding many of them at once
 //! - **Instructions**: [`Instruction`] combining gates with their operands
 //! - **DAG**: [`CircuitDag`] for the internal graph representation
 //! - **Circuit**: [`Circuit`] high-level builder API
+//! - **Serialization**: [`binary`] compact, versioned format behind
+//!   [`Circuit::to_bytes`] and [`Circuit::from_bytes`]
+//! - **Random circuits**: [`Circuit::random`] reproducible layered workloads,
+//!   optionally Clifford-only, for benchmarks and fuzzing
+//! - **Clifford tableaux**: [`CliffordTableau`] stabilizer form of Clifford
+//!   circuits, with [`CliffordTableau::to_circuit`] for resynthesis
+//! - **Unitaries**: [`unitary`] dense matrices of gates and, via
+//!   [`Circuit::to_matrix`], of circuits up to ten qubits; gates without
+//!   parameters also have [`exact`] matrices
+//! - **Circuit library**: [`circuits`] GHZ, QFT, adders, multi-controlled X
+//!   ladders and state preparation, verified against their matrices
+//! - **Visitors**: [`CircuitVisitor`] and [`DagTransformer`] per-kind callbacks
+//!   for analyzing and rewriting circuits without touching the graph
 //!
 //! # Example: Building a Bell State
 //!
@@ -56,6 +71,10 @@
 //!
 //! // Later, bind the parameter to a concrete value
 //! let bound = theta.bind("theta", PI / 4.0);
+//!
+//! // Or bind every parameter of the circuit at once
+//! let numeric = circuit.assign(&[PI / 4.0]).unwrap();
+//! assert!(numeric.parameters().is_empty());
 //! ```
 //!
 //! # Supported Gates
@@ -73,20 +92,32 @@
 //! | `Swap` | 2 | SWAP gate |
 //! | `CCX` | 3 | Toffoli (CCNOT) gate |
 
+pub mod binary;
 pub mod circuit;
+pub mod circuits;
+pub mod clifford;
 pub mod dag;
 pub mod error;
+pub mod exact;
 pub mod gate;
 pub mod instruction;
+pub mod modifier;
 pub mod noise;
 pub mod parameter;
 pub mod qubit;
+pub mod random;
+pub mod unitary;
+pub mod visit;
 
 pub use circuit::Circuit;
+pub use clifford::CliffordTableau;
 pub use dag::{CircuitDag, CircuitLevel, DagEdge, DagNode, NodeIndex, WireId};
 pub use error::{IrError, IrResult};
-pub use gate::{ClassicalCondition, CustomGate, Gate, GateKind, StandardGate};
-pub use instruction::{Instruction, InstructionKind};
+pub use gate::{ClassicalCondition, CustomGate, Gate, GateDefinition, GateKind, StandardGate};
+pub use instruction::{Instruction, InstructionKind, InstructionMetadata};
+pub use modifier::ModifiedGate;
 pub use noise::{NoiseModel, NoiseProfile, NoiseRole};
-pub use parameter::ParameterExpression;
+pub use parameter::{ParameterExpression, ParameterVector};
 pub use qubit::{Clbit, ClbitId, Qubit, QubitId};
+pub use random::{RandomCircuitOptions, SplitMix64};
+pub use visit::{CircuitVisitor, DagTransformer, Rewrite};
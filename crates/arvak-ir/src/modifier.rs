@@ -0,0 +1,796 @@
+//! Controlled and powered gates.
+//!
+//! [`Gate::controlled`](crate::Gate::controlled) and
+//! [`Gate::power`](crate::Gate::power) wrap a gate in a [`ModifiedGate`],
+//! the IR form of the OpenQASM 3 `ctrl @` and `pow(k) @` modifiers
+//! (`inv @` is `pow(-1)`). The wrapper travels through the IR unchanged and
+//! is emitted back with its modifiers; it is only decomposed when something
+//! needs plain gates, through [`ModifiedGate::expand`], which basis
+//! translation calls for targets that lack the gate.
+//!
+//! ```rust
+//! use arvak_ir::{Circuit, Gate, QubitId, StandardGate};
+//!
+//! // The controlled-U^4 step of a phase estimation circuit.
+//! let step = Gate::standard(StandardGate::T).power(4.0).controlled(1);
+//! assert_eq!(step.name(), "ctrl @ pow(4) @ t");
+//! assert_eq!(step.num_qubits(), 2);
+//!
+//! let mut circuit = Circuit::with_size("qpe", 2, 0);
+//! circuit.gate(step, [QubitId(0), QubitId(1)]).unwrap();
+//! ```
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use std::fmt::Write;
+
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{IrError, IrResult};
+use crate::gate::{GateKind, StandardGate};
+use crate::instruction::Instruction;
+use crate::parameter::ParameterExpression;
+use crate::qubit::QubitId;
+
+/// Tolerance for integer powers and vanishing angles.
+const TOLERANCE: f64 = 1e-12;
+
+/// A gate raised to a power and controlled on extra qubits.
+///
+/// Control and power modifiers commute, so any chain of OpenQASM 3
+/// modifiers reduces to a number of controls and a single exponent: the
+/// gate applies `base^power` to its last operands when all of its first
+/// `num_controls` operands are `|1⟩`. Wrapping a modified gate again
+/// flattens the two, adding the controls and multiplying the powers.
+///
+/// Non-integer powers are the principal powers of rotation and phase gates
+/// (their angle is scaled); other gates only take integer powers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ModifiedGateFields")]
+pub struct ModifiedGate {
+    #[serde(skip_serializing)]
+    name: String,
+    base: Box<GateKind>,
+    num_controls: u32,
+    power: f64,
+}
+
+#[derive(Deserialize)]
+struct ModifiedGateFields {
+    base: Box<GateKind>,
+    num_controls: u32,
+    power: f64,
+}
+
+impl From<ModifiedGateFields> for ModifiedGate {
+    fn from(fields: ModifiedGateFields) -> Self {
+        Self::new(*fields.base, fields.num_controls, fields.power)
+    }
+}
+
+impl ModifiedGate {
+    /// Raise `base` to `power` and control it on `num_controls` qubits.
+    pub fn new(base: GateKind, num_controls: u32, power: f64) -> Self {
+        let (base, num_controls, power) = match base {
+            GateKind::Modified(inner) => (
+                *inner.base,
+                inner.num_controls + num_controls,
+                inner.power * power,
+            ),
+            base => (base, num_controls, power),
+        };
+
+        let mut name = String::new();
+        match num_controls {
+            0 => {}
+            1 => name.push_str("ctrl @ "),
+            n => {
+                let _ = write!(name, "ctrl({n}) @ ");
+            }
+        }
+        if (power + 1.0).abs() < TOLERANCE {
+            name.push_str("inv @ ");
+        } else if (power - 1.0).abs() >= TOLERANCE {
+            let _ = write!(name, "pow({power}) @ ");
+        }
+        name.push_str(base.name());
+
+        Self {
+            name,
+            base: Box::new(base),
+            num_controls,
+            power,
+        }
+    }
+
+    /// Name of the gate with its modifiers, e.g. `ctrl(2) @ inv @ s`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The unmodified gate.
+    pub fn base(&self) -> &GateKind {
+        &self.base
+    }
+
+    /// Number of control qubits, which precede the base gate's operands.
+    pub fn num_controls(&self) -> u32 {
+        self.num_controls
+    }
+
+    /// Power the base gate is raised to.
+    pub fn power(&self) -> f64 {
+        self.power
+    }
+
+    /// Number of qubits: the controls plus the base gate's.
+    pub fn num_qubits(&self) -> u32 {
+        self.num_controls + self.base.num_qubits()
+    }
+
+    /// Whether the modifiers leave the base gate unchanged.
+    pub fn is_trivial(&self) -> bool {
+        self.num_controls == 0 && (self.power - 1.0).abs() < TOLERANCE
+    }
+
+    /// The parameters of the base gate.
+    pub fn parameters(&self) -> Vec<&ParameterExpression> {
+        match self.base.as_ref() {
+            GateKind::Standard(g) => g.parameters(),
+            GateKind::Custom(g) => g.params.iter().collect(),
+            GateKind::Modified(g) => g.parameters(),
+        }
+    }
+
+    /// Mutable references to the parameters of the base gate.
+    pub fn parameters_mut(&mut self) -> Vec<&mut ParameterExpression> {
+        match self.base.as_mut() {
+            GateKind::Standard(g) => g.parameters_mut(),
+            GateKind::Custom(g) => g.params.iter_mut().collect(),
+            GateKind::Modified(g) => g.parameters_mut(),
+        }
+    }
+
+    /// The inverse: the same gate raised to the opposite power.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        Self::new((*self.base).clone(), self.num_controls, -self.power)
+    }
+
+    /// The equivalent standard gate, if there is one: `ctrl(2) @ x` is
+    /// `ccx`, `pow(0.5) @ rz(θ)` is `rz(θ/2)`.
+    pub fn to_standard(&self) -> Option<StandardGate> {
+        let GateKind::Standard(base) = self.base.as_ref() else {
+            return None;
+        };
+        with_controls(&standard_power(base, self.power)?, self.num_controls)
+    }
+
+    /// Decompose into gates without modifiers, applied to `qubits`
+    /// (controls first).
+    ///
+    /// A gate with a standard equivalent becomes that gate. Otherwise the
+    /// base is raised to the power, by scaling rotation angles or, for
+    /// integer powers, by repetition (of the inverse for negative powers),
+    /// and every resulting gate is controlled: X, Z and phase gates become
+    /// their multi-controlled forms, other single-qubit gates use an exact
+    /// decomposition around multi-controlled X gates, and two-qubit gates
+    /// are rewritten into those first. Custom gates expand through their
+    /// definition, whose global phase is dropped, so a controlled custom
+    /// gate is only exact if its definition is.
+    ///
+    /// Fails for non-integer powers of other gates, and for controlled or
+    /// inverted custom gates without a definition (single-qubit ones with a
+    /// matrix can be controlled).
+    pub fn expand(&self, qubits: &[QubitId]) -> IrResult<Vec<Instruction>> {
+        if qubits.len() != self.num_qubits() as usize {
+            return Err(IrError::QubitCountMismatch {
+                gate_name: self.name.clone(),
+                expected: self.num_qubits(),
+                got: qubits.len() as u32,
+            });
+        }
+        if let Some(gate) = self.to_standard() {
+            return Ok(vec![Instruction::gate(gate, qubits.iter().copied())]);
+        }
+
+        let (controls, targets) = qubits.split_at(self.num_controls as usize);
+        let mut out = Vec::new();
+        for step in self.powered(targets)? {
+            control_step(&step, controls, &mut out)?;
+        }
+        Ok(out)
+    }
+
+    /// Gates implementing the uncontrolled `base^power` on `targets`.
+    fn powered(&self, targets: &[QubitId]) -> IrResult<Vec<Instruction>> {
+        let on_targets = |gate: StandardGate| Instruction::gate(gate, targets.iter().copied());
+        if let GateKind::Standard(gate) = self.base.as_ref() {
+            if let Some(gate) = standard_power(gate, self.power) {
+                return Ok(vec![on_targets(gate)]);
+            }
+            if *gate == StandardGate::ISwap {
+                // iSWAP = RXX(-π/2) · RYY(-π/2), and the two commute.
+                let angle = ParameterExpression::constant(-FRAC_PI_2 * self.power);
+                return Ok(vec![
+                    on_targets(StandardGate::RXX(angle.clone())),
+                    on_targets(StandardGate::RYY(angle)),
+                ]);
+            }
+        }
+
+        let Some(power) = integer_power(self.power) else {
+            return Err(IrError::UnsupportedModifier(format!(
+                "'{}': only rotation and phase gates take non-integer powers",
+                self.name
+            )));
+        };
+        if let GateKind::Standard(gate) = self.base.as_ref() {
+            if power % 2 == 0 && gate.inverse().as_ref() == Some(gate) {
+                return Ok(vec![]);
+            }
+        }
+
+        let once = match self.base.as_ref() {
+            GateKind::Standard(gate) => vec![on_targets(gate.clone())],
+            GateKind::Custom(custom) if custom.definition.is_some() => custom.expand(targets)?,
+            base => vec![Instruction::gate(
+                crate::gate::Gate {
+                    kind: base.clone(),
+                    label: None,
+                    condition: None,
+                    duration: None,
+                },
+                targets.iter().copied(),
+            )],
+        };
+        let once = if power < 0 {
+            let mut inverted = Vec::with_capacity(once.len());
+            invert_steps(&once, &mut inverted)?;
+            inverted
+        } else {
+            once
+        };
+        let repetitions = usize::try_from(power.unsigned_abs()).unwrap_or(usize::MAX);
+        Ok(once
+            .iter()
+            .cycle()
+            .take(once.len().saturating_mul(repetitions))
+            .cloned()
+            .collect())
+    }
+}
+
+impl From<ModifiedGate> for GateKind {
+    /// The modified gate, or just its base if the modifiers are trivial.
+    fn from(gate: ModifiedGate) -> Self {
+        if gate.is_trivial() {
+            *gate.base
+        } else {
+            GateKind::Modified(gate)
+        }
+    }
+}
+
+/// `power` as an integer, if it is one.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn integer_power(power: f64) -> Option<i64> {
+    let rounded = power.round();
+    ((power - rounded).abs() < TOLERANCE && rounded.abs() < 2f64.powi(62)).then_some(rounded as i64)
+}
+
+/// `gate^power` as a single standard gate, if there is one.
+fn standard_power(gate: &StandardGate, power: f64) -> Option<StandardGate> {
+    if (power - 1.0).abs() < TOLERANCE {
+        return Some(gate.clone());
+    }
+    if (power + 1.0).abs() < TOLERANCE {
+        if let Some(inverse) = gate.inverse() {
+            return Some(inverse);
+        }
+    }
+    if integer_power(power).is_some_and(|k| k % 2 != 0) && gate.inverse().as_ref() == Some(gate) {
+        return Some(gate.clone());
+    }
+
+    let scale = |angle: &ParameterExpression| {
+        (angle.clone() * ParameterExpression::constant(power)).simplify()
+    };
+    let phase = |angle: f64| ParameterExpression::constant(angle * power);
+    Some(match gate {
+        StandardGate::Rx(t) => StandardGate::Rx(scale(t)),
+        StandardGate::Ry(t) => StandardGate::Ry(scale(t)),
+        StandardGate::Rz(t) => StandardGate::Rz(scale(t)),
+        StandardGate::P(l) => StandardGate::P(scale(l)),
+        StandardGate::CRx(t) => StandardGate::CRx(scale(t)),
+        StandardGate::CRy(t) => StandardGate::CRy(scale(t)),
+        StandardGate::CRz(t) => StandardGate::CRz(scale(t)),
+        StandardGate::CP(l) => StandardGate::CP(scale(l)),
+        StandardGate::MCP(n, l) => StandardGate::MCP(*n, scale(l)),
+        StandardGate::RXX(t) => StandardGate::RXX(scale(t)),
+        StandardGate::RYY(t) => StandardGate::RYY(scale(t)),
+        StandardGate::RZZ(t) => StandardGate::RZZ(scale(t)),
+        StandardGate::PRX(t, p) => StandardGate::PRX(scale(t), p.clone()),
+        StandardGate::Z => StandardGate::P(phase(PI)),
+        StandardGate::S => StandardGate::P(phase(FRAC_PI_2)),
+        StandardGate::Sdg => StandardGate::P(phase(-FRAC_PI_2)),
+        StandardGate::T => StandardGate::P(phase(FRAC_PI_4)),
+        StandardGate::Tdg => StandardGate::P(phase(-FRAC_PI_4)),
+        StandardGate::CZ => StandardGate::CP(phase(PI)),
+        StandardGate::MCZ(n) => StandardGate::MCP(*n, phase(PI)),
+        _ => return None,
+    })
+}
+
+/// `gate` with `num_controls` more controls as a single standard gate, if
+/// there is one.
+fn with_controls(gate: &StandardGate, num_controls: u32) -> Option<StandardGate> {
+    if num_controls == 0 {
+        return Some(gate.clone());
+    }
+    let n = num_controls;
+    let x = |c: u32| match c {
+        1 => StandardGate::CX,
+        2 => StandardGate::CCX,
+        c => StandardGate::MCX(c),
+    };
+    let z = |c: u32| match c {
+        1 => StandardGate::CZ,
+        c => StandardGate::MCZ(c),
+    };
+    let p = |c: u32, lambda: ParameterExpression| match c {
+        1 => StandardGate::CP(lambda),
+        c => StandardGate::MCP(c, lambda),
+    };
+    Some(match gate {
+        StandardGate::X => x(n),
+        StandardGate::CX => x(n + 1),
+        StandardGate::CCX => x(n + 2),
+        StandardGate::MCX(m) => x(m + n),
+        StandardGate::Z => z(n),
+        StandardGate::CZ => z(n + 1),
+        StandardGate::MCZ(m) => z(m + n),
+        StandardGate::P(l) => p(n, l.clone()),
+        StandardGate::CP(l) => p(n + 1, l.clone()),
+        StandardGate::MCP(m, l) => p(m + n, l.clone()),
+        StandardGate::S => p(n, ParameterExpression::constant(FRAC_PI_2)),
+        StandardGate::Sdg => p(n, ParameterExpression::constant(-FRAC_PI_2)),
+        StandardGate::T => p(n, ParameterExpression::constant(FRAC_PI_4)),
+        StandardGate::Tdg => p(n, ParameterExpression::constant(-FRAC_PI_4)),
+        _ if n > 1 => return None,
+        StandardGate::Y => StandardGate::CY,
+        StandardGate::H => StandardGate::CH,
+        StandardGate::Rx(t) => StandardGate::CRx(t.clone()),
+        StandardGate::Ry(t) => StandardGate::CRy(t.clone()),
+        StandardGate::Rz(t) => StandardGate::CRz(t.clone()),
+        StandardGate::Swap => StandardGate::CSwap,
+        _ => return None,
+    })
+}
+
+/// Append the inverses of `steps`, last step first, to `out`.
+fn invert_steps(steps: &[Instruction], out: &mut Vec<Instruction>) -> IrResult<()> {
+    for step in steps.iter().rev() {
+        let Some(gate) = step.as_gate() else {
+            out.push(step.inverse()?);
+            continue;
+        };
+        match &gate.kind {
+            GateKind::Custom(custom) if custom.definition.is_some() => {
+                invert_steps(&custom.expand(&step.qubits)?, out)?;
+            }
+            GateKind::Custom(_) => out.push(step.inverse()?),
+            // iSWAP has no standard inverse; wrap it instead.
+            GateKind::Standard(_) | GateKind::Modified(_) => {
+                let mut inverse = step.clone();
+                if let Some(gate) = inverse.gate_mut() {
+                    *gate = gate.clone().power(-1.0);
+                }
+                out.push(inverse);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Append `step` controlled on `controls` to `out`.
+fn control_step(
+    step: &Instruction,
+    controls: &[QubitId],
+    out: &mut Vec<Instruction>,
+) -> IrResult<()> {
+    let Some(gate) = step.as_gate() else {
+        out.push(step.clone());
+        return Ok(());
+    };
+    match &gate.kind {
+        GateKind::Standard(g) => control_standard(g, controls, &step.qubits, out),
+        GateKind::Custom(_) if controls.is_empty() => {
+            out.push(step.clone());
+            Ok(())
+        }
+        GateKind::Custom(custom) => {
+            if let (None, Some(matrix), [target]) =
+                (&custom.definition, &custom.matrix, step.qubits.as_slice())
+            {
+                return control_unitary(matrix, controls, *target, out);
+            }
+            for inner in custom.expand(&step.qubits)? {
+                control_step(&inner, controls, out)?;
+            }
+            Ok(())
+        }
+        GateKind::Modified(_) => {
+            let wrapped = ModifiedGate::new(gate.kind.clone(), controls.len() as u32, 1.0);
+            let qubits: Vec<_> = controls.iter().chain(&step.qubits).copied().collect();
+            out.extend(wrapped.expand(&qubits)?);
+            Ok(())
+        }
+    }
+}
+
+/// Append `gate` on `targets`, controlled on `controls`, to `out`.
+fn control_standard(
+    gate: &StandardGate,
+    controls: &[QubitId],
+    targets: &[QubitId],
+    out: &mut Vec<Instruction>,
+) -> IrResult<()> {
+    let qubits = |targets: &[QubitId]| controls.iter().chain(targets).copied().collect::<Vec<_>>();
+    if let Some(controlled) = with_controls(gate, controls.len() as u32) {
+        out.push(Instruction::gate(controlled, qubits(targets)));
+        return Ok(());
+    }
+    let push = |out: &mut Vec<Instruction>, gate: StandardGate, qubits: &[QubitId]| {
+        out.push(Instruction::gate(gate, qubits.iter().copied()));
+    };
+    let constant = ParameterExpression::constant;
+
+    match gate {
+        StandardGate::I => {}
+        // Fold the gate's own control into the modifier's.
+        StandardGate::CY
+        | StandardGate::CH
+        | StandardGate::CRx(_)
+        | StandardGate::CRy(_)
+        | StandardGate::CRz(_)
+        | StandardGate::CSwap => {
+            let base = match gate {
+                StandardGate::CY => StandardGate::Y,
+                StandardGate::CH => StandardGate::H,
+                StandardGate::CRx(t) => StandardGate::Rx(t.clone()),
+                StandardGate::CRy(t) => StandardGate::Ry(t.clone()),
+                StandardGate::CRz(t) => StandardGate::Rz(t.clone()),
+                _ => StandardGate::Swap,
+            };
+            control_standard(&base, &qubits(&targets[..1]), &targets[1..], out)?;
+        }
+        StandardGate::Swap => {
+            let (a, b) = (targets[0], targets[1]);
+            push(out, StandardGate::CX, &[b, a]);
+            control_standard(&StandardGate::X, &qubits(&[a]), &[b], out)?;
+            push(out, StandardGate::CX, &[b, a]);
+        }
+        StandardGate::RZZ(t) => {
+            let (a, b) = (targets[0], targets[1]);
+            push(out, StandardGate::CX, &[a, b]);
+            control_standard(&StandardGate::Rz(t.clone()), controls, &[b], out)?;
+            push(out, StandardGate::CX, &[a, b]);
+        }
+        StandardGate::RXX(t) => {
+            for q in targets {
+                push(out, StandardGate::H, &[*q]);
+            }
+            control_standard(&StandardGate::RZZ(t.clone()), controls, targets, out)?;
+            for q in targets {
+                push(out, StandardGate::H, &[*q]);
+            }
+        }
+        StandardGate::RYY(t) => {
+            for q in targets {
+                push(out, StandardGate::Rx(constant(FRAC_PI_2)), &[*q]);
+            }
+            control_standard(&StandardGate::RZZ(t.clone()), controls, targets, out)?;
+            for q in targets {
+                push(out, StandardGate::Rx(constant(-FRAC_PI_2)), &[*q]);
+            }
+        }
+        StandardGate::ISwap => {
+            let angle = constant(-FRAC_PI_2);
+            control_standard(&StandardGate::RXX(angle.clone()), controls, targets, out)?;
+            control_standard(&StandardGate::RYY(angle), controls, targets, out)?;
+        }
+        StandardGate::ECR => {
+            // ECR = (X ⊗ I) · (I ⊗ RX(π/2)) · CRX(-π)
+            let (a, b) = (targets[0], targets[1]);
+            control_standard(&StandardGate::CRx(constant(-PI)), controls, targets, out)?;
+            control_standard(&StandardGate::Rx(constant(FRAC_PI_2)), controls, &[b], out)?;
+            control_standard(&StandardGate::X, controls, &[a], out)?;
+        }
+        // X · R(θ) · X = R(-θ) for R = RY, RZ, so the two half rotations
+        // cancel unless every control is set.
+        StandardGate::Ry(t) | StandardGate::Rz(t) => {
+            let rotation = |angle: ParameterExpression| match gate {
+                StandardGate::Ry(_) => StandardGate::Ry(angle),
+                _ => StandardGate::Rz(angle),
+            };
+            let half = (t.clone() / constant(2.0)).simplify();
+            let target = targets[0];
+            let mcx = with_controls(&StandardGate::X, controls.len() as u32)
+                .expect("X takes any number of controls");
+            push(out, rotation(half.clone()), &[target]);
+            push(out, mcx.clone(), &qubits(targets));
+            push(out, rotation((-half).simplify()), &[target]);
+            push(out, mcx, &qubits(targets));
+        }
+        StandardGate::Rx(t) => {
+            push(out, StandardGate::H, targets);
+            control_standard(&StandardGate::Rz(t.clone()), controls, targets, out)?;
+            push(out, StandardGate::H, targets);
+        }
+        _ => control_unitary(&gate.matrix()?, controls, targets[0], out)?,
+    }
+    Ok(())
+}
+
+/// Append the single-qubit unitary `matrix` on `target`, controlled on
+/// `controls`, to `out`.
+///
+/// Writing `U = e^{iα} RZ(β) RY(γ) RZ(δ)`, the target gets `A X B X C`
+/// with `ABC = I` and `AXBXC = e^{-iα} U`, where each X is controlled on
+/// all of `controls`, and the phase `e^{iα}` is applied to the controls.
+fn control_unitary(
+    matrix: &[Complex64],
+    controls: &[QubitId],
+    target: QubitId,
+    out: &mut Vec<Instruction>,
+) -> IrResult<()> {
+    if matrix.len() != 4 {
+        return Err(IrError::UnsupportedModifier(
+            "only single-qubit matrices can be controlled".into(),
+        ));
+    }
+    let (alpha, beta, gamma, delta) = zyz(matrix);
+    let rotate =
+        |out: &mut Vec<Instruction>, gate: fn(ParameterExpression) -> StandardGate, angle: f64| {
+            if angle.abs() > TOLERANCE {
+                out.push(Instruction::gate(
+                    gate(ParameterExpression::constant(angle)),
+                    [target],
+                ));
+            }
+        };
+    let mcx = with_controls(&StandardGate::X, controls.len() as u32)
+        .expect("X takes any number of controls");
+    let mcx_qubits: Vec<_> = controls.iter().copied().chain([target]).collect();
+
+    rotate(out, StandardGate::Rz, (delta - beta) / 2.0);
+    out.push(Instruction::gate(mcx.clone(), mcx_qubits.iter().copied()));
+    rotate(out, StandardGate::Rz, -(delta + beta) / 2.0);
+    rotate(out, StandardGate::Ry, -gamma / 2.0);
+    out.push(Instruction::gate(mcx, mcx_qubits.iter().copied()));
+    rotate(out, StandardGate::Ry, gamma / 2.0);
+    rotate(out, StandardGate::Rz, beta);
+
+    if alpha.abs() > TOLERANCE {
+        let (last, rest) = controls.split_last().expect("at least one control");
+        let phase = with_controls(
+            &StandardGate::P(ParameterExpression::constant(alpha)),
+            rest.len() as u32,
+        )
+        .expect("P takes any number of controls");
+        out.push(Instruction::gate(
+            phase,
+            rest.iter().copied().chain([*last]),
+        ));
+    }
+    Ok(())
+}
+
+/// ZYZ angles `(α, β, γ, δ)` with `U = e^{iα} RZ(β) RY(γ) RZ(δ)`.
+fn zyz(u: &[Complex64]) -> (f64, f64, f64, f64) {
+    let det = u[0] * u[3] - u[1] * u[2];
+    let alpha = det.arg() / 2.0;
+    let phase = Complex64::from_polar(1.0, -alpha);
+    let (v00, v10, v11) = (u[0] * phase, u[2] * phase, u[3] * phase);
+
+    // V = [[e^{-i(β+δ)/2} cos(γ/2), ·], [e^{i(β-δ)/2} sin(γ/2), e^{i(β+δ)/2} cos(γ/2)]]
+    let gamma = 2.0 * v10.norm().atan2(v00.norm());
+    let sum = if v11.norm() > TOLERANCE {
+        2.0 * v11.arg()
+    } else {
+        0.0
+    };
+    let difference = if v10.norm() > TOLERANCE {
+        2.0 * v10.arg()
+    } else {
+        0.0
+    };
+    let beta = f64::midpoint(sum, difference);
+    let delta = (sum - difference) / 2.0;
+    (alpha, beta, gamma, delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gate::{CustomGate, Gate, GateDefinition};
+    use crate::{Circuit, ClbitId};
+
+    /// Dense unitary of `gate` on its own qubits, first qubit most
+    /// significant, computed from the expansion.
+    fn expanded_matrix(gate: &ModifiedGate) -> Vec<Complex64> {
+        let n = gate.num_qubits();
+        let mut circuit = Circuit::with_size("expanded", n, 0);
+        let qubits: Vec<_> = (0..n).map(|j| QubitId(n - 1 - j)).collect();
+        for inst in gate.expand(&qubits).unwrap() {
+            circuit.dag_mut().apply(inst).unwrap();
+        }
+        circuit.to_matrix().unwrap()
+    }
+
+    fn assert_close(a: &[Complex64], b: &[Complex64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).norm() < 1e-9, "{a:?}\n!=\n{b:?}");
+        }
+    }
+
+    #[test]
+    fn test_names_and_flattening() {
+        let gate = Gate::standard(StandardGate::S).power(-1.0).controlled(2);
+        assert_eq!(gate.name(), "ctrl(2) @ inv @ s");
+        assert_eq!(gate.num_qubits(), 3);
+
+        let nested = gate.controlled(1).power(3.0);
+        let GateKind::Modified(modified) = &nested.kind else {
+            panic!("expected a modified gate");
+        };
+        assert_eq!(modified.num_controls(), 3);
+        assert_eq!(modified.power(), -3.0);
+        assert_eq!(modified.base(), &GateKind::Standard(StandardGate::S));
+
+        // Trivial modifiers unwrap.
+        let h = Gate::standard(StandardGate::H).controlled(0).power(1.0);
+        assert_eq!(h.kind, GateKind::Standard(StandardGate::H));
+    }
+
+    #[test]
+    fn test_to_standard() {
+        let standard = |gate: Gate| match gate.kind {
+            GateKind::Modified(m) => m.to_standard(),
+            GateKind::Standard(g) => Some(g),
+            GateKind::Custom(_) => None,
+        };
+        let x = Gate::standard(StandardGate::X);
+        assert_eq!(standard(x.clone().controlled(2)), Some(StandardGate::CCX));
+        assert_eq!(
+            standard(Gate::standard(StandardGate::CX).controlled(3)),
+            Some(StandardGate::MCX(4))
+        );
+        assert_eq!(standard(x.clone().power(3.0)), Some(StandardGate::X));
+        assert_eq!(standard(x.power(2.0)), None);
+        assert_eq!(
+            standard(Gate::standard(StandardGate::T).power(-1.0)),
+            Some(StandardGate::Tdg)
+        );
+        assert_eq!(
+            standard(Gate::standard(StandardGate::Rz(ParameterExpression::symbol("t"))).power(0.5)),
+            Some(StandardGate::Rz(
+                ParameterExpression::symbol("t") * ParameterExpression::constant(0.5)
+            ))
+        );
+        assert_eq!(
+            standard(Gate::standard(StandardGate::H).controlled(2)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expand_matches_matrix() {
+        let angle = || ParameterExpression::constant(0.37);
+        let bases = [
+            StandardGate::H,
+            StandardGate::Y,
+            StandardGate::SX,
+            StandardGate::U(angle(), 1.1.into(), (-0.4).into()),
+            StandardGate::Rx(angle()),
+            StandardGate::Ry(angle()),
+            StandardGate::Rz(angle()),
+            StandardGate::CH,
+            StandardGate::CRy(angle()),
+            StandardGate::Swap,
+            StandardGate::ISwap,
+            StandardGate::ECR,
+            StandardGate::RXX(angle()),
+            StandardGate::RYY(angle()),
+            StandardGate::RZZ(angle()),
+        ];
+        for base in bases {
+            for (controls, power) in [(1, 1.0), (2, 1.0), (2, -1.0), (1, 3.0), (0, -2.0)] {
+                let gate = ModifiedGate::new(GateKind::Standard(base.clone()), controls, power);
+                assert_close(&expanded_matrix(&gate), &gate.matrix().unwrap());
+            }
+        }
+
+        let sqrt_iswap = ModifiedGate::new(GateKind::Standard(StandardGate::ISwap), 1, 0.5);
+        assert_close(&expanded_matrix(&sqrt_iswap), &sqrt_iswap.matrix().unwrap());
+    }
+
+    #[test]
+    fn test_matrix() {
+        // ctrl @ h is CH; pow(2) @ sx is X.
+        let ch = ModifiedGate::new(GateKind::Standard(StandardGate::H), 1, 1.0);
+        assert_close(&ch.matrix().unwrap(), &StandardGate::CH.matrix().unwrap());
+        let x = ModifiedGate::new(GateKind::Standard(StandardGate::SX), 0, 2.0);
+        assert_close(&x.matrix().unwrap(), &StandardGate::X.matrix().unwrap());
+        let sqrt_x = ModifiedGate::new(GateKind::Standard(StandardGate::X), 0, 0.5);
+        assert!(matches!(
+            sqrt_x.matrix(),
+            Err(IrError::UnsupportedModifier(_))
+        ));
+    }
+
+    #[test]
+    fn test_expand_custom() {
+        let mut body = Circuit::with_size("zz", 2, 0);
+        body.cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .rz(ParameterExpression::symbol("t"), QubitId(1))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap();
+        let zz = CustomGate::new("zz", 2)
+            .with_params(vec![ParameterExpression::constant(0.8)])
+            .with_definition(GateDefinition::from_circuit(vec!["t".into()], &body).unwrap())
+            .unwrap();
+
+        let gate = ModifiedGate::new(GateKind::Custom(zz), 1, -2.0);
+        assert_eq!(gate.name(), "ctrl @ pow(-2) @ zz");
+        let expected = ModifiedGate::new(
+            GateKind::Standard(StandardGate::RZZ(ParameterExpression::constant(0.8))),
+            1,
+            -2.0,
+        );
+        assert_close(&expanded_matrix(&gate), &expected.matrix().unwrap());
+
+        // A single-qubit custom gate known only by its matrix.
+        let matrix = StandardGate::SX.matrix().unwrap();
+        let sx = CustomGate::new("my_sx", 1).with_matrix(matrix);
+        let gate = ModifiedGate::new(GateKind::Custom(sx), 2, 1.0);
+        let expected = ModifiedGate::new(GateKind::Standard(StandardGate::SX), 2, 1.0);
+        assert_close(&expanded_matrix(&gate), &expected.matrix().unwrap());
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let gate = ModifiedGate::new(GateKind::Standard(StandardGate::H), 1, 0.5);
+        assert!(matches!(
+            gate.expand(&[QubitId(0), QubitId(1)]),
+            Err(IrError::UnsupportedModifier(_))
+        ));
+        assert!(matches!(
+            gate.expand(&[QubitId(0)]),
+            Err(IrError::QubitCountMismatch { .. })
+        ));
+        let opaque = ModifiedGate::new(GateKind::Custom(CustomGate::new("opaque", 2)), 1, 1.0);
+        assert!(
+            opaque
+                .expand(&[QubitId(0), QubitId(1), QubitId(2)])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let gate = Gate::standard(StandardGate::Ry(0.3.into()))
+            .power(0.5)
+            .controlled(2)
+            .with_condition(crate::ClassicalCondition::on_clbit(ClbitId(0), 1));
+        let json = serde_json::to_string(&gate).unwrap();
+        assert!(!json.contains("\"name\""), "{json}");
+        let back: Gate = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, gate);
+        assert_eq!(back.name(), "ctrl(2) @ pow(0.5) @ ry");
+    }
+}
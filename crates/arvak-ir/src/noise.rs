@@ -229,6 +229,13 @@ pub struct NoiseProfile {
     #[serde(default)]
     pub readout_errors: Option<Vec<f64>>,
 
+    /// Two-qubit gate error per coupler, as `(qubit_a, qubit_b, error)`.
+    ///
+    /// Calibration data reports these per physical qubit pair; the
+    /// device-wide average stays in `gate_errors`.
+    #[serde(default)]
+    pub coupler_errors: Option<Vec<(u32, u32, f64)>>,
+
     /// Opaque backend-specific noise fingerprint.
     ///
     /// Deliberately untyped — ion traps, superconducting qubits, and
@@ -266,12 +273,23 @@ impl NoiseProfile {
             .copied()
     }
 
+    /// Get the two-qubit gate error on the coupler between two qubits, in
+    /// either order, if known.
+    pub fn coupler_error(&self, a: u32, b: u32) -> Option<f64> {
+        self.coupler_errors
+            .as_ref()?
+            .iter()
+            .find(|&&(p, q, _)| (p, q) == (a, b) || (p, q) == (b, a))
+            .map(|&(_, _, error)| error)
+    }
+
     /// Check if this profile has any noise data at all.
     pub fn is_empty(&self) -> bool {
         self.gate_errors.is_empty()
             && self.t1.is_none()
             && self.t2.is_none()
             && self.readout_errors.is_none()
+            && self.coupler_errors.is_none()
             && self.fingerprint.is_none()
     }
 }
@@ -326,6 +344,11 @@ mod tests {
         assert_eq!(profile.qubit_t2(2), Some(35.0));
         assert_eq!(profile.qubit_readout_error(0), Some(0.02));
         assert_eq!(profile.qubit_t1(99), None);
+
+        profile.coupler_errors = Some(vec![(0, 1, 0.008), (1, 2, 0.02)]);
+        assert_eq!(profile.coupler_error(1, 0), Some(0.008));
+        assert_eq!(profile.coupler_error(2, 1), Some(0.02));
+        assert_eq!(profile.coupler_error(0, 2), None);
     }
 
     #[test]
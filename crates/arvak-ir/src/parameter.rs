@@ -1,10 +1,12 @@
 //! Parameter expressions for parameterized circuits.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::f64::consts::PI;
 use std::fmt;
 
+use crate::error::{IrError, IrResult};
+
 /// A symbolic or concrete parameter expression.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ParameterExpression {
@@ -102,27 +104,46 @@ impl ParameterExpression {
     /// Bind a symbol to a value, returning a new expression.
     #[must_use]
     pub fn bind(&self, name: &str, value: f64) -> Self {
+        self.replace_symbols(&|n| (n == name).then_some(ParameterExpression::Constant(value)))
+    }
+
+    /// Bind every symbol that has a value in `values`, returning a new
+    /// expression. Symbols without a value are left in place.
+    #[must_use]
+    pub fn bind_all(&self, values: &HashMap<String, f64>) -> Self {
+        self.replace_symbols(&|n| values.get(n).copied().map(ParameterExpression::Constant))
+    }
+
+    /// Replace every symbol that has an entry in `exprs` by that expression,
+    /// returning a new expression. Symbols without an entry are left in
+    /// place.
+    #[must_use]
+    pub fn substitute(&self, exprs: &HashMap<String, ParameterExpression>) -> Self {
+        self.replace_symbols(&|n| exprs.get(n).cloned())
+    }
+
+    fn replace_symbols(&self, lookup: &impl Fn(&str) -> Option<ParameterExpression>) -> Self {
         match self {
-            ParameterExpression::Symbol(n) if n == name => ParameterExpression::Constant(value),
-            ParameterExpression::Constant(_)
-            | ParameterExpression::Pi
-            | ParameterExpression::Symbol(_) => self.clone(),
-            ParameterExpression::Neg(e) => ParameterExpression::Neg(Box::new(e.bind(name, value))),
+            ParameterExpression::Symbol(n) => lookup(n).unwrap_or_else(|| self.clone()),
+            ParameterExpression::Constant(_) | ParameterExpression::Pi => self.clone(),
+            ParameterExpression::Neg(e) => {
+                ParameterExpression::Neg(Box::new(e.replace_symbols(lookup)))
+            }
             ParameterExpression::Add(a, b) => ParameterExpression::Add(
-                Box::new(a.bind(name, value)),
-                Box::new(b.bind(name, value)),
+                Box::new(a.replace_symbols(lookup)),
+                Box::new(b.replace_symbols(lookup)),
             ),
             ParameterExpression::Sub(a, b) => ParameterExpression::Sub(
-                Box::new(a.bind(name, value)),
-                Box::new(b.bind(name, value)),
+                Box::new(a.replace_symbols(lookup)),
+                Box::new(b.replace_symbols(lookup)),
             ),
             ParameterExpression::Mul(a, b) => ParameterExpression::Mul(
-                Box::new(a.bind(name, value)),
-                Box::new(b.bind(name, value)),
+                Box::new(a.replace_symbols(lookup)),
+                Box::new(b.replace_symbols(lookup)),
             ),
             ParameterExpression::Div(a, b) => ParameterExpression::Div(
-                Box::new(a.bind(name, value)),
-                Box::new(b.bind(name, value)),
+                Box::new(a.replace_symbols(lookup)),
+                Box::new(b.replace_symbols(lookup)),
             ),
         }
     }
@@ -246,6 +267,90 @@ impl std::ops::Neg for ParameterExpression {
     }
 }
 
+/// A named, fixed-length vector of symbolic parameters.
+///
+/// Element `i` of a vector named `theta` is the symbol `theta_i`, which is
+/// a valid OpenQASM identifier, so vector parameters survive a QASM round
+/// trip. Variational circuits use one vector per layer or ansatz and bind
+/// it in one call:
+///
+/// ```
+/// use arvak_ir::{Circuit, ParameterVector, QubitId};
+///
+/// let theta = ParameterVector::new("theta", 2);
+/// let mut circuit = Circuit::with_size("ansatz", 2, 0);
+/// circuit.ry(theta.get(0).unwrap(), QubitId(0))?;
+/// circuit.ry(theta.get(1).unwrap(), QubitId(1))?;
+///
+/// let bound = circuit.bind_parameters(&theta.bindings(&[0.1, 0.2])?)?;
+/// assert!(bound.parameters().is_empty());
+/// # Ok::<(), arvak_ir::IrError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterVector {
+    name: String,
+    len: usize,
+}
+
+impl ParameterVector {
+    /// Create a vector of `len` parameters named `name`.
+    pub fn new(name: impl Into<String>, len: usize) -> Self {
+        Self {
+            name: name.into(),
+            len,
+        }
+    }
+
+    /// The vector's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of parameters in the vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the vector has no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Symbol name of the `index`-th parameter.
+    pub fn symbol_name(&self, index: usize) -> Option<String> {
+        (index < self.len).then(|| format!("{}_{index}", self.name))
+    }
+
+    /// The `index`-th parameter as a symbolic expression.
+    pub fn get(&self, index: usize) -> Option<ParameterExpression> {
+        self.symbol_name(index).map(ParameterExpression::Symbol)
+    }
+
+    /// Iterate over the parameters in index order.
+    pub fn iter(&self) -> impl Iterator<Item = ParameterExpression> + '_ {
+        (0..self.len).filter_map(|i| self.get(i))
+    }
+
+    /// Map each parameter to the value at the same index, for use with
+    /// [`Circuit::bind_parameters`](crate::Circuit::bind_parameters).
+    ///
+    /// Fails with [`IrError::ParameterCountMismatch`] if `values` does not
+    /// have one entry per parameter.
+    pub fn bindings(&self, values: &[f64]) -> IrResult<HashMap<String, f64>> {
+        if values.len() != self.len {
+            return Err(IrError::ParameterCountMismatch {
+                expected: self.len,
+                got: values.len(),
+            });
+        }
+        Ok(values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &v)| self.symbol_name(i).map(|name| (name, v)))
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,6 +385,38 @@ mod tests {
         assert!((bound.as_f64().unwrap() - PI / 2.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_bind_all() {
+        let expr = ParameterExpression::symbol("a") * ParameterExpression::symbol("b")
+            + ParameterExpression::symbol("c");
+        let values = HashMap::from([("a".to_string(), 2.0), ("b".to_string(), 3.0)]);
+
+        let partial = expr.bind_all(&values);
+        assert_eq!(partial.symbols(), HashSet::from(["c".to_string()]));
+
+        let full = partial.bind("c", 1.0);
+        assert_eq!(full.as_f64(), Some(7.0));
+    }
+
+    #[test]
+    fn test_parameter_vector() {
+        let theta = ParameterVector::new("theta", 3);
+        assert_eq!(theta.len(), 3);
+        assert_eq!(theta.get(2), Some(ParameterExpression::symbol("theta_2")));
+        assert_eq!(theta.get(3), None);
+        assert_eq!(theta.iter().count(), 3);
+
+        let bindings = theta.bindings(&[0.1, 0.2, 0.3]).unwrap();
+        assert_eq!(bindings["theta_1"], 0.2);
+        assert!(matches!(
+            theta.bindings(&[0.1]),
+            Err(IrError::ParameterCountMismatch {
+                expected: 3,
+                got: 1
+            })
+        ));
+    }
+
     #[test]
     fn test_arithmetic() {
         let a = ParameterExpression::constant(2.0);
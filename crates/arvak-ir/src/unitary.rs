@@ -0,0 +1,749 @@
+//! Dense unitary matrices of gates and small circuits.
+//!
+//! Matrices are `Vec<Complex64>` in row-major order: entry `(row, col)` of
+//! a `dim × dim` matrix is at `row * dim + col`.
+//!
+//! Two qubit orderings are used, each matching the rest of Arvak:
+//!
+//! - A gate's own matrix ([`StandardGate::matrix`], [`Gate::matrix`],
+//!   [`CustomGate::matrix`](crate::CustomGate::matrix) and
+//!   [`ModifiedGate::matrix`](crate::ModifiedGate::matrix)) lists its first
+//!   qubit as the most significant bit, so `CX` is the textbook
+//!   `[[1,0,0,0],[0,1,0,0],[0,0,0,1],[0,0,1,0]]`.
+//! - A circuit's matrix ([`Circuit::to_matrix`]) maps qubit `i` to bit `i`
+//!   of the basis-state index (qubit 0 least significant), the ordering of
+//!   statevector simulators and of HAL bitstrings (qubit 0 rightmost).
+//!
+//! Gates without parameters are defined once, in the exact table behind
+//! [`StandardGate::exact_matrix`]; every `f64` matrix of them is derived
+//! from it. Dense matrices grow as `4^n`, so circuits are limited to
+//! [`MAX_UNITARY_QUBITS`] qubits.
+//!
+//! ```rust
+//! use arvak_ir::{Circuit, QubitId};
+//!
+//! let mut bell = Circuit::with_size("bell", 2, 0);
+//! bell.h(QubitId(0)).unwrap().cx(QubitId(0), QubitId(1)).unwrap();
+//! let u = bell.to_matrix().unwrap();
+//! assert_eq!(u.len(), 16);
+//! // Column 0 is the Bell state (|00⟩ + |11⟩)/√2.
+//! assert!((u[0].re - u[3 * 4].re).abs() < 1e-12);
+//! ```
+
+use num_complex::Complex64;
+
+use crate::circuit::Circuit;
+use crate::dag::CircuitDag;
+use crate::error::{IrError, IrResult};
+use crate::exact::ExactComplex;
+use crate::gate::{CustomGate, Gate, GateKind, StandardGate};
+use crate::instruction::{Instruction, InstructionKind};
+use crate::modifier::{ModifiedGate, integer_power};
+use crate::parameter::ParameterExpression;
+use crate::qubit::QubitId;
+
+/// Largest circuit, in qubits, that [`Circuit::to_matrix`] builds.
+pub const MAX_UNITARY_QUBITS: usize = 10;
+
+const ZERO: Complex64 = Complex64::new(0.0, 0.0);
+const ONE: Complex64 = Complex64::new(1.0, 0.0);
+
+/// Matrix entry types: `f64` complex numbers and exact ones.
+trait Entry: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+impl Entry for Complex64 {
+    const ZERO: Self = ZERO;
+    const ONE: Self = ONE;
+}
+
+impl Entry for ExactComplex {
+    const ZERO: Self = ExactComplex::ZERO;
+    const ONE: Self = ExactComplex::ONE;
+}
+
+// Exact entries of the fixed gates.
+const E0: ExactComplex = ExactComplex::ZERO;
+const E1: ExactComplex = ExactComplex::ONE;
+const NEG_E1: ExactComplex = ExactComplex::new([-1, 0], [0, 0], 0);
+const EI: ExactComplex = ExactComplex::I;
+const NEG_EI: ExactComplex = ExactComplex::new([0, 0], [-1, 0], 0);
+/// `1/√2`
+const R: ExactComplex = ExactComplex::FRAC_1_SQRT_2;
+const NEG_R: ExactComplex = ExactComplex::new([0, -1], [0, 0], 1);
+/// `i/√2`
+const RI: ExactComplex = ExactComplex::new([0, 0], [0, 1], 1);
+const NEG_RI: ExactComplex = ExactComplex::new([0, 0], [0, -1], 1);
+/// `(1 + i)/2`
+const HALF_P: ExactComplex = ExactComplex::new([1, 0], [1, 0], 1);
+/// `(1 - i)/2`
+const HALF_M: ExactComplex = ExactComplex::new([1, 0], [-1, 0], 1);
+
+const EXACT_X: [ExactComplex; 4] = [E0, E1, E1, E0];
+const EXACT_Y: [ExactComplex; 4] = [E0, NEG_EI, EI, E0];
+const EXACT_Z: [ExactComplex; 4] = [E1, E0, E0, NEG_E1];
+const EXACT_H: [ExactComplex; 4] = [R, R, R, NEG_R];
+const EXACT_S: [ExactComplex; 4] = [E1, E0, E0, EI];
+const EXACT_SDG: [ExactComplex; 4] = [E1, E0, E0, NEG_EI];
+const EXACT_T: [ExactComplex; 4] = [E1, E0, E0, ExactComplex::root_of_unity(1)];
+const EXACT_TDG: [ExactComplex; 4] = [E1, E0, E0, ExactComplex::root_of_unity(-1)];
+const EXACT_SX: [ExactComplex; 4] = [HALF_P, HALF_M, HALF_M, HALF_P];
+const EXACT_SXDG: [ExactComplex; 4] = [HALF_M, HALF_P, HALF_P, HALF_M];
+#[rustfmt::skip]
+const EXACT_SWAP: [ExactComplex; 16] = [
+    E1, E0, E0, E0,
+    E0, E0, E1, E0,
+    E0, E1, E0, E0,
+    E0, E0, E0, E1,
+];
+#[rustfmt::skip]
+const EXACT_ISWAP: [ExactComplex; 16] = [
+    E1, E0, E0, E0,
+    E0, E0, EI, E0,
+    E0, EI, E0, E0,
+    E0, E0, E0, E1,
+];
+#[rustfmt::skip]
+const EXACT_ECR: [ExactComplex; 16] = [
+    E0, E0, R, RI,
+    E0, E0, RI, R,
+    R, NEG_RI, E0, E0,
+    NEG_RI, R, E0, E0,
+];
+
+fn real(v: f64) -> Complex64 {
+    Complex64::new(v, 0.0)
+}
+
+fn angle(p: &ParameterExpression) -> IrResult<f64> {
+    p.as_f64().ok_or_else(|| {
+        let mut symbols: Vec<_> = p.symbols().into_iter().collect();
+        symbols.sort();
+        IrError::UnboundParameter(symbols.into_iter().next().unwrap_or_else(|| p.to_string()))
+    })
+}
+
+/// `base` with `num_controls` controls prepended (as most significant bits).
+fn controlled<T: Entry>(base: &[T], num_controls: u32) -> Vec<T> {
+    let m = base.len().isqrt();
+    let dim = m << num_controls;
+    let offset = dim - m;
+    let mut out = identity(dim);
+    for r in 0..m {
+        for c in 0..m {
+            out[(offset + r) * dim + offset + c] = base[r * m + c];
+        }
+    }
+    out
+}
+
+fn identity<T: Entry>(dim: usize) -> Vec<T> {
+    let mut out = vec![T::ZERO; dim * dim];
+    for i in 0..dim {
+        out[i * dim + i] = T::ONE;
+    }
+    out
+}
+
+impl StandardGate {
+    /// The exact unitary of a gate without parameters, first qubit most
+    /// significant; `None` for parameterized gates.
+    ///
+    /// This table is the only definition of the fixed gates' matrices;
+    /// [`StandardGate::matrix`] rounds it to `f64`.
+    ///
+    /// ```rust
+    /// use arvak_ir::StandardGate;
+    ///
+    /// let t = StandardGate::T.exact_matrix().unwrap();
+    /// let s = StandardGate::S.exact_matrix().unwrap();
+    /// // T · T = S, exactly (both are diagonal).
+    /// assert_eq!(t[3] * t[3], s[3]);
+    /// assert!(StandardGate::Rz(0.5.into()).exact_matrix().is_none());
+    /// ```
+    pub fn exact_matrix(&self) -> Option<Vec<ExactComplex>> {
+        Some(match self {
+            StandardGate::I => identity(2),
+            StandardGate::X => EXACT_X.to_vec(),
+            StandardGate::Y => EXACT_Y.to_vec(),
+            StandardGate::Z => EXACT_Z.to_vec(),
+            StandardGate::H => EXACT_H.to_vec(),
+            StandardGate::S => EXACT_S.to_vec(),
+            StandardGate::Sdg => EXACT_SDG.to_vec(),
+            StandardGate::T => EXACT_T.to_vec(),
+            StandardGate::Tdg => EXACT_TDG.to_vec(),
+            StandardGate::SX => EXACT_SX.to_vec(),
+            StandardGate::SXdg => EXACT_SXDG.to_vec(),
+            StandardGate::CX => controlled(&EXACT_X, 1),
+            StandardGate::CY => controlled(&EXACT_Y, 1),
+            StandardGate::CZ => controlled(&EXACT_Z, 1),
+            StandardGate::CH => controlled(&EXACT_H, 1),
+            StandardGate::Swap => EXACT_SWAP.to_vec(),
+            StandardGate::ISwap => EXACT_ISWAP.to_vec(),
+            StandardGate::ECR => EXACT_ECR.to_vec(),
+            StandardGate::CCX => controlled(&EXACT_X, 2),
+            StandardGate::CSwap => controlled(&EXACT_SWAP, 1),
+            StandardGate::MCX(n) => controlled(&EXACT_X, *n),
+            StandardGate::MCZ(n) => controlled(&EXACT_Z, *n),
+            StandardGate::Rx(_)
+            | StandardGate::Ry(_)
+            | StandardGate::Rz(_)
+            | StandardGate::P(_)
+            | StandardGate::U(..)
+            | StandardGate::PRX(..)
+            | StandardGate::CRx(_)
+            | StandardGate::CRy(_)
+            | StandardGate::CRz(_)
+            | StandardGate::CP(_)
+            | StandardGate::RXX(_)
+            | StandardGate::RYY(_)
+            | StandardGate::RZZ(_)
+            | StandardGate::MCP(..) => return None,
+        })
+    }
+
+    /// The gate's unitary, first qubit most significant.
+    ///
+    /// Gates without parameters come from [`StandardGate::exact_matrix`].
+    /// Fails with [`IrError::UnboundParameter`] if a parameter is symbolic.
+    pub fn matrix(&self) -> IrResult<Vec<Complex64>> {
+        if let Some(exact) = self.exact_matrix() {
+            return Ok(exact.into_iter().map(Complex64::from).collect());
+        }
+        let rx = |t: f64| {
+            let (c, s) = (real((t / 2.0).cos()), Complex64::new(0.0, -(t / 2.0).sin()));
+            [c, s, s, c]
+        };
+        let ry = |t: f64| {
+            let (c, s) = (real((t / 2.0).cos()), real((t / 2.0).sin()));
+            [c, -s, s, c]
+        };
+        let rz = |t: f64| {
+            [
+                Complex64::from_polar(1.0, -t / 2.0),
+                ZERO,
+                ZERO,
+                Complex64::from_polar(1.0, t / 2.0),
+            ]
+        };
+        let phase = |l: f64| [ONE, ZERO, ZERO, Complex64::from_polar(1.0, l)];
+
+        Ok(match self {
+            StandardGate::Rx(t) => rx(angle(t)?).to_vec(),
+            StandardGate::Ry(t) => ry(angle(t)?).to_vec(),
+            StandardGate::Rz(t) => rz(angle(t)?).to_vec(),
+            StandardGate::P(l) => phase(angle(l)?).to_vec(),
+            StandardGate::U(t, p, l) => {
+                let (t, p, l) = (angle(t)?, angle(p)?, angle(l)?);
+                let (c, s) = ((t / 2.0).cos(), (t / 2.0).sin());
+                vec![
+                    real(c),
+                    -Complex64::from_polar(s, l),
+                    Complex64::from_polar(s, p),
+                    Complex64::from_polar(c, p + l),
+                ]
+            }
+            StandardGate::PRX(t, p) => {
+                // PRX(θ, φ) = RZ(φ) · RX(θ) · RZ(-φ)
+                let (t, p) = (angle(t)?, angle(p)?);
+                let (c, s) = (real((t / 2.0).cos()), Complex64::new(0.0, -(t / 2.0).sin()));
+                vec![
+                    c,
+                    s * Complex64::from_polar(1.0, -p),
+                    s * Complex64::from_polar(1.0, p),
+                    c,
+                ]
+            }
+            StandardGate::CRx(t) => controlled(&rx(angle(t)?), 1),
+            StandardGate::CRy(t) => controlled(&ry(angle(t)?), 1),
+            StandardGate::CRz(t) => controlled(&rz(angle(t)?), 1),
+            StandardGate::CP(l) => controlled(&phase(angle(l)?), 1),
+            StandardGate::RXX(t) => {
+                let t = angle(t)?;
+                let (c, s) = (real((t / 2.0).cos()), Complex64::new(0.0, -(t / 2.0).sin()));
+                vec![
+                    c, ZERO, ZERO, s, ZERO, c, s, ZERO, ZERO, s, c, ZERO, s, ZERO, ZERO, c,
+                ]
+            }
+            StandardGate::RYY(t) => {
+                let t = angle(t)?;
+                let (c, s) = (real((t / 2.0).cos()), Complex64::new(0.0, (t / 2.0).sin()));
+                vec![
+                    c, ZERO, ZERO, s, ZERO, c, -s, ZERO, ZERO, -s, c, ZERO, s, ZERO, ZERO, c,
+                ]
+            }
+            StandardGate::RZZ(t) => {
+                let t = angle(t)?;
+                let (n, p) = (
+                    Complex64::from_polar(1.0, -t / 2.0),
+                    Complex64::from_polar(1.0, t / 2.0),
+                );
+                let mut m = vec![ZERO; 16];
+                for (i, d) in [n, p, p, n].into_iter().enumerate() {
+                    m[i * 4 + i] = d;
+                }
+                m
+            }
+            StandardGate::MCP(n, l) => controlled(&phase(angle(l)?), *n),
+            _ => unreachable!("gates without parameters have exact matrices"),
+        })
+    }
+}
+
+impl Gate {
+    /// The gate's unitary, first qubit most significant.
+    ///
+    /// Custom gates use their matrix, or else their definition. Fails for
+    /// classically conditioned gates, which are not unitary.
+    pub fn matrix(&self) -> IrResult<Vec<Complex64>> {
+        if self.condition.is_some() {
+            return Err(IrError::NotUnitary(format!(
+                "'{}' is classically conditioned",
+                self.name()
+            )));
+        }
+        match &self.kind {
+            GateKind::Standard(gate) => gate.matrix(),
+            GateKind::Custom(custom) => custom.matrix(),
+            GateKind::Modified(modified) => modified.matrix(),
+        }
+    }
+}
+
+impl CustomGate {
+    /// The gate's unitary, first qubit most significant: the attached
+    /// matrix, or else the unitary of its definition.
+    pub fn matrix(&self) -> IrResult<Vec<Complex64>> {
+        if let Some(matrix) = &self.matrix {
+            return Ok(matrix.clone());
+        }
+        if self.definition.is_none() {
+            return Err(IrError::NotUnitary(format!(
+                "custom gate '{}' has neither a matrix nor a definition",
+                self.name
+            )));
+        }
+        local_matrix(self.num_qubits, |qubits| self.expand(qubits))
+    }
+}
+
+impl ModifiedGate {
+    /// The gate's unitary, controls first and most significant.
+    ///
+    /// Integer powers are taken of the base gate's matrix; non-integer
+    /// powers, which only rotation and phase gates have, are built from the
+    /// scaled rotations.
+    pub fn matrix(&self) -> IrResult<Vec<Complex64>> {
+        if let Some(gate) = self.to_standard() {
+            return gate.matrix();
+        }
+        let powered = match integer_power(self.power()) {
+            Some(power) => {
+                let base = match self.base() {
+                    GateKind::Standard(gate) => gate.matrix()?,
+                    GateKind::Custom(custom) => custom.matrix()?,
+                    GateKind::Modified(modified) => modified.matrix()?,
+                };
+                matrix_power(&base, power)
+            }
+            None => {
+                let uncontrolled = ModifiedGate::new(self.base().clone(), 0, self.power());
+                local_matrix(uncontrolled.num_qubits(), |qubits| {
+                    uncontrolled.expand(qubits)
+                })?
+            }
+        };
+        Ok(controlled(&powered, self.num_controls()))
+    }
+}
+
+/// Unitary of the instructions `body` returns for `n` local qubits, the
+/// first qubit most significant, i.e. local qubit `j` on bit `n - 1 - j`.
+fn local_matrix(
+    n: u32,
+    body: impl FnOnce(&[QubitId]) -> IrResult<Vec<Instruction>>,
+) -> IrResult<Vec<Complex64>> {
+    let qubits: Vec<_> = (0..n).map(|j| QubitId(n - 1 - j)).collect();
+    let mut u = identity(1 << n);
+    for inst in &body(&qubits)? {
+        apply_instruction(&mut u, n as usize, inst)?;
+    }
+    Ok(u)
+}
+
+/// `m` raised to an integer power; negative powers use the adjoint.
+fn matrix_power(m: &[Complex64], power: i64) -> Vec<Complex64> {
+    let dim = m.len().isqrt();
+    let mut base = if power < 0 {
+        let mut adjoint = vec![ZERO; m.len()];
+        for r in 0..dim {
+            for c in 0..dim {
+                adjoint[c * dim + r] = m[r * dim + c].conj();
+            }
+        }
+        adjoint
+    } else {
+        m.to_vec()
+    };
+    let mut result = identity(dim);
+    let mut k = power.unsigned_abs();
+    while k > 0 {
+        if k & 1 == 1 {
+            result = matmul(&result, &base, dim);
+        }
+        base = matmul(&base, &base, dim);
+        k >>= 1;
+    }
+    result
+}
+
+fn matmul(a: &[Complex64], b: &[Complex64], dim: usize) -> Vec<Complex64> {
+    let mut out = vec![ZERO; dim * dim];
+    for r in 0..dim {
+        for k in 0..dim {
+            let a_rk = a[r * dim + k];
+            for c in 0..dim {
+                out[r * dim + c] += a_rk * b[k * dim + c];
+            }
+        }
+    }
+    out
+}
+
+impl CircuitDag {
+    /// The circuit's unitary, qubit 0 least significant.
+    ///
+    /// Includes the global phase. Barriers, delays, shuttles and noise
+    /// annotations are ignored; measurements, resets, conditioned gates and
+    /// control flow are not unitary and make this fail, as do circuits with
+    /// more than [`MAX_UNITARY_QUBITS`] qubits.
+    pub fn to_matrix(&self) -> IrResult<Vec<Complex64>> {
+        let n = self.num_qubits();
+        if n > MAX_UNITARY_QUBITS {
+            return Err(IrError::NotUnitary(format!(
+                "{n} qubits exceeds the limit of {MAX_UNITARY_QUBITS} for dense unitaries"
+            )));
+        }
+        let mut u = identity(1 << n);
+        for (_, inst) in self.topological_ops() {
+            apply_instruction(&mut u, n, inst)?;
+        }
+        let phase = self.global_phase();
+        if phase != 0.0 {
+            let factor = Complex64::from_polar(1.0, phase);
+            for v in &mut u {
+                *v *= factor;
+            }
+        }
+        Ok(u)
+    }
+}
+
+impl Circuit {
+    /// The circuit's dense unitary, row-major, qubit 0 least significant.
+    ///
+    /// See [`CircuitDag::to_matrix`].
+    pub fn to_matrix(&self) -> IrResult<Vec<Complex64>> {
+        self.dag().to_matrix()
+    }
+}
+
+/// Left-multiply `u` (on `n` qubits) by the unitary of `inst`.
+fn apply_instruction(u: &mut [Complex64], n: usize, inst: &Instruction) -> IrResult<()> {
+    match &inst.kind {
+        InstructionKind::Gate(gate) => {
+            let matrix = gate.matrix()?;
+            apply_matrix(u, n, &matrix, inst)
+        }
+        InstructionKind::Barrier
+        | InstructionKind::Delay { .. }
+        | InstructionKind::Shuttle { .. }
+        | InstructionKind::NoiseChannel { .. } => Ok(()),
+        InstructionKind::Measure
+        | InstructionKind::Reset
+        | InstructionKind::IfElse { .. }
+        | InstructionKind::WhileLoop { .. } => Err(IrError::NotUnitary(format!(
+            "'{}' is not unitary",
+            inst.name()
+        ))),
+    }
+}
+
+/// Left-multiply `u` by `matrix` acting on `inst.qubits` (first qubit most
+/// significant in `matrix`).
+fn apply_matrix(
+    u: &mut [Complex64],
+    n: usize,
+    matrix: &[Complex64],
+    inst: &Instruction,
+) -> IrResult<()> {
+    let k = inst.qubits.len();
+    let m = 1usize << k;
+    if matrix.len() != m * m {
+        return Err(IrError::NotUnitary(format!(
+            "'{}' has a {}-element matrix for {k} qubits",
+            inst.name(),
+            matrix.len()
+        )));
+    }
+    let dim = 1usize << n;
+    // Global bit of each local index bit: local bit k-1-j is qubit j.
+    let masks: Vec<usize> = inst.qubits.iter().map(|q| 1usize << q.0).collect();
+    let target_mask = masks.iter().fold(0, |acc, m| acc | m);
+    let offsets: Vec<usize> = (0..m)
+        .map(|local| {
+            (0..k)
+                .filter(|j| local & (1 << (k - 1 - j)) != 0)
+                .fold(0, |acc, j| acc | masks[j])
+        })
+        .collect();
+
+    let mut column = vec![ZERO; m];
+    for base in (0..dim).filter(|i| i & target_mask == 0) {
+        for col in 0..dim {
+            for (local, offset) in offsets.iter().enumerate() {
+                column[local] = u[(base | offset) * dim + col];
+            }
+            for (row, offset) in offsets.iter().enumerate() {
+                u[(base | offset) * dim + col] = matrix[row * m..(row + 1) * m]
+                    .iter()
+                    .zip(&column)
+                    .map(|(a, b)| a * b)
+                    .sum();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gate::GateDefinition;
+
+    fn assert_close(a: &[Complex64], b: &[Complex64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).norm() < 1e-10, "{a:?} != {b:?}");
+        }
+    }
+
+    fn is_unitary(u: &[Complex64]) -> bool {
+        let dim = (u.len() as f64).sqrt() as usize;
+        (0..dim).all(|r| {
+            (0..dim).all(|c| {
+                let dot: Complex64 = (0..dim)
+                    .map(|k| u[k * dim + r].conj() * u[k * dim + c])
+                    .sum();
+                let expected = if r == c { ONE } else { ZERO };
+                (dot - expected).norm() < 1e-10
+            })
+        })
+    }
+
+    #[test]
+    fn test_standard_gates_are_unitary() {
+        let t = ParameterExpression::constant(0.7);
+        let gates = [
+            StandardGate::H,
+            StandardGate::SX,
+            StandardGate::U(t.clone(), t.clone(), t.clone()),
+            StandardGate::PRX(t.clone(), t.clone()),
+            StandardGate::ISwap,
+            StandardGate::RYY(t.clone()),
+            StandardGate::ECR,
+            StandardGate::CSwap,
+            StandardGate::MCP(3, t),
+        ];
+        for gate in gates {
+            let m = gate.matrix().unwrap();
+            assert_eq!(m.len(), 1 << (2 * gate.num_qubits()), "{}", gate.name());
+            assert!(is_unitary(&m), "{} is not unitary", gate.name());
+        }
+        assert!(matches!(
+            StandardGate::Rz(ParameterExpression::symbol("theta")).matrix(),
+            Err(IrError::UnboundParameter(name)) if name == "theta"
+        ));
+    }
+
+    /// Every gate without parameters, with small multi-control counts.
+    fn fixed_gates() -> Vec<StandardGate> {
+        let mut gates = vec![
+            StandardGate::I,
+            StandardGate::X,
+            StandardGate::Y,
+            StandardGate::Z,
+            StandardGate::H,
+            StandardGate::S,
+            StandardGate::Sdg,
+            StandardGate::T,
+            StandardGate::Tdg,
+            StandardGate::SX,
+            StandardGate::SXdg,
+            StandardGate::CX,
+            StandardGate::CY,
+            StandardGate::CZ,
+            StandardGate::CH,
+            StandardGate::Swap,
+            StandardGate::ISwap,
+            StandardGate::ECR,
+            StandardGate::CCX,
+            StandardGate::CSwap,
+        ];
+        for n in 1..=3 {
+            gates.extend([StandardGate::MCX(n), StandardGate::MCZ(n)]);
+        }
+        gates
+    }
+
+    #[test]
+    fn test_fixed_gates_are_exactly_unitary() {
+        for gate in fixed_gates() {
+            let exact = gate.exact_matrix().unwrap();
+            assert_eq!(exact.len(), 1 << (2 * gate.num_qubits()), "{}", gate.name());
+            assert!(
+                crate::exact::is_unitary(&exact),
+                "{} is not unitary",
+                gate.name()
+            );
+            let rounded: Vec<Complex64> = exact.into_iter().map(Complex64::from).collect();
+            assert_eq!(gate.matrix().unwrap(), rounded, "{}", gate.name());
+        }
+    }
+
+    #[test]
+    fn test_exact_identities() {
+        let exact = |gate: StandardGate| gate.exact_matrix().unwrap();
+        let mul = |a: &[ExactComplex], b: &[ExactComplex]| {
+            let dim = a.len().isqrt();
+            let mut out = vec![ExactComplex::ZERO; a.len()];
+            for r in 0..dim {
+                for c in 0..dim {
+                    out[r * dim + c] = (0..dim).fold(ExactComplex::ZERO, |acc, k| {
+                        acc + a[r * dim + k] * b[k * dim + c]
+                    });
+                }
+            }
+            out
+        };
+        let sq = |gate: StandardGate| mul(&exact(gate.clone()), &exact(gate));
+
+        assert_eq!(sq(StandardGate::H), exact(StandardGate::I));
+        assert_eq!(sq(StandardGate::T), exact(StandardGate::S));
+        assert_eq!(sq(StandardGate::S), exact(StandardGate::Z));
+        assert_eq!(sq(StandardGate::SX), exact(StandardGate::X));
+        assert_eq!(
+            mul(&exact(StandardGate::SX), &exact(StandardGate::SXdg)),
+            exact(StandardGate::I)
+        );
+        assert_eq!(sq(StandardGate::ECR), identity::<ExactComplex>(4));
+        assert_eq!(exact(StandardGate::MCX(2)), exact(StandardGate::CCX),);
+    }
+
+    #[test]
+    fn test_parametric_gates_match_fixed_gates() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+        let fixed = |gate: StandardGate| gate.matrix().unwrap();
+        let c = |v: f64| ParameterExpression::constant(v);
+        assert_close(
+            &StandardGate::P(c(FRAC_PI_4)).matrix().unwrap(),
+            &fixed(StandardGate::T),
+        );
+        assert_close(
+            &StandardGate::P(c(FRAC_PI_2)).matrix().unwrap(),
+            &fixed(StandardGate::S),
+        );
+        assert_close(
+            &StandardGate::CP(c(PI)).matrix().unwrap(),
+            &fixed(StandardGate::CZ),
+        );
+        assert_close(
+            &StandardGate::MCP(2, c(PI)).matrix().unwrap(),
+            &fixed(StandardGate::MCZ(2)),
+        );
+        assert_close(
+            &StandardGate::U(c(FRAC_PI_2), c(0.0), c(PI))
+                .matrix()
+                .unwrap(),
+            &fixed(StandardGate::H),
+        );
+    }
+
+    #[test]
+    fn test_qubit_ordering() {
+        // CX with control q0: |q1 q0⟩ = |01⟩ (index 1) maps to |11⟩ (index 3).
+        let mut circuit = Circuit::with_size("cx", 2, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        let u = circuit.to_matrix().unwrap();
+        assert_eq!(u[3 * 4 + 1], ONE);
+        assert_eq!(u[4 + 1], ZERO);
+        assert_eq!(u[0], ONE);
+
+        // The same gate with the control on q1 is the textbook matrix.
+        let mut flipped = Circuit::with_size("cx", 2, 0);
+        flipped.cx(QubitId(1), QubitId(0)).unwrap();
+        assert_close(
+            &flipped.to_matrix().unwrap(),
+            &StandardGate::CX.matrix().unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_circuit_identities() {
+        // H Z H = X, including the global phase.
+        let mut hzh = Circuit::with_size("hzh", 1, 0);
+        hzh.h(QubitId(0)).unwrap();
+        hzh.z(QubitId(0)).unwrap();
+        hzh.h(QubitId(0)).unwrap();
+        assert_close(
+            &hzh.to_matrix().unwrap(),
+            &StandardGate::X.matrix().unwrap(),
+        );
+
+        // Three CXs make a SWAP.
+        let mut swap = Circuit::with_size("swap", 2, 0);
+        swap.cx(QubitId(0), QubitId(1)).unwrap();
+        swap.cx(QubitId(1), QubitId(0)).unwrap();
+        swap.cx(QubitId(0), QubitId(1)).unwrap();
+        assert_close(
+            &swap.to_matrix().unwrap(),
+            &StandardGate::Swap.matrix().unwrap(),
+        );
+
+        swap.dag_mut().set_global_phase(std::f64::consts::PI);
+        let negated: Vec<_> = StandardGate::Swap
+            .matrix()
+            .unwrap()
+            .into_iter()
+            .map(|v| -v)
+            .collect();
+        assert_close(&swap.to_matrix().unwrap(), &negated);
+    }
+
+    #[test]
+    fn test_custom_gate_definition() {
+        // A CX built from H · CZ · H on the target.
+        let mut body = Circuit::with_size("mycx", 2, 0);
+        body.h(QubitId(1)).unwrap();
+        body.cz(QubitId(0), QubitId(1)).unwrap();
+        body.h(QubitId(1)).unwrap();
+        let gate = CustomGate::new("mycx", 2)
+            .with_definition(GateDefinition::from_circuit(vec![], &body).unwrap())
+            .unwrap();
+        assert_close(&gate.matrix().unwrap(), &StandardGate::CX.matrix().unwrap());
+        assert!(CustomGate::new("opaque", 1).matrix().is_err());
+    }
+
+    #[test]
+    fn test_non_unitary_circuits() {
+        let bell = Circuit::bell().unwrap();
+        assert!(bell.to_matrix().is_err());
+        assert!(Circuit::with_size("wide", 11, 0).to_matrix().is_err());
+        assert_eq!(
+            Circuit::with_size("empty", 2, 0).to_matrix().unwrap(),
+            identity::<Complex64>(4)
+        );
+    }
+}
@@ -0,0 +1,492 @@
+//! Visiting and rewriting circuits through stable traits.
+//!
+//! [`CircuitDag`] is backed by a graph whose representation may change.
+//! Tools built on the IR — linters, exporters, research passes — should
+//! read circuits through a [`CircuitVisitor`] and rewrite them through a
+//! [`DagTransformer`] rather than walking [`CircuitDag::graph`]. Both get
+//! one callback per instruction kind, called in topological order. By
+//! default a visitor ignores every instruction and a transformer keeps it;
+//! both descend into the bodies of control-flow blocks.
+//!
+//! ```rust
+//! use arvak_ir::visit::{CircuitVisitor, DagTransformer, Rewrite};
+//! use arvak_ir::{Circuit, Gate, Instruction, IrResult, QubitId};
+//!
+//! /// Counts T gates.
+//! #[derive(Default)]
+//! struct TCount(usize);
+//!
+//! impl CircuitVisitor for TCount {
+//!     fn visit_gate(&mut self, gate: &Gate, _instruction: &Instruction) {
+//!         if gate.name() == "t" {
+//!             self.0 += 1;
+//!         }
+//!     }
+//! }
+//!
+//! /// Drops barriers.
+//! struct StripBarriers;
+//!
+//! impl DagTransformer for StripBarriers {
+//!     fn transform_barrier(&mut self, _instruction: &Instruction) -> IrResult<Rewrite> {
+//!         Ok(Rewrite::Replace(vec![]))
+//!     }
+//! }
+//!
+//! let mut circuit = Circuit::with_size("demo", 2, 0);
+//! circuit.t(QubitId(0)).unwrap();
+//! circuit.barrier([QubitId(0), QubitId(1)]).unwrap();
+//! circuit.t(QubitId(1)).unwrap();
+//!
+//! let mut count = TCount::default();
+//! circuit.visit(&mut count);
+//! assert_eq!(count.0, 2);
+//!
+//! assert_eq!(circuit.transform(&mut StripBarriers).unwrap(), 1);
+//! assert_eq!(circuit.dag().num_ops(), 2);
+//! ```
+
+use crate::circuit::Circuit;
+use crate::dag::CircuitDag;
+use crate::error::IrResult;
+use crate::gate::{ClassicalCondition, Gate};
+use crate::instruction::{Instruction, InstructionKind};
+use crate::noise::{NoiseModel, NoiseRole};
+use crate::qubit::{ClbitId, QubitId};
+
+/// Read-only callbacks for each kind of instruction.
+///
+/// Every callback receives the whole instruction as its last argument,
+/// for its operands and metadata.
+pub trait CircuitVisitor {
+    /// Called for every instruction; dispatches to the callback for its
+    /// kind. Override to see every instruction in one place.
+    fn visit_instruction(&mut self, instruction: &Instruction) {
+        walk_instruction(self, instruction);
+    }
+
+    /// A gate, possibly classically conditioned.
+    fn visit_gate(&mut self, _gate: &Gate, _instruction: &Instruction) {}
+
+    /// A measurement.
+    fn visit_measure(&mut self, _instruction: &Instruction) {}
+
+    /// A reset to |0⟩.
+    fn visit_reset(&mut self, _instruction: &Instruction) {}
+
+    /// A barrier.
+    fn visit_barrier(&mut self, _instruction: &Instruction) {}
+
+    /// A delay of `duration` device time units.
+    fn visit_delay(&mut self, _duration: u64, _instruction: &Instruction) {}
+
+    /// A shuttle between zones of a neutral-atom device.
+    fn visit_shuttle(&mut self, _from_zone: u32, _to_zone: u32, _instruction: &Instruction) {}
+
+    /// A noise channel.
+    fn visit_noise_channel(
+        &mut self,
+        _model: &NoiseModel,
+        _role: NoiseRole,
+        _instruction: &Instruction,
+    ) {
+    }
+
+    /// An if/else block. The default visits both bodies.
+    fn visit_if_else(
+        &mut self,
+        _condition: &ClassicalCondition,
+        then_body: &[Instruction],
+        else_body: &[Instruction],
+        _instruction: &Instruction,
+    ) {
+        for inst in then_body.iter().chain(else_body) {
+            self.visit_instruction(inst);
+        }
+    }
+
+    /// A bounded while loop. The default visits the body once.
+    fn visit_while_loop(
+        &mut self,
+        _condition: &ClassicalCondition,
+        body: &[Instruction],
+        _max_iterations: u32,
+        _instruction: &Instruction,
+    ) {
+        for inst in body {
+            self.visit_instruction(inst);
+        }
+    }
+}
+
+/// Call the callback of `visitor` for the kind of `instruction`.
+///
+/// This is what [`CircuitVisitor::visit_instruction`] does by default;
+/// call it from an override to keep the per-kind callbacks.
+pub fn walk_instruction<V: CircuitVisitor + ?Sized>(visitor: &mut V, instruction: &Instruction) {
+    match &instruction.kind {
+        InstructionKind::Gate(gate) => visitor.visit_gate(gate, instruction),
+        InstructionKind::Measure => visitor.visit_measure(instruction),
+        InstructionKind::Reset => visitor.visit_reset(instruction),
+        InstructionKind::Barrier => visitor.visit_barrier(instruction),
+        InstructionKind::Delay { duration } => visitor.visit_delay(*duration, instruction),
+        InstructionKind::Shuttle { from_zone, to_zone } => {
+            visitor.visit_shuttle(*from_zone, *to_zone, instruction);
+        }
+        InstructionKind::NoiseChannel { model, role } => {
+            visitor.visit_noise_channel(model, *role, instruction);
+        }
+        InstructionKind::IfElse {
+            condition,
+            then_body,
+            else_body,
+        } => visitor.visit_if_else(condition, then_body, else_body, instruction),
+        InstructionKind::WhileLoop {
+            condition,
+            body,
+            max_iterations,
+        } => visitor.visit_while_loop(condition, body, *max_iterations, instruction),
+    }
+}
+
+/// What a [`DagTransformer`] does with one instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rewrite {
+    /// Leave the instruction as it is.
+    Keep,
+    /// Replace the instruction with these, in order; an empty list removes
+    /// it.
+    Replace(Vec<Instruction>),
+}
+
+/// Rewriting callbacks for each kind of instruction.
+///
+/// Replacements may only use qubits and classical bits the circuit already
+/// has. Callbacks return an error to abort the rewrite, leaving the circuit
+/// unchanged.
+pub trait DagTransformer {
+    /// Called for every instruction; dispatches to the callback for its
+    /// kind. Override to rewrite every instruction in one place.
+    fn transform_instruction(&mut self, instruction: &Instruction) -> IrResult<Rewrite> {
+        walk_transform(self, instruction)
+    }
+
+    /// A gate, possibly classically conditioned.
+    fn transform_gate(&mut self, _gate: &Gate, _instruction: &Instruction) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// A measurement.
+    fn transform_measure(&mut self, _instruction: &Instruction) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// A reset to |0⟩.
+    fn transform_reset(&mut self, _instruction: &Instruction) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// A barrier.
+    fn transform_barrier(&mut self, _instruction: &Instruction) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// A delay of `duration` device time units.
+    fn transform_delay(&mut self, _duration: u64, _instruction: &Instruction) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// A shuttle between zones of a neutral-atom device.
+    fn transform_shuttle(
+        &mut self,
+        _from_zone: u32,
+        _to_zone: u32,
+        _instruction: &Instruction,
+    ) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// A noise channel.
+    fn transform_noise_channel(
+        &mut self,
+        _model: &NoiseModel,
+        _role: NoiseRole,
+        _instruction: &Instruction,
+    ) -> IrResult<Rewrite> {
+        Ok(Rewrite::Keep)
+    }
+
+    /// An if/else block. The default rewrites both bodies and rebuilds the
+    /// block if either changed.
+    fn transform_if_else(
+        &mut self,
+        condition: &ClassicalCondition,
+        then_body: &[Instruction],
+        else_body: &[Instruction],
+        instruction: &Instruction,
+    ) -> IrResult<Rewrite> {
+        let new_then = transform_body(self, then_body)?;
+        let new_else = transform_body(self, else_body)?;
+        if new_then.is_none() && new_else.is_none() {
+            return Ok(Rewrite::Keep);
+        }
+        Ok(Rewrite::Replace(vec![
+            Instruction::if_else(
+                condition.clone(),
+                new_then.unwrap_or_else(|| then_body.to_vec()),
+                new_else.unwrap_or_else(|| else_body.to_vec()),
+            )
+            .with_metadata_from(instruction),
+        ]))
+    }
+
+    /// A bounded while loop. The default rewrites the body and rebuilds
+    /// the loop if it changed.
+    fn transform_while_loop(
+        &mut self,
+        condition: &ClassicalCondition,
+        body: &[Instruction],
+        max_iterations: u32,
+        instruction: &Instruction,
+    ) -> IrResult<Rewrite> {
+        Ok(match transform_body(self, body)? {
+            Some(body) => Rewrite::Replace(vec![
+                Instruction::while_loop(condition.clone(), body, max_iterations)
+                    .with_metadata_from(instruction),
+            ]),
+            None => Rewrite::Keep,
+        })
+    }
+}
+
+/// Call the callback of `transformer` for the kind of `instruction`.
+///
+/// This is what [`DagTransformer::transform_instruction`] does by default;
+/// call it from an override to keep the per-kind callbacks.
+pub fn walk_transform<T: DagTransformer + ?Sized>(
+    transformer: &mut T,
+    instruction: &Instruction,
+) -> IrResult<Rewrite> {
+    match &instruction.kind {
+        InstructionKind::Gate(gate) => transformer.transform_gate(gate, instruction),
+        InstructionKind::Measure => transformer.transform_measure(instruction),
+        InstructionKind::Reset => transformer.transform_reset(instruction),
+        InstructionKind::Barrier => transformer.transform_barrier(instruction),
+        InstructionKind::Delay { duration } => transformer.transform_delay(*duration, instruction),
+        InstructionKind::Shuttle { from_zone, to_zone } => {
+            transformer.transform_shuttle(*from_zone, *to_zone, instruction)
+        }
+        InstructionKind::NoiseChannel { model, role } => {
+            transformer.transform_noise_channel(model, *role, instruction)
+        }
+        InstructionKind::IfElse {
+            condition,
+            then_body,
+            else_body,
+        } => transformer.transform_if_else(condition, then_body, else_body, instruction),
+        InstructionKind::WhileLoop {
+            condition,
+            body,
+            max_iterations,
+        } => transformer.transform_while_loop(condition, body, *max_iterations, instruction),
+    }
+}
+
+/// Rewrite the instructions of a control-flow body; `None` if none
+/// changed.
+pub fn transform_body<T: DagTransformer + ?Sized>(
+    transformer: &mut T,
+    body: &[Instruction],
+) -> IrResult<Option<Vec<Instruction>>> {
+    let mut rewritten: Option<Vec<Instruction>> = None;
+    for (i, inst) in body.iter().enumerate() {
+        match transformer.transform_instruction(inst)? {
+            Rewrite::Keep => {
+                if let Some(out) = &mut rewritten {
+                    out.push(inst.clone());
+                }
+            }
+            Rewrite::Replace(replacement) => {
+                rewritten
+                    .get_or_insert_with(|| body[..i].to_vec())
+                    .extend(replacement);
+            }
+        }
+    }
+    Ok(rewritten)
+}
+
+impl CircuitDag {
+    /// Call `visitor` for every instruction, in topological order.
+    pub fn visit<V: CircuitVisitor + ?Sized>(&self, visitor: &mut V) {
+        for (_, inst) in self.topological_ops() {
+            visitor.visit_instruction(inst);
+        }
+    }
+
+    /// Rewrite the DAG with `transformer` and return the number of
+    /// top-level instructions it replaced or removed.
+    ///
+    /// Wires, global phase and level are kept. On error the DAG is left
+    /// unchanged.
+    pub fn transform<T: DagTransformer + ?Sized>(
+        &mut self,
+        transformer: &mut T,
+    ) -> IrResult<usize> {
+        let mut rewrites = Vec::with_capacity(self.num_ops());
+        for (_, inst) in self.topological_ops() {
+            rewrites.push(transformer.transform_instruction(inst)?);
+        }
+        let changed = rewrites
+            .iter()
+            .filter(|r| matches!(r, Rewrite::Replace(_)))
+            .count();
+        if changed == 0 {
+            return Ok(0);
+        }
+
+        let mut dag = CircuitDag::new();
+        let mut qubits: Vec<QubitId> = self.qubits().collect();
+        qubits.sort_by_key(|q| q.0);
+        for qubit in qubits {
+            dag.add_qubit(qubit);
+        }
+        let mut clbits: Vec<ClbitId> = self.clbits().collect();
+        clbits.sort_by_key(|c| c.0);
+        for clbit in clbits {
+            dag.add_clbit(clbit);
+        }
+        dag.set_global_phase(self.global_phase());
+        dag.set_level(self.level());
+
+        for ((_, inst), rewrite) in self.topological_ops().zip(rewrites) {
+            match rewrite {
+                Rewrite::Keep => {
+                    dag.apply(inst.clone())?;
+                }
+                Rewrite::Replace(replacement) => {
+                    for inst in replacement {
+                        dag.apply(inst)?;
+                    }
+                }
+            }
+        }
+        *self = dag;
+        Ok(changed)
+    }
+}
+
+impl Circuit {
+    /// Call `visitor` for every instruction, in topological order.
+    pub fn visit<V: CircuitVisitor + ?Sized>(&self, visitor: &mut V) {
+        self.dag().visit(visitor);
+    }
+
+    /// Rewrite the circuit with `transformer`; see [`CircuitDag::transform`].
+    pub fn transform<T: DagTransformer + ?Sized>(
+        &mut self,
+        transformer: &mut T,
+    ) -> IrResult<usize> {
+        self.dag_mut().transform(transformer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gate::StandardGate;
+
+    /// Records the kind of every instruction it sees.
+    #[derive(Default)]
+    struct Kinds(Vec<String>);
+
+    impl CircuitVisitor for Kinds {
+        fn visit_gate(&mut self, gate: &Gate, _instruction: &Instruction) {
+            self.0.push(gate.name().to_string());
+        }
+
+        fn visit_measure(&mut self, _instruction: &Instruction) {
+            self.0.push("measure".into());
+        }
+
+        fn visit_if_else(
+            &mut self,
+            _condition: &ClassicalCondition,
+            then_body: &[Instruction],
+            else_body: &[Instruction],
+            _instruction: &Instruction,
+        ) {
+            self.0.push("if".into());
+            for inst in then_body.iter().chain(else_body) {
+                self.visit_instruction(inst);
+            }
+        }
+    }
+
+    /// Decomposes SWAP into three CX gates.
+    struct SwapToCx;
+
+    impl DagTransformer for SwapToCx {
+        fn transform_gate(&mut self, gate: &Gate, instruction: &Instruction) -> IrResult<Rewrite> {
+            if gate.name() != "swap" {
+                return Ok(Rewrite::Keep);
+            }
+            let (a, b) = (instruction.qubits[0], instruction.qubits[1]);
+            Ok(Rewrite::Replace(vec![
+                Instruction::two_qubit_gate(StandardGate::CX, a, b),
+                Instruction::two_qubit_gate(StandardGate::CX, b, a),
+                Instruction::two_qubit_gate(StandardGate::CX, a, b),
+            ]))
+        }
+    }
+
+    fn conditioned_swap() -> Circuit {
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.h(QubitId(0)).unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .dag_mut()
+            .apply(Instruction::if_else(
+                ClassicalCondition::on_clbit(ClbitId(0), 1),
+                vec![Instruction::two_qubit_gate(
+                    StandardGate::Swap,
+                    QubitId(0),
+                    QubitId(1),
+                )],
+                vec![],
+            ))
+            .unwrap();
+        circuit.swap(QubitId(0), QubitId(1)).unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_visitor_sees_kinds_in_order() {
+        let mut kinds = Kinds::default();
+        conditioned_swap().visit(&mut kinds);
+        assert_eq!(kinds.0, vec!["h", "measure", "if", "swap", "swap"]);
+    }
+
+    #[test]
+    fn test_transformer_rewrites_nested_bodies() {
+        let mut circuit = conditioned_swap();
+        assert_eq!(circuit.transform(&mut SwapToCx).unwrap(), 2);
+
+        let mut kinds = Kinds::default();
+        circuit.visit(&mut kinds);
+        assert_eq!(
+            kinds.0,
+            vec!["h", "measure", "if", "cx", "cx", "cx", "cx", "cx", "cx"]
+        );
+        assert_eq!(circuit.num_qubits(), 2);
+        circuit.dag().verify_integrity().unwrap();
+    }
+
+    #[test]
+    fn test_unchanged_dag_is_kept() {
+        let mut circuit = Circuit::bell().unwrap();
+        let before = circuit.dag().to_bytes();
+        assert_eq!(circuit.transform(&mut SwapToCx).unwrap(), 0);
+        assert_eq!(circuit.dag().to_bytes(), before);
+    }
+}
@@ -0,0 +1,127 @@
+//! Property tests for the standard gate matrices.
+//!
+//! Gates without parameters are checked exactly in `unitary.rs`; here every
+//! parameterized gate must be unitary, agree with its inverse, and reduce
+//! to the exact table at angles where it equals a fixed gate.
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use arvak_ir::{ParameterExpression, StandardGate};
+use num_complex::Complex64;
+use proptest::prelude::*;
+
+const TOLERANCE: f64 = 1e-10;
+
+fn parametric_gates(t: f64, p: f64, l: f64) -> Vec<StandardGate> {
+    let c = ParameterExpression::constant;
+    vec![
+        StandardGate::Rx(c(t)),
+        StandardGate::Ry(c(t)),
+        StandardGate::Rz(c(t)),
+        StandardGate::P(c(t)),
+        StandardGate::U(c(t), c(p), c(l)),
+        StandardGate::PRX(c(t), c(p)),
+        StandardGate::CRx(c(t)),
+        StandardGate::CRy(c(t)),
+        StandardGate::CRz(c(t)),
+        StandardGate::CP(c(t)),
+        StandardGate::RXX(c(t)),
+        StandardGate::RYY(c(t)),
+        StandardGate::RZZ(c(t)),
+        StandardGate::MCP(2, c(t)),
+    ]
+}
+
+fn dim(m: &[Complex64]) -> usize {
+    m.len().isqrt()
+}
+
+fn adjoint(m: &[Complex64]) -> Vec<Complex64> {
+    let d = dim(m);
+    (0..m.len())
+        .map(|i| m[(i % d) * d + i / d].conj())
+        .collect()
+}
+
+fn matmul(a: &[Complex64], b: &[Complex64]) -> Vec<Complex64> {
+    let d = dim(a);
+    (0..a.len())
+        .map(|i| {
+            let (r, c) = (i / d, i % d);
+            (0..d).map(|k| a[r * d + k] * b[k * d + c]).sum()
+        })
+        .collect()
+}
+
+fn assert_identity(m: &[Complex64], label: &str) {
+    let d = dim(m);
+    for (i, v) in m.iter().enumerate() {
+        let expected = if i / d == i % d { 1.0 } else { 0.0 };
+        assert!(
+            (v - Complex64::new(expected, 0.0)).norm() < TOLERANCE,
+            "{label}: entry {i} is {v}"
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn parametric_gates_are_unitary(
+        t in -4.0 * PI..4.0 * PI,
+        p in -4.0 * PI..4.0 * PI,
+        l in -4.0 * PI..4.0 * PI,
+    ) {
+        for gate in parametric_gates(t, p, l) {
+            let m = gate.matrix().unwrap();
+            prop_assert_eq!(m.len(), 1 << (2 * gate.num_qubits()));
+            assert_identity(&matmul(&adjoint(&m), &m), gate.name());
+        }
+    }
+
+    #[test]
+    fn rotations_compose_additively(a in -PI..PI, b in -PI..PI) {
+        let c = ParameterExpression::constant;
+        let pairs: [fn(ParameterExpression) -> StandardGate; 5] = [
+            StandardGate::Rx,
+            StandardGate::Rz,
+            StandardGate::P,
+            StandardGate::CRy,
+            StandardGate::RZZ,
+        ];
+        for make in pairs {
+            let product = matmul(&make(c(a)).matrix().unwrap(), &make(c(b)).matrix().unwrap());
+            let sum = make(c(a + b)).matrix().unwrap();
+            let label = make(c(a)).name();
+            assert_identity(&matmul(&adjoint(&sum), &product), label);
+        }
+    }
+}
+
+#[test]
+fn rotations_reduce_to_exact_gates() {
+    let c = ParameterExpression::constant;
+    let cases = [
+        (StandardGate::P(c(PI)), StandardGate::Z),
+        (StandardGate::P(c(FRAC_PI_2)), StandardGate::S),
+        (StandardGate::P(c(-FRAC_PI_2)), StandardGate::Sdg),
+        (StandardGate::P(c(FRAC_PI_4)), StandardGate::T),
+        (StandardGate::P(c(-FRAC_PI_4)), StandardGate::Tdg),
+        (StandardGate::CP(c(PI)), StandardGate::CZ),
+        (StandardGate::MCP(3, c(PI)), StandardGate::MCZ(3)),
+        (StandardGate::U(c(PI), c(0.0), c(PI)), StandardGate::X),
+        (
+            StandardGate::U(c(PI), c(FRAC_PI_2), c(FRAC_PI_2)),
+            StandardGate::Y,
+        ),
+    ];
+    for (rotation, fixed) in cases {
+        let exact: Vec<Complex64> = fixed
+            .exact_matrix()
+            .unwrap()
+            .into_iter()
+            .map(Complex64::from)
+            .collect();
+        let product = matmul(&adjoint(&exact), &rotation.matrix().unwrap());
+        assert_identity(&product, fixed.name());
+    }
+}
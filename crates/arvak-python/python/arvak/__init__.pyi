@@ -1,6 +1,6 @@
 """Type stubs for Arvak Python bindings."""
 
-from typing import List, Optional, Union
+from typing import Dict, List, Optional, Union
 
 class QubitId:
     """Unique identifier for a qubit within a circuit."""
@@ -86,6 +86,11 @@ class Circuit:
     def cswap(
         self, control: QubitArg, t1: QubitArg, t2: QubitArg
     ) -> Circuit: ...
+    def mcx(self, controls: list[QubitArg], target: QubitArg) -> Circuit: ...
+    def mcz(self, controls: list[QubitArg], target: QubitArg) -> Circuit: ...
+    def mcp(
+        self, lam: float, controls: list[QubitArg], target: QubitArg
+    ) -> Circuit: ...
 
     # Other operations
     def measure(self, qubit: QubitArg, clbit: ClbitArg) -> Circuit: ...
@@ -94,6 +99,19 @@ class Circuit:
     def barrier_all(self) -> Circuit: ...
     def delay(self, qubit: QubitArg, duration: int) -> Circuit: ...
 
+    # Composition
+    def compose(
+        self,
+        other: Circuit,
+        qubits: list[QubitArg],
+        clbits: list[ClbitArg] = ...,
+    ) -> Circuit: ...
+    def append(self, other: Circuit) -> Circuit: ...
+    def inverse(self) -> Circuit: ...
+    def parameters(self) -> List[str]: ...
+    def bind_parameters(self, values: Dict[str, float]) -> Circuit: ...
+    def assign(self, values: List[float]) -> Circuit: ...
+
     # Pre-built circuits
     @staticmethod
     def bell() -> Circuit: ...
@@ -133,6 +151,10 @@ class CouplingMap:
     def full(n: int) -> CouplingMap: ...
     @staticmethod
     def star(n: int) -> CouplingMap: ...
+    @staticmethod
+    def grid(rows: int, cols: int) -> CouplingMap: ...
+    @staticmethod
+    def heavy_hex(distance: int) -> CouplingMap: ...
     def __repr__(self) -> str: ...
 
 class BasisGates:
@@ -30,6 +30,7 @@ Quick start::
     print(parts)  # e.g. [[0, 2], [1, 3]]
 """
 
+from ._adapt import AdaptVqeResult, AdaptVQESolver, qubit_excitation_pool
 from ._backend import HalBackend, NoisyBackend
 from ._encoding import DenseEncoding, Encoding, PolyEncoding
 from ._partition import spectral_partition
@@ -60,6 +61,10 @@ __all__ = [
     "VQESolver",
     "VqeResult",
     "SparsePauliOp",
+    # ADAPT-VQE
+    "AdaptVQESolver",
+    "AdaptVqeResult",
+    "qubit_excitation_pool",
     # QAOA
     "QAOASolver",
     "QaoaResult",
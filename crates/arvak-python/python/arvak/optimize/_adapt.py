@@ -0,0 +1,312 @@
+"""ADAPT-VQE — adaptive ansatz growth from an operator pool.
+
+Instead of fixing the ansatz up front, ADAPT-VQE (Grimsley et al., 2019)
+grows it one operator at a time, so the circuit only contains the rotations
+the Hamiltonian actually needs.
+
+Algorithm:
+  1. Prepare a reference state: X on the ``reference`` (occupied) qubits,
+     i.e. the Hartree-Fock state of a Jordan-Wigner Hamiltonian.
+  2. Screen the pool: for every Pauli string P, estimate the gradient of
+     appending exp(-iθP/2) at θ = 0 with the parameter-shift rule
+     ∂E/∂θ = (E(+π/2) − E(−π/2)) / 2, using the same grouped Pauli
+     estimator as ``VQESolver``.
+  3. Stop if the gradient norm is below ``gradient_tol``; otherwise append
+     the operator with the largest |∂E/∂θ| and re-optimise all parameters
+     with COBYLA, starting from the previous optimum.
+  4. Stop when the energy improves by less than ``energy_tol`` or
+     ``max_operators`` operators have been added.
+
+The default pool is the qubit pool of qubit-ADAPT-VQE (Tang et al., 2021):
+the Pauli strings of single and double excitations with their
+Jordan-Wigner Z strings dropped.
+
+Quick start::
+
+    from arvak.optimize import AdaptVQESolver, SparsePauliOp
+
+    h2 = SparsePauliOp([...])  # 4-qubit Jordan-Wigner H2 Hamiltonian
+    result = AdaptVQESolver(h2, n_qubits=4, reference=[0, 1]).solve()
+    print(result.energy, result.operators)
+"""
+
+from __future__ import annotations
+
+import itertools
+import math
+from collections.abc import Sequence
+from dataclasses import dataclass, field
+
+import numpy as np
+from scipy.optimize import OptimizeResult, minimize
+
+from ._pce import _default_backend
+from ._vqe import SparsePauliOp, _estimate_energy, _group_by_basis
+
+
+# ---------------------------------------------------------------------------
+# Operator pool
+# ---------------------------------------------------------------------------
+
+def qubit_excitation_pool(
+    n_qubits: int, occupied: Sequence[int] | None = None
+) -> list[dict[int, str]]:
+    """Qubit-ADAPT operator pool of single and double excitations.
+
+    A single excitation between qubits i and a contributes X_i Y_a and
+    Y_i X_a; a double excitation on i, j, a, b the eight strings on those
+    qubits with an odd number of Ys. An odd number of Ys keeps the
+    rotations real, like the fermionic excitations they come from.
+
+    Args:
+        n_qubits: Number of qubits (spin orbitals).
+        occupied: Occupied qubits of the reference state. If given, only
+                  excitations from occupied to virtual qubits are included,
+                  as in UCCSD; otherwise all pairs and quadruples are.
+
+    Returns:
+        Pauli strings as dicts mapping qubit index → 'X' or 'Y'.
+    """
+    if occupied is None:
+        singles = list(itertools.combinations(range(n_qubits), 2))
+        doubles = list(itertools.combinations(range(n_qubits), 4))
+    else:
+        occ = sorted(set(occupied))
+        virt = [q for q in range(n_qubits) if q not in occ]
+        singles = [(i, a) for i in occ for a in virt]
+        doubles = [
+            ij + ab
+            for ij in itertools.combinations(occ, 2)
+            for ab in itertools.combinations(virt, 2)
+        ]
+
+    pool: list[dict[int, str]] = []
+    for qubits in singles + doubles:
+        for ys in range(1, 1 << len(qubits)):
+            if bin(ys).count("1") % 2 == 1:
+                pool.append({
+                    q: 'Y' if (ys >> k) & 1 else 'X' for k, q in enumerate(qubits)
+                })
+    return pool
+
+
+# ---------------------------------------------------------------------------
+# Result type
+# ---------------------------------------------------------------------------
+
+@dataclass
+class AdaptVqeResult:
+    """Result of an ADAPT-VQE solve."""
+
+    energy: float
+    """Ground-state energy estimate of the final ansatz."""
+
+    params: np.ndarray
+    """Optimal angles, one per operator in ``operators``."""
+
+    operators: list[dict[int, str]]
+    """Pauli strings of the grown ansatz, in circuit order."""
+
+    converged: bool
+    """Whether a gradient or energy criterion stopped the growth."""
+
+    stop_reason: str
+    """``"gradient"``, ``"energy"`` or ``"max_operators"``."""
+
+    n_evals: int
+    """Energy estimates made, including gradient screening."""
+
+    energy_history: list[float] = field(default_factory=list)
+    """Reference energy, then the optimised energy after each added operator."""
+
+    gradient_norm_history: list[float] = field(default_factory=list)
+    """Norm of the pool gradient at each screening."""
+
+    energy_std_error: float = 0.0
+    """Shot-noise standard error of ``energy``."""
+
+
+# ---------------------------------------------------------------------------
+# AdaptVQESolver
+# ---------------------------------------------------------------------------
+
+class AdaptVQESolver:
+    """ADAPT-VQE: grows the ansatz by gradient screening of an operator pool.
+
+    Args:
+        hamiltonian:   SparsePauliOp describing H.
+        n_qubits:      Number of qubits.
+        reference:     Qubits set to |1⟩ in the reference state.
+        pool:          Pauli strings (dict qubit → 'X'/'Y'/'Z') to choose
+                       from. Defaults to ``qubit_excitation_pool`` over the
+                       reference's occupied qubits (all pairs and quadruples
+                       without a reference).
+        shots:         Shots per circuit evaluation.
+        backend:       Callable (circuit, shots) → dict[str, int].
+                       Defaults to the local statevector simulator.
+        noise_model:   Optional noise model; wraps backend in NoisyBackend.
+        max_operators: Maximum number of operators to add.
+        gradient_tol:  Stop when the norm of the pool gradient is below this.
+        energy_tol:    Stop when adding an operator lowers the energy by
+                       less than this.
+        max_iter:      Maximum COBYLA iterations per re-optimisation.
+
+    Example::
+
+        from arvak.optimize import AdaptVQESolver, SparsePauliOp
+
+        h = SparsePauliOp([(-1.0, {0: 'X', 1: 'X'}), (-1.0, {0: 'Y', 1: 'Y'})])
+        result = AdaptVQESolver(h, n_qubits=2, reference=[0]).solve()
+        print(result.energy, result.operators)
+    """
+
+    def __init__(
+        self,
+        hamiltonian: SparsePauliOp,
+        *,
+        n_qubits: int,
+        reference: Sequence[int] = (),
+        pool: Sequence[dict[int, str]] | None = None,
+        shots: int = 4096,
+        backend=None,
+        noise_model=None,
+        max_operators: int = 20,
+        gradient_tol: float = 1e-2,
+        energy_tol: float = 1e-4,
+        max_iter: int = 200,
+    ) -> None:
+        self.hamiltonian = hamiltonian
+        self.n_qubits = n_qubits
+        self.reference = sorted(set(reference))
+        if pool is None:
+            pool = qubit_excitation_pool(n_qubits, self.reference or None)
+        self.pool = [
+            {int(q): str(p).upper() for q, p in ops.items() if str(p).upper() != 'I'}
+            for ops in pool
+        ]
+        if not self.pool or any(not ops for ops in self.pool):
+            raise ValueError("pool must contain non-identity Pauli strings")
+        self.shots = shots
+        self.max_operators = max_operators
+        self.gradient_tol = gradient_tol
+        self.energy_tol = energy_tol
+        self.max_iter = max_iter
+        self._n_evals = 0
+
+        if noise_model is not None:
+            from ._backend import NoisyBackend
+            self._backend = NoisyBackend(backend or _default_backend, noise_model)
+        else:
+            self._backend = backend or _default_backend
+
+        self._groups = _group_by_basis(hamiltonian.terms)
+
+    def solve(self) -> AdaptVqeResult:
+        """Grow and optimise the ansatz; return the final energy estimate."""
+        self._n_evals = 0
+        operators: list[dict[int, str]] = []
+        params = np.zeros(0)
+        energy, variance = self._energy(operators, params)
+        energies = [energy]
+        gradient_norms: list[float] = []
+        stop_reason = "max_operators"
+
+        while True:
+            gradients = self.gradients(operators, params)
+            norm = float(np.linalg.norm(gradients))
+            gradient_norms.append(norm)
+            if norm < self.gradient_tol:
+                stop_reason = "gradient"
+                break
+            if len(operators) == self.max_operators:
+                break
+
+            operators.append(self.pool[int(np.argmax(np.abs(gradients)))])
+            opt: OptimizeResult = minimize(
+                lambda theta: self._energy(operators, theta)[0],
+                np.append(params, 0.0),
+                method="COBYLA",
+                options={"maxiter": self.max_iter, "rhobeg": 0.3},
+            )
+            params = opt.x
+            # Re-estimate at the optimum for its error bar.
+            previous = energy
+            energy, variance = self._energy(operators, params)
+            energies.append(energy)
+            if previous - energy < self.energy_tol:
+                stop_reason = "energy"
+                break
+
+        return AdaptVqeResult(
+            energy=energy,
+            params=params,
+            operators=[dict(ops) for ops in operators],
+            converged=stop_reason != "max_operators",
+            stop_reason=stop_reason,
+            n_evals=self._n_evals,
+            energy_history=energies,
+            gradient_norm_history=gradient_norms,
+            energy_std_error=math.sqrt(variance),
+        )
+
+    def gradients(
+        self, operators: Sequence[dict[int, str]], params: np.ndarray
+    ) -> np.ndarray:
+        """Energy gradient of appending each pool operator to the ansatz.
+
+        Entry k is ∂E/∂θ at θ = 0 for the ansatz followed by
+        exp(-iθ P_k / 2), from the parameter-shift rule.
+        """
+        gradients = np.empty(len(self.pool))
+        for k, ops in enumerate(self.pool):
+            shifted = [*operators, ops]
+            plus, _ = self._energy(shifted, np.append(params, math.pi / 2))
+            minus, _ = self._energy(shifted, np.append(params, -math.pi / 2))
+            gradients[k] = (plus - minus) / 2.0
+        return gradients
+
+    # ------------------------------------------------------------------
+    # Estimator
+    # ------------------------------------------------------------------
+
+    def _energy(
+        self, operators: Sequence[dict[int, str]], params: np.ndarray
+    ) -> tuple[float, float]:
+        """Estimate ⟨H⟩ and its variance for the given ansatz."""
+        self._n_evals += 1
+        lines = [f"x q[{q}];" for q in self.reference]
+        for ops, theta in zip(operators, params):
+            lines.extend(_pauli_rotation_lines(ops, float(theta)))
+        return _estimate_energy(
+            self._backend, self.shots, self.n_qubits, self._groups, lines
+        )
+
+
+# ---------------------------------------------------------------------------
+# Circuit construction
+# ---------------------------------------------------------------------------
+
+def _pauli_rotation_lines(ops: dict[int, str], theta: float) -> list[str]:
+    """QASM statements applying exp(-iθ/2 · P) for the Pauli string ``ops``.
+
+    Each qubit is rotated into the Z basis (X: H, Y: RX(π/2)), a CNOT
+    ladder collects the parity on the last qubit for an RZ(θ), and the
+    ladder and basis changes are undone.
+    """
+    qubits = sorted(ops)
+    lines: list[str] = []
+    for q in qubits:
+        if ops[q] == 'X':
+            lines.append(f"h q[{q}];")
+        elif ops[q] == 'Y':
+            lines.append(f"rx({math.pi / 2}) q[{q}];")
+    ladder = [f"cx q[{a}], q[{b}];" for a, b in zip(qubits, qubits[1:])]
+    lines.extend(ladder)
+    lines.append(f"rz({theta}) q[{qubits[-1]}];")
+    lines.extend(reversed(ladder))
+    for q in qubits:
+        if ops[q] == 'X':
+            lines.append(f"h q[{q}];")
+        elif ops[q] == 'Y':
+            lines.append(f"rx({-math.pi / 2}) q[{q}];")
+    return lines
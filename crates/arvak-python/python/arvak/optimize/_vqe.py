@@ -7,7 +7,7 @@ Algorithm:
   1. For each Pauli term P_k: build a measurement circuit = ansatz + basis
      rotations (X→H, Y→Sdg+H) + measure_all.
   2. Run through backend → shot counts → parity sum → ⟨P_k⟩.
-  3. ⟨H⟩ = Σ c_k ⟨P_k⟩.
+  3. ⟨H⟩ = Σ c_k ⟨P_k⟩, with its shot-noise standard error.
   4. COBYLA minimises ⟨H⟩ over the ansatz parameters.
 
 Commuting terms sharing the same qubit basis are grouped to reduce circuit
@@ -129,6 +129,12 @@ class VqeResult:
     energy_history: list[float] = field(default_factory=list)
     """Energy value at each cost function evaluation."""
 
+    energy_std_error: float = 0.0
+    """Shot-noise standard error of ``energy``."""
+
+    std_error_history: list[float] = field(default_factory=list)
+    """Standard error of each entry of ``energy_history``."""
+
 
 # ---------------------------------------------------------------------------
 # VQESolver
@@ -179,6 +185,7 @@ class VQESolver:
         self.max_iter = max_iter
         self._rng = np.random.default_rng(seed)
         self._history: list[float] = []
+        self._std_errors: list[float] = []
 
         if noise_model is not None:
             from ._backend import NoisyBackend
@@ -195,6 +202,7 @@ class VQESolver:
         n_params = self.n_layers * self.n_qubits
         theta0 = self._rng.uniform(0.0, 2.0 * math.pi, n_params)
         self._history = []
+        self._std_errors = []
 
         opt: OptimizeResult = minimize(
             self._cost,
@@ -203,12 +211,21 @@ class VQESolver:
             options={"maxiter": self.max_iter, "rhobeg": 0.3},
         )
 
+        # COBYLA reports the best evaluation; its error is the one recorded
+        # with it.
+        best = min(
+            range(len(self._history)),
+            key=lambda i: abs(self._history[i] - opt.fun),
+            default=None,
+        )
         return VqeResult(
             energy=float(opt.fun),
             params=opt.x,
             n_iters=int(opt.nfev),
             converged=bool(opt.success),
             energy_history=list(self._history),
+            energy_std_error=self._std_errors[best] if best is not None else 0.0,
+            std_error_history=list(self._std_errors),
         )
 
     # ------------------------------------------------------------------
@@ -217,38 +234,65 @@ class VQESolver:
 
     def _cost(self, theta: np.ndarray) -> float:
         """Evaluate ⟨H⟩ = Σ c_k ⟨P_k⟩ for the given ansatz parameters."""
-        energy = 0.0
-        for basis, term_list in self._groups.items():
-            # Build measurement circuit: ansatz + basis rotations + measure_all
-            circuit = _build_measurement_circuit(
-                self.n_qubits, self.n_layers, theta, basis
-            )
-            counts = self._backend(circuit, self.shots)
-            total = sum(counts.values())
-            if total == 0:
-                continue
-
-            for coeff, ops in term_list:
-                # ⟨P_k⟩ = Σ_bitstring (-1)^parity(bitstring, ops) * count / total
-                exp_val = _parity_expectation(counts, ops, self.n_qubits, total)
-                energy += coeff * exp_val
-
+        energy, variance = _estimate_energy(
+            self._backend,
+            self.shots,
+            self.n_qubits,
+            self._groups,
+            _ansatz_lines(self.n_qubits, self.n_layers, theta),
+        )
         self._history.append(energy)
+        self._std_errors.append(math.sqrt(variance))
         return energy
 
 
+# ---------------------------------------------------------------------------
+# Energy estimator
+# ---------------------------------------------------------------------------
+
+def _estimate_energy(
+    backend,
+    shots: int,
+    n_qubits: int,
+    groups: dict[frozenset[tuple[int, str]], list[tuple[float, dict[int, str]]]],
+    ansatz_lines: list[str],
+) -> tuple[float, float]:
+    """Estimate ⟨H⟩ for the state prepared by ``ansatz_lines``.
+
+    Runs one circuit per group of ``_group_by_basis`` and returns the
+    energy and the shot-noise variance of the estimate.
+    """
+    energy = 0.0
+    variance = 0.0
+    for basis, term_list in groups.items():
+        # Build measurement circuit: ansatz + basis rotations + measure_all
+        circuit = _build_measurement_circuit(n_qubits, ansatz_lines, basis)
+        counts = backend(circuit, shots)
+        total = sum(counts.values())
+        if total == 0:
+            continue
+
+        # Terms of a group share shots, so their errors are correlated;
+        # groups run as separate circuits and their variances add.
+        mean, group_variance = _group_moments(counts, term_list, n_qubits, total)
+        energy += mean
+        variance += group_variance
+    return energy, variance
+
+
 # ---------------------------------------------------------------------------
 # Circuit construction
 # ---------------------------------------------------------------------------
 
 def _build_measurement_circuit(
     n_qubits: int,
-    n_layers: int,
-    theta: np.ndarray,
+    ansatz_lines: list[str],
     basis: frozenset[tuple[int, str]],
 ) -> arvak.Circuit:
     """Build ansatz + basis rotations + measure circuit for a Pauli basis.
 
+    ``ansatz_lines`` are the QASM gate statements preparing the state.
+
     Basis rotations:
       - X measurement: H gate (rotate X basis → Z basis)
       - Y measurement: Sdg + H gate (rotate Y basis → Z basis)
@@ -259,19 +303,9 @@ def _build_measurement_circuit(
         'include "stdgates.inc";',
         f"qubit[{n_qubits}] q;",
         f"bit[{n_qubits}] c;",
+        *ansatz_lines,
     ]
 
-    # Ansatz layers (same as PCESolver ansatz but without final RY layer)
-    for layer in range(n_layers):
-        offset = layer * n_qubits
-        for i in range(n_qubits):
-            angle = float(theta[offset + i])
-            lines.append(f"ry({angle}) q[{i}];")
-        if n_qubits > 1:
-            for i in range(n_qubits - 1):
-                lines.append(f"cx q[{i}], q[{i + 1}];")
-            lines.append(f"cx q[{n_qubits - 1}], q[0];")
-
     # Basis rotations
     basis_dict = dict(basis)
     for q in range(n_qubits):
@@ -286,6 +320,24 @@ def _build_measurement_circuit(
     return arvak.from_qasm("\n".join(lines))
 
 
+def _ansatz_lines(n_qubits: int, n_layers: int, theta: np.ndarray) -> list[str]:
+    """QASM statements of the RY + CNOT-ring ansatz.
+
+    Same as the PCESolver ansatz but without the final RY layer.
+    """
+    lines: list[str] = []
+    for layer in range(n_layers):
+        offset = layer * n_qubits
+        for i in range(n_qubits):
+            angle = float(theta[offset + i])
+            lines.append(f"ry({angle}) q[{i}];")
+        if n_qubits > 1:
+            for i in range(n_qubits - 1):
+                lines.append(f"cx q[{i}], q[{i + 1}];")
+            lines.append(f"cx q[{n_qubits - 1}], q[0];")
+    return lines
+
+
 # ---------------------------------------------------------------------------
 # Expectation value helpers
 # ---------------------------------------------------------------------------
@@ -317,6 +369,30 @@ def _parity_expectation(
     return exp_val / total
 
 
+def _group_moments(
+    counts: dict[str, int],
+    term_list: list[tuple[float, dict[int, str]]],
+    n_qubits: int,
+    total: int,
+) -> tuple[float, float]:
+    """Mean of Σ c_k P_k over the shots, and the variance of that mean.
+
+    Each shot yields Σ_k c_k (-1)^{parity_k}; the variance of the mean is
+    (E[v²] − E[v]²) / total, which accounts for the covariance between
+    terms measured on the same shots.
+    """
+    first = 0.0
+    second = 0.0
+    for bitstring, count in counts.items():
+        value = sum(
+            coeff * _parity_expectation({bitstring: 1}, ops, n_qubits, 1)
+            for coeff, ops in term_list
+        )
+        first += value * count / total
+        second += value * value * count / total
+    return first, max(second - first * first, 0.0) / total
+
+
 def _group_by_basis(
     terms: list[tuple[float, dict[int, str]]]
 ) -> dict[frozenset[tuple[int, str]], list[tuple[float, dict[int, str]]]]:
@@ -1,5 +1,7 @@
 //! Python wrapper for the Circuit class.
 
+use std::collections::HashMap;
+
 use pyo3::prelude::*;
 
 use crate::error::ir_to_py_err;
@@ -500,6 +502,68 @@ impl PyCircuit {
         Ok(slf)
     }
 
+    // =========================================================================
+    // Multi-controlled gates
+    // =========================================================================
+
+    /// Apply an X gate controlled on every qubit in `controls`.
+    fn mcx(
+        slf: Py<Self>,
+        py: Python<'_>,
+        controls: Vec<Bound<'_, PyAny>>,
+        target: &Bound<'_, PyAny>,
+    ) -> PyResult<Py<Self>> {
+        let ctrls = controls
+            .iter()
+            .map(to_qubit_id)
+            .collect::<PyResult<Vec<_>>>()?;
+        let tgt = to_qubit_id(target)?;
+        slf.borrow_mut(py)
+            .inner
+            .mcx(&ctrls, tgt)
+            .map_err(ir_to_py_err)?;
+        Ok(slf)
+    }
+
+    /// Apply a Z gate controlled on every qubit in `controls`.
+    fn mcz(
+        slf: Py<Self>,
+        py: Python<'_>,
+        controls: Vec<Bound<'_, PyAny>>,
+        target: &Bound<'_, PyAny>,
+    ) -> PyResult<Py<Self>> {
+        let ctrls = controls
+            .iter()
+            .map(to_qubit_id)
+            .collect::<PyResult<Vec<_>>>()?;
+        let tgt = to_qubit_id(target)?;
+        slf.borrow_mut(py)
+            .inner
+            .mcz(&ctrls, tgt)
+            .map_err(ir_to_py_err)?;
+        Ok(slf)
+    }
+
+    /// Apply a phase gate P(λ) controlled on every qubit in `controls`.
+    fn mcp(
+        slf: Py<Self>,
+        py: Python<'_>,
+        lam: f64,
+        controls: Vec<Bound<'_, PyAny>>,
+        target: &Bound<'_, PyAny>,
+    ) -> PyResult<Py<Self>> {
+        let ctrls = controls
+            .iter()
+            .map(to_qubit_id)
+            .collect::<PyResult<Vec<_>>>()?;
+        let tgt = to_qubit_id(target)?;
+        slf.borrow_mut(py)
+            .inner
+            .mcp(lam, &ctrls, tgt)
+            .map_err(ir_to_py_err)?;
+        Ok(slf)
+    }
+
     // =========================================================================
     // Other operations
     // =========================================================================
@@ -566,6 +630,96 @@ impl PyCircuit {
         Ok(slf)
     }
 
+    // =========================================================================
+    // Composition
+    // =========================================================================
+
+    /// Append the operations of another circuit onto wires of this one.
+    ///
+    /// Args:
+    ///     other: The circuit to append.
+    ///     qubits: Qubits of this circuit receiving each qubit of `other`.
+    ///     clbits: Classical bits receiving each classical bit of `other`
+    ///         (default: none).
+    #[pyo3(signature = (other, qubits, clbits=vec![]))]
+    fn compose(
+        slf: Py<Self>,
+        py: Python<'_>,
+        other: Py<Self>,
+        qubits: Vec<Bound<'_, PyAny>>,
+        clbits: Vec<Bound<'_, PyAny>>,
+    ) -> PyResult<Py<Self>> {
+        let qubit_map = qubits
+            .iter()
+            .map(to_qubit_id)
+            .collect::<PyResult<Vec<_>>>()?;
+        let clbit_map = clbits
+            .iter()
+            .map(to_clbit_id)
+            .collect::<PyResult<Vec<_>>>()?;
+        // Copy first so a circuit can be composed with itself.
+        let other = other.borrow(py).inner.clone();
+        slf.borrow_mut(py)
+            .inner
+            .compose(&other, &qubit_map, &clbit_map)
+            .map_err(ir_to_py_err)?;
+        Ok(slf)
+    }
+
+    /// Append another circuit onto the leading qubits and classical bits.
+    fn append(slf: Py<Self>, py: Python<'_>, other: Py<Self>) -> PyResult<Py<Self>> {
+        let other = other.borrow(py).inner.clone();
+        slf.borrow_mut(py)
+            .inner
+            .append(&other)
+            .map_err(ir_to_py_err)?;
+        Ok(slf)
+    }
+
+    /// Return the inverse (adjoint) circuit.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the circuit contains a measurement, reset or a
+    ///         gate without a known inverse.
+    fn inverse(&self) -> PyResult<Self> {
+        Ok(Self {
+            inner: self.inner.inverse().map_err(ir_to_py_err)?,
+        })
+    }
+
+    /// Names of the unbound symbolic parameters, in binding order.
+    ///
+    /// Numeric suffixes sort as numbers, so `theta_2` comes before
+    /// `theta_10`.
+    fn parameters(&self) -> Vec<String> {
+        self.inner.parameters()
+    }
+
+    /// Return a copy with symbolic parameters bound by name.
+    ///
+    /// Args:
+    ///     values: Mapping from parameter name to value. Names that do not
+    ///         occur in the circuit are ignored.
+    ///
+    /// Raises:
+    ///     RuntimeError: If a parameter has no value.
+    fn bind_parameters(&self, values: HashMap<String, f64>) -> PyResult<Self> {
+        Ok(Self {
+            inner: self.inner.bind_parameters(&values).map_err(ir_to_py_err)?,
+        })
+    }
+
+    /// Return a copy with parameters bound positionally, in the order of
+    /// `parameters()`.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the number of values does not match.
+    fn assign(&self, values: Vec<f64>) -> PyResult<Self> {
+        Ok(Self {
+            inner: self.inner.assign(&values).map_err(ir_to_py_err)?,
+        })
+    }
+
     // =========================================================================
     // Pre-built circuits
     // =========================================================================
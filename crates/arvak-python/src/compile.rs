@@ -218,6 +218,23 @@ impl PyCouplingMap {
         }
     }
 
+    /// Create a rows x cols square grid, numbered row by row.
+    #[staticmethod]
+    fn grid(rows: u32, cols: u32) -> Self {
+        Self {
+            inner: arvak_compile::CouplingMap::grid(rows, cols),
+        }
+    }
+
+    /// Create a heavy-hex lattice (IBM Falcon/Eagle/Heron topology) with
+    /// `distance` rows of 2 * distance + 1 qubits.
+    #[staticmethod]
+    fn heavy_hex(distance: u32) -> Self {
+        Self {
+            inner: arvak_compile::CouplingMap::heavy_hex(distance),
+        }
+    }
+
     /// Create a coupling map from an explicit edge list.
     ///
     /// Args:
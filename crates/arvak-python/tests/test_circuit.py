@@ -93,6 +93,46 @@ class TestGates:
         qc.h(0).cx(0, 1).measure(0, 0).measure(1, 1)
         assert qc.depth() == 3  # H, CX, parallel measures
 
+    def test_compose_and_append(self):
+        """Test composing circuit fragments."""
+        layer = Circuit("layer", num_qubits=2)
+        layer.h(0).cx(0, 1)
+
+        qc = Circuit("test", num_qubits=3)
+        qc.compose(layer, [2, 1]).append(layer)
+        assert qc.depth() == 4
+
+        with pytest.raises(RuntimeError):
+            qc.compose(layer, [0])
+
+    def test_inverse(self):
+        """Test building the adjoint circuit."""
+        qc = Circuit("test", num_qubits=2)
+        qc.h(0).s(0).cx(0, 1)
+        inv = qc.inverse()
+        assert inv.name == "test_dg"
+        assert inv.depth() == 3
+
+        with pytest.raises(RuntimeError):
+            Circuit.bell().inverse()
+
+    def test_bind_parameters(self):
+        """Test binding symbolic parameters by name and by position."""
+        qc = arvak.from_qasm(
+            "OPENQASM 3.0;\nqubit[2] q;\n"
+            "ry(theta_0) q[0];\nry(theta_10) q[1];\nrz(theta_2) q[0];\n"
+        )
+        assert qc.parameters() == ["theta_0", "theta_2", "theta_10"]
+
+        bound = qc.bind_parameters({"theta_0": 0.1, "theta_2": 0.2, "theta_10": 0.3})
+        assert bound.parameters() == []
+        assert qc.assign([0.1, 0.2, 0.3]).parameters() == []
+
+        with pytest.raises(RuntimeError):
+            qc.bind_parameters({"theta_0": 0.1})
+        with pytest.raises(RuntimeError):
+            qc.assign([0.1])
+
 
 class TestQubitId:
     """Test QubitId class."""
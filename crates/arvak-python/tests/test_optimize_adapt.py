@@ -0,0 +1,156 @@
+"""Tests for arvak.optimize._adapt — AdaptVQESolver and the qubit pool.
+
+All tests run offline using the local statevector simulator.
+"""
+
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+from arvak.optimize import (
+    AdaptVqeResult,
+    AdaptVQESolver,
+    SparsePauliOp,
+    qubit_excitation_pool,
+)
+from arvak.optimize._adapt import _pauli_rotation_lines
+
+
+# H2 in STO-3G at 0.7414 Å, Jordan-Wigner, qubits 0 and 1 occupied.
+H2 = SparsePauliOp([
+    (-0.09886397, {}),
+    (0.17119775, {0: 'Z'}),
+    (0.17119775, {1: 'Z'}),
+    (-0.22278593, {2: 'Z'}),
+    (-0.22278593, {3: 'Z'}),
+    (0.16862219, {0: 'Z', 1: 'Z'}),
+    (0.12054482, {0: 'Z', 2: 'Z'}),
+    (0.16586702, {0: 'Z', 3: 'Z'}),
+    (0.16586702, {1: 'Z', 2: 'Z'}),
+    (0.12054482, {1: 'Z', 3: 'Z'}),
+    (0.17434844, {2: 'Z', 3: 'Z'}),
+    (-0.04532220, {0: 'X', 1: 'X', 2: 'Y', 3: 'Y'}),
+    (0.04532220, {0: 'X', 1: 'Y', 2: 'Y', 3: 'X'}),
+    (0.04532220, {0: 'Y', 1: 'X', 2: 'X', 3: 'Y'}),
+    (-0.04532220, {0: 'Y', 1: 'Y', 2: 'X', 3: 'X'}),
+])
+H2_HF_ENERGY = -1.11668438
+H2_FCI_ENERGY = -1.13728
+
+
+# ===========================================================================
+# Operator pool
+# ===========================================================================
+
+class TestQubitExcitationPool:
+    def test_pool_size_with_reference(self):
+        # 4 singles × 2 strings + 1 double × 8 strings
+        assert len(qubit_excitation_pool(4, occupied=[0, 1])) == 16
+
+    def test_pool_size_without_reference(self):
+        # C(4,2) pairs × 2 strings + C(4,4) quadruples × 8 strings
+        assert len(qubit_excitation_pool(4)) == 20
+
+    def test_strings_have_odd_number_of_ys(self):
+        for ops in qubit_excitation_pool(4):
+            assert set(ops.values()) <= {'X', 'Y'}
+            assert list(ops.values()).count('Y') % 2 == 1
+
+    def test_excitations_cross_occupied_and_virtual(self):
+        for ops in qubit_excitation_pool(6, occupied=[0, 1]):
+            occupied = [q for q in ops if q in (0, 1)]
+            assert 2 * len(occupied) == len(ops)
+
+
+def test_pauli_rotation_lines_ladder():
+    lines = _pauli_rotation_lines({0: 'X', 2: 'Y'}, 0.5)
+    assert lines[0] == "h q[0];"
+    assert lines[1].startswith("rx(") and lines[1].endswith("q[2];")
+    assert "cx q[0], q[2];" in lines
+    assert "rz(0.5) q[2];" in lines
+    assert lines[-1].startswith("rx(-")
+
+
+# ===========================================================================
+# AdaptVQESolver
+# ===========================================================================
+
+class TestAdaptVQESolver:
+    def test_result_fields(self):
+        h = SparsePauliOp([(-1.0, {0: 'X', 1: 'X'}), (-1.0, {0: 'Y', 1: 'Y'})])
+        result = AdaptVQESolver(h, n_qubits=2, reference=[0], shots=512,
+                                max_operators=2, max_iter=30).solve()
+        assert isinstance(result, AdaptVqeResult)
+        assert isinstance(result.params, np.ndarray)
+        assert len(result.params) == len(result.operators)
+        assert result.stop_reason in ("gradient", "energy", "max_operators")
+        assert result.converged == (result.stop_reason != "max_operators")
+        assert len(result.energy_history) == len(result.operators) + 1
+        assert result.n_evals > 0
+        assert result.energy_std_error >= 0.0
+
+    def test_hopping_reaches_ground_state(self):
+        # Ground state (|01⟩ + |10⟩)/√2 with energy −2, one rotation away
+        # from |01⟩.
+        h = SparsePauliOp([(-1.0, {0: 'X', 1: 'X'}), (-1.0, {0: 'Y', 1: 'Y'})])
+        result = AdaptVQESolver(h, n_qubits=2, reference=[0], shots=4096,
+                                max_operators=3, max_iter=50).solve()
+        assert result.energy_history[0] == pytest.approx(0.0, abs=0.1)
+        assert result.energy < -1.8
+
+    def test_max_operators_stops_growth(self):
+        result = AdaptVQESolver(H2, n_qubits=4, reference=[0, 1], shots=512,
+                                max_operators=1, gradient_tol=0.0,
+                                energy_tol=-np.inf, max_iter=20).solve()
+        assert len(result.operators) == 1
+        assert result.stop_reason == "max_operators"
+        assert not result.converged
+
+    def test_zero_gradient_stops_immediately(self):
+        # Rotations commuting with H leave every energy, and so every
+        # gradient, exactly unchanged.
+        h = SparsePauliOp([(-1.0, {0: 'Z'}), (-1.0, {1: 'Z'})])
+        pool = [{0: 'Z'}, {0: 'Z', 1: 'Z'}]
+        result = AdaptVQESolver(h, n_qubits=2, pool=pool, shots=256).solve()
+        assert result.operators == []
+        assert result.stop_reason == "gradient"
+        assert result.converged
+
+    def test_empty_pool_rejected(self):
+        h = SparsePauliOp([(-1.0, {0: 'Z'})])
+        with pytest.raises(ValueError):
+            AdaptVQESolver(h, n_qubits=1, pool=[])
+
+    def test_custom_backend(self):
+        import arvak
+        calls = []
+
+        def counting_backend(circuit, shots):
+            calls.append(shots)
+            return arvak.run_sim(circuit, shots)
+
+        h = SparsePauliOp([(-1.0, {0: 'X', 1: 'X'})])
+        AdaptVQESolver(h, n_qubits=2, reference=[0], shots=64, max_operators=1,
+                       max_iter=5, backend=counting_backend).solve()
+        assert len(calls) > 0
+        assert all(s == 64 for s in calls)
+
+
+# ===========================================================================
+# H2 — beyond the single-parameter ansatz
+# ===========================================================================
+
+class TestAdaptH2:
+    def test_h2_below_hartree_fock(self):
+        result = AdaptVQESolver(H2, n_qubits=4, reference=[0, 1], shots=8192,
+                                max_operators=3, max_iter=60).solve()
+        assert result.energy_history[0] == pytest.approx(H2_HF_ENERGY, abs=0.01)
+        assert result.energy < H2_HF_ENERGY - 0.01
+        assert result.energy == pytest.approx(H2_FCI_ENERGY, abs=0.01)
+
+    def test_h2_first_operator_is_double_excitation(self):
+        # Brillouin: single-excitation gradients vanish at Hartree-Fock.
+        result = AdaptVQESolver(H2, n_qubits=4, reference=[0, 1], shots=8192,
+                                max_operators=1, max_iter=30).solve()
+        assert sorted(result.operators[0]) == [0, 1, 2, 3]
@@ -5,6 +5,8 @@ All tests run offline using the local statevector simulator.
 
 from __future__ import annotations
 
+import math
+
 import numpy as np
 import pytest
 
@@ -77,6 +79,24 @@ class TestVQESolver:
         assert isinstance(result.converged, bool)
         assert isinstance(result.energy_history, list)
 
+    def test_energy_std_error(self):
+        h = SparsePauliOp([(-1.0, {0: 'Z', 1: 'Z'}), (0.5, {0: 'X'})])
+        result = VQESolver(h, n_qubits=2, n_layers=1, shots=256, seed=0, max_iter=30).solve()
+        assert len(result.std_error_history) == len(result.energy_history)
+        assert result.energy_std_error >= 0.0
+        # Two ±1 terms with |c| ≤ 1 from 256 shots each.
+        assert result.energy_std_error <= math.sqrt(1.0 / 256 + 0.25 / 256) + 1e-12
+
+    def test_group_moments_include_covariance(self):
+        from arvak.optimize._vqe import _group_moments
+
+        # Z0 and Z1 are perfectly correlated in this histogram.
+        counts = {"00": 600, "11": 400}
+        terms = [(1.0, {0: 'Z'}), (2.0, {1: 'Z'})]
+        mean, variance = _group_moments(counts, terms, 2, 1000)
+        assert mean == pytest.approx(0.6)
+        assert variance == pytest.approx((9.0 - 0.36) / 1000)
+
     def test_history_nonempty(self):
         h = SparsePauliOp([(-1.0, {0: 'Z', 1: 'Z'})])
         result = VQESolver(h, n_qubits=2, n_layers=1, shots=256, seed=0, max_iter=30).solve()
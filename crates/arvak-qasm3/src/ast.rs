@@ -23,6 +23,25 @@ pub enum Statement {
     /// Classical bit declaration: `bit[n] name;` or `bit name;`
     BitDecl { name: String, size: Option<u32> },
 
+    /// Input declaration: `input float[64] theta;`
+    ///
+    /// Inputs are bound when the program is submitted; in gate arguments
+    /// they lower to symbolic parameters.
+    InputDecl { name: String, ty: ClassicalType },
+
+    /// Output declaration: `output bit[2] c;`
+    OutputDecl { name: String, ty: ClassicalType },
+
+    /// Constant declaration: `const int n = 4;`
+    ///
+    /// Constants are substituted into the statements that follow, so they
+    /// can size loop ranges and index qubits.
+    ConstDecl {
+        name: String,
+        ty: ClassicalType,
+        value: Expression,
+    },
+
     /// Gate application.
     Gate(GateCall),
 
@@ -51,7 +70,16 @@ pub enum Statement {
         else_body: Option<Vec<Statement>>,
     },
 
-    /// For loop.
+    /// While loop; the iteration bound comes from an
+    /// `@arvak.max_iterations` annotation.
+    While {
+        condition: Expression,
+        body: Vec<Statement>,
+        max_iterations: Option<u32>,
+    },
+
+    /// For loop over an integer range; unrolled during lowering, with the
+    /// loop variable substituted into the body.
     For {
         variable: String,
         range: Range,
@@ -74,6 +102,45 @@ pub enum Statement {
     },
 }
 
+/// Type of an `input`/`output` declaration; the size is the designator,
+/// e.g. `64` in `float[64]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClassicalType {
+    /// `bit` or `bit[n]`.
+    Bit(Option<u32>),
+    /// `int` or `int[n]`.
+    Int(Option<u32>),
+    /// `float` or `float[n]`.
+    Float(Option<u32>),
+    /// `angle` or `angle[n]`.
+    Angle(Option<u32>),
+    /// `bool`.
+    Bool,
+}
+
+impl ClassicalType {
+    /// Whether values of this type can bind a gate parameter.
+    pub fn is_real(&self) -> bool {
+        matches!(self, ClassicalType::Float(_) | ClassicalType::Angle(_))
+    }
+}
+
+impl std::fmt::Display for ClassicalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (name, size) = match self {
+            ClassicalType::Bit(size) => ("bit", size),
+            ClassicalType::Int(size) => ("int", size),
+            ClassicalType::Float(size) => ("float", size),
+            ClassicalType::Angle(size) => ("angle", size),
+            ClassicalType::Bool => return write!(f, "bool"),
+        };
+        match size {
+            Some(n) => write!(f, "{name}[{n}]"),
+            None => write!(f, "{name}"),
+        }
+    }
+}
+
 /// A gate call.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GateCall {
@@ -85,6 +152,9 @@ pub struct GateCall {
     pub qubits: Vec<QubitRef>,
     /// Optional modifier (ctrl, inv, pow).
     pub modifiers: Vec<GateModifier>,
+    /// Duration from an `@arvak.duration` annotation, in `dt`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
 }
 
 /// Gate modifier.
@@ -114,6 +184,9 @@ pub enum QubitRef {
         start: u32,
         end: u32,
     },
+    /// Single qubit with a computed index: `q[i + 1]`, typically inside a
+    /// `for` loop.
+    Indexed { register: String, index: Expression },
 }
 
 impl QubitRef {
@@ -139,6 +212,7 @@ impl QubitRef {
         match self {
             QubitRef::Single { register, .. } => register,
             QubitRef::Range { register, .. } => register,
+            QubitRef::Indexed { register, .. } => register,
         }
     }
 }
@@ -157,6 +231,8 @@ pub enum BitRef {
         start: u32,
         end: u32,
     },
+    /// Single bit with a computed index: `c[i]`.
+    Indexed { register: String, index: Expression },
 }
 
 impl BitRef {
@@ -177,7 +253,8 @@ impl BitRef {
     }
 }
 
-/// A range for iteration.
+/// A range for iteration: `[start:end]` or `[start:step:end]`, with both
+/// ends inclusive.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
     pub start: Expression,
@@ -1,14 +1,55 @@
 //! QASM3 emitter for serializing circuits.
 
 use arvak_ir::{
-    Circuit, GateKind, Instruction, InstructionKind, ParameterExpression, StandardGate,
+    Circuit, ClassicalCondition, CustomGate, GateKind, Instruction, InstructionKind, ModifiedGate,
+    ParameterExpression, StandardGate,
 };
 
-use crate::error::ParseResult;
+use crate::error::{ParseError, ParseResult};
+use crate::layout::LayoutPragmas;
 
 /// Emit a circuit as QASM3 source code.
 pub fn emit(circuit: &Circuit) -> ParseResult<String> {
+    emit_with_options(circuit, &EmitOptions::default())
+}
+
+/// Options for [`emit_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct EmitOptions {
+    /// Write each instruction's metadata as a `//` comment above it, e.g.
+    /// `// origin=SabreRouting`. Comments are dropped by the parser.
+    pub metadata_comments: bool,
+    /// Record the virtual → physical qubit mapping as `// @pragma layout`
+    /// comments and mark compiler-inserted SWAPs (see [`LayoutPragmas`]).
+    pub layout: Option<LayoutPragmas>,
+}
+
+impl EmitOptions {
+    /// Default options: no comments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable metadata comments.
+    #[must_use]
+    pub fn with_metadata_comments(mut self, enabled: bool) -> Self {
+        self.metadata_comments = enabled;
+        self
+    }
+
+    /// Annotate the output with the qubit layout of a compiled circuit.
+    #[must_use]
+    pub fn with_layout(mut self, layout: LayoutPragmas) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+}
+
+/// Emit a circuit as QASM3 source code with the given options.
+pub fn emit_with_options(circuit: &Circuit, options: &EmitOptions) -> ParseResult<String> {
     let mut emitter = Emitter::new();
+    emitter.metadata_comments = options.metadata_comments;
+    emitter.layout.clone_from(&options.layout);
     emitter.emit_circuit(circuit)
 }
 
@@ -25,11 +66,22 @@ pub fn emit_qasm2(circuit: &Circuit) -> ParseResult<String> {
     emitter.emit_circuit(circuit)
 }
 
+fn is_swap(instruction: &Instruction) -> bool {
+    matches!(
+        &instruction.kind,
+        InstructionKind::Gate(gate) if matches!(gate.kind, GateKind::Standard(StandardGate::Swap))
+    )
+}
+
 /// QASM3 emitter.
 struct Emitter {
     output: String,
-    // TODO: Use indent field for nested structure formatting
+    /// Nesting depth of `if`/`else` blocks.
     indent: usize,
+    /// Write instruction metadata as comments.
+    metadata_comments: bool,
+    /// Layout pragmas to write, if the circuit was compiled for a device.
+    layout: Option<LayoutPragmas>,
 }
 
 #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
@@ -38,6 +90,8 @@ impl Emitter {
         Self {
             output: String::new(),
             indent: 0,
+            metadata_comments: false,
+            layout: None,
         }
     }
 
@@ -48,6 +102,16 @@ impl Emitter {
         self.writeln("");
 
         self.emit_nonstandard_gate_defs(circuit);
+        self.emit_custom_gate_defs(circuit)?;
+
+        // Unbound symbolic parameters, bound at submission time
+        let parameters = circuit.parameters();
+        for name in &parameters {
+            self.writeln(&format!("input float[64] {name};"));
+        }
+        if !parameters.is_empty() {
+            self.writeln("");
+        }
 
         // Qubit declarations
         let num_qubits = circuit.num_qubits();
@@ -65,6 +129,17 @@ impl Emitter {
             self.writeln("");
         }
 
+        // Virtual → physical mapping of a compiled circuit
+        if let Some(layout) = &self.layout {
+            let lines = layout.header_lines();
+            for line in &lines {
+                self.writeln(line);
+            }
+            if !lines.is_empty() {
+                self.writeln("");
+            }
+        }
+
         // Instructions
         for (_, instruction) in circuit.dag().topological_ops() {
             self.emit_instruction(instruction)?;
@@ -76,8 +151,8 @@ impl Emitter {
     /// Emit inline `gate` definitions for gates that are not part of
     /// `stdgates.inc`, so the output is self-contained valid QASM3.
     ///
-    /// The definitions are exact up to global phase, which is unobservable
-    /// for uncontrolled use (all Arvak circuits emit these gates uncontrolled).
+    /// The definitions are exact except `sxdg`, which is off by a global
+    /// phase; controlled `sxdg` gates are therefore emitted decomposed.
     fn emit_nonstandard_gate_defs(&mut self, circuit: &Circuit) {
         let mut needs_sxdg = false;
         let mut needs_iswap = false;
@@ -87,19 +162,22 @@ impl Emitter {
         let mut needs_prx = false;
         let mut needs_ecr = false;
 
-        for (_, inst) in circuit.dag().topological_ops() {
-            if let InstructionKind::Gate(gate) = &inst.kind {
-                match &gate.kind {
-                    GateKind::Standard(StandardGate::SXdg) => needs_sxdg = true,
-                    GateKind::Standard(StandardGate::ISwap) => needs_iswap = true,
-                    GateKind::Standard(StandardGate::RXX(_)) => needs_rxx = true,
-                    GateKind::Standard(StandardGate::RYY(_)) => needs_ryy = true,
-                    GateKind::Standard(StandardGate::RZZ(_)) => needs_rzz = true,
-                    GateKind::Standard(StandardGate::PRX(_, _)) => needs_prx = true,
-                    GateKind::Standard(StandardGate::ECR) => needs_ecr = true,
-                    _ => {}
+        // Include gates nested inside if/else blocks and custom gate bodies.
+        for (_, top) in circuit.dag().topological_ops() {
+            for_each_instruction(top, &mut |inst| {
+                if let InstructionKind::Gate(gate) = &inst.kind {
+                    match unmodified(&gate.kind) {
+                        GateKind::Standard(StandardGate::SXdg) => needs_sxdg = true,
+                        GateKind::Standard(StandardGate::ISwap) => needs_iswap = true,
+                        GateKind::Standard(StandardGate::RXX(_)) => needs_rxx = true,
+                        GateKind::Standard(StandardGate::RYY(_)) => needs_ryy = true,
+                        GateKind::Standard(StandardGate::RZZ(_)) => needs_rzz = true,
+                        GateKind::Standard(StandardGate::PRX(_, _)) => needs_prx = true,
+                        GateKind::Standard(StandardGate::ECR) => needs_ecr = true,
+                        _ => {}
+                    }
                 }
-            }
+            });
         }
 
         let any = needs_sxdg
@@ -154,17 +232,112 @@ impl Emitter {
         }
     }
 
+    /// Emit a `gate` definition for every custom gate that carries one.
+    ///
+    /// Definitions are emitted before their first use, nested ones first.
+    /// Custom gates without a definition are emitted as opaque calls.
+    fn emit_custom_gate_defs(&mut self, circuit: &Circuit) -> ParseResult<()> {
+        let mut defined: Vec<&CustomGate> = Vec::new();
+        for (_, top) in circuit.dag().topological_ops() {
+            collect_defined_gates(top, &mut defined);
+        }
+        if defined.is_empty() {
+            return Ok(());
+        }
+
+        for custom in defined {
+            self.emit_custom_gate_def(custom)?;
+        }
+        self.writeln("");
+        Ok(())
+    }
+
+    fn emit_custom_gate_def(&mut self, custom: &CustomGate) -> ParseResult<()> {
+        let Some(definition) = &custom.definition else {
+            return Ok(());
+        };
+        let qubits: Vec<String> = (0..custom.num_qubits).map(|i| format!("q{i}")).collect();
+        let params = if definition.params.is_empty() {
+            String::new()
+        } else {
+            format!("({})", definition.params.join(", "))
+        };
+        self.writeln(&format!(
+            "gate {}{params} {} {{",
+            custom.name,
+            qubits.join(", ")
+        ));
+        self.indent += 1;
+        for inst in &definition.body {
+            let operands = inst
+                .qubits
+                .iter()
+                .map(|q| qubits[q.0 as usize].as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            match &inst.kind {
+                InstructionKind::Gate(gate) => {
+                    let name = self.emit_gate_name(&gate.kind);
+                    let params = self.emit_gate_params(&gate.kind);
+                    if params.is_empty() {
+                        self.writeln(&format!("{name} {operands};"));
+                    } else {
+                        self.writeln(&format!("{name}({params}) {operands};"));
+                    }
+                }
+                InstructionKind::Barrier => self.writeln(&format!("barrier {operands};")),
+                _ => {
+                    return Err(ParseError::Generic(format!(
+                        "gate '{}' has a non-unitary instruction in its definition",
+                        custom.name
+                    )));
+                }
+            }
+        }
+        self.indent -= 1;
+        self.writeln("}");
+        Ok(())
+    }
+
     fn emit_instruction(&mut self, instruction: &Instruction) -> ParseResult<()> {
+        if self.metadata_comments && !instruction.metadata.is_empty() {
+            let entries: Vec<String> = instruction
+                .metadata
+                .iter()
+                .map(|(key, value)| format!("{key}={}", value.replace(['\n', '\r'], " ")))
+                .collect();
+            self.writeln(&format!("// {}", entries.join(" ")));
+        }
+        if self.layout.is_some() && is_swap(instruction) {
+            if let Some(origin) = instruction.origin() {
+                self.writeln(&LayoutPragmas::swap_marker(origin));
+            }
+        }
         match &instruction.kind {
             InstructionKind::Gate(gate) => {
+                if let GateKind::Modified(modified) = &gate.kind {
+                    if modified.num_controls() > 0
+                        && matches!(modified.base(), GateKind::Standard(StandardGate::SXdg))
+                    {
+                        return self.emit_expanded(instruction, modified);
+                    }
+                }
                 let name = self.emit_gate_name(&gate.kind);
                 let params = self.emit_gate_params(&gate.kind);
                 let qubits = self.emit_qubits(&instruction.qubits);
-
+                let prefix = gate
+                    .condition
+                    .as_ref()
+                    .map(|c| format!("if ({}) ", self.emit_condition(c)))
+                    .unwrap_or_default();
+
+                if let Some(duration) = gate.duration {
+                    self.writeln(&format!("@arvak.duration {duration}dt"));
+                }
                 if params.is_empty() {
-                    self.writeln(&format!("{name} {qubits};"));
+                    self.writeln(&format!("{prefix}{name} {qubits};"));
                 } else {
-                    self.writeln(&format!("{name}({params}) {qubits};"));
+                    self.writeln(&format!("{prefix}{name}({params}) {qubits};"));
                 }
             }
 
@@ -215,11 +388,65 @@ impl Emitter {
                 let qubits = self.emit_qubits(&instruction.qubits);
                 self.writeln(&format!("// @pragma noise_{role}({model}) {qubits};"));
             }
+
+            InstructionKind::IfElse {
+                condition,
+                then_body,
+                else_body,
+            } => {
+                self.writeln(&format!("if ({}) {{", self.emit_condition(condition)));
+                self.emit_block(then_body)?;
+                if !else_body.is_empty() {
+                    self.writeln("} else {");
+                    self.emit_block(else_body)?;
+                }
+                self.writeln("}");
+            }
+
+            InstructionKind::WhileLoop {
+                condition,
+                body,
+                max_iterations,
+            } => {
+                // QASM3 loops are unbounded; the bound travels as an annotation.
+                self.writeln(&format!("@arvak.max_iterations {max_iterations}"));
+                self.writeln(&format!("while ({}) {{", self.emit_condition(condition)));
+                self.emit_block(body)?;
+                self.writeln("}");
+            }
         }
 
         Ok(())
     }
 
+    /// Emit the decomposition of a modified gate, keeping the instruction's
+    /// condition and duration on every gate.
+    fn emit_expanded(
+        &mut self,
+        instruction: &Instruction,
+        modified: &ModifiedGate,
+    ) -> ParseResult<()> {
+        let InstructionKind::Gate(gate) = &instruction.kind else {
+            return Ok(());
+        };
+        for mut step in modified.expand(&instruction.qubits)? {
+            if let InstructionKind::Gate(g) = &mut step.kind {
+                g.condition.clone_from(&gate.condition);
+                g.duration = gate.duration;
+            }
+            self.emit_instruction(&step)?;
+        }
+        Ok(())
+    }
+
+    /// Emit the body of an `if`/`else` branch or loop one level deeper.
+    fn emit_block(&mut self, body: &[Instruction]) -> ParseResult<()> {
+        self.indent += 1;
+        let result = body.iter().try_for_each(|inst| self.emit_instruction(inst));
+        self.indent -= 1;
+        result
+    }
+
     fn emit_gate_name(&self, kind: &GateKind) -> String {
         match kind {
             GateKind::Standard(std) => match std {
@@ -255,10 +482,28 @@ impl Emitter {
                 StandardGate::RZZ(_) => "rzz".into(),
                 StandardGate::CCX => "ccx".into(),
                 StandardGate::CSwap => "cswap".into(),
+                // Multi-controlled gates use the QASM3 control modifier.
+                StandardGate::MCX(n) => format!("ctrl({n}) @ x"),
+                StandardGate::MCZ(n) => format!("ctrl({n}) @ z"),
+                StandardGate::MCP(n, _) => format!("ctrl({n}) @ p"),
                 StandardGate::PRX(_, _) => "prx".into(),
                 StandardGate::ECR => "ecr".into(),
             },
             GateKind::Custom(custom) => custom.name.clone(),
+            GateKind::Modified(modified) => {
+                let mut name = match modified.num_controls() {
+                    0 => String::new(),
+                    1 => "ctrl @ ".into(),
+                    n => format!("ctrl({n}) @ "),
+                };
+                let power = modified.power();
+                if power == -1.0 {
+                    name.push_str("inv @ ");
+                } else if power != 1.0 {
+                    name.push_str(&format!("pow({power}) @ "));
+                }
+                name + &self.emit_gate_name(modified.base())
+            }
         }
     }
 
@@ -282,6 +527,7 @@ impl Emitter {
                 .map(|p| self.emit_param(p))
                 .collect::<Vec<_>>()
                 .join(", "),
+            GateKind::Modified(modified) => self.emit_gate_params(modified.base()),
         }
     }
 
@@ -331,6 +577,19 @@ impl Emitter {
             .join(", ")
     }
 
+    fn emit_condition(&self, condition: &ClassicalCondition) -> String {
+        match condition.clbits.as_slice() {
+            [] => format!("{} == {}", condition.register, condition.value),
+            [bit] => format!("c[{}] == {}", bit.0, condition.value),
+            bits => bits
+                .iter()
+                .enumerate()
+                .map(|(i, bit)| format!("c[{}] == {}", bit.0, (condition.value >> i) & 1))
+                .collect::<Vec<_>>()
+                .join(" && "),
+        }
+    }
+
     fn emit_clbits(&self, clbits: &[arvak_ir::ClbitId]) -> String {
         if clbits.len() == 1 {
             format!("c[{}]", clbits[0].0)
@@ -351,6 +610,51 @@ impl Emitter {
     }
 }
 
+/// The gate a modified gate applies its modifiers to, or `kind` itself.
+fn unmodified(kind: &GateKind) -> &GateKind {
+    match kind {
+        GateKind::Modified(modified) => modified.base(),
+        kind => kind,
+    }
+}
+
+/// Visit `inst` and every instruction nested in it: if/else branches and
+/// the bodies of custom gate definitions.
+fn for_each_instruction<'a>(inst: &'a Instruction, f: &mut dyn FnMut(&'a Instruction)) {
+    inst.walk(&mut |nested| {
+        f(nested);
+        if let InstructionKind::Gate(gate) = &nested.kind {
+            if let GateKind::Custom(custom) = unmodified(&gate.kind) {
+                if let Some(definition) = &custom.definition {
+                    for body_inst in &definition.body {
+                        for_each_instruction(body_inst, f);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Collect the custom gates with a definition used in `inst`, each after
+/// the gates its own body uses and deduplicated by name.
+fn collect_defined_gates<'a>(inst: &'a Instruction, out: &mut Vec<&'a CustomGate>) {
+    inst.walk(&mut |nested| {
+        if let InstructionKind::Gate(gate) = &nested.kind {
+            if let GateKind::Custom(custom) = unmodified(&gate.kind) {
+                if let Some(definition) = &custom.definition {
+                    if out.iter().any(|d| d.name == custom.name) {
+                        return;
+                    }
+                    for body_inst in &definition.body {
+                        collect_defined_gates(body_inst, out);
+                    }
+                    out.push(custom);
+                }
+            }
+        }
+    });
+}
+
 /// QASM 2.0 emitter.
 struct Qasm2Emitter {
     output: String,
@@ -436,14 +740,42 @@ impl Qasm2Emitter {
     fn emit_instruction(&mut self, instruction: &Instruction) -> ParseResult<()> {
         match &instruction.kind {
             InstructionKind::Gate(gate) => {
+                if let GateKind::Standard(std) = &gate.kind {
+                    if std.num_controls().is_some() {
+                        return Err(ParseError::Generic(format!(
+                            "OpenQASM 2.0 cannot express multi-controlled gate '{}'; \
+                             decompose it first",
+                            std.name()
+                        )));
+                    }
+                }
+                if let GateKind::Modified(modified) = &gate.kind {
+                    return Err(ParseError::Generic(format!(
+                        "OpenQASM 2.0 cannot express gate modifiers in '{}'; \
+                         decompose it first",
+                        modified.name()
+                    )));
+                }
                 let name = self.emit_gate_name(&gate.kind);
                 let params = self.emit_gate_params(&gate.kind);
                 let qubits = self.emit_qubits(&instruction.qubits);
+                // QASM 2.0 can only compare a whole register.
+                let prefix = match &gate.condition {
+                    None => String::new(),
+                    Some(c) if c.clbits.is_empty() => {
+                        format!("if({}=={}) ", c.register, c.value)
+                    }
+                    Some(_) => {
+                        return Err(ParseError::Generic(
+                            "OpenQASM 2.0 cannot express conditions on individual bits".into(),
+                        ));
+                    }
+                };
 
                 if params.is_empty() {
-                    self.writeln(&format!("{name} {qubits};"));
+                    self.writeln(&format!("{prefix}{name} {qubits};"));
                 } else {
-                    self.writeln(&format!("{name}({params}) {qubits};"));
+                    self.writeln(&format!("{prefix}{name}({params}) {qubits};"));
                 }
             }
 
@@ -491,6 +823,18 @@ impl Qasm2Emitter {
                 let qubits = self.emit_qubits(&instruction.qubits);
                 self.writeln(&format!("// @pragma noise_{role}({model}) {qubits};"));
             }
+
+            InstructionKind::IfElse { .. } => {
+                return Err(ParseError::Generic(
+                    "OpenQASM 2.0 cannot express if/else blocks".into(),
+                ));
+            }
+
+            InstructionKind::WhileLoop { .. } => {
+                return Err(ParseError::Generic(
+                    "OpenQASM 2.0 cannot express while loops".into(),
+                ));
+            }
         }
 
         Ok(())
@@ -531,10 +875,16 @@ impl Qasm2Emitter {
                 StandardGate::RZZ(_) => "rzz".into(),
                 StandardGate::CCX => "ccx".into(),
                 StandardGate::CSwap => "cswap".into(),
+                // Rejected in `emit_instruction`; QASM 2.0 has no control modifier.
+                StandardGate::MCX(_) => "mcx".into(),
+                StandardGate::MCZ(_) => "mcz".into(),
+                StandardGate::MCP(_, _) => "mcp".into(),
                 StandardGate::PRX(_, _) => "prx".into(),
                 StandardGate::ECR => "ecr".into(),
             },
             GateKind::Custom(custom) => custom.name.clone(),
+            // Rejected in `emit_instruction`.
+            GateKind::Modified(modified) => modified.name().into(),
         }
     }
 
@@ -559,6 +909,7 @@ impl Qasm2Emitter {
                 .map(|p| self.emit_param(p))
                 .collect::<Vec<_>>()
                 .join(", "),
+            GateKind::Modified(modified) => self.emit_gate_params(modified.base()),
         }
     }
 
@@ -616,7 +967,7 @@ impl Qasm2Emitter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arvak_ir::QubitId;
+    use arvak_ir::{Gate, QubitId};
 
     #[test]
     fn test_emit_bell_state() {
@@ -673,6 +1024,60 @@ mod tests {
         assert!(qasm.find("gate prx(").unwrap() < qasm.find("qubit[2]").unwrap());
     }
 
+    #[test]
+    fn test_roundtrip_custom_gate_definition() {
+        use arvak_ir::{CustomGate, GateDefinition};
+
+        let mut zz_body = Circuit::with_size("zz", 2, 0);
+        zz_body
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .rz(ParameterExpression::symbol("t"), QubitId(1))
+            .unwrap()
+            .cx(QubitId(0), QubitId(1))
+            .unwrap();
+        let zz = |t: f64| {
+            CustomGate::new("zz", 2)
+                .with_params(vec![ParameterExpression::constant(t)])
+                .with_definition(GateDefinition::from_circuit(vec!["t".into()], &zz_body).unwrap())
+                .unwrap()
+        };
+        let mut layer_body = Circuit::with_size("layer", 3, 0);
+        layer_body
+            .gate(zz(0.5), [QubitId(0), QubitId(1)])
+            .unwrap()
+            .sxdg(QubitId(2))
+            .unwrap();
+        let layer = CustomGate::new("layer", 3)
+            .with_definition(GateDefinition::from_circuit(vec![], &layer_body).unwrap())
+            .unwrap();
+
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit
+            .gate(layer, [QubitId(2), QubitId(0), QubitId(1)])
+            .unwrap()
+            .gate(zz(0.25), [QubitId(0), QubitId(1)])
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(qasm.contains("gate zz(t) q0, q1 {\n    cx q0, q1;\n    rz(t) q1;"));
+        assert_eq!(qasm.matches("gate zz(").count(), 1);
+        // Definitions precede their uses; nonstandard gates used only in a
+        // body still get their own definition.
+        assert!(qasm.find("gate sxdg").unwrap() < qasm.find("gate layer").unwrap());
+        assert!(qasm.find("gate zz(").unwrap() < qasm.find("gate layer").unwrap());
+        assert!(qasm.contains("layer q[2], q[0], q[1];"));
+
+        let reparsed = crate::parse(&qasm).unwrap();
+        assert_eq!(reparsed.dag().num_ops(), 2);
+        for (_, inst) in reparsed.dag().topological_ops() {
+            let GateKind::Custom(custom) = &inst.as_gate().unwrap().kind else {
+                panic!("expected a custom gate");
+            };
+            assert!(custom.definition.is_some());
+        }
+    }
+
     #[test]
     fn test_emit_no_defs_for_stdgates_only() {
         let circuit = Circuit::bell().unwrap();
@@ -732,6 +1137,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_roundtrip_gate_durations() {
+        use arvak_ir::ClbitId;
+
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.sx(QubitId(0)).unwrap().lasting(32).unwrap();
+        circuit
+            .cx(QubitId(0), QubitId(1))
+            .unwrap()
+            .lasting(300)
+            .unwrap();
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .x(QubitId(1))
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap()
+            .lasting(32)
+            .unwrap();
+        circuit.h(QubitId(1)).unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(qasm.contains("@arvak.duration 32dt\nsx q[0];"), "{qasm}");
+        assert!(
+            qasm.contains("@arvak.duration 32dt\nif (c[0] == 1) x q[1];"),
+            "{qasm}"
+        );
+
+        let durations = |c: &Circuit| -> Vec<_> {
+            c.dag()
+                .topological_ops()
+                .map(|(_, inst)| (inst.name().to_string(), inst.duration()))
+                .collect()
+        };
+        let reparsed = crate::parse(&qasm).unwrap();
+        assert_eq!(durations(&reparsed), durations(&circuit));
+    }
+
     #[test]
     fn test_emit_parameterized() {
         let mut circuit = Circuit::with_size("test", 1, 0);
@@ -741,6 +1184,20 @@ mod tests {
         assert!(qasm.contains("rx(pi/2) q[0];"));
     }
 
+    #[test]
+    fn test_roundtrip_input_parameters() {
+        let mut circuit = Circuit::with_size("test", 1, 0);
+        circuit
+            .rx(ParameterExpression::symbol("theta"), QubitId(0))
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(qasm.contains("input float[64] theta;"));
+
+        let reparsed = crate::parse(&qasm).unwrap();
+        assert_eq!(reparsed.parameters(), ["theta"]);
+    }
+
     #[test]
     fn test_roundtrip() {
         let original = r"OPENQASM 3.0;
@@ -759,6 +1216,234 @@ cx q[0], q[1];
         assert_eq!(circuit.depth(), circuit2.depth());
     }
 
+    #[test]
+    fn test_emit_conditioned_gate() {
+        use arvak_ir::ClbitId;
+
+        let mut circuit = Circuit::with_size("test", 2, 2);
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        circuit
+            .x(QubitId(1))
+            .unwrap()
+            .conditioned_on(ClbitId(0), 1)
+            .unwrap();
+        circuit
+            .rz(0.5, QubitId(1))
+            .unwrap()
+            .conditioned_on_bits([ClbitId(0), ClbitId(1)], 2)
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(qasm.contains("if (c[0] == 1) x q[1];"), "{qasm}");
+        assert!(
+            qasm.contains("if (c[0] == 0 && c[1] == 1) rz(0.500000) q[1];"),
+            "{qasm}"
+        );
+
+        // QASM 2.0 has no per-bit conditions.
+        assert!(emit_qasm2(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_emit_if_else_block() {
+        use arvak_ir::{ClassicalCondition, ClbitId};
+
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit.measure(QubitId(0), ClbitId(0)).unwrap();
+        let mut then_body = Circuit::with_size("then", 2, 1);
+        then_body.x(QubitId(1)).unwrap().sxdg(QubitId(1)).unwrap();
+        let mut else_body = Circuit::with_size("else", 2, 1);
+        else_body.h(QubitId(1)).unwrap();
+        circuit
+            .if_else(
+                ClassicalCondition::on_clbit(ClbitId(0), 1),
+                &then_body,
+                Some(&else_body),
+            )
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(
+            qasm.contains(
+                "if (c[0] == 1) {\n    x q[1];\n    sxdg q[1];\n} else {\n    h q[1];\n}\n"
+            ),
+            "{qasm}"
+        );
+        // Gates nested in blocks still get their definitions.
+        assert!(qasm.contains("gate sxdg"), "{qasm}");
+
+        assert!(emit_qasm2(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_emit_while_loop() {
+        use arvak_ir::{ClassicalCondition, ClbitId};
+
+        let mut attempt = Circuit::with_size("attempt", 2, 1);
+        attempt.h(QubitId(1)).unwrap();
+        attempt.measure(QubitId(1), ClbitId(0)).unwrap();
+        let mut circuit = Circuit::with_size("test", 2, 1);
+        circuit
+            .while_loop(ClassicalCondition::on_clbit(ClbitId(0), 1), &attempt, 4)
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(
+            qasm.contains(
+                "@arvak.max_iterations 4\nwhile (c[0] == 1) {\n    h q[1];\n    c[0] = measure q[1];\n}\n"
+            ),
+            "{qasm}"
+        );
+        assert!(emit_qasm2(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_emit_metadata_comments() {
+        use arvak_ir::Instruction;
+
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit
+            .dag_mut()
+            .apply(
+                Instruction::two_qubit_gate(StandardGate::Swap, QubitId(0), QubitId(1))
+                    .with_origin("SabreRouting")
+                    .with_label("swap\n0"),
+            )
+            .unwrap();
+
+        let plain = emit(&circuit).unwrap();
+        assert!(!plain.contains("//"), "{plain}");
+
+        let options = EmitOptions::new().with_metadata_comments(true);
+        let qasm = emit_with_options(&circuit, &options).unwrap();
+        assert!(
+            qasm.contains("// label=swap 0 origin=SabreRouting\nswap q[0], q[1];\n"),
+            "{qasm}"
+        );
+        assert_eq!(crate::parse(&qasm).unwrap().depth(), 1);
+    }
+
+    #[test]
+    fn test_emit_layout_pragmas() {
+        use arvak_ir::{Instruction, QubitId};
+
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit.cx(QubitId(0), QubitId(1)).unwrap();
+        circuit
+            .dag_mut()
+            .apply(
+                Instruction::two_qubit_gate(StandardGate::Swap, QubitId(1), QubitId(2))
+                    .with_origin("SabreRouting"),
+            )
+            .unwrap();
+        circuit.swap(QubitId(0), QubitId(1)).unwrap();
+
+        let layout = crate::LayoutPragmas::new([(QubitId(0), 0), (QubitId(1), 1)])
+            .with_final_layout([(QubitId(0), 0), (QubitId(1), 2)]);
+        let qasm =
+            emit_with_options(&circuit, &EmitOptions::new().with_layout(layout.clone())).unwrap();
+        assert!(
+            qasm.contains(
+                "// @pragma layout initial 0->$0 1->$1\n// @pragma layout final 0->$0 1->$2\n"
+            ),
+            "{qasm}"
+        );
+        assert!(
+            qasm.contains(
+                "// @pragma routing_swap SabreRouting\nswap q[1], q[2];\nswap q[0], q[1];"
+            ),
+            "{qasm}"
+        );
+
+        let parsed = crate::parse_layout(&qasm).unwrap().unwrap();
+        assert_eq!(parsed.initial, layout.initial);
+        assert_eq!(parsed.final_layout, layout.final_layout);
+        assert_eq!(parsed.routing_swaps, 1);
+        assert_eq!(crate::parse(&qasm).unwrap().dag().num_ops(), 3);
+    }
+
+    #[test]
+    fn test_roundtrip_multi_controlled() {
+        let mut circuit = Circuit::with_size("test", 5, 0);
+        let controls = [QubitId(0), QubitId(1), QubitId(2)];
+        circuit.mcx(&controls, QubitId(4)).unwrap();
+        circuit.mcz(&controls, QubitId(3)).unwrap();
+        circuit.mcp(0.5, &controls[..2], QubitId(4)).unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(
+            qasm.contains("ctrl(3) @ x q[0], q[1], q[2], q[4];"),
+            "{qasm}"
+        );
+        assert!(
+            qasm.contains("ctrl(3) @ z q[0], q[1], q[2], q[3];"),
+            "{qasm}"
+        );
+        assert!(
+            qasm.contains("ctrl(2) @ p(0.500000) q[0], q[1], q[4];"),
+            "{qasm}"
+        );
+
+        let reparsed = crate::parse(&qasm).unwrap();
+        let original: Vec<_> = circuit.dag().topological_ops().map(|(_, i)| i).collect();
+        let roundtrip: Vec<_> = reparsed.dag().topological_ops().map(|(_, i)| i).collect();
+        assert_eq!(original, roundtrip);
+
+        assert!(emit_qasm2(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_modifiers() {
+        let mut circuit = Circuit::with_size("test", 3, 0);
+        circuit
+            .gate(
+                Gate::standard(StandardGate::H).controlled(2),
+                [QubitId(0), QubitId(1), QubitId(2)],
+            )
+            .unwrap()
+            .gate(Gate::standard(StandardGate::S).power(-1.0), [QubitId(1)])
+            .unwrap()
+            .gate(
+                Gate::standard(StandardGate::Rz(0.5.into()))
+                    .power(0.5)
+                    .controlled(1),
+                [QubitId(1), QubitId(0)],
+            )
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(qasm.contains("ctrl(2) @ h q[0], q[1], q[2];"), "{qasm}");
+        assert!(qasm.contains("inv @ s q[1];"), "{qasm}");
+        assert!(
+            qasm.contains("ctrl @ pow(0.5) @ rz(0.500000) q[1], q[0];"),
+            "{qasm}"
+        );
+
+        let reparsed = crate::parse(&qasm).unwrap();
+        let original: Vec<_> = circuit.dag().topological_ops().map(|(_, i)| i).collect();
+        let roundtrip: Vec<_> = reparsed.dag().topological_ops().map(|(_, i)| i).collect();
+        assert_eq!(original, roundtrip);
+
+        assert!(emit_qasm2(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_emit_controlled_sxdg_decomposed() {
+        // The emitted sxdg definition drops a global phase, which a control
+        // would turn into a relative one.
+        let mut circuit = Circuit::with_size("test", 2, 0);
+        circuit
+            .gate(
+                Gate::standard(StandardGate::SXdg).controlled(1),
+                [QubitId(0), QubitId(1)],
+            )
+            .unwrap();
+
+        let qasm = emit(&circuit).unwrap();
+        assert!(!qasm.contains("@ sxdg"), "{qasm}");
+        assert!(crate::parse(&qasm).is_ok());
+    }
+
     #[test]
     fn test_emit_qasm2_bell_state() {
         let circuit = Circuit::bell().unwrap();
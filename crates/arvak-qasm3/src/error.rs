@@ -38,6 +38,10 @@ pub enum ParseError {
     #[error("Duplicate declaration: {0}")]
     DuplicateDeclaration(String),
 
+    /// Include of a file other than the built-in gate libraries.
+    #[error("Unsupported include at line {line}: '{path}' (only stdgates.inc is built in)")]
+    UnsupportedInclude { line: usize, path: String },
+
     /// Invalid gate.
     #[error("Unknown gate: {0}")]
     UnknownGate(String),
@@ -0,0 +1,179 @@
+//! Qubit layout pragmas for compiled circuits.
+//!
+//! A routed circuit addresses physical qubits: `q[i]` in the emitted source
+//! is device qubit `$i`. The compiler's virtual → physical mapping is needed
+//! to read the results back, so [`emit_with_options`] can record it as
+//! comments:
+//!
+//! ```text
+//! // @pragma layout initial 0->$2 1->$0 2->$1
+//! // @pragma layout final 0->$2 1->$1 2->$0
+//! ```
+//!
+//! SWAPs inserted by a compiler pass are marked with
+//! `// @pragma routing_swap <pass>` on the line above. [`parse`] and other
+//! QASM tools ignore the comments; [`parse_layout`] reads them back.
+//!
+//! [`emit_with_options`]: crate::emit_with_options
+//! [`parse`]: crate::parse
+
+use arvak_ir::QubitId;
+
+use crate::error::{ParseError, ParseResult};
+
+const LAYOUT_PRAGMA: &str = "// @pragma layout";
+const SWAP_PRAGMA: &str = "// @pragma routing_swap";
+
+/// Virtual → physical qubit mappings recorded in compiled QASM.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutPragmas {
+    /// Mapping at the start of the circuit, as chosen by the layout pass.
+    pub initial: Vec<(QubitId, u32)>,
+    /// Mapping at the end of the circuit, after the routing SWAPs.
+    pub final_layout: Vec<(QubitId, u32)>,
+    /// Number of SWAPs marked as inserted by the compiler. Filled in by
+    /// [`parse_layout`]; the emitter marks SWAPs from their metadata instead.
+    pub routing_swaps: usize,
+}
+
+impl LayoutPragmas {
+    /// Mappings for a circuit whose layout did not change during routing.
+    pub fn new(initial: impl IntoIterator<Item = (QubitId, u32)>) -> Self {
+        let initial = sorted(initial);
+        Self {
+            final_layout: initial.clone(),
+            initial,
+            routing_swaps: 0,
+        }
+    }
+
+    /// Set the mapping at the end of the circuit.
+    #[must_use]
+    pub fn with_final_layout(mut self, layout: impl IntoIterator<Item = (QubitId, u32)>) -> Self {
+        self.final_layout = sorted(layout);
+        self
+    }
+
+    /// Physical qubit holding virtual qubit `virtual_qubit` at the end of
+    /// the circuit, i.e. the qubit whose measurement gives its result.
+    pub fn final_physical(&self, virtual_qubit: QubitId) -> Option<u32> {
+        self.final_layout
+            .iter()
+            .find(|(v, _)| *v == virtual_qubit)
+            .map(|&(_, p)| p)
+    }
+
+    /// The `// @pragma layout` lines for these mappings.
+    pub(crate) fn header_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !self.initial.is_empty() {
+            lines.push(format!(
+                "{LAYOUT_PRAGMA} initial {}",
+                format_map(&self.initial)
+            ));
+        }
+        if !self.final_layout.is_empty() {
+            lines.push(format!(
+                "{LAYOUT_PRAGMA} final {}",
+                format_map(&self.final_layout)
+            ));
+        }
+        lines
+    }
+
+    /// The marker written above a SWAP inserted by `origin`.
+    pub(crate) fn swap_marker(origin: &str) -> String {
+        format!("{SWAP_PRAGMA} {origin}")
+    }
+}
+
+/// Read the layout pragmas written by [`emit_with_options`] from QASM source.
+///
+/// Returns `None` if the source carries no layout pragma, e.g. because it
+/// was not compiled for a device or was written by another tool.
+///
+/// [`emit_with_options`]: crate::emit_with_options
+pub fn parse_layout(source: &str) -> ParseResult<Option<LayoutPragmas>> {
+    let mut pragmas = LayoutPragmas::default();
+    let mut found = false;
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix(LAYOUT_PRAGMA) {
+            let (which, map) = rest.trim().split_once(' ').unwrap_or((rest.trim(), ""));
+            let map = parse_map(map, index + 1)?;
+            match which {
+                "initial" => pragmas.initial = map,
+                "final" => pragmas.final_layout = map,
+                other => {
+                    return Err(ParseError::Generic(format!(
+                        "line {}: unknown layout pragma '{other}'",
+                        index + 1
+                    )));
+                }
+            }
+            found = true;
+        } else if line.starts_with(SWAP_PRAGMA) {
+            pragmas.routing_swaps += 1;
+        }
+    }
+    Ok(found.then_some(pragmas))
+}
+
+fn sorted(map: impl IntoIterator<Item = (QubitId, u32)>) -> Vec<(QubitId, u32)> {
+    let mut map: Vec<_> = map.into_iter().collect();
+    map.sort_by_key(|(v, _)| v.0);
+    map
+}
+
+fn format_map(map: &[(QubitId, u32)]) -> String {
+    map.iter()
+        .map(|(v, p)| format!("{}->${p}", v.0))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_map(text: &str, line: usize) -> ParseResult<Vec<(QubitId, u32)>> {
+    let invalid =
+        |entry: &str| ParseError::Generic(format!("line {line}: invalid layout entry '{entry}'"));
+    let mut map: Vec<(QubitId, u32)> = Vec::new();
+    for entry in text.split_whitespace() {
+        let (v, p) = entry.split_once("->$").ok_or_else(|| invalid(entry))?;
+        let v = v.parse().map_err(|_| invalid(entry))?;
+        let p = p.parse().map_err(|_| invalid(entry))?;
+        if map.iter().any(|&(ov, op)| ov.0 == v || op == p) {
+            return Err(ParseError::Generic(format!(
+                "line {line}: layout maps qubit {v} or ${p} twice"
+            )));
+        }
+        map.push((QubitId(v), p));
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_pragma_roundtrip() {
+        let pragmas = LayoutPragmas::new([(QubitId(1), 0), (QubitId(0), 2)])
+            .with_final_layout([(QubitId(0), 0), (QubitId(1), 2)]);
+        let source = pragmas.header_lines().join("\n");
+        assert_eq!(
+            source,
+            "// @pragma layout initial 0->$2 1->$0\n// @pragma layout final 0->$0 1->$2"
+        );
+
+        let parsed = parse_layout(&source).unwrap().unwrap();
+        assert_eq!(parsed, pragmas);
+        assert_eq!(parsed.final_physical(QubitId(1)), Some(2));
+    }
+
+    #[test]
+    fn test_parse_layout_absent_or_invalid() {
+        assert_eq!(parse_layout("OPENQASM 3.0;\nqubit[2] q;").unwrap(), None);
+        assert!(parse_layout("// @pragma layout initial 0->$1 1->$1").is_err());
+        assert!(parse_layout("// @pragma layout initial 0->1").is_err());
+        assert!(parse_layout("// @pragma layout middle 0->$1").is_err());
+    }
+}
@@ -8,14 +8,21 @@
 //! | Feature | Status | Example |
 //! |---------|--------|---------|
 //! | Version declaration | ✅ | `OPENQASM 3.0;` |
+//! | Standard library include | ✅ | `include "stdgates.inc";` |
 //! | Qubit declarations | ✅ | `qubit[5] q;` |
 //! | Classical bits | ✅ | `bit[5] c;` |
 //! | Standard gates | ✅ | `h q[0];`, `cx q[0], q[1];` |
 //! | Parameterized gates | ✅ | `rx(pi/4) q[0];` |
+//! | Input parameters | ✅ | `input float[64] theta;` |
 //! | Measurements | ✅ | `c = measure q;` |
 //! | Barriers | ✅ | `barrier q;` |
+//! | Gate definitions | ✅ | `gate zz(t) a, b { cx a, b; rz(t) b; cx a, b; }` |
+//! | Global phase | ✅ | `gphase(pi / 2);` |
 //! | Reset | ✅ | `reset q[0];` |
+//! | Constants | ✅ | `const int n = 4;` |
+//! | For loops (unrolled) | ✅ | `for int i in [0:n-1] { ry(0.1 * i) q[i]; }` |
 //! | Comments | ✅ | `// comment` |
+//! | Layout pragmas | ✅ | `// @pragma layout initial 0->$3` |
 //!
 //! # Example: Parsing QASM
 //!
@@ -78,20 +85,44 @@
 //!
 //! Single-qubit: `id`, `x`, `y`, `z`, `h`, `s`, `sdg`, `t`, `tdg`, `sx`, `sxdg`
 //!
-//! Parameterized: `rx(θ)`, `ry(θ)`, `rz(θ)`, `p(θ)`, `u(θ,φ,λ)`
+//! Parameterized: `rx(θ)`, `ry(θ)`, `rz(θ)`, `p(θ)`, `u(θ,φ,λ)`, `u1(λ)`,
+//! `u2(φ,λ)`, `u3(θ,φ,λ)`
 //!
-//! Two-qubit: `cx`, `cy`, `cz`, `swap`, `iswap`, `crz(θ)`, `cp(θ)`
+//! Two-qubit: `cx`, `cy`, `cz`, `ch`, `swap`, `iswap`, `crx(θ)`, `cry(θ)`,
+//! `crz(θ)`, `cp(θ)`, `cu(θ,φ,λ,γ)`
+//!
+//! These cover `stdgates.inc` (and the `qelib1.inc` gates used in `OpenQASM`
+//! 2 exports), so including it is accepted without reading the file. Other
+//! includes are rejected with [`ParseError::UnsupportedInclude`]; inline
+//! their `gate` definitions instead.
+//!
+//! Calls of `gate`-defined gates lower to an [`arvak_ir::CustomGate`] that
+//! carries the lowered body as its definition, so the compiler's basis
+//! translation can expand it. A definition may call any gate declared in
+//! the file except itself; built-in gate names take precedence over
+//! definitions of the same name.
 //!
 //! Three-qubit: `ccx` (Toffoli), `cswap` (Fredkin)
+//!
+//! # Loops
+//!
+//! `for` loops over `[start:end]` or `[start:step:end]` (both ends
+//! inclusive) are unrolled during lowering: each iteration lowers the body
+//! with the loop variable replaced by its value, in qubit and bit indices
+//! (`q[i + 1]`) as well as gate parameters. Range bounds must be integer
+//! expressions of literals, `const` declarations and enclosing loop
+//! variables.
 
 mod ast;
 mod emitter;
 mod error;
+mod layout;
 mod lexer;
 mod parser;
 
-pub use emitter::{emit, emit_qasm2};
+pub use emitter::{EmitOptions, emit, emit_qasm2, emit_with_options};
 pub use error::{ParseError, ParseResult};
+pub use layout::{LayoutPragmas, parse_layout};
 pub use parser::parse;
 
 // Re-export AST types for advanced users
@@ -118,6 +118,14 @@ impl Parser {
                     let args = self.parse_expression_list()?;
                     self.expect(Token::RParen)?;
                     Ok(Expression::FnCall { name, args })
+                } else if self.consume(&Token::LBracket) {
+                    // Indexed register element, e.g. `c[0]` in a condition
+                    let index = self.parse_expression()?;
+                    self.expect(Token::RBracket)?;
+                    Ok(Expression::Index {
+                        target: Box::new(Expression::Identifier(name)),
+                        index: Box::new(index),
+                    })
                 } else {
                     Ok(Expression::Identifier(name))
                 }
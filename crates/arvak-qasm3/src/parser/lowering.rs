@@ -1,19 +1,39 @@
 //! AST-to-Circuit lowering for QASM3.
 
-use arvak_ir::{Circuit, ClbitId, ParameterExpression, QubitId};
+use std::cell::Cell;
+
+use arvak_ir::{
+    Circuit, ClassicalCondition, ClbitId, CustomGate, GateDefinition, Instruction, InstructionKind,
+    ParameterExpression, QubitId,
+};
 use rustc_hash::FxHashMap;
 
-use crate::ast::{BinOp, BitRef, Expression, GateCall, Program, QubitRef, Statement};
+use crate::ast::{
+    BinOp, BitRef, ClassicalType, Expression, GateCall, GateModifier, Program, QubitRef, Range,
+    Statement,
+};
 use crate::error::{ParseError, ParseResult};
 
+/// Maximum number of statements `for` loops may unroll to, counted over
+/// the whole program so that nested loops share one budget.
+const MAX_UNROLLED_STATEMENTS: usize = 1 << 20;
+
 /// Lower an AST Program to a Circuit.
 pub(crate) fn lower_to_circuit(program: &Program) -> ParseResult<Circuit> {
     let mut lowerer = Lowerer::new();
     lowerer.lower(program)
 }
 
+/// A user `gate` definition from the program.
+#[derive(Clone, Copy)]
+struct GateDefAst<'a> {
+    params: &'a [String],
+    qubits: &'a [String],
+    body: &'a [Statement],
+}
+
 /// Lowers AST to Circuit.
-struct Lowerer {
+struct Lowerer<'a> {
     /// Qubit registers: name -> (`start_id`, size).
     qregs: FxHashMap<String, (u32, u32)>,
     /// Classical bit registers: name -> (`start_id`, size).
@@ -22,6 +42,12 @@ struct Lowerer {
     next_qubit: u32,
     /// Next clbit ID.
     next_clbit: u32,
+    /// User gate definitions, by name.
+    gate_defs: FxHashMap<&'a str, GateDefAst<'a>>,
+    /// Values of the `const` declarations seen so far.
+    consts: FxHashMap<String, Expression>,
+    /// Statements unrolled from `for` loops so far.
+    unrolled: Cell<usize>,
 }
 
 #[allow(
@@ -30,17 +56,20 @@ struct Lowerer {
     clippy::unused_self,
     clippy::unnecessary_wraps
 )]
-impl Lowerer {
+impl<'a> Lowerer<'a> {
     fn new() -> Self {
         Self {
             qregs: FxHashMap::default(),
             cregs: FxHashMap::default(),
             next_qubit: 0,
             next_clbit: 0,
+            gate_defs: FxHashMap::default(),
+            consts: FxHashMap::default(),
+            unrolled: Cell::new(0),
         }
     }
 
-    fn lower(&mut self, program: &Program) -> ParseResult<Circuit> {
+    fn lower(&mut self, program: &'a Program) -> ParseResult<Circuit> {
         // First pass: collect declarations
         for stmt in &program.statements {
             match stmt {
@@ -49,11 +78,45 @@ impl Lowerer {
                     self.qregs.insert(name.clone(), (self.next_qubit, size));
                     self.next_qubit += size;
                 }
-                Statement::BitDecl { name, size } => {
+                Statement::BitDecl { name, size }
+                | Statement::OutputDecl {
+                    name,
+                    ty: ClassicalType::Bit(size),
+                } => {
                     let size = size.unwrap_or(1);
                     self.cregs.insert(name.clone(), (self.next_clbit, size));
                     self.next_clbit += size;
                 }
+                Statement::OutputDecl { name, ty } => {
+                    return Err(ParseError::Generic(format!(
+                        "output {ty} {name}: only bit outputs are supported"
+                    )));
+                }
+                // Real-valued inputs lower to symbolic parameters wherever
+                // they appear in gate arguments.
+                Statement::InputDecl { name, ty } if !ty.is_real() => {
+                    return Err(ParseError::Generic(format!(
+                        "input {ty} {name}: only float and angle inputs are supported"
+                    )));
+                }
+                Statement::GateDef {
+                    name,
+                    params,
+                    qubits,
+                    body,
+                } => {
+                    if self.gate_defs.contains_key(name.as_str()) {
+                        return Err(ParseError::DuplicateDeclaration(format!("gate {name}")));
+                    }
+                    self.gate_defs.insert(
+                        name,
+                        GateDefAst {
+                            params,
+                            qubits,
+                            body,
+                        },
+                    );
+                }
                 _ => {}
             }
         }
@@ -61,9 +124,19 @@ impl Lowerer {
         // Create circuit
         let mut circuit = Circuit::with_size("qasm_circuit", self.next_qubit, self.next_clbit);
 
-        // Second pass: lower statements
+        // Second pass: lower statements, with the constants declared so
+        // far substituted
         for stmt in &program.statements {
-            self.lower_statement(&mut circuit, stmt)?;
+            if let Statement::ConstDecl { name, value, .. } = stmt {
+                let value = fold_const(&substitute_expr(value, &self.consts)).ok_or_else(|| {
+                    ParseError::Generic(format!("const {name}: value is not a constant"))
+                })?;
+                self.consts.insert(name.clone(), value);
+            } else if self.consts.is_empty() {
+                self.lower_statement(&mut circuit, stmt)?;
+            } else {
+                self.lower_statement(&mut circuit, &substitute(stmt, &self.consts))?;
+            }
         }
 
         Ok(circuit)
@@ -71,12 +144,32 @@ impl Lowerer {
 
     fn lower_statement(&self, circuit: &mut Circuit, stmt: &Statement) -> ParseResult<()> {
         match stmt {
-            Statement::QubitDecl { .. } | Statement::BitDecl { .. } | Statement::Include(_) => {
+            Statement::QubitDecl { .. }
+            | Statement::BitDecl { .. }
+            | Statement::InputDecl { .. }
+            | Statement::OutputDecl { .. }
+            | Statement::Include(_) => {
                 // Already handled
                 Ok(())
             }
 
-            Statement::Gate(call) => self.lower_gate_call(circuit, call),
+            Statement::Gate(call) => match call.duration {
+                None => self.lower_gate_call(circuit, call),
+                Some(duration) => {
+                    // Lower on the side, then annotate every gate the call
+                    // expands to (broadcasts apply one gate per qubit).
+                    let mut block = Circuit::with_size("block", self.next_qubit, self.next_clbit);
+                    self.lower_gate_call(&mut block, call)?;
+                    for (_, inst) in block.dag().topological_ops() {
+                        let mut inst = inst.clone();
+                        if let Some(gate) = inst.gate_mut() {
+                            gate.duration = Some(duration);
+                        }
+                        circuit.dag_mut().apply(inst)?;
+                    }
+                    Ok(())
+                }
+            },
 
             Statement::Measure { qubits, bits } => {
                 let q_ids = self.resolve_qubits(qubits)?;
@@ -116,23 +209,95 @@ impl Lowerer {
                 Ok(())
             }
 
-            Statement::If { .. } => {
-                // TODO: Implement conditional execution
-                Err(ParseError::Generic(
-                    "If statements not yet supported".into(),
-                ))
+            Statement::If {
+                condition,
+                then_body,
+                else_body,
+            } => {
+                let condition = self.lower_condition(condition)?;
+                let then_ops = self.lower_block(then_body)?;
+                let else_ops = match else_body {
+                    Some(body) => self.lower_block(body)?,
+                    None => vec![],
+                };
+
+                // `if (cond) gate ...;` is a plain conditioned gate.
+                if let ([single], []) = (then_ops.as_slice(), else_ops.as_slice()) {
+                    if let InstructionKind::Gate(gate) = &single.kind {
+                        if gate.condition.is_none() {
+                            let mut gate = gate.clone();
+                            gate.condition = Some(condition);
+                            circuit
+                                .dag_mut()
+                                .apply(Instruction::gate(gate, single.qubits.clone()))?;
+                            return Ok(());
+                        }
+                    }
+                }
+
+                circuit
+                    .dag_mut()
+                    .apply(Instruction::if_else(condition, then_ops, else_ops))?;
+                Ok(())
+            }
+
+            Statement::While {
+                condition,
+                body,
+                max_iterations,
+            } => {
+                let max_iterations = max_iterations.ok_or_else(|| {
+                    ParseError::Generic(
+                        "while loops need an iteration bound (@arvak.max_iterations <n>)".into(),
+                    )
+                })?;
+                let condition = self.lower_condition(condition)?;
+                let body = self.lower_block(body)?;
+                circuit.dag_mut().apply(Instruction::while_loop(
+                    condition,
+                    body,
+                    max_iterations,
+                ))?;
+                Ok(())
             }
 
-            Statement::For { .. } => {
-                // TODO: Implement loops
-                Err(ParseError::Generic("For loops not yet supported".into()))
+            Statement::ConstDecl { name, .. } => Err(ParseError::Generic(format!(
+                "const {name}: constants can only be declared at the top level"
+            ))),
+
+            Statement::For {
+                variable,
+                range,
+                body,
+            } => {
+                // The IR has no counted loop, so unroll: every iteration is
+                // the body with the loop variable replaced by its value.
+                let values = loop_values(variable, range)?;
+                let unrolled = values
+                    .len()
+                    .saturating_mul(body.len())
+                    .saturating_add(self.unrolled.get());
+                if unrolled > MAX_UNROLLED_STATEMENTS {
+                    return Err(ParseError::Generic(format!(
+                        "for {variable}: unrolling exceeds {MAX_UNROLLED_STATEMENTS} statements"
+                    )));
+                }
+                self.unrolled.set(unrolled);
+                let mut bindings = FxHashMap::default();
+                for value in values {
+                    bindings.insert(variable.clone(), Expression::Int(value));
+                    for stmt in body {
+                        self.lower_statement(circuit, &substitute(stmt, &bindings))?;
+                    }
+                }
+                Ok(())
             }
 
             Statement::GateDef { .. } => {
-                // Gate definitions are treated as opaque declarations: calls
-                // to known gates (prx, ecr, iswap, ...) are resolved by name
-                // in `lower_gate_call`, so the body is not needed. Calls to
-                // truly unknown gates still fail at the call site.
+                // Definitions were collected in the first pass. Calls to
+                // known gates (prx, ecr, iswap, ...) are resolved by name in
+                // `lower_gate_call`; calls to other defined gates become
+                // custom gates carrying the lowered body.
                 Ok(())
             }
 
@@ -152,6 +317,219 @@ impl Lowerer {
         }
     }
 
+    /// Lower the statements of an `if`/`else` branch to instructions over
+    /// the program's qubits and classical bits.
+    fn lower_block(&self, body: &[Statement]) -> ParseResult<Vec<Instruction>> {
+        let mut block = Circuit::with_size("block", self.next_qubit, self.next_clbit);
+        for stmt in body {
+            self.lower_statement(&mut block, stmt)?;
+        }
+        Ok(block
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect())
+    }
+
+    /// Lower an `if` condition.
+    ///
+    /// Supported forms are `reg == v`, `reg[i] == v`, a bare `reg[i]`
+    /// (meaning `reg[i] == 1`), and `&&`-conjunctions of single-bit
+    /// comparisons as emitted for multi-bit conditions.
+    fn lower_condition(&self, expr: &Expression) -> ParseResult<ClassicalCondition> {
+        if let Expression::Paren(inner) = expr {
+            return self.lower_condition(inner);
+        }
+        if let Expression::BinOp {
+            left,
+            op: BinOp::Eq,
+            right,
+        } = expr
+        {
+            if let Expression::Identifier(register) = left.as_ref() {
+                let clbits = self.resolve_clbits(&[BitRef::Single {
+                    register: register.clone(),
+                    index: None,
+                }])?;
+                return Ok(ClassicalCondition {
+                    register: register.clone(),
+                    value: condition_value(right)?,
+                    clbits,
+                });
+            }
+        }
+
+        let mut terms = Vec::new();
+        self.collect_bit_terms(expr, &mut terms)?;
+        let value = terms
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, set))| *set)
+            .fold(0u64, |acc, (i, _)| acc | (1 << i));
+        Ok(ClassicalCondition::on_clbits(
+            terms.into_iter().map(|(clbit, _)| clbit),
+            value,
+        ))
+    }
+
+    /// Collect `(bit, expected)` pairs from a conjunction of single-bit
+    /// comparisons.
+    fn collect_bit_terms(
+        &self,
+        expr: &Expression,
+        terms: &mut Vec<(ClbitId, bool)>,
+    ) -> ParseResult<()> {
+        match expr {
+            Expression::Paren(inner) => self.collect_bit_terms(inner, terms),
+            Expression::BinOp {
+                left,
+                op: BinOp::And,
+                right,
+            } => {
+                self.collect_bit_terms(left, terms)?;
+                self.collect_bit_terms(right, terms)
+            }
+            Expression::BinOp {
+                left,
+                op: BinOp::Eq,
+                right,
+            } => {
+                let clbit = self.resolve_condition_bit(left)?;
+                match condition_value(right)? {
+                    0 => terms.push((clbit, false)),
+                    1 => terms.push((clbit, true)),
+                    v => {
+                        return Err(ParseError::Generic(format!(
+                            "cannot compare a single bit with {v}"
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            Expression::Index { .. } => {
+                terms.push((self.resolve_condition_bit(expr)?, true));
+                Ok(())
+            }
+            _ => Err(ParseError::Generic(format!(
+                "unsupported if condition: {expr:?}"
+            ))),
+        }
+    }
+
+    /// Resolve a `reg[i]` expression to a classical bit.
+    fn resolve_condition_bit(&self, expr: &Expression) -> ParseResult<ClbitId> {
+        if let Expression::Index { target, index } = expr {
+            if let (Expression::Identifier(register), Expression::Int(i)) =
+                (target.as_ref(), index.as_ref())
+            {
+                let index = u32::try_from(*i).map_err(|_| {
+                    ParseError::Generic(format!("invalid bit index {i} in if condition"))
+                })?;
+                let ids = self.resolve_clbits(&[BitRef::Single {
+                    register: register.clone(),
+                    index: Some(index),
+                }])?;
+                return Ok(ids[0]);
+            }
+        }
+        Err(ParseError::Generic(format!(
+            "expected a classical bit in if condition, found {expr:?}"
+        )))
+    }
+
+    /// Lower a gate call with `ctrl`, `inv` or `pow` modifiers.
+    ///
+    /// Controlled X, Z and phase gates become their multi-controlled
+    /// standard forms; every other combination becomes a modified gate
+    /// around the lowered base gate, whose operands follow the controls.
+    fn lower_modified_gate(
+        &self,
+        circuit: &mut Circuit,
+        call: &GateCall,
+        qubits: &[QubitId],
+        params: &[ParameterExpression],
+    ) -> ParseResult<()> {
+        let mut controls = 0usize;
+        let mut power = 1.0;
+        for modifier in &call.modifiers {
+            match modifier {
+                GateModifier::Ctrl(n) => controls += n.map_or(1, |n| n as usize),
+                GateModifier::Inv => power = -power,
+                GateModifier::Pow(exponent) => {
+                    power *= exponent.as_f64().ok_or_else(|| {
+                        ParseError::Generic(format!(
+                            "pow modifier on '{}' needs a constant exponent",
+                            call.name
+                        ))
+                    })?;
+                }
+                GateModifier::NegCtrl(_) => {
+                    return Err(ParseError::Generic(format!(
+                        "Unsupported gate modifier {modifier:?} on '{}'",
+                        call.name
+                    )));
+                }
+            }
+        }
+
+        if power == 1.0
+            && matches!(
+                call.name.to_lowercase().as_str(),
+                "x" | "cx" | "ccx" | "toffoli" | "z" | "cz" | "p" | "phase" | "cp" | "cphase"
+            )
+        {
+            return lower_controlled_gate(circuit, call, controls, qubits, params);
+        }
+
+        // The first `controls` operands are the controls; lower the base
+        // gate on the rest on the side.
+        if call.qubits.len() <= controls {
+            return Err(ParseError::WrongQubitCount {
+                gate: call.name.clone(),
+                expected: controls + 1,
+                got: call.qubits.len(),
+            });
+        }
+        let control_qubits = self.resolve_qubits(&call.qubits[..controls])?;
+        if control_qubits.len() != controls {
+            return Err(ParseError::Generic(format!(
+                "controls of '{}' must be single qubits",
+                call.name
+            )));
+        }
+        let base_call = GateCall {
+            qubits: call.qubits[controls..].to_vec(),
+            modifiers: vec![],
+            duration: None,
+            ..call.clone()
+        };
+        let mut block = Circuit::with_size("block", self.next_qubit, self.next_clbit);
+        self.lower_gate_call(&mut block, &base_call)?;
+
+        let mut base = block.dag().topological_ops().map(|(_, inst)| inst);
+        let (Some(inst), None) = (base.next(), base.next()) else {
+            // `id` lowers to nothing, and so does any modified identity.
+            return if block.dag().num_ops() == 0 {
+                Ok(())
+            } else {
+                Err(ParseError::Generic(format!(
+                    "modifiers on '{}' need a single gate, not a broadcast",
+                    call.name
+                )))
+            };
+        };
+        let Some(gate) = inst.as_gate() else {
+            return Err(ParseError::UnknownGate(call.name.clone()));
+        };
+        circuit.gate(
+            gate.clone().power(power).controlled(controls as u32),
+            control_qubits
+                .into_iter()
+                .chain(inst.qubits.iter().copied()),
+        )?;
+        Ok(())
+    }
+
     fn lower_gate_call(&self, circuit: &mut Circuit, call: &GateCall) -> ParseResult<()> {
         let qubits = self.resolve_qubits(&call.qubits)?;
         let params: Vec<_> = call
@@ -160,6 +538,10 @@ impl Lowerer {
             .map(expr_to_param)
             .collect::<ParseResult<_>>()?;
 
+        if !call.modifiers.is_empty() {
+            return self.lower_modified_gate(circuit, call, &qubits, &params);
+        }
+
         match call.name.to_lowercase().as_str() {
             // Single-qubit gates
             "id" | "i" => {
@@ -167,6 +549,18 @@ impl Lowerer {
                 // since they have no effect on circuit state.
                 Ok(())
             }
+            "gphase" => {
+                check_param_count("gphase", &params, 1)?;
+                check_qubit_count("gphase", &qubits, 0)?;
+                // A symbolic phase (e.g. in a `gate` body) is dropped: a
+                // global phase is unobservable, and gate definitions drop
+                // theirs anyway.
+                if let Some(phase) = params[0].as_f64() {
+                    let dag = circuit.dag_mut();
+                    dag.set_global_phase(dag.global_phase() + phase);
+                }
+                Ok(())
+            }
             "x" => {
                 for q in qubits {
                     circuit.x(q)?;
@@ -248,7 +642,7 @@ impl Lowerer {
                 }
                 Ok(())
             }
-            "p" | "phase" => {
+            "p" | "phase" | "u1" => {
                 check_param_count("p", &params, 1)?;
                 for q in qubits {
                     circuit.p(params[0].clone(), q)?;
@@ -262,6 +656,14 @@ impl Lowerer {
                 }
                 Ok(())
             }
+            "u2" => {
+                check_param_count("u2", &params, 2)?;
+                let theta = ParameterExpression::pi() / ParameterExpression::constant(2.0);
+                for q in qubits {
+                    circuit.u(theta.clone(), params[0].clone(), params[1].clone(), q)?;
+                }
+                Ok(())
+            }
             // `r` is the Qiskit/IQM name for the phased-RX rotation:
             // R(theta, phi) = PRX(theta, phi).
             "prx" | "r" => {
@@ -309,12 +711,32 @@ impl Lowerer {
                 circuit.crz(params[0].clone(), qubits[0], qubits[1])?;
                 Ok(())
             }
-            "cp" | "cphase" => {
+            "cp" | "cphase" | "cu1" => {
                 check_param_count("cp", &params, 1)?;
                 check_qubit_count("cp", &qubits, 2)?;
                 circuit.cp(params[0].clone(), qubits[0], qubits[1])?;
                 Ok(())
             }
+            // `cu3` is the `OpenQASM` 2 name, without the control phase.
+            "cu" | "cu3" => {
+                let name = call.name.to_lowercase();
+                let with_phase = name == "cu";
+                check_param_count(&name, &params, if with_phase { 4 } else { 3 })?;
+                check_qubit_count(&name, &qubits, 2)?;
+                // cu(θ, φ, λ, γ) applies the phase γ to the control, then a
+                // controlled U(θ, φ, λ).
+                if with_phase {
+                    circuit.p(params[3].clone(), qubits[0])?;
+                }
+                let controlled_u = GateCall {
+                    name: "u".into(),
+                    params: call.params[..3].to_vec(),
+                    qubits: call.qubits.clone(),
+                    modifiers: vec![GateModifier::Ctrl(None)],
+                    duration: None,
+                };
+                self.lower_modified_gate(circuit, &controlled_u, &qubits, &params[..3])
+            }
             "ch" => {
                 check_qubit_count("ch", &qubits, 2)?;
                 circuit.ch(qubits[0], qubits[1])?;
@@ -363,8 +785,49 @@ impl Lowerer {
                 Ok(())
             }
 
-            other => Err(ParseError::UnknownGate(other.to_string())),
+            _ => match self.gate_defs.get(call.name.as_str()) {
+                Some(def) => self.lower_defined_gate(circuit, &call.name, *def, &qubits, params),
+                None => Err(ParseError::UnknownGate(call.name.to_lowercase())),
+            },
+        }
+    }
+
+    /// Lower a call to a user-defined gate into a custom gate whose
+    /// definition is the lowered `gate` body.
+    fn lower_defined_gate(
+        &self,
+        circuit: &mut Circuit,
+        name: &str,
+        def: GateDefAst<'a>,
+        qubits: &[QubitId],
+        params: Vec<ParameterExpression>,
+    ) -> ParseResult<()> {
+        check_param_count(name, &params, def.params.len())?;
+        check_qubit_count(name, qubits, def.qubits.len())?;
+
+        // The body sees its formal qubits as one-qubit registers and every
+        // other definition except its own, so recursion is rejected.
+        let mut body_lowerer = Lowerer::new();
+        for formal in def.qubits {
+            body_lowerer
+                .qregs
+                .insert(formal.clone(), (body_lowerer.next_qubit, 1));
+            body_lowerer.next_qubit += 1;
         }
+        body_lowerer.gate_defs.clone_from(&self.gate_defs);
+        body_lowerer.gate_defs.remove(name);
+
+        let mut body = Circuit::with_size(name, body_lowerer.next_qubit, 0);
+        for stmt in def.body {
+            body_lowerer.lower_statement(&mut body, stmt)?;
+        }
+
+        let definition = GateDefinition::from_circuit(def.params.to_vec(), &body)?;
+        let gate = CustomGate::new(name, body_lowerer.next_qubit)
+            .with_params(params)
+            .with_definition(definition)?;
+        circuit.gate(gate, qubits.iter().copied())?;
+        Ok(())
     }
 
     fn resolve_qubits(&self, refs: &[QubitRef]) -> ParseResult<Vec<QubitId>> {
@@ -415,6 +878,10 @@ impl Lowerer {
                         ids.push(QubitId(base + i));
                     }
                 }
+                QubitRef::Indexed { register, index } => {
+                    let index = const_index(register, index)?;
+                    ids.extend(self.resolve_qubits(&[QubitRef::single(register, index)])?);
+                }
             }
         }
         Ok(ids)
@@ -467,6 +934,10 @@ impl Lowerer {
                         ids.push(ClbitId(base + i));
                     }
                 }
+                BitRef::Indexed { register, index } => {
+                    let index = const_index(register, index)?;
+                    ids.extend(self.resolve_clbits(&[BitRef::single(register, index)])?);
+                }
             }
         }
         Ok(ids)
@@ -476,10 +947,273 @@ impl Lowerer {
 /// Evaluate an expression to a constant non-negative integer (e.g. a delay
 /// duration in device units).
 fn const_u64(expr: &Expression) -> ParseResult<u64> {
+    const_int(expr)
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or_else(|| {
+            ParseError::Generic(format!(
+                "expected a non-negative integer duration, found {expr:?}"
+            ))
+        })
+}
+
+/// Evaluate a computed register index, e.g. `q[i + 1]` after the loop
+/// variable has been substituted.
+fn const_index(register: &str, index: &Expression) -> ParseResult<u32> {
+    const_int(index)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| {
+            ParseError::Generic(format!(
+                "index of '{register}' must be a non-negative integer constant, found {index:?}"
+            ))
+        })
+}
+
+/// Evaluate an integer expression built from literals.
+///
+/// Division truncates, as for `OpenQASM` `int`s; overflow and division by
+/// zero give `None`.
+fn const_int(expr: &Expression) -> Option<i64> {
+    match expr {
+        Expression::Int(v) => Some(*v),
+        Expression::Neg(e) => const_int(e)?.checked_neg(),
+        Expression::Paren(e) => const_int(e),
+        Expression::BinOp { left, op, right } => {
+            let (l, r) = (const_int(left)?, const_int(right)?);
+            match op {
+                BinOp::Add => l.checked_add(r),
+                BinOp::Sub => l.checked_sub(r),
+                BinOp::Mul => l.checked_mul(r),
+                BinOp::Div => l.checked_div(r),
+                BinOp::Mod => l.checked_rem(r),
+                BinOp::Pow => l.checked_pow(u32::try_from(r).ok()?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Fold a constant expression to a literal: an `Int` if it is an integer
+/// expression, a `Float` otherwise.
+fn fold_const(expr: &Expression) -> Option<Expression> {
+    match expr {
+        Expression::Bool(_) => Some(expr.clone()),
+        _ => const_int(expr)
+            .map(Expression::Int)
+            .or_else(|| expr.as_f64().map(Expression::Float)),
+    }
+}
+
+/// The values a `for` loop variable takes over its range, both ends
+/// inclusive.
+fn loop_values(variable: &str, range: &Range) -> ParseResult<Vec<i64>> {
+    let bound = |expr: &Expression| {
+        const_int(expr).ok_or_else(|| {
+            ParseError::Generic(format!(
+                "for {variable}: range bounds must be integer constants, found {expr:?}"
+            ))
+        })
+    };
+    let (start, end) = (bound(&range.start)?, bound(&range.end)?);
+    let step = range.step.as_ref().map_or(Ok(1), bound)?;
+    if step == 0 {
+        return Err(ParseError::Generic(format!(
+            "for {variable}: range step must not be zero"
+        )));
+    }
+
+    let mut values = Vec::new();
+    let mut value = start;
+    while (step > 0 && value <= end) || (step < 0 && value >= end) {
+        if values.len() == MAX_UNROLLED_STATEMENTS {
+            return Err(ParseError::Generic(format!(
+                "for {variable}: more than {MAX_UNROLLED_STATEMENTS} iterations"
+            )));
+        }
+        values.push(value);
+        let Some(next) = value.checked_add(step) else {
+            break;
+        };
+        value = next;
+    }
+    Ok(values)
+}
+
+/// Replace the identifiers bound in `bindings` (loop variables and
+/// constants) throughout a statement.
+///
+/// Computed indices that become constant are folded, so `q[i + 1]` turns
+/// into a plain `q[2]`. A nested `for` loop shadows its own variable, and
+/// gate definitions are left alone since their bodies have their own
+/// scope.
+fn substitute(stmt: &Statement, bindings: &FxHashMap<String, Expression>) -> Statement {
+    let exprs = |exprs: &[Expression]| -> Vec<Expression> {
+        exprs.iter().map(|e| substitute_expr(e, bindings)).collect()
+    };
+    let qubits = |refs: &[QubitRef]| -> Vec<QubitRef> {
+        refs.iter()
+            .map(|r| match r {
+                QubitRef::Indexed { register, index } => {
+                    let index = substitute_expr(index, bindings);
+                    match const_int(&index).and_then(|i| u32::try_from(i).ok()) {
+                        Some(i) => QubitRef::single(register, i),
+                        None => QubitRef::Indexed {
+                            register: register.clone(),
+                            index,
+                        },
+                    }
+                }
+                _ => r.clone(),
+            })
+            .collect()
+    };
+    let bits = |refs: &[BitRef]| -> Vec<BitRef> {
+        refs.iter()
+            .map(|r| match r {
+                BitRef::Indexed { register, index } => {
+                    let index = substitute_expr(index, bindings);
+                    match const_int(&index).and_then(|i| u32::try_from(i).ok()) {
+                        Some(i) => BitRef::single(register, i),
+                        None => BitRef::Indexed {
+                            register: register.clone(),
+                            index,
+                        },
+                    }
+                }
+                _ => r.clone(),
+            })
+            .collect()
+    };
+    let block = |body: &[Statement]| -> Vec<Statement> {
+        body.iter().map(|s| substitute(s, bindings)).collect()
+    };
+
+    match stmt {
+        Statement::Include(_)
+        | Statement::QubitDecl { .. }
+        | Statement::BitDecl { .. }
+        | Statement::InputDecl { .. }
+        | Statement::OutputDecl { .. }
+        | Statement::GateDef { .. } => stmt.clone(),
+        Statement::ConstDecl { name, ty, value } => Statement::ConstDecl {
+            name: name.clone(),
+            ty: *ty,
+            value: substitute_expr(value, bindings),
+        },
+        Statement::Gate(call) => Statement::Gate(GateCall {
+            name: call.name.clone(),
+            params: exprs(&call.params),
+            qubits: qubits(&call.qubits),
+            modifiers: call
+                .modifiers
+                .iter()
+                .map(|m| match m {
+                    GateModifier::Pow(e) => GateModifier::Pow(substitute_expr(e, bindings)),
+                    _ => m.clone(),
+                })
+                .collect(),
+            duration: call.duration,
+        }),
+        Statement::Measure { qubits: q, bits: b } => Statement::Measure {
+            qubits: qubits(q),
+            bits: bits(b),
+        },
+        Statement::Reset { qubits: q } => Statement::Reset { qubits: qubits(q) },
+        Statement::Barrier { qubits: q } => Statement::Barrier { qubits: qubits(q) },
+        Statement::Delay {
+            duration,
+            qubits: q,
+        } => Statement::Delay {
+            duration: substitute_expr(duration, bindings),
+            qubits: qubits(q),
+        },
+        Statement::If {
+            condition,
+            then_body,
+            else_body,
+        } => Statement::If {
+            condition: substitute_expr(condition, bindings),
+            then_body: block(then_body),
+            else_body: else_body.as_deref().map(block),
+        },
+        Statement::While {
+            condition,
+            body,
+            max_iterations,
+        } => Statement::While {
+            condition: substitute_expr(condition, bindings),
+            body: block(body),
+            max_iterations: *max_iterations,
+        },
+        Statement::For {
+            variable,
+            range,
+            body,
+        } => {
+            let range = Range {
+                start: substitute_expr(&range.start, bindings),
+                end: substitute_expr(&range.end, bindings),
+                step: range.step.as_ref().map(|e| substitute_expr(e, bindings)),
+            };
+            let body = if bindings.contains_key(variable) {
+                let mut inner = bindings.clone();
+                inner.remove(variable);
+                body.iter().map(|s| substitute(s, &inner)).collect()
+            } else {
+                block(body)
+            };
+            Statement::For {
+                variable: variable.clone(),
+                range,
+                body,
+            }
+        }
+        Statement::Assignment {
+            target,
+            index,
+            value,
+        } => Statement::Assignment {
+            target: target.clone(),
+            index: *index,
+            value: substitute_expr(value, bindings),
+        },
+    }
+}
+
+/// Replace the identifiers bound in `bindings` in an expression.
+fn substitute_expr(expr: &Expression, bindings: &FxHashMap<String, Expression>) -> Expression {
+    let sub = |e: &Expression| Box::new(substitute_expr(e, bindings));
     match expr {
+        Expression::Identifier(name) => bindings.get(name).unwrap_or(expr).clone(),
+        Expression::Neg(e) => Expression::Neg(sub(e)),
+        Expression::Paren(e) => Expression::Paren(sub(e)),
+        Expression::BinOp { left, op, right } => Expression::BinOp {
+            left: sub(left),
+            op: *op,
+            right: sub(right),
+        },
+        Expression::FnCall { name, args } => Expression::FnCall {
+            name: name.clone(),
+            args: args.iter().map(|a| substitute_expr(a, bindings)).collect(),
+        },
+        Expression::Index { target, index } => {
+            let index = substitute_expr(index, bindings);
+            Expression::Index {
+                target: target.clone(),
+                index: Box::new(const_int(&index).map_or(index, Expression::Int)),
+            }
+        }
+        _ => expr.clone(),
+    }
+}
+
+/// Evaluate the right-hand side of a condition comparison.
+fn condition_value(expr: &Expression) -> ParseResult<u64> {
+    match expr {
+        Expression::Bool(b) => Ok(u64::from(*b)),
         Expression::Int(v) if *v >= 0 => Ok(u64::try_from(*v).unwrap_or(0)),
         _ => Err(ParseError::Generic(format!(
-            "expected a non-negative integer duration, found {expr:?}"
+            "expected a non-negative integer in if condition, found {expr:?}"
         ))),
     }
 }
@@ -553,6 +1287,44 @@ fn check_param_count(
     }
 }
 
+/// Lower `controls` control modifiers on an X, Z or phase gate to a
+/// multi-controlled gate.
+///
+/// Controls from the modifiers are prepended to any controls the base gate
+/// already has, so `ctrl @ ccx` is a three-controlled X.
+fn lower_controlled_gate(
+    circuit: &mut Circuit,
+    call: &GateCall,
+    controls: usize,
+    qubits: &[QubitId],
+    params: &[ParameterExpression],
+) -> ParseResult<()> {
+    let name = call.name.to_lowercase();
+    let (base, base_controls, num_params) = match name.as_str() {
+        "x" => ("x", 0, 0),
+        "cx" => ("x", 1, 0),
+        "ccx" | "toffoli" => ("x", 2, 0),
+        "z" => ("z", 0, 0),
+        "cz" => ("z", 1, 0),
+        "p" | "phase" => ("p", 0, 1),
+        "cp" | "cphase" => ("p", 1, 1),
+        _ => return Err(ParseError::UnknownGate(call.name.clone())),
+    };
+
+    let controls = controls + base_controls;
+    let label = format!("ctrl({controls}) @ {base}");
+    check_param_count(&label, params, num_params)?;
+    check_qubit_count(&label, qubits, controls + 1)?;
+
+    let (controls, target) = (&qubits[..controls], qubits[controls]);
+    match base {
+        "x" => circuit.mcx(controls, target)?,
+        "z" => circuit.mcz(controls, target)?,
+        _ => circuit.mcp(params[0].clone(), controls, target)?,
+    };
+    Ok(())
+}
+
 fn check_qubit_count(gate: &str, qubits: &[QubitId], expected: usize) -> ParseResult<()> {
     if qubits.len() == expected {
         Ok(())
@@ -342,4 +342,388 @@ mod tests {
         let source = "OPENQASM 3.0;\nqubit q;\nrx((((pi / 2) + 0.1) * 2)) q;\n";
         parse(source).unwrap();
     }
+
+    #[test]
+    fn test_parse_if_else_block() {
+        let source = r"
+            OPENQASM 3.0;
+            qubit[2] q;
+            bit[2] c;
+            h q[0];
+            c[0] = measure q[0];
+            if (c[0] == 1) {
+                x q[1];
+                c[1] = measure q[1];
+            } else {
+                h q[1];
+            }
+        ";
+
+        let circuit = parse(source).unwrap();
+        let (_, block) = circuit.dag().topological_ops().last().unwrap();
+        let arvak_ir::InstructionKind::IfElse {
+            condition,
+            then_body,
+            else_body,
+        } = &block.kind
+        else {
+            panic!("expected if/else block, got {}", block.name());
+        };
+        assert_eq!(
+            *condition,
+            arvak_ir::ClassicalCondition::on_clbit(arvak_ir::ClbitId(0), 1)
+        );
+        assert_eq!(then_body.len(), 2);
+        assert_eq!(else_body.len(), 1);
+
+        // Emitting and re-parsing preserves the block.
+        let reparsed = parse(&crate::emit(&circuit).unwrap()).unwrap();
+        let (_, again) = reparsed.dag().topological_ops().last().unwrap();
+        assert_eq!(again, block);
+    }
+
+    #[test]
+    fn test_parse_while_loop() {
+        let source = r"
+            OPENQASM 3.0;
+            qubit[2] q;
+            bit[1] c;
+            c[0] = measure q[1];
+            @arvak.max_iterations 10
+            while (c[0] == 1) {
+                reset q[1];
+                h q[1];
+                cx q[1], q[0];
+                c[0] = measure q[1];
+            }
+        ";
+
+        let circuit = parse(source).unwrap();
+        let (_, block) = circuit.dag().topological_ops().last().unwrap();
+        let arvak_ir::InstructionKind::WhileLoop {
+            body,
+            max_iterations,
+            ..
+        } = &block.kind
+        else {
+            panic!("expected while loop, got {}", block.name());
+        };
+        assert_eq!(*max_iterations, 10);
+        assert_eq!(body.len(), 4);
+
+        // Emitting and re-parsing preserves the loop and its bound.
+        let reparsed = parse(&crate::emit(&circuit).unwrap()).unwrap();
+        let (_, again) = reparsed.dag().topological_ops().last().unwrap();
+        assert_eq!(again, block);
+
+        // Unbounded loops and misplaced bounds are rejected.
+        let unbounded = "OPENQASM 3.0;\nqubit q;\nbit c;\nwhile (c == 1) { c = measure q; }\n";
+        assert!(parse(unbounded).is_err());
+        let misplaced = "OPENQASM 3.0;\nqubit q;\n@arvak.max_iterations 3\nh q;\n";
+        assert!(parse(misplaced).is_err());
+    }
+
+    #[test]
+    fn test_parse_if_conditions() {
+        let source = r"
+            OPENQASM 3.0;
+            qubit[2] q;
+            bit[2] c;
+            c = measure q;
+            if (c == 2) x q[0];
+            if (c[1]) z q[1];
+            if (c[0] == 0 && c[1] == 1) {
+                h q[0];
+                h q[1];
+            }
+        ";
+
+        let circuit = parse(source).unwrap();
+        let conditions: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .filter_map(|(_, i)| i.condition().cloned())
+            .collect();
+        assert_eq!(conditions.len(), 3);
+
+        // Whole-register comparison keeps the register name and its bits.
+        assert!(conditions.iter().any(|c| c.register == "c"
+            && c.value == 2
+            && c.clbits == [arvak_ir::ClbitId(0), arvak_ir::ClbitId(1)]));
+        // A bare bit means `== 1`; single-statement bodies become
+        // conditioned gates, bit conjunctions fold into one value.
+        let gates = circuit
+            .dag()
+            .topological_ops()
+            .filter(|(_, i)| i.is_conditional() && i.is_gate())
+            .count();
+        assert_eq!(gates, 2);
+        assert!(
+            conditions
+                .iter()
+                .any(|c| c.register.is_empty() && c.clbits.len() == 2 && c.value == 2)
+        );
+
+        assert!(parse("OPENQASM 3.0; qubit q; bit c; if (c[0] == 2) x q;").is_err());
+        assert!(parse("OPENQASM 3.0; qubit q; bit c; if (c[0] < 1) x q;").is_err());
+    }
+
+    #[test]
+    fn test_parse_user_gate_definition() {
+        let source = r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            gate zz(t) a, b { cx a, b; rz(t) b; cx a, b; }
+            gate layer(t) a, b, c { zz(t) a, b; zz(2 * t) b, c; }
+            qubit[3] q;
+            layer(0.5) q[2], q[0], q[1];
+        "#;
+
+        let circuit = parse(source).unwrap();
+        let (_, inst) = circuit.dag().topological_ops().next().unwrap();
+        let arvak_ir::GateKind::Custom(layer) = &inst.as_gate().unwrap().kind else {
+            panic!("expected a custom gate");
+        };
+        assert_eq!(layer.name, "layer");
+        let definition = layer.definition.as_ref().unwrap();
+        assert_eq!(definition.params, ["t"]);
+        assert_eq!(definition.body.len(), 2);
+        assert_eq!(layer.expand(&inst.qubits).unwrap().len(), 2);
+
+        // Arity is checked against the definition, and a gate cannot call
+        // itself.
+        assert!(parse("OPENQASM 3.0; gate g a { h a; } qubit[2] q; g q[0], q[1];").is_err());
+        assert!(parse("OPENQASM 3.0; gate g a { g a; } qubit q; g q[0];").is_err());
+    }
+
+    #[test]
+    fn test_parse_qiskit_export_with_stdgates() {
+        // As written by `qiskit.qasm3.dumps`.
+        let source = r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            bit[2] c;
+            qubit[2] q;
+            u2(0, pi) q[0];
+            u1(pi/4) q[1];
+            cu(pi/2, 0, pi, pi/4) q[0], q[1];
+            barrier q[0], q[1];
+            c[0] = measure q[0];
+            c[1] = measure q[1];
+        "#;
+        let circuit = parse(source).unwrap();
+        assert_eq!(circuit.num_qubits(), 2);
+        let names: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.name().to_string())
+            .collect();
+        assert_eq!(names.iter().filter(|n| n.as_str() == "p").count(), 2);
+
+        assert!(
+            parse("OPENQASM 3.0; include \"qelib1.inc\"; qubit[2] q; cu1(pi) q[0], q[1];").is_ok()
+        );
+        assert!(matches!(
+            parse("OPENQASM 3.0; include \"mygates.inc\"; qubit q;"),
+            Err(ParseError::UnsupportedInclude { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_exported_gate_definitions() {
+        // Definitions as Qiskit exports them: generated operand names, no
+        // parameter list, `U` and `gphase` in bodies, and use before
+        // definition.
+        let source = r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            gate outer _gate_q_0, _gate_q_1 { inner(pi) _gate_q_1, _gate_q_0; }
+            gate inner(_gate_p_0) _gate_q_0, _gate_q_1 {
+                gphase(-_gate_p_0 / 2);
+                U(0, 0, _gate_p_0) _gate_q_0;
+                cx _gate_q_0, _gate_q_1;
+            }
+            gate noop() a { }
+            qubit[2] q;
+            outer q[0], q[1];
+            noop q[1];
+            gphase(pi / 4);
+        "#;
+        let circuit = parse(source).unwrap();
+        assert!((circuit.dag().global_phase() - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+
+        let gates: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .filter_map(|(_, inst)| match &inst.as_gate()?.kind {
+                arvak_ir::GateKind::Custom(gate) => Some(gate.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(gates.len(), 2);
+        let outer = gates.iter().find(|g| g.name == "outer").unwrap();
+        let body = &outer.definition.as_ref().unwrap().body;
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].name(), "inner");
+
+        assert!(matches!(
+            parse("OPENQASM 3.0; gate g a { h a; } gate g a { x a; } qubit q; g q;"),
+            Err(ParseError::DuplicateDeclaration(_))
+        ));
+        assert!(parse("OPENQASM 3.0; qubit q; gphase(pi) q;").is_err());
+    }
+
+    #[test]
+    fn test_parse_for_loops() {
+        // A hardware-efficient ansatz: layers of rotations and brickwork
+        // entanglers, with a constant size and a counting-down readout.
+        let source = r"
+            OPENQASM 3.0;
+            const int n = 4;
+            qubit[4] q;
+            bit[4] c;
+            for int layer in [0:1] {
+                for int i in [0:n-1] { ry(0.1 * (i + 1)) q[i]; }
+                for int i in [0:2:n-2] { cz q[i], q[i+1]; }
+            }
+            for uint i in [n-1:-1:0] c[i] = measure q[i];
+        ";
+        let circuit = parse(source).unwrap();
+        let ops: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.clone())
+            .collect();
+        assert_eq!(ops.iter().filter(|inst| inst.name() == "ry").count(), 8);
+        assert_eq!(ops.iter().filter(|inst| inst.is_measure()).count(), 4);
+        let mut cz: Vec<_> = ops
+            .iter()
+            .filter(|inst| inst.name() == "cz")
+            .map(|inst| (inst.qubits[0].0, inst.qubits[1].0))
+            .collect();
+        cz.sort_unstable();
+        assert_eq!(cz, [(0, 1), (0, 1), (2, 3), (2, 3)]);
+
+        // Loop bounds and indices must resolve to constants in range.
+        for source in [
+            "OPENQASM 3.0; qubit[2] q; for int i in [0:2] { h q[i]; }",
+            "OPENQASM 3.0; qubit[2] q; for int i in [0:m] { h q[i]; }",
+            "OPENQASM 3.0; qubit[2] q; for int i in [0:0:1] { h q[i]; }",
+            "OPENQASM 3.0; qubit[2] q; h q[i];",
+        ] {
+            assert!(parse(source).is_err(), "{source}");
+        }
+
+        // Nested loops share one unrolling budget: each loop alone is
+        // within it, but together they would unroll 2^21 gates.
+        let source = r"
+            OPENQASM 3.0;
+            qubit[1] q;
+            for int i in [0:1] {
+                for int j in [0:1048575] { h q[0]; }
+            }
+        ";
+        let err = parse(source).unwrap_err();
+        assert!(err.to_string().contains("unrolling exceeds"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_ctrl_modifier() {
+        let source = r"
+            OPENQASM 3.0;
+            qubit[5] q;
+            ctrl(3) @ x q[0], q[1], q[2], q[3];
+            ctrl @ ccx q[0], q[1], q[2], q[4];
+            ctrl(2) @ p(pi/4) q[0], q[1], q[4];
+            ctrl @ x q[0], q[1];
+        ";
+
+        let circuit = parse(source).unwrap();
+        let gates: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .filter_map(|(_, i)| i.as_gate())
+            .map(|g| match &g.kind {
+                arvak_ir::GateKind::Standard(std) => std.clone(),
+                other => panic!("unexpected gate {}", other.name()),
+            })
+            .collect();
+        assert!(gates.contains(&arvak_ir::StandardGate::MCX(3)));
+        assert!(
+            gates
+                .iter()
+                .any(|g| matches!(g, arvak_ir::StandardGate::MCP(2, _)))
+        );
+        assert!(gates.contains(&arvak_ir::StandardGate::CX));
+        assert_eq!(
+            gates
+                .iter()
+                .filter(|g| **g == arvak_ir::StandardGate::MCX(3))
+                .count(),
+            2
+        );
+
+        // Wrong arity, unsupported modifiers and symbolic exponents.
+        assert!(parse("OPENQASM 3.0; qubit[3] q; ctrl(3) @ x q[0], q[1], q[2];").is_err());
+        assert!(parse("OPENQASM 3.0; qubit[2] q; ctrl(2) @ h q[0], q[1];").is_err());
+        assert!(parse("OPENQASM 3.0; qubit[2] q; negctrl @ h q[0], q[1];").is_err());
+        assert!(parse("OPENQASM 3.0; qubit q; pow(t) @ s q[0];").is_err());
+    }
+
+    #[test]
+    fn test_parse_annotations() {
+        let source = r"
+            OPENQASM 3.0;
+            qubit[2] q;
+            @arvak.duration 40dt
+            x q;
+            @vendor.pulse calibrated(1, 2)
+            @arvak.duration 300
+            cx q[0], q[1];
+            @vendor.hint fast
+            h q[0];
+        ";
+
+        let circuit = parse(source).unwrap();
+        let durations: Vec<_> = circuit
+            .dag()
+            .topological_ops()
+            .map(|(_, inst)| inst.duration())
+            .collect();
+        assert_eq!(durations, [Some(40), Some(40), Some(300), None]);
+
+        // Durations are in dt, and only gates carry one.
+        assert!(parse("OPENQASM 3.0; qubit q;\n@arvak.duration 40ns\nx q;").is_err());
+        assert!(parse("OPENQASM 3.0; qubit q;\n@arvak.duration 40dt\nreset q;").is_err());
+    }
+
+    #[test]
+    fn test_parse_input_output_declarations() {
+        let source = r"
+            OPENQASM 3.0;
+            input float[64] theta;
+            input angle phi;
+            output bit[2] c;
+            qubit[2] q;
+            rx(theta) q[0];
+            rz(2 * phi) q[1];
+            c = measure q;
+        ";
+
+        let circuit = parse(source).unwrap();
+        assert_eq!(circuit.num_clbits(), 2);
+        assert_eq!(circuit.parameters(), ["phi", "theta"]);
+
+        let values = [("theta".to_string(), 0.5), ("phi".to_string(), 0.25)];
+        let bound = circuit
+            .bind_parameters(&values.into_iter().collect())
+            .unwrap();
+        assert!(bound.parameters().is_empty());
+
+        // Only real-valued inputs can bind gate parameters, and only bits
+        // can be outputs.
+        assert!(parse("OPENQASM 3.0; input int[32] n; qubit q;").is_err());
+        assert!(parse("OPENQASM 3.0; output float[64] e; qubit q;").is_err());
+        assert!(parse("OPENQASM 3.0; input qubit q;").is_err());
+    }
 }
@@ -1,10 +1,18 @@
 //! Statement parsing for QASM3.
 
 use super::Parser;
-use crate::ast::{BitRef, GateCall, QubitRef, Range, Statement};
+use crate::ast::{
+    BitRef, ClassicalType, Expression, GateCall, GateModifier, QubitRef, Range, Statement,
+};
 use crate::error::{ParseError, ParseResult};
 use crate::lexer::Token;
 
+/// Includes whose gates are built into the parser: the `OpenQASM` 3
+/// standard library, and the `OpenQASM` 2 one that `emit_qasm2` writes.
+/// Every gate they define is lowered natively, so the files themselves are
+/// never read.
+const BUILTIN_INCLUDES: [&str; 2] = ["stdgates.inc", "qelib1.inc"];
+
 impl Parser {
     /// Parse a statement.
     pub(super) fn parse_statement(&mut self) -> ParseResult<Statement> {
@@ -17,13 +25,17 @@ impl Parser {
             Token::Include => self.parse_include(),
             Token::Qubit => self.parse_qubit_decl(),
             Token::Bit => self.parse_bit_decl(),
+            Token::Input | Token::Output => self.parse_io_decl(),
+            Token::Const => self.parse_const_decl(),
             Token::Measure => self.parse_measure(),
             Token::Reset => self.parse_reset(),
             Token::Barrier => self.parse_barrier(),
             Token::Delay => self.parse_delay(),
             Token::If => self.parse_if(),
+            Token::While => self.parse_while(),
             Token::For => self.parse_for(),
             Token::Gate => self.parse_gate_def(),
+            Token::At => self.parse_annotated(),
             // Built-in gates have dedicated tokens but parse as gate calls.
             Token::GateU => {
                 self.advance();
@@ -62,6 +74,84 @@ impl Parser {
         Ok(Statement::Delay { duration, qubits })
     }
 
+    /// Parse annotations (`@keyword content`, one per line) and the
+    /// statement they annotate.
+    ///
+    /// `@arvak.duration <n>dt` sets the duration of the annotated gate call
+    /// (or of the single gate of a one-line `if`), and
+    /// `@arvak.max_iterations <n>` bounds the annotated `while` loop. Other
+    /// annotations are skipped to the end of their line.
+    fn parse_annotated(&mut self) -> ParseResult<Statement> {
+        let mut duration = None;
+        let mut max_iterations = None;
+        while self.consume(&Token::At) {
+            let line = self.line();
+            let mut keyword = self.parse_identifier()?;
+            while self.peek_line() == line && self.consume(&Token::Dot) {
+                keyword.push('.');
+                keyword.push_str(&self.parse_identifier()?);
+            }
+            if keyword == "arvak.duration" {
+                duration = Some((line, self.parse_int_literal()?));
+                if self.peek_line() == line {
+                    match self.advance() {
+                        Some(Token::Identifier(unit)) if unit == "dt" => {}
+                        Some(other) => {
+                            return Err(ParseError::UnexpectedToken {
+                                line,
+                                expected: "duration in dt".into(),
+                                found: other.to_string(),
+                            });
+                        }
+                        None => {}
+                    }
+                }
+            } else if keyword == "arvak.max_iterations" {
+                let bound = self.parse_int_literal()?;
+                let bound = u32::try_from(bound).map_err(|_| {
+                    ParseError::Generic(format!(
+                        "line {line}: iteration bound {bound} is too large"
+                    ))
+                })?;
+                max_iterations = Some((line, bound));
+            }
+            while !self.is_eof() && self.peek_line() == line {
+                self.advance();
+            }
+        }
+
+        let mut statement = self.parse_statement()?;
+        if let Some((line, duration)) = duration {
+            let call = match &mut statement {
+                Statement::Gate(call) => Some(call),
+                Statement::If {
+                    then_body,
+                    else_body: None,
+                    ..
+                } => match then_body.as_mut_slice() {
+                    [Statement::Gate(call)] => Some(call),
+                    _ => None,
+                },
+                _ => None,
+            };
+            call.ok_or_else(|| {
+                ParseError::Generic(format!(
+                    "line {line}: @arvak.duration must annotate a gate call"
+                ))
+            })?
+            .duration = Some(duration);
+        }
+        if let Some((line, bound)) = max_iterations {
+            let Statement::While { max_iterations, .. } = &mut statement else {
+                return Err(ParseError::Generic(format!(
+                    "line {line}: @arvak.max_iterations must annotate a while loop"
+                )));
+            };
+            *max_iterations = Some(bound);
+        }
+        Ok(statement)
+    }
+
     /// Parse include statement.
     fn parse_include(&mut self) -> ParseResult<Statement> {
         self.expect(Token::Include)?;
@@ -76,6 +166,12 @@ impl Parser {
             }
             None => return Err(ParseError::UnexpectedEof("include path".into())),
         };
+        if !BUILTIN_INCLUDES.contains(&path.as_str()) {
+            return Err(ParseError::UnsupportedInclude {
+                line: self.line(),
+                path,
+            });
+        }
         self.expect(Token::Semicolon)?;
         Ok(Statement::Include(path))
     }
@@ -120,6 +216,70 @@ impl Parser {
         Ok(Statement::BitDecl { name, size })
     }
 
+    /// Parse an input or output declaration: `input float[64] theta;`
+    fn parse_io_decl(&mut self) -> ParseResult<Statement> {
+        let is_input = self.check(&Token::Input);
+        self.advance();
+
+        let ty = self.parse_classical_type()?;
+        let name = self.parse_identifier()?;
+        self.expect(Token::Semicolon)?;
+
+        Ok(if is_input {
+            Statement::InputDecl { name, ty }
+        } else {
+            Statement::OutputDecl { name, ty }
+        })
+    }
+
+    /// Parse a constant declaration: `const int n = 4;`
+    fn parse_const_decl(&mut self) -> ParseResult<Statement> {
+        self.expect(Token::Const)?;
+        let ty = self.parse_classical_type()?;
+        let name = self.parse_identifier()?;
+        self.expect(Token::Eq)?;
+        let value = self.parse_expression()?;
+        self.expect(Token::Semicolon)?;
+
+        Ok(Statement::ConstDecl { name, ty, value })
+    }
+
+    /// Parse a classical type with an optional designator: `float[64]`.
+    fn parse_classical_type(&mut self) -> ParseResult<ClassicalType> {
+        let line = self.peek_line();
+        let token = self
+            .advance()
+            .ok_or_else(|| ParseError::UnexpectedEof("type".into()))?;
+        let ty: fn(Option<u32>) -> ClassicalType = match &token {
+            Token::Bit => ClassicalType::Bit,
+            // Unsigned integers only ever appear as constants and loop
+            // variables, where the sign makes no difference.
+            Token::Int => ClassicalType::Int,
+            Token::Identifier(name) if name == "uint" => ClassicalType::Int,
+            Token::Float => ClassicalType::Float,
+            Token::Identifier(name) if name == "angle" => ClassicalType::Angle,
+            Token::Bool => return Ok(ClassicalType::Bool),
+            other => {
+                return Err(ParseError::UnexpectedToken {
+                    line,
+                    expected: "classical type".into(),
+                    found: other.to_string(),
+                });
+            }
+        };
+
+        let size = if self.consume(&Token::LBracket) {
+            let size = self.parse_int_literal()?;
+            self.expect(Token::RBracket)?;
+            Some(u32::try_from(size).map_err(|_| {
+                ParseError::Generic(format!("Type designator {size} exceeds maximum"))
+            })?)
+        } else {
+            None
+        };
+        Ok(ty(size))
+    }
+
     /// Parse measure statement.
     fn parse_measure(&mut self) -> ParseResult<Statement> {
         self.expect(Token::Measure)?;
@@ -181,17 +341,47 @@ impl Parser {
         })
     }
 
-    /// Parse for loop.
+    /// Parse while loop.
+    fn parse_while(&mut self) -> ParseResult<Statement> {
+        self.expect(Token::While)?;
+        self.expect(Token::LParen)?;
+        let condition = self.parse_expression()?;
+        self.expect(Token::RParen)?;
+        let body = self.parse_block_or_statement()?;
+
+        Ok(Statement::While {
+            condition,
+            body,
+            max_iterations: None,
+        })
+    }
+
+    /// Parse for loop: `for int i in [start:end] { ... }`, or
+    /// `[start:step:end]` with a step.
+    ///
+    /// The loop variable's type may be omitted, as in early `OpenQASM` 3
+    /// drafts.
     fn parse_for(&mut self) -> ParseResult<Statement> {
         self.expect(Token::For)?;
+        let typed = match self.peek() {
+            Some(Token::Identifier(_)) => !matches!(
+                self.tokens.get(self.pos + 1).map(|t| &t.token),
+                Some(Token::In)
+            ),
+            _ => true,
+        };
+        if typed {
+            self.parse_classical_type()?;
+        }
         let variable = self.parse_identifier()?;
         self.expect(Token::In)?;
         self.expect(Token::LBracket)?;
         let start = self.parse_expression()?;
         self.expect(Token::Colon)?;
-        let end = self.parse_expression()?;
+        let mut end = self.parse_expression()?;
         let step = if self.consume(&Token::Colon) {
-            Some(self.parse_expression()?)
+            // The step is the middle component.
+            Some(std::mem::replace(&mut end, self.parse_expression()?))
         } else {
             None
         };
@@ -211,9 +401,13 @@ impl Parser {
         self.expect(Token::Gate)?;
         let name = self.parse_identifier()?;
 
-        // Parse parameters
+        // Parse parameters; `gate g() a` declares none.
         let params = if self.consume(&Token::LParen) {
-            let p = self.parse_identifier_list()?;
+            let p = if self.check(&Token::RParen) {
+                vec![]
+            } else {
+                self.parse_identifier_list()?
+            };
             self.expect(Token::RParen)?;
             p
         } else {
@@ -254,14 +448,12 @@ impl Parser {
 
     /// Parse assignment statement.
     fn parse_assignment(&mut self, target: String) -> ParseResult<Statement> {
-        let index = if self.consume(&Token::LBracket) {
-            let idx_val = self.parse_int_literal()?;
-            let idx = u32::try_from(idx_val)
-                .map_err(|_| ParseError::Generic(format!("Index {idx_val} exceeds maximum")))?;
+        let bit = if self.consume(&Token::LBracket) {
+            let bit = self.parse_bit_index(target.clone())?;
             self.expect(Token::RBracket)?;
-            Some(idx)
+            bit
         } else {
-            None
+            BitRef::register(&target)
         };
 
         self.expect(Token::Eq)?;
@@ -270,16 +462,21 @@ impl Parser {
         if self.consume(&Token::Measure) {
             let qubits = self.parse_qubit_refs()?;
             self.expect(Token::Semicolon)?;
-
-            let bits = if let Some(idx) = index {
-                vec![BitRef::single(&target, idx)]
-            } else {
-                vec![BitRef::register(&target)]
-            };
-
-            return Ok(Statement::Measure { qubits, bits });
+            return Ok(Statement::Measure {
+                qubits,
+                bits: vec![bit],
+            });
         }
 
+        let index = match bit {
+            BitRef::Single { index, .. } => index,
+            _ => {
+                return Err(ParseError::Generic(format!(
+                    "line {}: classical assignments need a constant index",
+                    self.line()
+                )));
+            }
+        };
         let value = self.parse_expression()?;
         self.expect(Token::Semicolon)?;
 
@@ -301,8 +498,35 @@ impl Parser {
             vec![]
         };
 
-        // Parse qubits
-        let qubits = self.parse_qubit_refs()?;
+        // Gate modifier: `ctrl(2) @ x ...`, `inv @ s ...`
+        if self.consume(&Token::At) {
+            let modifier = Self::gate_modifier(&name, params)?;
+            let name = match self.advance() {
+                Some(Token::Identifier(s)) => s,
+                Some(Token::GateU) => "U".into(),
+                Some(Token::GateCX) => "CX".into(),
+                Some(other) => {
+                    return Err(ParseError::UnexpectedToken {
+                        line: self.line(),
+                        expected: "gate name".into(),
+                        found: other.to_string(),
+                    });
+                }
+                None => return Err(ParseError::UnexpectedEof("gate name".into())),
+            };
+            let Statement::Gate(mut call) = self.parse_gate_call(name)? else {
+                unreachable!("parse_gate_call always returns a gate statement");
+            };
+            call.modifiers.insert(0, modifier);
+            return Ok(Statement::Gate(call));
+        }
+
+        // Parse qubits; `gphase(θ);` is the one gate that takes none.
+        let qubits = if name == "gphase" && self.check(&Token::Semicolon) {
+            vec![]
+        } else {
+            self.parse_qubit_refs()?
+        };
         self.expect(Token::Semicolon)?;
 
         Ok(Statement::Gate(GateCall {
@@ -310,9 +534,32 @@ impl Parser {
             params,
             qubits,
             modifiers: vec![],
+            duration: None,
         }))
     }
 
+    /// Build a gate modifier from its keyword and optional argument.
+    fn gate_modifier(keyword: &str, mut args: Vec<Expression>) -> ParseResult<GateModifier> {
+        let control_count = |args: Vec<Expression>| match args.as_slice() {
+            [] => Ok(None),
+            [Expression::Int(n)] if *n > 0 => u32::try_from(*n)
+                .map(Some)
+                .map_err(|_| ParseError::Generic(format!("Control count {n} is too large"))),
+            _ => Err(ParseError::Generic(format!(
+                "'{keyword}' modifier takes a positive integer literal"
+            ))),
+        };
+        match keyword {
+            "ctrl" => control_count(args).map(GateModifier::Ctrl),
+            "negctrl" => control_count(args).map(GateModifier::NegCtrl),
+            "inv" if args.is_empty() => Ok(GateModifier::Inv),
+            "pow" if args.len() == 1 => Ok(GateModifier::Pow(args.remove(0))),
+            _ => Err(ParseError::Generic(format!(
+                "Unknown gate modifier '{keyword}'"
+            ))),
+        }
+    }
+
     /// Parse a block or single statement.
     pub(super) fn parse_block_or_statement(&mut self) -> ParseResult<Vec<Statement>> {
         if self.consume(&Token::LBrace) {
@@ -341,15 +588,20 @@ impl Parser {
         let register = self.parse_identifier()?;
 
         if self.consume(&Token::LBracket) {
-            let idx_val = self.parse_int_literal()?;
-            let index = u32::try_from(idx_val).map_err(|_| {
-                ParseError::Generic(format!("Qubit index {idx_val} exceeds maximum"))
-            })?;
+            let qubit = match self.parse_expression()? {
+                Expression::Int(idx_val) => {
+                    let index = u32::try_from(idx_val).map_err(|_| {
+                        ParseError::Generic(format!("Qubit index {idx_val} exceeds maximum"))
+                    })?;
+                    QubitRef::Single {
+                        register,
+                        index: Some(index),
+                    }
+                }
+                index => QubitRef::Indexed { register, index },
+            };
             self.expect(Token::RBracket)?;
-            Ok(QubitRef::Single {
-                register,
-                index: Some(index),
-            })
+            Ok(qubit)
         } else {
             Ok(QubitRef::Single {
                 register,
@@ -372,14 +624,9 @@ impl Parser {
         let register = self.parse_identifier()?;
 
         if self.consume(&Token::LBracket) {
-            let idx_val = self.parse_int_literal()?;
-            let index = u32::try_from(idx_val)
-                .map_err(|_| ParseError::Generic(format!("Bit index {idx_val} exceeds maximum")))?;
+            let bit = self.parse_bit_index(register)?;
             self.expect(Token::RBracket)?;
-            Ok(BitRef::Single {
-                register,
-                index: Some(index),
-            })
+            Ok(bit)
         } else {
             Ok(BitRef::Single {
                 register,
@@ -387,4 +634,21 @@ impl Parser {
             })
         }
     }
+
+    /// Parse the index of `register[...]`: a literal gives a single bit,
+    /// any other expression an indexed bit resolved during lowering.
+    fn parse_bit_index(&mut self, register: String) -> ParseResult<BitRef> {
+        Ok(match self.parse_expression()? {
+            Expression::Int(idx_val) => {
+                let index = u32::try_from(idx_val).map_err(|_| {
+                    ParseError::Generic(format!("Bit index {idx_val} exceeds maximum"))
+                })?;
+                BitRef::Single {
+                    register,
+                    index: Some(index),
+                }
+            }
+            index => BitRef::Indexed { register, index },
+        })
+    }
 }
@@ -595,6 +595,8 @@ pub unsafe extern "C" fn ARVAK_QDMI_device_job_submit(job_handle: *mut c_void) -
                 backend_id,
                 shots,
                 optimization_level: 1,
+                signature: None,
+                parameters: std::collections::HashMap::new(),
             });
 
             match client.submit_job(req).await {
@@ -0,0 +1,617 @@
+//! Experiment campaigns: grids of circuit executions with resumable results.
+//!
+//! A campaign spec lists circuits, backends, shot counts and mitigation
+//! settings; the campaign runs every combination of them. Each completed
+//! point is appended to a JSON Lines dataset as a [`CampaignRecord`] with
+//! its counts and provenance, so an interrupted campaign resumes by skipping
+//! the points already in the dataset.
+//!
+//! A spec is a YAML file. Circuit paths are relative to the spec file;
+//! `mitigation` defaults to `[none]` and `executor` to direct execution on
+//! the backends:
+//!
+//! ```yaml
+//! name: readout-study
+//! circuits: [bell.qasm, ghz5.qasm]
+//! backends: [simulator, iqm]
+//! shots: [1000, 4000]
+//! mitigation: [none, readout]
+//! executor:
+//!   type: slurm
+//!   partition: q_fiqci
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use arvak_hal::Counts;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SchedError, SchedResult};
+use crate::pbs::PbsConfig;
+use crate::scheduler::SchedulerConfig;
+use crate::slurm::SlurmConfig;
+
+/// Largest register that readout mitigation handles. The tensored inverse
+/// can spread the counts over all `2^n` bitstrings.
+pub const MAX_MITIGATED_BITS: usize = 20;
+
+/// A campaign definition: the grid of experiments to run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CampaignSpec {
+    /// Campaign name, recorded in every dataset row.
+    pub name: String,
+
+    /// Circuit files (QASM3).
+    pub circuits: Vec<String>,
+
+    /// Backend names, as accepted by `arvak run --backend`.
+    pub backends: Vec<String>,
+
+    /// Shot counts.
+    pub shots: Vec<u32>,
+
+    /// Mitigation settings.
+    #[serde(default = "default_mitigation")]
+    pub mitigation: Vec<Mitigation>,
+
+    /// How jobs are executed.
+    #[serde(default)]
+    pub executor: Executor,
+}
+
+fn default_mitigation() -> Vec<Mitigation> {
+    vec![Mitigation::None]
+}
+
+impl CampaignSpec {
+    /// Parse a campaign spec from YAML.
+    pub fn from_yaml(yaml: &str) -> SchedResult<Self> {
+        let spec: Self = serde_yaml_ng::from_str(yaml)
+            .map_err(|e| SchedError::ConfigError(format!("invalid campaign spec: {e}")))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Load a campaign spec from a YAML file.
+    pub fn from_file(path: impl AsRef<Path>) -> SchedResult<Self> {
+        Self::from_yaml(&fs::read_to_string(path)?)
+    }
+
+    fn validate(&self) -> SchedResult<()> {
+        let axes = [
+            ("circuits", self.circuits.is_empty()),
+            ("backends", self.backends.is_empty()),
+            ("shots", self.shots.is_empty()),
+            ("mitigation", self.mitigation.is_empty()),
+        ];
+        if let Some((axis, _)) = axes.iter().find(|(_, empty)| *empty) {
+            return Err(SchedError::ConfigError(format!(
+                "campaign '{}' has no {axis}",
+                self.name
+            )));
+        }
+        if self.shots.contains(&0) {
+            return Err(SchedError::ConfigError(format!(
+                "campaign '{}': shot counts must be positive",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// All points of the grid, circuits varying slowest and mitigation
+    /// fastest. Repeated values yield one point.
+    pub fn points(&self) -> Vec<CampaignPoint> {
+        let mut seen = HashSet::new();
+        let mut points = Vec::new();
+        for circuit in &self.circuits {
+            for backend in &self.backends {
+                for &shots in &self.shots {
+                    for &mitigation in &self.mitigation {
+                        let point = CampaignPoint {
+                            circuit: circuit.clone(),
+                            backend: backend.clone(),
+                            shots,
+                            mitigation,
+                        };
+                        if seen.insert(point.key()) {
+                            points.push(point);
+                        }
+                    }
+                }
+            }
+        }
+        points
+    }
+}
+
+/// Error mitigation applied to a campaign point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mitigation {
+    /// Raw counts only.
+    None,
+    /// Tensored readout-error mitigation from all-zeros and all-ones
+    /// calibration runs (see [`ReadoutCalibration`]).
+    Readout,
+}
+
+impl fmt::Display for Mitigation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mitigation::None => write!(f, "none"),
+            Mitigation::Readout => write!(f, "readout"),
+        }
+    }
+}
+
+/// Where campaign jobs run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Executor {
+    /// Submit directly to the backends.
+    #[default]
+    Direct,
+    /// Submit through SLURM.
+    Slurm {
+        /// SLURM partition.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        partition: Option<String>,
+        /// SLURM account.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account: Option<String>,
+    },
+    /// Submit through PBS.
+    Pbs {
+        /// PBS queue.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        queue: Option<String>,
+        /// PBS account.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account: Option<String>,
+    },
+}
+
+impl Executor {
+    /// The batch scheduler configuration, or `None` for direct execution.
+    pub fn scheduler_config(&self) -> Option<SchedulerConfig> {
+        match self {
+            Executor::Direct => None,
+            Executor::Slurm { partition, account } => {
+                let mut slurm = SlurmConfig::default();
+                if let Some(p) = partition {
+                    slurm.partition.clone_from(p);
+                }
+                slurm.account.clone_from(account);
+                Some(SchedulerConfig::with_slurm(slurm))
+            }
+            Executor::Pbs { queue, account } => {
+                let mut pbs = PbsConfig::default();
+                if let Some(q) = queue {
+                    pbs.queue.clone_from(q);
+                }
+                pbs.account.clone_from(account);
+                Some(SchedulerConfig::with_pbs(pbs))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Executor::Direct => write!(f, "direct"),
+            Executor::Slurm { .. } => write!(f, "slurm"),
+            Executor::Pbs { .. } => write!(f, "pbs"),
+        }
+    }
+}
+
+/// One experiment of a campaign.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CampaignPoint {
+    /// Circuit file, as written in the spec.
+    pub circuit: String,
+    /// Backend name.
+    pub backend: String,
+    /// Shot count.
+    pub shots: u32,
+    /// Mitigation setting.
+    pub mitigation: Mitigation,
+}
+
+impl CampaignPoint {
+    /// Key identifying the point in a dataset.
+    pub fn key(&self) -> String {
+        format!(
+            "{}|{}|{}|{}",
+            self.circuit, self.backend, self.shots, self.mitigation
+        )
+    }
+}
+
+/// Where and how a result was produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Arvak version that ran the point.
+    pub arvak_version: String,
+    /// Content hash of the circuit source.
+    pub circuit_hash: String,
+    /// How the job was executed.
+    pub executor: Executor,
+    /// Scheduler job ID, for batch executors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    /// When execution of the point started.
+    pub started_at: DateTime<Utc>,
+    /// When execution of the point finished.
+    pub finished_at: DateTime<Utc>,
+    /// Execution time reported by the backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_time_ms: Option<u64>,
+}
+
+/// One row of a campaign dataset: the result of a completed point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignRecord {
+    /// Campaign name.
+    pub campaign: String,
+    /// Circuit file, as written in the spec.
+    pub circuit: String,
+    /// Backend name.
+    pub backend: String,
+    /// Shot count.
+    pub shots: u32,
+    /// Mitigation setting.
+    pub mitigation: Mitigation,
+    /// Raw measurement counts.
+    pub counts: BTreeMap<String, u64>,
+    /// Mitigated quasi-probabilities (may be slightly negative).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mitigated: Option<BTreeMap<String, f64>>,
+    /// Readout calibration used for mitigation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readout_calibration: Option<ReadoutCalibration>,
+    /// Where and how the result was produced.
+    pub provenance: Provenance,
+}
+
+impl CampaignRecord {
+    /// The point this record belongs to.
+    pub fn point(&self) -> CampaignPoint {
+        CampaignPoint {
+            circuit: self.circuit.clone(),
+            backend: self.backend.clone(),
+            shots: self.shots,
+            mitigation: self.mitigation,
+        }
+    }
+}
+
+/// A campaign's results: a JSON Lines file with one [`CampaignRecord`]
+/// per completed point.
+///
+/// Records are appended and flushed as points complete. A final line cut
+/// off by a crash is dropped when the dataset is reopened.
+#[derive(Debug)]
+pub struct CampaignDataset {
+    path: PathBuf,
+    campaign: String,
+    records: Vec<CampaignRecord>,
+    done: HashSet<String>,
+}
+
+impl CampaignDataset {
+    /// Open the dataset of `campaign` at `path`, loading existing records.
+    ///
+    /// Fails if the file holds records of a different campaign.
+    pub fn open(path: impl Into<PathBuf>, campaign: &str) -> SchedResult<Self> {
+        let path = path.into();
+        let mut records = Vec::new();
+        if path.exists() {
+            let data = fs::read_to_string(&path)?;
+            let lines: Vec<&str> = data.lines().filter(|l| !l.trim().is_empty()).collect();
+            for (i, line) in lines.iter().enumerate() {
+                match serde_json::from_str::<CampaignRecord>(line) {
+                    Ok(record) => records.push(record),
+                    Err(e) if i + 1 == lines.len() => {
+                        tracing::warn!("Dropping truncated last record of {}: {e}", path.display());
+                        let mut file = File::create(&path)?;
+                        for record in &records {
+                            writeln!(file, "{}", serde_json::to_string(record)?)?;
+                        }
+                    }
+                    Err(e) => {
+                        return Err(SchedError::PersistenceError(format!(
+                            "{}:{}: invalid campaign record: {e}",
+                            path.display(),
+                            i + 1
+                        )));
+                    }
+                }
+            }
+        }
+        if let Some(other) = records.iter().find(|r| r.campaign != campaign) {
+            return Err(SchedError::ConfigError(format!(
+                "{} holds results of campaign '{}', not '{campaign}'",
+                path.display(),
+                other.campaign
+            )));
+        }
+        let done = records.iter().map(|r| r.point().key()).collect();
+        Ok(Self {
+            path,
+            campaign: campaign.to_string(),
+            records,
+            done,
+        })
+    }
+
+    /// Path of the dataset file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records loaded or appended so far.
+    pub fn records(&self) -> &[CampaignRecord] {
+        &self.records
+    }
+
+    /// Check whether `point` already has a result.
+    pub fn is_done(&self, point: &CampaignPoint) -> bool {
+        self.done.contains(&point.key())
+    }
+
+    /// Append a record and flush it to disk.
+    pub fn append(&mut self, record: CampaignRecord) -> SchedResult<()> {
+        if record.campaign != self.campaign {
+            return Err(SchedError::ConfigError(format!(
+                "record of campaign '{}' appended to dataset of '{}'",
+                record.campaign, self.campaign
+            )));
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        file.sync_data()?;
+        self.done.insert(record.point().key());
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+/// Per-bit readout error rates, measured by preparing all zeros and all
+/// ones.
+///
+/// Bit `i` is the `i`-th character from the right of a bitstring, matching
+/// the HAL convention that qubit 0 is rightmost.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadoutCalibration {
+    /// Probability of reading 1 when 0 was prepared, per bit.
+    pub p1_given_0: Vec<f64>,
+    /// Probability of reading 0 when 1 was prepared, per bit.
+    pub p0_given_1: Vec<f64>,
+}
+
+impl ReadoutCalibration {
+    /// Estimate error rates from the counts of the all-zeros and all-ones
+    /// calibration circuits on `num_bits` bits.
+    pub fn from_counts(zeros: &Counts, ones: &Counts, num_bits: usize) -> SchedResult<Self> {
+        let flip_rates = |counts: &Counts, prepared: u8| -> SchedResult<Vec<f64>> {
+            let total = counts.total_shots();
+            if total == 0 {
+                return Err(SchedError::BackendError(
+                    "readout calibration returned no shots".into(),
+                ));
+            }
+            let mut flips = vec![0u64; num_bits];
+            for (bitstring, &count) in counts.iter() {
+                let bits = bitstring.as_bytes();
+                if bits.len() != num_bits {
+                    return Err(SchedError::BackendError(format!(
+                        "readout calibration returned '{bitstring}', expected {num_bits} bits"
+                    )));
+                }
+                for (i, flipped) in flips.iter_mut().enumerate() {
+                    if bits[num_bits - 1 - i] != prepared {
+                        *flipped += count;
+                    }
+                }
+            }
+            Ok(flips.iter().map(|&f| f as f64 / total as f64).collect())
+        };
+        Ok(Self {
+            p1_given_0: flip_rates(zeros, b'0')?,
+            p0_given_1: flip_rates(ones, b'1')?,
+        })
+    }
+
+    /// Number of calibrated bits.
+    pub fn num_bits(&self) -> usize {
+        self.p1_given_0.len()
+    }
+
+    /// Apply the inverse of the per-bit confusion matrices to `counts`.
+    ///
+    /// Returns quasi-probabilities: they sum to one but may be slightly
+    /// negative where the raw counts are noisy.
+    pub fn mitigate(&self, counts: &Counts) -> SchedResult<BTreeMap<String, f64>> {
+        let n = self.num_bits();
+        if n > MAX_MITIGATED_BITS {
+            return Err(SchedError::ConfigError(format!(
+                "readout mitigation supports at most {MAX_MITIGATED_BITS} bits, got {n}"
+            )));
+        }
+        let total = counts.total_shots() as f64;
+        let mut dist: BTreeMap<Vec<u8>, f64> = BTreeMap::new();
+        for (bitstring, &count) in counts.iter() {
+            if bitstring.len() != n {
+                return Err(SchedError::BackendError(format!(
+                    "result bitstring '{bitstring}' does not match the {n}-bit calibration"
+                )));
+            }
+            *dist.entry(bitstring.as_bytes().to_vec()).or_default() += count as f64 / total;
+        }
+
+        for i in 0..n {
+            let (e0, e1) = (self.p1_given_0[i], self.p0_given_1[i]);
+            let det = 1.0 - e0 - e1;
+            if det.abs() < 1e-9 {
+                return Err(SchedError::BackendError(format!(
+                    "readout calibration of bit {i} is not invertible"
+                )));
+            }
+            // Inverse of [[1 - e0, e1], [e0, 1 - e1]] (column = prepared).
+            let inverse = [[(1.0 - e1) / det, -e1 / det], [-e0 / det, (1.0 - e0) / det]];
+            let pos = n - 1 - i;
+            let mut next: BTreeMap<Vec<u8>, f64> = BTreeMap::new();
+            for (bits, p) in dist {
+                let measured = usize::from(bits[pos] == b'1');
+                for (actual, row) in inverse.iter().enumerate() {
+                    let mut target = bits.clone();
+                    target[pos] = if actual == 1 { b'1' } else { b'0' };
+                    *next.entry(target).or_default() += row[measured] * p;
+                }
+            }
+            dist = next;
+        }
+
+        Ok(dist
+            .into_iter()
+            .filter(|(_, p)| p.abs() > 1e-12)
+            .map(|(bits, p)| (String::from_utf8_lossy(&bits).into_owned(), p))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r"
+name: study
+circuits: [bell.qasm, ghz.qasm]
+backends: [simulator]
+shots: [100, 1000, 100]
+mitigation: [none, readout]
+executor:
+  type: slurm
+  partition: q_fiqci
+";
+
+    fn record(spec: &CampaignSpec, point: &CampaignPoint) -> CampaignRecord {
+        CampaignRecord {
+            campaign: spec.name.clone(),
+            circuit: point.circuit.clone(),
+            backend: point.backend.clone(),
+            shots: point.shots,
+            mitigation: point.mitigation,
+            counts: BTreeMap::from([("00".to_string(), u64::from(point.shots))]),
+            mitigated: None,
+            readout_calibration: None,
+            provenance: Provenance {
+                arvak_version: "test".into(),
+                circuit_hash: "abc".into(),
+                executor: spec.executor.clone(),
+                job_id: None,
+                started_at: Utc::now(),
+                finished_at: Utc::now(),
+                execution_time_ms: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_spec_points() {
+        let spec = CampaignSpec::from_yaml(SPEC).unwrap();
+        assert_eq!(
+            spec.executor,
+            Executor::Slurm {
+                partition: Some("q_fiqci".into()),
+                account: None
+            }
+        );
+        assert!(spec.executor.scheduler_config().is_some());
+
+        let points = spec.points();
+        assert_eq!(points.len(), 2 * 2 * 2);
+        assert_eq!(points[0].key(), "bell.qasm|simulator|100|none");
+        assert_eq!(points[1].key(), "bell.qasm|simulator|100|readout");
+        assert_eq!(points[7].key(), "ghz.qasm|simulator|1000|readout");
+    }
+
+    #[test]
+    fn test_spec_defaults_and_validation() {
+        let spec =
+            CampaignSpec::from_yaml("name: s\ncircuits: [a.qasm]\nbackends: [sim]\nshots: [10]")
+                .unwrap();
+        assert_eq!(spec.mitigation, vec![Mitigation::None]);
+        assert_eq!(spec.executor, Executor::Direct);
+        assert!(spec.executor.scheduler_config().is_none());
+
+        assert!(
+            CampaignSpec::from_yaml("name: s\ncircuits: []\nbackends: [sim]\nshots: [10]").is_err()
+        );
+        assert!(
+            CampaignSpec::from_yaml("name: s\ncircuits: [a]\nbackends: [sim]\nshots: [0]").is_err()
+        );
+        assert!(
+            CampaignSpec::from_yaml("name: s\ncircuits: [a]\nbackends: [sim]\nshot: [1]").is_err()
+        );
+    }
+
+    #[test]
+    fn test_dataset_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("study.jsonl");
+        let spec = CampaignSpec::from_yaml(SPEC).unwrap();
+        let points = spec.points();
+
+        let mut dataset = CampaignDataset::open(&path, &spec.name).unwrap();
+        dataset.append(record(&spec, &points[0])).unwrap();
+        dataset.append(record(&spec, &points[1])).unwrap();
+
+        // Simulate a crash in the middle of writing the third record.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"campaign\":\"study\",\"circ").unwrap();
+        drop(file);
+
+        let mut dataset = CampaignDataset::open(&path, &spec.name).unwrap();
+        assert_eq!(dataset.records().len(), 2);
+        assert!(dataset.is_done(&points[1]));
+        assert!(!dataset.is_done(&points[2]));
+        dataset.append(record(&spec, &points[2])).unwrap();
+
+        let dataset = CampaignDataset::open(&path, &spec.name).unwrap();
+        assert_eq!(dataset.records().len(), 3);
+        assert!(CampaignDataset::open(&path, "other").is_err());
+    }
+
+    #[test]
+    fn test_readout_mitigation() {
+        // Bit 0 reads 1 a tenth of the time when prepared in 0; bit 1 is ideal.
+        let zeros = Counts::from_pairs([("00", 900), ("01", 100)]);
+        let ones = Counts::from_pairs([("11", 1000)]);
+        let calibration = ReadoutCalibration::from_counts(&zeros, &ones, 2).unwrap();
+        assert_eq!(calibration.p1_given_0, vec![0.1, 0.0]);
+        assert_eq!(calibration.p0_given_1, vec![0.0, 0.0]);
+
+        // A noisy Bell state: half of |00> leaks to 01 at the same rate.
+        let noisy = Counts::from_pairs([("00", 450), ("01", 50), ("11", 500)]);
+        let mitigated = calibration.mitigate(&noisy).unwrap();
+        assert!((mitigated["00"] - 0.5).abs() < 1e-9);
+        assert!((mitigated["11"] - 0.5).abs() < 1e-9);
+        assert!(!mitigated.contains_key("01"));
+
+        let wrong_width = Counts::from_pairs([("000", 10)]);
+        assert!(calibration.mitigate(&wrong_width).is_err());
+    }
+}
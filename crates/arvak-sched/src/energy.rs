@@ -0,0 +1,131 @@
+//! Node-hour and energy accounting for batch jobs and workflows.
+//!
+//! SLURM reports elapsed time, node count and consumed energy for finished
+//! jobs through `sacct` (`ElapsedRaw`, `NNodes`, `ConsumedEnergyRaw`). The
+//! scheduler records them on each job as [`JobAccounting`], and
+//! [`EnergyReport`] sums them over a set of jobs such as a workflow.
+//! Energy is only available where the site runs an energy accounting
+//! plugin; node-hours are always reported.
+
+use serde::{Deserialize, Serialize};
+
+use crate::job::ScheduledJob;
+
+/// Joules per kilowatt-hour.
+const JOULES_PER_KWH: f64 = 3.6e6;
+
+/// Resource usage of one batch job, from scheduler accounting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobAccounting {
+    /// Wall-clock run time in seconds.
+    pub elapsed_seconds: u64,
+
+    /// Number of nodes allocated to the job.
+    pub num_nodes: u32,
+
+    /// Energy consumed in joules, if the site measures it.
+    pub consumed_energy_joules: Option<f64>,
+}
+
+impl JobAccounting {
+    /// Node-hours consumed (elapsed time × nodes).
+    pub fn node_hours(&self) -> f64 {
+        self.elapsed_seconds as f64 * f64::from(self.num_nodes) / 3600.0
+    }
+
+    /// Energy consumed in kilowatt-hours, if measured.
+    pub fn energy_kwh(&self) -> Option<f64> {
+        self.consumed_energy_joules.map(|j| j / JOULES_PER_KWH)
+    }
+}
+
+/// Node-hours and energy summed over a set of jobs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnergyReport {
+    /// Jobs with accounting data.
+    pub accounted_jobs: usize,
+
+    /// Jobs whose energy was measured.
+    pub measured_jobs: usize,
+
+    /// Total node-hours.
+    pub node_hours: f64,
+
+    /// Total measured energy in kilowatt-hours.
+    pub energy_kwh: f64,
+
+    /// Estimated emissions in kg CO₂-equivalent, if a carbon intensity is
+    /// configured.
+    pub co2_kg: Option<f64>,
+}
+
+impl EnergyReport {
+    /// Sum the accounting data of `jobs`; jobs without any are skipped.
+    pub fn from_jobs<'a>(jobs: impl IntoIterator<Item = &'a ScheduledJob>) -> Self {
+        let mut report = Self::default();
+        for accounting in jobs.into_iter().filter_map(|j| j.accounting.as_ref()) {
+            report.accounted_jobs += 1;
+            report.node_hours += accounting.node_hours();
+            if let Some(kwh) = accounting.energy_kwh() {
+                report.measured_jobs += 1;
+                report.energy_kwh += kwh;
+            }
+        }
+        report
+    }
+
+    /// Estimate emissions from the measured energy with a grid carbon
+    /// intensity in g CO₂e/kWh.
+    #[must_use]
+    pub fn with_carbon_intensity(mut self, grams_per_kwh: f64) -> Self {
+        self.co2_kg = Some(self.energy_kwh * grams_per_kwh / 1000.0);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::CircuitSpec;
+
+    fn job(accounting: Option<JobAccounting>) -> ScheduledJob {
+        let mut job = ScheduledJob::new("job", CircuitSpec::from_qasm("OPENQASM 3.0;"));
+        job.accounting = accounting;
+        job
+    }
+
+    #[test]
+    fn test_job_accounting() {
+        let accounting = JobAccounting {
+            elapsed_seconds: 1800,
+            num_nodes: 2,
+            consumed_energy_joules: Some(7.2e6),
+        };
+        assert!((accounting.node_hours() - 1.0).abs() < 1e-12);
+        assert_eq!(accounting.energy_kwh(), Some(2.0));
+    }
+
+    #[test]
+    fn test_energy_report() {
+        let jobs = [
+            job(Some(JobAccounting {
+                elapsed_seconds: 3600,
+                num_nodes: 1,
+                consumed_energy_joules: Some(3.6e6),
+            })),
+            job(Some(JobAccounting {
+                elapsed_seconds: 3600,
+                num_nodes: 2,
+                consumed_energy_joules: None,
+            })),
+            job(None),
+        ];
+
+        let report = EnergyReport::from_jobs(&jobs).with_carbon_intensity(250.0);
+        assert_eq!(report.accounted_jobs, 2);
+        assert_eq!(report.measured_jobs, 1);
+        assert!((report.node_hours - 3.0).abs() < 1e-12);
+        assert!((report.energy_kwh - 1.0).abs() < 1e-12);
+        assert_eq!(report.co2_kg, Some(0.25));
+    }
+}
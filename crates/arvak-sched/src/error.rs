@@ -56,6 +56,14 @@ pub enum SchedError {
     #[error("No matching backend found: {0}")]
     NoMatchingBackend(String),
 
+    /// Every backend that would match is in, or about to enter, maintenance.
+    #[error("All matching backends are in maintenance: {0}")]
+    BackendsInMaintenance(String),
+
+    /// The job's circuit signatures are missing, invalid or untrusted.
+    #[error("Signature rejected: {0}")]
+    SignatureRejected(String),
+
     /// Backend error during execution.
     #[error("Backend error: {0}")]
     BackendError(String),
@@ -103,6 +111,18 @@ impl From<arvak_hal::HalError> for SchedError {
     }
 }
 
+impl From<arvak_hal::EncryptionError> for SchedError {
+    fn from(e: arvak_hal::EncryptionError) -> Self {
+        SchedError::PersistenceError(e.to_string())
+    }
+}
+
+impl From<arvak_hal::SignatureError> for SchedError {
+    fn from(e: arvak_hal::SignatureError) -> Self {
+        SchedError::SignatureRejected(e.to_string())
+    }
+}
+
 impl From<rusqlite::Error> for SchedError {
     fn from(e: rusqlite::Error) -> Self {
         SchedError::DatabaseError(e.to_string())
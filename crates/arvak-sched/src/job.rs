@@ -1,11 +1,15 @@
 //! Job types for the HPC scheduler.
 
-use arvak_hal::JobId;
+use std::collections::BTreeMap;
+
+use arvak_hal::{CircuitSignature, JobId, Provenance};
 use arvak_ir::Circuit;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::energy::JobAccounting;
+
 /// Unique identifier for a scheduled job.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ScheduledJobId(pub Uuid);
@@ -373,17 +377,19 @@ impl CircuitSpec {
         CircuitSpec::QasmFile(path.into())
     }
 
-    /// Resolve the circuit spec to a circuit.
-    pub fn resolve(&self) -> crate::SchedResult<Circuit> {
+    /// The QASM3 source of the circuit, read from disk for file specs.
+    pub fn source(&self) -> crate::SchedResult<String> {
         match self {
-            CircuitSpec::Qasm3(qasm) => Ok(arvak_qasm3::parse(qasm)?),
-            CircuitSpec::QasmFile(path) => {
-                let qasm = std::fs::read_to_string(path)?;
-                Ok(arvak_qasm3::parse(&qasm)?)
-            }
+            CircuitSpec::Qasm3(qasm) => Ok(qasm.clone()),
+            CircuitSpec::QasmFile(path) => Ok(std::fs::read_to_string(path)?),
         }
     }
 
+    /// Resolve the circuit spec to a circuit.
+    pub fn resolve(&self) -> crate::SchedResult<Circuit> {
+        Ok(arvak_qasm3::parse(&self.source()?)?)
+    }
+
     /// Get the number of qubits in the circuit.
     // TODO: Cache parsed qubit count to avoid re-parsing on every call
     pub fn num_qubits(&self) -> crate::SchedResult<u32> {
@@ -433,6 +439,27 @@ pub struct ScheduledJob {
 
     /// Arbitrary metadata.
     pub metadata: rustc_hash::FxHashMap<String, String>,
+
+    /// Node-hours and energy reported by the batch scheduler once the job
+    /// has finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounting: Option<JobAccounting>,
+
+    /// Signatures over the circuit sources, one per circuit in order, or
+    /// empty for an unsigned job.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<CircuitSignature>,
+
+    /// Values for the circuits' OpenQASM 3.0 `input float[64]` parameters,
+    /// bound when the batch job runs. Sorted by name so generated batch
+    /// scripts are reproducible.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, f64>,
+
+    /// Provenance of the submitted circuit (its source or compiled
+    /// artifact id), linking the job and its result to that artifact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
 }
 
 impl ScheduledJob {
@@ -452,6 +479,10 @@ impl ScheduledJob {
             submitted_at: None,
             completed_at: None,
             metadata: rustc_hash::FxHashMap::default(),
+            accounting: None,
+            signatures: Vec::new(),
+            parameters: BTreeMap::new(),
+            provenance: None,
         }
     }
 
@@ -471,6 +502,10 @@ impl ScheduledJob {
             submitted_at: None,
             completed_at: None,
             metadata: rustc_hash::FxHashMap::default(),
+            accounting: None,
+            signatures: Vec::new(),
+            parameters: BTreeMap::new(),
+            provenance: None,
         }
     }
 
@@ -504,6 +539,53 @@ impl ScheduledJob {
         self
     }
 
+    /// Attach signatures over the circuit sources, one per circuit.
+    pub fn with_signatures(
+        mut self,
+        signatures: impl IntoIterator<Item = CircuitSignature>,
+    ) -> Self {
+        self.signatures = signatures.into_iter().collect();
+        self
+    }
+
+    /// Set values for the circuits' `input` parameters.
+    pub fn with_parameters(
+        mut self,
+        parameters: impl IntoIterator<Item = (impl Into<String>, f64)>,
+    ) -> Self {
+        self.parameters = parameters
+            .into_iter()
+            .map(|(name, value)| (name.into(), value))
+            .collect();
+        self
+    }
+
+    /// Set the provenance of the submitted circuit.
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Provenance of this job's submission to `backend`: the job's own
+    /// provenance extended by the backend, shots and parameters. `None` if
+    /// the job has no provenance.
+    pub fn submission_provenance(&self, backend: &str) -> Option<Provenance> {
+        let provenance = self.provenance.as_ref()?;
+        if provenance.stage == arvak_hal::provenance::Stage::Submission {
+            return Some(provenance.clone());
+        }
+        let parameters: std::collections::HashMap<String, f64> = self
+            .parameters
+            .iter()
+            .map(|(name, value)| (name.clone(), *value))
+            .collect();
+        Some(provenance.submission(
+            backend,
+            self.shots,
+            (!parameters.is_empty()).then_some(&parameters),
+        ))
+    }
+
     /// Add metadata.
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.metadata.insert(key.into(), value.into());
@@ -527,6 +609,47 @@ impl ScheduledJob {
         Ok(max)
     }
 
+    /// Check that [`parameters`](Self::parameters) gives a value for every
+    /// `input` parameter of every circuit.
+    pub fn check_parameters(&self) -> crate::SchedResult<()> {
+        for circuit in &self.circuits {
+            let unbound: Vec<String> = circuit
+                .resolve()?
+                .parameters()
+                .into_iter()
+                .filter(|name| !self.parameters.contains_key(name))
+                .collect();
+            if !unbound.is_empty() {
+                return Err(crate::SchedError::ConfigError(
+                    arvak_ir::IrError::UnboundParameters(unbound).to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// `--param name=value` arguments for `arvak run`, one per parameter.
+    ///
+    /// Names must be identifiers, so the arguments are safe to interpolate
+    /// into a batch script.
+    pub(crate) fn parameter_args(&self) -> crate::SchedResult<String> {
+        let mut args = String::new();
+        for (name, value) in &self.parameters {
+            let is_identifier = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !is_identifier || !value.is_finite() {
+                return Err(crate::SchedError::ConfigError(format!(
+                    "invalid parameter binding {name}={value}"
+                )));
+            }
+            args.push_str(&format!(" --param {name}={value}"));
+        }
+        Ok(args)
+    }
+
     /// Check if this is a batch job.
     pub fn is_batch(&self) -> bool {
         self.circuits.len() > 1
@@ -702,6 +825,31 @@ mod tests {
         assert_eq!(job.metadata.get("user"), Some(&"alice".to_string()));
     }
 
+    #[test]
+    fn test_submission_provenance() {
+        let qasm = "OPENQASM 3.0; qubit[1] q; rx(theta) q[0];";
+        let job = ScheduledJob::new("sweep", CircuitSpec::from_qasm(qasm));
+        assert!(job.submission_provenance("sim").is_none());
+
+        let source = Provenance::source(qasm);
+        let job = job
+            .with_provenance(source.clone())
+            .with_parameters([("theta", 0.5)]);
+        let submission = job.submission_provenance("sim").unwrap();
+        assert_eq!(submission.parent(), Some(&source.artifact_id));
+        assert_ne!(
+            submission,
+            job.clone()
+                .with_parameters([("theta", 1.0)])
+                .submission_provenance("sim")
+                .unwrap()
+        );
+
+        // A job submitted with a submission id keeps it.
+        let job = job.with_provenance(submission.clone());
+        assert_eq!(job.submission_provenance("other"), Some(submission));
+    }
+
     #[test]
     fn test_resource_requirements_builder() {
         let req = ResourceRequirements::new(5)
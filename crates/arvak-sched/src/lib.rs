@@ -23,8 +23,13 @@
 //! - **Multi-Scheduler**: Unified API for SLURM and PBS
 //! - **Workflows**: DAG-based job dependencies for complex pipelines
 //! - **Persistence**: JSON or `SQLite` storage for job state
+//! - **Retention**: Age- and size-based cleanup of finished jobs, with archiving
 //! - **Batch Jobs**: Submit multiple circuits as array jobs
 //! - **Resource Matching**: Automatic backend selection based on circuit requirements
+//! - **Maintenance Windows**: Jobs are kept out of planned backend maintenance
+//! - **Capacity Planning**: Discrete-event replay of job traces to predict queue times
+//! - **Campaigns**: Resumable experiment grids over circuits, backends, shots and mitigation
+//! - **Energy Accounting**: Node-hours and consumed energy per job and workflow from `sacct`
 //!
 //! # Example: Single Job Submission
 //!
@@ -105,29 +110,53 @@
 //! ```
 
 pub mod broker;
+pub mod campaign;
+pub mod energy;
 pub mod error;
 pub mod job;
+pub mod maintenance;
 pub mod matcher;
 pub mod pbs;
 pub mod persistence;
 pub mod queue;
+pub mod retention;
 pub mod router;
 pub mod scheduler;
+pub mod simulation;
 pub mod slurm;
 pub mod workflow;
 
 // Re-exports
 pub use broker::{InMemoryBroker, JobMessage, MessageBroker, MessageSubscription};
+pub use campaign::{
+    CampaignDataset, CampaignPoint, CampaignRecord, CampaignSpec, Executor, Mitigation,
+    ReadoutCalibration,
+};
+pub use energy::{EnergyReport, JobAccounting};
 pub use error::{SchedError, SchedResult};
 pub use job::{
     CircuitSpec, JobFilter, Priority, ResourceRequirements, ScheduledJob, ScheduledJobId,
     ScheduledJobStatus, TopologyPreference,
 };
+pub use maintenance::MaintenanceCalendar;
 pub use matcher::{MatchResult, ResourceMatcher};
 pub use pbs::{PbsAdapter, PbsConfig};
 pub use persistence::{JsonStore, SqliteStore, StateStore};
 pub use queue::PriorityQueue;
+pub use retention::{RetentionPolicy, RetentionReport};
 pub use router::{JobRouter, RouteTarget, RoutingRules};
 pub use scheduler::{BatchSchedulerType, HpcScheduler, Scheduler, SchedulerConfig};
+pub use simulation::{SimulationReport, SiteConstraints, Workload, WorkloadJob, simulate};
 pub use slurm::{SlurmAdapter, SlurmConfig};
-pub use workflow::{Workflow, WorkflowBuilder, WorkflowId, WorkflowStatus};
+pub use workflow::{Workflow, WorkflowBuilder, WorkflowId, WorkflowReport, WorkflowStatus};
+
+/// Add this crate's features and storage backends to `info`.
+pub fn describe_build(info: arvak_hal::BuildInfo) -> arvak_hal::BuildInfo {
+    info.with_component(
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        [("chaos", cfg!(feature = "chaos"))],
+    )
+    .with_storage_backend("json")
+    .with_storage_backend("sqlite")
+}
@@ -0,0 +1,114 @@
+//! Site maintenance calendars.
+//!
+//! Providers do not always publish maintenance through their APIs, so a
+//! site can list planned windows per backend in a YAML (or JSON) file:
+//!
+//! ```yaml
+//! backends:
+//!   iqm_garnet:
+//!     - start: 2026-11-02T06:00:00Z
+//!       end: 2026-11-02T14:00:00Z
+//!       reason: cryostat warm-up
+//! ```
+//!
+//! The calendar is merged into each backend's reported
+//! [`BackendAvailability`]; the scheduler then avoids matching jobs to a
+//! backend whose maintenance would overlap them, and warns on submission
+//! when a job will likely be interrupted.
+
+use std::fs;
+use std::path::Path;
+
+use arvak_hal::{BackendAvailability, MaintenanceWindow};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SchedError, SchedResult};
+
+/// Planned maintenance windows per backend name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MaintenanceCalendar {
+    /// Windows keyed by backend name.
+    #[serde(default)]
+    pub backends: FxHashMap<String, Vec<MaintenanceWindow>>,
+}
+
+impl MaintenanceCalendar {
+    /// Parse a calendar from YAML (or JSON).
+    pub fn from_yaml(yaml: &str) -> SchedResult<Self> {
+        let calendar: Self = serde_yaml_ng::from_str(yaml)
+            .map_err(|e| SchedError::ConfigError(format!("invalid maintenance calendar: {e}")))?;
+        calendar.validate()?;
+        Ok(calendar)
+    }
+
+    /// Load a calendar from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> SchedResult<Self> {
+        Self::from_yaml(&fs::read_to_string(path)?)
+    }
+
+    /// Add a window for a backend.
+    #[must_use]
+    pub fn with_window(mut self, backend: impl Into<String>, window: MaintenanceWindow) -> Self {
+        self.backends
+            .entry(backend.into())
+            .or_default()
+            .push(window);
+        self
+    }
+
+    /// Whether the calendar has no windows.
+    pub fn is_empty(&self) -> bool {
+        self.backends.values().all(Vec::is_empty)
+    }
+
+    /// Windows planned for a backend.
+    pub fn windows(&self, backend: &str) -> &[MaintenanceWindow] {
+        self.backends.get(backend).map_or(&[], Vec::as_slice)
+    }
+
+    /// Merge the calendar's windows for `backend` into its availability.
+    pub fn apply(&self, backend: &str, availability: BackendAvailability) -> BackendAvailability {
+        availability.with_maintenance_windows(self.windows(backend).iter().cloned())
+    }
+
+    fn validate(&self) -> SchedResult<()> {
+        for (backend, windows) in &self.backends {
+            if let Some(window) = windows.iter().find(|w| w.end <= w.start) {
+                return Err(SchedError::ConfigError(format!(
+                    "maintenance window for '{backend}' ends before it starts: {window}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = r"
+backends:
+  iqm_garnet:
+    - start: 2026-11-02T06:00:00Z
+      end: 2026-11-02T14:00:00Z
+      reason: cryostat warm-up
+";
+
+    #[test]
+    fn test_calendar_from_yaml() {
+        let calendar = MaintenanceCalendar::from_yaml(CALENDAR).unwrap();
+        let windows = calendar.windows("iqm_garnet");
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].reason.as_deref(), Some("cryostat warm-up"));
+        assert!(calendar.windows("simulator").is_empty());
+
+        let avail = calendar.apply("iqm_garnet", BackendAvailability::always_available());
+        assert_eq!(avail.maintenance_windows, windows);
+
+        let inverted = CALENDAR.replace("14:00", "05:00");
+        assert!(MaintenanceCalendar::from_yaml(&inverted).is_err());
+    }
+}
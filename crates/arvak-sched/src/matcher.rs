@@ -1,12 +1,15 @@
 //! Resource matcher for matching circuits to backends.
 
 use std::sync::Arc;
+use std::time::Duration;
 
-use arvak_hal::{Backend, Capabilities};
+use arvak_hal::{Backend, BackendAvailability, Capabilities, MaintenanceWindow};
 use async_trait::async_trait;
+use chrono::Utc;
 
 use crate::error::{SchedError, SchedResult};
 use crate::job::{ResourceRequirements, TopologyPreference};
+use crate::maintenance::MaintenanceCalendar;
 
 /// Result of a resource match.
 #[derive(Debug, Clone)]
@@ -42,6 +45,10 @@ pub struct ResourceMatcher {
     backends: Vec<Arc<dyn Backend>>,
     /// Cache of backend capabilities.
     capabilities_cache: tokio::sync::RwLock<rustc_hash::FxHashMap<String, Capabilities>>,
+    /// Site maintenance calendar, merged into reported availability.
+    maintenance: MaintenanceCalendar,
+    /// Expected job duration, used to detect maintenance conflicts.
+    job_duration: Duration,
 }
 
 impl ResourceMatcher {
@@ -50,14 +57,60 @@ impl ResourceMatcher {
         Self {
             backends,
             capabilities_cache: tokio::sync::RwLock::new(rustc_hash::FxHashMap::default()),
+            maintenance: MaintenanceCalendar::default(),
+            job_duration: Duration::ZERO,
         }
     }
 
+    /// Avoid backends whose maintenance would overlap a job of
+    /// `job_duration` started after the backend's estimated queue wait.
+    #[must_use]
+    pub fn with_maintenance(
+        mut self,
+        calendar: MaintenanceCalendar,
+        job_duration: Duration,
+    ) -> Self {
+        self.maintenance = calendar;
+        self.job_duration = job_duration;
+        self
+    }
+
+    /// Query a backend's availability, with the site maintenance calendar
+    /// merged in.
+    pub async fn availability(&self, backend: &dyn Backend) -> BackendAvailability {
+        let avail = backend
+            .availability()
+            .await
+            .unwrap_or(BackendAvailability::unavailable("query failed"));
+        self.maintenance.apply(backend.name(), avail)
+    }
+
+    /// The maintenance window, if any, that will likely interrupt a job
+    /// submitted to `backend` now.
+    pub async fn maintenance_conflict(&self, backend: &str) -> Option<MaintenanceWindow> {
+        let backend = self.backends.iter().find(|b| b.name() == backend)?;
+        let avail = self.availability(backend.as_ref()).await;
+        maintenance_conflict(&avail, self.job_duration).cloned()
+    }
+
     /// Add a backend to the matcher.
     pub fn add_backend(&mut self, backend: Arc<dyn Backend>) {
         self.backends.push(backend);
     }
 
+    /// Names of the backends the matcher chooses from.
+    pub fn backend_names(&self) -> Vec<String> {
+        self.backends.iter().map(|b| b.name().to_string()).collect()
+    }
+
+    /// Capabilities of the backend named `name`, if the matcher has it.
+    pub fn capabilities(&self, name: &str) -> Option<Capabilities> {
+        self.backends
+            .iter()
+            .find(|b| b.name() == name)
+            .map(|b| b.capabilities().clone())
+    }
+
     /// Refresh the capabilities cache for all backends.
     pub async fn refresh_cache(&self) -> SchedResult<()> {
         let mut cache = self.capabilities_cache.write().await;
@@ -208,28 +261,41 @@ impl ResourceMatcher {
 #[async_trait]
 impl Matcher for ResourceMatcher {
     async fn find_match(&self, requirements: &ResourceRequirements) -> SchedResult<MatchResult> {
-        let matches = self.find_all_matches(requirements).await?;
+        let (matches, in_maintenance) = self.collect_matches(requirements).await;
 
-        matches.into_iter().next().ok_or_else(|| {
-            SchedError::NoMatchingBackend(format!(
-                "No backend found with {} qubits",
-                requirements.min_qubits
-            ))
-        })
+        if let Some(best) = matches.into_iter().next() {
+            return Ok(best);
+        }
+        if !in_maintenance.is_empty() {
+            return Err(SchedError::BackendsInMaintenance(in_maintenance.join("; ")));
+        }
+        Err(SchedError::NoMatchingBackend(format!(
+            "No backend found with {} qubits",
+            requirements.min_qubits
+        )))
     }
 
     async fn find_all_matches(
         &self,
         requirements: &ResourceRequirements,
     ) -> SchedResult<Vec<MatchResult>> {
+        Ok(self.collect_matches(requirements).await.0)
+    }
+}
+
+impl ResourceMatcher {
+    /// Score all backends, sorted by score (highest first). Also returns
+    /// the backends that would match but were skipped for maintenance.
+    async fn collect_matches(
+        &self,
+        requirements: &ResourceRequirements,
+    ) -> (Vec<MatchResult>, Vec<String>) {
         let mut matches = Vec::new();
+        let mut in_maintenance = Vec::new();
 
         for backend in &self.backends {
             // Check availability
-            let avail = backend
-                .availability()
-                .await
-                .unwrap_or(arvak_hal::BackendAvailability::unavailable("query failed"));
+            let avail = self.availability(backend.as_ref()).await;
             if !avail.is_available {
                 continue;
             }
@@ -250,6 +316,17 @@ impl Matcher for ResourceMatcher {
                 continue;
             }
 
+            // Avoid planning the job into a maintenance window
+            if let Some(window) = maintenance_conflict(&avail, self.job_duration) {
+                tracing::debug!(
+                    "Skipping backend {}: maintenance {}",
+                    backend.name(),
+                    window
+                );
+                in_maintenance.push(format!("{} ({window})", backend.name()));
+                continue;
+            }
+
             matches.push(MatchResult {
                 backend_name: backend.name().to_string(),
                 score,
@@ -265,10 +342,21 @@ impl Matcher for ResourceMatcher {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
-        Ok(matches)
+        (matches, in_maintenance)
     }
 }
 
+/// The maintenance window overlapping a job that starts after the
+/// estimated queue wait and runs for `job_duration`.
+fn maintenance_conflict(
+    avail: &BackendAvailability,
+    job_duration: Duration,
+) -> Option<&MaintenanceWindow> {
+    let wait = avail.estimated_wait.unwrap_or(Duration::ZERO);
+    let start = Utc::now() + chrono::Duration::from_std(wait).unwrap_or(chrono::Duration::MAX);
+    avail.maintenance_conflict(start, job_duration)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,6 +455,7 @@ mod tests {
                 is_simulator,
                 features: vec![],
                 noise_profile: None,
+                decoherence: None,
             },
             available: true,
         })
@@ -432,6 +521,52 @@ mod tests {
         assert_eq!(result.backend_name, "backend_b");
     }
 
+    #[tokio::test]
+    async fn test_find_match_avoids_maintenance() {
+        let backends = vec![
+            make_backend("backend_a", 10, true),
+            make_backend("backend_b", 10, true),
+        ];
+        let now = Utc::now();
+        let hour = chrono::Duration::hours(1);
+        let calendar = MaintenanceCalendar::default()
+            .with_window(
+                "backend_b",
+                MaintenanceWindow::new(now + hour, now + hour * 2),
+            )
+            .with_window(
+                "backend_a",
+                MaintenanceWindow::new(now + hour * 4, now + hour * 5),
+            );
+        let matcher = ResourceMatcher::new(backends)
+            .with_maintenance(calendar, Duration::from_secs(2 * 3600));
+
+        // backend_b goes down during the job; backend_a only afterwards.
+        let requirements = ResourceRequirements::new(2).prefer_backend("backend_b");
+        let result = matcher.find_match(&requirements).await.unwrap();
+        assert_eq!(result.backend_name, "backend_a");
+        assert!(matcher.maintenance_conflict("backend_b").await.is_some());
+        assert!(matcher.maintenance_conflict("backend_a").await.is_none());
+
+        // Too few qubits anywhere is a plain mismatch...
+        let requirements = ResourceRequirements::new(20);
+        assert!(matches!(
+            matcher.find_match(&requirements).await,
+            Err(SchedError::NoMatchingBackend(_))
+        ));
+        // ...but a job that only fits backends in maintenance has to wait.
+        let matcher = ResourceMatcher::new(vec![make_backend("backend_b", 10, true)])
+            .with_maintenance(
+                MaintenanceCalendar::default()
+                    .with_window("backend_b", MaintenanceWindow::new(now - hour, now + hour)),
+                Duration::ZERO,
+            );
+        assert!(matches!(
+            matcher.find_match(&ResourceRequirements::new(2)).await,
+            Err(SchedError::BackendsInMaintenance(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_no_matching_backend() {
         let backends = vec![make_backend("small", 5, true)];
@@ -149,6 +149,7 @@ pub fn generate_pbs_script(
     // Execute Arvak command
     script.push_str("# Execute quantum job\n");
 
+    let param_args = job.parameter_args()?;
     let backend_flag = if let Some(ref backend) = job.matched_backend {
         format!("--backend {backend}")
     } else {
@@ -156,10 +157,11 @@ pub fn generate_pbs_script(
     };
 
     script.push_str(&format!(
-        "{} run {} --shots {} {} --output {}\n",
+        "{} run {} --shots {}{} {} --output {}\n",
         config.arvak_binary.display(),
         circuit_file.display(),
         job.shots,
+        param_args,
         backend_flag,
         result_file.display(),
     ));
@@ -297,6 +299,7 @@ pub fn generate_pbs_script_multi(
     script.push_str("# Execute quantum jobs\n");
     script.push_str("FAILED=0\n\n");
 
+    let param_args = job.parameter_args()?;
     let backend_flag = if let Some(ref backend) = job.matched_backend {
         format!("--backend {backend}")
     } else {
@@ -311,10 +314,11 @@ pub fn generate_pbs_script_multi(
             circuit_files.len()
         ));
         script.push_str(&format!(
-            "if ! {} run {} --shots {} {} --output {}; then\n",
+            "if ! {} run {} --shots {}{} {} --output {}; then\n",
             config.arvak_binary.display(),
             circuit_file.display(),
             job.shots,
+            param_args,
             backend_flag,
             result_file.display(),
         ));
@@ -478,6 +482,7 @@ pub fn generate_pbs_array_script(
     // Execute
     script.push_str("echo \"Array task $PBS_ARRAYID: Running $CIRCUIT\"\n");
 
+    let param_args = job.parameter_args()?;
     let backend_flag = if let Some(ref backend) = job.matched_backend {
         format!("--backend {backend}")
     } else {
@@ -485,9 +490,10 @@ pub fn generate_pbs_array_script(
     };
 
     script.push_str(&format!(
-        "{} run $CIRCUIT --shots {} {} --output $RESULT\n",
+        "{} run $CIRCUIT --shots {}{} {} --output $RESULT\n",
         config.arvak_binary.display(),
         job.shots,
+        param_args,
         backend_flag,
     ));
 
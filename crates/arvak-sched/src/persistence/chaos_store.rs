@@ -0,0 +1,136 @@
+//! Fault-injecting state store for recovery tests.
+
+use std::sync::Arc;
+
+use arvak_hal::ExecutionResult;
+use arvak_hal::chaos::FaultInjector;
+use async_trait::async_trait;
+
+use super::StateStore;
+use crate::error::SchedResult;
+use crate::job::{JobFilter, ScheduledJob, ScheduledJobId, ScheduledJobStatus};
+use crate::workflow::{Workflow, WorkflowId};
+
+/// A [`StateStore`] that delays writes and silently drops status updates
+/// as its [`FaultInjector`] decides.
+///
+/// Reads, deletes and workflow calls go straight to the wrapped store.
+pub struct ChaosStore {
+    inner: Arc<dyn StateStore>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosStore {
+    /// Wrap `inner`, drawing faults from `injector`.
+    pub fn new(inner: Arc<dyn StateStore>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+
+    async fn delay(&self) {
+        if let Some(delay) = self.injector.storage_delay() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl StateStore for ChaosStore {
+    async fn save_job(&self, job: &ScheduledJob) -> SchedResult<()> {
+        self.delay().await;
+        self.inner.save_job(job).await
+    }
+
+    async fn load_job(&self, job_id: &ScheduledJobId) -> SchedResult<Option<ScheduledJob>> {
+        self.inner.load_job(job_id).await
+    }
+
+    async fn update_status(
+        &self,
+        job_id: &ScheduledJobId,
+        status: ScheduledJobStatus,
+    ) -> SchedResult<()> {
+        self.delay().await;
+        if self.injector.drop_status_update() {
+            return Ok(());
+        }
+        self.inner.update_status(job_id, status).await
+    }
+
+    async fn delete_job(&self, job_id: &ScheduledJobId) -> SchedResult<bool> {
+        self.inner.delete_job(job_id).await
+    }
+
+    async fn list_jobs(&self, filter: &JobFilter) -> SchedResult<Vec<ScheduledJob>> {
+        self.inner.list_jobs(filter).await
+    }
+
+    async fn save_result(
+        &self,
+        job_id: &ScheduledJobId,
+        result: &ExecutionResult,
+    ) -> SchedResult<()> {
+        self.delay().await;
+        self.inner.save_result(job_id, result).await
+    }
+
+    async fn load_result(&self, job_id: &ScheduledJobId) -> SchedResult<Option<ExecutionResult>> {
+        self.inner.load_result(job_id).await
+    }
+
+    async fn save_workflow(&self, workflow: &Workflow) -> SchedResult<()> {
+        self.delay().await;
+        self.inner.save_workflow(workflow).await
+    }
+
+    async fn load_workflow(&self, workflow_id: &WorkflowId) -> SchedResult<Option<Workflow>> {
+        self.inner.load_workflow(workflow_id).await
+    }
+
+    async fn delete_workflow(&self, workflow_id: &WorkflowId) -> SchedResult<bool> {
+        self.inner.delete_workflow(workflow_id).await
+    }
+
+    async fn list_workflows(&self) -> SchedResult<Vec<WorkflowId>> {
+        self.inner.list_workflows().await
+    }
+
+    async fn cleanup_old_jobs(&self, max_age_seconds: u64) -> SchedResult<usize> {
+        self.inner.cleanup_old_jobs(max_age_seconds).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::CircuitSpec;
+    use crate::persistence::JsonStore;
+    use arvak_hal::chaos::{DroppedStatusUpdates, FaultScenario, StorageDelay};
+
+    #[tokio::test]
+    async fn test_chaos_store_drops_and_delays() {
+        let injector = Arc::new(FaultInjector::new(FaultScenario {
+            storage_delay: Some(StorageDelay {
+                probability: 1.0,
+                millis: 1,
+            }),
+            dropped_status_updates: Some(DroppedStatusUpdates { probability: 1.0 }),
+            ..Default::default()
+        }));
+        let inner = Arc::new(JsonStore::temp().await.unwrap());
+        let store = ChaosStore::new(inner.clone(), injector.clone());
+
+        let job = ScheduledJob::new("chaos", CircuitSpec::from_qasm("OPENQASM 3.0; qubit[1] q;"));
+        store.save_job(&job).await.unwrap();
+        store
+            .update_status(&job.id, ScheduledJobStatus::Cancelled)
+            .await
+            .unwrap();
+
+        // The save landed late; the status update never did.
+        let loaded = inner.load_job(&job.id).await.unwrap().unwrap();
+        assert!(matches!(loaded.status, ScheduledJobStatus::Pending));
+        let counts = injector.counts();
+        assert_eq!(counts.storage_delays, 2);
+        assert_eq!(counts.dropped_status_updates, 1);
+    }
+}
@@ -3,6 +3,7 @@
 use std::path::{Path, PathBuf};
 
 use arvak_hal::ExecutionResult;
+use arvak_hal::encryption::{self, PayloadCipher};
 use async_trait::async_trait;
 use tokio::fs;
 use tokio::sync::RwLock;
@@ -22,11 +23,26 @@ pub struct JsonStore {
 
     /// In-memory cache of jobs.
     cache: RwLock<rustc_hash::FxHashMap<ScheduledJobId, ScheduledJob>>,
+
+    /// Cipher for file contents, if encryption is enabled.
+    cipher: Option<PayloadCipher>,
 }
 
 impl JsonStore {
     /// Create a new JSON store at the given path.
     pub async fn new(base_dir: impl AsRef<Path>) -> SchedResult<Self> {
+        Self::open_dir(base_dir, None).await
+    }
+
+    /// Create a new JSON store whose files are encrypted with `cipher`.
+    pub async fn encrypted(base_dir: impl AsRef<Path>, cipher: PayloadCipher) -> SchedResult<Self> {
+        Self::open_dir(base_dir, Some(cipher)).await
+    }
+
+    async fn open_dir(
+        base_dir: impl AsRef<Path>,
+        cipher: Option<PayloadCipher>,
+    ) -> SchedResult<Self> {
         let base_dir = base_dir.as_ref().to_path_buf();
 
         // Create directories
@@ -37,6 +53,7 @@ impl JsonStore {
         let store = Self {
             base_dir,
             cache: RwLock::new(rustc_hash::FxHashMap::default()),
+            cipher,
         };
 
         // Load existing jobs into cache
@@ -65,6 +82,14 @@ impl JsonStore {
             .join(format!("{workflow_id}.json"))
     }
 
+    fn seal(&self, json: String) -> SchedResult<String> {
+        Ok(encryption::seal_with(self.cipher.as_ref(), json)?)
+    }
+
+    fn open(&self, content: String) -> SchedResult<String> {
+        Ok(encryption::open_with(self.cipher.as_ref(), content)?)
+    }
+
     async fn load_all_jobs(&self) -> SchedResult<()> {
         let jobs_dir = self.base_dir.join("jobs");
         let mut cache = self.cache.write().await;
@@ -74,7 +99,10 @@ impl JsonStore {
             let path = entry.path();
             if path.extension().is_some_and(|ext| ext == "json") {
                 match fs::read_to_string(&path).await {
-                    Ok(content) => match serde_json::from_str::<ScheduledJob>(&content) {
+                    Ok(content) => match self
+                        .open(content)
+                        .and_then(|json| Ok(serde_json::from_str::<ScheduledJob>(&json)?))
+                    {
                         Ok(job) => {
                             cache.insert(job.id.clone(), job);
                         }
@@ -97,7 +125,7 @@ impl JsonStore {
 impl StateStore for JsonStore {
     async fn save_job(&self, job: &ScheduledJob) -> SchedResult<()> {
         let path = self.job_path(&job.id);
-        let json = serde_json::to_string_pretty(job)?;
+        let json = self.seal(serde_json::to_string_pretty(job)?)?;
         fs::write(&path, json).await?;
 
         // Update cache
@@ -119,7 +147,7 @@ impl StateStore for JsonStore {
         let path = self.job_path(job_id);
         match fs::read_to_string(&path).await {
             Ok(content) => {
-                let job: ScheduledJob = serde_json::from_str(&content)?;
+                let job: ScheduledJob = serde_json::from_str(&self.open(content)?)?;
                 // Update cache
                 let mut cache = self.cache.write().await;
                 cache.insert(job.id.clone(), job.clone());
@@ -147,7 +175,7 @@ impl StateStore for JsonStore {
 
             // Write to file
             let path = self.job_path(job_id);
-            let json = serde_json::to_string_pretty(&*job)?;
+            let json = self.seal(serde_json::to_string_pretty(&*job)?)?;
             fs::write(&path, json).await?;
 
             Ok(())
@@ -163,6 +191,13 @@ impl StateStore for JsonStore {
         let mut cache = self.cache.write().await;
         let was_present = cache.remove(job_id).is_some();
 
+        // Remove the result, if any
+        match fs::remove_file(self.result_path(job_id)).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(SchedError::IoError(e)),
+        }
+
         // Remove file
         match fs::remove_file(&path).await {
             Ok(()) => Ok(true),
@@ -200,7 +235,7 @@ impl StateStore for JsonStore {
         result: &ExecutionResult,
     ) -> SchedResult<()> {
         let path = self.result_path(job_id);
-        let json = serde_json::to_string_pretty(result)?;
+        let json = self.seal(serde_json::to_string_pretty(result)?)?;
         fs::write(&path, json).await?;
         Ok(())
     }
@@ -209,7 +244,7 @@ impl StateStore for JsonStore {
         let path = self.result_path(job_id);
         match fs::read_to_string(&path).await {
             Ok(content) => {
-                let result: ExecutionResult = serde_json::from_str(&content)?;
+                let result: ExecutionResult = serde_json::from_str(&self.open(content)?)?;
                 Ok(Some(result))
             }
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
@@ -219,7 +254,7 @@ impl StateStore for JsonStore {
 
     async fn save_workflow(&self, workflow: &Workflow) -> SchedResult<()> {
         let path = self.workflow_path(&workflow.id);
-        let json = serde_json::to_string_pretty(workflow)?;
+        let json = self.seal(serde_json::to_string_pretty(workflow)?)?;
         fs::write(&path, json).await?;
         Ok(())
     }
@@ -228,7 +263,7 @@ impl StateStore for JsonStore {
         let path = self.workflow_path(workflow_id);
         match fs::read_to_string(&path).await {
             Ok(content) => {
-                let workflow: Workflow = serde_json::from_str(&content)?;
+                let workflow: Workflow = serde_json::from_str(&self.open(content)?)?;
                 Ok(Some(workflow))
             }
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
@@ -280,9 +315,6 @@ impl StateStore for JsonStore {
 
         for job_id in to_remove {
             if self.delete_job(&job_id).await? {
-                // Also delete result if exists
-                let result_path = self.result_path(&job_id);
-                let _ = fs::remove_file(&result_path).await;
                 removed += 1;
             }
         }
@@ -353,4 +385,27 @@ mod tests {
         assert_eq!(jobs[0].name, "job2");
         assert_eq!(jobs[1].name, "job1");
     }
+
+    #[tokio::test]
+    async fn test_json_store_encrypted_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let cipher = PayloadCipher::new(&[42; 32]).unwrap();
+
+        let circuit = CircuitSpec::from_qasm("OPENQASM 3.0; qubit[2] q; h q[0];");
+        let job = ScheduledJob::new("secret_job", circuit);
+        let job_id = job.id.clone();
+
+        let store = JsonStore::encrypted(dir.path(), cipher.clone())
+            .await
+            .unwrap();
+        store.save_job(&job).await.unwrap();
+
+        let on_disk = std::fs::read_to_string(store.job_path(&job_id)).unwrap();
+        assert!(!on_disk.contains("qubit"));
+
+        // A fresh store with the same key repopulates its cache.
+        let reopened = JsonStore::encrypted(dir.path(), cipher).await.unwrap();
+        let loaded = reopened.load_job(&job_id).await.unwrap().unwrap();
+        assert_eq!(loaded.name, "secret_job");
+    }
 }
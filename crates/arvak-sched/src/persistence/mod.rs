@@ -1,8 +1,12 @@
 //! Persistence layer for job state.
 
+#[cfg(feature = "chaos")]
+mod chaos_store;
 mod json_store;
 mod sqlite_store;
 
+#[cfg(feature = "chaos")]
+pub use chaos_store::ChaosStore;
 pub use json_store::JsonStore;
 pub use sqlite_store::SqliteStore;
 
@@ -29,7 +33,7 @@ pub trait StateStore: Send + Sync {
         status: ScheduledJobStatus,
     ) -> SchedResult<()>;
 
-    /// Delete a job from the store.
+    /// Delete a job and its result from the store.
     async fn delete_job(&self, job_id: &ScheduledJobId) -> SchedResult<bool>;
 
     /// List jobs matching a filter.
@@ -58,5 +62,7 @@ pub trait StateStore: Send + Sync {
     async fn list_workflows(&self) -> SchedResult<Vec<WorkflowId>>;
 
     /// Clean up old completed/failed jobs.
+    ///
+    /// See [`crate::retention`] for size caps, archiving and dry runs.
     async fn cleanup_old_jobs(&self, max_age_seconds: u64) -> SchedResult<usize>;
 }
@@ -4,6 +4,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use arvak_hal::ExecutionResult;
+use arvak_hal::encryption::{self, PayloadCipher};
 use async_trait::async_trait;
 use rusqlite::Connection;
 use std::sync::Mutex;
@@ -17,8 +18,14 @@ use crate::workflow::{Workflow, WorkflowId};
 ///
 /// Provides persistent storage with ACID guarantees. Recommended for
 /// production use.
+///
+/// With [`with_cipher`](Self::with_cipher), job, result and workflow
+/// payloads are encrypted with AES-256-GCM before they are written; the
+/// indexed columns (id, name, status, priority, timestamps) stay in clear
+/// text so filtering keeps working.
 pub struct SqliteStore {
     conn: Arc<Mutex<Connection>>,
+    cipher: Option<PayloadCipher>,
 }
 
 impl SqliteStore {
@@ -27,6 +34,7 @@ impl SqliteStore {
         let conn = Connection::open(path)?;
         let store = Self {
             conn: Arc::new(Mutex::new(conn)),
+            cipher: None,
         };
         store.init_schema_sync()?;
         Ok(store)
@@ -37,11 +45,27 @@ impl SqliteStore {
         let conn = Connection::open_in_memory()?;
         let store = Self {
             conn: Arc::new(Mutex::new(conn)),
+            cipher: None,
         };
         store.init_schema_sync()?;
         Ok(store)
     }
 
+    /// Encrypt stored payloads with the given cipher.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: PayloadCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn seal(&self, data: String) -> SchedResult<String> {
+        Ok(encryption::seal_with(self.cipher.as_ref(), data)?)
+    }
+
+    fn open(&self, data: String) -> SchedResult<String> {
+        Ok(encryption::open_with(self.cipher.as_ref(), data)?)
+    }
+
     fn init_schema_sync(&self) -> SchedResult<()> {
         let conn = self
             .conn
@@ -89,7 +113,7 @@ impl StateStore for SqliteStore {
             .conn
             .lock()
             .map_err(|e| SchedError::DatabaseError(e.to_string()))?;
-        let data = serde_json::to_string(job)?;
+        let data = self.seal(serde_json::to_string(job)?)?;
 
         conn.execute(
             r"
@@ -121,7 +145,7 @@ impl StateStore for SqliteStore {
         let mut rows = stmt.query(rusqlite::params![job_id.to_string()])?;
 
         if let Some(row) = rows.next()? {
-            let data: String = row.get(0)?;
+            let data = self.open(row.get(0)?)?;
             let job: ScheduledJob = serde_json::from_str(&data)?;
             Ok(Some(job))
         } else {
@@ -153,6 +177,10 @@ impl StateStore for SqliteStore {
             .conn
             .lock()
             .map_err(|e| SchedError::DatabaseError(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM results WHERE job_id = ?1",
+            rusqlite::params![job_id.to_string()],
+        )?;
         let deleted = conn.execute(
             "DELETE FROM jobs WHERE id = ?1",
             rusqlite::params![job_id.to_string()],
@@ -219,7 +247,7 @@ impl StateStore for SqliteStore {
 
         let mut jobs = Vec::new();
         while let Some(row) = rows.next()? {
-            let data: String = row.get(0)?;
+            let data = self.open(row.get(0)?)?;
             let job: ScheduledJob = serde_json::from_str(&data)?;
 
             // Apply additional filters that can't be done in SQL
@@ -256,7 +284,7 @@ impl StateStore for SqliteStore {
             .conn
             .lock()
             .map_err(|e| SchedError::DatabaseError(e.to_string()))?;
-        let data = serde_json::to_string(result)?;
+        let data = self.seal(serde_json::to_string(result)?)?;
 
         conn.execute(
             "INSERT OR REPLACE INTO results (job_id, data) VALUES (?1, ?2)",
@@ -276,7 +304,7 @@ impl StateStore for SqliteStore {
         let mut rows = stmt.query(rusqlite::params![job_id.to_string()])?;
 
         if let Some(row) = rows.next()? {
-            let data: String = row.get(0)?;
+            let data = self.open(row.get(0)?)?;
             let result: ExecutionResult = serde_json::from_str(&data)?;
             Ok(Some(result))
         } else {
@@ -289,7 +317,7 @@ impl StateStore for SqliteStore {
             .conn
             .lock()
             .map_err(|e| SchedError::DatabaseError(e.to_string()))?;
-        let data = serde_json::to_string(workflow)?;
+        let data = self.seal(serde_json::to_string(workflow)?)?;
 
         conn.execute(
             r"
@@ -317,7 +345,7 @@ impl StateStore for SqliteStore {
         let mut rows = stmt.query(rusqlite::params![workflow_id.to_string()])?;
 
         if let Some(row) = rows.next()? {
-            let data: String = row.get(0)?;
+            let data = self.open(row.get(0)?)?;
             let workflow: Workflow = serde_json::from_str(&data)?;
             Ok(Some(workflow))
         } else {
@@ -487,4 +515,40 @@ mod tests {
         assert_eq!(loaded.shots, 1000);
         assert_eq!(loaded.counts.get("00"), 500);
     }
+
+    #[tokio::test]
+    async fn test_sqlite_store_encrypted() {
+        use arvak_hal::Counts;
+
+        let cipher = PayloadCipher::new(&[42; 32]).unwrap();
+        let store = SqliteStore::in_memory().unwrap().with_cipher(cipher);
+
+        let circuit = CircuitSpec::from_qasm("OPENQASM 3.0; qubit[2] q; h q[0];");
+        let job = ScheduledJob::new("secret_job", circuit);
+        let job_id = job.id.clone();
+        store.save_job(&job).await.unwrap();
+        let result = ExecutionResult::new(Counts::from_pairs([("00", 10u64)]), 10);
+        store.save_result(&job_id, &result).await.unwrap();
+
+        // Payload columns hold no clear-text circuit or counts.
+        {
+            let conn = store.conn.lock().unwrap();
+            let job_data: String = conn
+                .query_row("SELECT data FROM jobs", [], |row| row.get(0))
+                .unwrap();
+            let result_data: String = conn
+                .query_row("SELECT data FROM results", [], |row| row.get(0))
+                .unwrap();
+            assert!(encryption::is_sealed(&job_data));
+            assert!(!job_data.contains("qubit"));
+            assert!(encryption::is_sealed(&result_data));
+        }
+
+        let loaded = store.load_job(&job_id).await.unwrap().unwrap();
+        assert_eq!(loaded.name, "secret_job");
+        let jobs = store.list_jobs(&JobFilter::default()).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        let loaded = store.load_result(&job_id).await.unwrap().unwrap();
+        assert_eq!(loaded.counts.get("00"), 10);
+    }
 }
@@ -0,0 +1,288 @@
+//! Retention policies and garbage collection for the job store.
+//!
+//! Completed, failed and cancelled jobs accumulate in the state store
+//! indefinitely. A [`RetentionPolicy`] bounds the store by age and by size:
+//! terminal jobs older than `max_age_seconds` expire, and when the store
+//! holds more than `max_jobs` jobs the oldest terminal jobs are evicted until
+//! it fits. Pending and running jobs are never touched.
+//!
+//! Removed jobs are deleted, or first written to an archive directory as
+//! `<job-id>.json` (`{"job": ..., "result": ...}`), sealed with the policy's
+//! `archive_cipher` when one is set. [`collect_garbage`] runs one sweep (with
+//! an optional dry run), [`spawn_sweeper`] repeats it in the background.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arvak_hal::encryption::{self, PayloadCipher};
+use arvak_hal::retention;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::error::SchedResult;
+use crate::job::{JobFilter, ScheduledJob, ScheduledJobId};
+use crate::persistence::StateStore;
+
+/// Limits on how long and how many jobs are kept in a state store.
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    /// Terminal jobs completed longer ago than this are removed.
+    pub max_age_seconds: Option<u64>,
+
+    /// Maximum number of jobs kept in the store.
+    pub max_jobs: Option<usize>,
+
+    /// Directory removed jobs are archived to before deletion.
+    pub archive_dir: Option<PathBuf>,
+
+    /// Cipher for archive files.
+    pub archive_cipher: Option<PayloadCipher>,
+}
+
+impl RetentionPolicy {
+    /// Create a policy that keeps everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expire terminal jobs older than `days`.
+    #[must_use]
+    pub fn with_max_age_days(self, days: u64) -> Self {
+        self.with_max_age_seconds(days * 86_400)
+    }
+
+    /// Expire terminal jobs older than `seconds`.
+    #[must_use]
+    pub fn with_max_age_seconds(mut self, seconds: u64) -> Self {
+        self.max_age_seconds = Some(seconds);
+        self
+    }
+
+    /// Cap the store at `max_jobs` jobs.
+    #[must_use]
+    pub fn with_max_jobs(mut self, max_jobs: usize) -> Self {
+        self.max_jobs = Some(max_jobs);
+        self
+    }
+
+    /// Archive removed jobs to `dir` instead of only deleting them.
+    #[must_use]
+    pub fn with_archive_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.archive_dir = Some(dir.into());
+        self
+    }
+
+    /// Seal archive files with `cipher`.
+    #[must_use]
+    pub fn with_archive_cipher(mut self, cipher: PayloadCipher) -> Self {
+        self.archive_cipher = Some(cipher);
+        self
+    }
+
+    /// Returns `true` if the policy never removes anything.
+    pub fn is_unbounded(&self) -> bool {
+        self.max_age_seconds.is_none() && self.max_jobs.is_none()
+    }
+}
+
+pub use arvak_hal::retention::RemovalReason;
+
+/// A job selected by a retention sweep.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovedJob {
+    /// Job identifier.
+    pub id: ScheduledJobId,
+    /// Job name.
+    pub name: String,
+    /// Completion time (creation time for jobs without one).
+    pub finished_at: DateTime<Utc>,
+    /// Why the job was selected.
+    pub reason: RemovalReason,
+}
+
+/// Outcome of a retention sweep.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionReport {
+    /// Jobs selected for removal, oldest first.
+    pub removed: Vec<RemovedJob>,
+    /// Number of jobs written to the archive directory.
+    pub archived: usize,
+    /// Number of jobs in the store before the sweep.
+    pub total_jobs: usize,
+    /// Whether this was a dry run (nothing was archived or deleted).
+    pub dry_run: bool,
+}
+
+/// Select the jobs `policy` would remove from a set of stored jobs.
+///
+/// Only terminal jobs are candidates. Expired jobs are selected first; if the
+/// store is still over `max_jobs`, the oldest remaining terminal jobs follow
+/// (see [`arvak_hal::retention::select_for_removal`]).
+pub fn select_for_removal(
+    jobs: &[ScheduledJob],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<RemovedJob> {
+    let terminal = jobs
+        .iter()
+        .filter(|job| job.status.is_terminal())
+        .map(|job| (job, job.completed_at.unwrap_or(job.created_at)))
+        .collect();
+    retention::select_for_removal(
+        terminal,
+        jobs.len(),
+        policy.max_age_seconds.map(Duration::from_secs),
+        policy.max_jobs,
+        now,
+    )
+    .into_iter()
+    .map(|(job, finished_at, reason)| RemovedJob {
+        id: job.id.clone(),
+        name: job.name.clone(),
+        finished_at,
+        reason,
+    })
+    .collect()
+}
+
+/// Run one retention sweep over `store`.
+///
+/// With `dry_run` the report lists what would be removed without archiving
+/// or deleting anything.
+pub async fn collect_garbage(
+    store: &dyn StateStore,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> SchedResult<RetentionReport> {
+    let jobs = store.list_jobs(&JobFilter::default()).await?;
+    let mut report = RetentionReport {
+        removed: select_for_removal(&jobs, policy, Utc::now()),
+        total_jobs: jobs.len(),
+        dry_run,
+        ..Default::default()
+    };
+    if dry_run || report.removed.is_empty() {
+        return Ok(report);
+    }
+
+    if let Some(dir) = &policy.archive_dir {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+
+    for removed in &report.removed {
+        if let Some(dir) = &policy.archive_dir {
+            let Some(job) = store.load_job(&removed.id).await? else {
+                continue;
+            };
+            let result = store.load_result(&removed.id).await?;
+            let json = serde_json::to_string_pretty(&serde_json::json!({
+                "job": job,
+                "result": result,
+            }))?;
+            let contents = encryption::seal_with(policy.archive_cipher.as_ref(), json)?;
+            tokio::fs::write(dir.join(format!("{}.json", removed.id)), contents).await?;
+            report.archived += 1;
+        }
+        store.delete_job(&removed.id).await?;
+    }
+
+    Ok(report)
+}
+
+/// Spawn a background task that applies `policy` to `store` every
+/// `interval`.
+///
+/// Sweep failures are logged and retried on the next tick. Abort the
+/// returned handle to stop the sweeper.
+pub fn spawn_sweeper(
+    store: Arc<dyn StateStore>,
+    policy: RetentionPolicy,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match collect_garbage(store.as_ref(), &policy, false).await {
+                Ok(report) if !report.removed.is_empty() => info!(
+                    "Retention sweep removed {} of {} job(s) ({} archived)",
+                    report.removed.len(),
+                    report.total_jobs,
+                    report.archived
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Retention sweep failed: {e}"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::{CircuitSpec, ScheduledJobStatus};
+    use crate::persistence::JsonStore;
+
+    fn finished_job(name: &str, days_ago: i64) -> ScheduledJob {
+        let mut job = ScheduledJob::new(name, CircuitSpec::from_qasm("OPENQASM 3.0;"));
+        job.status = ScheduledJobStatus::Cancelled;
+        job.completed_at = Some(Utc::now() - chrono::Duration::days(days_ago));
+        job
+    }
+
+    #[test]
+    fn test_select_by_age_and_capacity() {
+        let jobs = vec![
+            finished_job("old", 40),
+            finished_job("recent", 5),
+            finished_job("newest", 1),
+            ScheduledJob::new("pending", CircuitSpec::from_qasm("OPENQASM 3.0;")),
+        ];
+
+        let policy = RetentionPolicy::new().with_max_age_days(30);
+        let removed = select_for_removal(&jobs, &policy, Utc::now());
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].name, "old");
+        assert_eq!(removed[0].reason, RemovalReason::Expired);
+
+        // Capacity 2: "old" expires, "recent" is evicted, pending is kept.
+        let policy = policy.with_max_jobs(2);
+        let removed = select_for_removal(&jobs, &policy, Utc::now());
+        let names: Vec<_> = removed.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["old", "recent"]);
+        assert_eq!(removed[1].reason, RemovalReason::OverCapacity);
+
+        assert!(select_for_removal(&jobs, &RetentionPolicy::new(), Utc::now()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_garbage_dry_run_and_archive() {
+        let store = JsonStore::temp().await.unwrap();
+        let old = finished_job("old", 40);
+        let recent = finished_job("recent", 1);
+        store.save_job(&old).await.unwrap();
+        store.save_job(&recent).await.unwrap();
+
+        let archive = tempfile::tempdir().unwrap();
+        let policy = RetentionPolicy::new()
+            .with_max_age_days(30)
+            .with_archive_dir(archive.path());
+
+        let report = collect_garbage(&store, &policy, true).await.unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.removed.len(), 1);
+        assert!(store.load_job(&old.id).await.unwrap().is_some());
+
+        let report = collect_garbage(&store, &policy, false).await.unwrap();
+        assert_eq!(report.archived, 1);
+        assert!(store.load_job(&old.id).await.unwrap().is_none());
+        assert!(store.load_job(&recent.id).await.unwrap().is_some());
+
+        let archived =
+            std::fs::read_to_string(archive.path().join(format!("{}.json", old.id))).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&archived).unwrap();
+        assert_eq!(value["job"]["name"], "old");
+    }
+}
@@ -1,10 +1,14 @@
 //! HPC Scheduler implementation.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
-use arvak_hal::{Backend, ExecutionResult};
+use arvak_hal::dead_letter::MAX_TRACE_EVENTS;
+use arvak_hal::{
+    Backend, DeadLetterQueue, ExecutionResult, FailureBundle, MaintenanceWindow, Provenance,
+    SignaturePolicy,
+};
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 use tokio::time::interval;
@@ -14,12 +18,13 @@ use crate::job::{
     CircuitSpec, JobFilter, Priority, ResourceRequirements, ScheduledJob, ScheduledJobId,
     ScheduledJobStatus,
 };
+use crate::maintenance::MaintenanceCalendar;
 use crate::matcher::{Matcher, ResourceMatcher};
 use crate::pbs::{PbsAdapter, PbsConfig, PbsState};
 use crate::persistence::StateStore;
 use crate::queue::PriorityQueue;
 use crate::slurm::{SlurmAdapter, SlurmConfig, SlurmState};
-use crate::workflow::{Workflow, WorkflowBuilder, WorkflowId, WorkflowStatus};
+use crate::workflow::{Workflow, WorkflowBuilder, WorkflowId, WorkflowReport, WorkflowStatus};
 
 /// The type of HPC batch scheduler to use.
 #[derive(Debug, Clone, Default)]
@@ -60,6 +65,16 @@ pub struct SchedulerConfig {
 
     /// Working directory for scheduler state.
     pub state_dir: PathBuf,
+
+    /// Grid carbon intensity in g CO₂e/kWh, used to estimate emissions in
+    /// workflow reports.
+    pub carbon_intensity_g_per_kwh: Option<f64>,
+
+    /// Planned backend maintenance, in addition to what backends report.
+    pub maintenance: MaintenanceCalendar,
+
+    /// Circuit signature policy; jobs are not checked when unset.
+    pub signing: Option<SignaturePolicy>,
 }
 
 impl Default for SchedulerConfig {
@@ -78,6 +93,11 @@ impl Default for SchedulerConfig {
                         .map(|d| PathBuf::from(d).join("arvak-scheduler"))
                 })
                 .unwrap_or_else(|_| std::env::temp_dir().join("arvak-scheduler")),
+            carbon_intensity_g_per_kwh: std::env::var("ARVAK_CARBON_INTENSITY")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            maintenance: MaintenanceCalendar::default(),
+            signing: None,
         }
     }
 }
@@ -100,6 +120,48 @@ impl SchedulerConfig {
             ..Default::default()
         }
     }
+
+    /// Set the site maintenance calendar.
+    #[must_use]
+    pub fn with_maintenance(mut self, calendar: MaintenanceCalendar) -> Self {
+        self.maintenance = calendar;
+        self
+    }
+
+    /// Check circuit signatures against `policy` before jobs run.
+    #[must_use]
+    pub fn with_signing(mut self, policy: SignaturePolicy) -> Self {
+        self.signing = Some(policy);
+        self
+    }
+
+    /// Directory of the dead-letter queue of failed jobs.
+    pub fn dead_letter_dir(&self) -> PathBuf {
+        self.state_dir.join("dead-letter")
+    }
+
+    /// Walltime requested for each batch job, the expected job duration
+    /// when checking for maintenance conflicts.
+    pub fn walltime(&self) -> Duration {
+        match self.scheduler_type {
+            BatchSchedulerType::Slurm => Duration::from_secs(u64::from(self.slurm.time_limit) * 60),
+            BatchSchedulerType::Pbs => parse_walltime(&self.pbs.walltime).unwrap_or_else(|| {
+                tracing::warn!("Invalid PBS walltime '{}'", self.pbs.walltime);
+                Duration::ZERO
+            }),
+        }
+    }
+}
+
+/// Parse a PBS walltime (`[[HH:]MM:]SS`).
+fn parse_walltime(walltime: &str) -> Option<Duration> {
+    let mut seconds = 0u64;
+    for part in walltime.split(':') {
+        seconds = seconds
+            .checked_mul(60)?
+            .checked_add(part.trim().parse().ok()?)?;
+    }
+    Some(Duration::from_secs(seconds))
 }
 
 /// Trait for scheduler implementations.
@@ -161,6 +223,7 @@ pub struct HpcScheduler {
     queue: RwLock<PriorityQueue>,
     workflows: RwLock<rustc_hash::FxHashMap<WorkflowId, Workflow>>,
     completed_jobs: RwLock<rustc_hash::FxHashSet<ScheduledJobId>>,
+    dead_letter: DeadLetterQueue,
 }
 
 impl HpcScheduler {
@@ -178,7 +241,9 @@ impl HpcScheduler {
                 BatchAdapter::Pbs(PbsAdapter::new(config.pbs.clone()).await?)
             }
         };
-        let matcher = ResourceMatcher::new(backends);
+        let matcher = ResourceMatcher::new(backends)
+            .with_maintenance(config.maintenance.clone(), config.walltime());
+        let dead_letter = DeadLetterQueue::new(config.dead_letter_dir());
 
         Ok(Self {
             config,
@@ -188,6 +253,7 @@ impl HpcScheduler {
             queue: RwLock::new(PriorityQueue::new()),
             workflows: RwLock::new(rustc_hash::FxHashMap::default()),
             completed_jobs: RwLock::new(rustc_hash::FxHashSet::default()),
+            dead_letter,
         })
     }
 
@@ -198,7 +264,9 @@ impl HpcScheduler {
         store: Arc<dyn StateStore>,
     ) -> Self {
         let adapter = BatchAdapter::Slurm(SlurmAdapter::mock(config.slurm.clone()));
-        let matcher = ResourceMatcher::new(backends);
+        let matcher = ResourceMatcher::new(backends)
+            .with_maintenance(config.maintenance.clone(), config.walltime());
+        let dead_letter = DeadLetterQueue::new(config.dead_letter_dir());
 
         Self {
             config,
@@ -208,6 +276,7 @@ impl HpcScheduler {
             queue: RwLock::new(PriorityQueue::new()),
             workflows: RwLock::new(rustc_hash::FxHashMap::default()),
             completed_jobs: RwLock::new(rustc_hash::FxHashSet::default()),
+            dead_letter,
         }
     }
 
@@ -218,7 +287,9 @@ impl HpcScheduler {
         store: Arc<dyn StateStore>,
     ) -> Self {
         let adapter = BatchAdapter::Pbs(PbsAdapter::mock(config.pbs.clone()));
-        let matcher = ResourceMatcher::new(backends);
+        let matcher = ResourceMatcher::new(backends)
+            .with_maintenance(config.maintenance.clone(), config.walltime());
+        let dead_letter = DeadLetterQueue::new(config.dead_letter_dir());
 
         Self {
             config,
@@ -228,7 +299,84 @@ impl HpcScheduler {
             queue: RwLock::new(PriorityQueue::new()),
             workflows: RwLock::new(rustc_hash::FxHashMap::default()),
             completed_jobs: RwLock::new(rustc_hash::FxHashSet::default()),
+            dead_letter,
+        }
+    }
+
+    /// Forensics bundles of failed jobs, in `state_dir/dead-letter`.
+    pub fn dead_letter(&self) -> &DeadLetterQueue {
+        &self.dead_letter
+    }
+
+    /// Summarize a workflow's progress and resource usage.
+    ///
+    /// Job state is read from the store, which holds the latest status and
+    /// accounting; jobs missing from the store fall back to the workflow's
+    /// own copy.
+    pub async fn workflow_report(&self, workflow_id: &WorkflowId) -> SchedResult<WorkflowReport> {
+        let workflows = self.workflows.read().await;
+        let workflow = workflows
+            .get(workflow_id)
+            .ok_or_else(|| SchedError::WorkflowNotFound(workflow_id.to_string()))?;
+
+        let mut jobs = Vec::with_capacity(workflow.len());
+        for job in workflow.all_jobs() {
+            let stored = self.store.load_job(&job.id).await?;
+            jobs.push(stored.unwrap_or_else(|| job.clone()));
+        }
+
+        let mut report = WorkflowReport::new(workflow, &jobs);
+        if let Some(intensity) = self.config.carbon_intensity_g_per_kwh {
+            report.energy = report.energy.with_carbon_intensity(intensity);
+        }
+        Ok(report)
+    }
+
+    /// Maintenance windows that will likely interrupt `job` if it is
+    /// submitted now, per backend it may run on: its matched backend, or
+    /// every backend when resources are matched automatically.
+    pub async fn maintenance_conflicts(
+        &self,
+        job: &ScheduledJob,
+    ) -> Vec<(String, MaintenanceWindow)> {
+        let names: Vec<String> = match &job.matched_backend {
+            Some(name) => vec![name.clone()],
+            None => self.matcher.backend_names(),
+        };
+        let mut conflicts = Vec::new();
+        for name in names {
+            if let Some(window) = self.matcher.maintenance_conflict(&name).await {
+                conflicts.push((name, window));
+            }
+        }
+        conflicts
+    }
+
+    /// Check `job`'s circuit signatures against the configured policy for
+    /// every backend it may run on: its matched backend, or every backend
+    /// when resources are matched automatically.
+    pub fn verify_signatures(&self, job: &ScheduledJob) -> SchedResult<()> {
+        let Some(policy) = &self.config.signing else {
+            return Ok(());
+        };
+        if !job.signatures.is_empty() && job.signatures.len() != job.circuits.len() {
+            return Err(SchedError::SignatureRejected(format!(
+                "{} signatures for {} circuits",
+                job.signatures.len(),
+                job.circuits.len()
+            )));
         }
+        let names: Vec<String> = match &job.matched_backend {
+            Some(name) => vec![name.clone()],
+            None => self.matcher.backend_names(),
+        };
+        for (i, circuit) in job.circuits.iter().enumerate() {
+            let source = circuit.source()?;
+            for name in &names {
+                policy.check(name, &source, job.signatures.get(i))?;
+            }
+        }
+        Ok(())
     }
 
     /// Start the background job processing loop.
@@ -266,6 +414,12 @@ impl HpcScheduler {
                     Ok(match_result) => {
                         job.matched_backend = Some(match_result.backend_name);
                     }
+                    Err(SchedError::BackendsInMaintenance(backends)) => {
+                        // Hold the job until a backend is out of maintenance.
+                        tracing::info!("Deferring job {}: maintenance on {}", job.id, backends);
+                        self.queue.write().await.push(job);
+                        continue;
+                    }
                     Err(e) => {
                         tracing::warn!("Resource matching failed for job {}: {}", job.id, e);
                         job.status = ScheduledJobStatus::Failed {
@@ -274,11 +428,26 @@ impl HpcScheduler {
                             quantum_job_id: None,
                         };
                         self.store.save_job(&job).await?;
+                        self.record_failure(&job, Some(format!("{e:?}"))).await;
                         continue;
                     }
                 }
             }
 
+            // Re-check now that the backend is known; circuit files may also
+            // have changed since submission.
+            if let Err(e) = self.verify_signatures(&job) {
+                tracing::warn!("Rejecting job {}: {}", job.id, e);
+                job.status = ScheduledJobStatus::Failed {
+                    reason: e.to_string(),
+                    slurm_job_id: None,
+                    quantum_job_id: None,
+                };
+                self.store.save_job(&job).await?;
+                self.record_failure(&job, Some(format!("{e:?}"))).await;
+                continue;
+            }
+
             // Submit to batch scheduler (SLURM or PBS)
             let submit_result = match &self.adapter {
                 BatchAdapter::Slurm(slurm) => slurm.submit(&job).await,
@@ -302,6 +471,7 @@ impl HpcScheduler {
                         quantum_job_id: None,
                     };
                     self.store.save_job(&job).await?;
+                    self.record_failure(&job, Some(format!("{e:?}"))).await;
                 }
             }
         }
@@ -315,9 +485,20 @@ impl HpcScheduler {
 
         for job in jobs {
             if let Some(batch_job_id) = job.status.slurm_job_id() {
+                let mut accounting = None;
+                let mut batch_state = None;
                 let new_status = match &self.adapter {
                     BatchAdapter::Slurm(slurm) => match slurm.status(batch_job_id).await {
-                        Ok(info) => Some(self.map_slurm_status(&job, &info)),
+                        Ok(info) => {
+                            accounting.clone_from(&info.accounting);
+                            batch_state = Some(format!(
+                                "state={:?} reason={} exit_code={}",
+                                info.state,
+                                info.reason.as_deref().unwrap_or("-"),
+                                info.exit_code.map_or("-".to_string(), |c| c.to_string())
+                            ));
+                            Some(self.map_slurm_status(&job, &info))
+                        }
                         Err(e) => {
                             tracing::warn!(
                                 "Failed to get status for SLURM job {}: {}",
@@ -328,7 +509,14 @@ impl HpcScheduler {
                         }
                     },
                     BatchAdapter::Pbs(pbs) => match pbs.status(batch_job_id).await {
-                        Ok(info) => Some(self.map_pbs_status(&job, &info)),
+                        Ok(info) => {
+                            batch_state = Some(format!(
+                                "state={:?} exit_status={}",
+                                info.state,
+                                info.exit_status.map_or("-".to_string(), |c| c.to_string())
+                            ));
+                            Some(self.map_pbs_status(&job, &info))
+                        }
                         Err(e) => {
                             tracing::warn!(
                                 "Failed to get status for PBS job {}: {}",
@@ -346,6 +534,19 @@ impl HpcScheduler {
                             .update_status(&job.id, new_status.clone())
                             .await?;
 
+                        if accounting.is_some() {
+                            if let Some(mut stored) = self.store.load_job(&job.id).await? {
+                                stored.accounting = accounting;
+                                self.store.save_job(&stored).await?;
+                            }
+                        }
+
+                        if matches!(new_status, ScheduledJobStatus::Failed { .. }) {
+                            let mut failed = job.clone();
+                            failed.status = new_status.clone();
+                            self.record_failure(&failed, batch_state).await;
+                        }
+
                         if new_status.is_terminal() {
                             let mut completed = self.completed_jobs.write().await;
                             // Evict oldest entries when cache exceeds limit
@@ -393,6 +594,93 @@ impl HpcScheduler {
         Ok(())
     }
 
+    /// File a forensics bundle for `job`, which has just failed.
+    ///
+    /// Best effort: a bundle that cannot be written is logged and does not
+    /// affect the job.
+    async fn record_failure(&self, job: &ScheduledJob, provider_error: Option<String>) {
+        let ScheduledJobStatus::Failed {
+            reason,
+            slurm_job_id,
+            quantum_job_id,
+        } = &job.status
+        else {
+            return;
+        };
+
+        let mut bundle =
+            FailureBundle::new(job.id.to_string(), reason.clone()).with_name(&job.name);
+        if let Some(backend) = &job.matched_backend {
+            bundle = bundle.with_backend(backend.clone());
+            if let Some(capabilities) = self.matcher.capabilities(backend) {
+                bundle = bundle.with_target(capabilities);
+            }
+        }
+        if let Some(id) = quantum_job_id {
+            bundle = bundle.with_provider_job_id(id.0.clone());
+        } else if let Some(id) = slurm_job_id {
+            bundle = bundle.with_provider_job_id(id.clone());
+        }
+        for spec in &job.circuits {
+            // Prefer the QASM the batch job was given; fall back to the
+            // source when it does not even parse.
+            let qasm = spec
+                .resolve()
+                .ok()
+                .and_then(|circuit| arvak_qasm3::emit(&circuit).ok())
+                .or_else(|| spec.source().ok());
+            if let Some(qasm) = qasm {
+                bundle = bundle.with_circuit(qasm);
+            }
+        }
+        if let Some(error) = provider_error {
+            bundle = bundle.with_provider_error(error);
+        }
+
+        let mut trace = vec![format!("{} created", job.created_at.to_rfc3339())];
+        if let Some(submitted_at) = job.submitted_at {
+            trace.push(format!(
+                "{} submitted to batch job {}",
+                submitted_at.to_rfc3339(),
+                slurm_job_id.as_deref().unwrap_or("-")
+            ));
+        }
+        if let Some(id) = slurm_job_id {
+            trace.extend(
+                tail_lines(&self.batch_error_log(id), MAX_TRACE_EVENTS)
+                    .await
+                    .into_iter()
+                    .map(|line| format!("stderr: {line}")),
+            );
+        }
+        let bundle = bundle.with_trace(trace);
+
+        match self.dead_letter.record(&bundle) {
+            Ok(path) => tracing::info!(
+                "Recorded failure bundle for job {} at {}",
+                job.id,
+                path.display()
+            ),
+            Err(e) => tracing::warn!("Failed to record failure bundle for job {}: {}", job.id, e),
+        }
+    }
+
+    /// Path of the stderr log the batch scheduler writes for a job.
+    fn batch_error_log(&self, batch_job_id: &str) -> PathBuf {
+        match &self.adapter {
+            BatchAdapter::Slurm(_) => self
+                .config
+                .slurm
+                .work_dir
+                .join(format!("slurm-{batch_job_id}.err")),
+            BatchAdapter::Pbs(_) => self
+                .config
+                .pbs
+                .work_dir
+                .join(format!("pbs-{batch_job_id}.err")),
+        }
+    }
+
     /// Map SLURM job state to scheduler job status.
     fn map_slurm_status(
         &self,
@@ -488,10 +776,24 @@ impl HpcScheduler {
     }
 }
 
+/// The last `n` lines of the file at `path`, or none if it cannot be read.
+async fn tail_lines(path: &Path, n: usize) -> Vec<String> {
+    let Ok(contents) = tokio::fs::read_to_string(path).await else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+    lines[lines.len().saturating_sub(n)..]
+        .iter()
+        .map(|line| (*line).to_string())
+        .collect()
+}
+
 #[async_trait]
 impl Scheduler for HpcScheduler {
     async fn submit(&self, mut job: ScheduledJob) -> SchedResult<ScheduledJobId> {
         let job_id = job.id.clone();
+        self.verify_signatures(&job)?;
+        job.check_parameters()?;
 
         // Check if job has unsatisfied dependencies
         if !job.dependencies.is_empty() {
@@ -501,6 +803,15 @@ impl Scheduler for HpcScheduler {
             }
         }
 
+        for (backend, window) in self.maintenance_conflicts(&job).await {
+            tracing::warn!(
+                "Job {} may be interrupted by maintenance on {}: {}",
+                job_id,
+                backend,
+                window
+            );
+        }
+
         // Save to store
         self.store.save_job(&job).await?;
 
@@ -608,10 +919,23 @@ impl Scheduler for HpcScheduler {
     }
 
     async fn result(&self, job_id: &ScheduledJobId) -> SchedResult<ExecutionResult> {
-        self.store
+        let mut result = self
+            .store
             .load_result(job_id)
             .await?
-            .ok_or_else(|| SchedError::JobNotFound(format!("No result for job {job_id}")))
+            .ok_or_else(|| SchedError::JobNotFound(format!("No result for job {job_id}")))?;
+
+        // Link the result to the job's artifact chain unless the backend
+        // already recorded a provenance.
+        if Provenance::from_result(&result).is_none() {
+            if let Some(job) = self.store.load_job(job_id).await? {
+                let backend = job.matched_backend.as_deref().unwrap_or("unknown");
+                if let Some(provenance) = job.submission_provenance(backend) {
+                    provenance.attach(&mut result);
+                }
+            }
+        }
+        Ok(result)
     }
 
     async fn list_jobs(&self, filter: JobFilter) -> SchedResult<Vec<ScheduledJob>> {
@@ -777,6 +1101,67 @@ mod tests {
         assert!(status.is_pending());
     }
 
+    #[tokio::test]
+    async fn test_scheduler_result_provenance() {
+        let store = Arc::new(SqliteStore::in_memory().unwrap());
+        let scheduler = HpcScheduler::with_mock_slurm(
+            SchedulerConfig::default(),
+            Vec::new(),
+            Arc::clone(&store) as Arc<dyn StateStore>,
+        );
+
+        let qasm = "OPENQASM 3.0; qubit[2] q; h q[0]; cx q[0], q[1];";
+        let source = Provenance::source(qasm);
+        let mut job = ScheduledJob::new("traced", CircuitSpec::from_qasm(qasm))
+            .with_shots(1000)
+            .with_provenance(source.clone());
+        job.matched_backend = Some("test_backend".to_string());
+        store.save_job(&job).await.unwrap();
+        let counts = Counts::from_pairs([("00", 500u64), ("11", 500u64)]);
+        store
+            .save_result(&job.id, &ExecutionResult::new(counts, 1000))
+            .await
+            .unwrap();
+
+        let result = scheduler.result(&job.id).await.unwrap();
+        let provenance = Provenance::from_result(&result).unwrap();
+        assert_eq!(provenance, source.submission("test_backend", 1000, None),);
+        assert!(provenance.contains(&source.artifact_id));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_requires_signed_circuits() {
+        let signer = arvak_hal::CircuitSigner::from_bytes(&[3; 32]).unwrap();
+        let stranger = arvak_hal::CircuitSigner::from_bytes(&[4; 32]).unwrap();
+        let policy = SignaturePolicy::new()
+            .with_trusted_key(&signer.public_key())
+            .unwrap()
+            .require_for_backend("hardware");
+        let config = SchedulerConfig::default().with_signing(policy);
+        let backends: Vec<Arc<dyn Backend>> = vec![Arc::new(MockBackend {
+            name: "hardware".to_string(),
+            capabilities: Capabilities::simulator(10),
+        })];
+        let store = Arc::new(SqliteStore::in_memory().unwrap());
+        let scheduler = HpcScheduler::with_mock_slurm(config, backends, store.clone());
+
+        let qasm = "OPENQASM 3.0; qubit[2] q; h q[0]; cx q[0], q[1];";
+        let unsigned = ScheduledJob::new("unsigned", CircuitSpec::from_qasm(qasm));
+        assert!(matches!(
+            scheduler.submit(unsigned).await,
+            Err(SchedError::SignatureRejected(_))
+        ));
+        let untrusted = ScheduledJob::new("untrusted", CircuitSpec::from_qasm(qasm))
+            .with_signatures([stranger.sign(qasm)]);
+        assert!(scheduler.submit(untrusted).await.is_err());
+
+        let signed = ScheduledJob::new("signed", CircuitSpec::from_qasm(qasm))
+            .with_signatures([signer.sign(qasm)]);
+        let job_id = scheduler.submit(signed).await.unwrap();
+        let stored = store.load_job(&job_id).await.unwrap().unwrap();
+        assert_eq!(stored.signatures, vec![signer.sign(qasm)]);
+    }
+
     #[tokio::test]
     async fn test_scheduler_submit_batch() {
         let config = SchedulerConfig::default();
@@ -836,6 +1221,143 @@ mod tests {
         assert!(matches!(status, WorkflowStatus::Pending));
     }
 
+    #[tokio::test]
+    async fn test_scheduler_workflow_report() {
+        let config = SchedulerConfig {
+            carbon_intensity_g_per_kwh: Some(100.0),
+            ..Default::default()
+        };
+        let store = Arc::new(SqliteStore::in_memory().unwrap());
+        let scheduler = HpcScheduler::with_mock_slurm(config, Vec::new(), store.clone());
+
+        let circuit = CircuitSpec::from_qasm("OPENQASM 3.0; qubit[2] q;");
+        let job1 = ScheduledJob::new("job1", circuit.clone());
+        let job2 = ScheduledJob::new("job2", circuit);
+        let job1_id = job1.id.clone();
+
+        let workflow = scheduler
+            .create_workflow("energy_workflow")
+            .add_job(job1)
+            .then(job2)
+            .unwrap()
+            .build();
+        let workflow_id = scheduler.submit_workflow(workflow).await.unwrap();
+
+        // Simulate a finished SLURM job with accounting data
+        let mut stored = store.load_job(&job1_id).await.unwrap().unwrap();
+        stored.status = ScheduledJobStatus::Completed {
+            slurm_job_id: "1".to_string(),
+            quantum_job_id: arvak_hal::JobId::new("q1"),
+        };
+        stored.accounting = Some(crate::energy::JobAccounting {
+            elapsed_seconds: 3600,
+            num_nodes: 2,
+            consumed_energy_joules: Some(7.2e6),
+        });
+        store.save_job(&stored).await.unwrap();
+
+        let report = scheduler.workflow_report(&workflow_id).await.unwrap();
+        assert_eq!(report.name, "energy_workflow");
+        assert_eq!(report.total_jobs, 2);
+        assert_eq!(report.completed_jobs, 1);
+        assert_eq!(report.energy.accounted_jobs, 1);
+        assert!((report.energy.node_hours - 2.0).abs() < 1e-12);
+        assert!((report.energy.energy_kwh - 2.0).abs() < 1e-12);
+        assert_eq!(report.energy.co2_kg, Some(0.2));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_defers_jobs_during_maintenance() {
+        let now = chrono::Utc::now();
+        let window = MaintenanceWindow::new(
+            now - chrono::Duration::hours(1),
+            now + chrono::Duration::hours(1),
+        )
+        .with_reason("recalibration");
+        let config = SchedulerConfig::default().with_maintenance(
+            MaintenanceCalendar::default().with_window("test_backend", window.clone()),
+        );
+        let backends: Vec<Arc<dyn Backend>> = vec![Arc::new(MockBackend {
+            name: "test_backend".to_string(),
+            capabilities: Capabilities::simulator(10),
+        })];
+        let store = Arc::new(SqliteStore::in_memory().unwrap());
+        let scheduler = HpcScheduler::with_mock_slurm(config, backends, store.clone());
+
+        let circuit = CircuitSpec::from_qasm("OPENQASM 3.0; qubit[2] q;");
+        let job = ScheduledJob::new("during_maintenance", circuit);
+        assert_eq!(
+            scheduler.maintenance_conflicts(&job).await,
+            vec![("test_backend".to_string(), window)]
+        );
+
+        let job_id = scheduler.submit(job).await.unwrap();
+        scheduler.process_pending_jobs().await.unwrap();
+
+        // Held in the queue instead of failing resource matching.
+        assert!(scheduler.queue.read().await.contains(&job_id));
+        let stored = store.load_job(&job_id).await.unwrap().unwrap();
+        assert!(matches!(stored.status, ScheduledJobStatus::Pending));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_records_failure_bundle() {
+        let state_dir = tempfile::tempdir().unwrap();
+        let config = SchedulerConfig {
+            state_dir: state_dir.path().to_path_buf(),
+            ..SchedulerConfig::default()
+        };
+        let backends: Vec<Arc<dyn Backend>> = vec![Arc::new(MockBackend {
+            name: "test_backend".to_string(),
+            capabilities: Capabilities::simulator(2),
+        })];
+        let store = Arc::new(SqliteStore::in_memory().unwrap());
+        let scheduler = HpcScheduler::with_mock_slurm(config, backends, store);
+
+        // Needs more qubits than any backend has, so matching fails.
+        let circuit = CircuitSpec::from_qasm("OPENQASM 3.0; qubit[2] q; h q[0];");
+        let job =
+            ScheduledJob::new("too_big", circuit).with_requirements(ResourceRequirements::new(20));
+        let job_id = scheduler.submit(job).await.unwrap();
+        scheduler.process_pending_jobs().await.unwrap();
+
+        let bundle = scheduler
+            .dead_letter()
+            .get(&job_id.to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(bundle.name.as_deref(), Some("too_big"));
+        assert!(bundle.reason.contains("No matching backend"));
+        assert!(bundle.provider_error.is_some());
+        assert_eq!(bundle.circuits.len(), 1);
+        assert!(bundle.circuits[0].contains("h q[0];"));
+        assert!(bundle.trace[0].ends_with("created"));
+    }
+
+    #[tokio::test]
+    async fn test_tail_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slurm-1.err");
+        std::fs::write(&path, "one\ntwo\nthree\n").unwrap();
+        assert_eq!(tail_lines(&path, 2).await, vec!["two", "three"]);
+        assert!(tail_lines(&dir.path().join("missing"), 2).await.is_empty());
+    }
+
+    #[test]
+    fn test_walltime() {
+        let mut config = SchedulerConfig::default();
+        config.slurm.time_limit = 30;
+        assert_eq!(config.walltime(), Duration::from_secs(1800));
+
+        let config = SchedulerConfig::with_pbs(PbsConfig {
+            walltime: "01:30:00".into(),
+            ..PbsConfig::default()
+        });
+        assert_eq!(config.walltime(), Duration::from_secs(5400));
+        assert_eq!(parse_walltime("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_walltime("1:xx"), None);
+    }
+
     #[tokio::test]
     async fn test_scheduler_submit_with_pbs() {
         let config = SchedulerConfig::with_pbs(PbsConfig::default());
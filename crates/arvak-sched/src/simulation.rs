@@ -0,0 +1,558 @@
+//! Discrete-event queueing simulation for capacity planning.
+//!
+//! Replays a job trace against a site's constraints to predict queue wait
+//! times and QPU utilization before real hardware time is reserved. Jobs
+//! arrive at their `submit_at` offset, wait in a priority queue (higher
+//! priority first, FIFO within a priority, as in [`PriorityQueue`]) and
+//! run on the first free QPU for their runtime plus the site's per-job
+//! overhead. Jobs that need more qubits than the device has, or more time
+//! than the walltime limit allows, are rejected on arrival.
+//!
+//! A workload file is a JSON array of [`WorkloadJob`]s, or an object with a
+//! `jobs` array:
+//!
+//! ```json
+//! [
+//!   {"name": "vqe-1", "submit_at": 0, "runtime_seconds": 120, "qubits": 5},
+//!   {"name": "qaoa-1", "submit_at": 30, "runtime_seconds": 300, "priority": 150}
+//! ]
+//! ```
+//!
+//! [`PriorityQueue`]: crate::queue::PriorityQueue
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SchedError, SchedResult};
+use crate::job::Priority;
+
+/// Capacity and limits of an HPC site, as seen by the simulator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteConstraints {
+    /// Name of the site.
+    pub name: String,
+
+    /// Number of QPUs that run jobs in parallel.
+    pub num_qpus: u32,
+
+    /// Qubits available on each QPU.
+    pub max_qubits: u32,
+
+    /// Walltime limit per job in seconds.
+    pub max_walltime_seconds: u64,
+
+    /// Fixed per-job overhead (setup, circuit upload, readout) in seconds.
+    #[serde(default)]
+    pub job_overhead_seconds: f64,
+}
+
+impl SiteConstraints {
+    /// LRZ quantum partition (`qc_iqm`, one 20-qubit IQM device).
+    pub fn lrz() -> Self {
+        Self {
+            name: "LRZ".into(),
+            num_qpus: 1,
+            max_qubits: 20,
+            max_walltime_seconds: 3600,
+            job_overhead_seconds: 10.0,
+        }
+    }
+
+    /// LUMI `q_fiqci` partition (one 5-qubit Helmi device).
+    pub fn lumi() -> Self {
+        Self {
+            name: "LUMI".into(),
+            num_qpus: 1,
+            max_qubits: 5,
+            max_walltime_seconds: 900,
+            job_overhead_seconds: 10.0,
+        }
+    }
+
+    /// Look up a built-in site by name (`lrz`, `lumi`).
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lrz" => Some(Self::lrz()),
+            "lumi" => Some(Self::lumi()),
+            _ => None,
+        }
+    }
+
+    /// Load site constraints from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> SchedResult<Self> {
+        let data = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// Override the number of QPUs.
+    #[must_use]
+    pub fn with_num_qpus(mut self, num_qpus: u32) -> Self {
+        self.num_qpus = num_qpus;
+        self
+    }
+}
+
+/// One job in a workload trace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadJob {
+    /// Job name.
+    pub name: String,
+
+    /// Submission time in seconds from the start of the trace.
+    pub submit_at: f64,
+
+    /// QPU time the job needs once started, in seconds.
+    pub runtime_seconds: f64,
+
+    /// Job priority.
+    #[serde(default)]
+    pub priority: Priority,
+
+    /// Qubits the job needs (0 if unknown).
+    #[serde(default)]
+    pub qubits: u32,
+}
+
+/// A job trace to replay.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workload {
+    /// Jobs in the trace, in any order.
+    pub jobs: Vec<WorkloadJob>,
+}
+
+/// On-disk workload formats: a bare array or `{"jobs": [...]}`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WorkloadFile {
+    Jobs(Vec<WorkloadJob>),
+    Workload(Workload),
+}
+
+impl Workload {
+    /// Create a workload from a list of jobs.
+    pub fn new(jobs: Vec<WorkloadJob>) -> Self {
+        Self { jobs }
+    }
+
+    /// Parse a workload from JSON.
+    pub fn from_json(json: &str) -> SchedResult<Self> {
+        let workload = match serde_json::from_str(json)? {
+            WorkloadFile::Jobs(jobs) => Self { jobs },
+            WorkloadFile::Workload(workload) => workload,
+        };
+        workload.validate()?;
+        Ok(workload)
+    }
+
+    /// Load a workload from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> SchedResult<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    fn validate(&self) -> SchedResult<()> {
+        for job in &self.jobs {
+            if !(job.submit_at.is_finite() && job.submit_at >= 0.0) {
+                return Err(SchedError::ConfigError(format!(
+                    "job '{}': submit_at must be a non-negative number of seconds",
+                    job.name
+                )));
+            }
+            if !(job.runtime_seconds.is_finite() && job.runtime_seconds >= 0.0) {
+                return Err(SchedError::ConfigError(format!(
+                    "job '{}': runtime_seconds must be a non-negative number of seconds",
+                    job.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a job was rejected instead of queued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// The job needs more qubits than a QPU has.
+    TooManyQubits,
+    /// Runtime plus overhead exceeds the walltime limit.
+    ExceedsWalltime,
+}
+
+/// Simulated timeline of one job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedJob {
+    /// Job name.
+    pub name: String,
+
+    /// Submission time in seconds.
+    pub submit_at: f64,
+
+    /// Time the job started running, if it ran.
+    pub start_at: Option<f64>,
+
+    /// Time the job finished, if it ran.
+    pub end_at: Option<f64>,
+
+    /// Seconds spent queued before starting.
+    pub wait_seconds: Option<f64>,
+
+    /// QPU the job ran on.
+    pub qpu: Option<u32>,
+
+    /// Set if the job was rejected on arrival.
+    pub rejected: Option<RejectionReason>,
+}
+
+/// Predicted queue times and utilization for a workload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Site the workload was replayed against.
+    pub site: SiteConstraints,
+
+    /// Per-job timelines, in workload order.
+    pub jobs: Vec<SimulatedJob>,
+
+    /// Jobs that ran to completion.
+    pub completed: usize,
+
+    /// Jobs rejected on arrival.
+    pub rejected: usize,
+
+    /// Seconds from the first submission to the last completion.
+    pub makespan_seconds: f64,
+
+    /// Mean queue wait in seconds.
+    pub mean_wait_seconds: f64,
+
+    /// Median queue wait in seconds.
+    pub p50_wait_seconds: f64,
+
+    /// 95th percentile queue wait in seconds.
+    pub p95_wait_seconds: f64,
+
+    /// Longest queue wait in seconds.
+    pub max_wait_seconds: f64,
+
+    /// Most jobs waiting in the queue at once.
+    pub peak_queue_length: usize,
+
+    /// Busy fraction of each QPU over the makespan.
+    pub qpu_utilization: Vec<f64>,
+
+    /// Busy fraction across all QPUs over the makespan.
+    pub utilization: f64,
+}
+
+/// Replay `workload` against `site`.
+pub fn simulate(workload: &Workload, site: &SiteConstraints) -> SchedResult<SimulationReport> {
+    if site.num_qpus == 0 {
+        return Err(SchedError::ConfigError(
+            "site must have at least one QPU".into(),
+        ));
+    }
+    workload.validate()?;
+
+    let jobs = &workload.jobs;
+    let mut results: Vec<SimulatedJob> = jobs
+        .iter()
+        .map(|job| SimulatedJob {
+            name: job.name.clone(),
+            submit_at: job.submit_at,
+            start_at: None,
+            end_at: None,
+            wait_seconds: None,
+            qpu: None,
+            rejected: None,
+        })
+        .collect();
+
+    // Arrival order; ties keep workload order.
+    let mut arrivals: Vec<usize> = (0..jobs.len()).collect();
+    arrivals.sort_by(|&a, &b| jobs[a].submit_at.total_cmp(&jobs[b].submit_at));
+
+    // Highest priority first, then earliest submission, then workload order.
+    let mut waiting: BinaryHeap<(Priority, Reverse<ArrivalTime>, Reverse<usize>)> =
+        BinaryHeap::new();
+    let mut busy_until: Vec<Option<f64>> = vec![None; site.num_qpus as usize];
+    let mut busy_seconds = vec![0.0; site.num_qpus as usize];
+    let mut peak_queue_length = 0;
+    let mut next_arrival = 0;
+    let mut now = 0.0;
+
+    loop {
+        // Release QPUs whose job has finished.
+        for slot in &mut busy_until {
+            if slot.is_some_and(|end| end <= now) {
+                *slot = None;
+            }
+        }
+
+        // Admit everything submitted by now.
+        while let Some(&i) = arrivals.get(next_arrival) {
+            if jobs[i].submit_at > now {
+                break;
+            }
+            next_arrival += 1;
+            let job = &jobs[i];
+            if job.qubits > site.max_qubits {
+                results[i].rejected = Some(RejectionReason::TooManyQubits);
+            } else if job.runtime_seconds + site.job_overhead_seconds
+                > site.max_walltime_seconds as f64
+            {
+                results[i].rejected = Some(RejectionReason::ExceedsWalltime);
+            } else {
+                waiting.push((
+                    job.priority,
+                    Reverse(ArrivalTime(job.submit_at)),
+                    Reverse(i),
+                ));
+            }
+        }
+
+        // Start queued jobs on free QPUs.
+        while !waiting.is_empty() {
+            let Some(qpu) = busy_until.iter().position(Option::is_none) else {
+                break;
+            };
+            let Some((_, _, Reverse(i))) = waiting.pop() else {
+                break;
+            };
+            let duration = jobs[i].runtime_seconds + site.job_overhead_seconds;
+            let end = now + duration;
+            busy_until[qpu] = Some(end);
+            busy_seconds[qpu] += duration;
+
+            let result = &mut results[i];
+            result.start_at = Some(now);
+            result.end_at = Some(end);
+            result.wait_seconds = Some(now - jobs[i].submit_at);
+            result.qpu = Some(qpu as u32);
+        }
+        peak_queue_length = peak_queue_length.max(waiting.len());
+
+        // Advance to the next arrival or completion.
+        let arrival = arrivals.get(next_arrival).map(|&i| jobs[i].submit_at);
+        let completion = busy_until.iter().flatten().copied().reduce(f64::min);
+        now = match (arrival, completion) {
+            (Some(a), Some(c)) => a.min(c),
+            (Some(t), None) | (None, Some(t)) => t,
+            (None, None) => break,
+        };
+    }
+
+    let mut waits: Vec<f64> = results.iter().filter_map(|r| r.wait_seconds).collect();
+    waits.sort_by(f64::total_cmp);
+
+    let first_submit = jobs.iter().map(|j| j.submit_at).reduce(f64::min);
+    let last_end = results.iter().filter_map(|r| r.end_at).reduce(f64::max);
+    let makespan_seconds = match (first_submit, last_end) {
+        (Some(start), Some(end)) => end - start,
+        _ => 0.0,
+    };
+    let busy_fraction = |busy: f64| {
+        if makespan_seconds > 0.0 {
+            busy / makespan_seconds
+        } else {
+            0.0
+        }
+    };
+    let qpu_utilization: Vec<f64> = busy_seconds.iter().map(|&b| busy_fraction(b)).collect();
+    let utilization = busy_fraction(busy_seconds.iter().sum::<f64>()) / f64::from(site.num_qpus);
+
+    Ok(SimulationReport {
+        site: site.clone(),
+        completed: waits.len(),
+        rejected: results.iter().filter(|r| r.rejected.is_some()).count(),
+        jobs: results,
+        makespan_seconds,
+        mean_wait_seconds: if waits.is_empty() {
+            0.0
+        } else {
+            waits.iter().sum::<f64>() / waits.len() as f64
+        },
+        p50_wait_seconds: percentile(&waits, 50.0),
+        p95_wait_seconds: percentile(&waits, 95.0),
+        max_wait_seconds: waits.last().copied().unwrap_or(0.0),
+        peak_queue_length,
+        qpu_utilization,
+        utilization,
+    })
+}
+
+/// Nearest-rank percentile of sorted values (0 if empty).
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Submission time with a total order, so it can key the waiting queue.
+#[derive(Debug, Clone, Copy)]
+struct ArrivalTime(f64);
+
+impl PartialEq for ArrivalTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ArrivalTime {}
+
+impl PartialOrd for ArrivalTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArrivalTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(name: &str, submit_at: f64, runtime_seconds: f64) -> WorkloadJob {
+        WorkloadJob {
+            name: name.into(),
+            submit_at,
+            runtime_seconds,
+            priority: Priority::default(),
+            qubits: 0,
+        }
+    }
+
+    fn site(num_qpus: u32) -> SiteConstraints {
+        SiteConstraints {
+            name: "test".into(),
+            num_qpus,
+            max_qubits: 5,
+            max_walltime_seconds: 1000,
+            job_overhead_seconds: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_single_qpu_fifo() {
+        let workload = Workload::new(vec![
+            job("a", 0.0, 100.0),
+            job("b", 10.0, 50.0),
+            job("c", 20.0, 50.0),
+        ]);
+        let report = simulate(&workload, &site(1)).unwrap();
+
+        assert_eq!(report.completed, 3);
+        assert_eq!(report.jobs[1].start_at, Some(100.0));
+        assert_eq!(report.jobs[2].start_at, Some(150.0));
+        assert_eq!(report.jobs[2].wait_seconds, Some(130.0));
+        assert_eq!(report.makespan_seconds, 200.0);
+        assert_eq!(report.peak_queue_length, 2);
+        assert_eq!(report.max_wait_seconds, 130.0);
+        assert_eq!(report.p50_wait_seconds, 90.0);
+        assert_eq!(report.utilization, 1.0);
+    }
+
+    #[test]
+    fn test_fifo_follows_arrival_not_workload_order() {
+        let workload = Workload::new(vec![
+            job("a", 0.0, 100.0),
+            job("late", 20.0, 10.0),
+            job("early", 10.0, 10.0),
+        ]);
+        let report = simulate(&workload, &site(1)).unwrap();
+
+        assert_eq!(report.jobs[2].start_at, Some(100.0));
+        assert_eq!(report.jobs[1].start_at, Some(110.0));
+    }
+
+    #[test]
+    fn test_priority_jumps_queue() {
+        let mut urgent = job("urgent", 20.0, 10.0);
+        urgent.priority = Priority::high();
+        let workload = Workload::new(vec![job("a", 0.0, 100.0), job("b", 10.0, 10.0), urgent]);
+        let report = simulate(&workload, &site(1)).unwrap();
+
+        assert_eq!(report.jobs[2].start_at, Some(100.0));
+        assert_eq!(report.jobs[1].start_at, Some(110.0));
+    }
+
+    #[test]
+    fn test_more_qpus_reduce_waits() {
+        let workload = Workload::new((0..4).map(|i| job(&format!("j{i}"), 0.0, 60.0)).collect());
+
+        let one = simulate(&workload, &site(1)).unwrap();
+        let two = simulate(&workload, &site(2)).unwrap();
+        assert_eq!(one.max_wait_seconds, 180.0);
+        assert_eq!(two.max_wait_seconds, 60.0);
+        assert_eq!(two.qpu_utilization, vec![1.0, 1.0]);
+        assert_eq!(two.jobs[1].qpu, Some(1));
+    }
+
+    #[test]
+    fn test_rejections() {
+        let mut wide = job("wide", 0.0, 10.0);
+        wide.qubits = 20;
+        let workload = Workload::new(vec![wide, job("long", 0.0, 5000.0), job("ok", 0.0, 10.0)]);
+        let report = simulate(&workload, &site(1)).unwrap();
+
+        assert_eq!(report.rejected, 2);
+        assert_eq!(report.completed, 1);
+        assert_eq!(
+            report.jobs[0].rejected,
+            Some(RejectionReason::TooManyQubits)
+        );
+        assert_eq!(
+            report.jobs[1].rejected,
+            Some(RejectionReason::ExceedsWalltime)
+        );
+        assert_eq!(report.jobs[2].wait_seconds, Some(0.0));
+    }
+
+    #[test]
+    fn test_overhead_counts_as_busy_time() {
+        let mut site = site(1);
+        site.job_overhead_seconds = 10.0;
+        let workload = Workload::new(vec![job("a", 0.0, 40.0), job("b", 100.0, 40.0)]);
+        let report = simulate(&workload, &site).unwrap();
+
+        assert_eq!(report.jobs[0].end_at, Some(50.0));
+        assert_eq!(report.makespan_seconds, 150.0);
+        assert!((report.utilization - 100.0 / 150.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_workload_formats() {
+        let array = r#"[{"name": "a", "submit_at": 0, "runtime_seconds": 5}]"#;
+        let object =
+            r#"{"jobs": [{"name": "a", "submit_at": 0, "runtime_seconds": 5, "priority": 150}]}"#;
+
+        assert_eq!(Workload::from_json(array).unwrap().jobs.len(), 1);
+        let workload = Workload::from_json(object).unwrap();
+        assert_eq!(workload.jobs[0].priority, Priority::HIGH);
+
+        let negative = r#"[{"name": "a", "submit_at": -1, "runtime_seconds": 5}]"#;
+        assert!(matches!(
+            Workload::from_json(negative),
+            Err(SchedError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_qpus_is_an_error() {
+        assert!(simulate(&Workload::default(), &site(0)).is_err());
+    }
+
+    #[test]
+    fn test_site_presets() {
+        assert_eq!(SiteConstraints::by_name("LUMI").unwrap().max_qubits, 5);
+        assert_eq!(SiteConstraints::by_name("lrz").unwrap().max_qubits, 20);
+        assert!(SiteConstraints::by_name("unknown").is_none());
+    }
+}
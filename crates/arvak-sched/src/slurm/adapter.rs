@@ -6,6 +6,7 @@ use std::process::Stdio;
 use tokio::fs;
 use tokio::process::Command;
 
+use crate::energy::JobAccounting;
 use crate::error::{SchedError, SchedResult};
 use crate::job::ScheduledJob;
 use crate::slurm::parser;
@@ -64,6 +65,9 @@ pub struct SlurmJobInfo {
 
     /// Exit code (for completed jobs).
     pub exit_code: Option<i32>,
+
+    /// Elapsed time, nodes and energy (for jobs reported by `sacct`).
+    pub accounting: Option<JobAccounting>,
 }
 
 /// Configuration for SLURM adapter.
@@ -206,6 +210,11 @@ impl SlurmAdapter {
                 state: SlurmState::Completed,
                 reason: None,
                 exit_code: Some(0),
+                accounting: Some(JobAccounting {
+                    elapsed_seconds: 60,
+                    num_nodes: 1,
+                    consumed_energy_joules: None,
+                }),
             });
         }
 
@@ -333,7 +342,7 @@ impl SlurmAdapter {
         parser::parse_squeue_output(&stdout)
     }
 
-    /// Run sacct command to get completed job status.
+    /// Run sacct command to get completed job status and accounting.
     async fn run_sacct(&self, slurm_job_id: &str) -> SchedResult<Option<SlurmJobInfo>> {
         let output = tokio::time::timeout(
             std::time::Duration::from_secs(30),
@@ -342,7 +351,7 @@ impl SlurmAdapter {
                     "-j",
                     slurm_job_id,
                     "-o",
-                    "JobID,JobName,State,ExitCode",
+                    "JobID,JobName,State,ExitCode,ElapsedRaw,NNodes,ConsumedEnergyRaw",
                     "-P",
                 ])
                 .stdout(Stdio::piped())
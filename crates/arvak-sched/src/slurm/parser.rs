@@ -1,5 +1,6 @@
 //! Parsers for SLURM command output.
 
+use crate::energy::JobAccounting;
 use crate::error::{SchedError, SchedResult};
 use crate::slurm::adapter::{SlurmJobInfo, SlurmState};
 
@@ -64,15 +65,21 @@ pub fn parse_squeue_output(output: &str) -> SchedResult<Option<SlurmJobInfo>> {
         state,
         reason,
         exit_code: None,
+        accounting: None,
     }))
 }
 
 /// Parse sacct output for completed job information.
 ///
-/// Expected format (from `sacct -j <id> -o JobID,JobName,State,ExitCode -P`):
-/// JobID|JobName|State|ExitCode
-/// `12345|job_name|COMPLETED|0:0`
-/// 12345.batch|batch|COMPLETED|0:0
+/// Expected format (from `sacct -j <id> -o
+/// JobID,JobName,State,ExitCode,ElapsedRaw,NNodes,ConsumedEnergyRaw -P`):
+/// JobID|JobName|State|ExitCode|ElapsedRaw|NNodes|ConsumedEnergyRaw
+/// `12345|job_name|COMPLETED|0:0|120|1|54000`
+/// 12345.batch|batch|COMPLETED|0:0|120|1|53000
+///
+/// The accounting columns are optional. Energy is taken from the job line,
+/// falling back to the largest step value; an empty or zero value means the
+/// site does not measure energy.
 pub fn parse_sacct_output(output: &str) -> SchedResult<Option<SlurmJobInfo>> {
     let lines: Vec<&str> = output.lines().collect();
 
@@ -81,33 +88,55 @@ pub fn parse_sacct_output(output: &str) -> SchedResult<Option<SlurmJobInfo>> {
         return Ok(None);
     }
 
+    let rows: Vec<Vec<&str>> = lines[1..]
+        .iter()
+        .map(|line| line.split('|').map(str::trim).collect::<Vec<_>>())
+        .filter(|parts| parts.len() >= 4)
+        .collect();
+
     // Find the main job line (not .batch or .extern)
-    for line in &lines[1..] {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() < 4 {
-            continue;
-        }
+    let Some(main) = rows.iter().find(|parts| !parts[0].contains('.')) else {
+        return Ok(None);
+    };
 
-        let job_id = parts[0].trim();
-        // Skip sub-jobs (e.g., "12345.batch", "12345.extern")
-        if job_id.contains('.') {
-            continue;
+    let accounting = parse_accounting(main).map(|mut accounting| {
+        if accounting.consumed_energy_joules.is_none() {
+            // Sub-jobs (e.g., "12345.batch") carry the energy on some sites
+            accounting.consumed_energy_joules = rows
+                .iter()
+                .filter(|parts| parts[0].contains('.'))
+                .filter_map(|parts| parse_energy(parts.get(6).copied()))
+                .reduce(f64::max);
         }
+        accounting
+    });
 
-        let name = parts[1].trim().to_string();
-        let state = parse_slurm_state(parts[2].trim());
-        let exit_code = parse_exit_code(parts[3].trim());
+    Ok(Some(SlurmJobInfo {
+        job_id: main[0].to_string(),
+        name: main[1].to_string(),
+        state: parse_slurm_state(main[2]),
+        reason: None,
+        exit_code: parse_exit_code(main[3]),
+        accounting,
+    }))
+}
 
-        return Ok(Some(SlurmJobInfo {
-            job_id: job_id.to_string(),
-            name,
-            state,
-            reason: None,
-            exit_code,
-        }));
-    }
+/// Parse the `ElapsedRaw|NNodes|ConsumedEnergyRaw` columns of a sacct row.
+fn parse_accounting(parts: &[&str]) -> Option<JobAccounting> {
+    let elapsed_seconds = parts.get(4)?.parse().ok()?;
+    let num_nodes = parts.get(5).and_then(|n| n.parse().ok()).unwrap_or(1);
+    Some(JobAccounting {
+        elapsed_seconds,
+        num_nodes,
+        consumed_energy_joules: parse_energy(parts.get(6).copied()),
+    })
+}
 
-    Ok(None)
+/// Parse a `ConsumedEnergyRaw` value in joules (zero means not measured).
+fn parse_energy(value: Option<&str>) -> Option<f64> {
+    value
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|&joules| joules > 0.0)
 }
 
 /// Parse SLURM state string.
@@ -207,6 +236,40 @@ mod tests {
         assert_eq!(info.name, "my_job");
         assert!(matches!(info.state, SlurmState::Completed));
         assert_eq!(info.exit_code, Some(0));
+        assert!(info.accounting.is_none());
+    }
+
+    #[test]
+    fn test_parse_sacct_output_accounting() {
+        let output = "JobID|JobName|State|ExitCode|ElapsedRaw|NNodes|ConsumedEnergyRaw\n\
+                      12345|my_job|COMPLETED|0:0|1800|2|7200000\n\
+                      12345.batch|batch|COMPLETED|0:0|1800|2|7100000\n";
+        let info = parse_sacct_output(output).unwrap().unwrap();
+        let accounting = info.accounting.unwrap();
+        assert_eq!(accounting.elapsed_seconds, 1800);
+        assert_eq!(accounting.num_nodes, 2);
+        assert_eq!(accounting.consumed_energy_joules, Some(7.2e6));
+
+        // Energy only on the step line
+        let output = "JobID|JobName|State|ExitCode|ElapsedRaw|NNodes|ConsumedEnergyRaw\n\
+                      12345|my_job|COMPLETED|0:0|60|1|\n\
+                      12345.batch|batch|COMPLETED|0:0|60|1|9000\n\
+                      12345.extern|extern|COMPLETED|0:0|60|1|0\n";
+        let info = parse_sacct_output(output).unwrap().unwrap();
+        assert_eq!(
+            info.accounting.unwrap().consumed_energy_joules,
+            Some(9000.0)
+        );
+
+        // No energy plugin: energy is reported as zero
+        let output = "JobID|JobName|State|ExitCode|ElapsedRaw|NNodes|ConsumedEnergyRaw\n\
+                      12345|my_job|COMPLETED|0:0|60|1|0\n";
+        let accounting = parse_sacct_output(output)
+            .unwrap()
+            .unwrap()
+            .accounting
+            .unwrap();
+        assert_eq!(accounting.consumed_energy_joules, None);
     }
 
     #[test]
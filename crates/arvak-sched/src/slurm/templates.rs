@@ -129,6 +129,7 @@ pub fn generate_batch_script(
     // Execute Arvak command
     script.push_str("# Execute quantum job\n");
 
+    let param_args = job.parameter_args()?;
     let backend_flag = if let Some(ref backend) = job.matched_backend {
         format!("--backend {backend}")
     } else {
@@ -136,10 +137,11 @@ pub fn generate_batch_script(
     };
 
     script.push_str(&format!(
-        "{} run {} --shots {} {} --output {}\n",
+        "{} run {} --shots {}{} {} --output {}\n",
         config.arvak_binary.display(),
         circuit_file.display(),
         job.shots,
+        param_args,
         backend_flag,
         result_file.display(),
     ));
@@ -250,6 +252,7 @@ pub fn generate_batch_script_multi(
     script.push_str("# Execute quantum jobs\n");
     script.push_str("FAILED=0\n\n");
 
+    let param_args = job.parameter_args()?;
     let backend_flag = if let Some(ref backend) = job.matched_backend {
         format!("--backend {backend}")
     } else {
@@ -264,10 +267,11 @@ pub fn generate_batch_script_multi(
             circuit_files.len()
         ));
         script.push_str(&format!(
-            "if ! {} run {} --shots {} {} --output {}; then\n",
+            "if ! {} run {} --shots {}{} {} --output {}; then\n",
             config.arvak_binary.display(),
             circuit_file.display(),
             job.shots,
+            param_args,
             backend_flag,
             result_file.display(),
         ));
@@ -361,6 +365,37 @@ mod tests {
         assert!(script.contains("/opt/arvak/bin/arvak run"));
     }
 
+    #[test]
+    fn test_batch_script_passes_parameters() {
+        let config = test_config();
+        let circuit =
+            CircuitSpec::from_qasm("OPENQASM 3.0; input float[64] theta; qubit q; rx(theta) q;");
+        let job = ScheduledJob::new("vqe", circuit).with_parameters([("theta", 0.5)]);
+        job.check_parameters().unwrap();
+
+        let script = generate_batch_script(
+            &job,
+            &config,
+            Path::new("/scratch/circuit.qasm"),
+            Path::new("/scratch/result.json"),
+        )
+        .unwrap();
+        assert!(script.contains("--shots 1024 --param theta=0.5 "));
+
+        let unbound = job.clone().with_parameters([("phi", 0.5)]);
+        assert!(unbound.check_parameters().is_err());
+        let injected = job.with_parameters([("x;rm", 0.5)]);
+        assert!(
+            generate_batch_script(
+                &injected,
+                &config,
+                Path::new("/scratch/circuit.qasm"),
+                Path::new("/scratch/result.json"),
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_sanitize_name() {
         assert_eq!(sanitize_name("my_job"), "my_job");
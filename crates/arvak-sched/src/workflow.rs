@@ -7,8 +7,9 @@ use petgraph::visit::EdgeRef;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::energy::EnergyReport;
 use crate::error::{SchedError, SchedResult};
-use crate::job::{ScheduledJob, ScheduledJobId};
+use crate::job::{ScheduledJob, ScheduledJobId, ScheduledJobStatus};
 
 /// Unique identifier for a workflow.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -357,6 +358,52 @@ impl Workflow {
     }
 }
 
+/// Summary of a workflow's progress and resource usage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowReport {
+    /// Workflow identifier.
+    pub id: WorkflowId,
+
+    /// Workflow name.
+    pub name: String,
+
+    /// Current status.
+    pub status: WorkflowStatus,
+
+    /// Number of jobs in the workflow.
+    pub total_jobs: usize,
+
+    /// Number of jobs that completed successfully.
+    pub completed_jobs: usize,
+
+    /// Number of jobs that failed.
+    pub failed_jobs: usize,
+
+    /// Node-hours and energy consumed by the workflow's jobs.
+    pub energy: EnergyReport,
+}
+
+impl WorkflowReport {
+    /// Build a report for `workflow` from the current state of its `jobs`.
+    pub fn new(workflow: &Workflow, jobs: &[ScheduledJob]) -> Self {
+        Self {
+            id: workflow.id.clone(),
+            name: workflow.name.clone(),
+            status: workflow.status.clone(),
+            total_jobs: jobs.len(),
+            completed_jobs: jobs
+                .iter()
+                .filter(|j| matches!(j.status, ScheduledJobStatus::Completed { .. }))
+                .count(),
+            failed_jobs: jobs
+                .iter()
+                .filter(|j| matches!(j.status, ScheduledJobStatus::Failed { .. }))
+                .count(),
+            energy: EnergyReport::from_jobs(jobs),
+        }
+    }
+}
+
 /// Builder for creating workflows with a fluent API.
 pub struct WorkflowBuilder {
     workflow: Workflow,
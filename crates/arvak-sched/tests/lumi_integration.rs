@@ -17,8 +17,9 @@ use arvak_hal::{
 };
 use arvak_ir::Circuit;
 use arvak_sched::{
-    BatchSchedulerType, CircuitSpec, HpcScheduler, PbsConfig, Priority, ResourceRequirements,
-    ScheduledJob, ScheduledJobStatus, Scheduler, SchedulerConfig, SlurmConfig,
+    BatchSchedulerType, CircuitSpec, HpcScheduler, MaintenanceCalendar, PbsConfig, Priority,
+    ResourceRequirements, ScheduledJob, ScheduledJobStatus, Scheduler, SchedulerConfig,
+    SlurmConfig,
 };
 use async_trait::async_trait;
 
@@ -108,6 +109,9 @@ fn lumi_scheduler_config() -> SchedulerConfig {
         max_wait_time_secs: 1800, // 30 minutes
         auto_match_resources: true,
         state_dir: PathBuf::from("/tmp/arvak-lumi-test/state"),
+        carbon_intensity_g_per_kwh: None,
+        maintenance: MaintenanceCalendar::default(),
+        signing: None,
     }
 }
 
@@ -18,7 +18,7 @@ OUT=docs/cli.md
   echo '```text'
   "$BIN" --help
   echo '```'
-  for cmd in compile run submit status result auth wait eval backends; do
+  for cmd in compile run submit status result auth wait top trace eval campaign backends completions; do
     echo
     echo "## arvak $cmd"
     echo
@@ -14,6 +14,10 @@
 #   QDMI/DDSIM: Arvak → QASM → QDMI FFI → MQT DDSIM → histogram
 #   gRPC      : Arvak → protobuf → gRPC service → job result
 #
+# RUST SUITE      — `arvak smoke` (simulator, mock adapters, and live
+#                   backends whose credentials are set; tier via
+#                   ARVAK_SMOKE_TIER, default live)
+#
 # Usage: bash scripts/smoke-test.sh
 # =============================================================================
 
@@ -433,6 +437,31 @@ else
     fail "Audit script (failures detected — run 'bash scripts/audit.sh' for details)"
 fi
 
+# =============================================================================
+# 11. Rust smoke suite (arvak smoke)
+# =============================================================================
+echo ""
+echo "--- 11. Rust smoke suite ---"
+
+SMOKE_TIER="${ARVAK_SMOKE_TIER:-live}"
+SMOKE_JSON="$($CARGO run -q -p arvak-cli -- smoke --tier "$SMOKE_TIER" -f json 2>/dev/null)"
+if [[ -z "$SMOKE_JSON" ]]; then
+    fail "arvak smoke (no report — does arvak-cli build?)"
+else
+    # One "status<TAB>tier/name: detail" line per check
+    while IFS=$'\t' read -r status line; do
+        case "$status" in
+            pass) pass "$line" ;;
+            fail) fail "$line" ;;
+            *)    skip "$line" ;;
+        esac
+    done < <(echo "$SMOKE_JSON" | "$PYTHON" -c '
+import json, sys
+for c in json.load(sys.stdin)["checks"]:
+    print("%s\t%s/%s: %s" % (c["status"], c["tier"], c["name"], c["detail"]))
+')
+fi
+
 # =============================================================================
 # Summary
 # =============================================================================